@@ -5,6 +5,12 @@ mod quest;
 
 use mod_sdk::log;
 
+/// ビルドに使ったModAPIのバージョン（ゲームがロード時に確認する）
+#[no_mangle]
+pub extern "C" fn mod_api_version() -> u32 {
+    mod_sdk::API_VERSION
+}
+
 /// Mod初期化
 #[no_mangle]
 pub extern "C" fn mod_init() -> i32 {
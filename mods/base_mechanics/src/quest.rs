@@ -1,6 +1,6 @@
 //! クエスト進行ロジック
 
-use mod_sdk::log;
+use mod_sdk::{add_inventory_item, log, PLAYER_ENTITY};
 
 /// 現在のクエスト状態
 #[derive(Clone, Copy)]
@@ -15,6 +15,8 @@ static mut CURRENT_QUEST_STATE: QuestState = QuestState::NotStarted;
 static mut QUEST_TARGET_COUNT: u32 = 0;
 static mut QUEST_CURRENT_COUNT: u32 = 0;
 static mut QUEST_STATE_CHANGED: bool = false;
+/// 完了時の報酬（item_id, 個数）
+static mut QUEST_REWARD: Option<(u32, u32)> = None;
 
 /// クエスト進行をチェック
 pub fn check_quest_progress() {
@@ -32,14 +34,15 @@ pub fn check_quest_progress() {
     }
 }
 
-/// 新しいクエストを開始
+/// 新しいクエストを開始（報酬は完了時にプレイヤーへ渡す）
 #[allow(dead_code)]
-pub fn start_quest(target_count: u32) {
+pub fn start_quest(target_count: u32, reward: Option<(u32, u32)>) {
     unsafe {
         QUEST_STATE_CHANGED = true;
         CURRENT_QUEST_STATE = QuestState::InProgress;
         QUEST_TARGET_COUNT = target_count;
         QUEST_CURRENT_COUNT = 0;
+        QUEST_REWARD = reward;
     }
     log("Quest started");
 }
@@ -61,6 +64,10 @@ pub fn complete_quest() -> bool {
         if matches!(CURRENT_QUEST_STATE, QuestState::ReadyToComplete) {
             CURRENT_QUEST_STATE = QuestState::Completed;
             log("Quest completed!");
+            if let Some((item_id, count)) = QUEST_REWARD {
+                QUEST_REWARD = None;
+                grant_reward(item_id, count);
+            }
             true
         } else {
             false
@@ -68,6 +75,15 @@ pub fn complete_quest() -> bool {
     }
 }
 
+/// 報酬をプレイヤーのインベントリに入れる
+fn grant_reward(item_id: u32, count: u32) {
+    if add_inventory_item(PLAYER_ENTITY, item_id, count) > 0 {
+        log("Quest reward didn't fit in the inventory");
+    } else {
+        log("Quest reward granted");
+    }
+}
+
 /// クエストの進捗を取得
 #[allow(dead_code)]
 pub fn get_progress() -> (u32, u32) {
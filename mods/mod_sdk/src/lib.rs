@@ -9,14 +9,89 @@ fn panic(_info: &PanicInfo) -> ! {
     loop {}
 }
 
+/// このSDKが対応するModAPIのバージョン
+///
+/// Modは `mod_api_version()` でこの値を返す。ゲーム側のバージョンより
+/// 新しいとロードされない。
+pub const API_VERSION: u32 = 4;
+
+/// ゲームが提供する機能のフラグ（`capabilities()` で取得）
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// クエスト操作
+    pub const QUESTS: Self = Self(1 << 0);
+    /// インベントリ操作
+    pub const INVENTORY: Self = Self(1 << 1);
+    /// 機械の状態取得・有効/無効切り替え
+    pub const MACHINE_CONTROL: Self = Self(1 << 2);
+    /// イベントv2（購読とデータ付き通知）
+    pub const EVENTS_V2: Self = Self(1 << 3);
+    /// mod_init でのアイテム・レシピ登録
+    pub const CONTENT: Self = Self(1 << 4);
+
+    /// 何も無い
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// 生のビット列から作成（未知のビットも保持する）
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// 生のビット列
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// `other` の機能をすべて含むか
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 // ホスト関数のextern宣言
 extern "C" {
+    pub fn host_get_api_version() -> u32;
+    pub fn host_get_capabilities(ptr: *mut u8, len: u32) -> u32;
     pub fn host_log_info(ptr: *const u8, len: u32);
     pub fn host_log_error(ptr: *const u8, len: u32);
     pub fn host_get_machine_state(entity_id: u64) -> i32;
     pub fn host_set_machine_enabled(entity_id: u64, enabled: i32) -> i32;
     pub fn host_get_inventory_slot(entity_id: u64, slot: u32) -> u64; // item_id << 32 | count
     pub fn host_transfer_item(from_entity: u64, to_entity: u64, item_id: u32, count: u32) -> i32;
+    pub fn host_register_item(json_ptr: *const u8, json_len: u32) -> i32;
+    pub fn host_register_recipe(json_ptr: *const u8, json_len: u32) -> i32;
+    pub fn host_add_inventory_item(entity_id: u64, item_id: u32, count: u32) -> u32;
+    pub fn host_consume_inventory_item(entity_id: u64, item_id: u32, count: u32) -> i32;
+}
+
+/// ローカルプレイヤーのインベントリを指す entity_id
+pub const PLAYER_ENTITY: u64 = 0;
+
+/// ゲーム側のModAPIバージョン
+pub fn host_api_version() -> u32 {
+    unsafe { host_get_api_version() }
+}
+
+/// ゲームが提供する機能（機能検出に使う）
+pub fn capabilities() -> Capabilities {
+    let mut buf = [0u8; 4];
+    let written = unsafe { host_get_capabilities(buf.as_mut_ptr(), buf.len() as u32) };
+    if written as usize != buf.len() {
+        return Capabilities::empty();
+    }
+    Capabilities::from_bits(u32::from_le_bytes(buf))
 }
 
 /// ログ出力（info）
@@ -34,11 +109,13 @@ pub fn log_error(msg: &str) {
 }
 
 /// 機械の状態を取得
+/// 戻り値: 0=正常, 1=処理中, 2=待機中（停止中）, -1=エンティティ不存在
 pub fn get_machine_state(entity_id: u64) -> i32 {
     unsafe { host_get_machine_state(entity_id) }
 }
 
-/// 機械の有効/無効を設定
+/// 機械の有効/無効を設定（次のtickで反映）
+/// 戻り値: 0=成功, -1=エンティティ不存在
 pub fn set_machine_enabled(entity_id: u64, enabled: bool) -> i32 {
     unsafe { host_set_machine_enabled(entity_id, if enabled { 1 } else { 0 }) }
 }
@@ -49,7 +126,38 @@ pub fn get_inventory_slot(entity_id: u64, slot: u32) -> (u32, u32) {
     ((result >> 32) as u32, result as u32)
 }
 
+/// アイテムを追加（次のtickで反映）
+///
+/// `entity_id` は PLAYER_ENTITY か機械。機械では同じアイテムのスロットか
+/// 空いた出力スロットに入る。
+/// 戻り値: 入りきらなかった数（未知のアイテム・entity_idなら count 全部）
+pub fn add_inventory_item(entity_id: u64, item_id: u32, count: u32) -> u32 {
+    unsafe { host_add_inventory_item(entity_id, item_id, count) }
+}
+
+/// アイテムを消費（足りなければある分だけ、次のtickで反映）
+/// 戻り値: 消費した数、-1=未知のアイテム
+pub fn consume_inventory_item(entity_id: u64, item_id: u32, count: u32) -> i32 {
+    unsafe { host_consume_inventory_item(entity_id, item_id, count) }
+}
+
 /// アイテムを転送
 pub fn transfer_item(from_entity: u64, to_entity: u64, item_id: u32, count: u32) -> i32 {
     unsafe { host_transfer_item(from_entity, to_entity, item_id, count) }
 }
+
+/// アイテムを登録（mod_init の中でだけ有効）
+///
+/// `json` はデータパックと同じアイテム定義。IDは `<mod_id>:<名前>` になる。
+/// 戻り値: 0=成功, -1=mod_init外, -2=JSON不正, -3=ID不正, -4=ID重複
+pub fn register_item(json: &str) -> i32 {
+    unsafe { host_register_item(json.as_ptr(), json.len() as u32) }
+}
+
+/// レシピを登録（mod_init の中でだけ有効）
+///
+/// 入力・出力は base のアイテムか、先に登録したこのModのアイテム。
+/// 戻り値: register_item と同じ、加えて -5=未知のアイテムを参照
+pub fn register_recipe(json: &str) -> i32 {
+    unsafe { host_register_recipe(json.as_ptr(), json.len() as u32) }
+}
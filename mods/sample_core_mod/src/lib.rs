@@ -4,6 +4,12 @@ use mod_sdk::*;
 
 static mut TICK_COUNT: u64 = 0;
 
+/// ビルドに使ったModAPIのバージョン（ゲームがロード時に確認する）
+#[no_mangle]
+pub extern "C" fn mod_api_version() -> u32 {
+    API_VERSION
+}
+
 /// Mod初期化
 #[no_mangle]
 pub extern "C" fn mod_init() -> i32 {
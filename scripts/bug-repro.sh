@@ -84,7 +84,7 @@ start_game() {
     cleanup
     cd "$GAME_DIR"
 
-    cargo run --bin idle_factory 2>&1 > "$GAME_LOG_FILE" &
+    cargo run --bin idle_factory -- --skip-menu 2>&1 > "$GAME_LOG_FILE" &
     GAME_PID=$!
 
     for i in {1..30}; do
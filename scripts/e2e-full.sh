@@ -237,7 +237,7 @@ test_native() {
 
     # ゲーム起動
     log "ゲーム起動中..."
-    cargo run 2>/dev/null &
+    cargo run -- --skip-menu 2>/dev/null &
     GAME_PID=$!
 
     # ウィンドウ待機
@@ -54,7 +54,7 @@ start_game() {
     cd "$GAME_DIR"
 
     # バックグラウンドでゲーム起動（ログを記録）
-    cargo run --bin idle_factory 2>&1 > "$GAME_LOG_FILE" &
+    cargo run --bin idle_factory -- --skip-menu 2>&1 > "$GAME_LOG_FILE" &
     GAME_PID=$!
 
     # 起動待機（最大30秒）
@@ -9,7 +9,7 @@ cd "$PROJECT_DIR"
 
 # Start game in background
 echo "Starting game..."
-DISPLAY=:10 cargo run --release &
+DISPLAY=:10 cargo run --release -- --skip-menu &
 GAME_PID=$!
 
 # Wait for game to start and WebSocket to be ready
@@ -47,7 +47,7 @@ WINDOW_ID=$(xdotool search --name "$GAME_WINDOW" 2>/dev/null | head -1 || true)
 if [ -z "$WINDOW_ID" ]; then
     log "Starting game..."
     cd /home/bacon/idle_factory
-    cargo run --bin idle_factory &
+    cargo run --bin idle_factory -- --skip-menu &
     GAME_PID=$!
     sleep 15
     WINDOW_ID=$(xdotool search --name "$GAME_WINDOW" 2>/dev/null | head -1)
@@ -37,7 +37,7 @@ if ! nc -z 127.0.0.1 9877 2>/dev/null; then
     cargo build --manifest-path "$PROJECT_DIR/Cargo.toml" 2>&1 | tail -5
 
     # Start game in background
-    DISPLAY=:10 cargo run --manifest-path "$PROJECT_DIR/Cargo.toml" --bin idle_factory >/dev/null 2>&1 &
+    DISPLAY=:10 cargo run --manifest-path "$PROJECT_DIR/Cargo.toml" --bin idle_factory -- --skip-menu >/dev/null 2>&1 &
     GAME_PID=$!
     STARTED_GAME=true
 
@@ -48,7 +48,7 @@ echo "Starting game..." | tee -a "$LOG_FILE"
 pkill -x idle_factory 2>/dev/null || true
 sleep 1
 
-cargo run &
+cargo run -- --skip-menu &
 GAME_PID=$!
 echo "Game PID: $GAME_PID" | tee -a "$LOG_FILE"
 
@@ -59,7 +59,7 @@ cd "$GAME_DIR"
 
 # ゲーム起動
 log "Starting game..."
-cargo run 2>&1 &
+cargo run -- --skip-menu 2>&1 &
 GAME_PID=$!
 
 # ウィンドウ待機
@@ -45,7 +45,7 @@ log "✓ Build OK"
 
 # Test 2: Start game
 log "[2/5] Starting game..."
-cargo run --release 2>&1 > "$LOG_FILE" &
+cargo run --release -- --skip-menu 2>&1 > "$LOG_FILE" &
 GAME_PID=$!
 
 # Test 3: Window appears within 15 seconds
@@ -6,7 +6,7 @@ echo "=== WebSocket Mod API E2E Test ==="
 
 # 1. ゲーム起動
 echo "Starting game..."
-DISPLAY=:10 cargo run --bin idle_factory &
+DISPLAY=:10 cargo run --bin idle_factory -- --skip-menu &
 GAME_PID=$!
 trap "kill $GAME_PID 2>/dev/null || true" EXIT
 
@@ -48,10 +48,10 @@ fi
 # Start game in background
 echo "Starting game..."
 if [ "$SKIP_BUILD" = false ]; then
-    RUST_LOG=warn cargo run --release &
+    RUST_LOG=warn cargo run --release -- --skip-menu &
 else
     # Run binary directly
-    RUST_LOG=warn ./target/release/idle_factory &
+    RUST_LOG=warn ./target/release/idle_factory --skip-menu &
 fi
 GAME_PID=$!
 
@@ -1,8 +1,14 @@
 //! Achievement system
 
+mod toast;
+
+pub use toast::{AchievementToasts, Toast, TOAST_SECS};
+
 use crate::core::{items, ItemId};
 use crate::events::game_events::{BlockPlaced, ItemDelivered, MachineCompleted, MachineSpawned};
 use crate::events::GuardedMessageWriter;
+use crate::main_menu::{AppState, InGameSet};
+use crate::statistics::{PlayerStats, StatKind};
 use bevy::prelude::*;
 use std::sync::LazyLock;
 
@@ -21,6 +27,8 @@ pub enum AchievementCondition {
     CollectItem { item: ItemId, count: u32 },
     /// 初回イベント
     FirstTime { event: &'static str },
+    /// プレイヤー統計が一定値に到達
+    Stat { stat: StatKind, count: u64 },
 }
 
 /// 実績定義
@@ -75,6 +83,15 @@ impl PlayerAchievements {
     pub fn is_unlocked(&self, id: &str) -> bool {
         self.progress.get(id).map(|p| p.unlocked).unwrap_or(false)
     }
+
+    /// アンロック済みの実績ID（定義順）
+    pub fn unlocked_ids(&self) -> Vec<String> {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|a| self.is_unlocked(a.id))
+            .map(|a| a.id.to_string())
+            .collect()
+    }
 }
 
 /// 実績アンロックイベント
@@ -139,9 +156,92 @@ pub static ACHIEVEMENTS: LazyLock<Vec<Achievement>> = LazyLock::new(|| {
             icon: None,
             hidden: false,
         },
+        Achievement {
+            id: "miner",
+            name: "採掘者",
+            description: "ブロックを100個採掘する",
+            condition: AchievementCondition::Stat {
+                stat: StatKind::BlocksMined,
+                count: 100,
+            },
+            icon: None,
+            hidden: false,
+        },
+        Achievement {
+            id: "ingot_supplier",
+            name: "インゴット納入業者",
+            description: "インゴットを50個納品する",
+            condition: AchievementCondition::Stat {
+                stat: StatKind::IngotsDelivered,
+                count: 50,
+            },
+            icon: None,
+            hidden: false,
+        },
+        Achievement {
+            id: "conveyor_builder",
+            name: "物流網",
+            description: "コンベアを20個設置する",
+            condition: AchievementCondition::Stat {
+                stat: StatKind::ConveyorsPlaced,
+                count: 20,
+            },
+            icon: None,
+            hidden: false,
+        },
     ]
 });
 
+/// 条件の (現在値, 目標値) を計算
+///
+/// カウンターで判定しない条件（クエスト・プレイ時間）は None
+pub fn condition_progress(
+    condition: &AchievementCondition,
+    counters: &AchievementCounters,
+    stats: &PlayerStats,
+) -> Option<(u32, u32)> {
+    match condition {
+        AchievementCondition::PlaceMachines { count } => Some((counters.machines_placed, *count)),
+        AchievementCondition::ProduceItem { item, count } => Some((
+            counters.items_produced.get(item).copied().unwrap_or(0),
+            *count,
+        )),
+        AchievementCondition::FirstTime { event } => {
+            let done = match *event {
+                "item_delivered" => counters.total_delivered > 0,
+                _ => false,
+            };
+            Some((u32::from(done), 1))
+        }
+        AchievementCondition::CollectItem { item, count } => Some((
+            counters.items_delivered.get(item).copied().unwrap_or(0),
+            *count,
+        )),
+        AchievementCondition::Stat { stat, count } => {
+            let current = stats.get(*stat).min(u32::MAX as u64) as u32;
+            Some((current, (*count).min(u32::MAX as u64) as u32))
+        }
+        AchievementCondition::CompleteQuest { .. } | AchievementCondition::PlayTime { .. } => None,
+    }
+}
+
+/// 未アンロックのうち条件を満たした実績を返す
+pub fn evaluate_achievements<'a>(
+    definitions: &'a [Achievement],
+    counters: &AchievementCounters,
+    stats: &PlayerStats,
+    player: &PlayerAchievements,
+) -> Vec<&'a Achievement> {
+    definitions
+        .iter()
+        .filter(|a| !player.is_unlocked(a.id))
+        .filter(|a| {
+            condition_progress(&a.condition, counters, stats)
+                .is_some_and(|(current, target)| current >= target)
+        })
+        .collect()
+}
+
 /// 機械設置イベントを購読してカウンターを更新
 fn handle_machine_spawned(
     mut events: MessageReader<MachineSpawned>,
@@ -188,57 +288,32 @@ fn handle_item_delivered_for_achievements(
 /// 実績の進捗をチェックして必要に応じてアンロック
 fn check_achievements(
     counters: Res<AchievementCounters>,
+    stats: Res<PlayerStats>,
     mut achievements: ResMut<PlayerAchievements>,
     mut unlock_events: GuardedMessageWriter<AchievementUnlocked>,
     time: Res<Time>,
 ) {
     // カウンターが変更されていない場合はスキップ
-    if !counters.is_changed() {
+    if !counters.is_changed() && !stats.is_changed() {
         return;
     }
 
     let timestamp = time.elapsed_secs_f64();
 
+    // 進捗を更新
     for achievement in ACHIEVEMENTS.iter() {
-        // 既にアンロック済みならスキップ
-        if achievements.is_unlocked(achievement.id) {
-            continue;
+        if let Some((current, _)) = condition_progress(&achievement.condition, &counters, &stats) {
+            achievements.update_progress(achievement.id, current);
         }
+    }
 
-        let (current, _target, unlocked) = match &achievement.condition {
-            AchievementCondition::PlaceMachines { count } => {
-                let current = counters.machines_placed;
-                (current, count, current >= *count)
-            }
-            AchievementCondition::ProduceItem { item, count } => {
-                let current = counters.items_produced.get(item).copied().unwrap_or(0);
-                (current, count, current >= *count)
-            }
-            AchievementCondition::FirstTime { event } => {
-                let unlocked = match *event {
-                    "item_delivered" => counters.total_delivered > 0,
-                    _ => false,
-                };
-                (if unlocked { 1 } else { 0 }, &1u32, unlocked)
-            }
-            AchievementCondition::CollectItem { item, count } => {
-                let current = counters.items_delivered.get(item).copied().unwrap_or(0);
-                (current, count, current >= *count)
-            }
-            _ => continue, // 他の条件は別途処理
-        };
-
-        // 進捗を更新
-        achievements.update_progress(achievement.id, current);
-
-        // アンロック判定
-        if unlocked {
-            achievements.unlock(achievement.id, timestamp);
-            let _ = unlock_events.write(AchievementUnlocked {
-                id: achievement.id.to_string(),
-                name: achievement.name.to_string(),
-            });
-        }
+    // アンロック判定
+    for achievement in evaluate_achievements(&ACHIEVEMENTS, &counters, &stats, &achievements) {
+        achievements.unlock(achievement.id, timestamp);
+        let _ = unlock_events.write(AchievementUnlocked {
+            id: achievement.id.to_string(),
+            name: achievement.name.to_string(),
+        });
     }
 }
 
@@ -252,6 +327,7 @@ fn setup_achievement_progress(mut achievements: ResMut<PlayerAchievements>) {
             AchievementCondition::PlayTime { minutes } => *minutes,
             AchievementCondition::FirstTime { .. } => 1,
             AchievementCondition::CompleteQuest { .. } => 1,
+            AchievementCondition::Stat { count, .. } => (*count).min(u32::MAX as u64) as u32,
         };
 
         achievements.progress.insert(
@@ -272,8 +348,10 @@ impl Plugin for AchievementsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerAchievements>()
             .init_resource::<AchievementCounters>()
+            .init_resource::<AchievementToasts>()
             .add_message::<AchievementUnlocked>()
             .add_systems(Startup, setup_achievement_progress)
+            .add_systems(OnEnter(AppState::InGame), toast::setup_achievement_toast)
             .add_systems(
                 Update,
                 (
@@ -282,8 +360,11 @@ impl Plugin for AchievementsPlugin {
                     handle_machine_completed_for_achievements,
                     handle_item_delivered_for_achievements,
                     check_achievements,
+                    toast::queue_achievement_toasts,
+                    toast::update_achievement_toast,
                 )
-                    .chain(),
+                    .chain()
+                    .in_set(InGameSet),
             );
     }
 }
@@ -371,4 +452,63 @@ mod tests {
         let first_machine = ACHIEVEMENTS.iter().find(|a| a.id == "first_machine");
         assert!(first_machine.is_some());
     }
+
+    fn player_with_all_progress() -> PlayerAchievements {
+        let mut player = PlayerAchievements::default();
+        for achievement in ACHIEVEMENTS.iter() {
+            player
+                .progress
+                .insert(achievement.id.to_string(), AchievementProgress::default());
+        }
+        player
+    }
+
+    #[test]
+    fn test_evaluate_stat_achievements() {
+        let counters = AchievementCounters::default();
+        let player = player_with_all_progress();
+        let mut stats = PlayerStats {
+            blocks_mined: 99,
+            ingots_delivered: 50,
+            ..default()
+        };
+
+        let ids: Vec<_> = evaluate_achievements(&ACHIEVEMENTS, &counters, &stats, &player)
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, vec!["ingot_supplier"]);
+
+        stats.blocks_mined = 100;
+        stats.conveyors_placed = 20;
+        let ids: Vec<_> = evaluate_achievements(&ACHIEVEMENTS, &counters, &stats, &player)
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, vec!["miner", "ingot_supplier", "conveyor_builder"]);
+    }
+
+    #[test]
+    fn test_evaluate_skips_unlocked() {
+        let counters = AchievementCounters {
+            machines_placed: 1,
+            ..default()
+        };
+        let stats = PlayerStats {
+            blocks_mined: 500,
+            ..default()
+        };
+        let mut player = player_with_all_progress();
+
+        let first = evaluate_achievements(&ACHIEVEMENTS, &counters, &stats, &player);
+        assert_eq!(first.len(), 2);
+        for achievement in first {
+            player.unlock(achievement.id, 0.0);
+        }
+
+        // 同じ統計で再評価しても二重にアンロックしない
+        assert!(evaluate_achievements(&ACHIEVEMENTS, &counters, &stats, &player).is_empty());
+        assert_eq!(player.total_unlocked, 2);
+        assert_eq!(player.unlocked_ids(), vec!["first_machine", "miner"]);
+    }
 }
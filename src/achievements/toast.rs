@@ -0,0 +1,189 @@
+//! 実績アンロック時のトースト通知
+
+use std::collections::{HashSet, VecDeque};
+
+use bevy::prelude::*;
+
+use super::AchievementUnlocked;
+use crate::components::GameFont;
+use crate::setup::ui::{text_font, TEXT_BODY, TEXT_SMALL};
+
+/// トースト1件の表示時間（秒）
+pub const TOAST_SECS: f32 = 3.0;
+
+/// 表示待ちのトースト
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub id: String,
+    pub name: String,
+}
+
+/// トースト通知キュー
+///
+/// 同じ実績は一度しか通知しない
+#[derive(Resource, Debug, Default)]
+pub struct AchievementToasts {
+    queue: VecDeque<Toast>,
+    shown: HashSet<String>,
+    /// 表示中トーストの残り時間
+    remaining: f32,
+}
+
+impl AchievementToasts {
+    /// トーストを追加（通知済みの実績ならfalse）
+    pub fn push(&mut self, id: &str, name: &str) -> bool {
+        if !self.shown.insert(id.to_string()) {
+            return false;
+        }
+        self.queue.push_back(Toast {
+            id: id.to_string(),
+            name: name.to_string(),
+        });
+        true
+    }
+
+    /// 表示中のトースト
+    pub fn current(&self) -> Option<&Toast> {
+        self.queue.front()
+    }
+
+    /// 経過時間を進め、表示時間を過ぎたトーストを取り除く
+    pub fn tick(&mut self, delta: f32) {
+        if self.queue.is_empty() {
+            return;
+        }
+        if self.remaining <= 0.0 {
+            self.remaining = TOAST_SECS;
+        }
+        self.remaining -= delta;
+        if self.remaining <= 0.0 {
+            self.queue.pop_front();
+        }
+    }
+
+    /// 待ち件数
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+/// トーストのルートノード
+#[derive(Component)]
+pub struct AchievementToastUI;
+
+/// トーストの実績名テキスト
+#[derive(Component)]
+pub struct AchievementToastText;
+
+pub(super) fn setup_achievement_toast(mut commands: Commands, game_font: Res<GameFont>) {
+    commands
+        .spawn((
+            AchievementToastUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                right: Val::Px(20.0),
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                border: UiRect::all(Val::Px(2.0)),
+                border_radius: BorderRadius::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.92)),
+            BorderColor::all(Color::srgb(0.9, 0.75, 0.2)),
+            GlobalZIndex(90),
+            Visibility::Hidden,
+        ))
+        .with_children(|toast| {
+            toast.spawn((
+                Text::new("実績解除"),
+                text_font(&game_font.0, TEXT_SMALL),
+                TextColor(Color::srgb(0.9, 0.75, 0.2)),
+            ));
+            toast.spawn((
+                Text::new(""),
+                AchievementToastText,
+                text_font(&game_font.0, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// アンロックイベントをキューに積む
+pub(super) fn queue_achievement_toasts(
+    mut events: MessageReader<AchievementUnlocked>,
+    mut toasts: ResMut<AchievementToasts>,
+) {
+    for event in events.read() {
+        toasts.push(&event.id, &event.name);
+    }
+}
+
+/// 先頭のトーストを表示し、時間経過で次へ進める
+pub(super) fn update_achievement_toast(
+    time: Res<Time>,
+    mut toasts: ResMut<AchievementToasts>,
+    mut ui_query: Query<&mut Visibility, With<AchievementToastUI>>,
+    mut text_query: Query<&mut Text, With<AchievementToastText>>,
+) {
+    if toasts.is_empty() {
+        return;
+    }
+    toasts.tick(time.delta_secs());
+
+    let current = toasts.current().map(|toast| toast.name.clone());
+    for mut vis in ui_query.iter_mut() {
+        *vis = if current.is_some() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Some(name) = current {
+        for mut text in text_query.iter_mut() {
+            if **text != name {
+                **text = name.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_push_deduplicates() {
+        let mut toasts = AchievementToasts::default();
+        assert!(toasts.push("miner", "採掘者"));
+        assert!(!toasts.push("miner", "採掘者"));
+        assert!(toasts.push("conveyor_builder", "物流網"));
+        assert_eq!(toasts.len(), 2);
+
+        // 表示し終えた後も再通知しない
+        toasts.tick(0.0);
+        toasts.tick(TOAST_SECS + 0.1);
+        assert_eq!(
+            toasts.current().map(|t| t.id.as_str()),
+            Some("conveyor_builder")
+        );
+        assert!(!toasts.push("miner", "採掘者"));
+        assert_eq!(toasts.len(), 1);
+    }
+
+    #[test]
+    fn test_toast_expires_after_duration() {
+        let mut toasts = AchievementToasts::default();
+        toasts.push("miner", "採掘者");
+
+        toasts.tick(TOAST_SECS * 0.5);
+        assert!(toasts.current().is_some());
+        toasts.tick(TOAST_SECS * 0.6);
+        assert!(toasts.is_empty());
+    }
+}
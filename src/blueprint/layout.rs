@@ -0,0 +1,425 @@
+//! Shareable factory layouts
+//!
+//! - `/export layout <name> x1 y1 z1 x2 y2 z2`: machines, conveyors and placed
+//!   blocks inside the box, written to `layouts/<name>.yaml`
+//! - `/import layout <name>`: load a layout into the blueprint library and
+//!   start placing it as a preview
+//! - F10: screenshot saved next to the last exported/imported layout
+//!
+//! Items are stored by string ID so layouts survive ID changes. Entries with
+//! block types this version doesn't know are skipped and reported.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+use super::{Blueprint, BlueprintBlock, BlueprintDirection, BlueprintLibrary, BlueprintPreview};
+use crate::components::{Machine, Player};
+use crate::core::ItemId;
+use crate::input::{GameAction, InputManager};
+use crate::save::ConveyorShapeSave;
+use crate::systems::command::ScreenshotEvent;
+use crate::utils::parse_item_name;
+use crate::{Conveyor, ConveyorShape, BLOCK_SIZE};
+
+/// Directory for exported layouts (and their preview screenshots)
+pub const LAYOUT_DIR: &str = "layouts";
+
+/// Directory for F10 screenshots taken before any layout was exported
+pub const SCREENSHOT_DIR: &str = "screenshots";
+
+/// Layout command from `/export layout` and `/import layout`
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum LayoutCommandEvent {
+    /// Write everything between the two corners (inclusive) to `layouts/<name>.yaml`
+    Export {
+        name: String,
+        min: IVec3,
+        max: IVec3,
+    },
+    /// Load `layouts/<name>.yaml` as a blueprint preview
+    Import { name: String },
+}
+
+/// Summary written at the top of a layout file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutHeader {
+    /// Game version that exported the layout
+    pub game_version: String,
+    /// Bounding box size in blocks
+    pub size: [i32; 3],
+    /// Number of entries per item ID
+    pub counts: BTreeMap<String, u32>,
+}
+
+/// One machine, conveyor or block in a layout
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutEntry {
+    /// Item string ID (e.g. "base:furnace_block")
+    pub item: String,
+    /// Offset from the layout's minimum corner
+    pub offset: [i32; 3],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<BlueprintDirection>,
+    /// Conveyor shape at export time (placement recomputes it from neighbors)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<ConveyorShapeSave>,
+}
+
+/// A standalone layout file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutFile {
+    pub header: LayoutHeader,
+    pub entries: Vec<LayoutEntry>,
+}
+
+impl LayoutFile {
+    /// Collect machines, conveyors and placed blocks inside `min..=max`
+    ///
+    /// Blocks under a machine or conveyor are left out; removed blocks
+    /// (`None` in `placed_blocks`) are terrain edits, not layout.
+    pub fn capture<'a>(
+        min: IVec3,
+        max: IVec3,
+        machines: impl IntoIterator<Item = &'a Machine>,
+        conveyors: impl IntoIterator<Item = &'a Conveyor>,
+        placed_blocks: &HashMap<IVec3, Option<ItemId>>,
+    ) -> Self {
+        let (min, max) = (min.min(max), min.max(max));
+        let inside = |pos: IVec3| pos.cmpge(min).all() && pos.cmple(max).all();
+        let mut entries = Vec::new();
+
+        for machine in machines.into_iter().filter(|m| inside(m.position)) {
+            let Some(item) = machine.spec.item_id().name() else {
+                continue;
+            };
+            entries.push(LayoutEntry {
+                item: item.to_string(),
+                offset: (machine.position - min).to_array(),
+                direction: Some(machine.facing.into()),
+                shape: None,
+            });
+        }
+        for conveyor in conveyors.into_iter().filter(|c| inside(c.position)) {
+            let Some(item) = conveyor.tier_item().name() else {
+                continue;
+            };
+            entries.push(LayoutEntry {
+                item: item.to_string(),
+                offset: (conveyor.position - min).to_array(),
+                direction: Some(conveyor.direction.into()),
+                shape: Some(shape_to_save(conveyor.shape)),
+            });
+        }
+
+        let occupied: Vec<[i32; 3]> = entries.iter().map(|e| e.offset).collect();
+        for (&pos, block) in placed_blocks {
+            let Some(item) = block.filter(|_| inside(pos)).and_then(|id| id.name()) else {
+                continue;
+            };
+            let offset = (pos - min).to_array();
+            if occupied.contains(&offset) {
+                continue;
+            }
+            entries.push(LayoutEntry {
+                item: item.to_string(),
+                offset,
+                direction: None,
+                shape: None,
+            });
+        }
+
+        // Stable output for diffs and sharing
+        entries.sort_by(|a, b| a.offset.cmp(&b.offset).then(a.item.cmp(&b.item)));
+
+        let mut counts = BTreeMap::new();
+        for entry in &entries {
+            *counts.entry(entry.item.clone()).or_insert(0) += 1;
+        }
+        Self {
+            header: LayoutHeader {
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+                size: (max - min + IVec3::ONE).to_array(),
+                counts,
+            },
+            entries,
+        }
+    }
+
+    /// Convert to a blueprint; returns the item IDs this version doesn't know
+    pub fn to_blueprint(&self, name: &str) -> (Blueprint, Vec<String>) {
+        let mut blueprint = Blueprint::new(name);
+        let mut unknown = Vec::new();
+        for entry in &self.entries {
+            let Some(item_id) = parse_item_name(&entry.item) else {
+                if !unknown.contains(&entry.item) {
+                    unknown.push(entry.item.clone());
+                }
+                continue;
+            };
+            blueprint.add_block(BlueprintBlock {
+                offset: IVec3::from_array(entry.offset),
+                item_id,
+                rotation: 0,
+                direction: entry.direction,
+            });
+        }
+        (blueprint, unknown)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| format!("Failed to serialize layout: {}", e))
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse layout: {}", e))
+    }
+
+    fn file_path(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(LAYOUT_DIR).join(format!("{}.yaml", name))
+    }
+
+    /// Save to `layouts/<name>.yaml`
+    pub fn save(&self, name: &str) -> Result<(), String> {
+        std::fs::create_dir_all(LAYOUT_DIR)
+            .map_err(|e| format!("Failed to create layout directory: {}", e))?;
+        std::fs::write(Self::file_path(name), self.to_yaml()?)
+            .map_err(|e| format!("Failed to write layout: {}", e))
+    }
+
+    /// Load from `layouts/<name>.yaml`
+    pub fn load(name: &str) -> Result<Self, String> {
+        let yaml = std::fs::read_to_string(Self::file_path(name))
+            .map_err(|e| format!("Failed to read layout: {}", e))?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+fn shape_to_save(shape: ConveyorShape) -> ConveyorShapeSave {
+    match shape {
+        ConveyorShape::Straight => ConveyorShapeSave::Straight,
+        ConveyorShape::CornerLeft => ConveyorShapeSave::CornerLeft,
+        ConveyorShape::CornerRight => ConveyorShapeSave::CornerRight,
+        ConveyorShape::TJunction => ConveyorShapeSave::TJunction,
+        ConveyorShape::Splitter => ConveyorShapeSave::Splitter,
+    }
+}
+
+/// Layout last exported or imported (F10 saves its preview image)
+#[derive(Resource, Debug, Default)]
+pub struct LayoutState {
+    pub last: Option<String>,
+}
+
+/// Handle `/export layout` and `/import layout`
+#[allow(clippy::too_many_arguments)]
+pub fn handle_layout_command(
+    mut events: MessageReader<LayoutCommandEvent>,
+    world_data: Res<crate::world::WorldData>,
+    machines: Query<&Machine>,
+    conveyors: Query<&Conveyor>,
+    player_query: Query<&Transform, With<Player>>,
+    mut library: ResMut<BlueprintLibrary>,
+    mut preview: ResMut<BlueprintPreview>,
+    mut state: ResMut<LayoutState>,
+) {
+    for event in events.read() {
+        match event {
+            LayoutCommandEvent::Export { name, min, max } => {
+                let layout = LayoutFile::capture(
+                    *min,
+                    *max,
+                    machines.iter(),
+                    conveyors.iter(),
+                    &world_data.modified_blocks,
+                );
+                match layout.save(name) {
+                    Ok(()) => {
+                        info!(
+                            category = "LAYOUT",
+                            %name,
+                            entries = layout.entries.len(),
+                            size = ?layout.header.size,
+                            "Layout exported"
+                        );
+                        state.last = Some(name.clone());
+                    }
+                    Err(e) => warn!(category = "LAYOUT", %name, "{}", e),
+                }
+            }
+            LayoutCommandEvent::Import { name } => {
+                let layout = match LayoutFile::load(name) {
+                    Ok(layout) => layout,
+                    Err(e) => {
+                        warn!(category = "LAYOUT", %name, "{}", e);
+                        continue;
+                    }
+                };
+                let (blueprint, unknown) = layout.to_blueprint(name);
+                if !unknown.is_empty() {
+                    warn!(
+                        category = "LAYOUT",
+                        %name,
+                        version = %layout.header.game_version,
+                        ?unknown,
+                        "Layout has unknown block types, skipped"
+                    );
+                }
+                info!(
+                    category = "LAYOUT",
+                    %name,
+                    blocks = blueprint.block_count(),
+                    "Layout imported"
+                );
+
+                // Re-importing replaces the earlier copy
+                if let Some(index) = library.blueprints.iter().position(|b| b.name == *name) {
+                    library.remove(index);
+                }
+                library.add(blueprint);
+                preview.selected = Some(library.count() - 1);
+                preview.rotation = 0;
+                preview.active = true;
+                if let Ok(player) = player_query.single() {
+                    preview.position = (player.translation / BLOCK_SIZE).floor().as_ivec3();
+                }
+                state.last = Some(name.clone());
+            }
+        }
+    }
+}
+
+/// F10: screenshot next to the last layout, or into `screenshots/`
+pub fn capture_layout_screenshot(
+    input: Res<InputManager>,
+    state: Res<LayoutState>,
+    mut screenshots: MessageWriter<ScreenshotEvent>,
+) {
+    if !input.just_pressed(GameAction::Screenshot) {
+        return;
+    }
+    let (dir, file) = match &state.last {
+        Some(name) => (LAYOUT_DIR, format!("{}.png", name)),
+        None => (
+            SCREENSHOT_DIR,
+            format!(
+                "screenshot_{}.png",
+                chrono::Local::now().format("%Y%m%d_%H%M%S")
+            ),
+        ),
+    };
+    // On WASM the file becomes a browser download instead
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        warn!(category = "LAYOUT", "Failed to create {}: {}", dir, e);
+        return;
+    }
+    screenshots.write(ScreenshotEvent {
+        filename: format!("{}/{}", dir, file),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ConveyorItem, MachineBundle};
+    use crate::core::items;
+    use crate::game_spec::{FURNACE, MINER};
+    use crate::Direction;
+
+    fn conveyor(position: IVec3, direction: Direction, shape: ConveyorShape) -> Conveyor {
+        Conveyor {
+            position,
+            direction,
+            output_direction: direction,
+            items: vec![ConveyorItem::new(items::iron_ore(), 0.5)],
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_layout_roundtrip() {
+        let machines = [
+            MachineBundle::new_centered(&MINER, IVec3::new(10, 8, 10), Direction::East).machine,
+            MachineBundle::new_centered(&FURNACE, IVec3::new(12, 8, 10), Direction::East).machine,
+            // Outside the box
+            MachineBundle::new_centered(&FURNACE, IVec3::new(40, 8, 10), Direction::East).machine,
+        ];
+        let conveyors = [conveyor(
+            IVec3::new(11, 8, 10),
+            Direction::East,
+            ConveyorShape::CornerLeft,
+        )];
+        let mut blocks = HashMap::new();
+        blocks.insert(IVec3::new(10, 8, 11), Some(items::stone()));
+        // Dug out terrain is not part of the layout
+        blocks.insert(IVec3::new(11, 8, 11), None);
+
+        let layout = LayoutFile::capture(
+            IVec3::new(12, 9, 11),
+            IVec3::new(10, 8, 10),
+            &machines,
+            &conveyors,
+            &blocks,
+        );
+        assert_eq!(layout.header.size, [3, 2, 2]);
+        assert_eq!(layout.header.game_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(layout.entries.len(), 4);
+        assert_eq!(layout.header.counts.values().sum::<u32>(), 4);
+
+        let loaded = LayoutFile::from_yaml(&layout.to_yaml().unwrap()).unwrap();
+        assert_eq!(loaded, layout);
+        let belt = loaded.entries.iter().find(|e| e.shape.is_some()).unwrap();
+        assert_eq!(belt.offset, [1, 0, 0]);
+        assert_eq!(belt.shape, Some(ConveyorShapeSave::CornerLeft));
+
+        let (blueprint, unknown) = loaded.to_blueprint("smelter");
+        assert!(unknown.is_empty());
+        assert_eq!(blueprint.block_count(), 4);
+        let furnace = blueprint
+            .blocks
+            .iter()
+            .find(|b| b.item_id == items::furnace_block())
+            .unwrap();
+        assert_eq!(furnace.offset, IVec3::new(2, 0, 0));
+        assert_eq!(furnace.direction, Some(BlueprintDirection::East));
+        assert!(blueprint
+            .blocks
+            .iter()
+            .any(|b| b.item_id == items::stone() && b.offset == IVec3::new(0, 0, 1)));
+    }
+
+    #[test]
+    fn test_unknown_types_skipped() {
+        let yaml = r#"
+header:
+  game_version: 9.9.0
+  size: [3, 1, 1]
+  counts:
+    base:conveyor_block: 1
+    base:quantum_smelter: 2
+entries:
+  - item: base:conveyor_block
+    offset: [0, 0, 0]
+    direction: East
+    shape: Straight
+  - item: base:quantum_smelter
+    offset: [1, 0, 0]
+    direction: East
+  - item: base:quantum_smelter
+    offset: [2, 0, 0]
+"#;
+        let layout = LayoutFile::from_yaml(yaml).unwrap();
+        let (blueprint, unknown) = layout.to_blueprint("future");
+
+        assert_eq!(blueprint.block_count(), 1);
+        assert_eq!(blueprint.blocks[0].item_id, items::conveyor_block());
+        // Each unknown type is reported once
+        assert_eq!(unknown, vec!["base:quantum_smelter".to_string()]);
+    }
+}
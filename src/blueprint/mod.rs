@@ -1,5 +1,8 @@
 //! Blueprint system for saving and loading building patterns
 
+pub mod layout;
+pub mod region_share;
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,6 +10,9 @@ use std::collections::HashMap;
 use crate::components::Direction;
 use crate::core::ItemId;
 
+pub use layout::{LayoutCommandEvent, LayoutFile, LayoutState};
+pub use region_share::{RegionShareCommandEvent, RegionSnapshot};
+
 /// Direction for serialization (separate from gameplay Direction to maintain clean separation)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BlueprintDirection {
@@ -177,7 +183,18 @@ pub struct BlueprintPlugin;
 impl Plugin for BlueprintPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<BlueprintLibrary>()
-            .init_resource::<BlueprintPreview>();
+            .init_resource::<BlueprintPreview>()
+            .init_resource::<LayoutState>()
+            .add_message::<LayoutCommandEvent>()
+            .add_message::<RegionShareCommandEvent>()
+            .add_systems(
+                Update,
+                (
+                    layout::handle_layout_command,
+                    layout::capture_layout_screenshot,
+                    region_share::handle_region_share_command,
+                ),
+            );
     }
 }
 
@@ -0,0 +1,562 @@
+//! Region strings for bug reports
+//!
+//! - `/debug copy-region [x1 y1 z1 x2 y2 z2]`: blocks, machines and conveyors
+//!   (with their items) inside the box, or the 16x16 area around the player,
+//!   copied to the clipboard as one string
+//! - `/debug paste-region [string]`: rebuild a copied region in front of the
+//!   player (creative only). Without an argument the clipboard is read; on
+//!   WASM the browser only allows async clipboard reads, so the string must be
+//!   passed as the argument there.
+//!
+//! The string is base64 of `[version][crc32 of the rest][deflated JSON]`, so a
+//! truncated or mangled paste is rejected instead of building half a factory.
+
+use base64::Engine;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use tracing::info;
+
+use crate::components::{CreativeMode, Machine, Player};
+use crate::core::ItemId;
+use crate::save::{conveyor_to_save, machine_to_save, spawn_saved_machine, MachineSaveDataV2};
+use crate::utils::parse_item_name;
+use crate::world::{DirtyChunks, WorldData};
+use crate::{Conveyor, BLOCK_SIZE};
+
+/// Format version written as the first byte
+pub const REGION_FORMAT_VERSION: u8 = 1;
+
+/// Largest region string accepted (encoded size)
+pub const MAX_REGION_STRING_BYTES: usize = 64 * 1024;
+
+/// Half width of the default copy area around the player (16x16)
+const DEFAULT_HALF_WIDTH: i32 = 8;
+
+/// Default copy area height below/above the player's feet
+const DEFAULT_DEPTH: i32 = 2;
+const DEFAULT_HEIGHT: i32 = 5;
+
+/// Gap between the player and the pasted region (blocks)
+const PASTE_DISTANCE: i32 = 3;
+
+/// Region command from `/debug copy-region` and `/debug paste-region`
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum RegionShareCommandEvent {
+    /// Copy the box between two corners (inclusive), or the area around the player
+    Copy { bounds: Option<(IVec3, IVec3)> },
+    /// Paste a region string, or the clipboard contents
+    Paste { encoded: Option<String> },
+}
+
+/// Why a region string was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionShareError {
+    /// Encoded string is over `MAX_REGION_STRING_BYTES`
+    TooLarge(usize),
+    /// Not valid base64 (or too short to hold the header)
+    Malformed,
+    /// Written by a different format version
+    UnsupportedVersion(u8),
+    /// Contents don't match the checksum
+    ChecksumMismatch,
+    /// Payload could not be serialized, decompressed or parsed
+    Payload(String),
+}
+
+impl std::fmt::Display for RegionShareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegionShareError::TooLarge(size) => write!(
+                f,
+                "Region string is {} KB (limit {} KB), select a smaller area",
+                size.div_ceil(1024),
+                MAX_REGION_STRING_BYTES / 1024
+            ),
+            RegionShareError::Malformed => {
+                write!(f, "Not a region string (was it copied completely?)")
+            }
+            RegionShareError::UnsupportedVersion(v) => write!(
+                f,
+                "Region string version {} is not supported (expected {})",
+                v, REGION_FORMAT_VERSION
+            ),
+            RegionShareError::ChecksumMismatch => {
+                write!(f, "Region string is corrupted (checksum mismatch)")
+            }
+            RegionShareError::Payload(e) => write!(f, "Invalid region data: {}", e),
+        }
+    }
+}
+
+/// One world block in a region
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegionBlock {
+    /// Offset from the region's minimum corner
+    pub offset: [i32; 3],
+    /// Item string ID (e.g. "base:stone")
+    pub item: String,
+}
+
+/// Everything inside a copied box; machine positions are relative to its minimum corner
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegionSnapshot {
+    /// Game version that copied the region
+    pub game_version: String,
+    /// World position the region was copied from (for the report)
+    pub origin: [i32; 3],
+    /// Box size in blocks
+    pub size: [i32; 3],
+    /// Solid blocks; every other cell is air
+    pub blocks: Vec<RegionBlock>,
+    /// Machines and conveyors in save format, including their items
+    pub machines: Vec<MachineSaveDataV2>,
+}
+
+impl RegionSnapshot {
+    /// Collect blocks, machines and conveyors inside `min..=max`
+    pub fn capture<'a>(
+        min: IVec3,
+        max: IVec3,
+        block_at: impl Fn(IVec3) -> Option<ItemId>,
+        machines: impl IntoIterator<Item = &'a Machine>,
+        conveyors: impl IntoIterator<Item = &'a Conveyor>,
+    ) -> Self {
+        let (min, max) = (min.min(max), min.max(max));
+        let inside = |pos: IVec3| pos.cmpge(min).all() && pos.cmple(max).all();
+
+        let mut blocks = Vec::new();
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                for x in min.x..=max.x {
+                    let pos = IVec3::new(x, y, z);
+                    if let Some(item) = block_at(pos).and_then(|id| id.name()) {
+                        blocks.push(RegionBlock {
+                            offset: (pos - min).to_array(),
+                            item: item.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut saved: Vec<MachineSaveDataV2> = machines
+            .into_iter()
+            .filter(|m| inside(m.position))
+            .filter_map(machine_to_save)
+            .chain(
+                conveyors
+                    .into_iter()
+                    .filter(|c| inside(c.position))
+                    .map(conveyor_to_save),
+            )
+            .collect();
+        for machine in &mut saved {
+            machine.translate(-min);
+        }
+        saved.sort_by_key(|m| m.position().to_array());
+
+        Self {
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            origin: min.to_array(),
+            size: (max - min + IVec3::ONE).to_array(),
+            blocks,
+            machines: saved,
+        }
+    }
+
+    /// Encode as a shareable string
+    pub fn encode(&self) -> Result<String, RegionShareError> {
+        let json =
+            serde_json::to_vec(self).map_err(|e| RegionShareError::Payload(e.to_string()))?;
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder
+            .write_all(&json)
+            .map_err(|e| RegionShareError::Payload(e.to_string()))?;
+        let compressed = encoder
+            .finish()
+            .map_err(|e| RegionShareError::Payload(e.to_string()))?;
+
+        let mut bytes = Vec::with_capacity(compressed.len() + 5);
+        bytes.push(REGION_FORMAT_VERSION);
+        bytes.extend_from_slice(&crc32(&compressed).to_le_bytes());
+        bytes.extend_from_slice(&compressed);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        if encoded.len() > MAX_REGION_STRING_BYTES {
+            return Err(RegionShareError::TooLarge(encoded.len()));
+        }
+        Ok(encoded)
+    }
+
+    /// Decode a string made by `encode`
+    pub fn decode(encoded: &str) -> Result<Self, RegionShareError> {
+        let encoded = encoded.trim();
+        if encoded.len() > MAX_REGION_STRING_BYTES {
+            return Err(RegionShareError::TooLarge(encoded.len()));
+        }
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| RegionShareError::Malformed)?;
+        if bytes.len() < 5 {
+            return Err(RegionShareError::Malformed);
+        }
+        if bytes[0] != REGION_FORMAT_VERSION {
+            return Err(RegionShareError::UnsupportedVersion(bytes[0]));
+        }
+        let checksum = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        let compressed = &bytes[5..];
+        if crc32(compressed) != checksum {
+            return Err(RegionShareError::ChecksumMismatch);
+        }
+
+        let mut json = Vec::new();
+        flate2::read::DeflateDecoder::new(compressed)
+            .read_to_end(&mut json)
+            .map_err(|e| RegionShareError::Payload(e.to_string()))?;
+        serde_json::from_slice(&json).map_err(|e| RegionShareError::Payload(e.to_string()))
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+/// Default copy box: 16x16 around the player, from 2 below the feet to 5 above
+pub fn default_copy_bounds(player: IVec3) -> (IVec3, IVec3) {
+    (
+        player - IVec3::new(DEFAULT_HALF_WIDTH, DEFAULT_DEPTH, DEFAULT_HALF_WIDTH),
+        player
+            + IVec3::new(
+                DEFAULT_HALF_WIDTH - 1,
+                DEFAULT_HEIGHT,
+                DEFAULT_HALF_WIDTH - 1,
+            ),
+    )
+}
+
+/// Minimum corner for a paste: `PASTE_DISTANCE` ahead of the player along the
+/// closest axis, centered sideways, bottom at the default copy depth
+pub fn paste_origin(player: IVec3, forward: Vec3, size: IVec3) -> IVec3 {
+    let (min_x, min_z) = if forward.x.abs() >= forward.z.abs() {
+        let x = if forward.x >= 0.0 {
+            player.x + PASTE_DISTANCE
+        } else {
+            player.x - PASTE_DISTANCE - size.x + 1
+        };
+        (x, player.z - size.z / 2)
+    } else {
+        let z = if forward.z >= 0.0 {
+            player.z + PASTE_DISTANCE
+        } else {
+            player.z - PASTE_DISTANCE - size.z + 1
+        };
+        (player.x - size.x / 2, z)
+    };
+    IVec3::new(min_x, player.y - DEFAULT_DEPTH, min_z)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_clipboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_clipboard(text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or("no window")?;
+    // Resolves later; the browser reports failures in the console
+    let _ = window.navigator().clipboard().write_text(text);
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_clipboard() -> Result<String, String> {
+    Err("clipboard can't be read here, use /debug paste-region <string>".to_string())
+}
+
+/// Handle `/debug copy-region` and `/debug paste-region`
+#[allow(clippy::too_many_arguments)]
+pub fn handle_region_share_command(
+    mut commands: Commands,
+    mut events: MessageReader<RegionShareCommandEvent>,
+    creative_mode: Res<CreativeMode>,
+    mut world_data: ResMut<WorldData>,
+    mut dirty_chunks: ResMut<DirtyChunks>,
+    machines: Query<(Entity, &Machine)>,
+    conveyors: Query<(Entity, &Conveyor)>,
+    player_query: Query<&Transform, With<Player>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for event in events.read() {
+        let Ok(player) = player_query.single() else {
+            continue;
+        };
+        let player_pos = (player.translation / BLOCK_SIZE).floor().as_ivec3();
+
+        match event {
+            RegionShareCommandEvent::Copy { bounds } => {
+                let (min, max) = bounds.unwrap_or_else(|| default_copy_bounds(player_pos));
+                let region = RegionSnapshot::capture(
+                    min,
+                    max,
+                    |pos| world_data.get_block(pos),
+                    machines.iter().map(|(_, m)| m),
+                    conveyors.iter().map(|(_, c)| c),
+                );
+                let encoded = match region.encode() {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        warn!(category = "REGION", "{}", e);
+                        continue;
+                    }
+                };
+                info!(
+                    category = "REGION",
+                    origin = ?region.origin,
+                    size = ?region.size,
+                    blocks = region.blocks.len(),
+                    machines = region.machines.len(),
+                    bytes = encoded.len(),
+                    "Region copied"
+                );
+                if let Err(e) = write_clipboard(&encoded) {
+                    // The log still has it for the report
+                    warn!(
+                        category = "REGION",
+                        "Clipboard unavailable ({}): {}", e, encoded
+                    );
+                }
+            }
+            RegionShareCommandEvent::Paste { encoded } => {
+                if !creative_mode.enabled {
+                    info!("/debug paste-region requires creative mode");
+                    continue;
+                }
+                let encoded = match encoded.clone().map(Ok).unwrap_or_else(read_clipboard) {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        warn!(category = "REGION", "Failed to read clipboard: {}", e);
+                        continue;
+                    }
+                };
+                let region = match RegionSnapshot::decode(&encoded) {
+                    Ok(region) => region,
+                    Err(e) => {
+                        warn!(category = "REGION", "{}", e);
+                        continue;
+                    }
+                };
+
+                let size = IVec3::from_array(region.size);
+                let min = paste_origin(player_pos, player.forward().as_vec3(), size);
+                let max = min + size - IVec3::ONE;
+                let inside = |pos: IVec3| pos.cmpge(min).all() && pos.cmple(max).all();
+
+                // Replace whatever stood there so the paste matches the copy exactly
+                for (entity, machine) in machines.iter() {
+                    if inside(machine.position) {
+                        commands.entity(entity).despawn();
+                    }
+                }
+                for (entity, conveyor) in conveyors.iter() {
+                    if inside(conveyor.position) {
+                        commands.entity(entity).despawn();
+                    }
+                }
+
+                let mut unknown = Vec::new();
+                let mut solid = std::collections::HashMap::new();
+                for block in &region.blocks {
+                    match parse_item_name(&block.item) {
+                        Some(item_id) => {
+                            solid.insert(min + IVec3::from_array(block.offset), item_id);
+                        }
+                        None if !unknown.contains(&block.item) => unknown.push(block.item.clone()),
+                        None => {}
+                    }
+                }
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        for x in min.x..=max.x {
+                            let pos = IVec3::new(x, y, z);
+                            let block = solid.get(&pos).copied();
+                            if world_data.get_block(pos) == block {
+                                continue;
+                            }
+                            match block {
+                                Some(item_id) => world_data.set_block(pos, item_id),
+                                None => {
+                                    world_data.remove_block(pos);
+                                }
+                            }
+                            dirty_chunks.mark_dirty(
+                                WorldData::world_to_chunk(pos),
+                                WorldData::world_to_local(pos),
+                            );
+                        }
+                    }
+                }
+
+                for machine in &region.machines {
+                    let mut machine = machine.clone();
+                    machine.translate(min);
+                    spawn_saved_machine(&mut commands, &mut meshes, &mut materials, &machine);
+                }
+
+                if !unknown.is_empty() {
+                    warn!(
+                        category = "REGION",
+                        version = %region.game_version,
+                        ?unknown,
+                        "Region has unknown block types, skipped"
+                    );
+                }
+                info!(
+                    category = "REGION",
+                    from = ?region.origin,
+                    to = ?min.to_array(),
+                    size = ?region.size,
+                    machines = region.machines.len(),
+                    "Region pasted"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ConveyorItem, MachineBundle};
+    use crate::core::items;
+    use crate::game_spec::FURNACE;
+    use crate::{ConveyorShape, Direction};
+
+    fn sample_region() -> RegionSnapshot {
+        let furnace =
+            MachineBundle::new_centered(&FURNACE, IVec3::new(12, 8, 10), Direction::East).machine;
+        let conveyor = Conveyor {
+            position: IVec3::new(11, 8, 10),
+            direction: Direction::East,
+            output_direction: Direction::East,
+            items: vec![ConveyorItem::new(items::iron_ore(), 0.5)],
+            last_output_index: 0,
+            last_input_pos: Some(IVec3::new(10, 8, 10)),
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        };
+        RegionSnapshot::capture(
+            IVec3::new(10, 7, 10),
+            IVec3::new(13, 9, 11),
+            |pos| (pos.y == 7).then(items::stone),
+            [&furnace],
+            [&conveyor],
+        )
+    }
+
+    #[test]
+    fn test_region_roundtrip() {
+        let region = sample_region();
+        assert_eq!(region.size, [4, 3, 2]);
+        assert_eq!(region.blocks.len(), 8);
+        assert_eq!(region.machines.len(), 2);
+
+        let encoded = region.encode().unwrap();
+        let decoded = RegionSnapshot::decode(&encoded).unwrap();
+        assert_eq!(decoded.origin, [10, 7, 10]);
+        assert_eq!(decoded.size, region.size);
+        assert_eq!(decoded.blocks, region.blocks);
+
+        // Positions are relative to the box, including the belt's last input
+        let MachineSaveDataV2::Conveyor(belt) = &decoded.machines[0] else {
+            panic!("expected the conveyor first");
+        };
+        assert_eq!(IVec3::from(belt.position), IVec3::new(1, 1, 0));
+        assert_eq!(belt.last_input_pos.map(IVec3::from), Some(IVec3::ZERO));
+        assert_eq!(belt.items.len(), 1);
+        assert_eq!(decoded.machines[1].position(), IVec3::new(2, 1, 0));
+
+        // Surrounding whitespace from chat or email is ignored
+        assert!(RegionSnapshot::decode(&format!("\n {} \n", encoded)).is_ok());
+    }
+
+    #[test]
+    fn test_corrupted_string_rejected() {
+        let encoded = sample_region().encode().unwrap();
+        let mut bytes = base64::engine::general_purpose::STANDARD
+            .decode(&encoded)
+            .unwrap();
+
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x55;
+        let flipped = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        assert_eq!(
+            RegionSnapshot::decode(&flipped).unwrap_err(),
+            RegionShareError::ChecksumMismatch
+        );
+
+        bytes[last] ^= 0x55;
+        bytes[0] = REGION_FORMAT_VERSION + 1;
+        let future = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        assert_eq!(
+            RegionSnapshot::decode(&future).unwrap_err(),
+            RegionShareError::UnsupportedVersion(REGION_FORMAT_VERSION + 1)
+        );
+
+        assert_eq!(
+            RegionSnapshot::decode("not a region!").unwrap_err(),
+            RegionShareError::Malformed
+        );
+        assert_eq!(
+            RegionSnapshot::decode(&encoded[..4]).unwrap_err(),
+            RegionShareError::Malformed
+        );
+    }
+
+    #[test]
+    fn test_size_guard() {
+        // Unique block names don't compress, so the string outgrows the limit
+        let mut region = sample_region();
+        region.blocks = (0..20_000)
+            .map(|i| RegionBlock {
+                offset: [i, i * 7, i * 13],
+                item: format!("mod:block_{}", i.wrapping_mul(2_654_435_761u32 as i32)),
+            })
+            .collect();
+        let err = region.encode().unwrap_err();
+        assert!(matches!(err, RegionShareError::TooLarge(size) if size > MAX_REGION_STRING_BYTES));
+        assert!(err.to_string().contains("smaller area"));
+
+        let oversized = "A".repeat(MAX_REGION_STRING_BYTES + 1);
+        assert!(matches!(
+            RegionSnapshot::decode(&oversized),
+            Err(RegionShareError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_paste_lands_in_front_of_player() {
+        let size = IVec3::new(16, 8, 16);
+        let player = IVec3::new(0, 10, 0);
+
+        let east = paste_origin(player, Vec3::X, size);
+        assert_eq!(east, IVec3::new(3, 8, -8));
+        let north = paste_origin(player, Vec3::NEG_Z, size);
+        assert_eq!(north, IVec3::new(-8, 8, -18));
+        // The near edge is the same distance away either way
+        assert_eq!(north.z + size.z - 1, player.z - PASTE_DISTANCE);
+    }
+}
@@ -0,0 +1,649 @@
+//! Cinematic (spectator) camera for screenshots and trailers
+//!
+//! - `/camera`: detach a free-fly camera from the player (player input is frozen)
+//! - `/camera keyframe add|clear`, `/camera play <seconds>`: catmull-rom camera paths
+//! - `/camera save|load <name>`: keyframe paths as YAML under `cinematic/`
+//! - F12 (while detached): hide all root UI nodes for clean captures
+//!
+//! The player camera is only deactivated, never moved, so exiting restores it exactly.
+
+use crate::input::{GameAction, InputManager};
+use crate::main_menu::InGameSet;
+use crate::PlayerCamera;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Sub};
+use tracing::info;
+
+/// Directory for saved camera paths
+pub const CINEMATIC_DIR: &str = "cinematic";
+
+/// Base fly speed (blocks/sec)
+const FLY_SPEED: f32 = 8.0;
+/// Speed multiplier while Shift is held
+const FLY_FAST_MULTIPLIER: f32 = 4.0;
+/// Velocity smoothing rate (higher = snappier)
+const FLY_SMOOTHING: f32 = 6.0;
+/// Look speed (radians/sec) for arrow keys
+const LOOK_SPEED: f32 = 1.5;
+/// Mouse look sensitivity (radians/pixel) during middle-drag
+const MOUSE_SENSITIVITY: f32 = 0.003;
+/// FOV change per scroll line (degrees)
+const FOV_SCROLL_STEP: f32 = 2.0;
+const FOV_MIN: f32 = 20.0;
+const FOV_MAX: f32 = 110.0;
+const PITCH_LIMIT: f32 = 1.54;
+
+/// Camera command from `/camera`
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum CameraCommandEvent {
+    /// Enter or leave cinematic mode
+    Toggle,
+    /// Record the current free camera pose
+    KeyframeAdd,
+    /// Remove all keyframes
+    KeyframeClear,
+    /// Play the keyframe path over the given seconds
+    Play(f32),
+    /// Save the keyframe path to `cinematic/<name>.yaml`
+    Save(String),
+    /// Load a keyframe path from `cinematic/<name>.yaml`
+    Load(String),
+}
+
+/// Marker for the detached free camera
+#[derive(Component)]
+pub struct CinematicFreeCamera;
+
+/// One camera pose on a path
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CameraKeyframe {
+    pub position: [f32; 3],
+    /// Yaw in radians
+    pub yaw: f32,
+    /// Pitch in radians
+    pub pitch: f32,
+    /// Vertical field of view in degrees
+    pub fov: f32,
+}
+
+impl CameraKeyframe {
+    pub fn translation(&self) -> Vec3 {
+        Vec3::from_array(self.position)
+    }
+
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0)
+    }
+}
+
+/// Keyframe path (serialized as YAML)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+impl CameraPath {
+    /// Sample the path at `t` (0.0 = first keyframe, 1.0 = last keyframe)
+    ///
+    /// Keyframes are evenly spaced in time; the end points are duplicated so
+    /// the curve passes through every keyframe.
+    pub fn sample(&self, t: f32) -> Option<CameraKeyframe> {
+        let keys = &self.keyframes;
+        match keys.len() {
+            0 => return None,
+            1 => return Some(keys[0]),
+            _ => {}
+        }
+
+        let segments = keys.len() - 1;
+        let scaled = t.clamp(0.0, 1.0) * segments as f32;
+        let index = (scaled.floor() as usize).min(segments - 1);
+        let local = scaled - index as f32;
+
+        let k = |i: isize| keys[i.clamp(0, keys.len() as isize - 1) as usize];
+        let i = index as isize;
+        let (k0, k1, k2, k3) = (k(i - 1), k(i), k(i + 1), k(i + 2));
+
+        Some(CameraKeyframe {
+            position: catmull_rom(
+                k0.translation(),
+                k1.translation(),
+                k2.translation(),
+                k3.translation(),
+                local,
+            )
+            .to_array(),
+            yaw: catmull_rom(k0.yaw, k1.yaw, k2.yaw, k3.yaw, local),
+            pitch: catmull_rom(k0.pitch, k1.pitch, k2.pitch, k3.pitch, local),
+            fov: catmull_rom(k0.fov, k1.fov, k2.fov, k3.fov, local),
+        })
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| format!("Failed to serialize camera path: {}", e))
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse camera path: {}", e))
+    }
+
+    fn file_path(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(CINEMATIC_DIR).join(format!("{}.yaml", name))
+    }
+
+    /// Save to `cinematic/<name>.yaml`
+    pub fn save(&self, name: &str) -> Result<(), String> {
+        std::fs::create_dir_all(CINEMATIC_DIR)
+            .map_err(|e| format!("Failed to create {}: {}", CINEMATIC_DIR, e))?;
+        std::fs::write(Self::file_path(name), self.to_yaml()?)
+            .map_err(|e| format!("Failed to write camera path: {}", e))
+    }
+
+    /// Load from `cinematic/<name>.yaml`
+    pub fn load(name: &str) -> Result<Self, String> {
+        let yaml = std::fs::read_to_string(Self::file_path(name))
+            .map_err(|e| format!("Failed to read camera path: {}", e))?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+/// Uniform catmull-rom interpolation between `p1` and `p2`
+pub fn catmull_rom<T>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+/// Path playback progress
+#[derive(Debug, Clone, Copy)]
+pub struct Playback {
+    pub elapsed: f32,
+    pub duration: f32,
+}
+
+/// Cinematic camera state
+#[derive(Resource, Default)]
+pub struct CinematicCamera {
+    /// Free camera is detached from the player
+    pub active: bool,
+    /// Free camera entity
+    pub camera: Option<Entity>,
+    /// Current free camera pose
+    pub yaw: f32,
+    pub pitch: f32,
+    pub fov: f32,
+    /// Smoothed fly velocity
+    pub velocity: Vec3,
+    /// Recorded keyframes
+    pub path: CameraPath,
+    /// Active path playback
+    pub playback: Option<Playback>,
+    /// Cameras deactivated on entry (restored on exit)
+    disabled_cameras: Vec<Entity>,
+    /// Root UI nodes hidden by F12 and their previous visibility
+    hidden_ui: Vec<(Entity, Visibility)>,
+}
+
+impl CinematicCamera {
+    /// Whether the UI is hidden for capture
+    pub fn ui_hidden(&self) -> bool {
+        !self.hidden_ui.is_empty()
+    }
+
+    fn current_keyframe(&self, translation: Vec3) -> CameraKeyframe {
+        CameraKeyframe {
+            position: translation.to_array(),
+            yaw: self.yaw,
+            pitch: self.pitch,
+            fov: self.fov,
+        }
+    }
+}
+
+fn perspective_fov(projection: &Projection) -> Option<f32> {
+    match projection {
+        Projection::Perspective(p) => Some(p.fov),
+        _ => None,
+    }
+}
+
+/// Restore every root UI node hidden by F12
+fn restore_ui(state: &mut CinematicCamera, ui_query: &mut Query<&mut Visibility, With<Node>>) {
+    for (entity, previous) in state.hidden_ui.drain(..) {
+        if let Ok(mut vis) = ui_query.get_mut(entity) {
+            *vis = previous;
+        }
+    }
+}
+
+/// Handle `/camera` commands
+#[allow(clippy::too_many_arguments)]
+fn handle_camera_command(
+    mut commands: Commands,
+    mut events: MessageReader<CameraCommandEvent>,
+    mut state: ResMut<CinematicCamera>,
+    player_camera_query: Query<(&GlobalTransform, &PlayerCamera, &Projection)>,
+    mut camera_query: Query<(Entity, &mut Camera), Without<CinematicFreeCamera>>,
+    free_camera_query: Query<&Transform, With<CinematicFreeCamera>>,
+    mut ui_query: Query<&mut Visibility, With<Node>>,
+) {
+    for event in events.read() {
+        match event {
+            CameraCommandEvent::Toggle if !state.active => {
+                let Ok((global, player_camera, projection)) = player_camera_query.single() else {
+                    info!("Cinematic camera: no player camera");
+                    continue;
+                };
+                state.yaw = player_camera.yaw;
+                state.pitch = player_camera.pitch;
+                state.fov = perspective_fov(projection)
+                    .unwrap_or(90.0_f32.to_radians())
+                    .to_degrees();
+                state.velocity = Vec3::ZERO;
+
+                // Deactivate player (and overlay) cameras
+                state.disabled_cameras.clear();
+                for (entity, mut camera) in camera_query.iter_mut() {
+                    if camera.is_active {
+                        camera.is_active = false;
+                        state.disabled_cameras.push(entity);
+                    }
+                }
+
+                let pose = state.current_keyframe(global.translation());
+                let entity = commands
+                    .spawn((
+                        Camera3d::default(),
+                        Projection::Perspective(PerspectiveProjection {
+                            fov: pose.fov.to_radians(),
+                            ..default()
+                        }),
+                        Tonemapping::Reinhard,
+                        Transform::from_translation(pose.translation())
+                            .with_rotation(pose.rotation()),
+                        CinematicFreeCamera,
+                    ))
+                    .id();
+                state.camera = Some(entity);
+                state.active = true;
+                info!(category = "CAMERA", "Cinematic camera enabled");
+            }
+            CameraCommandEvent::Toggle => {
+                if let Some(entity) = state.camera.take() {
+                    commands.entity(entity).despawn();
+                }
+                let disabled = std::mem::take(&mut state.disabled_cameras);
+                for entity in disabled {
+                    if let Ok((_, mut camera)) = camera_query.get_mut(entity) {
+                        camera.is_active = true;
+                    }
+                }
+                restore_ui(&mut state, &mut ui_query);
+                state.playback = None;
+                state.active = false;
+                info!(category = "CAMERA", "Cinematic camera disabled");
+            }
+            CameraCommandEvent::KeyframeAdd => {
+                let Ok(transform) = free_camera_query.single() else {
+                    info!("Cinematic camera: enable with /camera first");
+                    continue;
+                };
+                let keyframe = state.current_keyframe(transform.translation);
+                state.path.keyframes.push(keyframe);
+                info!(
+                    category = "CAMERA",
+                    count = state.path.keyframes.len(),
+                    "Keyframe added"
+                );
+            }
+            CameraCommandEvent::KeyframeClear => {
+                state.path.keyframes.clear();
+                state.playback = None;
+                info!(category = "CAMERA", "Keyframes cleared");
+            }
+            CameraCommandEvent::Play(seconds) => {
+                if !state.active {
+                    info!("Cinematic camera: enable with /camera first");
+                } else if state.path.keyframes.len() < 2 {
+                    info!("Cinematic camera: need at least 2 keyframes");
+                } else {
+                    state.playback = Some(Playback {
+                        elapsed: 0.0,
+                        duration: seconds.max(0.1),
+                    });
+                }
+            }
+            CameraCommandEvent::Save(name) => match state.path.save(name) {
+                Ok(()) => info!(category = "CAMERA", %name, "Camera path saved"),
+                Err(e) => info!("{}", e),
+            },
+            CameraCommandEvent::Load(name) => match CameraPath::load(name) {
+                Ok(path) => {
+                    info!(
+                        category = "CAMERA",
+                        %name,
+                        count = path.keyframes.len(),
+                        "Camera path loaded"
+                    );
+                    state.path = path;
+                    state.playback = None;
+                }
+                Err(e) => info!("{}", e),
+            },
+        }
+    }
+}
+
+/// Free camera fly controls (WASD/Space/Shift-descend, arrows or middle-drag to look, scroll for FOV)
+#[allow(clippy::too_many_arguments)]
+fn cinematic_fly(
+    time: Res<Time>,
+    input: Res<InputManager>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    command_state: Res<crate::components::CommandInputState>,
+    mut state: ResMut<CinematicCamera>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<CinematicFreeCamera>>,
+) {
+    if !state.active || state.playback.is_some() || command_state.open {
+        return;
+    }
+    let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let dt = time.delta_secs();
+
+    // Look
+    if input.pressed(GameAction::LookLeft) {
+        state.yaw += LOOK_SPEED * dt;
+    }
+    if input.pressed(GameAction::LookRight) {
+        state.yaw -= LOOK_SPEED * dt;
+    }
+    if input.pressed(GameAction::LookUp) {
+        state.pitch += LOOK_SPEED * dt;
+    }
+    if input.pressed(GameAction::LookDown) {
+        state.pitch -= LOOK_SPEED * dt;
+    }
+    if mouse_button.pressed(MouseButton::Middle) {
+        state.yaw -= mouse_motion.delta.x * MOUSE_SENSITIVITY;
+        state.pitch -= mouse_motion.delta.y * MOUSE_SENSITIVITY;
+    }
+    state.pitch = state.pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
+
+    // FOV
+    if mouse_scroll.delta.y != 0.0 {
+        state.fov = (state.fov - mouse_scroll.delta.y * FOV_SCROLL_STEP).clamp(FOV_MIN, FOV_MAX);
+    }
+
+    // Move along the view direction (smoothed)
+    let rotation = Quat::from_euler(EulerRot::YXZ, state.yaw, state.pitch, 0.0);
+    let forward = rotation * Vec3::NEG_Z;
+    let right = rotation * Vec3::X;
+    let mut direction = Vec3::ZERO;
+    if input.pressed(GameAction::MoveForward) {
+        direction += forward;
+    }
+    if input.pressed(GameAction::MoveBackward) {
+        direction -= forward;
+    }
+    if input.pressed(GameAction::MoveLeft) {
+        direction -= right;
+    }
+    if input.pressed(GameAction::MoveRight) {
+        direction += right;
+    }
+    if input.pressed(GameAction::Jump) {
+        direction.y += 1.0;
+    }
+    if input.pressed(GameAction::Descend) {
+        direction.y -= 1.0;
+    }
+    let speed = if input.pressed(GameAction::ModifierShift) {
+        FLY_SPEED * FLY_FAST_MULTIPLIER
+    } else {
+        FLY_SPEED
+    };
+    let target_velocity = direction.normalize_or_zero() * speed;
+    let blend = 1.0 - (-FLY_SMOOTHING * dt).exp();
+    state.velocity = state.velocity.lerp(target_velocity, blend);
+
+    transform.translation += state.velocity * dt;
+    transform.rotation = rotation;
+    if let Projection::Perspective(p) = projection.as_mut() {
+        p.fov = state.fov.to_radians();
+    }
+}
+
+/// Move the free camera along the keyframe path
+fn cinematic_playback(
+    time: Res<Time>,
+    mut state: ResMut<CinematicCamera>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<CinematicFreeCamera>>,
+) {
+    let Some(mut playback) = state.playback else {
+        return;
+    };
+    let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+
+    playback.elapsed += time.delta_secs();
+    let t = (playback.elapsed / playback.duration).min(1.0);
+    if let Some(pose) = state.path.sample(t) {
+        transform.translation = pose.translation();
+        transform.rotation = pose.rotation();
+        if let Projection::Perspective(p) = projection.as_mut() {
+            p.fov = pose.fov.to_radians();
+        }
+        state.yaw = pose.yaw;
+        state.pitch = pose.pitch;
+        state.fov = pose.fov;
+    }
+
+    state.playback = (t < 1.0).then_some(playback);
+}
+
+/// F12: hide/show all root UI nodes while the cinematic camera is active
+#[allow(clippy::type_complexity)]
+fn toggle_cinematic_ui(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<CinematicCamera>,
+    mut root_query: Query<(Entity, &mut Visibility), (With<Node>, Without<ChildOf>)>,
+) {
+    if !state.active || !key_input.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    if state.ui_hidden() {
+        for (entity, previous) in std::mem::take(&mut state.hidden_ui) {
+            if let Ok((_, mut vis)) = root_query.get_mut(entity) {
+                *vis = previous;
+            }
+        }
+    } else {
+        for (entity, mut vis) in root_query.iter_mut() {
+            state.hidden_ui.push((entity, *vis));
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+/// Plugin for the cinematic camera
+pub struct CinematicPlugin;
+
+impl Plugin for CinematicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CinematicCamera>()
+            .add_message::<CameraCommandEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_camera_command,
+                    cinematic_fly,
+                    cinematic_playback,
+                    toggle_cinematic_ui,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(x: f32, yaw: f32) -> CameraKeyframe {
+        CameraKeyframe {
+            position: [x, 10.0, 0.0],
+            yaw,
+            pitch: 0.0,
+            fov: 60.0,
+        }
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_control_points() {
+        assert_eq!(catmull_rom(0.0, 1.0, 2.0, 3.0, 0.0), 1.0);
+        assert_eq!(catmull_rom(0.0, 1.0, 2.0, 3.0, 1.0), 2.0);
+        // Collinear, evenly spaced points interpolate linearly
+        assert!((catmull_rom(0.0, 1.0, 2.0, 3.0, 0.5) - 1.5).abs() < 1e-5);
+
+        let v = catmull_rom(Vec3::ZERO, Vec3::X, Vec3::X * 2.0, Vec3::X * 3.0, 0.25);
+        assert!((v - Vec3::X * 1.25).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_path_sample_hits_keyframes() {
+        let path = CameraPath {
+            keyframes: vec![key(0.0, 0.0), key(10.0, 1.0), key(20.0, 0.5)],
+        };
+        assert_eq!(path.sample(0.0), Some(path.keyframes[0]));
+        assert_eq!(path.sample(1.0), Some(path.keyframes[2]));
+
+        let mid = path.sample(0.5).unwrap();
+        assert!((mid.translation() - Vec3::new(10.0, 10.0, 0.0)).length() < 1e-4);
+        assert!((mid.yaw - 1.0).abs() < 1e-5);
+
+        // Out of range is clamped
+        assert_eq!(path.sample(2.0), Some(path.keyframes[2]));
+    }
+
+    #[test]
+    fn test_path_sample_degenerate() {
+        assert_eq!(CameraPath::default().sample(0.5), None);
+        let single = CameraPath {
+            keyframes: vec![key(3.0, 0.0)],
+        };
+        assert_eq!(single.sample(0.7), Some(single.keyframes[0]));
+    }
+
+    #[test]
+    fn test_path_yaml_roundtrip() {
+        let path = CameraPath {
+            keyframes: vec![key(0.0, 0.0), key(5.0, 1.5)],
+        };
+        let yaml = path.to_yaml().unwrap();
+        assert!(yaml.contains("keyframes"));
+        assert_eq!(CameraPath::from_yaml(&yaml).unwrap(), path);
+        assert!(CameraPath::from_yaml("keyframes: 3").is_err());
+    }
+
+    #[test]
+    fn test_f12_toggles_root_ui_visibility() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.insert_resource(CinematicCamera {
+            active: true,
+            ..default()
+        });
+        app.add_systems(Update, toggle_cinematic_ui);
+
+        let hud = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Visible))
+            .id();
+        let menu = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Hidden))
+            .id();
+        let child = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Inherited, ChildOf(hud)))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::F12);
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Visibility>(hud),
+            Some(&Visibility::Hidden)
+        );
+        assert_eq!(
+            app.world().get::<Visibility>(menu),
+            Some(&Visibility::Hidden)
+        );
+        // Children inherit from the root and are left untouched
+        assert_eq!(
+            app.world().get::<Visibility>(child),
+            Some(&Visibility::Inherited)
+        );
+        assert!(app.world().resource::<CinematicCamera>().ui_hidden());
+
+        // Press again: previous visibility restored
+        {
+            let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keys.release(KeyCode::F12);
+            keys.clear();
+            keys.press(KeyCode::F12);
+        }
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Visibility>(hud),
+            Some(&Visibility::Visible)
+        );
+        assert_eq!(
+            app.world().get::<Visibility>(menu),
+            Some(&Visibility::Hidden)
+        );
+        assert!(!app.world().resource::<CinematicCamera>().ui_hidden());
+    }
+
+    #[test]
+    fn test_f12_ignored_when_inactive() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<CinematicCamera>();
+        app.add_systems(Update, toggle_cinematic_ui);
+
+        let hud = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Visible))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::F12);
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Visibility>(hud),
+            Some(&Visibility::Visible)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Cursor ownership state machine
+//!
+//! Screens, the pause menu and window focus changes don't touch the window's
+//! cursor themselves; they send requests to `CursorController`, and
+//! `apply_cursor_controller` applies the result to the window once per frame.
+//! The input matrix (`InputState`) reads the same state, so "can't look
+//! around but not paused" can't happen: either the game owns the cursor or
+//! something else does.
+
+use super::ui_state::UIContext;
+use bevy::prelude::*;
+
+/// Who owns the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorState {
+    /// Gameplay: cursor confined to the window, look and block actions active
+    Locked,
+    /// A screen owns the cursor (free and visible)
+    UnlockedForUi { owner: UIContext },
+    /// Pause menu (free cursor, the world keeps running)
+    Paused,
+    /// The window lost focus; the cursor is released until it comes back
+    FocusLost,
+}
+
+/// Single owner of the cursor state
+///
+/// While the focus is lost, requests update the state to return to instead,
+/// so a screen closed in the meantime doesn't come back when the window does.
+#[derive(Resource, Debug, Clone)]
+pub struct CursorController {
+    state: CursorState,
+    /// State to return to on focus regain (only used in `FocusLost`)
+    resume: CursorState,
+    /// The click that returned control to the game shouldn't also break a block
+    swallow_click: bool,
+}
+
+impl Default for CursorController {
+    /// Paused, like `UIState` (the game starts on the pause menu)
+    fn default() -> Self {
+        Self {
+            state: CursorState::Paused,
+            resume: CursorState::Paused,
+            swallow_click: false,
+        }
+    }
+}
+
+impl CursorController {
+    /// Current state
+    pub fn state(&self) -> &CursorState {
+        &self.state
+    }
+
+    /// Whether the game owns the cursor (look and block actions)
+    pub fn is_locked(&self) -> bool {
+        self.state == CursorState::Locked
+    }
+
+    /// Whether a screen, the pause menu or focus loss has freed the cursor
+    pub fn is_released(&self) -> bool {
+        !self.is_locked()
+    }
+
+    /// Whether the focused game shows a screen (WASM `data-ui-open` attribute)
+    pub fn ui_open(&self) -> bool {
+        matches!(
+            self.state,
+            CursorState::UnlockedForUi { .. } | CursorState::Paused
+        )
+    }
+
+    /// Set the state, or the state to resume while the focus is lost
+    fn set(&mut self, state: CursorState) {
+        let target = if self.state == CursorState::FocusLost {
+            &mut self.resume
+        } else {
+            &mut self.state
+        };
+        *target = state;
+    }
+
+    /// The state requests currently act on
+    fn effective(&self) -> &CursorState {
+        if self.state == CursorState::FocusLost {
+            &self.resume
+        } else {
+            &self.state
+        }
+    }
+
+    /// Return the cursor to the game
+    pub fn request_lock(&mut self) {
+        self.set(CursorState::Locked);
+    }
+
+    /// Hand the cursor to a screen (replaces any other screen's claim)
+    ///
+    /// Gameplay and the pause menu map to `request_lock` / `request_pause`, so
+    /// the top of `UIState` can be passed as is.
+    pub fn request_ui(&mut self, owner: UIContext) {
+        match owner {
+            UIContext::Gameplay => self.request_lock(),
+            UIContext::PauseMenu => self.request_pause(),
+            owner => self.set(CursorState::UnlockedForUi { owner }),
+        }
+    }
+
+    /// A screen closed: back to the game if it still owned the cursor
+    ///
+    /// A stale close (another screen or the pause menu took over since) is ignored.
+    pub fn release_ui(&mut self, owner: &UIContext) {
+        if matches!(self.effective(), CursorState::UnlockedForUi { owner: current } if current == owner)
+        {
+            self.set(CursorState::Locked);
+        }
+    }
+
+    /// Open the pause menu
+    pub fn request_pause(&mut self) {
+        self.set(CursorState::Paused);
+    }
+
+    /// The window lost focus (alt-tab, the browser took the pointer)
+    pub fn focus_lost(&mut self) {
+        if self.state != CursorState::FocusLost {
+            self.resume = std::mem::replace(&mut self.state, CursorState::FocusLost);
+        }
+    }
+
+    /// The window got focus back: return to the state before (or requested since)
+    pub fn focus_gained(&mut self) {
+        if self.state != CursorState::FocusLost {
+            return;
+        }
+        self.state = self.resume.clone();
+        // The click that focused the window lands in the game
+        self.swallow_click = self.state == CursorState::Locked;
+    }
+
+    /// Whether to ignore this click because it only returned focus (clears the flag)
+    pub fn take_swallowed_click(&mut self) -> bool {
+        std::mem::take(&mut self.swallow_click)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked() -> CursorController {
+        let mut cursor = CursorController::default();
+        cursor.request_lock();
+        cursor
+    }
+
+    #[test]
+    fn test_open_close_and_pause() {
+        let mut cursor = CursorController::default();
+        assert_eq!(cursor.state(), &CursorState::Paused);
+        assert!(cursor.ui_open());
+
+        cursor.request_ui(UIContext::Gameplay);
+        assert!(cursor.is_locked());
+
+        cursor.request_ui(UIContext::Inventory);
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: UIContext::Inventory
+            }
+        );
+        cursor.release_ui(&UIContext::Inventory);
+        assert!(cursor.is_locked());
+
+        cursor.request_pause();
+        assert_eq!(cursor.state(), &CursorState::Paused);
+        cursor.request_ui(UIContext::Gameplay);
+        assert!(cursor.is_locked());
+        assert!(!cursor.ui_open());
+    }
+
+    #[test]
+    fn test_double_open_and_stale_close() {
+        let mut cursor = locked();
+        cursor.request_ui(UIContext::CommandInput);
+        cursor.request_ui(UIContext::CommandInput);
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: UIContext::CommandInput
+            }
+        );
+
+        // A second screen takes over; closing the first one changes nothing
+        let machine = UIContext::Machine(Entity::PLACEHOLDER);
+        cursor.request_ui(machine.clone());
+        cursor.release_ui(&UIContext::CommandInput);
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: machine.clone()
+            }
+        );
+
+        // Closing a machine UI after ESC opened the pause menu stays paused
+        cursor.request_pause();
+        cursor.release_ui(&machine);
+        assert_eq!(cursor.state(), &CursorState::Paused);
+
+        // Closing twice is harmless
+        cursor.request_ui(machine.clone());
+        cursor.release_ui(&machine);
+        cursor.release_ui(&machine);
+        assert!(cursor.is_locked());
+    }
+
+    #[test]
+    fn test_focus_loss_round_trip() {
+        let mut cursor = locked();
+        cursor.focus_lost();
+        assert_eq!(cursor.state(), &CursorState::FocusLost);
+        assert!(cursor.is_released());
+        assert!(!cursor.ui_open());
+
+        // Losing focus twice keeps the state to return to
+        cursor.focus_lost();
+        cursor.focus_gained();
+        assert!(cursor.is_locked());
+        assert!(cursor.take_swallowed_click());
+        assert!(!cursor.take_swallowed_click());
+
+        // Gaining focus without losing it does nothing
+        cursor.focus_gained();
+        assert!(cursor.is_locked());
+        assert!(!cursor.take_swallowed_click());
+
+        // A screen open before the focus loss is still open after it
+        cursor.request_ui(UIContext::Settings);
+        cursor.focus_lost();
+        cursor.focus_gained();
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: UIContext::Settings
+            }
+        );
+        assert!(!cursor.take_swallowed_click());
+    }
+
+    #[test]
+    fn test_requests_while_focus_lost() {
+        // Closing a screen while unfocused returns to the game, not the screen
+        let mut cursor = locked();
+        cursor.request_ui(UIContext::Inventory);
+        cursor.focus_lost();
+        cursor.release_ui(&UIContext::Inventory);
+        assert_eq!(cursor.state(), &CursorState::FocusLost);
+        cursor.focus_gained();
+        assert!(cursor.is_locked());
+
+        // Opening a screen while unfocused shows it on return
+        cursor.focus_lost();
+        cursor.request_ui(UIContext::QuestLog);
+        assert_eq!(cursor.state(), &CursorState::FocusLost);
+        cursor.focus_gained();
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: UIContext::QuestLog
+            }
+        );
+
+        // Pausing while unfocused
+        cursor.focus_lost();
+        cursor.request_pause();
+        cursor.focus_gained();
+        assert_eq!(cursor.state(), &CursorState::Paused);
+
+        // A stale close while unfocused is still ignored
+        cursor.request_ui(UIContext::Stats);
+        cursor.focus_lost();
+        cursor.release_ui(&UIContext::Inventory);
+        cursor.focus_gained();
+        assert_eq!(
+            cursor.state(),
+            &CursorState::UnlockedForUi {
+                owner: UIContext::Stats
+            }
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Input state management
 
-use super::player::CursorLockState;
+use super::cursor::CursorController;
 use super::ui::{CommandInputState, InteractingMachine, InventoryOpen};
+use crate::cinematic::CinematicCamera;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
@@ -19,6 +20,8 @@ pub enum InputState {
     Command,
     /// Game is paused (ESC) - only click to resume
     Paused,
+    /// Cinematic free camera is detached - player inputs frozen
+    Cinematic,
 }
 
 impl InputState {
@@ -26,38 +29,43 @@ impl InputState {
     ///
     /// Priority: Command > Inventory > MachineUI > Paused > Gameplay
     ///
-    /// Note: `cursor_state.paused` is true for ALL UI states (not just PauseMenu).
-    /// So we check specific UI states first, and only fall back to Paused
-    /// when no other UI is active.
+    /// The cursor is released for every screen, so the specific screens are
+    /// checked first; a released cursor with none of them open (pause menu,
+    /// settings, stats, lost window focus) is Paused.
     pub fn current(
         inventory_open: &InventoryOpen,
         interacting_machine: &InteractingMachine,
         command_state: &CommandInputState,
-        cursor_state: &CursorLockState,
+        cursor: &CursorController,
     ) -> Self {
-        // Check specific UI states first (they all set paused=true)
         if command_state.open {
             InputState::Command
         } else if inventory_open.0 {
             InputState::Inventory
         } else if interacting_machine.0.is_some() {
             InputState::MachineUI
-        } else if cursor_state.paused {
-            // paused=true with no other UI = PauseMenu or Settings
+        } else if cursor.is_released() {
             InputState::Paused
         } else {
             InputState::Gameplay
         }
     }
 
+    /// Override Gameplay with Cinematic while the free camera is detached
+    fn with_cinematic(self, cinematic: Option<&CinematicCamera>) -> Self {
+        match (self, cinematic) {
+            (InputState::Gameplay, Some(c)) if c.active => InputState::Cinematic,
+            (state, _) => state,
+        }
+    }
+
     /// Check if player movement is allowed
     #[allow(dead_code)]
     pub fn allows_movement(self) -> bool {
         matches!(self, InputState::Gameplay)
     }
 
-    /// Check if player camera movement is allowed
-    #[allow(dead_code)]
+    /// Check if mouse/arrow-key look may turn the camera
     pub fn allows_camera(self) -> bool {
         matches!(self, InputState::Gameplay)
     }
@@ -83,6 +91,14 @@ impl InputState {
     pub fn allows_hotbar(self) -> bool {
         matches!(self, InputState::Gameplay)
     }
+
+    /// Check if scrollable UI panels may consume the mouse wheel
+    ///
+    /// Never true together with `allows_hotbar`, so one wheel tick can't both
+    /// scroll a panel and change the hotbar slot.
+    pub fn allows_ui_scroll(self) -> bool {
+        !matches!(self, InputState::Gameplay | InputState::Cinematic)
+    }
 }
 
 /// SystemParam for reading all input state resources
@@ -91,17 +107,19 @@ pub struct InputStateResources<'w> {
     pub inventory_open: Res<'w, InventoryOpen>,
     pub interacting_machine: Res<'w, InteractingMachine>,
     pub command_state: Res<'w, CommandInputState>,
+    pub cinematic: Option<Res<'w, CinematicCamera>>,
 }
 
 impl InputStateResources<'_> {
-    /// Get state with external cursor_state (for systems that need ResMut<CursorLockState>)
-    pub fn get_state_with(&self, cursor_state: &CursorLockState) -> InputState {
+    /// Get state with an external cursor (for systems that need ResMut<CursorController>)
+    pub fn get_state_with(&self, cursor: &CursorController) -> InputState {
         InputState::current(
             &self.inventory_open,
             &self.interacting_machine,
             &self.command_state,
-            cursor_state,
+            cursor,
         )
+        .with_cinematic(self.cinematic.as_deref())
     }
 }
 
@@ -111,7 +129,8 @@ pub struct InputStateResourcesWithCursor<'w> {
     pub inventory_open: Res<'w, InventoryOpen>,
     pub interacting_machine: Res<'w, InteractingMachine>,
     pub command_state: Res<'w, CommandInputState>,
-    pub cursor_state: Res<'w, CursorLockState>,
+    pub cursor: Res<'w, CursorController>,
+    pub cinematic: Option<Res<'w, CinematicCamera>>,
 }
 
 impl InputStateResourcesWithCursor<'_> {
@@ -120,8 +139,9 @@ impl InputStateResourcesWithCursor<'_> {
             &self.inventory_open,
             &self.interacting_machine,
             &self.command_state,
-            &self.cursor_state,
+            &self.cursor,
         )
+        .with_cinematic(self.cinematic.as_deref())
     }
 }
 
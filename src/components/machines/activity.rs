@@ -0,0 +1,191 @@
+//! Per-machine utilization history for the machine UI sparkline
+//!
+//! Every machine keeps a fixed-size ring of duty-cycle samples (the share of
+//! each `ACTIVITY_SAMPLE_SECS` window it spent working), so memory per
+//! machine stays bounded however long the game runs. The history is
+//! transient diagnostics and is not saved.
+
+use bevy::prelude::*;
+
+/// Simulation seconds covered by one sample
+pub const ACTIVITY_SAMPLE_SECS: f32 = 2.0;
+
+/// Samples kept per machine (4 minutes)
+pub const ACTIVITY_SAMPLES: usize = 120;
+
+/// Recent duty cycle of one machine
+#[derive(Component, Clone, Debug)]
+pub struct MachineActivity {
+    /// Ring buffer of duty cycles (0 = idle all window, 255 = working all window)
+    samples: [u8; ACTIVITY_SAMPLES],
+    /// Index the next sample goes to
+    next: usize,
+    /// Number of samples recorded so far (at most ACTIVITY_SAMPLES)
+    len: usize,
+    /// Working seconds in the open window
+    active: f32,
+    /// Working seconds already past the window boundary (belong to the next window)
+    carry: f32,
+}
+
+impl Default for MachineActivity {
+    fn default() -> Self {
+        Self {
+            samples: [0; ACTIVITY_SAMPLES],
+            next: 0,
+            len: 0,
+            active: 0.0,
+            carry: 0.0,
+        }
+    }
+}
+
+impl MachineActivity {
+    /// Count `secs` of work, the last `carry` seconds of which fall into the next window
+    pub fn record(&mut self, secs: f32, carry: f32) {
+        let carry = carry.clamp(0.0, secs);
+        self.active += secs - carry;
+        self.carry += carry;
+    }
+
+    /// Close the open window as a sample and start the next one
+    pub fn close_sample(&mut self) {
+        let duty = (self.active / ACTIVITY_SAMPLE_SECS).clamp(0.0, 1.0);
+        self.samples[self.next] = (duty * 255.0).round() as u8;
+        self.next = (self.next + 1) % ACTIVITY_SAMPLES;
+        self.len = (self.len + 1).min(ACTIVITY_SAMPLES);
+        self.active = std::mem::take(&mut self.carry);
+    }
+
+    /// Number of recorded samples
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no sample has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Recorded duty cycles (0.0-1.0), oldest first
+    pub fn samples(&self) -> impl Iterator<Item = f32> + '_ {
+        let start = (self.next + ACTIVITY_SAMPLES - self.len) % ACTIVITY_SAMPLES;
+        (0..self.len).map(move |i| self.samples[(start + i) % ACTIVITY_SAMPLES] as f32 / 255.0)
+    }
+
+    /// Average duty cycle over the recorded samples (None before the first one)
+    pub fn utilization(&self) -> Option<f32> {
+        (!self.is_empty()).then(|| self.samples().sum::<f32>() / self.len as f32)
+    }
+
+    /// Simulation seconds the recorded samples cover
+    pub fn span_secs(&self) -> f32 {
+        self.len as f32 * ACTIVITY_SAMPLE_SECS
+    }
+}
+
+/// Position in the sample window shared by all machines
+///
+/// One clock keeps every machine's samples in step, so a window closes for
+/// all machines at once, sleeping ones included.
+#[derive(Resource, Default, Debug)]
+pub struct ActivityClock {
+    elapsed: f32,
+}
+
+impl ActivityClock {
+    /// Advance by `secs`; when a window closes, returns how much of `secs` is past the boundary
+    pub fn advance(&mut self, secs: f32) -> Option<f32> {
+        self.elapsed += secs;
+        if self.elapsed < ACTIVITY_SAMPLE_SECS {
+            return None;
+        }
+        self.elapsed -= ACTIVITY_SAMPLE_SECS;
+        Some(self.elapsed.min(secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run ticks of `secs`, `working` deciding per tick, like sample_machine_activity
+    fn run(
+        clock: &mut ActivityClock,
+        activity: &mut MachineActivity,
+        secs: f32,
+        working: impl IntoIterator<Item = bool>,
+    ) {
+        for busy in working {
+            let carry = clock.advance(secs);
+            if busy {
+                activity.record(secs, carry.unwrap_or(0.0));
+            }
+            if carry.is_some() {
+                activity.close_sample();
+            }
+        }
+    }
+
+    #[test]
+    fn test_duty_cycle_per_window() {
+        let mut clock = ActivityClock::default();
+        let mut activity = MachineActivity::default();
+        assert_eq!(activity.utilization(), None);
+
+        // 20 Hz: busy the whole first window, the first quarter of the second
+        // (one spare tick in case float sums put the boundary a tick late)
+        let ticks = (0..40)
+            .map(|_| true)
+            .chain((0..40).map(|i| i < 10))
+            .chain([false]);
+        run(&mut clock, &mut activity, 0.05, ticks);
+        let samples: Vec<f32> = activity.samples().collect();
+        assert_eq!(samples.len(), 2);
+        assert!((samples[0] - 1.0).abs() < 0.01, "{:?}", samples);
+        assert!((samples[1] - 0.25).abs() < 0.01, "{:?}", samples);
+        assert!((activity.utilization().unwrap() - 0.625).abs() < 0.01);
+        assert_eq!(activity.span_secs(), 4.0);
+    }
+
+    #[test]
+    fn test_tick_straddling_boundary_is_split() {
+        let mut clock = ActivityClock::default();
+        let mut activity = MachineActivity::default();
+
+        // 0.3 s ticks: the 7th ends 0.1 s into the second window
+        let ticks = [false, false, false, false, false, false, true];
+        run(&mut clock, &mut activity, 0.3, ticks);
+        assert_eq!(activity.len(), 1);
+        // 0.2 s of the straddling tick in the first window
+        assert!((activity.samples().next().unwrap() - 0.1).abs() < 0.01);
+
+        // The other 0.1 s counts toward the second window: 0.1 + 5 busy ticks = 1.6 s
+        run(
+            &mut clock,
+            &mut activity,
+            0.3,
+            [true, true, true, true, true, false],
+        );
+        assert_eq!(activity.len(), 1);
+        run(&mut clock, &mut activity, 0.3, [false]);
+        assert_eq!(activity.len(), 2);
+        assert!((activity.samples().last().unwrap() - 0.8).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut activity = MachineActivity::default();
+        for i in 0..ACTIVITY_SAMPLES + 10 {
+            // Mark each sample with its index so the order can be checked
+            activity.record(i as f32 / 100.0, 0.0);
+            activity.close_sample();
+        }
+        assert_eq!(activity.len(), ACTIVITY_SAMPLES);
+        let first = activity.samples().next().unwrap();
+        let last = activity.samples().last().unwrap();
+        // Oldest kept sample is #10 (0.1 s of work), newest #129
+        assert!((first - 0.05).abs() < 0.01, "{}", first);
+        assert!((last - 0.645).abs() < 0.01, "{}", last);
+    }
+}
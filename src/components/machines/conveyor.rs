@@ -1,17 +1,19 @@
-//! Conveyor components: Conveyor, ConveyorItem, ConveyorShape, ConveyorVisual, ConveyorItemVisual
+//! Conveyor components: Conveyor, ConveyorItem, ConveyorShape, ConveyorVisual, ConveyorItemVisual, ConveyorItemStackVisual, ConveyorItemBillboard, ConveyorTierVisual
 
 use crate::constants::*;
-use crate::core::ItemId;
+use crate::core::{items, ItemId};
 use bevy::prelude::*;
 
 use super::Direction;
 
-/// Single item on a conveyor
+/// Single item (or small stack of identical items) on a conveyor
 /// Stores ItemId directly to support Mod items without data loss
 #[derive(Clone)]
 pub struct ConveyorItem {
     /// The item being transported (ItemId preserves mod items)
     pub item_id: ItemId,
+    /// Number of items in this stack (1..=CONVEYOR_MAX_STACK), moved as one unit
+    pub count: u32,
     /// Position on conveyor (0.0 = entry, 1.0 = exit)
     pub progress: f32,
     /// Previous progress for interpolation (set before each FixedUpdate tick)
@@ -29,6 +31,7 @@ impl ConveyorItem {
     pub fn new(item_id: ItemId, progress: f32) -> Self {
         Self {
             item_id,
+            count: 1,
             progress,
             previous_progress: progress,
             visual_entity: None,
@@ -72,13 +75,62 @@ pub struct Conveyor {
     pub items: Vec<ConveyorItem>,
     /// Index for round-robin output (splitter mode)
     pub last_output_index: usize,
-    /// Index for alternating input (zipper mode)
-    pub last_input_source: usize,
+    /// Position of the source that last inserted an item (zipper mode)
+    ///
+    /// Stored as a position rather than an entity so it survives save/load.
+    pub last_input_pos: Option<IVec3>,
+    /// Whether the belt is running (disabled belts hold their items in place)
+    pub enabled: bool,
     /// Current shape (updated based on adjacent conveyors)
     pub shape: ConveyorShape,
+    /// Belt speed relative to CONVEYOR_SPEED (1.0 = base belt, Mk2 = 2.0, Mk3 = 4.0)
+    pub speed_multiplier: f32,
+}
+
+/// Speed multiplier of a conveyor item (None = not a conveyor)
+pub fn conveyor_speed_multiplier(item_id: ItemId) -> Option<f32> {
+    if item_id == items::conveyor_block() {
+        Some(1.0)
+    } else if item_id == items::conveyor_block_mk2() {
+        Some(CONVEYOR_MK2_SPEED_MULTIPLIER)
+    } else if item_id == items::conveyor_block_mk3() {
+        Some(CONVEYOR_MK3_SPEED_MULTIPLIER)
+    } else {
+        None
+    }
+}
+
+/// Conveyor item for a speed multiplier (the fastest tier not above it)
+pub fn conveyor_tier_item(speed_multiplier: f32) -> ItemId {
+    if speed_multiplier >= CONVEYOR_MK3_SPEED_MULTIPLIER {
+        items::conveyor_block_mk3()
+    } else if speed_multiplier >= CONVEYOR_MK2_SPEED_MULTIPLIER {
+        items::conveyor_block_mk2()
+    } else {
+        items::conveyor_block()
+    }
 }
 
 impl Conveyor {
+    /// Conveyor item of this belt's tier (returned when broken or upgraded)
+    pub fn tier_item(&self) -> ItemId {
+        conveyor_tier_item(self.speed_multiplier)
+    }
+
+    /// Switch to the tier of `item_id` if that tier is faster
+    ///
+    /// Items in transit keep their progress. Returns the item of the replaced tier,
+    /// or None if `item_id` is not a faster conveyor.
+    pub fn upgrade_tier(&mut self, item_id: ItemId) -> Option<ItemId> {
+        let speed_multiplier = conveyor_speed_multiplier(item_id)?;
+        if speed_multiplier <= self.speed_multiplier {
+            return None;
+        }
+        let old = self.tier_item();
+        self.speed_multiplier = speed_multiplier;
+        Some(old)
+    }
+
     /// Check if conveyor can accept a new item at the given position
     pub fn can_accept_item(&self, at_progress: f32) -> bool {
         if self.items.len() >= CONVEYOR_MAX_ITEMS {
@@ -100,8 +152,21 @@ impl Conveyor {
         at_progress: f32,
         visual_entity: Option<Entity>,
         lateral_offset: f32,
+    ) {
+        self.add_stack_with_visual(item_id, 1, at_progress, visual_entity, lateral_offset);
+    }
+
+    /// Add a stack of `count` items as one belt item
+    pub fn add_stack_with_visual(
+        &mut self,
+        item_id: ItemId,
+        count: u32,
+        at_progress: f32,
+        visual_entity: Option<Entity>,
+        lateral_offset: f32,
     ) {
         let mut item = ConveyorItem::new(item_id, at_progress);
+        item.count = count.clamp(1, CONVEYOR_MAX_STACK);
         item.visual_entity = visual_entity;
         item.lateral_offset = lateral_offset;
         self.items.push(item);
@@ -118,6 +183,33 @@ impl Conveyor {
         self.add_item_with_visual(item_id, at_progress, None, 0.0);
     }
 
+    /// Merge one item into the trailing stack (lowest progress)
+    ///
+    /// Only succeeds for the same item while the stack is not full and still
+    /// within CONVEYOR_STACK_WINDOW of the entry.
+    pub fn try_stack_item(&mut self, item_id: ItemId) -> bool {
+        let Some(trailing) = self.items.iter_mut().min_by(|a, b| {
+            a.progress
+                .partial_cmp(&b.progress)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }) else {
+            return false;
+        };
+        if trailing.item_id != item_id
+            || trailing.count >= CONVEYOR_MAX_STACK
+            || trailing.progress > CONVEYOR_STACK_WINDOW
+        {
+            return false;
+        }
+        trailing.count += 1;
+        true
+    }
+
+    /// Total number of items on the belt (sum of stack counts)
+    pub fn total_item_count(&self) -> u32 {
+        self.items.iter().map(|item| item.count).sum()
+    }
+
     /// Check if conveyor can accept item at entry (progress = 0.0)
     #[allow(dead_code)]
     pub fn can_accept_at_entry(&self) -> bool {
@@ -142,8 +234,13 @@ impl Conveyor {
     }
 
     /// Calculate join info (progress, lateral_offset) for an item coming from a source position.
+    ///
+    /// Sources must be on the same Y level; belts never accept items from above or below.
     pub fn get_join_info(&self, from_pos: IVec3) -> Option<(f32, f32)> {
         let offset = self.position - from_pos;
+        if offset.y != 0 {
+            return None;
+        }
 
         match self.direction {
             Direction::East => {
@@ -200,6 +297,15 @@ impl Conveyor {
         let right = self.position + self.direction.right().to_ivec3();
         [front, left, right]
     }
+
+    /// Whether items leaving this belt go to `pos` (any of a splitter's outputs)
+    pub fn outputs_to(&self, pos: IVec3) -> bool {
+        if self.shape == ConveyorShape::Splitter {
+            self.get_splitter_outputs().contains(&pos)
+        } else {
+            self.position + self.output_direction.to_ivec3() == pos
+        }
+    }
 }
 
 /// Marker for conveyor's visual model child entity (for model swapping)
@@ -210,6 +316,18 @@ pub struct ConveyorVisual;
 #[derive(Component)]
 pub struct ConveyorItemVisual;
 
+/// Stack size a conveyor item visual was built for (extra items are child cubes)
+#[derive(Component)]
+pub struct ConveyorItemStackVisual(pub u32);
+
+/// Conveyor item visual drawn as an icon quad that turns to face the camera
+#[derive(Component)]
+pub struct ConveyorItemBillboard;
+
+/// Tier stripe on an upgraded conveyor, tagged with the speed multiplier it shows
+#[derive(Component)]
+pub struct ConveyorTierVisual(pub f32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,8 +370,10 @@ mod tests {
             output_direction: Direction::East,
             items: Vec::new(),
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
 
         // Adding Mod item should NOT panic
@@ -262,4 +382,98 @@ mod tests {
         // Should preserve the mod item ID
         assert_eq!(conveyor.items[0].get_item_id(), mod_item_id);
     }
+
+    #[test]
+    fn test_try_stack_item() {
+        let mut conveyor = Conveyor {
+            position: IVec3::ZERO,
+            direction: Direction::East,
+            output_direction: Direction::East,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        };
+
+        // Nothing to stack onto
+        assert!(!conveyor.try_stack_item(items::iron_ore()));
+
+        conveyor.add_item(items::iron_ore(), 0.0);
+        for _ in 1..CONVEYOR_MAX_STACK {
+            assert!(conveyor.try_stack_item(items::iron_ore()));
+        }
+        // Full stack
+        assert!(!conveyor.try_stack_item(items::iron_ore()));
+        assert_eq!(conveyor.items.len(), 1);
+        assert_eq!(conveyor.total_item_count(), CONVEYOR_MAX_STACK);
+
+        // Different item or past the window doesn't merge
+        conveyor.items[0].count = 1;
+        assert!(!conveyor.try_stack_item(items::coal()));
+        conveyor.items[0].progress = CONVEYOR_STACK_WINDOW + 0.1;
+        assert!(!conveyor.try_stack_item(items::iron_ore()));
+    }
+
+    #[test]
+    fn test_join_info_requires_same_y() {
+        let conveyor = Conveyor {
+            position: IVec3::new(0, 9, 0),
+            direction: Direction::East,
+            output_direction: Direction::East,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        };
+
+        assert_eq!(
+            conveyor.get_join_info(IVec3::new(-1, 9, 0)),
+            Some((0.0, 0.0))
+        );
+        assert_eq!(conveyor.get_join_info(IVec3::new(-1, 8, 0)), None);
+        assert_eq!(conveyor.get_join_info(IVec3::new(0, 8, 1)), None);
+    }
+
+    #[test]
+    fn test_upgrade_tier_keeps_items_in_transit() {
+        let mut conveyor = Conveyor {
+            position: IVec3::ZERO,
+            direction: Direction::East,
+            output_direction: Direction::East,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        };
+        conveyor.add_item(items::iron_ore(), 0.2);
+        conveyor.add_item(items::coal(), 0.7);
+
+        // Same tier is not an upgrade
+        assert_eq!(conveyor.upgrade_tier(items::conveyor_block()), None);
+        assert_eq!(
+            conveyor.upgrade_tier(items::conveyor_block_mk2()),
+            Some(items::conveyor_block())
+        );
+        assert_eq!(conveyor.speed_multiplier, CONVEYOR_MK2_SPEED_MULTIPLIER);
+        assert_eq!(conveyor.tier_item(), items::conveyor_block_mk2());
+
+        // Skipping a tier returns the tier actually replaced; no downgrades
+        assert_eq!(
+            conveyor.upgrade_tier(items::conveyor_block_mk3()),
+            Some(items::conveyor_block_mk2())
+        );
+        assert_eq!(conveyor.upgrade_tier(items::conveyor_block_mk2()), None);
+        assert_eq!(conveyor.upgrade_tier(items::iron_ore()), None);
+        assert_eq!(conveyor.tier_item(), items::conveyor_block_mk3());
+
+        let progress: Vec<f32> = conveyor.items.iter().map(|i| i.progress).collect();
+        assert_eq!(progress, vec![0.2, 0.7]);
+        assert_eq!(conveyor.items[0].get_item_id(), items::iron_ore());
+    }
 }
@@ -57,6 +57,16 @@ impl Direction {
         self.rotate_cw()
     }
 
+    /// Display name for UI (compass direction)
+    pub fn label(self) -> &'static str {
+        match self {
+            Direction::North => "北",
+            Direction::South => "南",
+            Direction::East => "東",
+            Direction::West => "西",
+        }
+    }
+
     /// Get the opposite direction
     pub fn opposite(self) -> Self {
         match self {
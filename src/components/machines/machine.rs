@@ -2,9 +2,13 @@
 
 use bevy::prelude::*;
 
-use crate::core::ItemId;
-use crate::game_spec::{find_recipe, MachineSpec, MachineType, UiSlotType};
+use crate::core::{items, ItemId};
+use crate::game_spec::drill_spec::{DrillTier, DRILL_DURABILITY};
+use crate::game_spec::{
+    find_recipe, find_recycle_recipe, MachineSpec, MachineType, PortSide, ProcessType, UiSlotType,
+};
 
+use super::activity::MachineActivity;
 use super::Direction;
 
 // =============================================================================
@@ -20,6 +24,8 @@ pub struct MachineBundle {
     pub visibility: Visibility,
     pub inherited_visibility: InheritedVisibility,
     pub view_visibility: ViewVisibility,
+    /// Utilization history for the machine UI (not saved)
+    pub activity: MachineActivity,
 }
 
 impl MachineBundle {
@@ -39,6 +45,7 @@ impl MachineBundle {
             visibility: Visibility::Inherited,
             inherited_visibility: InheritedVisibility::default(),
             view_visibility: ViewVisibility::default(),
+            activity: MachineActivity::default(),
         }
     }
 
@@ -53,6 +60,7 @@ impl MachineBundle {
             visibility: Visibility::Inherited,
             inherited_visibility: InheritedVisibility::default(),
             view_visibility: ViewVisibility::default(),
+            activity: MachineActivity::default(),
         }
     }
 }
@@ -118,6 +126,44 @@ impl MachineSlot {
     }
 }
 
+/// Drill head installed in a miner, with the wear it has left
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrillHead {
+    pub item_id: ItemId,
+    /// Mining operations left before it breaks
+    pub durability: u32,
+}
+
+impl DrillHead {
+    /// A fresh drill head (None if the item isn't a drill)
+    pub fn new(item_id: ItemId) -> Option<Self> {
+        DrillTier::of(item_id)?;
+        Some(Self {
+            item_id,
+            durability: DRILL_DURABILITY,
+        })
+    }
+
+    pub fn tier(&self) -> Option<DrillTier> {
+        DrillTier::of(self.item_id)
+    }
+
+    pub fn can_mine(&self, item_id: ItemId) -> bool {
+        self.tier().is_some_and(|tier| tier.can_mine(item_id))
+    }
+
+    /// Durability left as a fraction (for the UI bar)
+    pub fn remaining(&self) -> f32 {
+        self.durability as f32 / DRILL_DURABILITY as f32
+    }
+
+    /// Use up one operation; returns true when the drill breaks
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+}
+
 /// Generic machine slots container
 #[derive(Clone, Debug)]
 pub struct MachineSlots {
@@ -127,6 +173,8 @@ pub struct MachineSlots {
     pub outputs: Vec<MachineSlot>,
     /// Fuel count (for machines that require fuel)
     pub fuel: u32,
+    /// Installed drill head (miner)
+    pub drill: Option<DrillHead>,
 }
 
 impl Default for MachineSlots {
@@ -135,6 +183,7 @@ impl Default for MachineSlots {
             inputs: vec![MachineSlot::empty()],
             outputs: vec![MachineSlot::empty()],
             fuel: 0,
+            drill: None,
         }
     }
 }
@@ -149,7 +198,7 @@ impl MachineSlots {
             match slot_def.slot_type {
                 UiSlotType::Input => max_input_id = max_input_id.max(slot_def.slot_id + 1),
                 UiSlotType::Output => max_output_id = max_output_id.max(slot_def.slot_id + 1),
-                UiSlotType::Fuel => {} // Fuel is separate
+                UiSlotType::Fuel | UiSlotType::Drill => {} // Stored separately
             }
         }
 
@@ -157,6 +206,7 @@ impl MachineSlots {
             inputs: vec![MachineSlot::empty(); max_input_id as usize],
             outputs: vec![MachineSlot::empty(); max_output_id as usize],
             fuel: 0,
+            drill: None,
         }
     }
 
@@ -181,6 +231,160 @@ impl MachineSlots {
     }
 }
 
+/// Why a machine is (not) running, set by the machine tick each simulation tick
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MachineStatus {
+    #[default]
+    Working,
+    /// Needs fuel and has none
+    NoFuel,
+    /// Input slot empty or not enough for one craft
+    NoInput,
+    /// Output slot can't take the next result
+    OutputFull,
+    /// Not enough power (no machine uses power yet)
+    NoPower,
+    /// Turned off by the player
+    Disabled,
+    /// Nothing to work on: unmineable ground, or an input without a recipe
+    NoValidTarget,
+    /// Recipe needs fluid and no adjacent pipe/tank holds enough
+    NoFluid,
+}
+
+impl MachineStatus {
+    pub fn is_working(self) -> bool {
+        self == MachineStatus::Working
+    }
+
+    /// Label for machine UIs
+    pub fn label(self) -> &'static str {
+        match self {
+            MachineStatus::Working => "稼働中",
+            MachineStatus::NoFuel => "燃料切れ",
+            MachineStatus::NoInput => "入力待ち",
+            MachineStatus::OutputFull => "出力満杯",
+            MachineStatus::NoPower => "電力不足",
+            MachineStatus::Disabled => "停止中",
+            MachineStatus::NoValidTarget => "対象なし",
+            MachineStatus::NoFluid => "液体不足",
+        }
+    }
+
+    /// Indicator color above stalled machines
+    pub fn color(self) -> Color {
+        match self {
+            MachineStatus::Working => Color::srgb(0.3, 0.9, 0.3),
+            MachineStatus::NoFuel => Color::srgb(1.0, 0.5, 0.1),
+            MachineStatus::NoInput => Color::srgb(1.0, 0.9, 0.2),
+            MachineStatus::OutputFull => Color::srgb(0.95, 0.2, 0.2),
+            MachineStatus::NoPower => Color::srgb(0.6, 0.3, 1.0),
+            MachineStatus::Disabled => Color::srgb(0.5, 0.5, 0.5),
+            MachineStatus::NoValidTarget => Color::srgb(1.0, 0.3, 0.8),
+            MachineStatus::NoFluid => Color::srgb(0.2, 0.6, 1.0),
+        }
+    }
+}
+
+/// Slot a belt at one machine face inserts into
+///
+/// Lets a furnace face be dedicated to fuel or to smelting input, so a line
+/// carrying an item that could go either way fills the slot the player wants.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SlotRoute {
+    /// The port layout and the item decide (ore at the back, fuel at the sides)
+    #[default]
+    Auto,
+    Input,
+    Fuel,
+}
+
+impl SlotRoute {
+    /// Auto -> Input -> Fuel -> Auto
+    pub fn next(self) -> Self {
+        match self {
+            SlotRoute::Auto => SlotRoute::Input,
+            SlotRoute::Input => SlotRoute::Fuel,
+            SlotRoute::Fuel => SlotRoute::Auto,
+        }
+    }
+
+    /// Label for machine UIs
+    pub fn label(self) -> &'static str {
+        match self {
+            SlotRoute::Auto => "自動",
+            SlotRoute::Input => "入力",
+            SlotRoute::Fuel => "燃料",
+        }
+    }
+
+    /// Color of the dot marking a routed face
+    pub fn color(self) -> Color {
+        match self {
+            SlotRoute::Auto => Color::WHITE,
+            SlotRoute::Input => Color::srgb(0.3, 0.6, 1.0),
+            SlotRoute::Fuel => Color::srgb(1.0, 0.5, 0.1),
+        }
+    }
+}
+
+/// Slot a neighbor feeds through a machine face, before the item is known
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotTarget {
+    /// The face takes nothing
+    None,
+    /// Input slot
+    Input,
+    /// Furnace fuel slot (fuel items only)
+    Fuel,
+    /// From above (any face on fuel-burning data machines): fuel goes to the
+    /// fuel slot, anything else to the input
+    ByItem,
+}
+
+/// Offset from a machine to the neighbor on a port side
+fn port_offset(facing: Direction, side: PortSide) -> IVec3 {
+    match side {
+        PortSide::Front => facing.to_ivec3(),
+        PortSide::Back => facing.opposite().to_ivec3(),
+        PortSide::Left => facing.left().to_ivec3(),
+        PortSide::Right => facing.right().to_ivec3(),
+        PortSide::Top => IVec3::Y,
+        PortSide::Bottom => IVec3::NEG_Y,
+    }
+}
+
+/// Slot a neighbor at `offset` from a machine (facing `facing`) feeds
+///
+/// The face rules behind `Machine::insert_from`, shared with the placement
+/// preview's belt hints so the two can't disagree. Besides the spec's input
+/// ports every belt-fed machine takes items from directly above (a hopper on
+/// top); the furnace takes ore at the back and fuel at the sides. Data-defined
+/// machines with a fuel slot sort by item on every face. Slot routes are
+/// applied on top by `insert_from`.
+pub fn machine_accepts_from(spec: &MachineSpec, facing: Direction, offset: IVec3) -> SlotTarget {
+    let from_above = offset == IVec3::Y;
+    let on_input_port = spec
+        .ports
+        .iter()
+        .any(|port| port.is_input && port_offset(facing, port.side) == offset);
+    if !from_above && !on_input_port {
+        return SlotTarget::None;
+    }
+    match spec.process_type {
+        ProcessType::Recipe(MachineType::Furnace) if from_above => SlotTarget::ByItem,
+        ProcessType::Recipe(MachineType::Furnace) if offset == facing.opposite().to_ivec3() => {
+            SlotTarget::Input
+        }
+        ProcessType::Recipe(MachineType::Furnace) => SlotTarget::Fuel,
+        ProcessType::Recipe(MachineType::Custom(_)) if spec.requires_fuel => SlotTarget::ByItem,
+        ProcessType::Recipe(MachineType::Crusher | MachineType::Custom(_))
+        | ProcessType::Recycle
+        | ProcessType::Hopper => SlotTarget::Input,
+        _ => SlotTarget::None,
+    }
+}
+
 /// Generic machine component - data-driven machine
 #[derive(Component, Clone, Debug)]
 pub struct Machine {
@@ -196,8 +400,64 @@ pub struct Machine {
     pub slots: MachineSlots,
     /// Tick counter (for timing/randomization)
     pub tick_count: u32,
+    /// Whether the machine is running (disabled machines freeze progress)
+    pub enabled: bool,
+    /// Fall back to the other sides when the facing side can't take output
+    pub output_any_side: bool,
+    /// World-space faces marked "pass-through": belts at these faces never insert
+    pub pass_through_sides: Vec<Direction>,
+    /// World-space faces whose belts insert into one slot only (Auto faces are not listed)
+    pub slot_routes: Vec<(Direction, SlotRoute)>,
+    /// Next side to try for round-robin output (recycler)
+    pub output_cursor: usize,
+    /// Result of the last tick (not saved, recomputed on the next tick)
+    pub status: MachineStatus,
+    /// Hopper: horizontal side to push items toward (None = down)
+    pub push_side: Option<Direction>,
 }
 
+/// Marker for the small notch child mesh showing a machine's output face
+#[derive(Component)]
+pub struct MachineOutputNotch;
+
+/// Floating quad above a stalled machine, colored by its status
+///
+/// A separate entity (not a child) so it can face the player without
+/// fighting the machine's rotation; holds the machine it belongs to.
+#[derive(Component)]
+pub struct MachineStatusIndicator(pub Entity);
+
+/// Red stripe child mesh marking a pass-through face
+#[derive(Component)]
+pub struct PassThroughStripe;
+
+/// Pass-through faces the machine's stripes were last built for
+#[derive(Component, Default)]
+pub struct PassThroughStripes(pub Vec<Direction>);
+
+/// Colored dot marking a face routed to one slot (child of the machine)
+#[derive(Component)]
+pub struct SlotRouteDot;
+
+/// Slot routes the machine's dots were last built for
+#[derive(Component, Default)]
+pub struct SlotRouteDots(pub Vec<(Direction, SlotRoute)>);
+
+/// A stalled machine skipped by generic_machine_tick until something changes it
+///
+/// Added and removed by `update_machine_sleep`. Any mutable access to the
+/// `Machine` after `since` (belt or hopper insert, UI click, toggle, drill
+/// swap) wakes it on the next simulation tick.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Idle {
+    /// Change tick (`Tick::get`) the machine went to sleep at
+    pub since: u32,
+}
+
+/// Original material of a disabled machine or conveyor while it shows the dimmed tint
+#[derive(Component)]
+pub struct DisabledTint(pub Handle<StandardMaterial>);
+
 impl Machine {
     /// Create a new machine from spec
     pub fn new(spec: &'static MachineSpec, position: IVec3, facing: Direction) -> Self {
@@ -208,6 +468,13 @@ impl Machine {
             progress: 0.0,
             slots: MachineSlots::from_spec(spec),
             tick_count: 0,
+            enabled: true,
+            output_any_side: false,
+            pass_through_sides: Vec::new(),
+            slot_routes: Vec::new(),
+            output_cursor: 0,
+            status: MachineStatus::Working,
+            push_side: None,
         }
     }
 
@@ -216,6 +483,25 @@ impl Machine {
         self.progress > 0.0
     }
 
+    /// Whether the tick can skip this machine until its slots or settings change
+    ///
+    /// Only stalls nothing but a mutation can end: waiting for input or fuel,
+    /// turned off, or a miner with nothing to mine. Recipe machines without a
+    /// recipe may be waiting on a quest unlock, and buffered output still has to
+    /// go out to belts, so those stay awake.
+    pub fn can_sleep(&self) -> bool {
+        let ticked_by_generic = matches!(
+            self.spec.process_type,
+            ProcessType::AutoGenerate | ProcessType::Recipe(_) | ProcessType::Recycle
+        );
+        let waiting = match self.status {
+            MachineStatus::NoInput | MachineStatus::NoFuel | MachineStatus::Disabled => true,
+            MachineStatus::NoValidTarget => self.spec.process_type == ProcessType::AutoGenerate,
+            _ => false,
+        };
+        ticked_by_generic && waiting && self.slots.outputs.iter().all(|s| s.is_empty())
+    }
+
     /// Get output position (facing direction + 1)
     pub fn output_position(&self) -> IVec3 {
         self.position + self.facing.to_ivec3()
@@ -231,6 +517,240 @@ impl Machine {
         };
         self.position + back.to_ivec3()
     }
+
+    /// Get the world position adjacent to a port side (relative to facing)
+    pub fn port_position(&self, side: PortSide) -> IVec3 {
+        self.position + port_offset(self.facing, side)
+    }
+
+    /// Output positions in priority order: the facing side, then (with
+    /// `output_any_side`) left, right and back
+    pub fn output_positions(&self) -> Vec<IVec3> {
+        let mut positions = vec![self.output_position()];
+        if self.output_any_side {
+            positions.extend(
+                [PortSide::Left, PortSide::Right, PortSide::Back]
+                    .into_iter()
+                    .map(|side| self.port_position(side))
+                    .filter(|&pos| !self.accepts_input_from(pos)),
+            );
+        }
+        positions
+    }
+
+    /// Check if a neighbor at `source` sits on one of the spec's input ports
+    pub fn accepts_input_from(&self, source: IVec3) -> bool {
+        self.spec
+            .ports
+            .iter()
+            .any(|port| port.is_input && self.port_position(port.side) == source)
+    }
+
+    /// Whether the face toward a horizontal neighbor at `source` is marked pass-through
+    pub fn is_pass_through(&self, source: IVec3) -> bool {
+        self.pass_through_sides
+            .iter()
+            .any(|side| self.position + side.to_ivec3() == source)
+    }
+
+    /// Toggle the pass-through marking on a face, returning whether it is now set
+    pub fn toggle_pass_through(&mut self, side: Direction) -> bool {
+        if let Some(idx) = self.pass_through_sides.iter().position(|&s| s == side) {
+            self.pass_through_sides.remove(idx);
+            false
+        } else {
+            self.pass_through_sides.push(side);
+            true
+        }
+    }
+
+    /// World direction of a horizontal port side (None for top/bottom)
+    pub fn port_direction(&self, side: PortSide) -> Option<Direction> {
+        match side {
+            PortSide::Front => Some(self.facing),
+            PortSide::Back => Some(self.facing.opposite()),
+            PortSide::Left => Some(self.facing.left()),
+            PortSide::Right => Some(self.facing.right()),
+            PortSide::Top | PortSide::Bottom => None,
+        }
+    }
+
+    /// Whether the spec has a fuel slot (and so can route faces to it)
+    pub fn has_fuel_slot(&self) -> bool {
+        self.spec
+            .ui_slots
+            .iter()
+            .any(|slot| slot.slot_type == UiSlotType::Fuel)
+    }
+
+    /// Slot the belts at a face insert into
+    pub fn slot_route(&self, side: Direction) -> SlotRoute {
+        self.slot_routes
+            .iter()
+            .find(|(s, _)| *s == side)
+            .map(|&(_, route)| route)
+            .unwrap_or_default()
+    }
+
+    /// Slot a horizontal neighbor at `source` inserts into (Auto for anything else)
+    pub fn slot_route_from(&self, source: IVec3) -> SlotRoute {
+        self.slot_routes
+            .iter()
+            .find(|(side, _)| self.position + side.to_ivec3() == source)
+            .map(|&(_, route)| route)
+            .unwrap_or_default()
+    }
+
+    /// Set the slot a face's belts insert into
+    pub fn set_slot_route(&mut self, side: Direction, route: SlotRoute) {
+        self.slot_routes.retain(|(s, _)| *s != side);
+        if route != SlotRoute::Auto {
+            self.slot_routes.push((side, route));
+        }
+    }
+
+    /// Step a face to the next route, returning the new one
+    pub fn cycle_slot_route(&mut self, side: Direction) -> SlotRoute {
+        let route = self.slot_route(side).next();
+        self.set_slot_route(side, route);
+        route
+    }
+
+    /// Hopper: the block items are pushed into (below, or the configured side)
+    pub fn hopper_target(&self) -> IVec3 {
+        match self.push_side {
+            Some(side) => self.position + side.to_ivec3(),
+            None => self.position + IVec3::NEG_Y,
+        }
+    }
+
+    /// Hopper: next push direction (down, then north, east, south, west)
+    pub fn cycle_push_side(&mut self) {
+        self.push_side = match self.push_side {
+            None => Some(Direction::North),
+            Some(Direction::North) => Some(Direction::East),
+            Some(Direction::East) => Some(Direction::South),
+            Some(Direction::South) => Some(Direction::West),
+            Some(Direction::West) => None,
+        };
+    }
+
+    /// Miner: install a fresh drill head, returning the one it replaces if unused
+    ///
+    /// Inventory stacks have no wear, so a used drill head can't go back into
+    /// the inventory; replacing it scraps it.
+    pub fn install_drill(&mut self, item_id: ItemId) -> Option<ItemId> {
+        let new = DrillHead::new(item_id)?;
+        self.slots
+            .drill
+            .replace(new)
+            .filter(|old| old.durability == DRILL_DURABILITY)
+            .map(|old| old.item_id)
+    }
+
+    /// Miner: take out the drill head if it is unused (see `install_drill`)
+    pub fn take_unused_drill(&mut self) -> Option<ItemId> {
+        let drill = self
+            .slots
+            .drill
+            .filter(|drill| drill.durability == DRILL_DURABILITY)?;
+        self.slots.drill = None;
+        Some(drill.item_id)
+    }
+
+    /// Insert up to `count` items handed over by a neighbor at `source`
+    ///
+    /// The acceptance rules shared by belts and hoppers; returns how many were
+    /// taken. Which faces feed which slot comes from `machine_accepts_from`;
+    /// from above, the item decides. A face routed to one slot (`slot_routes`)
+    /// only ever fills that slot.
+    pub fn insert_from(&mut self, item_id: ItemId, count: u32, source: IVec3) -> u32 {
+        let target = machine_accepts_from(self.spec, self.facing, source - self.position);
+        if target == SlotTarget::None {
+            return 0;
+        }
+        let cap = self.spec.buffer_size;
+        match self.spec.process_type {
+            ProcessType::Recipe(MachineType::Furnace) => {
+                if self.routes_to_fuel(item_id, source, target) {
+                    self.insert_fuel(item_id, count)
+                } else if can_smelt_by_id(item_id) {
+                    fill_slot(self.slots.inputs.first_mut(), item_id, count, cap)
+                } else {
+                    0
+                }
+            }
+            ProcessType::Recipe(MachineType::Crusher) if can_crush_by_id(item_id) => {
+                fill_slot(self.slots.inputs.first_mut(), item_id, count, cap)
+            }
+            ProcessType::Recipe(work_type @ MachineType::Custom(_)) => {
+                if self.spec.requires_fuel && self.routes_to_fuel(item_id, source, target) {
+                    self.insert_fuel(item_id, count)
+                } else if find_recipe(work_type, item_id).is_some() {
+                    fill_slot(self.slots.inputs.first_mut(), item_id, count, cap)
+                } else {
+                    0
+                }
+            }
+            ProcessType::Recycle if can_recycle_by_id(item_id) => {
+                fill_slot(self.slots.inputs.first_mut(), item_id, count, cap)
+            }
+            ProcessType::Hopper => {
+                // Never take back from the side we push into
+                if source == self.hopper_target() {
+                    return 0;
+                }
+                // Top up matching stacks first, then empty slots
+                let mut left = count;
+                for matching in [true, false] {
+                    for slot in self.slots.inputs.iter_mut() {
+                        if left == 0 || slot.contains_id(item_id) != matching {
+                            continue;
+                        }
+                        left -= fill_slot(Some(slot), item_id, left, cap);
+                    }
+                }
+                count - left
+            }
+            _ => 0,
+        }
+    }
+
+    /// Whether an item from `source` goes to the fuel slot (route, then face rule)
+    fn routes_to_fuel(&self, item_id: ItemId, source: IVec3, target: SlotTarget) -> bool {
+        match (self.slot_route_from(source), target) {
+            (SlotRoute::Fuel, _) => true,
+            (SlotRoute::Input, _) => false,
+            (SlotRoute::Auto, SlotTarget::ByItem) => items::is_fuel(item_id),
+            (SlotRoute::Auto, target) => target == SlotTarget::Fuel,
+        }
+    }
+
+    /// Add up to `count` fuel items, returning how many fit
+    fn insert_fuel(&mut self, item_id: ItemId, count: u32) -> u32 {
+        if !items::is_fuel(item_id) {
+            return 0;
+        }
+        let amount = count.min(self.spec.buffer_size.saturating_sub(self.slots.fuel));
+        self.slots.fuel += amount;
+        amount
+    }
+}
+
+/// Add up to `count` of `item_id` to a slot holding nothing or the same item
+fn fill_slot(slot: Option<&mut MachineSlot>, item_id: ItemId, count: u32, cap: u32) -> u32 {
+    let Some(slot) = slot else {
+        return 0;
+    };
+    if !slot.is_empty() && !slot.contains_id(item_id) {
+        return 0;
+    }
+    let amount = count.min(cap.saturating_sub(slot.count));
+    if amount > 0 {
+        slot.item_id = Some(item_id);
+        slot.count += amount;
+    }
+    amount
 }
 
 // =============================================================================
@@ -240,19 +760,29 @@ impl Machine {
 /// Get smelt output by ItemId
 pub fn get_smelt_output_by_id(ore: ItemId) -> Option<ItemId> {
     find_recipe(MachineType::Furnace, ore)
-        .and_then(|recipe| recipe.outputs.first())
+        .and_then(|recipe| recipe.outputs_for(ore).next())
         .map(|output| output.item)
 }
 
+/// Check if item can be smelted by ItemId (includes tag recipe inputs)
+pub fn can_smelt_by_id(ore: ItemId) -> bool {
+    find_recipe(MachineType::Furnace, ore).is_some()
+}
+
 /// Check if item can be crushed by ItemId
 pub fn can_crush_by_id(ore: ItemId) -> bool {
     find_recipe(MachineType::Crusher, ore).is_some()
 }
 
+/// Check if item can be recycled (it is the guaranteed output of a known recipe)
+pub fn can_recycle_by_id(item: ItemId) -> bool {
+    find_recycle_recipe(item).is_some()
+}
+
 /// Get crush output by ItemId
 pub fn get_crush_output_by_id(ore: ItemId) -> Option<(ItemId, u32)> {
     find_recipe(MachineType::Crusher, ore)
-        .and_then(|recipe| recipe.outputs.first())
+        .and_then(|recipe| recipe.outputs_for(ore).next())
         .map(|output| (output.item, output.count))
 }
 
@@ -267,6 +797,40 @@ mod tests {
         Id::<ItemCategory>::from_string("mymod:super_ingot", &mut interner)
     }
 
+    #[test]
+    fn test_output_face_never_accepts_input() {
+        for facing in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            let machine = Machine::new(&crate::game_spec::CRUSHER, IVec3::new(3, 1, -2), facing);
+            assert!(!machine.accepts_input_from(machine.output_position()));
+            assert!(machine.accepts_input_from(machine.input_position()));
+            assert!(
+                machine.accepts_input_from(machine.position + facing.left().to_ivec3()),
+                "side faces are inputs"
+            );
+            // Not adjacent
+            assert!(!machine.accepts_input_from(machine.position + IVec3::Y));
+        }
+    }
+
+    #[test]
+    fn test_pass_through_toggle() {
+        let pos = IVec3::new(0, 8, 0);
+        let mut machine = Machine::new(&crate::game_spec::FURNACE, pos, Direction::East);
+        assert!(!machine.is_pass_through(machine.input_position()));
+
+        assert!(machine.toggle_pass_through(Direction::West));
+        assert!(machine.is_pass_through(machine.input_position()));
+        assert!(!machine.is_pass_through(pos + IVec3::Z));
+
+        assert!(!machine.toggle_pass_through(Direction::West));
+        assert!(machine.pass_through_sides.is_empty());
+    }
+
     #[test]
     fn test_machine_slot_with_base_item_no_panic() {
         let mut slot = MachineSlot::empty();
@@ -1,5 +1,6 @@
 //! Machine components: Miner, Conveyor, Furnace, Crusher
 
+mod activity;
 mod conveyor;
 mod descriptor;
 mod direction;
@@ -10,13 +11,22 @@ mod ports;
 // Re-export Direction (widely used)
 pub use direction::Direction;
 
+// Re-export utilization history types
+pub use activity::{ActivityClock, MachineActivity, ACTIVITY_SAMPLES, ACTIVITY_SAMPLE_SECS};
+
 // Re-export Conveyor types
-pub use conveyor::{Conveyor, ConveyorItem, ConveyorItemVisual, ConveyorShape, ConveyorVisual};
+pub use conveyor::{
+    conveyor_speed_multiplier, conveyor_tier_item, Conveyor, ConveyorItem, ConveyorItemBillboard,
+    ConveyorItemStackVisual, ConveyorItemVisual, ConveyorShape, ConveyorTierVisual, ConveyorVisual,
+};
 
 // Re-export Machine types
 pub use machine::{
-    can_crush_by_id, get_crush_output_by_id, get_smelt_output_by_id, Machine, MachineBundle,
-    MachineSlot, MachineSlots,
+    can_crush_by_id, can_recycle_by_id, can_smelt_by_id, get_crush_output_by_id,
+    get_smelt_output_by_id, machine_accepts_from, DisabledTint, DrillHead, Idle, Machine,
+    MachineBundle, MachineOutputNotch, MachineSlot, MachineSlots, MachineStatus,
+    MachineStatusIndicator, PassThroughStripe, PassThroughStripes, SlotRoute, SlotRouteDot,
+    SlotRouteDots, SlotTarget,
 };
 
 // Re-export MachineModels resource
@@ -132,7 +132,7 @@ impl MachineModels {
             self.furnace.clone()
         } else if item_id == items::crusher_block() {
             self.crusher.clone()
-        } else if item_id == items::conveyor_block() {
+        } else if items::is_conveyor(item_id) {
             self.conveyor_straight.clone()
         } else {
             None
@@ -3,6 +3,7 @@
 //! This module defines all ECS components and resources used in the game.
 //! It is the lowest layer and has no dependencies on other game modules.
 
+mod cursor;
 mod input;
 mod machines;
 mod network;
@@ -10,6 +11,7 @@ mod player;
 mod ui;
 mod ui_state;
 
+pub use cursor::*;
 pub use input::*;
 pub use machines::*;
 pub use network::*;
@@ -55,6 +57,8 @@ pub struct TargetBlock {
     pub break_target: Option<IVec3>,
     /// Position where block would be placed (right click)
     pub place_target: Option<IVec3>,
+    /// Machine or conveyor under the crosshair (when nearer than the terrain)
+    pub machine_target: Option<Entity>,
     /// Entity for break highlight visualization
     pub break_highlight_entity: Option<Entity>,
     /// Entity for place highlight visualization
@@ -74,10 +78,12 @@ pub struct BreakingProgress {
     pub total_time: f32,
     /// Whether breaking a machine (true) or world block (false)
     pub is_machine: bool,
+    /// Set after a machine breaks; nothing breaks again until the button is released
+    pub wait_for_release: bool,
 }
 
 impl BreakingProgress {
-    /// Reset breaking progress
+    /// Reset breaking progress (keeps `wait_for_release`)
     pub fn reset(&mut self) {
         self.target_pos = None;
         self.target_entity = None;
@@ -86,6 +92,12 @@ impl BreakingProgress {
         self.is_machine = false;
     }
 
+    /// Mouse button released: reset and allow breaking again
+    pub fn release(&mut self) {
+        self.reset();
+        self.wait_for_release = false;
+    }
+
     /// Check if currently breaking something
     pub fn is_breaking(&self) -> bool {
         self.target_pos.is_some() || self.target_entity.is_some()
@@ -136,6 +148,54 @@ pub struct ConveyorRotationOffset {
     pub offset: u8,
 }
 
+/// Distance culling for conveyor item visuals
+///
+/// Items stay on their conveyors logically; only the visual entity is dropped.
+#[derive(Resource)]
+pub struct ConveyorItemCulling {
+    /// Conveyors farther than this from the player (blocks) show no item visuals
+    pub radius: f32,
+    /// Item visuals alive after the last update
+    pub active: usize,
+    /// Items without a visual because of distance after the last update
+    pub culled: usize,
+}
+
+impl Default for ConveyorItemCulling {
+    fn default() -> Self {
+        Self {
+            radius: crate::constants::CONVEYOR_ITEM_CULL_RADIUS,
+            active: 0,
+            culled: 0,
+        }
+    }
+}
+
+/// Generic machines skipped by the tick (`Idle`) vs. processed, for the debug HUD
+#[derive(Resource, Default, Debug)]
+pub struct MachineSleepStats {
+    pub active: usize,
+    pub sleeping: usize,
+}
+
+/// Meshes and materials shared by conveyor item visuals
+///
+/// Built lazily, once per item type, instead of once per spawned visual.
+/// Color materials are dropped when the block palette changes.
+#[derive(Resource, Default)]
+pub struct ConveyorVisualAssets {
+    /// Item cube (colored fallback)
+    pub cube: Option<Handle<Mesh>>,
+    /// Quad lying flat on the belt (simple mode)
+    pub flat: Option<Handle<Mesh>>,
+    /// Upright quad for icon billboards
+    pub billboard: Option<Handle<Mesh>>,
+    /// Palette color material per item type
+    pub colors: HashMap<ItemId, Handle<StandardMaterial>>,
+    /// Icon material per item type
+    pub icons: HashMap<ItemId, Handle<StandardMaterial>>,
+}
+
 /// Creative mode resource (CAD-style: always enabled for fly mode)
 #[derive(Resource)]
 pub struct CreativeMode {
@@ -391,6 +451,8 @@ pub struct CurrentQuest {
     pub completed: bool,
     /// Whether rewards were claimed
     pub rewards_claimed: bool,
+    /// When each quest's rewards were claimed: quest id -> Unix milliseconds
+    pub claimed_at: HashMap<String, u64>,
 }
 
 // NOTE: SubQuestState and ActiveSubQuests removed (dead code)
@@ -434,11 +496,24 @@ pub struct QuestDeliverButton;
 pub struct DeliveryPlatform {
     /// Position of the platform origin (bottom-left corner)
     pub position: IVec3,
+    /// Side length in blocks (starter platform: PLATFORM_SIZE, pad platforms: 3)
+    pub size: i32,
 }
 
 impl DeliveryPlatform {
     pub fn new(position: IVec3) -> Self {
-        Self { position }
+        Self::with_size(position, crate::constants::PLATFORM_SIZE)
+    }
+
+    pub fn with_size(position: IVec3, size: i32) -> Self {
+        Self { position, size }
+    }
+
+    /// Check if a grid position lies on the platform surface
+    pub fn contains(&self, pos: IVec3) -> bool {
+        pos.y == self.position.y
+            && (self.position.x..self.position.x + self.size).contains(&pos.x)
+            && (self.position.z..self.position.z + self.size).contains(&pos.z)
     }
 }
 
@@ -469,8 +544,16 @@ static CREATIVE_ITEMS_VEC: std::sync::LazyLock<Vec<(ItemId, &'static str)>> =
             // Machines
             (items::miner_block(), "Machines"),
             (items::conveyor_block(), "Machines"),
+            (items::conveyor_block_mk2(), "Machines"),
+            (items::conveyor_block_mk3(), "Machines"),
             (items::crusher_block(), "Machines"),
             (items::furnace_block(), "Machines"),
+            (items::recycler_block(), "Machines"),
+            (items::hopper_block(), "Machines"),
+            (items::delivery_pad(), "Machines"),
+            (items::display_panel(), "Machines"),
+            (items::lamp_block(), "Machines"),
+            (items::sign_block(), "Machines"),
         ]
     });
 
@@ -33,35 +33,19 @@ impl Default for PlayerPhysics {
     }
 }
 
-/// Tracks cursor lock state and handles mouse input for both local and RDP environments
-#[derive(Resource)]
+/// Mouse look bookkeeping for both local and RDP environments
+///
+/// Who owns the cursor (gameplay, a screen, pause, focus loss) lives in `CursorController`.
+#[derive(Resource, Default)]
 pub struct CursorLockState {
     pub was_locked: bool,
     pub skip_frames: u8,
     /// Last mouse position for calculating delta in RDP/absolute mode
     pub last_mouse_pos: Option<Vec2>,
-    /// Skip next block break (used when resuming from pointer lock release)
-    pub just_locked: bool,
-    /// Game is paused (ESC pressed, waiting for click to resume)
-    pub paused: bool,
     /// Skip inventory toggle this frame (used when closing machine UI with E key)
     pub skip_inventory_toggle: bool,
 }
 
-impl Default for CursorLockState {
-    fn default() -> Self {
-        Self {
-            was_locked: false,
-            skip_frames: 0,
-            last_mouse_pos: None,
-            just_locked: false,
-            // 起動時はカーソル解放状態（ポーズメニュー表示中）
-            paused: true,
-            skip_inventory_toggle: false,
-        }
-    }
-}
-
 /// Timer for continuous block break/place operations
 #[derive(Resource)]
 pub struct ContinuousActionTimer {
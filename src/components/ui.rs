@@ -1,6 +1,7 @@
 //! UI-related components and resources
 
 use crate::core::ItemId;
+use crate::game_spec::PortSide;
 use bevy::prelude::*;
 
 // === Inventory UI ===
@@ -29,6 +30,57 @@ pub struct InventorySlotImage(pub usize);
 #[derive(Component)]
 pub struct TrashSlot;
 
+/// Marker for the dimmed sprite of the stack waiting in the trash slot
+#[derive(Component)]
+pub struct TrashPendingImage;
+
+/// Marker for the trash slot label ("X", or the count of the waiting stack)
+#[derive(Component)]
+pub struct TrashSlotText;
+
+/// Inventory button that sorts the main inventory
+#[derive(Component)]
+pub struct InventorySortButton;
+
+/// Inventory button that stacks main inventory items into nearby machines
+#[derive(Component)]
+pub struct InventoryQuickStackButton;
+
+/// Seconds a trashed stack waits for a confirming click before it is returned
+pub const TRASH_CONFIRM_SECS: f32 = 3.0;
+
+/// Stack put into the trash slot, waiting for confirmation
+///
+/// Only one stack waits at a time: trashing another one confirms it.
+#[derive(Resource, Debug, Default)]
+pub struct PendingTrash {
+    pub stack: Option<(ItemId, u32)>,
+    /// Seconds left in the confirmation window
+    pub remaining: f32,
+}
+
+impl PendingTrash {
+    /// Put a stack in the trash; returns the previously waiting stack, which is now deleted
+    pub fn stage(&mut self, stack: (ItemId, u32)) -> Option<(ItemId, u32)> {
+        self.remaining = TRASH_CONFIRM_SECS;
+        self.stack.replace(stack)
+    }
+
+    /// Take the waiting stack (to delete or to give back)
+    pub fn take(&mut self) -> Option<(ItemId, u32)> {
+        self.stack.take()
+    }
+
+    /// Count down; true once the confirmation window has passed
+    pub fn tick(&mut self, delta: f32) -> bool {
+        if self.stack.is_none() {
+            return false;
+        }
+        self.remaining -= delta;
+        self.remaining <= 0.0
+    }
+}
+
 /// Currently held item for drag and drop
 #[derive(Resource, Default)]
 pub struct HeldItem(pub Option<(ItemId, u32)>);
@@ -61,6 +113,30 @@ pub struct CreativePanel;
 #[derive(Component)]
 pub struct InventoryTooltip;
 
+/// Marker for the radial quick-select ring container
+#[derive(Component)]
+pub struct QuickSelectRing;
+
+/// Quick-select ring segment (index, 0 = top, clockwise)
+#[derive(Component)]
+pub struct QuickSelectSegment(pub usize);
+
+/// Item sprite of a quick-select segment
+#[derive(Component)]
+pub struct QuickSelectSegmentImage(pub usize);
+
+/// Item count of a quick-select segment
+#[derive(Component)]
+pub struct QuickSelectSegmentText(pub usize);
+
+/// Marker for the machine status card (shown when the crosshair rests on a machine)
+#[derive(Component)]
+pub struct MachineHoverCard;
+
+/// Marker for the compass arrow pointing to the delivery platform
+#[derive(Component)]
+pub struct PlatformCompass;
+
 // === Hotbar UI ===
 
 /// Marker for hotbar UI container
@@ -75,6 +151,10 @@ pub struct HotbarSlot(pub usize);
 #[derive(Component)]
 pub struct HotbarSlotCount(pub usize);
 
+/// Marker for the hotbar slot cooldown overlay (height = remaining cooldown)
+#[derive(Component)]
+pub struct HotbarSlotCooldown(pub usize);
+
 /// Marker for the hotbar item name display (shown above hotbar)
 #[derive(Component)]
 pub struct HotbarItemNameText;
@@ -121,6 +201,80 @@ pub struct GenericMachineProgressBar;
 #[derive(Component)]
 pub struct GenericMachineHeaderText;
 
+/// Generic machine UI facing (output face) text
+#[derive(Component)]
+pub struct GenericMachineFacingText;
+
+/// Generic machine UI status text (why the machine is stalled)
+#[derive(Component)]
+pub struct GenericMachineStatusText;
+
+/// Generic machine UI utilization sparkline bar (0 = oldest sample slot)
+#[derive(Component)]
+pub struct GenericMachineActivityBar(pub usize);
+
+/// Generic machine UI utilization label ("稼働率: 73% (4分)")
+#[derive(Component)]
+pub struct GenericMachineActivityText;
+
+/// Generic machine UI enable/disable toggle button
+#[derive(Component)]
+pub struct GenericMachineEnableButton;
+
+/// Generic machine UI enable/disable toggle label
+#[derive(Component)]
+pub struct GenericMachineEnableText;
+
+/// Generic machine UI "output to any side" toggle button
+#[derive(Component)]
+pub struct GenericMachineOutputSideButton;
+
+/// Generic machine UI "output to any side" toggle label
+#[derive(Component)]
+pub struct GenericMachineOutputSideText;
+
+/// Generic machine UI button cycling the slot an input face feeds (fuel machines)
+#[derive(Component)]
+pub struct GenericMachineSlotRouteButton(pub PortSide);
+
+/// Generic machine UI slot route label
+#[derive(Component)]
+pub struct GenericMachineSlotRouteText(pub PortSide);
+
+/// Generic machine UI drill head slot button (miner)
+#[derive(Component)]
+pub struct GenericMachineDrillButton;
+
+/// Generic machine UI drill head slot label
+#[derive(Component)]
+pub struct GenericMachineDrillText;
+
+/// Generic machine UI drill durability bar fill
+#[derive(Component)]
+pub struct GenericMachineDrillBar;
+
+/// Generic machine UI slot view (hidden while the recipe browser is open)
+#[derive(Component)]
+pub struct GenericMachineSlotView;
+
+/// Generic machine UI recipe browser (recipe machines, hidden by default)
+#[derive(Component)]
+pub struct GenericMachineRecipeView;
+
+/// Generic machine UI button opening the recipe browser
+#[derive(Component)]
+pub struct GenericMachineRecipesButton;
+
+/// Generic machine UI button returning from the recipe browser to the slots
+#[derive(Component)]
+pub struct GenericMachineRecipeBackButton;
+
+/// Generic machine UI recipe list, filled each time the browser opens
+#[derive(Component)]
+pub struct GenericMachineRecipeList {
+    pub machine_id: &'static str,
+}
+
 // === Command UI ===
 
 /// Command input UI state
@@ -134,6 +288,8 @@ pub struct CommandInputState {
     pub skip_input_frame: bool,
     /// Currently selected suggestion index
     pub suggestion_index: usize,
+    /// Sign being edited (the text box edits its text instead of a command)
+    pub sign: Option<Entity>,
 }
 
 /// Available command suggestions
@@ -149,6 +305,23 @@ pub const COMMAND_SUGGESTIONS: &[&str] = &[
     "/setblock",
     "/spawn_machine",
     "/screenshot",
+    "/time",
+    "/skip-night",
+    "/tickrate",
+    "/camera",
+    "/spawn",
+    "/setspawn",
+    "/protection",
+    "/worldgen",
+    "/recipes",
+    "/stalled",
+    "/trace",
+    "/waypoint",
+    "/log",
+    "/export",
+    "/import",
+    "/debug",
+    "/devlink",
 ];
 
 /// Marker for command suggestions UI
@@ -167,6 +340,12 @@ pub struct CommandInputUI;
 #[derive(Component)]
 pub struct CommandInputText;
 
+// === Crafting Tab (Integrated in Inventory UI) ===
+
+/// Whether the inventory crafting tab is expanded
+#[derive(Resource, Default)]
+pub struct CraftingTabOpen(pub bool);
+
 // === Upper Panel (Integrated in Inventory UI) ===
 
 /// Global inventory page state (used for upper panel pagination)
@@ -214,9 +393,12 @@ impl ItemCategory {
             }
             ItemCategory::Machines => {
                 item_id == items::miner_block()
-                    || item_id == items::conveyor_block()
+                    || items::is_conveyor(item_id)
                     || item_id == items::furnace_block()
                     || item_id == items::crusher_block()
+                    || item_id == items::recycler_block()
+                    || item_id == items::hopper_block()
+                    || (items::is_runtime(item_id) && item_id.is_machine())
             }
         }
     }
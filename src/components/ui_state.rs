@@ -18,6 +18,12 @@ pub enum UIContext {
     PauseMenu,
     /// 設定画面
     Settings,
+    /// 統計・実績画面
+    Stats,
+    /// クエストログ (J key or pause menu)
+    QuestLog,
+    /// 生産目標（統計画面から）
+    Goals,
     /// マシンUI（汎用化、Entityで特定）
     Machine(Entity),
 }
@@ -122,6 +128,9 @@ impl UIState {
                 UIContext::CommandInput => "Command".to_string(),
                 UIContext::PauseMenu => "PauseMenu".to_string(),
                 UIContext::Settings => "Settings".to_string(),
+                UIContext::Stats => "Stats".to_string(),
+                UIContext::QuestLog => "QuestLog".to_string(),
+                UIContext::Goals => "Goals".to_string(),
                 UIContext::Machine(_) => "MachineUI".to_string(),
             })
             .collect()
@@ -5,6 +5,10 @@ pub const CHUNK_SIZE: i32 = 16;
 pub const CHUNK_HEIGHT: i32 = 32;
 pub const GROUND_LEVEL: i32 = 7; // Y coordinate of ground surface
 
+/// Chunk meshes are split into sections of this many layers, rebuilt independently
+pub const CHUNK_SECTION_HEIGHT: i32 = 16;
+pub const CHUNK_SECTIONS: i32 = CHUNK_HEIGHT / CHUNK_SECTION_HEIGHT;
+
 /// Block size in world units
 pub const BLOCK_SIZE: f32 = 1.0;
 
@@ -28,8 +32,15 @@ pub const VIEW_DISTANCE: i32 = 3;
 pub const MOUSE_SENSITIVITY: f32 = 0.002;
 pub const KEY_ROTATION_SPEED: f32 = 2.0;
 
+/// Simulation tick rate (FixedUpdate ticks/second at 1x speed)
+pub const SIMULATION_HZ: f64 = 20.0;
+/// Simulated seconds per tick (machines and conveyors advance by this much each tick)
+pub const SIMULATION_TICK_SECS: f32 = (1.0 / SIMULATION_HZ) as f32;
+
 /// Conveyor speed (synced with game_spec/machines.rs CONVEYOR.process_time)
 pub const CONVEYOR_SPEED: f32 = 2.0; // Conveyor blocks/second
+pub const CONVEYOR_MK2_SPEED_MULTIPLIER: f32 = 2.0; // Mk2 belts run at 2x CONVEYOR_SPEED
+pub const CONVEYOR_MK3_SPEED_MULTIPLIER: f32 = 4.0; // Mk3 belts run at 4x CONVEYOR_SPEED
 
 /// Conveyor settings
 pub const CONVEYOR_MAX_ITEMS: usize = 3; // Maximum items per conveyor
@@ -37,6 +48,11 @@ pub const CONVEYOR_ITEM_SPACING: f32 = 0.4; // Minimum spacing between items (0.
 pub const CONVEYOR_ITEM_SIZE: f32 = 0.25; // Item visual size (fraction of BLOCK_SIZE)
 pub const CONVEYOR_BELT_WIDTH: f32 = 0.8; // Belt width (fraction of BLOCK_SIZE, 8/10)
 pub const CONVEYOR_BELT_HEIGHT: f32 = 0.5; // Belt height (fraction of BLOCK_SIZE) - half block
+pub const CONVEYOR_ITEM_CULL_RADIUS: f32 = 48.0; // Items farther than this (blocks) have no visual
+pub const CONVEYOR_MAX_STACK: u32 = 4; // Maximum identical items carried as one belt item
+pub const CONVEYOR_STACK_WINDOW: f32 = 0.2; // Machine output merges into a stack below this progress
+pub const CONVEYOR_STACK_OFFSET: f32 = 0.1; // Vertical offset per extra stacked item (fraction of BLOCK_SIZE)
+pub const CONVEYOR_ITEM_BOB: f32 = 0.02; // Peak height items bob over the rollers (fraction of BLOCK_SIZE)
 
 /// Delivery platform
 pub const PLATFORM_SIZE: i32 = 8;
@@ -37,6 +37,18 @@ impl BlockCategory {
         }
     }
 
+    /// Parse the category name used in data packs (e.g. "machine")
+    pub fn from_data_name(name: &str) -> Option<Self> {
+        match name {
+            "terrain" => Some(BlockCategory::Terrain),
+            "ore" => Some(BlockCategory::Ore),
+            "machine" => Some(BlockCategory::Machine),
+            "processed" => Some(BlockCategory::Processed),
+            "tool" => Some(BlockCategory::Tool),
+            _ => None,
+        }
+    }
+
     /// Check if items in this category can be placed in the world
     pub fn is_placeable(&self) -> bool {
         matches!(
@@ -73,6 +85,19 @@ mod tests {
         assert!(!BlockCategory::Tool.is_material());
     }
 
+    #[test]
+    fn test_category_from_data_name() {
+        assert_eq!(
+            BlockCategory::from_data_name("machine"),
+            Some(BlockCategory::Machine)
+        );
+        assert_eq!(
+            BlockCategory::from_data_name("terrain"),
+            Some(BlockCategory::Terrain)
+        );
+        assert_eq!(BlockCategory::from_data_name(""), None);
+    }
+
     #[test]
     fn test_category_names() {
         assert_eq!(BlockCategory::Terrain.name(), "地形");
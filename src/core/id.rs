@@ -243,9 +243,9 @@ impl AsRef<ItemId> for ValidItemId {
 pub const BASE_NAMESPACE: &str = "base";
 
 impl ItemId {
-    /// Get the string ID using the global interner
+    /// Get the string ID (base items and items interned from data packs)
     pub fn name(&self) -> Option<&'static str> {
-        self.to_string_id(items::interner())
+        items::string_id(*self)
     }
 
     /// Get the color for this item (convenience method for UI)
@@ -258,40 +258,16 @@ impl ItemId {
             .unwrap_or_else(|| bevy::prelude::Color::srgb(0.5, 0.5, 0.5))
     }
 
-    /// Get the texture atlas index for this block type.
-    ///
-    /// Returns an index into the block texture atlas (8x8 grid).
-    /// Layout:
-    /// - 0: stone, 1: grass_top, 2: grass_side, 3: iron_ore
-    /// - 4: copper_ore, 5: coal, 6: dirt, 7: sand
-    pub fn texture_index(&self) -> u32 {
-        let interner = items::interner();
-        match self.local_name(interner) {
-            Some("stone") => 0,
-            Some("grass") => 1, // Top face uses grass_top
-            Some("iron_ore") => 3,
-            Some("copper_ore") => 4,
-            Some("coal") => 5,
-            Some("dirt") => 6,
-            Some("sand") => 7,
-            _ => 0, // Default: stone texture
-        }
-    }
-
-    /// Get the texture atlas index for a specific face direction.
+    /// Get the block texture array layer for a face.
     ///
     /// Grass blocks use different textures for top vs sides.
     /// `is_top_face`: true for +Y faces, false for other faces.
+    /// See [`crate::graphics::BlockTextureLayout`] for the layer order.
     pub fn texture_index_for_face(&self, is_top_face: bool) -> u32 {
-        let interner = items::interner();
-        if self.local_name(interner) == Some("grass") {
-            if is_top_face {
-                1 // grass_top
-            } else {
-                2 // grass_side
-            }
-        } else {
-            self.texture_index()
+        let layout = crate::graphics::BlockTextureLayout::base();
+        match self.local_name(items::interner()) {
+            Some(name) => layout.face_layer(name, is_top_face),
+            None => 0,
         }
     }
 
@@ -411,13 +387,19 @@ pub type SharedInterner = Arc<RwLock<StringInterner>>;
 
 /// Pre-defined ItemId values for base game items.
 /// These are lazily initialized on first access.
+///
+/// Items from data packs (mod items) are added with `intern` at startup and
+/// get the IDs after the base items.
 pub mod items {
     use super::*;
-    use std::sync::OnceLock;
+    use std::sync::{OnceLock, PoisonError};
 
     // Global interner for static item IDs
     static INTERNER: OnceLock<StringInterner> = OnceLock::new();
 
+    // String IDs of interned data pack items (ID = base item count + index)
+    static RUNTIME: RwLock<Vec<&'static str>> = RwLock::new(Vec::new());
+
     fn get_interner() -> &'static StringInterner {
         INTERNER.get_or_init(|| {
             let mut interner = StringInterner::new();
@@ -436,6 +418,7 @@ pub mod items {
         "iron_ore",
         "copper_ore",
         "coal",
+        "crystal",
         "iron_ingot",
         "copper_ingot",
         "iron_dust",
@@ -446,14 +429,89 @@ pub mod items {
         "crusher_block",
         "assembler_block",
         "platform_block",
+        "pipe_block",
+        "tank_block",
+        "delivery_pad",
+        "display_panel",
+        "conveyor_block_mk2",
+        "conveyor_block_mk3",
+        "recycler_block",
+        "lamp_block",
+        "hopper_block",
+        "sign_block",
         "stone_pickaxe",
+        "wrench",
+        "ore_scanner",
+        "stone_drill",
+        "iron_drill",
+        "steel_drill",
     ];
 
     /// Get an ItemId by its base name (e.g., "stone", "iron_ore")
     pub fn by_name(name: &str) -> Option<ItemId> {
+        by_string_id(&format!("{}:{}", BASE_NAMESPACE, name))
+    }
+
+    /// Get an ItemId by its full string ID (e.g., "base:stone", "mymod:gear")
+    pub fn by_string_id(string_id: &str) -> Option<ItemId> {
         let interner = get_interner();
-        let full_id = format!("{}:{}", BASE_NAMESPACE, name);
-        interner.get(&full_id).map(Id::new)
+        if let Some(raw) = interner.get(string_id) {
+            return Some(Id::new(raw));
+        }
+        let runtime = RUNTIME.read().unwrap_or_else(PoisonError::into_inner);
+        runtime
+            .iter()
+            .position(|s| *s == string_id)
+            .map(|index| Id::new((interner.len() + index) as u32))
+    }
+
+    /// Get the ItemId for a data pack item, interning it on first use
+    ///
+    /// Base items keep their IDs. The string is leaked (items are only added
+    /// while loading content) so `ItemId::name` can return it.
+    pub fn intern(string_id: &str) -> ItemId {
+        if let Some(id) = by_string_id(string_id) {
+            return id;
+        }
+        let base = get_interner().len();
+        let mut runtime = RUNTIME.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have added it between the lookup and the lock
+        let index = match runtime.iter().position(|s| *s == string_id) {
+            Some(index) => index,
+            None => {
+                runtime.push(Box::leak(string_id.to_string().into_boxed_str()));
+                runtime.len() - 1
+            }
+        };
+        Id::new((base + index) as u32)
+    }
+
+    /// Full string ID of a base or interned item (e.g., "base:stone")
+    pub fn string_id(item_id: ItemId) -> Option<&'static str> {
+        let interner = get_interner();
+        if let Some(s) = interner.resolve(item_id.raw()) {
+            return Some(s);
+        }
+        let index = (item_id.raw() as usize).checked_sub(interner.len())?;
+        RUNTIME
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(index)
+            .copied()
+    }
+
+    /// Items interned at runtime by data packs and mods, in interning order
+    pub fn runtime_items() -> Vec<ItemId> {
+        let base = get_interner().len();
+        let count = RUNTIME.read().unwrap_or_else(PoisonError::into_inner).len();
+        (base..base + count)
+            .map(|raw| Id::new(raw as u32))
+            .collect()
+    }
+
+    /// Whether an item was interned at runtime rather than built in
+    pub fn is_runtime(item_id: ItemId) -> bool {
+        item_id.raw() as usize >= get_interner().len()
     }
 
     /// Get the global interner (read-only)
@@ -479,6 +537,9 @@ pub mod items {
     pub fn coal() -> ItemId {
         by_name("coal").unwrap_or_else(stone)
     }
+    pub fn crystal() -> ItemId {
+        by_name("crystal").unwrap_or_else(stone)
+    }
 
     // Processed
     pub fn iron_ingot() -> ItemId {
@@ -513,11 +574,58 @@ pub mod items {
     pub fn platform_block() -> ItemId {
         by_name("platform_block").unwrap_or_else(stone)
     }
+    pub fn pipe_block() -> ItemId {
+        by_name("pipe_block").unwrap_or_else(stone)
+    }
+    pub fn tank_block() -> ItemId {
+        by_name("tank_block").unwrap_or_else(stone)
+    }
+    pub fn delivery_pad() -> ItemId {
+        by_name("delivery_pad").unwrap_or_else(stone)
+    }
+    pub fn display_panel() -> ItemId {
+        by_name("display_panel").unwrap_or_else(stone)
+    }
+    pub fn conveyor_block_mk2() -> ItemId {
+        by_name("conveyor_block_mk2").unwrap_or_else(stone)
+    }
+    pub fn conveyor_block_mk3() -> ItemId {
+        by_name("conveyor_block_mk3").unwrap_or_else(stone)
+    }
+    pub fn recycler_block() -> ItemId {
+        by_name("recycler_block").unwrap_or_else(stone)
+    }
+    pub fn lamp_block() -> ItemId {
+        by_name("lamp_block").unwrap_or_else(stone)
+    }
+    pub fn hopper_block() -> ItemId {
+        by_name("hopper_block").unwrap_or_else(stone)
+    }
+    pub fn sign_block() -> ItemId {
+        by_name("sign_block").unwrap_or_else(stone)
+    }
 
     // Tools
     pub fn stone_pickaxe() -> ItemId {
         by_name("stone_pickaxe").unwrap_or_else(stone)
     }
+    pub fn wrench() -> ItemId {
+        by_name("wrench").unwrap_or_else(stone)
+    }
+    pub fn ore_scanner() -> ItemId {
+        by_name("ore_scanner").unwrap_or_else(stone)
+    }
+
+    // Miner drill heads
+    pub fn stone_drill() -> ItemId {
+        by_name("stone_drill").unwrap_or_else(stone)
+    }
+    pub fn iron_drill() -> ItemId {
+        by_name("iron_drill").unwrap_or_else(stone)
+    }
+    pub fn steel_drill() -> ItemId {
+        by_name("steel_drill").unwrap_or_else(stone)
+    }
 
     /// Get all base item IDs
     pub fn all() -> Vec<ItemId> {
@@ -569,7 +677,93 @@ pub mod items {
             || item_id == furnace_block()
             || item_id == crusher_block()
             || item_id == assembler_block()
+            || item_id == recycler_block()
+            || item_id == hopper_block()
             || item_id == platform_block()
+            || item_id == pipe_block()
+            || item_id == tank_block()
+            || item_id == delivery_pad()
+            || is_conveyor(item_id)
+    }
+
+    /// Check if an item places a conveyor (any speed tier)
+    pub fn is_conveyor(item_id: ItemId) -> bool {
+        item_id == conveyor_block()
+            || item_id == conveyor_block_mk2()
+            || item_id == conveyor_block_mk3()
+    }
+
+    /// Check if an item is a miner drill head
+    pub fn is_drill(item_id: ItemId) -> bool {
+        item_id == stone_drill() || item_id == iron_drill() || item_id == steel_drill()
+    }
+
+    /// Check if an item is part of a fluid network (pipe or tank)
+    pub fn is_fluid_block(item_id: ItemId) -> bool {
+        item_id == pipe_block() || item_id == tank_block()
+    }
+}
+
+/// Pre-defined FluidId values for base game fluids.
+pub mod fluids {
+    use super::*;
+    use std::sync::OnceLock;
+
+    // Global interner for static fluid IDs
+    static INTERNER: OnceLock<StringInterner> = OnceLock::new();
+
+    /// All base fluid names (snake_case)
+    const BASE_FLUID_NAMES: &[&str] = &["water", "steam"];
+
+    /// Get the global fluid interner (read-only)
+    pub fn interner() -> &'static StringInterner {
+        INTERNER.get_or_init(|| {
+            let mut interner = StringInterner::new();
+            for name in BASE_FLUID_NAMES {
+                interner.get_or_intern(&format!("{}:{}", BASE_NAMESPACE, name));
+            }
+            interner
+        })
+    }
+
+    /// Get a FluidId by its base name (e.g., "water")
+    pub fn by_name(name: &str) -> Option<FluidId> {
+        let full_id = format!("{}:{}", BASE_NAMESPACE, name);
+        interner().get(&full_id).map(Id::new)
+    }
+
+    /// Get a FluidId by its full string ID (e.g., "base:water")
+    pub fn by_string_id(string_id: &str) -> Option<FluidId> {
+        interner().get(string_id).map(Id::new)
+    }
+
+    /// Get the full string ID of a fluid (e.g., "base:water")
+    pub fn string_id(fluid: FluidId) -> Option<&'static str> {
+        fluid.to_string_id(interner())
+    }
+
+    /// Display name for UI (e.g., "Water")
+    pub fn display_name(fluid: FluidId) -> &'static str {
+        match string_id(fluid) {
+            Some("base:water") => "Water",
+            Some("base:steam") => "Steam",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn water() -> FluidId {
+        by_name("water").expect("water must be registered")
+    }
+    pub fn steam() -> FluidId {
+        by_name("steam").expect("steam must be registered")
+    }
+
+    /// Get all base fluid IDs
+    pub fn all() -> Vec<FluidId> {
+        BASE_FLUID_NAMES
+            .iter()
+            .filter_map(|name| by_name(name))
+            .collect()
     }
 }
 
@@ -598,7 +792,7 @@ mod tests {
     #[test]
     fn test_base_items_all() {
         let all = items::all();
-        assert_eq!(all.len(), 16); // All 16 base items
+        assert_eq!(all.len(), 32); // All 32 base items
     }
 
     #[test]
@@ -608,6 +802,26 @@ mod tests {
         assert!(items::by_name("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_intern_data_pack_item() {
+        // Base items keep their IDs
+        assert_eq!(items::intern("base:stone"), items::stone());
+
+        let gear = items::intern("test_intern:gear");
+        assert_eq!(items::intern("test_intern:gear"), gear);
+        assert!(items::all().iter().all(|&base| base != gear));
+        assert_eq!(items::by_string_id("test_intern:gear"), Some(gear));
+        assert_eq!(gear.name(), Some("test_intern:gear"));
+        assert!(items::runtime_items().contains(&gear));
+        assert!(!items::runtime_items().contains(&items::stone()));
+        assert!(items::is_runtime(gear) && !items::is_runtime(items::stone()));
+
+        // Unqualified names resolve in the base namespace
+        let cog = items::intern("base:test_intern_cog");
+        assert_eq!(items::by_name("test_intern_cog"), Some(cog));
+        assert_ne!(cog, gear);
+    }
+
     #[test]
     fn test_id_equality() {
         let id1: ItemId = Id::new(42);
@@ -7,7 +7,10 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use crate::core::ItemId;
-use crate::game_spec::recipes::{RecipeInput, RecipeOutput};
+use crate::game_spec::recipes::{
+    get_recipes_for_machine, Ingredient, MachineType, Recipe, RecipeInput, RecipeOutput,
+};
+use crate::player::PlayerInventory;
 
 /// クラフト可能な場所
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
@@ -57,8 +60,11 @@ impl CraftingRecipe {
     /// 入力アイテムが足りているか確認
     pub fn can_craft(&self, inventory: &HashMap<ItemId, u32>) -> bool {
         for input in &self.inputs {
-            let item_id: ItemId = input.item;
-            let have = inventory.get(&item_id).copied().unwrap_or(0);
+            let have = input
+                .item()
+                .and_then(|item_id| inventory.get(&item_id))
+                .copied()
+                .unwrap_or(0);
             if have < input.count {
                 return false;
             }
@@ -68,7 +74,10 @@ impl CraftingRecipe {
 
     /// 必要なアイテム一覧を取得
     pub fn required_items(&self) -> Vec<(ItemId, u32)> {
-        self.inputs.iter().map(|i| (i.item, i.count)).collect()
+        self.inputs
+            .iter()
+            .filter_map(|i| i.item().map(|item| (item, i.count)))
+            .collect()
     }
 
     /// 出力アイテム一覧を取得
@@ -221,6 +230,78 @@ impl CraftingRegistry {
     }
 }
 
+/// 手持ちクラフトの失敗理由
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandCraftError {
+    /// 材料が足りない
+    MissingInput,
+    /// 完成品がインベントリに入りきらない
+    NoSpace,
+}
+
+/// 手持ちクラフトのレシピ一覧（機械と同じレシピ表の `MachineType::Hand`）
+pub fn hand_recipes() -> impl Iterator<Item = &'static Recipe> {
+    get_recipes_for_machine(MachineType::Hand)
+}
+
+/// 入力の表示名（タグ入力は `#tag`）
+pub fn input_label(input: &RecipeInput) -> String {
+    match input.ingredient {
+        Ingredient::Item(item_id) => item_id.display_name().to_string(),
+        Ingredient::Tag(tag) => format!("#{}", tag),
+    }
+}
+
+/// 入力に使えるインベントリ内の個数（タグ入力は該当アイテムの合計）
+pub fn available_for_input(inventory: &PlayerInventory, input: &RecipeInput) -> u32 {
+    match input.item() {
+        Some(item_id) => inventory.get_total_count_by_id(item_id),
+        None => inventory
+            .get_all_items_by_id()
+            .into_iter()
+            .filter(|(item_id, _)| input.matches(*item_id))
+            .map(|(_, count)| count)
+            .sum(),
+    }
+}
+
+/// 手持ちレシピを即時クラフト
+///
+/// 材料を消費して完成品を追加する。失敗時はインベントリを変更しない。
+pub fn hand_craft(recipe: &Recipe, inventory: &mut PlayerInventory) -> Result<(), HandCraftError> {
+    let mut result = inventory.clone();
+
+    for input in &recipe.inputs {
+        if available_for_input(&result, input) < input.count {
+            return Err(HandCraftError::MissingInput);
+        }
+        let mut remaining = input.count;
+        let candidates: Vec<ItemId> = match input.item() {
+            Some(item_id) => vec![item_id],
+            None => result
+                .get_all_items_by_id()
+                .into_iter()
+                .map(|(item_id, _)| item_id)
+                .filter(|item_id| input.matches(*item_id))
+                .collect(),
+        };
+        for item_id in candidates {
+            let take = remaining.min(result.get_total_count_by_id(item_id));
+            result.consume_item_by_id(item_id, take);
+            remaining -= take;
+        }
+    }
+
+    for output in recipe.guaranteed_outputs() {
+        if result.add_item_by_id(output.item, output.count) > 0 {
+            return Err(HandCraftError::NoSpace);
+        }
+    }
+
+    *inventory = result;
+    Ok(())
+}
+
 /// クラフト開始イベント
 #[derive(Message)]
 pub struct StartCraftEvent {
@@ -266,6 +347,14 @@ fn setup_default_recipes(mut registry: ResMut<CraftingRegistry>) {
             .build(),
     );
 
+    // 手持ちクラフト - レンチ
+    registry.register(
+        CraftingRecipe::builder("wrench", CraftingStation::Hand, 2.0)
+            .input(items::iron_ingot(), 2)
+            .output(items::wrench(), 1)
+            .build(),
+    );
+
     // 手持ちクラフト - 松明（石炭使用）
     registry.register(
         CraftingRecipe::builder("torch", CraftingStation::Hand, 1.0)
@@ -339,6 +428,64 @@ mod tests {
     use super::*;
     use crate::core::items;
 
+    #[test]
+    fn test_hand_craft_with_exact_materials() {
+        let recipe = crate::game_spec::find_recipe_by_id("hand_furnace").unwrap();
+        let mut inventory = PlayerInventory::with_initial_items_by_id(&[
+            (items::stone(), 5),
+            (items::iron_ingot(), 2),
+        ]);
+
+        assert_eq!(hand_craft(recipe, &mut inventory), Ok(()));
+        assert_eq!(inventory.get_total_count_by_id(items::stone()), 0);
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 0);
+        assert_eq!(inventory.get_total_count_by_id(items::furnace_block()), 1);
+    }
+
+    #[test]
+    fn test_hand_craft_short_ingredient_changes_nothing() {
+        let recipe = crate::game_spec::find_recipe_by_id("hand_furnace").unwrap();
+        let mut inventory = PlayerInventory::with_initial_items_by_id(&[
+            (items::stone(), 5),
+            (items::iron_ingot(), 1),
+        ]);
+        let before = inventory.slots;
+
+        assert_eq!(
+            hand_craft(recipe, &mut inventory),
+            Err(HandCraftError::MissingInput)
+        );
+        assert_eq!(inventory.slots, before);
+    }
+
+    #[test]
+    fn test_hand_craft_full_inventory_changes_nothing() {
+        use crate::constants::{MAX_STACK_SIZE, NUM_SLOTS};
+
+        let recipe = crate::game_spec::find_recipe_by_id("hand_conveyor").unwrap();
+        let mut inventory = PlayerInventory::default();
+        // 材料スロット以外を全て埋める（消費しても空きスロットが出ない）
+        inventory.slots[0] = Some((items::iron_ingot(), 4));
+        for slot in inventory.slots.iter_mut().skip(1).take(NUM_SLOTS - 1) {
+            *slot = Some((items::stone(), MAX_STACK_SIZE));
+        }
+        let before = inventory.slots;
+
+        assert_eq!(
+            hand_craft(recipe, &mut inventory),
+            Err(HandCraftError::NoSpace)
+        );
+        assert_eq!(inventory.slots, before);
+    }
+
+    #[test]
+    fn test_hand_recipes_from_recipe_table() {
+        let ids: Vec<_> = hand_recipes().map(|r| r.id).collect();
+        assert!(ids.contains(&"hand_furnace"));
+        assert!(ids.contains(&"hand_conveyor"));
+        assert!(hand_recipes().all(|r| r.machine == MachineType::Hand));
+    }
+
     #[test]
     fn test_crafting_station_default() {
         let station = CraftingStation::default();
@@ -0,0 +1,373 @@
+//! Lamps and machine work lights
+//!
+//! Lamps are regular world blocks (placement, breaking and world saves
+//! already handle them). Every placed lamp gets a `Lamp` entity with an
+//! emissive shell; machines get a small work light child while processing.
+//!
+//! Each light is a `LightSource`. Point lights are costly, so only the
+//! `LightBudget` active sources nearest the player carry a `PointLight`;
+//! the rest keep just their emissive look.
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use crate::components::{Machine, Player};
+use crate::core::items;
+use crate::events::game_events::{BlockBroken, BlockPlaced};
+use crate::settings::GameSettings;
+use crate::BLOCK_SIZE;
+
+/// Default number of lights that get a point light
+pub const DEFAULT_LIGHT_BUDGET: usize = 32;
+
+/// Height of a work light above the machine's center (blocks)
+const WORK_LIGHT_HEIGHT: f32 = 0.8;
+
+/// What a light source belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightKind {
+    /// A placed lamp block
+    Lamp,
+    /// Work light over a processing machine
+    Work,
+}
+
+impl LightKind {
+    /// Point light used while the source is within the budget
+    pub fn point_light(self) -> PointLight {
+        match self {
+            LightKind::Lamp => PointLight {
+                color: Color::srgb(1.0, 0.85, 0.6),
+                intensity: 200_000.0,
+                range: 12.0 * BLOCK_SIZE,
+                shadows_enabled: false,
+                ..default()
+            },
+            LightKind::Work => PointLight {
+                color: Color::srgb(1.0, 0.75, 0.45),
+                intensity: 40_000.0,
+                range: 5.0 * BLOCK_SIZE,
+                shadows_enabled: false,
+                ..default()
+            },
+        }
+    }
+}
+
+/// A light that competes for the point light budget
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct LightSource {
+    pub kind: LightKind,
+    /// World position used for the distance to the player
+    pub position: Vec3,
+    /// Inactive sources (idle machines) never get a point light
+    pub active: bool,
+}
+
+/// A placed lamp block
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lamp {
+    /// Block position of the lamp
+    pub position: IVec3,
+}
+
+impl Lamp {
+    pub fn new(position: IVec3) -> Self {
+        Self { position }
+    }
+
+    /// World-space center of the lamp block
+    pub fn center(&self) -> Vec3 {
+        (self.position.as_vec3() + Vec3::splat(0.5)) * BLOCK_SIZE
+    }
+}
+
+/// Maximum number of light sources with a real point light
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightBudget {
+    pub max_active: usize,
+}
+
+impl Default for LightBudget {
+    fn default() -> Self {
+        Self {
+            max_active: DEFAULT_LIGHT_BUDGET,
+        }
+    }
+}
+
+/// Shared mesh/material for the lamp's emissive shell
+#[derive(Resource)]
+pub struct LampAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Pick the `n` candidates nearest to `origin` (ties broken by entity for stable picks)
+pub fn nearest_lights(candidates: &[(Entity, Vec3)], origin: Vec3, n: usize) -> Vec<Entity> {
+    let mut sorted: Vec<(f32, Entity)> = candidates
+        .iter()
+        .map(|&(entity, pos)| (pos.distance_squared(origin), entity))
+        .collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    sorted
+        .into_iter()
+        .take(n)
+        .map(|(_, entity)| entity)
+        .collect()
+}
+
+pub(super) fn setup_lamp_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Slightly larger than the block so the shell covers the chunk mesh faces
+    let size = BLOCK_SIZE * 1.02;
+    commands.insert_resource(LampAssets {
+        mesh: meshes.add(Cuboid::new(size, size, size)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.85, 0.45),
+            emissive: LinearRgba::new(4.0, 3.0, 1.2, 1.0),
+            unlit: true,
+            ..default()
+        }),
+    });
+}
+
+/// Spawn a lamp entity for each placed lamp block, despawn it when the block breaks
+pub(super) fn track_lamps(
+    mut commands: Commands,
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+    lamps: Query<(Entity, &Lamp)>,
+) {
+    let lamp_item = items::lamp_block();
+
+    for event in broken.read() {
+        if event.block != lamp_item {
+            continue;
+        }
+        for (entity, lamp) in lamps.iter() {
+            if lamp.position == event.pos {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    for event in placed.read() {
+        if event.block == lamp_item {
+            commands.spawn(Lamp::new(event.pos));
+        }
+    }
+}
+
+/// Give new lamp entities their transform, light source and emissive shell
+pub(super) fn attach_lamp_parts(
+    mut commands: Commands,
+    assets: Option<Res<LampAssets>>,
+    added: Query<(Entity, &Lamp), Added<Lamp>>,
+) {
+    for (entity, lamp) in added.iter() {
+        let center = lamp.center();
+        let mut entity = commands.entity(entity);
+        entity.insert((
+            Transform::from_translation(center),
+            Visibility::Visible,
+            LightSource {
+                kind: LightKind::Lamp,
+                position: center,
+                active: true,
+            },
+        ));
+        if let Some(assets) = &assets {
+            entity.insert((
+                Mesh3d(assets.mesh.clone()),
+                MeshMaterial3d(assets.material.clone()),
+                NotShadowCaster,
+            ));
+        }
+    }
+}
+
+/// Keep a work light child on each machine, active while it is processing
+pub(super) fn update_work_lights(
+    mut commands: Commands,
+    machines: Query<(Entity, &Machine, Option<&Children>)>,
+    mut sources: Query<&mut LightSource>,
+) {
+    for (entity, machine, children) in machines.iter() {
+        let working = machine.enabled && machine.progress > 0.0;
+        let light = children.and_then(|c| c.iter().find(|e| sources.contains(*e)));
+        match light {
+            Some(light) => {
+                if let Ok(mut source) = sources.get_mut(light) {
+                    if source.active != working {
+                        source.active = working;
+                    }
+                }
+            }
+            None if working => {
+                let position = (machine.position.as_vec3() + Vec3::splat(0.5)) * BLOCK_SIZE
+                    + Vec3::Y * WORK_LIGHT_HEIGHT * BLOCK_SIZE;
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        Transform::from_xyz(0.0, WORK_LIGHT_HEIGHT * BLOCK_SIZE, 0.0),
+                        Visibility::Inherited,
+                        LightSource {
+                            kind: LightKind::Work,
+                            position,
+                            active: true,
+                        },
+                    ));
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Follow the light budget setting
+pub(super) fn sync_light_budget(
+    settings: Option<Res<GameSettings>>,
+    mut budget: ResMut<LightBudget>,
+) {
+    let Some(settings) = settings.filter(|s| s.is_changed()) else {
+        return;
+    };
+    let max_active = settings.light_budget as usize;
+    if budget.max_active != max_active {
+        budget.max_active = max_active;
+    }
+}
+
+/// Give point lights to the active sources nearest the player, take them from the rest
+pub(super) fn apply_light_budget(
+    mut commands: Commands,
+    budget: Res<LightBudget>,
+    player_query: Query<&Transform, With<Player>>,
+    sources: Query<(Entity, &LightSource, Has<PointLight>)>,
+) {
+    let origin = player_query
+        .single()
+        .map(|t| t.translation)
+        .unwrap_or(Vec3::ZERO);
+
+    let candidates: Vec<(Entity, Vec3)> = sources
+        .iter()
+        .filter(|(_, source, _)| source.active)
+        .map(|(entity, source, _)| (entity, source.position))
+        .collect();
+    let lit: HashSet<Entity> = nearest_lights(&candidates, origin, budget.max_active)
+        .into_iter()
+        .collect();
+
+    for (entity, source, has_light) in sources.iter() {
+        match (lit.contains(&entity), has_light) {
+            (true, false) => {
+                commands.entity(entity).insert(source.kind.point_light());
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<PointLight>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::game_events::EventSource;
+
+    fn lamp_app() -> App {
+        let mut app = App::new();
+        app.add_message::<BlockPlaced>()
+            .add_message::<BlockBroken>()
+            .init_resource::<LightBudget>()
+            .add_systems(
+                Update,
+                (track_lamps, attach_lamp_parts, apply_light_budget).chain(),
+            );
+        app
+    }
+
+    fn place_lamp(app: &mut App, pos: IVec3) {
+        app.world_mut().write_message(BlockPlaced {
+            pos,
+            block: items::lamp_block(),
+            source: EventSource::System,
+        });
+    }
+
+    fn lit_positions(app: &mut App) -> Vec<Vec3> {
+        app.world_mut()
+            .query_filtered::<&LightSource, With<PointLight>>()
+            .iter(app.world())
+            .map(|source| source.position)
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_lights_picks_closest() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..5).map(|_| world.spawn_empty().id()).collect();
+        let candidates: Vec<(Entity, Vec3)> = entities
+            .iter()
+            .zip([10.0, 2.0, 7.0, 1.0, 4.0])
+            .map(|(&e, x)| (e, Vec3::new(x, 0.0, 0.0)))
+            .collect();
+
+        let picked = nearest_lights(&candidates, Vec3::ZERO, 3);
+        assert_eq!(picked, vec![entities[3], entities[1], entities[4]]);
+
+        // Budget larger than the candidate list returns everything
+        assert_eq!(nearest_lights(&candidates, Vec3::ZERO, 10).len(), 5);
+        assert!(nearest_lights(&candidates, Vec3::ZERO, 0).is_empty());
+    }
+
+    #[test]
+    fn test_budget_caps_point_lights() {
+        let mut app = lamp_app();
+        app.world_mut()
+            .spawn((Player, Transform::from_translation(Vec3::ZERO)));
+        for x in 0..40 {
+            place_lamp(&mut app, IVec3::new(x, 5, 0));
+        }
+        // Spawn, attach parts, then apply the budget
+        app.update();
+        app.update();
+
+        let lit = lit_positions(&mut app);
+        assert_eq!(lit.len(), DEFAULT_LIGHT_BUDGET);
+        // The far lamps are the ones left emissive-only
+        let farthest = Lamp::new(IVec3::new(DEFAULT_LIGHT_BUDGET as i32 - 1, 5, 0)).center();
+        assert!(lit
+            .iter()
+            .all(|pos| pos.distance(Vec3::ZERO) <= farthest.distance(Vec3::ZERO)));
+
+        // Lowering the budget takes lights away
+        app.world_mut().resource_mut::<LightBudget>().max_active = 4;
+        app.update();
+        assert_eq!(lit_positions(&mut app).len(), 4);
+    }
+
+    #[test]
+    fn test_broken_lamp_is_removed() {
+        let mut app = lamp_app();
+        let pos = IVec3::new(2, 5, 2);
+        place_lamp(&mut app, pos);
+        app.update();
+
+        app.world_mut().write_message(BlockBroken {
+            pos,
+            block: items::lamp_block(),
+            source: EventSource::System,
+        });
+        app.update();
+
+        let lamps = app.world_mut().query::<&Lamp>().iter(app.world()).count();
+        assert_eq!(lamps, 0);
+    }
+}
@@ -0,0 +1,487 @@
+//! Day/night cycle
+//!
+//! - `GameClock`: time of day advancing through a 20-minute day
+//! - Sun rotation and light/ambient/sky interpolation between presets
+//! - Furnace glow at night while processing
+//! - Lamps and machine work lights under a point light budget (`lights`)
+//! - `/time` and `/skip-night` command handling
+//!
+//! The clock is purely visual: machine processing never reads it.
+
+use bevy::color::Mix;
+use bevy::light::{GlobalAmbientLight, NotShadowCaster};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::components::Machine;
+use crate::core::items;
+use crate::main_menu::InGameSet;
+use crate::settings::GameSettings;
+
+mod lights;
+
+pub use lights::{
+    nearest_lights, Lamp, LampAssets, LightBudget, LightKind, LightSource, DEFAULT_LIGHT_BUDGET,
+};
+
+/// Real-time length of one in-game day (seconds)
+pub const DAY_LENGTH_SECS: f32 = 20.0 * 60.0;
+
+/// Hour the player starts at and wakes up at after `/skip-night`
+pub const MORNING_HOUR: f32 = 6.0;
+
+/// Below this sun elevation shadows are disabled (avoids artifacts at the horizon)
+pub const SHADOW_ELEVATION_THRESHOLD: f32 = 0.05;
+
+/// In-game clock (separate from `Time` so it can be saved, set and skipped)
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct GameClock {
+    /// Fraction of the day (0.0 = midnight, 0.25 = 06:00, 0.5 = noon)
+    pub time_of_day: f32,
+    /// Number of completed days
+    pub day: u32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            time_of_day: 8.0 / 24.0,
+            day: 0,
+        }
+    }
+}
+
+impl GameClock {
+    /// Advance the clock by real seconds
+    pub fn advance(&mut self, secs: f32) {
+        self.add_days(secs / DAY_LENGTH_SECS);
+    }
+
+    /// Advance (or rewind) by a fraction of a day, rolling the day counter
+    fn add_days(&mut self, days: f32) {
+        let total = self.time_of_day + days;
+        let whole = total.floor();
+        self.time_of_day = total - whole;
+        self.day = (self.day as i64 + whole as i64).max(0) as u32;
+    }
+
+    /// Current hour (0.0 - 24.0)
+    pub fn hours(&self) -> f32 {
+        self.time_of_day * 24.0
+    }
+
+    /// Set the hour of the current day
+    pub fn set_hours(&mut self, hours: f32) {
+        self.time_of_day = hours.rem_euclid(24.0) / 24.0;
+    }
+
+    /// Add hours, rolling over into the next day(s)
+    pub fn add_hours(&mut self, hours: f32) {
+        self.add_days(hours / 24.0);
+    }
+
+    /// Sun elevation (-1.0 = midnight, 0.0 = horizon at 06:00/18:00, 1.0 = noon)
+    pub fn sun_elevation(&self) -> f32 {
+        ((self.time_of_day - 0.25) * TAU).sin()
+    }
+
+    /// Whether the sun is below the horizon
+    pub fn is_night(&self) -> bool {
+        self.sun_elevation() < 0.0
+    }
+
+    /// Jump to the next morning if it is currently night
+    pub fn skip_night(&mut self) {
+        if !self.is_night() {
+            return;
+        }
+        let hours = self.hours();
+        let until_morning = if hours < MORNING_HOUR {
+            MORNING_HOUR - hours
+        } else {
+            24.0 - hours + MORNING_HOUR
+        };
+        self.add_hours(until_morning);
+    }
+
+    /// Format as "Day N HH:MM"
+    pub fn display(&self) -> String {
+        let minutes = (self.hours() * 60.0) as u32;
+        format!(
+            "Day {} {:02}:{:02}",
+            self.day + 1,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/// Lighting values for a point in the day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightingPreset {
+    /// Directional light illuminance (lux)
+    pub illuminance: f32,
+    /// Ambient light brightness
+    pub ambient: f32,
+    /// Directional light color
+    pub color: Color,
+    /// Sky (clear) color
+    pub sky: Color,
+}
+
+impl LightingPreset {
+    pub const DAY: Self = Self {
+        illuminance: 10000.0,
+        ambient: 300.0,
+        color: Color::WHITE,
+        sky: Color::srgb(0.47, 0.66, 0.88),
+    };
+
+    pub const DUSK: Self = Self {
+        illuminance: 2500.0,
+        ambient: 150.0,
+        color: Color::srgb(1.0, 0.7, 0.45),
+        sky: Color::srgb(0.85, 0.5, 0.35),
+    };
+
+    pub const NIGHT: Self = Self {
+        illuminance: 150.0,
+        ambient: 40.0,
+        color: Color::srgb(0.6, 0.7, 1.0),
+        sky: Color::srgb(0.03, 0.04, 0.1),
+    };
+
+    /// Linear interpolation between two presets (t = 0.0 → a, 1.0 → b)
+    pub fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self {
+            illuminance: a.illuminance + (b.illuminance - a.illuminance) * t,
+            ambient: a.ambient + (b.ambient - a.ambient) * t,
+            color: a.color.mix(&b.color, t),
+            sky: a.sky.mix(&b.sky, t),
+        }
+    }
+
+    /// Preset for a given sun elevation
+    ///
+    /// Night below -0.2, dusk at the horizon, full day above 0.3.
+    pub fn for_elevation(elevation: f32) -> Self {
+        const NIGHT_END: f32 = -0.2;
+        const DAY_START: f32 = 0.3;
+        if elevation <= NIGHT_END {
+            Self::NIGHT
+        } else if elevation < 0.0 {
+            Self::lerp(
+                &Self::NIGHT,
+                &Self::DUSK,
+                (elevation - NIGHT_END) / -NIGHT_END,
+            )
+        } else if elevation < DAY_START {
+            Self::lerp(&Self::DUSK, &Self::DAY, elevation / DAY_START)
+        } else {
+            Self::DAY
+        }
+    }
+}
+
+/// Time command from `/time` and `/skip-night`
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub enum TimeCommandEvent {
+    /// Log the current time
+    Query,
+    /// Set the hour of the current day
+    Set(f32),
+    /// Add hours (may roll over days)
+    Add(f32),
+    /// Jump to the next morning if it is night
+    SkipNight,
+}
+
+/// Parse a `/time set` value: an hour (0-24) or a named time
+pub fn parse_time_value(value: &str) -> Option<f32> {
+    match value.to_lowercase().as_str() {
+        "day" | "morning" => Some(MORNING_HOUR),
+        "noon" => Some(12.0),
+        "dusk" | "evening" => Some(18.0),
+        "night" => Some(21.0),
+        "midnight" => Some(0.0),
+        other => other
+            .parse::<f32>()
+            .ok()
+            .filter(|h| h.is_finite() && (0.0..=24.0).contains(h)),
+    }
+}
+
+/// Marker for the emissive glow child of a furnace
+#[derive(Component)]
+pub struct FurnaceGlow;
+
+/// Shared mesh/material for furnace glow
+#[derive(Resource)]
+pub struct FurnaceGlowAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+fn setup_furnace_glow_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(FurnaceGlowAssets {
+        mesh: meshes.add(Cuboid::new(0.5, 0.3, 0.02)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.5, 0.1),
+            emissive: LinearRgba::new(6.0, 2.5, 0.4, 1.0),
+            unlit: true,
+            ..default()
+        }),
+    });
+}
+
+/// Advance the clock with virtual time
+fn advance_game_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    clock.advance(time.delta_secs());
+}
+
+/// Rotate the sun and interpolate light, ambient and sky colors
+fn update_sun_lighting(
+    clock: Res<GameClock>,
+    mut sun_query: Query<(&mut DirectionalLight, &mut Transform)>,
+    ambient: Option<ResMut<GlobalAmbientLight>>,
+    clear_color: Option<ResMut<ClearColor>>,
+    settings: Option<Res<GameSettings>>,
+) {
+    if !clock.is_changed() {
+        return;
+    }
+    let shadows_allowed = settings.is_none_or(|s| s.shadow_quality.shadows_enabled());
+
+    let elevation = clock.sun_elevation();
+    let preset = LightingPreset::for_elevation(elevation);
+
+    // Sun travels east → west; below the horizon the moon (opposite side) lights the scene
+    let angle = (clock.time_of_day - 0.25) * TAU;
+    let sun_dir = Vec3::new(angle.cos(), angle.sin(), 0.35).normalize();
+    let light_from = if elevation >= 0.0 { sun_dir } else { -sun_dir };
+
+    for (mut light, mut transform) in sun_query.iter_mut() {
+        *transform = Transform::from_translation(light_from).looking_at(Vec3::ZERO, Vec3::Y);
+        light.illuminance = preset.illuminance;
+        light.color = preset.color;
+        light.shadows_enabled = shadows_allowed && elevation.abs() > SHADOW_ELEVATION_THRESHOLD;
+    }
+
+    if let Some(mut ambient) = ambient {
+        ambient.brightness = preset.ambient;
+    }
+    if let Some(mut clear_color) = clear_color {
+        clear_color.0 = preset.sky;
+    }
+}
+
+/// Show the furnace glow at night while the furnace is processing
+fn update_furnace_glow(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    assets: Option<Res<FurnaceGlowAssets>>,
+    furnace_query: Query<(Entity, &Machine, Option<&Children>)>,
+    mut glow_query: Query<&mut Visibility, With<FurnaceGlow>>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+    let night = clock.is_night();
+
+    for (entity, machine, children) in furnace_query.iter() {
+        if machine.spec.item_id() != items::furnace_block() {
+            continue;
+        }
+        let glowing = night && machine.progress > 0.0;
+
+        let glow_child = children.and_then(|c| c.iter().find(|e| glow_query.contains(*e)));
+        match glow_child {
+            Some(glow) => {
+                if let Ok(mut vis) = glow_query.get_mut(glow) {
+                    let target = if glowing {
+                        Visibility::Visible
+                    } else {
+                        Visibility::Hidden
+                    };
+                    if *vis != target {
+                        *vis = target;
+                    }
+                }
+            }
+            None if glowing => {
+                // Furnace mouth on the front face (-Z in local space)
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        Mesh3d(assets.mesh.clone()),
+                        MeshMaterial3d(assets.material.clone()),
+                        Transform::from_xyz(0.0, 0.35, -0.51),
+                        Visibility::Visible,
+                        FurnaceGlow,
+                        NotShadowCaster,
+                    ));
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Apply `/time` and `/skip-night` commands
+fn handle_time_command(mut events: MessageReader<TimeCommandEvent>, mut clock: ResMut<GameClock>) {
+    for event in events.read() {
+        match *event {
+            TimeCommandEvent::Query => {}
+            TimeCommandEvent::Set(hours) => clock.set_hours(hours),
+            TimeCommandEvent::Add(hours) => clock.add_hours(hours),
+            TimeCommandEvent::SkipNight => {
+                if !clock.is_night() {
+                    info!("It is not night ({})", clock.display());
+                    continue;
+                }
+                clock.skip_night();
+            }
+        }
+        info!(category = "TIME", time = %clock.display(), "Time of day");
+    }
+}
+
+/// Plugin for the day/night cycle
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>()
+            .init_resource::<LightBudget>()
+            .add_message::<TimeCommandEvent>()
+            .add_systems(
+                Startup,
+                (setup_furnace_glow_assets, lights::setup_lamp_assets),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_time_command,
+                    advance_game_clock,
+                    update_sun_lighting,
+                    update_furnace_glow,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    lights::track_lamps,
+                    lights::attach_lamp_parts,
+                    lights::update_work_lights,
+                    lights::sync_light_budget,
+                    lights::apply_light_budget,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_clock_advance_wraps_day() {
+        let mut clock = GameClock {
+            time_of_day: 0.9,
+            day: 0,
+        };
+        clock.advance(DAY_LENGTH_SECS * 0.2);
+        assert_eq!(clock.day, 1);
+        assert!(approx(clock.time_of_day, 0.1));
+    }
+
+    #[test]
+    fn test_set_and_add_hours() {
+        let mut clock = GameClock::default();
+        clock.set_hours(12.0);
+        assert!(approx(clock.hours(), 12.0));
+
+        clock.add_hours(14.0);
+        assert_eq!(clock.day, 1);
+        assert!(approx(clock.hours(), 2.0));
+
+        // Negative add rewinds without underflowing the day counter
+        clock.add_hours(-48.0);
+        assert_eq!(clock.day, 0);
+        assert!(approx(clock.hours(), 2.0));
+    }
+
+    #[test]
+    fn test_sun_elevation_and_night() {
+        let mut clock = GameClock::default();
+        clock.set_hours(12.0);
+        assert!(approx(clock.sun_elevation(), 1.0));
+        assert!(!clock.is_night());
+
+        clock.set_hours(0.0);
+        assert!(approx(clock.sun_elevation(), -1.0));
+        assert!(clock.is_night());
+    }
+
+    #[test]
+    fn test_skip_night() {
+        let mut clock = GameClock::default();
+        clock.set_hours(22.0);
+        clock.skip_night();
+        assert_eq!(clock.day, 1);
+        assert!(approx(clock.hours(), MORNING_HOUR));
+
+        // Early morning: same day
+        let mut clock = GameClock::default();
+        clock.set_hours(3.0);
+        clock.skip_night();
+        assert_eq!(clock.day, 0);
+        assert!(approx(clock.hours(), MORNING_HOUR));
+
+        // Daytime: no change
+        let mut clock = GameClock::default();
+        clock.set_hours(10.0);
+        clock.skip_night();
+        assert!(approx(clock.hours(), 10.0));
+    }
+
+    #[test]
+    fn test_preset_interpolation() {
+        assert_eq!(LightingPreset::for_elevation(1.0), LightingPreset::DAY);
+        assert_eq!(LightingPreset::for_elevation(-1.0), LightingPreset::NIGHT);
+        assert!(approx(
+            LightingPreset::for_elevation(0.0).illuminance,
+            LightingPreset::DUSK.illuminance
+        ));
+
+        let mid = LightingPreset::lerp(&LightingPreset::DUSK, &LightingPreset::DAY, 0.5);
+        assert!(approx(mid.illuminance, (2500.0 + 10000.0) / 2.0));
+        assert!(approx(mid.ambient, (150.0 + 300.0) / 2.0));
+
+        // Clamped outside 0..1
+        let over = LightingPreset::lerp(&LightingPreset::DUSK, &LightingPreset::DAY, 2.0);
+        assert_eq!(over.illuminance, LightingPreset::DAY.illuminance);
+    }
+
+    #[test]
+    fn test_parse_time_value() {
+        assert_eq!(parse_time_value("noon"), Some(12.0));
+        assert_eq!(parse_time_value("Midnight"), Some(0.0));
+        assert_eq!(parse_time_value("18.5"), Some(18.5));
+        assert_eq!(parse_time_value("25"), None);
+        assert_eq!(parse_time_value("NaN"), None);
+        assert_eq!(parse_time_value("abc"), None);
+    }
+}
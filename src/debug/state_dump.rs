@@ -169,7 +169,7 @@ fn extract_machines(world: &mut World) -> MachinesDump {
             ],
             direction: format!("{:?}", conveyor.direction),
             shape: format!("{:?}", conveyor.shape),
-            item_count: conveyor.items.len(),
+            item_count: conveyor.total_item_count() as usize,
         });
     }
 
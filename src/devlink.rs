@@ -0,0 +1,486 @@
+//! Editor live link: apply edited items and recipes to a running game
+//!
+//! Off by default. `/devlink on` starts a small HTTP listener on 127.0.0.1
+//! and prints a session token in the console. The editor POSTs one JSON
+//! message per request with `Authorization: Bearer <token>`:
+//!
+//! - `{"type": "upsert_item", "item": {..}}`
+//! - `{"type": "upsert_recipe", "recipe": {..}}`
+//! - `{"type": "reload_all", "items": [..], "recipes": [..]}`
+//!
+//! Items and recipes use the data pack format (the editor's export) and land
+//! in the base namespace. Every applied message emits [`AssetsReloaded`].
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::core::items;
+use crate::game_spec::registry::{
+    data_item_id, descriptor_from_definition, recipe_from_definition, register_item_descriptor,
+};
+use crate::game_spec::{build_item_info_cache, GameRegistry};
+use crate::modding::data::{ItemDefinition, ModDataPack, RecipeDefinition};
+use crate::modding::LoadedModData;
+
+/// Port used by `/devlink on`
+pub const DEFAULT_DEVLINK_PORT: u16 = 7879;
+
+/// Namespace pushed definitions belong to
+const DEVLINK_MOD_ID: &str = "base";
+
+/// Largest request accepted (headers and body)
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// How long to wait for the rest of a request after accepting it
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Message sent by the editor
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DevLinkMessage {
+    /// Add an item or replace the one with the same ID
+    UpsertItem { item: ItemDefinition },
+    /// Add a recipe or replace the one with the same ID
+    UpsertRecipe { recipe: RecipeDefinition },
+    /// Apply a full export (items first, so recipes can use them)
+    ReloadAll {
+        #[serde(default)]
+        items: Vec<ItemDefinition>,
+        #[serde(default)]
+        recipes: Vec<RecipeDefinition>,
+    },
+}
+
+/// Items or recipes changed at runtime; caches built from them are stale
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AssetsReloaded;
+
+/// `/devlink` command
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevLinkCommandEvent {
+    /// Log the listener address and token (or that it is off)
+    Query,
+    /// Start or stop the listener
+    Set(bool),
+}
+
+/// Live link listener and its session token (None while off)
+#[derive(Resource, Default)]
+pub struct DevLink {
+    listener: Option<TcpListener>,
+    token: String,
+}
+
+impl DevLink {
+    /// Listen on 127.0.0.1 with a fresh session token
+    ///
+    /// Port 0 picks a free port; the bound address is returned.
+    pub fn start(&mut self, port: u16) -> std::io::Result<SocketAddr> {
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        self.listener = Some(listener);
+        self.token = session_token();
+        Ok(addr)
+    }
+
+    pub fn stop(&mut self) {
+        self.listener = None;
+        self.token.clear();
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.as_ref()?.local_addr().ok()
+    }
+
+    /// Token the editor must send (None while off)
+    pub fn token(&self) -> Option<&str> {
+        self.listener.as_ref().map(|_| self.token.as_str())
+    }
+}
+
+/// Random token for one listener session
+fn session_token() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    hasher.write_u128(now.as_nanos());
+    format!("{:016x}", hasher.finish())
+}
+
+/// Apply `/devlink` commands
+fn handle_devlink_command(
+    mut events: MessageReader<DevLinkCommandEvent>,
+    mut devlink: ResMut<DevLink>,
+) {
+    for event in events.read() {
+        match *event {
+            DevLinkCommandEvent::Set(true) if devlink.token().is_none() => {
+                match devlink.start(DEFAULT_DEVLINK_PORT) {
+                    Ok(addr) => info!(
+                        "Dev link listening on {} (session token: {})",
+                        addr, devlink.token
+                    ),
+                    Err(e) => error!("Failed to start dev link: {}", e),
+                }
+            }
+            DevLinkCommandEvent::Set(false) => {
+                devlink.stop();
+                info!("Dev link off");
+            }
+            DevLinkCommandEvent::Set(true) | DevLinkCommandEvent::Query => {
+                match (devlink.local_addr(), devlink.token()) {
+                    (Some(addr), Some(token)) => {
+                        info!("Dev link listening on {} (session token: {})", addr, token)
+                    }
+                    _ => info!("Dev link off (/devlink on to start)"),
+                }
+            }
+        }
+    }
+}
+
+/// Answer pending requests, applying the messages they carry
+fn poll_devlink(
+    devlink: Res<DevLink>,
+    mut registry: ResMut<GameRegistry>,
+    mut mod_data: ResMut<LoadedModData>,
+    mut reloaded: MessageWriter<AssetsReloaded>,
+) {
+    let Some(listener) = devlink.listener.as_ref() else {
+        return;
+    };
+    loop {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                warn!("Dev link accept failed: {}", e);
+                break;
+            }
+        };
+        let result = read_message(&mut stream, &devlink.token).and_then(|message| {
+            apply_message(message, &mut registry, &mut mod_data).map_err(|e| (400, e))
+        });
+        let (status, body) = match result {
+            Ok(summary) => {
+                info!("Dev link: {}", summary);
+                reloaded.write(AssetsReloaded);
+                (200, summary)
+            }
+            Err((status, error)) => {
+                warn!("Dev link request rejected: {}", error);
+                (status, error)
+            }
+        };
+        write_response(&mut stream, status, &body);
+    }
+}
+
+/// Apply one message to the registries, returning a short summary
+///
+/// A `reload_all` stops at the first definition that can't be applied.
+pub fn apply_message(
+    message: DevLinkMessage,
+    registry: &mut GameRegistry,
+    mod_data: &mut LoadedModData,
+) -> Result<String, String> {
+    match message {
+        DevLinkMessage::UpsertItem { item } => {
+            let summary = format!("item '{}' updated", item.id);
+            upsert_item(item, registry, mod_data);
+            Ok(summary)
+        }
+        DevLinkMessage::UpsertRecipe { recipe } => {
+            let summary = format!("recipe '{}' updated", recipe.id);
+            upsert_recipe(recipe, registry, mod_data)?;
+            Ok(summary)
+        }
+        DevLinkMessage::ReloadAll { items, recipes } => {
+            let summary = format!(
+                "{} items and {} recipes updated",
+                items.len(),
+                recipes.len()
+            );
+            for item in items {
+                upsert_item(item, registry, mod_data);
+            }
+            for recipe in recipes {
+                upsert_recipe(recipe, registry, mod_data)?;
+            }
+            Ok(summary)
+        }
+    }
+}
+
+/// Built-in items keep their compiled descriptor; only their data pack entry
+/// (description, tooltip text) changes
+fn upsert_item(def: ItemDefinition, registry: &mut GameRegistry, mod_data: &mut LoadedModData) {
+    let item_id = data_item_id(DEVLINK_MOD_ID, &def.id);
+    if items::is_runtime(item_id) {
+        let descriptor = descriptor_from_definition(&def);
+        register_item_descriptor(item_id, descriptor.clone());
+        registry.register_mod_item(item_id, descriptor);
+    }
+    let pack = devlink_pack(mod_data);
+    match pack.items.iter_mut().find(|existing| existing.id == def.id) {
+        Some(existing) => *existing = def,
+        None => pack.items.push(def),
+    }
+}
+
+fn upsert_recipe(
+    def: RecipeDefinition,
+    registry: &mut GameRegistry,
+    mod_data: &mut LoadedModData,
+) -> Result<(), String> {
+    let recipe = recipe_from_definition(DEVLINK_MOD_ID, &def).ok_or_else(|| {
+        format!(
+            "recipe '{}' uses an unknown item or has no inputs/outputs",
+            def.id
+        )
+    })?;
+    crate::game_spec::recipes::upsert_recipe(recipe);
+    registry.refresh_recipes();
+    let pack = devlink_pack(mod_data);
+    match pack
+        .recipes
+        .iter_mut()
+        .find(|existing| existing.id == def.id)
+    {
+        Some(existing) => *existing = def,
+        None => pack.recipes.push(def),
+    }
+    Ok(())
+}
+
+fn devlink_pack(mod_data: &mut LoadedModData) -> &mut ModDataPack {
+    let index = match mod_data
+        .packs
+        .iter()
+        .position(|(mod_id, _)| mod_id == DEVLINK_MOD_ID)
+    {
+        Some(index) => index,
+        None => {
+            mod_data
+                .packs
+                .push((DEVLINK_MOD_ID.to_string(), ModDataPack::default()));
+            mod_data.packs.len() - 1
+        }
+    };
+    &mut mod_data.packs[index].1
+}
+
+/// Read one HTTP request and check its token
+fn read_message(stream: &mut TcpStream, token: &str) -> Result<DevLinkMessage, (u16, String)> {
+    let request = read_request(stream).map_err(|e| (400, format!("bad request: {}", e)))?;
+    if request.method != "POST" {
+        return Err((405, "use POST".to_string()));
+    }
+    let bearer = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if bearer != Some(token) {
+        return Err((401, "missing or wrong session token".to_string()));
+    }
+    serde_json::from_slice(&request.body).map_err(|e| (400, format!("invalid message: {}", e)))
+}
+
+/// The parts of an HTTP request the live link uses
+struct HttpRequest {
+    method: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &mut TcpStream) -> std::io::Result<HttpRequest> {
+    let invalid = |msg: &str| std::io::Error::new(ErrorKind::InvalidData, msg.to_string());
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_REQUEST_BYTES {
+            return Err(invalid("request too large"));
+        }
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(invalid("connection closed mid-request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buf[..header_end]).map_err(|_| invalid("headers not UTF-8"))?;
+    let mut lines = head.lines();
+    let method = lines
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .unwrap_or_default()
+        .to_string();
+    let mut content_length = 0;
+    let mut authorization = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid("bad Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.trim().to_string());
+        }
+    }
+    if header_end + content_length > MAX_REQUEST_BYTES {
+        return Err(invalid("request too large"));
+    }
+
+    let mut body = buf.split_off(header_end);
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(invalid("connection closed mid-body"));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+    Ok(HttpRequest {
+        method,
+        authorization,
+        body,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) {
+    let reason = match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        _ => "Bad Request",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()) {
+        warn!("Dev link failed to answer: {}", e);
+    }
+}
+
+/// Editor live link plugin (the listener stays off until `/devlink on`)
+pub struct DevLinkPlugin;
+
+impl Plugin for DevLinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DevLink>()
+            .add_message::<DevLinkCommandEvent>()
+            .add_message::<AssetsReloaded>()
+            .add_systems(
+                Update,
+                (
+                    handle_devlink_command,
+                    poll_devlink,
+                    build_item_info_cache.run_if(on_message::<AssetsReloaded>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// POST `body` with `token` from another thread, returning the raw response
+    fn post(addr: SocketAddr, token: &str, body: &str) -> std::thread::JoinHandle<String> {
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+            token,
+            body.len(),
+            body
+        );
+        std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        })
+    }
+
+    /// Update the app until the request thread has its answer
+    fn answer(app: &mut App, request: std::thread::JoinHandle<String>) -> String {
+        for _ in 0..200 {
+            app.update();
+            if request.is_finished() {
+                return request.join().unwrap();
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("dev link never answered");
+    }
+
+    fn headless_app() -> (App, SocketAddr, String) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<GameRegistry>()
+            .init_resource::<LoadedModData>()
+            .init_resource::<crate::localization::Localization>()
+            .add_plugins(DevLinkPlugin);
+        let mut devlink = app.world_mut().resource_mut::<DevLink>();
+        let addr = devlink.start(0).unwrap();
+        let token = devlink.token().unwrap().to_string();
+        (app, addr, token)
+    }
+
+    #[test]
+    fn test_pushed_recipe_reaches_registry() {
+        let (mut app, addr, token) = headless_app();
+        assert!(addr.ip().is_loopback());
+
+        let message = r#"{"type": "upsert_recipe", "recipe": {
+            "id": "devlink_test_press_dust", "machine": "furnace",
+            "inputs": {"iron_dust": 2}, "outputs": {"stone": 1}}}"#;
+        let response = answer(&mut app, post(addr, &token, message));
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+        let registry = app.world().resource::<GameRegistry>();
+        assert!(registry
+            .recipes()
+            .iter()
+            .any(|r| r.id == "devlink_test_press_dust"));
+        assert!(app
+            .world()
+            .contains_resource::<crate::game_spec::ItemInfoCache>());
+    }
+
+    #[test]
+    fn test_wrong_token_is_rejected() {
+        let (mut app, addr, _) = headless_app();
+
+        let message = r#"{"type": "upsert_recipe", "recipe": {
+            "id": "devlink_test_rejected", "machine": "furnace",
+            "inputs": {"iron_dust": 1}, "outputs": {"stone": 1}}}"#;
+        let response = answer(&mut app, post(addr, "not-the-token", message));
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+        assert!(!crate::game_spec::recipes::all_recipes()
+            .iter()
+            .any(|r| r.id == "devlink_test_rejected"));
+    }
+}
@@ -71,6 +71,8 @@ pub struct ConveyorTransfer {
     pub from_pos: IVec3,
     pub to_pos: IVec3,
     pub item: ItemId,
+    /// Items in the transferred stack
+    pub count: u32,
 }
 
 /// アイテム納品イベント
@@ -78,6 +80,16 @@ pub struct ConveyorTransfer {
 pub struct ItemDelivered {
     pub item: ItemId,
     pub count: u32,
+    /// 納品先のプラットフォーム
+    pub platform: Entity,
+}
+
+// ========== クエスト系 ==========
+
+/// クエスト報酬受取イベント
+#[derive(Message, Debug)]
+pub struct QuestRewardsClaimed {
+    pub quest_id: &'static str,
 }
 
 /// イベント登録プラグイン
@@ -92,7 +104,8 @@ impl Plugin for GameEventsExtPlugin {
             .add_message::<MachineCompleted>()
             .add_message::<InventoryChanged>()
             .add_message::<ConveyorTransfer>()
-            .add_message::<ItemDelivered>();
+            .add_message::<ItemDelivered>()
+            .add_message::<QuestRewardsClaimed>();
     }
 }
 
@@ -0,0 +1,711 @@
+//! Fluid system: pipes, tanks and fluid networks
+//!
+//! Pipes and tanks are regular world blocks (so placement, breaking and
+//! world saves already handle them). Face-adjacent pipes/tanks form a
+//! `PipeNetwork` that holds a single fluid type and a shared amount.
+//!
+//! - Placing a block next to several networks merges them
+//! - Breaking a block splits its network, dividing the fluid by capacity
+//! - Recipe machines touching a network draw `Recipe::fluid_input` from it
+//!   and push `Recipe::fluid_output` into it (`FluidNetworks::extract_at` /
+//!   `insert_at`)
+//! - Tanks show their fill level as a strip that grows up the tank's side
+
+use crate::core::{fluids, items, FluidId, ItemId};
+use crate::events::game_events::{BlockBroken, BlockPlaced};
+use crate::main_menu::InGameSet;
+use crate::BLOCK_SIZE;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Fluid capacity of a single pipe block
+pub const PIPE_CAPACITY: f32 = 10.0;
+
+/// Fluid capacity of a single tank block
+pub const TANK_CAPACITY: f32 = 1000.0;
+
+/// Face-adjacent neighbor offsets
+const NEIGHBORS: [IVec3; 6] = [
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::Z,
+    IVec3::NEG_Z,
+];
+
+/// Fluid capacity contributed by a block (0.0 for non-fluid blocks)
+pub fn block_capacity(item_id: ItemId) -> f32 {
+    if item_id == items::tank_block() {
+        TANK_CAPACITY
+    } else if item_id == items::pipe_block() {
+        PIPE_CAPACITY
+    } else {
+        0.0
+    }
+}
+
+/// A set of connected pipes/tanks sharing one fluid
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipeNetwork {
+    /// Block positions in this network
+    pub members: HashSet<IVec3>,
+    /// Fluid currently stored (None = empty)
+    pub fluid: Option<FluidId>,
+    /// Stored amount
+    pub amount: f32,
+    /// Total capacity of all members
+    pub capacity: f32,
+}
+
+impl PipeNetwork {
+    /// Fill ratio (0.0 - 1.0)
+    pub fn fill_ratio(&self) -> f32 {
+        if self.capacity <= 0.0 {
+            0.0
+        } else {
+            (self.amount / self.capacity).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Insert fluid, returns the amount actually accepted
+    pub fn insert(&mut self, fluid: FluidId, amount: f32) -> f32 {
+        if self.fluid.is_some_and(|f| f != fluid) {
+            return 0.0;
+        }
+        let accepted = amount.min(self.capacity - self.amount).max(0.0);
+        if accepted > 0.0 {
+            self.fluid = Some(fluid);
+            self.amount += accepted;
+        }
+        accepted
+    }
+
+    /// Extract fluid, returns the amount actually removed
+    pub fn extract(&mut self, fluid: FluidId, amount: f32) -> f32 {
+        if self.fluid != Some(fluid) {
+            return 0.0;
+        }
+        let removed = amount.min(self.amount).max(0.0);
+        self.amount -= removed;
+        if self.amount <= f32::EPSILON {
+            self.amount = 0.0;
+            self.fluid = None;
+        }
+        removed
+    }
+}
+
+/// All fluid networks in the world
+#[derive(Resource, Debug, Default)]
+pub struct FluidNetworks {
+    networks: HashMap<u32, PipeNetwork>,
+    /// Block position -> network ID
+    by_pos: HashMap<IVec3, u32>,
+    /// Block position -> capacity contributed by that block
+    capacities: HashMap<IVec3, f32>,
+    next_id: u32,
+}
+
+impl FluidNetworks {
+    /// Number of networks
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Network containing the block at `pos`
+    pub fn network_at(&self, pos: IVec3) -> Option<&PipeNetwork> {
+        self.by_pos.get(&pos).and_then(|id| self.networks.get(id))
+    }
+
+    fn network_at_mut(&mut self, pos: IVec3) -> Option<&mut PipeNetwork> {
+        let id = *self.by_pos.get(&pos)?;
+        self.networks.get_mut(&id)
+    }
+
+    /// Iterate over all networks
+    pub fn iter(&self) -> impl Iterator<Item = &PipeNetwork> {
+        self.networks.values()
+    }
+
+    /// IDs of the networks touching `pos`, each once, in neighbor order
+    fn adjacent_ids(&self, pos: IVec3) -> Vec<u32> {
+        let mut ids: Vec<u32> = Vec::new();
+        for offset in NEIGHBORS {
+            if let Some(id) = self.by_pos.get(&(pos + offset)) {
+                if !ids.contains(id) {
+                    ids.push(*id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Adjacent network that can take `fluid` (holds it already, or is empty)
+    fn accepting_id(&self, pos: IVec3, fluid: FluidId) -> Option<u32> {
+        let ids = self.adjacent_ids(pos);
+        let holds = |id: &u32| self.networks[id].fluid == Some(fluid);
+        let empty = |id: &u32| self.networks[id].fluid.is_none();
+        ids.iter()
+            .find(|id| holds(id))
+            .or_else(|| ids.iter().find(|id| empty(id)))
+            .copied()
+    }
+
+    /// Adjacent network holding `fluid`
+    fn holding_id(&self, pos: IVec3, fluid: FluidId) -> Option<u32> {
+        self.adjacent_ids(pos)
+            .into_iter()
+            .find(|id| self.networks[id].fluid == Some(fluid))
+    }
+
+    /// Free space for `fluid` in the networks adjacent to `pos`
+    pub fn space_at(&self, pos: IVec3, fluid: FluidId) -> f32 {
+        self.accepting_id(pos, fluid)
+            .map(|id| {
+                let network = &self.networks[&id];
+                network.capacity - network.amount
+            })
+            .unwrap_or(0.0)
+    }
+
+    /// Amount of `fluid` a machine at `pos` can draw from an adjacent network
+    pub fn available_at(&self, pos: IVec3, fluid: FluidId) -> f32 {
+        self.holding_id(pos, fluid)
+            .map(|id| self.networks[&id].amount)
+            .unwrap_or(0.0)
+    }
+
+    /// Insert fluid into a network adjacent to `pos`, returns accepted amount
+    ///
+    /// Prefers a network already holding `fluid`, then an empty one.
+    pub fn insert_at(&mut self, pos: IVec3, fluid: FluidId, amount: f32) -> f32 {
+        let Some(id) = self.accepting_id(pos, fluid) else {
+            return 0.0;
+        };
+        self.networks
+            .get_mut(&id)
+            .map(|n| n.insert(fluid, amount))
+            .unwrap_or(0.0)
+    }
+
+    /// Extract fluid from a network adjacent to `pos`, returns removed amount
+    pub fn extract_at(&mut self, pos: IVec3, fluid: FluidId, amount: f32) -> f32 {
+        let Some(id) = self.holding_id(pos, fluid) else {
+            return 0.0;
+        };
+        self.networks
+            .get_mut(&id)
+            .map(|n| n.extract(fluid, amount))
+            .unwrap_or(0.0)
+    }
+
+    fn allocate_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Add a pipe/tank block, merging any networks it connects
+    ///
+    /// When merged networks hold different fluids, the one with the larger
+    /// amount wins and the other fluid is discarded.
+    pub fn add_block(&mut self, pos: IVec3, capacity: f32) {
+        if self.by_pos.contains_key(&pos) {
+            return;
+        }
+
+        let mut neighbor_ids: Vec<u32> = NEIGHBORS
+            .iter()
+            .filter_map(|offset| self.by_pos.get(&(pos + *offset)).copied())
+            .collect();
+        neighbor_ids.sort_unstable();
+        neighbor_ids.dedup();
+
+        let id = neighbor_ids
+            .first()
+            .copied()
+            .unwrap_or_else(|| self.allocate_id());
+        let mut merged = self.networks.remove(&id).unwrap_or(PipeNetwork {
+            members: HashSet::new(),
+            fluid: None,
+            amount: 0.0,
+            capacity: 0.0,
+        });
+
+        for other_id in neighbor_ids.iter().skip(1) {
+            let Some(other) = self.networks.remove(other_id) else {
+                continue;
+            };
+            let conflict =
+                merged.fluid.is_some() && other.fluid.is_some() && merged.fluid != other.fluid;
+            if conflict {
+                if other.amount > merged.amount {
+                    merged.fluid = other.fluid;
+                    merged.amount = other.amount;
+                }
+            } else {
+                merged.fluid = merged.fluid.or(other.fluid);
+                merged.amount += other.amount;
+            }
+            merged.capacity += other.capacity;
+            for member in &other.members {
+                self.by_pos.insert(*member, id);
+            }
+            merged.members.extend(other.members);
+        }
+
+        merged.members.insert(pos);
+        merged.capacity += capacity;
+        self.by_pos.insert(pos, id);
+        self.capacities.insert(pos, capacity);
+        self.networks.insert(id, merged);
+    }
+
+    /// Remove a pipe/tank block, splitting its network if needed
+    ///
+    /// The fluid that no longer fits is lost; the rest is divided between
+    /// the resulting networks in proportion to their capacity.
+    pub fn remove_block(&mut self, pos: IVec3) {
+        let Some(id) = self.by_pos.remove(&pos) else {
+            return;
+        };
+        self.capacities.remove(&pos);
+        let Some(mut network) = self.networks.remove(&id) else {
+            return;
+        };
+        network.members.remove(&pos);
+        if network.members.is_empty() {
+            return;
+        }
+
+        let components = connected_components(&network.members);
+        let capacity_of = |members: &HashSet<IVec3>| -> f32 {
+            members
+                .iter()
+                .map(|p| self.capacities.get(p).copied().unwrap_or(0.0))
+                .sum()
+        };
+        let remaining_capacity: f32 = components.iter().map(&capacity_of).sum();
+        let amount = network.amount.min(remaining_capacity);
+
+        let parts: Vec<(HashSet<IVec3>, f32)> = components
+            .into_iter()
+            .map(|members| {
+                let capacity = capacity_of(&members);
+                (members, capacity)
+            })
+            .collect();
+
+        for (i, (members, capacity)) in parts.into_iter().enumerate() {
+            let part_id = if i == 0 { id } else { self.allocate_id() };
+            let share = if remaining_capacity > 0.0 {
+                amount * capacity / remaining_capacity
+            } else {
+                0.0
+            };
+            for member in &members {
+                self.by_pos.insert(*member, part_id);
+            }
+            self.networks.insert(
+                part_id,
+                PipeNetwork {
+                    members,
+                    fluid: if share > 0.0 { network.fluid } else { None },
+                    amount: share,
+                    capacity,
+                },
+            );
+        }
+    }
+
+    /// Rebuild all networks from a set of fluid blocks (amounts reset)
+    pub fn rebuild(&mut self, blocks: impl IntoIterator<Item = (IVec3, f32)>) {
+        *self = Self::default();
+        for (pos, capacity) in blocks {
+            self.add_block(pos, capacity);
+        }
+    }
+
+    /// Restore a saved fluid amount onto the network containing `pos`
+    pub fn restore(&mut self, pos: IVec3, fluid: FluidId, amount: f32) {
+        if let Some(network) = self.network_at_mut(pos) {
+            network.fluid = Some(fluid);
+            network.amount = amount.clamp(0.0, network.capacity);
+        }
+    }
+}
+
+/// Split positions into face-connected groups (flood fill)
+pub fn connected_components(positions: &HashSet<IVec3>) -> Vec<HashSet<IVec3>> {
+    let mut visited: HashSet<IVec3> = HashSet::new();
+    let mut components = Vec::new();
+
+    // Sorted start order keeps the result deterministic
+    let mut starts: Vec<IVec3> = positions.iter().copied().collect();
+    starts.sort_by_key(|p| (p.x, p.y, p.z));
+
+    for start in starts {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            for offset in NEIGHBORS {
+                let next = current + offset;
+                if positions.contains(&next) && visited.insert(next) {
+                    component.insert(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Update networks when pipes/tanks are placed or broken
+fn track_fluid_blocks(
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+    mut networks: ResMut<FluidNetworks>,
+) {
+    for event in placed.read() {
+        if items::is_fluid_block(event.block) {
+            networks.add_block(event.pos, block_capacity(event.block));
+        }
+    }
+    for event in broken.read() {
+        if items::is_fluid_block(event.block) {
+            networks.remove_block(event.pos);
+        }
+    }
+}
+
+/// Gauge anchored at a tank block's center
+#[derive(Component)]
+pub struct TankGauge {
+    pub position: IVec3,
+}
+
+/// Level strip on the tank's south face, child of `TankGauge`
+///
+/// Its height scale follows the network's fill ratio.
+#[derive(Component)]
+pub struct TankGaugeLevel;
+
+/// Shared mesh/material for tank gauges
+#[derive(Resource)]
+pub struct TankGaugeAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Height of a full gauge strip (blocks)
+const GAUGE_HEIGHT: f32 = 0.9;
+
+fn setup_tank_gauge_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(TankGaugeAssets {
+        mesh: meshes.add(Cuboid::new(
+            BLOCK_SIZE * 0.2,
+            BLOCK_SIZE * GAUGE_HEIGHT,
+            BLOCK_SIZE * 0.02,
+        )),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.2, 0.5, 1.0, 0.8),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+    });
+}
+
+/// Level strip transform relative to the tank center, filled from the bottom
+fn gauge_level_transform(fill: f32) -> Transform {
+    let scale = fill.clamp(0.001, 1.0);
+    let y = (scale - 1.0) * GAUGE_HEIGHT / 2.0;
+    Transform::from_xyz(0.0, y * BLOCK_SIZE, BLOCK_SIZE * 0.52)
+        .with_scale(Vec3::new(1.0, scale, 1.0))
+}
+
+/// Spawn and despawn tank gauges, and scale their level strips to the fill level
+fn update_tank_gauges(
+    mut commands: Commands,
+    networks: Res<FluidNetworks>,
+    assets: Option<Res<TankGaugeAssets>>,
+    gauge_query: Query<(Entity, &TankGauge)>,
+    mut level_query: Query<(&ChildOf, &mut Transform), With<TankGaugeLevel>>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+    if !networks.is_changed() {
+        return;
+    }
+
+    let tanks: HashMap<IVec3, f32> = networks
+        .capacities
+        .iter()
+        .filter(|(_, capacity)| **capacity >= TANK_CAPACITY)
+        .filter_map(|(pos, _)| networks.network_at(*pos).map(|n| (*pos, n.fill_ratio())))
+        .collect();
+
+    let mut has_gauge: HashSet<IVec3> = HashSet::new();
+    for (entity, gauge) in gauge_query.iter() {
+        if tanks.contains_key(&gauge.position) {
+            has_gauge.insert(gauge.position);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (child_of, mut transform) in level_query.iter_mut() {
+        let fill = gauge_query
+            .get(child_of.parent())
+            .ok()
+            .and_then(|(_, gauge)| tanks.get(&gauge.position));
+        if let Some(fill) = fill {
+            *transform = gauge_level_transform(*fill);
+        }
+    }
+
+    for (pos, fill) in tanks {
+        if has_gauge.contains(&pos) {
+            continue;
+        }
+        let center = Vec3::new(
+            pos.x as f32 * BLOCK_SIZE + 0.5,
+            pos.y as f32 * BLOCK_SIZE + 0.5,
+            pos.z as f32 * BLOCK_SIZE + 0.5,
+        );
+        commands
+            .spawn((
+                Transform::from_translation(center),
+                Visibility::default(),
+                TankGauge { position: pos },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Mesh3d(assets.mesh.clone()),
+                    MeshMaterial3d(assets.material.clone()),
+                    gauge_level_transform(fill),
+                    TankGaugeLevel,
+                ));
+            });
+    }
+}
+
+/// Save data for one network (any member position identifies it)
+pub fn network_save_entries(networks: &FluidNetworks) -> Vec<(IVec3, &'static str, f32)> {
+    networks
+        .iter()
+        .filter(|n| n.amount > 0.0)
+        .filter_map(|n| {
+            let fluid = fluids::string_id(n.fluid?)?;
+            let pos = n.members.iter().min_by_key(|p| (p.x, p.y, p.z))?;
+            Some((*pos, fluid, n.amount))
+        })
+        .collect()
+}
+
+pub struct FluidsPlugin;
+
+impl Plugin for FluidsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FluidNetworks>()
+            .add_systems(Startup, setup_tank_gauge_assets)
+            .add_systems(
+                Update,
+                (track_fluid_blocks, update_tank_gauges)
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(from: i32, to: i32) -> Vec<IVec3> {
+        (from..=to).map(|x| IVec3::new(x, 8, 0)).collect()
+    }
+
+    #[test]
+    fn test_connected_components_flood_fill() {
+        let mut positions: HashSet<IVec3> = line(0, 3).into_iter().collect();
+        positions.insert(IVec3::new(10, 8, 0));
+        positions.insert(IVec3::new(10, 9, 0));
+        // Diagonal does not connect
+        positions.insert(IVec3::new(11, 10, 0));
+
+        let components = connected_components(&positions);
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0].len(), 4);
+        assert_eq!(components[1].len(), 2);
+        assert_eq!(components[2].len(), 1);
+    }
+
+    #[test]
+    fn test_add_block_merges_networks() {
+        let mut networks = FluidNetworks::default();
+        networks.add_block(IVec3::new(0, 8, 0), TANK_CAPACITY);
+        networks.add_block(IVec3::new(2, 8, 0), TANK_CAPACITY);
+        assert_eq!(networks.len(), 2);
+
+        networks.restore(IVec3::new(0, 8, 0), fluids::water(), 100.0);
+        networks.restore(IVec3::new(2, 8, 0), fluids::water(), 50.0);
+
+        // Pipe in between joins both tanks
+        networks.add_block(IVec3::new(1, 8, 0), PIPE_CAPACITY);
+        assert_eq!(networks.len(), 1);
+
+        let network = networks.network_at(IVec3::new(1, 8, 0)).unwrap();
+        assert_eq!(network.members.len(), 3);
+        assert_eq!(network.amount, 150.0);
+        assert_eq!(network.capacity, TANK_CAPACITY * 2.0 + PIPE_CAPACITY);
+        assert_eq!(network.fluid, Some(fluids::water()));
+    }
+
+    #[test]
+    fn test_merge_conflicting_fluids_keeps_larger() {
+        let mut networks = FluidNetworks::default();
+        networks.add_block(IVec3::new(0, 8, 0), TANK_CAPACITY);
+        networks.add_block(IVec3::new(2, 8, 0), TANK_CAPACITY);
+        networks.restore(IVec3::new(0, 8, 0), fluids::water(), 100.0);
+        networks.restore(IVec3::new(2, 8, 0), fluids::steam(), 300.0);
+
+        networks.add_block(IVec3::new(1, 8, 0), PIPE_CAPACITY);
+
+        let network = networks.network_at(IVec3::new(0, 8, 0)).unwrap();
+        assert_eq!(network.fluid, Some(fluids::steam()));
+        assert_eq!(network.amount, 300.0);
+    }
+
+    #[test]
+    fn test_remove_block_splits_by_capacity() {
+        let mut networks = FluidNetworks::default();
+        // Tank - pipe - pipe - tank - tank
+        networks.add_block(IVec3::new(0, 8, 0), TANK_CAPACITY);
+        networks.add_block(IVec3::new(1, 8, 0), PIPE_CAPACITY);
+        networks.add_block(IVec3::new(2, 8, 0), PIPE_CAPACITY);
+        networks.add_block(IVec3::new(3, 8, 0), TANK_CAPACITY);
+        networks.add_block(IVec3::new(4, 8, 0), TANK_CAPACITY);
+        networks.restore(IVec3::new(0, 8, 0), fluids::water(), 1500.0);
+
+        networks.remove_block(IVec3::new(2, 8, 0));
+        assert_eq!(networks.len(), 2);
+
+        let left = networks.network_at(IVec3::new(0, 8, 0)).unwrap();
+        let right = networks.network_at(IVec3::new(4, 8, 0)).unwrap();
+        let total_capacity = TANK_CAPACITY * 3.0 + PIPE_CAPACITY;
+        let left_capacity = TANK_CAPACITY + PIPE_CAPACITY;
+
+        assert!((left.amount - 1500.0 * left_capacity / total_capacity).abs() < 0.01);
+        assert!((right.amount - 1500.0 * TANK_CAPACITY * 2.0 / total_capacity).abs() < 0.01);
+        assert!((left.amount + right.amount - 1500.0).abs() < 0.01);
+        assert_eq!(right.fluid, Some(fluids::water()));
+    }
+
+    #[test]
+    fn test_remove_block_clamps_to_remaining_capacity() {
+        let mut networks = FluidNetworks::default();
+        networks.add_block(IVec3::new(0, 8, 0), TANK_CAPACITY);
+        networks.add_block(IVec3::new(1, 8, 0), PIPE_CAPACITY);
+        networks.restore(IVec3::new(0, 8, 0), fluids::water(), TANK_CAPACITY);
+
+        networks.remove_block(IVec3::new(0, 8, 0));
+        let pipe = networks.network_at(IVec3::new(1, 8, 0)).unwrap();
+        assert_eq!(pipe.amount, PIPE_CAPACITY);
+
+        networks.remove_block(IVec3::new(1, 8, 0));
+        assert!(networks.is_empty());
+    }
+
+    #[test]
+    fn test_insert_and_extract_at_adjacent() {
+        let mut networks = FluidNetworks::default();
+        networks.add_block(IVec3::new(0, 8, 0), PIPE_CAPACITY);
+        let machine_pos = IVec3::new(0, 8, 1);
+
+        assert_eq!(
+            networks.insert_at(machine_pos, fluids::water(), 25.0),
+            PIPE_CAPACITY
+        );
+        // Different fluid is rejected
+        assert_eq!(networks.insert_at(machine_pos, fluids::steam(), 1.0), 0.0);
+        assert_eq!(networks.extract_at(machine_pos, fluids::water(), 4.0), 4.0);
+        assert_eq!(
+            networks.extract_at(machine_pos, fluids::water(), 100.0),
+            6.0
+        );
+        assert_eq!(
+            networks.network_at(IVec3::new(0, 8, 0)).unwrap().fluid,
+            None
+        );
+
+        // Not adjacent
+        assert_eq!(
+            networks.insert_at(IVec3::new(5, 8, 5), fluids::water(), 1.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_gauge_level_fills_from_bottom() {
+        let bottom = |t: Transform| t.translation.y - t.scale.y * GAUGE_HEIGHT * BLOCK_SIZE / 2.0;
+        let half = gauge_level_transform(0.5);
+        let full = gauge_level_transform(1.0);
+        assert_eq!(half.scale.y, 0.5);
+        assert_eq!(full.scale.y, 1.0);
+        assert!((bottom(half) - bottom(full)).abs() < 1e-5);
+        assert!(gauge_level_transform(0.0).scale.y > 0.0);
+    }
+
+    #[test]
+    fn test_machine_between_two_networks_picks_by_fluid() {
+        let mut networks = FluidNetworks::default();
+        let machine_pos = IVec3::new(0, 8, 0);
+        networks.add_block(machine_pos + IVec3::X, PIPE_CAPACITY);
+        networks.add_block(machine_pos - IVec3::X, TANK_CAPACITY);
+        networks.insert_at(machine_pos, fluids::steam(), 5.0);
+
+        // Steam went to the first neighbor; water takes the empty tank
+        assert_eq!(networks.available_at(machine_pos, fluids::steam()), 5.0);
+        assert_eq!(
+            networks.space_at(machine_pos, fluids::water()),
+            TANK_CAPACITY
+        );
+        assert_eq!(networks.insert_at(machine_pos, fluids::water(), 40.0), 40.0);
+        assert_eq!(networks.available_at(machine_pos, fluids::water()), 40.0);
+        assert_eq!(
+            networks.extract_at(machine_pos, fluids::water(), 15.0),
+            15.0
+        );
+        assert_eq!(networks.space_at(machine_pos, fluids::steam()), 5.0);
+
+        let tank = networks.network_at(machine_pos - IVec3::X).unwrap();
+        assert_eq!((tank.fluid, tank.amount), (Some(fluids::water()), 25.0));
+    }
+
+    #[test]
+    fn test_rebuild_and_save_entries() {
+        let mut networks = FluidNetworks::default();
+        networks.rebuild([
+            (IVec3::new(0, 8, 0), TANK_CAPACITY),
+            (IVec3::new(1, 8, 0), PIPE_CAPACITY),
+        ]);
+        networks.restore(IVec3::new(1, 8, 0), fluids::water(), 42.0);
+
+        let entries = network_save_entries(&networks);
+        assert_eq!(entries, vec![(IVec3::new(0, 8, 0), "base:water", 42.0)]);
+    }
+}
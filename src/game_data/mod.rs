@@ -4,6 +4,7 @@
 //! This allows for easy modding and data-driven game design.
 
 use crate::core::ItemId;
+use crate::game_spec::UnlockCondition;
 use bevy::prelude::*;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -17,19 +18,41 @@ pub use loader::*;
 // =============================================================================
 
 /// Recipe input from JSON
+///
+/// Either `{"item": "iron_ore", ...}` or `{"tag": "raw_ore", ...}`.
 #[derive(Debug, Clone, Deserialize)]
 pub struct RecipeInputData {
-    pub item: String,
+    #[serde(default)]
+    pub item: Option<String>,
+    /// Accept any item with this tag (see tags.yaml)
+    #[serde(default)]
+    pub tag: Option<String>,
     pub count: u32,
+    #[serde(default)]
     pub slot: u8,
 }
 
+impl RecipeInputData {
+    /// Check whether `item` (short name, e.g. "iron_ore") satisfies this input
+    pub fn matches(&self, item: &str) -> bool {
+        if let Some(name) = &self.item {
+            return name == item;
+        }
+        self.tag.as_deref().is_some_and(|tag| {
+            crate::core::items::by_name(item).is_some_and(|id| crate::game_spec::has_tag(id, tag))
+        })
+    }
+}
+
 /// Recipe output from JSON
 #[derive(Debug, Clone, Deserialize)]
 pub struct RecipeOutputData {
     pub item: String,
     pub count: u32,
     pub chance: f32,
+    /// Only produced for this input item (tag recipes)
+    #[serde(default)]
+    pub for_input: Option<String>,
 }
 
 /// Fuel requirement from JSON
@@ -48,6 +71,9 @@ pub struct RecipeData {
     pub outputs: Vec<RecipeOutputData>,
     pub craft_time: f32,
     pub fuel: Option<FuelData>,
+    /// Unlock condition (missing = always available)
+    #[serde(default)]
+    pub unlock: UnlockCondition,
 }
 
 /// Recipes file structure
@@ -193,7 +219,7 @@ impl GameData {
             .unwrap_or_default();
         self.get_recipes_for_machine(machine)
             .into_iter()
-            .find(|r| r.inputs.iter().any(|i| i.item == input_str))
+            .find(|r| r.inputs.iter().any(|i| i.matches(input_str)))
     }
 
     /// Get machine data by item id
@@ -278,4 +304,53 @@ mod tests {
         assert_eq!(parsed[0], (crate::core::items::iron_ingot(), 10));
         assert_eq!(parsed[1], (crate::core::items::coal(), 5));
     }
+
+    #[test]
+    fn test_recipe_data_tag_input() {
+        let json = r#"{
+            "id": "smelt_raw_ore", "machine": "furnace",
+            "inputs": [{"tag": "raw_ore", "count": 1}],
+            "outputs": [
+                {"item": "iron_ingot", "count": 1, "chance": 1.0, "for_input": "iron_ore"},
+                {"item": "copper_ingot", "count": 1, "chance": 1.0, "for_input": "copper_ore"}
+            ],
+            "craft_time": 2.0, "fuel": null
+        }"#;
+        let recipe: RecipeData = serde_json::from_str(json).unwrap();
+        assert_eq!(recipe.inputs[0].tag.as_deref(), Some("raw_ore"));
+        assert_eq!(recipe.inputs[0].slot, 0);
+        assert!(recipe.inputs[0].matches("iron_ore"));
+        assert!(recipe.inputs[0].matches("copper_ore"));
+        assert!(!recipe.inputs[0].matches("stone"));
+
+        let mut data = GameData {
+            recipes: vec![recipe],
+            ..Default::default()
+        };
+        data.build_indices();
+        let found = data.find_recipe("furnace", crate::core::items::copper_ore());
+        assert_eq!(found.map(|r| r.id.as_str()), Some("smelt_raw_ore"));
+    }
+
+    #[test]
+    fn test_recipe_data_unlock_backward_compat() {
+        let legacy = r#"{
+            "id": "smelt_iron", "machine": "furnace",
+            "inputs": [{"item": "iron_ore", "count": 1, "slot": 0}],
+            "outputs": [{"item": "iron_ingot", "count": 1, "chance": 1.0}],
+            "craft_time": 2.0, "fuel": null
+        }"#;
+        let recipe: RecipeData = serde_json::from_str(legacy).unwrap();
+        assert_eq!(recipe.unlock, UnlockCondition::Always);
+
+        let gated = legacy.replace(
+            r#""fuel": null"#,
+            r#""fuel": null, "unlock": {"QuestCompleted": "main_1"}"#,
+        );
+        let recipe: RecipeData = serde_json::from_str(&gated).unwrap();
+        assert_eq!(
+            recipe.unlock,
+            UnlockCondition::QuestCompleted("main_1".to_string())
+        );
+    }
 }
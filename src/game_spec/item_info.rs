@@ -0,0 +1,323 @@
+//! Precomputed item info for tooltips
+//!
+//! Names, descriptions and recipe usage are built once after the base mod
+//! loads (`ItemInfoCache`) instead of being looked up every frame. Line
+//! labels come from the locale files (`ui.tooltip.*`).
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::recipes::{Ingredient, MachineType, Recipe};
+use crate::core::{items, ItemId};
+use crate::localization::{LocaleFile, Localization};
+use crate::modding::data::ItemDefinition;
+
+/// Tooltip info for one item
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemInfo {
+    /// Display name (mod data name, falling back to the static descriptor)
+    pub name: String,
+    /// Description from item data (None when empty)
+    pub description: Option<String>,
+    /// "Furnace from Iron Ore x1" for the first recipe producing this item
+    pub produced_by: Option<String>,
+    /// Number of recipes that take this item as an input
+    pub used_in: usize,
+}
+
+/// Tooltip line templates from the locale ("{}" is filled in)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TooltipLabels {
+    /// "Produced by: {}"
+    pub produced_by: String,
+    /// "Used in: {} recipes"
+    pub used_in: String,
+    /// "{} from {}" (machine, inputs)
+    pub recipe_source: String,
+    /// Machine name for hand recipes
+    pub hand: String,
+}
+
+impl TooltipLabels {
+    pub fn from_locale(locale: &LocaleFile) -> Self {
+        Self {
+            produced_by: locale.text("ui.tooltip.produced_by").to_string(),
+            used_in: locale.text("ui.tooltip.used_in").to_string(),
+            recipe_source: locale.text("ui.tooltip.recipe_source").to_string(),
+            hand: locale.text("machine.hand").to_string(),
+        }
+    }
+}
+
+/// Fill each "{}" in `template` with the next argument
+fn fill(template: &str, args: &[&str]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        text.push_str(args.get(i).copied().unwrap_or_default());
+        text.push_str(part);
+    }
+    text
+}
+
+impl ItemInfo {
+    /// Multi-line tooltip text (count is shown for inventory stacks)
+    pub fn tooltip(&self, count: Option<u32>, labels: &TooltipLabels) -> String {
+        let mut lines = vec![match count {
+            Some(count) => format!("{} ({})", self.name, count),
+            None => self.name.clone(),
+        }];
+        if let Some(description) = &self.description {
+            lines.push(description.clone());
+        }
+        if let Some(produced_by) = &self.produced_by {
+            lines.push(fill(&labels.produced_by, &[produced_by.as_str()]));
+        }
+        if self.used_in > 0 {
+            lines.push(fill(&labels.used_in, &[self.used_in.to_string().as_str()]));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Item info for every known item
+#[derive(Resource, Debug, Default)]
+pub struct ItemInfoCache {
+    items: HashMap<ItemId, ItemInfo>,
+    labels: TooltipLabels,
+}
+
+impl ItemInfoCache {
+    /// Build from item IDs, item data definitions and recipes
+    ///
+    /// Definitions are matched by short ID ("iron_ore" or "base:iron_ore").
+    pub fn build<'a>(
+        item_ids: impl IntoIterator<Item = ItemId>,
+        definitions: impl IntoIterator<Item = &'a ItemDefinition>,
+        recipes: &[&Recipe],
+        locale: &LocaleFile,
+    ) -> Self {
+        let labels = TooltipLabels::from_locale(locale);
+        let definitions: HashMap<&str, &ItemDefinition> = definitions
+            .into_iter()
+            .map(|def| (def.id.strip_prefix("base:").unwrap_or(&def.id), def))
+            .collect();
+
+        let items = item_ids
+            .into_iter()
+            .map(|item_id| {
+                let short = item_id
+                    .name()
+                    .map(|n| n.split(':').next_back().unwrap_or(n))
+                    .unwrap_or_default();
+                let definition = definitions.get(short);
+                let name = definition
+                    .map(|def| def.name.clone())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| item_id.display_name().to_string());
+                let description = definition
+                    .map(|def| def.description.clone())
+                    .filter(|d| !d.is_empty());
+                let produced_by = recipes
+                    .iter()
+                    .find(|r| r.outputs.iter().any(|o| o.item == item_id))
+                    .map(|r| {
+                        let machine = machine_name(r.machine, &labels);
+                        fill(
+                            &labels.recipe_source,
+                            &[machine.as_str(), inputs_label(r).as_str()],
+                        )
+                    });
+                let used_in = recipes.iter().filter(|r| r.accepts(item_id)).count();
+                (
+                    item_id,
+                    ItemInfo {
+                        name,
+                        description,
+                        produced_by,
+                        used_in,
+                    },
+                )
+            })
+            .collect();
+        Self { items, labels }
+    }
+
+    pub fn get(&self, item_id: ItemId) -> Option<&ItemInfo> {
+        self.items.get(&item_id)
+    }
+
+    /// Tooltip text, falling back to the raw item name for unknown items
+    pub fn tooltip(&self, item_id: ItemId, count: Option<u32>) -> String {
+        match self.get(item_id) {
+            Some(info) => info.tooltip(count, &self.labels),
+            None => {
+                let name = item_id.name().unwrap_or("unknown");
+                match count {
+                    Some(count) => format!("{} ({})", name, count),
+                    None => name.to_string(),
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+fn machine_name(machine: MachineType, labels: &TooltipLabels) -> String {
+    match machine {
+        MachineType::Furnace => items::furnace_block().display_name().to_string(),
+        MachineType::Crusher => items::crusher_block().display_name().to_string(),
+        MachineType::Assembler => items::assembler_block().display_name().to_string(),
+        MachineType::Hand => labels.hand.clone(),
+        MachineType::Custom(work_type) => super::machines::machine_spec_for_type(machine)
+            .map_or(work_type, |spec| spec.name)
+            .to_string(),
+    }
+}
+
+fn inputs_label(recipe: &Recipe) -> String {
+    recipe
+        .inputs
+        .iter()
+        .map(|input| match input.ingredient {
+            Ingredient::Item(item) => format!("{} x{}", item.display_name(), input.count),
+            Ingredient::Tag(tag) => format!("#{} x{}", tag, input.count),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Build the cache once the base mod data is available
+pub fn build_item_info_cache(
+    mut commands: Commands,
+    mod_data: Res<crate::modding::LoadedModData>,
+    registry: Res<super::GameRegistry>,
+    localization: Res<Localization>,
+) {
+    let recipes: Vec<&Recipe> = super::all_recipes().iter().collect();
+    let cache = ItemInfoCache::build(
+        registry.all_item_ids(),
+        mod_data.all_items(),
+        &recipes,
+        &localization.0,
+    );
+    info!("Built item info cache for {} items", cache.len());
+    commands.insert_resource(cache);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_spec::{FuelRequirement, RecipeInput, RecipeOutput, UnlockCondition};
+
+    fn english() -> LocaleFile {
+        crate::localization::LocalizationManager::default()
+            .load("en")
+            .unwrap()
+    }
+
+    fn definition(id: &str, name: &str, description: &str) -> ItemDefinition {
+        toml::from_str(&format!(
+            "id = \"{}\"\nname = \"{}\"\ndescription = \"{}\"",
+            id, name, description
+        ))
+        .unwrap()
+    }
+
+    fn recipe(
+        id: &'static str,
+        machine: MachineType,
+        inputs: Vec<RecipeInput>,
+        output: ItemId,
+    ) -> Recipe {
+        Recipe {
+            id,
+            machine,
+            inputs,
+            outputs: vec![RecipeOutput::guaranteed(output, 1)],
+            craft_time: 1.0,
+            fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_cache_from_fixture() {
+        let definitions = [
+            definition("iron_ore", "Iron Ore", "Raw iron"),
+            definition("base:iron_ingot", "Iron Ingot", ""),
+        ];
+        let smelt = recipe(
+            "smelt",
+            MachineType::Furnace,
+            vec![RecipeInput::new(items::iron_ore(), 1, 0)],
+            items::iron_ingot(),
+        );
+        let crush = recipe(
+            "crush",
+            MachineType::Crusher,
+            vec![RecipeInput::new(items::iron_ore(), 1, 0)],
+            items::iron_dust(),
+        );
+        let conveyor = recipe(
+            "conveyor",
+            MachineType::Assembler,
+            vec![RecipeInput::new(items::iron_ingot(), 2, 0)],
+            items::conveyor_block(),
+        );
+
+        let cache = ItemInfoCache::build(
+            [items::iron_ore(), items::iron_ingot(), items::stone()],
+            &definitions,
+            &[&smelt, &crush, &conveyor],
+            &english(),
+        );
+        assert_eq!(cache.len(), 3);
+
+        let ore = cache.get(items::iron_ore()).unwrap();
+        assert_eq!(ore.description.as_deref(), Some("Raw iron"));
+        assert_eq!(ore.produced_by, None);
+        assert_eq!(ore.used_in, 2);
+
+        let ingot = cache.get(items::iron_ingot()).unwrap();
+        assert_eq!(ingot.name, "Iron Ingot");
+        assert_eq!(ingot.description, None);
+        assert_eq!(
+            ingot.produced_by.as_deref(),
+            Some(format!("{} from Iron Ore x1", items::furnace_block().display_name()).as_str())
+        );
+        assert_eq!(ingot.used_in, 1);
+
+        // No definition: static descriptor name, no recipe lines
+        let stone = cache.get(items::stone()).unwrap();
+        assert_eq!(stone.name, items::stone().display_name());
+        assert_eq!(
+            cache.tooltip(items::stone(), Some(5)),
+            format!("{} (5)", stone.name)
+        );
+    }
+
+    #[test]
+    fn test_tooltip_lines() {
+        let info = ItemInfo {
+            name: "Iron Ingot".to_string(),
+            description: Some("Refined iron".to_string()),
+            produced_by: Some("Furnace from Iron Ore x1".to_string()),
+            used_in: 3,
+        };
+        assert_eq!(
+            info.tooltip(None, &TooltipLabels::from_locale(&english())),
+            "Iron Ingot\nRefined iron\nProduced by: Furnace from Iron Ore x1\nUsed in: 3 recipes"
+        );
+    }
+}
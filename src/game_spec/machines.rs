@@ -2,8 +2,14 @@
 //!
 //! All machines are defined as `MachineSpec`.
 //! UI is automatically generated from the spec.
+//!
+//! Built-in machines are constants; data packs add more at startup
+//! (`register_data_machine`), processing the recipes of their work type.
+
+use std::sync::{PoisonError, RwLock};
 
-use crate::core::ItemId;
+use crate::core::{items, ItemId};
+use crate::modding::data::MachineDefinition;
 
 use super::recipes::MachineType;
 
@@ -29,7 +35,21 @@ pub enum PortSide {
     #[allow(dead_code)]
     Top, // Top of machine
     #[allow(dead_code)]
-    Bottom, // Bottom (unused but for future)
+    Bottom, // Bottom of machine
+}
+
+impl PortSide {
+    /// Short label for machine UIs
+    pub fn label(self) -> &'static str {
+        match self {
+            PortSide::Front => "前",
+            PortSide::Back => "後",
+            PortSide::Left => "左",
+            PortSide::Right => "右",
+            PortSide::Top => "上",
+            PortSide::Bottom => "下",
+        }
+    }
 }
 
 /// I/O port definition
@@ -56,6 +76,8 @@ pub enum UiSlotType {
     Output,
     /// Fuel slot (coal etc.)
     Fuel,
+    /// Drill head slot (miner), see `drill_spec`
+    Drill,
 }
 
 /// UI slot definition for auto-generated machine UI
@@ -86,6 +108,10 @@ pub enum ProcessType {
     Recipe(MachineType),
     /// Auto-generates resources from terrain (miner)
     AutoGenerate,
+    /// Decomposes crafted items back into part of their ingredients (recycler)
+    Recycle,
+    /// Buffers items and pushes them into the block below or beside (hopper)
+    Hopper,
     /// Transfer only, no processing (conveyor) - not a machine UI
     #[allow(dead_code)]
     Transfer,
@@ -113,6 +139,8 @@ pub struct MachineSpec {
     pub ui_slots: &'static [UiSlotDef],
     /// Processing type
     pub process_type: ProcessType,
+    /// UI header color (None = default)
+    pub ui_color: Option<(f32, f32, f32)>,
 }
 
 // =============================================================================
@@ -132,8 +160,12 @@ pub const MINER: MachineSpec = MachineSpec {
     process_time: 1.5,
     requires_fuel: false,
     auto_generate: true,
-    ui_slots: &[UiSlotDef::new(UiSlotType::Output, 0, "出力")],
+    ui_slots: &[
+        UiSlotDef::new(UiSlotType::Output, 0, "出力"),
+        UiSlotDef::new(UiSlotType::Drill, 0, "ドリル"),
+    ],
     process_type: ProcessType::AutoGenerate,
+    ui_color: None,
 };
 
 /// Furnace - smelts ore into ingots (requires fuel)
@@ -172,6 +204,7 @@ pub const FURNACE: MachineSpec = MachineSpec {
         UiSlotDef::new(UiSlotType::Output, 0, "出力"),
     ],
     process_type: ProcessType::Recipe(MachineType::Furnace),
+    ui_color: None,
 };
 
 /// Crusher - crushes ore into dust (doubles output)
@@ -184,6 +217,16 @@ pub const CRUSHER: MachineSpec = MachineSpec {
             is_input: true,
             slot_id: 0,
         },
+        IoPort {
+            side: PortSide::Left,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Right,
+            is_input: true,
+            slot_id: 0,
+        },
         IoPort {
             side: PortSide::Front,
             is_input: false,
@@ -199,6 +242,7 @@ pub const CRUSHER: MachineSpec = MachineSpec {
         UiSlotDef::new(UiSlotType::Output, 0, "出力"),
     ],
     process_type: ProcessType::Recipe(MachineType::Crusher),
+    ui_color: None,
 };
 
 /// Assembler - crafts machines and components
@@ -237,10 +281,111 @@ pub const ASSEMBLER: MachineSpec = MachineSpec {
         UiSlotDef::new(UiSlotType::Output, 0, "出力"),
     ],
     process_type: ProcessType::Recipe(MachineType::Assembler),
+    ui_color: None,
+};
+
+/// Recycler - breaks crafted items back down into part of their ingredients
+///
+/// Process time and return amount come from the producing recipe
+/// (see `RECYCLE_TIME_RATIO` / `RECYCLE_RETURN_RATIO`).
+pub const RECYCLER: MachineSpec = MachineSpec {
+    id: "recycler",
+    name: "リサイクラー",
+    ports: &[
+        IoPort {
+            side: PortSide::Back,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Left,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Right,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Front,
+            is_input: false,
+            slot_id: 0,
+        },
+    ],
+    buffer_size: 64,
+    process_time: 1.0,
+    requires_fuel: false,
+    auto_generate: false,
+    ui_slots: &[
+        UiSlotDef::new(UiSlotType::Input, 0, "入力"),
+        UiSlotDef::new(UiSlotType::Output, 0, "出力1"),
+        UiSlotDef::new(UiSlotType::Output, 1, "出力2"),
+        UiSlotDef::new(UiSlotType::Output, 2, "出力3"),
+        UiSlotDef::new(UiSlotType::Output, 3, "出力4"),
+    ],
+    process_type: ProcessType::Recycle,
+    ui_color: None,
+};
+
+/// Hopper - catches dropped items and feeds the machine below it
+///
+/// Takes belt input from every horizontal side and dropped items from above.
+/// Pushes one item per `process_time` downwards, or toward the side chosen
+/// in its UI (`Machine::push_side`).
+pub const HOPPER: MachineSpec = MachineSpec {
+    id: "hopper",
+    name: "ホッパー",
+    ports: &[
+        IoPort {
+            side: PortSide::Front,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Back,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Left,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Right,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Top,
+            is_input: true,
+            slot_id: 0,
+        },
+        IoPort {
+            side: PortSide::Bottom,
+            is_input: false,
+            slot_id: 0,
+        },
+    ],
+    buffer_size: 64,
+    process_time: 0.5,
+    requires_fuel: false,
+    auto_generate: false,
+    ui_slots: &[
+        UiSlotDef::new(UiSlotType::Input, 0, "1"),
+        UiSlotDef::new(UiSlotType::Input, 1, "2"),
+        UiSlotDef::new(UiSlotType::Input, 2, "3"),
+        UiSlotDef::new(UiSlotType::Input, 3, "4"),
+        UiSlotDef::new(UiSlotType::Input, 4, "5"),
+    ],
+    process_type: ProcessType::Hopper,
+    ui_color: None,
 };
 
 /// All machines
-pub const ALL_MACHINES: &[&MachineSpec] = &[&MINER, &FURNACE, &CRUSHER, &ASSEMBLER];
+pub const ALL_MACHINES: &[&MachineSpec] =
+    &[&MINER, &FURNACE, &CRUSHER, &ASSEMBLER, &RECYCLER, &HOPPER];
 
 impl MachineSpec {
     /// Get ItemId for this machine
@@ -251,17 +396,130 @@ impl MachineSpec {
             "furnace" => crate::core::items::furnace_block(),
             "crusher" => crate::core::items::crusher_block(),
             "assembler" => crate::core::items::assembler_block(),
-            _ => crate::core::items::stone(), // Fallback
+            "recycler" => crate::core::items::recycler_block(),
+            "hopper" => crate::core::items::hopper_block(),
+            // Data machines use the full item ID
+            id => items::by_string_id(id).unwrap_or_else(items::stone), // Fallback
         }
     }
 }
 
-/// Get machine spec from ItemId
+/// Get machine spec from ItemId (built-in or data-defined)
 pub fn get_machine_spec_by_id(item_id: ItemId) -> Option<&'static MachineSpec> {
     ALL_MACHINES
         .iter()
         .find(|m| m.item_id() == item_id)
         .copied()
+        .or_else(|| data_machine_spec(item_id))
+}
+
+// =============================================================================
+// Data-defined Machines
+// =============================================================================
+
+/// Machines defined in data packs (registered at startup)
+static DATA_MACHINES: RwLock<Vec<&'static MachineSpec>> = RwLock::new(Vec::new());
+
+/// Build the spec of a data-defined machine
+///
+/// `id` is the full item ID of the machine block. Items come in at the back
+/// (then the left and right for more input ports) and leave at the front.
+/// The UI gets the slot counts of the definition plus a fuel slot if the
+/// machine burns fuel.
+pub fn machine_spec_from_definition(id: &str, def: &MachineDefinition) -> MachineSpec {
+    let leak = |s: &str| -> &'static str { Box::leak(s.to_string().into_boxed_str()) };
+
+    let input_sides = [PortSide::Back, PortSide::Left, PortSide::Right];
+    let input_ports = (def.input_ports as usize).clamp(1, input_sides.len());
+    let mut ports: Vec<IoPort> = input_sides[..input_ports]
+        .iter()
+        .map(|&side| IoPort {
+            side,
+            is_input: true,
+            slot_id: 0,
+        })
+        .collect();
+    ports.push(IoPort {
+        side: PortSide::Front,
+        is_input: false,
+        slot_id: 0,
+    });
+
+    let input_slots = def.input_slots.max(1);
+    let mut ui_slots: Vec<UiSlotDef> = (0..input_slots)
+        .map(|slot| UiSlotDef::new(UiSlotType::Input, slot, "入力"))
+        .collect();
+    if def.requires_fuel {
+        ui_slots.push(UiSlotDef::new(UiSlotType::Fuel, input_slots, "燃料"));
+    }
+    ui_slots.extend(
+        (0..def.output_slots.max(1)).map(|slot| UiSlotDef::new(UiSlotType::Output, slot, "出力")),
+    );
+
+    let work_type = if def.work_type.is_empty() {
+        &def.id
+    } else {
+        &def.work_type
+    };
+    MachineSpec {
+        id: leak(id),
+        name: leak(&def.name),
+        ports: Box::leak(ports.into_boxed_slice()),
+        buffer_size: def.buffer_size.max(1),
+        process_time: def.process_time,
+        requires_fuel: def.requires_fuel,
+        auto_generate: false,
+        ui_slots: Box::leak(ui_slots.into_boxed_slice()),
+        process_type: ProcessType::Recipe(MachineType::from_data_name(work_type)),
+        ui_color: def.color.map(|[r, g, b]| (r, g, b)),
+    }
+}
+
+/// Register a data-defined machine (the first spec for an ID wins)
+pub fn register_data_machine(spec: MachineSpec) -> &'static MachineSpec {
+    let mut data = DATA_MACHINES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(existing) = data.iter().find(|m| m.id == spec.id) {
+        return existing;
+    }
+    let spec: &'static MachineSpec = Box::leak(Box::new(spec));
+    data.push(spec);
+    spec
+}
+
+/// All data-defined machines
+pub fn data_machines() -> Vec<&'static MachineSpec> {
+    DATA_MACHINES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Data-defined machine placed by `item_id`
+pub fn data_machine_spec(item_id: ItemId) -> Option<&'static MachineSpec> {
+    DATA_MACHINES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|m| m.item_id() == item_id)
+        .copied()
+}
+
+/// Built-in machines followed by data-defined ones
+pub fn all_machine_specs() -> Vec<&'static MachineSpec> {
+    ALL_MACHINES
+        .iter()
+        .copied()
+        .chain(data_machines())
+        .collect()
+}
+
+/// Machine that runs the recipes of `machine` (built-in first)
+pub fn machine_spec_for_type(machine: MachineType) -> Option<&'static MachineSpec> {
+    all_machine_specs()
+        .into_iter()
+        .find(|spec| spec.process_type == ProcessType::Recipe(machine))
 }
 
 /// Get input ports for a machine
@@ -406,6 +664,8 @@ mod tests {
         // Test item_id() method on MachineSpec
         assert_eq!(MINER.item_id(), items::miner_block());
         assert_eq!(FURNACE.item_id(), items::furnace_block());
+        assert_eq!(RECYCLER.item_id(), items::recycler_block());
+        assert_eq!(HOPPER.item_id(), items::hopper_block());
     }
 
     #[test]
@@ -421,9 +681,10 @@ mod tests {
         assert_eq!(furnace_inputs.len(), 3);
         assert_eq!(furnace_outputs.len(), 1);
 
+        // Crusher: 3 inputs (back + left + right), 1 output
         let crusher_inputs: Vec<_> = get_input_ports(&CRUSHER).collect();
         let crusher_outputs: Vec<_> = get_output_ports(&CRUSHER).collect();
-        assert_eq!(crusher_inputs.len(), 1);
+        assert_eq!(crusher_inputs.len(), 3);
         assert_eq!(crusher_outputs.len(), 1);
 
         // Assembler: 3 inputs (main + sub left + sub right), 1 output
@@ -432,4 +693,37 @@ mod tests {
         assert_eq!(assembler_inputs.len(), 3);
         assert_eq!(assembler_outputs.len(), 1);
     }
+    #[test]
+    fn test_machine_spec_from_definition() {
+        let mut def = MachineDefinition::new("grinder", "Grinder");
+        def.work_type = "grinding".to_string();
+        def.input_ports = 2;
+        def.output_slots = 2;
+        def.requires_fuel = true;
+        def.color = Some([0.2, 0.4, 0.6]);
+
+        let spec = machine_spec_from_definition("test_spec:grinder", &def);
+        assert_eq!(spec.id, "test_spec:grinder");
+        assert_eq!(
+            spec.process_type,
+            ProcessType::Recipe(MachineType::Custom("grinding"))
+        );
+        assert_eq!(get_input_ports(&spec).count(), 2);
+        assert_eq!(get_output_ports(&spec).count(), 1);
+        let slots = |t| spec.ui_slots.iter().filter(|s| s.slot_type == t).count();
+        assert_eq!(slots(UiSlotType::Input), 1);
+        assert_eq!(slots(UiSlotType::Fuel), 1);
+        assert_eq!(slots(UiSlotType::Output), 2);
+        assert_eq!(spec.ui_color, Some((0.2, 0.4, 0.6)));
+
+        // No work type: recipes name the machine ID
+        let plain = machine_spec_from_definition(
+            "test_spec:press",
+            &MachineDefinition::new("press", "Press"),
+        );
+        assert_eq!(
+            plain.process_type,
+            ProcessType::Recipe(MachineType::Custom("press"))
+        );
+    }
 }
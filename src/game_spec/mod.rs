@@ -3,25 +3,36 @@
 //! This file is the Single Source of Truth for game design.
 //! If you change the spec, update this file. Tests will verify implementation matches.
 
+pub mod item_info;
 pub mod machines;
+pub mod planning;
 pub mod recipes;
 pub mod registry;
+pub mod tags;
 pub mod ui_elements;
 pub mod ui_style;
 
 // Re-exports for convenience
+pub use item_info::{build_item_info_cache, ItemInfo, ItemInfoCache, TooltipLabels};
 pub use machines::{
-    get_input_ports, get_machine_spec_by_id, get_output_ports, IoPort, MachineSpec, MachineState,
-    PortSide, ProcessType, UiSlotDef, UiSlotType, ALL_MACHINES, ASSEMBLER, CRUSHER, FURNACE, MINER,
+    all_machine_specs, data_machine_spec, get_input_ports, get_machine_spec_by_id,
+    get_output_ports, machine_spec_for_type, IoPort, MachineSpec, MachineState, PortSide,
+    ProcessType, UiSlotDef, UiSlotType, ALL_MACHINES, ASSEMBLER, CRUSHER, FURNACE, HOPPER, MINER,
+    RECYCLER,
 };
+pub use planning::{material_cost, producer, CostError, MaterialCost, RAW_TAG};
 pub use recipes::{
-    all_recipes, find_recipe, find_recipe_by_id, get_recipes_for_machine, FuelRequirement,
-    MachineType, Recipe, RecipeInput, RecipeOutput,
+    all_recipes, built_in_recipes, find_recipe, find_recipe_by_id, find_recycle_recipe,
+    get_recipes_for_machine, is_unlocked, items_per_minute, locked_recipe_for_output,
+    recipe_conflicts, resolve_recipe, FluidAmount, FuelRequirement, Ingredient, MachineType,
+    Recipe, RecipeConflict, RecipeInput, RecipeOutput, UnlockCondition, RECYCLE_RETURN_RATIO,
+    RECYCLE_TIME_RATIO,
 };
 pub use registry::{
     get_item_descriptor, item_descriptors, load_ui_elements, GameRegistry, ItemDescriptor,
     RegistryPlugin,
 };
+pub use tags::{has_tag, item_tags};
 pub use ui_elements::{
     load_ui_elements_from_toml, UIElementRegistry, UIElementSpec, UIElementTag, UIElementToml,
 };
@@ -84,6 +95,10 @@ pub mod breaking_spec {
     pub const BARE_HAND_MULTIPLIER: f32 = 2.0;
     /// Multiplier when breaking with stone pickaxe (normal speed)
     pub const STONE_PICKAXE_MULTIPLIER: f32 = 1.0;
+    /// Seconds left-click must be held on a machine to break it (any tool)
+    pub const MACHINE_HOLD_SECS: f32 = 0.6;
+    /// Break time for world blocks in creative mode (machines break instantly)
+    pub const CREATIVE_BLOCK_BREAK_SECS: f32 = 0.1;
 
     /// Get base break time from ItemDescriptor.hardness (via ItemId)
     /// This is now data-driven - each item has its own hardness value
@@ -102,6 +117,59 @@ pub mod breaking_spec {
     }
 }
 
+/// Miner drill heads
+///
+/// A miner only produces the items its installed drill can cut; without a
+/// drill it mines nothing. Each mined item wears the drill by one, and a drill
+/// at zero breaks and is gone.
+pub mod drill_spec {
+    use crate::core::{items, ItemId};
+
+    /// Mining operations a fresh drill head lasts
+    pub const DRILL_DURABILITY: u32 = 500;
+
+    /// Drill tiers, each mining everything the one below does
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum DrillTier {
+        /// Stone and coal
+        Stone,
+        /// Adds iron ore
+        Iron,
+        /// Adds copper ore and any ore added later
+        Steel,
+    }
+
+    impl DrillTier {
+        /// Tier of a drill head item (None for anything else)
+        pub fn of(item_id: ItemId) -> Option<Self> {
+            if item_id == items::stone_drill() {
+                Some(Self::Stone)
+            } else if item_id == items::iron_drill() {
+                Some(Self::Iron)
+            } else if item_id == items::steel_drill() {
+                Some(Self::Steel)
+            } else {
+                None
+            }
+        }
+
+        /// Lowest tier that mines `item_id`
+        pub fn required_for(item_id: ItemId) -> Self {
+            if item_id == items::stone() || item_id == items::coal() {
+                Self::Stone
+            } else if item_id == items::iron_ore() {
+                Self::Iron
+            } else {
+                Self::Steel
+            }
+        }
+
+        pub fn can_mine(self, item_id: ItemId) -> bool {
+            self >= Self::required_for(item_id)
+        }
+    }
+}
+
 /// Biome Mining Spec (ItemId-based)
 #[allow(dead_code)]
 pub mod biome_mining_spec {
@@ -165,8 +233,10 @@ pub fn initial_equipment() -> Vec<(ItemId, u32)> {
     vec![
         (items::stone_pickaxe(), 1),
         (items::miner_block(), 2),
+        (items::stone_drill(), 2),
         (items::conveyor_block(), 90),
         (items::furnace_block(), 1),
+        (items::wrench(), 1),
     ]
 }
 
@@ -175,6 +245,7 @@ pub fn initial_equipment() -> Vec<(ItemId, u32)> {
 pub fn creative_mode_equipment() -> Vec<(ItemId, u32)> {
     vec![
         (items::miner_block(), 99),
+        (items::steel_drill(), 16),
         (items::conveyor_block(), 999),
         (items::crusher_block(), 99),
         (items::furnace_block(), 99),
@@ -231,7 +302,11 @@ static MAIN_QUESTS: LazyLock<Vec<Quest>> = LazyLock::new(|| {
             quest_type: QuestType::Main,
             description: "鉄インゴットを10個納品せよ",
             required_items: vec![(items::iron_ingot(), 10)],
-            rewards: vec![(items::assembler_block(), 1), (items::conveyor_block(), 20)],
+            rewards: vec![
+                (items::assembler_block(), 1),
+                (items::conveyor_block(), 20),
+                (items::iron_drill(), 2),
+            ],
             unlocks: vec![items::assembler_block()], // Unlock Assembler (machine crafting)
         },
         Quest {
@@ -268,7 +343,11 @@ static SUB_QUESTS: LazyLock<Vec<Quest>> = LazyLock::new(|| {
             quest_type: QuestType::Sub,
             description: "鉄インゴット100個を納品",
             required_items: vec![(items::iron_ingot(), 100)],
-            rewards: vec![(items::miner_block(), 2), (items::conveyor_block(), 30)],
+            rewards: vec![
+                (items::miner_block(), 2),
+                (items::iron_drill(), 2),
+                (items::conveyor_block(), 30),
+            ],
             unlocks: vec![],
         },
         Quest {
@@ -287,6 +366,14 @@ static SUB_QUESTS: LazyLock<Vec<Quest>> = LazyLock::new(|| {
             rewards: vec![(items::crusher_block(), 1)],
             unlocks: vec![],
         },
+        Quest {
+            id: "sub_crystal_20",
+            quest_type: QuestType::Sub,
+            description: "クリスタル20個を納品",
+            required_items: vec![(items::crystal(), 20)],
+            rewards: vec![(items::miner_block(), 1), (items::lamp_block(), 4)],
+            unlocks: vec![],
+        },
     ]
 });
 
@@ -345,7 +432,7 @@ mod tests {
     fn test_initial_equipment_not_empty() {
         let equipment = initial_equipment();
         assert!(!equipment.is_empty());
-        assert_eq!(equipment.len(), 4); // StonePickaxe, Miner, Conveyor, Furnace
+        assert_eq!(equipment.len(), 6); // StonePickaxe, Miner, StoneDrill, Conveyor, Furnace, Wrench
     }
 
     #[test]
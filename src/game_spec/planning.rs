@@ -0,0 +1,316 @@
+//! Production planning: which recipe makes an item and what it costs in raw materials
+//!
+//! `producer` picks the machine recipe the production goals panel plans
+//! with. `material_cost` resolves an item down to raw materials for `/cost`:
+//! items with no recipe, or tagged `raw` in `assets/data/tags.yaml`. Demand
+//! for shared intermediates is summed before crafts are rounded up, and
+//! recipe cycles are reported instead of followed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use super::recipes::{Ingredient, MachineType, Recipe};
+use crate::core::ItemId;
+
+/// Tag that stops `/cost` from breaking an item down further
+pub const RAW_TAG: &str = "raw";
+
+/// Machine recipe that makes `item` (highest priority, first in recipe order on ties)
+pub fn producer(recipes: &[Recipe], item: ItemId) -> Option<&Recipe> {
+    recipes
+        .iter()
+        .filter(|r| r.machine != MachineType::Hand && r.output_count_of(item) > 0)
+        .min_by_key(|r| std::cmp::Reverse(r.priority))
+}
+
+/// Recipe used to break `item` down: the machine recipe, else a hand recipe
+fn crafting_recipe(recipes: &[Recipe], item: ItemId) -> Option<&Recipe> {
+    producer(recipes, item).or_else(|| {
+        recipes
+            .iter()
+            .find(|r| r.machine == MachineType::Hand && r.output_count_of(item) > 0)
+    })
+}
+
+/// Items a recipe consumes per craft (inputs, then fuel)
+fn recipe_items(recipe: &Recipe) -> impl Iterator<Item = (ItemId, u32)> + '_ {
+    recipe
+        .inputs
+        .iter()
+        .filter_map(|input| input.item().map(|item| (item, input.count)))
+        .chain(recipe.fuel.iter().map(|fuel| (fuel.fuel_type, fuel.amount)))
+}
+
+/// Raw materials and machine time for a number of items
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialCost {
+    /// Raw materials, most needed first
+    pub raw: Vec<(ItemId, u64)>,
+    /// Tag inputs, which could be any item of the tag
+    pub tags: Vec<(&'static str, u64)>,
+    /// Total crafting time per machine type (seconds at base speed)
+    pub machine_seconds: Vec<(MachineType, f32)>,
+}
+
+impl MaterialCost {
+    /// "Iron Ore x45, Coal x12, #dust x4"
+    pub fn materials_line(&self) -> String {
+        self.raw
+            .iter()
+            .map(|(item, count)| format!("{} x{}", item.display_name(), count))
+            .chain(
+                self.tags
+                    .iter()
+                    .map(|(tag, count)| format!("#{} x{}", tag, count)),
+            )
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// "Furnace 90.0s, Assembler 24.0s"
+    pub fn machine_line(&self) -> String {
+        self.machine_seconds
+            .iter()
+            .map(|(machine, secs)| format!("{:?} {:.1}s", machine, secs))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Why a cost could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CostError {
+    /// Items whose recipes lead back to the first one
+    Cycle(Vec<ItemId>),
+}
+
+impl fmt::Display for CostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CostError::Cycle(items) => {
+                let names: Vec<&str> = items.iter().map(|item| item.display_name()).collect();
+                write!(f, "Recipe cycle: {}", names.join(" -> "))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+/// Order items so every item comes before the items its recipe consumes
+fn visit(
+    item: ItemId,
+    recipe_for: &impl Fn(ItemId) -> Option<&'static Recipe>,
+    state: &mut HashMap<ItemId, Visit>,
+    path: &mut Vec<ItemId>,
+    postorder: &mut Vec<ItemId>,
+) -> Result<(), CostError> {
+    match state.get(&item) {
+        Some(Visit::Done) => return Ok(()),
+        Some(Visit::InProgress) => {
+            let start = path.iter().position(|i| *i == item).unwrap_or(0);
+            let mut cycle = path[start..].to_vec();
+            cycle.push(item);
+            return Err(CostError::Cycle(cycle));
+        }
+        None => {}
+    }
+    state.insert(item, Visit::InProgress);
+    path.push(item);
+    if let Some(recipe) = recipe_for(item) {
+        for (input, _) in recipe_items(recipe) {
+            visit(input, recipe_for, state, path, postorder)?;
+        }
+    }
+    path.pop();
+    state.insert(item, Visit::Done);
+    postorder.push(item);
+    Ok(())
+}
+
+/// Raw materials for `count` of `item`
+///
+/// `is_raw` marks items that are not broken down even if a recipe makes them.
+pub fn material_cost(
+    recipes: &'static [Recipe],
+    item: ItemId,
+    count: u64,
+    is_raw: impl Fn(ItemId) -> bool,
+) -> Result<MaterialCost, CostError> {
+    let recipe_for = |item: ItemId| {
+        if is_raw(item) {
+            None
+        } else {
+            crafting_recipe(recipes, item)
+        }
+    };
+
+    let mut postorder = Vec::new();
+    visit(
+        item,
+        &recipe_for,
+        &mut HashMap::new(),
+        &mut Vec::new(),
+        &mut postorder,
+    )?;
+
+    // Products before their inputs, so an item's demand is complete when it is expanded
+    let mut demand: HashMap<ItemId, u64> = HashMap::from([(item, count)]);
+    let mut raw = Vec::new();
+    let mut tags: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut machine_seconds: Vec<(MachineType, f32)> = Vec::new();
+    for &current in postorder.iter().rev() {
+        let needed = demand.get(&current).copied().unwrap_or(0);
+        if needed == 0 {
+            continue;
+        }
+        let Some(recipe) = recipe_for(current) else {
+            raw.push((current, needed));
+            continue;
+        };
+
+        let crafts = needed.div_ceil(recipe.output_count_of(current) as u64);
+        for (input, per_craft) in recipe_items(recipe) {
+            *demand.entry(input).or_insert(0) += crafts * per_craft as u64;
+        }
+        for input in &recipe.inputs {
+            if let Ingredient::Tag(tag) = input.ingredient {
+                *tags.entry(tag).or_insert(0) += crafts * input.count as u64;
+            }
+        }
+        let secs = crafts as f32 * recipe.craft_time;
+        match machine_seconds
+            .iter_mut()
+            .find(|(m, _)| *m == recipe.machine)
+        {
+            Some(entry) => entry.1 += secs,
+            None => machine_seconds.push((recipe.machine, secs)),
+        }
+    }
+
+    raw.sort_by(|(a, a_count), (b, b_count)| {
+        b_count
+            .cmp(a_count)
+            .then_with(|| a.display_name().cmp(b.display_name()))
+    });
+    Ok(MaterialCost {
+        raw,
+        tags: tags.into_iter().collect(),
+        machine_seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+    use crate::game_spec::{FuelRequirement, RecipeInput, RecipeOutput, UnlockCondition};
+
+    fn recipe(
+        id: &'static str,
+        machine: MachineType,
+        inputs: &[(ItemId, u32)],
+        output: (ItemId, u32),
+        craft_time: f32,
+    ) -> Recipe {
+        Recipe {
+            id,
+            machine,
+            inputs: inputs
+                .iter()
+                .map(|&(item, count)| RecipeInput::new(item, count, 0))
+                .collect(),
+            outputs: vec![RecipeOutput::guaranteed(output.0, output.1)],
+            craft_time,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        }
+    }
+
+    fn leak(recipes: Vec<Recipe>) -> &'static [Recipe] {
+        Box::leak(recipes.into_boxed_slice())
+    }
+
+    #[test]
+    fn test_diamond_sums_shared_intermediates() {
+        // conveyor (x5) <- 2 ingot + 1 gear; gear <- 2 ingot; ingot <- 1 ore + coal fuel
+        let ore = items::iron_ore();
+        let ingot = items::iron_ingot();
+        let gear = items::copper_ingot();
+        let belt = items::conveyor_block();
+        let mut smelt = recipe("smelt", MachineType::Furnace, &[(ore, 1)], (ingot, 1), 2.0);
+        smelt.fuel = Some(FuelRequirement::new(items::coal(), 1));
+        let recipes = leak(vec![
+            smelt,
+            recipe(
+                "gear",
+                MachineType::Assembler,
+                &[(ingot, 2)],
+                (gear, 1),
+                1.0,
+            ),
+            recipe(
+                "belt",
+                MachineType::Assembler,
+                &[(ingot, 2), (gear, 1)],
+                (belt, 5),
+                2.0,
+            ),
+        ]);
+
+        // 10 belts = 2 crafts: 4 ingots direct + 2 gears (4 ingots) = 8 ingots, not max(4, 4)
+        let cost = material_cost(recipes, belt, 10, |_| false).unwrap();
+        // Equal counts are ordered by name
+        assert_eq!(cost.raw, vec![(items::coal(), 8), (items::iron_ore(), 8)]);
+        assert_eq!(
+            cost.machine_seconds,
+            vec![(MachineType::Assembler, 6.0), (MachineType::Furnace, 16.0)]
+        );
+        assert_eq!(cost.materials_line(), "Coal x8, Iron Ore x8");
+
+        // Crafts round up after the demand is summed: 7 belts still take 2 crafts
+        let cost = material_cost(recipes, belt, 7, |_| false).unwrap();
+        assert_eq!(cost.raw[1], (items::iron_ore(), 8));
+
+        // Items tagged raw are not broken down
+        let cost = material_cost(recipes, belt, 10, |item| item == ingot).unwrap();
+        assert_eq!(cost.raw, vec![(ingot, 8)]);
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let a = items::iron_dust();
+        let b = items::copper_dust();
+        let recipes = leak(vec![
+            recipe("a_from_b", MachineType::Crusher, &[(b, 1)], (a, 1), 1.0),
+            recipe("b_from_a", MachineType::Crusher, &[(a, 1)], (b, 1), 1.0),
+        ]);
+        assert_eq!(
+            material_cost(recipes, a, 1, |_| false),
+            Err(CostError::Cycle(vec![a, b, a]))
+        );
+
+        // Tagging one side raw breaks the cycle
+        let cost = material_cost(recipes, a, 3, |item| item == b).unwrap();
+        assert_eq!(cost.raw, vec![(b, 3)]);
+    }
+
+    #[test]
+    fn test_bundled_recipes_resolve() {
+        let cost = material_cost(
+            crate::game_spec::all_recipes(),
+            items::iron_ingot(),
+            10,
+            |item| crate::game_spec::has_tag(item, RAW_TAG),
+        )
+        .unwrap();
+        assert!(cost.raw.iter().any(|(item, _)| *item == items::iron_ore()));
+    }
+}
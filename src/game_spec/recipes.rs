@@ -3,8 +3,18 @@
 //! All processing recipes are defined using ItemId (no BlockType dependency).
 //! Recipes are lazily initialized at runtime.
 
-use crate::core::{items, ItemId};
-use std::sync::LazyLock;
+use super::tags::has_tag;
+use crate::components::CurrentQuest;
+use crate::core::{items, FluidId, ItemId};
+use crate::player::PlatformInventory;
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+/// Share of a recipe's ingredients the recycler gives back (rounded down per ingredient)
+pub const RECYCLE_RETURN_RATIO: f32 = 0.5;
+
+/// Recycling time as a share of the producing recipe's craft_time
+pub const RECYCLE_TIME_RATIO: f32 = 0.5;
 
 /// Machine type for recipes
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
@@ -12,13 +22,72 @@ pub enum MachineType {
     Furnace,   // Smelter
     Crusher,   // Crusher
     Assembler, // Assembler
+    Hand,      // Player hand crafting (inventory crafting tab)
+    /// Data-defined machine, matched by work type (e.g. "grinding")
+    Custom(&'static str),
+}
+
+impl MachineType {
+    /// Parse the machine name used in data packs; unknown names are work types
+    pub fn from_data_name(name: &str) -> Self {
+        match name {
+            "furnace" => MachineType::Furnace,
+            "crusher" => MachineType::Crusher,
+            "assembler" => MachineType::Assembler,
+            "hand" => MachineType::Hand,
+            work_type => MachineType::Custom(intern_work_type(work_type)),
+        }
+    }
+
+    /// Machine name used in data packs
+    pub fn data_name(&self) -> &'static str {
+        match self {
+            MachineType::Furnace => "furnace",
+            MachineType::Crusher => "crusher",
+            MachineType::Assembler => "assembler",
+            MachineType::Hand => "hand",
+            MachineType::Custom(work_type) => work_type,
+        }
+    }
+}
+
+/// Work types seen in data packs (leaked once each)
+static WORK_TYPES: RwLock<Vec<&'static str>> = RwLock::new(Vec::new());
+
+fn intern_work_type(name: &str) -> &'static str {
+    if let Some(&known) = WORK_TYPES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|w| **w == name)
+    {
+        return known;
+    }
+    let mut work_types = WORK_TYPES.write().unwrap_or_else(PoisonError::into_inner);
+    match work_types.iter().find(|w| **w == name) {
+        Some(&known) => known,
+        None => {
+            let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+            work_types.push(leaked);
+            leaked
+        }
+    }
+}
+
+/// What a recipe input accepts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ingredient {
+    /// One specific item
+    Item(ItemId),
+    /// Any item with this tag (see `assets/data/tags.yaml`)
+    Tag(&'static str),
 }
 
 /// Recipe input
 #[derive(Clone, Debug)]
 pub struct RecipeInput {
-    /// Item type
-    pub item: ItemId,
+    /// Accepted item or tag
+    pub ingredient: Ingredient,
     /// Required count
     pub count: u32,
     /// Input slot ID (0 = main, 1+ = sub)
@@ -27,7 +96,36 @@ pub struct RecipeInput {
 
 impl RecipeInput {
     pub fn new(item: ItemId, count: u32, slot: u8) -> Self {
-        Self { item, count, slot }
+        Self {
+            ingredient: Ingredient::Item(item),
+            count,
+            slot,
+        }
+    }
+
+    /// Input accepting any item with `tag`
+    pub fn tag(tag: &'static str, count: u32, slot: u8) -> Self {
+        Self {
+            ingredient: Ingredient::Tag(tag),
+            count,
+            slot,
+        }
+    }
+
+    /// Specific item (None for tag inputs)
+    pub fn item(&self) -> Option<ItemId> {
+        match self.ingredient {
+            Ingredient::Item(item) => Some(item),
+            Ingredient::Tag(_) => None,
+        }
+    }
+
+    /// Check whether `item` satisfies this input
+    pub fn matches(&self, item: ItemId) -> bool {
+        match self.ingredient {
+            Ingredient::Item(id) => id == item,
+            Ingredient::Tag(tag) => has_tag(item, tag),
+        }
     }
 }
 
@@ -40,6 +138,8 @@ pub struct RecipeOutput {
     pub count: u32,
     /// Output chance (0.0-1.0, 1.0 = guaranteed)
     pub chance: f32,
+    /// Only produced when the main input is this item (for tag inputs)
+    pub for_input: Option<ItemId>,
 }
 
 impl RecipeOutput {
@@ -49,6 +149,7 @@ impl RecipeOutput {
             item,
             count,
             chance: 1.0,
+            for_input: None,
         }
     }
 
@@ -59,8 +160,29 @@ impl RecipeOutput {
             item,
             count,
             chance,
+            for_input: None,
         }
     }
+
+    /// Restrict this output to one input item (e.g. iron_ore → iron_ingot)
+    pub fn for_input(mut self, input: ItemId) -> Self {
+        self.for_input = Some(input);
+        self
+    }
+}
+
+/// Fluid taken from or given to a pipe/tank network next to the machine
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FluidAmount {
+    pub fluid: FluidId,
+    /// Amount per craft
+    pub amount: f32,
+}
+
+impl FluidAmount {
+    pub fn new(fluid: FluidId, amount: f32) -> Self {
+        Self { fluid, amount }
+    }
 }
 
 /// Fuel requirement
@@ -78,6 +200,65 @@ impl FuelRequirement {
     }
 }
 
+/// Condition that must be met before a recipe can be used
+///
+/// Missing in data files = `Always` (backward compatible).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnlockCondition {
+    /// Main quest with this ID has been completed
+    QuestCompleted(String),
+    /// At least `count` of `item` is stored on the delivery platform
+    ItemDelivered { item: String, count: u32 },
+    /// Available from the start
+    #[default]
+    Always,
+}
+
+impl UnlockCondition {
+    /// Check the condition against quest progress and delivered items
+    pub fn is_met(&self, current_quest: &CurrentQuest, platform: &PlatformInventory) -> bool {
+        match self {
+            UnlockCondition::Always => true,
+            UnlockCondition::QuestCompleted(quest_id) => {
+                let Some(index) = crate::game_spec::main_quests()
+                    .iter()
+                    .position(|q| q.id == quest_id)
+                else {
+                    return false;
+                };
+                index < current_quest.index
+                    || (index == current_quest.index && current_quest.completed)
+            }
+            UnlockCondition::ItemDelivered { item, count } => {
+                let name = item.strip_prefix("base:").unwrap_or(item);
+                items::by_name(name).is_some_and(|id| platform.get_count_by_id(id) >= *count)
+            }
+        }
+    }
+
+    /// Human-readable condition for UI (None when always unlocked)
+    pub fn description(&self) -> Option<String> {
+        match self {
+            UnlockCondition::Always => None,
+            UnlockCondition::QuestCompleted(quest_id) => {
+                let label = crate::game_spec::main_quests()
+                    .iter()
+                    .find(|q| q.id == quest_id)
+                    .map(|q| q.description)
+                    .unwrap_or(quest_id);
+                Some(format!("クエスト達成: {}", label))
+            }
+            UnlockCondition::ItemDelivered { item, count } => {
+                let name = item.strip_prefix("base:").unwrap_or(item);
+                let label = items::by_name(name)
+                    .map(|id| id.display_name())
+                    .unwrap_or(name);
+                Some(format!("納品: {} x{}", label, count))
+            }
+        }
+    }
+}
+
 /// Recipe definition
 #[derive(Clone, Debug)]
 pub struct Recipe {
@@ -93,6 +274,14 @@ pub struct Recipe {
     pub craft_time: f32,
     /// Fuel requirement (None = no fuel needed)
     pub fuel: Option<FuelRequirement>,
+    /// Fluid drawn from an adjacent pipe/tank network per craft
+    pub fluid_input: Option<FluidAmount>,
+    /// Fluid pushed into an adjacent pipe/tank network per craft
+    pub fluid_output: Option<FluidAmount>,
+    /// Unlock condition
+    pub unlock: UnlockCondition,
+    /// Wins over lower priorities when several recipes accept the same input
+    pub priority: i32,
 }
 
 impl Recipe {
@@ -106,15 +295,78 @@ impl Recipe {
         self.outputs.iter().filter(|o| o.chance < 1.0)
     }
 
-    /// Get input item as ItemId (for compatibility)
+    /// Get input item as ItemId (for compatibility, None for tag inputs)
     pub fn input_item(&self, slot: u8) -> Option<ItemId> {
-        self.inputs.iter().find(|i| i.slot == slot).map(|i| i.item)
+        self.inputs
+            .iter()
+            .find(|i| i.slot == slot)
+            .and_then(RecipeInput::item)
     }
 
     /// Get primary output item
     pub fn output_item(&self) -> Option<ItemId> {
         self.outputs.first().map(|o| o.item)
     }
+
+    /// Check whether any input accepts `item`
+    pub fn accepts(&self, item: ItemId) -> bool {
+        self.inputs.iter().any(|i| i.matches(item))
+    }
+
+    /// Outputs produced when processing `input`
+    ///
+    /// Outputs without `for_input` are always produced.
+    pub fn outputs_for(&self, input: ItemId) -> impl Iterator<Item = &RecipeOutput> {
+        self.outputs
+            .iter()
+            .filter(move |o| o.for_input.is_none_or(|i| i == input))
+    }
+
+    /// Ingredients the recycler returns for one batch of this recipe's output
+    ///
+    /// Each specific-item input gives back `RECYCLE_RETURN_RATIO` of its count,
+    /// rounded down. If that rounds everything to zero, one of the main
+    /// ingredient is returned so recycling never destroys items outright.
+    /// Tag inputs are skipped (there is no single item to give back).
+    pub fn recycle_returns(&self) -> Vec<(ItemId, u32)> {
+        let ingredients: Vec<(ItemId, u32)> = self
+            .inputs
+            .iter()
+            .filter_map(|input| input.item().map(|item| (item, input.count)))
+            .collect();
+        let returns: Vec<(ItemId, u32)> = ingredients
+            .iter()
+            .map(|&(item, count)| (item, (count as f32 * RECYCLE_RETURN_RATIO).floor() as u32))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        if returns.is_empty() {
+            return ingredients
+                .first()
+                .map(|&(item, _)| vec![(item, 1)])
+                .unwrap_or_default();
+        }
+        returns
+    }
+
+    /// Expected items per minute of `output` at `speed` (1.0 = normal simulation speed)
+    ///
+    /// Chance outputs count their expected share.
+    pub fn output_per_minute(&self, output: &RecipeOutput, speed: f32) -> f32 {
+        items_per_minute(output.count, self.craft_time, speed) * output.chance
+    }
+
+    /// Items of `input` consumed per minute at `speed`
+    pub fn input_per_minute(&self, input: &RecipeInput, speed: f32) -> f32 {
+        items_per_minute(input.count, self.craft_time, speed)
+    }
+
+    /// Guaranteed output count of `item` per batch (0 if not produced)
+    pub fn output_count_of(&self, item: ItemId) -> u32 {
+        self.guaranteed_outputs()
+            .filter(|o| o.item == item)
+            .map(|o| o.count)
+            .sum()
+    }
 }
 
 // =============================================================================
@@ -133,6 +385,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::iron_ingot(), 1)],
             craft_time: 2.0,
             fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         Recipe {
             id: "smelt_copper",
@@ -141,6 +397,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::copper_ingot(), 1)],
             craft_time: 2.0,
             fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         // =================================================================
         // Furnace - dust smelting (faster than ore)
@@ -152,6 +412,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::iron_ingot(), 1)],
             craft_time: 1.5,
             fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         Recipe {
             id: "smelt_copper_dust",
@@ -160,6 +424,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::copper_ingot(), 1)],
             craft_time: 1.5,
             fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         // =================================================================
         // Crusher
@@ -171,6 +439,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::iron_dust(), 2)],
             craft_time: 1.5,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         Recipe {
             id: "crush_copper",
@@ -179,6 +451,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::copper_dust(), 2)],
             craft_time: 1.5,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         // =================================================================
         // Assembler
@@ -190,6 +466,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::conveyor_block(), 5)],
             craft_time: 2.0,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 2,
         },
         Recipe {
             id: "craft_miner",
@@ -201,6 +481,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::miner_block(), 1)],
             craft_time: 5.0,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 1,
         },
         Recipe {
             id: "craft_furnace",
@@ -212,6 +496,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::furnace_block(), 1)],
             craft_time: 6.0,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
         Recipe {
             id: "craft_crusher",
@@ -223,6 +511,10 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::crusher_block(), 1)],
             craft_time: 8.0,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 1,
         },
         Recipe {
             id: "craft_assembler",
@@ -234,6 +526,250 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
             outputs: vec![RecipeOutput::guaranteed(items::assembler_block(), 1)],
             craft_time: 10.0,
             fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_delivery_pad",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 2, 0),
+                RecipeInput::new(items::stone(), 4, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::delivery_pad(), 1)],
+            craft_time: 3.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_display_panel",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 2, 0),
+                RecipeInput::new(items::copper_ingot(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::display_panel(), 1)],
+            craft_time: 2.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_recycler",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 10, 0),
+                RecipeInput::new(items::stone(), 10, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::recycler_block(), 1)],
+            craft_time: 6.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_lamp",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 1, 0),
+                RecipeInput::new(items::coal(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::lamp_block(), 1)],
+            craft_time: 1.5,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_hopper",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 5, 0),
+                RecipeInput::new(items::stone(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::hopper_block(), 1)],
+            craft_time: 3.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_steel_drill",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::iron_drill(), 1, 0),
+                RecipeInput::new(items::iron_ingot(), 8, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::steel_drill(), 1)],
+            craft_time: 5.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "craft_steel_drill_crystal",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::crystal(), 4, 0),
+                RecipeInput::new(items::iron_drill(), 1, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::steel_drill(), 1)],
+            craft_time: 5.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "upgrade_conveyor_mk2",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::conveyor_block(), 1, 0),
+                RecipeInput::new(items::iron_ingot(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::conveyor_block_mk2(), 1)],
+            craft_time: 2.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "upgrade_conveyor_mk3",
+            machine: MachineType::Assembler,
+            inputs: vec![
+                RecipeInput::new(items::conveyor_block_mk2(), 1, 0),
+                RecipeInput::new(items::copper_ingot(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::conveyor_block_mk3(), 1)],
+            craft_time: 3.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        // =================================================================
+        // Hand crafting - first machines from raw materials
+        // =================================================================
+        Recipe {
+            id: "hand_furnace",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::stone(), 5, 0),
+                RecipeInput::new(items::iron_ingot(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::furnace_block(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_conveyor",
+            machine: MachineType::Hand,
+            inputs: vec![RecipeInput::new(items::iron_ingot(), 3, 0)],
+            outputs: vec![RecipeOutput::guaranteed(items::conveyor_block(), 2)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_miner",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::stone(), 8, 0),
+                RecipeInput::new(items::iron_ingot(), 3, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::miner_block(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_stone_drill",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::stone(), 6, 0),
+                RecipeInput::new(items::coal(), 2, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::stone_drill(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_iron_drill",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::stone_drill(), 1, 0),
+                RecipeInput::new(items::iron_ingot(), 3, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::iron_drill(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_ore_scanner",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::iron_ingot(), 2, 0),
+                RecipeInput::new(items::copper_ingot(), 3, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::ore_scanner(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        },
+        Recipe {
+            id: "hand_sign",
+            machine: MachineType::Hand,
+            inputs: vec![
+                RecipeInput::new(items::stone(), 4, 0),
+                RecipeInput::new(items::iron_ingot(), 1, 1),
+            ],
+            outputs: vec![RecipeOutput::guaranteed(items::sign_block(), 2)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
         },
     ]
 });
@@ -242,26 +778,205 @@ static RECIPES: LazyLock<Vec<Recipe>> = LazyLock::new(|| {
 // Public API
 // =============================================================================
 
-/// Get all recipes
+/// Built-in recipes followed by data pack recipes (empty until registered)
+static REGISTERED: RwLock<&'static [Recipe]> = RwLock::new(&[]);
+
+/// Get all recipes (built-in and registered from data packs)
 pub fn all_recipes() -> &'static [Recipe] {
-    &RECIPES
+    let registered = *REGISTERED.read().unwrap_or_else(PoisonError::into_inner);
+    if registered.is_empty() {
+        RECIPES.as_slice()
+    } else {
+        registered
+    }
+}
+
+/// Get the built-in recipes only
+pub fn built_in_recipes() -> &'static [Recipe] {
+    RECIPES.as_slice()
+}
+
+/// Add recipes from data packs, skipping IDs already in use
+///
+/// The combined table is leaked so lookups stay `&'static`; this only runs
+/// while loading content. Returns how many recipes were added.
+pub fn register_recipes(recipes: impl IntoIterator<Item = Recipe>) -> usize {
+    let mut registered = REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+    let mut table: Vec<Recipe> = if registered.is_empty() {
+        RECIPES.to_vec()
+    } else {
+        registered.to_vec()
+    };
+    let before = table.len();
+    for recipe in recipes {
+        if !table.iter().any(|r| r.id == recipe.id) {
+            table.push(recipe);
+        }
+    }
+    let added = table.len() - before;
+    if added > 0 {
+        *registered = Box::leak(table.into_boxed_slice());
+    }
+    added
 }
 
-/// Find recipe by input item ID and machine type
+/// Add a recipe, replacing any recipe with the same ID (editor live link)
+///
+/// Leaks the combined table like `register_recipes`; only used while editing.
+pub fn upsert_recipe(recipe: Recipe) {
+    let mut registered = REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+    let mut table: Vec<Recipe> = if registered.is_empty() {
+        RECIPES.to_vec()
+    } else {
+        registered.to_vec()
+    };
+    match table.iter_mut().find(|r| r.id == recipe.id) {
+        Some(existing) => *existing = recipe,
+        None => table.push(recipe),
+    }
+    *registered = Box::leak(table.into_boxed_slice());
+}
+
+/// Items per minute for `count` items every `craft_time` seconds at `speed`
+///
+/// `speed` multiplies the crafting rate (`SimulationSpeed`; 1.0 = normal).
+pub fn items_per_minute(count: u32, craft_time: f32, speed: f32) -> f32 {
+    if craft_time <= 0.0 {
+        return 0.0;
+    }
+    count as f32 * 60.0 / craft_time * speed
+}
+
+/// Find recipe by input item ID and machine type (tag inputs match any tagged item)
 pub fn find_recipe(machine: MachineType, input: ItemId) -> Option<&'static Recipe> {
-    RECIPES
+    resolve_recipe(all_recipes(), machine, input)
+}
+
+/// Pick the recipe a machine runs for `input`
+///
+/// Highest `priority` wins; ties go to the lowest recipe ID, so the result
+/// never depends on the order recipes were defined or loaded in.
+pub fn resolve_recipe(recipes: &[Recipe], machine: MachineType, input: ItemId) -> Option<&Recipe> {
+    recipes
         .iter()
-        .find(|r| r.machine == machine && r.inputs.iter().any(|i| i.item == input))
+        .filter(|r| r.machine == machine && r.accepts(input))
+        .min_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.id.cmp(b.id)))
+}
+
+/// Recipes tied for the same input at the same (highest) priority
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecipeConflict {
+    pub machine: MachineType,
+    pub input: ItemId,
+    /// Tied recipe IDs, sorted (the first one is picked)
+    pub recipes: Vec<&'static str>,
+}
+
+impl std::fmt::Display for RecipeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} input {}: {} (picks {})",
+            self.machine,
+            self.input.name().unwrap_or("?"),
+            self.recipes.join(", "),
+            self.recipes[0]
+        )
+    }
+}
+
+/// Inputs for which several recipes tie in `resolve_recipe`
+///
+/// A recipe that is always outranked for an input is not ambiguous, so only
+/// ties at the top priority are reported. Hand recipes are skipped: they are
+/// picked by ID in the crafting tab, not by input.
+pub fn recipe_conflicts(recipes: &[Recipe]) -> Vec<RecipeConflict> {
+    let mut machines: Vec<MachineType> = Vec::new();
+    for recipe in recipes {
+        if recipe.machine != MachineType::Hand && !machines.contains(&recipe.machine) {
+            machines.push(recipe.machine);
+        }
+    }
+
+    let all_items = items::all();
+    let mut conflicts = Vec::new();
+    for machine in machines {
+        for &input in &all_items {
+            let candidates: Vec<&Recipe> = recipes
+                .iter()
+                .filter(|r| r.machine == machine && r.accepts(input))
+                .collect();
+            let Some(top) = candidates.iter().map(|r| r.priority).max() else {
+                continue;
+            };
+            let mut tied: Vec<&'static str> = candidates
+                .iter()
+                .filter(|r| r.priority == top)
+                .map(|r| r.id)
+                .collect();
+            if tied.len() > 1 {
+                tied.sort_unstable();
+                conflicts.push(RecipeConflict {
+                    machine,
+                    input,
+                    recipes: tied,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Log a warning for every ambiguous recipe input (run at startup)
+pub fn warn_recipe_conflicts() {
+    let conflicts = recipe_conflicts(all_recipes());
+    if conflicts.is_empty() {
+        return;
+    }
+    tracing::warn!(
+        count = conflicts.len(),
+        "Ambiguous recipes (same machine and input, no distinct priority)"
+    );
+    for conflict in &conflicts {
+        tracing::warn!("  {}", conflict);
+    }
+}
+
+/// Find the recipe the recycler undoes for `item` (first recipe with it as a guaranteed output)
+pub fn find_recycle_recipe(item: ItemId) -> Option<&'static Recipe> {
+    all_recipes()
+        .iter()
+        .find(|r| r.output_count_of(item) > 0 && !r.recycle_returns().is_empty())
 }
 
 /// Get all recipes for a machine type
 pub fn get_recipes_for_machine(machine: MachineType) -> impl Iterator<Item = &'static Recipe> {
-    RECIPES.iter().filter(move |r| r.machine == machine)
+    all_recipes().iter().filter(move |r| r.machine == machine)
 }
 
 /// Find recipe by ID
 pub fn find_recipe_by_id(id: &str) -> Option<&'static Recipe> {
-    RECIPES.iter().find(|r| r.id == id)
+    all_recipes().iter().find(|r| r.id == id)
+}
+
+/// Check whether a recipe is unlocked (unknown recipes are locked)
+pub fn is_unlocked(
+    recipe_id: &str,
+    current_quest: &CurrentQuest,
+    platform: &PlatformInventory,
+) -> bool {
+    find_recipe_by_id(recipe_id).is_some_and(|r| r.unlock.is_met(current_quest, platform))
+}
+
+/// Find a locked recipe producing `item` (for greying out catalog entries)
+pub fn locked_recipe_for_output(
+    item: ItemId,
+    current_quest: &CurrentQuest,
+    platform: &PlatformInventory,
+) -> Option<&'static Recipe> {
+    all_recipes().iter().find(|r| {
+        r.outputs.iter().any(|o| o.item == item) && !r.unlock.is_met(current_quest, platform)
+    })
 }
 
 // =============================================================================
@@ -339,9 +1054,13 @@ mod tests {
         let crusher_recipes: Vec<_> = get_recipes_for_machine(MachineType::Crusher).collect();
         assert_eq!(crusher_recipes.len(), 2);
 
-        // Assembler: 5 recipes (conveyor, miner, furnace, crusher, assembler)
+        // Assembler: 14 recipes (conveyor, miner, furnace, crusher, assembler, delivery pad, display panel, recycler, lamp, hopper, steel drill x2, conveyor Mk2/Mk3)
         let assembler_recipes: Vec<_> = get_recipes_for_machine(MachineType::Assembler).collect();
-        assert_eq!(assembler_recipes.len(), 5);
+        assert_eq!(assembler_recipes.len(), 14);
+
+        // Hand: 7 recipes (furnace, conveyor, miner, stone/iron drill, ore scanner, sign)
+        let hand_recipes: Vec<_> = get_recipes_for_machine(MachineType::Hand).collect();
+        assert_eq!(hand_recipes.len(), 7);
     }
 
     #[test]
@@ -381,9 +1100,280 @@ mod tests {
         assert_eq!(output, Some(items::iron_ingot()));
     }
 
+    #[test]
+    fn test_tag_input_maps_outputs_per_input() {
+        let recipe = Recipe {
+            id: "smelt_raw_ore",
+            machine: MachineType::Furnace,
+            inputs: vec![RecipeInput::tag("raw_ore", 1, 0)],
+            outputs: vec![
+                RecipeOutput::guaranteed(items::iron_ingot(), 1).for_input(items::iron_ore()),
+                RecipeOutput::guaranteed(items::copper_ingot(), 1).for_input(items::copper_ore()),
+            ],
+            craft_time: 2.0,
+            fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        };
+
+        assert!(recipe.accepts(items::iron_ore()));
+        assert!(recipe.accepts(items::copper_ore()));
+        assert!(!recipe.accepts(items::stone()));
+        assert!(!recipe.accepts(items::iron_dust()));
+        assert_eq!(recipe.input_item(0), None);
+
+        let output = |input| {
+            recipe
+                .outputs_for(input)
+                .map(|o| o.item)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(output(items::iron_ore()), vec![items::iron_ingot()]);
+        assert_eq!(output(items::copper_ore()), vec![items::copper_ingot()]);
+    }
+
     #[test]
     fn test_all_recipes_count() {
-        // Total: 4 furnace + 2 crusher + 5 assembler = 11
-        assert_eq!(all_recipes().len(), 11);
+        // Total: 4 furnace + 2 crusher + 14 assembler + 7 hand = 27
+        assert_eq!(built_in_recipes().len(), 27);
+    }
+
+    #[test]
+    fn test_recycle_returns_half_of_each_ingredient() {
+        // craft_crusher: 10 iron ingot + 5 copper ingot
+        let recipe = find_recycle_recipe(items::crusher_block()).unwrap();
+        assert_eq!(recipe.id, "craft_crusher");
+        assert_eq!(
+            recipe.recycle_returns(),
+            vec![(items::iron_ingot(), 5), (items::copper_ingot(), 2)]
+        );
+        assert_eq!(recipe.output_count_of(items::crusher_block()), 1);
+    }
+
+    #[test]
+    fn test_recycle_returns_at_least_one_ingredient() {
+        // smelt_iron: 1 iron ore rounds down to 0, main ingredient is still returned
+        let recipe = find_recycle_recipe(items::iron_ingot()).unwrap();
+        assert_eq!(recipe.recycle_returns(), vec![(items::iron_ore(), 1)]);
+
+        // Raw materials have no producing recipe
+        assert!(find_recycle_recipe(items::iron_ore()).is_none());
+        assert!(find_recycle_recipe(items::coal()).is_none());
+    }
+
+    fn quest(index: usize, completed: bool) -> CurrentQuest {
+        CurrentQuest {
+            index,
+            completed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unlock_always() {
+        let platform = PlatformInventory::new();
+        assert!(UnlockCondition::Always.is_met(&quest(0, false), &platform));
+        assert_eq!(UnlockCondition::Always.description(), None);
+        // All base recipes are available from the start
+        for recipe in all_recipes() {
+            assert!(is_unlocked(recipe.id, &quest(0, false), &platform));
+        }
+        assert!(!is_unlocked("no_such_recipe", &quest(0, false), &platform));
+    }
+
+    #[test]
+    fn test_unlock_quest_completed() {
+        let platform = PlatformInventory::new();
+        let condition = UnlockCondition::QuestCompleted("main_1".to_string());
+        assert!(!condition.is_met(&quest(0, false), &platform));
+        assert!(condition.is_met(&quest(0, true), &platform));
+        assert!(condition.is_met(&quest(1, false), &platform));
+
+        let unknown = UnlockCondition::QuestCompleted("no_such_quest".to_string());
+        assert!(!unknown.is_met(&quest(99, true), &platform));
+        assert!(condition.description().is_some());
+    }
+
+    #[test]
+    fn test_unlock_item_delivered() {
+        let condition = UnlockCondition::ItemDelivered {
+            item: "base:iron_ingot".to_string(),
+            count: 10,
+        };
+        let mut platform = PlatformInventory::new();
+        platform.add_item_by_id(items::iron_ingot(), 9);
+        assert!(!condition.is_met(&quest(0, false), &platform));
+        platform.add_item_by_id(items::iron_ingot(), 1);
+        assert!(condition.is_met(&quest(0, false), &platform));
+
+        // Short names work too
+        let short = UnlockCondition::ItemDelivered {
+            item: "iron_ingot".to_string(),
+            count: 10,
+        };
+        assert!(short.is_met(&quest(0, false), &platform));
+    }
+
+    #[test]
+    fn test_unlock_condition_serde() {
+        for condition in [
+            UnlockCondition::Always,
+            UnlockCondition::QuestCompleted("main_2".to_string()),
+            UnlockCondition::ItemDelivered {
+                item: "base:copper_ingot".to_string(),
+                count: 50,
+            },
+        ] {
+            let json = serde_json::to_string(&condition).unwrap();
+            let restored: UnlockCondition = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, condition);
+        }
+    }
+
+    fn smelt(id: &'static str, input: ItemId, output: ItemId, priority: i32) -> Recipe {
+        Recipe {
+            id,
+            machine: MachineType::Furnace,
+            inputs: vec![RecipeInput::new(input, 1, 0)],
+            outputs: vec![RecipeOutput::guaranteed(output, 1)],
+            craft_time: 2.0,
+            fuel: Some(FuelRequirement::new(items::coal(), 1)),
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_conflicting_recipes_resolve_by_priority_then_id() {
+        let iron = items::iron_ore();
+        let mut recipes = vec![
+            smelt("smelt_iron_b", iron, items::iron_ingot(), 0),
+            smelt("smelt_iron_a", iron, items::iron_dust(), 0),
+            smelt(
+                "smelt_copper",
+                items::copper_ore(),
+                items::copper_ingot(),
+                0,
+            ),
+        ];
+
+        // Same priority: the lower ID wins regardless of definition order
+        let picked = resolve_recipe(&recipes, MachineType::Furnace, iron).unwrap();
+        assert_eq!(picked.id, "smelt_iron_a");
+        assert_eq!(
+            recipe_conflicts(&recipes),
+            vec![RecipeConflict {
+                machine: MachineType::Furnace,
+                input: iron,
+                recipes: vec!["smelt_iron_a", "smelt_iron_b"],
+            }]
+        );
+
+        // A higher priority settles it and clears the report
+        recipes[0].priority = 5;
+        let picked = resolve_recipe(&recipes, MachineType::Furnace, iron).unwrap();
+        assert_eq!(picked.id, "smelt_iron_b");
+        assert!(recipe_conflicts(&recipes).is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_ignore_outranked_and_hand_recipes() {
+        let stone = items::stone();
+        let mut low = smelt("smelt_low", stone, items::iron_ingot(), 0);
+        low.inputs.push(RecipeInput::new(items::coal(), 1, 1));
+        let recipes = vec![
+            smelt("smelt_top", items::coal(), items::iron_ingot(), 1),
+            low.clone(),
+            Recipe {
+                id: "hand_a",
+                machine: MachineType::Hand,
+                ..low.clone()
+            },
+            Recipe {
+                id: "hand_b",
+                machine: MachineType::Hand,
+                ..low
+            },
+        ];
+        // smelt_low only loses coal to smelt_top; hand recipes are picked by ID
+        assert!(recipe_conflicts(&recipes).is_empty());
+    }
+
+    #[test]
+    fn test_builtin_recipes_have_no_conflicts() {
+        let conflicts = recipe_conflicts(built_in_recipes());
+        assert!(conflicts.is_empty(), "ambiguous recipes: {:?}", conflicts);
+
+        // The assembler keeps its pick for each primary input
+        let assembler = |item| find_recipe(MachineType::Assembler, item).map(|r| r.id);
+        assert_eq!(assembler(items::iron_ingot()), Some("craft_conveyor"));
+        assert_eq!(assembler(items::stone()), Some("craft_miner"));
+        assert_eq!(assembler(items::copper_ingot()), Some("craft_crusher"));
+    }
+
+    #[test]
+    fn test_machine_type_data_names() {
+        assert_eq!(MachineType::from_data_name("furnace"), MachineType::Furnace);
+        assert_eq!(MachineType::from_data_name("hand"), MachineType::Hand);
+        let grinding = MachineType::from_data_name("grinding");
+        assert_eq!(grinding, MachineType::Custom("grinding"));
+        assert_eq!(grinding.data_name(), "grinding");
+        assert_eq!(MachineType::Crusher.data_name(), "crusher");
+    }
+
+    #[test]
+    fn test_register_recipes_adds_new_ids_only() {
+        let work_type = MachineType::from_data_name("test_register_recipes");
+        let recipe = |id: &'static str| Recipe {
+            id,
+            machine: work_type,
+            inputs: vec![RecipeInput::new(items::stone(), 1, 0)],
+            outputs: vec![RecipeOutput::guaranteed(items::iron_dust(), 1)],
+            craft_time: 1.0,
+            fuel: None,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        };
+
+        // Built-in IDs are kept
+        assert_eq!(register_recipes([recipe("smelt_iron")]), 0);
+        assert_eq!(
+            find_recipe_by_id("smelt_iron").unwrap().machine,
+            MachineType::Furnace
+        );
+
+        assert_eq!(register_recipes([recipe("test_register_recipes_grind")]), 1);
+        assert_eq!(register_recipes([recipe("test_register_recipes_grind")]), 0);
+        let found = find_recipe(work_type, items::stone()).unwrap();
+        assert_eq!(found.id, "test_register_recipes_grind");
+        assert_eq!(built_in_recipes().len(), 27);
+    }
+
+    #[test]
+    fn test_items_per_minute() {
+        // smelt_iron: 1 ingot every 2s = 30/min
+        let smelt = find_recipe_by_id("smelt_iron").unwrap();
+        let ingot = &smelt.outputs[0];
+        assert_eq!(smelt.output_per_minute(ingot, 1.0), 30.0);
+        assert_eq!(smelt.input_per_minute(&smelt.inputs[0], 1.0), 30.0);
+
+        // Twice the speed, twice the rate
+        assert_eq!(smelt.output_per_minute(ingot, 2.0), 60.0);
+        assert_eq!(items_per_minute(3, 1.5, 0.5), 60.0);
+
+        // Chance outputs count their expected share
+        let recipe = Recipe {
+            outputs: vec![RecipeOutput::chance(items::coal(), 2, 0.25)],
+            ..smelt.clone()
+        };
+        assert_eq!(recipe.output_per_minute(&recipe.outputs[0], 1.0), 15.0);
+
+        assert_eq!(items_per_minute(1, 0.0, 1.0), 0.0);
     }
 }
@@ -17,12 +17,12 @@
 
 use bevy::prelude::*;
 use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, PoisonError, RwLock};
 
 use crate::core::{items, BlockCategory, ItemId, ValidItemId};
 
 use super::machines::MachineSpec;
-use super::recipes::Recipe;
+use super::recipes::{FuelRequirement, MachineType, Recipe, RecipeInput, RecipeOutput};
 
 // =============================================================================
 // Item Descriptor (unified block/item definition)
@@ -159,6 +159,18 @@ static ITEM_DESCRIPTORS: LazyLock<Vec<(ItemId, ItemDescriptor)>> = LazyLock::new
             )
             .with_hardness(1.0),
         ),
+        (
+            items::crystal(),
+            ItemDescriptor::new(
+                "Crystal",
+                "Cry",
+                (0.6, 0.45, 0.9),
+                BlockCategory::Ore,
+                999,
+                true,
+            )
+            .with_hardness(1.5),
+        ),
         // Processed (not placeable, no hardness needed)
         (
             items::iron_ingot(),
@@ -265,6 +277,30 @@ static ITEM_DESCRIPTORS: LazyLock<Vec<(ItemId, ItemDescriptor)>> = LazyLock::new
             )
             .with_hardness(0.5),
         ),
+        (
+            items::recycler_block(),
+            ItemDescriptor::new(
+                "Recycler",
+                "Rcy",
+                (0.35, 0.45, 0.3),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.5),
+        ),
+        (
+            items::hopper_block(),
+            ItemDescriptor::new(
+                "Hopper",
+                "Hop",
+                (0.3, 0.3, 0.35),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.5),
+        ),
         (
             items::platform_block(),
             ItemDescriptor::new(
@@ -277,6 +313,102 @@ static ITEM_DESCRIPTORS: LazyLock<Vec<(ItemId, ItemDescriptor)>> = LazyLock::new
             )
             .with_hardness(0.5),
         ),
+        (
+            items::pipe_block(),
+            ItemDescriptor::new(
+                "Pipe",
+                "Pipe",
+                (0.55, 0.6, 0.65),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
+        (
+            items::tank_block(),
+            ItemDescriptor::new(
+                "Tank",
+                "Tank",
+                (0.35, 0.45, 0.6),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.5),
+        ),
+        (
+            items::delivery_pad(),
+            ItemDescriptor::new(
+                "Delivery Pad",
+                "Pad",
+                (0.25, 0.6, 0.35),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.5),
+        ),
+        (
+            items::display_panel(),
+            ItemDescriptor::new(
+                "Display Panel",
+                "Disp",
+                (0.15, 0.2, 0.25),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
+        (
+            items::conveyor_block_mk2(),
+            ItemDescriptor::new(
+                "Conveyor Mk2",
+                "Cv2",
+                (0.25, 0.35, 0.55),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
+        (
+            items::conveyor_block_mk3(),
+            ItemDescriptor::new(
+                "Conveyor Mk3",
+                "Cv3",
+                (0.55, 0.25, 0.5),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
+        (
+            items::lamp_block(),
+            ItemDescriptor::new(
+                "Lamp",
+                "Lamp",
+                (1.0, 0.85, 0.45),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
+        (
+            items::sign_block(),
+            ItemDescriptor::new(
+                "Sign",
+                "Sign",
+                (0.55, 0.4, 0.25),
+                BlockCategory::Machine,
+                999,
+                true,
+            )
+            .with_hardness(0.3),
+        ),
         // Tools (not placeable)
         (
             items::stone_pickaxe(),
@@ -289,6 +421,62 @@ static ITEM_DESCRIPTORS: LazyLock<Vec<(ItemId, ItemDescriptor)>> = LazyLock::new
                 false,
             ),
         ),
+        (
+            items::wrench(),
+            ItemDescriptor::new(
+                "Wrench",
+                "Wrench",
+                (0.75, 0.55, 0.3),
+                BlockCategory::Tool,
+                1,
+                false,
+            ),
+        ),
+        (
+            items::ore_scanner(),
+            ItemDescriptor::new(
+                "Ore Scanner",
+                "Scan",
+                (0.3, 0.8, 0.75),
+                BlockCategory::Tool,
+                1,
+                false,
+            ),
+        ),
+        // Miner drill heads (not placeable, inserted in the miner UI)
+        (
+            items::stone_drill(),
+            ItemDescriptor::new(
+                "Stone Drill",
+                "SDrl",
+                (0.55, 0.55, 0.55),
+                BlockCategory::Tool,
+                16,
+                false,
+            ),
+        ),
+        (
+            items::iron_drill(),
+            ItemDescriptor::new(
+                "Iron Drill",
+                "IDrl",
+                (0.8, 0.75, 0.7),
+                BlockCategory::Tool,
+                16,
+                false,
+            ),
+        ),
+        (
+            items::steel_drill(),
+            ItemDescriptor::new(
+                "Steel Drill",
+                "StDrl",
+                (0.45, 0.5, 0.6),
+                BlockCategory::Tool,
+                16,
+                false,
+            ),
+        ),
     ]
 });
 
@@ -306,6 +494,76 @@ pub fn get_item_descriptor(item_id: ItemId) -> Option<&'static ItemDescriptor> {
         .iter()
         .find(|(id, _)| *id == item_id)
         .map(|(_, desc)| desc)
+        .or_else(|| {
+            DATA_ITEM_DESCRIPTORS
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .find(|(id, _)| *id == item_id)
+                .map(|(_, desc)| *desc)
+        })
+}
+
+/// Descriptors for items defined in data packs (registered at startup)
+static DATA_ITEM_DESCRIPTORS: RwLock<Vec<(ItemId, &'static ItemDescriptor)>> =
+    RwLock::new(Vec::new());
+
+/// Register the descriptor of a data pack item so static lookups
+/// (`ItemId::name`, `ItemId::color`, `ItemId::is_machine`...) find it
+///
+/// Built-in items keep their static descriptor; re-registering replaces.
+pub fn register_item_descriptor(item_id: ItemId, descriptor: ItemDescriptor) {
+    if ITEM_DESCRIPTORS.iter().any(|(id, _)| *id == item_id) {
+        return;
+    }
+    let descriptor: &'static ItemDescriptor = Box::leak(Box::new(descriptor));
+    let mut data = DATA_ITEM_DESCRIPTORS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    match data.iter_mut().find(|(id, _)| *id == item_id) {
+        Some(entry) => entry.1 = descriptor,
+        None => data.push((item_id, descriptor)),
+    }
+}
+
+/// Full string ID for a data pack definition ("grinder" in pack "base" -> "base:grinder")
+pub fn data_string_id(mod_id: &str, id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("{}:{}", mod_id, id)
+    }
+}
+
+/// ItemId for a data pack item, interning new ones in the pack's namespace
+///
+/// Unqualified IDs that name a base item ("stone") resolve to the base item.
+pub fn data_item_id(mod_id: &str, id: &str) -> ItemId {
+    (!id.contains(':'))
+        .then(|| items::by_name(id))
+        .flatten()
+        .unwrap_or_else(|| items::intern(&data_string_id(mod_id, id)))
+}
+
+/// Build a descriptor from a data pack item definition
+pub fn descriptor_from_definition(def: &crate::modding::data::ItemDefinition) -> ItemDescriptor {
+    let leak = |s: &str| -> &'static str { Box::leak(s.to_string().into_boxed_str()) };
+    let category = BlockCategory::from_data_name(&def.category).unwrap_or(BlockCategory::Processed);
+    let short_name = if def.short_name.is_empty() {
+        &def.name
+    } else {
+        &def.short_name
+    };
+    let [r, g, b] = def.color.unwrap_or([0.5, 0.5, 0.5]);
+    ItemDescriptor::new(
+        leak(&def.name),
+        leak(short_name),
+        (r, g, b),
+        category,
+        def.stack_size,
+        def.is_placeable,
+    )
+    .with_hardness(def.hardness)
 }
 
 // =============================================================================
@@ -438,8 +696,7 @@ impl GameRegistry {
     /// to exist in the registry.
     pub fn item_by_valid_id(&self, valid_id: ValidItemId) -> &ItemDescriptor {
         // SAFETY: ValidItemId can only be created via validate() which checks existence
-        self.items
-            .get(&valid_id.get())
+        self.item(valid_id.get())
             .expect("ValidItemId must exist in registry")
     }
 
@@ -456,6 +713,11 @@ impl GameRegistry {
     pub fn recipes(&self) -> &[&'static Recipe] {
         &self.recipes
     }
+
+    /// Re-read the recipe table after recipes were registered at runtime
+    pub fn refresh_recipes(&mut self) {
+        self.recipes = super::recipes::all_recipes().iter().collect();
+    }
 }
 
 // =============================================================================
@@ -467,12 +729,17 @@ pub struct RegistryPlugin;
 impl Plugin for RegistryPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameRegistry>()
+            .insert_resource(crate::localization::Localization::load())
             .init_resource::<super::UIElementRegistry>()
+            .init_resource::<super::ItemInfoCache>()
             .add_systems(
                 Startup,
                 (
-                    integrate_mod_items.after(crate::modding::load_base_mod),
-                    load_ui_elements.after(crate::modding::load_base_mod),
+                    integrate_mod_items.after(crate::modding::ModLoadSet),
+                    integrate_mod_machines.after(integrate_mod_items),
+                    load_ui_elements.after(crate::modding::ModLoadSet),
+                    super::build_item_info_cache.after(crate::modding::ModLoadSet),
+                    super::recipes::warn_recipe_conflicts,
                 ),
             );
     }
@@ -499,9 +766,12 @@ pub fn load_ui_elements(mut registry: ResMut<super::UIElementRegistry>) {
 
 /// Integrate items from LoadedModData into GameRegistry
 ///
-/// Currently this only logs the loaded mod items. Full integration requires
-/// mutable string interner support which will be added in a future update.
-fn integrate_mod_items(mod_data: Res<crate::modding::LoadedModData>, registry: Res<GameRegistry>) {
+/// Items that match a static definition keep it. New items are interned
+/// and their descriptors registered both here and for static lookups.
+pub(crate) fn integrate_mod_items(
+    mod_data: Res<crate::modding::LoadedModData>,
+    mut registry: ResMut<GameRegistry>,
+) {
     use bevy::log::info;
 
     let mod_item_count = mod_data.item_count();
@@ -513,22 +783,149 @@ fn integrate_mod_items(mod_data: Res<crate::modding::LoadedModData>, registry: R
             mod_item_count, static_item_count
         );
 
-        // Log which items from TOML match existing static items
         let mut matched = 0;
-        for item_def in mod_data.all_items() {
-            if let Some(item_id) = crate::core::items::by_name(&item_def.id) {
+        let mut added = 0;
+        for (mod_id, pack) in &mod_data.packs {
+            for item_def in &pack.items {
+                let item_id = data_item_id(mod_id, &item_def.id);
                 if registry.items.contains_key(&item_id) {
                     matched += 1;
+                    continue;
                 }
+                let descriptor = descriptor_from_definition(item_def);
+                register_item_descriptor(item_id, descriptor.clone());
+                registry.register_mod_item(item_id, descriptor);
+                added += 1;
             }
         }
 
         if matched > 0 {
             info!("{} mod items match existing static definitions", matched);
         }
+        if added > 0 {
+            info!("Registered {} new items from mods", added);
+        }
     }
 }
 
+/// Register machines and recipes from LoadedModData
+///
+/// A machine needs an item with the same ID (the block that places it);
+/// built-in machines keep their specs. New recipes go into the recipe table,
+/// their `machine` naming a built-in machine or a data machine's work type.
+pub(crate) fn integrate_mod_machines(
+    mod_data: Res<crate::modding::LoadedModData>,
+    mut registry: ResMut<GameRegistry>,
+) {
+    use bevy::log::{info, warn};
+
+    let mut added_machines = 0;
+    for (mod_id, pack) in &mod_data.packs {
+        for machine_def in &pack.machines {
+            if super::machines::ALL_MACHINES
+                .iter()
+                .any(|spec| spec.id == machine_def.id)
+            {
+                continue;
+            }
+            let string_id = data_string_id(mod_id, &machine_def.id);
+            let Some(item_id) = items::by_string_id(&string_id).filter(|id| id.is_machine()) else {
+                warn!(
+                    "Machine '{}' has no machine item with the same ID",
+                    string_id
+                );
+                continue;
+            };
+            let spec = super::machines::register_data_machine(
+                super::machines::machine_spec_from_definition(&string_id, machine_def),
+            );
+            registry.machines.insert(item_id, spec);
+            added_machines += 1;
+        }
+    }
+
+    let mut recipes = Vec::new();
+    for (mod_id, pack) in &mod_data.packs {
+        for recipe_def in &pack.recipes {
+            if super::recipes::built_in_recipes()
+                .iter()
+                .any(|r| r.id == recipe_def.id)
+            {
+                continue;
+            }
+            match recipe_from_definition(mod_id, recipe_def) {
+                Some(recipe) => recipes.push(recipe),
+                None => warn!(
+                    "Recipe '{}' skipped: unknown item or no inputs/outputs",
+                    recipe_def.id
+                ),
+            }
+        }
+    }
+    let added_recipes = super::recipes::register_recipes(recipes);
+    registry.refresh_recipes();
+
+    if added_machines > 0 || added_recipes > 0 {
+        info!(
+            "Registered {} machines and {} recipes from mods",
+            added_machines, added_recipes
+        );
+    }
+}
+
+/// Build a recipe from a data pack definition
+///
+/// None if an item is unknown or the recipe has no inputs or outputs.
+/// Items are looked up in the pack's namespace first, then in base. Inputs
+/// fill slots in ID order; without a process time the recipe takes the base
+/// processing time of its machine.
+pub fn recipe_from_definition(
+    mod_id: &str,
+    def: &crate::modding::data::RecipeDefinition,
+) -> Option<Recipe> {
+    let item = |name: &str| {
+        items::by_string_id(&data_string_id(mod_id, name)).or_else(|| items::by_name(name))
+    };
+    let sorted = |map: &HashMap<String, u32>| {
+        let mut entries: Vec<(String, u32)> = map.iter().map(|(k, &v)| (k.clone(), v)).collect();
+        entries.sort();
+        entries
+    };
+
+    let mut inputs = Vec::new();
+    for (slot, (name, count)) in sorted(&def.inputs).into_iter().enumerate() {
+        inputs.push(RecipeInput::new(item(&name)?, count, slot as u8));
+    }
+    let mut outputs = Vec::new();
+    for (name, count) in sorted(&def.outputs) {
+        outputs.push(RecipeOutput::guaranteed(item(&name)?, count));
+    }
+    if inputs.is_empty() || outputs.is_empty() {
+        return None;
+    }
+    let fuel = match sorted(&def.fuel).into_iter().next() {
+        Some((name, amount)) => Some(FuelRequirement::new(item(&name)?, amount)),
+        None => None,
+    };
+
+    let machine = MachineType::from_data_name(&def.machine);
+    let craft_time = def.process_time.unwrap_or_else(|| {
+        super::machines::machine_spec_for_type(machine).map_or(2.0, |spec| spec.process_time)
+    });
+    Some(Recipe {
+        id: Box::leak(def.id.clone().into_boxed_str()),
+        machine,
+        inputs,
+        outputs,
+        craft_time,
+        fuel,
+        fluid_input: None,
+        fluid_output: None,
+        unlock: def.unlock.clone(),
+        priority: 0,
+    })
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -651,7 +1048,7 @@ mod tests {
         let registry = GameRegistry::new();
         let all_ids: Vec<_> = registry.all_item_ids().collect();
 
-        assert_eq!(all_ids.len(), 16); // All 16 base items
+        assert_eq!(all_ids.len(), 32); // All 32 base items
     }
 
     #[test]
@@ -659,7 +1056,7 @@ mod tests {
         let registry = GameRegistry::new();
         let machine_ids: Vec<_> = registry.all_machine_ids().collect();
 
-        assert_eq!(machine_ids.len(), 4); // 4 machines: miner, furnace, crusher, assembler
+        assert_eq!(machine_ids.len(), 6); // 6 machines: miner, furnace, crusher, assembler, recycler, hopper
     }
 
     #[test]
@@ -739,6 +1136,73 @@ mod tests {
         assert!(no_spec.is_none());
     }
 
+    #[test]
+    fn test_integrate_mod_items_registers_new_items() {
+        use crate::modding::data::{ItemDefinition, ModDataPack};
+        use crate::modding::LoadedModData;
+
+        let mut gear = ItemDefinition::new("test_registry_gear", "Test Gear");
+        gear.category = "machine".to_string();
+        gear.is_placeable = true;
+        gear.color = Some([0.1, 0.2, 0.3]);
+        let pack = ModDataPack {
+            items: vec![ItemDefinition::new("stone", "Stone"), gear],
+            ..Default::default()
+        };
+
+        let mut app = App::new();
+        app.init_resource::<GameRegistry>()
+            .insert_resource(LoadedModData {
+                packs: vec![("test_registry".to_string(), pack)],
+            })
+            .add_systems(Update, integrate_mod_items);
+        app.update();
+
+        // Base items keep their static definition
+        let registry = app.world().resource::<GameRegistry>();
+        assert_eq!(registry.mod_item_count(), 1);
+
+        let gear_id = items::by_string_id("test_registry:test_registry_gear").unwrap();
+        assert!(registry.is_registered(gear_id));
+        assert_eq!(gear_id.name(), Some("test_registry:test_registry_gear"));
+        assert_eq!(gear_id.display_name(), "Test Gear");
+        assert!(gear_id.is_machine());
+        assert!(gear_id.is_placeable());
+    }
+
+    #[test]
+    fn test_core_mod_content_reaches_catalog_and_machines() {
+        use crate::game_spec::{find_recipe, MachineType};
+        use crate::modding::data::{ItemDefinition, ModDataPack, RecipeDefinition};
+        use crate::modding::LoadedModData;
+
+        // Shape of what load_core_mods collects from host_register_* calls
+        let pack = ModDataPack {
+            items: vec![ItemDefinition::new("test_core_ore", "Test Core Ore")],
+            recipes: vec![RecipeDefinition::new("test_core_crush", "crusher")
+                .with_input("test_core_ore", 1)
+                .with_output("iron_dust", 2)],
+            ..Default::default()
+        };
+
+        let mut app = App::new();
+        app.init_resource::<GameRegistry>()
+            .insert_resource(LoadedModData {
+                packs: vec![("test_core_mod".to_string(), pack)],
+            })
+            .add_systems(
+                Update,
+                (integrate_mod_items, integrate_mod_machines).chain(),
+            );
+        app.update();
+
+        let ore = items::by_string_id("test_core_mod:test_core_ore").unwrap();
+        assert!(items::runtime_items().contains(&ore));
+        let recipe = find_recipe(MachineType::Crusher, ore).unwrap();
+        assert_eq!(recipe.id, "test_core_crush");
+        assert_eq!(recipe.outputs[0].item, items::iron_dust());
+    }
+
     #[test]
     fn test_validate_all_base_items() {
         let registry = GameRegistry::new();
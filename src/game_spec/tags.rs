@@ -0,0 +1,73 @@
+//! Item tags used by recipe ingredients
+//!
+//! Tags come from `assets/data/tags.yaml` (tag name → item IDs), bundled at
+//! compile time. Unknown item IDs are skipped with a warning.
+
+use crate::core::{items, ItemId, TagRegistry};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+static ITEM_TAGS: LazyLock<TagRegistry> = LazyLock::new(|| {
+    let yaml = include_str!("../../assets/data/tags.yaml");
+    parse_tags(yaml).unwrap_or_else(|e| {
+        tracing::error!("{}", e);
+        TagRegistry::new()
+    })
+});
+
+/// Build a registry from `tag: [item, ...]` YAML
+pub fn parse_tags(yaml: &str) -> Result<TagRegistry, String> {
+    let tags: BTreeMap<String, Vec<String>> =
+        serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse tags: {}", e))?;
+
+    // TagRegistry::register takes every tag of one item at once
+    let mut by_item: HashMap<ItemId, Vec<String>> = HashMap::new();
+    for (tag, names) in tags {
+        for name in names {
+            let short = name.strip_prefix("base:").unwrap_or(&name);
+            match items::by_name(short) {
+                Some(item_id) => by_item.entry(item_id).or_default().push(tag.clone()),
+                None => tracing::warn!("Unknown item '{}' in tag '{}'", name, tag),
+            }
+        }
+    }
+
+    let mut registry = TagRegistry::new();
+    for (item_id, tags) in by_item {
+        registry.register(item_id, &tags);
+    }
+    Ok(registry)
+}
+
+/// Global item tags
+pub fn item_tags() -> &'static TagRegistry {
+    &ITEM_TAGS
+}
+
+/// Check whether an item belongs to a tag
+pub fn has_tag(item_id: ItemId, tag: &str) -> bool {
+    ITEM_TAGS.has_tag(item_id, tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_tags() {
+        assert!(has_tag(items::iron_ore(), "raw_ore"));
+        assert!(has_tag(items::copper_ore(), "raw_ore"));
+        assert!(!has_tag(items::coal(), "raw_ore"));
+        assert!(has_tag(items::coal(), "fuel"));
+    }
+
+    #[test]
+    fn test_parse_tags_skips_unknown_items() {
+        let registry = parse_tags("metal:\n  - base:iron_ingot\n  - unobtainium\n").unwrap();
+        let metals = registry.items_with_tag("metal");
+        assert_eq!(metals.len(), 1);
+        assert!(metals.contains(&items::iron_ingot()));
+
+        assert!(parse_tags("not: [a mapping").is_err());
+    }
+}
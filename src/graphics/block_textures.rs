@@ -0,0 +1,271 @@
+//! Block texture array built from per-block PNG tiles
+//!
+//! Every placeable block gets a layer in the voxel array texture. Tiles are
+//! read from `assets/textures/blocks/<name>.png` at startup and stacked
+//! vertically, then `configure_array_texture` reinterprets the stack as a
+//! 2D array. Blocks without a PNG get a solid tile in their registry color.
+//!
+//! The WASM build has no synchronous file access, so it keeps the prebuilt
+//! `block_textures_array.png` and only knows the legacy layers.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::core::{items, ItemId};
+
+/// Tile edge length in pixels
+pub const TILE_SIZE: u32 = 16;
+
+/// Bytes in one RGBA8 tile
+pub const TILE_BYTES: usize = (TILE_SIZE * TILE_SIZE * 4) as usize;
+
+/// Layers of the prebuilt `block_textures_array.png`, in order
+pub const LEGACY_LAYERS: [&str; 8] = [
+    "stone",
+    "grass_top",
+    "grass_side",
+    "iron_ore",
+    "copper_ore",
+    "coal_ore",
+    "dirt",
+    "sand",
+];
+
+/// Texture name for one face of a block (grass has separate top/side tiles)
+pub fn face_texture(block_name: &'static str, is_top_face: bool) -> &'static str {
+    match block_name {
+        "grass" if is_top_face => "grass_top",
+        "grass" => "grass_side",
+        "coal" => "coal_ore",
+        name => name,
+    }
+}
+
+/// Layer order of the block texture array
+pub struct BlockTextureLayout {
+    names: Vec<&'static str>,
+}
+
+impl BlockTextureLayout {
+    /// Legacy layers first (so both texture paths agree), then every other placeable block
+    pub fn new(block_names: impl IntoIterator<Item = &'static str>) -> Self {
+        let mut names = LEGACY_LAYERS.to_vec();
+        for block in block_names {
+            for is_top_face in [true, false] {
+                let texture = face_texture(block, is_top_face);
+                if !names.contains(&texture) {
+                    names.push(texture);
+                }
+            }
+        }
+        Self { names }
+    }
+
+    /// Layout for the base game blocks
+    pub fn base() -> &'static Self {
+        static LAYOUT: OnceLock<BlockTextureLayout> = OnceLock::new();
+        LAYOUT.get_or_init(|| {
+            if cfg!(target_arch = "wasm32") {
+                return Self::new([]);
+            }
+            let interner = items::interner();
+            Self::new(
+                items::all()
+                    .into_iter()
+                    .filter(|id| id.is_placeable())
+                    .filter_map(|id| id.local_name(interner)),
+            )
+        })
+    }
+
+    /// Texture names in layer order
+    pub fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+
+    pub fn layer_count(&self) -> u32 {
+        self.names.len() as u32
+    }
+
+    /// Layer of a texture name
+    pub fn layer(&self, texture: &str) -> Option<u32> {
+        self.names
+            .iter()
+            .position(|&name| name == texture)
+            .map(|i| i as u32)
+    }
+
+    /// Layer for a block face (unknown blocks use the stone layer)
+    pub fn face_layer(&self, block_name: &'static str, is_top_face: bool) -> u32 {
+        self.layer(face_texture(block_name, is_top_face))
+            .unwrap_or(0)
+    }
+}
+
+/// Byte range of a layer inside the stacked tile buffer
+pub fn tile_byte_range(layer: u32) -> std::ops::Range<usize> {
+    let start = layer as usize * TILE_BYTES;
+    start..start + TILE_BYTES
+}
+
+/// Stack RGBA8 tiles vertically into one `TILE_SIZE` x (`TILE_SIZE` * n) buffer
+pub fn stack_tiles(tiles: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = vec![0; tiles.len() * TILE_BYTES];
+    for (layer, tile) in tiles.iter().enumerate() {
+        data[tile_byte_range(layer as u32)].copy_from_slice(&tile[..TILE_BYTES]);
+    }
+    data
+}
+
+/// Solid RGBA8 tile in a block's color
+pub fn solid_tile(color: Color) -> Vec<u8> {
+    let rgba = color.to_srgba().to_u8_array();
+    rgba.repeat((TILE_SIZE * TILE_SIZE) as usize)
+}
+
+/// Decode a PNG into an RGBA8 tile (None if it isn't `TILE_SIZE` square)
+fn decode_tile(bytes: &[u8]) -> Option<Image> {
+    let image = Image::from_buffer(
+        bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::nearest(),
+        RenderAssetUsages::default(),
+    )
+    .ok()?
+    .convert(TextureFormat::Rgba8UnormSrgb)?;
+    (image.width() == TILE_SIZE && image.height() == TILE_SIZE).then_some(image)
+}
+
+/// Color used for a texture's fallback tile
+fn fallback_color(texture: &str) -> Color {
+    items::by_name(texture)
+        .map(|id| id.color())
+        .unwrap_or(Color::srgb(0.5, 0.5, 0.5))
+}
+
+/// Per-block 2D tile images for textured machine cubes
+#[derive(Resource, Default)]
+pub struct BlockTextures {
+    tiles: HashMap<ItemId, Handle<Image>>,
+}
+
+impl BlockTextures {
+    /// Material for a block cube: its PNG tile if there is one, else its flat color
+    pub fn material(&self, item_id: ItemId) -> StandardMaterial {
+        match self.tiles.get(&item_id) {
+            Some(texture) => StandardMaterial {
+                base_color_texture: Some(texture.clone()),
+                ..default()
+            },
+            None => StandardMaterial {
+                base_color: item_id.color(),
+                ..default()
+            },
+        }
+    }
+}
+
+/// Build the stacked block texture image from `assets/textures/blocks/`
+pub fn build_block_texture_array(
+    mut images: ResMut<Assets<Image>>,
+    mut array_tex: ResMut<crate::vox_loader::VoxelArrayTexture>,
+    mut block_textures: ResMut<BlockTextures>,
+) {
+    let layout = BlockTextureLayout::base();
+    let mut tiles = Vec::with_capacity(layout.names().len());
+    let mut missing = 0;
+
+    for &texture in layout.names() {
+        let path = format!("assets/textures/blocks/{}.png", texture);
+        let tile = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| decode_tile(&bytes));
+        match tile {
+            Some(image) => {
+                let data = image.data.clone().unwrap_or_default();
+                if let Some(item_id) = items::by_name(texture) {
+                    block_textures.tiles.insert(item_id, images.add(image));
+                }
+                tiles.push(data);
+            }
+            None => {
+                missing += 1;
+                tiles.push(solid_tile(fallback_color(texture)));
+            }
+        }
+    }
+
+    let layers = tiles.len() as u32;
+    let image = Image::new(
+        Extent3d {
+            width: TILE_SIZE,
+            height: TILE_SIZE * layers,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        stack_tiles(&tiles),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    array_tex.texture = images.add(image);
+    array_tex.layer_count = layers;
+    array_tex.is_loaded = false;
+
+    tracing::info!(
+        "Built block texture array: {} layers ({} solid-color fallbacks)",
+        layers,
+        missing
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_layers_come_first() {
+        let layout = BlockTextureLayout::new(["stone", "grass", "miner", "tank_block"]);
+        for (i, name) in LEGACY_LAYERS.iter().enumerate() {
+            assert_eq!(layout.layer(name), Some(i as u32));
+        }
+        assert_eq!(layout.layer("miner"), Some(8));
+        assert_eq!(layout.layer("tank_block"), Some(9));
+        assert_eq!(layout.layer_count(), 10);
+    }
+
+    #[test]
+    fn test_face_layers() {
+        let layout = BlockTextureLayout::new(["grass", "coal"]);
+        assert_eq!(layout.face_layer("grass", true), 1);
+        assert_eq!(layout.face_layer("grass", false), 2);
+        assert_eq!(layout.face_layer("coal", true), 5);
+        // Unknown blocks fall back to stone
+        assert_eq!(layout.face_layer("not_a_block", false), 0);
+    }
+
+    #[test]
+    fn test_stack_tiles_places_each_layer() {
+        let tiles: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; TILE_BYTES]).collect();
+        let data = stack_tiles(&tiles);
+        assert_eq!(data.len(), 3 * TILE_BYTES);
+        for layer in 0..3u32 {
+            let range = tile_byte_range(layer);
+            assert_eq!(range.start, layer as usize * 16 * 16 * 4);
+            assert!(data[range].iter().all(|&b| b == layer as u8));
+        }
+    }
+
+    #[test]
+    fn test_solid_tile_is_one_color() {
+        let tile = solid_tile(Color::srgb_u8(255, 0, 128));
+        assert_eq!(tile.len(), TILE_BYTES);
+        assert!(tile.chunks(4).all(|px| px == [255, 0, 128, 255]));
+    }
+}
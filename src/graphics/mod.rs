@@ -1,5 +1,9 @@
 //! Graphics module - Custom materials and shaders for voxel rendering
 
+pub mod block_textures;
+pub mod palette;
 mod voxel_material;
 
+pub use block_textures::{BlockTextureLayout, BlockTextures};
+pub use palette::BlockPalette;
 pub use voxel_material::VoxelMaterial;
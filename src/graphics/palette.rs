@@ -0,0 +1,147 @@
+//! Block color palette (standard or colorblind-safe)
+//!
+//! UI tints, held items and conveyor item visuals look colors up here
+//! instead of calling `ItemId::color()` directly, so switching palettes
+//! is a single resource change.
+
+use bevy::prelude::*;
+
+use crate::components::HeldItem3DCache;
+use crate::core::{items, ItemId};
+use crate::settings::GameSettings;
+use crate::setup::ui::SLOT_BORDER_COLOR;
+
+/// Machine slot border colors in colorblind mode (blue / vermillion / yellow)
+const COLORBLIND_INPUT_SLOT: Color = Color::srgb(0.0, 0.45, 0.70);
+const COLORBLIND_FUEL_SLOT: Color = Color::srgb(0.84, 0.37, 0.0);
+const COLORBLIND_OUTPUT_SLOT: Color = Color::srgb(0.95, 0.90, 0.25);
+
+/// Alpha of the slot background tint behind item sprites in colorblind mode
+const SLOT_TINT_ALPHA: f32 = 0.45;
+
+/// Active block color palette, mirrored from `GameSettings::colorblind_mode`
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockPalette {
+    pub colorblind: bool,
+}
+
+impl BlockPalette {
+    /// Display color for an item
+    pub fn item_color(&self, item_id: ItemId) -> Color {
+        if self.colorblind {
+            if let Some(color) = item_id
+                .local_name(items::interner())
+                .and_then(colorblind_color)
+            {
+                return color;
+            }
+        }
+        item_id.color()
+    }
+
+    /// Background tint for a filled inventory/hotbar slot (colorblind mode only)
+    pub fn slot_tint(&self, item_id: ItemId) -> Option<Color> {
+        self.colorblind
+            .then(|| self.item_color(item_id).with_alpha(SLOT_TINT_ALPHA))
+    }
+
+    /// Border color for a machine UI slot
+    pub fn machine_slot_border(&self, is_input: bool, is_fuel: bool) -> Color {
+        if !self.colorblind {
+            return SLOT_BORDER_COLOR;
+        }
+        if is_fuel {
+            COLORBLIND_FUEL_SLOT
+        } else if is_input {
+            COLORBLIND_INPUT_SLOT
+        } else {
+            COLORBLIND_OUTPUT_SLOT
+        }
+    }
+}
+
+/// High-contrast replacements for hues that are hard to tell apart with deuteranopia
+/// (ores vs stone, copper vs coal). Based on the Okabe-Ito palette.
+fn colorblind_color(name: &str) -> Option<Color> {
+    let color = match name {
+        "stone" => Color::srgb(0.55, 0.55, 0.55),
+        "grass" => Color::srgb(0.0, 0.62, 0.45),
+        "iron_ore" => Color::srgb(0.80, 0.47, 0.65),
+        "copper_ore" => Color::srgb(0.90, 0.62, 0.0),
+        "coal" => Color::srgb(0.08, 0.08, 0.08),
+        "iron_ingot" => Color::srgb(0.35, 0.70, 0.90),
+        "copper_ingot" => Color::srgb(0.95, 0.90, 0.25),
+        "iron_dust" => Color::srgb(0.0, 0.45, 0.70),
+        "copper_dust" => Color::srgb(0.84, 0.37, 0.0),
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Keep the palette in sync with the colorblind setting
+pub fn sync_block_palette(settings: Res<GameSettings>, mut palette: ResMut<BlockPalette>) {
+    if palette.colorblind != settings.colorblind_mode {
+        palette.colorblind = settings.colorblind_mode;
+    }
+}
+
+/// Recolor the cached held item materials when the palette changes
+pub fn recolor_held_item_materials(
+    palette: Res<BlockPalette>,
+    cache: Option<Res<HeldItem3DCache>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(cache) = cache else {
+        return;
+    };
+    if !palette.is_changed() && !cache.is_added() {
+        return;
+    }
+    for (item_id, handle) in &cache.materials {
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color = palette.item_color(*item_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_colors_differ_per_mode() {
+        let standard = BlockPalette { colorblind: false };
+        let colorblind = BlockPalette { colorblind: true };
+
+        for item_id in [items::iron_ore(), items::copper_ore(), items::coal()] {
+            assert_eq!(standard.item_color(item_id), item_id.color());
+            assert_ne!(standard.item_color(item_id), colorblind.item_color(item_id));
+        }
+        // Ores must not share a color with stone in colorblind mode
+        assert_ne!(
+            colorblind.item_color(items::iron_ore()),
+            colorblind.item_color(items::stone())
+        );
+    }
+
+    #[test]
+    fn test_palette_slot_colors() {
+        let standard = BlockPalette { colorblind: false };
+        let colorblind = BlockPalette { colorblind: true };
+
+        assert!(standard.slot_tint(items::iron_ore()).is_none());
+        assert!(colorblind.slot_tint(items::iron_ore()).is_some());
+        assert_eq!(standard.machine_slot_border(true, false), SLOT_BORDER_COLOR);
+        assert_ne!(
+            colorblind.machine_slot_border(true, false),
+            colorblind.machine_slot_border(false, false)
+        );
+    }
+
+    #[test]
+    fn test_unlisted_items_keep_their_color() {
+        let colorblind = BlockPalette { colorblind: true };
+        let wrench = items::wrench();
+        assert_eq!(colorblind.item_color(wrench), wrench.color());
+    }
+}
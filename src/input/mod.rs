@@ -7,10 +7,23 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::mouse::MouseButton;
 use bevy::input::ButtonInput;
 use bevy::prelude::*;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Semantic game actions that can be triggered by input
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum::EnumIter,
+)]
 pub enum GameAction {
     // Movement
     MoveForward,
@@ -30,6 +43,7 @@ pub enum GameAction {
     ToggleInventory,
     TogglePause,
     ToggleQuest,
+    ToggleQuestLog,
     OpenCommand,
     CloseUI,
     Confirm,
@@ -45,29 +59,287 @@ pub enum GameAction {
     Hotbar7,
     Hotbar8,
     Hotbar9,
+    /// Hold for the radial quick-select menu
+    QuickSelect,
 
     // Block operations
     PrimaryAction,
     SecondaryAction,
     RotateBlock,
+    /// Undo the last wrench paste (with ModifierCtrl)
+    Undo,
+    /// Drop one of the selected item (the whole stack with ModifierCtrl)
+    DropItem,
 
     // Modifier keys
     ModifierShift,
+    ModifierCtrl,
 
     // Debug
     ToggleDebug,
+    /// Show the in-game log console
+    ToggleLogConsole,
+    /// Capture a screenshot (saved next to the last layout)
+    Screenshot,
 
     // Command input
     DeleteChar,
 }
 
+impl GameAction {
+    /// Every action, in the order the controls settings list them
+    pub const ALL: [GameAction; 39] = [
+        GameAction::MoveForward,
+        GameAction::MoveBackward,
+        GameAction::MoveLeft,
+        GameAction::MoveRight,
+        GameAction::Jump,
+        GameAction::Descend,
+        GameAction::LookUp,
+        GameAction::LookDown,
+        GameAction::LookLeft,
+        GameAction::LookRight,
+        GameAction::ToggleInventory,
+        GameAction::TogglePause,
+        GameAction::ToggleQuest,
+        GameAction::ToggleQuestLog,
+        GameAction::OpenCommand,
+        GameAction::CloseUI,
+        GameAction::Confirm,
+        GameAction::Cancel,
+        GameAction::Hotbar1,
+        GameAction::Hotbar2,
+        GameAction::Hotbar3,
+        GameAction::Hotbar4,
+        GameAction::Hotbar5,
+        GameAction::Hotbar6,
+        GameAction::Hotbar7,
+        GameAction::Hotbar8,
+        GameAction::Hotbar9,
+        GameAction::QuickSelect,
+        GameAction::PrimaryAction,
+        GameAction::SecondaryAction,
+        GameAction::RotateBlock,
+        GameAction::Undo,
+        GameAction::DropItem,
+        GameAction::ModifierShift,
+        GameAction::ModifierCtrl,
+        GameAction::ToggleDebug,
+        GameAction::ToggleLogConsole,
+        GameAction::Screenshot,
+        GameAction::DeleteChar,
+    ];
+
+    /// Display name for the controls settings
+    pub fn label(self) -> &'static str {
+        match self {
+            GameAction::MoveForward => "前進",
+            GameAction::MoveBackward => "後退",
+            GameAction::MoveLeft => "左移動",
+            GameAction::MoveRight => "右移動",
+            GameAction::Jump => "ジャンプ・上昇",
+            GameAction::Descend => "下降",
+            GameAction::LookUp => "視点 上",
+            GameAction::LookDown => "視点 下",
+            GameAction::LookLeft => "視点 左",
+            GameAction::LookRight => "視点 右",
+            GameAction::ToggleInventory => "インベントリ",
+            GameAction::TogglePause => "ポーズ",
+            GameAction::ToggleQuest => "クエスト",
+            GameAction::ToggleQuestLog => "クエストログ",
+            GameAction::OpenCommand => "コマンド入力",
+            GameAction::CloseUI => "UIを閉じる",
+            GameAction::Confirm => "決定",
+            GameAction::Cancel => "キャンセル",
+            GameAction::Hotbar1 => "ホットバー1",
+            GameAction::Hotbar2 => "ホットバー2",
+            GameAction::Hotbar3 => "ホットバー3",
+            GameAction::Hotbar4 => "ホットバー4",
+            GameAction::Hotbar5 => "ホットバー5",
+            GameAction::Hotbar6 => "ホットバー6",
+            GameAction::Hotbar7 => "ホットバー7",
+            GameAction::Hotbar8 => "ホットバー8",
+            GameAction::Hotbar9 => "ホットバー9",
+            GameAction::QuickSelect => "クイック選択",
+            GameAction::PrimaryAction => "破壊・使用",
+            GameAction::SecondaryAction => "設置・操作",
+            GameAction::RotateBlock => "回転",
+            GameAction::Undo => "元に戻す",
+            GameAction::DropItem => "アイテムを捨てる",
+            GameAction::ModifierShift => "Shift修飾",
+            GameAction::ModifierCtrl => "Ctrl修飾",
+            GameAction::ToggleDebug => "デバッグ表示",
+            GameAction::ToggleLogConsole => "ログコンソール",
+            GameAction::Screenshot => "スクリーンショット",
+            GameAction::DeleteChar => "文字削除",
+        }
+    }
+}
+
 /// Physical input binding (key or mouse button)
+///
+/// Serialized as the key's name ("KeyW") or "MouseLeft"/"MouseRight"/"MouseMiddle".
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputBinding {
     Key(KeyCode),
     Mouse(MouseButton),
 }
 
+/// Keys that can be bound from the controls settings (and stored in the settings file)
+const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::Delete,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::CapsLock,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Backslash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Backquote,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+];
+
+impl InputBinding {
+    /// Whether the key can be bound in the controls settings
+    pub fn is_bindable_key(key: KeyCode) -> bool {
+        BINDABLE_KEYS.contains(&key)
+    }
+
+    /// Stable name used in the settings file
+    pub fn name(self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::Mouse(button) => format!("Mouse{:?}", button),
+        }
+    }
+
+    /// Parse a name written by [`InputBinding::name`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "MouseLeft" => Some(InputBinding::Mouse(MouseButton::Left)),
+            "MouseRight" => Some(InputBinding::Mouse(MouseButton::Right)),
+            "MouseMiddle" => Some(InputBinding::Mouse(MouseButton::Middle)),
+            _ => BINDABLE_KEYS
+                .iter()
+                .find(|key| format!("{:?}", key) == name)
+                .map(|&key| InputBinding::Key(key)),
+        }
+    }
+
+    /// Short label for UIs ("W", "1", "Space", "左クリック")
+    pub fn label(self) -> String {
+        match self {
+            InputBinding::Mouse(MouseButton::Left) => "左クリック".to_string(),
+            InputBinding::Mouse(MouseButton::Right) => "右クリック".to_string(),
+            InputBinding::Mouse(MouseButton::Middle) => "中クリック".to_string(),
+            InputBinding::Mouse(button) => format!("{:?}", button),
+            InputBinding::Key(key) => {
+                let name = format!("{:?}", key);
+                ["Key", "Digit"]
+                    .iter()
+                    .find_map(|prefix| name.strip_prefix(prefix))
+                    .map(str::to_string)
+                    .unwrap_or(name)
+            }
+        }
+    }
+}
+
+impl Serialize for InputBinding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for InputBinding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        InputBinding::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown input '{}'", name)))
+    }
+}
+
 /// Input Manager resource that handles input mapping and state
 #[derive(Resource)]
 pub struct InputManager {
@@ -150,6 +422,65 @@ impl InputManager {
         self.bindings.get(&action)
     }
 
+    /// Other actions that would fire together with `action` if it were bound to `binding`
+    ///
+    /// Actions that share the binding in the defaults (Escape for pause/close/cancel,
+    /// left Shift for descend/modifier) are meant to overlap and don't count.
+    pub fn conflicts(&self, action: GameAction, binding: InputBinding) -> Vec<GameAction> {
+        let defaults = InputManager::default();
+        let default_has = |a: GameAction| {
+            defaults
+                .get_bindings(a)
+                .is_some_and(|bindings| bindings.contains(&binding))
+        };
+        let mut conflicts: Vec<GameAction> = self
+            .bindings
+            .iter()
+            .filter(|(other, bindings)| **other != action && bindings.contains(&binding))
+            .map(|(other, _)| *other)
+            .filter(|other| !(default_has(action) && default_has(*other)))
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Bind `action` to `binding` alone, unless another action already uses it
+    pub fn rebind(
+        &mut self,
+        action: GameAction,
+        binding: InputBinding,
+    ) -> Result<(), Vec<GameAction>> {
+        let conflicts = self.conflicts(action, binding);
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+        self.bindings.insert(action, vec![binding]);
+        Ok(())
+    }
+
+    /// Restore every action's default bindings
+    pub fn reset_bindings(&mut self) {
+        self.bindings = InputManager::default().bindings;
+    }
+
+    /// Bindings that differ from the defaults (what the settings file stores)
+    pub fn binding_overrides(&self) -> BTreeMap<GameAction, Vec<InputBinding>> {
+        let defaults = InputManager::default();
+        self.bindings
+            .iter()
+            .filter(|(action, bindings)| defaults.get_bindings(**action) != Some(*bindings))
+            .map(|(action, bindings)| (*action, bindings.clone()))
+            .collect()
+    }
+
+    /// Start from the defaults and apply stored overrides
+    pub fn apply_binding_overrides(&mut self, overrides: &BTreeMap<GameAction, Vec<InputBinding>>) {
+        self.reset_bindings();
+        for (action, bindings) in overrides {
+            self.bindings.insert(*action, bindings.clone());
+        }
+    }
+
     /// Update internal state based on physical input
     pub(crate) fn update(
         &mut self,
@@ -251,6 +582,10 @@ impl Default for InputManager {
             GameAction::ToggleQuest,
             vec![InputBinding::Key(KeyCode::KeyQ)],
         );
+        bindings.insert(
+            GameAction::ToggleQuestLog,
+            vec![InputBinding::Key(KeyCode::KeyJ)],
+        );
         bindings.insert(
             GameAction::OpenCommand,
             vec![
@@ -302,6 +637,10 @@ impl Default for InputManager {
             GameAction::Hotbar9,
             vec![InputBinding::Key(KeyCode::Digit9)],
         );
+        bindings.insert(
+            GameAction::QuickSelect,
+            vec![InputBinding::Key(KeyCode::Tab)],
+        );
 
         // Block operations
         bindings.insert(
@@ -316,6 +655,8 @@ impl Default for InputManager {
             GameAction::RotateBlock,
             vec![InputBinding::Key(KeyCode::KeyR)],
         );
+        bindings.insert(GameAction::Undo, vec![InputBinding::Key(KeyCode::KeyZ)]);
+        bindings.insert(GameAction::DropItem, vec![InputBinding::Key(KeyCode::KeyG)]);
 
         // Modifier keys (both shift keys)
         bindings.insert(
@@ -325,12 +666,27 @@ impl Default for InputManager {
                 InputBinding::Key(KeyCode::ShiftRight),
             ],
         );
+        bindings.insert(
+            GameAction::ModifierCtrl,
+            vec![
+                InputBinding::Key(KeyCode::ControlLeft),
+                InputBinding::Key(KeyCode::ControlRight),
+            ],
+        );
 
         // Debug
         bindings.insert(
             GameAction::ToggleDebug,
             vec![InputBinding::Key(KeyCode::F3)],
         );
+        bindings.insert(
+            GameAction::ToggleLogConsole,
+            vec![InputBinding::Key(KeyCode::F8)],
+        );
+        bindings.insert(
+            GameAction::Screenshot,
+            vec![InputBinding::Key(KeyCode::F10)],
+        );
 
         // Command input
         bindings.insert(
@@ -389,6 +745,13 @@ impl Plugin for InputManagerPlugin {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_all_lists_every_action_in_order() {
+        use strum::IntoEnumIterator;
+        let declared: Vec<GameAction> = GameAction::iter().collect();
+        assert_eq!(declared, GameAction::ALL);
+    }
+
     #[test]
     fn test_default_bindings() {
         let manager = InputManager::default();
@@ -510,6 +873,73 @@ mod tests {
         assert!(manager.just_pressed(GameAction::SecondaryAction));
     }
 
+    #[test]
+    fn test_rebind_conflicts() {
+        let mut manager = InputManager::default();
+
+        // W already moves forward
+        let w = InputBinding::Key(KeyCode::KeyW);
+        assert_eq!(
+            manager.rebind(GameAction::Jump, w),
+            Err(vec![GameAction::MoveForward])
+        );
+        assert_eq!(
+            manager.get_bindings(GameAction::Jump).unwrap()[0],
+            InputBinding::Key(KeyCode::Space)
+        );
+
+        // A free key replaces every old binding of the action
+        let f = InputBinding::Key(KeyCode::KeyF);
+        assert_eq!(manager.rebind(GameAction::Jump, f), Ok(()));
+        assert_eq!(manager.get_bindings(GameAction::Jump).unwrap(), &vec![f]);
+        // Rebinding to the key it already has is fine
+        assert_eq!(manager.rebind(GameAction::Jump, f), Ok(()));
+        // ...and now F is taken
+        assert_eq!(
+            manager.conflicts(GameAction::DropItem, f),
+            vec![GameAction::Jump]
+        );
+
+        // Default overlaps are intentional, but only between the actions that share them
+        let escape = InputBinding::Key(KeyCode::Escape);
+        assert!(manager.conflicts(GameAction::Cancel, escape).is_empty());
+        assert_eq!(
+            manager.conflicts(GameAction::RotateBlock, escape),
+            vec![
+                GameAction::TogglePause,
+                GameAction::CloseUI,
+                GameAction::Cancel
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binding_overrides_roundtrip() {
+        let mut manager = InputManager::default();
+        assert!(manager.binding_overrides().is_empty());
+
+        manager
+            .rebind(GameAction::DropItem, InputBinding::Key(KeyCode::KeyH))
+            .unwrap();
+        let overrides = manager.binding_overrides();
+        assert_eq!(overrides.len(), 1);
+
+        let json = serde_json::to_string(&overrides).expect("should serialize");
+        assert_eq!(json, r#"{"DropItem":["KeyH"]}"#);
+        let parsed: BTreeMap<GameAction, Vec<InputBinding>> =
+            serde_json::from_str(&json).expect("should deserialize");
+
+        let mut restored = InputManager::default();
+        restored.apply_binding_overrides(&parsed);
+        assert_eq!(
+            restored.get_bindings(GameAction::DropItem).unwrap(),
+            &vec![InputBinding::Key(KeyCode::KeyH)]
+        );
+
+        restored.reset_bindings();
+        assert!(restored.binding_overrides().is_empty());
+    }
+
     #[test]
     fn test_all_hotbar_bindings() {
         let manager = InputManager::default();
@@ -6,38 +6,52 @@ pub mod achievements;
 pub mod audio;
 pub mod blockbench;
 pub mod blueprint;
+pub mod cinematic;
 pub mod components;
 pub mod constants;
 pub mod core;
 pub mod craft;
+pub mod daynight;
 pub mod debug;
+pub mod devlink;
 pub mod events;
+pub mod fluids;
 pub mod game_data;
 pub mod game_spec;
 pub mod graphics;
 pub mod input;
+pub mod localization;
+pub mod log_console;
 pub mod logging;
 pub mod logistics;
 pub mod machines;
+pub mod main_menu;
 pub mod map;
 pub mod meshes;
 pub mod modding;
+pub mod network;
 pub mod player;
 pub mod plugins;
+pub mod respawn;
 pub mod rng;
 pub mod robot;
 pub mod save;
 pub mod settings;
 pub mod setup;
+pub mod sign;
 pub mod skin;
+pub mod startup_report;
 pub mod statistics;
 pub mod storage;
 pub mod systems;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod ui;
 #[cfg(feature = "updater")]
 pub mod updater;
 pub mod utils;
 pub mod vox_loader;
+pub mod waypoint;
 pub mod world;
 
 // Re-export commonly used types at crate root
@@ -69,13 +83,36 @@ pub use utils::{
 };
 
 // Re-export achievements
-pub use achievements::{AchievementUnlocked, AchievementsPlugin, PlayerAchievements};
+pub use achievements::{
+    AchievementToasts, AchievementUnlocked, AchievementsPlugin, PlayerAchievements,
+};
+
+// Re-export cinematic camera types
+pub use cinematic::{CameraPath, CinematicCamera, CinematicPlugin};
+
+// Re-export day/night types
+pub use daynight::{DayNightPlugin, GameClock};
+
+// Re-export respawn types
+pub use respawn::{RespawnPlugin, SpawnPoint};
+
+// Re-export waypoint types
+pub use waypoint::{WaypointPlugin, Waypoints};
+
+// Re-export sign types
+pub use sign::{Sign, SignPlugin};
+
+// Re-export network types
+pub use network::{HeadlessServerPlugin, NetworkMode, NetworkPlugin};
+
+// Re-export fluid types
+pub use fluids::{FluidNetworks, FluidsPlugin, PipeNetwork};
 
 // Re-export map types
 pub use map::{MapData, MapMarker, MapPlugin, MarkerType, ToggleMap};
 
 // Re-export statistics types
-pub use statistics::{ProductionStats, StatisticsPlugin, TimeSeries};
+pub use statistics::{PlayerStats, ProductionStats, StatKind, StatisticsPlugin, TimeSeries};
 
 // Re-export storage types
 pub use storage::{StorageBlock, StorageNetwork, StoragePlugin, StorageSize};
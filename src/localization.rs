@@ -0,0 +1,274 @@
+//! Locale files (`assets/locales/<lang>.ron`)
+//!
+//! Each file maps a key ("item.stone", "ui.tooltip.used_in") to a name and a
+//! description. The data editor and the game share the files, so
+//! `LocalizationManager` writes are safe against concurrent writers: the
+//! read-modify-write runs under an exclusive lock on `<lang>.ron.lock`,
+//! re-reads the file inside the lock so keys added meanwhile survive, and
+//! replaces the file through a temp file + rename. Keys are kept sorted so
+//! diffs stay stable.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Default locale directory (relative to the working directory)
+pub const LOCALES_DIR: &str = "assets/locales";
+
+/// Language the game UI reads its strings from
+pub const GAME_LANGUAGE: &str = "ja";
+
+/// Name and description for one key
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizationEntry {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl LocalizationEntry {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+/// Contents of one locale file, sorted by key
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LocaleFile {
+    #[serde(default)]
+    pub entries: BTreeMap<String, LocalizationEntry>,
+}
+
+impl LocaleFile {
+    /// Name for `key` (None when missing or empty)
+    pub fn name(&self, key: &str) -> Option<&str> {
+        self.entries
+            .get(key)
+            .map(|entry| entry.name.as_str())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Name for `key`, or the key itself so missing strings stand out
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.name(key).unwrap_or(key)
+    }
+}
+
+/// Locale file error
+#[derive(Debug)]
+pub enum LocalizationError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl std::fmt::Display for LocalizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalizationError::Io(e) => write!(f, "IO error: {}", e),
+            LocalizationError::Parse(e) => write!(f, "Parse error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LocalizationError {}
+
+impl From<std::io::Error> for LocalizationError {
+    fn from(e: std::io::Error) -> Self {
+        LocalizationError::Io(e)
+    }
+}
+
+/// Reads and writes the locale files in one directory
+#[derive(Debug, Clone)]
+pub struct LocalizationManager {
+    dir: PathBuf,
+}
+
+impl Default for LocalizationManager {
+    fn default() -> Self {
+        Self::new(LOCALES_DIR)
+    }
+}
+
+impl LocalizationManager {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path(&self, lang: &str) -> PathBuf {
+        self.dir.join(format!("{}.ron", lang))
+    }
+
+    /// Read a locale file (a missing file is empty)
+    pub fn load(&self, lang: &str) -> Result<LocaleFile, LocalizationError> {
+        read_locale(&self.path(lang))
+    }
+
+    /// Add or replace one entry
+    pub fn update_entry(
+        &self,
+        lang: &str,
+        key: &str,
+        entry: LocalizationEntry,
+    ) -> Result<(), LocalizationError> {
+        self.bulk_update_entries(lang, HashMap::from([(key.to_string(), entry)]))
+    }
+
+    /// Add or replace several entries
+    pub fn update_entries(
+        &self,
+        lang: &str,
+        entries: impl IntoIterator<Item = (String, LocalizationEntry)>,
+    ) -> Result<(), LocalizationError> {
+        self.bulk_update_entries(lang, entries.into_iter().collect())
+    }
+
+    /// Add or replace entries in one locked write
+    ///
+    /// Entries other writers added since this caller last read the file are
+    /// kept; only the given keys change.
+    pub fn bulk_update_entries(
+        &self,
+        lang: &str,
+        entries: HashMap<String, LocalizationEntry>,
+    ) -> Result<(), LocalizationError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(lang);
+        let _lock = lock_exclusive(&path)?;
+
+        let mut locale = read_locale(&path)?;
+        locale.entries.extend(entries);
+        let text = ron::ser::to_string_pretty(&locale, ron::ser::PrettyConfig::default())
+            .map_err(|e| LocalizationError::Parse(e.to_string()))?;
+
+        let tmp = path.with_extension("ron.tmp");
+        fs::write(&tmp, text + "\n")?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+fn read_locale(path: &Path) -> Result<LocaleFile, LocalizationError> {
+    match fs::read_to_string(path) {
+        Ok(text) => ron::de::from_str(&text)
+            .map_err(|e| LocalizationError::Parse(format!("{}: {}", path.display(), e))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(LocaleFile::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Hold an exclusive advisory lock on `<path>.lock` until the file is dropped
+///
+/// The lock lives on a side file because the locale file itself is replaced
+/// by rename while the lock is held.
+fn lock_exclusive(path: &Path) -> Result<File, LocalizationError> {
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("ron.lock"))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// Strings for the game UI (`GAME_LANGUAGE`), read once at startup
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Localization(pub LocaleFile);
+
+impl Localization {
+    /// Load the game language, logging and falling back to no strings on error
+    pub fn load() -> Self {
+        match LocalizationManager::default().load(GAME_LANGUAGE) {
+            Ok(locale) => Self(locale),
+            Err(e) => {
+                warn!("Failed to load locale '{}': {}", GAME_LANGUAGE, e);
+                Self::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_locales_parse() {
+        let manager = LocalizationManager::default();
+        for lang in ["en", "ja"] {
+            let locale = manager.load(lang).unwrap();
+            assert!(locale.name("ui.tooltip.used_in").is_some(), "{}", lang);
+        }
+    }
+
+    #[test]
+    fn test_writes_are_sorted_and_merged() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LocalizationManager::new(dir.path());
+
+        manager
+            .update_entry("en", "item.wood", LocalizationEntry::new("Wood", ""))
+            .unwrap();
+        manager
+            .update_entries(
+                "en",
+                [
+                    (
+                        "item.stone".to_string(),
+                        LocalizationEntry::new("Stone", ""),
+                    ),
+                    ("item.wood".to_string(), LocalizationEntry::new("Log", "")),
+                ],
+            )
+            .unwrap();
+
+        let locale = manager.load("en").unwrap();
+        assert_eq!(locale.text("item.wood"), "Log");
+        assert_eq!(locale.text("item.stone"), "Stone");
+        assert_eq!(locale.text("item.missing"), "item.missing");
+
+        let text = fs::read_to_string(manager.path("en")).unwrap();
+        assert!(text.find("item.stone").unwrap() < text.find("item.wood").unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_writers_keep_both_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LocalizationManager::new(dir.path());
+
+        let writers: Vec<_> = ["a", "b"]
+            .into_iter()
+            .map(|writer| {
+                let manager = manager.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        let key = format!("test.{}_{}", writer, i);
+                        manager
+                            .update_entry("en", &key, LocalizationEntry::new(&key, ""))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let locale = manager.load("en").unwrap();
+        assert_eq!(locale.entries.len(), 100);
+        for writer in ["a", "b"] {
+            for i in 0..50 {
+                let key = format!("test.{}_{}", writer, i);
+                assert_eq!(locale.text(&key), key);
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! In-game log console for bug reports
+//!
+//! - F8 toggles an overlay with the tail of the structured log
+//!   (`category = "BLOCK"`, `action = "place"`, ... fields on tracing events)
+//! - Filter chips narrow it to BLOCK / MACHINE / QUEST / SAVE records
+//! - `/log export` writes the whole buffer as JSON lines to
+//!   `logs/<timestamp>.jsonl` (native) or downloads it (WASM)
+//!
+//! Records come from `RingBufferLogLayer`, installed by `logging::init_logging`.
+//! Logs carry no personal data, so nothing is redacted.
+
+mod ring_buffer;
+
+pub use ring_buffer::{
+    drain_log_records, LogBuffer, LogFilter, LogReceiver, LogRecord, RingBufferLogLayer,
+    FILTER_CATEGORIES, LOG_BUFFER_CAPACITY,
+};
+
+use crate::components::{CommandInputState, GameFont};
+use crate::input::{GameAction, InputManager};
+use crate::setup::ui::{text_font, TEXT_CAPTION, TEXT_SMALL};
+use bevy::prelude::*;
+use tracing::{info, warn};
+
+/// Lines shown in the console
+const CONSOLE_LINES: usize = 24;
+
+const CHIP_BG: Color = Color::srgb(0.2, 0.2, 0.25);
+const CHIP_SELECTED_BG: Color = Color::srgb(0.45, 0.3, 0.05);
+
+/// Log command from `/log ...`
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub enum LogCommandEvent {
+    /// Write the buffer as JSON lines
+    Export,
+}
+
+/// Parse the arguments after `/log` (None = show usage)
+pub fn parse_log_command(args: &[&str]) -> Option<LogCommandEvent> {
+    match args {
+        ["export"] => Some(LogCommandEvent::Export),
+        _ => None,
+    }
+}
+
+/// Whether the console overlay is shown
+#[derive(Resource, Debug, Default)]
+pub struct LogConsoleState {
+    pub open: bool,
+}
+
+/// Console overlay root
+#[derive(Component)]
+pub struct LogConsoleUI;
+
+/// Text holding the log tail
+#[derive(Component)]
+pub struct LogConsoleText;
+
+/// Category filter chip
+#[derive(Component)]
+pub struct LogFilterChip(pub &'static str);
+
+fn setup_log_console(mut commands: Commands, game_font: Res<GameFont>) {
+    commands
+        .spawn((
+            LogConsoleUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                width: Val::Percent(60.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.07, 0.88)),
+            GlobalZIndex(110),
+            Visibility::Hidden,
+        ))
+        .with_children(|console| {
+            console
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(6.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|chips| {
+                    chips.spawn((
+                        Text::new("ログ (F8)  /log export で保存"),
+                        text_font(&game_font.0, TEXT_SMALL),
+                        TextColor(Color::srgb(0.67, 0.67, 0.67)),
+                        Node {
+                            margin: UiRect::right(Val::Px(8.0)),
+                            ..default()
+                        },
+                    ));
+                    for category in FILTER_CATEGORIES {
+                        chips
+                            .spawn((
+                                Button,
+                                LogFilterChip(category),
+                                Node {
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                    border_radius: BorderRadius::all(Val::Px(8.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(CHIP_BG),
+                            ))
+                            .with_child((
+                                Text::new(category),
+                                text_font(&game_font.0, TEXT_SMALL),
+                                TextColor(Color::WHITE),
+                            ));
+                    }
+                });
+            console.spawn((
+                LogConsoleText,
+                Text::new(""),
+                text_font(&game_font.0, TEXT_CAPTION),
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            ));
+        });
+}
+
+/// F8 toggles the console (not while typing a command)
+fn toggle_log_console(
+    input: Res<InputManager>,
+    command_state: Res<CommandInputState>,
+    mut state: ResMut<LogConsoleState>,
+    mut ui_query: Query<&mut Visibility, With<LogConsoleUI>>,
+) {
+    if command_state.open || !input.just_pressed(GameAction::ToggleLogConsole) {
+        return;
+    }
+    state.open = !state.open;
+    for mut visibility in ui_query.iter_mut() {
+        *visibility = if state.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Select or deselect a category on chip click
+fn handle_log_filter_chips(
+    chips: Query<(&Interaction, &LogFilterChip), Changed<Interaction>>,
+    mut filter: ResMut<LogFilter>,
+) {
+    for (interaction, chip) in chips.iter() {
+        if *interaction == Interaction::Pressed {
+            filter.toggle(chip.0);
+        }
+    }
+}
+
+/// Refresh the tail and chip colors while the console is open
+fn update_log_console(
+    state: Res<LogConsoleState>,
+    buffer: Res<LogBuffer>,
+    filter: Res<LogFilter>,
+    mut text_query: Query<&mut Text, With<LogConsoleText>>,
+    mut chips: Query<(&LogFilterChip, &mut BackgroundColor)>,
+) {
+    if !state.open || !(state.is_changed() || buffer.is_changed() || filter.is_changed()) {
+        return;
+    }
+
+    let lines: Vec<String> = buffer
+        .tail(&filter, CONSOLE_LINES)
+        .iter()
+        .map(|record| record.console_line())
+        .collect();
+    for mut text in text_query.iter_mut() {
+        text.0 = lines.join("\n");
+    }
+
+    for (chip, mut bg) in chips.iter_mut() {
+        *bg = BackgroundColor(if filter.is_selected(chip.0) {
+            CHIP_SELECTED_BG
+        } else {
+            CHIP_BG
+        });
+    }
+}
+
+fn handle_log_command(mut events: MessageReader<LogCommandEvent>, buffer: Res<LogBuffer>) {
+    for event in events.read() {
+        match event {
+            LogCommandEvent::Export => {
+                let filename = format!("{}.jsonl", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+                match export_jsonl(&filename, &buffer.to_jsonl()) {
+                    Ok(target) => info!("{}件のログを書き出しました: {}", buffer.len(), target),
+                    Err(e) => warn!("ログの書き出しに失敗しました: {}", e),
+                }
+            }
+        }
+    }
+}
+
+/// Write to `logs/<filename>`, returning the path
+#[cfg(not(target_arch = "wasm32"))]
+fn export_jsonl(filename: &str, contents: &str) -> Result<String, String> {
+    let path = std::path::Path::new("logs").join(filename);
+    std::fs::create_dir_all("logs").map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// Trigger a browser download of `filename`
+#[cfg(target_arch = "wasm32")]
+fn export_jsonl(filename: &str, contents: &str) -> Result<String, String> {
+    use base64::Engine;
+    use web_sys::wasm_bindgen::JsCast;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("no document")?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(|_| "can't create link")?
+        .dyn_into()
+        .map_err(|_| "can't create link")?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(contents);
+    anchor.set_href(&format!("data:application/x-ndjson;base64,{}", encoded));
+    anchor.set_download(filename);
+    anchor.click();
+    Ok(filename.to_string())
+}
+
+pub struct LogConsolePlugin;
+
+impl Plugin for LogConsolePlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(receiver) = LogReceiver::take_installed() {
+            app.insert_resource(receiver);
+        }
+        app.init_resource::<LogBuffer>()
+            .init_resource::<LogFilter>()
+            .init_resource::<LogConsoleState>()
+            .add_message::<LogCommandEvent>()
+            .add_systems(Startup, setup_log_console)
+            .add_systems(First, drain_log_records)
+            .add_systems(
+                Update,
+                (
+                    toggle_log_console,
+                    handle_log_filter_chips,
+                    handle_log_command,
+                    update_log_console,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_command() {
+        assert_eq!(
+            parse_log_command(&["export"]),
+            Some(LogCommandEvent::Export)
+        );
+        assert_eq!(parse_log_command(&[]), None);
+        assert_eq!(parse_log_command(&["import"]), None);
+    }
+}
@@ -0,0 +1,336 @@
+//! In-memory ring buffer of structured log records
+//!
+//! `RingBufferLogLayer` turns every tracing event into a `LogRecord` and pushes
+//! it into a bounded channel with `try_send`, so logging never blocks a frame
+//! (records are dropped while the channel is full). `drain_log_records` moves
+//! them into the `LogBuffer` resource once per frame.
+
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Records kept in memory
+pub const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// Records in flight between the layer and the buffer
+const LOG_CHANNEL_CAPACITY: usize = 1024;
+
+/// Categories offered as filter chips in the console
+pub const FILTER_CATEGORIES: [&str; 5] = ["BLOCK", "MACHINE", "QUEST", "SAVE", "DATA"];
+
+/// One structured log event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogRecord {
+    /// Unix time in seconds
+    pub timestamp: f64,
+    pub level: String,
+    /// `category = "..."` field (BLOCK, MACHINE, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// `action = "..."` field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    pub message: String,
+    /// Remaining fields, formatted with `Debug`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogRecord {
+    /// One console line: `12:34:56 INFO [BLOCK] Block placed block=Stone`
+    pub fn console_line(&self) -> String {
+        let time = chrono::DateTime::from_timestamp_millis((self.timestamp * 1000.0) as i64)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let mut line = format!("{} {:<5}", time, self.level);
+        if let Some(category) = &self.category {
+            line.push_str(&format!(" [{}]", category));
+        }
+        line.push(' ');
+        line.push_str(&self.message);
+        for (key, value) in &self.fields {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+        line
+    }
+}
+
+/// Collects the fields of one event
+#[derive(Default)]
+struct RecordVisitor {
+    category: Option<String>,
+    action: Option<String>,
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "category" => self.category = Some(value.to_string()),
+            "action" => self.action = Some(value.to_string()),
+            "message" => self.message = value.to_string(),
+            name => {
+                self.fields.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "category" | "action" => self.record_str(field, &format!("{:?}", value)),
+            name => {
+                self.fields.insert(name.to_string(), format!("{:?}", value));
+            }
+        }
+    }
+}
+
+/// tracing layer feeding the in-game log console
+pub struct RingBufferLogLayer {
+    sender: SyncSender<LogRecord>,
+}
+
+/// Receiving end handed from `init_logging` to the Bevy app
+static LOG_RECEIVER: Mutex<Option<Receiver<LogRecord>>> = Mutex::new(None);
+
+impl RingBufferLogLayer {
+    pub fn new() -> (Self, Receiver<LogRecord>) {
+        let (sender, receiver) = sync_channel(LOG_CHANNEL_CAPACITY);
+        (Self { sender }, receiver)
+    }
+
+    /// Create the layer and park its receiver for `LogConsolePlugin`
+    pub fn install() -> Self {
+        let (layer, receiver) = Self::new();
+        if let Ok(mut slot) = LOG_RECEIVER.lock() {
+            *slot = Some(receiver);
+        }
+        layer
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RingBufferLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+        let record = LogRecord {
+            timestamp: chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
+            level: event.metadata().level().to_string(),
+            category: visitor.category,
+            action: visitor.action,
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        // Full channel: drop the record rather than stall the frame
+        let _ = self.sender.try_send(record);
+    }
+}
+
+/// Receiver of the installed layer (absent when logging wasn't initialized, e.g. tests)
+#[derive(Resource)]
+pub struct LogReceiver(pub Mutex<Receiver<LogRecord>>);
+
+impl LogReceiver {
+    /// Take the receiver parked by `RingBufferLogLayer::install`
+    pub fn take_installed() -> Option<Self> {
+        LOG_RECEIVER
+            .lock()
+            .ok()
+            .and_then(|mut slot| slot.take())
+            .map(|receiver| Self(Mutex::new(receiver)))
+    }
+}
+
+/// The last `LOG_BUFFER_CAPACITY` records, oldest first
+#[derive(Resource, Debug)]
+pub struct LogBuffer {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::with_capacity(LOG_BUFFER_CAPACITY)
+    }
+}
+
+impl LogBuffer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append a record, evicting the oldest when full
+    pub fn push(&mut self, record: LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogRecord> {
+        self.records.iter()
+    }
+
+    /// The newest `count` records passing `filter`, oldest first
+    pub fn tail<'a>(&'a self, filter: &LogFilter, count: usize) -> Vec<&'a LogRecord> {
+        let mut tail: Vec<_> = self
+            .records
+            .iter()
+            .rev()
+            .filter(|r| filter.matches(r))
+            .take(count)
+            .collect();
+        tail.reverse();
+        tail
+    }
+
+    /// All records as JSON lines
+    pub fn to_jsonl(&self) -> String {
+        let mut out = String::new();
+        for record in &self.records {
+            if let Ok(json) = serde_json::to_string(record) {
+                out.push_str(&json);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// Categories selected in the console (none selected = show everything)
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    categories: BTreeSet<String>,
+}
+
+impl LogFilter {
+    /// Select or deselect a category
+    pub fn toggle(&mut self, category: &str) {
+        let category = category.to_ascii_uppercase();
+        if !self.categories.remove(&category) {
+            self.categories.insert(category);
+        }
+    }
+
+    pub fn is_selected(&self, category: &str) -> bool {
+        self.categories.contains(&category.to_ascii_uppercase())
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        self.categories.is_empty()
+            || record
+                .category
+                .as_ref()
+                .is_some_and(|c| self.categories.contains(&c.to_ascii_uppercase()))
+    }
+}
+
+/// Move records from the layer's channel into the buffer
+pub fn drain_log_records(receiver: Option<Res<LogReceiver>>, mut buffer: ResMut<LogBuffer>) {
+    let Some(receiver) = receiver else {
+        return;
+    };
+    let Ok(receiver) = receiver.0.lock() else {
+        return;
+    };
+    for record in receiver.try_iter() {
+        buffer.push(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(message: &str, category: Option<&str>) -> LogRecord {
+        LogRecord {
+            timestamp: 0.0,
+            level: "INFO".to_string(),
+            category: category.map(str::to_string),
+            action: None,
+            message: message.to_string(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_log_buffer_evicts_oldest() {
+        let mut buffer = LogBuffer::with_capacity(3);
+        for i in 0..5 {
+            buffer.push(record(&i.to_string(), None));
+        }
+
+        assert_eq!(buffer.len(), 3);
+        let messages: Vec<_> = buffer.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["2", "3", "4"]);
+    }
+
+    #[test]
+    fn test_log_filter_matching() {
+        let block = record("placed", Some("BLOCK"));
+        let quest = record("delivered", Some("QUEST"));
+        let plain = record("startup", None);
+
+        let mut filter = LogFilter::default();
+        assert!(filter.matches(&block) && filter.matches(&plain));
+
+        filter.toggle("block");
+        assert!(filter.is_selected("BLOCK"));
+        assert!(filter.matches(&block));
+        assert!(!filter.matches(&quest));
+        assert!(!filter.matches(&plain));
+
+        filter.toggle("QUEST");
+        assert!(filter.matches(&quest));
+
+        filter.toggle("BLOCK");
+        filter.toggle("QUEST");
+        assert!(filter.matches(&plain));
+    }
+
+    #[test]
+    fn test_log_buffer_tail_and_jsonl() {
+        let mut buffer = LogBuffer::with_capacity(10);
+        buffer.push(record("a", Some("BLOCK")));
+        buffer.push(record("b", Some("SAVE")));
+        buffer.push(record("c", Some("BLOCK")));
+
+        let mut filter = LogFilter::default();
+        filter.toggle("BLOCK");
+        let tail: Vec<_> = buffer.tail(&filter, 1).iter().map(|r| &r.message).collect();
+        assert_eq!(tail, ["c"]);
+
+        let jsonl = buffer.to_jsonl();
+        assert_eq!(jsonl.lines().count(), 3);
+        let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+        assert_eq!(first["category"], "BLOCK");
+        assert_eq!(first["message"], "a");
+    }
+}
@@ -13,6 +13,8 @@
 //!
 //! Log collection:
 //! - Logs are written to `logs/game_YYYYMMDD_HHMMSS.log`
+//! - The last records are also kept in memory for the in-game log console
+//!   (F8, `/log export`), see `log_console`
 //!
 //! Crash handling:
 //! - Call `setup_crash_handler()` at the start of main() to capture panic backtraces
@@ -30,6 +32,8 @@ use std::io::Write;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
+use crate::log_console::RingBufferLogLayer;
+
 /// Resource to hold the log file guard (keeps writer alive)
 #[derive(Resource)]
 #[allow(dead_code)]
@@ -121,6 +125,7 @@ pub fn init_logging() -> Option<WorkerGuard> {
         .with(env_filter)
         .with(file_layer)
         .with(stdout_layer)
+        .with(RingBufferLogLayer::install())
         .init();
 
     tracing::info!("Idle Factory - Logging initialized");
@@ -0,0 +1,297 @@
+//! Scrolling belt texture and item bobbing on running conveyors
+//!
+//! Procedural belts share one chevron material per (tier, running) pair, and
+//! glTF belts get one copy of their `belt` material per pair, so the per-frame
+//! update touches a handful of materials however many belts are placed. Belt
+//! UVs run against the direction of travel in mesh space (see
+//! `meshes::create_conveyor_mesh`), so raising the V offset moves the chevrons
+//! with the items whichever way the belt faces. Disabled belts switch to a
+//! material that never scrolls. `GameSettings::conveyor_belt_animation` turns
+//! the scrolling and the bobbing off.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::gltf::GltfMaterialName;
+use bevy::image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::collections::HashMap;
+
+use crate::components::{conveyor_tier_item, Conveyor};
+use crate::constants::{CONVEYOR_ITEM_BOB, CONVEYOR_SPEED};
+use crate::settings::GameSettings;
+
+/// glTF material name of the belt surface in conveyor models
+pub const BELT_MATERIAL_NAME: &str = "belt";
+
+/// Edge length of the chevron texture in pixels
+const BELT_TEXTURE_SIZE: u32 = 16;
+
+/// Which shared belt material a belt uses
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BeltMaterialKey {
+    /// glTF material the copy was made from (None = procedural chevron material)
+    pub source: Option<AssetId<StandardMaterial>>,
+    /// Speed multiplier in thousandths
+    pub speed_permille: u32,
+    pub running: bool,
+}
+
+impl BeltMaterialKey {
+    pub fn new(conveyor: &Conveyor, source: Option<AssetId<StandardMaterial>>) -> Self {
+        Self {
+            source,
+            speed_permille: (conveyor.speed_multiplier * 1000.0).round() as u32,
+            running: conveyor.enabled,
+        }
+    }
+
+    /// Texture repeats (= blocks) scrolled per second, matching the item speed
+    /// of `conveyor_transfer` (0 for stopped belts)
+    pub fn scroll_speed(&self) -> f32 {
+        if self.running {
+            self.speed_permille as f32 / 1000.0 / CONVEYOR_SPEED
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Shared belt materials and the chevron texture, created on first use
+#[derive(Resource, Default)]
+pub struct BeltMaterials {
+    texture: Option<Handle<Image>>,
+    materials: HashMap<BeltMaterialKey, Handle<StandardMaterial>>,
+}
+
+/// Belt material currently on this entity
+#[derive(Component)]
+pub struct BeltMaterialAssigned(pub BeltMaterialKey);
+
+/// Mesh of a glTF conveyor drawn with the model's belt material
+#[derive(Component)]
+pub struct GltfBeltMesh {
+    /// Conveyor the model belongs to
+    pub conveyor: Entity,
+    /// Material from the model, copied once per key
+    pub original: Handle<StandardMaterial>,
+}
+
+/// Height an item on a belt bobs up at `progress` (two bumps per block)
+pub fn conveyor_item_bob(progress: f32) -> f32 {
+    CONVEYOR_ITEM_BOB * (progress * std::f32::consts::TAU).sin().abs()
+}
+
+/// Light chevrons pointing toward v = 0 on a darker belt, greyscale so the
+/// material's base color tints it
+fn chevron_texture() -> Image {
+    let size = BELT_TEXTURE_SIZE as i32;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let tip = (x as f32 - (size as f32 - 1.0) / 2.0).abs() * 0.6;
+            let on_chevron = (y as f32 - 4.0 - tip).rem_euclid(size as f32) < 3.0;
+            let value = if on_chevron { 255 } else { 140 };
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: BELT_TEXTURE_SIZE,
+            height: BELT_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = repeating_sampler();
+    image
+}
+
+fn repeating_sampler() -> ImageSampler {
+    ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::nearest()
+    })
+}
+
+impl BeltMaterials {
+    /// Shared material for `key`, created on first use
+    fn get_or_create(
+        &mut self,
+        key: BeltMaterialKey,
+        materials: &mut Assets<StandardMaterial>,
+        images: &mut Assets<Image>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(handle) = self.materials.get(&key) {
+            return handle.clone();
+        }
+        let texture = self
+            .texture
+            .get_or_insert_with(|| images.add(chevron_texture()))
+            .clone();
+        let material = match key.source.and_then(|id| materials.get(id)) {
+            Some(original) => {
+                let mut copy = original.clone();
+                match &copy.base_color_texture {
+                    // The model's own texture has to wrap for the offset to scroll
+                    Some(image) => {
+                        if let Some(image) = images.get_mut(image) {
+                            image.sampler = repeating_sampler();
+                        }
+                    }
+                    None => copy.base_color_texture = Some(texture),
+                }
+                copy
+            }
+            None => StandardMaterial {
+                base_color: conveyor_tier_item(key.speed_permille as f32 / 1000.0).color(),
+                base_color_texture: Some(texture),
+                perceptual_roughness: 0.9,
+                ..default()
+            },
+        };
+        let handle = materials.add(material);
+        self.materials.insert(key, handle.clone());
+        handle
+    }
+}
+
+/// Tag meshes of freshly spawned glTF conveyors that use the belt material
+pub fn tag_gltf_belt_meshes(
+    mut commands: Commands,
+    meshes: Query<
+        (Entity, &GltfMaterialName, &MeshMaterial3d<StandardMaterial>),
+        Added<GltfMaterialName>,
+    >,
+    parents: Query<&ChildOf>,
+    conveyors: Query<(), With<Conveyor>>,
+) {
+    for (entity, name, material) in meshes.iter() {
+        if !name.0.eq_ignore_ascii_case(BELT_MATERIAL_NAME) {
+            continue;
+        }
+        if let Some(conveyor) = parents
+            .iter_ancestors(entity)
+            .find(|&ancestor| conveyors.contains(ancestor))
+        {
+            commands.entity(entity).insert(GltfBeltMesh {
+                conveyor,
+                original: material.0.clone(),
+            });
+        }
+    }
+}
+
+/// Give belts the shared material for their tier and running state
+#[allow(clippy::type_complexity)]
+pub fn assign_belt_materials(
+    mut commands: Commands,
+    mut belt_materials: ResMut<BeltMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    procedural: Query<
+        (Entity, &Conveyor, Option<&BeltMaterialAssigned>),
+        (With<Mesh3d>, Without<SceneRoot>),
+    >,
+    gltf_belts: Query<(Entity, &GltfBeltMesh, Option<&BeltMaterialAssigned>)>,
+    conveyors: Query<&Conveyor>,
+) {
+    let mut assign =
+        |entity: Entity, key: BeltMaterialKey, current: Option<&BeltMaterialAssigned>| {
+            if current.is_some_and(|assigned| assigned.0 == key) {
+                return;
+            }
+            let handle = belt_materials.get_or_create(key, &mut materials, &mut images);
+            commands
+                .entity(entity)
+                .insert((MeshMaterial3d(handle), BeltMaterialAssigned(key)));
+        };
+
+    for (entity, conveyor, current) in procedural.iter() {
+        assign(entity, BeltMaterialKey::new(conveyor, None), current);
+    }
+    for (entity, belt, current) in gltf_belts.iter() {
+        let Ok(conveyor) = conveyors.get(belt.conveyor) else {
+            continue;
+        };
+        assign(
+            entity,
+            BeltMaterialKey::new(conveyor, Some(belt.original.id())),
+            current,
+        );
+    }
+}
+
+/// Scroll every running belt material toward its direction of travel
+pub fn scroll_belt_materials(
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+    belt_materials: Res<BeltMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !settings.conveyor_belt_animation {
+        return;
+    }
+    let elapsed = time.elapsed_secs_f64();
+    for (key, handle) in &belt_materials.materials {
+        let speed = key.scroll_speed();
+        if speed == 0.0 {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle) {
+            let offset = (elapsed * speed as f64).rem_euclid(1.0) as f32;
+            material.uv_transform = Affine2::from_translation(Vec2::new(0.0, offset));
+        }
+    }
+}
+
+pub struct BeltAnimationPlugin;
+
+impl Plugin for BeltAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BeltMaterials>().add_systems(
+            Update,
+            (
+                tag_gltf_belt_meshes,
+                assign_belt_materials,
+                scroll_belt_materials,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ConveyorShape, Direction};
+
+    #[test]
+    fn test_belt_key_scrolls_with_tier_and_stops_when_disabled() {
+        let mut conveyor = Conveyor {
+            position: IVec3::ZERO,
+            direction: Direction::North,
+            output_direction: Direction::North,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        };
+        let base = BeltMaterialKey::new(&conveyor, None);
+        assert!((base.scroll_speed() - 1.0 / CONVEYOR_SPEED).abs() < 1e-6);
+
+        conveyor.speed_multiplier = 4.0;
+        let mk3 = BeltMaterialKey::new(&conveyor, None);
+        assert_ne!(mk3, base);
+        assert!((mk3.scroll_speed() - 4.0 * base.scroll_speed()).abs() < 1e-6);
+
+        conveyor.enabled = false;
+        assert_eq!(BeltMaterialKey::new(&conveyor, None).scroll_speed(), 0.0);
+    }
+}
@@ -0,0 +1,200 @@
+//! Belt connection hints for the machine placement preview
+//!
+//! While a machine item is held, every belt next to the preview gets a
+//! chevron: green when the belt will insert into the machine, blue when the
+//! machine will hand output to it, grey when it won't interact. Input faces
+//! come from `machine_accepts_from`, the same rules `Machine::insert_from`
+//! applies at runtime, and output faces from a freshly placed `Machine`.
+
+use crate::components::{machine_accepts_from, Machine, SlotTarget};
+use crate::game_spec::{MachineSpec, ProcessType};
+use crate::{Conveyor, Direction};
+use bevy::prelude::*;
+
+/// How an adjacent belt will interact with the machine
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BeltConnection {
+    /// The belt inserts into this slot
+    Feeds(SlotTarget),
+    /// The machine outputs onto the belt
+    Receives,
+    /// Adjacent but not connected
+    Idle,
+}
+
+/// One belt next to the machine preview
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectionHint {
+    /// Face of the machine the belt is on
+    pub side: Direction,
+    pub connection: BeltConnection,
+}
+
+/// Hints for belts on the four sides of a machine about to be placed
+///
+/// A belt is only fed from on its own level, so belts above or below get no hint.
+pub fn connection_hints<'a>(
+    spec: &'static MachineSpec,
+    position: IVec3,
+    facing: Direction,
+    conveyors: impl IntoIterator<Item = &'a Conveyor>,
+) -> Vec<ConnectionHint> {
+    let machine = Machine::new(spec, position, facing);
+    // Same candidates as try_output_to_conveyor / try_output_round_robin / the hopper push
+    let outputs = match spec.process_type {
+        ProcessType::Recipe(_) | ProcessType::AutoGenerate => machine.output_positions(),
+        ProcessType::Recycle => [facing, facing.right(), facing.opposite(), facing.left()]
+            .iter()
+            .map(|side| position + side.to_ivec3())
+            .collect(),
+        ProcessType::Hopper => vec![machine.hopper_target()],
+        _ => Vec::new(),
+    };
+
+    let mut hints = Vec::new();
+    for conveyor in conveyors {
+        let offset = conveyor.position - position;
+        let Some(side) = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+        .find(|d| d.to_ivec3() == offset) else {
+            continue;
+        };
+
+        let target = machine_accepts_from(spec, facing, offset);
+        let flows_in = conveyor.outputs_to(position);
+        let connection = if flows_in && target != SlotTarget::None {
+            BeltConnection::Feeds(target)
+        } else if outputs.contains(&conveyor.position)
+            && conveyor.position + conveyor.direction.to_ivec3() != position
+        {
+            BeltConnection::Receives
+        } else {
+            BeltConnection::Idle
+        };
+        hints.push(ConnectionHint { side, connection });
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+    use crate::game_spec::{ASSEMBLER, CRUSHER, FURNACE, HOPPER, MINER, RECYCLER};
+    use crate::logistics::conveyor::takes_belt_input;
+    use crate::ConveyorShape;
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn belt(position: IVec3, direction: Direction) -> Conveyor {
+        Conveyor {
+            position,
+            direction,
+            output_direction: direction,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_hints_match_insert_from_across_faces() {
+        let probes = [
+            items::iron_ore(),
+            items::coal(),
+            items::iron_ingot(),
+            items::conveyor_block(),
+            items::furnace_block(),
+        ];
+        let origin = IVec3::new(3, 8, -2);
+        for spec in [&FURNACE, &CRUSHER, &RECYCLER, &HOPPER, &ASSEMBLER, &MINER] {
+            for facing in DIRECTIONS {
+                for side in DIRECTIONS {
+                    for belt_dir in DIRECTIONS {
+                        let conveyor = belt(origin + side.to_ivec3(), belt_dir);
+                        let hints = connection_hints(spec, origin, facing, [&conveyor]);
+                        assert_eq!(hints.len(), 1);
+                        let hint = hints[0];
+                        assert_eq!(hint.side, side);
+
+                        // What conveyor_transfer + insert_from would actually do
+                        let machine = Machine::new(spec, origin, facing);
+                        let accepts =
+                            |item| machine.clone().insert_from(item, 1, conveyor.position) > 0;
+                        let inserts = conveyor.outputs_to(origin)
+                            && takes_belt_input(&machine)
+                            && probes.iter().any(|&item| accepts(item));
+                        let context = format!(
+                            "{} facing {:?}, belt {:?} on {:?}",
+                            spec.id, facing, belt_dir, side
+                        );
+                        assert_eq!(
+                            matches!(hint.connection, BeltConnection::Feeds(_)),
+                            inserts,
+                            "{}",
+                            context
+                        );
+                        match hint.connection {
+                            BeltConnection::Feeds(SlotTarget::Fuel) => {
+                                assert!(accepts(items::coal()), "{}", context);
+                                assert!(!accepts(items::iron_ore()), "{}", context);
+                            }
+                            BeltConnection::Feeds(SlotTarget::Input) if spec.id == FURNACE.id => {
+                                assert!(accepts(items::iron_ore()), "{}", context);
+                                assert!(!accepts(items::coal()), "{}", context);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hints_output_and_idle_belts() {
+        let origin = IVec3::ZERO;
+        // Furnace facing east: ore from the west, fuel from north/south, output east
+        let feeding = belt(IVec3::new(-1, 0, 0), Direction::East);
+        let fuel = belt(IVec3::new(0, 0, 1), Direction::North);
+        let output = belt(IVec3::new(1, 0, 0), Direction::East);
+        let passing = belt(IVec3::new(0, 0, -1), Direction::East);
+        let above = belt(IVec3::new(0, 1, 0), Direction::East);
+        let hints = connection_hints(
+            &FURNACE,
+            origin,
+            Direction::East,
+            [&feeding, &fuel, &output, &passing, &above],
+        );
+        let connection = |side| hints.iter().find(|h| h.side == side).map(|h| h.connection);
+        assert_eq!(hints.len(), 4);
+        assert_eq!(
+            connection(Direction::West),
+            Some(BeltConnection::Feeds(SlotTarget::Input))
+        );
+        assert_eq!(
+            connection(Direction::South),
+            Some(BeltConnection::Feeds(SlotTarget::Fuel))
+        );
+        assert_eq!(connection(Direction::East), Some(BeltConnection::Receives));
+        assert_eq!(connection(Direction::North), Some(BeltConnection::Idle));
+
+        // A belt flowing back into the output face gets nothing
+        let backwards = belt(IVec3::new(1, 0, 0), Direction::West);
+        let hints = connection_hints(&FURNACE, origin, Direction::East, [&backwards]);
+        assert_eq!(hints[0].connection, BeltConnection::Idle);
+    }
+}
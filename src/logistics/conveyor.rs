@@ -1,15 +1,23 @@
 //! Conveyor systems: transfer, visuals
 
+use super::belt_animation::conveyor_item_bob;
+use super::delivery_filter::{backed_up, drains_platform, DeliveryFilter};
 use crate::components::Machine;
-use crate::constants::{CONVEYOR_ITEM_SPACING, CONVEYOR_SPEED, PLATFORM_SIZE};
+use crate::constants::{
+    CONVEYOR_ITEM_SPACING, CONVEYOR_SPEED, CONVEYOR_STACK_OFFSET, SIMULATION_TICK_SECS,
+};
 use crate::core::id::ItemId;
-use crate::core::items;
 use crate::events::game_events::{ConveyorTransfer, ItemDelivered};
 use crate::events::GuardedMessageWriter;
+use crate::game_spec::{MachineType, ProcessType};
+use crate::graphics::BlockPalette;
 use crate::player::LocalPlatformInventory;
+use crate::settings::GameSettings;
 use crate::{
-    Conveyor, ConveyorItemVisual, ConveyorShape, DeliveryPlatform, Direction, MachineModels,
-    BLOCK_SIZE, CONVEYOR_BELT_HEIGHT, CONVEYOR_ITEM_SIZE,
+    Conveyor, ConveyorItemBillboard, ConveyorItemCulling, ConveyorItemStackVisual,
+    ConveyorItemVisual, ConveyorShape, ConveyorVisualAssets, DeliveryPlatform, Direction,
+    ItemSprites, MachineModels, Player, PlayerCamera, BLOCK_SIZE, CONVEYOR_BELT_HEIGHT,
+    CONVEYOR_ITEM_SIZE,
 };
 use bevy::prelude::*;
 use bevy::time::Fixed;
@@ -17,13 +25,14 @@ use std::collections::{HashMap, HashSet};
 use tracing::info;
 
 /// Conveyor transfer logic - move items along conveyor chain (supports multiple items per conveyor)
-#[allow(clippy::too_many_arguments)]
+///
+/// Runs in FixedUpdate after generic_machine_tick; advances one simulation tick per run.
 pub fn conveyor_transfer(
-    time: Res<Time>,
     mut commands: Commands,
     mut conveyor_query: Query<(Entity, &mut Conveyor)>,
-    mut machine_query: Query<&mut Machine>,
-    platform_query: Query<(&Transform, &DeliveryPlatform)>,
+    mut machine_query: Query<(Entity, &mut Machine)>,
+    platform_query: Query<(Entity, &DeliveryPlatform)>,
+    mut filter_query: Query<&mut DeliveryFilter>,
     mut platform_inventory: LocalPlatformInventory,
     mut transfer_events: GuardedMessageWriter<ConveyorTransfer>,
     mut delivery_events: GuardedMessageWriter<ItemDelivered>,
@@ -34,28 +43,27 @@ pub fn conveyor_transfer(
         .map(|(e, c)| (c.position, e))
         .collect();
 
-    // Collect furnace and crusher positions from Machine components
-    let mut furnace_positions: HashMap<IVec3, Entity> = HashMap::new();
-    let mut crusher_positions: HashMap<IVec3, Entity> = HashMap::new();
+    // Machines that take belt input (furnace, crusher, recycler, hopper)
+    let mut machine_positions: HashMap<IVec3, Entity> = HashMap::new();
+    // (machine position, neighbor position) pairs whose face is marked pass-through
+    let mut pass_through_faces: HashSet<(IVec3, IVec3)> = HashSet::new();
 
-    for (entity, machine) in machine_query.iter().map(|m| (Entity::PLACEHOLDER, m)) {
-        let machine_id = machine.spec.item_id();
-        if machine_id == items::furnace_block() {
-            furnace_positions.insert(machine.position, entity);
-        } else if machine_id == items::crusher_block() {
-            crusher_positions.insert(machine.position, entity);
+    for (entity, machine) in machine_query.iter() {
+        for side in &machine.pass_through_sides {
+            pass_through_faces.insert((machine.position, machine.position + side.to_ivec3()));
+        }
+        if takes_belt_input(machine) {
+            machine_positions.insert(machine.position, entity);
         }
     }
 
-    // Check if position is on delivery platform
-    let platform_bounds: Option<(IVec3, IVec3)> = platform_query.iter().next().map(|(t, _)| {
-        let center = crate::world_to_grid(t.translation);
-        let half = PLATFORM_SIZE / 2;
-        (
-            IVec3::new(center.x - half, center.y, center.z - half),
-            IVec3::new(center.x + half, center.y, center.z + half),
-        )
-    });
+    // Delivery platform covering a position (starter platform and pad platforms)
+    let platform_at = |pos: IVec3| -> Option<Entity> {
+        platform_query
+            .iter()
+            .find(|(_, platform)| platform.contains(pos))
+            .map(|(entity, _)| entity)
+    };
 
     // Transfer actions to apply
     struct TransferAction {
@@ -67,22 +75,25 @@ pub fn conveyor_transfer(
     }
     enum TransferTarget {
         Conveyor(Entity, IVec3), // Target conveyor entity and position
-        Furnace(IVec3),
-        Crusher(IVec3),
-        Delivery,
+        Machine(Entity),         // Furnace, crusher, recycler or hopper
+        Delivery(Entity),        // Receiving platform
     }
 
     let mut actions: Vec<TransferAction> = Vec::new();
 
     // Collect events to send
-    let mut conveyor_transfer_items: Vec<(IVec3, IVec3, ItemId)> = Vec::new();
-    let mut delivered_items: Vec<(ItemId, u32)> = Vec::new();
+    let mut conveyor_transfer_items: Vec<(IVec3, IVec3, ItemId, u32)> = Vec::new();
+    let mut delivered_items: Vec<(Entity, ItemId, u32)> = Vec::new();
 
     // Track splitter output indices for round-robin (entity -> next output index)
     let mut splitter_indices: HashMap<Entity, usize> = HashMap::new();
 
     // First pass: update progress and collect transfer actions
     for (entity, conveyor) in conveyor_query.iter() {
+        // Disabled conveyors hold their items
+        if !conveyor.enabled {
+            continue;
+        }
         for (idx, item) in conveyor.items.iter().enumerate() {
             // Only transfer items that reached the end
             if item.progress < 1.0 {
@@ -107,46 +118,24 @@ pub fn conveyor_transfer(
                 vec![conveyor.position + conveyor.output_direction.to_ivec3()]
             };
 
-            // Try each output position in order
+            // Try each output position in order. Outputs are always horizontal, so a belt
+            // only inserts into a machine on its own Y level: an elevated belt passing over
+            // a furnace/crusher carries items across instead of dropping them in (a hopper
+            // placed on top of the machine does that).
+            // Machine faces marked pass-through are skipped so splitters route past them.
             let mut found_target = false;
             for next_pos in output_positions {
+                let pass_through = pass_through_faces.contains(&(next_pos, conveyor.position));
                 // Check if next position is on delivery platform
-                if let Some((min, max)) = platform_bounds {
-                    if next_pos.x >= min.x
-                        && next_pos.x <= max.x
-                        && next_pos.y >= min.y
-                        && next_pos.y <= max.y
-                        && next_pos.z >= min.z
-                        && next_pos.z <= max.z
-                    {
-                        actions.push(TransferAction {
-                            source_entity: entity,
-                            source_pos: conveyor.position,
-                            item_index: idx,
-                            item_id: item.item_id,
-                            target: TransferTarget::Delivery,
-                        });
-                        // Update splitter index for next item
-                        if conveyor.shape == ConveyorShape::Splitter {
-                            let current = splitter_indices
-                                .entry(entity)
-                                .or_insert(conveyor.last_output_index);
-                            *current = (*current + 1) % 3;
-                        }
-                        found_target = true;
-                        break;
-                    }
-                }
-
-                // Check if next position has a conveyor
-                if let Some(&next_entity) = conveyor_positions.get(&next_pos) {
+                if let Some(platform) = platform_at(next_pos) {
                     actions.push(TransferAction {
                         source_entity: entity,
                         source_pos: conveyor.position,
                         item_index: idx,
                         item_id: item.item_id,
-                        target: TransferTarget::Conveyor(next_entity, next_pos),
+                        target: TransferTarget::Delivery(platform),
                     });
+                    // Update splitter index for next item
                     if conveyor.shape == ConveyorShape::Splitter {
                         let current = splitter_indices
                             .entry(entity)
@@ -155,13 +144,16 @@ pub fn conveyor_transfer(
                     }
                     found_target = true;
                     break;
-                } else if furnace_positions.contains_key(&next_pos) {
+                }
+
+                // Check if next position has a conveyor
+                if let Some(&next_entity) = conveyor_positions.get(&next_pos) {
                     actions.push(TransferAction {
                         source_entity: entity,
                         source_pos: conveyor.position,
                         item_index: idx,
                         item_id: item.item_id,
-                        target: TransferTarget::Furnace(next_pos),
+                        target: TransferTarget::Conveyor(next_entity, next_pos),
                     });
                     if conveyor.shape == ConveyorShape::Splitter {
                         let current = splitter_indices
@@ -171,13 +163,15 @@ pub fn conveyor_transfer(
                     }
                     found_target = true;
                     break;
-                } else if crusher_positions.contains_key(&next_pos) {
+                } else if let Some(&machine) =
+                    machine_positions.get(&next_pos).filter(|_| !pass_through)
+                {
                     actions.push(TransferAction {
                         source_entity: entity,
                         source_pos: conveyor.position,
                         item_index: idx,
                         item_id: item.item_id,
-                        target: TransferTarget::Crusher(next_pos),
+                        target: TransferTarget::Machine(machine),
                     });
                     if conveyor.shape == ConveyorShape::Splitter {
                         let current = splitter_indices
@@ -204,14 +198,14 @@ pub fn conveyor_transfer(
     actions.sort_by(|a, b| b.item_index.cmp(&a.item_index));
 
     // === ZIPPER MERGE LOGIC ===
-    // Group sources by target conveyor for zipper merge (HashSet for O(1) dedup)
-    let mut sources_by_target: HashMap<Entity, HashSet<Entity>> = HashMap::new();
+    // Group pending sources by target conveyor (source entity, source position)
+    let mut sources_by_target: HashMap<Entity, Vec<(Entity, IVec3)>> = HashMap::new();
     for action in &actions {
         if let TransferTarget::Conveyor(target, _) = action.target {
-            sources_by_target
-                .entry(target)
-                .or_default()
-                .insert(action.source_entity);
+            let sources = sources_by_target.entry(target).or_default();
+            if !sources.iter().any(|(e, _)| *e == action.source_entity) {
+                sources.push((action.source_entity, action.source_pos));
+            }
         }
     }
 
@@ -220,23 +214,16 @@ pub fn conveyor_transfer(
     let allowed_source: HashMap<Entity, Entity> = sources_by_target
         .iter()
         .filter_map(|(target, sources)| {
-            if sources.len() <= 1 {
-                // Only one source, always allow
-                sources.iter().next().map(|s| (*target, *s))
-            } else {
-                // Multiple sources - use zipper logic with last_input_source
-                conveyor_query.get(*target).ok().map(|(_, c)| {
-                    let mut sorted_sources: Vec<Entity> = sources.iter().copied().collect();
-                    sorted_sources.sort_by_key(|e| e.index());
-                    let idx = c.last_input_source % sorted_sources.len();
-                    (*target, sorted_sources[idx])
-                })
-            }
+            let last = conveyor_query
+                .get(*target)
+                .ok()
+                .and_then(|(_, c)| c.last_input_pos);
+            pick_zipper_source(sources, last).map(|s| (*target, s))
         })
         .collect();
 
-    // Track which targets successfully received an item (to update last_input_source)
-    let mut targets_to_update: HashSet<Entity> = HashSet::new();
+    // Track which source successfully fed each target (to update last_input_pos)
+    let mut targets_to_update: HashMap<Entity, IVec3> = HashMap::new();
 
     // First pass: check which conveyor-to-conveyor transfers can proceed
     // This avoids borrow conflicts
@@ -258,8 +245,8 @@ pub fn conveyor_transfer(
         .collect();
 
     // Collect conveyor adds for second pass (to avoid borrow conflicts)
-    // Tuple: (target_entity, item_id, join_progress, visual_entity, lateral_offset)
-    let mut conveyor_adds: Vec<(Entity, ItemId, f32, Option<Entity>, f32)> = Vec::new();
+    // Tuple: (target_entity, item_id, count, join_progress, visual_entity, lateral_offset)
+    let mut conveyor_adds: Vec<(Entity, ItemId, u32, f32, Option<Entity>, f32)> = Vec::new();
 
     // Apply transfers
     for action in actions {
@@ -294,118 +281,62 @@ pub fn conveyor_transfer(
                     let visual = item.visual_entity;
                     source_conv.items.remove(action.item_index);
                     // Queue add to target conveyor with visual and lateral offset
+                    // (stacks move as one unit)
                     conveyor_adds.push((
                         target_entity,
                         item.item_id,
+                        item.count,
                         progress,
                         visual,
                         lateral_offset,
                     ));
-                    // Mark target for last_input_source update
-                    targets_to_update.insert(target_entity);
+                    // Mark target for last_input_pos update
+                    targets_to_update.insert(target_entity, action.source_pos);
                     // Collect event for ConveyorTransfer
-                    conveyor_transfer_items.push((action.source_pos, target_pos, action.item_id));
+                    conveyor_transfer_items.push((
+                        action.source_pos,
+                        target_pos,
+                        action.item_id,
+                        item.count,
+                    ));
                 }
             }
-            TransferTarget::Furnace(furnace_pos) => {
-                let mut accepted = false;
-                for mut machine in machine_query.iter_mut() {
-                    if machine.spec.item_id() != items::furnace_block()
-                        || machine.position != furnace_pos
-                    {
-                        continue;
-                    }
-                    // Calculate input ports based on furnace facing direction
-                    // FURNACE spec: Back=ore(slot0), Left=fuel(slot1), Right=fuel(slot1)
-                    let back_port = machine.position + machine.facing.opposite().to_ivec3();
-                    let left_port = machine.position + machine.facing.left().to_ivec3();
-                    let right_port = machine.position + machine.facing.right().to_ivec3();
-
-                    // Determine which port the conveyor is at
-                    let at_back = action.source_pos == back_port;
-                    let at_left = action.source_pos == left_port;
-                    let at_right = action.source_pos == right_port;
-
-                    if !at_back && !at_left && !at_right {
-                        break; // Not at any input port, reject
-                    }
-
-                    // Accept items based on port type
-                    use crate::core::items;
-                    let input_count = machine.slots.inputs.first().map(|s| s.count).unwrap_or(0);
-                    let input_item_id = machine.slots.inputs.first().and_then(|s| s.item_id);
-                    let item_id = item.item_id;
-                    let can_accept = if items::is_fuel(item_id) {
-                        // Fuel only from left or right ports
-                        (at_left || at_right) && machine.slots.fuel < 64
-                    } else if items::is_smeltable(item_id) {
-                        // Ore/Dust only from back port
-                        at_back
-                            && (input_item_id.is_none() || input_item_id == Some(item_id))
-                            && input_count < 64
-                    } else {
-                        false
-                    };
-                    if can_accept {
-                        if items::is_fuel(item_id) {
-                            machine.slots.fuel += 1;
-                        } else if items::is_smeltable(item_id) {
-                            if let Some(input_slot) = machine.slots.inputs.first_mut() {
-                                input_slot.item_id = Some(item_id);
-                                input_slot.count += 1;
-                            }
+            TransferTarget::Machine(machine_entity) => {
+                // Acceptance rules (input faces, item kinds, slot room) live on Machine.
+                // Only an actual insert counts as a change, so a belt backed up
+                // at a sleeping machine doesn't wake it every tick.
+                let accepted = machine_query
+                    .get_mut(machine_entity)
+                    .map(|(_, mut machine)| {
+                        let accepted = machine.bypass_change_detection().insert_from(
+                            item.item_id,
+                            item.count,
+                            action.source_pos,
+                        );
+                        if accepted > 0 {
+                            machine.set_changed();
                         }
-                        accepted = true;
-                    }
-                    break;
-                }
-                if accepted {
-                    if let Some(visual) = item.visual_entity {
-                        commands.entity(visual).despawn();
-                    }
-                    source_conv.items.remove(action.item_index);
-                }
+                        accepted
+                    })
+                    .unwrap_or(0);
+                take_from_stack(&mut commands, &mut source_conv, action.item_index, accepted);
             }
-            TransferTarget::Crusher(crusher_pos) => {
-                let mut accepted = false;
-                for mut machine in machine_query.iter_mut() {
-                    if machine.spec.item_id() != items::crusher_block()
-                        || machine.position != crusher_pos
-                    {
-                        continue;
+            TransferTarget::Delivery(platform) => {
+                // Rejected stacks bounce (stay at the belt end) until items queue
+                // up behind them, then wait in the platform's reject buffer
+                let rejecting = filter_query
+                    .get_mut(platform)
+                    .ok()
+                    .filter(|filter| !filter.accepts(item.item_id));
+                if let Some(mut filter) = rejecting {
+                    if backed_up(&source_conv, action.item_index) {
+                        let taken = filter.reject(item.item_id, item.count);
+                        take_from_stack(&mut commands, &mut source_conv, action.item_index, taken);
                     }
-                    // Check if conveyor is at input port (back of crusher)
-                    let input_port = machine.position + machine.facing.opposite().to_ivec3();
-                    if action.source_pos != input_port {
-                        break; // Not at input port, reject
-                    }
-
-                    use crate::core::items;
-                    let input_count = machine.slots.inputs.first().map(|s| s.count).unwrap_or(0);
-                    let input_item_id = machine.slots.inputs.first().and_then(|s| s.item_id);
-                    let item_id = item.item_id;
-                    let can_accept_item = items::is_crushable(item_id)
-                        && (input_item_id.is_none() || input_item_id == Some(item_id))
-                        && input_count < 64;
-                    if can_accept_item {
-                        if let Some(input_slot) = machine.slots.inputs.first_mut() {
-                            input_slot.item_id = Some(item_id);
-                            input_slot.count += 1;
-                        }
-                        accepted = true;
-                    }
-                    break;
+                    continue;
                 }
-                if accepted {
-                    if let Some(visual) = item.visual_entity {
-                        commands.entity(visual).despawn();
-                    }
-                    source_conv.items.remove(action.item_index);
-                }
-            }
-            TransferTarget::Delivery => {
-                // Deliver the item to PlatformInventory
-                platform_inventory.add_item(item.item_id, 1);
+                // Deliver the whole stack to PlatformInventory (shared by all platforms)
+                platform_inventory.add_item(item.item_id, item.count);
                 let total = platform_inventory.get_count(item.item_id);
                 info!(category = "QUEST", action = "deliver", item = ?item.item_id, total = total, "Item delivered to storage");
                 if let Some(visual) = item.visual_entity {
@@ -413,35 +344,62 @@ pub fn conveyor_transfer(
                 }
                 source_conv.items.remove(action.item_index);
                 // Collect event for ItemDelivered
-                delivered_items.push((action.item_id, 1));
+                delivered_items.push((platform, action.item_id, item.count));
             }
         }
     }
 
     // Second pass: add items to target conveyors at their calculated join progress
-    for (target_entity, item_id, progress, visual, lateral_offset) in conveyor_adds {
+    for (target_entity, item_id, count, progress, visual, lateral_offset) in conveyor_adds {
         if let Ok((_, mut target_conv)) = conveyor_query.get_mut(target_entity) {
-            target_conv.add_item_with_visual(item_id, progress, visual, lateral_offset);
+            target_conv.add_stack_with_visual(item_id, count, progress, visual, lateral_offset);
         }
     }
 
-    // Update last_input_source for conveyors that received items (zipper merge)
-    for target_entity in targets_to_update {
+    // Drain each platform's reject buffer onto the first free belt leading away from it
+    for (platform_entity, platform) in platform_query.iter() {
+        let Ok(mut filter) = filter_query.get_mut(platform_entity) else {
+            continue;
+        };
+        let Some((item_id, count)) = filter.next_stack() else {
+            continue;
+        };
+        let mut outlets: Vec<(Entity, IVec3)> = conveyor_query
+            .iter()
+            .filter(|(_, c)| c.enabled && drains_platform(c, platform) && c.can_accept_item(0.0))
+            .map(|(e, c)| (e, c.position))
+            .collect();
+        outlets.sort_by_key(|(_, pos)| (pos.x, pos.y, pos.z));
+        if let Some(&(outlet, _)) = outlets.first() {
+            if let Ok((_, mut conveyor)) = conveyor_query.get_mut(outlet) {
+                conveyor.add_stack_with_visual(item_id, count, 0.0, None, 0.0);
+                filter.take_stack(count);
+            }
+        }
+    }
+
+    // Remember who fed each conveyor so the other side goes next (zipper merge)
+    for (target_entity, source_pos) in targets_to_update {
         if let Ok((_, mut target_conv)) = conveyor_query.get_mut(target_entity) {
-            target_conv.last_input_source += 1;
+            target_conv.last_input_pos = Some(source_pos);
         }
     }
 
     // Send collected events
-    for (from_pos, to_pos, item) in conveyor_transfer_items {
+    for (from_pos, to_pos, item, count) in conveyor_transfer_items {
         let _ = transfer_events.write(ConveyorTransfer {
             from_pos,
             to_pos,
             item,
+            count,
         });
     }
-    for (item, count) in delivered_items {
-        let _ = delivery_events.write(ItemDelivered { item, count });
+    for (platform, item, count) in delivered_items {
+        let _ = delivery_events.write(ItemDelivered {
+            item,
+            count,
+            platform,
+        });
     }
 
     // Persist splitter output indices
@@ -451,10 +409,19 @@ pub fn conveyor_transfer(
         }
     }
 
-    // Update progress for all items on all conveyors
-    let delta = time.delta_secs() / CONVEYOR_SPEED;
-    let lateral_decay = time.delta_secs() * 3.0; // Decay rate for lateral offset (BUG-5 fix)
+    // Update progress for all items on all conveyors (scaled by each belt's tier)
+    let base_delta = SIMULATION_TICK_SECS / CONVEYOR_SPEED;
+    let lateral_decay = SIMULATION_TICK_SECS * 3.0; // Decay rate for lateral offset (BUG-5 fix)
     for (_, mut conveyor) in conveyor_query.iter_mut() {
+        if !conveyor.enabled {
+            // Freeze in place (no interpolation toward the last moved position)
+            for item in conveyor.items.iter_mut() {
+                item.previous_progress = item.progress;
+                item.previous_lateral_offset = item.lateral_offset;
+            }
+            continue;
+        }
+        let delta = base_delta * conveyor.speed_multiplier;
         let item_count = conveyor.items.len();
         for i in 0..item_count {
             // Store previous values for interpolation (before updating)
@@ -474,41 +441,229 @@ pub fn conveyor_transfer(
             }
 
             if conveyor.items[i].progress < 1.0 {
-                // Check if blocked by item ahead (higher progress)
+                // Stop short of the item ahead (higher progress) so fast belts
+                // never close the gap below CONVEYOR_ITEM_SPACING in one step
                 let current_progress = conveyor.items[i].progress;
-                let blocked = conveyor.items.iter().any(|other| {
-                    other.progress > current_progress
-                        && other.progress - current_progress < CONVEYOR_ITEM_SPACING
-                });
-                if !blocked {
-                    conveyor.items[i].progress += delta;
-                    if conveyor.items[i].progress > 1.0 {
-                        conveyor.items[i].progress = 1.0;
-                    }
-                }
+                let limit = conveyor
+                    .items
+                    .iter()
+                    .filter(|other| other.progress > current_progress)
+                    .map(|other| other.progress - CONVEYOR_ITEM_SPACING)
+                    .fold(1.0, f32::min);
+                conveyor.items[i].progress =
+                    (current_progress + delta).min(limit).max(current_progress);
             }
         }
     }
 }
 
+/// Machines a belt can insert into (see `Machine::insert_from` for what each accepts)
+pub(crate) fn takes_belt_input(machine: &Machine) -> bool {
+    matches!(
+        machine.spec.process_type,
+        ProcessType::Recipe(MachineType::Furnace | MachineType::Crusher | MachineType::Custom(_))
+            | ProcessType::Recycle
+            | ProcessType::Hopper
+    )
+}
+
+/// Remove `amount` items from a belt stack, dropping the belt item once it is empty
+///
+/// A partially accepted stack stays on the belt with the remainder (split).
+fn take_from_stack(commands: &mut Commands, conveyor: &mut Conveyor, index: usize, amount: u32) {
+    if amount == 0 {
+        return;
+    }
+    let item = &mut conveyor.items[index];
+    if amount < item.count {
+        item.count -= amount;
+        return;
+    }
+    if let Some(visual) = item.visual_entity {
+        commands.entity(visual).despawn();
+    }
+    conveyor.items.remove(index);
+}
+
+/// Pick which pending source may feed a merge target this tick (zipper merge)
+///
+/// Sources are ordered by position and the one after `last` (cyclically) wins,
+/// so any other pending source goes before the last one is allowed again.
+/// A single pending source always proceeds.
+fn pick_zipper_source(sources: &[(Entity, IVec3)], last: Option<IVec3>) -> Option<Entity> {
+    let key = |p: IVec3| (p.x, p.y, p.z);
+    let mut sorted: Vec<(Entity, IVec3)> = sources.to_vec();
+    sorted.sort_by_key(|(_, pos)| key(*pos));
+    let next = last.and_then(|last| sorted.iter().find(|(_, pos)| key(*pos) > key(last)));
+    next.or(sorted.first()).map(|(e, _)| *e)
+}
+
+/// World position of an item on a conveyor (item center, resting on the belt)
+///
+/// Straight, TJunction and Splitter belts interpolate along the belt axis:
+/// progress 0.0 = back edge, 1.0 = front edge, with `lateral_offset` pushing the
+/// item sideways (positive = right of `direction`, BUG-5/BUG-9 convention).
+///
+/// Corners follow a quarter circle from the side arm's edge to the front edge,
+/// pivoting on the block corner between them. The side arm matches the L-shaped
+/// mesh: CornerLeft's arm is on the right of `direction`, CornerRight's on the left.
+/// Side-merged items start on the straight path at their lateral offset and blend
+/// onto the arc as the offset decays, so they don't pop sideways.
+pub fn conveyor_item_world_pos(
+    shape: ConveyorShape,
+    direction: Direction,
+    position: IVec3,
+    progress: f32,
+    lateral_offset: f32,
+) -> Vec3 {
+    let half_block = BLOCK_SIZE / 2.0;
+    // Items sit on top of the belt (belt height + item size/2)
+    let center = Vec3::new(
+        position.x as f32 * BLOCK_SIZE + half_block,
+        position.y as f32 * BLOCK_SIZE + CONVEYOR_BELT_HEIGHT + CONVEYOR_ITEM_SIZE / 2.0,
+        position.z as f32 * BLOCK_SIZE + half_block,
+    );
+    let forward = direction.to_ivec3().as_vec3();
+    let right = direction.right().to_ivec3().as_vec3();
+
+    let straight =
+        center + forward * (progress - 0.5) * BLOCK_SIZE + right * lateral_offset * BLOCK_SIZE;
+
+    let side = match shape {
+        ConveyorShape::CornerLeft => right,
+        ConveyorShape::CornerRight => -right,
+        ConveyorShape::Straight | ConveyorShape::TJunction | ConveyorShape::Splitter => {
+            return straight;
+        }
+    };
+
+    // Quarter circle of radius half_block around the front/side corner
+    let pivot = center + (forward + side) * half_block;
+    let angle = progress.clamp(0.0, 1.0) * std::f32::consts::FRAC_PI_2;
+    let arc = pivot - forward * half_block * angle.cos() - side * half_block * angle.sin();
+
+    // Full lateral offset (0.5) = still on the side-merge path
+    let blend = (lateral_offset.abs() * 2.0).min(1.0);
+    arc.lerp(straight, blend)
+}
+
+/// Shared palette color material for an item type
+pub(crate) fn item_color_material(
+    assets: &mut ConveyorVisualAssets,
+    materials: &mut Assets<StandardMaterial>,
+    palette: &BlockPalette,
+    item_id: ItemId,
+) -> Handle<StandardMaterial> {
+    assets
+        .colors
+        .entry(item_id)
+        .or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: palette.item_color(item_id),
+                ..default()
+            })
+        })
+        .clone()
+}
+
+/// Shared icon material for an item type (None if the item has no sprite)
+fn item_icon_material(
+    assets: &mut ConveyorVisualAssets,
+    materials: &mut Assets<StandardMaterial>,
+    sprites: &ItemSprites,
+    item_id: ItemId,
+) -> Option<Handle<StandardMaterial>> {
+    if let Some(material) = assets.icons.get(&item_id) {
+        return Some(material.clone());
+    }
+    let texture = sprites.get_id(item_id)?;
+    let material = materials.add(StandardMaterial {
+        base_color_texture: Some(texture),
+        alpha_mode: AlphaMode::Mask(0.5),
+        unlit: true,
+        cull_mode: None,
+        ..default()
+    });
+    assets.icons.insert(item_id, material.clone());
+    Some(material)
+}
+
+/// Rotation that turns an upright icon quad towards the camera
+///
+/// Only yaw is applied so icons stay upright on the belt.
+pub fn billboard_rotation(item_pos: Vec3, camera_pos: Vec3) -> Quat {
+    let to_camera = camera_pos - item_pos;
+    if to_camera.x == 0.0 && to_camera.z == 0.0 {
+        return Quat::IDENTITY;
+    }
+    Quat::from_rotation_y(to_camera.x.atan2(to_camera.z))
+}
+
 /// Update conveyor item visuals - spawn/despawn/move items on conveyors (multiple items)
-/// Uses 3D GLB models when available, falls back to colored cubes
+/// Uses 3D GLB models when available, then item icons on camera-facing quads,
+/// falls back to colored cubes (models and icons can be turned off in settings)
+/// Meshes and materials are shared per item type via `ConveyorVisualAssets`
 /// Uses interpolation for smooth rendering between FixedUpdate ticks
+/// Conveyors beyond the culling radius from the player drop their item visuals
+/// With `simple_conveyor_items` each stack is a single flat quad
+/// With `conveyor_belt_animation` items bob as they ride over the rollers
+#[allow(clippy::too_many_arguments)]
 pub fn update_conveyor_item_visuals(
     mut commands: Commands,
+    settings: Res<GameSettings>,
+    palette: Res<BlockPalette>,
+    mut visual_mode: Local<(bool, bool)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut assets: ResMut<ConveyorVisualAssets>,
     models: Res<MachineModels>,
+    sprites: Res<ItemSprites>,
     fixed_time: Res<Time<Fixed>>,
+    mut culling: ResMut<ConveyorItemCulling>,
+    player_query: Query<&Transform, (With<Player>, Without<ConveyorItemVisual>)>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     mut conveyor_query: Query<&mut Conveyor>,
-    mut visual_query: Query<&mut Transform, With<ConveyorItemVisual>>,
+    mut visual_query: Query<
+        (
+            &mut Transform,
+            &ConveyorItemStackVisual,
+            Has<ConveyorItemBillboard>,
+        ),
+        With<ConveyorItemVisual>,
+    >,
 ) {
-    // Fallback mesh for items without GLB models
-    let fallback_mesh = meshes.add(Cuboid::new(
-        BLOCK_SIZE * CONVEYOR_ITEM_SIZE,
-        BLOCK_SIZE * CONVEYOR_ITEM_SIZE,
-        BLOCK_SIZE * CONVEYOR_ITEM_SIZE,
-    ));
+    // Visual style or palette switched: rebuild every item visual
+    let simple = settings.simple_conveyor_items;
+    let detailed = settings.conveyor_item_icons;
+    let bob = settings.conveyor_belt_animation;
+    if *visual_mode != (simple, detailed) || palette.is_changed() {
+        *visual_mode = (simple, detailed);
+        if palette.is_changed() {
+            assets.colors.clear();
+        }
+        for mut conveyor in conveyor_query.iter_mut() {
+            for item in conveyor.items.iter_mut() {
+                if let Some(entity) = item.visual_entity.take() {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+    }
+
+    // Shared meshes, created on first use
+    let item_size = BLOCK_SIZE * CONVEYOR_ITEM_SIZE;
+    let cube_mesh = assets
+        .cube
+        .get_or_insert_with(|| meshes.add(Cuboid::new(item_size, item_size, item_size)))
+        .clone();
+    let flat_mesh = assets
+        .flat
+        .get_or_insert_with(|| meshes.add(Plane3d::default().mesh().size(item_size, item_size)))
+        .clone();
+    let billboard_mesh = assets
+        .billboard
+        .get_or_insert_with(|| meshes.add(Rectangle::new(item_size, item_size)))
+        .clone();
 
     // Item model scale (GLB models are 8x8x8 voxels = 0.5 blocks, scale down for conveyor)
     const ITEM_MODEL_SCALE: f32 = 0.5;
@@ -516,26 +671,28 @@ pub fn update_conveyor_item_visuals(
     // Interpolation factor (0.0 = at previous tick, 1.0 = at current tick)
     let alpha = fixed_time.overstep_fraction();
 
+    // No player (e.g. headless) = no culling
+    let player_pos = player_query.single().ok().map(|t| t.translation);
+    let camera_pos = camera_query.single().ok().map(|t| t.translation());
+    let radius_sq = culling.radius * culling.radius;
+    let mut active = 0;
+    let mut culled = 0;
+
     for mut conveyor in conveyor_query.iter_mut() {
-        // Position items on top of the belt (belt height + item size/2)
-        let item_y = conveyor.position.y as f32 * BLOCK_SIZE
-            + CONVEYOR_BELT_HEIGHT
-            + CONVEYOR_ITEM_SIZE / 2.0;
-        let base_pos = Vec3::new(
-            conveyor.position.x as f32 * BLOCK_SIZE + 0.5,
-            item_y,
-            conveyor.position.z as f32 * BLOCK_SIZE + 0.5,
-        );
-        let direction_vec = conveyor.direction.to_ivec3().as_vec3();
-        // Perpendicular vector for lateral offset (BUG-5 fix, BUG-9 fix)
-        // Positive lateral_offset = right side of conveyor direction
-        let lateral_vec = match conveyor.direction {
-            Direction::East => Vec3::new(0.0, 0.0, 1.0), // Right is +Z (South)
-            Direction::West => Vec3::new(0.0, 0.0, -1.0), // Right is -Z (North)
-            Direction::South => Vec3::new(-1.0, 0.0, 0.0), // Right is -X (West)
-            Direction::North => Vec3::new(1.0, 0.0, 0.0), // Right is +X (East)
-        };
+        // Cull: despawn visuals far from the player, items keep moving logically
+        let center = crate::grid_to_world_center(conveyor.position);
+        if player_pos.is_some_and(|p| p.distance_squared(center) > radius_sq) {
+            for item in conveyor.items.iter_mut() {
+                if let Some(entity) = item.visual_entity.take() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            culled += conveyor.items.len();
+            continue;
+        }
+        active += conveyor.items.len();
 
+        let (shape, direction, position) = (conveyor.shape, conveyor.direction, conveyor.position);
         for item in conveyor.items.iter_mut() {
             // Interpolate between previous and current values for smooth rendering
             let interpolated_progress =
@@ -543,21 +700,61 @@ pub fn update_conveyor_item_visuals(
             let interpolated_lateral = item.previous_lateral_offset
                 + (item.lateral_offset - item.previous_lateral_offset) * alpha;
 
-            // Calculate position: progress 0.0 = entry (-0.5), 1.0 = exit (+0.5)
-            let forward_offset = (interpolated_progress - 0.5) * BLOCK_SIZE;
-            let lateral_offset_world = interpolated_lateral * BLOCK_SIZE;
-            let item_pos =
-                base_pos + direction_vec * forward_offset + lateral_vec * lateral_offset_world;
+            let mut item_pos = conveyor_item_world_pos(
+                shape,
+                direction,
+                position,
+                interpolated_progress,
+                interpolated_lateral,
+            );
+            if bob {
+                item_pos.y += conveyor_item_bob(interpolated_progress) * BLOCK_SIZE;
+            }
+            let facing = camera_pos
+                .map(|camera| billboard_rotation(item_pos, camera))
+                .unwrap_or_default();
+
+            // Stack size changed (merge or partial insert): rebuild the visual
+            if let Some(entity) = item.visual_entity {
+                if visual_query
+                    .get(entity)
+                    .is_ok_and(|(_, stack, _)| stack.0 != item.count)
+                {
+                    commands.entity(entity).despawn();
+                    item.visual_entity = None;
+                }
+            }
 
             match item.visual_entity {
                 None => {
-                    // Try to spawn with GLB model, fall back to colored cube
+                    // Model, then icon, then colored cube
+                    // Extra stacked items are children raised by CONVEYOR_STACK_OFFSET each
                     let item_id = item.get_item_id();
-                    let entity = if let Some(scene_handle) = models.get_item_model(item_id) {
+                    let model = models.get_item_model(item_id).filter(|_| detailed);
+                    let icon = if detailed && model.is_none() {
+                        item_icon_material(&mut assets, &mut materials, &sprites, item_id)
+                    } else {
+                        None
+                    };
+                    let entity = if simple {
+                        // Simple: one quad per stack, no model or stacked children
+                        let material =
+                            item_color_material(&mut assets, &mut materials, &palette, item_id);
+                        commands
+                            .spawn((
+                                Mesh3d(flat_mesh.clone()),
+                                MeshMaterial3d(material),
+                                Transform::from_translation(item_pos),
+                                ConveyorItemVisual,
+                                ConveyorItemStackVisual(item.count),
+                            ))
+                            .id()
+                    } else if let Some(scene_handle) = model {
                         // Spawn GLB model
+                        let step = CONVEYOR_STACK_OFFSET * BLOCK_SIZE / ITEM_MODEL_SCALE;
                         commands
                             .spawn((
-                                SceneRoot(scene_handle),
+                                SceneRoot(scene_handle.clone()),
                                 Transform::from_translation(item_pos)
                                     .with_scale(Vec3::splat(ITEM_MODEL_SCALE)),
                                 GlobalTransform::default(),
@@ -565,32 +762,800 @@ pub fn update_conveyor_item_visuals(
                                 InheritedVisibility::default(),
                                 ViewVisibility::default(),
                                 ConveyorItemVisual,
+                                ConveyorItemStackVisual(item.count),
                             ))
+                            .with_children(|stack| {
+                                for i in 1..item.count {
+                                    stack.spawn((
+                                        SceneRoot(scene_handle.clone()),
+                                        Transform::from_xyz(0.0, step * i as f32, 0.0),
+                                    ));
+                                }
+                            })
                             .id()
                     } else {
-                        // Fallback: spawn colored cube
-                        let material = materials.add(StandardMaterial {
-                            base_color: item_id.color(),
-                            ..default()
-                        });
-                        commands
-                            .spawn((
-                                Mesh3d(fallback_mesh.clone()),
-                                MeshMaterial3d(material),
-                                Transform::from_translation(item_pos),
-                                ConveyorItemVisual,
-                            ))
+                        // Icon quad facing the camera, or colored cube
+                        let billboard = icon.is_some();
+                        let (mesh, material) = match icon {
+                            Some(material) => (billboard_mesh.clone(), material),
+                            None => (
+                                cube_mesh.clone(),
+                                item_color_material(&mut assets, &mut materials, &palette, item_id),
+                            ),
+                        };
+                        let rotation = if billboard { facing } else { Quat::IDENTITY };
+                        let step = CONVEYOR_STACK_OFFSET * BLOCK_SIZE;
+                        let mut visual = commands.spawn((
+                            Mesh3d(mesh.clone()),
+                            MeshMaterial3d(material.clone()),
+                            Transform::from_translation(item_pos).with_rotation(rotation),
+                            ConveyorItemVisual,
+                            ConveyorItemStackVisual(item.count),
+                        ));
+                        if billboard {
+                            visual.insert(ConveyorItemBillboard);
+                        }
+                        visual
+                            .with_children(|stack| {
+                                for i in 1..item.count {
+                                    stack.spawn((
+                                        Mesh3d(mesh.clone()),
+                                        MeshMaterial3d(material.clone()),
+                                        Transform::from_xyz(0.0, step * i as f32, 0.0),
+                                    ));
+                                }
+                            })
                             .id()
                     };
                     item.visual_entity = Some(entity);
                 }
                 Some(entity) => {
-                    // Update position
-                    if let Ok(mut transform) = visual_query.get_mut(entity) {
+                    // Update position (icons also turn with the camera)
+                    if let Ok((mut transform, _, billboard)) = visual_query.get_mut(entity) {
                         transform.translation = item_pos;
+                        if billboard {
+                            transform.rotation = facing;
+                        }
                     }
                 }
             }
         }
     }
+
+    culling.active = active;
+    culling.culled = culled;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    const ALL_DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn assert_near(actual: Vec3, expected: Vec3) {
+        assert!(
+            actual.distance(expected) < 1e-4,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    /// Center of the belt surface where items ride (block center at item height)
+    fn belt_center(position: IVec3) -> Vec3 {
+        conveyor_item_world_pos(
+            ConveyorShape::Straight,
+            Direction::North,
+            position,
+            0.5,
+            0.0,
+        )
+    }
+
+    #[test]
+    fn test_straight_item_path_is_linear() {
+        let position = IVec3::new(2, 8, -3);
+        let center = belt_center(position);
+        for direction in ALL_DIRECTIONS {
+            let forward = direction.to_ivec3().as_vec3();
+            let right = direction.right().to_ivec3().as_vec3();
+            for shape in [
+                ConveyorShape::Straight,
+                ConveyorShape::TJunction,
+                ConveyorShape::Splitter,
+            ] {
+                let pos = |p, l| conveyor_item_world_pos(shape, direction, position, p, l);
+                assert_near(pos(0.0, 0.0), center - forward * 0.5);
+                assert_near(pos(0.5, 0.0), center);
+                assert_near(pos(1.0, 0.0), center + forward * 0.5);
+                assert_near(pos(0.5, 0.5), center + right * 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_corner_item_path_follows_quarter_circle() {
+        let position = IVec3::new(-4, 8, 5);
+        let center = belt_center(position);
+        let bulge = 0.5 * (1.0 - std::f32::consts::FRAC_1_SQRT_2);
+        for direction in ALL_DIRECTIONS {
+            let forward = direction.to_ivec3().as_vec3();
+            let right = direction.right().to_ivec3().as_vec3();
+            for (shape, side) in [
+                (ConveyorShape::CornerLeft, right),
+                (ConveyorShape::CornerRight, -right),
+            ] {
+                let pos = |p| conveyor_item_world_pos(shape, direction, position, p, 0.0);
+                // Enters at the side arm's edge, exits at the front edge
+                assert_near(pos(0.0), center + side * 0.5);
+                assert_near(pos(1.0), center + forward * 0.5);
+                // Midpoint hugs the inside of the turn, 45 degrees around the pivot
+                assert_near(pos(0.5), center + (forward + side) * bulge);
+                // Stays on the circle around the corner pivot
+                let pivot = center + (forward + side) * 0.5;
+                for p in [0.25, 0.75] {
+                    assert!((pos(p).distance(pivot) - 0.5).abs() < 1e-4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_corner_side_merge_blends_from_lateral_offset() {
+        let position = IVec3::new(0, 8, 0);
+        let center = belt_center(position);
+        let direction = Direction::East;
+        let right = direction.right().to_ivec3().as_vec3();
+
+        // Just merged from the side arm: same spot as on a straight belt
+        let merged =
+            conveyor_item_world_pos(ConveyorShape::CornerLeft, direction, position, 0.5, 0.5);
+        assert_near(merged, center + right * 0.5);
+
+        // Offset decayed: on the arc
+        let settled =
+            conveyor_item_world_pos(ConveyorShape::CornerLeft, direction, position, 0.5, 0.0);
+        let straight =
+            conveyor_item_world_pos(ConveyorShape::Straight, direction, position, 0.5, 0.0);
+        assert!(settled.distance(straight) > 0.1);
+
+        // Partially decayed: between the two
+        let halfway =
+            conveyor_item_world_pos(ConveyorShape::CornerLeft, direction, position, 0.5, 0.25);
+        assert_near(halfway, settled.lerp(center + right * 0.25, 0.5));
+    }
+
+    fn two_entities() -> (Entity, Entity) {
+        let mut world = World::new();
+        (world.spawn_empty().id(), world.spawn_empty().id())
+    }
+
+    #[test]
+    fn test_zipper_single_source_always_proceeds() {
+        let (a, _) = two_entities();
+        let pos = IVec3::new(0, 8, 0);
+        assert_eq!(pick_zipper_source(&[(a, pos)], None), Some(a));
+        assert_eq!(pick_zipper_source(&[(a, pos)], Some(pos)), Some(a));
+        assert_eq!(pick_zipper_source(&[], Some(pos)), None);
+    }
+
+    #[test]
+    fn test_zipper_prefers_other_source() {
+        let (a, b) = two_entities();
+        let a_pos = IVec3::new(-1, 8, 0);
+        let b_pos = IVec3::new(0, 8, 1);
+        let sources = [(b, b_pos), (a, a_pos)];
+        assert_eq!(pick_zipper_source(&sources, Some(a_pos)), Some(b));
+        assert_eq!(pick_zipper_source(&sources, Some(b_pos)), Some(a));
+    }
+
+    /// Fast line has an item ready every tick, side line every other tick.
+    /// The target accepts one item per tick.
+    #[test]
+    fn test_zipper_slow_side_not_starved_by_fast_line() {
+        let (fast, slow) = two_entities();
+        let fast_pos = IVec3::new(-1, 8, 0);
+        let slow_pos = IVec3::new(0, 8, 1);
+
+        let mut last = None;
+        let mut slow_waiting_since: Option<u32> = None;
+        let mut slow_delivered = 0;
+        let mut fast_delivered = 0;
+        let mut max_wait = 0;
+
+        for tick in 0..120u32 {
+            if tick % 2 == 0 && slow_waiting_since.is_none() {
+                slow_waiting_since = Some(tick);
+            }
+
+            let mut pending = vec![(fast, fast_pos)];
+            if slow_waiting_since.is_some() {
+                pending.push((slow, slow_pos));
+            }
+
+            let winner = pick_zipper_source(&pending, last).expect("a source is pending");
+            if winner == slow {
+                let since = slow_waiting_since.take().expect("slow was pending");
+                max_wait = max_wait.max(tick - since);
+                slow_delivered += 1;
+                last = Some(slow_pos);
+            } else {
+                fast_delivered += 1;
+                last = Some(fast_pos);
+            }
+        }
+
+        // Slow item never waits more than one tick behind the fast line
+        assert!(max_wait <= 1, "slow side waited {} ticks", max_wait);
+        assert_eq!(slow_delivered, 60);
+        assert_eq!(fast_delivered, 60);
+    }
+
+    fn count_visuals(app: &mut App) -> usize {
+        app.world_mut()
+            .query_filtered::<Entity, With<ConveyorItemVisual>>()
+            .iter(app.world())
+            .count()
+    }
+
+    fn count_items(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&Conveyor>()
+            .iter(app.world())
+            .map(|c| c.items.len())
+            .sum()
+    }
+
+    fn item_visual_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<MachineModels>()
+            .init_resource::<ItemSprites>()
+            .init_resource::<ConveyorVisualAssets>()
+            .init_resource::<Time<Fixed>>()
+            .init_resource::<ConveyorItemCulling>()
+            .init_resource::<GameSettings>()
+            .init_resource::<BlockPalette>()
+            .add_systems(Update, update_conveyor_item_visuals);
+        app
+    }
+
+    #[test]
+    fn test_item_visuals_culled_when_far_and_restored_when_near() {
+        use crate::components::ConveyorItem;
+
+        let mut app = item_visual_app();
+
+        // A line of 10 conveyors near the origin, 2 items each
+        for x in 0..10 {
+            app.world_mut().spawn(Conveyor {
+                position: IVec3::new(x, 8, 0),
+                direction: Direction::East,
+                output_direction: Direction::East,
+                items: vec![
+                    ConveyorItem::new(items::iron_ore(), 0.1),
+                    ConveyorItem::new(items::coal(), 0.6),
+                ],
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier: 1.0,
+            });
+        }
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_xyz(5.0, 9.0, 2.0)))
+            .id();
+
+        app.update();
+        assert_eq!(count_visuals(&mut app), 20);
+        assert_eq!(app.world().resource::<ConveyorItemCulling>().active, 20);
+
+        // Walk far away: visuals despawned, items untouched
+        for _ in 0..3 {
+            app.world_mut()
+                .get_mut::<Transform>(player)
+                .unwrap()
+                .translation = Vec3::new(500.0, 9.0, 0.0);
+            app.update();
+            assert_eq!(count_visuals(&mut app), 0);
+            assert_eq!(count_items(&mut app), 20);
+        }
+        let culling = app.world().resource::<ConveyorItemCulling>();
+        assert_eq!((culling.active, culling.culled), (0, 20));
+
+        // Walk back: one visual per item again (no duplicates, no leaks)
+        for _ in 0..3 {
+            app.world_mut()
+                .get_mut::<Transform>(player)
+                .unwrap()
+                .translation = Vec3::new(5.0, 9.0, 2.0);
+            app.update();
+            assert_eq!(count_visuals(&mut app), 20);
+            assert_eq!(count_items(&mut app), 20);
+        }
+        let culling = app.world().resource::<ConveyorItemCulling>();
+        assert_eq!((culling.active, culling.culled), (20, 0));
+    }
+
+    #[test]
+    fn test_delivering_stack_adds_full_count() {
+        use crate::components::ConveyorItem;
+        use crate::events::{EventDepth, EventSystemConfig};
+        use crate::player::{LocalPlatform, PlatformInventory};
+
+        let mut app = App::new();
+        app.init_resource::<EventDepth>()
+            .init_resource::<EventSystemConfig>()
+            .add_message::<ConveyorTransfer>()
+            .add_message::<ItemDelivered>()
+            .add_systems(Update, conveyor_transfer);
+
+        // Platform at the origin covers x in 0..8 at y = 0
+        let platform = app
+            .world_mut()
+            .spawn((DeliveryPlatform::new(IVec3::ZERO), PlatformInventory::new()))
+            .id();
+        app.insert_resource(LocalPlatform(platform));
+
+        let mut stack = ConveyorItem::new(items::iron_ingot(), 1.0);
+        stack.count = 3;
+        app.world_mut().spawn(Conveyor {
+            position: IVec3::new(5, 0, 0),
+            direction: Direction::West,
+            output_direction: Direction::West,
+            items: vec![stack],
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        });
+
+        app.update();
+
+        let inventory = app.world().get::<PlatformInventory>(platform).unwrap();
+        assert_eq!(inventory.get_count_by_id(items::iron_ingot()), 3);
+        let events = app.world().resource::<Messages<ItemDelivered>>();
+        let mut reader = events.get_cursor();
+        let delivered: Vec<u32> = reader.read(events).map(|e| e.count).collect();
+        assert_eq!(delivered, vec![3]);
+    }
+
+    fn transfer_app() -> App {
+        use crate::events::{EventDepth, EventSystemConfig};
+
+        let mut app = App::new();
+        app.init_resource::<EventDepth>()
+            .init_resource::<EventSystemConfig>()
+            .add_message::<ConveyorTransfer>()
+            .add_message::<ItemDelivered>()
+            .add_systems(Update, conveyor_transfer);
+        app
+    }
+
+    fn spawn_belt(app: &mut App, position: IVec3, item: Option<ItemId>) -> Entity {
+        use crate::components::ConveyorItem;
+
+        app.world_mut()
+            .spawn(Conveyor {
+                position,
+                direction: Direction::East,
+                output_direction: Direction::East,
+                items: item
+                    .map(|id| ConveyorItem::new(id, 1.0))
+                    .into_iter()
+                    .collect(),
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier: 1.0,
+            })
+            .id()
+    }
+
+    fn furnace_input(app: &App, furnace: Entity) -> u32 {
+        app.world().get::<Machine>(furnace).unwrap().slots.inputs[0].count
+    }
+
+    #[test]
+    fn test_recycler_rejects_items_without_recipe() {
+        use crate::game_spec::RECYCLER;
+
+        let mut app = transfer_app();
+        // Recycler facing east, so the belt at x = 0 feeds its back port
+        let recycler = app
+            .world_mut()
+            .spawn(Machine::new(
+                &RECYCLER,
+                IVec3::new(1, 8, 0),
+                Direction::East,
+            ))
+            .id();
+        let belt = spawn_belt(&mut app, IVec3::new(0, 8, 0), Some(items::iron_ore()));
+
+        // Raw ore is not produced by any recipe: it stays on the belt
+        app.update();
+        assert_eq!(app.world().get::<Conveyor>(belt).unwrap().items.len(), 1);
+        assert_eq!(furnace_input(&app, recycler), 0);
+
+        // A crafted machine is accepted
+        app.world_mut().get_mut::<Conveyor>(belt).unwrap().items[0].item_id =
+            items::furnace_block();
+        app.update();
+        assert!(app.world().get::<Conveyor>(belt).unwrap().items.is_empty());
+        assert_eq!(furnace_input(&app, recycler), 1);
+    }
+
+    #[test]
+    fn test_elevated_belt_carries_items_over_furnace() {
+        use crate::game_spec::FURNACE;
+
+        let mut app = transfer_app();
+        // Furnace at y = 8 whose back port faces the belt line's start
+        let furnace = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::new(1, 8, 0), Direction::East))
+            .id();
+        // Belt line one block up, passing over the furnace
+        let start = spawn_belt(&mut app, IVec3::new(0, 9, 0), Some(items::iron_ore()));
+        let over = spawn_belt(&mut app, IVec3::new(1, 9, 0), None);
+        let end = spawn_belt(&mut app, IVec3::new(2, 9, 0), None);
+
+        app.update();
+        assert!(app.world().get::<Conveyor>(start).unwrap().items.is_empty());
+        assert_eq!(app.world().get::<Conveyor>(over).unwrap().items.len(), 1);
+        assert_eq!(furnace_input(&app, furnace), 0);
+
+        // Push the item to the end of the belt above the furnace
+        app.world_mut().get_mut::<Conveyor>(over).unwrap().items[0].progress = 1.0;
+        app.update();
+        assert!(app.world().get::<Conveyor>(over).unwrap().items.is_empty());
+        assert_eq!(app.world().get::<Conveyor>(end).unwrap().items.len(), 1);
+        assert_eq!(furnace_input(&app, furnace), 0);
+    }
+
+    #[test]
+    fn test_belt_above_machine_without_next_belt_keeps_item() {
+        use crate::game_spec::FURNACE;
+
+        let mut app = transfer_app();
+        let furnace = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::new(1, 8, 0), Direction::East))
+            .id();
+        // Belt end sits directly above the furnace's back port, facing over it
+        let belt = spawn_belt(&mut app, IVec3::new(0, 9, 0), Some(items::iron_ore()));
+
+        app.update();
+        assert_eq!(app.world().get::<Conveyor>(belt).unwrap().items.len(), 1);
+        assert_eq!(furnace_input(&app, furnace), 0);
+    }
+
+    #[test]
+    fn test_same_level_belt_feeds_furnace() {
+        use crate::game_spec::FURNACE;
+
+        let mut app = transfer_app();
+        let furnace = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::new(1, 8, 0), Direction::East))
+            .id();
+        let belt = spawn_belt(&mut app, IVec3::new(0, 8, 0), Some(items::iron_ore()));
+
+        app.update();
+        assert!(app.world().get::<Conveyor>(belt).unwrap().items.is_empty());
+        assert_eq!(furnace_input(&app, furnace), 1);
+    }
+
+    fn spawn_tier_belt(app: &mut App, x: i32, speed_multiplier: f32, progress: &[f32]) -> Entity {
+        use crate::components::ConveyorItem;
+
+        app.world_mut()
+            .spawn(Conveyor {
+                position: IVec3::new(x, 8, 0),
+                direction: Direction::East,
+                output_direction: Direction::East,
+                items: progress
+                    .iter()
+                    .map(|&p| ConveyorItem::new(items::iron_ore(), p))
+                    .collect(),
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier,
+            })
+            .id()
+    }
+
+    #[test]
+    fn test_belt_speed_scales_with_tier() {
+        let mut app = transfer_app();
+        let base = spawn_tier_belt(&mut app, 0, 1.0, &[0.0]);
+        let mk3 = spawn_tier_belt(&mut app, 10, 4.0, &[0.0]);
+
+        app.update();
+        let base_progress = app.world().get::<Conveyor>(base).unwrap().items[0].progress;
+        let mk3_progress = app.world().get::<Conveyor>(mk3).unwrap().items[0].progress;
+        assert!(base_progress > 0.0);
+        assert!((mk3_progress - base_progress * 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_mixed_speed_chain_keeps_spacing() {
+        let mut app = transfer_app();
+        // Mk3 feeds a base belt which feeds an Mk2 belt with nowhere to go
+        let belts = [
+            spawn_tier_belt(&mut app, 0, 4.0, &[0.0, 0.4, 0.8]),
+            spawn_tier_belt(&mut app, 1, 1.0, &[]),
+            spawn_tier_belt(&mut app, 2, 2.0, &[]),
+        ];
+
+        for _ in 0..400 {
+            app.update();
+            let mut total = 0;
+            for belt in belts {
+                let conveyor = app.world().get::<Conveyor>(belt).unwrap();
+                total += conveyor.items.len();
+                for pair in conveyor.items.windows(2) {
+                    let gap = pair[1].progress - pair[0].progress;
+                    assert!(
+                        gap >= CONVEYOR_ITEM_SPACING - 1e-4,
+                        "items clipped on {:?}: gap {}",
+                        conveyor.position,
+                        gap
+                    );
+                }
+            }
+            assert_eq!(total, 3, "no items lost or duplicated");
+        }
+
+        // Everything backs up behind the end of the line
+        let counts: Vec<usize> = belts
+            .iter()
+            .map(|&belt| app.world().get::<Conveyor>(belt).unwrap().items.len())
+            .collect();
+        assert_eq!(counts, vec![0, 0, 3]);
+    }
+
+    /// Loop of belts whose first belt points at a full furnace's back face
+    ///
+    /// Returns (furnace, belt entities in loop order starting at the furnace-facing belt).
+    fn overflow_loop_app(pass_through: bool) -> (App, Entity, Vec<Entity>) {
+        use crate::components::ConveyorItem;
+        use crate::game_spec::FURNACE;
+        use crate::systems::update_conveyor_shapes;
+
+        let mut app = transfer_app();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<MachineModels>()
+            .add_systems(Update, update_conveyor_shapes.before(conveyor_transfer));
+
+        let mut furnace = Machine::new(&FURNACE, IVec3::new(1, 8, 0), Direction::East);
+        furnace.slots.inputs[0].item_id = Some(items::iron_ore());
+        furnace.slots.inputs[0].count = 64;
+        if pass_through {
+            furnace.toggle_pass_through(Direction::West);
+        }
+        let furnace = app.world_mut().spawn(furnace).id();
+
+        // (x, z, direction): east into the furnace, then a loop round to the south
+        let layout = [
+            (0, 0, Direction::East),
+            (0, 1, Direction::South),
+            (0, 2, Direction::South),
+            (0, 3, Direction::West),
+            (-1, 3, Direction::West),
+            (-2, 3, Direction::North),
+            (-2, 2, Direction::North),
+            (-2, 1, Direction::North),
+            (-2, 0, Direction::East),
+            (-1, 0, Direction::East),
+        ];
+        let belts = layout
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, z, direction))| {
+                app.world_mut()
+                    .spawn((
+                        Conveyor {
+                            position: IVec3::new(x, 8, z),
+                            direction,
+                            output_direction: direction,
+                            items: if i == 9 {
+                                vec![ConveyorItem::new(items::iron_ore(), 0.5)]
+                            } else {
+                                Vec::new()
+                            },
+                            last_output_index: 0,
+                            last_input_pos: None,
+                            enabled: true,
+                            shape: ConveyorShape::Straight,
+                            speed_multiplier: 1.0,
+                        },
+                        Transform::default(),
+                    ))
+                    .id()
+            })
+            .collect();
+        (app, furnace, belts)
+    }
+
+    #[test]
+    fn test_full_furnace_without_marking_keeps_belt_pointed_at_it() {
+        let (mut app, furnace, belts) = overflow_loop_app(false);
+        app.update();
+
+        // The belt still targets the furnace (splitter between furnace and loop)
+        let first = app.world().get::<Conveyor>(belts[0]).unwrap();
+        assert_eq!(first.shape, ConveyorShape::Splitter);
+        assert_eq!(furnace_input(&app, furnace), 64);
+    }
+
+    #[test]
+    fn test_pass_through_face_sends_items_around_loop() {
+        let (mut app, furnace, belts) = overflow_loop_app(true);
+
+        let mut visited = vec![false; belts.len()];
+        for _ in 0..1000 {
+            app.update();
+            for (i, &belt) in belts.iter().enumerate() {
+                if !app.world().get::<Conveyor>(belt).unwrap().items.is_empty() {
+                    visited[i] = true;
+                }
+            }
+        }
+
+        // The marked face is not a target: the belt turns onto the loop
+        let first = app.world().get::<Conveyor>(belts[0]).unwrap();
+        assert_eq!(first.shape, ConveyorShape::CornerRight);
+        assert_eq!(first.output_direction, Direction::South);
+        // The item keeps circulating instead of queuing at the full furnace
+        assert!(visited.iter().all(|&v| v), "visited: {:?}", visited);
+        let total: usize = belts
+            .iter()
+            .map(|&belt| app.world().get::<Conveyor>(belt).unwrap().items.len())
+            .sum();
+        assert_eq!(total, 1);
+        assert_eq!(furnace_input(&app, furnace), 64);
+    }
+
+    #[test]
+    fn test_routed_faces_fill_fuel_and_input_independently() {
+        use crate::components::{ConveyorItem, SlotRoute};
+        use crate::game_spec::FURNACE;
+
+        let mut app = transfer_app();
+        // Furnace facing east: back is west, sides are north and south
+        let mut furnace = Machine::new(&FURNACE, IVec3::new(0, 8, 0), Direction::East);
+        furnace.set_slot_route(Direction::North, SlotRoute::Fuel);
+        furnace.set_slot_route(Direction::South, SlotRoute::Input);
+        furnace.set_slot_route(Direction::West, SlotRoute::Input);
+        let furnace = app.world_mut().spawn(furnace).id();
+
+        // (position, direction into the furnace, item)
+        let feeds = [
+            (IVec3::new(0, 8, -1), Direction::South, items::coal()),
+            (IVec3::new(0, 8, 1), Direction::North, items::iron_ore()),
+            (IVec3::new(-1, 8, 0), Direction::East, items::coal()),
+        ];
+        let belts: Vec<Entity> = feeds
+            .iter()
+            .map(|&(position, direction, item)| {
+                app.world_mut()
+                    .spawn(Conveyor {
+                        position,
+                        direction,
+                        output_direction: direction,
+                        items: vec![ConveyorItem::new(item, 1.0)],
+                        last_output_index: 0,
+                        last_input_pos: None,
+                        enabled: true,
+                        shape: ConveyorShape::Straight,
+                        speed_multiplier: 1.0,
+                    })
+                    .id()
+            })
+            .collect();
+
+        // Keep the two feeding belts stocked for a few transfers
+        for _ in 0..3 {
+            app.update();
+            for (&belt, &(_, _, item)) in belts.iter().zip(&feeds).take(2) {
+                let mut conveyor = app.world_mut().get_mut::<Conveyor>(belt).unwrap();
+                if conveyor.items.is_empty() {
+                    conveyor.items.push(ConveyorItem::new(item, 1.0));
+                }
+            }
+        }
+
+        let machine = app.world().get::<Machine>(furnace).unwrap();
+        // Fuel face feeds only the fuel slot, input face only the input slot
+        assert_eq!(machine.slots.fuel, 3);
+        assert_eq!(machine.slots.inputs[0].item_id, Some(items::iron_ore()));
+        assert_eq!(furnace_input(&app, furnace), 3);
+        // Coal at an input-routed face never reaches the fuel slot
+        assert_eq!(
+            app.world().get::<Conveyor>(belts[2]).unwrap().items.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_item_visuals_share_materials_and_use_icons() {
+        use crate::components::ConveyorItem;
+
+        let mut app = item_visual_app();
+        // Coal has an icon, iron ore only a color
+        app.world_mut()
+            .resource_mut::<ItemSprites>()
+            .insert_id(items::coal(), Handle::default());
+        for x in 0..10 {
+            app.world_mut().spawn(Conveyor {
+                position: IVec3::new(x, 8, 0),
+                direction: Direction::East,
+                output_direction: Direction::East,
+                items: vec![
+                    ConveyorItem::new(items::iron_ore(), 0.1),
+                    ConveyorItem::new(items::coal(), 0.6),
+                ],
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier: 1.0,
+            });
+        }
+
+        app.update();
+        assert_eq!(count_visuals(&mut app), 20);
+        let billboards = app
+            .world_mut()
+            .query_filtered::<Entity, With<ConveyorItemBillboard>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(billboards, 10);
+        // One material per item type, not per visual
+        assert_eq!(app.world().resource::<Assets<StandardMaterial>>().len(), 2);
+
+        // Turning icons off rebuilds everything as cubes
+        app.world_mut()
+            .resource_mut::<GameSettings>()
+            .conveyor_item_icons = false;
+        app.update();
+        let billboards = app
+            .world_mut()
+            .query_filtered::<Entity, With<ConveyorItemBillboard>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(billboards, 0);
+        assert_eq!(count_visuals(&mut app), 20);
+    }
+
+    #[test]
+    fn test_billboard_rotation_faces_camera() {
+        let item = Vec3::new(1.0, 8.0, 1.0);
+        for camera in [
+            Vec3::new(1.0, 10.0, 6.0),
+            Vec3::new(-4.0, 9.0, 1.0),
+            Vec3::new(4.0, 12.0, -3.0),
+        ] {
+            let normal = billboard_rotation(item, camera) * Vec3::Z;
+            let to_camera = (camera - item).with_y(0.0).normalize();
+            assert_near(normal, to_camera);
+        }
+        // Straight above: no yaw to pick
+        assert_eq!(
+            billboard_rotation(item, item + Vec3::Y * 3.0),
+            Quat::IDENTITY
+        );
+    }
 }
@@ -0,0 +1,416 @@
+//! Delivery platform acceptance policy and reject buffer
+//!
+//! Every platform has a `DeliveryFilter` deciding which items count as
+//! delivered. Rejected items are never destroyed:
+//!
+//! - A rejected stack bounces: it stays at the end of the belt it came on
+//! - When items queue up behind it, the stack moves into the platform's reject
+//!   buffer (at most REJECT_BUFFER_CAPACITY items) to free the belt
+//! - Belts leading away from the platform drain the buffer, one stack per tick
+//!
+//! Shift+right-clicking a platform changes the policy: an empty hand cycles
+//! Accept All / Quest Items Only, a held item toggles it in the whitelist.
+//! Policy and buffer are saved by platform origin.
+
+use bevy::prelude::*;
+
+use crate::components::CurrentQuest;
+use crate::constants::{CONVEYOR_ITEM_SPACING, CONVEYOR_MAX_STACK};
+use crate::core::ItemId;
+use crate::input::{GameAction, InputManager};
+use crate::main_menu::InGameSet;
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::systems::quest::QuestCache;
+use crate::{
+    ContinuousActionTimer, Conveyor, DeliveryPlatform, InputStateResourcesWithCursor, TargetBlock,
+};
+
+/// Items the reject buffer holds before rejected stacks have to stay on their belts
+pub const REJECT_BUFFER_CAPACITY: u32 = 16;
+
+/// Which items a platform accepts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DeliveryPolicy {
+    /// Everything counts as delivered
+    #[default]
+    AcceptAll,
+    /// Only items the active quest requires
+    QuestItemsOnly,
+    /// Only the listed items
+    Whitelist(Vec<ItemId>),
+}
+
+impl DeliveryPolicy {
+    /// Save ID ("accept_all", "quest_items", "whitelist")
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::AcceptAll => "accept_all",
+            Self::QuestItemsOnly => "quest_items",
+            Self::Whitelist(_) => "whitelist",
+        }
+    }
+
+    /// Policy from its save ID (the whitelist is stored separately)
+    pub fn from_id(id: &str, whitelist: Vec<ItemId>) -> Option<Self> {
+        match id {
+            "accept_all" => Some(Self::AcceptAll),
+            "quest_items" => Some(Self::QuestItemsOnly),
+            "whitelist" => Some(Self::Whitelist(whitelist)),
+            _ => None,
+        }
+    }
+
+    /// UI label
+    pub fn label(&self) -> String {
+        match self {
+            Self::AcceptAll => "すべて受け入れ".to_string(),
+            Self::QuestItemsOnly => "クエスト品のみ".to_string(),
+            Self::Whitelist(items) => {
+                let names: Vec<&str> = items.iter().map(|item| item.display_name()).collect();
+                format!("指定品のみ ({})", names.join(", "))
+            }
+        }
+    }
+}
+
+/// Acceptance policy, reject buffer and rejection tally of one platform
+///
+/// Platforms without one accept everything.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct DeliveryFilter {
+    pub policy: DeliveryPolicy,
+    /// Required items of the active quest (kept current by `sync_quest_items`)
+    pub quest_items: Vec<ItemId>,
+    /// Rejected stacks waiting for an outgoing belt, oldest first
+    pub buffer: Vec<(ItemId, u32)>,
+    /// Items moved into the buffer since the platform was built or loaded
+    pub rejected: Vec<(ItemId, u32)>,
+}
+
+impl DeliveryFilter {
+    pub fn new(policy: DeliveryPolicy) -> Self {
+        Self {
+            policy,
+            ..default()
+        }
+    }
+
+    /// Whether `item` counts as delivered
+    pub fn accepts(&self, item: ItemId) -> bool {
+        match &self.policy {
+            DeliveryPolicy::AcceptAll => true,
+            DeliveryPolicy::QuestItemsOnly => self.quest_items.contains(&item),
+            DeliveryPolicy::Whitelist(items) => items.contains(&item),
+        }
+    }
+
+    /// Items currently in the buffer
+    pub fn buffered(&self) -> u32 {
+        self.buffer.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Move up to `count` rejected items into the buffer; returns how many fit
+    pub fn reject(&mut self, item: ItemId, count: u32) -> u32 {
+        let taken = count.min(REJECT_BUFFER_CAPACITY - self.buffered());
+        if taken == 0 {
+            return 0;
+        }
+        match self.buffer.last_mut() {
+            Some((last, buffered)) if *last == item => *buffered += taken,
+            _ => self.buffer.push((item, taken)),
+        }
+        match self.rejected.iter_mut().find(|(id, _)| *id == item) {
+            Some((_, total)) => *total += taken,
+            None => self.rejected.push((item, taken)),
+        }
+        taken
+    }
+
+    /// Oldest buffered stack, at most CONVEYOR_MAX_STACK items (without removing it)
+    pub fn next_stack(&self) -> Option<(ItemId, u32)> {
+        self.buffer
+            .first()
+            .map(|(item, count)| (*item, (*count).min(CONVEYOR_MAX_STACK)))
+    }
+
+    /// Remove `count` items of the oldest buffered stack (after it was put on a belt)
+    pub fn take_stack(&mut self, count: u32) {
+        if let Some((_, buffered)) = self.buffer.first_mut() {
+            *buffered = buffered.saturating_sub(count);
+            if *buffered == 0 {
+                self.buffer.remove(0);
+            }
+        }
+    }
+
+    /// Shift+right-click: an empty hand cycles Accept All / Quest Items Only,
+    /// a held item toggles it in the whitelist (an empty whitelist accepts all)
+    pub fn cycle(&mut self, held: Option<ItemId>) {
+        self.policy = match (held, std::mem::take(&mut self.policy)) {
+            (None, DeliveryPolicy::AcceptAll) => DeliveryPolicy::QuestItemsOnly,
+            (None, _) => DeliveryPolicy::AcceptAll,
+            (Some(item), DeliveryPolicy::Whitelist(mut items)) => {
+                if let Some(index) = items.iter().position(|id| *id == item) {
+                    items.remove(index);
+                } else {
+                    items.push(item);
+                }
+                if items.is_empty() {
+                    DeliveryPolicy::AcceptAll
+                } else {
+                    DeliveryPolicy::Whitelist(items)
+                }
+            }
+            (Some(item), _) => DeliveryPolicy::Whitelist(vec![item]),
+        };
+    }
+}
+
+/// Whether the stack at `index` has another one queued right behind it
+///
+/// Belt items are sorted by progress, so the one behind is `index - 1`.
+pub fn backed_up(conveyor: &Conveyor, index: usize) -> bool {
+    let Some(behind) = index.checked_sub(1).and_then(|i| conveyor.items.get(i)) else {
+        return false;
+    };
+    conveyor.items[index].progress - behind.progress <= CONVEYOR_ITEM_SPACING + 0.001
+}
+
+/// Whether a belt leads away from the platform (takes items from a platform cell)
+pub fn drains_platform(conveyor: &Conveyor, platform: &DeliveryPlatform) -> bool {
+    platform.contains(conveyor.position - conveyor.direction.to_ivec3())
+        && !platform.contains(conveyor.position)
+}
+
+/// Keep each filter's quest item list in step with the active quest
+fn sync_quest_items(
+    current_quest: Res<CurrentQuest>,
+    quest_cache: Res<QuestCache>,
+    mut filters: Query<&mut DeliveryFilter>,
+) {
+    let required: Vec<ItemId> = quest_cache
+        .main_quests
+        .get(current_quest.index)
+        .map(|quest| quest.required_items.iter().map(|(item, _)| *item).collect())
+        .unwrap_or_default();
+    for mut filter in filters.iter_mut() {
+        // New platforms (built or loaded) pick the list up on their first frame
+        if (current_quest.is_changed() || filter.is_added()) && filter.quest_items != required {
+            filter.quest_items = required.clone();
+        }
+    }
+}
+
+/// Shift+right-click a delivery platform to change its policy
+///
+/// Swallows the right-click it handles (see `BlockClickSet`).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_delivery_policy_click(
+    input: Res<InputManager>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    input_resources: InputStateResourcesWithCursor,
+    target: Res<TargetBlock>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    mut platforms: Query<(&DeliveryPlatform, &mut DeliveryFilter)>,
+) {
+    if !input.pressed(GameAction::SecondaryAction) || !input.pressed(GameAction::ModifierShift) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Some(pos) = target.break_target else {
+        return;
+    };
+    let Some((platform, mut filter)) = platforms
+        .iter_mut()
+        .find(|(platform, _)| platform.contains(pos))
+    else {
+        return;
+    };
+
+    // Keep block_place from building on the platform (also while the button is held)
+    action_timer.place_timer.reset();
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+    mouse_button.clear_just_pressed(MouseButton::Right);
+
+    let held = local_player
+        .and_then(|lp| inventories.get(lp.0).ok())
+        .and_then(|inv| inv.selected_item_id());
+    filter.cycle(held);
+    info!(origin = ?platform.position, policy = filter.policy.id(), "Delivery policy set");
+}
+
+pub struct DeliveryFilterPlugin;
+
+impl Plugin for DeliveryFilterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_quest_items.in_set(InGameSet));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ConveyorItem, ConveyorShape, Direction};
+    use crate::core::items;
+    use crate::events::game_events::{ConveyorTransfer, ItemDelivered};
+    use crate::events::{EventDepth, EventSystemConfig};
+    use crate::logistics::conveyor_transfer;
+    use crate::logistics::delivery_pad::pad_platform;
+    use crate::player::{LocalPlatform, PlatformInventory};
+
+    fn belt(position: IVec3, direction: Direction) -> Conveyor {
+        Conveyor {
+            position,
+            direction,
+            output_direction: direction,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_policies_and_cycling() {
+        let mut filter = DeliveryFilter {
+            quest_items: vec![items::iron_ingot()],
+            ..default()
+        };
+        assert!(filter.accepts(items::stone()));
+
+        filter.cycle(None);
+        assert_eq!(filter.policy, DeliveryPolicy::QuestItemsOnly);
+        assert!(filter.accepts(items::iron_ingot()));
+        assert!(!filter.accepts(items::stone()));
+
+        // Held items build a whitelist; removing the last one accepts all again
+        filter.cycle(Some(items::copper_ingot()));
+        filter.cycle(Some(items::stone()));
+        assert!(filter.accepts(items::stone()));
+        assert!(!filter.accepts(items::iron_ingot()));
+        filter.cycle(Some(items::copper_ingot()));
+        filter.cycle(Some(items::stone()));
+        assert_eq!(filter.policy, DeliveryPolicy::AcceptAll);
+
+        for policy in [
+            DeliveryPolicy::AcceptAll,
+            DeliveryPolicy::QuestItemsOnly,
+            DeliveryPolicy::Whitelist(vec![items::stone()]),
+        ] {
+            let whitelist = match &policy {
+                DeliveryPolicy::Whitelist(items) => items.clone(),
+                _ => Vec::new(),
+            };
+            assert_eq!(
+                DeliveryPolicy::from_id(policy.id(), whitelist),
+                Some(policy)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reject_buffer_is_bounded() {
+        let mut filter = DeliveryFilter::new(DeliveryPolicy::QuestItemsOnly);
+        assert_eq!(filter.reject(items::stone(), 10), 10);
+        assert_eq!(filter.reject(items::stone(), 4), 4);
+        assert_eq!(filter.reject(items::coal(), 4), 2);
+        assert_eq!(filter.reject(items::coal(), 1), 0);
+        assert_eq!(filter.buffered(), REJECT_BUFFER_CAPACITY);
+        assert_eq!(
+            filter.rejected,
+            vec![(items::stone(), 14), (items::coal(), 2)]
+        );
+
+        // Drained in order, a belt stack at a time
+        assert_eq!(
+            filter.next_stack(),
+            Some((items::stone(), CONVEYOR_MAX_STACK))
+        );
+        for _ in 0..4 {
+            let (_, count) = filter.next_stack().unwrap();
+            filter.take_stack(count);
+        }
+        assert_eq!(filter.next_stack(), Some((items::coal(), 2)));
+    }
+
+    #[test]
+    fn test_rejected_stone_bounces_and_drains_without_loss() {
+        let mut app = App::new();
+        app.init_resource::<EventDepth>()
+            .init_resource::<EventSystemConfig>()
+            .add_message::<ConveyorTransfer>()
+            .add_message::<ItemDelivered>()
+            .add_systems(Update, conveyor_transfer);
+
+        let inventory = app.world_mut().spawn(PlatformInventory::new()).id();
+        app.insert_resource(LocalPlatform(inventory));
+        let mut filter = DeliveryFilter::new(DeliveryPolicy::QuestItemsOnly);
+        filter.quest_items = vec![items::iron_ingot()];
+        let platform = app
+            .world_mut()
+            .spawn(pad_platform(IVec3::ZERO))
+            .insert(filter)
+            .id();
+
+        // Feed belt into the platform: stone at the end, another stone and an ingot behind it
+        let mut feed = belt(IVec3::new(3, 0, 1), Direction::West);
+        feed.items = vec![
+            ConveyorItem::new(items::iron_ingot(), 0.0),
+            ConveyorItem::new(items::stone(), 0.5),
+            ConveyorItem::new(items::stone(), 1.0),
+        ];
+        let feed = app.world_mut().spawn(feed).id();
+        // Return belt leading away from the platform
+        let ret = app
+            .world_mut()
+            .spawn(belt(IVec3::new(3, 0, 2), Direction::East))
+            .id();
+
+        let stones = |app: &App, entity: Entity| -> u32 {
+            app.world()
+                .get::<Conveyor>(entity)
+                .unwrap()
+                .items
+                .iter()
+                .filter(|item| item.item_id == items::stone())
+                .map(|item| item.count)
+                .sum()
+        };
+
+        // The lone stone at the end bounces: still on its belt, nothing delivered
+        app.update();
+        assert_eq!(stones(&app, feed), 2);
+        assert_eq!(stones(&app, ret), 0);
+        let front = app
+            .world()
+            .get::<Conveyor>(feed)
+            .unwrap()
+            .items
+            .last()
+            .cloned();
+        assert_eq!(front.map(|item| item.progress), Some(1.0));
+
+        // Once the second stone queues behind it, it leaves through the return belt
+        for _ in 0..200 {
+            app.update();
+        }
+        let stored = app.world().get::<PlatformInventory>(inventory).unwrap();
+        assert_eq!(stored.get_count_by_id(items::stone()), 0);
+        assert_eq!(stored.get_count_by_id(items::iron_ingot()), 1);
+
+        // Both stones went through the buffer onto the return belt
+        assert_eq!(stones(&app, feed), 0);
+        assert_eq!(stones(&app, ret), 2);
+        let filter = app.world().get::<DeliveryFilter>(platform).unwrap();
+        assert_eq!(filter.buffered(), 0);
+        assert_eq!(filter.rejected, vec![(items::stone(), 2)]);
+    }
+}
@@ -0,0 +1,495 @@
+//! Player-built delivery platforms
+//!
+//! Delivery pads are regular world blocks. A filled 3x3 square of pads
+//! forms a `DeliveryPlatform` covering those cells, and conveyors deliver
+//! into it like the starter platform. Every platform feeds the shared
+//! platform inventory, so quests count deliveries from any of them.
+//!
+//! - Pads only join one platform; leftover pads stay inert
+//! - Breaking any pad dissolves its platform (the other pads stay inert)
+//! - Right-clicking a platform assigns a `DeliveryContract` for the held item;
+//!   right-clicking again with the same item cycles the rate target
+//! - Platforms are rebuilt from pad blocks on load, contracts are saved
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::delivery_filter::DeliveryFilter;
+use crate::core::{items, ItemId};
+use crate::events::game_events::{BlockBroken, BlockPlaced, ItemDelivered};
+use crate::input::{GameAction, InputManager};
+use crate::main_menu::InGameSet;
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::world::WorldData;
+use crate::{ContinuousActionTimer, DeliveryPlatform, InputStateResourcesWithCursor, TargetBlock};
+
+/// Side length of a pad platform (blocks)
+pub const PAD_PLATFORM_SIZE: i32 = 3;
+
+/// Rate targets cycled by right-clicking with the contracted item (items/minute)
+pub const CONTRACT_TARGETS: [u32; 4] = [30, 60, 120, 240];
+
+/// Length of the window a contract's delivery rate is measured over (seconds)
+pub const CONTRACT_WINDOW_SECS: f32 = 60.0;
+
+/// Marks a platform formed from delivery pads (the starter platform has none)
+#[derive(Component, Debug)]
+pub struct PadPlatform;
+
+/// Delivery target assigned to a pad platform
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DeliveryContract {
+    pub item: ItemId,
+    /// Target delivery rate (items/minute)
+    pub target_per_min: u32,
+    /// Contracted items delivered in the current window
+    pub window_delivered: u32,
+    /// Time spent in the current window (seconds)
+    pub window_elapsed: f32,
+    /// Rate measured over the last full window (None until one completes)
+    pub last_rate: Option<u32>,
+}
+
+impl DeliveryContract {
+    pub fn new(item: ItemId, target_per_min: u32) -> Self {
+        Self {
+            item,
+            target_per_min,
+            window_delivered: 0,
+            window_elapsed: 0.0,
+            last_rate: None,
+        }
+    }
+
+    /// Count a delivery (other items don't count toward the contract)
+    pub fn record(&mut self, item: ItemId, count: u32) {
+        if item == self.item {
+            self.window_delivered += count;
+        }
+    }
+
+    /// Advance the measurement window, closing it after CONTRACT_WINDOW_SECS
+    pub fn advance(&mut self, delta_secs: f32) {
+        self.window_elapsed += delta_secs;
+        if self.window_elapsed >= CONTRACT_WINDOW_SECS {
+            self.last_rate = Some(self.window_delivered);
+            self.window_delivered = 0;
+            self.window_elapsed -= CONTRACT_WINDOW_SECS;
+        }
+    }
+
+    /// Whether the last full window reached the target rate
+    pub fn is_met(&self) -> bool {
+        self.last_rate
+            .is_some_and(|rate| rate >= self.target_per_min)
+    }
+
+    /// Next rate target after the current one (wraps around)
+    pub fn next_target(&self) -> u32 {
+        let index = CONTRACT_TARGETS
+            .iter()
+            .position(|t| *t == self.target_per_min)
+            .map_or(0, |i| (i + 1) % CONTRACT_TARGETS.len());
+        CONTRACT_TARGETS[index]
+    }
+}
+
+/// Cells of the pad platform with the given origin (min x/z corner)
+pub fn platform_cells(origin: IVec3) -> impl Iterator<Item = IVec3> {
+    (0..PAD_PLATFORM_SIZE)
+        .flat_map(move |dx| (0..PAD_PLATFORM_SIZE).map(move |dz| origin + IVec3::new(dx, 0, dz)))
+}
+
+/// Origin of a filled 3x3 pad square containing `pos`
+///
+/// Squares with a cell that is not a pad or already belongs to a platform
+/// are skipped. Candidates are tried in a fixed order, so the result only
+/// depends on the pad layout.
+pub fn find_formation(
+    pos: IVec3,
+    is_pad: impl Fn(IVec3) -> bool,
+    is_claimed: impl Fn(IVec3) -> bool,
+) -> Option<IVec3> {
+    for dx in 0..PAD_PLATFORM_SIZE {
+        for dz in 0..PAD_PLATFORM_SIZE {
+            let origin = pos - IVec3::new(dx, 0, dz);
+            if platform_cells(origin).all(|cell| is_pad(cell) && !is_claimed(cell)) {
+                return Some(origin);
+            }
+        }
+    }
+    None
+}
+
+/// Platform origins formed by a set of pads (used to rebuild platforms on load)
+pub fn detect_formations(pads: &HashSet<IVec3>) -> Vec<IVec3> {
+    let mut sorted: Vec<IVec3> = pads.iter().copied().collect();
+    sorted.sort_by_key(|p| (p.y, p.x, p.z));
+
+    let mut claimed: HashSet<IVec3> = HashSet::new();
+    let mut origins = Vec::new();
+    for pos in sorted {
+        if claimed.contains(&pos) {
+            continue;
+        }
+        if let Some(origin) = find_formation(pos, |p| pads.contains(&p), |p| claimed.contains(&p)) {
+            claimed.extend(platform_cells(origin));
+            origins.push(origin);
+        }
+    }
+    origins
+}
+
+/// Components of a pad platform (accepts everything until a policy is set)
+pub fn pad_platform(origin: IVec3) -> (DeliveryPlatform, PadPlatform, DeliveryFilter) {
+    (
+        DeliveryPlatform::with_size(origin, PAD_PLATFORM_SIZE),
+        PadPlatform,
+        DeliveryFilter::default(),
+    )
+}
+
+/// Form platforms when pads complete a square, dissolve them when a pad breaks
+fn track_delivery_pads(
+    mut commands: Commands,
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+    world_data: Res<WorldData>,
+    platforms: Query<(Entity, &DeliveryPlatform), With<PadPlatform>>,
+) {
+    let pad = items::delivery_pad();
+
+    for event in broken.read() {
+        if event.block != pad {
+            continue;
+        }
+        for (entity, platform) in platforms.iter() {
+            if platform.contains(event.pos) {
+                info!(origin = ?platform.position, "Delivery platform dissolved");
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    // Platforms formed this frame aren't visible to the query until commands apply
+    let mut formed: Vec<IVec3> = Vec::new();
+    for event in placed.read() {
+        if event.block != pad {
+            continue;
+        }
+        let claimed = |p: IVec3| {
+            platforms.iter().any(|(_, platform)| platform.contains(p))
+                || formed
+                    .iter()
+                    .any(|origin| platform_cells(*origin).any(|cell| cell == p))
+        };
+        // Pads are always player-placed, so they're tracked in modified_blocks
+        let is_pad = |p: IVec3| world_data.modified_blocks.get(&p) == Some(&Some(pad));
+        if let Some(origin) = find_formation(event.pos, is_pad, claimed) {
+            info!(?origin, "Delivery platform formed");
+            commands.spawn(pad_platform(origin));
+            formed.push(origin);
+        }
+    }
+}
+
+/// Right-click a pad platform to assign a contract for the held item
+///
+/// Same item again cycles the rate target, an empty hand clears the contract.
+/// Swallows the right-click it handles (see `BlockClickSet`).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_delivery_contract_click(
+    mut commands: Commands,
+    input: Res<InputManager>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    input_resources: InputStateResourcesWithCursor,
+    target: Res<TargetBlock>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    platforms: Query<(Entity, &DeliveryPlatform, Option<&DeliveryContract>), With<PadPlatform>>,
+) {
+    if !input.pressed(GameAction::SecondaryAction) || input.pressed(GameAction::ModifierShift) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Some(pos) = target.break_target else {
+        return;
+    };
+    let Some((entity, platform, contract)) = platforms
+        .iter()
+        .find(|(_, platform, _)| platform.contains(pos))
+    else {
+        return;
+    };
+
+    // Keep block_place from building on the platform (also while the button is held)
+    action_timer.place_timer.reset();
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+    mouse_button.clear_just_pressed(MouseButton::Right);
+
+    let held = local_player
+        .and_then(|lp| inventories.get(lp.0).ok())
+        .and_then(|inv| inv.selected_item_id());
+    match held {
+        None => {
+            commands.entity(entity).remove::<DeliveryContract>();
+            info!(origin = ?platform.position, "Delivery contract cleared");
+        }
+        Some(item) => {
+            let target_per_min = match contract {
+                Some(contract) if contract.item == item => contract.next_target(),
+                _ => CONTRACT_TARGETS[0],
+            };
+            info!(
+                origin = ?platform.position,
+                item = ?item.name(),
+                target_per_min,
+                "Delivery contract set"
+            );
+            commands
+                .entity(entity)
+                .insert(DeliveryContract::new(item, target_per_min));
+        }
+    }
+}
+
+/// Count deliveries toward each platform's contract
+fn track_contract_deliveries(
+    time: Res<Time>,
+    mut delivered: MessageReader<ItemDelivered>,
+    mut contracts: Query<&mut DeliveryContract>,
+) {
+    for event in delivered.read() {
+        if let Ok(mut contract) = contracts.get_mut(event.platform) {
+            contract.record(event.item, event.count);
+        }
+    }
+    for mut contract in contracts.iter_mut() {
+        contract.advance(time.delta_secs());
+    }
+}
+
+pub struct DeliveryPadPlugin;
+
+impl Plugin for DeliveryPadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (track_delivery_pads, track_contract_deliveries).in_set(InGameSet),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Conveyor, ConveyorItem, ConveyorShape, Direction};
+    use crate::events::game_events::{ConveyorTransfer, EventSource};
+    use crate::events::{EventDepth, EventSystemConfig};
+    use crate::logistics::conveyor_transfer;
+    use crate::player::{LocalPlatform, PlatformInventory};
+    use crate::world::ChunkData;
+
+    fn square(origin: IVec3) -> HashSet<IVec3> {
+        platform_cells(origin).collect()
+    }
+
+    #[test]
+    fn test_formation_detection() {
+        let origin = IVec3::new(10, 8, 4);
+        let mut pads = square(origin);
+
+        // Every pad of a full square finds the same origin
+        for pad in &pads {
+            assert_eq!(
+                find_formation(*pad, |p| pads.contains(&p), |_| false),
+                Some(origin)
+            );
+        }
+
+        // A missing corner means no platform
+        pads.remove(&origin);
+        let last = origin + IVec3::new(2, 0, 2);
+        assert_eq!(find_formation(last, |p| pads.contains(&p), |_| false), None);
+
+        // A 3x4 strip forms one platform and leaves a row of inert pads
+        let strip: HashSet<IVec3> = square(origin)
+            .into_iter()
+            .chain((0..3).map(|dx| origin + IVec3::new(dx, 0, 3)))
+            .collect();
+        assert_eq!(detect_formations(&strip), vec![origin]);
+
+        // Claimed cells can't be reused by a neighbouring square
+        let claimed = square(origin);
+        assert_eq!(
+            find_formation(
+                origin + IVec3::new(1, 0, 3),
+                |p| strip.contains(&p),
+                |p| claimed.contains(&p)
+            ),
+            None
+        );
+
+        // Two separate squares both form
+        let two: HashSet<IVec3> = square(origin)
+            .union(&square(origin + IVec3::new(5, 0, 0)))
+            .copied()
+            .collect();
+        assert_eq!(
+            detect_formations(&two),
+            vec![origin, origin + IVec3::new(5, 0, 0)]
+        );
+    }
+
+    fn pad_app() -> App {
+        let mut world = WorldData::default();
+        world
+            .chunks
+            .insert(IVec2::ZERO, ChunkData::generate(IVec2::ZERO));
+        let mut app = App::new();
+        app.insert_resource(world)
+            .add_message::<BlockPlaced>()
+            .add_message::<BlockBroken>()
+            .add_systems(Update, track_delivery_pads);
+        app
+    }
+
+    fn place_pad(app: &mut App, pos: IVec3) {
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .set_block(pos, items::delivery_pad());
+        app.world_mut().write_message(BlockPlaced {
+            pos,
+            block: items::delivery_pad(),
+            source: EventSource::System,
+        });
+    }
+
+    fn platform_origins(app: &mut App) -> Vec<IVec3> {
+        let mut origins: Vec<IVec3> = app
+            .world_mut()
+            .query_filtered::<&DeliveryPlatform, With<PadPlatform>>()
+            .iter(app.world())
+            .map(|p| p.position)
+            .collect();
+        origins.sort_by_key(|p| p.to_array());
+        origins
+    }
+
+    #[test]
+    fn test_breaking_pad_dissolves_platform() {
+        let mut app = pad_app();
+        let first = IVec3::new(0, 8, 0);
+        let second = IVec3::new(6, 8, 0);
+        for pos in square(first).into_iter().chain(square(second)) {
+            place_pad(&mut app, pos);
+        }
+        app.update();
+        assert_eq!(platform_origins(&mut app), vec![first, second]);
+
+        let broken = first + IVec3::new(1, 0, 1);
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .remove_block(broken);
+        app.world_mut().write_message(BlockBroken {
+            pos: broken,
+            block: items::delivery_pad(),
+            source: EventSource::System,
+        });
+        app.update();
+
+        // Only the broken platform is gone; its other pads stay inert
+        assert_eq!(platform_origins(&mut app), vec![second]);
+        let world = app.world().resource::<WorldData>();
+        assert_eq!(world.get_block(first), Some(items::delivery_pad()));
+
+        // Replacing the pad forms the platform again
+        place_pad(&mut app, broken);
+        app.update();
+        assert_eq!(platform_origins(&mut app), vec![first, second]);
+    }
+
+    #[test]
+    fn test_conveyors_deliver_to_each_platform() {
+        let mut app = App::new();
+        app.init_resource::<EventDepth>()
+            .init_resource::<EventSystemConfig>()
+            .add_message::<ConveyorTransfer>()
+            .add_message::<ItemDelivered>()
+            .add_systems(Update, conveyor_transfer);
+
+        let inventory = app.world_mut().spawn(PlatformInventory::new()).id();
+        app.insert_resource(LocalPlatform(inventory));
+        let west = app.world_mut().spawn(pad_platform(IVec3::ZERO)).id();
+        let east = app
+            .world_mut()
+            .spawn(pad_platform(IVec3::new(10, 0, 0)))
+            .id();
+
+        let belt = |position: IVec3, direction: Direction, item: ItemId| {
+            let mut stack = ConveyorItem::new(item, 1.0);
+            stack.count = 2;
+            Conveyor {
+                position,
+                direction,
+                output_direction: direction,
+                items: vec![stack],
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier: 1.0,
+            }
+        };
+        app.world_mut().spawn(belt(
+            IVec3::new(3, 0, 1),
+            Direction::West,
+            items::iron_ingot(),
+        ));
+        app.world_mut().spawn(belt(
+            IVec3::new(9, 0, 1),
+            Direction::East,
+            items::copper_ingot(),
+        ));
+
+        app.update();
+
+        // Both platforms feed the shared inventory
+        let stored = app.world().get::<PlatformInventory>(inventory).unwrap();
+        assert_eq!(stored.get_count_by_id(items::iron_ingot()), 2);
+        assert_eq!(stored.get_count_by_id(items::copper_ingot()), 2);
+
+        // Events name the receiving platform
+        let events = app.world().resource::<Messages<ItemDelivered>>();
+        let mut reader = events.get_cursor();
+        let mut delivered: Vec<(Entity, ItemId)> =
+            reader.read(events).map(|e| (e.platform, e.item)).collect();
+        delivered.sort_by_key(|(entity, _)| *entity);
+        let mut expected = vec![(west, items::iron_ingot()), (east, items::copper_ingot())];
+        expected.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(delivered, expected);
+    }
+
+    #[test]
+    fn test_contract_rate_window() {
+        let mut contract = DeliveryContract::new(items::iron_ingot(), 60);
+        contract.record(items::iron_ingot(), 40);
+        contract.record(items::copper_ingot(), 100);
+        contract.advance(30.0);
+        assert_eq!(contract.last_rate, None);
+        contract.record(items::iron_ingot(), 25);
+        contract.advance(30.0);
+        assert_eq!(contract.last_rate, Some(65));
+        assert!(contract.is_met());
+        assert_eq!(contract.window_delivered, 0);
+
+        assert_eq!(contract.next_target(), 120);
+        let last = DeliveryContract::new(items::iron_ingot(), 240);
+        assert_eq!(last.next_target(), CONTRACT_TARGETS[0]);
+    }
+}
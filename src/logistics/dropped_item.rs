@@ -0,0 +1,234 @@
+//! Items lying loose in the world
+//!
+//! The drop key (G) throws one of the selected hotbar item in front of the
+//! player (with Ctrl: the whole stack). Dropped items fall until they rest on
+//! a block, machine or belt. A hopper underneath collects them
+//! (`machines::generic::hopper`); otherwise the player picks them back up by
+//! walking over them.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use crate::components::{Conveyor, ConveyorVisualAssets, Machine, Player, PlayerCamera};
+use crate::constants::SIMULATION_TICK_SECS;
+use crate::core::ItemId;
+use crate::graphics::BlockPalette;
+use crate::input::{GameAction, InputManager};
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::utils::world_to_grid;
+use crate::world::WorldData;
+use crate::{InputStateResourcesWithCursor, BLOCK_SIZE, CONVEYOR_ITEM_SIZE};
+
+use super::conveyor::item_color_material;
+
+/// Fall speed of dropped items (blocks per second)
+pub const DROPPED_ITEM_FALL_SPEED: f32 = 8.0;
+
+/// Distance from the player's center within which dropped items are picked up
+pub const DROPPED_ITEM_PICKUP_RADIUS: f32 = 1.5;
+
+/// Seconds before a freshly dropped item can be picked up again
+pub const DROPPED_ITEM_PICKUP_DELAY: f32 = 1.5;
+
+/// How far in front of the camera items are thrown (blocks)
+const DROP_DISTANCE: f32 = 1.2;
+
+/// A stack lying in the world; its transform is the bottom center of the stack
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DroppedItem {
+    pub item_id: ItemId,
+    pub count: u32,
+    /// Seconds until the player can pick it up
+    pub pickup_delay: f32,
+}
+
+impl DroppedItem {
+    pub fn new(item_id: ItemId, count: u32) -> Self {
+        Self {
+            item_id,
+            count,
+            pickup_delay: DROPPED_ITEM_PICKUP_DELAY,
+        }
+    }
+}
+
+/// Grid cell a dropped item lies in
+pub fn dropped_item_cell(transform: &Transform) -> IVec3 {
+    world_to_grid(transform.translation)
+}
+
+/// Height after one fall step, stopping on top of the first solid cell below
+///
+/// `solid` tells whether a grid cell blocks falling. Items never fall below y = 0.
+pub fn fall_step(pos: Vec3, distance: f32, solid: impl Fn(IVec3) -> bool) -> f32 {
+    let cell = world_to_grid(pos);
+    let next = (pos.y - distance).max(0.0);
+    // Every cell boundary crossed on the way down must be free
+    let mut y = cell.y;
+    while (y as f32) > next {
+        if solid(IVec3::new(cell.x, y - 1, cell.z)) {
+            return y as f32;
+        }
+        y -= 1;
+    }
+    next
+}
+
+/// Let dropped items fall until they rest on something
+///
+/// Runs in FixedUpdate so hoppers see items arrive on the simulation tick.
+pub fn fall_dropped_items(
+    world_data: Res<WorldData>,
+    machines: Query<&Machine>,
+    conveyors: Query<&Conveyor>,
+    mut dropped_query: Query<&mut Transform, With<DroppedItem>>,
+) {
+    let occupied: HashSet<IVec3> = machines
+        .iter()
+        .map(|m| m.position)
+        .chain(conveyors.iter().map(|c| c.position))
+        .collect();
+    let solid = |pos: IVec3| world_data.has_block(pos) || occupied.contains(&pos);
+    let distance = DROPPED_ITEM_FALL_SPEED * SIMULATION_TICK_SECS;
+
+    for mut transform in dropped_query.iter_mut() {
+        let y = fall_step(transform.translation, distance, solid);
+        if y != transform.translation.y {
+            transform.translation.y = y;
+        }
+    }
+}
+
+/// Throw the selected hotbar item with the drop key
+#[allow(clippy::too_many_arguments)]
+pub fn drop_selected_item(
+    mut commands: Commands,
+    input: Res<InputManager>,
+    input_resources: InputStateResourcesWithCursor,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventories: Query<&mut PlayerInventory>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    palette: Res<BlockPalette>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut assets: ResMut<ConveyorVisualAssets>,
+) {
+    if !input.just_pressed(GameAction::DropItem)
+        || !input_resources.get_state().allows_block_actions()
+    {
+        return;
+    }
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let (Ok(mut inventory), Ok(camera)) =
+        (inventories.get_mut(local_player.0), camera_query.single())
+    else {
+        return;
+    };
+    let Some(item_id) = inventory.get_selected_item_id() else {
+        return;
+    };
+    let count = if input.pressed(GameAction::ModifierCtrl) {
+        inventory.get_slot_count(inventory.selected_slot)
+    } else {
+        1
+    };
+    if !inventory.consume_item_by_id(item_id, count) {
+        return;
+    }
+
+    let forward = camera.forward().as_vec3();
+    let pos = camera.translation() + forward * DROP_DISTANCE;
+    let item_size = BLOCK_SIZE * CONVEYOR_ITEM_SIZE;
+    let mesh = assets
+        .cube
+        .get_or_insert_with(|| meshes.add(Cuboid::new(item_size, item_size, item_size)))
+        .clone();
+    let material = item_color_material(&mut assets, &mut materials, &palette, item_id);
+    commands
+        .spawn((
+            DroppedItem::new(item_id, count),
+            Transform::from_translation(pos),
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Mesh3d(mesh),
+                MeshMaterial3d(material),
+                Transform::from_xyz(0.0, item_size / 2.0, 0.0),
+            ));
+        });
+    info!(item = ?item_id.name(), count, "Dropped item");
+}
+
+/// Pick up dropped items next to the local player
+pub fn pickup_dropped_items(
+    mut commands: Commands,
+    time: Res<Time>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventories: Query<&mut PlayerInventory>,
+    player_query: Query<&Transform, (With<Player>, Without<DroppedItem>)>,
+    mut dropped_query: Query<(Entity, &mut DroppedItem, &Transform)>,
+) {
+    let delta = time.delta_secs();
+    for (_, mut dropped, _) in dropped_query.iter_mut() {
+        if dropped.pickup_delay > 0.0 {
+            dropped.pickup_delay -= delta;
+        }
+    }
+
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let (Ok(mut inventory), Ok(player)) =
+        (inventories.get_mut(local_player.0), player_query.single())
+    else {
+        return;
+    };
+
+    for (entity, mut dropped, transform) in dropped_query.iter_mut() {
+        if dropped.pickup_delay > 0.0
+            || transform.translation.distance(player.translation) > DROPPED_ITEM_PICKUP_RADIUS
+        {
+            continue;
+        }
+        let left = inventory.add_item_by_id(dropped.item_id, dropped.count);
+        if left == 0 {
+            commands.entity(entity).despawn();
+        } else {
+            dropped.count = left;
+        }
+    }
+}
+
+/// Dropped item falling, throwing and pickup
+pub struct DroppedItemPlugin;
+
+impl Plugin for DroppedItemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, fall_dropped_items)
+            .add_systems(Update, (drop_selected_item, pickup_dropped_items));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fall_step_lands_on_solid_cell() {
+        let floor = |pos: IVec3| pos.y <= 4;
+
+        // Free fall inside open air
+        assert_eq!(fall_step(Vec3::new(0.5, 9.5, 0.5), 1.0, floor), 8.5);
+        // Would pass into the floor: rests on top of it
+        assert_eq!(fall_step(Vec3::new(0.5, 5.3, 0.5), 1.0, floor), 5.0);
+        // Already resting
+        assert_eq!(fall_step(Vec3::new(0.5, 5.0, 0.5), 1.0, floor), 5.0);
+        // Big steps don't tunnel through the floor
+        assert_eq!(fall_step(Vec3::new(0.5, 12.0, 0.5), 20.0, floor), 5.0);
+        // Nothing below: stops at the bottom of the world
+        assert_eq!(fall_step(Vec3::new(0.5, 0.4, 0.5), 1.0, |_| false), 0.0);
+    }
+}
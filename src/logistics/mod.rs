@@ -1,4 +1,4 @@
-//! Logistics infrastructure (conveyors, inserters, pipes)
+//! Logistics infrastructure (conveyors and their connection hints, delivery pads, contracts and acceptance policies, quest holograms, dropped items, inserters, pipes)
 //!
 //! This module contains logistics-related systems that are separate from
 //! machine processing. Conveyors are treated as infrastructure rather than
@@ -8,6 +8,22 @@
 //! - Corner and splitter shape handling
 //! - Round-robin output distribution
 
+pub mod belt_animation;
+pub mod connection_hint;
 pub mod conveyor;
+pub mod delivery_filter;
+pub mod delivery_pad;
+pub mod dropped_item;
+pub mod path_trace;
+pub mod quest_hologram;
+pub mod timed_contract;
 
+pub use belt_animation::BeltAnimationPlugin;
+pub use connection_hint::{connection_hints, BeltConnection, ConnectionHint};
 pub use conveyor::*;
+pub use delivery_filter::{DeliveryFilter, DeliveryFilterPlugin, DeliveryPolicy};
+pub use delivery_pad::{DeliveryContract, DeliveryPadPlugin, PadPlatform};
+pub use dropped_item::{DroppedItem, DroppedItemPlugin};
+pub use path_trace::{PathTracePlugin, TraceCommandEvent};
+pub use quest_hologram::QuestHologramPlugin;
+pub use timed_contract::{TimedContract, TimedContractPlugin, TimedContracts};
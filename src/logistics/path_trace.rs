@@ -0,0 +1,467 @@
+//! Conveyor path trace (`/trace`)
+//!
+//! Follows the route an item placed on the belt under the crosshair would
+//! take, using the same target order as `conveyor_transfer` (delivery
+//! platform, then belt, then machine). Splitters fan out into one branch per
+//! connected output. The route is drawn as a dotted line of glowing markers
+//! for `TRACE_DISPLAY_SECS`: one color per branch, red where a branch dead
+//! ends and a yellow ring where it runs in a circle.
+//!
+//! The trace itself (`trace_conveyor_path`) only reads position maps, so it
+//! never touches the simulation.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::components::{Conveyor, ConveyorShape, DeliveryPlatform, Machine, TargetBlock};
+use crate::main_menu::InGameSet;
+
+use super::conveyor::takes_belt_input;
+
+/// Belts a trace follows before giving up (all branches together)
+pub const TRACE_MAX_HOPS: usize = 256;
+
+/// How long the traced path stays visible (seconds)
+pub const TRACE_DISPLAY_SECS: f32 = 5.0;
+
+/// Height of the markers above the belt cell's floor
+const MARKER_HEIGHT: f32 = 0.35;
+const MARKER_RADIUS: f32 = 0.07;
+/// Extra markers between two belt centers, so the path reads as a line
+const MARKERS_BETWEEN: u32 = 2;
+
+/// Branch colors, cycled by branch index
+const BRANCH_COLORS: [Color; 4] = [
+    Color::srgb(0.2, 0.9, 1.0),
+    Color::srgb(0.4, 1.0, 0.3),
+    Color::srgb(1.0, 0.4, 1.0),
+    Color::srgb(1.0, 0.65, 0.2),
+];
+const DEAD_END_COLOR: Color = Color::srgb(1.0, 0.15, 0.15);
+const LOOP_COLOR: Color = Color::srgb(1.0, 0.95, 0.2);
+
+/// `/trace`: trace the path from the belt under the crosshair
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceCommandEvent;
+
+/// Read-only snapshot of what items can move into
+#[derive(Debug, Default)]
+pub struct TraceWorld {
+    /// Belt position -> cells it outputs to, in the order they are tried
+    /// (disabled belts hold their items and output nowhere)
+    pub belts: HashMap<IVec3, Vec<IVec3>>,
+    /// Machines that take belt input
+    pub machines: HashSet<IVec3>,
+    /// (machine position, neighbor position) faces marked pass-through
+    pub pass_through: HashSet<(IVec3, IVec3)>,
+    /// Cells covered by a delivery platform
+    pub delivery: HashSet<IVec3>,
+}
+
+impl TraceWorld {
+    /// Where an item leaving `from` toward `cell` goes (None = nothing takes it)
+    fn target(&self, from: IVec3, cell: IVec3) -> Option<TraceTarget> {
+        if self.delivery.contains(&cell) {
+            Some(TraceTarget::Delivery(cell))
+        } else if self.belts.contains_key(&cell) {
+            Some(TraceTarget::Belt(cell))
+        } else if self.machines.contains(&cell) && !self.pass_through.contains(&(cell, from)) {
+            Some(TraceTarget::Machine(cell))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TraceTarget {
+    Belt(IVec3),
+    Machine(IVec3),
+    Delivery(IVec3),
+}
+
+/// How a traced branch ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEnd {
+    /// Goes into a machine at this position
+    Machine(IVec3),
+    /// Delivered on a platform at this position
+    Delivery(IVec3),
+    /// The last belt outputs into nothing (or is disabled)
+    DeadEnd(IVec3),
+    /// Comes back to this belt of its own path
+    Loop(IVec3),
+    /// Joins a belt another branch already traced
+    Joins(IVec3),
+    /// Splits at the last belt; the child branches continue
+    Split,
+    /// `TRACE_MAX_HOPS` reached
+    HopLimit,
+}
+
+/// One stretch of the traced route
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceBranch {
+    /// Branch this one splits off from (None for the first)
+    pub parent: Option<usize>,
+    /// Belts in travel order (empty when a splitter feeds its target directly)
+    pub path: Vec<IVec3>,
+    pub end: TraceEnd,
+}
+
+/// Trace the route from the belt at `start`
+///
+/// The first branch starts at `start`; every splitter with more than one
+/// connected output ends its branch with `TraceEnd::Split` and adds one child
+/// branch per output. Returns nothing if there's no belt at `start`.
+pub fn trace_conveyor_path(start: IVec3, world: &TraceWorld) -> Vec<TraceBranch> {
+    if !world.belts.contains_key(&start) {
+        return Vec::new();
+    }
+    let mut branches = vec![TraceBranch {
+        parent: None,
+        path: Vec::new(),
+        end: TraceEnd::HopLimit,
+    }];
+    let mut pending = vec![(0, start)];
+    let mut visited: HashSet<IVec3> = HashSet::new();
+
+    while let Some((index, mut pos)) = pending.pop() {
+        let end = loop {
+            if on_route(&branches, index, pos) {
+                break TraceEnd::Loop(pos);
+            }
+            if visited.contains(&pos) {
+                break TraceEnd::Joins(pos);
+            }
+            if visited.len() >= TRACE_MAX_HOPS {
+                break TraceEnd::HopLimit;
+            }
+            visited.insert(pos);
+            branches[index].path.push(pos);
+
+            let targets: Vec<TraceTarget> = world.belts[&pos]
+                .iter()
+                .filter_map(|&cell| world.target(pos, cell))
+                .collect();
+            match targets.as_slice() {
+                [] => break TraceEnd::DeadEnd(pos),
+                [TraceTarget::Belt(next)] => pos = *next,
+                [TraceTarget::Machine(at)] => break TraceEnd::Machine(*at),
+                [TraceTarget::Delivery(at)] => break TraceEnd::Delivery(*at),
+                _ => {
+                    for &target in &targets {
+                        let child = branches.len();
+                        let end = match target {
+                            TraceTarget::Belt(next) => {
+                                pending.push((child, next));
+                                TraceEnd::HopLimit
+                            }
+                            TraceTarget::Machine(at) => TraceEnd::Machine(at),
+                            TraceTarget::Delivery(at) => TraceEnd::Delivery(at),
+                        };
+                        branches.push(TraceBranch {
+                            parent: Some(index),
+                            path: Vec::new(),
+                            end,
+                        });
+                    }
+                    break TraceEnd::Split;
+                }
+            }
+        };
+        branches[index].end = end;
+    }
+    branches
+}
+
+/// Whether `pos` is already on branch `index` or one of its ancestors
+fn on_route(branches: &[TraceBranch], index: usize, pos: IVec3) -> bool {
+    let mut current = Some(index);
+    while let Some(i) = current {
+        if branches[i].path.contains(&pos) {
+            return true;
+        }
+        current = branches[i].parent;
+    }
+    false
+}
+
+/// Points to draw for a branch: where it leaves its parent, its belts and its target
+fn branch_points(branches: &[TraceBranch], index: usize) -> Vec<IVec3> {
+    let branch = &branches[index];
+    let mut points: Vec<IVec3> = branch
+        .parent
+        .and_then(|parent| branches[parent].path.last().copied())
+        .into_iter()
+        .chain(branch.path.iter().copied())
+        .collect();
+    match branch.end {
+        TraceEnd::Machine(at)
+        | TraceEnd::Delivery(at)
+        | TraceEnd::Loop(at)
+        | TraceEnd::Joins(at) => points.push(at),
+        TraceEnd::DeadEnd(_) | TraceEnd::Split | TraceEnd::HopLimit => {}
+    }
+    points
+}
+
+/// Cells a belt hands items to, in the order `conveyor_transfer` tries them
+fn belt_outputs(conveyor: &Conveyor) -> Vec<IVec3> {
+    if !conveyor.enabled {
+        Vec::new()
+    } else if conveyor.shape == ConveyorShape::Splitter {
+        conveyor.get_splitter_outputs().to_vec()
+    } else {
+        vec![conveyor.position + conveyor.output_direction.to_ivec3()]
+    }
+}
+
+/// A path marker that disappears after a while
+#[derive(Component)]
+pub struct PathTraceMarker {
+    /// Seconds left
+    pub remaining: f32,
+}
+
+/// Trace from the targeted belt and draw the result
+#[allow(clippy::too_many_arguments)]
+pub fn handle_trace_command(
+    mut commands: Commands,
+    mut events: MessageReader<TraceCommandEvent>,
+    target: Res<TargetBlock>,
+    conveyors: Query<&Conveyor>,
+    machines: Query<&Machine>,
+    platforms: Query<&DeliveryPlatform>,
+    old_markers: Query<Entity, With<PathTraceMarker>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    let start = target
+        .machine_target
+        .and_then(|entity| conveyors.get(entity).ok())
+        .or_else(|| {
+            let pos = target.break_target?;
+            conveyors.iter().find(|c| c.position == pos)
+        })
+        .map(|c| c.position);
+    let Some(start) = start else {
+        info!("Usage: /trace (look at a conveyor)");
+        return;
+    };
+
+    let mut world = TraceWorld::default();
+    for conveyor in conveyors.iter() {
+        let outputs = belt_outputs(conveyor);
+        for &cell in &outputs {
+            if platforms.iter().any(|p| p.contains(cell)) {
+                world.delivery.insert(cell);
+            }
+        }
+        world.belts.insert(conveyor.position, outputs);
+    }
+    for machine in machines.iter() {
+        for side in &machine.pass_through_sides {
+            world
+                .pass_through
+                .insert((machine.position, machine.position + side.to_ivec3()));
+        }
+        if takes_belt_input(machine) {
+            world.machines.insert(machine.position);
+        }
+    }
+
+    let branches = trace_conveyor_path(start, &world);
+    info!(
+        category = "LOGISTICS",
+        start = ?start,
+        branches = branches.len(),
+        ends = ?branches.iter().map(|b| b.end).collect::<Vec<_>>(),
+        "Traced conveyor path"
+    );
+
+    // A new trace replaces the previous one
+    for entity in old_markers.iter() {
+        commands.entity(entity).despawn();
+    }
+    let dot = meshes.add(Sphere::new(MARKER_RADIUS));
+    let ring = meshes.add(Torus::new(0.15, 0.25));
+    let glow = |materials: &mut Assets<StandardMaterial>, color: Color| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            emissive: LinearRgba::from(color) * 4.0,
+            unlit: true,
+            ..default()
+        })
+    };
+    let mut spawn_marker = |mesh: &Handle<Mesh>, material: Handle<StandardMaterial>, at: Vec3| {
+        commands.spawn((
+            PathTraceMarker {
+                remaining: TRACE_DISPLAY_SECS,
+            },
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(at),
+        ));
+    };
+    let marker_pos = |cell: IVec3| cell.as_vec3() + Vec3::new(0.5, MARKER_HEIGHT, 0.5);
+
+    for (index, branch) in branches.iter().enumerate() {
+        let material = glow(&mut materials, BRANCH_COLORS[index % BRANCH_COLORS.len()]);
+        let points = branch_points(&branches, index);
+        for (i, &cell) in points.iter().enumerate() {
+            spawn_marker(&dot, material.clone(), marker_pos(cell));
+            if let Some(&next) = points.get(i + 1) {
+                for step in 1..=MARKERS_BETWEEN {
+                    let t = step as f32 / (MARKERS_BETWEEN + 1) as f32;
+                    let at = marker_pos(cell).lerp(marker_pos(next), t);
+                    spawn_marker(&dot, material.clone(), at);
+                }
+            }
+        }
+        match branch.end {
+            TraceEnd::DeadEnd(at) => {
+                let red = glow(&mut materials, DEAD_END_COLOR);
+                spawn_marker(&ring, red, marker_pos(at) + Vec3::Y * 0.2);
+            }
+            TraceEnd::HopLimit => {
+                if let Some(&at) = branch.path.last() {
+                    let red = glow(&mut materials, DEAD_END_COLOR);
+                    spawn_marker(&ring, red, marker_pos(at) + Vec3::Y * 0.2);
+                }
+            }
+            TraceEnd::Loop(at) => {
+                let yellow = glow(&mut materials, LOOP_COLOR);
+                spawn_marker(&ring, yellow, marker_pos(at) + Vec3::Y * 0.2);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Remove path markers once their time is up
+pub fn expire_path_trace_markers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut markers: Query<(Entity, &mut PathTraceMarker)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut marker) in markers.iter_mut() {
+        marker.remaining -= delta;
+        if marker.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// `/trace` handling and marker expiry
+pub struct PathTracePlugin;
+
+impl Plugin for PathTracePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TraceCommandEvent>().add_systems(
+            Update,
+            (handle_trace_command, expire_path_trace_markers).in_set(InGameSet),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Belt at `pos` outputting one cell toward `dir`
+    fn belt(world: &mut TraceWorld, pos: IVec3, dir: IVec3) {
+        world.belts.insert(pos, vec![pos + dir]);
+    }
+
+    #[test]
+    fn test_trace_straight_run_into_machine() {
+        let mut world = TraceWorld::default();
+        for x in 0..4 {
+            belt(&mut world, IVec3::new(x, 0, 0), IVec3::X);
+        }
+        world.machines.insert(IVec3::new(4, 0, 0));
+
+        let branches = trace_conveyor_path(IVec3::ZERO, &world);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].path.len(), 4);
+        assert_eq!(branches[0].end, TraceEnd::Machine(IVec3::new(4, 0, 0)));
+
+        // A pass-through face turns it into a dead end
+        world
+            .pass_through
+            .insert((IVec3::new(4, 0, 0), IVec3::new(3, 0, 0)));
+        let branches = trace_conveyor_path(IVec3::ZERO, &world);
+        assert_eq!(branches[0].end, TraceEnd::DeadEnd(IVec3::new(3, 0, 0)));
+    }
+
+    #[test]
+    fn test_trace_splitter_fans_out() {
+        let mut world = TraceWorld::default();
+        // Splitter at the origin facing +X: front +X, left -Z, right +Z
+        let splitter = IVec3::ZERO;
+        world.belts.insert(
+            splitter,
+            vec![
+                IVec3::new(1, 0, 0),
+                IVec3::new(0, 0, -1),
+                IVec3::new(0, 0, 1),
+            ],
+        );
+        // Front: two belts into a platform
+        belt(&mut world, IVec3::new(1, 0, 0), IVec3::X);
+        belt(&mut world, IVec3::new(2, 0, 0), IVec3::X);
+        world.delivery.insert(IVec3::new(3, 0, 0));
+        // Left: straight into a machine
+        world.machines.insert(IVec3::new(0, 0, -1));
+        // Right: a belt going nowhere
+        belt(&mut world, IVec3::new(0, 0, 1), IVec3::Z);
+
+        let branches = trace_conveyor_path(splitter, &world);
+        assert_eq!(branches.len(), 4);
+        assert_eq!(branches[0].end, TraceEnd::Split);
+        let mut ends: Vec<TraceEnd> = branches[1..].iter().map(|b| b.end).collect();
+        ends.sort_by_key(|end| format!("{end:?}"));
+        assert_eq!(
+            ends,
+            vec![
+                TraceEnd::DeadEnd(IVec3::new(0, 0, 1)),
+                TraceEnd::Delivery(IVec3::new(3, 0, 0)),
+                TraceEnd::Machine(IVec3::new(0, 0, -1)),
+            ]
+        );
+        assert!(branches[1..].iter().all(|b| b.parent == Some(0)));
+        // The drawn line of a child starts at the splitter
+        assert_eq!(branch_points(&branches, 1)[0], splitter);
+    }
+
+    #[test]
+    fn test_trace_detects_loop() {
+        let mut world = TraceWorld::default();
+        // 2x2 circle: east, south, west, north
+        belt(&mut world, IVec3::new(0, 0, 0), IVec3::X);
+        belt(&mut world, IVec3::new(1, 0, 0), IVec3::Z);
+        belt(&mut world, IVec3::new(1, 0, 1), IVec3::NEG_X);
+        belt(&mut world, IVec3::new(0, 0, 1), IVec3::NEG_Z);
+
+        let branches = trace_conveyor_path(IVec3::new(1, 0, 0), &world);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].path.len(), 4);
+        assert_eq!(branches[0].end, TraceEnd::Loop(IVec3::new(1, 0, 0)));
+    }
+
+    #[test]
+    fn test_trace_stops_at_hop_limit() {
+        let mut world = TraceWorld::default();
+        for x in 0..(TRACE_MAX_HOPS as i32 + 10) {
+            belt(&mut world, IVec3::new(x, 0, 0), IVec3::X);
+        }
+        let branches = trace_conveyor_path(IVec3::ZERO, &world);
+        assert_eq!(branches[0].path.len(), TRACE_MAX_HOPS);
+        assert_eq!(branches[0].end, TraceEnd::HopLimit);
+    }
+}
@@ -0,0 +1,475 @@
+//! Quest requirement holograms over the delivery platform
+//!
+//! Each required item of the current main quest floats over the local
+//! platform's north edge as a translucent cube (item texture and color) with a
+//! "delivered/required" label under it:
+//!
+//! - Cubes are children of the platform entity, so they despawn with it
+//! - Labels are UI text nodes projected through the player camera (as for
+//!   display panels); a label whose cube is gone is despawned
+//! - The row is rebuilt only when the quest or its state changes; counts
+//!   refresh when the platform inventory changes
+//! - A requirement turns green and bounces once when it is fully delivered
+//! - While rewards are waiting the row is replaced by one "[Q]" hologram
+
+use bevy::ecs::system::SystemParam;
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+
+use crate::components::{CurrentQuest, DeliveryPlatform, GameFont, ItemSprites, PlayerCamera};
+use crate::core::ItemId;
+use crate::main_menu::InGameSet;
+use crate::player::{LocalPlatform, PlatformInventory};
+use crate::setup::ui::{text_font, TEXT_BODY};
+use crate::systems::quest::{main_quests_finished, QuestCache};
+use crate::{TutorialProgress, BLOCK_SIZE};
+
+/// Height of the holograms over the platform surface
+pub const HOLOGRAM_HOVER_HEIGHT: f32 = 1.6;
+
+/// Preferred distance between hologram centers
+pub const HOLOGRAM_SPACING: f32 = 1.25;
+
+/// Edge length of a hologram cube
+const HOLOGRAM_CUBE_SIZE: f32 = 0.5;
+
+/// Labels farther than this from the camera are hidden
+const HOLOGRAM_VIEW_DISTANCE: f32 = 32.0;
+
+/// Label node width (text is centered in it)
+const LABEL_WIDTH: f32 = 200.0;
+
+/// Length of the completion bounce (seconds) and its height
+const BOUNCE_SECS: f32 = 0.6;
+const BOUNCE_HEIGHT: f32 = 0.4;
+
+const PENDING_COLOR: Color = Color::WHITE;
+const COMPLETE_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+const CLAIM_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// What the platform shows for the current quest
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum HologramContent {
+    /// Tutorial running or every quest claimed
+    #[default]
+    Nothing,
+    /// Required items of the current quest
+    Requirements(Vec<(ItemId, u32)>),
+    /// Quest delivered, rewards waiting for Q
+    ClaimRewards,
+}
+
+/// Holograms the current quest state calls for
+pub fn hologram_content(
+    current_quest: &CurrentQuest,
+    quest_cache: &QuestCache,
+    tutorial_done: bool,
+) -> HologramContent {
+    if !tutorial_done || main_quests_finished(current_quest, quest_cache.main_quests.len()) {
+        return HologramContent::Nothing;
+    }
+    if current_quest.completed && !current_quest.rewards_claimed {
+        return HologramContent::ClaimRewards;
+    }
+    match quest_cache.main_quests.get(current_quest.index) {
+        Some(quest) => HologramContent::Requirements(quest.required_items.clone()),
+        None => HologramContent::Nothing,
+    }
+}
+
+/// World positions of `count` holograms in a row along the platform's north edge
+///
+/// The row is centered on the edge and squeezed to stay within the platform.
+pub fn hologram_positions(platform: &DeliveryPlatform, count: usize) -> Vec<Vec3> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let width = platform.size as f32 * BLOCK_SIZE;
+    let spacing = if count > 1 {
+        HOLOGRAM_SPACING.min((width - BLOCK_SIZE) / (count - 1) as f32)
+    } else {
+        0.0
+    };
+    let origin = platform.position.as_vec3() * BLOCK_SIZE;
+    let center_x = origin.x + width / 2.0;
+    let y = origin.y + HOLOGRAM_HOVER_HEIGHT;
+    // North is -Z: the row sits over the first row of cells
+    let z = origin.z + BLOCK_SIZE * 0.5;
+    let first = center_x - spacing * (count - 1) as f32 / 2.0;
+    (0..count)
+        .map(|i| Vec3::new(first + spacing * i as f32, y, z))
+        .collect()
+}
+
+/// "delivered/required" label text (delivered is capped at required)
+pub fn requirement_label(delivered: u32, required: u32) -> String {
+    format!("{}/{}", delivered.min(required), required)
+}
+
+/// A hologram cube (child of the platform)
+#[derive(Component, Debug)]
+pub struct QuestHologram {
+    /// Required item and count (None for the claim hologram)
+    pub requirement: Option<(ItemId, u32)>,
+    /// Label text node
+    pub label: Entity,
+    /// Whether the requirement is fully delivered
+    pub complete: bool,
+    /// Seconds into the completion bounce (None when not bouncing)
+    pub bounce: Option<f32>,
+    /// Resting position relative to the platform
+    pub rest: Vec3,
+}
+
+/// Label text node of a hologram
+#[derive(Component, Debug)]
+pub struct QuestHologramLabel {
+    pub hologram: Entity,
+}
+
+/// Platform and content the current holograms were built for
+#[derive(Resource, Debug, Default)]
+pub struct QuestHologramState {
+    pub platform: Option<Entity>,
+    pub content: HologramContent,
+}
+
+/// Assets for spawning holograms (reduces parameter count)
+#[derive(SystemParam)]
+pub struct HologramAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    item_sprites: Option<Res<'w, ItemSprites>>,
+    game_font: Res<'w, GameFont>,
+}
+
+impl HologramAssets<'_> {
+    fn material(&mut self, color: Color, item: Option<ItemId>) -> Handle<StandardMaterial> {
+        let texture = item.and_then(|item| self.item_sprites.as_ref()?.get_id(item));
+        self.materials.add(StandardMaterial {
+            base_color: color.with_alpha(0.6),
+            base_color_texture: texture,
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })
+    }
+}
+
+/// Rebuild the holograms when the quest, its state or the local platform changes
+#[allow(clippy::too_many_arguments)]
+pub fn sync_quest_holograms(
+    mut commands: Commands,
+    mut state: ResMut<QuestHologramState>,
+    current_quest: Res<CurrentQuest>,
+    quest_cache: Res<QuestCache>,
+    tutorial: Res<TutorialProgress>,
+    local_platform: Option<Res<LocalPlatform>>,
+    platforms: Query<(&DeliveryPlatform, &Transform)>,
+    holograms: Query<(Entity, &QuestHologram)>,
+    mut assets: HologramAssets,
+) {
+    let platform = local_platform
+        .map(|lp| lp.0)
+        .filter(|entity| platforms.contains(*entity));
+    let content = hologram_content(&current_quest, &quest_cache, tutorial.completed);
+    if state.platform == platform && state.content == content {
+        return;
+    }
+
+    for (entity, hologram) in holograms.iter() {
+        commands.entity(entity).despawn();
+        commands.entity(hologram.label).despawn();
+    }
+    state.platform = platform;
+    state.content = content.clone();
+
+    let Some((platform_entity, (platform, transform))) =
+        platform.and_then(|e| Some((e, platforms.get(e).ok()?)))
+    else {
+        return;
+    };
+    let entries: Vec<(Option<(ItemId, u32)>, Color)> = match &content {
+        HologramContent::Nothing => return,
+        HologramContent::Requirements(items) => items
+            .iter()
+            .map(|&(item, count)| (Some((item, count)), item.color()))
+            .collect(),
+        HologramContent::ClaimRewards => vec![(None, CLAIM_COLOR)],
+    };
+
+    let mesh = assets.meshes.add(Cuboid::from_length(HOLOGRAM_CUBE_SIZE));
+    let positions = hologram_positions(platform, entries.len());
+    for ((requirement, color), position) in entries.into_iter().zip(positions) {
+        let (text, text_color) = match requirement {
+            Some((_, required)) => (requirement_label(0, required), PENDING_COLOR),
+            None => ("[Q] 報酬を受け取る".to_string(), CLAIM_COLOR),
+        };
+        let label = commands
+            .spawn((
+                Text::new(text),
+                text_font(&assets.game_font.0, TEXT_BODY),
+                TextColor(text_color),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(LABEL_WIDTH),
+                    ..default()
+                },
+                Visibility::Hidden,
+            ))
+            .id();
+        let rest = position - transform.translation;
+        let material = assets.material(color, requirement.map(|(item, _)| item));
+        let hologram = commands
+            .spawn((
+                QuestHologram {
+                    requirement,
+                    label,
+                    complete: false,
+                    bounce: None,
+                    rest,
+                },
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material),
+                Transform::from_translation(rest),
+                NotShadowCaster,
+                ChildOf(platform_entity),
+            ))
+            .id();
+        commands
+            .entity(label)
+            .insert(QuestHologramLabel { hologram });
+    }
+}
+
+/// Refresh counts when the platform inventory changes or the row was rebuilt
+pub fn update_quest_hologram_counts(
+    state: Res<QuestHologramState>,
+    inventories: Query<Ref<PlatformInventory>>,
+    mut holograms: Query<(&mut QuestHologram, &MeshMaterial3d<StandardMaterial>)>,
+    mut labels: Query<(&mut Text, &mut TextColor), With<QuestHologramLabel>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(inventory) = state.platform.and_then(|e| inventories.get(e).ok()) else {
+        return;
+    };
+    for (mut hologram, material) in holograms.iter_mut() {
+        let added = hologram.is_added();
+        if !inventory.is_changed() && !added {
+            continue;
+        }
+        let Some((item, required)) = hologram.requirement else {
+            continue;
+        };
+        let delivered = inventory.get_count_by_id(item);
+        if let Ok((mut text, mut color)) = labels.get_mut(hologram.label) {
+            let content = requirement_label(delivered, required);
+            if **text != content {
+                **text = content;
+            }
+            color.0 = if delivered >= required {
+                COMPLETE_COLOR
+            } else {
+                PENDING_COLOR
+            };
+        }
+
+        let complete = delivered >= required;
+        if complete != hologram.complete {
+            hologram.complete = complete;
+            // Bounce once on completion, not when the row is rebuilt already complete
+            if complete && !added {
+                hologram.bounce = Some(0.0);
+            }
+            if let Some(material) = materials.get_mut(&material.0) {
+                let color = if complete {
+                    COMPLETE_COLOR
+                } else {
+                    item.color()
+                };
+                material.base_color = color.with_alpha(0.6);
+            }
+        }
+    }
+}
+
+/// Slow spin and bob, plus the completion bounce
+pub fn animate_quest_holograms(
+    time: Res<Time>,
+    mut holograms: Query<(&mut QuestHologram, &mut Transform)>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (mut hologram, mut transform) in holograms.iter_mut() {
+        let phase = hologram.rest.x;
+        let mut offset = (elapsed * 2.0 + phase).sin() * 0.05;
+        if let Some(t) = hologram.bounce {
+            let t = t + time.delta_secs();
+            offset += (t / BOUNCE_SECS * std::f32::consts::PI).sin().max(0.0) * BOUNCE_HEIGHT;
+            hologram.bounce = (t < BOUNCE_SECS).then_some(t);
+        }
+        transform.translation = hologram.rest + Vec3::Y * offset;
+        transform.rotation = Quat::from_rotation_y(elapsed * 0.8 + phase);
+    }
+}
+
+/// Keep each label under its cube; despawn labels whose cube is gone
+pub fn position_quest_hologram_labels(
+    mut commands: Commands,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    holograms: Query<&GlobalTransform, With<QuestHologram>>,
+    mut labels: Query<(Entity, &QuestHologramLabel, &mut Node, &mut Visibility)>,
+) {
+    let camera = camera_query.single().ok();
+    for (entity, label, mut node, mut visibility) in labels.iter_mut() {
+        let Ok(hologram) = holograms.get(label.hologram) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let Some((camera, camera_transform)) = camera else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let anchor = hologram.translation() - Vec3::Y * HOLOGRAM_CUBE_SIZE;
+        let in_range = camera_transform.translation().distance(anchor) < HOLOGRAM_VIEW_DISTANCE;
+        let Ok(screen) = camera.world_to_viewport(camera_transform, anchor) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if !in_range {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        node.left = Val::Px(screen.x - LABEL_WIDTH / 2.0);
+        node.top = Val::Px(screen.y);
+        *visibility = Visibility::Visible;
+    }
+}
+
+/// Plugin for the quest requirement holograms
+pub struct QuestHologramPlugin;
+
+impl Plugin for QuestHologramPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuestHologramState>().add_systems(
+            Update,
+            (
+                sync_quest_holograms,
+                update_quest_hologram_counts,
+                animate_quest_holograms,
+                position_quest_hologram_labels,
+            )
+                .chain()
+                .in_set(InGameSet),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PLATFORM_SIZE;
+
+    #[test]
+    fn test_hologram_positions_along_north_edge() {
+        let platform = DeliveryPlatform::new(IVec3::new(20, 8, 10));
+        assert!(hologram_positions(&platform, 0).is_empty());
+
+        // One hologram: over the middle of the north edge
+        let center_x = (20.0 + PLATFORM_SIZE as f32 / 2.0) * BLOCK_SIZE;
+        let one = hologram_positions(&platform, 1);
+        assert_eq!(
+            one,
+            vec![Vec3::new(center_x, 8.0 + HOLOGRAM_HOVER_HEIGHT, 10.5)]
+        );
+
+        // Three: evenly spaced and centered
+        let three = hologram_positions(&platform, 3);
+        assert_eq!(three[1].x, center_x);
+        assert_eq!(three[2].x - three[1].x, HOLOGRAM_SPACING);
+        assert_eq!(three[1].x - three[0].x, HOLOGRAM_SPACING);
+        assert!(three.iter().all(|p| p.z == 10.5));
+
+        // Many on a small pad: squeezed inside the platform
+        let pad = DeliveryPlatform::with_size(IVec3::ZERO, 3);
+        let many = hologram_positions(&pad, 6);
+        assert!(many.first().unwrap().x >= 0.0);
+        assert!(many.last().unwrap().x <= 3.0 * BLOCK_SIZE);
+        assert!((many[1].x - many[0].x) < HOLOGRAM_SPACING);
+    }
+
+    fn hologram_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .insert_resource(GameFont(Handle::default()))
+            .insert_resource(TutorialProgress {
+                completed: true,
+                ..default()
+            })
+            .init_resource::<CurrentQuest>()
+            .init_resource::<QuestCache>()
+            .init_resource::<QuestHologramState>()
+            .add_systems(
+                Update,
+                (sync_quest_holograms, update_quest_hologram_counts).chain(),
+            );
+        let platform = app
+            .world_mut()
+            .spawn((
+                DeliveryPlatform::new(IVec3::ZERO),
+                Transform::default(),
+                PlatformInventory::new(),
+            ))
+            .id();
+        app.insert_resource(LocalPlatform(platform));
+        (app, platform)
+    }
+
+    fn labels(app: &mut App) -> Vec<String> {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&Text, With<QuestHologramLabel>>();
+        query.iter(app.world()).map(|t| t.0.clone()).collect()
+    }
+
+    #[test]
+    fn test_holograms_follow_quest_progress() {
+        let (mut app, platform) = hologram_app();
+        app.update();
+
+        let quest = &app.world().resource::<QuestCache>().main_quests[0];
+        let requirements = quest.required_items.clone();
+        let (item, required) = requirements[0];
+        assert_eq!(labels(&mut app).len(), requirements.len());
+        assert!(labels(&mut app).contains(&requirement_label(0, required)));
+        let children = app.world().get::<Children>(platform).unwrap().len();
+        assert_eq!(children, requirements.len());
+
+        // Deliveries update the count without rebuilding; completion starts the bounce
+        app.world_mut()
+            .get_mut::<PlatformInventory>(platform)
+            .unwrap()
+            .add_item_by_id(item, required);
+        app.update();
+        assert!(labels(&mut app).contains(&requirement_label(required, required)));
+        let mut query = app.world_mut().query::<&QuestHologram>();
+        let done = query
+            .iter(app.world())
+            .find(|h| h.requirement == Some((item, required)))
+            .unwrap();
+        assert!(done.complete);
+        assert_eq!(done.bounce, Some(0.0));
+
+        // Rewards waiting: a single claim hologram
+        app.world_mut().resource_mut::<CurrentQuest>().completed = true;
+        app.update();
+        assert_eq!(labels(&mut app), vec!["[Q] 報酬を受け取る".to_string()]);
+
+        // Despawning the platform takes the cubes with it
+        app.world_mut().entity_mut(platform).despawn();
+        let mut query = app.world_mut().query::<&QuestHologram>();
+        assert_eq!(query.iter(app.world()).count(), 0);
+    }
+}
@@ -0,0 +1,398 @@
+//! Timed delivery contracts
+//!
+//! Once the main quest line is finished, the platform offers one contract at
+//! a time: "deliver N of an item within M minutes". Finishing early pays a
+//! reward multiplier; a contract that runs out simply expires, and the next
+//! one is offered after a cooldown.
+//!
+//! - Requirements scale with `ThroughputTracker`'s recent delivery rates
+//!   (and the streak of finished contracts), so a contract is always a bit
+//!   below what the factory already delivers
+//! - Items come from `GameRng`, so a seed gives the same contracts
+//! - Deadlines count simulation ticks, so `/time` and `/skip-night` don't
+//!   move them; contracts and streaks are saved
+
+use bevy::prelude::*;
+
+use crate::components::CurrentQuest;
+use crate::constants::SIMULATION_HZ;
+use crate::core::{items, ItemId};
+use crate::events::game_events::ItemDelivered;
+use crate::main_menu::InGameSet;
+use crate::player::LocalPlatformInventory;
+use crate::rng::GameRng;
+use crate::statistics::ThroughputTracker;
+use crate::systems::{main_quests_finished, QuestCache};
+
+/// Simulation ticks per minute
+pub const TICKS_PER_MIN: u64 = 60 * SIMULATION_HZ as u64;
+
+/// Time limits a contract can have (minutes)
+pub const CONTRACT_DURATIONS_MIN: [u64; 3] = [5, 10, 15];
+
+/// Wait after a contract expires before the next one is offered (ticks)
+pub const CONTRACT_COOLDOWN_TICKS: u64 = 2 * TICKS_PER_MIN;
+
+/// Smallest amount a contract asks for
+pub const CONTRACT_MIN_AMOUNT: u32 = 10;
+
+/// Rate assumed for the starter items while nothing has been delivered (items/minute)
+const FALLBACK_RATE_PER_MIN: f32 = 4.0;
+
+/// Share of the recent rate asked for, and the increase per streak step
+const BASE_DIFFICULTY: f32 = 0.6;
+const STREAK_DIFFICULTY: f32 = 0.05;
+/// Streak steps that still raise the difficulty
+const MAX_STREAK_STEPS: u32 = 6;
+
+/// Items offered when nothing has been delivered recently
+fn fallback_items() -> [ItemId; 2] {
+    [items::iron_ingot(), items::copper_ingot()]
+}
+
+/// Rewards a contract can pay (item, count before the multiplier)
+fn contract_rewards() -> [(ItemId, u32); 4] {
+    [
+        (items::conveyor_block(), 32),
+        (items::miner_block(), 2),
+        (items::furnace_block(), 2),
+        (items::crusher_block(), 1),
+    ]
+}
+
+/// One timed contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedContract {
+    pub item: ItemId,
+    pub amount: u32,
+    /// Items of `item` delivered since the contract started
+    pub delivered: u32,
+    /// Time limit (ticks)
+    pub duration_ticks: u64,
+    /// Ticks since the contract started
+    pub elapsed_ticks: u64,
+    /// Reward before the early-completion multiplier
+    pub reward: (ItemId, u32),
+}
+
+impl TimedContract {
+    pub fn is_complete(&self) -> bool {
+        self.delivered >= self.amount
+    }
+
+    pub fn is_expired(&self) -> bool {
+        !self.is_complete() && self.elapsed_ticks >= self.duration_ticks
+    }
+
+    pub fn remaining_ticks(&self) -> u64 {
+        self.duration_ticks.saturating_sub(self.elapsed_ticks)
+    }
+
+    /// Reward multiplier if the contract were finished now
+    ///
+    /// x2 within the first half of the time limit, x1.5 within three quarters.
+    pub fn reward_multiplier(&self) -> f32 {
+        let used = self.elapsed_ticks as f32 / self.duration_ticks.max(1) as f32;
+        if used <= 0.5 {
+            2.0
+        } else if used <= 0.75 {
+            1.5
+        } else {
+            1.0
+        }
+    }
+
+    /// Reward paid if the contract were finished now
+    pub fn reward_now(&self) -> (ItemId, u32) {
+        let (item, count) = self.reward;
+        (
+            item,
+            (count as f32 * self.reward_multiplier()).round() as u32,
+        )
+    }
+}
+
+/// What a contract tick did
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractEvent {
+    Offered,
+    /// Finished; carries the reward to pay out
+    Completed((ItemId, u32)),
+    Expired,
+}
+
+/// Contract state of the local player
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct TimedContracts {
+    pub active: Option<TimedContract>,
+    /// Ticks until the next contract is offered
+    pub cooldown_ticks: u64,
+    /// Contracts finished in a row (reset when one expires)
+    pub streak: u32,
+    pub completed: u32,
+    pub expired: u32,
+}
+
+impl TimedContracts {
+    /// Count a delivery toward the active contract
+    pub fn record(&mut self, item: ItemId, count: u32) {
+        if let Some(contract) = self.active.as_mut().filter(|c| c.item == item) {
+            contract.delivered += count;
+        }
+    }
+
+    /// Advance one simulation tick, offering a new contract from `rates` when due
+    pub fn tick(&mut self, rng: &mut GameRng, rates: &[(ItemId, f32)]) -> Option<ContractEvent> {
+        let Some(contract) = self.active.as_mut() else {
+            if self.cooldown_ticks > 0 {
+                self.cooldown_ticks -= 1;
+                return None;
+            }
+            self.active = Some(generate_contract(rng, rates, self.streak));
+            return Some(ContractEvent::Offered);
+        };
+
+        if contract.is_complete() {
+            let reward = contract.reward_now();
+            self.active = None;
+            self.streak += 1;
+            self.completed += 1;
+            return Some(ContractEvent::Completed(reward));
+        }
+        contract.elapsed_ticks += 1;
+        if contract.is_expired() {
+            self.active = None;
+            self.streak = 0;
+            self.expired += 1;
+            self.cooldown_ticks = CONTRACT_COOLDOWN_TICKS;
+            return Some(ContractEvent::Expired);
+        }
+        None
+    }
+}
+
+/// Pick the next contract
+///
+/// The item is drawn from the recently delivered ones (`rates`, items/minute;
+/// the starter ingots if there are none). The amount is the item's recent
+/// rate over the time limit, scaled by a difficulty that grows with the
+/// streak but stays below 1, rounded up to a multiple of 5.
+pub fn generate_contract(rng: &mut GameRng, rates: &[(ItemId, f32)], streak: u32) -> TimedContract {
+    let candidates: Vec<(ItemId, f32)> = {
+        let delivered: Vec<(ItemId, f32)> = rates
+            .iter()
+            .copied()
+            .filter(|(_, rate)| *rate > 0.0)
+            .collect();
+        if delivered.is_empty() {
+            fallback_items()
+                .into_iter()
+                .map(|item| (item, FALLBACK_RATE_PER_MIN))
+                .collect()
+        } else {
+            delivered
+        }
+    };
+    let (item, rate) = candidates[rng.next_u32(candidates.len() as u32) as usize];
+    let minutes =
+        CONTRACT_DURATIONS_MIN[rng.next_u32(CONTRACT_DURATIONS_MIN.len() as u32) as usize];
+    let rewards = contract_rewards();
+    let reward = rewards[rng.next_u32(rewards.len() as u32) as usize];
+
+    let difficulty = BASE_DIFFICULTY + STREAK_DIFFICULTY * streak.min(MAX_STREAK_STEPS) as f32;
+    let wanted = (rate * minutes as f32 * difficulty).ceil() as u32;
+    let amount = wanted.div_ceil(5).max(1) * 5;
+
+    TimedContract {
+        item,
+        amount: amount.max(CONTRACT_MIN_AMOUNT),
+        delivered: 0,
+        duration_ticks: minutes * TICKS_PER_MIN,
+        elapsed_ticks: 0,
+        reward,
+    }
+}
+
+/// "mm:ss" for a tick count
+pub fn format_ticks(ticks: u64) -> String {
+    let secs = ticks.div_ceil(SIMULATION_HZ as u64);
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Quest panel lines for the contract state
+pub fn contract_status_text(contracts: &TimedContracts) -> String {
+    match &contracts.active {
+        Some(contract) => format!(
+            "📦 納品契約: {} {}/{}\n残り {} (報酬 x{})\n連続達成: {}",
+            contract.item.name().unwrap_or("unknown"),
+            contract.delivered.min(contract.amount),
+            contract.amount,
+            format_ticks(contract.remaining_ticks()),
+            contract.reward_multiplier(),
+            contracts.streak,
+        ),
+        None => format!(
+            "次の納品契約まで {}",
+            format_ticks(contracts.cooldown_ticks)
+        ),
+    }
+}
+
+/// Count deliveries toward the active contract
+fn track_timed_contract_deliveries(
+    mut delivered: MessageReader<ItemDelivered>,
+    mut contracts: ResMut<TimedContracts>,
+) {
+    for event in delivered.read() {
+        contracts.record(event.item, event.count);
+    }
+}
+
+/// Advance contracts one simulation tick (only after the main quests)
+fn tick_timed_contracts(
+    current_quest: Res<CurrentQuest>,
+    quest_cache: Res<QuestCache>,
+    tracker: Res<ThroughputTracker>,
+    mut rng: ResMut<GameRng>,
+    mut contracts: ResMut<TimedContracts>,
+    mut platform_inventory: LocalPlatformInventory,
+) {
+    if !main_quests_finished(&current_quest, quest_cache.main_quests.len()) {
+        return;
+    }
+    match contracts.tick(&mut rng, &tracker.rates()) {
+        Some(ContractEvent::Offered) => {
+            if let Some(contract) = &contracts.active {
+                info!(
+                    item = ?contract.item.name(),
+                    amount = contract.amount,
+                    minutes = contract.duration_ticks / TICKS_PER_MIN,
+                    "Timed contract offered"
+                );
+            }
+        }
+        Some(ContractEvent::Completed((item, count))) => {
+            platform_inventory.add_item(item, count);
+            info!(reward = ?item.name(), count, streak = contracts.streak, "Timed contract completed");
+        }
+        Some(ContractEvent::Expired) => info!("Timed contract expired"),
+        None => {}
+    }
+}
+
+pub struct TimedContractPlugin;
+
+impl Plugin for TimedContractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimedContracts>()
+            .init_resource::<GameRng>()
+            .add_systems(FixedUpdate, tick_timed_contracts.in_set(InGameSet))
+            .add_systems(Update, track_timed_contract_deliveries.in_set(InGameSet));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{timed_contracts_from_save, timed_contracts_to_save};
+
+    #[test]
+    fn test_generator_scales_with_rate_and_streak() {
+        let copper = items::copper_ingot();
+
+        // Same seed, same contract
+        let first = generate_contract(&mut GameRng::new(7), &[(copper, 20.0)], 0);
+        assert_eq!(
+            first,
+            generate_contract(&mut GameRng::new(7), &[(copper, 20.0)], 0)
+        );
+        assert_eq!(first.item, copper);
+
+        let minutes = first.duration_ticks / TICKS_PER_MIN;
+        let recent = 20.0 * minutes as f32;
+        // Challenging but below what the factory delivers
+        assert!(first.amount as f32 >= recent * BASE_DIFFICULTY);
+        assert!((first.amount as f32) < recent);
+        assert_eq!(first.amount % 5, 0);
+
+        // A faster factory and a streak both raise the bar
+        let faster = generate_contract(&mut GameRng::new(7), &[(copper, 40.0)], 0);
+        assert!(faster.amount > first.amount);
+        let streak = generate_contract(&mut GameRng::new(7), &[(copper, 20.0)], 6);
+        assert!(streak.amount > first.amount);
+        assert!((streak.amount as f32) < recent);
+
+        // Nothing delivered yet: a small starter contract
+        let starter = generate_contract(&mut GameRng::new(7), &[], 0);
+        assert!(fallback_items().contains(&starter.item));
+        assert!(starter.amount >= CONTRACT_MIN_AMOUNT);
+    }
+
+    fn contract(amount: u32, minutes: u64) -> TimedContract {
+        TimedContract {
+            item: items::copper_ingot(),
+            amount,
+            delivered: 0,
+            duration_ticks: minutes * TICKS_PER_MIN,
+            elapsed_ticks: 0,
+            reward: (items::miner_block(), 2),
+        }
+    }
+
+    #[test]
+    fn test_early_completion_multiplier() {
+        let mut contracts = TimedContracts {
+            active: Some(contract(40, 10)),
+            ..default()
+        };
+        let mut rng = GameRng::new(1);
+        for _ in 0..2 * TICKS_PER_MIN {
+            contracts.tick(&mut rng, &[]);
+        }
+        contracts.record(items::iron_ingot(), 100);
+        assert_eq!(contracts.tick(&mut rng, &[]), None);
+        contracts.record(items::copper_ingot(), 40);
+
+        assert_eq!(
+            contracts.tick(&mut rng, &[]),
+            Some(ContractEvent::Completed((items::miner_block(), 4)))
+        );
+        assert_eq!(contracts.streak, 1);
+        // The next contract is offered right away
+        assert_eq!(contracts.tick(&mut rng, &[]), Some(ContractEvent::Offered));
+    }
+
+    #[test]
+    fn test_deadline_survives_save_load() {
+        let mut contracts = TimedContracts {
+            active: Some(contract(40, 5)),
+            streak: 3,
+            ..default()
+        };
+        let mut rng = GameRng::new(1);
+        for _ in 0..3 * TICKS_PER_MIN {
+            contracts.tick(&mut rng, &[]);
+        }
+        contracts.record(items::copper_ingot(), 12);
+
+        let json = serde_json::to_string(&timed_contracts_to_save(&contracts)).unwrap();
+        let mut loaded = timed_contracts_from_save(&serde_json::from_str(&json).unwrap());
+        assert_eq!(loaded, contracts);
+
+        // Exactly two minutes of ticks remain after loading
+        for _ in 0..2 * TICKS_PER_MIN - 1 {
+            assert_eq!(loaded.tick(&mut rng, &[]), None);
+        }
+        assert_eq!(loaded.tick(&mut rng, &[]), Some(ContractEvent::Expired));
+        assert_eq!(loaded.streak, 0);
+        assert_eq!(loaded.cooldown_ticks, CONTRACT_COOLDOWN_TICKS);
+
+        // The cooldown is saved too
+        let json = serde_json::to_string(&timed_contracts_to_save(&loaded)).unwrap();
+        let mut reloaded = timed_contracts_from_save(&serde_json::from_str(&json).unwrap());
+        for _ in 0..CONTRACT_COOLDOWN_TICKS {
+            assert_eq!(reloaded.tick(&mut rng, &[]), None);
+        }
+        assert_eq!(reloaded.tick(&mut rng, &[]), Some(ContractEvent::Offered));
+    }
+}
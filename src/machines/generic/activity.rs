@@ -0,0 +1,33 @@
+//! Utilization sampling for the machine UI sparkline
+
+use crate::components::{ActivityClock, Idle, Machine, MachineActivity};
+use crate::constants::SIMULATION_TICK_SECS;
+use bevy::prelude::*;
+
+/// Add this tick's working time to each machine and close a sample every ACTIVITY_SAMPLE_SECS
+///
+/// Runs in FixedUpdate after the machine and hopper ticks, so `status` says
+/// whether the machine progressed this tick. Sleeping machines (`Idle`) can't
+/// be working, so only awake ones are visited per tick; every machine takes
+/// part when a window closes, every 2 seconds.
+pub fn sample_machine_activity(
+    mut clock: ResMut<ActivityClock>,
+    mut queries: ParamSet<(
+        Query<(&Machine, &mut MachineActivity), Without<Idle>>,
+        Query<&mut MachineActivity>,
+    )>,
+) {
+    let carry = clock.advance(SIMULATION_TICK_SECS);
+
+    for (machine, mut activity) in queries.p0().iter_mut() {
+        if machine.status.is_working() {
+            activity.record(SIMULATION_TICK_SECS, carry.unwrap_or(0.0));
+        }
+    }
+
+    if carry.is_some() {
+        for mut activity in queries.p1().iter_mut() {
+            activity.close_sample();
+        }
+    }
+}
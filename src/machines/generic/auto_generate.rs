@@ -8,6 +8,7 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use super::output::try_output_to_conveyor;
+use super::status::miner_status;
 
 /// Tick for auto-generating machines (like Miner)
 /// Returns Some(output_item_id) when an item is produced
@@ -26,9 +27,25 @@ pub(super) fn tick_auto_generate(
         .map(|s| s.count < spec.buffer_size)
         .unwrap_or(false);
 
+    // Unmineable ground (ocean, lava, void) produces nothing, and neither does
+    // a miner without a drill that cuts something found here
+    let biome = biome_map.get_biome(machine.position);
+    let drill = machine.slots.drill;
+    let can_mine = biome_map.can_mine(machine.position)
+        && drill.is_some_and(|drill| {
+            biome_probabilities(biome)
+                .iter()
+                .any(|&(item_id, _)| drill.can_mine(item_id))
+        });
+    machine.status = miner_status(can_mine, !can_output);
+
     if !can_output {
         return None;
     }
+    if !can_mine {
+        try_output_to_conveyor(machine, conveyor_map, conveyor_query);
+        return None;
+    }
 
     // Progress mining
     machine.progress += delta / spec.process_time;
@@ -38,9 +55,10 @@ pub(super) fn tick_auto_generate(
         machine.progress = 0.0;
         machine.tick_count = machine.tick_count.wrapping_add(1);
 
-        // Determine what to mine based on biome
-        let biome = biome_map.get_biome(machine.position);
-        let mined_id = get_biome_output(biome, machine.tick_count);
+        // Determine what to mine based on biome and drill
+        let mined_id = get_biome_output_for(biome, machine.tick_count, |item_id| {
+            drill.is_some_and(|drill| drill.can_mine(item_id))
+        });
 
         // Add to output buffer
         if let Some(output) = machine.slots.outputs.first_mut() {
@@ -50,6 +68,21 @@ pub(super) fn tick_auto_generate(
                 produced = Some(mined_id);
             }
         }
+
+        // Every mined item wears the drill; a worn out drill is gone
+        if produced.is_some() {
+            if let Some(drill) = machine.slots.drill.as_mut() {
+                if drill.wear() {
+                    info!(
+                        category = "MACHINE",
+                        pos = ?machine.position,
+                        drill = ?drill.item_id.name(),
+                        "Miner drill broke"
+                    );
+                    machine.slots.drill = None;
+                }
+            }
+        }
     }
 
     // Try to output to conveyor
@@ -57,25 +90,46 @@ pub(super) fn tick_auto_generate(
     produced
 }
 
-/// Get mining output based on biome (returns ItemId)
-pub fn get_biome_output(biome: BiomeType, tick: u32) -> ItemId {
+/// Mining table (item, weight) of a biome
+fn biome_probabilities(biome: BiomeType) -> &'static [(ItemId, u32)] {
     use crate::game_spec::biome_mining_spec::*;
 
-    let probabilities: &[(ItemId, u32)] = match biome {
+    match biome {
         BiomeType::Iron => &IRON_BIOME,
         BiomeType::Copper => &COPPER_BIOME,
         BiomeType::Coal => &COAL_BIOME,
         BiomeType::Stone => &STONE_BIOME,
         BiomeType::Mixed => &MIXED_BIOME,
         BiomeType::Unmailable => &STONE_BIOME,
-    };
+    }
+}
+
+/// Get mining output based on biome (returns ItemId)
+pub fn get_biome_output(biome: BiomeType, tick: u32) -> ItemId {
+    get_biome_output_for(biome, tick, |_| true)
+}
+
+/// Get mining output among the items `can_mine` allows (the drill's tier)
+pub fn get_biome_output_for(
+    biome: BiomeType,
+    tick: u32,
+    can_mine: impl Fn(ItemId) -> bool,
+) -> ItemId {
+    let probabilities: Vec<(ItemId, u32)> = biome_probabilities(biome)
+        .iter()
+        .copied()
+        .filter(|&(item_id, _)| can_mine(item_id))
+        .collect();
 
     // Simple deterministic selection based on tick
     let total: u32 = probabilities.iter().map(|(_, p)| p).sum();
+    if total == 0 {
+        return items::stone();
+    }
     let roll = tick % total;
 
     let mut acc = 0;
-    for (item_id, prob) in probabilities {
+    for (item_id, prob) in &probabilities {
         acc += prob;
         if roll < acc {
             return *item_id;
@@ -1,25 +1,32 @@
 //! Cleanup and visual feedback systems
 
-use crate::components::{GenericMachineUI, InteractingMachine, Machine};
-use crate::systems::cursor;
+use crate::components::{
+    CursorController, DisabledTint, GenericMachineUI, InteractingMachine, Machine,
+    MachineOutputNotch, PassThroughStripe, PassThroughStripes, SlotRoute, SlotRouteDot,
+    SlotRouteDots, UIContext,
+};
+use crate::statistics::DisplayPanel;
+use crate::systems::WrenchToast;
+use crate::Conveyor;
 use bevy::prelude::*;
-use bevy::window::{CursorOptions, PrimaryWindow};
 
 /// Cleanup system: clear InteractingMachine if the referenced entity no longer exists
 ///
-/// This handles the case where a machine is despawned while its UI is open.
-/// Without this cleanup, the UI would remain in MachineUI state with a dangling entity reference.
+/// This handles the case where a machine is despawned while its UI is open
+/// (broken by another player, removed by a mod, ...). Without this cleanup the
+/// UI would stay open with a dangling entity reference and an unlocked cursor.
 pub fn cleanup_invalid_interacting_machine(
     mut interacting: ResMut<InteractingMachine>,
-    machine_query: Query<Entity, With<Machine>>,
+    machine_query: Query<Entity, Or<(With<Machine>, With<DisplayPanel>)>>,
     mut ui_query: Query<(&GenericMachineUI, &mut Visibility)>,
-    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    mut cursor: ResMut<CursorController>,
+    toast: Option<ResMut<WrenchToast>>,
 ) {
     let Some(entity) = interacting.0 else {
         return;
     };
 
-    // Check if the entity still exists and is a machine
+    // Check if the entity still exists and is a machine (or a display panel)
     if machine_query.get(entity).is_ok() {
         return; // Entity still exists, nothing to cleanup
     }
@@ -33,9 +40,17 @@ pub fn cleanup_invalid_interacting_machine(
     }
 
     // Lock cursor back to gameplay mode
-    if let Ok(mut cursor_options) = cursor_query.single_mut() {
-        cursor::lock_cursor(&mut cursor_options);
+    cursor.release_ui(&UIContext::Machine(entity));
+
+    if let Some(mut toast) = toast {
+        toast.show("機械が撤去されました");
     }
+    info!(
+        category = "MACHINE",
+        action = "ui_closed",
+        ?entity,
+        "Machine UI closed: machine was removed"
+    );
 }
 
 /// Visual feedback for machine activity (pulse scale when processing)
@@ -44,7 +59,7 @@ pub fn machine_visual_feedback(
     mut machine_query: Query<(&Machine, &mut Transform)>,
 ) {
     for (machine, mut transform) in machine_query.iter_mut() {
-        if machine.progress > 0.0 {
+        if machine.enabled && machine.progress > 0.0 {
             // Pulse effect while processing
             let pulse = 1.0 + 0.05 * (machine.progress * std::f32::consts::TAU * 2.0).sin();
             transform.scale = Vec3::splat(pulse);
@@ -54,3 +69,265 @@ pub fn machine_visual_feedback(
         }
     }
 }
+
+/// Attach a small notch to the output face (local -Z) of newly spawned machines
+pub fn spawn_machine_output_notch(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    machine_query: Query<(Entity, &Machine, &Transform), Added<Machine>>,
+    mut assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    if machine_query.is_empty() {
+        return;
+    }
+    let (mesh, material) = assets
+        .get_or_insert_with(|| {
+            (
+                meshes.add(Cuboid::new(0.3, 0.1, 0.06)),
+                materials.add(StandardMaterial {
+                    base_color: Color::srgb(1.0, 0.85, 0.2),
+                    unlit: true,
+                    ..default()
+                }),
+            )
+        })
+        .clone();
+
+    for (entity, machine, transform) in machine_query.iter() {
+        // VOX models have a bottom-center origin, fallback cubes a center origin
+        let block_center_y = machine.position.y as f32 + 0.5 - transform.translation.y;
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(0.0, block_center_y + 0.3, -0.53),
+                MachineOutputNotch,
+            ));
+        });
+    }
+}
+
+/// Draw a red stripe on each machine face marked pass-through
+///
+/// Stripes are children in the machine's local (rotated) frame and are
+/// rebuilt whenever the marked faces differ from the ones last drawn.
+#[allow(clippy::type_complexity)]
+pub fn update_pass_through_stripes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    machine_query: Query<(
+        Entity,
+        &Machine,
+        &Transform,
+        Option<&PassThroughStripes>,
+        Option<&Children>,
+    )>,
+    stripe_query: Query<(), With<PassThroughStripe>>,
+    mut assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    for (entity, machine, transform, shown, children) in machine_query.iter() {
+        let shown_sides = shown.map(|s| s.0.as_slice()).unwrap_or(&[]);
+        if shown_sides == machine.pass_through_sides.as_slice() {
+            continue;
+        }
+
+        for child in children.into_iter().flatten() {
+            if stripe_query.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let (mesh, material) = assets
+            .get_or_insert_with(|| {
+                (
+                    meshes.add(Cuboid::new(0.8, 0.08, 0.04)),
+                    materials.add(StandardMaterial {
+                        base_color: Color::srgb(0.9, 0.1, 0.1),
+                        unlit: true,
+                        ..default()
+                    }),
+                )
+            })
+            .clone();
+        // VOX models have a bottom-center origin, fallback cubes a center origin
+        let block_center_y = machine.position.y as f32 + 0.5 - transform.translation.y;
+        let to_local = transform.rotation.inverse();
+        commands.entity(entity).with_children(|parent| {
+            for side in &machine.pass_through_sides {
+                let offset = side.to_ivec3().as_vec3() * 0.53 + Vec3::Y * block_center_y;
+                parent.spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_translation(to_local * offset)
+                        .with_rotation(to_local * side.to_rotation()),
+                    PassThroughStripe,
+                ));
+            }
+        });
+        commands
+            .entity(entity)
+            .insert(PassThroughStripes(machine.pass_through_sides.clone()));
+    }
+}
+
+/// Draw a colored dot above the stripe line on each face routed to one slot
+///
+/// Blue marks an input-only face, orange a fuel-only face. Rebuilt like the
+/// pass-through stripes whenever the routes differ from the ones last drawn.
+#[allow(clippy::type_complexity)]
+pub fn update_slot_route_dots(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    machine_query: Query<(
+        Entity,
+        &Machine,
+        &Transform,
+        Option<&SlotRouteDots>,
+        Option<&Children>,
+    )>,
+    dot_query: Query<(), With<SlotRouteDot>>,
+    mut assets: Local<
+        Option<(
+            Handle<Mesh>,
+            Handle<StandardMaterial>,
+            Handle<StandardMaterial>,
+        )>,
+    >,
+) {
+    for (entity, machine, transform, shown, children) in machine_query.iter() {
+        let shown_routes = shown.map(|s| s.0.as_slice()).unwrap_or(&[]);
+        if shown_routes == machine.slot_routes.as_slice() {
+            continue;
+        }
+
+        for child in children.into_iter().flatten() {
+            if dot_query.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let (mesh, input_material, fuel_material) = assets
+            .get_or_insert_with(|| {
+                let mut material = |route: SlotRoute| {
+                    materials.add(StandardMaterial {
+                        base_color: route.color(),
+                        unlit: true,
+                        ..default()
+                    })
+                };
+                (
+                    meshes.add(Cuboid::new(0.12, 0.12, 0.04)),
+                    material(SlotRoute::Input),
+                    material(SlotRoute::Fuel),
+                )
+            })
+            .clone();
+        // VOX models have a bottom-center origin, fallback cubes a center origin
+        let block_center_y = machine.position.y as f32 + 0.5 - transform.translation.y;
+        let to_local = transform.rotation.inverse();
+        commands.entity(entity).with_children(|parent| {
+            for &(side, route) in &machine.slot_routes {
+                let material = match route {
+                    SlotRoute::Fuel => fuel_material.clone(),
+                    _ => input_material.clone(),
+                };
+                let offset = side.to_ivec3().as_vec3() * 0.53 + Vec3::Y * (block_center_y + 0.25);
+                parent.spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_translation(to_local * offset)
+                        .with_rotation(to_local * side.to_rotation()),
+                    SlotRouteDot,
+                ));
+            }
+        });
+        commands
+            .entity(entity)
+            .insert(SlotRouteDots(machine.slot_routes.clone()));
+    }
+}
+
+/// Brightness multiplier for disabled machines and conveyors
+const DISABLED_BRIGHTNESS: f32 = 0.35;
+
+/// Dim disabled machines and conveyors by swapping in a darkened copy of their material
+#[allow(clippy::type_complexity)]
+pub fn update_disabled_tint(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut machine_query: Query<
+        (
+            Entity,
+            &Machine,
+            &mut MeshMaterial3d<StandardMaterial>,
+            Option<&DisabledTint>,
+        ),
+        Changed<Machine>,
+    >,
+    mut conveyor_query: Query<
+        (
+            Entity,
+            &Conveyor,
+            &mut MeshMaterial3d<StandardMaterial>,
+            Option<&DisabledTint>,
+        ),
+        (Changed<Conveyor>, Without<Machine>),
+    >,
+) {
+    for (entity, machine, mut material, tint) in machine_query.iter_mut() {
+        apply_disabled_tint(
+            &mut commands,
+            &mut materials,
+            entity,
+            machine.enabled,
+            &mut material,
+            tint,
+        );
+    }
+    for (entity, conveyor, mut material, tint) in conveyor_query.iter_mut() {
+        apply_disabled_tint(
+            &mut commands,
+            &mut materials,
+            entity,
+            conveyor.enabled,
+            &mut material,
+            tint,
+        );
+    }
+}
+
+fn apply_disabled_tint(
+    commands: &mut Commands,
+    materials: &mut Assets<StandardMaterial>,
+    entity: Entity,
+    enabled: bool,
+    material: &mut MeshMaterial3d<StandardMaterial>,
+    tint: Option<&DisabledTint>,
+) {
+    match (enabled, tint) {
+        (false, None) => {
+            // Copy rather than edit: the original may be shared with other blocks
+            let Some(mut dimmed) = materials.get(&material.0).cloned() else {
+                return;
+            };
+            let c = dimmed.base_color.to_linear();
+            dimmed.base_color = LinearRgba::new(
+                c.red * DISABLED_BRIGHTNESS,
+                c.green * DISABLED_BRIGHTNESS,
+                c.blue * DISABLED_BRIGHTNESS,
+                c.alpha,
+            )
+            .into();
+            let original = std::mem::replace(&mut material.0, materials.add(dimmed));
+            commands.entity(entity).insert(DisabledTint(original));
+        }
+        (true, Some(tint)) => {
+            material.0 = tint.0.clone();
+            commands.entity(entity).remove::<DisabledTint>();
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,159 @@
+//! Hopper: catches dropped items and feeds the block below
+//!
+//! Hoppers take dropped items resting on their top face and belt input from
+//! any horizontal side (`Machine::insert_from`). Every `HOPPER.process_time`
+//! one item moves into the machine, hopper or conveyor below, or toward
+//! `Machine::push_side`. The first buffered item the target accepts goes;
+//! anything else stays in the buffer, so a full target backs the hopper up
+//! instead of losing items.
+
+use crate::components::{Machine, MachineStatus};
+use crate::constants::SIMULATION_TICK_SECS;
+use crate::game_spec::ProcessType;
+use crate::logistics::dropped_item::{dropped_item_cell, DroppedItem};
+use crate::Conveyor;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Belt progress where hopper output lands (the middle of the belt, like a side feed)
+const CONVEYOR_DROP_PROGRESS: f32 = 0.5;
+
+/// Collect dropped items and push one buffered item per interval
+///
+/// Runs in FixedUpdate between generic_machine_tick and conveyor_transfer.
+/// Hoppers go top to bottom so a chain moves every item by at most one hopper per tick.
+pub fn hopper_tick(
+    mut commands: Commands,
+    mut machine_query: Query<(Entity, &mut Machine)>,
+    mut conveyor_query: Query<(Entity, &mut Conveyor)>,
+    mut dropped_query: Query<(Entity, &mut DroppedItem, &Transform)>,
+) {
+    let mut hoppers: Vec<(Entity, IVec3)> = machine_query
+        .iter()
+        .filter(|(_, m)| m.spec.process_type == ProcessType::Hopper)
+        .map(|(e, m)| (e, m.position))
+        .collect();
+    if hoppers.is_empty() {
+        return;
+    }
+    hoppers.sort_by_key(|&(_, pos)| (-pos.y, pos.x, pos.z));
+
+    let machine_map: HashMap<IVec3, Entity> =
+        machine_query.iter().map(|(e, m)| (m.position, e)).collect();
+    let conveyor_map: HashMap<IVec3, Entity> = conveyor_query
+        .iter()
+        .map(|(e, c)| (c.position, e))
+        .collect();
+
+    // Dropped items resting on a hopper fall in
+    for (entity, mut dropped, transform) in dropped_query.iter_mut() {
+        let above = dropped_item_cell(transform);
+        let Some(&hopper) = machine_map.get(&(above + IVec3::NEG_Y)) else {
+            continue;
+        };
+        let Ok((_, mut machine)) = machine_query.get_mut(hopper) else {
+            continue;
+        };
+        if machine.spec.process_type != ProcessType::Hopper || !machine.enabled {
+            continue;
+        }
+        let taken = machine.insert_from(dropped.item_id, dropped.count, above);
+        dropped.count -= taken;
+        if dropped.count == 0 {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (entity, position) in hoppers {
+        let target = {
+            let Ok((_, mut hopper)) = machine_query.get_mut(entity) else {
+                continue;
+            };
+            // Disabled hoppers are marked by generic_machine_tick
+            if !hopper.enabled {
+                continue;
+            }
+            if hopper.slots.inputs.iter().all(|slot| slot.is_empty()) {
+                hopper.progress = 0.0;
+                hopper.status = MachineStatus::NoInput;
+                continue;
+            }
+            let step = SIMULATION_TICK_SECS / hopper.spec.process_time;
+            hopper.progress = (hopper.progress + step).min(1.0);
+            if hopper.progress < 1.0 {
+                continue;
+            }
+            hopper.hopper_target()
+        };
+
+        let status = if let Some(&target_entity) = machine_map.get(&target) {
+            let Ok([(_, mut hopper), (_, mut target_machine)]) =
+                machine_query.get_many_mut([entity, target_entity])
+            else {
+                continue;
+            };
+            // Like belt inserts, only a real insert wakes a sleeping target
+            let status = push_into_machine(
+                &mut hopper,
+                target_machine.bypass_change_detection(),
+                position,
+            );
+            if status.is_working() {
+                target_machine.set_changed();
+            }
+            status
+        } else if let Some(&conveyor_entity) = conveyor_map.get(&target) {
+            let (Ok((_, mut hopper)), Ok((_, mut conveyor))) = (
+                machine_query.get_mut(entity),
+                conveyor_query.get_mut(conveyor_entity),
+            ) else {
+                continue;
+            };
+            push_onto_conveyor(&mut hopper, &mut conveyor)
+        } else {
+            MachineStatus::NoValidTarget
+        };
+
+        // A stuck hopper stays ready and retries every tick
+        if let Ok((_, mut hopper)) = machine_query.get_mut(entity) {
+            if status.is_working() {
+                hopper.progress = 0.0;
+            }
+            hopper.status = status;
+        }
+    }
+}
+
+/// Move the first buffered item the target accepts
+fn push_into_machine(hopper: &mut Machine, target: &mut Machine, from: IVec3) -> MachineStatus {
+    for slot in hopper.slots.inputs.iter_mut() {
+        let Some(item_id) = slot.item_id.filter(|_| slot.count > 0) else {
+            continue;
+        };
+        if target.insert_from(item_id, 1, from) == 1 {
+            slot.take(1);
+            return MachineStatus::Working;
+        }
+    }
+    MachineStatus::OutputFull
+}
+
+/// Put the first buffered item onto the belt if there's room
+fn push_onto_conveyor(hopper: &mut Machine, conveyor: &mut Conveyor) -> MachineStatus {
+    let Some(slot) = hopper.slots.inputs.iter_mut().find(|slot| !slot.is_empty()) else {
+        return MachineStatus::NoInput;
+    };
+    let Some(item_id) = slot.item_id else {
+        return MachineStatus::NoInput;
+    };
+    // Belts flowing into the hopper would hand the item straight back
+    if conveyor.position + conveyor.direction.to_ivec3() == hopper.position {
+        return MachineStatus::NoValidTarget;
+    }
+    if !conveyor.can_accept_item(CONVEYOR_DROP_PROGRESS) {
+        return MachineStatus::OutputFull;
+    }
+    conveyor.add_item(item_id, CONVEYOR_DROP_PROGRESS);
+    slot.take(1);
+    MachineStatus::Working
+}
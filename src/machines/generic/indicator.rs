@@ -0,0 +1,126 @@
+//! Status indicators over stalled machines
+
+use crate::components::{Machine, MachineStatus, MachineStatusIndicator, Player};
+use crate::logistics::billboard_rotation;
+use crate::BLOCK_SIZE;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Indicators only show for machines within this many blocks of the player
+pub const STATUS_INDICATOR_RANGE: f32 = 24.0;
+
+/// Height of the indicator above the machine's center (blocks)
+const STATUS_INDICATOR_HEIGHT: f32 = 1.0;
+
+/// Indicator quad size (blocks)
+const STATUS_INDICATOR_SIZE: f32 = 0.35;
+
+/// Blink half-period (seconds)
+const STATUS_BLINK_SECS: f32 = 0.5;
+
+/// Shared indicator mesh and one material per status, built lazily
+#[derive(Resource, Default)]
+pub struct MachineStatusIndicatorAssets {
+    pub mesh: Option<Handle<Mesh>>,
+    pub materials: HashMap<MachineStatus, Handle<StandardMaterial>>,
+}
+
+fn indicator_material(
+    assets: &mut MachineStatusIndicatorAssets,
+    materials: &mut Assets<StandardMaterial>,
+    status: MachineStatus,
+) -> Handle<StandardMaterial> {
+    assets
+        .materials
+        .entry(status)
+        .or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: status.color(),
+                unlit: true,
+                cull_mode: None,
+                ..default()
+            })
+        })
+        .clone()
+}
+
+/// Spawn, move and blink indicators over stalled machines near the player
+///
+/// Indicators of working, distant or removed machines are despawned.
+#[allow(clippy::type_complexity)]
+pub fn update_machine_status_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut assets: ResMut<MachineStatusIndicatorAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    player_query: Query<&Transform, (With<Player>, Without<MachineStatusIndicator>)>,
+    machines: Query<(Entity, &Machine)>,
+    mut indicators: Query<(
+        Entity,
+        &MachineStatusIndicator,
+        &mut Transform,
+        &mut MeshMaterial3d<StandardMaterial>,
+        &mut Visibility,
+    )>,
+) {
+    let player_pos = player_query.single().ok().map(|t| t.translation);
+    let range = STATUS_INDICATOR_RANGE * BLOCK_SIZE;
+    let indicator_pos = |machine: &Machine| {
+        (machine.position.as_vec3() + Vec3::splat(0.5)) * BLOCK_SIZE
+            + Vec3::Y * STATUS_INDICATOR_HEIGHT * BLOCK_SIZE
+    };
+    let shown = |machine: &Machine| {
+        !machine.status.is_working()
+            && player_pos.is_some_and(|p| p.distance(indicator_pos(machine)) <= range)
+    };
+    let blink_on = (time.elapsed_secs() / STATUS_BLINK_SECS) as u32 % 2 == 0;
+
+    let mut has_indicator = HashSet::new();
+    for (entity, indicator, mut transform, mut material, mut visibility) in indicators.iter_mut() {
+        let machine = machines.get(indicator.0).ok().map(|(_, m)| m);
+        let Some(machine) = machine.filter(|m| shown(m)) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        has_indicator.insert(indicator.0);
+
+        let handle = indicator_material(&mut assets, &mut materials, machine.status);
+        if material.0 != handle {
+            material.0 = handle;
+        }
+        let pos = indicator_pos(machine);
+        transform.translation = pos;
+        if let Some(player_pos) = player_pos {
+            transform.rotation = billboard_rotation(pos, player_pos);
+        }
+        visibility.set_if_neq(if blink_on {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+
+    for (entity, machine) in machines.iter() {
+        if has_indicator.contains(&entity) || !shown(machine) {
+            continue;
+        }
+        let mesh = assets
+            .mesh
+            .get_or_insert_with(|| {
+                meshes.add(Rectangle::new(
+                    STATUS_INDICATOR_SIZE * BLOCK_SIZE,
+                    STATUS_INDICATOR_SIZE * BLOCK_SIZE,
+                ))
+            })
+            .clone();
+        let material = indicator_material(&mut assets, &mut materials, machine.status);
+        commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(indicator_pos(machine)),
+            Visibility::Visible,
+            MachineStatusIndicator(entity),
+        ));
+    }
+}
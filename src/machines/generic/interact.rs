@@ -1,13 +1,12 @@
 //! Generic machine interaction (open/close UI)
 
 use crate::components::{
-    CursorLockState, GenericMachineUI, InteractingMachine, InventoryOpen, Machine, PlayerCamera,
+    CursorController, CursorLockState, GenericMachineUI, InteractingMachine, InventoryOpen,
+    Machine, PlayerCamera, UIContext,
 };
 use crate::input::{GameAction, InputManager};
-use crate::systems::cursor;
 use crate::REACH_DISTANCE;
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
 /// Generic machine interaction (open/close UI)
 #[allow(clippy::too_many_arguments)]
@@ -18,14 +17,9 @@ pub fn generic_machine_interact(
     mut interacting: ResMut<InteractingMachine>,
     inventory_open: Res<InventoryOpen>,
     mut ui_query: Query<(&GenericMachineUI, &mut Visibility)>,
-    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    mut cursor: ResMut<CursorController>,
     mut cursor_state: ResMut<CursorLockState>,
 ) {
-    let Ok(cursor_options) = cursor_query.single() else {
-        return;
-    };
-    let cursor_locked = cursor_options.grab_mode != CursorGrabMode::None;
-
     // Don't interact if inventory is open
     if inventory_open.0 {
         return;
@@ -35,9 +29,9 @@ pub fn generic_machine_interact(
     let esc_pressed = input.just_pressed(GameAction::Cancel);
 
     // Close UI with E or ESC
-    if interacting.0.is_some() && (e_pressed || esc_pressed) {
+    if let Some(entity) = interacting.0.filter(|_| e_pressed || esc_pressed) {
         let machine_id = machine_query
-            .get(interacting.0.unwrap())
+            .get(entity)
             .map(|(_, _, m)| m.spec.id)
             .unwrap_or("");
 
@@ -50,18 +44,16 @@ pub fn generic_machine_interact(
 
         interacting.0 = None;
 
-        // Lock cursor (unless ESC)
+        // Hand the cursor back (ESC opening the pause menu takes precedence)
+        cursor.release_ui(&UIContext::Machine(entity));
         if !esc_pressed {
-            if let Ok(mut cursor_options) = cursor_query.single_mut() {
-                cursor::lock_cursor(&mut cursor_options);
-            }
             cursor_state.skip_inventory_toggle = true;
         }
         return;
     }
 
     // Open UI with right-click when cursor locked
-    if !input.just_pressed(GameAction::SecondaryAction) || !cursor_locked {
+    if !input.just_pressed(GameAction::SecondaryAction) || !cursor.is_locked() {
         return;
     }
 
@@ -100,8 +92,6 @@ pub fn generic_machine_interact(
         }
 
         // Unlock cursor
-        if let Ok(mut cursor_options) = cursor_query.single_mut() {
-            cursor::unlock_cursor(&mut cursor_options);
-        }
+        cursor.request_ui(UIContext::Machine(entity));
     }
 }
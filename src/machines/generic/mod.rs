@@ -3,21 +3,45 @@
 //! These systems work with the generic `Machine` component,
 //! using `MachineSpec` to determine behavior.
 
+mod activity;
 pub(crate) mod auto_generate;
 mod cleanup;
+mod hopper;
+mod indicator;
 mod interact;
 mod output;
 mod recipe;
+pub mod recipe_browser;
+mod recycle;
+mod sleep;
+pub mod status;
 mod tick;
 mod ui;
 
 // Re-export public systems
+pub use activity::sample_machine_activity;
 pub use cleanup::cleanup_invalid_interacting_machine;
 pub use cleanup::machine_visual_feedback;
+pub use cleanup::spawn_machine_output_notch;
+pub use cleanup::update_disabled_tint;
+pub use cleanup::update_pass_through_stripes;
+pub use cleanup::update_slot_route_dots;
+pub use hopper::hopper_tick;
+pub use indicator::update_machine_status_indicators;
+pub use indicator::MachineStatusIndicatorAssets;
 pub use interact::generic_machine_interact;
+pub use recipe_browser::generic_machine_recipe_browser_input;
+pub use sleep::update_machine_sleep;
 pub use tick::generic_machine_tick;
+pub use ui::generic_machine_drill_input;
+pub use ui::generic_machine_slot_route_input;
+pub use ui::generic_machine_toggle_input;
 pub use ui::generic_machine_ui_input;
+pub use ui::update_generic_machine_activity_ui;
+pub use ui::update_generic_machine_drill_ui;
+pub use ui::update_generic_machine_slot_route_ui;
 pub use ui::update_generic_machine_ui;
+pub use ui::update_machine_slot_palette;
 
 #[cfg(test)]
 mod tests;
@@ -1,47 +1,109 @@
 //! Output to conveyor logic
 
-use crate::components::{ConveyorItem, Machine};
+use crate::components::Machine;
 use crate::Conveyor;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
 /// Try to output items to a connected conveyor (O(1) lookup)
+///
+/// The facing side always goes first; other sides are only tried when the
+/// machine allows any-side output and the facing belt is missing or full.
 pub(super) fn try_output_to_conveyor(
     machine: &mut Machine,
     conveyor_map: &HashMap<IVec3, Entity>,
     conveyor_query: &mut Query<(Entity, &mut Conveyor)>,
 ) {
-    let output_pos = machine.output_position();
+    let machine_pos = machine.position;
+    for output_pos in machine.output_positions() {
+        // O(1) lookup for conveyor at output position
+        let Some(&conveyor_entity) = conveyor_map.get(&output_pos) else {
+            continue;
+        };
 
-    // O(1) lookup for conveyor at output position
-    let Some(&conveyor_entity) = conveyor_map.get(&output_pos) else {
-        return;
-    };
+        // Get the conveyor component
+        let Ok((_, mut conveyor)) = conveyor_query.get_mut(conveyor_entity) else {
+            continue;
+        };
 
-    // Get the conveyor component
-    let Ok((_, mut conveyor)) = conveyor_query.get_mut(conveyor_entity) else {
-        return;
-    };
+        // Belts flowing into the machine would just hand the item back
+        if conveyor.position + conveyor.direction.to_ivec3() == machine_pos {
+            continue;
+        }
 
-    // Check if conveyor can accept items
-    if conveyor.items.len() >= crate::constants::CONVEYOR_MAX_ITEMS {
-        return;
-    }
+        // Get item from output slot
+        let Some(output_slot) = machine.slots.outputs.first_mut() else {
+            return;
+        };
 
-    // Get item from output slot
-    let Some(output_slot) = machine.slots.outputs.first_mut() else {
-        return;
-    };
+        if output_slot.is_empty() {
+            return;
+        }
+
+        let Some(item_id) = output_slot.item_id else {
+            return;
+        };
+
+        // Merge into the trailing stack near the entry, otherwise start a new item
+        if conveyor.try_stack_item(item_id) {
+            output_slot.take(1);
+            return;
+        }
+        if !conveyor.can_accept_item(0.0) {
+            continue;
+        }
 
-    if output_slot.is_empty() {
+        // Transfer one item
+        output_slot.take(1);
+        conveyor.add_item(item_id, 0.0);
         return;
     }
+}
 
-    let Some(item_id) = output_slot.item_id else {
+/// Push one item from the output slots onto the next adjacent conveyor (round-robin)
+///
+/// Every horizontal side is a candidate except belts flowing into the machine.
+/// `machine.output_cursor` remembers where to start next time so items spread
+/// evenly over all connected belts; output slots are drained in order.
+pub(super) fn try_output_round_robin(
+    machine: &mut Machine,
+    conveyor_map: &HashMap<IVec3, Entity>,
+    conveyor_query: &mut Query<(Entity, &mut Conveyor)>,
+) {
+    let Some(slot_idx) = machine.slots.outputs.iter().position(|s| !s.is_empty()) else {
+        return;
+    };
+    let Some(item_id) = machine.slots.outputs[slot_idx].item_id else {
         return;
     };
 
-    // Transfer one item
-    output_slot.take(1);
-    conveyor.items.push(ConveyorItem::new(item_id, 0.0));
+    let machine_pos = machine.position;
+    let sides = [
+        machine.facing,
+        machine.facing.right(),
+        machine.facing.opposite(),
+        machine.facing.left(),
+    ];
+    for offset in 0..sides.len() {
+        let side_idx = (machine.output_cursor + offset) % sides.len();
+        let output_pos = machine_pos + sides[side_idx].to_ivec3();
+        let Some(&conveyor_entity) = conveyor_map.get(&output_pos) else {
+            continue;
+        };
+        let Ok((_, mut conveyor)) = conveyor_query.get_mut(conveyor_entity) else {
+            continue;
+        };
+        if conveyor.position + conveyor.direction.to_ivec3() == machine_pos {
+            continue;
+        }
+        if !conveyor.try_stack_item(item_id) {
+            if !conveyor.can_accept_item(0.0) {
+                continue;
+            }
+            conveyor.add_item(item_id, 0.0);
+        }
+        machine.slots.outputs[slot_idx].take(1);
+        machine.output_cursor = (side_idx + 1) % sides.len();
+        return;
+    }
 }
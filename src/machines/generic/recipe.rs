@@ -1,13 +1,16 @@
 //! Recipe-based machine processing (Furnace, Crusher, Assembler)
 
-use crate::components::Machine;
+use crate::components::{CurrentQuest, Machine};
 use crate::core::ItemId;
+use crate::fluids::FluidNetworks;
 use crate::game_spec::{find_recipe, MachineType};
+use crate::player::PlatformInventory;
 use crate::Conveyor;
 use bevy::prelude::*;
 use std::collections::HashMap;
 
 use super::output::try_output_to_conveyor;
+use super::status::{recipe_status, RecipeCheck};
 
 /// Event result from tick_recipe: (started_inputs, completed_outputs)
 pub(super) type RecipeEventResult =
@@ -17,47 +20,83 @@ pub(super) type RecipeEventResult =
 /// Returns Some((started_inputs, completed_outputs)) for event emission
 /// - started_inputs: Some when processing started (inputs consumed)
 /// - completed_outputs: Some when processing completed (outputs produced)
+///
+/// Recipe fluids go through pipes/tanks touching the machine. Only a
+/// finished craft writes to `fluid_networks`, so idle checks don't mark the
+/// resource changed.
 pub(super) fn tick_recipe(
     machine: &mut Machine,
     delta: f32,
     machine_type: MachineType,
+    unlock_state: (&CurrentQuest, &PlatformInventory),
     conveyor_map: &HashMap<IVec3, Entity>,
     conveyor_query: &mut Query<(Entity, &mut Conveyor)>,
+    mut fluid_networks: Option<&mut ResMut<FluidNetworks>>,
 ) -> RecipeEventResult {
     let spec = machine.spec;
 
-    // Get input item
-    let input_item_id = machine.slots.inputs.first().and_then(|s| s.item_id);
-
-    // Find recipe
-    let input_id = input_item_id?;
-    let recipe = find_recipe(machine_type, input_id)?;
-
-    // Check fuel requirement
-    if spec.requires_fuel && machine.slots.fuel == 0 {
+    // Get input item and its recipe
+    // (locked recipes don't run: the input stays in the slot until unlocked)
+    let input = machine
+        .slots
+        .inputs
+        .first()
+        .and_then(|s| Some((s.item_id?, s.count)));
+    let (current_quest, platform) = unlock_state;
+    let recipe = input
+        .and_then(|(input_id, _)| find_recipe(machine_type, input_id))
+        .filter(|recipe| recipe.unlock.is_met(current_quest, platform));
+    let (Some((input_id, input_count)), Some(recipe)) = (input, recipe) else {
+        machine.status = recipe_status(&RecipeCheck {
+            has_input: input.is_some(),
+            ..default()
+        });
         return None;
-    }
+    };
 
-    // Check if we have enough input
-    let input_slot = &machine.slots.inputs[0];
-    let required_count = recipe.inputs.first().map(|i| i.count).unwrap_or(1);
-    if input_slot.count < required_count {
-        return None;
-    }
+    let required_count = recipe
+        .inputs
+        .iter()
+        .find(|i| i.matches(input_id))
+        .map(|i| i.count)
+        .unwrap_or(1);
 
-    // Check if output has space
-    let output_item_id: Option<ItemId> = recipe.outputs.first().map(|o| o.item);
-    let output_count = recipe.outputs.first().map(|o| o.count).unwrap_or(1);
+    // Check if output has space (tag recipes map each input to its own output)
+    let output = recipe.outputs_for(input_id).next();
+    let output_item_id: Option<ItemId> = output.map(|o| o.item);
+    let output_count = output.map(|o| o.count).unwrap_or(1);
 
     let output_slot = machine.slots.outputs.first();
-    let can_output = output_slot
-        .map(|s| {
-            s.count + output_count <= spec.buffer_size
-                && (s.item_id.is_none() || s.item_id == output_item_id)
-        })
-        .unwrap_or(false);
-
-    if !can_output {
+    let can_output = output_item_id.is_none()
+        || output_slot
+            .map(|s| {
+                s.count + output_count <= spec.buffer_size
+                    && (s.item_id.is_none() || s.item_id == output_item_id)
+            })
+            .unwrap_or(false);
+
+    // Fluid input must be in an adjacent network, fluid output must fit one
+    let pos = machine.position;
+    let fluid_ready = recipe.fluid_input.is_none_or(|input| {
+        fluid_networks
+            .as_ref()
+            .is_some_and(|n| n.available_at(pos, input.fluid) >= input.amount)
+    });
+    let fluid_fits = recipe.fluid_output.is_none_or(|output| {
+        fluid_networks
+            .as_ref()
+            .is_some_and(|n| n.space_at(pos, output.fluid) >= output.amount)
+    });
+
+    machine.status = recipe_status(&RecipeCheck {
+        has_input: true,
+        has_recipe: true,
+        out_of_fuel: spec.requires_fuel && machine.slots.fuel == 0,
+        enough_input: input_count >= required_count,
+        fluid_ready,
+        output_fits: can_output && fluid_fits,
+    });
+    if !machine.status.is_working() {
         return None;
     }
 
@@ -90,6 +129,16 @@ pub(super) fn tick_recipe(
             }
         }
 
+        // Move recipe fluids (checked above, so both fit)
+        if let Some(networks) = fluid_networks.as_mut() {
+            if let Some(input) = recipe.fluid_input {
+                networks.extract_at(pos, input.fluid, input.amount);
+            }
+            if let Some(output) = recipe.fluid_output {
+                networks.insert_at(pos, output.fluid, output.amount);
+            }
+        }
+
         // Produce output
         if let (Some(item_id), Some(output_slot)) =
             (output_item_id, machine.slots.outputs.first_mut())
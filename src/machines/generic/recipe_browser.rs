@@ -0,0 +1,369 @@
+//! Recipe browser in the machine UI (Furnace, Crusher, Assembler)
+//!
+//! The "レシピ" button swaps the slot view for every recipe of the machine's
+//! type: ingredients, products, craft time and items per minute at the
+//! current `SimulationSpeed`. Locked recipes show their unlock condition.
+//! Rows are built when the browser opens, not every frame; closing the UI or
+//! opening another machine returns to the slot view.
+
+use crate::components::{
+    CurrentQuest, GameFont, GenericMachineRecipeBackButton, GenericMachineRecipeList,
+    GenericMachineRecipeView, GenericMachineRecipesButton, GenericMachineSlotView,
+    InteractingMachine, ItemSprites, Machine,
+};
+use crate::core::{fluids, ItemId};
+use crate::game_spec::{
+    all_recipes, items_per_minute, FluidAmount, Ingredient, ItemInfoCache, MachineType,
+    ProcessType, UnlockCondition,
+};
+use crate::machines::SimulationSpeed;
+use crate::player::LocalPlatformInventory;
+use crate::setup::ui::{text_font, TEXT_MINI, TEXT_SMALL};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+const ICON_SIZE: f32 = 20.0;
+const ROW_BG: Color = Color::srgba(0.16, 0.16, 0.2, 0.9);
+const LOCKED_ROW_BG: Color = Color::srgba(0.12, 0.12, 0.12, 0.9);
+const TEXT_PRIMARY: Color = Color::WHITE;
+const TEXT_SECONDARY: Color = Color::srgb(0.67, 0.67, 0.67);
+const LOCKED_COLOR: Color = Color::srgb(1.0, 0.55, 0.3);
+
+/// One ingredient or product of a recipe row
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipeEntry {
+    /// Item for the icon (None for tag inputs)
+    pub item: Option<ItemId>,
+    pub label: String,
+    pub count: u32,
+    pub per_minute: f32,
+}
+
+/// One recipe as shown in the browser
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipeRow {
+    pub id: &'static str,
+    pub inputs: Vec<RecipeEntry>,
+    pub outputs: Vec<RecipeEntry>,
+    pub craft_time: f32,
+    /// Unlock condition of a locked recipe
+    pub locked: Option<String>,
+}
+
+/// Rows for every recipe `machine` runs, in definition order
+///
+/// `is_met` decides whether an unlock condition is satisfied.
+pub fn recipe_rows(
+    machine: MachineType,
+    speed: f32,
+    item_info: Option<&ItemInfoCache>,
+    is_met: impl Fn(&UnlockCondition) -> bool,
+) -> Vec<RecipeRow> {
+    let item_name = |item: ItemId| {
+        item_info
+            .and_then(|info| info.get(item))
+            .map(|info| info.name.clone())
+            .unwrap_or_else(|| item.display_name().to_string())
+    };
+
+    all_recipes()
+        .iter()
+        .filter(|recipe| recipe.machine == machine)
+        .map(|recipe| {
+            // Fluids have no icon; the count is the amount per craft
+            let fluid_entry = |fluid: &FluidAmount| RecipeEntry {
+                item: None,
+                label: fluids::display_name(fluid.fluid).to_string(),
+                count: fluid.amount as u32,
+                per_minute: items_per_minute(fluid.amount as u32, recipe.craft_time, speed),
+            };
+            RecipeRow {
+                id: recipe.id,
+                inputs: recipe
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        let (item, label) = match input.ingredient {
+                            Ingredient::Item(item) => (Some(item), item_name(item)),
+                            Ingredient::Tag(tag) => (None, format!("#{}", tag)),
+                        };
+                        RecipeEntry {
+                            item,
+                            label,
+                            count: input.count,
+                            per_minute: recipe.input_per_minute(input, speed),
+                        }
+                    })
+                    .chain(recipe.fluid_input.iter().map(fluid_entry))
+                    .collect(),
+                outputs: recipe
+                    .outputs
+                    .iter()
+                    .map(|output| RecipeEntry {
+                        item: Some(output.item),
+                        label: item_name(output.item),
+                        count: output.count,
+                        per_minute: recipe.output_per_minute(output, speed),
+                    })
+                    .chain(recipe.fluid_output.iter().map(fluid_entry))
+                    .collect(),
+                craft_time: recipe.craft_time,
+                locked: (!is_met(&recipe.unlock))
+                    .then(|| recipe.unlock.description())
+                    .flatten(),
+            }
+        })
+        .collect()
+}
+
+/// Format a rate for the browser ("30/分", "7.5/分")
+pub fn format_per_minute(per_minute: f32) -> String {
+    if (per_minute - per_minute.round()).abs() < 0.05 {
+        format!("{}/分", per_minute.round() as i64)
+    } else {
+        format!("{:.1}/分", per_minute)
+    }
+}
+
+/// Everything the browser rows are built from (reduces parameter count)
+#[derive(SystemParam)]
+pub struct RecipeBrowserData<'w, 's> {
+    speed: Res<'w, SimulationSpeed>,
+    item_info: Option<Res<'w, ItemInfoCache>>,
+    item_sprites: Res<'w, ItemSprites>,
+    game_font: Res<'w, GameFont>,
+    current_quest: Res<'w, CurrentQuest>,
+    platform_inventory: LocalPlatformInventory<'w, 's>,
+}
+
+impl RecipeBrowserData<'_, '_> {
+    fn rows(&self, machine: MachineType) -> Vec<RecipeRow> {
+        let platform = self.platform_inventory.get();
+        recipe_rows(machine, self.speed.0, self.item_info.as_deref(), |unlock| {
+            platform.is_some_and(|p| unlock.is_met(&self.current_quest, p))
+        })
+    }
+}
+
+/// Open/close the recipe browser and reset it when the machine UI changes
+#[allow(clippy::type_complexity)]
+pub fn generic_machine_recipe_browser_input(
+    mut commands: Commands,
+    interacting: Res<InteractingMachine>,
+    machine_query: Query<&Machine>,
+    mut button_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Has<GenericMachineRecipeBackButton>,
+        ),
+        (
+            Changed<Interaction>,
+            Or<(
+                With<GenericMachineRecipesButton>,
+                With<GenericMachineRecipeBackButton>,
+            )>,
+        ),
+    >,
+    mut slot_views: Query<
+        &mut Node,
+        (
+            With<GenericMachineSlotView>,
+            Without<GenericMachineRecipeView>,
+        ),
+    >,
+    mut recipe_views: Query<&mut Node, With<GenericMachineRecipeView>>,
+    lists: Query<(Entity, &GenericMachineRecipeList)>,
+    data: RecipeBrowserData,
+) {
+    // Opening, closing or switching machines starts on the slot view
+    let mut open = None;
+    if interacting.is_changed() {
+        open = Some(false);
+    }
+
+    for (interaction, mut bg_color, is_back) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                open = Some(!is_back);
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.25, 0.25, 0.3));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.15, 0.15, 0.2));
+            }
+        }
+    }
+
+    let Some(open) = open else {
+        return;
+    };
+    let machine = interacting.0.and_then(|e| machine_query.get(e).ok());
+    let recipe_machine = machine.and_then(|m| match m.spec.process_type {
+        ProcessType::Recipe(machine_type) => Some((m.spec.id, machine_type)),
+        _ => None,
+    });
+    let open = open && recipe_machine.is_some();
+
+    for mut node in slot_views.iter_mut() {
+        node.display = if open { Display::None } else { Display::Flex };
+    }
+    for mut node in recipe_views.iter_mut() {
+        node.display = if open { Display::Flex } else { Display::None };
+    }
+
+    let Some((machine_id, machine_type)) = recipe_machine.filter(|_| open) else {
+        return;
+    };
+    let rows = data.rows(machine_type);
+    for (list, marker) in lists.iter() {
+        if marker.machine_id != machine_id {
+            continue;
+        }
+        commands.entity(list).despawn_related::<Children>();
+        commands.entity(list).with_children(|list| {
+            for row in &rows {
+                spawn_recipe_row(list, row, &data);
+            }
+        });
+    }
+}
+
+/// Spawn one recipe: "icons inputs → icons outputs", then time/rate and lock lines
+fn spawn_recipe_row(list: &mut ChildSpawnerCommands, row: &RecipeRow, data: &RecipeBrowserData) {
+    let font = &data.game_font.0;
+    let locked = row.locked.is_some();
+    list.spawn((
+        Node {
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(6.0)),
+            row_gap: Val::Px(2.0),
+            border_radius: BorderRadius::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(if locked { LOCKED_ROW_BG } else { ROW_BG }),
+    ))
+    .with_children(|row_node| {
+        row_node
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.0),
+                ..default()
+            })
+            .with_children(|line| {
+                for (i, entry) in row.inputs.iter().enumerate() {
+                    if i > 0 {
+                        spawn_text(line, font, "+", TEXT_SECONDARY);
+                    }
+                    spawn_entry(line, entry, data);
+                }
+                spawn_text(line, font, "→", TEXT_SECONDARY);
+                for (i, entry) in row.outputs.iter().enumerate() {
+                    if i > 0 {
+                        spawn_text(line, font, "+", TEXT_SECONDARY);
+                    }
+                    spawn_entry(line, entry, data);
+                }
+            });
+
+        let rates: Vec<String> = row
+            .outputs
+            .iter()
+            .map(|o| format!("{} {}", o.label, format_per_minute(o.per_minute)))
+            .collect();
+        row_node.spawn((
+            Text::new(format!("{:.1}秒 / {}", row.craft_time, rates.join(", "))),
+            text_font(font, TEXT_MINI),
+            TextColor(TEXT_SECONDARY),
+        ));
+
+        if let Some(reason) = &row.locked {
+            row_node.spawn((
+                Text::new(format!("ロック中: {}", reason)),
+                text_font(font, TEXT_MINI),
+                TextColor(LOCKED_COLOR),
+            ));
+        }
+    });
+}
+
+/// Item icon (or name when there is no sprite) followed by the count
+fn spawn_entry(line: &mut ChildSpawnerCommands, entry: &RecipeEntry, data: &RecipeBrowserData) {
+    let font = &data.game_font.0;
+    match entry.item.and_then(|item| data.item_sprites.get_id(item)) {
+        Some(image) => {
+            line.spawn((
+                ImageNode { image, ..default() },
+                Node {
+                    width: Val::Px(ICON_SIZE),
+                    height: Val::Px(ICON_SIZE),
+                    ..default()
+                },
+            ));
+            spawn_text(line, font, &format!("x{}", entry.count), TEXT_PRIMARY);
+        }
+        None => spawn_text(
+            line,
+            font,
+            &format!("{} x{}", entry.label, entry.count),
+            TEXT_PRIMARY,
+        ),
+    }
+}
+
+fn spawn_text(line: &mut ChildSpawnerCommands, font: &Handle<Font>, text: &str, color: Color) {
+    line.spawn((
+        Text::new(text),
+        text_font(font, TEXT_SMALL),
+        TextColor(color),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    #[test]
+    fn test_recipe_rows_rates_and_locks() {
+        let rows = recipe_rows(MachineType::Furnace, 1.0, None, |_| true);
+        assert!(!rows.is_empty());
+        let smelt = rows.iter().find(|r| r.id == "smelt_iron").unwrap();
+        assert_eq!(smelt.inputs[0].item, Some(items::iron_ore()));
+        assert_eq!(smelt.outputs[0].per_minute, 30.0);
+        assert!(smelt.locked.is_none());
+
+        // Simulation speed scales every rate
+        let fast = recipe_rows(MachineType::Furnace, 2.0, None, |_| true);
+        let smelt = fast.iter().find(|r| r.id == "smelt_iron").unwrap();
+        assert_eq!(smelt.outputs[0].per_minute, 60.0);
+        assert_eq!(smelt.inputs[0].per_minute, 60.0);
+
+        // Fluids are listed after the items
+        crate::test_support::register_fluid_recipes();
+        let rows = recipe_rows(crate::test_support::FLUID_TEST_WORK, 1.0, None, |_| true);
+        let wash = rows.iter().find(|r| r.id == "test_wash_stone").unwrap();
+        assert_eq!(wash.inputs.len(), 2);
+        assert_eq!((wash.inputs[1].item, wash.inputs[1].count), (None, 100));
+        assert_eq!(wash.inputs[1].label, "Water");
+        assert_eq!(wash.inputs[1].per_minute, 2000.0);
+
+        // Only recipes with an actual condition show a lock reason
+        let locked = recipe_rows(MachineType::Assembler, 1.0, None, |_| false);
+        for row in &locked {
+            let recipe = crate::game_spec::find_recipe_by_id(row.id).unwrap();
+            assert_eq!(row.locked, recipe.unlock.description());
+        }
+    }
+
+    #[test]
+    fn test_format_per_minute() {
+        assert_eq!(format_per_minute(30.0), "30/分");
+        assert_eq!(format_per_minute(7.5), "7.5/分");
+        assert_eq!(format_per_minute(0.0), "0/分");
+    }
+}
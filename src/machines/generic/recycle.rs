@@ -0,0 +1,99 @@
+//! Recycler processing - decomposes crafted items back into ingredients
+
+use crate::components::Machine;
+use crate::core::ItemId;
+use crate::game_spec::{find_recycle_recipe, RECYCLE_TIME_RATIO};
+use crate::Conveyor;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::output::try_output_round_robin;
+use super::recipe::RecipeEventResult;
+use super::status::{recycler_status, RecipeCheck};
+
+/// Tick for the recycler
+///
+/// Consumes one batch of a recipe's output (e.g. 5 conveyors for craft_conveyor)
+/// and returns `Recipe::recycle_returns`, one ingredient per output slot.
+/// Returns Some((started_inputs, completed_outputs)) for event emission.
+pub(super) fn tick_recycle(
+    machine: &mut Machine,
+    delta: f32,
+    conveyor_map: &HashMap<IVec3, Entity>,
+    conveyor_query: &mut Query<(Entity, &mut Conveyor)>,
+) -> RecipeEventResult {
+    let result = process_recycle(machine, delta);
+
+    // Output even while idle so the grid drains
+    try_output_round_robin(machine, conveyor_map, conveyor_query);
+
+    result
+}
+
+fn process_recycle(machine: &mut Machine, delta: f32) -> RecipeEventResult {
+    let buffer_size = machine.spec.buffer_size;
+
+    let input = machine
+        .slots
+        .inputs
+        .first()
+        .and_then(|slot| Some((slot.item_id?, slot.count)));
+    let recipe = input.and_then(|(input_id, _)| find_recycle_recipe(input_id));
+    let (Some((input_id, input_count)), Some(recipe)) = (input, recipe) else {
+        machine.status = recycler_status(&RecipeCheck {
+            has_input: input.is_some(),
+            ..default()
+        });
+        return None;
+    };
+
+    let required_count = recipe.output_count_of(input_id);
+
+    // Each returned ingredient goes to its own output slot
+    let returns = recipe.recycle_returns();
+    let can_output = returns.len() <= machine.slots.outputs.len()
+        && returns
+            .iter()
+            .zip(&machine.slots.outputs)
+            .all(|(&(item_id, count), slot)| {
+                (slot.item_id.is_none() || slot.item_id == Some(item_id))
+                    && slot.count + count <= buffer_size
+            });
+
+    machine.status = recycler_status(&RecipeCheck {
+        has_input: true,
+        has_recipe: true,
+        out_of_fuel: false,
+        enough_input: input_count >= required_count,
+        fluid_ready: true,
+        output_fits: can_output,
+    });
+    if !machine.status.is_working() {
+        return None;
+    }
+
+    let was_idle = machine.progress == 0.0;
+    machine.progress += delta / (recipe.craft_time * RECYCLE_TIME_RATIO);
+
+    let started_inputs = if was_idle && machine.progress > 0.0 && machine.progress < 1.0 {
+        Some(vec![(input_id, required_count)])
+    } else {
+        None
+    };
+
+    let mut completed_outputs: Option<Vec<(ItemId, u32)>> = None;
+    if machine.progress >= 1.0 {
+        machine.progress = 0.0;
+        machine.slots.inputs[0].take(required_count);
+        for (&(item_id, count), slot) in returns.iter().zip(machine.slots.outputs.iter_mut()) {
+            slot.add_id(item_id, count);
+        }
+        completed_outputs = Some(returns);
+    }
+
+    if started_inputs.is_some() || completed_outputs.is_some() {
+        Some((started_inputs, completed_outputs))
+    } else {
+        None
+    }
+}
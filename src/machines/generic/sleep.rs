@@ -0,0 +1,73 @@
+//! Sleeping idle machines
+//!
+//! Most machines in a big factory sit waiting for input or fuel. Instead of
+//! running the full tick for them every simulation tick, a stalled machine gets
+//! the `Idle` marker and generic_machine_tick skips it (`Without<Idle>`).
+//!
+//! Waking relies on change detection: everything that feeds a machine (belt
+//! and hopper inserts, UI slot clicks, the ON/OFF toggle, drill swaps, mod
+//! toggles) goes through `&mut Machine`, so any such write after the machine
+//! fell asleep wakes it. No wake call has to be remembered at each call site.
+
+use crate::components::{Idle, Machine, MachineSleepStats};
+use bevy::ecs::system::SystemChangeTick;
+use bevy::prelude::*;
+
+/// Whether change tick `changed` is later than `since`, as seen from `this_run`
+///
+/// Change ticks wrap around, so they are compared by their age.
+fn changed_after(changed: u32, since: u32, this_run: u32) -> bool {
+    this_run.wrapping_sub(changed) < this_run.wrapping_sub(since)
+}
+
+/// Put stalled machines to sleep and wake the ones that changed since
+///
+/// Runs in FixedUpdate right after generic_machine_tick, so the tick's own
+/// status writes are older than the sleep tick and never wake a machine.
+pub fn update_machine_sleep(
+    mut commands: Commands,
+    ticks: SystemChangeTick,
+    awake: Query<(Entity, &Machine), Without<Idle>>,
+    sleeping: Query<(Entity, Ref<Machine>, &Idle)>,
+    mut stats: ResMut<MachineSleepStats>,
+) {
+    let this_run = ticks.this_run().get();
+    let mut asleep = 0;
+    let mut active = 0;
+
+    for (entity, machine, idle) in sleeping.iter() {
+        if changed_after(machine.last_changed().get(), idle.since, this_run) {
+            commands.entity(entity).remove::<Idle>();
+            active += 1;
+        } else {
+            asleep += 1;
+        }
+    }
+
+    for (entity, machine) in awake.iter() {
+        if machine.can_sleep() {
+            commands.entity(entity).insert(Idle { since: this_run });
+            asleep += 1;
+        } else {
+            active += 1;
+        }
+    }
+
+    stats.active = active;
+    stats.sleeping = asleep;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_after_handles_wraparound() {
+        assert!(changed_after(11, 10, 20));
+        assert!(!changed_after(10, 10, 20));
+        assert!(!changed_after(9, 10, 20));
+        // Ticks wrapped past u32::MAX between sleeping and the change
+        assert!(changed_after(2, u32::MAX - 1, 5));
+        assert!(!changed_after(u32::MAX - 2, u32::MAX - 1, 5));
+    }
+}
@@ -0,0 +1,61 @@
+//! Machine status evaluation
+//!
+//! One small function per machine kind, fed with what the tick already
+//! checked. Disabled machines are skipped by the tick and marked there.
+
+use crate::components::MachineStatus;
+
+/// Miner: needs mineable ground and room in its buffer
+pub fn miner_status(can_mine: bool, output_full: bool) -> MachineStatus {
+    if output_full {
+        MachineStatus::OutputFull
+    } else if !can_mine {
+        MachineStatus::NoValidTarget
+    } else {
+        MachineStatus::Working
+    }
+}
+
+/// What a recipe machine found when it tried to start
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecipeCheck {
+    /// Input slot holds something
+    pub has_input: bool,
+    /// The input has an unlocked recipe on this machine
+    pub has_recipe: bool,
+    /// Needs fuel and has none
+    pub out_of_fuel: bool,
+    /// Enough input for one craft
+    pub enough_input: bool,
+    /// Adjacent pipes/tanks hold the recipe's fluid input (true without one)
+    pub fluid_ready: bool,
+    /// The result fits the output slot
+    pub output_fits: bool,
+}
+
+/// Furnace, crusher, assembler
+pub fn recipe_status(check: &RecipeCheck) -> MachineStatus {
+    if !check.has_input {
+        MachineStatus::NoInput
+    } else if !check.has_recipe {
+        MachineStatus::NoValidTarget
+    } else if check.out_of_fuel {
+        MachineStatus::NoFuel
+    } else if !check.enough_input {
+        MachineStatus::NoInput
+    } else if !check.fluid_ready {
+        MachineStatus::NoFluid
+    } else if !check.output_fits {
+        MachineStatus::OutputFull
+    } else {
+        MachineStatus::Working
+    }
+}
+
+/// Recycler: a recipe machine without fuel
+pub fn recycler_status(check: &RecipeCheck) -> MachineStatus {
+    recipe_status(&RecipeCheck {
+        out_of_fuel: false,
+        ..*check
+    })
+}
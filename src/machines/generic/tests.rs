@@ -149,6 +149,79 @@ fn test_machine_despawn_clears_interacting_machine() {
     assert_eq!(app.world().resource::<InteractingMachine>().0, None);
 }
 
+#[test]
+fn test_cleanup_system_resets_ui_when_furnace_despawned() {
+    use crate::components::{CursorController, GenericMachineUI, InteractingMachine, UIContext};
+    use crate::machines::generic::cleanup_invalid_interacting_machine;
+    use crate::systems::{apply_cursor_controller, WrenchToast};
+    use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<InteractingMachine>();
+    app.init_resource::<WrenchToast>();
+    app.init_resource::<CursorController>();
+    app.add_systems(
+        Update,
+        (cleanup_invalid_interacting_machine, apply_cursor_controller).chain(),
+    );
+
+    let window = app
+        .world_mut()
+        .spawn((
+            PrimaryWindow,
+            CursorOptions {
+                grab_mode: CursorGrabMode::None,
+                ..default()
+            },
+        ))
+        .id();
+    let ui = app
+        .world_mut()
+        .spawn((
+            GenericMachineUI {
+                machine_id: FURNACE.id,
+            },
+            Visibility::Inherited,
+        ))
+        .id();
+    let furnace = app
+        .world_mut()
+        .spawn(Machine::new(
+            &FURNACE,
+            IVec3::ZERO,
+            crate::components::Direction::North,
+        ))
+        .id();
+    app.world_mut().resource_mut::<InteractingMachine>().0 = Some(furnace);
+    app.world_mut()
+        .resource_mut::<CursorController>()
+        .request_ui(UIContext::Machine(furnace));
+
+    // Still there: the UI stays open
+    app.update();
+    assert_eq!(
+        app.world().resource::<InteractingMachine>().0,
+        Some(furnace)
+    );
+    assert_eq!(
+        app.world().get::<CursorOptions>(window).unwrap().grab_mode,
+        CursorGrabMode::None
+    );
+
+    app.world_mut().despawn(furnace);
+    app.update();
+
+    assert_eq!(app.world().resource::<InteractingMachine>().0, None);
+    assert_eq!(app.world().get::<Visibility>(ui), Some(&Visibility::Hidden));
+    assert!(app.world().resource::<CursorController>().is_locked());
+    assert_eq!(
+        app.world().get::<CursorOptions>(window).unwrap().grab_mode,
+        CursorGrabMode::Confined
+    );
+    assert!(app.world().resource::<WrenchToast>().remaining > 0.0);
+}
+
 #[test]
 fn test_cleanup_preserves_valid_interacting_machine() {
     use crate::components::InteractingMachine;
@@ -197,7 +270,8 @@ fn test_cleanup_preserves_valid_interacting_machine() {
 #[test]
 fn test_input_state_transitions_on_machine_despawn() {
     use crate::components::{
-        CommandInputState, CursorLockState, InputState, InteractingMachine, InventoryOpen,
+        CommandInputState, CursorController, InputState, InteractingMachine, InventoryOpen,
+        UIContext,
     };
 
     // Test that InputState correctly transitions from MachineUI to Gameplay
@@ -207,7 +281,7 @@ fn test_input_state_transitions_on_machine_despawn() {
     app.init_resource::<InteractingMachine>();
     app.init_resource::<InventoryOpen>();
     app.init_resource::<CommandInputState>();
-    app.init_resource::<CursorLockState>();
+    app.init_resource::<CursorController>();
 
     // Create a machine entity
     let machine = app
@@ -219,24 +293,21 @@ fn test_input_state_transitions_on_machine_despawn() {
         ))
         .id();
 
-    // Simulate opening machine UI (sets both InteractingMachine and paused)
+    // Simulate opening machine UI (sets InteractingMachine and hands over the cursor)
     // This mirrors sync_legacy_ui_state behavior for UIContext::Machine
     app.world_mut().resource_mut::<InteractingMachine>().0 = Some(machine);
-    app.world_mut().resource_mut::<CursorLockState>().paused = true;
+    app.world_mut()
+        .resource_mut::<CursorController>()
+        .request_ui(UIContext::Machine(machine));
 
     // Check InputState - should be MachineUI
     {
         let inventory_open = app.world().resource::<InventoryOpen>();
         let interacting_machine = app.world().resource::<InteractingMachine>();
         let command_state = app.world().resource::<CommandInputState>();
-        let cursor_state = app.world().resource::<CursorLockState>();
+        let cursor = app.world().resource::<CursorController>();
 
-        let state = InputState::current(
-            inventory_open,
-            interacting_machine,
-            command_state,
-            cursor_state,
-        );
+        let state = InputState::current(inventory_open, interacting_machine, command_state, cursor);
 
         assert_eq!(state, InputState::MachineUI);
     }
@@ -254,7 +325,9 @@ fn test_input_state_transitions_on_machine_despawn() {
 
     if !machine_exists {
         app.world_mut().resource_mut::<InteractingMachine>().0 = None;
-        app.world_mut().resource_mut::<CursorLockState>().paused = false;
+        app.world_mut()
+            .resource_mut::<CursorController>()
+            .release_ui(&UIContext::Machine(machine));
     }
 
     // Check InputState - should be Gameplay now
@@ -262,15 +335,687 @@ fn test_input_state_transitions_on_machine_despawn() {
         let inventory_open = app.world().resource::<InventoryOpen>();
         let interacting_machine = app.world().resource::<InteractingMachine>();
         let command_state = app.world().resource::<CommandInputState>();
-        let cursor_state = app.world().resource::<CursorLockState>();
+        let cursor = app.world().resource::<CursorController>();
 
-        let state = InputState::current(
-            inventory_open,
-            interacting_machine,
-            command_state,
-            cursor_state,
-        );
+        let state = InputState::current(inventory_open, interacting_machine, command_state, cursor);
 
         assert_eq!(state, InputState::Gameplay);
     }
 }
+
+fn machine_tick_app() -> App {
+    use crate::components::CurrentQuest;
+    use crate::events::game_events::{MachineCompleted, MachineStarted};
+    use crate::events::{EventDepth, EventSystemConfig};
+    use crate::machines::generic::generic_machine_tick;
+    use crate::world::biome::BiomeMap;
+
+    let mut app = App::new();
+    app.init_resource::<BiomeMap>()
+        .init_resource::<CurrentQuest>()
+        .init_resource::<EventDepth>()
+        .init_resource::<EventSystemConfig>()
+        .add_message::<MachineStarted>()
+        .add_message::<MachineCompleted>()
+        .add_systems(Update, generic_machine_tick);
+    app
+}
+
+/// Run `n` simulation ticks
+fn tick(app: &mut App, n: u32) {
+    for _ in 0..n {
+        app.update();
+    }
+}
+
+#[test]
+fn test_disabled_furnace_freezes_progress() {
+    let mut app = machine_tick_app();
+
+    let mut furnace = Machine::new(
+        &FURNACE,
+        IVec3::new(0, 8, 0),
+        crate::components::Direction::North,
+    );
+    furnace.slots.fuel = 10;
+    furnace.slots.inputs[0].add_id(items::iron_ore(), 5);
+    let entity = app.world_mut().spawn(furnace).id();
+
+    // Smelt partway
+    tick(&mut app, 10);
+    let (progress, fuel) = {
+        let machine = app.world().get::<Machine>(entity).unwrap();
+        (machine.progress, machine.slots.fuel)
+    };
+    assert!(progress > 0.0 && progress < 1.0, "progress = {progress}");
+
+    // Disable mid-smelt: nothing moves
+    app.world_mut().get_mut::<Machine>(entity).unwrap().enabled = false;
+    tick(&mut app, 100);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert_eq!(machine.progress, progress);
+    assert_eq!(machine.slots.fuel, fuel);
+    assert!(machine.slots.outputs[0].is_empty());
+
+    // Re-enable: resumes from the frozen value
+    app.world_mut().get_mut::<Machine>(entity).unwrap().enabled = true;
+    tick(&mut app, 2);
+    let resumed = app.world().get::<Machine>(entity).unwrap().progress;
+    assert!(
+        resumed > progress,
+        "{resumed} should continue from {progress}"
+    );
+    assert!(
+        resumed < progress + 0.5,
+        "{resumed} should not restart or jump"
+    );
+}
+
+#[test]
+fn test_miner_stacks_items_on_slow_belt() {
+    use crate::components::{Conveyor, ConveyorShape, Direction};
+    use crate::constants::CONVEYOR_MAX_STACK;
+
+    let mut app = machine_tick_app();
+
+    let mut miner = Machine::new(&MINER, IVec3::new(0, 8, 0), Direction::North);
+    miner.slots.outputs[0].add_id(items::iron_ore(), 6);
+    let belt_pos = miner.output_position();
+    let machine = app.world_mut().spawn(miner).id();
+    let belt = app
+        .world_mut()
+        .spawn(Conveyor {
+            position: belt_pos,
+            direction: Direction::North,
+            output_direction: Direction::North,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        })
+        .id();
+
+    // The belt doesn't move here, so every output lands near the entry
+    tick(&mut app, 6);
+    let conveyor = app.world().get::<Conveyor>(belt).unwrap();
+    assert_eq!(conveyor.items.len(), 1, "outputs merge into one stack");
+    assert_eq!(conveyor.items[0].count, CONVEYOR_MAX_STACK);
+    // Full stack at the entry blocks further output
+    let left = app.world().get::<Machine>(machine).unwrap().slots.outputs[0].count;
+    assert_eq!(left, 6 - CONVEYOR_MAX_STACK);
+
+    // Once the stack moves on, a new one starts
+    app.world_mut().get_mut::<Conveyor>(belt).unwrap().items[0].progress = 0.6;
+    tick(&mut app, 1);
+    let conveyor = app.world().get::<Conveyor>(belt).unwrap();
+    assert_eq!(conveyor.items.len(), 2);
+    assert_eq!(conveyor.total_item_count(), CONVEYOR_MAX_STACK + 1);
+}
+
+#[test]
+fn test_miner_outputs_to_facing_side_first() {
+    use crate::components::{Conveyor, ConveyorShape, Direction};
+    use crate::constants::CONVEYOR_MAX_STACK;
+    use crate::game_spec::PortSide;
+
+    fn belt(position: IVec3, direction: Direction) -> Conveyor {
+        Conveyor {
+            position,
+            direction,
+            output_direction: direction,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    let mut app = machine_tick_app();
+
+    // Belts leading away on the facing (north) side and the right side
+    let mut miner = Machine::new(&MINER, IVec3::new(0, 8, 0), Direction::North);
+    miner.slots.outputs[0].add_id(items::iron_ore(), 20);
+    let front = belt(miner.output_position(), Direction::North);
+    let side = belt(
+        miner.port_position(PortSide::Right),
+        Direction::North.right(),
+    );
+    let machine = app.world_mut().spawn(miner).id();
+    let front = app.world_mut().spawn(front).id();
+    let side = app.world_mut().spawn(side).id();
+
+    // Everything goes to the facing belt until its entry stack is full
+    tick(&mut app, 10);
+    let count = |app: &App, belt: Entity| {
+        app.world()
+            .get::<Conveyor>(belt)
+            .unwrap()
+            .total_item_count()
+    };
+    assert_eq!(count(&app, front), CONVEYOR_MAX_STACK);
+    assert_eq!(
+        count(&app, side),
+        0,
+        "facing-only miners never use the side"
+    );
+
+    // With any-side output the backed-up miner spills onto the side belt
+    app.world_mut()
+        .get_mut::<Machine>(machine)
+        .unwrap()
+        .output_any_side = true;
+    tick(&mut app, 1);
+    assert_eq!(count(&app, front), CONVEYOR_MAX_STACK);
+    assert_eq!(count(&app, side), 1);
+}
+
+#[test]
+fn test_fluid_recipes_go_through_adjacent_tank() {
+    use crate::components::{Direction, MachineStatus};
+    use crate::core::fluids;
+    use crate::fluids::{FluidNetworks, TANK_CAPACITY};
+    use crate::test_support::{register_fluid_recipes, FLUID_TEST_MACHINE};
+
+    register_fluid_recipes();
+    let mut app = machine_tick_app();
+    let tank = IVec3::new(1, 8, 0);
+    let mut networks = FluidNetworks::default();
+    networks.add_block(tank, TANK_CAPACITY);
+    app.insert_resource(networks);
+    let water = |app: &App| {
+        let network = app.world().resource::<FluidNetworks>().network_at(tank);
+        network.map_or(0.0, |n| n.amount)
+    };
+
+    // test_wash_stone: 4 stone + 100 water → 1 iron dust, waits while the tank is dry
+    let mut washer = Machine::new(&FLUID_TEST_MACHINE, IVec3::new(2, 8, 0), Direction::North);
+    washer.slots.inputs[0].add_id(items::stone(), 4);
+    let washer = app.world_mut().spawn(washer).id();
+    tick(&mut app, 1);
+    let machine = app.world().get::<Machine>(washer).unwrap();
+    assert_eq!(machine.status, MachineStatus::NoFluid);
+    assert_eq!(machine.progress, 0.0);
+
+    // test_grass_to_water: 2 grass → 100 water every 2s (40 ticks)
+    let mut grinder = Machine::new(&FLUID_TEST_MACHINE, IVec3::new(0, 8, 0), Direction::North);
+    grinder.slots.inputs[0].add_id(items::grass(), 4);
+    let grinder = app.world_mut().spawn(grinder).id();
+    tick(&mut app, 45);
+    assert_eq!(water(&app), 100.0);
+    let machine = app.world().get::<Machine>(grinder).unwrap();
+    assert!(machine.slots.outputs[0].is_empty());
+
+    // Second batch of water lands before the 3s wash takes the first
+    tick(&mut app, 70);
+    assert_eq!(water(&app), 100.0);
+    let machine = app.world().get::<Machine>(washer).unwrap();
+    assert_eq!(machine.slots.inputs[0].count, 0);
+    assert_eq!(machine.slots.outputs[0].item_id, Some(items::iron_dust()));
+    assert_eq!(machine.slots.outputs[0].count, 1);
+    let tank_fluid = app.world().resource::<FluidNetworks>().network_at(tank);
+    assert_eq!(tank_fluid.unwrap().fluid, Some(fluids::water()));
+}
+
+#[test]
+fn test_recycler_returns_half_of_two_ingredient_recipe() {
+    use crate::components::Direction;
+    use crate::game_spec::RECYCLER;
+
+    let mut app = machine_tick_app();
+
+    // craft_crusher: 10 iron ingot + 5 copper ingot, 8s → recycled in 4s (80 ticks)
+    let mut recycler = Machine::new(&RECYCLER, IVec3::new(0, 8, 0), Direction::North);
+    assert_eq!(recycler.slots.outputs.len(), 4);
+    recycler.slots.inputs[0].add_id(items::crusher_block(), 2);
+    let entity = app.world_mut().spawn(recycler).id();
+
+    tick(&mut app, 70);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert!(machine.slots.outputs[0].is_empty(), "not done before 4s");
+
+    tick(&mut app, 15);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert_eq!(machine.slots.inputs[0].count, 1);
+    assert_eq!(machine.slots.outputs[0].item_id, Some(items::iron_ingot()));
+    assert_eq!(machine.slots.outputs[0].count, 5);
+    assert_eq!(
+        machine.slots.outputs[1].item_id,
+        Some(items::copper_ingot())
+    );
+    assert_eq!(machine.slots.outputs[1].count, 2);
+    assert!(machine.slots.outputs[2].is_empty());
+}
+
+#[test]
+fn test_recycler_outputs_round_robin() {
+    use crate::components::{Conveyor, ConveyorShape, Direction};
+    use crate::game_spec::{PortSide, RECYCLER};
+
+    fn belt(position: IVec3, direction: Direction) -> Conveyor {
+        Conveyor {
+            position,
+            direction,
+            output_direction: direction,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    let mut app = machine_tick_app();
+
+    let mut recycler = Machine::new(&RECYCLER, IVec3::new(0, 8, 0), Direction::North);
+    recycler.slots.outputs[0].add_id(items::iron_ingot(), 4);
+    let front = belt(recycler.output_position(), Direction::North);
+    let side = belt(recycler.port_position(PortSide::Right), Direction::East);
+    // A belt feeding the machine never receives output
+    let feeder = belt(recycler.input_position(), Direction::North);
+    app.world_mut().spawn(recycler);
+    let front = app.world_mut().spawn(front).id();
+    let side = app.world_mut().spawn(side).id();
+    let feeder = app.world_mut().spawn(feeder).id();
+
+    tick(&mut app, 4);
+    let count = |app: &App, belt: Entity| {
+        app.world()
+            .get::<Conveyor>(belt)
+            .unwrap()
+            .total_item_count()
+    };
+    assert_eq!(count(&app, front), 2);
+    assert_eq!(count(&app, side), 2);
+    assert_eq!(count(&app, feeder), 0);
+}
+
+#[test]
+fn test_miner_status() {
+    use crate::components::MachineStatus;
+    use crate::machines::generic::status::miner_status;
+
+    assert_eq!(miner_status(true, false), MachineStatus::Working);
+    assert_eq!(miner_status(false, false), MachineStatus::NoValidTarget);
+    assert_eq!(miner_status(true, true), MachineStatus::OutputFull);
+    // A full buffer is the more urgent fix
+    assert_eq!(miner_status(false, true), MachineStatus::OutputFull);
+}
+
+#[test]
+fn test_recipe_status_failure_combinations() {
+    use crate::components::MachineStatus;
+    use crate::machines::generic::status::{recipe_status, RecipeCheck};
+
+    let ready = RecipeCheck {
+        has_input: true,
+        has_recipe: true,
+        out_of_fuel: false,
+        enough_input: true,
+        fluid_ready: true,
+        output_fits: true,
+    };
+    assert_eq!(recipe_status(&ready), MachineStatus::Working);
+
+    let empty = RecipeCheck::default();
+    assert_eq!(recipe_status(&empty), MachineStatus::NoInput);
+    let no_recipe = RecipeCheck {
+        has_recipe: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&no_recipe), MachineStatus::NoValidTarget);
+    let no_fuel = RecipeCheck {
+        out_of_fuel: true,
+        ..ready
+    };
+    assert_eq!(recipe_status(&no_fuel), MachineStatus::NoFuel);
+    let too_few = RecipeCheck {
+        enough_input: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&too_few), MachineStatus::NoInput);
+    let dry = RecipeCheck {
+        fluid_ready: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&dry), MachineStatus::NoFluid);
+    let full = RecipeCheck {
+        output_fits: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&full), MachineStatus::OutputFull);
+
+    // Several problems: report what blocks the craft first
+    let no_fuel_and_full = RecipeCheck {
+        out_of_fuel: true,
+        output_fits: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&no_fuel_and_full), MachineStatus::NoFuel);
+    let too_few_and_full = RecipeCheck {
+        enough_input: false,
+        output_fits: false,
+        ..ready
+    };
+    assert_eq!(recipe_status(&too_few_and_full), MachineStatus::NoInput);
+    let empty_without_fuel = RecipeCheck {
+        out_of_fuel: true,
+        ..empty
+    };
+    assert_eq!(recipe_status(&empty_without_fuel), MachineStatus::NoInput);
+}
+
+#[test]
+fn test_recycler_status_ignores_fuel() {
+    use crate::components::MachineStatus;
+    use crate::machines::generic::status::{recycler_status, RecipeCheck};
+
+    let check = RecipeCheck {
+        has_input: true,
+        has_recipe: true,
+        out_of_fuel: true,
+        enough_input: true,
+        output_fits: true,
+    };
+    assert_eq!(recycler_status(&check), MachineStatus::Working);
+    let full = RecipeCheck {
+        output_fits: false,
+        ..check
+    };
+    assert_eq!(recycler_status(&full), MachineStatus::OutputFull);
+}
+
+#[test]
+fn test_furnace_status_follows_tick() {
+    use crate::components::{Direction, MachineStatus};
+
+    let mut app = machine_tick_app();
+    let entity = app
+        .world_mut()
+        .spawn(Machine::new(
+            &FURNACE,
+            IVec3::new(0, 8, 0),
+            Direction::North,
+        ))
+        .id();
+    let status = |app: &App| app.world().get::<Machine>(entity).unwrap().status;
+
+    tick(&mut app, 1);
+    assert_eq!(status(&app), MachineStatus::NoInput);
+
+    app.world_mut()
+        .get_mut::<Machine>(entity)
+        .unwrap()
+        .slots
+        .inputs[0]
+        .add_id(items::iron_ore(), 5);
+    tick(&mut app, 1);
+    assert_eq!(status(&app), MachineStatus::NoFuel);
+
+    app.world_mut()
+        .get_mut::<Machine>(entity)
+        .unwrap()
+        .slots
+        .fuel = 10;
+    tick(&mut app, 1);
+    assert_eq!(status(&app), MachineStatus::Working);
+
+    app.world_mut().get_mut::<Machine>(entity).unwrap().enabled = false;
+    tick(&mut app, 1);
+    assert_eq!(status(&app), MachineStatus::Disabled);
+
+    {
+        let world = app.world_mut();
+        let mut machine = world.get_mut::<Machine>(entity).unwrap();
+        machine.enabled = true;
+        machine.slots.outputs[0].add_id(items::iron_ingot(), FURNACE.buffer_size);
+    }
+    tick(&mut app, 1);
+    assert_eq!(status(&app), MachineStatus::OutputFull);
+}
+
+#[test]
+fn test_status_indicator_follows_machine() {
+    use crate::components::{Direction, MachineStatus, MachineStatusIndicator, Player};
+    use crate::machines::generic::{
+        update_machine_status_indicators, MachineStatusIndicatorAssets,
+    };
+
+    let mut app = App::new();
+    app.init_resource::<Time>()
+        .init_resource::<MachineStatusIndicatorAssets>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .add_systems(Update, update_machine_status_indicators);
+    app.world_mut()
+        .spawn((Player, Transform::from_xyz(0.0, 9.0, 0.0)));
+
+    let mut furnace = Machine::new(&FURNACE, IVec3::new(3, 8, 0), Direction::North);
+    furnace.status = MachineStatus::NoFuel;
+    let entity = app.world_mut().spawn(furnace).id();
+    // Far away: no indicator
+    let mut far = Machine::new(&FURNACE, IVec3::new(60, 8, 0), Direction::North);
+    far.status = MachineStatus::NoFuel;
+    app.world_mut().spawn(far);
+    // Working: no indicator
+    app.world_mut().spawn(Machine::new(
+        &FURNACE,
+        IVec3::new(0, 8, 3),
+        Direction::North,
+    ));
+
+    let indicators = |app: &mut App| -> Vec<Entity> {
+        app.world_mut()
+            .query::<&MachineStatusIndicator>()
+            .iter(app.world())
+            .map(|indicator| indicator.0)
+            .collect()
+    };
+
+    app.update();
+    assert_eq!(indicators(&mut app), vec![entity]);
+
+    // Running again removes it
+    app.world_mut().get_mut::<Machine>(entity).unwrap().status = MachineStatus::Working;
+    app.update();
+    assert!(indicators(&mut app).is_empty());
+
+    // Breaking a stalled machine removes its indicator
+    app.world_mut().get_mut::<Machine>(entity).unwrap().status = MachineStatus::OutputFull;
+    app.update();
+    assert_eq!(indicators(&mut app).len(), 1);
+    app.world_mut().despawn(entity);
+    app.update();
+    assert!(indicators(&mut app).is_empty());
+}
+
+#[test]
+fn test_drill_tier_limits_biome_output() {
+    use crate::game_spec::drill_spec::DrillTier;
+    use crate::machines::generic::auto_generate::get_biome_output_for;
+    use crate::world::biome::BiomeType;
+
+    assert!(DrillTier::Stone.can_mine(items::coal()));
+    assert!(!DrillTier::Stone.can_mine(items::iron_ore()));
+    assert!(DrillTier::Iron.can_mine(items::iron_ore()));
+    assert!(!DrillTier::Iron.can_mine(items::copper_ore()));
+    assert!(DrillTier::Steel.can_mine(items::copper_ore()));
+
+    // A stone drill in a mixed biome only ever brings up stone and coal
+    for tick in 0..200 {
+        let mined =
+            get_biome_output_for(BiomeType::Mixed, tick, |id| DrillTier::Stone.can_mine(id));
+        assert!(
+            mined == items::stone() || mined == items::coal(),
+            "tick {tick}: {:?}",
+            mined.name()
+        );
+    }
+}
+
+#[test]
+fn test_miner_needs_drill_and_wears_it_out() {
+    use crate::components::{Direction, DrillHead, MachineStatus};
+
+    let mut app = machine_tick_app();
+
+    // Spawn center is always a mixed biome
+    let miner = Machine::new(&MINER, IVec3::new(26, 8, 16), Direction::North);
+    assert!(miner.slots.drill.is_none());
+    let entity = app.world_mut().spawn(miner).id();
+
+    // No drill: nothing is mined
+    tick(&mut app, 200);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert_eq!(machine.status, MachineStatus::NoValidTarget);
+    assert!(machine.slots.outputs[0].is_empty());
+
+    // A drill with one use left mines one item, then breaks
+    let mut drill = DrillHead::new(items::stone_drill()).unwrap();
+    drill.durability = 1;
+    app.world_mut()
+        .get_mut::<Machine>(entity)
+        .unwrap()
+        .slots
+        .drill = Some(drill);
+    tick(&mut app, 200);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert_eq!(machine.slots.outputs[0].count, 1);
+    assert!(machine.slots.drill.is_none());
+    assert_eq!(machine.status, MachineStatus::NoValidTarget);
+}
+
+#[test]
+fn test_install_drill_returns_only_unused_heads() {
+    use crate::components::Direction;
+
+    let mut miner = Machine::new(&MINER, IVec3::ZERO, Direction::North);
+    assert_eq!(miner.install_drill(items::stone_drill()), None);
+
+    // A fresh head comes back when swapped out
+    assert_eq!(
+        miner.install_drill(items::iron_drill()),
+        Some(items::stone_drill())
+    );
+
+    // A used head is scrapped instead
+    miner.slots.drill.as_mut().unwrap().wear();
+    assert_eq!(miner.install_drill(items::steel_drill()), None);
+    assert_eq!(miner.take_unused_drill(), Some(items::steel_drill()));
+    assert!(miner.slots.drill.is_none());
+}
+
+#[test]
+fn test_idle_furnaces_sleep_until_fed() {
+    use crate::components::{Direction, Idle, MachineSleepStats};
+    use crate::machines::generic::{generic_machine_tick, update_machine_sleep};
+
+    let mut app = machine_tick_app();
+    app.init_resource::<MachineSleepStats>()
+        .add_systems(Update, update_machine_sleep.after(generic_machine_tick));
+
+    let furnaces: Vec<Entity> = (0..1000)
+        .map(|i| {
+            let pos = IVec3::new(i % 100 * 2, 8, i / 100 * 2);
+            app.world_mut()
+                .spawn(Machine::new(&FURNACE, pos, Direction::North))
+                .id()
+        })
+        .collect();
+
+    // One tick finds them empty, then they are all put to sleep
+    tick(&mut app, 2);
+    let stats = app.world().resource::<MachineSleepStats>();
+    assert_eq!((stats.active, stats.sleeping), (0, 1000));
+
+    // Sleeping furnaces aren't touched by the tick at all
+    let last_changed = |app: &App, entity: Entity| {
+        app.world()
+            .entity(entity)
+            .get_ref::<Machine>()
+            .unwrap()
+            .last_changed()
+    };
+    let before: Vec<_> = furnaces.iter().map(|&e| last_changed(&app, e)).collect();
+    tick(&mut app, 100);
+    let after: Vec<_> = furnaces.iter().map(|&e| last_changed(&app, e)).collect();
+    assert_eq!(before, after);
+
+    // Feeding one wakes only that one, and it starts smelting
+    {
+        let mut fed = app.world_mut().get_mut::<Machine>(furnaces[0]).unwrap();
+        fed.slots.fuel = 5;
+        fed.slots.inputs[0].add_id(items::iron_ore(), 1);
+    }
+    tick(&mut app, 3);
+    assert!(app.world().get::<Idle>(furnaces[0]).is_none());
+    assert!(app.world().get::<Machine>(furnaces[0]).unwrap().progress > 0.0);
+    let stats = app.world().resource::<MachineSleepStats>();
+    assert_eq!((stats.active, stats.sleeping), (1, 999));
+}
+
+#[test]
+fn test_data_defined_grinder_processes_end_to_end() {
+    use crate::components::Direction;
+    use crate::game_spec::registry::{integrate_mod_items, integrate_mod_machines};
+    use crate::game_spec::{get_machine_spec_by_id, GameRegistry};
+    use crate::modding::data::{ItemDefinition, MachineDefinition, ModDataPack, RecipeDefinition};
+    use crate::modding::LoadedModData;
+
+    // Fixture pack: a grinder and its recipe exist only as data
+    let mut grinder_item = ItemDefinition::new("grinder", "Fixture Grinder");
+    grinder_item.category = "machine".to_string();
+    grinder_item.is_placeable = true;
+    let mut grinder = MachineDefinition::new("grinder", "Fixture Grinder");
+    grinder.work_type = "fixture_grinding".to_string();
+    grinder.process_time = 0.5;
+    let pack = ModDataPack {
+        items: vec![grinder_item, ItemDefinition::new("grit", "Fixture Grit")],
+        machines: vec![grinder],
+        recipes: vec![
+            RecipeDefinition::new("fixture_grind_stone", "fixture_grinding")
+                .with_input("stone", 2)
+                .with_output("grit", 1),
+        ],
+    };
+
+    let mut registry_app = App::new();
+    registry_app
+        .init_resource::<GameRegistry>()
+        .insert_resource(LoadedModData {
+            packs: vec![("test_data_machine".to_string(), pack)],
+        })
+        .add_systems(
+            Update,
+            (integrate_mod_items, integrate_mod_machines).chain(),
+        );
+    registry_app.update();
+
+    let grinder_id = items::by_string_id("test_data_machine:grinder").unwrap();
+    let grit = items::by_string_id("test_data_machine:grit").unwrap();
+    assert!(grinder_id.is_machine());
+    let registry = registry_app.world().resource::<GameRegistry>();
+    assert!(registry.machine(grinder_id).is_some());
+    let spec = get_machine_spec_by_id(grinder_id).unwrap();
+    assert_eq!(spec.item_id(), grinder_id);
+
+    // Belt at the back feeds recipe inputs only
+    let pos = IVec3::new(0, 8, 0);
+    let mut machine = Machine::new(spec, pos, Direction::North);
+    let back = pos + Direction::North.opposite().to_ivec3();
+    assert_eq!(machine.insert_from(items::iron_ore(), 1, back), 0);
+    assert_eq!(machine.insert_from(items::stone(), 4, back), 4);
+
+    // 0.5s per craft (machine's base time): two crafts in 25 ticks
+    let mut app = machine_tick_app();
+    let entity = app.world_mut().spawn(machine).id();
+    tick(&mut app, 25);
+    let machine = app.world().get::<Machine>(entity).unwrap();
+    assert!(machine.slots.inputs[0].is_empty());
+    assert_eq!(machine.slots.outputs[0].item_id, Some(grit));
+    assert_eq!(machine.slots.outputs[0].count, 2);
+}
@@ -1,10 +1,13 @@
 //! Main tick system for generic machines
 
-use crate::components::Machine;
+use crate::components::{CurrentQuest, Idle, Machine, MachineStatus};
+use crate::constants::SIMULATION_TICK_SECS;
 use crate::core::ItemId;
 use crate::events::game_events::{MachineCompleted, MachineStarted};
 use crate::events::GuardedMessageWriter;
+use crate::fluids::FluidNetworks;
 use crate::game_spec::ProcessType;
+use crate::player::{LocalPlatformInventory, PlatformInventory};
 use crate::world::biome::BiomeMap;
 use crate::Conveyor;
 use bevy::prelude::*;
@@ -12,17 +15,36 @@ use std::collections::HashMap;
 
 use super::auto_generate::tick_auto_generate;
 use super::recipe::tick_recipe;
+use super::recycle::tick_recycle;
 
 /// Generic machine tick system - processes all Machine components
+///
+/// Runs in FixedUpdate and advances every machine by one simulation tick.
+/// Miners go first so processors see this tick's output.
+///
+/// All machine output to belts happens here, one machine at a time, writing
+/// straight into the `Conveyor` components. A machine's spacing check therefore
+/// sees every item inserted earlier in the same tick, so two machines flanking
+/// one belt cell can't both place an item at the same spot. Machines are
+/// ordered by position (not entity order) so who wins a contested belt is the
+/// same after a save/load.
+///
+/// Sleeping machines (`Idle`, see `update_machine_sleep`) are skipped.
 pub fn generic_machine_tick(
-    time: Res<Time>,
     biome_map: Res<BiomeMap>,
-    mut machine_query: Query<(Entity, &mut Machine)>,
+    mut machine_query: Query<(Entity, &mut Machine), Without<Idle>>,
     mut conveyor_query: Query<(Entity, &mut Conveyor)>,
     mut started_events: GuardedMessageWriter<MachineStarted>,
     mut completed_events: GuardedMessageWriter<MachineCompleted>,
+    current_quest: Res<CurrentQuest>,
+    platform_inventory: LocalPlatformInventory,
+    mut fluid_networks: Option<ResMut<FluidNetworks>>,
 ) {
-    let delta = time.delta_secs();
+    let delta = SIMULATION_TICK_SECS;
+
+    // Recipe unlock conditions check delivered items (none without a platform)
+    let no_platform = PlatformInventory::new();
+    let platform = platform_inventory.get().unwrap_or(&no_platform);
 
     // Build conveyor position map for O(1) lookup
     let conveyor_map: HashMap<IVec3, Entity> = conveyor_query
@@ -34,7 +56,23 @@ pub fn generic_machine_tick(
     let mut started: Vec<(Entity, Vec<(ItemId, u32)>)> = Vec::new();
     let mut completed: Vec<(Entity, Vec<(ItemId, u32)>)> = Vec::new();
 
-    for (entity, mut machine) in machine_query.iter_mut() {
+    // Miners first, then processors; by position within each group
+    let mut ordered: Vec<_> = machine_query.iter_mut().collect();
+    ordered.sort_by_key(|(_, machine)| {
+        (
+            !matches!(machine.spec.process_type, ProcessType::AutoGenerate),
+            machine.position.to_array(),
+        )
+    });
+
+    for (entity, mut machine) in ordered {
+        // Disabled machines keep their progress and slots untouched
+        if !machine.enabled {
+            if machine.status != MachineStatus::Disabled {
+                machine.status = MachineStatus::Disabled;
+            }
+            continue;
+        }
         match machine.spec.process_type {
             ProcessType::AutoGenerate => {
                 let result = tick_auto_generate(
@@ -53,8 +91,10 @@ pub fn generic_machine_tick(
                     &mut machine,
                     delta,
                     machine_type,
+                    (&current_quest, platform),
                     &conveyor_map,
                     &mut conveyor_query,
+                    fluid_networks.as_mut(),
                 );
                 if let Some((started_inputs, completed_outputs)) = result {
                     if let Some(inputs) = started_inputs {
@@ -65,6 +105,20 @@ pub fn generic_machine_tick(
                     }
                 }
             }
+            ProcessType::Recycle => {
+                let result = tick_recycle(&mut machine, delta, &conveyor_map, &mut conveyor_query);
+                if let Some((started_inputs, completed_outputs)) = result {
+                    if let Some(inputs) = started_inputs {
+                        started.push((entity, inputs));
+                    }
+                    if let Some(outputs) = completed_outputs {
+                        completed.push((entity, outputs));
+                    }
+                }
+            }
+            ProcessType::Hopper => {
+                // Needs the target machine too, see hopper_tick
+            }
             ProcessType::Transfer => {
                 // Conveyors are handled separately
             }
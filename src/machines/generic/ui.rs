@@ -1,19 +1,64 @@
 //! Generic machine UI systems
 
 use crate::components::{
-    GenericMachineProgressBar, GenericMachineSlotButton, GenericMachineSlotCount,
-    InteractingMachine, Machine, MachineSlot,
+    GenericMachineActivityBar, GenericMachineActivityText, GenericMachineDrillBar,
+    GenericMachineDrillButton, GenericMachineDrillText, GenericMachineEnableButton,
+    GenericMachineEnableText, GenericMachineFacingText, GenericMachineOutputSideButton,
+    GenericMachineOutputSideText, GenericMachineProgressBar, GenericMachineSlotButton,
+    GenericMachineSlotCount, GenericMachineSlotRouteButton, GenericMachineSlotRouteText,
+    GenericMachineStatusText, InteractingMachine, Machine, MachineActivity, MachineSlot, SlotRoute,
+    ACTIVITY_SAMPLES,
 };
 use crate::core::items;
+use crate::game_spec::ProcessType;
+use crate::graphics::BlockPalette;
 use crate::player::{LocalPlayer, PlayerInventory};
 use bevy::prelude::*;
 
-/// Update generic machine UI slot counts and progress bar
+/// Update generic machine UI slot counts, progress bar, facing and toggles
+#[allow(clippy::type_complexity)]
 pub fn update_generic_machine_ui(
     interacting: Res<InteractingMachine>,
     machine_query: Query<&Machine>,
     mut slot_count_query: Query<(&GenericMachineSlotCount, &mut Text)>,
     mut progress_bar_query: Query<&mut Node, With<GenericMachineProgressBar>>,
+    mut facing_query: Query<
+        &mut Text,
+        (
+            With<GenericMachineFacingText>,
+            Without<GenericMachineSlotCount>,
+            Without<GenericMachineStatusText>,
+            Without<GenericMachineEnableText>,
+            Without<GenericMachineOutputSideText>,
+        ),
+    >,
+    mut enable_query: Query<
+        &mut Text,
+        (
+            With<GenericMachineEnableText>,
+            Without<GenericMachineSlotCount>,
+            Without<GenericMachineStatusText>,
+            Without<GenericMachineFacingText>,
+            Without<GenericMachineOutputSideText>,
+        ),
+    >,
+    mut output_side_query: Query<
+        &mut Text,
+        (
+            With<GenericMachineOutputSideText>,
+            Without<GenericMachineSlotCount>,
+            Without<GenericMachineStatusText>,
+            Without<GenericMachineFacingText>,
+            Without<GenericMachineEnableText>,
+        ),
+    >,
+    mut status_query: Query<
+        &mut Text,
+        (
+            With<GenericMachineStatusText>,
+            Without<GenericMachineSlotCount>,
+        ),
+    >,
 ) {
     let Some(entity) = interacting.0 else {
         return;
@@ -49,6 +94,158 @@ pub fn update_generic_machine_ui(
     for mut node in progress_bar_query.iter_mut() {
         node.width = Val::Percent(machine.progress * 100.0);
     }
+
+    // Update facing (output face)
+    for mut text in facing_query.iter_mut() {
+        **text = format!("出力面: {}", machine.facing.label());
+    }
+
+    // Update status (why a stalled machine isn't running)
+    for mut text in status_query.iter_mut() {
+        let label = format!("状態: {}", machine.status.label());
+        if **text != label {
+            **text = label;
+        }
+    }
+
+    // Update enable toggle label
+    for mut text in enable_query.iter_mut() {
+        **text = if machine.enabled {
+            "稼働中 (クリックで停止)"
+        } else {
+            "停止中 (クリックで再開)"
+        }
+        .to_string();
+    }
+
+    // Update output side toggle label (hoppers: push direction)
+    for mut text in output_side_query.iter_mut() {
+        **text = if machine.spec.process_type == ProcessType::Hopper {
+            match machine.push_side {
+                Some(side) => format!("送り先: {}", side.label()),
+                None => "送り先: 下".to_string(),
+            }
+        } else if machine.output_any_side {
+            "出力: 全方向 (正面優先)".to_string()
+        } else {
+            "出力: 正面のみ".to_string()
+        };
+    }
+}
+
+/// Durability left below which the drill bar turns red
+const DRILL_LOW: f32 = 0.25;
+
+/// Update the miner's drill head slot and durability bar
+pub fn update_generic_machine_drill_ui(
+    interacting: Res<InteractingMachine>,
+    machine_query: Query<&Machine>,
+    mut text_query: Query<&mut Text, With<GenericMachineDrillText>>,
+    mut bar_query: Query<(&mut Node, &mut BackgroundColor), With<GenericMachineDrillBar>>,
+) {
+    let Some(machine) = interacting.0.and_then(|e| machine_query.get(e).ok()) else {
+        return;
+    };
+    let drill = machine.slots.drill;
+
+    for mut text in text_query.iter_mut() {
+        let label = drill
+            .map(|d| d.item_id.short_name().to_string())
+            .unwrap_or_default();
+        if **text != label {
+            **text = label;
+        }
+    }
+
+    let remaining = drill.map(|d| d.remaining()).unwrap_or(0.0);
+    for (mut node, mut color) in bar_query.iter_mut() {
+        node.width = Val::Percent(remaining * 100.0);
+        *color = if remaining < DRILL_LOW {
+            BackgroundColor(Color::srgb(0.9, 0.25, 0.2))
+        } else {
+            BackgroundColor(Color::srgb(0.3, 0.8, 0.4))
+        };
+    }
+}
+
+/// Update the utilization sparkline and its "稼働率: 73% (4分)" label
+pub fn update_generic_machine_activity_ui(
+    interacting: Res<InteractingMachine>,
+    activity_query: Query<&MachineActivity>,
+    mut bar_query: Query<(&GenericMachineActivityBar, &mut Node)>,
+    mut text_query: Query<&mut Text, With<GenericMachineActivityText>>,
+) {
+    let Some(activity) = interacting.0.and_then(|e| activity_query.get(e).ok()) else {
+        return;
+    };
+
+    // Newest sample in the rightmost bar, empty bars until the history fills up
+    let samples: Vec<f32> = activity.samples().collect();
+    let empty = ACTIVITY_SAMPLES - samples.len();
+    for (bar, mut node) in bar_query.iter_mut() {
+        let duty = bar
+            .0
+            .checked_sub(empty)
+            .and_then(|i| samples.get(i))
+            .copied()
+            .unwrap_or(0.0);
+        let height = Val::Percent(duty * 100.0);
+        if node.height != height {
+            node.height = height;
+        }
+    }
+
+    let label = match activity.utilization() {
+        Some(utilization) => format!(
+            "稼働率: {:.0}% ({})",
+            utilization * 100.0,
+            format_span(activity.span_secs())
+        ),
+        None => "稼働率: --".to_string(),
+    };
+    for mut text in text_query.iter_mut() {
+        if **text != label {
+            **text = label.clone();
+        }
+    }
+}
+
+/// "30秒" below a minute, whole minutes above
+fn format_span(secs: f32) -> String {
+    if secs < 60.0 {
+        format!("{:.0}秒", secs)
+    } else {
+        format!("{:.0}分", (secs / 60.0).floor())
+    }
+}
+
+/// Update the slot route button labels ("後: 燃料")
+pub fn update_generic_machine_slot_route_ui(
+    interacting: Res<InteractingMachine>,
+    machine_query: Query<&Machine>,
+    mut text_query: Query<(&GenericMachineSlotRouteText, &mut Text, &mut TextColor)>,
+) {
+    let Some(machine) = interacting.0.and_then(|e| machine_query.get(e).ok()) else {
+        return;
+    };
+
+    for (route_text, mut text, mut color) in text_query.iter_mut() {
+        let route = machine
+            .port_direction(route_text.0)
+            .map(|side| machine.slot_route(side))
+            .unwrap_or_default();
+        let label = format!("{}: {}", route_text.0.label(), route.label());
+        if **text != label {
+            **text = label;
+        }
+        let tint = match route {
+            SlotRoute::Auto => Color::WHITE,
+            routed => routed.color(),
+        };
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
 }
 
 /// Format slot count for display
@@ -158,3 +355,155 @@ pub fn generic_machine_ui_input(
         }
     }
 }
+
+/// Color machine UI slot borders by role from the active palette
+#[allow(clippy::type_complexity)]
+pub fn update_machine_slot_palette(
+    palette: Res<BlockPalette>,
+    mut slot_query: Query<(Ref<GenericMachineSlotButton>, &mut BorderColor)>,
+) {
+    for (slot_btn, mut border) in slot_query.iter_mut() {
+        if palette.is_changed() || slot_btn.is_added() {
+            *border =
+                BorderColor::all(palette.machine_slot_border(slot_btn.is_input, slot_btn.is_fuel));
+        }
+    }
+}
+
+/// Handle the enable/disable and output side toggle buttons in the generic machine UI
+#[allow(clippy::type_complexity)]
+pub fn generic_machine_toggle_input(
+    interacting: Res<InteractingMachine>,
+    mut machine_query: Query<&mut Machine>,
+    mut button_query: Query<
+        (
+            &Interaction,
+            &mut BackgroundColor,
+            Has<GenericMachineOutputSideButton>,
+        ),
+        (
+            Changed<Interaction>,
+            Or<(
+                With<GenericMachineEnableButton>,
+                With<GenericMachineOutputSideButton>,
+            )>,
+        ),
+    >,
+) {
+    let Some(entity) = interacting.0 else {
+        return;
+    };
+
+    for (interaction, mut bg_color, is_output_side) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Ok(mut machine) = machine_query.get_mut(entity) {
+                    if is_output_side && machine.spec.process_type == ProcessType::Hopper {
+                        machine.cycle_push_side();
+                    } else if is_output_side {
+                        machine.output_any_side = !machine.output_any_side;
+                    } else {
+                        machine.enabled = !machine.enabled;
+                    }
+                }
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.25, 0.25, 0.3));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.15, 0.15, 0.2));
+            }
+        }
+    }
+}
+
+/// Cycle a face's slot route (自動 -> 入力 -> 燃料) on button click
+pub fn generic_machine_slot_route_input(
+    interacting: Res<InteractingMachine>,
+    mut machine_query: Query<&mut Machine>,
+    mut button_query: Query<
+        (
+            &Interaction,
+            &GenericMachineSlotRouteButton,
+            &mut BackgroundColor,
+        ),
+        Changed<Interaction>,
+    >,
+) {
+    let Some(entity) = interacting.0 else {
+        return;
+    };
+
+    for (interaction, button, mut bg_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Ok(mut machine) = machine_query.get_mut(entity) {
+                    if let Some(side) = machine.port_direction(button.0) {
+                        machine.cycle_slot_route(side);
+                    }
+                }
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.25, 0.25, 0.3));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.15, 0.15, 0.2));
+            }
+        }
+    }
+}
+
+/// Handle clicks on the miner's drill head slot
+///
+/// With a drill selected: install it (an unused drill it replaces goes back
+/// to the inventory, a worn one is scrapped). Otherwise: take out an unused drill.
+pub fn generic_machine_drill_input(
+    interacting: Res<InteractingMachine>,
+    mut machine_query: Query<&mut Machine>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventory_query: Query<&mut PlayerInventory>,
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<GenericMachineDrillButton>),
+    >,
+) {
+    let Some(entity) = interacting.0 else {
+        return;
+    };
+
+    for (interaction, mut bg_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                let (Ok(mut machine), Some(local_player)) =
+                    (machine_query.get_mut(entity), local_player.as_ref())
+                else {
+                    continue;
+                };
+                let Ok(mut inventory) = inventory_query.get_mut(local_player.0) else {
+                    continue;
+                };
+                let returned = match inventory.selected_item_id() {
+                    Some(selected_id)
+                        if items::is_drill(selected_id)
+                            && inventory.consume_item_by_id(selected_id, 1) =>
+                    {
+                        machine.install_drill(selected_id)
+                    }
+                    _ => machine.take_unused_drill(),
+                };
+                if let Some(item_id) = returned {
+                    inventory.add_item_by_id(item_id, 1);
+                }
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.25, 0.25, 0.3));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.15, 0.15, 0.2));
+            }
+        }
+    }
+}
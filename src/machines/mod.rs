@@ -8,5 +8,9 @@
 //! (discrete item processing).
 
 pub mod generic;
+pub mod simulation;
 
 pub use generic::*;
+pub use simulation::{
+    apply_simulation_speed, handle_tickrate_command, SimulationSpeed, TickRateCommandEvent,
+};
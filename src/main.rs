@@ -9,8 +9,10 @@ use bevy::render::pipelined_rendering::PipelinedRenderingPlugin;
 use bevy::time::Fixed;
 #[cfg(not(target_arch = "wasm32"))]
 use bevy::window::PresentMode;
+use idle_factory::constants::SIMULATION_HZ;
 use idle_factory::logging;
 use idle_factory::plugins::GamePlugin;
+use idle_factory::{HeadlessServerPlugin, NetworkMode, NetworkPlugin};
 
 fn main() {
     // Set up crash handler first (captures panic backtraces to logs/crash.log)
@@ -19,10 +21,17 @@ fn main() {
     // Initialize logging
     let _log_guard = logging::init_logging();
 
+    // --server runs the simulation headless; --connect joins a server
+    let network_mode = NetworkMode::from_args(std::env::args().skip(1));
+    if network_mode.is_server() {
+        run_headless_server(network_mode);
+        return;
+    }
+
     let mut app = App::new();
 
-    // Configure fixed timestep for deterministic game logic (20 ticks/second)
-    app.insert_resource(Time::<Fixed>::from_hz(20.0));
+    // Configure fixed timestep for deterministic game logic (SIMULATION_HZ ticks/second)
+    app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ));
 
     // Configure DefaultPlugins
     #[cfg(not(target_arch = "wasm32"))]
@@ -74,6 +83,26 @@ fn main() {
     // Add main game plugin
     app.add_plugins(GamePlugin);
 
+    // Multiplayer (no-op in single-player)
+    app.add_plugins(NetworkPlugin { mode: network_mode });
+
+    app.run();
+}
+
+/// Dedicated server: world simulation and networking, no window or rendering
+fn run_headless_server(network_mode: NetworkMode) {
+    use bevy::app::ScheduleRunnerPlugin;
+    use std::time::Duration;
+
+    let mut app = App::new();
+    app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ));
+    app.add_plugins(
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        ))),
+    );
+    app.add_plugins(HeadlessServerPlugin);
+    app.add_plugins(NetworkPlugin { mode: network_mode });
     app.run();
 }
 
@@ -249,14 +278,13 @@ mod tests {
 
     #[test]
     fn test_input_state_priority() {
+        let mut locked = CursorController::default();
+        locked.request_lock();
         let state = InputState::current(
             &InventoryOpen(false),
             &InteractingMachine(None),
             &CommandInputState::default(),
-            &CursorLockState {
-                paused: false,
-                ..default()
-            },
+            &locked,
         );
         assert!(matches!(state, InputState::Gameplay));
 
@@ -265,22 +293,26 @@ mod tests {
             &InventoryOpen(true),
             &InteractingMachine(None),
             &CommandInputState::default(),
-            &CursorLockState {
-                paused: true,
-                ..default()
-            },
+            &CursorController::default(),
         );
         assert!(matches!(state, InputState::Inventory));
 
-        // When no UI is open but paused=true, should be Paused
+        // When no UI is open but the cursor is released, should be Paused
         let state = InputState::current(
             &InventoryOpen(false),
             &InteractingMachine(None),
             &CommandInputState::default(),
-            &CursorLockState {
-                paused: true,
-                ..default()
-            },
+            &CursorController::default(),
+        );
+        assert!(matches!(state, InputState::Paused));
+
+        // Losing window focus pauses gameplay input too
+        locked.focus_lost();
+        let state = InputState::current(
+            &InventoryOpen(false),
+            &InteractingMachine(None),
+            &CommandInputState::default(),
+            &locked,
         );
         assert!(matches!(state, InputState::Paused));
     }
@@ -295,8 +327,8 @@ mod tests {
 
     #[test]
     fn test_biome_generation() {
-        let biome1 = ChunkData::get_biome(0, 0);
-        let biome2 = ChunkData::get_biome(0, 0);
+        let biome1 = ChunkData::get_biome(0, 0, 0);
+        let biome2 = ChunkData::get_biome(0, 0, 0);
         assert_eq!(biome1, biome2);
     }
 
@@ -308,8 +340,10 @@ mod tests {
             output_direction: Direction::North,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
         assert!(conveyor.can_accept_item(0.0));
         assert!(conveyor.can_accept_item(0.5));
@@ -323,8 +357,10 @@ mod tests {
             output_direction: Direction::North,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
         conveyor.add_item(items::iron_ore(), 0.5);
         // Item at 0.5, so 0.4 and 0.6 should be too close
@@ -454,8 +490,10 @@ mod tests {
             output_direction: Direction::North,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Splitter,
+            speed_multiplier: 1.0,
         };
         let outputs = conveyor.get_splitter_outputs();
         // front, left, right
@@ -554,8 +592,10 @@ mod tests {
             output_direction: Direction::East,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
 
         // From behind (West) should join at 0.0
@@ -576,8 +616,10 @@ mod tests {
             output_direction: Direction::East,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::TJunction,
+            speed_multiplier: 1.0,
         };
 
         // From North side should join at 0.5 with lateral offset
@@ -605,8 +647,10 @@ mod tests {
             output_direction: Direction::North,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
 
         // Add first item at 0.0
@@ -633,8 +677,10 @@ mod tests {
             output_direction: Direction::North,
             items: vec![],
             last_output_index: 0,
-            last_input_source: 0,
+            last_input_pos: None,
+            enabled: true,
             shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
         };
 
         // Fill up to max items
@@ -671,8 +717,10 @@ mod tests {
                 output_direction: dir,
                 items: vec![],
                 last_output_index: 0,
-                last_input_source: 0,
+                last_input_pos: None,
+                enabled: true,
                 shape: ConveyorShape::Splitter,
+                speed_multiplier: 1.0,
             };
 
             let outputs = conveyor.get_splitter_outputs();
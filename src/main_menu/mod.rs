@@ -0,0 +1,266 @@
+//! Main menu, world selection and world creation
+//!
+//! The game starts in `AppState::MainMenu` with a title screen (Continue,
+//! World Selection, New World, Settings, Quit). Picking a world stores a
+//! `WorldSelection` and switches to `AppState::InGame`; the world and HUD are
+//! built by `OnEnter(AppState::InGame)` systems, and gameplay systems sit in
+//! `InGameSet`, which only runs in `InGame`.
+//!
+//! The world list is read from the save headers (`save::native::list_worlds`).
+//! There is no way back to the menu from a world yet; quitting closes the game.
+
+mod ui;
+
+pub use ui::{delete_confirmed, format_playtime, MainMenuState, MenuScreen, NewWorldForm};
+
+use crate::components::{CreativeMode, LoadGameEvent};
+use crate::network::NetworkMode;
+use crate::save::{ActiveWorld, WorldInfoSaveDataV2, WorldSlotInfo};
+use crate::world::{WorldData, WorldGenConfig, WorldPreset};
+use bevy::prelude::*;
+
+/// Command-line flag that enters a fresh world without the menu (E2E scripts)
+pub const SKIP_MENU_FLAG: &str = "--skip-menu";
+
+/// Top-level screen of the app
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    InGame,
+}
+
+/// Gameplay systems that only run in `AppState::InGame`
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InGameSet;
+
+/// World entered on the switch to `AppState::InGame`
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub enum WorldSelection {
+    /// Fresh world from the new world screen
+    New {
+        filename: String,
+        info: WorldInfoSaveDataV2,
+        creative: bool,
+    },
+    /// Saved world from the world list
+    Load(WorldSlotInfo),
+}
+
+impl Default for WorldSelection {
+    /// Unnamed standard survival world in the autosave slot
+    fn default() -> Self {
+        let active = ActiveWorld::default();
+        Self::New {
+            filename: active.filename,
+            info: active.info,
+            creative: false,
+        }
+    }
+}
+
+impl WorldSelection {
+    /// Worldgen config for this world: the loaded config with its preset and seed
+    pub fn gen_config(&self, base: WorldGenConfig) -> WorldGenConfig {
+        let info = match self {
+            WorldSelection::New { info, .. } => info,
+            WorldSelection::Load(slot) => &slot.info,
+        };
+        WorldPreset::from_id(&info.preset).apply(base, info.seed)
+    }
+}
+
+/// Whether to go straight into a world (network clients join the server's world)
+pub fn skip_menu_requested<I: IntoIterator<Item = String>>(args: I, mode: &NetworkMode) -> bool {
+    matches!(mode, NetworkMode::Client { .. }) || args.into_iter().any(|a| a == SKIP_MENU_FLAG)
+}
+
+fn skip_menu(mode: Option<Res<NetworkMode>>, mut next_state: ResMut<NextState<AppState>>) {
+    let mode = mode.map(|m| m.clone()).unwrap_or_default();
+    if skip_menu_requested(std::env::args().skip(1), &mode) {
+        info!("Skipping the main menu");
+        next_state.set(AppState::InGame);
+    }
+}
+
+/// Set up the selected world before the rest of `OnEnter(InGame)` runs
+///
+/// Chunks regenerate from `WorldData`'s config, so a loaded world gets its
+/// saved preset and seed back before any chunk is generated.
+pub fn start_selected_world(
+    selection: Option<Res<WorldSelection>>,
+    mut world_data: ResMut<WorldData>,
+    mut creative_mode: ResMut<CreativeMode>,
+    mut active_world: ResMut<ActiveWorld>,
+    mut load_events: MessageWriter<LoadGameEvent>,
+) {
+    let selection = selection.map(|s| s.clone()).unwrap_or_default();
+    *world_data = WorldData::with_gen_config(selection.gen_config(WorldGenConfig::load()));
+
+    match selection {
+        WorldSelection::New {
+            filename,
+            info,
+            creative,
+        } => {
+            info!(
+                category = "SAVE",
+                action = "new_world",
+                "New world '{}' ({})",
+                info.name,
+                filename
+            );
+            creative_mode.enabled = creative;
+            *active_world = ActiveWorld { filename, info };
+        }
+        WorldSelection::Load(slot) => {
+            // handle_load_event also points ActiveWorld at the slot
+            *active_world = ActiveWorld {
+                filename: slot.filename.clone(),
+                info: slot.info.clone(),
+            };
+            load_events.write(LoadGameEvent {
+                filename: slot.filename,
+            });
+        }
+    }
+}
+
+/// Plugin for the app states and the main menu screens
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .init_resource::<ActiveWorld>()
+            .init_resource::<MainMenuState>()
+            .configure_sets(Update, InGameSet.run_if(in_state(AppState::InGame)))
+            .configure_sets(FixedUpdate, InGameSet.run_if(in_state(AppState::InGame)))
+            .configure_sets(PostUpdate, InGameSet.run_if(in_state(AppState::InGame)))
+            .configure_sets(Last, InGameSet.run_if(in_state(AppState::InGame)))
+            .add_systems(Startup, skip_menu)
+            .add_systems(OnEnter(AppState::MainMenu), ui::setup_main_menu)
+            .add_systems(OnExit(AppState::MainMenu), ui::cleanup_main_menu)
+            .add_systems(OnEnter(AppState::InGame), start_selected_world)
+            .add_systems(
+                Update,
+                (
+                    ui::menu_escape,
+                    ui::menu_text_input,
+                    ui::handle_menu_buttons,
+                    ui::update_menu_button_colors,
+                    ui::rebuild_main_menu,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::MainMenu)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{GameFont, SaveGameEvent, UIAction, UIState};
+    use crate::input::InputManager;
+    use crate::save::{tick_world_playtime, AutoSaveTimer, StatsSaveDataV2};
+    use bevy::state::app::StatesPlugin;
+    use std::time::Duration;
+
+    fn slot(preset: &str, seed: Option<u64>) -> WorldSlotInfo {
+        WorldSlotInfo {
+            filename: "world_1".to_string(),
+            timestamp: 0,
+            info: WorldInfoSaveDataV2 {
+                name: "Test".to_string(),
+                seed,
+                preset: preset.to_string(),
+                playtime_secs: 0.0,
+            },
+            creative: false,
+            stats: StatsSaveDataV2::default(),
+        }
+    }
+
+    #[test]
+    fn test_skip_menu_requested() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert!(!skip_menu_requested(args(""), &NetworkMode::Offline));
+        assert!(skip_menu_requested(
+            args("--skip-menu"),
+            &NetworkMode::Offline
+        ));
+        let client = NetworkMode::from_args(args("--connect 127.0.0.1"));
+        assert!(skip_menu_requested(args(""), &client));
+    }
+
+    #[test]
+    fn test_selection_applies_saved_preset_and_seed() {
+        let base = WorldGenConfig::default();
+        let hills = WorldSelection::Load(slot("hills", Some(99))).gen_config(base.clone());
+        assert_eq!(hills, WorldPreset::Hills.apply(base.clone(), Some(99)));
+        assert_eq!(hills.seed, 99);
+
+        // Saves from before the menu regenerate with the unchanged config
+        let old = WorldSelection::Load(slot("", None)).gen_config(base.clone());
+        assert_eq!(old.config_hash(), base.config_hash());
+    }
+
+    /// `MainMenuPlugin` app with two representative gameplay systems in InGameSet
+    fn gated_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(GameFont(Handle::default()))
+            .init_resource::<InputManager>()
+            .init_resource::<UIState>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<WorldData>()
+            .init_resource::<CreativeMode>()
+            .init_resource::<AutoSaveTimer>()
+            .insert_resource(WorldSelection::Load(slot("", None)))
+            .add_message::<UIAction>()
+            .add_message::<LoadGameEvent>()
+            .add_message::<SaveGameEvent>()
+            .add_plugins(MainMenuPlugin)
+            .add_systems(
+                Update,
+                (crate::save::auto_save_system, tick_world_playtime).in_set(InGameSet),
+            );
+        app.world_mut().resource_mut::<AutoSaveTimer>().timer =
+            Timer::from_seconds(0.001, TimerMode::Repeating);
+        app
+    }
+
+    fn saves_requested(app: &mut App) -> Vec<String> {
+        app.world_mut()
+            .resource_mut::<Messages<SaveGameEvent>>()
+            .drain()
+            .map(|e| e.filename)
+            .collect()
+    }
+
+    #[test]
+    fn test_gameplay_systems_wait_for_in_game() {
+        let mut app = gated_app();
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(2));
+            app.update();
+        }
+        assert!(saves_requested(&mut app).is_empty());
+        assert_eq!(
+            app.world().resource::<ActiveWorld>().info.playtime_secs,
+            0.0
+        );
+
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(2));
+            app.update();
+        }
+        let saves = saves_requested(&mut app);
+        assert!(!saves.is_empty());
+        assert!(saves.iter().all(|f| f == "world_1"));
+        assert!(app.world().resource::<ActiveWorld>().info.playtime_secs > 0.0);
+    }
+}
@@ -0,0 +1,778 @@
+//! Main menu screens
+//!
+//! The whole menu is rebuilt from `MainMenuState` whenever it changes, so the
+//! button handlers only edit the state.
+
+use super::{AppState, WorldSelection};
+use crate::components::{GameFont, UIAction, UIContext, UIState};
+use crate::input::{GameAction, InputManager};
+use crate::save::{self, WorldInfoSaveDataV2, WorldSlotInfo};
+use crate::setup::ui::{
+    text_font, KeyRebindState, TEXT_BODY, TEXT_BUTTON, TEXT_CAPTION, TEXT_HUGE, TEXT_LARGE,
+    TEXT_SMALL,
+};
+use crate::systems::keycode_to_char;
+use crate::world::WorldPreset;
+use bevy::prelude::*;
+use tracing::{info, warn};
+
+/// Longest name, seed or confirmation text
+const MAX_INPUT_LEN: usize = 32;
+
+const BUTTON_BG: Color = Color::srgba(0.2, 0.2, 0.2, 0.9);
+const BUTTON_HOVER_BG: Color = Color::srgba(0.3, 0.3, 0.3, 0.95);
+const BUTTON_DISABLED_BG: Color = Color::srgba(0.15, 0.15, 0.15, 0.6);
+const ACCENT: Color = Color::srgb(0.8, 0.5, 0.0);
+const FIELD_BG: Color = Color::srgba(0.08, 0.08, 0.1, 0.95);
+const DANGER: Color = Color::srgb(0.75, 0.2, 0.15);
+const MUTED: Color = Color::srgb(0.65, 0.65, 0.65);
+
+/// Which menu screen is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuScreen {
+    #[default]
+    Title,
+    Worlds,
+    NewWorld,
+}
+
+/// Text field of the new world form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Name,
+    Seed,
+}
+
+/// Inputs of the new world screen
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewWorldForm {
+    pub name: String,
+    /// Number, any text (hashed) or empty (random)
+    pub seed: String,
+    pub creative: bool,
+    pub preset: WorldPreset,
+}
+
+impl NewWorldForm {
+    /// World seed: numbers as-is, other text hashed, empty = `fallback`
+    pub fn seed_value(&self, fallback: u64) -> u64 {
+        let text = self.seed.trim();
+        if text.is_empty() {
+            return fallback;
+        }
+        text.parse().unwrap_or_else(|_| {
+            text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+        })
+    }
+}
+
+/// World whose deletion waits for its name to be typed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteConfirm {
+    pub index: usize,
+    pub typed: String,
+}
+
+/// Menu screen, world list and form contents
+#[derive(Resource, Debug, Default)]
+pub struct MainMenuState {
+    pub screen: MenuScreen,
+    /// Saved worlds, most recently played first
+    pub worlds: Vec<WorldSlotInfo>,
+    pub form: NewWorldForm,
+    pub focus: Option<FormField>,
+    pub delete: Option<DeleteConfirm>,
+}
+
+impl MainMenuState {
+    fn refresh_worlds(&mut self) {
+        self.worlds = save::storage::list_worlds();
+    }
+
+    fn go_to(&mut self, screen: MenuScreen) {
+        self.screen = screen;
+        self.focus = None;
+        self.delete = None;
+    }
+}
+
+/// Deletion goes ahead only when the world's name is typed exactly
+pub fn delete_confirmed(typed: &str, world_name: &str) -> bool {
+    !world_name.is_empty() && typed == world_name
+}
+
+/// "2時間05分" / "12分"
+pub fn format_playtime(secs: f64) -> String {
+    let minutes = (secs.max(0.0) / 60.0) as u64;
+    if minutes >= 60 {
+        format!("{}時間{:02}分", minutes / 60, minutes % 60)
+    } else {
+        format!("{}分", minutes)
+    }
+}
+
+fn format_last_played(timestamp_ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn stats_summary(world: &WorldSlotInfo) -> String {
+    let stats = &world.stats;
+    format!(
+        "採掘 {}  設置 {}  機械 {}  納品 {}  クエスト {}",
+        stats.blocks_mined,
+        stats.blocks_placed,
+        stats.machines_placed,
+        stats.items_delivered,
+        stats.quests_completed
+    )
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Menu camera (the player camera only exists in a world)
+#[derive(Component)]
+pub struct MainMenuCamera;
+
+/// Root node of the current screen
+#[derive(Component)]
+pub struct MainMenuRoot;
+
+/// Menu button actions
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuButton {
+    Continue,
+    Worlds,
+    NewWorld,
+    Settings,
+    Quit,
+    Back,
+    Play(usize),
+    Delete(usize),
+    ConfirmDelete,
+    CancelDelete,
+    Focus(FormField),
+    ToggleMode,
+    CyclePreset,
+    Create,
+}
+
+/// Button that does nothing in the current state (drawn dimmed)
+#[derive(Component)]
+pub struct MenuButtonDisabled;
+
+pub(super) fn setup_main_menu(mut commands: Commands, mut menu: ResMut<MainMenuState>) {
+    commands.spawn((MainMenuCamera, Camera2d));
+    menu.go_to(MenuScreen::Title);
+    menu.refresh_worlds();
+}
+
+pub(super) fn cleanup_main_menu(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<MainMenuCamera>, With<MainMenuRoot>)>>,
+) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// ESC closes the settings panel, then the delete prompt, then goes back to the title
+pub(super) fn menu_escape(
+    input: Res<InputManager>,
+    ui_state: Res<UIState>,
+    rebind: Option<Res<KeyRebindState>>,
+    mut menu: ResMut<MainMenuState>,
+    mut action_writer: MessageWriter<UIAction>,
+) {
+    // ESC cancels a pending key rebind instead of leaving the settings screen
+    if !input.just_pressed(GameAction::Cancel) || rebind.is_some_and(|r| r.is_capturing()) {
+        return;
+    }
+    if ui_state.is_active(&UIContext::Settings) {
+        action_writer.write(UIAction::Pop);
+    } else if menu.delete.is_some() {
+        menu.delete = None;
+    } else if menu.screen != MenuScreen::Title {
+        menu.go_to(MenuScreen::Title);
+    }
+}
+
+/// Type into the focused field or the delete confirmation
+pub(super) fn menu_text_input(
+    key_input: Res<ButtonInput<KeyCode>>,
+    ui_state: Res<UIState>,
+    mut menu: ResMut<MainMenuState>,
+) {
+    if ui_state.is_active(&UIContext::Settings) {
+        return;
+    }
+    let backspace = key_input.just_pressed(KeyCode::Backspace);
+    let shift = key_input.pressed(KeyCode::ShiftLeft) || key_input.pressed(KeyCode::ShiftRight);
+    let typed: Vec<char> = key_input
+        .get_just_pressed()
+        .filter_map(|key| keycode_to_char(*key, shift))
+        .collect();
+    let has_target =
+        menu.delete.is_some() || (menu.screen == MenuScreen::NewWorld && menu.focus.is_some());
+    // Touch the state only on input, so the menu isn't rebuilt every frame
+    if !has_target || (!backspace && typed.is_empty()) {
+        return;
+    }
+
+    let menu = &mut *menu;
+    let text = if let Some(delete) = menu.delete.as_mut() {
+        &mut delete.typed
+    } else {
+        match (menu.screen, menu.focus) {
+            (MenuScreen::NewWorld, Some(FormField::Name)) => &mut menu.form.name,
+            (MenuScreen::NewWorld, Some(FormField::Seed)) => &mut menu.form.seed,
+            _ => return,
+        }
+    };
+    if backspace {
+        text.pop();
+    }
+    for c in typed {
+        if text.chars().count() < MAX_INPUT_LEN {
+            text.push(c);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn handle_menu_buttons(
+    mut commands: Commands,
+    interactions: Query<(&Interaction, &MenuButton, Has<MenuButtonDisabled>), Changed<Interaction>>,
+    ui_state: Res<UIState>,
+    mut menu: ResMut<MainMenuState>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut action_writer: MessageWriter<UIAction>,
+    mut app_exit: MessageWriter<AppExit>,
+) {
+    if ui_state.is_active(&UIContext::Settings) {
+        return;
+    }
+    for (interaction, button, disabled) in interactions.iter() {
+        if *interaction != Interaction::Pressed || disabled {
+            continue;
+        }
+        match *button {
+            MenuButton::Continue => {
+                if let Some(world) = menu.worlds.first() {
+                    commands.insert_resource(WorldSelection::Load(world.clone()));
+                    next_state.set(AppState::InGame);
+                }
+            }
+            MenuButton::Worlds => {
+                menu.refresh_worlds();
+                menu.go_to(MenuScreen::Worlds);
+            }
+            MenuButton::NewWorld => {
+                menu.form = NewWorldForm {
+                    name: format!("World {}", menu.worlds.len() + 1),
+                    ..default()
+                };
+                menu.go_to(MenuScreen::NewWorld);
+                menu.focus = Some(FormField::Name);
+            }
+            MenuButton::Settings => {
+                action_writer.write(UIAction::Push(UIContext::Settings));
+            }
+            MenuButton::Quit => {
+                app_exit.write(AppExit::Success);
+            }
+            MenuButton::Back => menu.go_to(MenuScreen::Title),
+            MenuButton::Play(index) => {
+                if let Some(world) = menu.worlds.get(index) {
+                    commands.insert_resource(WorldSelection::Load(world.clone()));
+                    next_state.set(AppState::InGame);
+                }
+            }
+            MenuButton::Delete(index) => {
+                menu.delete = Some(DeleteConfirm {
+                    index,
+                    typed: String::new(),
+                });
+            }
+            MenuButton::ConfirmDelete => {
+                let Some(delete) = menu.delete.take() else {
+                    continue;
+                };
+                let Some(world) = menu.worlds.get(delete.index).cloned() else {
+                    continue;
+                };
+                if !delete_confirmed(&delete.typed, world.display_name()) {
+                    menu.delete = Some(delete);
+                    continue;
+                }
+                match save::storage::delete_save(&world.filename) {
+                    Ok(()) => info!(
+                        category = "SAVE",
+                        action = "delete",
+                        "Deleted world '{}' ({})",
+                        world.display_name(),
+                        world.filename
+                    ),
+                    Err(e) => warn!("Failed to delete world: {}", e),
+                }
+                menu.refresh_worlds();
+            }
+            MenuButton::CancelDelete => menu.delete = None,
+            MenuButton::Focus(field) => menu.focus = Some(field),
+            MenuButton::ToggleMode => menu.form.creative = !menu.form.creative,
+            MenuButton::CyclePreset => menu.form.preset = menu.form.preset.next(),
+            MenuButton::Create => {
+                let now = now_millis();
+                let form = &menu.form;
+                let name = match form.name.trim() {
+                    "" => format!("World {}", menu.worlds.len() + 1),
+                    name => name.to_string(),
+                };
+                commands.insert_resource(WorldSelection::New {
+                    filename: format!("world_{}", now),
+                    info: WorldInfoSaveDataV2 {
+                        name,
+                        seed: Some(form.seed_value(now)),
+                        preset: form.preset.id().to_string(),
+                        playtime_secs: 0.0,
+                    },
+                    creative: form.creative,
+                });
+                next_state.set(AppState::InGame);
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(super) fn update_menu_button_colors(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor, Has<MenuButtonDisabled>),
+        (Changed<Interaction>, With<MenuButton>),
+    >,
+) {
+    for (interaction, mut bg, disabled) in buttons.iter_mut() {
+        *bg = BackgroundColor(match (interaction, disabled) {
+            (_, true) => BUTTON_DISABLED_BG,
+            (Interaction::Hovered | Interaction::Pressed, false) => BUTTON_HOVER_BG,
+            (Interaction::None, false) => BUTTON_BG,
+        });
+    }
+}
+
+/// Respawn the current screen after any state change
+pub(super) fn rebuild_main_menu(
+    mut commands: Commands,
+    menu: Res<MainMenuState>,
+    game_font: Res<GameFont>,
+    roots: Query<Entity, With<MainMenuRoot>>,
+) {
+    if !menu.is_changed() {
+        return;
+    }
+    for entity in roots.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let font = &game_font.0;
+    commands
+        .spawn((
+            MainMenuRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(14.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.1, 0.11, 0.13)),
+            GlobalZIndex(90),
+        ))
+        .with_children(|root| match menu.screen {
+            MenuScreen::Title => spawn_title_screen(root, font, &menu),
+            MenuScreen::Worlds => spawn_world_list(root, font, &menu),
+            MenuScreen::NewWorld => spawn_new_world_form(root, font, &menu),
+        });
+}
+
+fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    button: MenuButton,
+    width: f32,
+    enabled: bool,
+) {
+    let mut entity = parent.spawn((
+        Button,
+        button,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(44.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(2.0)),
+            border_radius: BorderRadius::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(if enabled {
+            BUTTON_BG
+        } else {
+            BUTTON_DISABLED_BG
+        }),
+        BorderColor::all(if enabled { ACCENT } else { MUTED }),
+    ));
+    if !enabled {
+        entity.insert(MenuButtonDisabled);
+    }
+    entity.with_child((
+        Text::new(label),
+        text_font(font, TEXT_BUTTON),
+        TextColor(if enabled { Color::WHITE } else { MUTED }),
+    ));
+}
+
+fn spawn_label(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, text: &str, size: f32) {
+    parent.spawn((
+        Text::new(text),
+        text_font(font, size),
+        TextColor(Color::WHITE),
+    ));
+}
+
+fn spawn_title_screen(root: &mut ChildSpawnerCommands, font: &Handle<Font>, menu: &MainMenuState) {
+    spawn_label(root, font, "Idle Factory", TEXT_HUGE);
+
+    let continue_label = match menu.worlds.first() {
+        Some(world) => format!("続きから ({})", world.display_name()),
+        None => "続きから".to_string(),
+    };
+    root.spawn(Node {
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(12.0),
+        margin: UiRect::top(Val::Px(24.0)),
+        ..default()
+    })
+    .with_children(|buttons| {
+        spawn_button(
+            buttons,
+            font,
+            &continue_label,
+            MenuButton::Continue,
+            280.0,
+            !menu.worlds.is_empty(),
+        );
+        spawn_button(
+            buttons,
+            font,
+            "ワールド選択",
+            MenuButton::Worlds,
+            280.0,
+            true,
+        );
+        spawn_button(
+            buttons,
+            font,
+            "新しいワールド",
+            MenuButton::NewWorld,
+            280.0,
+            true,
+        );
+        spawn_button(buttons, font, "設定", MenuButton::Settings, 280.0, true);
+        // Closing the tab is the way out on the web
+        #[cfg(not(target_arch = "wasm32"))]
+        spawn_button(buttons, font, "終了", MenuButton::Quit, 280.0, true);
+    });
+}
+
+fn spawn_world_list(root: &mut ChildSpawnerCommands, font: &Handle<Font>, menu: &MainMenuState) {
+    spawn_label(root, font, "ワールド選択", TEXT_LARGE);
+
+    root.spawn(Node {
+        width: Val::Px(640.0),
+        max_height: Val::Percent(65.0),
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(8.0),
+        overflow: Overflow::scroll_y(),
+        ..default()
+    })
+    .with_children(|list| {
+        if menu.worlds.is_empty() {
+            list.spawn((
+                Text::new("保存されたワールドはありません"),
+                text_font(font, TEXT_BODY),
+                TextColor(MUTED),
+            ));
+        }
+        for (index, world) in menu.worlds.iter().enumerate() {
+            let deleting = menu.delete.as_ref().filter(|d| d.index == index);
+            spawn_world_row(list, font, index, world, deleting);
+        }
+    });
+
+    spawn_button(root, font, "戻る", MenuButton::Back, 200.0, true);
+}
+
+fn spawn_world_row(
+    list: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    index: usize,
+    world: &WorldSlotInfo,
+    deleting: Option<&DeleteConfirm>,
+) {
+    list.spawn((
+        Node {
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(10.0)),
+            row_gap: Val::Px(6.0),
+            border: UiRect::all(Val::Px(1.0)),
+            border_radius: BorderRadius::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(FIELD_BG),
+        BorderColor::all(if deleting.is_some() { DANGER } else { MUTED }),
+    ))
+    .with_children(|row| {
+        let mode = if world.creative {
+            "クリエイティブ"
+        } else {
+            "サバイバル"
+        };
+        row.spawn((
+            Text::new(format!(
+                "{}  [{} / {}]",
+                world.display_name(),
+                mode,
+                WorldPreset::from_id(&world.info.preset).label()
+            )),
+            text_font(font, TEXT_BUTTON),
+            TextColor(Color::WHITE),
+        ));
+        row.spawn((
+            Text::new(format!(
+                "プレイ時間 {}  最終プレイ {}",
+                format_playtime(world.info.playtime_secs),
+                format_last_played(world.timestamp)
+            )),
+            text_font(font, TEXT_CAPTION),
+            TextColor(MUTED),
+        ));
+        row.spawn((
+            Text::new(stats_summary(world)),
+            text_font(font, TEXT_SMALL),
+            TextColor(MUTED),
+        ));
+
+        row.spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(8.0),
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|actions| match deleting {
+            None => {
+                spawn_button(
+                    actions,
+                    font,
+                    "プレイ",
+                    MenuButton::Play(index),
+                    120.0,
+                    true,
+                );
+                spawn_button(
+                    actions,
+                    font,
+                    "削除",
+                    MenuButton::Delete(index),
+                    120.0,
+                    true,
+                );
+            }
+            Some(delete) => {
+                actions.spawn((
+                    Text::new(format!(
+                        "削除するには「{}」と入力: {}|",
+                        world.display_name(),
+                        delete.typed
+                    )),
+                    text_font(font, TEXT_CAPTION),
+                    TextColor(DANGER),
+                ));
+                spawn_button(
+                    actions,
+                    font,
+                    "削除",
+                    MenuButton::ConfirmDelete,
+                    100.0,
+                    delete_confirmed(&delete.typed, world.display_name()),
+                );
+                spawn_button(
+                    actions,
+                    font,
+                    "キャンセル",
+                    MenuButton::CancelDelete,
+                    120.0,
+                    true,
+                );
+            }
+        });
+    });
+}
+
+fn spawn_field(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    value: &str,
+    field: FormField,
+    focused: bool,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(12.0),
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                text_font(font, TEXT_BODY),
+                TextColor(MUTED),
+                Node {
+                    width: Val::Px(110.0),
+                    ..default()
+                },
+            ));
+            row.spawn((
+                Button,
+                MenuButton::Focus(field),
+                Node {
+                    width: Val::Px(320.0),
+                    height: Val::Px(36.0),
+                    padding: UiRect::horizontal(Val::Px(8.0)),
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(FIELD_BG),
+                BorderColor::all(if focused { ACCENT } else { MUTED }),
+            ))
+            .with_child((
+                Text::new(if focused {
+                    format!("{}|", value)
+                } else {
+                    value.to_string()
+                }),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_new_world_form(
+    root: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    menu: &MainMenuState,
+) {
+    let form = &menu.form;
+    spawn_label(root, font, "新しいワールド", TEXT_LARGE);
+
+    root.spawn(Node {
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(12.0),
+        margin: UiRect::vertical(Val::Px(12.0)),
+        ..default()
+    })
+    .with_children(|fields| {
+        spawn_field(
+            fields,
+            font,
+            "名前",
+            &form.name,
+            FormField::Name,
+            menu.focus == Some(FormField::Name),
+        );
+        spawn_field(
+            fields,
+            font,
+            "シード",
+            &form.seed,
+            FormField::Seed,
+            menu.focus == Some(FormField::Seed),
+        );
+        fields.spawn((
+            Text::new(
+                "シードは空欄でランダム（地形・鉱脈・バイオーム・地表の岩の配置が変わります）",
+            ),
+            text_font(font, TEXT_SMALL),
+            TextColor(MUTED),
+        ));
+
+        let mode = if form.creative {
+            "モード: クリエイティブ"
+        } else {
+            "モード: サバイバル"
+        };
+        spawn_button(fields, font, mode, MenuButton::ToggleMode, 442.0, true);
+        spawn_button(
+            fields,
+            font,
+            &format!("地形: {}", form.preset.label()),
+            MenuButton::CyclePreset,
+            442.0,
+            true,
+        );
+    });
+
+    root.spawn(Node {
+        flex_direction: FlexDirection::Row,
+        column_gap: Val::Px(12.0),
+        ..default()
+    })
+    .with_children(|buttons| {
+        spawn_button(buttons, font, "戻る", MenuButton::Back, 200.0, true);
+        spawn_button(buttons, font, "作成", MenuButton::Create, 200.0, true);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_requires_exact_name() {
+        assert!(delete_confirmed("Copper Valley", "Copper Valley"));
+        assert!(!delete_confirmed("copper valley", "Copper Valley"));
+        assert!(!delete_confirmed("Copper", "Copper Valley"));
+        assert!(!delete_confirmed("", ""));
+    }
+
+    #[test]
+    fn test_seed_and_playtime_formatting() {
+        let form = |seed: &str| NewWorldForm {
+            seed: seed.to_string(),
+            ..default()
+        };
+        assert_eq!(form("42").seed_value(7), 42);
+        assert_eq!(form("  ").seed_value(7), 7);
+        assert_eq!(form("abc").seed_value(7), form("abc").seed_value(8));
+        assert_ne!(form("abc").seed_value(7), form("abd").seed_value(7));
+
+        assert_eq!(format_playtime(30.0), "0分");
+        assert_eq!(format_playtime(12.0 * 60.0), "12分");
+        assert_eq!(format_playtime(125.0 * 60.0), "2時間05分");
+    }
+}
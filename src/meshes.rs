@@ -3,7 +3,7 @@
 //! This module contains functions for creating procedural meshes used in the game.
 
 use bevy::asset::RenderAssetUsages;
-use bevy::mesh::PrimitiveTopology;
+use bevy::mesh::{PrimitiveTopology, VertexAttributeValues};
 use bevy::prelude::*;
 
 use crate::constants::{BLOCK_SIZE, CONVEYOR_BELT_HEIGHT, CONVEYOR_BELT_WIDTH};
@@ -252,6 +252,25 @@ pub fn create_wireframe_cube_mesh() -> Mesh {
     mesh
 }
 
+/// Create a small "X" wireframe mesh (two diagonals on the top face)
+///
+/// Used to mark invalid placement positions.
+pub fn create_wireframe_cross_mesh() -> Mesh {
+    let half = BLOCK_SIZE * 0.3;
+    let y = BLOCK_SIZE * 0.51; // Just above the top face of the wireframe cube
+
+    let positions: Vec<[f32; 3]> = vec![
+        [-half, y, -half],
+        [half, y, half],
+        [half, y, -half],
+        [-half, y, half],
+    ];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::RENDER_WORLD);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh
+}
+
 /// Create conveyor mesh based on connection shape
 pub fn create_conveyor_mesh(shape: ConveyorShape) -> Mesh {
     let width = BLOCK_SIZE * CONVEYOR_BELT_WIDTH;
@@ -263,7 +282,16 @@ pub fn create_conveyor_mesh(shape: ConveyorShape) -> Mesh {
     match shape {
         ConveyorShape::Straight => {
             // Simple rectangular belt
-            Cuboid::new(width, height, BLOCK_SIZE).into()
+            let mut mesh: Mesh = Cuboid::new(width, height, BLOCK_SIZE).into();
+            let uvs: Vec<[f32; 2]> = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap_or_default()
+                .iter()
+                .map(|&p| belt_uv_z(p))
+                .collect();
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+            mesh
         }
         ConveyorShape::CornerLeft => {
             // L-shaped: main belt + left extension
@@ -284,6 +312,19 @@ pub fn create_conveyor_mesh(shape: ConveyorShape) -> Mesh {
     }
 }
 
+/// Belt UV for a segment running toward -Z: u across the belt, v along it
+///
+/// v shrinks in the direction of travel, so raising the texture's V offset
+/// moves the pattern along with the items. One block = one texture repeat.
+fn belt_uv_z(p: [f32; 3]) -> [f32; 2] {
+    [p[0] / BLOCK_SIZE + 0.5, p[2] / BLOCK_SIZE + 0.5]
+}
+
+/// Belt UV for a side segment whose items travel toward `toward` (+1/-1) on X
+fn belt_uv_x(p: [f32; 3], toward: f32) -> [f32; 2] {
+    [p[2] / BLOCK_SIZE + 0.5, -toward * p[0] / BLOCK_SIZE + 0.5]
+}
+
 /// Create L-shaped conveyor mesh
 fn create_l_shaped_mesh(half_width: f32, half_height: f32, half_block: f32, is_left: bool) -> Mesh {
     // The conveyor faces -Z, so:
@@ -337,7 +378,18 @@ fn create_l_shaped_mesh(half_width: f32, half_height: f32, half_block: f32, is_l
         [0.0, 0.0, 1.0],
     ];
 
-    let uvs: Vec<[f32; 2]> = vec![[0.0; 2]; 16];
+    // Items come in from the side extension toward the main belt
+    let uvs: Vec<[f32; 2]> = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            if i < 8 {
+                belt_uv_z(p)
+            } else {
+                belt_uv_x(p, -side_sign)
+            }
+        })
+        .collect();
 
     // Indices - each face needs to be wound correctly for back-face culling
     let indices = if is_left {
@@ -445,7 +497,16 @@ fn create_t_shaped_mesh(half_width: f32, half_height: f32, half_block: f32) -> M
         [0.0, 0.0, 1.0],
     ];
 
-    let uvs: Vec<[f32; 2]> = vec![[0.0; 2]; 24];
+    // Both extensions feed inward
+    let uvs: Vec<[f32; 2]> = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| match i {
+            0..8 => belt_uv_z(p),
+            8..16 => belt_uv_x(p, -1.0),
+            _ => belt_uv_x(p, 1.0),
+        })
+        .collect();
 
     let indices: Vec<u32> = vec![
         // Main belt faces
@@ -557,7 +618,16 @@ fn create_splitter_mesh(half_width: f32, half_height: f32, half_block: f32) -> M
         [-1.0, 0.0, 0.0],
     ];
 
-    let uvs: Vec<[f32; 2]> = vec![[0.0; 2]; 32];
+    // All three extensions carry items outward from the hub
+    let uvs: Vec<[f32; 2]> = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| match i {
+            0..16 => belt_uv_z(p),
+            16..24 => belt_uv_x(p, 1.0),
+            _ => belt_uv_x(p, -1.0),
+        })
+        .collect();
 
     let indices: Vec<u32> = vec![
         // Center hub - top face only (sides connect to extensions)
@@ -590,3 +660,76 @@ fn create_splitter_mesh(half_width: f32, half_height: f32, half_block: f32) -> M
     mesh.insert_indices(bevy::mesh::Indices::U32(indices));
     mesh
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions_and_uvs(mesh: &Mesh) -> Vec<([f32; 3], [f32; 2])> {
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("conveyor mesh has no UVs");
+        };
+        positions.iter().copied().zip(uvs.iter().copied()).collect()
+    }
+
+    /// v of the top vertex at (x, z) among `vertices` (one belt segment)
+    fn top_v(mesh: &Mesh, vertices: std::ops::Range<usize>, x: f32, z: f32) -> f32 {
+        positions_and_uvs(mesh)[vertices]
+            .iter()
+            .find(|(p, _)| (p[0] - x).abs() < 1e-4 && (p[2] - z).abs() < 1e-4 && p[1] > 0.0)
+            .map(|(_, uv)| uv[1])
+            .unwrap_or_else(|| panic!("no top vertex at ({}, {})", x, z))
+    }
+
+    #[test]
+    fn test_conveyor_meshes_have_uvs() {
+        for shape in [
+            ConveyorShape::Straight,
+            ConveyorShape::CornerLeft,
+            ConveyorShape::CornerRight,
+            ConveyorShape::TJunction,
+            ConveyorShape::Splitter,
+        ] {
+            let mesh = create_conveyor_mesh(shape);
+            assert!(
+                positions_and_uvs(&mesh)
+                    .iter()
+                    .any(|(_, uv)| *uv != [0.0, 0.0]),
+                "{:?} has all-zero UVs",
+                shape
+            );
+        }
+    }
+
+    #[test]
+    fn test_belt_uvs_decrease_along_travel() {
+        let half = BLOCK_SIZE / 2.0;
+        let edge = BLOCK_SIZE * CONVEYOR_BELT_WIDTH / 2.0;
+
+        // Main belt carries items toward -Z
+        let straight = create_conveyor_mesh(ConveyorShape::Straight);
+        assert!(top_v(&straight, 0..24, edge, -half) < top_v(&straight, 0..24, edge, half));
+
+        // Corner extensions feed inward: +X side for CornerLeft, -X for CornerRight
+        let left = create_conveyor_mesh(ConveyorShape::CornerLeft);
+        assert!(top_v(&left, 0..8, edge, -half) < top_v(&left, 0..8, edge, half));
+        assert!(top_v(&left, 8..16, edge, half) < top_v(&left, 8..16, half, half));
+        let right = create_conveyor_mesh(ConveyorShape::CornerRight);
+        assert!(top_v(&right, 8..16, -edge, half) < top_v(&right, 8..16, -half, half));
+
+        let t = create_conveyor_mesh(ConveyorShape::TJunction);
+        assert!(top_v(&t, 8..16, edge, half) < top_v(&t, 8..16, half, half));
+        assert!(top_v(&t, 16..24, -edge, half) < top_v(&t, 16..24, -half, half));
+
+        // Splitter extensions carry items outward from the hub
+        let splitter = create_conveyor_mesh(ConveyorShape::Splitter);
+        assert!(top_v(&splitter, 8..16, -edge, -half) < top_v(&splitter, 8..16, -edge, -edge));
+        assert!(top_v(&splitter, 16..24, half, edge) < top_v(&splitter, 16..24, edge, edge));
+        assert!(top_v(&splitter, 24..32, -half, edge) < top_v(&splitter, 24..32, -edge, edge));
+    }
+}
@@ -0,0 +1,346 @@
+//! Content bundles: a single zip of mod data files with a checksummed manifest
+//!
+//! A bundle contains `manifest.yaml` (content version + CRC32 of every file) and the
+//! same data files as a loose mod directory (items.toml, machines.toml, recipes.toml),
+//! plus any referenced assets. The game prefers `assets/bundles/*.zip` over loose files.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::data::ModDataPack;
+
+/// Directory searched for bundles (relative to the working directory)
+pub const BUNDLE_DIR: &str = "assets/bundles";
+
+/// Manifest file name inside a bundle
+pub const MANIFEST_FILE: &str = "manifest.yaml";
+
+/// Content version of the loaded bundle (None = loose files)
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct ContentVersion(pub Option<String>);
+
+/// One file listed in the bundle manifest
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleFileEntry {
+    /// Path inside the bundle ('/' separated)
+    pub path: String,
+    /// CRC32 of the file contents
+    pub crc32: u32,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// manifest.yaml contents
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Content version the bundle was built as
+    pub content_version: String,
+    /// Every file in the bundle except the manifest itself
+    pub files: Vec<BundleFileEntry>,
+}
+
+/// A bundle that passed checksum verification
+#[derive(Debug)]
+pub struct ContentBundle {
+    /// Path the bundle was loaded from
+    pub path: PathBuf,
+    /// Verified manifest
+    pub manifest: BundleManifest,
+    /// Data parsed from the bundled data files
+    pub pack: ModDataPack,
+}
+
+impl ContentBundle {
+    /// Content version from the manifest
+    pub fn version(&self) -> &str {
+        &self.manifest.content_version
+    }
+}
+
+/// Bundle build/load error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleError {
+    /// Filesystem error
+    Io(String),
+    /// Zip archive error
+    Zip(String),
+    /// manifest.yaml missing from the archive
+    MissingManifest,
+    /// manifest.yaml could not be parsed or written
+    Manifest(String),
+    /// A file listed in the manifest is missing from the archive
+    MissingFile(String),
+    /// A file's contents don't match its manifest checksum
+    ChecksumMismatch(String),
+    /// A data file could not be parsed (file, error)
+    Parse(String, String),
+}
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleError::Io(e) => write!(f, "IO error: {}", e),
+            BundleError::Zip(e) => write!(f, "Zip error: {}", e),
+            BundleError::MissingManifest => write!(f, "{} not found in bundle", MANIFEST_FILE),
+            BundleError::Manifest(e) => write!(f, "Invalid manifest: {}", e),
+            BundleError::MissingFile(path) => write!(f, "Missing file: {}", path),
+            BundleError::ChecksumMismatch(path) => write!(f, "Checksum mismatch: {}", path),
+            BundleError::Parse(path, e) => write!(f, "Failed to parse {}: {}", path, e),
+        }
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+/// Collect all files under `dir` as ('/' separated relative path, absolute path), sorted
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    out: &mut Vec<(String, PathBuf)>,
+) -> std::io::Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let rel = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, &rel, out)?;
+        } else if rel != MANIFEST_FILE {
+            out.push((rel, path));
+        }
+    }
+    Ok(())
+}
+
+/// Zip every file under `source_dir` into `output` with a checksummed manifest
+pub fn build_bundle(
+    source_dir: &Path,
+    content_version: &str,
+    output: &Path,
+) -> Result<BundleManifest, BundleError> {
+    let mut files = Vec::new();
+    collect_files(source_dir, "", &mut files).map_err(|e| BundleError::Io(e.to_string()))?;
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| BundleError::Io(e.to_string()))?;
+    }
+    let file = std::fs::File::create(output).map_err(|e| BundleError::Io(e.to_string()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+
+    let mut manifest = BundleManifest {
+        content_version: content_version.to_string(),
+        files: Vec::with_capacity(files.len()),
+    };
+    for (rel, path) in files {
+        let bytes = std::fs::read(&path).map_err(|e| BundleError::Io(e.to_string()))?;
+        zip.start_file(rel.as_str(), options)
+            .map_err(|e| BundleError::Zip(e.to_string()))?;
+        zip.write_all(&bytes)
+            .map_err(|e| BundleError::Io(e.to_string()))?;
+        manifest.files.push(BundleFileEntry {
+            path: rel,
+            crc32: crc32(&bytes),
+            size: bytes.len() as u64,
+        });
+    }
+
+    let yaml =
+        serde_yaml::to_string(&manifest).map_err(|e| BundleError::Manifest(e.to_string()))?;
+    zip.start_file(MANIFEST_FILE, options)
+        .map_err(|e| BundleError::Zip(e.to_string()))?;
+    zip.write_all(yaml.as_bytes())
+        .map_err(|e| BundleError::Io(e.to_string()))?;
+    zip.finish().map_err(|e| BundleError::Zip(e.to_string()))?;
+
+    Ok(manifest)
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    path: &str,
+) -> Option<Vec<u8>> {
+    let mut entry = archive.by_name(path).ok()?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Parse a bundled data file into `pack` (non-data files are ignored)
+fn parse_data_file(path: &str, bytes: &[u8], pack: &mut ModDataPack) -> Result<(), BundleError> {
+    if !matches!(path, "items.toml" | "machines.toml" | "recipes.toml") {
+        return Ok(());
+    }
+    let parse_err = |e: String| BundleError::Parse(path.to_string(), e);
+    let text = std::str::from_utf8(bytes).map_err(|e| parse_err(e.to_string()))?;
+    match path {
+        "items.toml" => {
+            pack.items =
+                ModDataPack::load_items_toml(text).map_err(|e| parse_err(e.to_string()))?;
+        }
+        "machines.toml" => {
+            pack.machines =
+                ModDataPack::load_machines_toml(text).map_err(|e| parse_err(e.to_string()))?;
+        }
+        _ => {
+            pack.recipes =
+                ModDataPack::load_recipes_toml(text).map_err(|e| parse_err(e.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Open a bundle, verify every manifest checksum and parse its data files
+pub fn load_bundle(path: &Path) -> Result<ContentBundle, BundleError> {
+    let file = std::fs::File::open(path).map_err(|e| BundleError::Io(e.to_string()))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| BundleError::Zip(e.to_string()))?;
+
+    let manifest_bytes =
+        read_entry(&mut archive, MANIFEST_FILE).ok_or(BundleError::MissingManifest)?;
+    let manifest: BundleManifest = serde_yaml::from_slice(&manifest_bytes)
+        .map_err(|e| BundleError::Manifest(e.to_string()))?;
+
+    let mut pack = ModDataPack::new();
+    for entry in &manifest.files {
+        let bytes = read_entry(&mut archive, &entry.path)
+            .ok_or_else(|| BundleError::MissingFile(entry.path.clone()))?;
+        if bytes.len() as u64 != entry.size || crc32(&bytes) != entry.crc32 {
+            return Err(BundleError::ChecksumMismatch(entry.path.clone()));
+        }
+
+        parse_data_file(&entry.path, &bytes, &mut pack)?;
+    }
+
+    Ok(ContentBundle {
+        path: path.to_path_buf(),
+        manifest,
+        pack,
+    })
+}
+
+/// Pick the bundle to load from `dir`: the last `*.zip` by file name, if any
+pub fn find_bundle(dir: &Path) -> Option<PathBuf> {
+    let mut bundles: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        })
+        .collect();
+    bundles.sort();
+    bundles.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Minimal content project: one item, one recipe, an icon
+    fn write_fixture(dir: &Path) {
+        std::fs::write(
+            dir.join("items.toml"),
+            r#"
+[[item]]
+id = "bundle_gear"
+name = "Bundle Gear"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("recipes.toml"),
+            r#"
+[[recipe]]
+id = "bundle_gear_recipe"
+machine = "assembler"
+
+[recipe.inputs]
+iron_ingot = 2
+
+[recipe.outputs]
+bundle_gear = 1
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        std::fs::write(dir.join("icons/bundle_gear.png"), [0x89, b'P', b'N', b'G']).unwrap();
+    }
+
+    #[test]
+    fn test_build_and_load_bundle() {
+        let temp = tempdir().unwrap();
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        write_fixture(&project);
+
+        let output = temp.path().join("bundles/content-1.2.0.zip");
+        let manifest = build_bundle(&project, "1.2.0", &output).unwrap();
+        let paths: Vec<_> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["icons/bundle_gear.png", "items.toml", "recipes.toml"]
+        );
+
+        let bundle = load_bundle(&output).unwrap();
+        assert_eq!(bundle.version(), "1.2.0");
+        assert!(bundle.pack.items.iter().any(|i| i.id == "bundle_gear"));
+        assert_eq!(bundle.pack.recipe_count(), 1);
+    }
+
+    #[test]
+    fn test_checksum_mismatch_rejected() {
+        let temp = tempdir().unwrap();
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        write_fixture(&project);
+        let output = temp.path().join("bundle.zip");
+        let mut manifest = build_bundle(&project, "1.0.0", &output).unwrap();
+
+        // Rewrite the archive with a tampered manifest checksum
+        manifest.files[1].crc32 ^= 1;
+        let file = std::fs::File::create(&output).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        for entry in &manifest.files {
+            zip.start_file(entry.path.as_str(), options).unwrap();
+            zip.write_all(&std::fs::read(project.join(&entry.path)).unwrap())
+                .unwrap();
+        }
+        zip.start_file(MANIFEST_FILE, options).unwrap();
+        zip.write_all(serde_yaml::to_string(&manifest).unwrap().as_bytes())
+            .unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(
+            load_bundle(&output).unwrap_err(),
+            BundleError::ChecksumMismatch("items.toml".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_bundle_prefers_last_zip() {
+        let temp = tempdir().unwrap();
+        assert_eq!(find_bundle(temp.path()), None);
+
+        std::fs::write(temp.path().join("content-1.0.0.zip"), b"").unwrap();
+        std::fs::write(temp.path().join("content-1.1.0.zip"), b"").unwrap();
+        std::fs::write(temp.path().join("notes.txt"), b"").unwrap();
+        assert_eq!(
+            find_bundle(temp.path()),
+            Some(temp.path().join("content-1.1.0.zip"))
+        );
+        assert_eq!(find_bundle(&temp.path().join("missing")), None);
+    }
+}
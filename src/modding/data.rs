@@ -1,10 +1,13 @@
 //! Data-driven mod loading from TOML/JSON files
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::core::ItemId;
+use crate::game_spec::UnlockCondition;
+use crate::startup_report::{DataIssue, LoadOutcome};
 
 /// Modデータファイル形式
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -104,7 +107,10 @@ pub struct MachineDefinition {
     pub id: String,
     /// 表示名
     pub name: String,
-    /// 処理時間（秒）
+    /// 作業タイプ（同じ名前の `machine` を持つレシピを処理、空ならID）
+    #[serde(default)]
+    pub work_type: String,
+    /// 基本処理時間（秒、処理時間を省略したレシピに使用）
     #[serde(default = "default_process_time")]
     pub process_time: f32,
     /// 入力ポート数
@@ -113,9 +119,21 @@ pub struct MachineDefinition {
     /// 出力ポート数
     #[serde(default = "default_one")]
     pub output_ports: u8,
-    /// 燃料が必要か
+    /// 入力スロット数
+    #[serde(default = "default_one")]
+    pub input_slots: u8,
+    /// 出力スロット数
+    #[serde(default = "default_one")]
+    pub output_slots: u8,
+    /// スロットごとの容量
+    #[serde(default = "default_buffer_size")]
+    pub buffer_size: u32,
+    /// 燃料が必要か（燃料スロットを持つ）
     #[serde(default)]
     pub requires_fuel: bool,
+    /// UIの見出し色 [R, G, B]
+    #[serde(default)]
+    pub color: Option<[f32; 3]>,
     /// 電力消費
     #[serde(default)]
     pub power_consumption: f32,
@@ -132,16 +150,25 @@ fn default_one() -> u8 {
     1
 }
 
+fn default_buffer_size() -> u32 {
+    64
+}
+
 impl MachineDefinition {
     /// 新しい機械定義を作成
     pub fn new(id: &str, name: &str) -> Self {
         Self {
             id: id.to_string(),
             name: name.to_string(),
+            work_type: String::new(),
             process_time: 2.0,
             input_ports: 1,
             output_ports: 1,
+            input_slots: 1,
+            output_slots: 1,
+            buffer_size: 64,
             requires_fuel: false,
+            color: None,
             power_consumption: 0.0,
             model: String::new(),
         }
@@ -160,11 +187,14 @@ pub struct RecipeDefinition {
     /// 出力アイテム（ID -> 個数）
     pub outputs: HashMap<String, u32>,
     /// 処理時間（秒、Noneの場合は機械のデフォルト）
-    #[serde(default)]
+    #[serde(default, alias = "craft_time")]
     pub process_time: Option<f32>,
     /// 燃料消費（ID -> 個数）
     #[serde(default)]
     pub fuel: HashMap<String, u32>,
+    /// 解放条件（省略時は最初から使用可能）
+    #[serde(default)]
+    pub unlock: UnlockCondition,
 }
 
 impl RecipeDefinition {
@@ -177,6 +207,7 @@ impl RecipeDefinition {
             outputs: HashMap::new(),
             process_time: None,
             fuel: HashMap::new(),
+            unlock: UnlockCondition::Always,
         }
     }
 
@@ -262,6 +293,16 @@ impl ModDataPack {
         Ok(parsed.recipes)
     }
 
+    /// 内容の安定したハッシュ（キー順を揃えたJSONへのFNV-1a、セーブに記録）
+    pub fn content_hash(&self) -> u64 {
+        let json = serde_json::to_value(self)
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        json.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
     /// JSONに書き出し
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
@@ -333,6 +374,92 @@ impl ModDataPack {
 
         Ok(pack)
     }
+
+    /// Modディレクトリから読み込み、壊れたエントリはスキップして`outcome`に警告を記録
+    ///
+    /// ファイル全体が読めない（IOエラー・構文エラー）場合はエラーとして記録する。
+    pub fn load_from_directory_checked(mod_path: &Path, outcome: &mut LoadOutcome) -> Self {
+        Self {
+            items: load_entries(&mod_path.join("items.toml"), "item", outcome),
+            machines: load_entries(&mod_path.join("machines.toml"), "machine", outcome),
+            recipes: load_entries(&mod_path.join("recipes.toml"), "recipe", outcome),
+        }
+    }
+}
+
+/// TOMLファイルの`[[key]]`エントリを読み込み（ファイルがなければ空）
+fn load_entries<T: DeserializeOwned>(path: &Path, key: &str, outcome: &mut LoadOutcome) -> Vec<T> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let file = path.display().to_string();
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_entries(&content, &file, key, outcome),
+        Err(e) => {
+            outcome.error(DataIssue::new(file, e.to_string()));
+            Vec::new()
+        }
+    }
+}
+
+/// `[[key]]`エントリを1件ずつデシリアライズ
+///
+/// 構文エラーはファイル全体のエラー、個々のエントリの不正は警告にしてスキップする。
+/// 警告の行番号はそのエントリの`[[key]]`ヘッダー行。
+pub fn parse_entries<T: DeserializeOwned>(
+    content: &str,
+    file: &str,
+    key: &str,
+    outcome: &mut LoadOutcome,
+) -> Vec<T> {
+    let mut table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(e) => {
+            outcome.error(DataIssue::from_toml_error(file, content, &e));
+            return Vec::new();
+        }
+    };
+    let entries = match table.remove(key) {
+        None => return Vec::new(),
+        Some(toml::Value::Array(entries)) => entries,
+        Some(_) => {
+            outcome.error(DataIssue::new(
+                file,
+                format!("`{}` must be an array of tables ([[{}]])", key, key),
+            ));
+            return Vec::new();
+        }
+    };
+
+    let header = format!("[[{}]]", key);
+    let header_lines: Vec<usize> = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == header)
+        .map(|(i, _)| i + 1)
+        .collect();
+
+    let mut parsed = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.into_iter().enumerate() {
+        let name = match entry.get("id").and_then(|id| id.as_str()) {
+            Some(id) => format!("{} '{}'", key, id),
+            None => format!("{} #{}", key, index + 1),
+        };
+        match entry.try_into::<T>() {
+            Ok(value) => {
+                parsed.push(value);
+                outcome.loaded += 1;
+            }
+            Err(e) => {
+                let mut issue = DataIssue::new(file, format!("{} skipped: {}", name, e.message()));
+                if let Some(&line) = header_lines.get(index) {
+                    issue = issue.at(line, 1);
+                }
+                outcome.warn(issue);
+            }
+        }
+    }
+    parsed
 }
 
 /// Modロードエラー
@@ -416,6 +543,31 @@ mod tests {
         assert_eq!(recipe.outputs.get("iron_ingot"), Some(&1));
     }
 
+    #[test]
+    fn test_recipe_definition_unlock_default() {
+        let toml_str = r#"
+            id = "custom:recipe"
+            machine = "furnace"
+            inputs = { iron_ore = 1 }
+            outputs = { iron_ingot = 1 }
+        "#;
+        let recipe: RecipeDefinition = toml::from_str(toml_str).unwrap();
+        assert_eq!(recipe.unlock, UnlockCondition::Always);
+
+        let gated = format!(
+            "{}\n[unlock.ItemDelivered]\nitem = \"base:iron_ingot\"\ncount = 20\n",
+            toml_str
+        );
+        let recipe: RecipeDefinition = toml::from_str(&gated).unwrap();
+        assert_eq!(
+            recipe.unlock,
+            UnlockCondition::ItemDelivered {
+                item: "base:iron_ingot".to_string(),
+                count: 20
+            }
+        );
+    }
+
     #[test]
     fn test_mod_data_pack() {
         let mut pack = ModDataPack::new();
@@ -553,4 +705,49 @@ coal = 1
             assert!(pack.recipe_count() > 0, "Base mod should have recipes");
         }
     }
+
+    #[test]
+    fn test_checked_load_skips_bad_entries() {
+        let toml_str = r#"
+[[recipe]]
+id = "iron_smelting"
+machine = "furnace"
+inputs = { iron_ore = 1 }
+outputs = { iron_ingot = 1 }
+
+[[recipe]]
+id = "broken"
+machine = "furnace"
+inputs = { iron_ore = "one" }
+outputs = { iron_ingot = 1 }
+
+[[recipe]]
+machine = "crusher"
+"#;
+        let mut outcome = LoadOutcome::new("base mod");
+        let recipes: Vec<RecipeDefinition> =
+            parse_entries(toml_str, "recipes.toml", "recipe", &mut outcome);
+        assert_eq!(recipes.len(), 1);
+        assert_eq!(outcome.loaded, 1);
+        assert!(!outcome.is_fatal());
+        assert_eq!(outcome.warnings.len(), 2);
+        assert_eq!(outcome.warnings[0].line, Some(8));
+        assert!(outcome.warnings[0].message.contains("recipe 'broken'"));
+        assert!(outcome.warnings[1].message.contains("recipe #3"));
+
+        // A syntax error makes the whole file unreadable
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("items.toml"),
+            "[[item]]\nid = \"a\"\nname = \n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("recipes.toml"), toml_str).unwrap();
+        let mut outcome = LoadOutcome::new("base mod");
+        let pack = ModDataPack::load_from_directory_checked(dir.path(), &mut outcome);
+        assert!(outcome.is_fatal());
+        assert_eq!(outcome.errors[0].line, Some(3));
+        assert_eq!(pack.item_count(), 0);
+        assert_eq!(pack.recipe_count(), 1);
+    }
 }
@@ -130,8 +130,7 @@ pub fn handle_item_add(request: &JsonRpcRequest) -> JsonRpcResponse {
     }
 
     // Check for duplicate ID
-    let interner = items::interner();
-    if interner.get(&add_params.id).is_some() {
+    if items::by_string_id(&add_params.id).is_some() {
         return JsonRpcResponse::error(
             request.id,
             ITEM_ALREADY_EXISTS,
@@ -2,7 +2,7 @@
 //!
 //! Provides JSON-RPC methods for machine queries and management.
 
-use crate::game_spec::machines::ALL_MACHINES;
+use crate::game_spec::machines::all_machine_specs;
 use crate::modding::protocol::{JsonRpcRequest, JsonRpcResponse, INVALID_PARAMS};
 use serde::{Deserialize, Serialize};
 
@@ -58,8 +58,8 @@ pub struct MachineAddResponse {
 
 /// Handle machine.list request
 pub fn handle_machine_list(request: &JsonRpcRequest) -> JsonRpcResponse {
-    let machines: Vec<MachineInfo> = ALL_MACHINES
-        .iter()
+    let machines: Vec<MachineInfo> = all_machine_specs()
+        .into_iter()
         .map(|spec| {
             // Count input/output slots from ui_slots
             let input_slots = spec
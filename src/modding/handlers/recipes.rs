@@ -2,7 +2,9 @@
 //!
 //! Implements `recipe.list` and `recipe.add` methods.
 
-use crate::game_spec::recipes::{all_recipes, get_recipes_for_machine, MachineType, Recipe};
+use crate::game_spec::recipes::{
+    all_recipes, get_recipes_for_machine, Ingredient, MachineType, Recipe,
+};
 use crate::modding::protocol::{JsonRpcRequest, JsonRpcResponse, INVALID_PARAMS};
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -12,7 +14,7 @@ use tracing::info;
 pub struct RecipeInfo {
     /// Recipe ID
     pub id: String,
-    /// Machine type (furnace, crusher, assembler)
+    /// Machine type (furnace, crusher, assembler, hand or a work type)
     pub machine_type: String,
     /// Input item IDs with counts
     pub inputs: Vec<RecipeItemInfo>,
@@ -79,20 +81,21 @@ struct RecipeAddResponse {
 
 /// Convert MachineType to string
 fn machine_type_to_string(machine: MachineType) -> &'static str {
-    match machine {
-        MachineType::Furnace => "furnace",
-        MachineType::Crusher => "crusher",
-        MachineType::Assembler => "assembler",
-    }
+    machine.data_name()
 }
 
-/// Parse machine type from string
+/// Parse machine type from string (built-in types or work types with recipes)
 fn parse_machine_type(s: &str) -> Option<MachineType> {
-    match s.to_lowercase().as_str() {
+    let name = s.to_lowercase();
+    match name.as_str() {
         "furnace" => Some(MachineType::Furnace),
         "crusher" => Some(MachineType::Crusher),
         "assembler" => Some(MachineType::Assembler),
-        _ => None,
+        "hand" => Some(MachineType::Hand),
+        _ => all_recipes()
+            .iter()
+            .map(|r| r.machine)
+            .find(|m| matches!(m, MachineType::Custom(work_type) if *work_type == name)),
     }
 }
 
@@ -105,7 +108,10 @@ fn recipe_to_info(recipe: &Recipe) -> RecipeInfo {
             .inputs
             .iter()
             .map(|input| RecipeItemInfo {
-                item: input.item.name().unwrap_or("unknown").to_string(),
+                item: match input.ingredient {
+                    Ingredient::Item(item) => item.name().unwrap_or("unknown").to_string(),
+                    Ingredient::Tag(tag) => format!("#{}", tag),
+                },
                 count: input.count,
             })
             .collect(),
@@ -128,7 +134,7 @@ fn recipe_to_info(recipe: &Recipe) -> RecipeInfo {
 /// Handle recipe.list method
 ///
 /// Parameters:
-/// - `machine_type` (optional): Filter by machine type ("furnace", "crusher", "assembler")
+/// - `machine_type` (optional): Filter by machine type ("furnace", "crusher", "assembler", "hand")
 ///
 /// Returns:
 /// - `recipes`: Array of recipe info objects
@@ -162,7 +168,7 @@ pub fn handle_recipe_list(request: &JsonRpcRequest) -> JsonRpcResponse {
                         request.id,
                         INVALID_PARAMS,
                         format!(
-                            "Unknown machine_type: {}. Valid values: furnace, crusher, assembler",
+                            "Unknown machine_type: {}. Valid values: furnace, crusher, assembler, hand",
                             machine_str
                         ),
                     );
@@ -263,8 +269,8 @@ mod tests {
         let result = response.result.unwrap();
         let recipes = result["recipes"].as_array().unwrap();
 
-        // Should return all recipes (11 total)
-        assert_eq!(recipes.len(), 11);
+        // Should return all recipes (21 total)
+        assert_eq!(recipes.len(), 21);
     }
 
     #[test]
@@ -307,8 +313,8 @@ mod tests {
         let result = response.result.unwrap();
         let recipes = result["recipes"].as_array().unwrap();
 
-        // Assembler has 5 recipes
-        assert_eq!(recipes.len(), 5);
+        // Assembler has 12 recipes
+        assert_eq!(recipes.len(), 12);
     }
 
     #[test]
@@ -454,6 +460,7 @@ mod tests {
         assert_eq!(machine_type_to_string(MachineType::Furnace), "furnace");
         assert_eq!(machine_type_to_string(MachineType::Crusher), "crusher");
         assert_eq!(machine_type_to_string(MachineType::Assembler), "assembler");
+        assert_eq!(machine_type_to_string(MachineType::Hand), "hand");
     }
 
     #[test]
@@ -465,6 +472,7 @@ mod tests {
             parse_machine_type("assembler"),
             Some(MachineType::Assembler)
         );
+        assert_eq!(parse_machine_type("hand"), Some(MachineType::Hand));
         assert_eq!(parse_machine_type("invalid"), None);
     }
 }
@@ -151,6 +151,8 @@ pub const VALID_UI_STATES: &[&str] = &[
     "GlobalInventory",
     "Command",
     "Settings",
+    "Stats",
+    "QuestLog",
 ];
 
 pub fn handle_test_set_ui_state(request: &JsonRpcRequest) -> JsonRpcResponse {
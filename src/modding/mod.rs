@@ -2,10 +2,12 @@
 //!
 //! ## Architecture
 //! - `api`: Mod API server (WebSocket/JSON-RPC)
+//! - `bundle`: Checksummed content bundles (assets/bundles/*.zip)
 //! - `data`: Data-driven mod loading (TOML/JSON)
 //! - `registry`: Mod content registration
 
 pub mod api;
+pub mod bundle;
 pub mod connection;
 pub mod data;
 pub mod dependency;
@@ -21,6 +23,7 @@ pub mod server;
 pub mod wasm;
 
 // Re-export server types for convenience
+pub use bundle::ContentVersion;
 pub use dependency::{DependencyError, DependencyResolver, ModDependencyInfo};
 #[cfg(not(target_arch = "wasm32"))]
 pub use event_bridge::EventBridgePlugin;
@@ -29,9 +32,10 @@ pub use hot_reload::{HotReloadError, ModChange, ModHotReloader};
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::{ModApiServer, ModApiServerConfig, ModApiServerPlugin};
 
+use crate::startup_report::{DataIssue, LoadOutcome, StartupReport};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Mod情報
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -295,14 +299,75 @@ impl LoadedModData {
     pub fn recipe_count(&self) -> usize {
         self.packs.iter().map(|(_, p)| p.recipe_count()).sum()
     }
+
+    /// base以外のModごとのコンテンツハッシュ（セーブヘッダーに記録）
+    pub fn mod_content_hashes(&self) -> BTreeMap<String, u64> {
+        self.packs
+            .iter()
+            .filter(|(mod_id, _)| mod_id != "base")
+            .map(|(mod_id, pack)| (mod_id.clone(), pack.content_hash()))
+            .collect()
+    }
 }
 
+/// Modコンテンツの読み込み（base と Core Mod）。レジストリ構築はこの後
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModLoadSet;
+
 /// base Modをロード
+///
+/// 結果は`StartupReport`に記録する。壊れたエントリはスキップして警告、
+/// base Modが見つからない・ファイルが読めない場合は致命的エラー（エラー画面を表示）。
 pub(crate) fn load_base_mod(
     mut mod_data: ResMut<LoadedModData>,
     mut mod_manager: ResMut<ModManager>,
+    mut content_version: ResMut<ContentVersion>,
+    mut report: ResMut<StartupReport>,
 ) {
-    use tracing::{info, warn};
+    use tracing::info;
+
+    let mut outcome = LoadOutcome::new("base mod");
+
+    // 配布用バンドル (assets/bundles/*.zip) があればルーズファイルより優先
+    let bundle_dirs = [
+        std::path::PathBuf::from(bundle::BUNDLE_DIR),
+        std::path::Path::new("..").join(bundle::BUNDLE_DIR),
+    ];
+    if let Some(bundle_path) = bundle_dirs.iter().find_map(|dir| bundle::find_bundle(dir)) {
+        match bundle::load_bundle(&bundle_path) {
+            Ok(bundle) => {
+                info!(
+                    "Base content loaded from bundle {:?} (version {}): {} items, {} machines, {} recipes",
+                    bundle.path,
+                    bundle.version(),
+                    bundle.pack.item_count(),
+                    bundle.pack.machine_count(),
+                    bundle.pack.recipe_count()
+                );
+                let base_info = ModInfo::new("base", "Base Game", bundle.version())
+                    .with_author("Idle Factory Team")
+                    .with_description("Core game content");
+                mod_manager.register(base_info);
+                if let Some(loaded) = mod_manager.get_mut("base") {
+                    loaded.state = ModState::Loaded;
+                }
+                content_version.0 = Some(bundle.version().to_string());
+                outcome.loaded = bundle.pack.item_count()
+                    + bundle.pack.machine_count()
+                    + bundle.pack.recipe_count();
+                mod_data.packs.push(("base".to_string(), bundle.pack));
+                report.record(outcome);
+                return;
+            }
+            Err(e) => {
+                // 検証に失敗したバンドルは使わず、ルーズファイルにフォールバック
+                outcome.warn(DataIssue::new(
+                    bundle_path.display().to_string(),
+                    format!("{}; falling back to loose files", e),
+                ));
+            }
+        }
+    }
 
     // 実行ファイルからの相対パスでmodsディレクトリを探す
     let mods_paths = [
@@ -317,25 +382,14 @@ pub(crate) fn load_base_mod(
         // Base Modが見つからない場合は致命的エラー
         // 配布パッケージにmods/base/が含まれていない可能性が高い
         let cwd = std::env::current_dir().unwrap_or_default();
-        tracing::error!(
-            "=== FATAL ERROR ===\n\
-             Base mod not found!\n\
-             \n\
-             Searched locations:\n\
-             - mods/base\n\
-             - ../mods/base\n\
-             - ../../mods/base\n\
-             \n\
-             Current directory: {:?}\n\
-             \n\
-             Solution:\n\
-             1. Ensure 'mods/base/' folder exists next to the executable\n\
-             2. Re-download the game from the official release\n\
-             ===================",
-            cwd
-        );
-        // パニックせずにreturnするが、後続処理でアイテムがないためクラッシュする可能性あり
-        // TODO: AppExitイベントを送ってgracefulに終了する方法を検討
+        outcome.error(DataIssue::new(
+            "mods/base",
+            format!(
+                "Base mod not found (searched mods/base, ../mods/base, ../../mods/base from {:?})",
+                cwd
+            ),
+        ));
+        report.record(outcome);
         return;
     };
 
@@ -347,31 +401,25 @@ pub(crate) fn load_base_mod(
         .with_description("Core game content");
     mod_manager.register(base_info);
 
-    // データパックをロード
-    match data::ModDataPack::load_from_directory(base_path) {
-        Ok(pack) => {
-            info!(
-                "Base mod loaded: {} items, {} machines, {} recipes",
-                pack.item_count(),
-                pack.machine_count(),
-                pack.recipe_count()
-            );
-
-            // Loadedステートに変更
-            if let Some(loaded) = mod_manager.get_mut("base") {
-                loaded.state = ModState::Loaded;
-            }
-
-            mod_data.packs.push(("base".to_string(), pack));
-        }
-        Err(e) => {
-            warn!("Failed to load base mod: {:?}", e);
-            if let Some(loaded) = mod_manager.get_mut("base") {
-                loaded.state = ModState::Error;
-                loaded.error = Some(format!("{:?}", e));
-            }
+    // データパックをロード（壊れたエントリはスキップ）
+    let pack = data::ModDataPack::load_from_directory_checked(base_path, &mut outcome);
+    info!(
+        "Base mod loaded: {} items, {} machines, {} recipes ({} skipped)",
+        pack.item_count(),
+        pack.machine_count(),
+        pack.recipe_count(),
+        outcome.warnings.len()
+    );
+    if let Some(loaded) = mod_manager.get_mut("base") {
+        if outcome.is_fatal() {
+            loaded.state = ModState::Error;
+            loaded.error = outcome.errors.first().map(ToString::to_string);
+        } else {
+            loaded.state = ModState::Loaded;
         }
     }
+    mod_data.packs.push(("base".to_string(), pack));
+    report.record(outcome);
 }
 
 /// Moddingプラグイン
@@ -381,10 +429,19 @@ impl Plugin for ModdingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ModManager>()
             .init_resource::<LoadedModData>()
+            .init_resource::<StartupReport>()
+            .init_resource::<ContentVersion>()
             .add_message::<ModLoadedEvent>()
             .add_message::<ModUnloadedEvent>()
             .add_message::<ModErrorEvent>()
-            .add_systems(Startup, load_base_mod);
+            .add_systems(Startup, load_base_mod.in_set(ModLoadSet));
+
+        // WASM Core Modのアイテム・レシピ登録（non-WASM only）
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(
+            Startup,
+            wasm::load_core_mods.in_set(ModLoadSet).after(load_base_mod),
+        );
     }
 }
 
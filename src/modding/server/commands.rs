@@ -2,7 +2,7 @@
 
 use bevy::prelude::*;
 
-use crate::core::{items, ItemId};
+use crate::core::items;
 use crate::input::GameAction;
 use crate::systems::command::{SetBlockEvent, TeleportEvent};
 
@@ -70,11 +70,8 @@ pub fn parse_and_execute_command(
             let y: i32 = parts[2].parse().unwrap_or(0);
             let z: i32 = parts[3].parse().unwrap_or(0);
             let item_id_str = parts[4];
-            // Try to get ItemId from interner, fall back to stone if not found
-            let item_id = items::interner()
-                .get(item_id_str)
-                .map(ItemId::from_raw)
-                .unwrap_or_else(items::stone);
+            // Look up the full string ID (base or mod item), fall back to stone
+            let item_id = items::by_string_id(item_id_str).unwrap_or_else(items::stone);
             setblock_writer.write(SetBlockEvent {
                 position: IVec3::new(x, y, z),
                 block_type: item_id,
@@ -112,6 +109,7 @@ pub fn parse_game_action(s: &str) -> Option<GameAction> {
         "ToggleInventory" => Some(GameAction::ToggleInventory),
         "TogglePause" => Some(GameAction::TogglePause),
         "ToggleQuest" => Some(GameAction::ToggleQuest),
+        "ToggleQuestLog" => Some(GameAction::ToggleQuestLog),
         "OpenCommand" => Some(GameAction::OpenCommand),
         "CloseUI" => Some(GameAction::CloseUI),
         "Confirm" => Some(GameAction::Confirm),
@@ -125,11 +123,17 @@ pub fn parse_game_action(s: &str) -> Option<GameAction> {
         "Hotbar7" => Some(GameAction::Hotbar7),
         "Hotbar8" => Some(GameAction::Hotbar8),
         "Hotbar9" => Some(GameAction::Hotbar9),
+        "QuickSelect" => Some(GameAction::QuickSelect),
         "PrimaryAction" => Some(GameAction::PrimaryAction),
         "SecondaryAction" => Some(GameAction::SecondaryAction),
         "RotateBlock" => Some(GameAction::RotateBlock),
+        "Undo" => Some(GameAction::Undo),
+        "DropItem" => Some(GameAction::DropItem),
         "ModifierShift" => Some(GameAction::ModifierShift),
+        "ModifierCtrl" => Some(GameAction::ModifierCtrl),
         "ToggleDebug" => Some(GameAction::ToggleDebug),
+        "ToggleLogConsole" => Some(GameAction::ToggleLogConsole),
+        "Screenshot" => Some(GameAction::Screenshot),
         "DeleteChar" => Some(GameAction::DeleteChar),
         _ => None,
     }
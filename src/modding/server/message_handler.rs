@@ -3,7 +3,7 @@
 use bevy::prelude::*;
 
 use crate::components::{
-    BreakingProgress, CommandInputState, CursorLockState, InputState, InteractingMachine,
+    BreakingProgress, CommandInputState, CursorController, InputState, InteractingMachine,
     InventoryOpen, TargetBlock, UIContext, UIState,
 };
 use crate::events::TestEventBuffer;
@@ -65,7 +65,7 @@ pub fn setup_mod_api_server(mut commands: Commands, config: Res<ModApiServerConf
 pub fn process_server_messages(
     server: Option<ResMut<ModApiServer>>,
     mod_manager: Res<ModManager>,
-    mut cursor_lock: Option<ResMut<CursorLockState>>,
+    mut cursor: Option<ResMut<CursorController>>,
     time: Res<Time>,
     mut ui_state: Option<ResMut<UIState>>,
     mut inventory_open: Option<ResMut<InventoryOpen>>,
@@ -83,7 +83,7 @@ pub fn process_server_messages(
 
     // Build game state info
     let game_state = GameStateInfo {
-        paused: cursor_lock.as_ref().is_some_and(|c| c.paused),
+        paused: cursor.as_ref().is_some_and(|c| c.is_released()),
         tick: (time.elapsed_secs_f64() * 1000.0) as u64,
         player_count: 1, // Single-player for now
     };
@@ -116,7 +116,7 @@ pub fn process_server_messages(
         inventory_open.as_ref(),
         interacting_machine.as_ref(),
         command_state.as_ref(),
-        cursor_lock.as_ref(),
+        cursor.as_ref(),
     ) {
         (Some(inv), Some(machine), Some(cmd), Some(cursor)) => {
             InputState::current(inv, machine, cmd, cursor)
@@ -240,7 +240,7 @@ pub fn process_server_messages(
                             &mut ui_state,
                             &mut inventory_open,
                             &mut interacting_machine,
-                            &mut cursor_lock,
+                            &mut cursor,
                             &mut command_state,
                         );
                     }
@@ -3,7 +3,7 @@
 use bevy::prelude::*;
 
 use crate::components::{
-    CommandInputState, CursorLockState, InteractingMachine, InventoryOpen, UIContext, UIState,
+    CommandInputState, CursorController, InteractingMachine, InventoryOpen, UIContext, UIState,
 };
 
 /// Convert UIContext to string for test API
@@ -14,6 +14,9 @@ pub fn ui_context_to_string(ctx: &UIContext) -> String {
         UIContext::CommandInput => "Command".to_string(),
         UIContext::PauseMenu => "PauseMenu".to_string(),
         UIContext::Settings => "Settings".to_string(),
+        UIContext::Stats => "Stats".to_string(),
+        UIContext::QuestLog => "QuestLog".to_string(),
+        UIContext::Goals => "Goals".to_string(),
         UIContext::Machine(_) => "MachineUI".to_string(),
     }
 }
@@ -24,7 +27,7 @@ pub fn apply_ui_state_change(
     ui_state: &mut Option<ResMut<UIState>>,
     inventory_open: &mut Option<ResMut<InventoryOpen>>,
     interacting_machine: &mut Option<ResMut<InteractingMachine>>,
-    cursor_lock: &mut Option<ResMut<CursorLockState>>,
+    cursor: &mut Option<ResMut<CursorController>>,
     command_state: &mut Option<ResMut<CommandInputState>>,
 ) {
     // Get mutable references to all resources
@@ -32,7 +35,7 @@ pub fn apply_ui_state_change(
         ui_state.as_mut(),
         inventory_open.as_mut(),
         interacting_machine.as_mut(),
-        cursor_lock.as_mut(),
+        cursor.as_mut(),
     ) else {
         tracing::warn!("Cannot apply UI state change: missing resources");
         return;
@@ -53,14 +56,12 @@ pub fn apply_ui_state_change(
         "Gameplay" => {
             ui.clear();
             reset_legacy(inv, machine, command_state);
-            cursor.paused = false;
         }
         "Inventory" => {
             ui.clear();
             ui.push(UIContext::Inventory);
             reset_legacy(inv, machine, command_state);
             inv.0 = true;
-            cursor.paused = false;
         }
         "MachineUI" => {
             ui.clear();
@@ -69,13 +70,11 @@ pub fn apply_ui_state_change(
             ui.push(UIContext::Machine(dummy_entity));
             reset_legacy(inv, machine, command_state);
             machine.0 = Some(dummy_entity);
-            cursor.paused = false;
         }
         "PauseMenu" => {
             ui.clear();
             ui.push(UIContext::PauseMenu);
             reset_legacy(inv, machine, command_state);
-            cursor.paused = true;
         }
         "Command" => {
             ui.clear();
@@ -84,18 +83,33 @@ pub fn apply_ui_state_change(
             if let Some(c) = command_state.as_mut() {
                 c.open = true;
             }
-            cursor.paused = true; // Unlock cursor when command input is open
         }
         "Settings" => {
             ui.clear();
             ui.push(UIContext::Settings);
             reset_legacy(inv, machine, command_state);
-            cursor.paused = true;
+        }
+        "Stats" => {
+            ui.clear();
+            ui.push(UIContext::Stats);
+            reset_legacy(inv, machine, command_state);
+        }
+        "QuestLog" => {
+            ui.clear();
+            ui.push(UIContext::QuestLog);
+            reset_legacy(inv, machine, command_state);
+        }
+        "Goals" => {
+            ui.clear();
+            ui.push(UIContext::Goals);
+            reset_legacy(inv, machine, command_state);
         }
         _ => {
             tracing::warn!("Unknown UI state: {}", state_str);
+            return;
         }
     }
+    cursor.request_ui(ui.current());
 
     tracing::info!("UI state changed to: {}", state_str);
 }
@@ -0,0 +1,243 @@
+//! コンテンツ登録ホスト関数
+//!
+//! Modは `mod_init` の中でだけアイテムとレシピを登録できる。JSONは
+//! データパック（items.toml / recipes.toml）と同じ `ItemDefinition` /
+//! `RecipeDefinition` 形式。IDは `<mod_id>:<名前>` に付け替えるので、
+//! 別のModやbaseと衝突しない。
+
+use super::super::{ModState, WasmError};
+use super::read_string;
+use crate::core::items;
+use crate::modding::data::{ItemDefinition, ModDataPack, RecipeDefinition};
+use std::collections::HashMap;
+use wasmtime::{Caller, Linker};
+
+/// 戻り値: 登録成功
+pub const CONTENT_OK: i32 = 0;
+/// 戻り値: mod_init の外で呼ばれた
+pub const CONTENT_ERR_NOT_IN_INIT: i32 = -1;
+/// 戻り値: JSONが読めない・メモリ範囲外
+pub const CONTENT_ERR_INVALID_JSON: i32 = -2;
+/// 戻り値: IDが空・不正
+pub const CONTENT_ERR_INVALID_ID: i32 = -3;
+/// 戻り値: 同じIDを登録済み
+pub const CONTENT_ERR_DUPLICATE_ID: i32 = -4;
+/// 戻り値: レシピが未知のアイテムを参照している
+pub const CONTENT_ERR_UNKNOWN_ITEM: i32 = -5;
+
+/// 登録を拒否した理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentError {
+    NotInInit,
+    InvalidJson(String),
+    InvalidId(String),
+    DuplicateId(String),
+    UnknownItem(String),
+}
+
+impl ContentError {
+    /// ホスト関数の戻り値
+    pub fn code(&self) -> i32 {
+        match self {
+            ContentError::NotInInit => CONTENT_ERR_NOT_IN_INIT,
+            ContentError::InvalidJson(_) => CONTENT_ERR_INVALID_JSON,
+            ContentError::InvalidId(_) => CONTENT_ERR_INVALID_ID,
+            ContentError::DuplicateId(_) => CONTENT_ERR_DUPLICATE_ID,
+            ContentError::UnknownItem(_) => CONTENT_ERR_UNKNOWN_ITEM,
+        }
+    }
+}
+
+impl std::fmt::Display for ContentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentError::NotInInit => write!(f, "content can only be registered in mod_init"),
+            ContentError::InvalidJson(e) => write!(f, "invalid JSON: {}", e),
+            ContentError::InvalidId(id) => write!(f, "invalid id: {:?}", id),
+            ContentError::DuplicateId(id) => write!(f, "duplicate id: {}", id),
+            ContentError::UnknownItem(id) => write!(f, "unknown item: {}", id),
+        }
+    }
+}
+
+/// コンテンツ登録ホスト関数を登録
+pub fn register(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
+    linker
+        .func_wrap("env", "host_register_item", host_register_item)
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
+    linker
+        .func_wrap("env", "host_register_recipe", host_register_recipe)
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// アイテム定義JSONを登録
+fn host_register_item(mut caller: Caller<'_, ModState>, ptr: u32, len: u32) -> i32 {
+    let json = read_string(&mut caller, ptr, len);
+    let state = caller.data_mut();
+    let result = match json {
+        Some(_) if !state.accepting_content => Err(ContentError::NotInInit),
+        Some(json) => register_item_json(&mut state.content, &state.mod_id, &json),
+        None => Err(ContentError::InvalidJson("unreadable string".to_string())),
+    };
+    report(state, "item", result)
+}
+
+/// レシピ定義JSONを登録
+fn host_register_recipe(mut caller: Caller<'_, ModState>, ptr: u32, len: u32) -> i32 {
+    let json = read_string(&mut caller, ptr, len);
+    let state = caller.data_mut();
+    let result = match json {
+        Some(_) if !state.accepting_content => Err(ContentError::NotInInit),
+        Some(json) => register_recipe_json(&mut state.content, &state.mod_id, &json),
+        None => Err(ContentError::InvalidJson("unreadable string".to_string())),
+    };
+    report(state, "recipe", result)
+}
+
+fn report(state: &ModState, kind: &str, result: Result<String, ContentError>) -> i32 {
+    match result {
+        Ok(id) => {
+            tracing::info!("[Mod:{}] registered {} {}", state.mod_id, kind, id);
+            CONTENT_OK
+        }
+        Err(e) => {
+            tracing::warn!("[Mod:{}] {} rejected: {}", state.mod_id, kind, e);
+            e.code()
+        }
+    }
+}
+
+/// `id` を `<mod_id>:<名前>` にする（名前は元の名前空間を外したもの）
+fn namespaced_id(mod_id: &str, id: &str) -> Result<String, ContentError> {
+    let local = id.rsplit(':').next().unwrap_or(id);
+    let valid = !local.is_empty()
+        && local
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(ContentError::InvalidId(id.to_string()));
+    }
+    Ok(format!("{}:{}", mod_id, local))
+}
+
+/// レシピが参照するアイテムを完全なIDにする（base か、このModが登録済みのもの）
+fn resolve_item(pack: &ModDataPack, mod_id: &str, id: &str) -> Result<String, ContentError> {
+    let (namespace, local) = id.split_once(':').unwrap_or(("", id));
+    if matches!(namespace, "" | "base") && items::by_name(local).is_some() {
+        return Ok(format!("base:{}", local));
+    }
+    let own = format!("{}:{}", mod_id, local);
+    if (namespace.is_empty() || namespace == mod_id) && pack.items.iter().any(|i| i.id == own) {
+        return Ok(own);
+    }
+    Err(ContentError::UnknownItem(id.to_string()))
+}
+
+fn resolve_items(
+    pack: &ModDataPack,
+    mod_id: &str,
+    stacks: &HashMap<String, u32>,
+) -> Result<HashMap<String, u32>, ContentError> {
+    stacks
+        .iter()
+        .map(|(id, &count)| Ok((resolve_item(pack, mod_id, id)?, count)))
+        .collect()
+}
+
+/// アイテム定義を検証してパックに追加し、登録したIDを返す
+pub fn register_item_json(
+    pack: &mut ModDataPack,
+    mod_id: &str,
+    json: &str,
+) -> Result<String, ContentError> {
+    let mut item: ItemDefinition =
+        serde_json::from_str(json).map_err(|e| ContentError::InvalidJson(e.to_string()))?;
+    item.id = namespaced_id(mod_id, &item.id)?;
+    if pack.items.iter().any(|existing| existing.id == item.id) {
+        return Err(ContentError::DuplicateId(item.id));
+    }
+    let id = item.id.clone();
+    pack.add_item(item);
+    Ok(id)
+}
+
+/// レシピ定義を検証してパックに追加し、登録したIDを返す
+///
+/// 入力・出力・燃料は base のアイテムか、先に登録したこのModのアイテムだけ
+pub fn register_recipe_json(
+    pack: &mut ModDataPack,
+    mod_id: &str,
+    json: &str,
+) -> Result<String, ContentError> {
+    let mut recipe: RecipeDefinition =
+        serde_json::from_str(json).map_err(|e| ContentError::InvalidJson(e.to_string()))?;
+    recipe.id = namespaced_id(mod_id, &recipe.id)?;
+    if pack.recipes.iter().any(|existing| existing.id == recipe.id) {
+        return Err(ContentError::DuplicateId(recipe.id));
+    }
+    if recipe.outputs.is_empty() {
+        return Err(ContentError::InvalidJson(
+            "recipe has no outputs".to_string(),
+        ));
+    }
+    recipe.inputs = resolve_items(pack, mod_id, &recipe.inputs)?;
+    recipe.outputs = resolve_items(pack, mod_id, &recipe.outputs)?;
+    recipe.fuel = resolve_items(pack, mod_id, &recipe.fuel)?;
+    let id = recipe.id.clone();
+    pack.add_recipe(recipe);
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_ids_are_prefixed_with_mod_id() {
+        let mut pack = ModDataPack::new();
+        let id = register_item_json(&mut pack, "gears", r#"{"id": "base:stone", "name": "石"}"#);
+        assert_eq!(id, Ok("gears:stone".to_string()));
+
+        let duplicate = register_item_json(&mut pack, "gears", r#"{"id": "stone", "name": "石"}"#);
+        assert_eq!(
+            duplicate,
+            Err(ContentError::DuplicateId("gears:stone".to_string()))
+        );
+        assert_eq!(
+            register_item_json(&mut pack, "gears", r#"{"id": "Bad Id", "name": "x"}"#)
+                .map_err(|e| e.code()),
+            Err(CONTENT_ERR_INVALID_ID)
+        );
+        assert_eq!(pack.item_count(), 1);
+    }
+
+    #[test]
+    fn test_recipe_references_are_validated() {
+        let mut pack = ModDataPack::new();
+        register_item_json(&mut pack, "gears", r#"{"id": "gear", "name": "歯車"}"#).unwrap();
+
+        let unknown = register_recipe_json(
+            &mut pack,
+            "gears",
+            r#"{"id": "x", "machine": "assembler", "inputs": {"mithril": 1}, "outputs": {"gear": 1}}"#,
+        );
+        assert_eq!(
+            unknown,
+            Err(ContentError::UnknownItem("mithril".to_string()))
+        );
+
+        let id = register_recipe_json(
+            &mut pack,
+            "gears",
+            r#"{"id": "gear", "machine": "assembler", "inputs": {"iron_ingot": 2}, "outputs": {"gear": 1}}"#,
+        )
+        .unwrap();
+        assert_eq!(id, "gears:gear");
+        let recipe = &pack.recipes[0];
+        assert_eq!(recipe.inputs.get("base:iron_ingot"), Some(&2));
+        assert_eq!(recipe.outputs.get("gears:gear"), Some(&1));
+    }
+}
@@ -1,8 +1,17 @@
 //! インベントリ関連ホスト関数
+//!
+//! entity_id 0 はローカルプレイヤー、それ以外は機械。読み書きは
+//! `InventoryTable` 経由で、追加・消費は次のtickでゲームに反映される。
+//! Modによる変更は `category = "MOD"` の構造化ログに残す。
 
+use super::super::inventory_table::InventoryError;
 use super::super::{ModState, WasmError};
+use crate::core::ItemId;
 use wasmtime::{Caller, Linker};
 
+/// host_consume_inventory_item の戻り値: 未知のアイテム
+pub const INVENTORY_ERR_UNKNOWN_ITEM: i32 = -1;
+
 /// インベントリ関連ホスト関数を登録
 pub fn register(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
     linker
@@ -13,19 +22,117 @@ pub fn register(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
         .func_wrap("env", "host_transfer_item", host_transfer_item)
         .map_err(|e| WasmError::LinkError(e.to_string()))?;
 
+    linker
+        .func_wrap("env", "host_add_inventory_item", host_add_inventory_item)
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
+    linker
+        .func_wrap(
+            "env",
+            "host_consume_inventory_item",
+            host_consume_inventory_item,
+        )
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
     Ok(())
 }
 
 /// インベントリスロットを取得
-/// 戻り値: 上位32bit=item_id, 下位32bit=count
-fn host_get_inventory_slot(_caller: Caller<'_, ModState>, entity_id: u64, slot: u32) -> u64 {
-    // TODO: 実際のインベントリからの取得
-    tracing::debug!(
-        "host_get_inventory_slot called for entity {} slot {}",
+/// 戻り値: 上位32bit=item_id, 下位32bit=count（空・範囲外は0）
+fn host_get_inventory_slot(caller: Caller<'_, ModState>, entity_id: u64, slot: u32) -> u64 {
+    caller
+        .data()
+        .inventories
+        .slot(entity_id, slot as usize)
+        .map(|(item_id, count)| (u64::from(item_id.raw()) << 32) | u64::from(count))
+        .unwrap_or(0)
+}
+
+/// アイテムを追加
+/// 戻り値: 入りきらなかった数（拒否した場合は count 全部）
+fn host_add_inventory_item(
+    caller: Caller<'_, ModState>,
+    entity_id: u64,
+    item_id: u32,
+    count: u32,
+) -> u32 {
+    let state = caller.data();
+    match state.inventories.add(entity_id, item_id, count) {
+        Ok(overflow) => {
+            tracing::info!(
+                category = "MOD",
+                action = "inventory_add",
+                mod_id = %state.mod_id,
+                entity_id,
+                item = item_name(item_id),
+                requested = count,
+                added = count - overflow,
+                "Mod added items"
+            );
+            overflow
+        }
+        Err(e) => {
+            reject(state, "inventory_add", entity_id, item_id, count, e);
+            count
+        }
+    }
+}
+
+/// アイテムを消費（足りなければある分だけ）
+/// 戻り値: 消費した数、-1=未知のアイテム（未知のentity_idは0）
+fn host_consume_inventory_item(
+    caller: Caller<'_, ModState>,
+    entity_id: u64,
+    item_id: u32,
+    count: u32,
+) -> i32 {
+    let state = caller.data();
+    match state.inventories.consume(entity_id, item_id, count) {
+        Ok(consumed) => {
+            tracing::info!(
+                category = "MOD",
+                action = "inventory_consume",
+                mod_id = %state.mod_id,
+                entity_id,
+                item = item_name(item_id),
+                requested = count,
+                consumed,
+                "Mod consumed items"
+            );
+            consumed as i32
+        }
+        Err(e) => {
+            reject(state, "inventory_consume", entity_id, item_id, count, e);
+            match e {
+                InventoryError::UnknownItem => INVENTORY_ERR_UNKNOWN_ITEM,
+                InventoryError::UnknownEntity => 0,
+            }
+        }
+    }
+}
+
+fn item_name(item_id: u32) -> &'static str {
+    ItemId::from_raw(item_id).name().unwrap_or("?")
+}
+
+fn reject(
+    state: &ModState,
+    action: &str,
+    entity_id: u64,
+    item_id: u32,
+    count: u32,
+    error: InventoryError,
+) {
+    tracing::warn!(
+        category = "MOD",
+        action,
+        mod_id = %state.mod_id,
         entity_id,
-        slot
+        item_id,
+        requested = count,
+        ?error,
+        "Mod inventory change rejected"
     );
-    0 // 空スロット
 }
 
 /// アイテムを転送
@@ -1,6 +1,7 @@
 //! ログ関連ホスト関数
 
 use super::super::{ModState, WasmError};
+use super::read_string;
 use wasmtime::{Caller, Linker};
 
 /// ログ関連ホスト関数を登録
@@ -29,11 +30,3 @@ fn host_log_error(mut caller: Caller<'_, ModState>, ptr: u32, len: u32) {
         tracing::error!("[Mod:{}] {}", mod_id, msg);
     }
 }
-
-/// WASMメモリから文字列を読み取る
-fn read_string(caller: &mut Caller<'_, ModState>, ptr: u32, len: u32) -> Option<String> {
-    let memory = caller.get_export("memory")?.into_memory()?;
-    let data = memory.data(&caller);
-    let slice = data.get(ptr as usize..(ptr + len) as usize)?;
-    String::from_utf8(slice.to_vec()).ok()
-}
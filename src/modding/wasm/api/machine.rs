@@ -17,22 +17,32 @@ pub fn register(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
 }
 
 /// 機械の状態を取得
-/// 戻り値: 0=正常, 1=処理中, 2=待機中, -1=エラー
-fn host_get_machine_state(_caller: Caller<'_, ModState>, entity_id: u64) -> i32 {
-    // TODO: 実際のBevy Worldからの状態取得
-    // 現在はスタブ実装
-    tracing::debug!("host_get_machine_state called for entity {}", entity_id);
-    0 // 正常
+/// 戻り値: 0=正常, 1=処理中, 2=待機中（停止中）, -1=エラー
+fn host_get_machine_state(caller: Caller<'_, ModState>, entity_id: u64) -> i32 {
+    match caller.data().machines.get(entity_id) {
+        Some(entry) => entry.state_code(),
+        None => {
+            tracing::debug!(
+                "host_get_machine_state: unknown entity {} (mod {})",
+                entity_id,
+                caller.data().mod_id
+            );
+            -1
+        }
+    }
 }
 
-/// 機械の有効/無効を設定
+/// 機械の有効/無効を設定（次のtickでゲームに反映）
 /// 戻り値: 0=成功, -1=エンティティ不存在, -2=権限エラー
-fn host_set_machine_enabled(_caller: Caller<'_, ModState>, entity_id: u64, enabled: i32) -> i32 {
-    // TODO: 実際のBevy Worldへの状態設定
-    tracing::debug!(
-        "host_set_machine_enabled called for entity {} -> {}",
-        entity_id,
-        enabled != 0
-    );
-    0 // 成功
+fn host_set_machine_enabled(caller: Caller<'_, ModState>, entity_id: u64, enabled: i32) -> i32 {
+    if caller.data().machines.set_enabled(entity_id, enabled != 0) {
+        0
+    } else {
+        tracing::debug!(
+            "host_set_machine_enabled: unknown entity {} (mod {})",
+            entity_id,
+            caller.data().mod_id
+        );
+        -1
+    }
 }
@@ -2,13 +2,15 @@
 //!
 //! Core Modから呼び出せるホスト関数を定義
 
+pub mod content;
 pub mod event;
 pub mod inventory;
 pub mod log;
 pub mod machine;
+pub mod version;
 
 use super::{ModState, WasmError};
-use wasmtime::Linker;
+use wasmtime::{Caller, Linker};
 
 /// 全ホスト関数をLinkerに登録
 pub fn register_all(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
@@ -16,5 +18,15 @@ pub fn register_all(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
     machine::register(linker)?;
     inventory::register(linker)?;
     event::register(linker)?;
+    version::register(linker)?;
+    content::register(linker)?;
     Ok(())
 }
+
+/// WASMメモリから文字列を読み取る
+pub(crate) fn read_string(caller: &mut Caller<'_, ModState>, ptr: u32, len: u32) -> Option<String> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let data = memory.data(&caller);
+    let slice = data.get(ptr as usize..(ptr as usize).checked_add(len as usize)?)?;
+    String::from_utf8(slice.to_vec()).ok()
+}
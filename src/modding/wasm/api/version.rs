@@ -0,0 +1,98 @@
+//! APIバージョン・機能フラグ関連ホスト関数
+//!
+//! Modは `mod_api_version() -> u32` をエクスポートして、ビルドに使った
+//! SDKのAPIバージョンを宣言する。ホストより新しいバージョンを要求する
+//! Modはロードを拒否し、古いModは警告を出して読み込む。
+
+use super::super::{ModState, WasmError};
+use wasmtime::{Caller, Linker};
+
+/// ホストが提供するModAPIのバージョン
+///
+/// 1 = バージョン宣言の無かった初期API、2 = バージョン・機能フラグ追加、
+/// 3 = アイテム・レシピ登録追加、4 = インベントリの追加・消費追加
+pub const HOST_API_VERSION: u32 = 4;
+
+/// `mod_api_version` をエクスポートしていないModのバージョン
+pub const LEGACY_API_VERSION: u32 = 1;
+
+/// 機能フラグ: クエスト操作
+pub const CAP_QUESTS: u32 = 1 << 0;
+/// 機能フラグ: インベントリ操作
+pub const CAP_INVENTORY: u32 = 1 << 1;
+/// 機能フラグ: 機械の状態取得・有効/無効切り替え
+pub const CAP_MACHINE_CONTROL: u32 = 1 << 2;
+/// 機能フラグ: イベントv2（購読とデータ付き通知）
+pub const CAP_EVENTS_V2: u32 = 1 << 3;
+/// 機能フラグ: mod_init でのアイテム・レシピ登録
+pub const CAP_CONTENT: u32 = 1 << 4;
+
+/// このホストで実際に動く機能
+///
+/// イベントのホスト関数はまだ仮実装なので立てない（インベントリも
+/// host_transfer_item だけは仮実装のまま）
+pub const HOST_CAPABILITIES: u32 = CAP_INVENTORY | CAP_MACHINE_CONTROL | CAP_CONTENT;
+
+/// ModとホストのAPIバージョンの関係
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiCompatibility {
+    /// 同じバージョン
+    Compatible,
+    /// Modの方が古い（警告して読み込む）
+    Older,
+    /// Modの方が新しい（読み込まない）
+    TooNew,
+}
+
+/// Modの要求バージョンとホストのバージョンを比較
+pub fn check_compatibility(mod_version: u32, host_version: u32) -> ApiCompatibility {
+    match mod_version.cmp(&host_version) {
+        std::cmp::Ordering::Equal => ApiCompatibility::Compatible,
+        std::cmp::Ordering::Less => ApiCompatibility::Older,
+        std::cmp::Ordering::Greater => ApiCompatibility::TooNew,
+    }
+}
+
+/// バージョン関連ホスト関数を登録
+pub fn register(linker: &mut Linker<ModState>) -> Result<(), WasmError> {
+    linker
+        .func_wrap("env", "host_get_api_version", host_get_api_version)
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
+    linker
+        .func_wrap("env", "host_get_capabilities", host_get_capabilities)
+        .map_err(|e| WasmError::LinkError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// ホストのAPIバージョンを返す
+fn host_get_api_version() -> u32 {
+    HOST_API_VERSION
+}
+
+/// 機能フラグ（u32、リトルエンディアン）をModのメモリに書き込む
+/// 戻り値: 書き込んだバイト数（4）、バッファ不足・範囲外は0
+fn host_get_capabilities(mut caller: Caller<'_, ModState>, ptr: u32, len: u32) -> u32 {
+    let bytes = HOST_CAPABILITIES.to_le_bytes();
+    if (len as usize) < bytes.len() {
+        return 0;
+    }
+    let Some(memory) = caller
+        .get_export("memory")
+        .and_then(|export| export.into_memory())
+    else {
+        return 0;
+    };
+    match memory.write(&mut caller, ptr as usize, &bytes) {
+        Ok(()) => bytes.len() as u32,
+        Err(_) => {
+            tracing::debug!(
+                "host_get_capabilities: out of bounds write at {} (mod {})",
+                ptr,
+                caller.data().mod_id
+            );
+            0
+        }
+    }
+}
@@ -0,0 +1,455 @@
+//! Mod向けインベントリテーブル
+//!
+//! `MachineTable` と同じく、WASM実行中のホスト関数はこのテーブルの写しを
+//! 読み書きする。追加・消費はその場で写しに反映して結果を返し、
+//! `sync_inventory_table` が次のtickで同じ操作を実際のインベントリへ
+//! 適用してから、写しをWorldの状態で作り直す。
+//!
+//! entity_id 0 はローカルプレイヤー、それ以外は機械（`Entity::to_bits`）。
+//! 機械のバッファは出力スロット→入力スロットの順に消費し、追加は同じ
+//! アイテムのスロットに積むか、空いた出力スロットに入れる。
+
+use crate::components::{Machine, MachineSlot, MachineSlots};
+use crate::constants::{MAX_STACK_SIZE, NUM_SLOTS};
+use crate::core::ItemId;
+use crate::game_spec::GameRegistry;
+use crate::player::{LocalPlayer, PlayerInventory};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// ローカルプレイヤーのインベントリを指す entity_id
+pub const PLAYER_ENTITY_ID: u64 = 0;
+
+/// 1回の呼び出しで動かせる最大数（プレイヤーのインベントリ1杯分）
+pub const MAX_ITEMS_PER_CALL: u32 = MAX_STACK_SIZE * NUM_SLOTS as u32;
+
+/// 追加・消費を拒否した理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryError {
+    /// ItemRegistryに無いアイテム
+    UnknownItem,
+    /// テーブルに無いentity_id
+    UnknownEntity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InventoryOp {
+    Add,
+    Consume,
+}
+
+/// Worldへ未反映の変更
+#[derive(Debug, Clone, Copy)]
+struct PendingChange {
+    entity_id: u64,
+    op: InventoryOp,
+    item: ItemId,
+    count: u32,
+}
+
+/// インベントリ1つ分の写し
+#[derive(Debug, Clone, Default)]
+struct InventoryMirror {
+    slots: Vec<Option<(ItemId, u32)>>,
+    /// 新しいスタックを置ける先頭のスロット数（機械は出力スロットだけ）
+    open_slots: usize,
+}
+
+#[derive(Default)]
+struct InventoryTableInner {
+    inventories: HashMap<u64, InventoryMirror>,
+    known_items: HashSet<ItemId>,
+    pending: Vec<PendingChange>,
+}
+
+/// entity_id とインベントリの対応表（全Modインスタンスで共有）
+#[derive(Resource, Clone, Default)]
+pub struct InventoryTable(Arc<Mutex<InventoryTableInner>>);
+
+impl InventoryTable {
+    /// スロットの中身（範囲外・空・未登録ならNone）
+    pub fn slot(&self, entity_id: u64, slot: usize) -> Option<(ItemId, u32)> {
+        let inner = self.0.lock().ok()?;
+        *inner.inventories.get(&entity_id)?.slots.get(slot)?
+    }
+
+    /// 最大 `count` 個追加し、入りきらなかった数を返す（次の同期でWorldへ反映）
+    pub fn add(&self, entity_id: u64, item_id: u32, count: u32) -> Result<u32, InventoryError> {
+        self.apply(entity_id, item_id, count, InventoryOp::Add)
+            .map(|added| count - added)
+    }
+
+    /// 最大 `count` 個消費し、消費した数を返す（次の同期でWorldへ反映）
+    pub fn consume(&self, entity_id: u64, item_id: u32, count: u32) -> Result<u32, InventoryError> {
+        self.apply(entity_id, item_id, count, InventoryOp::Consume)
+    }
+
+    /// 写しに操作を適用し、動いた数を返す
+    fn apply(
+        &self,
+        entity_id: u64,
+        item_id: u32,
+        count: u32,
+        op: InventoryOp,
+    ) -> Result<u32, InventoryError> {
+        let Ok(mut inner) = self.0.lock() else {
+            return Err(InventoryError::UnknownEntity);
+        };
+        let item = ItemId::from_raw(item_id);
+        if !inner.known_items.contains(&item) {
+            return Err(InventoryError::UnknownItem);
+        }
+        let Some(mirror) = inner.inventories.get_mut(&entity_id) else {
+            return Err(InventoryError::UnknownEntity);
+        };
+
+        let count = count.min(MAX_ITEMS_PER_CALL);
+        let moved = apply_to_slots(&mut mirror.slots, mirror.open_slots, op, item, count);
+        if moved > 0 {
+            inner.pending.push(PendingChange {
+                entity_id,
+                op,
+                item,
+                count: moved,
+            });
+        }
+        Ok(moved)
+    }
+
+    /// 保留中の変更を取り出す
+    fn take_pending(&self) -> Vec<PendingChange> {
+        self.0
+            .lock()
+            .map(|mut inner| std::mem::take(&mut inner.pending))
+            .unwrap_or_default()
+    }
+
+    /// テーブルを作り直す（`known_items` がNoneなら登録アイテムは前のまま）
+    fn replace(
+        &self,
+        inventories: HashMap<u64, InventoryMirror>,
+        known_items: Option<HashSet<ItemId>>,
+    ) {
+        if let Ok(mut inner) = self.0.lock() {
+            inner.inventories = inventories;
+            if let Some(known_items) = known_items {
+                inner.known_items = known_items;
+            }
+        }
+    }
+}
+
+/// `slots` に操作を適用し、動いた数を返す
+fn apply_to_slots(
+    slots: &mut [Option<(ItemId, u32)>],
+    open_slots: usize,
+    op: InventoryOp,
+    item: ItemId,
+    count: u32,
+) -> u32 {
+    match op {
+        InventoryOp::Add => count - add_to_slots(slots, open_slots, item, count),
+        InventoryOp::Consume => take_from_slots(slots, item, count),
+    }
+}
+
+/// 同じアイテムのスロットに積んでから空きスロット（先頭 `open_slots` 個）に入れる
+/// 戻り値: 入りきらなかった数
+fn add_to_slots(
+    slots: &mut [Option<(ItemId, u32)>],
+    open_slots: usize,
+    item: ItemId,
+    count: u32,
+) -> u32 {
+    let mut left = count;
+    for (id, stack) in slots.iter_mut().flatten() {
+        if *id == item && *stack < MAX_STACK_SIZE {
+            let added = left.min(MAX_STACK_SIZE - *stack);
+            *stack += added;
+            left -= added;
+        }
+    }
+    for slot in slots.iter_mut().take(open_slots) {
+        if left == 0 {
+            break;
+        }
+        if slot.is_none() {
+            let added = left.min(MAX_STACK_SIZE);
+            *slot = Some((item, added));
+            left -= added;
+        }
+    }
+    left
+}
+
+/// 前のスロットから最大 `count` 個取り出し、取り出した数を返す
+fn take_from_slots(slots: &mut [Option<(ItemId, u32)>], item: ItemId, count: u32) -> u32 {
+    let mut taken = 0;
+    for slot in slots.iter_mut() {
+        let Some((id, stack)) = slot else {
+            continue;
+        };
+        if *id != item || taken == count {
+            continue;
+        }
+        let take = (count - taken).min(*stack);
+        *stack -= take;
+        taken += take;
+        if *stack == 0 {
+            *slot = None;
+        }
+    }
+    taken
+}
+
+/// 機械のバッファ（出力→入力の順）
+fn machine_buffer(slots: &MachineSlots) -> InventoryMirror {
+    let buffer = slots
+        .outputs
+        .iter()
+        .chain(slots.inputs.iter())
+        .map(|slot| {
+            slot.item_id
+                .filter(|_| slot.count > 0)
+                .map(|id| (id, slot.count))
+        })
+        .collect();
+    InventoryMirror {
+        slots: buffer,
+        open_slots: slots.outputs.len(),
+    }
+}
+
+/// バッファを機械のスロットへ書き戻す
+fn write_machine_buffer(slots: &mut MachineSlots, buffer: &[Option<(ItemId, u32)>]) {
+    let targets = slots.outputs.iter_mut().chain(slots.inputs.iter_mut());
+    for (slot, stack) in targets.zip(buffer) {
+        *slot = match *stack {
+            Some((item_id, count)) => MachineSlot {
+                item_id: Some(item_id),
+                count,
+            },
+            None => MachineSlot::empty(),
+        };
+    }
+}
+
+/// Modからの変更をWorldへ反映し、テーブルを最新の状態に更新
+pub fn sync_inventory_table(
+    table: Res<InventoryTable>,
+    registry: Res<GameRegistry>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut players: Query<&mut PlayerInventory>,
+    mut machines: Query<(Entity, &mut Machine)>,
+) {
+    let player = local_player.map(|p| p.0);
+
+    for change in table.take_pending() {
+        let moved = if change.entity_id == PLAYER_ENTITY_ID {
+            player
+                .and_then(|entity| players.get_mut(entity).ok())
+                .map(|mut inventory| {
+                    let open_slots = inventory.slots.len();
+                    apply_to_slots(
+                        &mut inventory.slots,
+                        open_slots,
+                        change.op,
+                        change.item,
+                        change.count,
+                    )
+                })
+        } else {
+            machines
+                .iter_mut()
+                .find(|(entity, _)| entity.to_bits() == change.entity_id)
+                .map(|(_, mut machine)| {
+                    let mut buffer = machine_buffer(&machine.slots);
+                    let moved = apply_to_slots(
+                        &mut buffer.slots,
+                        buffer.open_slots,
+                        change.op,
+                        change.item,
+                        change.count,
+                    );
+                    write_machine_buffer(&mut machine.slots, &buffer.slots);
+                    moved
+                })
+        };
+        // 写しを作ってからWorldが変わっていると、結果がずれることがある
+        if moved != Some(change.count) {
+            warn!(
+                category = "MOD",
+                action = "inventory_sync",
+                entity_id = change.entity_id,
+                op = ?change.op,
+                expected = change.count,
+                applied = moved.unwrap_or(0),
+                "Mod inventory change only partly applied"
+            );
+        }
+    }
+
+    let mut inventories = HashMap::new();
+    if let Some(inventory) = player.and_then(|entity| players.get(entity).ok()) {
+        inventories.insert(
+            PLAYER_ENTITY_ID,
+            InventoryMirror {
+                slots: inventory.slots.to_vec(),
+                open_slots: inventory.slots.len(),
+            },
+        );
+    }
+    for (entity, machine) in machines.iter() {
+        inventories.insert(entity.to_bits(), machine_buffer(&machine.slots));
+    }
+    let known_items = registry
+        .is_changed()
+        .then(|| registry.all_item_ids().collect());
+    table.replace(inventories, known_items);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+    use crate::game_spec::FURNACE;
+    use crate::Direction;
+
+    /// プレイヤーと精錬炉1台を置いたApp（1回更新済み）
+    fn table_app(player_items: &[(ItemId, u32)]) -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.init_resource::<InventoryTable>()
+            .init_resource::<GameRegistry>()
+            .add_systems(Update, sync_inventory_table);
+        let player = app
+            .world_mut()
+            .spawn(PlayerInventory::with_initial_items_by_id(player_items))
+            .id();
+        app.insert_resource(LocalPlayer(player));
+        let furnace = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::ZERO, Direction::North))
+            .id();
+        app.update();
+        (app, player, furnace)
+    }
+
+    fn table(app: &App) -> InventoryTable {
+        app.world().resource::<InventoryTable>().clone()
+    }
+
+    #[test]
+    fn test_add_to_player_reports_overflow() {
+        let (mut app, player, _) = table_app(&[(items::stone(), 1)]);
+        let table = table(&app);
+        let iron = items::iron_ingot().raw();
+
+        // 全スロットを石で埋めて、残りは iron の既存スタックにだけ入る
+        assert_eq!(table.add(PLAYER_ENTITY_ID, iron, 10), Ok(0));
+        let space = MAX_STACK_SIZE * (NUM_SLOTS as u32 - 2);
+        assert_eq!(
+            table.add(PLAYER_ENTITY_ID, items::stone().raw(), space),
+            Ok(0)
+        );
+        assert_eq!(table.add(PLAYER_ENTITY_ID, iron, MAX_STACK_SIZE), Ok(10));
+
+        // 巨大な要求は1回分に切り詰め、超えた分も溢れとして返す
+        assert_eq!(table.add(PLAYER_ENTITY_ID, iron, u32::MAX), Ok(u32::MAX));
+
+        app.update();
+        let inventory = app.world().get::<PlayerInventory>(player).unwrap();
+        assert_eq!(
+            inventory.get_total_count_by_id(items::iron_ingot()),
+            MAX_STACK_SIZE
+        );
+        assert!(inventory.slots.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_consume_is_partial_and_rejects_unknown_items() {
+        let (mut app, player, _) = table_app(&[(items::iron_ore(), 5)]);
+        let table = table(&app);
+        let ore = items::iron_ore().raw();
+
+        assert_eq!(table.consume(PLAYER_ENTITY_ID, ore, 3), Ok(3));
+        assert_eq!(table.consume(PLAYER_ENTITY_ID, ore, 10), Ok(2));
+        assert_eq!(table.consume(PLAYER_ENTITY_ID, ore, 1), Ok(0));
+
+        // 登録されていないID・entity_id
+        assert_eq!(
+            table.consume(PLAYER_ENTITY_ID, u32::MAX, 1),
+            Err(InventoryError::UnknownItem)
+        );
+        assert_eq!(
+            table.add(PLAYER_ENTITY_ID, u32::MAX, 1),
+            Err(InventoryError::UnknownItem)
+        );
+        assert_eq!(table.add(12345, ore, 1), Err(InventoryError::UnknownEntity));
+
+        app.update();
+        let inventory = app.world().get::<PlayerInventory>(player).unwrap();
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ore()), 0);
+    }
+
+    #[test]
+    fn test_machine_buffer_target() {
+        let (mut app, _, furnace) = table_app(&[]);
+        let table = table(&app);
+        let id = furnace.to_bits();
+        let ingot = items::iron_ingot().raw();
+
+        // 追加は出力スロットへ、1スロット分を超えた分は溢れる
+        assert_eq!(table.add(id, ingot, MAX_STACK_SIZE + 5), Ok(5));
+        assert_eq!(
+            table.slot(id, 0),
+            Some((items::iron_ingot(), MAX_STACK_SIZE))
+        );
+        // 空の入力スロットには新しいスタックを置かない
+        assert_eq!(table.add(id, items::coal().raw(), 1), Ok(1));
+
+        assert_eq!(table.consume(id, ingot, 4), Ok(4));
+        app.update();
+
+        let machine = app.world().get::<Machine>(furnace).unwrap();
+        assert_eq!(machine.slots.outputs[0].count, MAX_STACK_SIZE - 4);
+        assert!(machine.slots.inputs.iter().all(MachineSlot::is_empty));
+        assert_eq!(
+            table.slot(id, 0),
+            Some((items::iron_ingot(), MAX_STACK_SIZE - 4))
+        );
+    }
+
+    #[test]
+    fn test_host_functions_reach_inventory() {
+        use crate::modding::wasm::WasmRuntime;
+
+        let (mut app, player, _) = table_app(&[(items::coal(), 2)]);
+        // 鉄インゴット3個を追加、石炭5個を消費、未知のアイテムを消費
+        // 結果を 溢れ*10000 + 消費数*100 + (未知の結果+1) にまとめる
+        let wat = format!(
+            r#"(module
+                (import "env" "host_add_inventory_item" (func $add (param i64 i32 i32) (result i32)))
+                (import "env" "host_consume_inventory_item" (func $consume (param i64 i32 i32) (result i32)))
+                (func (export "mod_init") (result i32)
+                    (i32.add
+                        (i32.add
+                            (i32.mul (call $add (i64.const 0) (i32.const {iron}) (i32.const 3)) (i32.const 10000))
+                            (i32.mul (call $consume (i64.const 0) (i32.const {coal}) (i32.const 5)) (i32.const 100)))
+                        (i32.add (call $consume (i64.const 0) (i32.const -1) (i32.const 1)) (i32.const 1)))))"#,
+            iron = items::iron_ingot().raw(),
+            coal = items::coal().raw(),
+        );
+        let mut runtime = WasmRuntime::new()
+            .unwrap()
+            .with_inventory_table(table(&app));
+        runtime.load_module("rewards", wat.as_bytes()).unwrap();
+        runtime.instantiate("rewards").unwrap();
+        assert_eq!(runtime.call_init("rewards").unwrap(), 200);
+
+        app.update();
+        let inventory = app.world().get::<PlayerInventory>(player).unwrap();
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 3);
+        assert_eq!(inventory.get_total_count_by_id(items::coal()), 0);
+    }
+}
@@ -1,7 +1,34 @@
 //! WASMファイルローダー
+//!
+//! 起動時に `mods/*/mod.toml` の `type = "core"` なModを探し、
+//! `<mod_id>.wasm` を読み込んで mod_init を呼ぶ。登録されたアイテム・
+//! レシピはデータパックとして `LoadedModData` に入る（ワールド開始前）。
 
-use super::WasmError;
-use std::path::Path;
+use super::{InventoryTable, MachineTable, WasmError, WasmRuntime};
+use crate::modding::LoadedModData;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Modディレクトリの探索先（実行ファイルからの相対パス）
+const MODS_DIRS: [&str; 3] = ["mods", "../mods", "../../mods"];
+
+/// mod.toml のうちローダーが見る部分
+#[derive(Deserialize)]
+struct ModToml {
+    #[serde(rename = "mod")]
+    info: ModTomlInfo,
+}
+
+#[derive(Deserialize)]
+struct ModTomlInfo {
+    id: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+}
+
+/// 読み込んだCore Mod（mod_tick 等で使うためインスタンスを保持する）
+pub struct CoreModRuntime(pub WasmRuntime);
 
 /// WASMファイルローダー
 pub struct WasmModLoader;
@@ -12,6 +39,28 @@ impl WasmModLoader {
         std::fs::read(path).map_err(WasmError::from)
     }
 
+    /// `mods_dir` 直下の Core Mod を (mod_id, wasmパス) で列挙（ID順）
+    pub fn discover(mods_dir: &Path) -> Vec<(String, PathBuf)> {
+        let Ok(entries) = std::fs::read_dir(mods_dir) else {
+            return Vec::new();
+        };
+        let mut found: Vec<(String, PathBuf)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let dir = entry.path();
+                let toml_str = std::fs::read_to_string(dir.join("mod.toml")).ok()?;
+                let manifest: ModToml = toml::from_str(&toml_str).ok()?;
+                if manifest.info.kind != "core" {
+                    return None;
+                }
+                let wasm_path = dir.join(format!("{}.wasm", manifest.info.id));
+                wasm_path.exists().then_some((manifest.info.id, wasm_path))
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
     /// WASMバイトを検証
     pub fn validate_wasm(bytes: &[u8]) -> Result<(), WasmError> {
         // WASMマジックナンバーをチェック
@@ -39,6 +88,70 @@ impl WasmModLoader {
     }
 }
 
+/// Core Mod を読み込み、mod_init で登録されたコンテンツを `LoadedModData` に追加
+///
+/// 読み込み・初期化に失敗したModは警告を出して飛ばす
+pub fn load_core_mods(world: &mut World) {
+    let Some(mods_dir) = MODS_DIRS.iter().map(Path::new).find(|p| p.exists()) else {
+        return;
+    };
+    let machines = world
+        .get_resource::<MachineTable>()
+        .cloned()
+        .unwrap_or_default();
+    let inventories = world
+        .get_resource::<InventoryTable>()
+        .cloned()
+        .unwrap_or_default();
+    let mut runtime = match WasmRuntime::new() {
+        Ok(runtime) => runtime
+            .with_machine_table(machines.clone())
+            .with_inventory_table(inventories),
+        Err(e) => {
+            warn!("WASM runtime unavailable: {}", e);
+            return;
+        }
+    };
+
+    let mut packs = Vec::new();
+    for (mod_id, path) in WasmModLoader::discover(mods_dir) {
+        let result = WasmModLoader::load_from_path(&path).and_then(|bytes| {
+            WasmModLoader::validate_wasm(&bytes)?;
+            runtime.load_module(&mod_id, &bytes)?;
+            runtime.instantiate(&mod_id)?;
+            runtime.call_init(&mod_id)
+        });
+        match result {
+            Ok(0) => {
+                let pack = runtime
+                    .registered_content(&mod_id)
+                    .cloned()
+                    .unwrap_or_default();
+                info!(
+                    "Core mod {} loaded: {} items, {} recipes",
+                    mod_id,
+                    pack.item_count(),
+                    pack.recipe_count()
+                );
+                if pack.item_count() + pack.recipe_count() > 0 {
+                    packs.push((mod_id, pack));
+                }
+            }
+            Ok(code) => warn!("Core mod {} failed to initialize (code {})", mod_id, code),
+            Err(e) => warn!("Core mod {} not loaded: {}", mod_id, e),
+        }
+    }
+
+    if let Some(mut mod_data) = world.get_resource_mut::<LoadedModData>() {
+        mod_data.packs.extend(packs);
+    }
+    // Modがなければ機械テーブルは同期しない
+    if !runtime.loaded_mods().is_empty() {
+        machines.mark_in_use();
+    }
+    world.insert_non_send_resource(CoreModRuntime(runtime));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
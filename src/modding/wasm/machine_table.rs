@@ -0,0 +1,232 @@
+//! Mod向け機械テーブル
+//!
+//! WASM実行中はBevy Worldに触れられないため、ホスト関数はこのテーブルを
+//! 経由して機械の状態を読み書きする。`sync_machine_table` が毎tick
+//! 保留中の変更をWorldへ反映し、機械が変化したときだけテーブルを作り直す。
+//! Modが1つも読み込まれていなければ何もしない。
+
+use crate::components::Machine;
+use crate::Conveyor;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 機械1台分の状態
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MachineEntry {
+    /// 稼働中か（falseなら停止中）
+    pub enabled: bool,
+    /// 処理中か
+    pub processing: bool,
+}
+
+impl MachineEntry {
+    /// host_get_machine_state の戻り値: 0=正常, 1=処理中, 2=待機中（停止中）
+    pub fn state_code(&self) -> i32 {
+        if !self.enabled {
+            2
+        } else if self.processing {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Default)]
+struct MachineTableInner {
+    /// entity_id (Entity::to_bits) -> 状態
+    entries: HashMap<u64, MachineEntry>,
+    /// Worldへ未反映の有効/無効変更
+    pending: Vec<(u64, bool)>,
+    /// 読み込まれたModがテーブルを使っているか
+    in_use: bool,
+}
+
+/// entity_id と機械の対応表（全Modインスタンスで共有）
+#[derive(Resource, Clone, Default)]
+pub struct MachineTable(Arc<Mutex<MachineTableInner>>);
+
+impl MachineTable {
+    /// 機械の状態を取得（未登録ならNone）
+    pub fn get(&self, entity_id: u64) -> Option<MachineEntry> {
+        self.0.lock().ok()?.entries.get(&entity_id).copied()
+    }
+
+    /// 有効/無効を設定（次の同期でWorldへ反映）
+    /// 未登録のentity_idならfalse
+    pub fn set_enabled(&self, entity_id: u64, enabled: bool) -> bool {
+        let Ok(mut inner) = self.0.lock() else {
+            return false;
+        };
+        let Some(entry) = inner.entries.get_mut(&entity_id) else {
+            return false;
+        };
+        entry.enabled = enabled;
+        inner.pending.push((entity_id, enabled));
+        true
+    }
+
+    /// Modが読み込まれたことを記録する（これ以降 `sync_machine_table` が同期する）
+    pub fn mark_in_use(&self) {
+        if let Ok(mut inner) = self.0.lock() {
+            inner.in_use = true;
+        }
+    }
+
+    /// 読み込まれたModがテーブルを使っているか
+    pub fn in_use(&self) -> bool {
+        self.0.lock().is_ok_and(|inner| inner.in_use)
+    }
+
+    /// 保留中の変更を取り出す
+    fn take_pending(&self) -> Vec<(u64, bool)> {
+        self.0
+            .lock()
+            .map(|mut inner| std::mem::take(&mut inner.pending))
+            .unwrap_or_default()
+    }
+
+    /// テーブルを作り直す
+    fn replace(&self, entries: HashMap<u64, MachineEntry>) {
+        if let Ok(mut inner) = self.0.lock() {
+            inner.entries = entries;
+        }
+    }
+}
+
+/// Modからの変更をWorldへ反映し、テーブルを最新の状態に更新
+///
+/// 機械・コンベアの追加/変更/削除も保留中の変更もないtickは作り直さない。
+pub fn sync_machine_table(
+    table: Res<MachineTable>,
+    mut machines: Query<(Entity, &mut Machine)>,
+    mut conveyors: Query<(Entity, &mut Conveyor), Without<Machine>>,
+    mut removed_machines: RemovedComponents<Machine>,
+    mut removed_conveyors: RemovedComponents<Conveyor>,
+    mut synced: Local<bool>,
+) {
+    // 削除イベントは読み捨てないと次のtickに残る
+    let removed = removed_machines.read().count() + removed_conveyors.read().count() > 0;
+    if !table.in_use() {
+        return;
+    }
+
+    let pending = table.take_pending();
+    for &(entity_id, enabled) in &pending {
+        let Some(entity) = Entity::try_from_bits(entity_id) else {
+            continue;
+        };
+        if let Ok((_, mut machine)) = machines.get_mut(entity) {
+            machine.enabled = enabled;
+        } else if let Ok((_, mut conveyor)) = conveyors.get_mut(entity) {
+            conveyor.enabled = enabled;
+        }
+    }
+
+    // iter_mut は変更tickを読むだけで、変更済みにはしない
+    let changed = machines.iter_mut().any(|(_, m)| m.is_changed())
+        || conveyors.iter_mut().any(|(_, c)| c.is_changed());
+    // 初回は変更の有無に関係なく作る（Mod読み込み前からある機械も載せる）
+    if *synced && pending.is_empty() && !changed && !removed {
+        return;
+    }
+    *synced = true;
+
+    let mut entries = HashMap::new();
+    for (entity, machine) in machines.iter() {
+        entries.insert(
+            entity.to_bits(),
+            MachineEntry {
+                enabled: machine.enabled,
+                processing: machine.is_processing(),
+            },
+        );
+    }
+    for (entity, conveyor) in conveyors.iter() {
+        entries.insert(
+            entity.to_bits(),
+            MachineEntry {
+                enabled: conveyor.enabled,
+                processing: !conveyor.items.is_empty(),
+            },
+        );
+    }
+    table.replace(entries);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_spec::FURNACE;
+    use crate::Direction;
+
+    #[test]
+    fn test_unknown_entity_is_rejected() {
+        let table = MachineTable::default();
+        assert_eq!(table.get(42), None);
+        assert!(!table.set_enabled(42, false));
+    }
+
+    fn table_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<MachineTable>()
+            .add_systems(Update, sync_machine_table);
+        app
+    }
+
+    #[test]
+    fn test_set_enabled_reaches_machine() {
+        let mut app = table_app();
+        app.world().resource::<MachineTable>().mark_in_use();
+        let entity = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::ZERO, Direction::North))
+            .id();
+
+        app.update();
+        let table = app.world().resource::<MachineTable>().clone();
+        let id = entity.to_bits();
+        assert_eq!(table.get(id).map(|e| e.state_code()), Some(0));
+
+        assert!(table.set_enabled(id, false));
+        app.update();
+        assert!(!app.world().get::<Machine>(entity).unwrap().enabled);
+        assert_eq!(table.get(id).map(|e| e.state_code()), Some(2));
+    }
+
+    #[test]
+    fn test_table_idle_without_mods() {
+        let mut app = table_app();
+        let entity = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::ZERO, Direction::North))
+            .id();
+        app.update();
+        let table = app.world().resource::<MachineTable>().clone();
+        assert_eq!(table.get(entity.to_bits()), None);
+
+        // Once a mod uses the table it picks up the existing machines
+        table.mark_in_use();
+        app.update();
+        assert!(table.get(entity.to_bits()).is_some());
+    }
+
+    #[test]
+    fn test_despawned_machine_leaves_table() {
+        let mut app = table_app();
+        let table = app.world().resource::<MachineTable>().clone();
+        table.mark_in_use();
+        let entity = app
+            .world_mut()
+            .spawn(Machine::new(&FURNACE, IVec3::ZERO, Direction::North))
+            .id();
+        app.update();
+        assert!(table.get(entity.to_bits()).is_some());
+
+        app.world_mut().despawn(entity);
+        app.update();
+        assert_eq!(table.get(entity.to_bits()), None);
+        assert!(!table.set_enabled(entity.to_bits(), false));
+    }
+}
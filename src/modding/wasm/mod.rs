@@ -5,11 +5,19 @@
 #[cfg(not(target_arch = "wasm32"))]
 pub mod api;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod inventory_table;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod loader;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod machine_table;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod runtime;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use loader::WasmModLoader;
+pub use inventory_table::{sync_inventory_table, InventoryTable, PLAYER_ENTITY_ID};
+#[cfg(not(target_arch = "wasm32"))]
+pub use loader::{load_core_mods, CoreModRuntime, WasmModLoader};
+#[cfg(not(target_arch = "wasm32"))]
+pub use machine_table::{sync_machine_table, MachineEntry, MachineTable};
 #[cfg(not(target_arch = "wasm32"))]
 pub use runtime::{ModState, WasmError, WasmRuntime};
@@ -1,6 +1,11 @@
 //! WASMランタイム実装
 
 use super::api;
+use super::api::version::{
+    check_compatibility, ApiCompatibility, HOST_API_VERSION, LEGACY_API_VERSION,
+};
+use super::{InventoryTable, MachineTable};
+use crate::modding::data::ModDataPack;
 use std::collections::HashMap;
 use wasmtime::*;
 
@@ -12,6 +17,11 @@ pub enum WasmError {
     LinkError(String),
     RuntimeError(String),
     ModNotFound(String),
+    /// Modがホストより新しいAPIバージョンを要求している
+    UnsupportedApiVersion {
+        mod_version: u32,
+        host_version: u32,
+    },
 }
 
 impl std::fmt::Display for WasmError {
@@ -22,6 +32,14 @@ impl std::fmt::Display for WasmError {
             WasmError::LinkError(e) => write!(f, "Link error: {}", e),
             WasmError::RuntimeError(e) => write!(f, "Runtime error: {}", e),
             WasmError::ModNotFound(id) => write!(f, "Mod not found: {}", id),
+            WasmError::UnsupportedApiVersion {
+                mod_version,
+                host_version,
+            } => write!(
+                f,
+                "Mod requires API version {} but the game provides {}",
+                mod_version, host_version
+            ),
         }
     }
 }
@@ -37,12 +55,22 @@ impl From<std::io::Error> for WasmError {
 /// Modの実行コンテキスト
 pub struct ModState {
     pub mod_id: String,
+    /// 機械の対応表（ホスト関数から参照）
+    pub machines: MachineTable,
+    /// インベントリの対応表（ホスト関数から読み書き）
+    pub inventories: InventoryTable,
+    /// mod_init 実行中だけ true（コンテンツ登録を受け付ける）
+    pub accepting_content: bool,
+    /// Modが登録したアイテム・レシピ
+    pub content: ModDataPack,
 }
 
 /// ロード済みModインスタンス
 struct LoadedMod {
     instance: Instance,
     store: Store<ModState>,
+    /// Modが宣言したAPIバージョン
+    api_version: u32,
 }
 
 /// WASMランタイム
@@ -50,6 +78,8 @@ pub struct WasmRuntime {
     engine: Engine,
     modules: HashMap<String, Module>,
     instances: HashMap<String, LoadedMod>,
+    machines: MachineTable,
+    inventories: InventoryTable,
 }
 
 impl WasmRuntime {
@@ -60,9 +90,23 @@ impl WasmRuntime {
             engine,
             modules: HashMap::new(),
             instances: HashMap::new(),
+            machines: MachineTable::default(),
+            inventories: InventoryTable::default(),
         })
     }
 
+    /// ゲーム側と共有する機械テーブルを設定（インスタンス化前に呼ぶ）
+    pub fn with_machine_table(mut self, machines: MachineTable) -> Self {
+        self.machines = machines;
+        self
+    }
+
+    /// ゲーム側と共有するインベントリテーブルを設定（インスタンス化前に呼ぶ）
+    pub fn with_inventory_table(mut self, inventories: InventoryTable) -> Self {
+        self.inventories = inventories;
+        self
+    }
+
     /// WASMモジュールをロード（コンパイルのみ）
     pub fn load_module(&mut self, mod_id: &str, wasm_bytes: &[u8]) -> Result<(), WasmError> {
         let module = Module::new(&self.engine, wasm_bytes)
@@ -72,6 +116,8 @@ impl WasmRuntime {
     }
 
     /// Modをインスタンス化（ホスト関数をリンク）
+    ///
+    /// `mod_api_version` がホストより新しいModは拒否し、古いModは警告を出して読み込む
     pub fn instantiate(&mut self, mod_id: &str) -> Result<(), WasmError> {
         let module = self
             .modules
@@ -82,6 +128,10 @@ impl WasmRuntime {
             &self.engine,
             ModState {
                 mod_id: mod_id.to_string(),
+                machines: self.machines.clone(),
+                inventories: self.inventories.clone(),
+                accepting_content: false,
+                content: ModDataPack::new(),
             },
         );
 
@@ -94,12 +144,56 @@ impl WasmRuntime {
             .instantiate(&mut store, module)
             .map_err(|e| WasmError::LinkError(e.to_string()))?;
 
-        self.instances
-            .insert(mod_id.to_string(), LoadedMod { instance, store });
+        let api_version = Self::declared_api_version(&instance, &mut store)?;
+        match check_compatibility(api_version, HOST_API_VERSION) {
+            ApiCompatibility::Compatible => {}
+            ApiCompatibility::Older => {
+                tracing::warn!(
+                    "[Mod:{}] built for mod API version {} (game provides {}), some features may be missing",
+                    mod_id,
+                    api_version,
+                    HOST_API_VERSION
+                );
+            }
+            ApiCompatibility::TooNew => {
+                return Err(WasmError::UnsupportedApiVersion {
+                    mod_version: api_version,
+                    host_version: HOST_API_VERSION,
+                });
+            }
+        }
+
+        self.instances.insert(
+            mod_id.to_string(),
+            LoadedMod {
+                instance,
+                store,
+                api_version,
+            },
+        );
         Ok(())
     }
 
-    /// mod_init() を呼び出す
+    /// Modがエクスポートする mod_api_version()（無ければ初期API）
+    fn declared_api_version(
+        instance: &Instance,
+        store: &mut Store<ModState>,
+    ) -> Result<u32, WasmError> {
+        let Ok(version_fn) = instance.get_typed_func::<(), u32>(&mut *store, "mod_api_version")
+        else {
+            return Ok(LEGACY_API_VERSION);
+        };
+        version_fn
+            .call(store, ())
+            .map_err(|e| WasmError::RuntimeError(e.to_string()))
+    }
+
+    /// Modが宣言したAPIバージョン
+    pub fn api_version(&self, mod_id: &str) -> Option<u32> {
+        self.instances.get(mod_id).map(|loaded| loaded.api_version)
+    }
+
+    /// mod_init() を呼び出す（この間だけアイテム・レシピを登録できる）
     pub fn call_init(&mut self, mod_id: &str) -> Result<i32, WasmError> {
         let loaded = self
             .instances
@@ -111,9 +205,19 @@ impl WasmRuntime {
             .get_typed_func::<(), i32>(&mut loaded.store, "mod_init")
             .map_err(|e| WasmError::LinkError(format!("mod_init not found: {}", e)))?;
 
-        init_fn
+        loaded.store.data_mut().accepting_content = true;
+        let result = init_fn
             .call(&mut loaded.store, ())
-            .map_err(|e| WasmError::RuntimeError(e.to_string()))
+            .map_err(|e| WasmError::RuntimeError(e.to_string()));
+        loaded.store.data_mut().accepting_content = false;
+        result
+    }
+
+    /// Modが mod_init で登録したアイテム・レシピ
+    pub fn registered_content(&self, mod_id: &str) -> Option<&ModDataPack> {
+        self.instances
+            .get(mod_id)
+            .map(|loaded| &loaded.store.data().content)
     }
 
     /// mod_tick() を呼び出す
@@ -166,6 +270,10 @@ mod tests {
             WasmError::LinkError("link failed".to_string()),
             WasmError::RuntimeError("runtime failed".to_string()),
             WasmError::ModNotFound("test_mod".to_string()),
+            WasmError::UnsupportedApiVersion {
+                mod_version: 3,
+                host_version: 2,
+            },
         ];
 
         for error in errors {
@@ -180,4 +288,153 @@ mod tests {
         let result = runtime.call_init("nonexistent_mod");
         assert!(matches!(result, Err(WasmError::ModNotFound(_))));
     }
+
+    /// mod_api_version が `version` を返すテスト用Mod（None なら宣言なし）
+    fn fixture_mod(version: Option<u32>) -> String {
+        let version_fn = version
+            .map(|v| format!(r#"(func (export "mod_api_version") (result i32) i32.const {v})"#))
+            .unwrap_or_default();
+        format!(
+            r#"(module
+                {version_fn}
+                (func (export "mod_init") (result i32) i32.const 0))"#
+        )
+    }
+
+    fn load_fixture(runtime: &mut WasmRuntime, mod_id: &str, version: Option<u32>) {
+        runtime
+            .load_module(mod_id, fixture_mod(version).as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_mod_requiring_newer_api_is_refused() {
+        let mut runtime = WasmRuntime::new().unwrap();
+        load_fixture(&mut runtime, "too_new", Some(HOST_API_VERSION + 1));
+
+        let result = runtime.instantiate("too_new");
+        assert!(matches!(
+            result,
+            Err(WasmError::UnsupportedApiVersion {
+                mod_version,
+                host_version: HOST_API_VERSION,
+            }) if mod_version == HOST_API_VERSION + 1
+        ));
+        assert!(runtime.loaded_mods().is_empty());
+        assert!(matches!(
+            runtime.call_init("too_new"),
+            Err(WasmError::ModNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_mod_with_same_api_loads() {
+        let mut runtime = WasmRuntime::new().unwrap();
+        load_fixture(&mut runtime, "current", Some(HOST_API_VERSION));
+
+        runtime.instantiate("current").unwrap();
+        assert_eq!(runtime.api_version("current"), Some(HOST_API_VERSION));
+        assert_eq!(runtime.call_init("current").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_older_mods_still_load() {
+        let mut runtime = WasmRuntime::new().unwrap();
+        load_fixture(&mut runtime, "older", Some(LEGACY_API_VERSION));
+        load_fixture(&mut runtime, "undeclared", None);
+
+        runtime.instantiate("older").unwrap();
+        runtime.instantiate("undeclared").unwrap();
+        assert_eq!(runtime.api_version("older"), Some(LEGACY_API_VERSION));
+        assert_eq!(runtime.api_version("undeclared"), Some(LEGACY_API_VERSION));
+        assert_eq!(runtime.call_init("undeclared").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_host_version_and_capabilities_visible_to_mod() {
+        use super::api::version::HOST_CAPABILITIES;
+
+        // バージョン × 1000 + 書き込んだバイト数、機能フラグはメモリ先頭に
+        let wat = r#"(module
+            (import "env" "host_get_api_version" (func $version (result i32)))
+            (import "env" "host_get_capabilities" (func $caps (param i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "mod_init") (result i32)
+                (i32.add
+                    (i32.mul (call $version) (i32.const 1000))
+                    (call $caps (i32.const 0) (i32.const 4))))
+            (func (export "caps_short_buffer") (result i32)
+                (call $caps (i32.const 0) (i32.const 2)))
+            (func (export "read_caps") (result i32)
+                (i32.load (i32.const 0))))"#;
+        let mut runtime = WasmRuntime::new().unwrap();
+        runtime.load_module("probe", wat.as_bytes()).unwrap();
+        runtime.instantiate("probe").unwrap();
+
+        let result = runtime.call_init("probe").unwrap();
+        assert_eq!(result, HOST_API_VERSION as i32 * 1000 + 4);
+
+        let loaded = runtime.instances.get_mut("probe").unwrap();
+        let read_caps = loaded
+            .instance
+            .get_typed_func::<(), u32>(&mut loaded.store, "read_caps")
+            .unwrap();
+        assert_eq!(
+            read_caps.call(&mut loaded.store, ()).unwrap(),
+            HOST_CAPABILITIES
+        );
+        let short = loaded
+            .instance
+            .get_typed_func::<(), u32>(&mut loaded.store, "caps_short_buffer")
+            .unwrap();
+        assert_eq!(short.call(&mut loaded.store, ()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fixture_mod_registers_item_and_recipe() {
+        use super::api::content::{CONTENT_ERR_NOT_IN_INIT, CONTENT_OK};
+
+        let item = r#"{"id": "gear", "name": "歯車"}"#;
+        let recipe = r#"{"id": "gear", "machine": "assembler", "inputs": {"iron_ingot": 2}, "outputs": {"gear": 1}}"#;
+        // 登録結果を 10 倍して足す（両方成功なら 0）
+        let wat = format!(
+            r#"(module
+                (import "env" "host_register_item" (func $item (param i32 i32) (result i32)))
+                (import "env" "host_register_recipe" (func $recipe (param i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{item}")
+                (data (i32.const 256) "{recipe}")
+                (func (export "mod_init") (result i32)
+                    (i32.add
+                        (i32.mul (call $item (i32.const 0) (i32.const {item_len})) (i32.const 10))
+                        (call $recipe (i32.const 256) (i32.const {recipe_len}))))
+                (func (export "late_register") (result i32)
+                    (call $item (i32.const 0) (i32.const {item_len}))))"#,
+            item = item.replace('"', "\\\""),
+            recipe = recipe.replace('"', "\\\""),
+            item_len = item.len(),
+            recipe_len = recipe.len(),
+        );
+        let mut runtime = WasmRuntime::new().unwrap();
+        runtime.load_module("gears", wat.as_bytes()).unwrap();
+        runtime.instantiate("gears").unwrap();
+        assert_eq!(runtime.call_init("gears").unwrap(), CONTENT_OK);
+
+        let content = runtime.registered_content("gears").unwrap();
+        assert_eq!(content.items[0].id, "gears:gear");
+        assert_eq!(content.recipes[0].id, "gears:gear");
+        assert_eq!(content.recipes[0].outputs.get("gears:gear"), Some(&1));
+
+        // mod_init の後は登録できない
+        let loaded = runtime.instances.get_mut("gears").unwrap();
+        let late = loaded
+            .instance
+            .get_typed_func::<(), i32>(&mut loaded.store, "late_register")
+            .unwrap();
+        assert_eq!(
+            late.call(&mut loaded.store, ()).unwrap(),
+            CONTENT_ERR_NOT_IN_INIT
+        );
+        assert_eq!(runtime.registered_content("gears").unwrap().item_count(), 1);
+    }
 }
@@ -0,0 +1,210 @@
+//! Client: forwards local edits to the server and applies replicated state
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::protocol::{ClientPacket, MachineState, ServerPacket};
+use super::transport::Connection;
+use super::{block_item, inventory_changes, item_name, stacks_to_wire, POSITION_INTERVAL_SECS};
+use crate::components::{CurrentQuest, Player};
+use crate::core::ItemId;
+use crate::events::game_events::{BlockBroken, BlockPlaced, EventSource};
+use crate::player::LocalPlatformInventory;
+use crate::world::{DirtyChunks, WorldData};
+
+/// How long to wait for the initial TCP connect
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connection to the server
+#[derive(Resource)]
+pub struct NetClient {
+    conn: Connection,
+    /// ID assigned by the server (None until Welcome arrives)
+    pub player_id: Option<u64>,
+    /// Whether the last delivery request was applied (None until one is answered)
+    pub last_delivery_accepted: Option<bool>,
+    /// Platform inventory as last confirmed by the server
+    confirmed_inventory: HashMap<ItemId, u32>,
+    position_timer: Timer,
+}
+
+impl NetClient {
+    pub fn connect(addr: SocketAddr, name: &str) -> std::io::Result<Self> {
+        let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        let mut conn = Connection::new(stream)?;
+        conn.send(&ClientPacket::Hello {
+            name: name.to_string(),
+        });
+        Ok(Self {
+            conn,
+            player_id: None,
+            last_delivery_accepted: None,
+            confirmed_inventory: HashMap::new(),
+            position_timer: Timer::from_seconds(POSITION_INTERVAL_SECS, TimerMode::Repeating),
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        !self.conn.is_closed()
+    }
+
+    /// Ask the server to deliver these items from the platform inventory
+    ///
+    /// The quest completes once the server answers with an accepted DeliveryResult.
+    pub fn request_delivery(&mut self, items: &[(ItemId, u32)]) {
+        self.conn.send(&ClientPacket::Deliver {
+            items: stacks_to_wire(items),
+        });
+    }
+}
+
+/// Other players' last known positions, keyed by player ID
+#[derive(Resource, Debug, Default)]
+pub struct RemotePlayers(pub HashMap<u64, Vec3>);
+
+/// Latest machine snapshot from the server
+#[derive(Resource, Debug, Default)]
+pub struct RemoteMachines(pub Vec<MachineState>);
+
+/// Forward blocks the local player placed or broke to the server
+pub fn client_send_block_changes(
+    mut client: ResMut<NetClient>,
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+) {
+    for event in placed.read() {
+        if !matches!(event.source, EventSource::Player(_)) {
+            continue;
+        }
+        let Some(item) = item_name(event.block) else {
+            continue;
+        };
+        client.conn.send(&ClientPacket::PlaceBlock {
+            pos: event.pos.to_array(),
+            item,
+        });
+    }
+    for event in broken.read() {
+        if matches!(event.source, EventSource::Player(_)) {
+            client.conn.send(&ClientPacket::BreakBlock {
+                pos: event.pos.to_array(),
+            });
+        }
+    }
+}
+
+/// Send the local player's position a few times per second
+pub fn client_send_position(
+    time: Res<Time>,
+    mut client: ResMut<NetClient>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if !client.position_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok(transform) = player.single() else {
+        return;
+    };
+    client.conn.send(&ClientPacket::PlayerPosition {
+        pos: transform.translation.to_array(),
+    });
+}
+
+/// Apply everything the server sent since last frame
+pub fn client_receive(
+    mut client: ResMut<NetClient>,
+    mut world_data: ResMut<WorldData>,
+    mut dirty_chunks: Option<ResMut<DirtyChunks>>,
+    mut players: ResMut<RemotePlayers>,
+    mut machines: ResMut<RemoteMachines>,
+    mut current_quest: Option<ResMut<CurrentQuest>>,
+) {
+    let was_connected = client.is_connected();
+    for packet in client.conn.receive::<ServerPacket>() {
+        match packet {
+            ServerPacket::Welcome { player_id } => {
+                info!("Joined server as player {}", player_id);
+                client.player_id = Some(player_id);
+            }
+            ServerPacket::BlockSet { pos, item } => {
+                let pos = IVec3::from_array(pos);
+                let item_id = item.as_deref().and_then(block_item);
+                if world_data.get_block(pos) == item_id {
+                    continue;
+                }
+                match item_id {
+                    Some(item_id) => world_data.set_block(pos, item_id),
+                    None => {
+                        world_data.remove_block(pos);
+                        world_data.modified_blocks.insert(pos, None);
+                    }
+                }
+                if let Some(dirty_chunks) = dirty_chunks.as_deref_mut() {
+                    dirty_chunks.mark_dirty(
+                        WorldData::world_to_chunk(pos),
+                        WorldData::world_to_local(pos),
+                    );
+                }
+            }
+            ServerPacket::MachineSnapshot { machines: snapshot } => {
+                machines.0 = snapshot;
+            }
+            ServerPacket::PlayerPosition { player_id, pos } => {
+                players.0.insert(player_id, Vec3::from_array(pos));
+            }
+            ServerPacket::PlayerLeft { player_id } => {
+                players.0.remove(&player_id);
+            }
+            ServerPacket::InventorySet { item, count } => {
+                let Some(item_id) = crate::utils::parse_item_name(&item) else {
+                    continue;
+                };
+                if count == 0 {
+                    client.confirmed_inventory.remove(&item_id);
+                } else {
+                    client.confirmed_inventory.insert(item_id, count);
+                }
+            }
+            ServerPacket::DeliveryResult { accepted } => {
+                client.last_delivery_accepted = Some(accepted);
+                if accepted {
+                    if let Some(quest) = current_quest.as_deref_mut() {
+                        quest.completed = true;
+                    }
+                }
+            }
+        }
+    }
+    if was_connected && !client.is_connected() {
+        warn!("Lost connection to server");
+    }
+}
+
+/// Keep the local platform inventory in step with the server
+///
+/// Items spent locally are requested from the server, then every local change
+/// is rolled back to the confirmed counts until the server's answer arrives.
+pub fn client_sync_inventory(mut client: ResMut<NetClient>, mut platform: LocalPlatformInventory) {
+    let Some(mut inventory) = platform.get_mut() else {
+        return;
+    };
+    let spent: Vec<(ItemId, u32)> = client
+        .confirmed_inventory
+        .iter()
+        .filter_map(|(&item_id, &confirmed)| {
+            let local = inventory.get_count_by_id(item_id);
+            (local < confirmed).then_some((item_id, confirmed - local))
+        })
+        .collect();
+    if !spent.is_empty() {
+        client.conn.send(&ClientPacket::TakeItems {
+            items: stacks_to_wire(&spent),
+        });
+    }
+    if !inventory_changes(&client.confirmed_inventory, inventory.items_by_id()).is_empty() {
+        inventory.set_items_by_id(client.confirmed_inventory.clone());
+    }
+}
@@ -0,0 +1,445 @@
+//! Co-op multiplayer groundwork
+//!
+//! The server owns the world. Clients connect over TCP and exchange
+//! length-prefixed JSON messages ([`protocol`]).
+//!
+//! Replicated so far:
+//! - block place/break (the server validates and broadcasts the result)
+//! - machine state snapshots at 2 Hz
+//! - player positions
+//! - the delivery platform inventory: clients ask to spend items or deliver a
+//!   quest, the server applies what it can and broadcasts the new counts, and
+//!   clients roll back any local change it has not confirmed
+//!
+//! Not yet covered: lag compensation and chunk streaming.
+//!
+//! `NetworkMode::Offline` (single-player) adds no systems at all.
+
+mod client;
+pub mod protocol;
+mod server;
+mod transport;
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+
+use bevy::prelude::*;
+
+use crate::components::DeliveryPlatform;
+use crate::core::ItemId;
+use crate::events::game_events::{BlockBroken, BlockPlaced};
+use crate::events::GameEventsPlugin;
+use crate::logistics::DeliveryFilter;
+use crate::player::{LocalPlatform, PlatformInventory};
+use crate::world::{BiomeMap, WorldData, WorldGenConfig};
+
+pub use client::{NetClient, RemoteMachines, RemotePlayers};
+pub use protocol::{ClientPacket, MachineState, ServerPacket};
+pub use server::{NetServer, RemoteClient};
+
+/// Default port for `--server` / `--connect` without an explicit port
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Machine snapshot interval (2 Hz)
+pub const SNAPSHOT_INTERVAL_SECS: f32 = 0.5;
+
+/// Player position update interval
+pub const POSITION_INTERVAL_SECS: f32 = 0.1;
+
+/// How this process takes part in a multiplayer session
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub enum NetworkMode {
+    /// Single-player, no networking
+    #[default]
+    Offline,
+    /// Authoritative server listening on `addr`
+    Server { addr: SocketAddr },
+    /// Client connected to the server at `addr`
+    Client { addr: SocketAddr, name: String },
+}
+
+impl NetworkMode {
+    /// Parse `--server [addr]`, `--connect <addr>` and `--name <name>`
+    ///
+    /// Addresses without a port use DEFAULT_PORT. Anything else is ignored.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let args: Vec<String> = args.into_iter().collect();
+        let value_after = |flag: &str| {
+            args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+                .filter(|v| !v.starts_with("--"))
+        };
+
+        if args.iter().any(|a| a == "--server") {
+            let addr = value_after("--server")
+                .and_then(|v| parse_addr(v))
+                .unwrap_or_else(|| SocketAddr::from((Ipv4Addr::UNSPECIFIED, DEFAULT_PORT)));
+            return Self::Server { addr };
+        }
+        if let Some(addr) = value_after("--connect").and_then(|v| parse_addr(v)) {
+            let name = value_after("--name")
+                .cloned()
+                .unwrap_or_else(|| "Player".to_string());
+            return Self::Client { addr, name };
+        }
+        Self::Offline
+    }
+
+    pub fn is_server(&self) -> bool {
+        matches!(self, Self::Server { .. })
+    }
+}
+
+fn parse_addr(value: &str) -> Option<SocketAddr> {
+    value
+        .parse()
+        .ok()
+        .or_else(|| format!("{}:{}", value, DEFAULT_PORT).parse().ok())
+}
+
+/// Full item ID sent over the wire (e.g. "base:stone")
+pub(crate) fn item_name(item_id: ItemId) -> Option<String> {
+    item_id.name().map(str::to_string)
+}
+
+/// Resolve a wire item ID, rejecting unknown or non-placeable items
+pub(crate) fn block_item(name: &str) -> Option<ItemId> {
+    crate::utils::parse_item_name(name).filter(|id| id.is_placeable())
+}
+
+/// Item stacks as sent over the wire
+pub(crate) fn stacks_to_wire(stacks: &[(ItemId, u32)]) -> Vec<(String, u32)> {
+    stacks
+        .iter()
+        .filter_map(|&(item_id, count)| Some((item_name(item_id)?, count)))
+        .collect()
+}
+
+/// Resolve wire item stacks (None if any item is unknown)
+pub(crate) fn stacks_from_wire(stacks: &[(String, u32)]) -> Option<Vec<(ItemId, u32)>> {
+    stacks
+        .iter()
+        .map(|(name, count)| Some((crate::utils::parse_item_name(name)?, *count)))
+        .collect()
+}
+
+/// Items whose count differs between two inventories, with the new count (0 = gone)
+pub(crate) fn inventory_changes(
+    old: &HashMap<ItemId, u32>,
+    new: &HashMap<ItemId, u32>,
+) -> Vec<(ItemId, u32)> {
+    let count = |items: &HashMap<ItemId, u32>, id: &ItemId| items.get(id).copied().unwrap_or(0);
+    let mut changes: Vec<(ItemId, u32)> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|id| count(old, id) != count(new, id))
+        .map(|id| (*id, count(new, id)))
+        .collect();
+    changes.sort_by_key(|(id, _)| id.raw());
+    changes.dedup();
+    changes
+}
+
+/// Network plugin (no-op when offline)
+#[derive(Default)]
+pub struct NetworkPlugin {
+    pub mode: NetworkMode,
+}
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.mode.clone());
+        match &self.mode {
+            NetworkMode::Offline => {}
+            NetworkMode::Server { addr } => match NetServer::bind(*addr) {
+                Ok(server) => {
+                    info!("Server listening on {:?}", server.local_addr());
+                    app.insert_resource(server).add_systems(
+                        Update,
+                        (
+                            server::server_accept,
+                            server::server_receive,
+                            server::server_broadcast_inventory,
+                            server::server_broadcast_machines,
+                        )
+                            .chain(),
+                    );
+                }
+                Err(e) => error!("Failed to start server on {}: {}", addr, e),
+            },
+            NetworkMode::Client { addr, name } => match NetClient::connect(*addr, name) {
+                Ok(client) => {
+                    info!("Connected to {}", addr);
+                    app.insert_resource(client)
+                        .init_resource::<RemotePlayers>()
+                        .init_resource::<RemoteMachines>()
+                        .add_message::<BlockPlaced>()
+                        .add_message::<BlockBroken>()
+                        .add_systems(
+                            Update,
+                            (
+                                client::client_send_block_changes,
+                                client::client_send_position,
+                                client::client_receive,
+                                client::client_sync_inventory,
+                            )
+                                .chain(),
+                        );
+                }
+                Err(e) => error!("Failed to connect to {}: {}", addr, e),
+            },
+        }
+    }
+}
+
+/// World simulation without rendering, for `--server`
+///
+/// Use with MinimalPlugins and a NetworkPlugin in server mode.
+pub struct HeadlessServerPlugin;
+
+impl Plugin for HeadlessServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(GameEventsPlugin)
+            .insert_resource(WorldData::with_gen_config(WorldGenConfig::load()))
+            .insert_resource(BiomeMap::new(12345)) // Same fixed seed as GamePlugin
+            .init_resource::<crate::components::CurrentQuest>()
+            .add_systems(Startup, spawn_headless_platform)
+            .add_systems(
+                FixedUpdate,
+                (
+                    crate::machines::generic_machine_tick,
+                    crate::machines::hopper_tick,
+                    crate::systems::conveyor_transfer,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Delivery platform without meshes; the server owns its inventory
+fn spawn_headless_platform(mut commands: Commands, world_data: Res<WorldData>) {
+    let platform = commands
+        .spawn((
+            DeliveryPlatform::new(world_data.gen_config.platform.origin()),
+            DeliveryFilter::default(),
+            PlatformInventory::with_items_by_id(&crate::game_spec::initial_equipment()),
+        ))
+        .id();
+    commands.insert_resource(LocalPlatform(platform));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+    use crate::events::game_events::EventSource;
+    use std::time::Duration;
+
+    fn localhost() -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, 0))
+    }
+
+    #[test]
+    fn test_mode_from_args() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        assert_eq!(NetworkMode::from_args(args("")), NetworkMode::Offline);
+        assert_eq!(
+            NetworkMode::from_args(args("--server")),
+            NetworkMode::Server {
+                addr: SocketAddr::from((Ipv4Addr::UNSPECIFIED, DEFAULT_PORT))
+            }
+        );
+        assert_eq!(
+            NetworkMode::from_args(args("--server 127.0.0.1:9000")),
+            NetworkMode::Server {
+                addr: "127.0.0.1:9000".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            NetworkMode::from_args(args("--connect 127.0.0.1 --name alice")),
+            NetworkMode::Client {
+                addr: SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_PORT)),
+                name: "alice".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_offline_adds_no_network_resources() {
+        let mut app = App::new();
+        app.add_plugins(NetworkPlugin::default());
+        assert!(!app.world().contains_resource::<NetServer>());
+        assert!(!app.world().contains_resource::<NetClient>());
+    }
+
+    #[test]
+    fn test_client_block_place_reaches_server() {
+        let mut server = App::new();
+        server
+            .add_plugins(MinimalPlugins)
+            .init_resource::<WorldData>()
+            .add_plugins(NetworkPlugin {
+                mode: NetworkMode::Server { addr: localhost() },
+            });
+        let addr = server.world().resource::<NetServer>().local_addr().unwrap();
+
+        let mut client = App::new();
+        client
+            .add_plugins(MinimalPlugins)
+            .init_resource::<WorldData>()
+            .add_plugins(NetworkPlugin {
+                mode: NetworkMode::Client {
+                    addr,
+                    name: "tester".to_string(),
+                },
+            });
+
+        let pos = IVec3::new(3, 30, 4);
+        client
+            .world_mut()
+            .resource_mut::<WorldData>()
+            .ensure_chunk_loaded(WorldData::world_to_chunk(pos));
+        let player = client.world_mut().spawn_empty().id();
+        client.world_mut().write_message(BlockPlaced {
+            pos,
+            block: items::stone(),
+            source: EventSource::Player(player),
+        });
+
+        let mut placed = false;
+        for _ in 0..200 {
+            client.update();
+            server.update();
+            if server.world().resource::<WorldData>().get_block(pos) == Some(items::stone()) {
+                placed = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(placed, "server never applied the client's block");
+
+        // The server's broadcast also reaches the client
+        for _ in 0..200 {
+            client.update();
+            server.update();
+            if client.world().resource::<NetClient>().player_id.is_some()
+                && client.world().resource::<WorldData>().get_block(pos) == Some(items::stone())
+            {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("client never received the server's block state");
+    }
+
+    /// Update both apps until `done` holds, giving up after a second or so
+    fn pump(server: &mut App, client: &mut App, done: impl Fn(&App, &App) -> bool) -> bool {
+        for _ in 0..200 {
+            client.update();
+            server.update();
+            if done(server, client) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    fn insert_platform(app: &mut App, items: &[(ItemId, u32)]) {
+        let platform = app
+            .world_mut()
+            .spawn(PlatformInventory::with_items_by_id(items))
+            .id();
+        app.insert_resource(LocalPlatform(platform));
+    }
+
+    fn platform_mut(app: &mut App) -> Mut<'_, PlatformInventory> {
+        let platform = app.world().resource::<LocalPlatform>().0;
+        app.world_mut()
+            .get_mut::<PlatformInventory>(platform)
+            .unwrap()
+    }
+
+    fn platform_count(app: &App, item_id: ItemId) -> u32 {
+        let platform = app.world().resource::<LocalPlatform>().0;
+        app.world()
+            .get::<PlatformInventory>(platform)
+            .unwrap()
+            .get_count_by_id(item_id)
+    }
+
+    #[test]
+    fn test_server_owns_platform_inventory() {
+        let mut server = App::new();
+        server
+            .add_plugins(MinimalPlugins)
+            .init_resource::<WorldData>();
+        insert_platform(&mut server, &[(items::iron_ore(), 5)]);
+        server.add_plugins(NetworkPlugin {
+            mode: NetworkMode::Server { addr: localhost() },
+        });
+        let addr = server.world().resource::<NetServer>().local_addr().unwrap();
+
+        let mut client = App::new();
+        client
+            .add_plugins(MinimalPlugins)
+            .init_resource::<WorldData>()
+            .init_resource::<crate::components::CurrentQuest>();
+        insert_platform(&mut client, &[]);
+        client.add_plugins(NetworkPlugin {
+            mode: NetworkMode::Client {
+                addr,
+                name: "tester".to_string(),
+            },
+        });
+
+        // The client starts from the server's counts
+        assert!(pump(&mut server, &mut client, |_, c| {
+            platform_count(c, items::iron_ore()) == 5
+        }));
+
+        // Items the server never confirmed are rolled back
+        platform_mut(&mut client).add_item_by_id(items::stone(), 100);
+        assert!(pump(&mut server, &mut client, |_, c| {
+            platform_count(c, items::stone()) == 0
+        }));
+        assert_eq!(platform_count(&server, items::stone()), 0);
+
+        // Spending locally is applied by the server
+        platform_mut(&mut client).remove_item_by_id(items::iron_ore(), 1);
+        assert!(pump(&mut server, &mut client, |s, c| {
+            platform_count(s, items::iron_ore()) == 4 && platform_count(c, items::iron_ore()) == 4
+        }));
+
+        // A delivery the platform can't cover is rejected
+        client
+            .world_mut()
+            .resource_mut::<NetClient>()
+            .request_delivery(&[(items::iron_ore(), 10)]);
+        assert!(pump(&mut server, &mut client, |_, c| {
+            c.world().resource::<NetClient>().last_delivery_accepted == Some(false)
+        }));
+        assert!(
+            !client
+                .world()
+                .resource::<crate::components::CurrentQuest>()
+                .completed
+        );
+        assert_eq!(platform_count(&server, items::iron_ore()), 4);
+
+        // One it can cover completes the quest and reaches the client's counts
+        client
+            .world_mut()
+            .resource_mut::<NetClient>()
+            .request_delivery(&[(items::iron_ore(), 3)]);
+        assert!(pump(&mut server, &mut client, |_, c| {
+            c.world()
+                .resource::<crate::components::CurrentQuest>()
+                .completed
+                && platform_count(c, items::iron_ore()) == 1
+        }));
+        assert_eq!(platform_count(&server, items::iron_ore()), 1);
+    }
+}
@@ -0,0 +1,140 @@
+//! Wire protocol: length-prefixed JSON frames
+//!
+//! Each frame is a big-endian `u32` byte length followed by one JSON message.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::components::Direction;
+
+/// Largest frame accepted from a peer (guards against garbage lengths)
+pub const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// Messages sent from a client to the server
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientPacket {
+    /// First message after connecting
+    Hello { name: String },
+    /// Request to place a block (item is a full ID like "base:stone")
+    PlaceBlock { pos: [i32; 3], item: String },
+    /// Request to break a block
+    BreakBlock { pos: [i32; 3] },
+    /// Local player position
+    PlayerPosition { pos: [f32; 3] },
+    /// Request to spend items from the shared platform inventory (all or nothing)
+    TakeItems { items: Vec<(String, u32)> },
+    /// Request to deliver the current quest's items from the platform inventory
+    Deliver { items: Vec<(String, u32)> },
+}
+
+/// Messages sent from the server to clients
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerPacket {
+    /// Reply to Hello with the ID assigned to this client
+    Welcome { player_id: u64 },
+    /// Authoritative block state at a position (None = air)
+    BlockSet { pos: [i32; 3], item: Option<String> },
+    /// Periodic machine state snapshot
+    MachineSnapshot { machines: Vec<MachineState> },
+    /// Another player's position
+    PlayerPosition { player_id: u64, pos: [f32; 3] },
+    /// Another player disconnected
+    PlayerLeft { player_id: u64 },
+    /// Authoritative platform inventory count for an item (0 = none left)
+    InventorySet { item: String, count: u32 },
+    /// Whether the sender's last Deliver was applied
+    DeliveryResult { accepted: bool },
+}
+
+/// Replicated machine state
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MachineState {
+    pub pos: [i32; 3],
+    /// MachineSpec ID (e.g. "furnace")
+    pub machine: String,
+    pub facing: Direction,
+    pub progress: f32,
+    pub enabled: bool,
+}
+
+/// Frame decoding error
+#[derive(Debug)]
+pub enum FrameError {
+    /// Declared length exceeds MAX_FRAME_BYTES
+    TooLarge(usize),
+    /// Payload was not a valid message
+    Json(serde_json::Error),
+}
+
+/// Encode a message as one frame
+pub fn encode_frame<T: Serialize>(msg: &T) -> Vec<u8> {
+    let json = serde_json::to_vec(msg).expect("protocol messages always serialize");
+    let mut frame = Vec::with_capacity(4 + json.len());
+    frame.extend_from_slice(&(json.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&json);
+    frame
+}
+
+/// Take the next complete frame off the front of `buf`
+///
+/// Returns `Ok(None)` while the frame is still incomplete.
+pub fn decode_frame<T: DeserializeOwned>(buf: &mut Vec<u8>) -> Result<Option<T>, FrameError> {
+    if buf.len() < 4 {
+        return Ok(None);
+    }
+    let len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    if len > MAX_FRAME_BYTES {
+        return Err(FrameError::TooLarge(len));
+    }
+    if buf.len() < 4 + len {
+        return Ok(None);
+    }
+    let result = serde_json::from_slice(&buf[4..4 + len]);
+    buf.drain(..4 + len);
+    result.map(Some).map_err(FrameError::Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let msg = ClientPacket::PlaceBlock {
+            pos: [1, 2, 3],
+            item: "base:stone".to_string(),
+        };
+        let mut buf = encode_frame(&msg);
+        buf.extend(encode_frame(&ClientPacket::BreakBlock { pos: [4, 5, 6] }));
+
+        let first: Option<ClientPacket> = decode_frame(&mut buf).unwrap();
+        assert_eq!(first, Some(msg));
+        let second: Option<ClientPacket> = decode_frame(&mut buf).unwrap();
+        assert_eq!(second, Some(ClientPacket::BreakBlock { pos: [4, 5, 6] }));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_partial_frame_waits() {
+        let frame = encode_frame(&ServerPacket::Welcome { player_id: 7 });
+        let mut buf = frame[..frame.len() - 1].to_vec();
+        assert!(decode_frame::<ServerPacket>(&mut buf).unwrap().is_none());
+
+        buf.push(*frame.last().unwrap());
+        assert_eq!(
+            decode_frame::<ServerPacket>(&mut buf).unwrap(),
+            Some(ServerPacket::Welcome { player_id: 7 })
+        );
+    }
+
+    #[test]
+    fn test_oversized_frame_rejected() {
+        let mut buf = ((MAX_FRAME_BYTES + 1) as u32).to_be_bytes().to_vec();
+        assert!(matches!(
+            decode_frame::<ServerPacket>(&mut buf),
+            Err(FrameError::TooLarge(_))
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+//! Authoritative server: accepts clients, validates block edits and inventory
+//! spending, broadcasts state
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener};
+
+use bevy::prelude::*;
+
+use super::protocol::{ClientPacket, MachineState, ServerPacket};
+use super::transport::Connection;
+use super::{block_item, inventory_changes, item_name, stacks_from_wire, SNAPSHOT_INTERVAL_SECS};
+use crate::components::Machine;
+use crate::core::ItemId;
+use crate::player::{LocalPlatformInventory, PlatformInventory};
+use crate::world::{DirtyChunks, WorldData};
+
+/// A connected client
+pub struct RemoteClient {
+    pub player_id: u64,
+    pub name: String,
+    pub position: Option<Vec3>,
+    conn: Connection,
+}
+
+/// Listening socket and connected clients
+#[derive(Resource)]
+pub struct NetServer {
+    listener: TcpListener,
+    clients: Vec<RemoteClient>,
+    next_player_id: u64,
+    snapshot_timer: Timer,
+}
+
+impl NetServer {
+    pub fn bind(addr: SocketAddr) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            clients: Vec::new(),
+            next_player_id: 1,
+            snapshot_timer: Timer::from_seconds(SNAPSHOT_INTERVAL_SECS, TimerMode::Repeating),
+        })
+    }
+
+    /// Actual bound address (useful when binding port 0)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+
+    pub fn clients(&self) -> &[RemoteClient] {
+        &self.clients
+    }
+
+    fn broadcast(&mut self, msg: &ServerPacket) {
+        for client in &mut self.clients {
+            client.conn.send(msg);
+        }
+    }
+
+    fn broadcast_except(&mut self, player_id: u64, msg: &ServerPacket) {
+        for client in self.clients.iter_mut().filter(|c| c.player_id != player_id) {
+            client.conn.send(msg);
+        }
+    }
+}
+
+/// Accept pending connections
+pub fn server_accept(mut server: ResMut<NetServer>) {
+    loop {
+        match server.listener.accept() {
+            Ok((stream, addr)) => {
+                let conn = match Connection::new(stream) {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        warn!("Failed to set up connection from {}: {}", addr, e);
+                        continue;
+                    }
+                };
+                let player_id = server.next_player_id;
+                server.next_player_id += 1;
+                info!("Client {} connected from {}", player_id, addr);
+                server.clients.push(RemoteClient {
+                    player_id,
+                    name: String::new(),
+                    position: None,
+                    conn,
+                });
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                warn!("Accept failed: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Apply client requests to the world and relay the results
+pub fn server_receive(
+    mut server: ResMut<NetServer>,
+    mut world_data: ResMut<WorldData>,
+    mut dirty_chunks: Option<ResMut<DirtyChunks>>,
+    mut platform: LocalPlatformInventory,
+) {
+    let mut outgoing: Vec<(Option<u64>, ServerPacket)> = Vec::new();
+
+    for client in &mut server.clients {
+        for packet in client.conn.receive::<ClientPacket>() {
+            match packet {
+                ClientPacket::Hello { name } => {
+                    info!("Client {} is '{}'", client.player_id, name);
+                    client.name = name;
+                    client.conn.send(&ServerPacket::Welcome {
+                        player_id: client.player_id,
+                    });
+                    // Start from the server's inventory; the broadcast only sends changes
+                    if let Some(inventory) = platform.get() {
+                        let empty = HashMap::new();
+                        for (item_id, count) in inventory_changes(&empty, inventory.items_by_id()) {
+                            send_inventory_count(client, item_id, count);
+                        }
+                    }
+                }
+                ClientPacket::PlaceBlock { pos, item } => {
+                    let pos = IVec3::from_array(pos);
+                    world_data.ensure_chunk_loaded(WorldData::world_to_chunk(pos));
+                    match block_item(&item) {
+                        Some(item_id) if !world_data.has_block(pos) => {
+                            world_data.set_block(pos, item_id);
+                            mark_dirty(dirty_chunks.as_deref_mut(), pos);
+                            outgoing.push((None, block_set(&world_data, pos)));
+                        }
+                        // Rejected: tell the sender what is really there
+                        _ => client.conn.send(&block_set(&world_data, pos)),
+                    }
+                }
+                ClientPacket::BreakBlock { pos } => {
+                    let pos = IVec3::from_array(pos);
+                    world_data.ensure_chunk_loaded(WorldData::world_to_chunk(pos));
+                    if world_data.remove_block(pos).is_some() {
+                        mark_dirty(dirty_chunks.as_deref_mut(), pos);
+                        outgoing.push((None, block_set(&world_data, pos)));
+                    } else {
+                        client.conn.send(&block_set(&world_data, pos));
+                    }
+                }
+                ClientPacket::PlayerPosition { pos } => {
+                    client.position = Some(Vec3::from_array(pos));
+                    outgoing.push((
+                        Some(client.player_id),
+                        ServerPacket::PlayerPosition {
+                            player_id: client.player_id,
+                            pos,
+                        },
+                    ));
+                }
+                ClientPacket::TakeItems { items } => {
+                    spend_items(client, platform.get_mut().as_deref_mut(), &items);
+                }
+                ClientPacket::Deliver { items } => {
+                    let accepted = spend_items(client, platform.get_mut().as_deref_mut(), &items);
+                    client.conn.send(&ServerPacket::DeliveryResult { accepted });
+                }
+            }
+        }
+    }
+
+    // Drop disconnected clients
+    let mut left = Vec::new();
+    server.clients.retain(|client| {
+        if client.conn.is_closed() {
+            info!("Client {} disconnected", client.player_id);
+            left.push(client.player_id);
+            false
+        } else {
+            true
+        }
+    });
+    for player_id in left {
+        outgoing.push((None, ServerPacket::PlayerLeft { player_id }));
+    }
+
+    for (skip, msg) in outgoing {
+        match skip {
+            Some(player_id) => server.broadcast_except(player_id, &msg),
+            None => server.broadcast(&msg),
+        }
+    }
+}
+
+/// Send machine state to all clients at a fixed rate
+pub fn server_broadcast_machines(
+    time: Res<Time>,
+    mut server: ResMut<NetServer>,
+    machines: Query<&Machine>,
+) {
+    if !server.snapshot_timer.tick(time.delta()).just_finished() || server.clients.is_empty() {
+        return;
+    }
+    let machines = machines
+        .iter()
+        .map(|machine| MachineState {
+            pos: machine.position.to_array(),
+            machine: machine.spec.id.to_string(),
+            facing: machine.facing,
+            progress: machine.progress,
+            enabled: machine.enabled,
+        })
+        .collect();
+    server.broadcast(&ServerPacket::MachineSnapshot { machines });
+}
+
+/// Send platform inventory counts that changed since the last broadcast
+pub fn server_broadcast_inventory(
+    mut server: ResMut<NetServer>,
+    platform: LocalPlatformInventory,
+    mut sent: Local<HashMap<ItemId, u32>>,
+) {
+    let Some(inventory) = platform.get() else {
+        return;
+    };
+    for (item_id, count) in inventory_changes(&sent, inventory.items_by_id()) {
+        if count == 0 {
+            sent.remove(&item_id);
+        } else {
+            sent.insert(item_id, count);
+        }
+        if let Some(item) = item_name(item_id) {
+            server.broadcast(&ServerPacket::InventorySet { item, count });
+        }
+    }
+}
+
+/// Spend items for a client, all or nothing
+///
+/// Successful spending reaches every client through the inventory broadcast.
+/// On rejection the sender is told the real counts so it can roll back.
+fn spend_items(
+    client: &mut RemoteClient,
+    inventory: Option<&mut PlatformInventory>,
+    items: &[(String, u32)],
+) -> bool {
+    let (Some(stacks), Some(inventory)) = (stacks_from_wire(items), inventory) else {
+        return false;
+    };
+    if inventory.try_consume_by_id(&stacks) {
+        return true;
+    }
+    for (item_id, _) in stacks {
+        send_inventory_count(client, item_id, inventory.get_count_by_id(item_id));
+    }
+    false
+}
+
+fn send_inventory_count(client: &mut RemoteClient, item_id: ItemId, count: u32) {
+    if let Some(item) = item_name(item_id) {
+        client
+            .conn
+            .send(&ServerPacket::InventorySet { item, count });
+    }
+}
+
+fn block_set(world_data: &WorldData, pos: IVec3) -> ServerPacket {
+    ServerPacket::BlockSet {
+        pos: pos.to_array(),
+        item: world_data.get_block(pos).and_then(item_name),
+    }
+}
+
+fn mark_dirty(dirty_chunks: Option<&mut DirtyChunks>, pos: IVec3) {
+    if let Some(dirty_chunks) = dirty_chunks {
+        dirty_chunks.mark_dirty(
+            WorldData::world_to_chunk(pos),
+            WorldData::world_to_local(pos),
+        );
+    }
+}
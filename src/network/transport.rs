@@ -0,0 +1,96 @@
+//! Non-blocking TCP connection polled from Bevy systems
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::protocol::{decode_frame, encode_frame};
+
+/// One TCP peer with buffered reads and writes
+///
+/// Never blocks: unsent bytes stay queued until the socket accepts them.
+pub struct Connection {
+    stream: TcpStream,
+    read_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+    closed: bool,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> std::io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            read_buf: Vec::new(),
+            write_buf: Vec::new(),
+            closed: false,
+        })
+    }
+
+    /// Whether the peer disconnected or sent garbage
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Queue a message and try to flush
+    pub fn send<T: Serialize>(&mut self, msg: &T) {
+        if self.closed {
+            return;
+        }
+        self.write_buf.extend(encode_frame(msg));
+        self.flush();
+    }
+
+    /// Write as much of the queued data as the socket accepts
+    pub fn flush(&mut self) {
+        while !self.write_buf.is_empty() {
+            match self.stream.write(&self.write_buf) {
+                Ok(0) => {
+                    self.closed = true;
+                    return;
+                }
+                Ok(n) => {
+                    self.write_buf.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.closed = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Read everything available and decode complete messages
+    pub fn receive<T: DeserializeOwned>(&mut self) -> Vec<T> {
+        let mut chunk = [0u8; 4096];
+        while !self.closed {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => self.closed = true,
+                Ok(n) => self.read_buf.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.closed = true,
+            }
+        }
+
+        let mut messages = Vec::new();
+        loop {
+            match decode_frame(&mut self.read_buf) {
+                Ok(Some(msg)) => messages.push(msg),
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Dropping connection after bad frame: {:?}", e);
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+        self.flush();
+        messages
+    }
+}
@@ -3,7 +3,7 @@
 //! Uses ItemId for all item storage, supporting both base game and mod items.
 
 use crate::constants::{HOTBAR_SLOTS, MAX_STACK_SIZE, NUM_SLOTS};
-use crate::core::ItemId;
+use crate::core::{BlockCategory, ItemId};
 use bevy::prelude::*;
 
 // =============================================================================
@@ -169,6 +169,98 @@ impl PlayerInventory {
             .map(|(id, count)| (*id, *count))
             .collect()
     }
+
+    /// Sort the main inventory (the hotbar stays as it is)
+    pub fn sort_main(&mut self) {
+        sort_stacks(&mut self.slots[HOTBAR_SLOTS..]);
+    }
+
+    /// Move main inventory items into matching `targets` (the hotbar is kept)
+    ///
+    /// Returns how many items moved.
+    pub fn quick_stack_main(&mut self, targets: &mut [StackTarget]) -> u32 {
+        quick_stack(&mut self.slots[HOTBAR_SLOTS..], targets)
+    }
+}
+
+// =============================================================================
+// Sort and quick stack
+// =============================================================================
+
+/// Sort group: machines, ores, ingots and other processed items, terrain, tools,
+/// then unknown items
+fn sort_group(item_id: ItemId) -> u8 {
+    match item_id.category() {
+        Some(BlockCategory::Machine) => 0,
+        Some(BlockCategory::Ore) => 1,
+        Some(BlockCategory::Processed) => 2,
+        Some(BlockCategory::Terrain) => 3,
+        Some(BlockCategory::Tool) => 4,
+        None => 5,
+    }
+}
+
+/// Merge identical items into as few stacks as possible and order them by
+/// group, then name
+///
+/// Empty slots end up at the back. The order is stable: the same contents
+/// always sort the same way, whatever slots they started in.
+pub fn sort_stacks(slots: &mut [Option<(ItemId, u32)>]) {
+    let mut totals: Vec<(ItemId, u64)> = Vec::new();
+    for (item_id, count) in slots.iter().flatten() {
+        match totals.iter_mut().find(|(id, _)| id == item_id) {
+            Some((_, total)) => *total += *count as u64,
+            None => totals.push((*item_id, *count as u64)),
+        }
+    }
+    totals.sort_by_key(|(id, _)| (sort_group(*id), id.name(), id.raw()));
+
+    let mut sorted = totals.into_iter().flat_map(|(item_id, total)| {
+        let full = total / MAX_STACK_SIZE as u64;
+        let rest = (total % MAX_STACK_SIZE as u64) as u32;
+        std::iter::repeat_n((item_id, MAX_STACK_SIZE), full as usize)
+            .chain((rest > 0).then_some((item_id, rest)))
+    });
+    for slot in slots.iter_mut() {
+        *slot = sorted.next();
+    }
+}
+
+/// Room in a container slot for quick stack
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StackTarget {
+    /// Item the slot already holds
+    pub item_id: ItemId,
+    /// Current count
+    pub count: u32,
+    /// Most the slot can hold
+    pub capacity: u32,
+}
+
+/// Move items from `slots` into targets that already hold the same item
+///
+/// Targets are filled in order, so pass the nearest first. Returns how many
+/// items moved.
+pub fn quick_stack(slots: &mut [Option<(ItemId, u32)>], targets: &mut [StackTarget]) -> u32 {
+    let mut moved = 0;
+    for slot in slots.iter_mut() {
+        let Some((item_id, count)) = slot else {
+            continue;
+        };
+        for target in targets.iter_mut().filter(|t| t.item_id == *item_id) {
+            let amount = (*count).min(target.capacity.saturating_sub(target.count));
+            target.count += amount;
+            *count -= amount;
+            moved += amount;
+            if *count == 0 {
+                break;
+            }
+        }
+        if *count == 0 {
+            *slot = None;
+        }
+    }
+    moved
 }
 
 // =============================================================================
@@ -293,4 +385,103 @@ mod tests {
         assert_eq!(inv.get_total_count_by_id(items::stone()), 10);
         assert_eq!(inv.get_total_count_by_id(items::iron_ore()), 20);
     }
+
+    #[test]
+    fn test_sort_stacks_merges_and_orders() {
+        let mut slots = [
+            Some((items::iron_ingot(), 5)),
+            None,
+            Some((items::stone(), 3)),
+            Some((items::miner_block(), 1)),
+            Some((items::iron_ingot(), 998)),
+            Some((items::iron_ore(), 2)),
+            None,
+        ];
+        sort_stacks(&mut slots);
+        assert_eq!(
+            slots,
+            [
+                Some((items::miner_block(), 1)),
+                Some((items::iron_ore(), 2)),
+                Some((items::iron_ingot(), MAX_STACK_SIZE)),
+                Some((items::iron_ingot(), 4)),
+                Some((items::stone(), 3)),
+                None,
+                None,
+            ]
+        );
+
+        // Same contents in another order sort the same way
+        let mut shuffled = [
+            None,
+            Some((items::stone(), 3)),
+            Some((items::iron_ingot(), 1000)),
+            None,
+            Some((items::iron_ore(), 2)),
+            Some((items::iron_ingot(), 3)),
+            Some((items::miner_block(), 1)),
+        ];
+        sort_stacks(&mut shuffled);
+        assert_eq!(shuffled, slots);
+    }
+
+    #[test]
+    fn test_sort_main_keeps_hotbar() {
+        let mut inv = PlayerInventory::default();
+        inv.slots[0] = Some((items::stone(), 7));
+        inv.slots[4] = Some((items::iron_ore(), 1));
+        inv.slots[20] = Some((items::stone(), 5));
+        inv.slots[HOTBAR_SLOTS + 3] = Some((items::stone(), 2));
+
+        inv.sort_main();
+        assert_eq!(inv.slots[0], Some((items::stone(), 7)));
+        assert_eq!(inv.slots[4], Some((items::iron_ore(), 1)));
+        assert_eq!(inv.slots[HOTBAR_SLOTS], Some((items::stone(), 7)));
+        assert!(inv.slots[HOTBAR_SLOTS + 1..].iter().all(|s| s.is_none()));
+    }
+
+    #[test]
+    fn test_quick_stack_fills_two_partial_chests() {
+        let mut inv = PlayerInventory::default();
+        inv.slots[0] = Some((items::iron_ore(), 500)); // Hotbar is never stacked away
+        inv.slots[HOTBAR_SLOTS] = Some((items::iron_ore(), 50));
+        inv.slots[HOTBAR_SLOTS + 1] = Some((items::copper_ore(), 5));
+
+        // Nearest chest first
+        let mut targets = [
+            StackTarget {
+                item_id: items::iron_ore(),
+                count: 60,
+                capacity: 64,
+            },
+            StackTarget {
+                item_id: items::stone(),
+                count: 10,
+                capacity: 64,
+            },
+            StackTarget {
+                item_id: items::iron_ore(),
+                count: 30,
+                capacity: 64,
+            },
+        ];
+        let moved = inv.quick_stack_main(&mut targets);
+
+        assert_eq!(moved, 38);
+        assert_eq!(targets[0].count, 64);
+        assert_eq!(targets[1].count, 10);
+        assert_eq!(targets[2].count, 64);
+        assert_eq!(inv.slots[0], Some((items::iron_ore(), 500)));
+        assert_eq!(inv.slots[HOTBAR_SLOTS], Some((items::iron_ore(), 12)));
+        assert_eq!(inv.slots[HOTBAR_SLOTS + 1], Some((items::copper_ore(), 5)));
+
+        // Room for everything empties the slot
+        let mut roomy = [StackTarget {
+            item_id: items::iron_ore(),
+            count: 0,
+            capacity: 64,
+        }];
+        assert_eq!(inv.quick_stack_main(&mut roomy), 12);
+        assert_eq!(inv.slots[HOTBAR_SLOTS], None);
+    }
 }
@@ -10,3 +10,4 @@ pub use platform_inventory::PlatformInventory;
 
 pub use inventory::LocalPlayer;
 pub use inventory::PlayerInventory;
+pub use inventory::StackTarget;
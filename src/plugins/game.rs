@@ -4,43 +4,78 @@
 //! This plugin is the entry point for all game functionality.
 
 use bevy::prelude::*;
+use std::path::Path;
 
 use crate::achievements::AchievementsPlugin;
 use crate::audio::AudioPlugin;
 use crate::blueprint::BlueprintPlugin;
+use crate::cinematic::CinematicPlugin;
 use crate::components::*;
 use crate::craft::CraftPlugin;
+use crate::daynight::DayNightPlugin;
 use crate::events::GameEventsPlugin;
+use crate::fluids::FluidsPlugin;
 use crate::game_spec::{load_ui_elements, RegistryPlugin};
 use crate::graphics::VoxelMaterial;
 use crate::input::InputManagerPlugin;
+use crate::log_console::LogConsolePlugin;
+use crate::logistics::delivery_filter::handle_delivery_policy_click;
+use crate::logistics::delivery_pad::handle_delivery_contract_click;
+use crate::logistics::{
+    BeltAnimationPlugin, DeliveryFilterPlugin, DeliveryPadPlugin, DroppedItemPlugin,
+    PathTracePlugin, QuestHologramPlugin, TimedContractPlugin,
+};
+use crate::main_menu::{start_selected_world, AppState, InGameSet, MainMenuPlugin};
 use crate::map::MapPlugin;
 use crate::modding::ModdingPlugin;
 use crate::plugins::{DebugPlugin, MachineSystemsPlugin, SavePlugin, UIPlugin};
+use crate::respawn::RespawnPlugin;
 use crate::robot::RobotPlugin;
 use crate::settings::SettingsPlugin;
 use crate::setup::{
-    handle_settings_back, handle_settings_sliders, handle_settings_toggles,
-    handle_slider_drag_state, setup_initial_items, setup_lighting, setup_player, setup_ui,
-    update_settings_ui, update_settings_visibility, SliderDragState,
+    handle_goals_buttons, handle_goals_navigation, handle_key_rebind, handle_quest_log_back,
+    handle_quest_log_claim, handle_quest_log_rows, handle_settings_back, handle_settings_keyboard,
+    handle_settings_sliders, handle_settings_tabs, handle_settings_toggles,
+    handle_slider_drag_state, handle_stats_back, scroll_quest_log, setup_initial_items,
+    setup_lighting, setup_player, setup_ui, spawn_settings_ui, update_goals_ui,
+    update_goals_visibility, update_key_bind_texts, update_quest_log_ui,
+    update_quest_log_visibility, update_settings_focus, update_settings_tabs, update_settings_ui,
+    update_settings_visibility, update_stats_ui, update_stats_visibility, KeyRebindState,
+    QuestLogSelection, SettingsScreenState, SliderDragState,
 };
+use crate::sign::{handle_sign_click, SignPlugin};
 use crate::skin::SkinPlugin;
-use crate::statistics::StatisticsPlugin;
+use crate::startup_report::{record_startup_outcome, StartupReportPlugin};
+use crate::statistics::display_panel::handle_display_panel_click;
+use crate::statistics::{DisplayPanelPlugin, StatisticsPlugin};
 use crate::storage::StoragePlugin;
+use crate::systems::ore_scanner::{handle_ore_scanner, update_ore_scanner, OreScanner};
+use crate::systems::wrench::{
+    handle_wrench, setup_wrench_toast, undo_wrench_paste, update_wrench_toast,
+    MachineSettingsClipboard, WrenchToast,
+};
 use crate::systems::{
-    block_break, block_place, handle_assert_machine_event, handle_debug_event, handle_look_event,
-    handle_pause_menu_buttons, handle_screenshot_event, handle_setblock_event,
-    handle_spawn_machine_event, handle_teleport_event, initialize_cursor, load_machine_models,
-    player_look, player_move, process_dirty_chunks, quest_claim_rewards, quest_deliver_button,
-    quest_progress_check, receive_chunk_meshes, rotate_conveyor_placement, select_block_type,
-    setup_highlight_cache, spawn_chunk_tasks, sync_cursor_to_ui_state, sync_legacy_ui_state,
-    tick_action_timers, toggle_cursor_lock, ui_action_handler, ui_escape_handler,
-    ui_inventory_handler, unload_distant_chunks, update_conveyor_shapes, update_delivery_ui,
-    update_guide_markers, update_pause_ui, update_quest_ui, update_target_block,
-    update_target_highlight, AssertMachineEvent, DebugEvent, LookEvent, ScreenshotEvent,
-    SetBlockEvent, TeleportEvent,
+    apply_cursor_controller, block_break, block_place, clear_edit_patches,
+    handle_assert_machine_event, handle_debug_event, handle_look_event, handle_pause_menu_buttons,
+    handle_protection_command, handle_screenshot_event, handle_setblock_event,
+    handle_spawn_machine_event, handle_teleport_event, handle_window_focus,
+    hibernate_unloaded_machines, load_machine_models, player_look, player_move,
+    process_dirty_chunks, quest_claim_rewards, quest_deliver_button, quest_progress_check,
+    receive_chunk_meshes, rotate_conveyor_placement, rotate_targeted_block, select_block_type,
+    setup_highlight_cache, setup_world_border, spawn_chunk_tasks, spawn_edit_patches,
+    sync_legacy_ui_state, tick_action_timers, toggle_conveyor_enabled, toggle_cursor_lock,
+    ui_action_handler, ui_escape_handler, ui_inventory_handler, ui_quest_log_handler,
+    unload_distant_chunks, update_conveyor_shapes, update_conveyor_tier_visuals,
+    update_delivery_ui, update_guide_markers, update_pause_ui, update_platform_compass,
+    update_quest_ui, update_target_block, update_target_highlight, update_world_border_walls,
+    upgrade_conveyor_in_place, wake_hibernated_machines, AssertMachineEvent, BlockClickSet,
+    DebugEvent, LookEvent, ProtectionCommandEvent, ScreenshotEvent, SetBlockEvent, SpawnProtection,
+    TeleportEvent,
+};
+use crate::waypoint::WaypointPlugin;
+use crate::world::{
+    BiomeMap, ChunkMeshTasks, DirtyChunks, WorldData, WorldGenConfig, WORLDGEN_CONFIG_PATH,
 };
-use crate::world::{BiomeMap, ChunkMeshTasks, DirtyChunks, WorldData};
 
 /// Main game plugin that bundles all game systems.
 ///
@@ -63,12 +98,29 @@ impl Plugin for GamePlugin {
             .add_plugins(BlueprintPlugin)
             .add_plugins(CraftPlugin)
             .add_plugins(StoragePlugin)
+            .add_plugins(FluidsPlugin)
+            .add_plugins(DeliveryPadPlugin)
+            .add_plugins(DeliveryFilterPlugin)
+            .add_plugins(QuestHologramPlugin)
+            .add_plugins(DroppedItemPlugin)
+            .add_plugins(PathTracePlugin)
+            .add_plugins(BeltAnimationPlugin)
+            .add_plugins(TimedContractPlugin)
             .add_plugins(StatisticsPlugin)
+            .add_plugins(DisplayPanelPlugin)
             .add_plugins(AudioPlugin)
             .add_plugins(AchievementsPlugin)
             .add_plugins(SkinPlugin)
             .add_plugins(RobotPlugin)
+            .add_plugins(DayNightPlugin)
+            .add_plugins(CinematicPlugin)
+            .add_plugins(RespawnPlugin)
+            .add_plugins(WaypointPlugin)
+            .add_plugins(SignPlugin)
+            .add_plugins(LogConsolePlugin)
+            .add_plugins(StartupReportPlugin)
             .add_plugins(ModdingPlugin)
+            .add_plugins(crate::devlink::DevLinkPlugin)
             // VoxelMaterial for block textures
             .add_plugins(MaterialPlugin::<VoxelMaterial>::default());
 
@@ -84,17 +136,35 @@ impl Plugin for GamePlugin {
         #[cfg(not(target_arch = "wasm32"))]
         app.init_resource::<crate::modding::handlers::EventSubscriptions>();
 
+        // Machine and inventory tables shared with WASM mod host functions (non-WASM only)
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_resource::<crate::modding::wasm::MachineTable>()
+            .init_resource::<crate::modding::wasm::InventoryTable>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    crate::modding::wasm::sync_machine_table,
+                    crate::modding::wasm::sync_inventory_table,
+                )
+                    .in_set(InGameSet),
+            );
+
         // Initialize resources
         // NOTE: GlobalInventory Resource removed - PlatformInventory is now a Component
         // on the DeliveryPlatform entity, initialized in setup_delivery_platform
-        app.init_resource::<WorldData>()
+        let (gen_config, gen_outcome) =
+            WorldGenConfig::load_checked(Path::new(WORLDGEN_CONFIG_PATH));
+        record_startup_outcome(app, gen_outcome);
+        app.insert_resource(WorldData::with_gen_config(gen_config))
             .insert_resource(BiomeMap::new(12345)) // Fixed seed for deterministic biomes
             .init_resource::<CursorLockState>()
+            .init_resource::<CursorController>()
             // Network resources (M.7: multiplayer preparation)
             .init_resource::<EntityMap>()
             .init_resource::<NetworkIdGenerator>()
             .init_resource::<CurrentQuest>()
             .init_resource::<crate::systems::quest::QuestCache>()
+            .init_resource::<QuestLogSelection>()
             // NOTE: ActiveSubQuests removed (dead code) - reimplement with sub-quest UI
             .init_resource::<GameFont>()
             .init_resource::<ChunkMeshTasks>()
@@ -106,6 +176,12 @@ impl Plugin for GamePlugin {
             .init_resource::<GlobalInventorySearch>()
             .init_resource::<BreakingProgress>()
             .init_resource::<SliderDragState>()
+            .init_resource::<SettingsScreenState>()
+            .init_resource::<KeyRebindState>()
+            .init_resource::<MachineSettingsClipboard>()
+            .init_resource::<WrenchToast>()
+            .init_resource::<OreScanner>()
+            .init_resource::<SpawnProtection>()
             // Sky blue background color (simple skybox)
             .insert_resource(ClearColor(Color::srgb(0.47, 0.66, 0.88)));
 
@@ -116,24 +192,30 @@ impl Plugin for GamePlugin {
             .add_message::<DebugEvent>()
             .add_message::<AssertMachineEvent>()
             .add_message::<ScreenshotEvent>()
+            .add_message::<ProtectionCommandEvent>()
             .add_message::<UIAction>();
 
         // UI state management
         app.init_resource::<UIState>();
 
-        // Startup systems
+        // World and HUD setup runs when a world is entered from the main menu,
+        // after start_selected_world has set up WorldData for it
         app.add_systems(
-            Startup,
+            OnEnter(AppState::InGame),
             (
                 setup_lighting,
                 setup_player,
-                setup_ui.after(load_ui_elements),
+                setup_ui,
                 setup_initial_items,
                 // setup_delivery_platform removed - now a tutorial reward
                 load_machine_models,
                 setup_highlight_cache,
-            ),
+                setup_world_border,
+            )
+                .after(start_selected_world),
         );
+        // The settings panel is shared by the main menu and the pause menu
+        app.add_systems(Startup, spawn_settings_ui.after(load_ui_elements));
 
         // Update systems
         self.add_update_systems(app);
@@ -142,23 +224,28 @@ impl Plugin for GamePlugin {
 
 impl GamePlugin {
     fn add_update_systems(&self, app: &mut App) {
-        // Chunk systems: spawn → receive → LOD update (ordered)
+        // Chunk systems: spawn → receive → unload → machine hibernation → LOD update (ordered)
         app.add_systems(
             Update,
             (
                 spawn_chunk_tasks,
                 receive_chunk_meshes,
                 unload_distant_chunks,
+                hibernate_unloaded_machines,
+                wake_hibernated_machines,
                 crate::systems::update_chunk_lod,
             )
-                .chain(),
+                .chain()
+                .in_set(InGameSet),
         );
 
         // Pause UI and cursor control use UIState as single source of truth
         // Must run AFTER sync_legacy_ui_state to ensure UIState is updated first
         app.add_systems(
             Update,
-            (update_pause_ui, handle_pause_menu_buttons).after(sync_legacy_ui_state),
+            (update_pause_ui, handle_pause_menu_buttons)
+                .after(sync_legacy_ui_state)
+                .in_set(InGameSet),
         );
 
         // Player systems must run AFTER update_pause_ui to avoid cursor race conditions
@@ -171,24 +258,88 @@ impl GamePlugin {
                 player_move,
                 tick_action_timers,
             )
-                .after(update_pause_ui),
+                .after(update_pause_ui)
+                .in_set(InGameSet),
+        );
+        // World border walls and the platform compass follow the moved player
+        app.add_systems(
+            Update,
+            (update_world_border_walls, update_platform_compass)
+                .after(player_move)
+                .in_set(InGameSet),
         );
-
-        // Initialize cursor state after window is ready (waits 5 frames)
-        // See: https://github.com/bevyengine/bevy/issues/16237
-        app.add_systems(Update, initialize_cursor);
 
         // Targeting must run before block operations
-        app.add_systems(Update, update_target_block);
+        app.add_systems(Update, update_target_block.in_set(InGameSet));
         // Block operations (break/place blocks)
         // Note: No ordering constraint because systems have too many params for Bevy's trait impls
-        app.add_systems(Update, block_break);
-        app.add_systems(Update, block_place);
+        app.add_systems(Update, block_break.in_set(InGameSet));
+        app.add_systems(Update, block_place.in_set(InGameSet));
+        // Shift+right-click conveyor toggle swallows the click before placement sees it
+        app.add_systems(
+            Update,
+            toggle_conveyor_enabled
+                .before(block_place)
+                .in_set(InGameSet),
+        );
+        // Wrench copy/paste swallows its right-click before the conveyor toggle and placement
+        app.add_systems(
+            Update,
+            handle_wrench
+                .before(toggle_conveyor_enabled)
+                .before(block_place)
+                .in_set(InGameSet),
+        );
+        // Right-clicking a belt with a faster conveyor upgrades it instead of placing a block
+        app.add_systems(
+            Update,
+            upgrade_conveyor_in_place
+                .after(handle_wrench)
+                .before(block_place)
+                .in_set(InGameSet),
+        );
+        // Block right-click handlers take the click before placement sees it
+        app.configure_sets(
+            Update,
+            BlockClickSet
+                .after(handle_wrench)
+                .before(block_place)
+                .in_set(InGameSet),
+        );
+        app.add_systems(
+            Update,
+            (
+                handle_delivery_contract_click,
+                handle_delivery_policy_click,
+                handle_display_panel_click,
+                handle_sign_click,
+            )
+                .in_set(BlockClickSet),
+        );
+        // Ore scanner swallows its right-click before the wrench and everything after it
+        app.add_systems(
+            Update,
+            handle_ore_scanner.before(handle_wrench).in_set(InGameSet),
+        );
+        app.add_systems(Update, update_ore_scanner.in_set(InGameSet));
+        app.add_systems(OnEnter(AppState::InGame), setup_wrench_toast);
+        app.add_systems(
+            Update,
+            (undo_wrench_paste, update_wrench_toast).in_set(InGameSet),
+        );
 
-        // Process dirty chunks (batched mesh regeneration - runs every frame)
-        app.add_systems(Update, process_dirty_chunks);
+        // Process dirty chunks (batched mesh regeneration - runs every frame),
+        // with patch meshes standing in for edits until their sections are rebuilt
+        app.add_systems(
+            Update,
+            (spawn_edit_patches, process_dirty_chunks, clear_edit_patches)
+                .chain()
+                .after(block_break)
+                .after(block_place)
+                .in_set(InGameSet),
+        );
 
-        app.add_systems(Update, select_block_type);
+        app.add_systems(Update, select_block_type.in_set(InGameSet));
 
         // Quest systems
         app.add_systems(
@@ -197,13 +348,14 @@ impl GamePlugin {
                 crate::systems::targeting::update_conveyor_shapes,
                 quest_progress_check,
                 quest_claim_rewards,
-            ),
+            )
+                .in_set(InGameSet),
         );
 
         // Quest UI systems
         app.add_systems(
             Update,
-            (update_delivery_ui, update_quest_ui, quest_deliver_button),
+            (update_delivery_ui, update_quest_ui, quest_deliver_button).in_set(InGameSet),
         );
 
         // Targeting highlight (after target_block update)
@@ -212,10 +364,14 @@ impl GamePlugin {
             (
                 update_target_highlight,
                 rotate_conveyor_placement,
+                rotate_targeted_block,
+                update_belt_warnings,
                 update_conveyor_shapes,
+                update_conveyor_tier_visuals,
                 update_guide_markers,
             )
-                .after(update_target_block),
+                .after(update_target_block)
+                .in_set(InGameSet),
         );
 
         // E2E command handlers
@@ -229,23 +385,31 @@ impl GamePlugin {
                 handle_debug_event,
                 handle_assert_machine_event,
                 handle_screenshot_event,
-            ),
+            )
+                .in_set(InGameSet),
         );
+        // /protection toggles the spawn protection for this save
+        app.add_systems(Update, handle_protection_command.in_set(InGameSet));
 
         // UI navigation systems (must run early to process actions before other UI systems)
         // Order: input handlers emit events → action handler updates UIState → sync to legacy
+        // The action handler also runs in the main menu, which opens the settings panel
         app.add_systems(
             Update,
             (
-                ui_escape_handler,
-                ui_inventory_handler,
+                (
+                    ui_escape_handler,
+                    ui_inventory_handler,
+                    ui_quest_log_handler,
+                )
+                    .in_set(InGameSet),
                 ui_action_handler,
                 sync_legacy_ui_state,
             )
                 .chain(),
         );
 
-        // Settings UI systems
+        // Settings UI systems (also used from the main menu)
         app.add_systems(
             Update,
             (
@@ -255,12 +419,53 @@ impl GamePlugin {
                 handle_settings_sliders,
                 handle_settings_toggles,
                 handle_settings_back,
+                handle_settings_tabs,
+                update_settings_tabs,
+                update_settings_focus,
+                update_key_bind_texts,
+                (handle_key_rebind, handle_settings_keyboard)
+                    .chain()
+                    .after(ui_escape_handler),
             ),
         );
 
-        // Cursor sync system runs in PostUpdate to ensure it's the LAST word on cursor state
-        // This is the best practice: UIState is the single source of truth for cursor
+        // Stats UI systems
+        app.add_systems(
+            Update,
+            (update_stats_visibility, update_stats_ui, handle_stats_back).in_set(InGameSet),
+        );
+
+        // Production goals UI systems
+        app.add_systems(
+            Update,
+            (
+                update_goals_visibility,
+                update_goals_ui,
+                handle_goals_buttons,
+                handle_goals_navigation,
+            )
+                .in_set(InGameSet),
+        );
+
+        // Quest log UI systems
+        app.add_systems(
+            Update,
+            (
+                update_quest_log_visibility,
+                update_quest_log_ui,
+                handle_quest_log_rows,
+                handle_quest_log_claim,
+                handle_quest_log_back,
+                scroll_quest_log,
+            )
+                .in_set(InGameSet),
+        );
+
+        // CursorController is the single owner of the cursor; applying it in
+        // PostUpdate gives it the last word after every request this frame
         // See: https://bevy-cheatbook.github.io/window/mouse-grab.html
-        app.add_systems(PostUpdate, sync_cursor_to_ui_state);
+        // Not gated: it keeps the cursor free in the main menu too
+        app.add_systems(PreUpdate, handle_window_focus)
+            .add_systems(PostUpdate, apply_cursor_controller);
     }
 }
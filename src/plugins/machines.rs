@@ -8,11 +8,22 @@
 
 use bevy::prelude::*;
 
-use crate::components::{ConveyorRotationOffset, InteractingMachine, MachineModels};
+use crate::components::{
+    ActivityClock, ConveyorItemCulling, ConveyorRotationOffset, ConveyorVisualAssets,
+    InteractingMachine, MachineModels, MachineSleepStats,
+};
 use crate::machines::{
-    cleanup_invalid_interacting_machine, generic_machine_interact, generic_machine_tick,
-    generic_machine_ui_input, machine_visual_feedback, update_generic_machine_ui,
+    apply_simulation_speed, cleanup_invalid_interacting_machine, generic_machine_drill_input,
+    generic_machine_interact, generic_machine_recipe_browser_input,
+    generic_machine_slot_route_input, generic_machine_tick, generic_machine_toggle_input,
+    generic_machine_ui_input, handle_tickrate_command, hopper_tick, machine_visual_feedback,
+    sample_machine_activity, spawn_machine_output_notch, update_disabled_tint,
+    update_generic_machine_activity_ui, update_generic_machine_drill_ui,
+    update_generic_machine_slot_route_ui, update_generic_machine_ui, update_machine_sleep,
+    update_machine_slot_palette, update_machine_status_indicators, update_pass_through_stripes,
+    update_slot_route_dots, MachineStatusIndicatorAssets, SimulationSpeed, TickRateCommandEvent,
 };
+use crate::main_menu::InGameSet;
 use crate::systems::{conveyor_transfer, update_conveyor_item_visuals};
 
 /// Plugin that organizes all machine-related systems
@@ -23,7 +34,14 @@ impl Plugin for MachineSystemsPlugin {
         // Machine-related resources
         app.init_resource::<InteractingMachine>()
             .init_resource::<MachineModels>()
-            .init_resource::<ConveyorRotationOffset>();
+            .init_resource::<ConveyorRotationOffset>()
+            .init_resource::<ConveyorItemCulling>()
+            .init_resource::<ConveyorVisualAssets>()
+            .init_resource::<MachineStatusIndicatorAssets>()
+            .init_resource::<SimulationSpeed>()
+            .init_resource::<MachineSleepStats>()
+            .init_resource::<ActivityClock>()
+            .add_message::<TickRateCommandEvent>();
 
         // Machine interaction systems (Phase C: generic)
         app.add_systems(
@@ -31,21 +49,63 @@ impl Plugin for MachineSystemsPlugin {
             (
                 generic_machine_interact,
                 generic_machine_ui_input,
+                generic_machine_toggle_input,
+                generic_machine_slot_route_input,
+                generic_machine_drill_input,
+                generic_machine_recipe_browser_input,
                 cleanup_invalid_interacting_machine,
-            ),
+            )
+                .in_set(InGameSet),
         );
 
         // Machine processing systems - fixed timestep for deterministic logic
-        // FixedUpdate runs at 20 ticks/second for consistent game simulation
-        app.add_systems(FixedUpdate, (generic_machine_tick, conveyor_transfer));
+        // FixedUpdate runs at SIMULATION_HZ (scaled by SimulationSpeed);
+        // miners → processors → sleep/wake → hoppers → utilization → conveyor progress/transfers → delivery
+        app.add_systems(
+            FixedUpdate,
+            (
+                generic_machine_tick,
+                update_machine_sleep,
+                hopper_tick,
+                sample_machine_activity,
+                conveyor_transfer,
+            )
+                .chain()
+                .in_set(InGameSet),
+        );
+        app.add_systems(
+            Update,
+            (handle_tickrate_command, apply_simulation_speed)
+                .chain()
+                .in_set(InGameSet),
+        );
 
         // Visual update systems - run every frame for smooth rendering
         app.add_systems(
             Update,
-            (machine_visual_feedback, update_conveyor_item_visuals),
+            (
+                machine_visual_feedback,
+                spawn_machine_output_notch,
+                update_disabled_tint,
+                update_pass_through_stripes,
+                update_slot_route_dots,
+                update_conveyor_item_visuals,
+                update_machine_status_indicators,
+            )
+                .in_set(InGameSet),
         );
 
         // Machine UI update systems (Phase C: generic)
-        app.add_systems(Update, update_generic_machine_ui);
+        app.add_systems(
+            Update,
+            (
+                update_generic_machine_ui,
+                update_generic_machine_drill_ui,
+                update_generic_machine_activity_ui,
+                update_generic_machine_slot_route_ui,
+                update_machine_slot_palette,
+            )
+                .in_set(InGameSet),
+        );
     }
 }
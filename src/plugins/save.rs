@@ -4,7 +4,11 @@
 
 use bevy::prelude::*;
 
-use crate::save::AutoSaveTimer;
+use crate::main_menu::{AppState, InGameSet};
+use crate::save::{
+    poll_save_task, setup_save_indicator, update_pause_last_saved, update_save_indicator,
+    wait_for_save_before_load, AutoSaveTimer, LastSave, SaveDir, SaveIndicator, SaveTask,
+};
 use crate::systems::{auto_save_system, handle_load_event, handle_save_event};
 use crate::{LoadGameEvent, SaveGameEvent, SaveLoadState};
 
@@ -15,16 +19,43 @@ impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
         // Save resources
         app.init_resource::<AutoSaveTimer>()
-            .init_resource::<SaveLoadState>();
+            .init_resource::<SaveLoadState>()
+            .init_resource::<SaveTask>()
+            .init_resource::<SaveIndicator>()
+            .init_resource::<LastSave>()
+            .init_resource::<SaveDir>();
 
         // Save events
         app.add_message::<SaveGameEvent>()
             .add_message::<LoadGameEvent>();
 
-        // Save systems
-        app.add_systems(
-            Update,
-            (auto_save_system, handle_save_event, handle_load_event),
-        );
+        // Save systems (writing happens in the background, see SaveTask)
+        app.add_systems(OnEnter(AppState::InGame), setup_save_indicator)
+            .add_systems(
+                Update,
+                (
+                    auto_save_system,
+                    handle_save_event,
+                    poll_save_task,
+                    wait_for_save_before_load,
+                    handle_load_event,
+                    update_save_indicator,
+                    update_pause_last_saved,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+
+        // Last chance to save: after everything else that frame, before the runner exits
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Last, crate::save::save_on_exit.in_set(InGameSet));
+
+        // Browsers don't send AppExit; flush a prepared snapshot on page unload instead
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(Startup, crate::save::web::install_unload_hook)
+            .add_systems(
+                Update,
+                crate::save::web::prepare_unload_snapshot.in_set(InGameSet),
+            );
     }
 }
@@ -4,20 +4,24 @@
 
 use bevy::prelude::*;
 
+use crate::main_menu::{AppState, InGameSet};
 use crate::systems::{
-    command_input_handler, command_input_toggle, creative_inventory_click,
-    inventory_continuous_shift_click, inventory_slot_click, inventory_update_slots,
-    process_tutorial_events, spawn_breaking_progress_ui, track_inventory_open, track_movement,
-    track_production, trash_slot_click, update_breaking_progress_ui, update_command_suggestions,
-    update_creative_catalog_sprites, update_held_item_3d, update_held_item_display,
-    update_hotbar_item_name, update_hotbar_ui, update_inventory_tooltip,
-    update_inventory_visibility, update_tutorial_ui, update_upper_panel_slots,
-    upper_panel_category_click, upper_panel_page_nav, upper_panel_slot_click, HeldItemDisplayState,
-    TutorialEvent,
+    command_input_handler, command_input_toggle, crafting_tab_click, creative_inventory_click,
+    hand_craft_click, inventory_continuous_shift_click, inventory_quick_stack_click,
+    inventory_slot_click, inventory_sort_click, inventory_update_slots, process_tutorial_events,
+    spawn_breaking_progress_ui, track_inventory_open, track_movement, track_production,
+    track_recent_placeables, trash_slot_click, update_breaking_progress_ui,
+    update_command_suggestions, update_crafting_panel, update_creative_catalog_sprites,
+    update_held_item_3d, update_held_item_display, update_hotbar_item_name, update_hotbar_ui,
+    update_inventory_tooltip, update_inventory_visibility, update_machine_hover_card,
+    update_pending_trash, update_quick_select, update_quick_select_ui, update_trash_slot_display,
+    update_tutorial_ui, update_upper_panel_slots, upper_panel_category_click, upper_panel_page_nav,
+    upper_panel_slot_click, HeldItemDisplayState, MachineHoverState, PlacementPreview,
+    QuickSelectMenu, RecentPlaceables, TutorialEvent,
 };
 use crate::{
-    CommandInputState, GuideMarkers, HeldItem, InventoryOpen, ItemSprites, TargetBlock,
-    TutorialProgress, TutorialShown,
+    CommandInputState, CraftingTabOpen, GuideMarkers, HeldItem, InventoryOpen, ItemSprites,
+    PendingTrash, TargetBlock, TutorialProgress, TutorialShown,
 };
 
 /// Plugin for all UI-related systems
@@ -27,64 +31,101 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         // UI resources (DebugHudState is in DebugPlugin to avoid duplication)
         app.init_resource::<TargetBlock>()
+            .init_resource::<PlacementPreview>()
             .init_resource::<InventoryOpen>()
+            .init_resource::<CraftingTabOpen>()
             .init_resource::<TutorialShown>()
             .init_resource::<TutorialProgress>()
             .init_resource::<HeldItem>()
+            .init_resource::<PendingTrash>()
             .init_resource::<CommandInputState>()
             .init_resource::<GuideMarkers>()
             .init_resource::<ItemSprites>()
-            .init_resource::<HeldItemDisplayState>();
+            .init_resource::<HeldItemDisplayState>()
+            .init_resource::<MachineHoverState>()
+            .init_resource::<RecentPlaceables>()
+            .init_resource::<QuickSelectMenu>();
 
         // Tutorial event
         app.add_message::<TutorialEvent>();
 
         // Spawn breaking progress UI
-        app.add_systems(Startup, spawn_breaking_progress_ui);
+        app.add_systems(OnEnter(AppState::InGame), spawn_breaking_progress_ui);
 
         // UI update systems (debug HUD systems are in DebugPlugin)
-        app.add_systems(Update, (update_hotbar_ui, update_held_item_3d))
-            .add_systems(Update, update_breaking_progress_ui)
-            .add_systems(
-                Update,
-                (
-                    // Inventory systems
-                    update_inventory_visibility,
-                    inventory_slot_click,
-                    inventory_continuous_shift_click,
-                    inventory_update_slots,
-                    update_held_item_display,
-                    update_hotbar_item_name,
-                    update_inventory_tooltip,
-                    update_creative_catalog_sprites,
-                    trash_slot_click,
-                    creative_inventory_click,
-                    // Upper panel systems
-                    update_upper_panel_slots,
-                    upper_panel_slot_click,
-                    upper_panel_page_nav,
-                    upper_panel_category_click,
-                ),
+        app.add_systems(
+            Update,
+            (
+                update_hotbar_ui,
+                update_held_item_3d,
+                update_machine_hover_card,
             )
-            .add_systems(
-                Update,
-                (
-                    // Command input systems
-                    command_input_toggle,
-                    command_input_handler,
-                    update_command_suggestions,
-                ),
+                .in_set(InGameSet),
+        )
+        .add_systems(
+            Update,
+            (
+                track_recent_placeables,
+                update_quick_select,
+                update_quick_select_ui,
             )
-            .add_systems(
-                Update,
-                (
-                    // Tutorial systems
-                    track_movement,
-                    track_inventory_open,
-                    track_production,
-                    process_tutorial_events,
-                    update_tutorial_ui,
-                ),
-            );
+                .chain()
+                .in_set(InGameSet),
+        )
+        .add_systems(Update, update_breaking_progress_ui.in_set(InGameSet))
+        .add_systems(
+            Update,
+            (
+                // Inventory systems
+                update_inventory_visibility,
+                inventory_slot_click,
+                inventory_continuous_shift_click,
+                inventory_update_slots,
+                update_held_item_display,
+                update_hotbar_item_name,
+                update_inventory_tooltip,
+                update_creative_catalog_sprites,
+                trash_slot_click,
+                update_pending_trash,
+                update_trash_slot_display,
+                creative_inventory_click,
+                // Upper panel systems
+                update_upper_panel_slots,
+                upper_panel_slot_click,
+                upper_panel_page_nav,
+                upper_panel_category_click,
+                // Crafting tab systems
+                crafting_tab_click,
+                update_crafting_panel,
+                hand_craft_click,
+            )
+                .in_set(InGameSet),
+        )
+        .add_systems(
+            Update,
+            (inventory_sort_click, inventory_quick_stack_click).in_set(InGameSet),
+        )
+        .add_systems(
+            Update,
+            (
+                // Command input systems
+                command_input_toggle,
+                command_input_handler,
+                update_command_suggestions,
+            )
+                .in_set(InGameSet),
+        )
+        .add_systems(
+            Update,
+            (
+                // Tutorial systems
+                track_movement,
+                track_inventory_open,
+                track_production,
+                process_tutorial_events,
+                update_tutorial_ui,
+            )
+                .in_set(InGameSet),
+        );
     }
 }
@@ -0,0 +1,303 @@
+//! Player respawn and world bottom safety
+//!
+//! - Void check: below `VOID_Y` the player is sent back to their spawn point
+//!   (survival mode loses `RespawnSettings::void_item_loss` of each stack)
+//! - `/spawn` teleports home, `/setspawn` moves the spawn point (saved with the player)
+//!
+//! There is no fall damage: the player has no health and the controller has
+//! no gravity (see the no-HP design in `.specify/specs/core-concept.md`).
+//!
+//! Respawning returns the held item to the inventory and closes machine UIs.
+
+use crate::components::{
+    CreativeMode, CursorController, GenericMachineUI, HeldItem, InteractingMachine, Player,
+    UIContext,
+};
+use crate::main_menu::InGameSet;
+use crate::player::PlayerInventory;
+use bevy::prelude::*;
+use tracing::info;
+
+/// Where a new player (or a save without a spawn point) respawns
+pub const DEFAULT_SPAWN_POINT: Vec3 = Vec3::new(8.0, 12.0, 20.0);
+
+/// Below this height the player is considered lost in the void
+pub const VOID_Y: f32 = -32.0;
+
+/// Player's respawn position (saved with the player)
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SpawnPoint(pub Vec3);
+
+impl Default for SpawnPoint {
+    fn default() -> Self {
+        Self(DEFAULT_SPAWN_POINT)
+    }
+}
+
+/// Respawn tuning
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct RespawnSettings {
+    /// Fraction of each inventory stack lost on a void respawn in survival (0.0 = keep all)
+    pub void_item_loss: f32,
+}
+
+impl Default for RespawnSettings {
+    fn default() -> Self {
+        Self {
+            void_item_loss: 0.0,
+        }
+    }
+}
+
+/// Respawn command from `/spawn` and `/setspawn`
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespawnCommandEvent {
+    /// Teleport to the spawn point
+    Spawn,
+    /// Move the spawn point to the player's position
+    SetSpawn,
+}
+
+/// Why the player is being respawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespawnReason {
+    /// Fell below `VOID_Y`
+    Void,
+    /// `/spawn`
+    Command,
+}
+
+/// Request to move the player back to their spawn point
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RespawnRequest {
+    pub reason: RespawnReason,
+}
+
+/// Whether a position has fallen out of the world
+pub fn is_in_void(pos: Vec3) -> bool {
+    pos.y < VOID_Y
+}
+
+/// Remove `fraction` (0.0-1.0) of every stack, rounding down
+pub fn apply_item_loss(inventory: &mut PlayerInventory, fraction: f32) {
+    let fraction = fraction.clamp(0.0, 1.0);
+    if fraction == 0.0 {
+        return;
+    }
+    for slot in inventory.slots.iter_mut() {
+        if let Some((_, count)) = slot {
+            *count -= (*count as f32 * fraction) as u32;
+            if *count == 0 {
+                *slot = None;
+            }
+        }
+    }
+}
+
+/// Send the player home when they fall below the world
+fn check_void(
+    player_query: Query<&Transform, With<Player>>,
+    mut requests: MessageWriter<RespawnRequest>,
+) {
+    let Ok(transform) = player_query.single() else {
+        return;
+    };
+    if is_in_void(transform.translation) {
+        requests.write(RespawnRequest {
+            reason: RespawnReason::Void,
+        });
+    }
+}
+
+/// Apply `/spawn` and `/setspawn`
+fn handle_respawn_command(
+    mut events: MessageReader<RespawnCommandEvent>,
+    mut requests: MessageWriter<RespawnRequest>,
+    mut player_query: Query<(&Transform, &mut SpawnPoint), With<Player>>,
+) {
+    for event in events.read() {
+        match event {
+            RespawnCommandEvent::Spawn => {
+                requests.write(RespawnRequest {
+                    reason: RespawnReason::Command,
+                });
+            }
+            RespawnCommandEvent::SetSpawn => {
+                if let Ok((transform, mut spawn)) = player_query.single_mut() {
+                    spawn.0 = transform.translation;
+                    info!("Spawn point set to {:?}", spawn.0);
+                }
+            }
+        }
+    }
+}
+
+/// Teleport to the spawn point, return the held item and close machine UIs
+#[allow(clippy::too_many_arguments)]
+fn apply_respawn(
+    mut requests: MessageReader<RespawnRequest>,
+    mut player_query: Query<(&mut Transform, &SpawnPoint, &mut PlayerInventory), With<Player>>,
+    mut held_item: ResMut<HeldItem>,
+    mut interacting: ResMut<InteractingMachine>,
+    mut machine_ui_query: Query<&mut Visibility, With<GenericMachineUI>>,
+    mut cursor: ResMut<CursorController>,
+    creative_mode: Res<CreativeMode>,
+    settings: Res<RespawnSettings>,
+) {
+    // Several requests in one frame (e.g. void + /spawn) respawn once, void wins
+    let mut reason = None;
+    for request in requests.read() {
+        if reason != Some(RespawnReason::Void) {
+            reason = Some(request.reason);
+        }
+    }
+    let Some(reason) = reason else {
+        return;
+    };
+    let Ok((mut transform, spawn, mut inventory)) = player_query.single_mut() else {
+        return;
+    };
+
+    transform.translation = spawn.0;
+
+    if let Some((item_id, count)) = held_item.0.take() {
+        let left = inventory.add_item_by_id(item_id, count);
+        held_item.0 = (left > 0).then_some((item_id, left));
+    }
+
+    if let Some(entity) = interacting.0.take() {
+        for mut vis in machine_ui_query.iter_mut() {
+            *vis = Visibility::Hidden;
+        }
+        cursor.release_ui(&UIContext::Machine(entity));
+    }
+
+    if reason == RespawnReason::Void && !creative_mode.enabled {
+        apply_item_loss(&mut inventory, settings.void_item_loss);
+    }
+
+    info!("Respawned at {:?} ({:?})", spawn.0, reason);
+}
+
+/// Plugin for respawn and void safety
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RespawnSettings>()
+            .add_message::<RespawnCommandEvent>()
+            .add_message::<RespawnRequest>()
+            .add_systems(
+                Update,
+                (check_void, handle_respawn_command, apply_respawn)
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    fn respawn_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<CreativeMode>()
+            .init_resource::<HeldItem>()
+            .init_resource::<InteractingMachine>()
+            .add_plugins(RespawnPlugin);
+        app
+    }
+
+    fn spawn_player(app: &mut App, pos: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((
+                Player,
+                PlayerInventory::default(),
+                SpawnPoint::default(),
+                Transform::from_translation(pos),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn test_void_check_threshold() {
+        assert!(!is_in_void(Vec3::new(0.0, VOID_Y, 0.0)));
+        assert!(is_in_void(Vec3::new(0.0, VOID_Y - 0.1, 0.0)));
+        assert!(!is_in_void(DEFAULT_SPAWN_POINT));
+    }
+
+    #[test]
+    fn test_falling_into_void_teleports_home() {
+        let mut app = respawn_app();
+        let player = spawn_player(&mut app, Vec3::new(3.0, 20.0, 3.0));
+        app.world_mut().resource_mut::<HeldItem>().0 = Some((items::iron_ore(), 5));
+        app.world_mut().resource_mut::<InteractingMachine>().0 = Some(Entity::PLACEHOLDER);
+
+        // Above the void: nothing happens
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(player).unwrap().translation,
+            Vec3::new(3.0, 20.0, 3.0)
+        );
+
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .y = VOID_Y - 1.0;
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Transform>(player).unwrap().translation,
+            DEFAULT_SPAWN_POINT
+        );
+        // Held item went back into the inventory, machine UI closed
+        assert!(app.world().resource::<HeldItem>().0.is_none());
+        let inventory = app.world().get::<PlayerInventory>(player).unwrap();
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ore()), 5);
+        assert!(app.world().resource::<InteractingMachine>().0.is_none());
+    }
+
+    #[test]
+    fn test_setspawn_then_spawn_returns_to_new_point() {
+        let mut app = respawn_app();
+        let home = Vec3::new(-5.0, 30.0, 7.0);
+        let player = spawn_player(&mut app, home);
+
+        app.world_mut().write_message(RespawnCommandEvent::SetSpawn);
+        app.update();
+        assert_eq!(app.world().get::<SpawnPoint>(player).unwrap().0, home);
+
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation = Vec3::new(100.0, 10.0, 100.0);
+        app.world_mut().write_message(RespawnCommandEvent::Spawn);
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(player).unwrap().translation,
+            home
+        );
+    }
+
+    #[test]
+    fn test_item_loss_fraction() {
+        let mut inventory = PlayerInventory::default();
+        inventory.slots[0] = Some((items::iron_ore(), 10));
+        inventory.slots[1] = Some((items::coal(), 1));
+
+        // Default setting keeps everything
+        apply_item_loss(&mut inventory, RespawnSettings::default().void_item_loss);
+        assert_eq!(inventory.slots[0], Some((items::iron_ore(), 10)));
+
+        apply_item_loss(&mut inventory, 0.5);
+        assert_eq!(inventory.slots[0], Some((items::iron_ore(), 5)));
+        assert_eq!(inventory.slots[1], Some((items::coal(), 1)));
+
+        apply_item_loss(&mut inventory, 1.0);
+        assert_eq!(inventory.slots[0], None);
+        assert_eq!(inventory.slots[1], None);
+    }
+}
@@ -8,10 +8,13 @@ use serde::{Deserialize, Serialize};
 pub struct PlayerSaveData {
     pub position: Vec3Save,
     pub rotation: CameraRotation,
+    /// Respawn point set by /setspawn (None = default spawn)
+    #[serde(default)]
+    pub spawn_point: Option<Vec3Save>,
 }
 
 /// Vec3 wrapper for serialization
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Vec3Save {
     pub x: f32,
     pub y: f32,
@@ -65,9 +68,10 @@ pub struct CameraRotation {
     pub yaw: f32,
 }
 
-/// Direction for conveyors
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+/// Direction for conveyors and machine facing
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DirectionSave {
+    #[default]
     North,
     South,
     East,
@@ -88,4 +92,11 @@ pub enum ConveyorShapeSave {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GameModeSaveData {
     pub creative: bool,
+    /// Spawn protection around the delivery platforms (`/protection`)
+    #[serde(default = "default_spawn_protection")]
+    pub spawn_protection: bool,
+}
+
+fn default_spawn_protection() -> bool {
+    true
 }
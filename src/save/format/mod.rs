@@ -5,6 +5,7 @@
 
 mod common;
 pub mod native;
+pub mod palette;
 mod timer;
 mod v2;
 
@@ -25,13 +26,17 @@ pub use common::{
 };
 
 // Re-export timer types
-pub use timer::{AutoSaveTimer, SaveSlotInfo};
+pub use timer::{AutoSaveTimer, SaveSlotInfo, WorldSlotInfo};
 
 // Re-export V2 types
 pub use v2::{
-    ConveyorItemSaveV2, ConveyorSaveDataV2, CrusherSaveDataV2, FurnaceSaveDataV2,
-    InventorySaveDataV2, ItemStackV2, MachineSaveDataV2, MinerSaveDataV2,
-    PlatformInventorySaveDataV2, QuestSaveDataV2, SaveDataV2, WorldSaveDataV2,
+    ChunkBlocksSaveV2, ClockSaveDataV2, ConveyorItemSaveV2, ConveyorSaveDataV2, CrusherSaveDataV2,
+    DataMachineSaveDataV2, DeliveryContractSaveDataV2, DeliveryFilterSaveDataV2,
+    DisplayPanelSaveDataV2, DrillSaveDataV2, FaceRouteSaveV2, FluidNetworkSaveDataV2,
+    FurnaceSaveDataV2, HopperSaveDataV2, InventorySaveDataV2, ItemStackV2, MachineSaveDataV2,
+    MinerSaveDataV2, PlatformInventorySaveDataV2, QuestSaveDataV2, RecyclerSaveDataV2, SaveDataV2,
+    SignSaveDataV2, SlotRouteSave, StatsSaveDataV2, TimedContractSaveDataV2,
+    TimedContractsSaveDataV2, WaypointSaveDataV2, WorldInfoSaveDataV2, WorldSaveDataV2,
 };
 
 /// List all save files
@@ -101,6 +106,113 @@ mod tests {
         assert_eq!(restored, zero_pos);
     }
 
+    fn sorted(mut blocks: Vec<(IVec3, Option<String>)>) -> Vec<(IVec3, Option<String>)> {
+        blocks.sort_by_key(|(pos, _)| pos.to_array());
+        blocks
+    }
+
+    #[test]
+    fn test_world_palette_round_trip() {
+        let blocks = vec![
+            (IVec3::new(5, 10, 5), Some("base:stone".to_string())),
+            (IVec3::new(6, 10, 5), None),
+            (IVec3::new(-17, 0, -1), Some("base:iron_ore".to_string())),
+            (IVec3::new(15, 31, 15), Some("base:stone".to_string())),
+            // Outside the chunk height: kept in the legacy map
+            (IVec3::new(1, 40, 1), Some("base:coal".to_string())),
+        ];
+        let world = WorldSaveDataV2::from_modified(blocks.clone());
+        assert_eq!(world.chunks.len(), 2);
+        assert_eq!(world.modified_blocks.len(), 1);
+        assert_eq!(sorted(world.modified().unwrap()), sorted(blocks));
+
+        let json = serde_json::to_string(&world).unwrap();
+        let restored: WorldSaveDataV2 = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.chunks, world.chunks);
+
+        // Nothing modified: no chunks, and the legacy map isn't written
+        let empty = WorldSaveDataV2::from_modified(Vec::new());
+        assert!(empty.chunks.is_empty());
+        assert!(!serde_json::to_string(&empty)
+            .unwrap()
+            .contains("modified_blocks"));
+    }
+
+    #[test]
+    fn test_legacy_world_still_loads() {
+        let json = r#"{"modified_blocks": {"1,8,2": "base:stone", "3,8,4": null, "bad": null}}"#;
+        let world: WorldSaveDataV2 = serde_json::from_str(json).unwrap();
+        assert!(world.chunks.is_empty());
+        assert_eq!(
+            sorted(world.modified().unwrap()),
+            [
+                (IVec3::new(1, 8, 2), Some("base:stone".to_string())),
+                (IVec3::new(3, 8, 4), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_corrupt_chunk_is_an_error() {
+        let mut world = WorldSaveDataV2::from_modified([(IVec3::ONE, None)]);
+        world.chunks[0].cells = "AAAA".to_string();
+        assert!(world.modified().is_err());
+        world.chunks[0].cells = "not base64!".to_string();
+        assert!(world.modified().is_err());
+    }
+
+    #[test]
+    fn test_compressed_and_plain_saves_are_detected() {
+        let json = r#"{"timestamp": 1000}"#;
+        let compressed = native::compress_save(json).unwrap();
+        assert_eq!(compressed[0], native::COMPRESSED_SAVE_MAGIC);
+        assert_eq!(native::decompress_save(&compressed).unwrap(), json);
+
+        // Saves from before compression are plain JSON
+        assert_eq!(native::decompress_save(json.as_bytes()).unwrap(), json);
+        assert!(native::decompress_save(&[native::COMPRESSED_SAVE_MAGIC, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_palette_world_is_much_smaller() {
+        use crate::world::{ChunkData, WorldData};
+        use bevy::prelude::IVec2;
+
+        // Every cell of 10 generated chunks, as if the player had touched them all
+        let blocks: Vec<(IVec3, Option<String>)> = (0..10)
+            .flat_map(|i| {
+                let coord = IVec2::new(i % 5, i / 5);
+                let chunk = ChunkData::generate(coord);
+                (0..ChunkData::ARRAY_SIZE).map(move |index| {
+                    let pos = WorldData::local_to_world(coord, ChunkData::index_to_pos(index));
+                    let block = chunk.blocks[index].and_then(|b| b.name().map(str::to_string));
+                    (pos, block)
+                })
+            })
+            .collect();
+
+        let legacy = WorldSaveDataV2 {
+            modified_blocks: blocks
+                .iter()
+                .map(|(pos, block)| (WorldSaveDataV2::pos_to_key(*pos), block.clone()))
+                .collect(),
+            chunks: Vec::new(),
+        };
+        let legacy_size = serde_json::to_string_pretty(&legacy).unwrap().len();
+
+        let palette = WorldSaveDataV2::from_modified(blocks.clone());
+        let compact = native::compress_save(&serde_json::to_string_pretty(&palette).unwrap())
+            .unwrap()
+            .len();
+        assert!(
+            legacy_size >= compact * 5,
+            "legacy {} bytes, palette {} bytes",
+            legacy_size,
+            compact
+        );
+        assert_eq!(sorted(palette.modified().unwrap()), sorted(blocks));
+    }
+
     #[test]
     fn test_item_stack_v2_new() {
         let stack = ItemStackV2::new("base:iron_ore", 64);
@@ -136,14 +248,17 @@ mod tests {
                     pitch: 0.0,
                     yaw: 0.0,
                 },
+                spawn_point: None,
             },
             inventory: InventorySaveDataV2 {
                 selected_slot: 0,
                 slots: vec![Some(ItemStackV2::new("base:iron_ore", 64))],
+                recent_placeables: vec![],
             },
             platform_inventory: PlatformInventorySaveDataV2::default(),
             world: WorldSaveDataV2 {
                 modified_blocks: HashMap::new(),
+                chunks: Vec::new(),
             },
             machines: vec![],
             quests: QuestSaveDataV2 {
@@ -151,8 +266,25 @@ mod tests {
                 completed: false,
                 rewards_claimed: false,
                 delivered: HashMap::new(),
+                claimed_at: HashMap::new(),
+            },
+            mode: GameModeSaveData {
+                creative: false,
+                spawn_protection: true,
             },
-            mode: GameModeSaveData { creative: false },
+            clock: ClockSaveDataV2::default(),
+            fluids: Vec::new(),
+            delivery_contracts: Vec::new(),
+            delivery_filters: Vec::new(),
+            display_panels: Vec::new(),
+            signs: Vec::new(),
+            worldgen_hash: None,
+            content_version: None,
+            mod_content: Default::default(),
+            stats: StatsSaveDataV2::default(),
+            timed_contracts: TimedContractsSaveDataV2::default(),
+            waypoints: Vec::new(),
+            world_info: WorldInfoSaveDataV2::default(),
         };
 
         // Serialize and deserialize
@@ -180,6 +312,10 @@ mod tests {
                 position: IVec3Save { x: 0, y: 0, z: 0 },
                 progress: 0.5,
                 buffer: Some(ItemStackV2::new("base:iron_ore", 1)),
+                facing: DirectionSave::East,
+                enabled: true,
+                output_any_side: true,
+                drill: None,
             }),
             MachineSaveDataV2::Conveyor(ConveyorSaveDataV2 {
                 position: IVec3Save { x: 1, y: 0, z: 0 },
@@ -189,9 +325,12 @@ mod tests {
                     item_id: "base:coal".to_string(),
                     progress: 0.3,
                     lateral_offset: 0.0,
+                    count: 1,
                 }],
                 last_output_index: 0,
-                last_input_source: 0,
+                last_input_pos: None,
+                enabled: true,
+                speed_multiplier: 1.0,
             }),
             MachineSaveDataV2::Furnace(FurnaceSaveDataV2 {
                 position: IVec3Save { x: 2, y: 0, z: 0 },
@@ -199,12 +338,63 @@ mod tests {
                 input: Some(ItemStackV2::new("base:iron_ore", 5)),
                 output: Some(ItemStackV2::new("base:iron_ingot", 3)),
                 progress: 0.75,
+                facing: DirectionSave::West,
+                enabled: true,
+                output_any_side: false,
+                pass_through: Vec::new(),
+                slot_routes: Vec::new(),
             }),
             MachineSaveDataV2::Crusher(CrusherSaveDataV2 {
                 position: IVec3Save { x: 3, y: 0, z: 0 },
                 input: Some(ItemStackV2::new("base:copper_ore", 10)),
                 output: None,
                 progress: 0.25,
+                facing: DirectionSave::South,
+                enabled: true,
+                output_any_side: false,
+                pass_through: Vec::new(),
+            }),
+            MachineSaveDataV2::Recycler(RecyclerSaveDataV2 {
+                position: IVec3Save { x: 4, y: 0, z: 0 },
+                input: Some(ItemStackV2::new("base:crusher_block", 1)),
+                outputs: vec![
+                    Some(ItemStackV2::new("base:iron_ingot", 5)),
+                    None,
+                    None,
+                    None,
+                ],
+                progress: 0.5,
+                facing: DirectionSave::East,
+                enabled: true,
+                pass_through: Vec::new(),
+            }),
+            MachineSaveDataV2::Hopper(HopperSaveDataV2 {
+                position: IVec3Save { x: 5, y: 1, z: 0 },
+                slots: vec![
+                    Some(ItemStackV2::new("base:iron_ore", 3)),
+                    None,
+                    None,
+                    None,
+                    None,
+                ],
+                progress: 0.2,
+                facing: DirectionSave::North,
+                enabled: true,
+                push_side: Some(DirectionSave::West),
+                pass_through: Vec::new(),
+            }),
+            MachineSaveDataV2::DataMachine(DataMachineSaveDataV2 {
+                machine_id: "base:grinder".to_string(),
+                position: IVec3Save { x: 6, y: 0, z: 0 },
+                inputs: vec![Some(ItemStackV2::new("base:stone", 4))],
+                outputs: vec![None],
+                fuel: 0,
+                progress: 0.5,
+                facing: DirectionSave::East,
+                enabled: true,
+                output_any_side: false,
+                pass_through: Vec::new(),
+                slot_routes: Vec::new(),
             }),
         ];
 
@@ -219,6 +409,18 @@ mod tests {
                 (MachineSaveDataV2::Conveyor(_), MachineSaveDataV2::Conveyor(_)) => {}
                 (MachineSaveDataV2::Furnace(_), MachineSaveDataV2::Furnace(_)) => {}
                 (MachineSaveDataV2::Crusher(_), MachineSaveDataV2::Crusher(_)) => {}
+                (MachineSaveDataV2::Recycler(a), MachineSaveDataV2::Recycler(b)) => {
+                    assert_eq!(a.outputs.len(), b.outputs.len());
+                    assert_eq!(b.outputs[0].as_ref().map(|s| s.count), Some(5));
+                }
+                (MachineSaveDataV2::Hopper(_), MachineSaveDataV2::Hopper(b)) => {
+                    assert_eq!(b.slots.len(), 5);
+                    assert_eq!(b.push_side, Some(DirectionSave::West));
+                }
+                (MachineSaveDataV2::DataMachine(_), MachineSaveDataV2::DataMachine(b)) => {
+                    assert_eq!(b.machine_id, "base:grinder");
+                    assert_eq!(b.inputs[0].as_ref().map(|s| s.count), Some(4));
+                }
                 _ => panic!("Machine type mismatch after roundtrip"),
             }
         }
@@ -239,14 +441,17 @@ mod tests {
                     pitch: 0.0,
                     yaw: 0.0,
                 },
+                spawn_point: None,
             },
             inventory: InventorySaveDataV2 {
                 selected_slot: 0,
                 slots: vec![],
+                recent_placeables: vec![],
             },
             platform_inventory: PlatformInventorySaveDataV2::default(),
             world: WorldSaveDataV2 {
                 modified_blocks: HashMap::new(),
+                chunks: Vec::new(),
             },
             machines: vec![],
             quests: QuestSaveDataV2 {
@@ -254,8 +459,25 @@ mod tests {
                 completed: false,
                 rewards_claimed: false,
                 delivered: HashMap::new(),
+                claimed_at: HashMap::new(),
+            },
+            mode: GameModeSaveData {
+                creative: false,
+                spawn_protection: true,
             },
-            mode: GameModeSaveData { creative: false },
+            clock: ClockSaveDataV2::default(),
+            fluids: Vec::new(),
+            delivery_contracts: Vec::new(),
+            delivery_filters: Vec::new(),
+            display_panels: Vec::new(),
+            signs: Vec::new(),
+            worldgen_hash: None,
+            content_version: None,
+            mod_content: Default::default(),
+            stats: StatsSaveDataV2::default(),
+            timed_contracts: TimedContractsSaveDataV2::default(),
+            waypoints: Vec::new(),
+            world_info: WorldInfoSaveDataV2::default(),
         };
 
         let json = serde_json::to_string(&data).expect("serialization should succeed");
@@ -282,7 +504,9 @@ mod tests {
                 shape,
                 items: vec![],
                 last_output_index: 0,
-                last_input_source: 0,
+                last_input_pos: None,
+                enabled: true,
+                speed_multiplier: 1.0,
             };
 
             let json = serde_json::to_string(&conveyor).expect("serialization should succeed");
@@ -307,7 +531,9 @@ mod tests {
                 shape: ConveyorShapeSave::Straight,
                 items: vec![],
                 last_output_index: 0,
-                last_input_source: 0,
+                last_input_pos: None,
+                enabled: true,
+                speed_multiplier: 1.0,
             };
 
             let json = serde_json::to_string(&conveyor).expect("serialization should succeed");
@@ -318,6 +544,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_machine_facing_defaults_to_north() {
+        // Saves from before machine facing was stored
+        let json = r#"{"type":"Crusher","position":{"x":1,"y":2,"z":3},"input":null,"output":null,"progress":0.0}"#;
+        match serde_json::from_str::<MachineSaveDataV2>(json).expect("legacy crusher") {
+            MachineSaveDataV2::Crusher(c) => assert_eq!(c.facing, DirectionSave::North),
+            _ => panic!("Expected Crusher"),
+        }
+    }
+
+    #[test]
+    fn test_machine_enabled_defaults_to_true() {
+        // Saves from before machines could be disabled
+        let json =
+            r#"{"type":"Miner","position":{"x":0,"y":0,"z":0},"progress":0.0,"buffer":null}"#;
+        match serde_json::from_str::<MachineSaveDataV2>(json).expect("legacy miner") {
+            MachineSaveDataV2::Miner(m) => {
+                assert!(m.enabled);
+                assert_eq!(m.facing, DirectionSave::North);
+                assert!(!m.output_any_side);
+                // Miners from before drill heads keep mining everything
+                assert_eq!(
+                    m.drill.map(|d| d.item_id),
+                    Some("base:steel_drill".to_string())
+                );
+            }
+            _ => panic!("Expected Miner"),
+        }
+        let json = r#"{"type":"Conveyor","position":{"x":0,"y":0,"z":0},"direction":"North","shape":"Straight","items":[],"last_output_index":0}"#;
+        match serde_json::from_str::<MachineSaveDataV2>(json).expect("legacy conveyor") {
+            MachineSaveDataV2::Conveyor(c) => {
+                assert!(c.enabled);
+                assert_eq!(c.speed_multiplier, 1.0);
+            }
+            _ => panic!("Expected Conveyor"),
+        }
+    }
+
+    #[test]
+    fn test_player_spawn_point_defaults_to_none() {
+        // Saves from before /setspawn existed
+        let json = r#"{"position":{"x":1.0,"y":2.0,"z":3.0},"rotation":{"pitch":0.0,"yaw":0.0}}"#;
+        let player: PlayerSaveData = serde_json::from_str(json).expect("legacy player");
+        assert!(player.spawn_point.is_none());
+    }
+
+    #[test]
+    fn test_spawn_protection_defaults_to_on() {
+        // Saves from before /protection existed
+        let mode: GameModeSaveData =
+            serde_json::from_str(r#"{"creative":false}"#).expect("legacy mode");
+        assert!(mode.spawn_protection);
+    }
+
+    #[test]
+    fn test_conveyor_item_count_defaults_to_one() {
+        // Saves from before belt stacking
+        let json = r#"{"item_id":"base:coal","progress":0.5,"lateral_offset":0.0}"#;
+        let item: ConveyorItemSaveV2 = serde_json::from_str(json).expect("legacy conveyor item");
+        assert_eq!(item.count, 1);
+    }
+
+    #[test]
+    fn test_stats_partial_fields_default() {
+        // Saves written before a counter existed
+        let json = r#"{"blocks_mined":12}"#;
+        let stats: StatsSaveDataV2 = serde_json::from_str(json).expect("partial stats");
+        assert_eq!(stats.blocks_mined, 12);
+        assert_eq!(stats.conveyors_placed, 0);
+        assert!(stats.unlocked.is_empty());
+    }
+
     #[test]
     fn test_item_stack_v2_json_format() {
         // Verify the JSON format is what we expect
@@ -356,6 +654,11 @@ mod tests {
                     pitch: -0.5,
                     yaw: 3.14,
                 },
+                spawn_point: Some(Vec3Save {
+                    x: -4.0,
+                    y: 30.0,
+                    z: 12.5,
+                }),
             },
             inventory: InventorySaveDataV2 {
                 selected_slot: 3,
@@ -365,16 +668,30 @@ mod tests {
                     None,
                     Some(ItemStackV2::new("base:miner_block", 5)),
                 ],
+                recent_placeables: vec![
+                    "base:miner_block".to_string(),
+                    "base:conveyor_block".to_string(),
+                ],
             },
             platform_inventory: PlatformInventorySaveDataV2 {
                 items: global_items,
             },
-            world: WorldSaveDataV2 { modified_blocks },
+            world: WorldSaveDataV2 {
+                modified_blocks,
+                chunks: Vec::new(),
+            },
             machines: vec![
                 MachineSaveDataV2::Miner(MinerSaveDataV2 {
                     position: IVec3Save { x: 10, y: 5, z: 10 },
                     progress: 0.5,
                     buffer: Some(ItemStackV2::new("base:iron_ore", 1)),
+                    facing: DirectionSave::East,
+                    enabled: true,
+                    output_any_side: true,
+                    drill: Some(DrillSaveDataV2 {
+                        item_id: "base:iron_drill".to_string(),
+                        durability: 120,
+                    }),
                 }),
                 MachineSaveDataV2::Conveyor(ConveyorSaveDataV2 {
                     position: IVec3Save { x: 11, y: 5, z: 10 },
@@ -384,9 +701,12 @@ mod tests {
                         item_id: "base:iron_ore".to_string(),
                         progress: 0.3,
                         lateral_offset: 0.0,
+                        count: 3,
                     }],
                     last_output_index: 0,
-                    last_input_source: 0,
+                    last_input_pos: None,
+                    enabled: true,
+                    speed_multiplier: 4.0,
                 }),
                 MachineSaveDataV2::Furnace(FurnaceSaveDataV2 {
                     position: IVec3Save { x: 12, y: 5, z: 10 },
@@ -394,12 +714,24 @@ mod tests {
                     input: Some(ItemStackV2::new("base:iron_ore", 5)),
                     output: Some(ItemStackV2::new("base:iron_ingot", 3)),
                     progress: 0.75,
+                    facing: DirectionSave::East,
+                    enabled: false,
+                    output_any_side: false,
+                    pass_through: vec![DirectionSave::West],
+                    slot_routes: vec![FaceRouteSaveV2 {
+                        face: DirectionSave::North,
+                        slot: SlotRouteSave::Fuel,
+                    }],
                 }),
                 MachineSaveDataV2::Crusher(CrusherSaveDataV2 {
                     position: IVec3Save { x: 13, y: 5, z: 10 },
                     input: Some(ItemStackV2::new("base:copper_ore", 10)),
                     output: Some(ItemStackV2::new("base:copper_dust", 6)),
                     progress: 0.25,
+                    facing: DirectionSave::North,
+                    enabled: true,
+                    output_any_side: false,
+                    pass_through: Vec::new(),
                 }),
             ],
             quests: QuestSaveDataV2 {
@@ -407,8 +739,90 @@ mod tests {
                 completed: false,
                 rewards_claimed: false,
                 delivered,
+                claimed_at: HashMap::from([("main_1".to_string(), 1_700_000_000_000)]),
+            },
+            mode: GameModeSaveData {
+                creative: true,
+                spawn_protection: false,
+            },
+            clock: ClockSaveDataV2 {
+                time_of_day: 0.75,
+                day: 3,
+            },
+            fluids: vec![FluidNetworkSaveDataV2 {
+                position: IVec3Save { x: 1, y: 8, z: 2 },
+                fluid: "base:water".to_string(),
+                amount: 250.0,
+            }],
+            delivery_contracts: vec![DeliveryContractSaveDataV2 {
+                origin: IVec3Save { x: 20, y: 8, z: 0 },
+                item: "base:iron_ingot".to_string(),
+                target_per_min: 60,
+            }],
+            delivery_filters: vec![DeliveryFilterSaveDataV2 {
+                origin: IVec3Save { x: 20, y: 8, z: 0 },
+                policy: "whitelist".to_string(),
+                whitelist: vec!["base:iron_ingot".to_string()],
+                buffer: vec![("base:stone".to_string(), 3)],
+            }],
+            display_panels: vec![
+                DisplayPanelSaveDataV2 {
+                    position: IVec3Save { x: 3, y: 9, z: 4 },
+                    face: DirectionSave::South,
+                    item: Some("base:iron_ingot".to_string()),
+                    source: "produced".to_string(),
+                },
+                DisplayPanelSaveDataV2 {
+                    position: IVec3Save { x: 4, y: 9, z: 4 },
+                    face: DirectionSave::West,
+                    item: None,
+                    source: "delivered".to_string(),
+                },
+            ],
+            signs: vec![SignSaveDataV2 {
+                position: IVec3Save { x: 5, y: 9, z: 4 },
+                face: DirectionSave::East,
+                text: "smelting row\n銅ライン".to_string(),
+            }],
+            worldgen_hash: Some(0xdead_beef_cafe_f00d),
+            content_version: Some("1.2.0".to_string()),
+            mod_content: [("gears".to_string(), 0x1234)].into(),
+            stats: StatsSaveDataV2 {
+                blocks_mined: 120,
+                conveyors_placed: 7,
+                distance_walked: 345.5,
+                unlocked: vec!["first_machine".to_string(), "miner".to_string()],
+                ..Default::default()
+            },
+            timed_contracts: TimedContractsSaveDataV2 {
+                active: Some(TimedContractSaveDataV2 {
+                    item: "base:copper_ingot".to_string(),
+                    amount: 40,
+                    delivered: 12,
+                    duration_ticks: 12_000,
+                    elapsed_ticks: 3_456,
+                    reward: ItemStackV2::new("base:miner_block", 2),
+                }),
+                streak: 2,
+                completed: 5,
+                expired: 1,
+                ..Default::default()
+            },
+            waypoints: vec![WaypointSaveDataV2 {
+                name: "copper".to_string(),
+                position: Vec3Save {
+                    x: -140.5,
+                    y: 6.0,
+                    z: 33.0,
+                },
+                color: [1.0, 0.55, 0.2],
+            }],
+            world_info: WorldInfoSaveDataV2 {
+                name: "Copper Valley".to_string(),
+                seed: Some(1234),
+                preset: "hills".to_string(),
+                playtime_secs: 5400.0,
             },
-            mode: GameModeSaveData { creative: true },
         };
 
         // Serialize and deserialize
@@ -423,10 +837,17 @@ mod tests {
         // Player
         assert!((restored.player.position.x - 100.0).abs() < 0.001);
         assert!((restored.player.rotation.yaw - 3.14).abs() < 0.001);
+        let spawn = restored.player.spawn_point.expect("spawn point persisted");
+        assert!((spawn.y - 30.0).abs() < 0.001);
+        assert!((spawn.z - 12.5).abs() < 0.001);
 
         // Inventory
         assert_eq!(restored.inventory.selected_slot, 3);
         assert_eq!(restored.inventory.slots.len(), 4);
+        assert_eq!(
+            restored.inventory.recent_placeables,
+            vec!["base:miner_block", "base:conveyor_block"]
+        );
         assert_eq!(
             restored.inventory.slots[0]
                 .as_ref()
@@ -447,18 +868,34 @@ mod tests {
         // Machines
         assert_eq!(restored.machines.len(), 4);
         match &restored.machines[0] {
-            MachineSaveDataV2::Miner(m) => assert!((m.progress - 0.5).abs() < 0.001),
+            MachineSaveDataV2::Miner(m) => {
+                assert!((m.progress - 0.5).abs() < 0.001);
+                assert_eq!(m.facing, DirectionSave::East);
+                assert!(m.output_any_side);
+                assert_eq!(
+                    m.drill.as_ref().map(|d| (d.item_id.as_str(), d.durability)),
+                    Some(("base:iron_drill", 120))
+                );
+            }
             _ => panic!("Expected Miner"),
         }
         match &restored.machines[1] {
             MachineSaveDataV2::Conveyor(c) => {
                 assert_eq!(c.direction, DirectionSave::East);
                 assert_eq!(c.items.len(), 1);
+                assert_eq!(c.items[0].count, 3);
+                assert_eq!(c.speed_multiplier, 4.0);
             }
             _ => panic!("Expected Conveyor"),
         }
         match &restored.machines[2] {
-            MachineSaveDataV2::Furnace(f) => assert_eq!(f.fuel, 10),
+            MachineSaveDataV2::Furnace(f) => {
+                assert_eq!(f.fuel, 10);
+                assert_eq!(f.facing, DirectionSave::East);
+                assert!(!f.enabled);
+                assert_eq!(f.pass_through, vec![DirectionSave::West]);
+                assert_eq!(f.slot_routes[0].slot, SlotRouteSave::Fuel);
+            }
             _ => panic!("Expected Furnace"),
         }
         match &restored.machines[3] {
@@ -472,8 +909,173 @@ mod tests {
         // Quests
         assert_eq!(restored.quests.current_index, 2);
         assert_eq!(restored.quests.delivered.get("base:iron_ingot"), Some(&5));
+        assert_eq!(
+            restored.quests.claimed_at.get("main_1"),
+            Some(&1_700_000_000_000)
+        );
 
         // Mode
         assert!(restored.mode.creative);
+        assert!(!restored.mode.spawn_protection);
+
+        // Fluids
+        assert_eq!(restored.fluids, data.fluids);
+
+        // Delivery contracts
+        assert_eq!(restored.delivery_contracts, data.delivery_contracts);
+        assert_eq!(restored.delivery_filters, data.delivery_filters);
+
+        // Display panels and signs
+        assert_eq!(restored.display_panels, data.display_panels);
+        assert_eq!(restored.signs, data.signs);
+
+        // Worldgen
+        assert_eq!(restored.worldgen_hash, data.worldgen_hash);
+        assert_eq!(restored.content_version, data.content_version);
+        assert_eq!(restored.mod_content, data.mod_content);
+        assert!(restored.mod_content_warnings(&data.mod_content).is_empty());
+        // A missing or changed mod is reported when the save is loaded
+        assert_eq!(restored.mod_content_warnings(&Default::default()).len(), 1);
+        let changed = [("gears".to_string(), 0x5678)].into();
+        assert_eq!(restored.mod_content_warnings(&changed).len(), 1);
+
+        // Stats
+        assert_eq!(restored.stats, data.stats);
+
+        // Timed contracts
+        assert_eq!(restored.timed_contracts, data.timed_contracts);
+
+        // Waypoints
+        assert_eq!(restored.waypoints, data.waypoints);
+
+        // World info
+        assert_eq!(restored.world_info, data.world_info);
+
+        // Clock
+        assert_eq!(
+            restored.clock,
+            ClockSaveDataV2 {
+                time_of_day: 0.75,
+                day: 3
+            }
+        );
+    }
+    #[test]
+    fn test_background_save_matches_sync_save() {
+        use crate::save::spawn_background_save;
+        use bevy::tasks::{AsyncComputeTaskPool, TaskPool};
+
+        let mut modified_blocks = HashMap::new();
+        modified_blocks.insert("1,8,2".to_string(), Some("base:stone".to_string()));
+        modified_blocks.insert("3,8,4".to_string(), None);
+        let data = SaveDataV2 {
+            version: SAVE_VERSION.to_string(),
+            timestamp: 1704067200000,
+            player: PlayerSaveData {
+                position: Vec3Save {
+                    x: 4.5,
+                    y: 12.0,
+                    z: -3.25,
+                },
+                rotation: CameraRotation {
+                    pitch: 0.3,
+                    yaw: 1.2,
+                },
+                spawn_point: None,
+            },
+            inventory: InventorySaveDataV2 {
+                selected_slot: 2,
+                slots: vec![Some(ItemStackV2::new("base:iron_ore", 64)), None],
+                recent_placeables: vec!["base:conveyor_block".to_string()],
+            },
+            platform_inventory: PlatformInventorySaveDataV2::default(),
+            world: WorldSaveDataV2 {
+                modified_blocks,
+                chunks: Vec::new(),
+            },
+            machines: vec![MachineSaveDataV2::Miner(MinerSaveDataV2 {
+                position: IVec3Save { x: 1, y: 8, z: 1 },
+                progress: 0.25,
+                buffer: None,
+                facing: DirectionSave::South,
+                enabled: true,
+                output_any_side: false,
+                drill: None,
+            })],
+            quests: QuestSaveDataV2 {
+                current_index: 1,
+                completed: false,
+                rewards_claimed: false,
+                delivered: HashMap::new(),
+                claimed_at: HashMap::new(),
+            },
+            mode: GameModeSaveData {
+                creative: false,
+                spawn_protection: true,
+            },
+            clock: ClockSaveDataV2::default(),
+            fluids: Vec::new(),
+            delivery_contracts: Vec::new(),
+            delivery_filters: Vec::new(),
+            display_panels: Vec::new(),
+            signs: Vec::new(),
+            worldgen_hash: Some(42),
+            content_version: None,
+            mod_content: Default::default(),
+            stats: StatsSaveDataV2::default(),
+            timed_contracts: TimedContractsSaveDataV2::default(),
+            waypoints: Vec::new(),
+            world_info: WorldInfoSaveDataV2::default(),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+
+        // The snapshot handed to the background task writes the same file
+        // the synchronous path would
+        let sync_json = native::serialize_save_v2(&data).unwrap();
+        native::write_save_in(dir.path(), "sync", &sync_json).unwrap();
+        let task = spawn_background_save(data.clone(), dir.path().to_path_buf(), "bg".into());
+        futures_lite::future::block_on(task).unwrap();
+
+        let read = |name: &str| std::fs::read(dir.path().join(name)).unwrap();
+        assert_eq!(read("bg.json"), read("sync.json"));
+        assert!(!dir.path().join("bg.json.tmp").exists());
+
+        let json = native::decompress_save(&read("bg.json")).unwrap();
+        let restored: SaveDataV2 = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.world.modified_blocks, data.world.modified_blocks);
+        assert_eq!(restored.machines.len(), 1);
+    }
+
+    #[test]
+    fn test_list_worlds_reads_save_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, json: &str| std::fs::write(dir.path().join(name), json).unwrap();
+        write(
+            "world_1.json",
+            r#"{"timestamp": 1000, "mode": {"creative": true},
+                "stats": {"blocks_mined": 12, "quests_completed": 2},
+                "world_info": {"name": "Copper Valley", "seed": 7, "preset": "hills", "playtime_secs": 90.0}}"#,
+        );
+        // Saves from before world_info fall back to the filename
+        write("autosave.json", r#"{"timestamp": 2000}"#);
+        write("settings.json", r#"{"volume": 0.5}"#);
+        write("notes.txt", "not a save");
+
+        let worlds = native::list_worlds_in(dir.path());
+        let names: Vec<_> = worlds.iter().map(|w| w.display_name()).collect();
+        assert_eq!(names, ["autosave", "Copper Valley"]);
+
+        let valley = &worlds[1];
+        assert_eq!(valley.filename, "world_1");
+        assert!(valley.creative);
+        assert_eq!(valley.info.seed, Some(7));
+        assert_eq!(valley.info.preset, "hills");
+        assert_eq!(valley.stats.blocks_mined, 12);
+        assert_eq!(valley.stats.quests_completed, 2);
+        assert!(!worlds[0].creative);
+
+        assert!(native::list_worlds_in(&dir.path().join("missing")).is_empty());
     }
 }
@@ -1,9 +1,16 @@
 //! Native file I/O functions for save/load operations
 
-use super::timer::SaveSlotInfo;
-use super::v2::SaveDataV2;
+use super::common::GameModeSaveData;
+use super::timer::{SaveSlotInfo, WorldSlotInfo};
+use super::v2::{SaveDataV2, StatsSaveDataV2, WorldInfoSaveDataV2};
 use super::SAVE_DIR;
+use serde::Deserialize;
 use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// First byte of a compressed save file (0xFF never starts UTF-8, so JSON saves can't match)
+pub const COMPRESSED_SAVE_MAGIC: u8 = 0xFF;
 
 /// Get the saves directory path
 pub fn get_save_dir() -> std::path::PathBuf {
@@ -21,13 +28,62 @@ pub fn ensure_save_dir() -> std::io::Result<()> {
 
 /// Save game data in V2 format
 pub fn save_game_v2(data: &SaveDataV2, filename: &str) -> Result<(), String> {
-    ensure_save_dir().map_err(|e| format!("Failed to create save directory: {}", e))?;
+    let json = serialize_save_v2(data)?;
+    write_save_in(&get_save_dir(), filename, &json)
+}
 
-    let path = get_save_dir().join(format!("{}.json", filename));
-    let json = serde_json::to_string_pretty(data)
-        .map_err(|e| format!("Failed to serialize save data: {}", e))?;
+/// Serialize save data to JSON (`write_save_in` compresses it for the file)
+pub fn serialize_save_v2(data: &SaveDataV2) -> Result<String, String> {
+    serde_json::to_string_pretty(data).map_err(|e| format!("Failed to serialize save data: {}", e))
+}
+
+/// Deflate save JSON behind `COMPRESSED_SAVE_MAGIC`
+pub fn compress_save(json: &str) -> Result<Vec<u8>, String> {
+    let mut encoder = flate2::write::DeflateEncoder::new(
+        vec![COMPRESSED_SAVE_MAGIC],
+        flate2::Compression::default(),
+    );
+    encoder
+        .write_all(json.as_bytes())
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("Failed to compress save data: {}", e))
+}
+
+/// Save JSON from the bytes of a save file, compressed or not
+///
+/// Saves written before compression are plain JSON and read as they are.
+pub fn decompress_save(bytes: &[u8]) -> Result<String, String> {
+    match bytes.split_first() {
+        Some((&COMPRESSED_SAVE_MAGIC, compressed)) => {
+            let mut json = String::new();
+            flate2::read::DeflateDecoder::new(compressed)
+                .read_to_string(&mut json)
+                .map_err(|e| format!("Failed to decompress save data: {}", e))?;
+            Ok(json)
+        }
+        _ => String::from_utf8(bytes.to_vec())
+            .map_err(|e| format!("Failed to read save file: {}", e)),
+    }
+}
+
+/// Read the save JSON of a file
+fn read_save_json(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read save file: {}", e))?;
+    decompress_save(&bytes)
+}
+
+/// Write a serialized save, compressed, to `dir/<filename>.json`
+///
+/// Goes through a temp file and a rename, so a crash mid-write never leaves
+/// a truncated save behind.
+pub fn write_save_in(dir: &Path, filename: &str, json: &str) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create save directory: {}", e))?;
 
-    fs::write(&path, json).map_err(|e| format!("Failed to write save file: {}", e))?;
+    let path = dir.join(format!("{}.json", filename));
+    let tmp_path = dir.join(format!("{}.json.tmp", filename));
+    let bytes = compress_save(json)?;
+    fs::write(&tmp_path, bytes).map_err(|e| format!("Failed to write save file: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to write save file: {}", e))?;
 
     Ok(())
 }
@@ -40,7 +96,7 @@ pub fn load_game_v2(filename: &str) -> Result<SaveDataV2, String> {
         return Err(format!("Save file not found: {}", filename));
     }
 
-    let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read save file: {}", e))?;
+    let json = read_save_json(&path)?;
 
     serde_json::from_str(&json).map_err(|e| format!("Failed to parse save data: {}", e))
 }
@@ -62,7 +118,7 @@ pub fn list_saves() -> Result<Vec<SaveSlotInfo>, String> {
         if path.extension().is_some_and(|e| e == "json") {
             if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
                 // Try to read timestamp from file
-                if let Ok(json) = fs::read_to_string(&path) {
+                if let Ok(json) = read_save_json(&path) {
                     if let Ok(data) = serde_json::from_str::<SaveDataV2>(&json) {
                         saves.push(SaveSlotInfo {
                             filename: stem.to_string(),
@@ -80,8 +136,58 @@ pub fn list_saves() -> Result<Vec<SaveSlotInfo>, String> {
     Ok(saves)
 }
 
+/// The fields of a save the world list needs (the rest of the file is skipped)
+#[derive(Deserialize)]
+struct SaveHeader {
+    timestamp: u64,
+    #[serde(default)]
+    mode: Option<GameModeSaveData>,
+    #[serde(default)]
+    stats: StatsSaveDataV2,
+    #[serde(default)]
+    world_info: WorldInfoSaveDataV2,
+}
+
+/// List the worlds in the saves directory, most recently played first
+pub fn list_worlds() -> Vec<WorldSlotInfo> {
+    list_worlds_in(&get_save_dir())
+}
+
+/// List the worlds saved in `dir`, most recently played first
+///
+/// Files that aren't saves (or can't be read) are skipped.
+pub fn list_worlds_in(dir: &Path) -> Vec<WorldSlotInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut worlds: Vec<WorldSlotInfo> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            world_slot(stem, &read_save_json(&path).ok()?)
+        })
+        .collect();
+
+    worlds.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    worlds
+}
+
+/// World list entry from the header of a save's JSON (None if it isn't a save)
+pub fn world_slot(filename: String, json: &str) -> Option<WorldSlotInfo> {
+    let header: SaveHeader = serde_json::from_str(json).ok()?;
+    Some(WorldSlotInfo {
+        filename,
+        timestamp: header.timestamp,
+        info: header.world_info,
+        creative: header.mode.is_some_and(|m| m.creative),
+        stats: header.stats,
+    })
+}
+
 /// Delete a save file
-#[allow(dead_code)]
 pub fn delete_save(filename: &str) -> Result<(), String> {
     let path = get_save_dir().join(format!("{}.json", filename));
 
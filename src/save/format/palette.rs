@@ -0,0 +1,167 @@
+//! Palette encoding for per-chunk block data
+//!
+//! A chunk's cells are stored as a palette of the distinct values plus one
+//! index per cell. Index 0 marks an empty cell and `i + 1` palette entry `i`,
+//! so indices fit one byte while the palette has at most 255 entries and
+//! take two (little-endian) beyond that.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Largest palette whose indices fit one byte (index 0 is the empty cell)
+pub const MAX_U8_PALETTE: usize = u8::MAX as usize;
+
+/// Bytes per cell index for a palette of `palette_len` entries
+pub fn index_width(palette_len: usize) -> usize {
+    if palette_len <= MAX_U8_PALETTE {
+        1
+    } else {
+        2
+    }
+}
+
+/// Split cells into a palette (in order of first appearance) and packed indices
+pub fn encode_palette<T: Clone + Eq + Hash>(cells: &[Option<T>]) -> (Vec<T>, Vec<u8>) {
+    let mut palette: Vec<T> = Vec::new();
+    let mut lookup: HashMap<&T, usize> = HashMap::new();
+    let indices: Vec<usize> = cells
+        .iter()
+        .map(|cell| match cell {
+            None => 0,
+            Some(value) => {
+                1 + *lookup.entry(value).or_insert_with(|| {
+                    palette.push(value.clone());
+                    palette.len() - 1
+                })
+            }
+        })
+        .collect();
+
+    let bytes = if index_width(palette.len()) == 1 {
+        indices.iter().map(|&i| i as u8).collect()
+    } else {
+        indices
+            .iter()
+            .flat_map(|&i| (i as u16).to_le_bytes())
+            .collect()
+    };
+    (palette, bytes)
+}
+
+/// Rebuild the cells from a palette and the indices `encode_palette` made
+pub fn decode_palette<T: Clone>(palette: &[T], bytes: &[u8]) -> Result<Vec<Option<T>>, String> {
+    let indices: Vec<usize> = if index_width(palette.len()) == 1 {
+        bytes.iter().map(|&b| b as usize).collect()
+    } else {
+        if bytes.len() % 2 != 0 {
+            return Err(format!(
+                "Odd index data length {} for u16 indices",
+                bytes.len()
+            ));
+        }
+        bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]) as usize)
+            .collect()
+    };
+
+    indices
+        .into_iter()
+        .map(|index| match index {
+            0 => Ok(None),
+            i => palette.get(i - 1).cloned().map(Some).ok_or_else(|| {
+                format!(
+                    "Palette index {} out of range ({} entries)",
+                    i,
+                    palette.len()
+                )
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::ChunkData;
+
+    /// Deterministic pseudo-random cells with up to `kinds` distinct values
+    fn random_cells(seed: u64, kinds: u64, empty_every: u64) -> Vec<Option<u64>> {
+        let mut state = seed;
+        (0..ChunkData::ARRAY_SIZE)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let roll = state >> 33;
+                (roll % empty_every != 0).then_some(roll % kinds)
+            })
+            .collect()
+    }
+
+    fn round_trip<T: Clone + Eq + Hash + std::fmt::Debug>(cells: &[Option<T>]) -> Vec<T> {
+        let (palette, bytes) = encode_palette(cells);
+        assert_eq!(bytes.len(), cells.len() * index_width(palette.len()));
+        assert_eq!(decode_palette(&palette, &bytes).unwrap(), cells);
+        palette
+    }
+
+    #[test]
+    fn test_random_chunks_round_trip() {
+        for seed in 0..20 {
+            let palette = round_trip(&random_cells(seed, 1 + seed * 7, 3 + seed % 5));
+            assert!(palette.len() <= MAX_U8_PALETTE);
+        }
+    }
+
+    #[test]
+    fn test_empty_chunk() {
+        let cells: Vec<Option<String>> = vec![None; ChunkData::ARRAY_SIZE];
+        let (palette, bytes) = encode_palette(&cells);
+        assert!(palette.is_empty());
+        assert!(bytes.iter().all(|&b| b == 0));
+        assert_eq!(decode_palette(&palette, &bytes).unwrap(), cells);
+
+        // No cells at all
+        let (palette, bytes) = encode_palette::<u8>(&[]);
+        assert!(palette.is_empty() && bytes.is_empty());
+    }
+
+    #[test]
+    fn test_single_type_chunk() {
+        let cells = vec![Some("base:stone".to_string()); ChunkData::ARRAY_SIZE];
+        let palette = round_trip(&cells);
+        assert_eq!(palette, ["base:stone"]);
+
+        // Removed blocks (None in the save) are a palette value like any other
+        let removed: Vec<Option<Option<String>>> = vec![Some(None); 10];
+        assert_eq!(round_trip(&removed), vec![None::<String>]);
+    }
+
+    #[test]
+    fn test_large_palette_falls_back_to_u16() {
+        // Exactly 255 kinds still fit a byte
+        let cells: Vec<Option<u32>> = (0..MAX_U8_PALETTE as u32).map(Some).collect();
+        assert_eq!(round_trip(&cells).len(), MAX_U8_PALETTE);
+        assert_eq!(index_width(MAX_U8_PALETTE), 1);
+
+        // Beyond that every index takes two bytes
+        let cells: Vec<Option<u32>> = (0..ChunkData::ARRAY_SIZE as u32)
+            .map(|i| (i % 7 != 0).then_some(i % 300))
+            .collect();
+        let palette = round_trip(&cells);
+        assert_eq!(palette.len(), 300);
+        assert_eq!(index_width(palette.len()), 2);
+    }
+
+    #[test]
+    fn test_corrupt_indices_are_rejected() {
+        let palette = ["a", "b"];
+        assert!(decode_palette(&palette, &[0, 1, 2]).is_ok());
+        assert!(decode_palette(&palette, &[3]).is_err());
+
+        let wide: Vec<u32> = (0..300).collect();
+        assert!(decode_palette(&wide, &[1, 0, 2]).is_err());
+        assert!(decode_palette(&wide, &301u16.to_le_bytes()).is_err());
+    }
+}
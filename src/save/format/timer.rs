@@ -1,9 +1,12 @@
 //! Auto-save timer and save slot utilities
 
+use super::v2::{StatsSaveDataV2, WorldInfoSaveDataV2};
 use super::AUTO_SAVE_INTERVAL;
 use bevy::prelude::*;
 
 /// Auto-save timer resource
+///
+/// A paused timer means autosave is off (`autosave_interval_secs = 0`).
 #[derive(Resource)]
 pub struct AutoSaveTimer {
     pub timer: Timer,
@@ -17,6 +20,28 @@ impl Default for AutoSaveTimer {
     }
 }
 
+impl AutoSaveTimer {
+    /// Apply an interval in seconds; 0 (or less) turns autosave off
+    ///
+    /// The current countdown keeps its progress when only the length changes.
+    pub fn set_interval(&mut self, secs: f32) {
+        if secs <= 0.0 {
+            self.timer.pause();
+            return;
+        }
+        let interval = std::time::Duration::from_secs_f32(secs);
+        if self.timer.duration() != interval {
+            self.timer.set_duration(interval);
+        }
+        self.timer.unpause();
+    }
+
+    /// Whether autosaves are happening at all
+    pub fn is_enabled(&self) -> bool {
+        !self.timer.is_paused()
+    }
+}
+
 /// Save slot info for listing saves
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -24,3 +49,25 @@ pub struct SaveSlotInfo {
     pub filename: String,
     pub timestamp: u64,
 }
+
+/// World slot shown in the main menu, read from a save's header fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldSlotInfo {
+    pub filename: String,
+    /// Unix milliseconds of the last save
+    pub timestamp: u64,
+    pub info: WorldInfoSaveDataV2,
+    pub creative: bool,
+    pub stats: StatsSaveDataV2,
+}
+
+impl WorldSlotInfo {
+    /// World name, or the file name for saves from before worlds had names
+    pub fn display_name(&self) -> &str {
+        if self.info.name.is_empty() {
+            &self.filename
+        } else {
+            &self.info.name
+        }
+    }
+}
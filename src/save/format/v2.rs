@@ -1,11 +1,14 @@
 //! V2 Save Data Structures (String ID based)
 
 use super::common::{
-    ConveyorShapeSave, DirectionSave, GameModeSaveData, IVec3Save, PlayerSaveData,
+    ConveyorShapeSave, DirectionSave, GameModeSaveData, IVec3Save, PlayerSaveData, Vec3Save,
 };
-use bevy::prelude::IVec3;
+use super::palette;
+use crate::world::{ChunkData, WorldData};
+use base64::Engine;
+use bevy::prelude::{IVec2, IVec3};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Item stack using string IDs
 /// This allows for mod items and future extensibility
@@ -32,6 +35,9 @@ impl ItemStackV2 {
 pub struct InventorySaveDataV2 {
     pub selected_slot: usize,
     pub slots: Vec<Option<ItemStackV2>>,
+    /// Recently placed item types for the quick-select ring, newest first
+    #[serde(default)]
+    pub recent_placeables: Vec<String>,
 }
 
 /// Platform inventory save data using string IDs
@@ -41,14 +47,243 @@ pub struct PlatformInventorySaveDataV2 {
     pub items: HashMap<String, u32>,
 }
 
+/// Day/night clock save data
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClockSaveDataV2 {
+    /// Fraction of the day (0.0 = midnight, 0.5 = noon)
+    pub time_of_day: f32,
+    /// Number of completed days
+    pub day: u32,
+}
+
+impl Default for ClockSaveDataV2 {
+    /// Saves without a clock start at 08:00 on the first day
+    fn default() -> Self {
+        Self {
+            time_of_day: 8.0 / 24.0,
+            day: 0,
+        }
+    }
+}
+
+/// Lifetime player stats and unlocked achievements
+///
+/// Every field defaults so saves from before stats tracking load as all-zero
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct StatsSaveDataV2 {
+    pub blocks_mined: u64,
+    pub blocks_placed: u64,
+    pub machines_placed: u64,
+    pub conveyors_placed: u64,
+    pub items_smelted: u64,
+    pub items_delivered: u64,
+    pub ingots_delivered: u64,
+    pub quests_completed: u64,
+    pub distance_walked: f64,
+    /// Unlocked achievement IDs
+    pub unlocked: Vec<String>,
+}
+
+/// Fluid network save data (pipes/tanks themselves are saved as world blocks)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FluidNetworkSaveDataV2 {
+    /// Any block position in the network
+    pub position: IVec3Save,
+    /// Fluid string ID ("namespace:id")
+    pub fluid: String,
+    pub amount: f32,
+}
+
+/// Contract on a player-built delivery platform (the pads are saved as world blocks)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeliveryContractSaveDataV2 {
+    /// Platform origin (min x/z corner)
+    pub origin: IVec3Save,
+    /// Contracted item string ID ("namespace:id")
+    pub item: String,
+    /// Target delivery rate (items/minute)
+    pub target_per_min: u32,
+}
+
+/// Acceptance policy and reject buffer of a delivery platform (starter or pad)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeliveryFilterSaveDataV2 {
+    /// Platform origin (min x/z corner)
+    pub origin: IVec3Save,
+    /// Policy ID ("accept_all", "quest_items" or "whitelist")
+    pub policy: String,
+    /// Whitelisted item string IDs ("namespace:id")
+    #[serde(default)]
+    pub whitelist: Vec<String>,
+    /// Rejected stacks waiting to drain: item string ID, count
+    #[serde(default)]
+    pub buffer: Vec<(String, u32)>,
+}
+
+/// Active timed contract (deadline in simulation ticks, not clock time)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimedContractSaveDataV2 {
+    /// Contracted item string ID ("namespace:id")
+    pub item: String,
+    pub amount: u32,
+    pub delivered: u32,
+    pub duration_ticks: u64,
+    pub elapsed_ticks: u64,
+    /// Reward before the early-completion multiplier
+    pub reward: ItemStackV2,
+}
+
+/// Timed contract state; defaults so older saves start without one
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct TimedContractsSaveDataV2 {
+    pub active: Option<TimedContractSaveDataV2>,
+    /// Ticks until the next contract is offered
+    pub cooldown_ticks: u64,
+    pub streak: u32,
+    pub completed: u32,
+    pub expired: u32,
+}
+
+/// Personal waypoint set with `/waypoint add`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WaypointSaveDataV2 {
+    pub name: String,
+    pub position: Vec3Save,
+    /// sRGB color of the beacon and label
+    pub color: [f32; 3],
+}
+
+/// World name and generation choices from the new world screen
+///
+/// Every field defaults so saves from before the main menu list as unnamed
+/// standard worlds.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct WorldInfoSaveDataV2 {
+    pub name: String,
+    /// World seed (None = the one in worldgen.yaml)
+    pub seed: Option<u64>,
+    /// Worldgen preset ID ("standard", "flat", "hills")
+    pub preset: String,
+    /// Time spent in the world, in seconds
+    pub playtime_secs: f64,
+}
+
+/// Config of a display panel (the panels are saved as world blocks)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisplayPanelSaveDataV2 {
+    pub position: IVec3Save,
+    /// Face the number is shown on
+    pub face: DirectionSave,
+    /// Counted item string ID ("namespace:id"), None = not configured
+    pub item: Option<String>,
+    /// Data source ID ("delivered", "inventory", "produced")
+    pub source: String,
+}
+
+/// Text of a sign (the signs are saved as world blocks)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignSaveDataV2 {
+    pub position: IVec3Save,
+    /// Face the text is shown on
+    pub face: DirectionSave,
+    /// Up to 3 lines separated by '\n'
+    pub text: String,
+}
+
 /// World save data using string IDs
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WorldSaveDataV2 {
     /// Modified blocks: "x,y,z" -> Some("namespace:id") for placed, None for removed
+    ///
+    /// Saves from before palette encoding keep every block here; newer ones
+    /// only the few outside the chunk height.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub modified_blocks: HashMap<String, Option<String>>,
+    /// Palette-encoded modified blocks per chunk
+    #[serde(default)]
+    pub chunks: Vec<ChunkBlocksSaveV2>,
+}
+
+/// Modified blocks of one chunk
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChunkBlocksSaveV2 {
+    /// Chunk coordinate (x, z)
+    pub chunk: [i32; 2],
+    /// Distinct modifications: Some("namespace:id") for placed, None for removed
+    pub palette: Vec<Option<String>>,
+    /// Base64 of the per-cell palette indices (0 = unmodified, see `palette`)
+    pub cells: String,
 }
 
 impl WorldSaveDataV2 {
+    /// Palette-encode modified blocks chunk by chunk
+    pub fn from_modified(modified: impl IntoIterator<Item = (IVec3, Option<String>)>) -> Self {
+        let mut modified_blocks = HashMap::new();
+        let mut chunks: BTreeMap<[i32; 2], Vec<Option<Option<String>>>> = BTreeMap::new();
+        for (pos, block) in modified {
+            let local = WorldData::world_to_local(pos);
+            let Some(index) = ChunkData::pos_to_index_checked(local.x, local.y, local.z) else {
+                modified_blocks.insert(Self::pos_to_key(pos), block);
+                continue;
+            };
+            let chunk = WorldData::world_to_chunk(pos).to_array();
+            chunks
+                .entry(chunk)
+                .or_insert_with(|| vec![None; ChunkData::ARRAY_SIZE])[index] = Some(block);
+        }
+
+        let chunks = chunks
+            .into_iter()
+            .map(|(chunk, cells)| {
+                let (palette, indices) = palette::encode_palette(&cells);
+                ChunkBlocksSaveV2 {
+                    chunk,
+                    palette,
+                    cells: base64::engine::general_purpose::STANDARD.encode(indices),
+                }
+            })
+            .collect();
+        Self {
+            modified_blocks,
+            chunks,
+        }
+    }
+
+    /// All modified blocks, from the palette chunks and the legacy map
+    pub fn modified(&self) -> Result<Vec<(IVec3, Option<String>)>, String> {
+        let mut modified = Vec::new();
+        for chunk in &self.chunks {
+            let indices = base64::engine::general_purpose::STANDARD
+                .decode(&chunk.cells)
+                .map_err(|e| format!("Bad block data in chunk {:?}: {}", chunk.chunk, e))?;
+            let cells = palette::decode_palette(&chunk.palette, &indices)?;
+            if cells.len() != ChunkData::ARRAY_SIZE {
+                return Err(format!(
+                    "Chunk {:?} has {} cells, expected {}",
+                    chunk.chunk,
+                    cells.len(),
+                    ChunkData::ARRAY_SIZE
+                ));
+            }
+            let coord = IVec2::from_array(chunk.chunk);
+            modified.extend(cells.into_iter().enumerate().filter_map(|(index, cell)| {
+                cell.map(|block| {
+                    let local = ChunkData::index_to_pos(index);
+                    (WorldData::local_to_world(coord, local), block)
+                })
+            }));
+        }
+        modified.extend(
+            self.modified_blocks
+                .iter()
+                .filter_map(|(key, block)| Some((Self::key_to_pos(key)?, block.clone()))),
+        );
+        Ok(modified)
+    }
+
     /// Convert IVec3 to string key for JSON serialization
     pub fn pos_to_key(pos: IVec3) -> String {
         format!("{},{},{}", pos.x, pos.y, pos.z)
@@ -74,6 +309,17 @@ pub struct ConveyorItemSaveV2 {
     pub item_id: String,
     pub progress: f32,
     pub lateral_offset: f32,
+    /// Stack size (saves from before stacking hold single items)
+    #[serde(default = "default_stack_count")]
+    pub count: u32,
+}
+
+fn default_stack_count() -> u32 {
+    1
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 /// Miner save data
@@ -82,6 +328,34 @@ pub struct MinerSaveDataV2 {
     pub position: IVec3Save,
     pub progress: f32,
     pub buffer: Option<ItemStackV2>,
+    /// Output face direction (older saves: North)
+    #[serde(default)]
+    pub facing: DirectionSave,
+    /// Whether the machine is running (older saves: enabled)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Fall back to other sides when the facing belt is full (older saves: off)
+    #[serde(default)]
+    pub output_any_side: bool,
+    /// Installed drill head (older saves: a fresh steel drill, so they keep mining)
+    #[serde(default = "legacy_miner_drill")]
+    pub drill: Option<DrillSaveDataV2>,
+}
+
+/// Miner drill head and its remaining durability
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DrillSaveDataV2 {
+    /// Drill item string ID ("namespace:id")
+    pub item_id: String,
+    /// Mining operations left
+    pub durability: u32,
+}
+
+fn legacy_miner_drill() -> Option<DrillSaveDataV2> {
+    Some(DrillSaveDataV2 {
+        item_id: "base:steel_drill".to_string(),
+        durability: crate::game_spec::drill_spec::DRILL_DURABILITY,
+    })
 }
 
 /// Conveyor save data
@@ -92,7 +366,19 @@ pub struct ConveyorSaveDataV2 {
     pub shape: ConveyorShapeSave,
     pub items: Vec<ConveyorItemSaveV2>,
     pub last_output_index: usize,
-    pub last_input_source: usize,
+    /// Source position that last fed this conveyor (older saves: None)
+    #[serde(default)]
+    pub last_input_pos: Option<IVec3Save>,
+    /// Whether the machine is running (older saves: enabled)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Belt speed tier multiplier (older saves: base belt)
+    #[serde(default = "default_speed_multiplier")]
+    pub speed_multiplier: f32,
+}
+
+fn default_speed_multiplier() -> f32 {
+    1.0
 }
 
 /// Furnace save data
@@ -103,6 +389,36 @@ pub struct FurnaceSaveDataV2 {
     pub input: Option<ItemStackV2>,
     pub output: Option<ItemStackV2>,
     pub progress: f32,
+    /// Output face direction (older saves: North)
+    #[serde(default)]
+    pub facing: DirectionSave,
+    /// Whether the machine is running (older saves: enabled)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Fall back to other sides when the facing belt is full (older saves: off)
+    #[serde(default)]
+    pub output_any_side: bool,
+    /// Faces marked pass-through, belts there never insert (older saves: none)
+    #[serde(default)]
+    pub pass_through: Vec<DirectionSave>,
+    /// Faces whose belts feed only one slot (older saves: none)
+    #[serde(default)]
+    pub slot_routes: Vec<FaceRouteSaveV2>,
+}
+
+/// Slot a routed face feeds
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotRouteSave {
+    Input,
+    Fuel,
+}
+
+/// One machine face routed to a slot
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceRouteSaveV2 {
+    pub face: DirectionSave,
+    pub slot: SlotRouteSave,
 }
 
 /// Crusher save data
@@ -112,6 +428,82 @@ pub struct CrusherSaveDataV2 {
     pub input: Option<ItemStackV2>,
     pub output: Option<ItemStackV2>,
     pub progress: f32,
+    /// Output face direction (older saves: North)
+    #[serde(default)]
+    pub facing: DirectionSave,
+    /// Whether the machine is running (older saves: enabled)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Fall back to other sides when the facing belt is full (older saves: off)
+    #[serde(default)]
+    pub output_any_side: bool,
+    /// Faces marked pass-through, belts there never insert (older saves: none)
+    #[serde(default)]
+    pub pass_through: Vec<DirectionSave>,
+}
+
+/// Recycler save data
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecyclerSaveDataV2 {
+    pub position: IVec3Save,
+    pub input: Option<ItemStackV2>,
+    /// Output grid, one entry per slot
+    pub outputs: Vec<Option<ItemStackV2>>,
+    pub progress: f32,
+    pub facing: DirectionSave,
+    /// Whether the machine is running
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Faces marked pass-through, belts there never insert
+    #[serde(default)]
+    pub pass_through: Vec<DirectionSave>,
+}
+
+/// Hopper save data
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HopperSaveDataV2 {
+    pub position: IVec3Save,
+    /// Buffer, one entry per slot
+    pub slots: Vec<Option<ItemStackV2>>,
+    pub progress: f32,
+    pub facing: DirectionSave,
+    /// Whether the machine is running
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Side items are pushed toward (None = down)
+    #[serde(default)]
+    pub push_side: Option<DirectionSave>,
+    /// Faces marked pass-through, belts there never insert
+    #[serde(default)]
+    pub pass_through: Vec<DirectionSave>,
+}
+
+/// Data-defined machine save data (spec looked up by `machine_id` on load)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DataMachineSaveDataV2 {
+    /// Full item ID of the machine block (e.g. "base:grinder")
+    pub machine_id: String,
+    pub position: IVec3Save,
+    /// Input slots, one entry per slot
+    pub inputs: Vec<Option<ItemStackV2>>,
+    /// Output slots, one entry per slot
+    pub outputs: Vec<Option<ItemStackV2>>,
+    #[serde(default)]
+    pub fuel: u32,
+    pub progress: f32,
+    pub facing: DirectionSave,
+    /// Whether the machine is running
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Fall back to other sides when the facing belt is full
+    #[serde(default)]
+    pub output_any_side: bool,
+    /// Faces marked pass-through, belts there never insert
+    #[serde(default)]
+    pub pass_through: Vec<DirectionSave>,
+    /// Faces whose belts feed only one slot
+    #[serde(default)]
+    pub slot_routes: Vec<FaceRouteSaveV2>,
 }
 
 /// Machine save data (all machine types)
@@ -122,6 +514,43 @@ pub enum MachineSaveDataV2 {
     Conveyor(ConveyorSaveDataV2),
     Furnace(FurnaceSaveDataV2),
     Crusher(CrusherSaveDataV2),
+    Recycler(RecyclerSaveDataV2),
+    Hopper(HopperSaveDataV2),
+    DataMachine(DataMachineSaveDataV2),
+}
+
+impl MachineSaveDataV2 {
+    /// Block position of the machine or conveyor
+    pub fn position(&self) -> IVec3 {
+        match self {
+            Self::Miner(m) => m.position.into(),
+            Self::Conveyor(c) => c.position.into(),
+            Self::Furnace(f) => f.position.into(),
+            Self::Crusher(c) => c.position.into(),
+            Self::Recycler(r) => r.position.into(),
+            Self::Hopper(h) => h.position.into(),
+            Self::DataMachine(d) => d.position.into(),
+        }
+    }
+
+    /// Move the machine (and a conveyor's remembered input) by `offset`
+    pub fn translate(&mut self, offset: IVec3) {
+        let shift = |pos: &mut IVec3Save| *pos = (IVec3::from(*pos) + offset).into();
+        match self {
+            Self::Miner(m) => shift(&mut m.position),
+            Self::Conveyor(c) => {
+                shift(&mut c.position);
+                if let Some(pos) = c.last_input_pos.as_mut() {
+                    shift(pos);
+                }
+            }
+            Self::Furnace(f) => shift(&mut f.position),
+            Self::Crusher(c) => shift(&mut c.position),
+            Self::Recycler(r) => shift(&mut r.position),
+            Self::Hopper(h) => shift(&mut h.position),
+            Self::DataMachine(d) => shift(&mut d.position),
+        }
+    }
 }
 
 /// Quest save data using string IDs
@@ -132,6 +561,9 @@ pub struct QuestSaveDataV2 {
     pub rewards_claimed: bool,
     /// Items delivered: "namespace:id" -> count
     pub delivered: HashMap<String, u32>,
+    /// When each quest's rewards were claimed: quest id -> Unix milliseconds
+    #[serde(default)]
+    pub claimed_at: HashMap<String, u64>,
 }
 
 /// Main save data structure using string IDs throughout
@@ -156,4 +588,62 @@ pub struct SaveDataV2 {
     pub quests: QuestSaveDataV2,
     /// Game mode
     pub mode: GameModeSaveData,
+    /// Day/night clock
+    #[serde(default)]
+    pub clock: ClockSaveDataV2,
+    /// Fluid stored in pipe/tank networks
+    #[serde(default)]
+    pub fluids: Vec<FluidNetworkSaveDataV2>,
+    /// Contracts on player-built delivery platforms
+    #[serde(default)]
+    pub delivery_contracts: Vec<DeliveryContractSaveDataV2>,
+    /// Acceptance policies and reject buffers of delivery platforms
+    #[serde(default)]
+    pub delivery_filters: Vec<DeliveryFilterSaveDataV2>,
+    /// Display panel configs
+    #[serde(default)]
+    pub display_panels: Vec<DisplayPanelSaveDataV2>,
+    /// Sign texts
+    #[serde(default)]
+    pub signs: Vec<SignSaveDataV2>,
+    /// Hash of the worldgen config the world was generated with (None = unknown)
+    #[serde(default)]
+    pub worldgen_hash: Option<u64>,
+    /// Content bundle version the save was created with (None = loose files)
+    #[serde(default)]
+    pub content_version: Option<String>,
+    /// Content hash of each mod that registered items or recipes (older saves: none)
+    #[serde(default)]
+    pub mod_content: BTreeMap<String, u64>,
+    /// Player stats and achievements
+    #[serde(default)]
+    pub stats: StatsSaveDataV2,
+    /// Timed delivery contracts
+    #[serde(default)]
+    pub timed_contracts: TimedContractsSaveDataV2,
+    /// Personal waypoints
+    #[serde(default)]
+    pub waypoints: Vec<WaypointSaveDataV2>,
+    /// World name, seed, preset and playtime
+    #[serde(default)]
+    pub world_info: WorldInfoSaveDataV2,
+}
+
+impl SaveDataV2 {
+    /// Warnings for mods whose content the save used but that are missing or changed
+    pub fn mod_content_warnings(&self, loaded: &BTreeMap<String, u64>) -> Vec<String> {
+        self.mod_content
+            .iter()
+            .filter_map(|(mod_id, hash)| match loaded.get(mod_id) {
+                None => Some(format!(
+                    "mod '{}' is missing; its items were dropped or replaced with stone",
+                    mod_id
+                )),
+                Some(current) if current != hash => {
+                    Some(format!("mod '{}' content changed since this save", mod_id))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
 }
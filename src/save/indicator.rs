@@ -0,0 +1,229 @@
+//! Save indicator in the bottom-right corner
+//!
+//! Shows a spinner while a save is written in the background, then a
+//! checkmark for `SAVE_DONE_DISPLAY_SECS`, or the error for a while longer.
+//! The pause menu shows how long ago the last save finished (`LastSave`).
+
+use bevy::prelude::*;
+
+use crate::components::{GameFont, UIContext, UIState};
+use crate::setup::ui::{text_font, TEXT_CAPTION};
+
+/// How long the "saved" checkmark stays (seconds)
+pub const SAVE_DONE_DISPLAY_SECS: f32 = 2.0;
+
+/// How long a save error stays (seconds)
+pub const SAVE_ERROR_DISPLAY_SECS: f32 = 6.0;
+
+const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+const SPINNER_FRAME_SECS: f32 = 0.12;
+
+const SAVING_COLOR: Color = Color::srgb(0.85, 0.85, 0.9);
+const SAVED_COLOR: Color = Color::srgb(0.4, 0.95, 0.4);
+const FAILED_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+
+/// What the indicator shows
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SaveIndicatorState {
+    #[default]
+    Hidden,
+    Saving,
+    Saved,
+    Failed(String),
+}
+
+/// Save indicator state, driven by the background save
+#[derive(Resource, Debug, Default)]
+pub struct SaveIndicator {
+    pub state: SaveIndicatorState,
+    /// Seconds left for Saved/Failed; time spent for Saving (spinner frame)
+    timer: f32,
+}
+
+impl SaveIndicator {
+    /// A save was started
+    pub fn saving(&mut self) {
+        self.state = SaveIndicatorState::Saving;
+        self.timer = 0.0;
+    }
+
+    /// The save finished
+    pub fn finished(&mut self, result: &Result<(), String>) {
+        match result {
+            Ok(()) => {
+                self.state = SaveIndicatorState::Saved;
+                self.timer = SAVE_DONE_DISPLAY_SECS;
+            }
+            Err(e) => {
+                self.state = SaveIndicatorState::Failed(e.clone());
+                self.timer = SAVE_ERROR_DISPLAY_SECS;
+            }
+        }
+    }
+
+    /// Advance the spinner or the display time
+    pub fn tick(&mut self, delta: f32) {
+        match self.state {
+            SaveIndicatorState::Hidden => {}
+            SaveIndicatorState::Saving => self.timer += delta,
+            SaveIndicatorState::Saved | SaveIndicatorState::Failed(_) => {
+                self.timer -= delta;
+                if self.timer <= 0.0 {
+                    self.state = SaveIndicatorState::Hidden;
+                }
+            }
+        }
+    }
+
+    /// Text and color to show (None = hidden)
+    pub fn label(&self) -> Option<(String, Color)> {
+        match &self.state {
+            SaveIndicatorState::Hidden => None,
+            SaveIndicatorState::Saving => {
+                let frame = (self.timer / SPINNER_FRAME_SECS) as usize % SPINNER_FRAMES.len();
+                Some((format!("{} 保存中…", SPINNER_FRAMES[frame]), SAVING_COLOR))
+            }
+            SaveIndicatorState::Saved => Some(("✓ 保存しました".to_string(), SAVED_COLOR)),
+            SaveIndicatorState::Failed(e) => Some((format!("✗ 保存失敗: {}", e), FAILED_COLOR)),
+        }
+    }
+}
+
+/// When the last successful save finished
+#[derive(Resource, Debug, Default)]
+pub struct LastSave {
+    /// Real time since startup (seconds); None until the first save this session
+    pub at_secs: Option<f64>,
+}
+
+impl LastSave {
+    /// A save finished at `now`
+    pub fn record(&mut self, now: f64) {
+        self.at_secs = Some(now);
+    }
+
+    /// Pause menu line for the time `now`
+    pub fn label(&self, now: f64) -> String {
+        let Some(at) = self.at_secs else {
+            return "まだ保存されていません".to_string();
+        };
+        let ago = (now - at).max(0.0) as u64;
+        if ago < 60 {
+            format!("最終保存: {}秒前", ago)
+        } else if ago < 3600 {
+            format!("最終保存: {}分前", ago / 60)
+        } else {
+            format!("最終保存: {}時間前", ago / 3600)
+        }
+    }
+}
+
+/// Marker for the "last saved" line in the pause menu
+#[derive(Component)]
+pub struct PauseLastSavedText;
+
+/// Keep the pause menu's "last saved" line current while the menu is open
+pub fn update_pause_last_saved(
+    time: Res<Time<Real>>,
+    ui_state: Res<UIState>,
+    last_save: Res<LastSave>,
+    mut text_query: Query<&mut Text, With<PauseLastSavedText>>,
+) {
+    if !ui_state.is_active(&UIContext::PauseMenu) {
+        return;
+    }
+    let label = last_save.label(time.elapsed_secs_f64());
+    for mut text in text_query.iter_mut() {
+        if **text != label {
+            **text = label.clone();
+        }
+    }
+}
+
+/// Marker for the indicator text
+#[derive(Component)]
+pub struct SaveIndicatorText;
+
+pub fn setup_save_indicator(mut commands: Commands, game_font: Res<GameFont>) {
+    commands.spawn((
+        SaveIndicatorText,
+        Text::new(""),
+        text_font(&game_font.0, TEXT_CAPTION),
+        TextColor(SAVING_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            border_radius: BorderRadius::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.75)),
+        Visibility::Hidden,
+    ));
+}
+
+/// Show the indicator text and hide it once it expires
+pub fn update_save_indicator(
+    time: Res<Time>,
+    mut indicator: ResMut<SaveIndicator>,
+    mut ui_query: Query<(&mut Text, &mut TextColor, &mut Visibility), With<SaveIndicatorText>>,
+) {
+    let hidden = indicator.state == SaveIndicatorState::Hidden;
+    if hidden && !indicator.is_changed() {
+        return;
+    }
+    if !hidden {
+        indicator.tick(time.delta_secs());
+    }
+    let label = indicator.label();
+    for (mut text, mut color, mut vis) in ui_query.iter_mut() {
+        match &label {
+            Some((label, label_color)) => {
+                if **text != *label {
+                    **text = label.clone();
+                }
+                color.0 = *label_color;
+                *vis = Visibility::Visible;
+            }
+            None => *vis = Visibility::Hidden,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator_shows_result_then_hides() {
+        let mut indicator = SaveIndicator::default();
+        assert!(indicator.label().is_none());
+
+        indicator.saving();
+        indicator.tick(0.5);
+        assert!(indicator.label().unwrap().0.contains("保存中"));
+
+        indicator.finished(&Ok(()));
+        indicator.tick(SAVE_DONE_DISPLAY_SECS - 0.1);
+        assert_eq!(indicator.state, SaveIndicatorState::Saved);
+        indicator.tick(0.2);
+        assert_eq!(indicator.state, SaveIndicatorState::Hidden);
+
+        indicator.finished(&Err("disk full".to_string()));
+        assert!(indicator.label().unwrap().0.contains("disk full"));
+        indicator.tick(SAVE_ERROR_DISPLAY_SECS + 0.1);
+        assert!(indicator.label().is_none());
+    }
+
+    #[test]
+    fn test_last_save_label() {
+        let mut last_save = LastSave::default();
+        assert_eq!(last_save.label(10.0), "まだ保存されていません");
+
+        last_save.record(100.0);
+        assert_eq!(last_save.label(112.4), "最終保存: 12秒前");
+        assert_eq!(last_save.label(100.0 + 150.0), "最終保存: 2分前");
+        assert_eq!(last_save.label(100.0 + 7300.0), "最終保存: 2時間前");
+    }
+}
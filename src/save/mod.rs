@@ -4,9 +4,26 @@
 //! - Save data format definitions
 //! - Save/Load systems
 //! - Auto-save functionality
+//! - Background save writing and the save indicator
+//! - Saving on exit (synchronous on native, flushed on page unload on WASM)
+//! - Reading saves back: the saves directory, or `localStorage` on WASM (`storage`)
 
 pub mod format;
+pub mod indicator;
 pub mod systems;
+#[cfg(target_arch = "wasm32")]
+pub mod web;
 
 pub use format::*;
+pub use indicator::{
+    setup_save_indicator, update_pause_last_saved, update_save_indicator, LastSave,
+    PauseLastSavedText, SaveIndicator,
+};
 pub use systems::*;
+
+/// Where saves are listed, loaded and deleted from
+#[cfg(not(target_arch = "wasm32"))]
+pub use format::native as storage;
+/// Where saves are listed, loaded and deleted from (the unload snapshots)
+#[cfg(target_arch = "wasm32")]
+pub use web as storage;
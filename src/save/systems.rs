@@ -1,29 +1,207 @@
 //! Save/Load system implementations
 
 use super::format as save;
+use super::indicator::{LastSave, SaveIndicator};
+use crate::achievements::PlayerAchievements;
 use crate::components::{LoadGameEvent, SaveGameEvent};
 use crate::components::{MachineBundle, *};
 use crate::core::{items, ItemId};
-use crate::game_spec::{CRUSHER, FURNACE, MINER};
+use crate::daynight::{GameClock, Lamp};
+use crate::fluids::{self, FluidNetworks};
+use crate::game_spec::{CRUSHER, FURNACE, HOPPER, MINER, RECYCLER};
+use crate::logistics::delivery_pad::{self, DeliveryContract, PadPlatform};
+use crate::logistics::{DeliveryFilter, DeliveryPolicy, TimedContract, TimedContracts};
+use crate::meshes::create_conveyor_mesh;
+use crate::modding::{ContentVersion, LoadedModData};
 use crate::player::{LocalPlatformInventory, LocalPlayer, PlatformInventory, PlayerInventory};
+use crate::respawn::SpawnPoint;
+use crate::sign::Sign;
+use crate::statistics::{DisplayPanel, DisplaySource, PlayerStats};
+use crate::systems::{RecentPlaceables, SpawnProtection, QUICK_SELECT_SEGMENTS};
+use crate::waypoint::{Waypoint, Waypoints};
 use crate::world::WorldData;
-use crate::{Direction, BLOCK_SIZE};
+use crate::{Direction, BLOCK_SIZE, CONVEYOR_BELT_HEIGHT};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use tracing::info;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use futures_lite::future;
+use std::collections::{BTreeMap, HashSet};
+use tracing::{info, warn};
+
+/// Bundled clock, stats, achievements, content versions, quick-select history, timed contracts, waypoints, spawn protection and the active world for saving (reduces parameter count)
+#[derive(SystemParam)]
+pub struct ProgressRes<'w> {
+    pub clock: Res<'w, GameClock>,
+    pub stats: Res<'w, PlayerStats>,
+    pub achievements: Res<'w, PlayerAchievements>,
+    pub content_version: Option<Res<'w, ContentVersion>>,
+    pub mod_data: Option<Res<'w, LoadedModData>>,
+    pub recent_placeables: Option<Res<'w, RecentPlaceables>>,
+    pub timed_contracts: Option<Res<'w, TimedContracts>>,
+    pub waypoints: Option<Res<'w, Waypoints>>,
+    pub spawn_protection: Option<Res<'w, SpawnProtection>>,
+    pub active_world: Option<Res<'w, ActiveWorld>>,
+}
+
+/// Bundled clock, stats, achievements, loaded mods, quick-select history, timed contracts, waypoints, spawn protection and the active world for loading (reduces parameter count)
+#[derive(SystemParam)]
+pub struct ProgressResMut<'w> {
+    pub clock: ResMut<'w, GameClock>,
+    pub stats: ResMut<'w, PlayerStats>,
+    pub achievements: ResMut<'w, PlayerAchievements>,
+    pub mod_data: Option<Res<'w, LoadedModData>>,
+    pub recent_placeables: Option<ResMut<'w, RecentPlaceables>>,
+    pub timed_contracts: Option<ResMut<'w, TimedContracts>>,
+    pub waypoints: Option<ResMut<'w, Waypoints>>,
+    pub spawn_protection: Option<ResMut<'w, SpawnProtection>>,
+    pub active_world: Option<ResMut<'w, ActiveWorld>>,
+}
+
+/// Convert lifetime stats and unlocks to save format
+pub fn stats_to_save(
+    stats: &PlayerStats,
+    achievements: &PlayerAchievements,
+) -> save::StatsSaveDataV2 {
+    save::StatsSaveDataV2 {
+        blocks_mined: stats.blocks_mined,
+        blocks_placed: stats.blocks_placed,
+        machines_placed: stats.machines_placed,
+        conveyors_placed: stats.conveyors_placed,
+        items_smelted: stats.items_smelted,
+        items_delivered: stats.items_delivered,
+        ingots_delivered: stats.ingots_delivered,
+        quests_completed: stats.quests_completed,
+        distance_walked: stats.distance_walked,
+        unlocked: achievements.unlocked_ids(),
+    }
+}
+
+/// Merge saved stats and unlocks into the current ones
+///
+/// Counters only grow, so the larger value wins and unlocks are unioned;
+/// loading an older save never takes progress away.
+pub fn merge_saved_stats(
+    data: &save::StatsSaveDataV2,
+    stats: &mut PlayerStats,
+    achievements: &mut PlayerAchievements,
+) {
+    stats.merge_max(&PlayerStats {
+        blocks_mined: data.blocks_mined,
+        blocks_placed: data.blocks_placed,
+        machines_placed: data.machines_placed,
+        conveyors_placed: data.conveyors_placed,
+        items_smelted: data.items_smelted,
+        items_delivered: data.items_delivered,
+        ingots_delivered: data.ingots_delivered,
+        quests_completed: data.quests_completed,
+        distance_walked: data.distance_walked,
+    });
+    for id in &data.unlocked {
+        achievements.unlock(id, 0.0);
+    }
+}
+
+/// Convert timed contracts to save format
+pub fn timed_contracts_to_save(contracts: &TimedContracts) -> save::TimedContractsSaveDataV2 {
+    save::TimedContractsSaveDataV2 {
+        active: contracts
+            .active
+            .as_ref()
+            .map(|contract| save::TimedContractSaveDataV2 {
+                item: item_id_to_string(contract.item),
+                amount: contract.amount,
+                delivered: contract.delivered,
+                duration_ticks: contract.duration_ticks,
+                elapsed_ticks: contract.elapsed_ticks,
+                reward: save::ItemStackV2::new(
+                    item_id_to_string(contract.reward.0),
+                    contract.reward.1,
+                ),
+            }),
+        cooldown_ticks: contracts.cooldown_ticks,
+        streak: contracts.streak,
+        completed: contracts.completed,
+        expired: contracts.expired,
+    }
+}
+
+/// Restore timed contracts (a contract naming an unknown item is dropped)
+pub fn timed_contracts_from_save(data: &save::TimedContractsSaveDataV2) -> TimedContracts {
+    let active = data.active.as_ref().and_then(|contract| {
+        Some(TimedContract {
+            item: string_id_to_item_id(&contract.item)?,
+            amount: contract.amount,
+            delivered: contract.delivered,
+            duration_ticks: contract.duration_ticks,
+            elapsed_ticks: contract.elapsed_ticks,
+            reward: (
+                string_id_to_item_id(&contract.reward.item_id)?,
+                contract.reward.count,
+            ),
+        })
+    });
+    TimedContracts {
+        active,
+        cooldown_ticks: data.cooldown_ticks,
+        streak: data.streak,
+        completed: data.completed,
+        expired: data.expired,
+    }
+}
+
+/// Convert waypoints to save format
+pub fn waypoints_to_save(waypoints: &Waypoints) -> Vec<save::WaypointSaveDataV2> {
+    waypoints
+        .iter()
+        .map(|(name, wp)| {
+            let color = wp.color.to_srgba();
+            save::WaypointSaveDataV2 {
+                name: name.to_string(),
+                position: wp.position.into(),
+                color: [color.red, color.green, color.blue],
+            }
+        })
+        .collect()
+}
+
+/// Restore waypoints (beyond `MAX_WAYPOINTS` the rest are dropped)
+pub fn waypoints_from_save(data: &[save::WaypointSaveDataV2]) -> Waypoints {
+    let mut waypoints = Waypoints::default();
+    for wp in data {
+        waypoints.insert(
+            wp.name.clone(),
+            Waypoint {
+                position: wp.position.into(),
+                color: Color::srgb(wp.color[0], wp.color[1], wp.color[2]),
+            },
+        );
+    }
+    waypoints
+}
 
 /// Collect all game state into SaveDataV2 (string ID format)
 #[allow(clippy::too_many_arguments)]
 pub fn collect_save_data(
-    player_query: &Query<&Transform, With<Player>>,
+    player_query: &Query<(&Transform, Option<&SpawnPoint>), With<Player>>,
     camera_query: &Query<&PlayerCamera>,
     inventory: &PlayerInventory,
     world_data: &WorldData,
     machine_query: &Query<&Machine>,
     conveyor_query: &Query<&Conveyor>,
-    _delivery_query: &Query<&DeliveryPlatform>,
+    delivery_query: &Query<(&DeliveryPlatform, &DeliveryContract)>,
+    filter_query: &Query<(&DeliveryPlatform, &DeliveryFilter)>,
+    display_query: &Query<&DisplayPanel>,
     current_quest: &CurrentQuest,
     creative_mode: &CreativeMode,
     platform_inventory: &PlatformInventory,
+    clock: &GameClock,
+    fluid_networks: &FluidNetworks,
+    stats: save::StatsSaveDataV2,
+    content_version: Option<String>,
+    mod_content: BTreeMap<String, u64>,
+    recent_placeables: Option<&RecentPlaceables>,
+    timed_contracts: Option<&TimedContracts>,
+    waypoints: Option<&Waypoints>,
 ) -> save::SaveDataV2 {
     use save::*;
 
@@ -37,7 +215,7 @@ pub fn collect_save_data(
     };
 
     // Collect player data
-    let player_data = if let Ok(transform) = player_query.single() {
+    let player_data = if let Ok((transform, spawn_point)) = player_query.single() {
         let rotation = camera_query
             .single()
             .map(|c| CameraRotation {
@@ -51,6 +229,7 @@ pub fn collect_save_data(
         PlayerSaveData {
             position: transform.translation.into(),
             rotation,
+            spawn_point: spawn_point.map(|s| s.0.into()),
         }
     } else {
         PlayerSaveData {
@@ -63,14 +242,10 @@ pub fn collect_save_data(
                 pitch: 0.0,
                 yaw: 0.0,
             },
+            spawn_point: None,
         }
     };
 
-    // Helper to convert ItemId to string ID
-    fn item_id_to_string(id: ItemId) -> String {
-        id.name().unwrap_or("base:unknown").to_string()
-    }
-
     // Collect inventory data (V2 format with string IDs)
     let inventory_data = InventorySaveDataV2 {
         selected_slot: inventory.selected_slot,
@@ -84,126 +259,31 @@ pub fn collect_save_data(
                 })
             })
             .collect(),
+        recent_placeables: recent_placeables
+            .map(|recent| {
+                recent
+                    .items
+                    .iter()
+                    .copied()
+                    .map(item_id_to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
     };
 
-    // Collect world modifications (V2 format with string IDs)
-    let modified_blocks: std::collections::HashMap<String, Option<String>> = world_data
-        .modified_blocks
-        .iter()
-        .map(|(pos, block)| {
-            (
-                WorldSaveDataV2::pos_to_key(*pos),
-                block.and_then(|b| b.name().map(|s| s.to_string())),
-            )
-        })
-        .collect();
-
-    let world_save = WorldSaveDataV2 { modified_blocks };
-
-    // Collect machines (V2 format)
-    let mut machines = Vec::new();
-
-    // All machines (Miner, Furnace, Crusher) using Machine component
-    for machine in machine_query.iter() {
-        let machine_id = machine.spec.item_id();
-        if machine_id == items::miner_block() {
-            let buffer = machine
-                .slots
-                .outputs
-                .first()
-                .and_then(|s| s.item_id.map(|id| (id, s.count)));
-            machines.push(MachineSaveDataV2::Miner(MinerSaveDataV2 {
-                position: machine.position.into(),
-                progress: machine.progress,
-                buffer: buffer.map(|(id, count)| ItemStackV2 {
-                    item_id: item_id_to_string(id),
-                    count,
-                }),
-            }));
-        } else if machine_id == items::furnace_block() {
-            let input = machine
-                .slots
-                .inputs
-                .first()
-                .and_then(|s| s.item_id.map(|id| (id, s.count)));
-            let output = machine
-                .slots
-                .outputs
-                .first()
-                .and_then(|s| s.item_id.map(|id| (id, s.count)));
-            machines.push(MachineSaveDataV2::Furnace(FurnaceSaveDataV2 {
-                position: machine.position.into(),
-                fuel: machine.slots.fuel,
-                input: input.map(|(id, count)| ItemStackV2 {
-                    item_id: item_id_to_string(id),
-                    count,
-                }),
-                output: output.map(|(id, count)| ItemStackV2 {
-                    item_id: item_id_to_string(id),
-                    count,
-                }),
-                progress: machine.progress,
-            }));
-        } else if machine_id == items::crusher_block() {
-            let input = machine
-                .slots
-                .inputs
-                .first()
-                .and_then(|s| s.item_id.map(|id| (id, s.count)));
-            let output = machine
-                .slots
-                .outputs
-                .first()
-                .and_then(|s| s.item_id.map(|id| (id, s.count)));
-            machines.push(MachineSaveDataV2::Crusher(CrusherSaveDataV2 {
-                position: machine.position.into(),
-                input: input.map(|(id, count)| ItemStackV2 {
-                    item_id: item_id_to_string(id),
-                    count,
-                }),
-                output: output.map(|(id, count)| ItemStackV2 {
-                    item_id: item_id_to_string(id),
-                    count,
-                }),
-                progress: machine.progress,
-            }));
-        }
-    }
-
-    // Conveyors (V2 format)
-    for conveyor in conveyor_query.iter() {
-        let direction = match conveyor.direction {
-            Direction::North => DirectionSave::North,
-            Direction::South => DirectionSave::South,
-            Direction::East => DirectionSave::East,
-            Direction::West => DirectionSave::West,
-        };
-        let shape = match conveyor.shape {
-            ConveyorShape::Straight => ConveyorShapeSave::Straight,
-            ConveyorShape::CornerLeft => ConveyorShapeSave::CornerLeft,
-            ConveyorShape::CornerRight => ConveyorShapeSave::CornerRight,
-            ConveyorShape::TJunction => ConveyorShapeSave::TJunction,
-            ConveyorShape::Splitter => ConveyorShapeSave::Splitter,
-        };
-        let items: Vec<ConveyorItemSaveV2> = conveyor
-            .items
+    // Collect world modifications (V2 format with string IDs, palette-encoded per chunk)
+    let world_save = WorldSaveDataV2::from_modified(
+        world_data
+            .modified_blocks
             .iter()
-            .map(|item| ConveyorItemSaveV2 {
-                item_id: item_id_to_string(item.item_id),
-                progress: item.progress,
-                lateral_offset: item.lateral_offset,
-            })
-            .collect();
+            .map(|(pos, block)| (*pos, block.and_then(|b| b.name().map(|s| s.to_string())))),
+    );
 
-        machines.push(MachineSaveDataV2::Conveyor(ConveyorSaveDataV2 {
-            position: conveyor.position.into(),
-            direction,
-            shape,
-            items,
-            last_output_index: conveyor.last_output_index,
-            last_input_source: conveyor.last_input_source,
-        }));
-    }
+    // Collect machines (V2 format); hibernated ones are already in save format
+    let mut machines: Vec<MachineSaveDataV2> =
+        machine_query.iter().filter_map(machine_to_save).collect();
+    machines.extend(conveyor_query.iter().map(conveyor_to_save));
+    machines.extend(world_data.hibernated_machines.iter().cloned());
 
     // Collect quest data (V2 format with string IDs)
     let quest_data = QuestSaveDataV2 {
@@ -211,11 +291,13 @@ pub fn collect_save_data(
         completed: current_quest.completed,
         rewards_claimed: current_quest.rewards_claimed,
         delivered: std::collections::HashMap::new(),
+        claimed_at: current_quest.claimed_at.clone(),
     };
 
     // Game mode
     let mode_data = GameModeSaveData {
         creative: creative_mode.enabled,
+        spawn_protection: true,
     };
 
     // Save PlatformInventory items (V2 format with string IDs)
@@ -237,6 +319,660 @@ pub fn collect_save_data(
         machines,
         quests: quest_data,
         mode: mode_data,
+        clock: ClockSaveDataV2 {
+            time_of_day: clock.time_of_day,
+            day: clock.day,
+        },
+        fluids: fluids::network_save_entries(fluid_networks)
+            .into_iter()
+            .map(|(pos, fluid, amount)| FluidNetworkSaveDataV2 {
+                position: pos.into(),
+                fluid: fluid.to_string(),
+                amount,
+            })
+            .collect(),
+        delivery_contracts: delivery_query
+            .iter()
+            .map(|(platform, contract)| DeliveryContractSaveDataV2 {
+                origin: platform.position.into(),
+                item: item_id_to_string(contract.item),
+                target_per_min: contract.target_per_min,
+            })
+            .collect(),
+        delivery_filters: filter_query
+            .iter()
+            .filter(|(_, filter)| {
+                filter.policy != DeliveryPolicy::AcceptAll || !filter.buffer.is_empty()
+            })
+            .map(|(platform, filter)| filter_to_save(platform.position, filter))
+            .collect(),
+        display_panels: display_query
+            .iter()
+            .map(|panel| DisplayPanelSaveDataV2 {
+                position: panel.position.into(),
+                face: direction_to_save(panel.face),
+                item: panel.item.map(item_id_to_string),
+                source: panel.source.id().to_string(),
+            })
+            .collect(),
+        // Filled in by SaveSources::snapshot
+        signs: Vec::new(),
+        worldgen_hash: Some(world_data.gen_config.config_hash()),
+        content_version,
+        mod_content,
+        stats,
+        timed_contracts: timed_contracts
+            .map(timed_contracts_to_save)
+            .unwrap_or_default(),
+        waypoints: waypoints.map(waypoints_to_save).unwrap_or_default(),
+        world_info: WorldInfoSaveDataV2::default(),
+    }
+}
+
+/// Convert a platform's acceptance policy and reject buffer to save format
+pub fn filter_to_save(origin: IVec3, filter: &DeliveryFilter) -> save::DeliveryFilterSaveDataV2 {
+    let whitelist = match &filter.policy {
+        DeliveryPolicy::Whitelist(items) => items.iter().map(|id| item_id_to_string(*id)).collect(),
+        _ => Vec::new(),
+    };
+    save::DeliveryFilterSaveDataV2 {
+        origin: origin.into(),
+        policy: filter.policy.id().to_string(),
+        whitelist,
+        buffer: filter
+            .buffer
+            .iter()
+            .map(|(id, count)| (item_id_to_string(*id), *count))
+            .collect(),
+    }
+}
+
+/// Restore the filter saved for the platform at `origin` (default: accept all, empty buffer)
+///
+/// Buffered stacks of unknown items are dropped; an unknown policy accepts all.
+pub fn filter_from_save(origin: IVec3, saved: &[save::DeliveryFilterSaveDataV2]) -> DeliveryFilter {
+    let Some(data) = saved.iter().find(|f| IVec3::from(f.origin) == origin) else {
+        return DeliveryFilter::default();
+    };
+    let whitelist = data
+        .whitelist
+        .iter()
+        .filter_map(|id| string_id_to_item_id(id))
+        .collect();
+    let mut filter =
+        DeliveryFilter::new(DeliveryPolicy::from_id(&data.policy, whitelist).unwrap_or_default());
+    filter.buffer = data
+        .buffer
+        .iter()
+        .filter_map(|(id, count)| string_id_to_item_id(id).map(|item| (item, *count)))
+        .collect();
+    filter
+}
+
+/// Convert a sign to save format
+pub fn sign_to_save(sign: &Sign) -> save::SignSaveDataV2 {
+    save::SignSaveDataV2 {
+        position: sign.position.into(),
+        face: direction_to_save(sign.face),
+        text: sign.text.clone(),
+    }
+}
+
+/// Restore the sign at `pos` (default: blank, facing south)
+///
+/// The text is clamped again, so an edited save can't overfill the sign.
+pub fn sign_from_save(pos: IVec3, saved: &[save::SignSaveDataV2]) -> Sign {
+    match saved.iter().find(|s| IVec3::from(s.position) == pos) {
+        Some(data) => Sign::new(pos, direction_from_save(data.face)).with_text(&data.text),
+        None => Sign::new(pos, Direction::South),
+    }
+}
+
+/// Convert ItemId to its string ID for save files
+fn item_id_to_string(id: ItemId) -> String {
+    id.name().unwrap_or("base:unknown").to_string()
+}
+
+/// Convert a machine to save format (None for machine types that aren't saved)
+pub fn machine_to_save(machine: &Machine) -> Option<save::MachineSaveDataV2> {
+    use save::*;
+
+    let machine_id = machine.spec.item_id();
+    if machine_id == items::miner_block() {
+        let buffer = machine
+            .slots
+            .outputs
+            .first()
+            .and_then(|s| s.item_id.map(|id| (id, s.count)));
+        Some(MachineSaveDataV2::Miner(MinerSaveDataV2 {
+            position: machine.position.into(),
+            progress: machine.progress,
+            buffer: buffer.map(|(id, count)| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count,
+            }),
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            output_any_side: machine.output_any_side,
+            drill: machine.slots.drill.as_ref().map(|drill| DrillSaveDataV2 {
+                item_id: item_id_to_string(drill.item_id),
+                durability: drill.durability,
+            }),
+        }))
+    } else if machine_id == items::furnace_block() {
+        let input = machine
+            .slots
+            .inputs
+            .first()
+            .and_then(|s| s.item_id.map(|id| (id, s.count)));
+        let output = machine
+            .slots
+            .outputs
+            .first()
+            .and_then(|s| s.item_id.map(|id| (id, s.count)));
+        Some(MachineSaveDataV2::Furnace(FurnaceSaveDataV2 {
+            position: machine.position.into(),
+            fuel: machine.slots.fuel,
+            input: input.map(|(id, count)| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count,
+            }),
+            output: output.map(|(id, count)| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count,
+            }),
+            progress: machine.progress,
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            output_any_side: machine.output_any_side,
+            pass_through: machine
+                .pass_through_sides
+                .iter()
+                .map(|&side| direction_to_save(side))
+                .collect(),
+            slot_routes: slot_routes_to_save(machine),
+        }))
+    } else if machine_id == items::crusher_block() {
+        let input = machine
+            .slots
+            .inputs
+            .first()
+            .and_then(|s| s.item_id.map(|id| (id, s.count)));
+        let output = machine
+            .slots
+            .outputs
+            .first()
+            .and_then(|s| s.item_id.map(|id| (id, s.count)));
+        Some(MachineSaveDataV2::Crusher(CrusherSaveDataV2 {
+            position: machine.position.into(),
+            input: input.map(|(id, count)| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count,
+            }),
+            output: output.map(|(id, count)| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count,
+            }),
+            progress: machine.progress,
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            output_any_side: machine.output_any_side,
+            pass_through: machine
+                .pass_through_sides
+                .iter()
+                .map(|&side| direction_to_save(side))
+                .collect(),
+        }))
+    } else if machine_id == items::recycler_block() {
+        let stack = |slot: &MachineSlot| {
+            slot.item_id.map(|id| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count: slot.count,
+            })
+        };
+        Some(MachineSaveDataV2::Recycler(RecyclerSaveDataV2 {
+            position: machine.position.into(),
+            input: machine.slots.inputs.first().and_then(stack),
+            outputs: machine.slots.outputs.iter().map(stack).collect(),
+            progress: machine.progress,
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            pass_through: machine
+                .pass_through_sides
+                .iter()
+                .map(|&side| direction_to_save(side))
+                .collect(),
+        }))
+    } else if machine_id == items::hopper_block() {
+        let stack = |slot: &MachineSlot| {
+            slot.item_id.map(|id| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count: slot.count,
+            })
+        };
+        Some(MachineSaveDataV2::Hopper(HopperSaveDataV2 {
+            position: machine.position.into(),
+            slots: machine.slots.inputs.iter().map(stack).collect(),
+            progress: machine.progress,
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            push_side: machine.push_side.map(direction_to_save),
+            pass_through: machine
+                .pass_through_sides
+                .iter()
+                .map(|&side| direction_to_save(side))
+                .collect(),
+        }))
+    } else if crate::game_spec::data_machine_spec(machine_id).is_some() {
+        let stack = |slot: &MachineSlot| {
+            slot.item_id.map(|id| ItemStackV2 {
+                item_id: item_id_to_string(id),
+                count: slot.count,
+            })
+        };
+        Some(MachineSaveDataV2::DataMachine(DataMachineSaveDataV2 {
+            machine_id: machine.spec.id.to_string(),
+            position: machine.position.into(),
+            inputs: machine.slots.inputs.iter().map(stack).collect(),
+            outputs: machine.slots.outputs.iter().map(stack).collect(),
+            fuel: machine.slots.fuel,
+            progress: machine.progress,
+            facing: direction_to_save(machine.facing),
+            enabled: machine.enabled,
+            output_any_side: machine.output_any_side,
+            pass_through: machine
+                .pass_through_sides
+                .iter()
+                .map(|&side| direction_to_save(side))
+                .collect(),
+            slot_routes: slot_routes_to_save(machine),
+        }))
+    } else {
+        None
+    }
+}
+
+/// Faces routed to one slot, in save format
+fn slot_routes_to_save(machine: &Machine) -> Vec<save::FaceRouteSaveV2> {
+    machine
+        .slot_routes
+        .iter()
+        .filter_map(|&(side, route)| {
+            let slot = match route {
+                SlotRoute::Input => save::SlotRouteSave::Input,
+                SlotRoute::Fuel => save::SlotRouteSave::Fuel,
+                SlotRoute::Auto => return None,
+            };
+            Some(save::FaceRouteSaveV2 {
+                face: direction_to_save(side),
+                slot,
+            })
+        })
+        .collect()
+}
+
+/// Restore saved slot routes onto a machine
+fn restore_slot_routes(machine: &mut Machine, routes: &[save::FaceRouteSaveV2]) {
+    for route in routes {
+        let slot = match route.slot {
+            save::SlotRouteSave::Input => SlotRoute::Input,
+            save::SlotRouteSave::Fuel => SlotRoute::Fuel,
+        };
+        machine.set_slot_route(direction_from_save(route.face), slot);
+    }
+}
+
+/// Convert a conveyor (and the items on it) to save format
+pub fn conveyor_to_save(conveyor: &Conveyor) -> save::MachineSaveDataV2 {
+    use save::*;
+
+    let direction = direction_to_save(conveyor.direction);
+    let shape = match conveyor.shape {
+        ConveyorShape::Straight => ConveyorShapeSave::Straight,
+        ConveyorShape::CornerLeft => ConveyorShapeSave::CornerLeft,
+        ConveyorShape::CornerRight => ConveyorShapeSave::CornerRight,
+        ConveyorShape::TJunction => ConveyorShapeSave::TJunction,
+        ConveyorShape::Splitter => ConveyorShapeSave::Splitter,
+    };
+    let items: Vec<ConveyorItemSaveV2> = conveyor
+        .items
+        .iter()
+        .map(|item| ConveyorItemSaveV2 {
+            item_id: item_id_to_string(item.item_id),
+            progress: item.progress,
+            lateral_offset: item.lateral_offset,
+            count: item.count,
+        })
+        .collect();
+
+    MachineSaveDataV2::Conveyor(ConveyorSaveDataV2 {
+        position: conveyor.position.into(),
+        direction,
+        shape,
+        items,
+        last_output_index: conveyor.last_output_index,
+        last_input_pos: conveyor.last_input_pos.map(Into::into),
+        enabled: conveyor.enabled,
+        speed_multiplier: conveyor.speed_multiplier,
+    })
+}
+
+/// Spawn the entity for a saved machine or conveyor
+pub fn spawn_saved_machine(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    machine: &save::MachineSaveDataV2,
+) {
+    match machine {
+        save::MachineSaveDataV2::Miner(miner_data) => {
+            let pos: IVec3 = miner_data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle =
+                MachineBundle::new_centered(&MINER, pos, direction_from_save(miner_data.facing));
+            bundle.machine.progress = miner_data.progress;
+            bundle.machine.enabled = miner_data.enabled;
+            bundle.machine.output_any_side = miner_data.output_any_side;
+            bundle.machine.slots.drill = miner_data.drill.as_ref().and_then(|drill| {
+                let item_id = string_id_to_item_id(&drill.item_id)?;
+                DrillHead::new(item_id).map(|head| DrillHead {
+                    durability: drill.durability,
+                    ..head
+                })
+            });
+            if let Some(buffer) = &miner_data.buffer {
+                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut() {
+                    if let Some(item_id) = string_id_to_item_id(&buffer.item_id) {
+                        output_slot.item_id = Some(item_id);
+                        output_slot.count = buffer.count;
+                    }
+                }
+            }
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: items::miner_block().color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+        save::MachineSaveDataV2::Conveyor(conveyor_data) => {
+            let pos: IVec3 = conveyor_data.position.into();
+            let direction = direction_from_save(conveyor_data.direction);
+            let shape = conveyor_shape_from_save(conveyor_data.shape);
+            let world_pos = Vec3::new(
+                pos.x as f32 + 0.5,
+                pos.y as f32 * BLOCK_SIZE + CONVEYOR_BELT_HEIGHT / 2.0,
+                pos.z as f32 + 0.5,
+            );
+
+            let items: Vec<ConveyorItem> = conveyor_data
+                .items
+                .iter()
+                .filter_map(|item| {
+                    string_id_to_item_id(&item.item_id).map(|item_id| {
+                        let mut ci = ConveyorItem::new(item_id, item.progress);
+                        ci.count = item.count.clamp(1, crate::constants::CONVEYOR_MAX_STACK);
+                        ci.lateral_offset = item.lateral_offset;
+                        ci.previous_lateral_offset = item.lateral_offset;
+                        ci
+                    })
+                })
+                .collect();
+
+            let mesh = meshes.add(create_conveyor_mesh(shape));
+
+            commands.spawn((
+                Conveyor {
+                    position: pos,
+                    direction,
+                    output_direction: direction, // Will be updated by update_conveyor_shapes
+                    items,
+                    last_output_index: conveyor_data.last_output_index,
+                    last_input_pos: conveyor_data.last_input_pos.map(Into::into),
+                    enabled: conveyor_data.enabled,
+                    shape,
+                    speed_multiplier: conveyor_data.speed_multiplier,
+                },
+                Mesh3d(mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: conveyor_tier_item(conveyor_data.speed_multiplier).color(),
+                    ..default()
+                })),
+                Transform::from_translation(world_pos).with_rotation(direction.to_rotation()),
+                GlobalTransform::default(),
+                Visibility::default(),
+                InheritedVisibility::default(),
+                ViewVisibility::default(),
+            ));
+        }
+        save::MachineSaveDataV2::Furnace(furnace_data) => {
+            let pos: IVec3 = furnace_data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle = MachineBundle::new_centered(
+                &FURNACE,
+                pos,
+                direction_from_save(furnace_data.facing),
+            );
+            bundle.machine.slots.fuel = furnace_data.fuel;
+            bundle.machine.progress = furnace_data.progress;
+            bundle.machine.enabled = furnace_data.enabled;
+            bundle.machine.output_any_side = furnace_data.output_any_side;
+            bundle.machine.pass_through_sides = furnace_data
+                .pass_through
+                .iter()
+                .map(|&side| direction_from_save(side))
+                .collect();
+            restore_slot_routes(&mut bundle.machine, &furnace_data.slot_routes);
+            if let Some(input) = &furnace_data.input {
+                if let Some(input_slot) = bundle.machine.slots.inputs.first_mut() {
+                    if let Some(item_id) = string_id_to_item_id(&input.item_id) {
+                        input_slot.item_id = Some(item_id);
+                        input_slot.count = input.count;
+                    }
+                }
+            }
+            if let Some(output) = &furnace_data.output {
+                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut() {
+                    if let Some(item_id) = string_id_to_item_id(&output.item_id) {
+                        output_slot.item_id = Some(item_id);
+                        output_slot.count = output.count;
+                    }
+                }
+            }
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: items::furnace_block().color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+        save::MachineSaveDataV2::Crusher(crusher_data) => {
+            let pos: IVec3 = crusher_data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle = MachineBundle::new_centered(
+                &CRUSHER,
+                pos,
+                direction_from_save(crusher_data.facing),
+            );
+            bundle.machine.progress = crusher_data.progress;
+            bundle.machine.enabled = crusher_data.enabled;
+            bundle.machine.output_any_side = crusher_data.output_any_side;
+            bundle.machine.pass_through_sides = crusher_data
+                .pass_through
+                .iter()
+                .map(|&side| direction_from_save(side))
+                .collect();
+            if let Some(input) = &crusher_data.input {
+                if let Some(input_slot) = bundle.machine.slots.inputs.first_mut() {
+                    if let Some(item_id) = string_id_to_item_id(&input.item_id) {
+                        input_slot.item_id = Some(item_id);
+                        input_slot.count = input.count;
+                    }
+                }
+            }
+            if let Some(output) = &crusher_data.output {
+                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut() {
+                    if let Some(item_id) = string_id_to_item_id(&output.item_id) {
+                        output_slot.item_id = Some(item_id);
+                        output_slot.count = output.count;
+                    }
+                }
+            }
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: items::crusher_block().color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+        save::MachineSaveDataV2::Recycler(recycler_data) => {
+            let pos: IVec3 = recycler_data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle = MachineBundle::new_centered(
+                &RECYCLER,
+                pos,
+                direction_from_save(recycler_data.facing),
+            );
+            bundle.machine.progress = recycler_data.progress;
+            bundle.machine.enabled = recycler_data.enabled;
+            bundle.machine.pass_through_sides = recycler_data
+                .pass_through
+                .iter()
+                .map(|&side| direction_from_save(side))
+                .collect();
+            let restore = |slot: &mut MachineSlot, stack: &save::ItemStackV2| {
+                if let Some(item_id) = string_id_to_item_id(&stack.item_id) {
+                    slot.item_id = Some(item_id);
+                    slot.count = stack.count;
+                }
+            };
+            if let (Some(input), Some(input_slot)) = (
+                &recycler_data.input,
+                bundle.machine.slots.inputs.first_mut(),
+            ) {
+                restore(input_slot, input);
+            }
+            for (output, output_slot) in recycler_data
+                .outputs
+                .iter()
+                .zip(bundle.machine.slots.outputs.iter_mut())
+            {
+                if let Some(output) = output {
+                    restore(output_slot, output);
+                }
+            }
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: items::recycler_block().color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+        save::MachineSaveDataV2::Hopper(hopper_data) => {
+            let pos: IVec3 = hopper_data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle =
+                MachineBundle::new_centered(&HOPPER, pos, direction_from_save(hopper_data.facing));
+            bundle.machine.progress = hopper_data.progress;
+            bundle.machine.enabled = hopper_data.enabled;
+            bundle.machine.push_side = hopper_data.push_side.map(direction_from_save);
+            bundle.machine.pass_through_sides = hopper_data
+                .pass_through
+                .iter()
+                .map(|&side| direction_from_save(side))
+                .collect();
+            for (stack, slot) in hopper_data
+                .slots
+                .iter()
+                .zip(bundle.machine.slots.inputs.iter_mut())
+            {
+                let Some(stack) = stack else {
+                    continue;
+                };
+                if let Some(item_id) = string_id_to_item_id(&stack.item_id) {
+                    slot.item_id = Some(item_id);
+                    slot.count = stack.count;
+                }
+            }
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: items::hopper_block().color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+        save::MachineSaveDataV2::DataMachine(data) => {
+            let Some((item_id, spec)) = string_id_to_item_id(&data.machine_id)
+                .and_then(|id| Some((id, crate::game_spec::data_machine_spec(id)?)))
+            else {
+                warn!(
+                    "[SAVE] Skipping machine '{}': not defined by any loaded mod",
+                    data.machine_id
+                );
+                return;
+            };
+            let pos: IVec3 = data.position.into();
+
+            let cube_mesh = meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let mut bundle =
+                MachineBundle::new_centered(spec, pos, direction_from_save(data.facing));
+            bundle.machine.slots.fuel = data.fuel;
+            bundle.machine.progress = data.progress;
+            bundle.machine.enabled = data.enabled;
+            bundle.machine.output_any_side = data.output_any_side;
+            bundle.machine.pass_through_sides = data
+                .pass_through
+                .iter()
+                .map(|&side| direction_from_save(side))
+                .collect();
+            restore_slot_routes(&mut bundle.machine, &data.slot_routes);
+            let restore = |slots: &mut [MachineSlot], stacks: &[Option<save::ItemStackV2>]| {
+                for (slot, stack) in slots.iter_mut().zip(stacks) {
+                    let Some(stack) = stack else {
+                        continue;
+                    };
+                    if let Some(item_id) = string_id_to_item_id(&stack.item_id) {
+                        slot.item_id = Some(item_id);
+                        slot.count = stack.count;
+                    }
+                }
+            };
+            restore(&mut bundle.machine.slots.inputs, &data.inputs);
+            restore(&mut bundle.machine.slots.outputs, &data.outputs);
+            commands.spawn((
+                Mesh3d(cube_mesh),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: item_id.color(),
+                    ..default()
+                })),
+                bundle,
+            ));
+        }
+    }
+}
+
+/// Convert Direction to save format
+pub fn direction_to_save(dir: Direction) -> save::DirectionSave {
+    match dir {
+        Direction::North => save::DirectionSave::North,
+        Direction::South => save::DirectionSave::South,
+        Direction::East => save::DirectionSave::East,
+        Direction::West => save::DirectionSave::West,
     }
 }
 
@@ -261,85 +997,287 @@ pub fn conveyor_shape_from_save(shape: save::ConveyorShapeSave) -> ConveyorShape
     }
 }
 
-/// Auto-save system - saves game every minute
+/// Save slot of the world being played, chosen in the main menu
+///
+/// Auto-saves go to `filename`; `info` is written into every save.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ActiveWorld {
+    pub filename: String,
+    pub info: save::WorldInfoSaveDataV2,
+}
+
+impl Default for ActiveWorld {
+    fn default() -> Self {
+        Self {
+            filename: "autosave".to_string(),
+            info: save::WorldInfoSaveDataV2::default(),
+        }
+    }
+}
+
+/// Auto-save system - saves the active world every `autosave_interval_secs`
 pub fn auto_save_system(
     time: Res<Time>,
     mut auto_save_timer: ResMut<save::AutoSaveTimer>,
+    active_world: Option<Res<ActiveWorld>>,
     mut save_events: MessageWriter<SaveGameEvent>,
 ) {
+    // A paused timer (autosave off) doesn't tick or finish
     auto_save_timer.timer.tick(time.delta());
 
     if auto_save_timer.timer.just_finished() {
-        save_events.write(SaveGameEvent {
-            filename: "autosave".to_string(),
-        });
+        let filename = active_world
+            .map(|world| world.filename.clone())
+            .unwrap_or_else(|| ActiveWorld::default().filename);
+        save_events.write(SaveGameEvent { filename });
         info!("Auto-save triggered");
     }
 }
 
+/// Count time spent in the active world
+pub fn tick_world_playtime(time: Res<Time>, mut active_world: ResMut<ActiveWorld>) {
+    active_world.info.playtime_secs += time.delta_secs_f64();
+}
+
+/// Save being written in the background (at most one at a time)
+#[derive(Resource, Default)]
+pub struct SaveTask {
+    in_flight: Option<(String, Task<Result<(), String>>)>,
+}
+
+impl SaveTask {
+    pub fn is_running(&self) -> bool {
+        self.in_flight.is_some()
+    }
+}
+
+/// Directory saves are written to (tests point it at a temp dir)
+#[derive(Resource, Debug, Clone)]
+pub struct SaveDir(pub std::path::PathBuf);
+
+impl Default for SaveDir {
+    fn default() -> Self {
+        Self(save::native::get_save_dir())
+    }
+}
+
+/// Bundled background save task, indicator, last save time and save/load message (reduces parameter count)
+#[derive(SystemParam)]
+pub struct SaveStatus<'w> {
+    pub task: ResMut<'w, SaveTask>,
+    pub indicator: ResMut<'w, SaveIndicator>,
+    pub last_save: ResMut<'w, LastSave>,
+    pub save_load_state: ResMut<'w, SaveLoadState>,
+    pub dir: Res<'w, SaveDir>,
+    pub time: Res<'w, Time<Real>>,
+}
+
+impl SaveStatus<'_> {
+    /// Directory to write saves to
+    fn save_dir(&self) -> std::path::PathBuf {
+        self.dir.0.clone()
+    }
+
+    /// Record a finished save
+    fn finish(&mut self, filename: &str, result: Result<(), String>) {
+        let msg = match &result {
+            Ok(()) => format!("Game saved to '{}'", filename),
+            Err(e) => format!("Failed to save: {}", e),
+        };
+        info!(category = "SAVE", action = "save", "{}", msg);
+        self.save_load_state.last_message = Some(msg);
+        self.indicator.finished(&result);
+        if result.is_ok() {
+            self.last_save.record(self.time.elapsed_secs_f64());
+        }
+    }
+}
+
+/// Serialize and write a save snapshot on the async compute pool
+pub fn spawn_background_save(
+    data: save::SaveDataV2,
+    dir: std::path::PathBuf,
+    filename: String,
+) -> Task<Result<(), String>> {
+    AsyncComputeTaskPool::get().spawn(async move {
+        let json = save::native::serialize_save_v2(&data)?;
+        save::native::write_save_in(&dir, &filename, &json)
+    })
+}
+
+/// Everything a save snapshot is collected from (reduces parameter count)
+#[derive(SystemParam)]
+pub struct SaveSources<'w, 's> {
+    player_query: Query<'w, 's, (&'static Transform, Option<&'static SpawnPoint>), With<Player>>,
+    camera_query: Query<'w, 's, &'static PlayerCamera>,
+    local_player: Option<Res<'w, LocalPlayer>>,
+    inventory_query: Query<'w, 's, &'static PlayerInventory>,
+    world_data: Res<'w, WorldData>,
+    machine_query: Query<'w, 's, &'static Machine>,
+    conveyor_query: Query<'w, 's, &'static Conveyor>,
+    delivery_query: Query<'w, 's, (&'static DeliveryPlatform, &'static DeliveryContract)>,
+    filter_query: Query<'w, 's, (&'static DeliveryPlatform, &'static DeliveryFilter)>,
+    display_query: Query<'w, 's, &'static DisplayPanel>,
+    sign_query: Query<'w, 's, &'static Sign>,
+    current_quest: Res<'w, CurrentQuest>,
+    creative_mode: Res<'w, CreativeMode>,
+    platform_inventory: LocalPlatformInventory<'w, 's>,
+    progress: ProgressRes<'w>,
+    fluid_networks: Res<'w, FluidNetworks>,
+}
+
+impl SaveSources<'_, '_> {
+    /// Collect the current game state (None before the local player and platform exist)
+    pub fn snapshot(&self) -> Option<save::SaveDataV2> {
+        let local_player = self.local_player.as_ref()?;
+        let inventory = self.inventory_query.get(local_player.0).ok()?;
+        let Some(platform_inv) = self.platform_inventory.get() else {
+            info!("[SAVE] No platform inventory found");
+            return None;
+        };
+
+        let progress = &self.progress;
+        let mut save_data = collect_save_data(
+            &self.player_query,
+            &self.camera_query,
+            inventory,
+            &self.world_data,
+            &self.machine_query,
+            &self.conveyor_query,
+            &self.delivery_query,
+            &self.filter_query,
+            &self.display_query,
+            &self.current_quest,
+            &self.creative_mode,
+            platform_inv,
+            &progress.clock,
+            &self.fluid_networks,
+            stats_to_save(&progress.stats, &progress.achievements),
+            progress.content_version.as_ref().and_then(|v| v.0.clone()),
+            progress
+                .mod_data
+                .as_ref()
+                .map(|data| data.mod_content_hashes())
+                .unwrap_or_default(),
+            progress.recent_placeables.as_deref(),
+            progress.timed_contracts.as_deref(),
+            progress.waypoints.as_deref(),
+        );
+        save_data.signs = self.sign_query.iter().map(sign_to_save).collect();
+        if let Some(protection) = progress.spawn_protection.as_ref() {
+            save_data.mode.spawn_protection = protection.enabled;
+        }
+        if let Some(world) = progress.active_world.as_ref() {
+            save_data.world_info = world.info.clone();
+        }
+        Some(save_data)
+    }
+
+    /// Save slot of the world being played
+    pub fn active_filename(&self) -> String {
+        self.progress
+            .active_world
+            .as_ref()
+            .map(|world| world.filename.clone())
+            .unwrap_or_else(|| ActiveWorld::default().filename)
+    }
+}
+
 /// Handle save game events
-#[allow(clippy::too_many_arguments)]
+///
+/// Game state is collected into `SaveDataV2` here on the main thread; the
+/// JSON serialization and file write run in the background (`SaveTask`).
+/// A save requested while another one is still being written is skipped.
 pub fn handle_save_event(
     mut events: MessageReader<SaveGameEvent>,
-    player_query: Query<&Transform, With<Player>>,
-    camera_query: Query<&PlayerCamera>,
-    local_player: Option<Res<LocalPlayer>>,
-    inventory_query: Query<&PlayerInventory>,
-    world_data: Res<WorldData>,
-    machine_query: Query<&Machine>,
-    conveyor_query: Query<&Conveyor>,
-    delivery_query: Query<&DeliveryPlatform>,
-    current_quest: Res<CurrentQuest>,
-    creative_mode: Res<CreativeMode>,
-    platform_inventory: LocalPlatformInventory,
-    mut save_load_state: ResMut<SaveLoadState>,
+    sources: SaveSources,
+    mut status: SaveStatus,
 ) {
-    // Get local player's inventory
-    let Some(local_player) = local_player else {
+    for event in events.read() {
+        if status.task.is_running() {
+            let msg = format!(
+                "Save to '{}' skipped: another save is still being written",
+                event.filename
+            );
+            info!("{}", msg);
+            status.save_load_state.last_message = Some(msg);
+            continue;
+        }
+
+        let Some(save_data) = sources.snapshot() else {
+            return;
+        };
+        let task = spawn_background_save(save_data, status.save_dir(), event.filename.clone());
+        status.task.in_flight = Some((event.filename.clone(), task));
+        status.indicator.saving();
+    }
+}
+
+/// Save the active world synchronously when the app is about to exit
+///
+/// Runs in `Last`, so an `AppExit` written anywhere this frame (window close,
+/// pause menu Quit, updater restart) is seen before the runner stops. Any
+/// background save is finished first so the two writes can't interleave.
+pub fn save_on_exit(
+    exit_events: MessageReader<AppExit>,
+    sources: SaveSources,
+    mut status: SaveStatus,
+) {
+    if exit_events.is_empty() {
         return;
-    };
-    let Ok(inventory) = inventory_query.get(local_player.0) else {
+    }
+    if let Some((filename, task)) = status.task.in_flight.take() {
+        info!(
+            "[SAVE] Waiting for the save to '{}' before exiting",
+            filename
+        );
+        let result = future::block_on(task);
+        status.finish(&filename, result);
+    }
+
+    let Some(save_data) = sources.snapshot() else {
         return;
     };
+    let filename = sources.active_filename();
+    let result = save::native::serialize_save_v2(&save_data)
+        .and_then(|json| save::native::write_save_in(&status.save_dir(), &filename, &json));
+    status.finish(&filename, result);
+}
 
-    // Get platform inventory
-    let Some(platform_inv) = platform_inventory.get() else {
-        info!("[SAVE] No platform inventory found");
+/// Pick up the result of the background save once it is written
+pub fn poll_save_task(mut status: SaveStatus) {
+    let Some((filename, task)) = status.task.in_flight.as_mut() else {
         return;
     };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    let filename = filename.clone();
+    status.task.in_flight = None;
+    status.finish(&filename, result);
+}
 
-    for event in events.read() {
-        let save_data = collect_save_data(
-            &player_query,
-            &camera_query,
-            inventory,
-            &world_data,
-            &machine_query,
-            &conveyor_query,
-            &delivery_query,
-            &current_quest,
-            &creative_mode,
-            platform_inv,
-        );
-
-        match save::native::save_game_v2(&save_data, &event.filename) {
-            Ok(()) => {
-                let msg = format!("Game saved to '{}'", event.filename);
-                info!("{}", msg);
-                save_load_state.last_message = Some(msg);
-            }
-            Err(e) => {
-                let msg = format!("Failed to save: {}", e);
-                info!("{}", msg);
-                save_load_state.last_message = Some(msg);
-            }
-        }
+/// Finish an in-flight save before a load reads the save files
+///
+/// Runs right before handle_load_event; blocks only when both happen at once.
+pub fn wait_for_save_before_load(events: MessageReader<LoadGameEvent>, mut status: SaveStatus) {
+    if events.is_empty() {
+        return;
     }
+    let Some((filename, task)) = status.task.in_flight.take() else {
+        return;
+    };
+    info!(
+        "[SAVE] Waiting for the save to '{}' before loading",
+        filename
+    );
+    let result = future::block_on(task);
+    status.finish(&filename, result);
 }
 
-/// Helper to parse string ID to ItemId
+/// Helper to parse string ID ("base:iron_ore", "mymod:gear" or "iron_ore") to ItemId
 fn string_id_to_item_id(s: &str) -> Option<ItemId> {
-    crate::core::items::by_name(s)
+    crate::core::items::by_string_id(s).or_else(|| crate::core::items::by_name(s))
 }
 
 /// Handle load game events (V2 format with string IDs)
@@ -350,7 +1288,7 @@ pub fn handle_load_event(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut player_query: Query<&mut Transform, With<Player>>,
+    mut player_query: Query<(&mut Transform, Option<&mut SpawnPoint>), With<Player>>,
     mut camera_query: Query<&mut PlayerCamera>,
     local_player: Option<Res<LocalPlayer>>,
     mut inventory_query: Query<&mut PlayerInventory>,
@@ -358,8 +1296,20 @@ pub fn handle_load_event(
     mut current_quest: ResMut<CurrentQuest>,
     mut creative_mode: ResMut<CreativeMode>,
     mut platform_inventory: LocalPlatformInventory,
-    // All machine entities to despawn (combined query)
-    machine_entities: Query<Entity, Or<(With<Machine>, With<Conveyor>)>>,
+    mut progress: ProgressResMut,
+    mut fluid_networks: ResMut<FluidNetworks>,
+    // All machine entities to despawn (combined query, includes pad platforms, display panels, lamps and signs)
+    machine_entities: Query<
+        Entity,
+        Or<(
+            With<Machine>,
+            With<Conveyor>,
+            With<PadPlatform>,
+            With<DisplayPanel>,
+            With<Lamp>,
+            With<Sign>,
+        )>,
+    >,
 ) {
     // Get local player's inventory
     let Some(local_player) = local_player else {
@@ -384,11 +1334,20 @@ pub fn handle_load_event(
     };
 
     for event in events.read() {
-        match save::native::load_game_v2(&event.filename) {
-            Ok(data) => {
+        let loaded = super::storage::load_game_v2(&event.filename)
+            .and_then(|data| data.world.modified().map(|blocks| (blocks, data)));
+        match loaded {
+            Ok((modified_blocks, data)) => {
                 // Apply player position
-                if let Ok(mut transform) = player_query.single_mut() {
+                if let Ok((mut transform, spawn_point)) = player_query.single_mut() {
                     transform.translation = data.player.position.into();
+                    if let Some(mut spawn_point) = spawn_point {
+                        *spawn_point = data
+                            .player
+                            .spawn_point
+                            .map(|p| SpawnPoint(p.into()))
+                            .unwrap_or_default();
+                    }
                 }
 
                 // Apply camera rotation
@@ -420,11 +1379,36 @@ pub fn handle_load_event(
                 }
 
                 // Apply world modifications (V2 format with string IDs)
+                // Blocks of unknown (e.g. removed mod) items become inert stone placeholders
                 world_data.modified_blocks.clear();
-                for (key, block_opt) in &data.world.modified_blocks {
-                    if let Some(pos) = save::WorldSaveDataV2::key_to_pos(key) {
-                        let block = block_opt.as_ref().and_then(|id| items::by_name(id));
-                        world_data.modified_blocks.insert(pos, block);
+                let mut placeholders = 0;
+                for (pos, block_opt) in modified_blocks {
+                    let block = block_opt.map(|id| {
+                        string_id_to_item_id(&id).unwrap_or_else(|| {
+                            placeholders += 1;
+                            items::stone()
+                        })
+                    });
+                    world_data.modified_blocks.insert(pos, block);
+                }
+                if placeholders > 0 {
+                    warn!(
+                        "[SAVE] {} blocks of unknown items replaced with stone",
+                        placeholders
+                    );
+                }
+
+                // Rebuild fluid networks from placed pipes/tanks, then restore amounts
+                fluid_networks.rebuild(world_data.modified_blocks.iter().filter_map(
+                    |(pos, block)| {
+                        block
+                            .filter(|id| items::is_fluid_block(*id))
+                            .map(|id| (*pos, fluids::block_capacity(id)))
+                    },
+                ));
+                for network in &data.fluids {
+                    if let Some(fluid) = crate::core::fluids::by_string_id(&network.fluid) {
+                        fluid_networks.restore(network.position.into(), fluid, network.amount);
                     }
                 }
 
@@ -433,164 +1417,82 @@ pub fn handle_load_event(
                     commands.entity(entity).despawn();
                 }
 
-                // Spawn machines from save data (V2 format)
-                for machine in &data.machines {
-                    match machine {
-                        save::MachineSaveDataV2::Miner(miner_data) => {
-                            let pos: IVec3 = miner_data.position.into();
-
-                            let cube_mesh =
-                                meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                            let mut bundle =
-                                MachineBundle::new_centered(&MINER, pos, Direction::North);
-                            bundle.machine.progress = miner_data.progress;
-                            if let Some(buffer) = &miner_data.buffer {
-                                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut()
-                                {
-                                    if let Some(item_id) = string_id_to_item_id(&buffer.item_id) {
-                                        output_slot.item_id = Some(item_id);
-                                        output_slot.count = buffer.count;
-                                    }
-                                }
-                            }
-                            commands.spawn((
-                                Mesh3d(cube_mesh),
-                                MeshMaterial3d(materials.add(StandardMaterial {
-                                    base_color: items::miner_block().color(),
-                                    ..default()
-                                })),
-                                bundle,
-                            ));
-                        }
-                        save::MachineSaveDataV2::Conveyor(conveyor_data) => {
-                            let pos: IVec3 = conveyor_data.position.into();
-                            let direction = direction_from_save(conveyor_data.direction);
-                            let shape = conveyor_shape_from_save(conveyor_data.shape);
-                            let world_pos = Vec3::new(
-                                pos.x as f32 + 0.5,
-                                pos.y as f32 + 0.5,
-                                pos.z as f32 + 0.5,
-                            );
-
-                            let items: Vec<ConveyorItem> = conveyor_data
-                                .items
-                                .iter()
-                                .filter_map(|item| {
-                                    string_id_to_item_id(&item.item_id).map(|item_id| {
-                                        let mut ci = ConveyorItem::new(item_id, item.progress);
-                                        ci.lateral_offset = item.lateral_offset;
-                                        ci.previous_lateral_offset = item.lateral_offset;
-                                        ci
-                                    })
-                                })
-                                .collect();
-
-                            // Use simple cuboid mesh for now
-                            let mesh = meshes.add(Cuboid::new(
-                                BLOCK_SIZE * 0.9,
-                                BLOCK_SIZE * 0.2,
-                                BLOCK_SIZE,
-                            ));
-
-                            commands.spawn((
-                                Conveyor {
-                                    position: pos,
-                                    direction,
-                                    output_direction: direction, // Will be updated by update_conveyor_shapes
-                                    items,
-                                    last_output_index: conveyor_data.last_output_index,
-                                    last_input_source: conveyor_data.last_input_source,
-                                    shape,
-                                },
-                                Mesh3d(mesh),
-                                MeshMaterial3d(materials.add(StandardMaterial {
-                                    base_color: items::conveyor_block().color(),
-                                    ..default()
-                                })),
-                                Transform::from_translation(world_pos)
-                                    .with_rotation(direction.to_rotation()),
-                                GlobalTransform::default(),
-                                Visibility::default(),
-                                InheritedVisibility::default(),
-                                ViewVisibility::default(),
-                            ));
-                        }
-                        save::MachineSaveDataV2::Furnace(furnace_data) => {
-                            let pos: IVec3 = furnace_data.position.into();
-
-                            let cube_mesh =
-                                meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                            let mut bundle =
-                                MachineBundle::new_centered(&FURNACE, pos, Direction::North);
-                            bundle.machine.slots.fuel = furnace_data.fuel;
-                            bundle.machine.progress = furnace_data.progress;
-                            if let Some(input) = &furnace_data.input {
-                                if let Some(input_slot) = bundle.machine.slots.inputs.first_mut() {
-                                    if let Some(item_id) = string_id_to_item_id(&input.item_id) {
-                                        input_slot.item_id = Some(item_id);
-                                        input_slot.count = input.count;
-                                    }
-                                }
-                            }
-                            if let Some(output) = &furnace_data.output {
-                                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut()
-                                {
-                                    if let Some(item_id) = string_id_to_item_id(&output.item_id) {
-                                        output_slot.item_id = Some(item_id);
-                                        output_slot.count = output.count;
-                                    }
-                                }
-                            }
-                            commands.spawn((
-                                Mesh3d(cube_mesh),
-                                MeshMaterial3d(materials.add(StandardMaterial {
-                                    base_color: items::furnace_block().color(),
-                                    ..default()
-                                })),
-                                bundle,
-                            ));
-                        }
-                        save::MachineSaveDataV2::Crusher(crusher_data) => {
-                            let pos: IVec3 = crusher_data.position.into();
-
-                            let cube_mesh =
-                                meshes.add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                            let mut bundle =
-                                MachineBundle::new_centered(&CRUSHER, pos, Direction::North);
-                            bundle.machine.progress = crusher_data.progress;
-                            if let Some(input) = &crusher_data.input {
-                                if let Some(input_slot) = bundle.machine.slots.inputs.first_mut() {
-                                    if let Some(item_id) = string_id_to_item_id(&input.item_id) {
-                                        input_slot.item_id = Some(item_id);
-                                        input_slot.count = input.count;
-                                    }
-                                }
-                            }
-                            if let Some(output) = &crusher_data.output {
-                                if let Some(output_slot) = bundle.machine.slots.outputs.first_mut()
-                                {
-                                    if let Some(item_id) = string_id_to_item_id(&output.item_id) {
-                                        output_slot.item_id = Some(item_id);
-                                        output_slot.count = output.count;
-                                    }
-                                }
-                            }
-                            commands.spawn((
-                                Mesh3d(cube_mesh),
-                                MeshMaterial3d(materials.add(StandardMaterial {
-                                    base_color: items::crusher_block().color(),
-                                    ..default()
-                                })),
-                                bundle,
-                            ));
-                        }
+                // The starter platform stays; only its filter comes from the save
+                if let Some(starter) = platform_inventory.entity() {
+                    let origin = world_data.gen_config.platform.origin();
+                    commands
+                        .entity(starter)
+                        .insert(filter_from_save(origin, &data.delivery_filters));
+                }
+
+                // Rebuild pad platforms from placed pads, then restore contracts and filters
+                let pads: HashSet<IVec3> = world_data
+                    .modified_blocks
+                    .iter()
+                    .filter(|(_, block)| **block == Some(items::delivery_pad()))
+                    .map(|(pos, _)| *pos)
+                    .collect();
+                for origin in delivery_pad::detect_formations(&pads) {
+                    let mut platform = commands.spawn(delivery_pad::pad_platform(origin));
+                    platform.insert(filter_from_save(origin, &data.delivery_filters));
+                    let contract = data
+                        .delivery_contracts
+                        .iter()
+                        .find(|c| IVec3::from(c.origin) == origin)
+                        .and_then(|c| {
+                            string_id_to_item_id(&c.item)
+                                .map(|item| DeliveryContract::new(item, c.target_per_min))
+                        });
+                    if let Some(contract) = contract {
+                        platform.insert(contract);
+                    }
+                }
+
+                // Rebuild display panels from placed panel blocks, then restore configs
+                for (pos, block) in &world_data.modified_blocks {
+                    if *block != Some(items::display_panel()) {
+                        continue;
+                    }
+                    let saved = data
+                        .display_panels
+                        .iter()
+                        .find(|p| IVec3::from(p.position) == *pos);
+                    let panel = match saved {
+                        Some(p) => DisplayPanel::new(*pos, direction_from_save(p.face))
+                            .with_config(
+                                p.item.as_deref().and_then(string_id_to_item_id),
+                                DisplaySource::from_id(&p.source).unwrap_or_default(),
+                            ),
+                        None => DisplayPanel::new(*pos, Direction::South),
+                    };
+                    commands.spawn(panel);
+                }
+
+                // Rebuild lamps from placed lamp blocks
+                for (pos, block) in &world_data.modified_blocks {
+                    if *block == Some(items::lamp_block()) {
+                        commands.spawn(Lamp::new(*pos));
+                    }
+                }
+
+                // Rebuild signs from placed sign blocks, then restore their text
+                for (pos, block) in &world_data.modified_blocks {
+                    if *block == Some(items::sign_block()) {
+                        commands.spawn(sign_from_save(*pos, &data.signs));
                     }
                 }
 
+                // Spawn machines from save data (V2 format); none are hibernated yet
+                world_data.hibernated_machines.clear();
+                for machine in &data.machines {
+                    spawn_saved_machine(&mut commands, &mut meshes, &mut materials, machine);
+                }
+
                 // Apply quest progress (V2 format)
                 current_quest.index = data.quests.current_index;
                 current_quest.completed = data.quests.completed;
                 current_quest.rewards_claimed = data.quests.rewards_claimed;
+                current_quest.claimed_at = data.quests.claimed_at.clone();
 
                 // Note: quests.delivered is now empty in V2 format
                 // PlatformInventory is loaded from platform_inventory above
@@ -598,15 +1500,175 @@ pub fn handle_load_event(
                 // Apply game mode
                 creative_mode.enabled = data.mode.creative;
 
-                let msg = format!("Game loaded from '{}'", event.filename);
-                info!("{}", msg);
+                // Apply day/night clock
+                progress.clock.time_of_day = data.clock.time_of_day.rem_euclid(1.0);
+                progress.clock.day = data.clock.day;
+
+                // Quick-select history
+                if let Some(recent) = progress.recent_placeables.as_mut() {
+                    recent.items = data
+                        .inventory
+                        .recent_placeables
+                        .iter()
+                        .filter_map(|id| string_id_to_item_id(id))
+                        .take(QUICK_SELECT_SEGMENTS)
+                        .collect();
+                }
+
+                // Timed contracts (deadlines are in ticks, so they resume where they were)
+                if let Some(contracts) = progress.timed_contracts.as_mut() {
+                    **contracts = timed_contracts_from_save(&data.timed_contracts);
+                }
+
+                // Waypoints belong to the save slot
+                if let Some(waypoints) = progress.waypoints.as_mut() {
+                    **waypoints = waypoints_from_save(&data.waypoints);
+                }
+                if let Some(protection) = progress.spawn_protection.as_mut() {
+                    protection.enabled = data.mode.spawn_protection;
+                }
+
+                // Later auto-saves go to the loaded slot, keeping its name and playtime
+                if let Some(world) = progress.active_world.as_mut() {
+                    world.filename = event.filename.clone();
+                    world.info = data.world_info.clone();
+                }
+
+                // Merge stats and achievements (never reset by an older save)
+                merge_saved_stats(&data.stats, &mut progress.stats, &mut progress.achievements);
+
+                let mut msg = format!("Game loaded from '{}'", event.filename);
+                info!(category = "SAVE", action = "load", "{}", msg);
+
+                // Chunks regenerate from the current worldgen config, so a different
+                // config means unmodified terrain won't match the saved world
+                let current_hash = world_data.gen_config.config_hash();
+                if data.worldgen_hash.is_some_and(|hash| hash != current_hash) {
+                    let warning =
+                        "World was generated with different worldgen settings; terrain may differ";
+                    warn!("{} ({:016x})", warning, current_hash);
+                    msg = format!("{} (warning: {})", msg, warning);
+                }
+                let loaded_mods = progress
+                    .mod_data
+                    .as_ref()
+                    .map(|mod_data| mod_data.mod_content_hashes())
+                    .unwrap_or_default();
+                for warning in data.mod_content_warnings(&loaded_mods) {
+                    warn!("{}", warning);
+                    msg = format!("{} (warning: {})", msg, warning);
+                }
                 save_load_state.last_message = Some(msg);
             }
             Err(e) => {
                 let msg = format!("Failed to load '{}': {}", event.filename, e);
-                info!("{}", msg);
+                info!(category = "SAVE", action = "load", "{}", msg);
                 save_load_state.last_message = Some(msg);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::LocalPlatform;
+
+    /// Headless app with everything a save snapshot reads and `save_on_exit` in Last
+    fn exit_save_app(dir: &std::path::Path) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(SaveDir(dir.to_path_buf()))
+            .insert_resource(ActiveWorld {
+                filename: "world_1".to_string(),
+                info: save::WorldInfoSaveDataV2 {
+                    name: "Exit Test".to_string(),
+                    ..Default::default()
+                },
+            })
+            .init_resource::<WorldData>()
+            .init_resource::<CurrentQuest>()
+            .init_resource::<CreativeMode>()
+            .init_resource::<FluidNetworks>()
+            .init_resource::<GameClock>()
+            .init_resource::<PlayerStats>()
+            .init_resource::<PlayerAchievements>()
+            .init_resource::<SaveTask>()
+            .init_resource::<SaveIndicator>()
+            .init_resource::<LastSave>()
+            .init_resource::<SaveLoadState>()
+            .add_systems(Last, save_on_exit);
+
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::default(), PlayerInventory::default()))
+            .id();
+        let platform = app.world_mut().spawn(PlatformInventory::new()).id();
+        app.insert_resource(LocalPlayer(player))
+            .insert_resource(LocalPlatform(platform));
+        app
+    }
+
+    #[test]
+    fn test_app_exit_saves_active_world() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = exit_save_app(dir.path());
+
+        // Nothing is written while the game keeps running
+        app.update();
+        assert!(save::native::list_worlds_in(dir.path()).is_empty());
+
+        app.world_mut().write_message(AppExit::Success);
+        app.update();
+
+        // Written by the time the frame with AppExit ends
+        let worlds = save::native::list_worlds_in(dir.path());
+        assert_eq!(worlds.len(), 1);
+        assert_eq!(worlds[0].filename, "world_1");
+        assert_eq!(worlds[0].info.name, "Exit Test");
+        assert!(app.world().resource::<LastSave>().at_secs.is_some());
+    }
+
+    #[test]
+    fn test_sign_round_trip() {
+        let pos = IVec3::new(5, 9, 4);
+        let sign = Sign::new(pos, Direction::East).with_text("smelting row\ncopper line");
+        let saved = vec![sign_to_save(&sign)];
+
+        let json = serde_json::to_string(&saved).unwrap();
+        let loaded: Vec<save::SignSaveDataV2> = serde_json::from_str(&json).unwrap();
+        assert_eq!(sign_from_save(pos, &loaded), sign);
+
+        // Text edited past the limit in the save file is clamped on load
+        let mut overfull = loaded.clone();
+        overfull[0].text = "x".repeat(200);
+        assert_eq!(
+            sign_from_save(pos, &overfull).text.chars().count(),
+            crate::sign::SIGN_MAX_CHARS
+        );
+
+        // A sign block without saved text comes back blank
+        assert_eq!(
+            sign_from_save(IVec3::ZERO, &loaded),
+            Sign::new(IVec3::ZERO, Direction::South)
+        );
+    }
+
+    #[test]
+    fn test_delivery_filter_round_trip() {
+        let origin = IVec3::new(20, 8, 10);
+        let mut filter = DeliveryFilter::new(DeliveryPolicy::Whitelist(vec![items::iron_ingot()]));
+        filter.reject(items::stone(), 3);
+        let saved = vec![filter_to_save(origin, &filter)];
+
+        let restored = filter_from_save(origin, &saved);
+        assert_eq!(restored.policy, filter.policy);
+        assert_eq!(restored.buffer, vec![(items::stone(), 3)]);
+
+        // Other platforms keep the default
+        assert_eq!(
+            filter_from_save(IVec3::ZERO, &saved),
+            DeliveryFilter::default()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Save on page unload (WASM)
+//!
+//! `beforeunload` can't wait for a save, so the snapshot is serialized ahead
+//! of time: `prepare_unload_snapshot` refreshes it every
+//! `UNLOAD_SNAPSHOT_SECS`, and the unload handler only writes the prepared
+//! JSON to `localStorage`. Best effort: changes since the last refresh are lost.
+//!
+//! The snapshots are also the web's saves: the main menu lists them
+//! (`list_worlds`) when it opens at startup, and `LoadGameEvent` reads them back
+//! with `load_game_v2`, so Continue resumes the world the tab was closed on.
+
+use super::format as save;
+use super::systems::SaveSources;
+use super::{SaveDataV2, WorldSlotInfo};
+use bevy::prelude::*;
+use std::cell::RefCell;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+
+/// Seconds between snapshot refreshes
+pub const UNLOAD_SNAPSHOT_SECS: f32 = 5.0;
+
+/// `localStorage` key prefix; the save filename is appended
+pub const STORAGE_KEY_PREFIX: &str = "idle_factory_save_";
+
+thread_local! {
+    /// (filename, save JSON) written by the unload handler
+    static UNLOAD_SNAPSHOT: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+/// Register the `beforeunload` handler on the page
+pub fn install_unload_hook() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let handler = Closure::wrap(Box::new(flush_unload_snapshot) as Box<dyn FnMut()>);
+    if let Err(e) =
+        window.add_event_listener_with_callback("beforeunload", handler.as_ref().unchecked_ref())
+    {
+        warn!("[SAVE] Could not hook beforeunload: {:?}", e);
+    }
+    // The handler lives as long as the page
+    handler.forget();
+}
+
+/// Write the prepared snapshot to `localStorage`
+fn flush_unload_snapshot() {
+    let Some((filename, json)) = UNLOAD_SNAPSHOT.with(|s| s.borrow_mut().take()) else {
+        return;
+    };
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let _ = storage.set_item(&storage_key(&filename), &json);
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+fn storage_key(filename: &str) -> String {
+    format!("{}{}", STORAGE_KEY_PREFIX, filename)
+}
+
+/// Worlds with a snapshot in `localStorage`, most recently played first
+pub fn list_worlds() -> Vec<WorldSlotInfo> {
+    let Some(storage) = local_storage() else {
+        return Vec::new();
+    };
+    let len = storage.length().unwrap_or(0);
+    let mut worlds: Vec<WorldSlotInfo> = (0..len)
+        .filter_map(|i| storage.key(i).ok().flatten())
+        .filter_map(|key| {
+            let filename = key.strip_prefix(STORAGE_KEY_PREFIX)?.to_string();
+            let json = storage.get_item(&key).ok().flatten()?;
+            save::native::world_slot(filename, &json)
+        })
+        .collect();
+
+    worlds.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    worlds
+}
+
+/// Load the snapshot saved for `filename`
+pub fn load_game_v2(filename: &str) -> Result<SaveDataV2, String> {
+    let json = local_storage()
+        .and_then(|storage| storage.get_item(&storage_key(filename)).ok().flatten())
+        .ok_or_else(|| format!("Save file not found: {}", filename))?;
+
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse save data: {}", e))
+}
+
+/// Delete the snapshot saved for `filename`
+pub fn delete_save(filename: &str) -> Result<(), String> {
+    let storage = local_storage().ok_or_else(|| "No web storage available".to_string())?;
+    storage
+        .remove_item(&storage_key(filename))
+        .map_err(|e| format!("Failed to delete save: {:?}", e))
+}
+
+/// Refresh the snapshot the unload handler flushes
+pub fn prepare_unload_snapshot(
+    time: Res<Time>,
+    mut timer: Local<Option<Timer>>,
+    sources: SaveSources,
+) {
+    let timer = timer
+        .get_or_insert_with(|| Timer::from_seconds(UNLOAD_SNAPSHOT_SECS, TimerMode::Repeating));
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    let Some(save_data) = sources.snapshot() else {
+        return;
+    };
+    match save::native::serialize_save_v2(&save_data) {
+        Ok(json) => {
+            let filename = sources.active_filename();
+            UNLOAD_SNAPSHOT.with(|s| *s.borrow_mut() = Some((filename, json)));
+        }
+        Err(e) => warn!("[SAVE] Could not prepare the unload snapshot: {}", e),
+    }
+}
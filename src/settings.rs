@@ -2,14 +2,106 @@
 //!
 //! Provides user-configurable settings with persistence.
 
+use bevy::light::{CascadeShadowConfig, CascadeShadowConfigBuilder};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::components::{UIContext, UIState};
+use crate::graphics::palette::{recolor_held_item_materials, sync_block_palette, BlockPalette};
+use crate::input::{GameAction, InputBinding, InputManager};
+use crate::save::AutoSaveTimer;
+use crate::startup_report::{record_startup_outcome, DataIssue, LoadOutcome};
 
 /// Settings file name
 const SETTINGS_FILE: &str = "settings.json";
 
+/// UI scale range (applied through bevy's `UiScale`)
+pub const UI_SCALE_MIN: f32 = 0.75;
+pub const UI_SCALE_MAX: f32 = 2.0;
+
+/// Range for the number of lamps/work lights that get a real point light
+pub const LIGHT_BUDGET_MIN: u32 = 0;
+pub const LIGHT_BUDGET_MAX: u32 = 64;
+
+/// Autosave interval range in seconds (0 turns autosave off)
+pub const AUTOSAVE_INTERVAL_MIN: f32 = 30.0;
+pub const AUTOSAVE_INTERVAL_MAX: f32 = 600.0;
+
+/// Shadow quality presets for the sun light
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShadowQuality {
+    Off,
+    Low,
+    High,
+}
+
+impl Default for ShadowQuality {
+    fn default() -> Self {
+        // Cascaded shadows are the most expensive pass on WebGPU/low-end GPUs
+        if cfg!(target_arch = "wasm32") {
+            Self::Low
+        } else {
+            Self::High
+        }
+    }
+}
+
+impl ShadowQuality {
+    /// Whether the directional light casts shadows at all
+    pub fn shadows_enabled(self) -> bool {
+        self != Self::Off
+    }
+
+    /// Number of shadow cascades
+    pub fn cascade_count(self) -> usize {
+        match self {
+            Self::Off | Self::Low => 1,
+            Self::High => 4,
+        }
+    }
+
+    /// Distance covered by the shadow cascades
+    pub fn maximum_distance(self) -> f32 {
+        match self {
+            Self::Off | Self::Low => 40.0,
+            Self::High => 100.0,
+        }
+    }
+
+    /// Cascade config for the directional light
+    pub fn cascade_config(self) -> CascadeShadowConfig {
+        CascadeShadowConfigBuilder {
+            num_cascades: self.cascade_count(),
+            first_cascade_far_bound: 10.0,
+            maximum_distance: self.maximum_distance(),
+            ..default()
+        }
+        .build()
+    }
+
+    /// Next preset (settings button cycles Off → Low → High)
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Low,
+            Self::Low => Self::High,
+            Self::High => Self::Off,
+        }
+    }
+
+    /// Display label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Low => "低",
+            Self::High => "高",
+        }
+    }
+}
+
 /// User-configurable game settings
 #[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct GameSettings {
@@ -23,8 +115,9 @@ pub struct GameSettings {
     pub sfx_volume: f32,
     /// Music volume (0.0 - 1.0)
     pub music_volume: f32,
-    /// Enable shadows
-    pub shadows_enabled: bool,
+    /// Shadow quality
+    #[serde(default)]
+    pub shadow_quality: ShadowQuality,
     /// Vertical sync
     pub vsync_enabled: bool,
     /// Fullscreen mode
@@ -33,28 +126,97 @@ pub struct GameSettings {
     pub fov: f32,
     /// Invert Y axis
     pub invert_y: bool,
+    /// Draw conveyor items as flat quads instead of models/cubes
+    #[serde(default)]
+    pub simple_conveyor_items: bool,
+    /// Draw conveyor items with their model or icon instead of colored cubes
+    #[serde(default = "default_conveyor_item_icons")]
+    pub conveyor_item_icons: bool,
+    /// Scroll belt textures and bob items on running conveyors
+    #[serde(default = "default_conveyor_belt_animation")]
+    pub conveyor_belt_animation: bool,
+    /// UI scale (0.75 - 2.0)
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    /// Use the colorblind-safe block palette
+    #[serde(default)]
+    pub colorblind_mode: bool,
+    /// Lights nearest to the player that get a point light (0 - 64)
+    #[serde(default = "default_light_budget")]
+    pub light_budget: u32,
+    /// Show the machine status card when the crosshair rests on a machine
+    #[serde(default = "default_machine_hover_card")]
+    pub machine_hover_card: bool,
+    /// Warn about likely placement mistakes (miner without ore, walled-in furnace, blocked belt)
+    #[serde(default = "default_placement_warnings")]
+    pub placement_warnings: bool,
+    /// Seconds between autosaves (30 - 600, 0 = off)
+    #[serde(default = "default_autosave_interval", alias = "autosave_interval")]
+    pub autosave_interval_secs: f32,
+    /// Key bindings that differ from the defaults
+    #[serde(default)]
+    pub key_bindings: BTreeMap<GameAction, Vec<InputBinding>>,
+}
+
+fn default_conveyor_belt_animation() -> bool {
+    true
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_light_budget() -> u32 {
+    crate::daynight::DEFAULT_LIGHT_BUDGET as u32
+}
+
+fn default_conveyor_item_icons() -> bool {
+    true
+}
+
+fn default_machine_hover_card() -> bool {
+    true
+}
+
+fn default_placement_warnings() -> bool {
+    true
+}
+
+fn default_autosave_interval() -> f32 {
+    crate::save::AUTO_SAVE_INTERVAL
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
             mouse_sensitivity: 0.002,
-            view_distance: 3,
+            // Smaller radius on WASM, where chunk meshing is single-threaded
+            view_distance: if cfg!(target_arch = "wasm32") { 2 } else { 3 },
             master_volume: 1.0,
             sfx_volume: 1.0,
             music_volume: 0.5,
-            shadows_enabled: true,
+            shadow_quality: ShadowQuality::default(),
             vsync_enabled: true,
             fullscreen: false,
             fov: 70.0,
             invert_y: false,
+            simple_conveyor_items: false,
+            conveyor_item_icons: default_conveyor_item_icons(),
+            conveyor_belt_animation: default_conveyor_belt_animation(),
+            ui_scale: default_ui_scale(),
+            colorblind_mode: false,
+            light_budget: default_light_budget(),
+            machine_hover_card: default_machine_hover_card(),
+            placement_warnings: default_placement_warnings(),
+            autosave_interval_secs: default_autosave_interval(),
+            key_bindings: BTreeMap::new(),
         }
     }
 }
 
 impl GameSettings {
     /// Get the settings file path
-    fn settings_path() -> PathBuf {
+    pub fn settings_path() -> PathBuf {
         // Use project directory for development, or user config dir in production
         #[cfg(debug_assertions)]
         {
@@ -70,29 +232,73 @@ impl GameSettings {
 
     /// Load settings from file, or return default if not found
     pub fn load() -> Self {
-        let path = Self::settings_path();
-        match fs::read_to_string(&path) {
-            Ok(contents) => match serde_json::from_str(&contents) {
-                Ok(settings) => {
+        let (settings, outcome) = Self::load_checked(&Self::settings_path());
+        outcome.log();
+        settings
+    }
+
+    /// Load settings from `path`
+    ///
+    /// A file that doesn't parse falls back to defaults with a warning; when
+    /// only the key bindings are broken, the other settings are kept.
+    pub fn load_checked(path: &Path) -> (Self, LoadOutcome) {
+        let mut outcome = LoadOutcome::new("settings");
+        let settings = match fs::read_to_string(path) {
+            Ok(contents) => {
+                let settings =
+                    Self::parse_checked(&contents, &path.display().to_string(), &mut outcome);
+                if outcome.loaded > 0 {
                     tracing::info!("Settings loaded from {:?}", path);
-                    settings
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to parse settings: {}, using defaults", e);
-                    Self::default()
                 }
-            },
+                settings
+            }
             Err(_) => {
                 tracing::info!("No settings file found, using defaults");
                 Self::default()
             }
+        };
+        (settings, outcome)
+    }
+
+    fn parse_checked(contents: &str, file: &str, outcome: &mut LoadOutcome) -> Self {
+        let error = match serde_json::from_str::<Self>(contents) {
+            Ok(settings) => {
+                outcome.loaded = 1;
+                return settings;
+            }
+            Err(e) => e,
+        };
+
+        // Retry without the key bindings before dropping everything
+        let without_bindings = serde_json::from_str::<serde_json::Value>(contents)
+            .ok()
+            .and_then(|mut value| {
+                value.as_object_mut()?.remove("key_bindings")?;
+                serde_json::from_value::<Self>(value).ok()
+            });
+        let mut issue = DataIssue::from_json_error(file, &error);
+        match without_bindings {
+            Some(settings) => {
+                issue.message = format!("key_bindings: {}; using default key bindings", error);
+                outcome.warn(issue);
+                outcome.loaded = 1;
+                settings
+            }
+            None => {
+                issue.message = format!("{}; using default settings", error);
+                outcome.warn(issue);
+                Self::default()
+            }
         }
     }
 
     /// Save settings to file
     pub fn save(&self) -> Result<(), std::io::Error> {
-        let path = Self::settings_path();
+        self.save_to(&Self::settings_path())
+    }
 
+    /// Save settings to a specific file
+    pub fn save_to(&self, path: &Path) -> Result<(), std::io::Error> {
         // Create parent directory if needed
         if let Some(parent) = path.parent() {
             if !parent.exists() {
@@ -101,7 +307,7 @@ impl GameSettings {
         }
 
         let contents = serde_json::to_string_pretty(self)?;
-        fs::write(&path, contents)?;
+        fs::write(path, contents)?;
         tracing::info!("Settings saved to {:?}", path);
         Ok(())
     }
@@ -114,6 +320,14 @@ impl GameSettings {
         self.sfx_volume = self.sfx_volume.clamp(0.0, 1.0);
         self.music_volume = self.music_volume.clamp(0.0, 1.0);
         self.fov = self.fov.clamp(45.0, 120.0);
+        self.ui_scale = self.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        self.light_budget = self.light_budget.clamp(LIGHT_BUDGET_MIN, LIGHT_BUDGET_MAX);
+        self.autosave_interval_secs = if self.autosave_interval_secs > 0.0 {
+            self.autosave_interval_secs
+                .clamp(AUTOSAVE_INTERVAL_MIN, AUTOSAVE_INTERVAL_MAX)
+        } else {
+            0.0
+        };
     }
 
     /// Get effective mouse sensitivity (with invert Y option)
@@ -142,13 +356,32 @@ pub struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
-        let settings = GameSettings::load();
+        let (settings, outcome) = GameSettings::load_checked(&GameSettings::settings_path());
+        record_startup_outcome(app, outcome);
         app.insert_resource(settings)
+            .insert_resource(SettingsPath(GameSettings::settings_path()))
+            .init_resource::<BlockPalette>()
             .add_message::<SettingsChangedEvent>()
-            .add_systems(Update, (auto_save_settings, apply_settings_immediately));
+            .add_systems(Startup, apply_saved_key_bindings)
+            .add_systems(
+                Update,
+                (
+                    auto_save_settings,
+                    apply_settings_immediately,
+                    save_settings_on_close,
+                ),
+            )
+            .add_systems(
+                Update,
+                (sync_block_palette, recolor_held_item_materials).chain(),
+            );
     }
 }
 
+/// Where settings are written (tests point this at a temp dir)
+#[derive(Resource)]
+pub struct SettingsPath(pub PathBuf);
+
 /// Track if settings need saving
 #[derive(Resource, Default)]
 struct SettingsDirty {
@@ -159,6 +392,7 @@ struct SettingsDirty {
 /// Auto-save settings when changed (with debounce)
 fn auto_save_settings(
     settings: Res<GameSettings>,
+    path: Res<SettingsPath>,
     mut dirty: Local<SettingsDirty>,
     mut events: MessageReader<SettingsChangedEvent>,
     time: Res<Time>,
@@ -173,7 +407,7 @@ fn auto_save_settings(
     if dirty.dirty {
         dirty.save_timer -= time.delta_secs();
         if dirty.save_timer <= 0.0 {
-            if let Err(e) = settings.save() {
+            if let Err(e) = settings.save_to(&path.0) {
                 tracing::error!("Failed to save settings: {}", e);
             }
             dirty.dirty = false;
@@ -181,19 +415,61 @@ fn auto_save_settings(
     }
 }
 
+/// Write the settings file as soon as the settings screen closes
+///
+/// Key bindings live in `InputManager` while the screen is open; their
+/// overrides are copied into the settings before writing.
+pub fn save_settings_on_close(
+    ui_state: Res<UIState>,
+    input: Option<Res<InputManager>>,
+    mut settings: ResMut<GameSettings>,
+    path: Res<SettingsPath>,
+    mut was_open: Local<bool>,
+) {
+    let open = ui_state.is_active(&UIContext::Settings);
+    let closed = *was_open && !open;
+    *was_open = open;
+    if !closed {
+        return;
+    }
+
+    if let Some(input) = input {
+        let overrides = input.binding_overrides();
+        if settings.key_bindings != overrides {
+            settings.key_bindings = overrides;
+        }
+    }
+    if let Err(e) = settings.save_to(&path.0) {
+        tracing::error!("Failed to save settings: {}", e);
+    }
+}
+
+/// Apply the stored key binding overrides at startup
+fn apply_saved_key_bindings(settings: Res<GameSettings>, input: Option<ResMut<InputManager>>) {
+    if let Some(mut input) = input {
+        input.apply_binding_overrides(&settings.key_bindings);
+    }
+}
+
 /// Apply settings changes immediately to the game
+/// Also runs once at startup so the loaded settings take effect
+#[allow(clippy::too_many_arguments)]
 pub fn apply_settings_immediately(
     settings: Res<GameSettings>,
     mut events: MessageReader<SettingsChangedEvent>,
     mut windows: Query<&mut Window>,
     mut projection_query: Query<&mut Projection>,
+    mut light_query: Query<(&mut DirectionalLight, &mut CascadeShadowConfig)>,
+    ui_scale: Option<ResMut<UiScale>>,
+    auto_save_timer: Option<ResMut<AutoSaveTimer>>,
 ) {
     // Only process if there were changes
-    if events.read().next().is_none() {
-        return;
-    }
+    let changed = events.read().next().is_some();
     // Drain remaining events
     for _ in events.read() {}
+    if !changed && !settings.is_added() {
+        return;
+    }
 
     // Apply window settings
     if let Ok(mut window) = windows.single_mut() {
@@ -219,11 +495,31 @@ pub fn apply_settings_immediately(
         }
     }
 
+    // Apply shadow quality to the sun
+    for (mut light, mut cascades) in light_query.iter_mut() {
+        light.shadows_enabled = settings.shadow_quality.shadows_enabled();
+        *cascades = settings.shadow_quality.cascade_config();
+    }
+
+    // Apply UI scale (live preview while the slider is dragged)
+    if let Some(mut ui_scale) = ui_scale {
+        if ui_scale.0 != settings.ui_scale {
+            ui_scale.0 = settings.ui_scale;
+        }
+    }
+
+    // Apply the autosave interval (0 pauses the timer)
+    if let Some(mut auto_save_timer) = auto_save_timer {
+        auto_save_timer.set_interval(settings.autosave_interval_secs);
+    }
+
     tracing::info!(
-        "Settings applied: vsync={}, fullscreen={}, fov={}",
+        "Settings applied: vsync={}, fullscreen={}, fov={}, shadows={:?}, ui_scale={}",
         settings.vsync_enabled,
         settings.fullscreen,
-        settings.fov
+        settings.fov,
+        settings.shadow_quality,
+        settings.ui_scale
     );
 }
 
@@ -247,11 +543,21 @@ mod tests {
             master_volume: 2.0,     // Too high
             sfx_volume: -0.5,       // Too low
             music_volume: 0.5,
-            shadows_enabled: true,
+            shadow_quality: ShadowQuality::High,
             vsync_enabled: true,
             fullscreen: false,
             fov: 200.0, // Too high
             invert_y: false,
+            simple_conveyor_items: false,
+            conveyor_item_icons: true,
+            conveyor_belt_animation: true,
+            ui_scale: 5.0, // Too high
+            colorblind_mode: false,
+            light_budget: 500, // Too high
+            machine_hover_card: true,
+            placement_warnings: true,
+            autosave_interval_secs: 5.0, // Too low
+            key_bindings: BTreeMap::new(),
         };
 
         settings.validate();
@@ -261,6 +567,14 @@ mod tests {
         assert!((settings.master_volume - 1.0).abs() < f32::EPSILON);
         assert!((settings.sfx_volume - 0.0).abs() < f32::EPSILON);
         assert!((settings.fov - 120.0).abs() < f32::EPSILON);
+        assert!((settings.ui_scale - UI_SCALE_MAX).abs() < f32::EPSILON);
+        assert_eq!(settings.light_budget, LIGHT_BUDGET_MAX);
+        assert!((settings.autosave_interval_secs - AUTOSAVE_INTERVAL_MIN).abs() < f32::EPSILON);
+
+        // 0 means off and stays 0
+        settings.autosave_interval_secs = 0.0;
+        settings.validate();
+        assert_eq!(settings.autosave_interval_secs, 0.0);
     }
 
     #[test]
@@ -301,4 +615,173 @@ mod tests {
         assert_eq!(settings.view_distance, parsed.view_distance);
         assert_eq!(settings.fullscreen, parsed.fullscreen);
     }
+
+    #[test]
+    fn test_graphics_settings_roundtrip() {
+        let settings = GameSettings {
+            view_distance: 5,
+            shadow_quality: ShadowQuality::Low,
+            vsync_enabled: false,
+            simple_conveyor_items: true,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&settings).expect("should serialize");
+        assert!(json.contains("\"shadow_quality\":\"low\""));
+        let parsed: GameSettings = serde_json::from_str(&json).expect("should deserialize");
+
+        assert_eq!(parsed.view_distance, 5);
+        assert_eq!(parsed.shadow_quality, ShadowQuality::Low);
+        assert!(!parsed.vsync_enabled);
+        assert!(parsed.simple_conveyor_items);
+    }
+
+    #[test]
+    fn test_old_settings_file_gets_graphics_defaults() {
+        // Written before shadow quality existed
+        let json = r#"{
+            "mouse_sensitivity": 0.002, "view_distance": 4, "master_volume": 1.0,
+            "sfx_volume": 1.0, "music_volume": 0.5, "shadows_enabled": false,
+            "vsync_enabled": true, "fullscreen": false, "fov": 70.0, "invert_y": false
+        }"#;
+        let parsed: GameSettings = serde_json::from_str(json).expect("should deserialize");
+        assert_eq!(parsed.view_distance, 4);
+        assert_eq!(parsed.shadow_quality, ShadowQuality::default());
+        assert!(!parsed.simple_conveyor_items);
+        assert!(parsed.conveyor_belt_animation);
+        assert!((parsed.ui_scale - 1.0).abs() < f32::EPSILON);
+        assert!(!parsed.colorblind_mode);
+    }
+
+    #[test]
+    fn test_broken_settings_file_is_recoverable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+
+        // Bad key bindings only: keep the rest
+        let mut value = serde_json::to_value(GameSettings {
+            fov: 90.0,
+            ..Default::default()
+        })
+        .unwrap();
+        value["key_bindings"] = serde_json::json!({ "not_an_action": [] });
+        fs::write(&path, value.to_string()).unwrap();
+        let (settings, outcome) = GameSettings::load_checked(&path);
+        assert_eq!(settings.fov, 90.0);
+        assert!(settings.key_bindings.is_empty());
+        assert!(!outcome.is_fatal());
+        assert_eq!(outcome.loaded, 1);
+        assert!(outcome.warnings[0].message.starts_with("key_bindings:"));
+
+        // Syntax error: defaults with the position
+        fs::write(&path, "{\n  \"fov\": 90.0,\n  \"invert_y\": tru\n}").unwrap();
+        let (settings, outcome) = GameSettings::load_checked(&path);
+        assert_eq!(settings.fov, GameSettings::default().fov);
+        assert!(!outcome.is_fatal());
+        assert_eq!(outcome.loaded, 0);
+        assert_eq!(outcome.warnings[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_accessibility_settings_roundtrip() {
+        let settings = GameSettings {
+            ui_scale: 1.5,
+            colorblind_mode: true,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&settings).expect("should serialize");
+        let parsed: GameSettings = serde_json::from_str(&json).expect("should deserialize");
+
+        assert!((parsed.ui_scale - 1.5).abs() < f32::EPSILON);
+        assert!(parsed.colorblind_mode);
+    }
+
+    #[test]
+    fn test_shadow_quality_cycle() {
+        assert!(!ShadowQuality::Off.shadows_enabled());
+        assert_eq!(ShadowQuality::Low.cascade_count(), 1);
+        assert_eq!(ShadowQuality::High.cascade_count(), 4);
+        assert_eq!(ShadowQuality::Off.next(), ShadowQuality::Low);
+        assert_eq!(ShadowQuality::High.next(), ShadowQuality::Off);
+    }
+
+    #[test]
+    fn test_settings_written_when_screen_closes() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("settings.json");
+
+        let mut app = App::new();
+        app.insert_resource(UIState::default())
+            .insert_resource(GameSettings::default())
+            .insert_resource(InputManager::default())
+            .insert_resource(SettingsPath(path.clone()))
+            .add_systems(Update, save_settings_on_close);
+
+        // Change settings while the screen is open: nothing is written yet
+        app.world_mut()
+            .resource_mut::<UIState>()
+            .push(UIContext::Settings);
+        app.update();
+        app.world_mut().resource_mut::<GameSettings>().fov = 90.0;
+        app.world_mut()
+            .resource_mut::<InputManager>()
+            .rebind(GameAction::DropItem, InputBinding::Key(KeyCode::KeyH))
+            .unwrap();
+        app.update();
+        assert!(!path.exists());
+
+        // Closing writes the file, including the rebound key
+        app.world_mut().resource_mut::<UIState>().pop();
+        app.update();
+        let saved: GameSettings =
+            serde_json::from_str(&fs::read_to_string(&path).expect("settings written"))
+                .expect("should deserialize");
+        assert!((saved.fov - 90.0).abs() < f32::EPSILON);
+        assert_eq!(
+            saved.key_bindings.get(&GameAction::DropItem),
+            Some(&vec![InputBinding::Key(KeyCode::KeyH)])
+        );
+
+        // Staying closed doesn't write again
+        fs::remove_file(&path).unwrap();
+        app.update();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_autosave_interval_follows_settings() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(GameSettings {
+                autosave_interval_secs: 120.0,
+                ..Default::default()
+            })
+            .init_resource::<AutoSaveTimer>()
+            .add_message::<SettingsChangedEvent>()
+            .add_systems(Update, apply_settings_immediately);
+        let timer = |app: &App| app.world().resource::<AutoSaveTimer>().timer.clone();
+
+        // Loaded settings apply at startup
+        app.update();
+        assert_eq!(timer(&app).duration().as_secs(), 120);
+
+        // 0 turns autosave off: the timer stops and never finishes
+        app.world_mut()
+            .resource_mut::<GameSettings>()
+            .autosave_interval_secs = 0.0;
+        app.world_mut().write_message(SettingsChangedEvent);
+        app.update();
+        let mut auto_save = app.world_mut().resource_mut::<AutoSaveTimer>();
+        assert!(!auto_save.is_enabled());
+        auto_save.timer.tick(std::time::Duration::from_secs(1000));
+        assert!(!auto_save.timer.just_finished());
+
+        // Turning it back on resumes with the new interval
+        app.world_mut()
+            .resource_mut::<GameSettings>()
+            .autosave_interval_secs = 300.0;
+        app.world_mut().write_message(SettingsChangedEvent);
+        app.update();
+        assert!(app.world().resource::<AutoSaveTimer>().is_enabled());
+        assert_eq!(timer(&app).duration().as_secs(), 300);
+    }
 }
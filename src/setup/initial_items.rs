@@ -1,19 +1,28 @@
 //! Initial items setup
 //!
 //! NOTE: Initial equipment is now managed by GlobalInventory (see main.rs).
-//! Initial world objects (furnace, platform) are now given as tutorial rewards.
+//! Initial world objects (furnace, platform) are now given as tutorial rewards,
+//! unless the worldgen config places a starting furnace.
 
+use crate::core::items;
+use crate::events::SpawnMachineEvent;
+use crate::world::WorldData;
 use bevy::prelude::*;
 
 /// Setup initial world objects
 ///
 /// Initial equipment (machines) is added to GlobalInventory in main.rs.
-/// Furnace and platform are now tutorial rewards, so nothing is spawned here.
+/// Furnace and platform are tutorial rewards; only `initial_furnace` from the
+/// worldgen config is spawned here.
 pub fn setup_initial_items(
-    _commands: Commands,
-    _meshes: ResMut<Assets<Mesh>>,
-    _materials: ResMut<Assets<StandardMaterial>>,
+    world_data: Res<WorldData>,
+    mut spawn_events: MessageWriter<SpawnMachineEvent>,
 ) {
-    // Furnace and platform are now tutorial rewards
-    // Nothing to spawn here
+    if let Some(position) = world_data.gen_config.initial_furnace_position() {
+        spawn_events.write(SpawnMachineEvent {
+            position,
+            machine_id: items::furnace_block(),
+            direction: None,
+        });
+    }
 }
@@ -1,25 +1,22 @@
 //! Lighting setup
 
-use bevy::light::{CascadeShadowConfigBuilder, GlobalAmbientLight};
+use bevy::light::GlobalAmbientLight;
 use bevy::prelude::*;
 use std::f32::consts::PI;
 
-pub fn setup_lighting(mut commands: Commands) {
-    // Directional light with high-quality shadows
+use crate::settings::GameSettings;
+
+pub fn setup_lighting(mut commands: Commands, settings: Res<GameSettings>) {
+    // Directional light, shadow cascades follow the graphics settings
+    let shadows = settings.shadow_quality;
     commands.spawn((
         DirectionalLight {
             illuminance: 10000.0,
-            shadows_enabled: true,
+            shadows_enabled: shadows.shadows_enabled(),
             ..default()
         },
         Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -PI / 4.0, PI / 4.0, 0.0)),
-        CascadeShadowConfigBuilder {
-            num_cascades: 4,
-            first_cascade_far_bound: 10.0,
-            maximum_distance: 100.0,
-            ..default()
-        }
-        .build(),
+        shadows.cascade_config(),
     ));
 
     // Global ambient light
@@ -3,6 +3,7 @@
 use crate::components::*;
 use crate::core::items;
 use crate::player::{LocalPlayer, PlayerInventory};
+use crate::respawn::{SpawnPoint, DEFAULT_SPAWN_POINT};
 use bevy::camera::visibility::RenderLayers;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::light::NotShadowCaster;
@@ -38,7 +39,8 @@ pub fn setup_player(
             Player,
             PlayerPhysics::default(),
             PlayerInventory::default(),
-            Transform::from_xyz(8.0, 12.0, 20.0),
+            SpawnPoint::default(),
+            Transform::from_translation(DEFAULT_SPAWN_POINT),
             Visibility::default(),
         ))
         .with_children(|parent| {
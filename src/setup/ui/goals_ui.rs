@@ -0,0 +1,431 @@
+//! Production goals panel (opened from the stats panel): target rate and bottleneck
+
+use bevy::prelude::*;
+
+use crate::components::{UIAction, UIContext, UIState};
+use crate::game_spec::{all_recipes, UIElementRegistry, UIElementTag};
+use crate::setup::ui::{
+    text_font, SLOT_BORDER_COLOR, SLOT_RADIUS, TEXT_BODY, TEXT_BUTTON, TEXT_HEADING, TEXT_SECTION,
+    TEXT_SMALL,
+};
+use crate::statistics::{
+    analyze_goal, goal_items, MachineCensus, ProductionGoal, ThroughputTracker,
+};
+
+/// Rate change per +/- click (items per minute)
+const GOAL_RATE_STEP: f32 = 5.0;
+const GOAL_RATE_MIN: f32 = 5.0;
+const GOAL_RATE_MAX: f32 = 600.0;
+/// Stage rows in the panel (deeper chains are cut off)
+const GOAL_STAGE_ROWS: usize = 10;
+
+const SATISFIED_COLOR: Color = Color::srgb(0.5, 0.8, 0.5);
+const SHORT_COLOR: Color = Color::srgb(0.75, 0.75, 0.75);
+const BOTTLENECK_COLOR: Color = Color::srgb(1.0, 0.45, 0.3);
+const BUTTON_BG: Color = Color::srgba(0.2, 0.2, 0.2, 0.9);
+const BUTTON_HOVER_BG: Color = Color::srgba(0.3, 0.3, 0.3, 0.95);
+
+/// Marker for the goals panel root
+#[derive(Component)]
+pub struct GoalsPanel;
+
+/// Button in the stats panel that opens the goals panel
+#[derive(Component)]
+pub struct GoalsOpenButton;
+
+/// Item and rate adjust buttons
+#[derive(Component, Clone, Copy)]
+pub enum GoalAdjustButton {
+    PrevItem,
+    NextItem,
+    Decrease,
+    Increase,
+}
+
+/// Selected item name
+#[derive(Component)]
+pub struct GoalItemText;
+
+/// Target rate
+#[derive(Component)]
+pub struct GoalRateText;
+
+/// One stage line of the analysis (row index, upstream first)
+#[derive(Component)]
+pub struct GoalStageRow(pub usize);
+
+/// One-line suggestion for the bottleneck
+#[derive(Component)]
+pub struct GoalSuggestionText;
+
+/// Back button on goals panel
+#[derive(Component)]
+pub struct GoalsBackButton;
+
+/// Setup the goals UI panel
+pub fn setup_goals_ui(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    ui_registry: &UIElementRegistry,
+) {
+    commands
+        .spawn((
+            GoalsPanel,
+            ui_registry
+                .get_id("base:goals_menu")
+                .map(UIElementTag::new)
+                .unwrap_or_else(|| UIElementTag::new(Default::default())),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::NONE), // No background (use PauseUI background)
+            GlobalZIndex(102),            // Above stats panel
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(560.0),
+                    max_height: Val::Percent(85.0),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.14, 0.98)),
+                BorderColor::all(SLOT_BORDER_COLOR),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("生産目標"),
+                    text_font(font, TEXT_HEADING),
+                    TextColor(Color::WHITE),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+
+                spawn_adjust_row(
+                    panel,
+                    font,
+                    "アイテム",
+                    (GoalAdjustButton::PrevItem, GoalAdjustButton::NextItem),
+                    GoalItemText,
+                    ("<", ">"),
+                );
+                spawn_adjust_row(
+                    panel,
+                    font,
+                    "目標",
+                    (GoalAdjustButton::Decrease, GoalAdjustButton::Increase),
+                    GoalRateText,
+                    ("-", "+"),
+                );
+
+                panel.spawn((
+                    Text::new("実測 / 必要 (個/分)"),
+                    text_font(font, TEXT_SMALL),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    Node {
+                        margin: UiRect::top(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+                for i in 0..GOAL_STAGE_ROWS {
+                    panel.spawn((
+                        Text::new(""),
+                        GoalStageRow(i),
+                        text_font(font, TEXT_BODY),
+                        TextColor(SHORT_COLOR),
+                    ));
+                }
+
+                panel.spawn((
+                    Text::new(""),
+                    GoalSuggestionText,
+                    text_font(font, TEXT_SECTION),
+                    TextColor(BOTTLENECK_COLOR),
+                    Node {
+                        margin: UiRect::top(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+
+                // Back button
+                panel
+                    .spawn((
+                        Button,
+                        GoalsBackButton,
+                        Node {
+                            width: Val::Px(150.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(20.0)),
+                            align_self: AlignSelf::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            border_radius: BorderRadius::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(BUTTON_BG),
+                        BorderColor::all(Color::srgb(0.8, 0.5, 0.0)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("戻る"),
+                            text_font(font, TEXT_SECTION),
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            });
+        });
+}
+
+/// "label  [<] value [>]" row
+fn spawn_adjust_row(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    buttons: (GoalAdjustButton, GoalAdjustButton),
+    value_marker: impl Component,
+    button_labels: (&str, &str),
+) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+            row.spawn(Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..default()
+            })
+            .with_children(|controls| {
+                spawn_small_button(controls, font, buttons.0, button_labels.0);
+                controls.spawn((
+                    Text::new(""),
+                    value_marker,
+                    text_font(font, TEXT_BODY),
+                    TextColor(Color::WHITE),
+                    Node {
+                        min_width: Val::Px(140.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    TextLayout::new_with_justify(Justify::Center),
+                ));
+                spawn_small_button(controls, font, buttons.1, button_labels.1);
+            });
+        });
+}
+
+fn spawn_small_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    action: GoalAdjustButton,
+    label: &str,
+) {
+    parent
+        .spawn((
+            Button,
+            action,
+            Node {
+                width: Val::Px(32.0),
+                height: Val::Px(28.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(BUTTON_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new(label),
+                text_font(font, TEXT_BUTTON),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Update goals panel visibility based on UIState
+pub fn update_goals_visibility(
+    ui_state: Res<UIState>,
+    mut panel_query: Query<&mut Visibility, With<GoalsPanel>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+
+    *visibility = if ui_state.is_active(&UIContext::Goals) {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+/// Refresh the goal and its analysis while the panel is open
+#[allow(clippy::type_complexity)]
+pub fn update_goals_ui(
+    ui_state: Res<UIState>,
+    goal: Res<ProductionGoal>,
+    census: Res<MachineCensus>,
+    tracker: Res<ThroughputTracker>,
+    mut item_text: Query<&mut Text, (With<GoalItemText>, Without<GoalRateText>)>,
+    mut rate_text: Query<&mut Text, (With<GoalRateText>, Without<GoalItemText>)>,
+    mut rows: Query<
+        (&GoalStageRow, &mut Text, &mut TextColor),
+        (Without<GoalItemText>, Without<GoalRateText>),
+    >,
+    mut suggestion_text: Query<
+        &mut Text,
+        (
+            With<GoalSuggestionText>,
+            Without<GoalStageRow>,
+            Without<GoalItemText>,
+            Without<GoalRateText>,
+        ),
+    >,
+) {
+    if !ui_state.is_active(&UIContext::Goals) {
+        return;
+    }
+
+    let analysis = analyze_goal(all_recipes(), &census, &tracker.production_rates(), *goal);
+
+    if let Ok(mut text) = item_text.single_mut() {
+        text.set_if_neq(Text::new(goal.item.display_name()));
+    }
+    if let Ok(mut text) = rate_text.single_mut() {
+        text.set_if_neq(Text::new(format!("{:.0} 個/分", goal.rate_per_min)));
+    }
+    for (row, mut text, mut color) in rows.iter_mut() {
+        let Some(stage) = analysis.stages.get(row.0) else {
+            text.set_if_neq(Text::new(""));
+            continue;
+        };
+        let line = format!(
+            "{} {}: {:.1} / {:.1}  ({} {}台)",
+            if analysis.bottleneck == Some(row.0) {
+                "▶"
+            } else {
+                "  "
+            },
+            stage.item.display_name(),
+            stage.actual_rate,
+            stage.required_rate,
+            stage.machine.name,
+            stage.required_machines,
+        );
+        text.set_if_neq(Text::new(line));
+        color.set_if_neq(TextColor(if analysis.bottleneck == Some(row.0) {
+            BOTTLENECK_COLOR
+        } else if stage.is_satisfied() {
+            SATISFIED_COLOR
+        } else {
+            SHORT_COLOR
+        }));
+    }
+    if let Ok(mut text) = suggestion_text.single_mut() {
+        let line = analysis
+            .suggestion
+            .unwrap_or_else(|| "目標を達成中".to_string());
+        text.set_if_neq(Text::new(line));
+    }
+}
+
+/// Handle item/rate buttons
+pub fn handle_goals_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &GoalAdjustButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut goal: ResMut<ProductionGoal>,
+) {
+    for (interaction, action, mut bg) in interaction_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => match action {
+                GoalAdjustButton::PrevItem | GoalAdjustButton::NextItem => {
+                    let choices = goal_items(all_recipes());
+                    if choices.is_empty() {
+                        continue;
+                    }
+                    let current = choices.iter().position(|id| *id == goal.item).unwrap_or(0);
+                    let next = match action {
+                        GoalAdjustButton::NextItem => (current + 1) % choices.len(),
+                        _ => (current + choices.len() - 1) % choices.len(),
+                    };
+                    goal.item = choices[next];
+                }
+                GoalAdjustButton::Decrease => {
+                    goal.rate_per_min = (goal.rate_per_min - GOAL_RATE_STEP).max(GOAL_RATE_MIN);
+                }
+                GoalAdjustButton::Increase => {
+                    goal.rate_per_min = (goal.rate_per_min + GOAL_RATE_STEP).min(GOAL_RATE_MAX);
+                }
+            },
+            Interaction::Hovered => {
+                *bg = BackgroundColor(BUTTON_HOVER_BG);
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(BUTTON_BG);
+            }
+        }
+    }
+}
+
+/// Open the goals panel from the stats panel, and go back from it
+#[allow(clippy::type_complexity)]
+pub fn handle_goals_navigation(
+    mut open_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<GoalsOpenButton>),
+    >,
+    mut back_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (
+            Changed<Interaction>,
+            With<GoalsBackButton>,
+            Without<GoalsOpenButton>,
+        ),
+    >,
+    mut action_writer: MessageWriter<UIAction>,
+) {
+    let buttons = open_query
+        .iter_mut()
+        .map(|(i, bg)| (i, bg, UIAction::Push(UIContext::Goals)))
+        .chain(back_query.iter_mut().map(|(i, bg)| (i, bg, UIAction::Pop)));
+    for (interaction, mut bg, action) in buttons {
+        match interaction {
+            Interaction::Pressed => {
+                action_writer.write(action);
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(BUTTON_HOVER_BG);
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(BUTTON_BG);
+            }
+        }
+    }
+}
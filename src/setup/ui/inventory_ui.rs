@@ -2,18 +2,20 @@
 //!
 //! Unified inventory panel that displays:
 //! - Upper panel (Platform Inventory / Creative Catalog) with tabs, search, scrollable grid
+//! - Crafting tab (hand recipes, hidden in creative mode)
 //! - Main inventory (3x9)
 //! - Hotbar (1x9)
 //! - Trash slot
 
 use crate::components::*;
-use crate::game_spec::{UIElementRegistry, UIElementTag};
+use crate::craft::{hand_recipes, input_label};
+use crate::game_spec::{Recipe, UIElementRegistry, UIElementTag};
 use bevy::prelude::*;
 
 use super::{
     spawn_inventory_slot, text_font, QUEST_BG, QUEST_BORDER_COLOR, QUEST_RADIUS, SLOT_BG,
     SLOT_BORDER, SLOT_BORDER_COLOR, SLOT_GAP, SLOT_RADIUS, SLOT_SIZE, SPRITE_SIZE, TEXT_BODY,
-    TEXT_BUTTON, TEXT_TINY,
+    TEXT_BUTTON, TEXT_CAPTION, TEXT_TINY,
 };
 
 /// Calculate inventory UI width based on slot size
@@ -54,6 +56,29 @@ pub struct UpperPanelTabs;
 #[derive(Component)]
 pub struct UpperPanelPageText;
 
+/// Marker for the crafting panel (hidden in creative mode)
+#[derive(Component)]
+pub struct CraftingPanel;
+
+/// Marker for the crafting tab button (toggles the recipe list)
+#[derive(Component)]
+pub struct CraftingTabButton;
+
+/// Marker for the hand recipe list container
+#[derive(Component)]
+pub struct CraftingRecipeList;
+
+/// Craft button for a hand recipe
+#[derive(Component)]
+pub struct HandCraftButton(pub &'static str);
+
+/// Required vs available count text for one recipe input
+#[derive(Component)]
+pub struct HandCraftRequirement {
+    pub recipe_id: &'static str,
+    pub input: usize,
+}
+
 /// Number of slots in the upper panel grid
 pub const UPPER_PANEL_SLOTS: usize = 36; // 9 columns x 4 rows
 
@@ -112,6 +137,9 @@ pub fn setup_inventory_ui(
             // Conditionally visible when creative_mode.enabled || local_platform.entity.is_some()
             spawn_upper_panel(parent, font);
 
+            // === Crafting tab (hand recipes, hidden in creative mode) ===
+            spawn_crafting_panel(parent, font);
+
             // === Main inventory grid (3x9, slots 9-35) ===
             parent
                 .spawn((Node {
@@ -170,15 +198,36 @@ pub fn setup_inventory_ui(
                 BackgroundColor(Color::srgba(1.0, 0.53, 0.0, 0.4)), // Orange tint
             ));
 
-            // === Bottom row: Trash slot ===
+            // === Bottom row: Sort / Quick stack buttons, trash slot ===
             parent
                 .spawn((Node {
                     flex_direction: FlexDirection::Row,
-                    justify_content: JustifyContent::FlexEnd,
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
                     margin: UiRect::top(Val::Px(4.0)),
                     ..default()
                 },))
                 .with_children(|bottom_row| {
+                    bottom_row
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(SLOT_GAP * 2.0),
+                            ..default()
+                        })
+                        .with_children(|buttons| {
+                            spawn_inventory_action_button(
+                                buttons,
+                                font,
+                                InventorySortButton,
+                                "整理",
+                            );
+                            spawn_inventory_action_button(
+                                buttons,
+                                font,
+                                InventoryQuickStackButton,
+                                "近くに収納",
+                            );
+                        });
                     bottom_row
                         .spawn((
                             Button,
@@ -196,7 +245,20 @@ pub fn setup_inventory_ui(
                             BorderColor::all(Color::srgb(0.8, 0.3, 0.2)), // Red-orange
                         ))
                         .with_children(|btn| {
+                            // Stack waiting for confirmation (dimmed)
+                            btn.spawn((
+                                TrashPendingImage,
+                                ImageNode::default(),
+                                Visibility::Hidden,
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    width: Val::Px(SPRITE_SIZE),
+                                    height: Val::Px(SPRITE_SIZE),
+                                    ..default()
+                                },
+                            ));
                             btn.spawn((
+                                TrashSlotText,
                                 Text::new("X"),
                                 text_font(font, TEXT_BUTTON),
                                 TextColor(Color::srgb(1.0, 0.5, 0.4)),
@@ -206,6 +268,36 @@ pub fn setup_inventory_ui(
         });
 }
 
+/// Text button in the inventory's bottom row
+fn spawn_inventory_action_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    marker: impl Component,
+    label: &str,
+) {
+    parent
+        .spawn((
+            Button,
+            marker,
+            Node {
+                height: Val::Px(SLOT_SIZE * 0.7),
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(SLOT_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_child((
+            Text::new(label),
+            text_font(font, TEXT_BODY),
+            TextColor(Color::WHITE),
+        ));
+}
+
 /// Spawn the upper panel (Platform Inventory / Creative Catalog)
 fn spawn_upper_panel(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
     parent
@@ -425,6 +517,144 @@ fn spawn_upper_panel(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
         });
 }
 
+/// Spawn the crafting tab and its hand recipe rows
+fn spawn_crafting_panel(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent
+        .spawn((
+            CraftingPanel,
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn((
+                    Button,
+                    CraftingTabButton,
+                    Node {
+                        align_self: AlignSelf::FlexStart,
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.2, 0.2, 0.25, 1.0)),
+                    BorderColor::all(QUEST_BORDER_COLOR),
+                ))
+                .with_child((
+                    Text::new("クラフト"),
+                    text_font(font, TEXT_BODY),
+                    TextColor(Color::WHITE),
+                ));
+
+            panel
+                .spawn((
+                    CraftingRecipeList,
+                    Node {
+                        display: Display::None, // Opened with the crafting tab
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        padding: UiRect::all(Val::Px(4.0)),
+                        border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.15, 0.15, 0.2, 1.0)),
+                ))
+                .with_children(|list| {
+                    for recipe in hand_recipes() {
+                        // Each recipe is a column so a craft-time progress bar
+                        // can be added under the row later
+                        list.spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        })
+                        .with_children(|entry| {
+                            spawn_hand_recipe_row(entry, recipe, font);
+                        });
+                    }
+                });
+        });
+}
+
+/// Spawn one hand recipe row: output, requirements, craft button
+fn spawn_hand_recipe_row(
+    parent: &mut ChildSpawnerCommands,
+    recipe: &'static Recipe,
+    font: &Handle<Font>,
+) {
+    let output = recipe
+        .outputs
+        .first()
+        .map(|o| format!("{} x{}", o.item.display_name(), o.count))
+        .unwrap_or_default();
+
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(output),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+                Node {
+                    width: Val::Px(140.0),
+                    ..default()
+                },
+            ));
+
+            // Requirements: one span per input, colored by the update system
+            row.spawn((
+                Text::new(""),
+                text_font(font, TEXT_CAPTION),
+                TextColor(Color::WHITE),
+                Node {
+                    flex_grow: 1.0,
+                    ..default()
+                },
+            ))
+            .with_children(|text| {
+                for (input, recipe_input) in recipe.inputs.iter().enumerate() {
+                    text.spawn((
+                        TextSpan::new(format!(
+                            "{} 0/{}  ",
+                            input_label(recipe_input),
+                            recipe_input.count
+                        )),
+                        text_font(font, TEXT_CAPTION),
+                        TextColor(Color::WHITE),
+                        HandCraftRequirement {
+                            recipe_id: recipe.id,
+                            input,
+                        },
+                    ));
+                }
+            });
+
+            row.spawn((
+                Button,
+                HandCraftButton(recipe.id),
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.2, 0.2, 0.25, 1.0)),
+                BorderColor::all(QUEST_BORDER_COLOR),
+            ))
+            .with_child((
+                Text::new("作成"),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
 /// Spawn an upper panel slot button
 fn spawn_upper_panel_slot(parent: &mut ChildSpawnerCommands, slot_idx: usize, font: &Handle<Font>) {
     parent
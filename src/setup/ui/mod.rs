@@ -2,24 +2,40 @@
 //!
 //! Creates all UI panels (hotbar, machine UIs, inventory, quests, etc.)
 
+mod goals_ui;
 mod inventory_ui;
+mod quest_log_ui;
 pub mod settings_ui;
+mod stats_ui;
 
+pub use goals_ui::{
+    handle_goals_buttons, handle_goals_navigation, setup_goals_ui, update_goals_ui,
+    update_goals_visibility, GoalsOpenButton,
+};
 pub use inventory_ui::{
-    setup_inventory_ui, UpperPanel, UpperPanelGrid, UpperPanelPageText, UpperPanelSearchInput,
+    setup_inventory_ui, CraftingPanel, CraftingRecipeList, CraftingTabButton, HandCraftButton,
+    HandCraftRequirement, UpperPanel, UpperPanelGrid, UpperPanelPageText, UpperPanelSearchInput,
     UpperPanelSlot, UpperPanelSlotCount, UpperPanelSlotImage, UpperPanelTabs, UPPER_PANEL_SLOTS,
 };
+pub use quest_log_ui::{
+    handle_quest_log_back, handle_quest_log_claim, handle_quest_log_rows, scroll_quest_log,
+    setup_quest_log_ui, update_quest_log_ui, update_quest_log_visibility, QuestLogClaimButton,
+    QuestLogSelection,
+};
 pub use settings_ui::{
-    handle_settings_back, handle_settings_sliders, handle_settings_toggles,
-    handle_slider_drag_state, setup_settings_ui, update_settings_ui, update_settings_visibility,
-    SliderDragState,
+    handle_key_rebind, handle_settings_back, handle_settings_keyboard, handle_settings_sliders,
+    handle_settings_tabs, handle_settings_toggles, handle_slider_drag_state, setup_settings_ui,
+    update_key_bind_texts, update_settings_focus, update_settings_tabs, update_settings_ui,
+    update_settings_visibility, KeyRebindState, SettingsScreenState, SliderDragState,
 };
+pub use stats_ui::{handle_stats_back, setup_stats_ui, update_stats_ui, update_stats_visibility};
 
 // Re-export machine UI setup from ui module
 pub use crate::ui::machine_ui::setup_generic_machine_ui;
 
 use crate::components::*;
 use crate::game_spec::{UIElementRegistry, UIElementTag};
+use crate::save::PauseLastSavedText;
 use bevy::prelude::*;
 
 /// Helper to create TextFont with the game font
@@ -227,6 +243,20 @@ pub fn setup_ui(
                                 ..default()
                             },
                         ));
+                        // Cooldown overlay (shrinks from the top as the cooldown runs out)
+                        slot.spawn((
+                            HotbarSlotCooldown(i),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                bottom: Val::Px(0.0),
+                                left: Val::Px(0.0),
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.55)),
+                            Visibility::Hidden,
+                        ));
                         // Item count
                         slot.spawn((
                             HotbarSlotCount(i),
@@ -281,10 +311,15 @@ pub fn setup_ui(
     ));
 
     // Machine UI panels (hidden by default, data-driven from MachineSpec)
-    use crate::game_spec::{CRUSHER, FURNACE, MINER};
+    use crate::game_spec::{CRUSHER, FURNACE, HOPPER, MINER, RECYCLER};
     setup_generic_machine_ui(&mut commands, &FURNACE, font, &ui_registry);
     setup_generic_machine_ui(&mut commands, &CRUSHER, font, &ui_registry);
     setup_generic_machine_ui(&mut commands, &MINER, font, &ui_registry);
+    setup_generic_machine_ui(&mut commands, &RECYCLER, font, &ui_registry);
+    setup_generic_machine_ui(&mut commands, &HOPPER, font, &ui_registry);
+    for spec in crate::game_spec::machines::data_machines() {
+        setup_generic_machine_ui(&mut commands, spec, font, &ui_registry);
+    }
 
     // Inventory UI panel (hidden by default)
     setup_inventory_ui(&mut commands, font, &ui_registry);
@@ -306,6 +341,121 @@ pub fn setup_ui(
         Visibility::Hidden,
     ));
 
+    // Machine hover card (tooltip styling, beside the crosshair)
+    commands.spawn((
+        MachineHoverCard,
+        Text::new(""),
+        text_font(font, TEXT_SMALL),
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(52.0),
+            left: Val::Percent(52.0),
+            padding: UiRect::all(Val::Px(5.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+        Visibility::Hidden,
+    ));
+
+    // Platform compass (left of the hotbar, hidden after the first delivery)
+    commands
+        .spawn((
+            PlatformCompass,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0 + (SLOT_SIZE - 40.0) / 2.0),
+                left: Val::Percent(50.0),
+                width: Val::Px(40.0),
+                height: Val::Px(40.0),
+                margin: UiRect {
+                    left: Val::Px(-hotbar_width / 2.0 - 40.0 - SLOT_GAP * 2.0),
+                    ..default()
+                },
+                border: UiRect::all(Val::Px(2.0)),
+                border_radius: BorderRadius::MAX,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderColor::all(SLOT_BORDER_COLOR),
+            BackgroundColor(SLOT_BG),
+            bevy::ui::UiTransform::default(),
+            Visibility::Hidden,
+        ))
+        .with_children(|compass| {
+            compass.spawn((
+                Text::new("▲"),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            ));
+        });
+
+    // Quick-select ring (hidden until the quick-select key is held)
+    {
+        use crate::systems::{segment_direction, QUICK_SELECT_RADIUS, QUICK_SELECT_SEGMENTS};
+
+        commands
+            .spawn((
+                QuickSelectRing,
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.0),
+                    top: Val::Percent(50.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+            ))
+            .with_children(|ring| {
+                for segment in 0..QUICK_SELECT_SEGMENTS {
+                    let center =
+                        segment_direction(segment, QUICK_SELECT_SEGMENTS) * QUICK_SELECT_RADIUS;
+                    ring.spawn((
+                        QuickSelectSegment(segment),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(center.x - SLOT_SIZE / 2.0),
+                            top: Val::Px(center.y - SLOT_SIZE / 2.0),
+                            width: Val::Px(SLOT_SIZE),
+                            height: Val::Px(SLOT_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            border: UiRect::all(Val::Px(SLOT_BORDER)),
+                            border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                            ..default()
+                        },
+                        BackgroundColor(SLOT_BG),
+                        BorderColor::all(SLOT_BORDER_COLOR),
+                    ))
+                    .with_children(|slot| {
+                        slot.spawn((
+                            QuickSelectSegmentImage(segment),
+                            ImageNode::default(),
+                            Visibility::Hidden,
+                            Node {
+                                position_type: PositionType::Absolute,
+                                width: Val::Px(SPRITE_SIZE),
+                                height: Val::Px(SPRITE_SIZE),
+                                ..default()
+                            },
+                        ));
+                        slot.spawn((
+                            QuickSelectSegmentText(segment),
+                            Text::new(""),
+                            text_font(font, SLOT_COUNT_SIZE),
+                            TextColor(Color::WHITE),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                right: Val::Px(4.0),
+                                bottom: Val::Px(2.0),
+                                ..default()
+                            },
+                        ));
+                    });
+                }
+            });
+    }
+
     // Held item display (follows cursor when dragging)
     // Use GlobalZIndex to render on top of all other UI
     commands
@@ -388,11 +538,18 @@ pub fn setup_ui(
                     border: UiRect::bottom(Val::Px(1.0)),
                     ..default()
                 })
-                .with_child((
-                    Text::new("[Q] クエスト"),
-                    text_font(&font_clone, TEXT_BUTTON),
-                    TextColor(QUEST_HEADER_COLOR),
-                ));
+                .with_children(|header| {
+                    header.spawn((
+                        Text::new("[Q] クエスト"),
+                        text_font(&font_clone, TEXT_BUTTON),
+                        TextColor(QUEST_HEADER_COLOR),
+                    ));
+                    header.spawn((
+                        Text::new("[J] クエストログ"),
+                        text_font(&font_clone, TEXT_MINI),
+                        TextColor(Color::srgba(0.6, 0.6, 0.6, 1.0)),
+                    ));
+                });
 
             // Quest description
             parent.spawn((
@@ -641,8 +798,14 @@ pub fn setup_ui(
         BorderColor::all(QUEST_BORDER_COLOR),
     ));
 
-    // Settings UI panel (hidden by default)
-    setup_settings_ui(&mut commands, font, &ui_registry);
+    // Stats UI panel (hidden by default)
+    setup_stats_ui(&mut commands, font, &ui_registry);
+
+    // Production goals panel, opened from the stats panel (hidden by default)
+    setup_goals_ui(&mut commands, font, &ui_registry);
+
+    // Quest log panel (hidden by default)
+    setup_quest_log_ui(&mut commands, font, &ui_registry);
 
     // Pause overlay - shown when ESC pressed
     let font_pause = font.clone();
@@ -677,6 +840,14 @@ pub fn setup_ui(
                 TextColor(Color::WHITE),
             ));
 
+            // How long ago the world was last saved
+            pause.spawn((
+                PauseLastSavedText,
+                Text::new(""),
+                text_font(&font_pause, TEXT_BODY),
+                TextColor(Color::srgba(0.75, 0.75, 0.75, 1.0)),
+            ));
+
             // Button container
             pause
                 .spawn(Node {
@@ -690,6 +861,10 @@ pub fn setup_ui(
                     spawn_pause_button(btns, &font_pause, "再開", PauseMenuButton::Resume);
                     // Settings button
                     spawn_pause_button(btns, &font_pause, "設定", PauseMenuButton::Settings);
+                    // Stats button
+                    spawn_pause_button(btns, &font_pause, "統計", PauseMenuButton::Stats);
+                    // Quest log button
+                    spawn_pause_button(btns, &font_pause, "クエスト", PauseMenuButton::QuestLog);
                     // Quit button (native only)
                     #[cfg(not(target_arch = "wasm32"))]
                     spawn_pause_button(btns, &font_pause, "終了", PauseMenuButton::Quit);
@@ -708,11 +883,24 @@ pub fn setup_ui(
         });
 }
 
+/// Spawn the settings panel (hidden by default)
+///
+/// Separate from `setup_ui` so it exists in the main menu too.
+pub fn spawn_settings_ui(
+    mut commands: Commands,
+    game_font: Res<GameFont>,
+    ui_registry: Res<UIElementRegistry>,
+) {
+    setup_settings_ui(&mut commands, &game_font.0, &ui_registry);
+}
+
 /// Pause menu button types
 #[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub enum PauseMenuButton {
     Resume,
     Settings,
+    Stats,
+    QuestLog,
     #[allow(dead_code)]
     Quit,
 }
@@ -0,0 +1,691 @@
+//! Quest log panel (J key or pause menu): every main quest with history and preview
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+use crate::components::{
+    CurrentQuest, InputStateResourcesWithCursor, UIAction, UIContext, UIState,
+};
+use crate::core::ItemId;
+use crate::events::game_events::QuestRewardsClaimed;
+use crate::game_spec::{UIElementRegistry, UIElementTag};
+use crate::player::LocalPlatformInventory;
+use crate::setup::ui::{
+    text_font, QUEST_BORDER_COLOR, QUEST_HEADER_COLOR, QUEST_PROGRESS_COLOR, SLOT_BORDER_COLOR,
+    SLOT_RADIUS, TEXT_BODY, TEXT_CAPTION, TEXT_HEADING, TEXT_MINI, TEXT_SECTION,
+};
+use crate::systems::quest::{
+    claim_current_quest, get_main_quests, quest_log_entries, unix_millis_now, QuestCache,
+    QuestLogStatus,
+};
+
+const LOCKED_COLOR: Color = Color::srgb(0.45, 0.45, 0.45);
+const CLAIMED_COLOR: Color = Color::srgb(0.5, 0.8, 0.5);
+const ROW_BG: Color = Color::srgba(0.16, 0.16, 0.18, 0.95);
+const ROW_HOVER_BG: Color = Color::srgba(0.24, 0.24, 0.27, 0.95);
+const ROW_SELECTED_BG: Color = Color::srgba(0.32, 0.24, 0.10, 0.95);
+/// Pixels per wheel line when the platform reports line units
+const SCROLL_LINE_HEIGHT: f32 = 24.0;
+
+/// Marker for the quest log panel root
+#[derive(Component)]
+pub struct QuestLogPanel;
+
+/// Scrollable quest list
+#[derive(Component)]
+pub struct QuestLogList;
+
+/// One quest row in the list (quest index)
+#[derive(Component)]
+pub struct QuestLogRow(pub usize);
+
+/// Description text of a quest row
+#[derive(Component)]
+pub struct QuestLogRowText(pub usize);
+
+/// Status line of a quest row (claim time, in progress, locked)
+#[derive(Component)]
+pub struct QuestLogRowStatus(pub usize);
+
+/// Progress bars of a quest row, shown only while the quest is active
+#[derive(Component)]
+pub struct QuestLogRowBars(pub usize);
+
+/// Fill of one requirement's progress bar
+#[derive(Component)]
+pub struct QuestLogBarFill {
+    pub quest: usize,
+    pub requirement: usize,
+}
+
+/// Detail pane text for the selected quest
+#[derive(Component)]
+pub struct QuestLogDetailText;
+
+/// Claim rewards button in the detail pane
+#[derive(Component)]
+pub struct QuestLogClaimButton;
+
+/// Back button on quest log panel
+#[derive(Component)]
+pub struct QuestLogBackButton;
+
+/// Quest selected in the log (None follows the current quest)
+#[derive(Resource, Default)]
+pub struct QuestLogSelection(pub Option<usize>);
+
+/// Setup the quest log UI panel
+pub fn setup_quest_log_ui(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    ui_registry: &UIElementRegistry,
+) {
+    let quests = get_main_quests();
+
+    commands
+        .spawn((
+            QuestLogPanel,
+            ui_registry
+                .get_id("base:quest_log")
+                .map(UIElementTag::new)
+                .unwrap_or_else(|| UIElementTag::new(Default::default())),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(101), // Above pause menu
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(760.0),
+                    height: Val::Percent(80.0),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.14, 0.98)),
+                BorderColor::all(SLOT_BORDER_COLOR),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("クエストログ"),
+                    text_font(font, TEXT_HEADING),
+                    TextColor(Color::WHITE),
+                ));
+
+                panel
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        flex_grow: 1.0,
+                        min_height: Val::Px(0.0),
+                        column_gap: Val::Px(16.0),
+                        ..default()
+                    })
+                    .with_children(|columns| {
+                        // Quest list (left)
+                        columns
+                            .spawn((
+                                QuestLogList,
+                                Node {
+                                    width: Val::Px(340.0),
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(6.0),
+                                    overflow: Overflow::scroll_y(),
+                                    ..default()
+                                },
+                                ScrollPosition::default(),
+                            ))
+                            .with_children(|list| {
+                                for (i, quest) in quests.iter().enumerate() {
+                                    spawn_quest_row(list, font, i, quest.required_items.len());
+                                }
+                            });
+
+                        // Detail pane (right)
+                        columns
+                            .spawn((
+                                Node {
+                                    flex_grow: 1.0,
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(10.0),
+                                    padding: UiRect::all(Val::Px(12.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                                    ..default()
+                                },
+                                BorderColor::all(QUEST_BORDER_COLOR),
+                            ))
+                            .with_children(|detail| {
+                                detail.spawn((
+                                    QuestLogDetailText,
+                                    Text::new(""),
+                                    text_font(font, TEXT_BODY),
+                                    TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+                                ));
+                                detail
+                                    .spawn((
+                                        Button,
+                                        QuestLogClaimButton,
+                                        Node {
+                                            width: Val::Percent(100.0),
+                                            height: Val::Px(36.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            border_radius: BorderRadius::all(Val::Px(6.0)),
+                                            ..default()
+                                        },
+                                        BackgroundColor(Color::srgba(0.2, 0.5, 0.2, 0.95)),
+                                        BorderColor::all(Color::srgba(0.3, 0.6, 0.3, 1.0)),
+                                        Visibility::Hidden,
+                                    ))
+                                    .with_child((
+                                        Text::new("[Q] 報酬を受け取る"),
+                                        text_font(font, TEXT_BODY),
+                                        TextColor(Color::WHITE),
+                                    ));
+                            });
+                    });
+
+                // Back button
+                panel
+                    .spawn((
+                        Button,
+                        QuestLogBackButton,
+                        Node {
+                            width: Val::Px(150.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            align_self: AlignSelf::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            border_radius: BorderRadius::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+                        BorderColor::all(Color::srgb(0.8, 0.5, 0.0)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("戻る"),
+                            text_font(font, TEXT_SECTION),
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            });
+        });
+}
+
+fn spawn_quest_row(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    index: usize,
+    requirements: usize,
+) {
+    parent
+        .spawn((
+            Button,
+            QuestLogRow(index),
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                flex_shrink: 0.0,
+                ..default()
+            },
+            BackgroundColor(ROW_BG),
+        ))
+        .with_children(|row| {
+            row.spawn((
+                QuestLogRowText(index),
+                Text::new(""),
+                text_font(font, TEXT_CAPTION),
+                TextColor(Color::WHITE),
+            ));
+            row.spawn((
+                QuestLogRowStatus(index),
+                Text::new(""),
+                text_font(font, TEXT_MINI),
+                TextColor(LOCKED_COLOR),
+            ));
+            row.spawn((
+                QuestLogRowBars(index),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(3.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+            ))
+            .with_children(|bars| {
+                for requirement in 0..requirements {
+                    bars.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(6.0),
+                            border_radius: BorderRadius::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.15, 0.15, 0.2, 1.0)),
+                    ))
+                    .with_child((
+                        QuestLogBarFill {
+                            quest: index,
+                            requirement,
+                        },
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            border_radius: BorderRadius::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(QUEST_PROGRESS_COLOR),
+                    ));
+                }
+            });
+        });
+}
+
+/// Format a claim time (Unix ms) in local time
+fn format_claim_time(ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms as i64)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn status_label(status: QuestLogStatus) -> String {
+    match status {
+        QuestLogStatus::Claimed(Some(ms)) => format!("✓ 受取済 {}", format_claim_time(ms)),
+        QuestLogStatus::Claimed(None) => "✓ 受取済".to_string(),
+        QuestLogStatus::Active => "進行中".to_string(),
+        QuestLogStatus::Claimable => "報酬受取待ち".to_string(),
+        QuestLogStatus::Locked => "未解放".to_string(),
+    }
+}
+
+fn status_color(status: QuestLogStatus) -> Color {
+    match status {
+        QuestLogStatus::Claimed(_) => CLAIMED_COLOR,
+        QuestLogStatus::Active => Color::WHITE,
+        QuestLogStatus::Claimable => QUEST_HEADER_COLOR,
+        QuestLogStatus::Locked => LOCKED_COLOR,
+    }
+}
+
+/// Update quest log visibility based on UIState
+pub fn update_quest_log_visibility(
+    ui_state: Res<UIState>,
+    mut panel_query: Query<&mut Visibility, With<QuestLogPanel>>,
+) {
+    let Ok(mut visibility) = panel_query.single_mut() else {
+        return;
+    };
+
+    *visibility = if ui_state.is_active(&UIContext::QuestLog) {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+/// Refresh rows, live progress bars and the detail pane while the log is open
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_quest_log_ui(
+    ui_state: Res<UIState>,
+    current_quest: Res<CurrentQuest>,
+    quest_cache: Res<QuestCache>,
+    selection: Res<QuestLogSelection>,
+    platform_inventory: LocalPlatformInventory,
+    mut row_query: Query<(&QuestLogRow, &Interaction, &mut BackgroundColor)>,
+    mut row_text_query: Query<
+        (&QuestLogRowText, &mut Text, &mut TextColor),
+        Without<QuestLogRowStatus>,
+    >,
+    mut row_status_query: Query<
+        (&QuestLogRowStatus, &mut Text, &mut TextColor),
+        Without<QuestLogRowText>,
+    >,
+    mut row_bars_query: Query<(&QuestLogRowBars, &mut Visibility), Without<QuestLogClaimButton>>,
+    mut fill_query: Query<(&QuestLogBarFill, &mut Node)>,
+    mut detail_query: Query<
+        &mut Text,
+        (
+            With<QuestLogDetailText>,
+            Without<QuestLogRowText>,
+            Without<QuestLogRowStatus>,
+        ),
+    >,
+    mut claim_query: Query<&mut Visibility, With<QuestLogClaimButton>>,
+) {
+    if !ui_state.is_active(&UIContext::QuestLog) {
+        return;
+    }
+
+    let quests = &quest_cache.main_quests;
+    let entries = quest_log_entries(quests, &current_quest);
+    let selected = selection
+        .0
+        .unwrap_or(current_quest.index)
+        .min(quests.len().saturating_sub(1));
+    let progress_of = |(item_id, required): &(ItemId, u32)| {
+        (
+            platform_inventory.get_count(*item_id).min(*required),
+            *required,
+        )
+    };
+
+    for (row, interaction, mut bg) in row_query.iter_mut() {
+        let color = if row.0 == selected {
+            ROW_SELECTED_BG
+        } else if *interaction == Interaction::Hovered {
+            ROW_HOVER_BG
+        } else {
+            ROW_BG
+        };
+        if bg.0 != color {
+            *bg = BackgroundColor(color);
+        }
+    }
+
+    for (row, mut text, mut color) in row_text_query.iter_mut() {
+        let (Some(quest), Some(&status)) = (quests.get(row.0), entries.get(row.0)) else {
+            continue;
+        };
+        let content = format!("{}. {}", row.0 + 1, quest.description);
+        if **text != content {
+            **text = content;
+        }
+        let color_now = if status == QuestLogStatus::Locked {
+            LOCKED_COLOR
+        } else {
+            Color::WHITE
+        };
+        if color.0 != color_now {
+            *color = TextColor(color_now);
+        }
+    }
+
+    for (row, mut text, mut color) in row_status_query.iter_mut() {
+        let Some(&status) = entries.get(row.0) else {
+            continue;
+        };
+        let content = status_label(status);
+        if **text != content {
+            **text = content;
+        }
+        if color.0 != status_color(status) {
+            *color = TextColor(status_color(status));
+        }
+    }
+
+    for (bars, mut visibility) in row_bars_query.iter_mut() {
+        let active = entries.get(bars.0) == Some(&QuestLogStatus::Active);
+        *visibility = if active {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for (fill, mut node) in fill_query.iter_mut() {
+        if entries.get(fill.quest) != Some(&QuestLogStatus::Active) {
+            continue;
+        }
+        let Some(requirement) = quests
+            .get(fill.quest)
+            .and_then(|quest| quest.required_items.get(fill.requirement))
+        else {
+            continue;
+        };
+        let (have, required) = progress_of(requirement);
+        let pct = if required > 0 {
+            have as f32 / required as f32 * 100.0
+        } else {
+            100.0
+        };
+        if node.width != Val::Percent(pct) {
+            node.width = Val::Percent(pct);
+        }
+    }
+
+    let (Some(quest), Some(&status)) = (quests.get(selected), entries.get(selected)) else {
+        return;
+    };
+
+    if let Ok(mut text) = detail_query.single_mut() {
+        let mut lines = vec![
+            format!("クエスト {}", selected + 1),
+            quest.description.to_string(),
+            String::new(),
+        ];
+        // Locked quests are a preview: no requirement progress yet
+        if status != QuestLogStatus::Locked {
+            lines.push("必要アイテム:".to_string());
+            for requirement in &quest.required_items {
+                let (have, required) = progress_of(requirement);
+                let shown = if status == QuestLogStatus::Active {
+                    have
+                } else {
+                    required
+                };
+                lines.push(format!(
+                    "  {} {} ({}/{})",
+                    if shown >= required { "✓" } else { "○" },
+                    requirement.0.name().unwrap_or("unknown"),
+                    shown,
+                    required
+                ));
+            }
+            lines.push(String::new());
+        }
+        lines.push("報酬:".to_string());
+        for (item_id, amount) in &quest.rewards {
+            lines.push(format!(
+                "  {} ×{}",
+                item_id.name().unwrap_or("unknown"),
+                amount
+            ));
+        }
+        lines.push(String::new());
+        lines.push(status_label(status));
+
+        let content = lines.join("\n");
+        if **text != content {
+            **text = content;
+        }
+    }
+
+    if let Ok(mut visibility) = claim_query.single_mut() {
+        *visibility = if status == QuestLogStatus::Claimable {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Select a quest when its row is clicked
+pub fn handle_quest_log_rows(
+    row_query: Query<(&Interaction, &QuestLogRow), Changed<Interaction>>,
+    mut selection: ResMut<QuestLogSelection>,
+) {
+    for (interaction, row) in row_query.iter() {
+        if *interaction == Interaction::Pressed {
+            selection.0 = Some(row.0);
+        }
+    }
+}
+
+/// Claim the current quest's rewards from the log (same path as the Q key)
+#[allow(clippy::type_complexity)]
+pub fn handle_quest_log_claim(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<QuestLogClaimButton>),
+    >,
+    mut current_quest: ResMut<CurrentQuest>,
+    mut platform_inventory: LocalPlatformInventory,
+    quest_cache: Res<QuestCache>,
+    mut claimed_events: MessageWriter<QuestRewardsClaimed>,
+) {
+    for (interaction, mut bg) in interaction_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                let Some(mut platform) = platform_inventory.get_mut() else {
+                    continue;
+                };
+                if let Some(quest_id) = claim_current_quest(
+                    &mut current_quest,
+                    &quest_cache.main_quests,
+                    &mut platform,
+                    unix_millis_now(),
+                ) {
+                    claimed_events.write(QuestRewardsClaimed { quest_id });
+                }
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(Color::srgba(0.3, 0.6, 0.3, 0.95));
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(Color::srgba(0.2, 0.5, 0.2, 0.95));
+            }
+        }
+    }
+}
+
+/// Handle quest log back button
+#[allow(clippy::type_complexity)]
+pub fn handle_quest_log_back(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<QuestLogBackButton>),
+    >,
+    mut action_writer: MessageWriter<UIAction>,
+) {
+    for (interaction, mut bg) in interaction_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                action_writer.write(UIAction::Pop);
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(Color::srgba(0.3, 0.3, 0.3, 0.95));
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9));
+            }
+        }
+    }
+}
+
+/// Scroll the quest list with the mouse wheel
+///
+/// Gated on `InputState::allows_ui_scroll`, which excludes the states where
+/// the wheel changes the hotbar slot.
+pub fn scroll_quest_log(
+    ui_state: Res<UIState>,
+    input_resources: InputStateResourcesWithCursor,
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut list_query: Query<&mut ScrollPosition, With<QuestLogList>>,
+) {
+    let allowed =
+        ui_state.is_active(&UIContext::QuestLog) && input_resources.get_state().allows_ui_scroll();
+    let Some(mut scroll) = list_query.single_mut().ok().filter(|_| allowed) else {
+        // Drain so stale wheel ticks don't jump the list when it opens
+        mouse_wheel.clear();
+        return;
+    };
+
+    for event in mouse_wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        // Layout clamps the far end to the content height
+        scroll.y = (scroll.y - dy).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::{LocalPlatform, PlatformInventory};
+
+    fn claim_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CurrentQuest>()
+            .init_resource::<QuestCache>()
+            .add_message::<QuestRewardsClaimed>()
+            .add_systems(Update, handle_quest_log_claim);
+        let platform = app.world_mut().spawn(PlatformInventory::new()).id();
+        app.insert_resource(LocalPlatform(platform));
+        (app, platform)
+    }
+
+    #[test]
+    fn test_claim_from_log_gives_rewards_and_records_time() {
+        let (mut app, platform) = claim_app();
+        app.world_mut().resource_mut::<CurrentQuest>().completed = true;
+        let (quest_id, rewards) = {
+            let cache = app.world().resource::<QuestCache>();
+            (
+                cache.main_quests[0].id,
+                cache.main_quests[0].rewards.clone(),
+            )
+        };
+        app.world_mut()
+            .spawn((Button, QuestLogClaimButton, Interaction::Pressed));
+
+        app.update();
+
+        let inventory = app.world().get::<PlatformInventory>(platform).unwrap();
+        for (item_id, amount) in rewards {
+            assert_eq!(inventory.get_count_by_id(item_id), amount);
+        }
+        let quest = app.world().resource::<CurrentQuest>();
+        assert_eq!(quest.index, 1);
+        assert!(!quest.completed);
+        assert!(quest.claimed_at.contains_key(quest_id));
+
+        let claimed = app.world().resource::<Messages<QuestRewardsClaimed>>();
+        assert_eq!(
+            claimed
+                .iter_current_update_messages()
+                .map(|m| m.quest_id)
+                .collect::<Vec<_>>(),
+            vec![quest_id]
+        );
+    }
+
+    #[test]
+    fn test_claim_from_log_ignores_undelivered_quest() {
+        let (mut app, platform) = claim_app();
+        app.world_mut()
+            .spawn((Button, QuestLogClaimButton, Interaction::Pressed));
+
+        app.update();
+
+        let inventory = app.world().get::<PlatformInventory>(platform).unwrap();
+        assert!(inventory.is_empty());
+        let quest = app.world().resource::<CurrentQuest>();
+        assert_eq!(quest.index, 0);
+        assert!(quest.claimed_at.is_empty());
+    }
+}
@@ -2,9 +2,13 @@
 
 use bevy::prelude::*;
 
-use crate::components::UIContext;
+use crate::components::{UIContext, UIState};
 use crate::game_spec::{UIElementRegistry, UIElementTag};
-use crate::settings::GameSettings;
+use crate::input::{GameAction, InputBinding, InputManager};
+use crate::settings::{
+    GameSettings, SettingsChangedEvent, AUTOSAVE_INTERVAL_MAX, AUTOSAVE_INTERVAL_MIN,
+    LIGHT_BUDGET_MAX, LIGHT_BUDGET_MIN, UI_SCALE_MAX, UI_SCALE_MIN,
+};
 use crate::setup::ui::{
     text_font, SLOT_BORDER_COLOR, SLOT_RADIUS, TEXT_BODY, TEXT_HEADING, TEXT_SECTION, TEXT_SMALL,
 };
@@ -42,14 +46,95 @@ pub enum SettingType {
     MouseSensitivity,
     ViewDistance,
     Fov,
+    LightBudget,
+    ShadowQuality,
+    SimpleConveyorItems,
+    ConveyorItemIcons,
+    ConveyorBeltAnimation,
+    UiScale,
+    ColorblindMode,
+    MachineHoverCard,
+    PlacementWarnings,
     MasterVolume,
     SfxVolume,
     MusicVolume,
     VSync,
     Fullscreen,
     InvertY,
+    AutosaveInterval,
+}
+
+/// Tabs of the settings screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SettingsTab {
+    #[default]
+    Video,
+    Audio,
+    Controls,
+    Gameplay,
+}
+
+impl SettingsTab {
+    pub const ALL: [SettingsTab; 4] = [
+        SettingsTab::Video,
+        SettingsTab::Audio,
+        SettingsTab::Controls,
+        SettingsTab::Gameplay,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsTab::Video => "映像",
+            SettingsTab::Audio => "音声",
+            SettingsTab::Controls => "操作",
+            SettingsTab::Gameplay => "ゲームプレイ",
+        }
+    }
+
+    /// Neighbouring tab, wrapping around at either end
+    pub fn cycle(self, forward: bool) -> Self {
+        let index = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        let len = Self::ALL.len();
+        let next = if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        };
+        Self::ALL[next]
+    }
+}
+
+/// Tab header button
+#[derive(Component)]
+pub struct SettingsTabButton(pub SettingsTab);
+
+/// Container holding the rows of one tab
+#[derive(Component)]
+pub struct SettingsTabContent(pub SettingsTab);
+
+/// Row control reachable with keyboard navigation, in spawn order within its tab
+#[derive(Component)]
+pub struct SettingsFocusable {
+    pub tab: SettingsTab,
+    pub order: usize,
 }
 
+/// Button showing (and rebinding) the keys of one action
+#[derive(Component)]
+pub struct KeyBindButton(pub GameAction);
+
+/// Text inside a [`KeyBindButton`]
+#[derive(Component)]
+pub struct KeyBindText(pub GameAction);
+
+/// "Reset to defaults" button on the controls tab
+#[derive(Component)]
+pub struct KeyBindResetButton;
+
+/// Status line above the key binding list
+#[derive(Component)]
+pub struct KeyBindStatusText;
+
 /// Back button on settings panel
 #[derive(Component)]
 pub struct SettingsBackButton;
@@ -69,6 +154,35 @@ pub struct SliderDragState {
     pub dragging: Option<Entity>,
 }
 
+/// Selected tab and keyboard focus of the settings screen
+#[derive(Resource, Default)]
+pub struct SettingsScreenState {
+    pub tab: SettingsTab,
+    /// `SettingsFocusable::order` of the focused row in the current tab
+    pub focus: Option<usize>,
+}
+
+/// Key capture state of the rebind flow
+#[derive(Resource, Default)]
+pub struct KeyRebindState {
+    /// Action waiting for a key press
+    pub capturing: Option<GameAction>,
+    /// Result of the last rebind, shown above the list
+    pub message: String,
+}
+
+impl KeyRebindState {
+    pub fn is_capturing(&self) -> bool {
+        self.capturing.is_some()
+    }
+}
+
+const TAB_BG: Color = Color::srgb(0.18, 0.18, 0.22);
+const TAB_ACTIVE_BG: Color = Color::srgb(0.45, 0.3, 0.05);
+const KEY_BIND_BG: Color = Color::srgb(0.2, 0.2, 0.25);
+const KEY_BIND_CAPTURE_BG: Color = Color::srgb(0.45, 0.3, 0.05);
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.8, 0.0);
+
 /// Setup the settings UI panel
 pub fn setup_settings_ui(
     commands: &mut Commands,
@@ -100,7 +214,7 @@ pub fn setup_settings_ui(
             // Settings panel container
             root.spawn((
                 Node {
-                    width: Val::Px(500.0),
+                    width: Val::Px(640.0),
                     max_height: Val::Percent(85.0), // 画面の85%まで
                     padding: UiRect::all(Val::Px(20.0)),
                     flex_direction: FlexDirection::Column,
@@ -125,41 +239,177 @@ pub fn setup_settings_ui(
                     },
                 ));
 
-                // Graphics section
-                spawn_section_header(panel, font, "グラフィック");
-                spawn_slider(panel, font, "描画距離", SettingType::ViewDistance, 1.0, 8.0);
-                spawn_slider(panel, font, "視野角", SettingType::Fov, 45.0, 120.0);
-                spawn_toggle(panel, font, "VSync", SettingType::VSync);
-                spawn_toggle(panel, font, "フルスクリーン", SettingType::Fullscreen);
-
-                // Controls section
-                spawn_section_header(panel, font, "操作");
-                spawn_slider(
-                    panel,
-                    font,
-                    "マウス感度",
-                    SettingType::MouseSensitivity,
-                    0.0001,
-                    0.01,
-                );
-                spawn_toggle(panel, font, "Y軸反転", SettingType::InvertY);
-
-                // Audio section
-                spawn_section_header(panel, font, "音声");
-                spawn_slider(
-                    panel,
-                    font,
-                    "マスター音量",
-                    SettingType::MasterVolume,
-                    0.0,
-                    1.0,
-                );
-                spawn_slider(panel, font, "効果音", SettingType::SfxVolume, 0.0, 1.0);
-                spawn_slider(panel, font, "BGM", SettingType::MusicVolume, 0.0, 1.0);
-
-                // Update section
-                spawn_section_header(panel, font, "アップデート");
-                spawn_update_row(panel, font, ui_registry);
+                spawn_tab_bar(panel, font);
+
+                spawn_tab_content(panel, SettingsTab::Video, |content, focus| {
+                    spawn_section_header(content, font, "グラフィック");
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "描画距離",
+                        SettingType::ViewDistance,
+                        1.0,
+                        8.0,
+                    );
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "視野角",
+                        SettingType::Fov,
+                        45.0,
+                        120.0,
+                    );
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "ライト上限",
+                        SettingType::LightBudget,
+                        LIGHT_BUDGET_MIN as f32,
+                        LIGHT_BUDGET_MAX as f32,
+                    );
+                    spawn_toggle(content, font, focus, "影の品質", SettingType::ShadowQuality);
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "簡易コンベアアイテム",
+                        SettingType::SimpleConveyorItems,
+                    );
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "コンベアアイテムのアイコン表示",
+                        SettingType::ConveyorItemIcons,
+                    );
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "ベルトのアニメーション",
+                        SettingType::ConveyorBeltAnimation,
+                    );
+                    spawn_toggle(content, font, focus, "VSync", SettingType::VSync);
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "フルスクリーン",
+                        SettingType::Fullscreen,
+                    );
+
+                    // Accessibility section
+                    spawn_section_header(content, font, "アクセシビリティ");
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "UIスケール",
+                        SettingType::UiScale,
+                        UI_SCALE_MIN,
+                        UI_SCALE_MAX,
+                    );
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "色覚サポート",
+                        SettingType::ColorblindMode,
+                    );
+                });
+
+                spawn_tab_content(panel, SettingsTab::Audio, |content, focus| {
+                    spawn_section_header(content, font, "音声");
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "マスター音量",
+                        SettingType::MasterVolume,
+                        0.0,
+                        1.0,
+                    );
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "効果音",
+                        SettingType::SfxVolume,
+                        0.0,
+                        1.0,
+                    );
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "BGM",
+                        SettingType::MusicVolume,
+                        0.0,
+                        1.0,
+                    );
+                });
+
+                spawn_tab_content(panel, SettingsTab::Controls, |content, focus| {
+                    spawn_section_header(content, font, "マウス");
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "マウス感度",
+                        SettingType::MouseSensitivity,
+                        0.0001,
+                        0.01,
+                    );
+                    spawn_toggle(content, font, focus, "Y軸反転", SettingType::InvertY);
+
+                    spawn_section_header(content, font, "キー割り当て");
+                    spawn_key_bindings(content, font, focus);
+                });
+
+                spawn_tab_content(panel, SettingsTab::Gameplay, |content, focus| {
+                    spawn_section_header(content, font, "ゲームプレイ");
+                    spawn_slider(
+                        content,
+                        font,
+                        focus,
+                        "オートセーブ間隔",
+                        SettingType::AutosaveInterval,
+                        0.0,
+                        AUTOSAVE_INTERVAL_MAX,
+                    );
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "機械ホバー情報",
+                        SettingType::MachineHoverCard,
+                    );
+                    spawn_toggle(
+                        content,
+                        font,
+                        focus,
+                        "設置時の警告",
+                        SettingType::PlacementWarnings,
+                    );
+
+                    // Update section
+                    spawn_section_header(content, font, "アップデート");
+                    spawn_update_row(content, font, ui_registry);
+                });
+
+                // Keyboard hint
+                panel.spawn((
+                    Text::new("Tab: タブ切替  ↑↓: 選択  ←→: 調整  Enter: 決定  Esc: 戻る"),
+                    text_font(font, TEXT_SMALL),
+                    TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                    Node {
+                        align_self: AlignSelf::Center,
+                        ..default()
+                    },
+                ));
 
                 // Back button
                 panel
@@ -171,7 +421,6 @@ pub fn setup_settings_ui(
                             height: Val::Px(40.0),
                             justify_content: JustifyContent::Center,
                             align_items: AlignItems::Center,
-                            margin: UiRect::top(Val::Px(20.0)),
                             align_self: AlignSelf::Center,
                             border: UiRect::all(Val::Px(2.0)),
                             border_radius: BorderRadius::all(Val::Px(6.0)),
@@ -191,6 +440,166 @@ pub fn setup_settings_ui(
         });
 }
 
+/// Focus order of the rows spawned into one tab
+struct FocusOrder {
+    tab: SettingsTab,
+    next: usize,
+}
+
+impl FocusOrder {
+    fn next_focusable(&mut self) -> SettingsFocusable {
+        self.next += 1;
+        SettingsFocusable {
+            tab: self.tab,
+            order: self.next - 1,
+        }
+    }
+}
+
+/// Outline drawn around the keyboard-focused row (transparent until focused)
+fn focus_outline() -> Outline {
+    Outline::new(Val::Px(2.0), Val::Px(2.0), Color::NONE)
+}
+
+fn spawn_tab_bar(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|bar| {
+            for tab in SettingsTab::ALL {
+                bar.spawn((
+                    Button,
+                    SettingsTabButton(tab),
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        border_radius: BorderRadius::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(TAB_BG),
+                    BorderColor::all(SLOT_BORDER_COLOR),
+                ))
+                .with_child((
+                    Text::new(tab.label()),
+                    text_font(font, TEXT_BODY),
+                    TextColor(Color::WHITE),
+                ));
+            }
+        });
+}
+
+fn spawn_tab_content(
+    parent: &mut ChildSpawnerCommands,
+    tab: SettingsTab,
+    spawn_rows: impl FnOnce(&mut ChildSpawnerCommands, &mut FocusOrder),
+) {
+    parent
+        .spawn((
+            SettingsTabContent(tab),
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(15.0),
+                display: if tab == SettingsTab::default() {
+                    Display::Flex
+                } else {
+                    Display::None
+                },
+                ..default()
+            },
+        ))
+        .with_children(|content| {
+            let mut focus = FocusOrder { tab, next: 0 };
+            spawn_rows(content, &mut focus);
+        });
+}
+
+/// Status line, two-column list of every action and the reset button
+fn spawn_key_bindings(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    focus: &mut FocusOrder,
+) {
+    parent.spawn((
+        KeyBindStatusText,
+        Text::new("変更する操作のキーをクリック"),
+        text_font(font, TEXT_SMALL),
+        TextColor(Color::srgb(0.67, 0.67, 0.67)),
+    ));
+
+    parent
+        .spawn(Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::flex(2, 1.0),
+            column_gap: Val::Px(16.0),
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|grid| {
+            for action in GameAction::ALL {
+                grid.spawn(Node {
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Text::new(action.label()),
+                        text_font(font, TEXT_SMALL),
+                        TextColor(Color::WHITE),
+                    ));
+                    row.spawn((
+                        Button,
+                        KeyBindButton(action),
+                        focus.next_focusable(),
+                        focus_outline(),
+                        Node {
+                            min_width: Val::Px(110.0),
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(3.0)),
+                            justify_content: JustifyContent::Center,
+                            border: UiRect::all(Val::Px(1.0)),
+                            border_radius: BorderRadius::all(Val::Px(4.0)),
+                            ..default()
+                        },
+                        BackgroundColor(KEY_BIND_BG),
+                        BorderColor::all(SLOT_BORDER_COLOR),
+                    ))
+                    .with_child((
+                        KeyBindText(action),
+                        Text::new(""),
+                        text_font(font, TEXT_SMALL),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+            }
+        });
+
+    parent
+        .spawn((
+            Button,
+            KeyBindResetButton,
+            focus.next_focusable(),
+            focus_outline(),
+            Node {
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                align_self: AlignSelf::FlexEnd,
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(KEY_BIND_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_child((
+            Text::new("初期設定に戻す"),
+            text_font(font, TEXT_BODY),
+            TextColor(Color::WHITE),
+        ));
+}
+
 fn spawn_section_header(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, label: &str) {
     parent.spawn((
         Text::new(label),
@@ -206,6 +615,7 @@ fn spawn_section_header(parent: &mut ChildSpawnerCommands, font: &Handle<Font>,
 fn spawn_slider(
     parent: &mut ChildSpawnerCommands,
     font: &Handle<Font>,
+    focus: &mut FocusOrder,
     label: &str,
     setting: SettingType,
     min: f32,
@@ -234,6 +644,8 @@ fn spawn_slider(
             row.spawn((
                 Button,
                 SettingsSlider { setting, min, max },
+                focus.next_focusable(),
+                focus_outline(),
                 Node {
                     width: Val::Px(200.0),
                     height: Val::Px(20.0),
@@ -326,6 +738,7 @@ fn spawn_update_row(
 fn spawn_toggle(
     parent: &mut ChildSpawnerCommands,
     font: &Handle<Font>,
+    focus: &mut FocusOrder,
     label: &str,
     setting: SettingType,
 ) {
@@ -348,6 +761,8 @@ fn spawn_toggle(
             row.spawn((
                 Button,
                 SettingsToggle { setting },
+                focus.next_focusable(),
+                focus_outline(),
                 Node {
                     width: Val::Px(50.0),
                     height: Val::Px(26.0),
@@ -403,8 +818,12 @@ pub fn update_settings_ui(
 
     // Update value text displays
     for (mut text, value_text) in value_texts.iter_mut() {
-        let (value, _min, _max) = get_setting_value(&settings, value_text.setting);
-        **text = format_setting_value(value_text.setting, value);
+        **text = if value_text.setting == SettingType::ShadowQuality {
+            settings.shadow_quality.label().to_string()
+        } else {
+            let (value, _min, _max) = get_setting_value(&settings, value_text.setting);
+            format_setting_value(value_text.setting, value)
+        };
     }
 
     // Update toggle colors
@@ -423,10 +842,27 @@ fn get_setting_value(settings: &GameSettings, setting: SettingType) -> (f32, f32
         SettingType::MouseSensitivity => (settings.mouse_sensitivity, 0.0001, 0.01),
         SettingType::ViewDistance => (settings.view_distance as f32, 1.0, 8.0),
         SettingType::Fov => (settings.fov, 45.0, 120.0),
+        SettingType::LightBudget => (
+            settings.light_budget as f32,
+            LIGHT_BUDGET_MIN as f32,
+            LIGHT_BUDGET_MAX as f32,
+        ),
+        SettingType::UiScale => (settings.ui_scale, UI_SCALE_MIN, UI_SCALE_MAX),
         SettingType::MasterVolume => (settings.master_volume, 0.0, 1.0),
         SettingType::SfxVolume => (settings.sfx_volume, 0.0, 1.0),
         SettingType::MusicVolume => (settings.music_volume, 0.0, 1.0),
-        _ => (0.0, 0.0, 1.0),
+        SettingType::AutosaveInterval => {
+            (settings.autosave_interval_secs, 0.0, AUTOSAVE_INTERVAL_MAX)
+        }
+        // Toggles: 1.0 = ON
+        toggle => {
+            let value = if get_toggle_value(settings, toggle) {
+                1.0
+            } else {
+                0.0
+            };
+            (value, 0.0, 1.0)
+        }
     }
 }
 
@@ -435,6 +871,13 @@ fn get_toggle_value(settings: &GameSettings, setting: SettingType) -> bool {
         SettingType::VSync => settings.vsync_enabled,
         SettingType::Fullscreen => settings.fullscreen,
         SettingType::InvertY => settings.invert_y,
+        SettingType::ShadowQuality => settings.shadow_quality.shadows_enabled(),
+        SettingType::SimpleConveyorItems => settings.simple_conveyor_items,
+        SettingType::ConveyorItemIcons => settings.conveyor_item_icons,
+        SettingType::ConveyorBeltAnimation => settings.conveyor_belt_animation,
+        SettingType::ColorblindMode => settings.colorblind_mode,
+        SettingType::MachineHoverCard => settings.machine_hover_card,
+        SettingType::PlacementWarnings => settings.placement_warnings,
         _ => false,
     }
 }
@@ -442,12 +885,24 @@ fn get_toggle_value(settings: &GameSettings, setting: SettingType) -> bool {
 fn format_setting_value(setting: SettingType, value: f32) -> String {
     match setting {
         SettingType::MouseSensitivity => format!("{:.4}", value),
-        SettingType::ViewDistance => format!("{}", value as i32),
+        SettingType::ViewDistance | SettingType::LightBudget => format!("{}", value as i32),
         SettingType::Fov => format!("{}°", value as i32),
+        SettingType::UiScale => format!("{}%", (value * 100.0).round() as i32),
         SettingType::MasterVolume | SettingType::SfxVolume | SettingType::MusicVolume => {
             format!("{}%", (value * 100.0) as i32)
         }
-        SettingType::VSync | SettingType::Fullscreen | SettingType::InvertY => {
+        SettingType::AutosaveInterval if value < 1.0 => "OFF".to_string(),
+        SettingType::AutosaveInterval => format!("{}秒", value.round() as i32),
+        SettingType::VSync
+        | SettingType::Fullscreen
+        | SettingType::InvertY
+        | SettingType::ShadowQuality
+        | SettingType::SimpleConveyorItems
+        | SettingType::ConveyorItemIcons
+        | SettingType::ConveyorBeltAnimation
+        | SettingType::ColorblindMode
+        | SettingType::MachineHoverCard
+        | SettingType::PlacementWarnings => {
             if value > 0.5 {
                 "ON".to_string()
             } else {
@@ -482,7 +937,7 @@ pub fn handle_settings_sliders(
     drag_state: Res<SliderDragState>,
     slider_query: Query<(&SettingsSlider, &Node, &GlobalTransform)>,
     mut settings: ResMut<GameSettings>,
-    mut settings_changed: MessageWriter<crate::settings::SettingsChangedEvent>,
+    mut settings_changed: MessageWriter<SettingsChangedEvent>,
     windows: Query<&Window>,
 ) {
     // Only process when dragging
@@ -513,41 +968,85 @@ pub fn handle_settings_sliders(
     let percent = (relative_x / width).clamp(0.0, 1.0);
     let value = slider.min + percent * (slider.max - slider.min);
 
-    // Update setting
-    match slider.setting {
+    set_slider_value(&mut settings, slider.setting, value);
+    settings_changed.write(SettingsChangedEvent);
+}
+
+/// Write a slider value into the settings, snapping stepped settings
+fn set_slider_value(settings: &mut GameSettings, setting: SettingType, value: f32) {
+    match setting {
         SettingType::MouseSensitivity => settings.mouse_sensitivity = value,
         SettingType::ViewDistance => settings.view_distance = value.round() as i32,
         SettingType::Fov => settings.fov = value,
+        SettingType::LightBudget => settings.light_budget = value.round() as u32,
+        // Snap to 5% steps so the live preview doesn't jitter while dragging
+        SettingType::UiScale => settings.ui_scale = (value * 20.0).round() / 20.0,
         SettingType::MasterVolume => settings.master_volume = value,
         SettingType::SfxVolume => settings.sfx_volume = value,
         SettingType::MusicVolume => settings.music_volume = value,
+        // The bottom of the slider turns autosave off; anything else is at least the minimum
+        SettingType::AutosaveInterval => {
+            let snapped = (value / 15.0).round() * 15.0;
+            settings.autosave_interval_secs = if snapped < AUTOSAVE_INTERVAL_MIN / 2.0 {
+                0.0
+            } else {
+                snapped.max(AUTOSAVE_INTERVAL_MIN)
+            };
+        }
         _ => {}
     }
 
     settings.validate();
-    settings_changed.write(crate::settings::SettingsChangedEvent);
+}
+
+/// Flip a toggle setting (shadow quality cycles through its levels)
+fn toggle_setting(settings: &mut GameSettings, setting: SettingType) {
+    match setting {
+        SettingType::VSync => settings.vsync_enabled = !settings.vsync_enabled,
+        SettingType::Fullscreen => settings.fullscreen = !settings.fullscreen,
+        SettingType::InvertY => settings.invert_y = !settings.invert_y,
+        SettingType::ShadowQuality => settings.shadow_quality = settings.shadow_quality.next(),
+        SettingType::SimpleConveyorItems => {
+            settings.simple_conveyor_items = !settings.simple_conveyor_items
+        }
+        SettingType::ConveyorItemIcons => {
+            settings.conveyor_item_icons = !settings.conveyor_item_icons
+        }
+        SettingType::ConveyorBeltAnimation => {
+            settings.conveyor_belt_animation = !settings.conveyor_belt_animation
+        }
+        SettingType::ColorblindMode => settings.colorblind_mode = !settings.colorblind_mode,
+        SettingType::MachineHoverCard => settings.machine_hover_card = !settings.machine_hover_card,
+        SettingType::PlacementWarnings => {
+            settings.placement_warnings = !settings.placement_warnings
+        }
+        _ => {}
+    }
+}
+
+/// Keyboard step of a slider: a twentieth of its range, or its natural unit
+fn slider_step(setting: SettingType, min: f32, max: f32) -> f32 {
+    match setting {
+        SettingType::ViewDistance | SettingType::LightBudget => 1.0,
+        SettingType::Fov => 5.0,
+        SettingType::AutosaveInterval => 30.0,
+        _ => (max - min) / 20.0,
+    }
 }
 
 /// Handle toggle interactions
 pub fn handle_settings_toggles(
     mut interaction_query: Query<(&Interaction, &SettingsToggle), Changed<Interaction>>,
     mut settings: ResMut<GameSettings>,
-    mut settings_changed: MessageWriter<crate::settings::SettingsChangedEvent>,
+    mut settings_changed: MessageWriter<SettingsChangedEvent>,
 ) {
     for (interaction, toggle) in interaction_query.iter_mut() {
         if *interaction != Interaction::Pressed {
             continue;
         }
 
-        // Toggle the setting
-        match toggle.setting {
-            SettingType::VSync => settings.vsync_enabled = !settings.vsync_enabled,
-            SettingType::Fullscreen => settings.fullscreen = !settings.fullscreen,
-            SettingType::InvertY => settings.invert_y = !settings.invert_y,
-            _ => {}
-        }
-
-        settings_changed.write(crate::settings::SettingsChangedEvent);
+        toggle_setting(&mut settings, toggle.setting);
+        settings_changed.write(SettingsChangedEvent);
     }
 }
 
@@ -575,6 +1074,269 @@ pub fn handle_settings_back(
     }
 }
 
+/// Switch tabs on header click
+pub fn handle_settings_tabs(
+    interaction_query: Query<(&Interaction, &SettingsTabButton), Changed<Interaction>>,
+    mut screen: ResMut<SettingsScreenState>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed && screen.tab != button.0 {
+            screen.tab = button.0;
+            screen.focus = None;
+        }
+    }
+}
+
+/// Show the selected tab's rows and highlight its header
+pub fn update_settings_tabs(
+    screen: Res<SettingsScreenState>,
+    mut contents: Query<(&SettingsTabContent, &mut Node)>,
+    mut buttons: Query<(&SettingsTabButton, &mut BackgroundColor)>,
+) {
+    if !screen.is_changed() {
+        return;
+    }
+
+    for (content, mut node) in contents.iter_mut() {
+        node.display = if content.0 == screen.tab {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for (button, mut bg) in buttons.iter_mut() {
+        *bg = BackgroundColor(if button.0 == screen.tab {
+            TAB_ACTIVE_BG
+        } else {
+            TAB_BG
+        });
+    }
+}
+
+/// Capture a new key for the selected action, or start a capture / reset on click
+///
+/// Runs after `ui_escape_handler`, which ignores ESC while a capture is pending
+/// so ESC cancels the capture instead of closing the screen.
+#[allow(clippy::type_complexity)]
+pub fn handle_key_rebind(
+    ui_state: Res<UIState>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut input: ResMut<InputManager>,
+    mut rebind: ResMut<KeyRebindState>,
+    bind_buttons: Query<(&Interaction, &KeyBindButton), Changed<Interaction>>,
+    reset_buttons: Query<&Interaction, (Changed<Interaction>, With<KeyBindResetButton>)>,
+) {
+    if !ui_state.is_active(&UIContext::Settings) {
+        if rebind.is_capturing() {
+            rebind.capturing = None;
+            rebind.message.clear();
+        }
+        return;
+    }
+
+    if let Some(action) = rebind.capturing {
+        if key_input.just_pressed(KeyCode::Escape) {
+            rebind.capturing = None;
+            rebind.message = "キャンセルしました".to_string();
+            return;
+        }
+        let Some(&key) = key_input
+            .get_just_pressed()
+            .find(|&&key| InputBinding::is_bindable_key(key))
+        else {
+            return;
+        };
+        let binding = InputBinding::Key(key);
+        rebind.capturing = None;
+        rebind.message = match input.rebind(action, binding) {
+            Ok(()) => format!("{} を {} に割り当てました", action.label(), binding.label()),
+            Err(conflicts) => {
+                let names: Vec<&str> = conflicts.iter().map(|a| a.label()).collect();
+                format!("{} は {} で使用中です", binding.label(), names.join("、"))
+            }
+        };
+        return;
+    }
+
+    for (interaction, button) in bind_buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            start_capture(&mut rebind, button.0);
+        }
+    }
+
+    if reset_buttons.iter().any(|i| *i == Interaction::Pressed) {
+        reset_key_bindings(&mut input, &mut rebind);
+    }
+}
+
+fn start_capture(rebind: &mut KeyRebindState, action: GameAction) {
+    rebind.capturing = Some(action);
+    rebind.message = format!(
+        "{} に割り当てるキーを押してください (Esc: キャンセル)",
+        action.label()
+    );
+}
+
+fn reset_key_bindings(input: &mut InputManager, rebind: &mut KeyRebindState) {
+    input.reset_bindings();
+    rebind.message = "キー割り当てを初期設定に戻しました".to_string();
+}
+
+/// Outline the keyboard-focused row
+pub fn update_settings_focus(
+    screen: Res<SettingsScreenState>,
+    mut focusables: Query<(&SettingsFocusable, &mut Outline)>,
+) {
+    if !screen.is_changed() {
+        return;
+    }
+
+    for (focusable, mut outline) in focusables.iter_mut() {
+        let focused = focusable.tab == screen.tab && screen.focus == Some(focusable.order);
+        outline.color = if focused {
+            FOCUS_OUTLINE_COLOR
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// Show each action's current keys and the rebind status line
+#[allow(clippy::type_complexity)]
+pub fn update_key_bind_texts(
+    ui_state: Res<UIState>,
+    input: Res<InputManager>,
+    rebind: Res<KeyRebindState>,
+    mut texts: Query<(&KeyBindText, &mut Text), Without<KeyBindStatusText>>,
+    mut buttons: Query<(&KeyBindButton, &mut BackgroundColor)>,
+    mut status: Query<&mut Text, With<KeyBindStatusText>>,
+) {
+    if !ui_state.is_active(&UIContext::Settings) {
+        return;
+    }
+
+    for (key_text, mut text) in texts.iter_mut() {
+        let label = if rebind.capturing == Some(key_text.0) {
+            "...".to_string()
+        } else {
+            input
+                .get_bindings(key_text.0)
+                .map(|bindings| {
+                    bindings
+                        .iter()
+                        .map(|b| b.label())
+                        .collect::<Vec<_>>()
+                        .join(" / ")
+                })
+                .unwrap_or_default()
+        };
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+
+    for (button, mut bg) in buttons.iter_mut() {
+        *bg = BackgroundColor(if rebind.capturing == Some(button.0) {
+            KEY_BIND_CAPTURE_BG
+        } else {
+            KEY_BIND_BG
+        });
+    }
+
+    if rebind.is_changed() && !rebind.message.is_empty() {
+        for mut text in status.iter_mut() {
+            text.0 = rebind.message.clone();
+        }
+    }
+}
+
+/// Keyboard navigation: Tab switches tabs, arrows move focus and adjust, Enter activates
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn handle_settings_keyboard(
+    ui_state: Res<UIState>,
+    mut input: ResMut<InputManager>,
+    mut screen: ResMut<SettingsScreenState>,
+    mut rebind: ResMut<KeyRebindState>,
+    mut settings: ResMut<GameSettings>,
+    mut settings_changed: MessageWriter<SettingsChangedEvent>,
+    focusables: Query<(
+        &SettingsFocusable,
+        Option<&SettingsSlider>,
+        Option<&SettingsToggle>,
+        Option<&KeyBindButton>,
+        Has<KeyBindResetButton>,
+    )>,
+) {
+    if !ui_state.is_active(&UIContext::Settings) || rebind.is_capturing() {
+        return;
+    }
+
+    if input.just_pressed(GameAction::QuickSelect) {
+        let forward = !input.pressed(GameAction::ModifierShift);
+        screen.tab = screen.tab.cycle(forward);
+        screen.focus = None;
+        return;
+    }
+
+    let count = focusables
+        .iter()
+        .filter(|(f, ..)| f.tab == screen.tab)
+        .count();
+    if count == 0 {
+        return;
+    }
+
+    if input.just_pressed(GameAction::LookDown) {
+        screen.focus = Some(screen.focus.map_or(0, |i| (i + 1) % count));
+        return;
+    }
+    if input.just_pressed(GameAction::LookUp) {
+        screen.focus = Some(screen.focus.map_or(count - 1, |i| (i + count - 1) % count));
+        return;
+    }
+
+    let Some(focus) = screen.focus else {
+        return;
+    };
+    let Some((_, slider, toggle, key_bind, is_reset)) = focusables
+        .iter()
+        .find(|(f, ..)| f.tab == screen.tab && f.order == focus)
+    else {
+        return;
+    };
+
+    let left = input.just_pressed(GameAction::LookLeft);
+    let right = input.just_pressed(GameAction::LookRight);
+    let confirm = input.just_pressed(GameAction::Confirm);
+
+    if let Some(slider) = slider {
+        if left || right {
+            let (value, min, max) = get_setting_value(&settings, slider.setting);
+            let step = slider_step(slider.setting, min, max);
+            let delta = if right { step } else { -step };
+            set_slider_value(
+                &mut settings,
+                slider.setting,
+                (value + delta).clamp(min, max),
+            );
+            settings_changed.write(SettingsChangedEvent);
+        }
+    } else if let Some(toggle) = toggle {
+        if left || right || confirm {
+            toggle_setting(&mut settings, toggle.setting);
+            settings_changed.write(SettingsChangedEvent);
+        }
+    } else if let Some(button) = key_bind {
+        if confirm {
+            rebind.capturing = Some(button.0);
+            // Runs after handle_key_rebind, so this Enter press isn't captured as the new key
+            start_capture(&mut rebind, button.0);
+        }
+    } else if is_reset && confirm {
+        reset_key_bindings(&mut input, &mut rebind);
+    }
+}
+
 /// Update the update section UI based on UpdateState
 #[cfg(feature = "updater")]
 pub fn update_settings_update_ui(
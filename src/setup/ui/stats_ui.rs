@@ -0,0 +1,272 @@
+//! Stats and achievements panel (opened from the pause menu)
+//!
+//! The production goals panel (`goals_ui`) opens from here.
+
+use bevy::prelude::*;
+
+use crate::achievements::{PlayerAchievements, ACHIEVEMENTS};
+use crate::components::UIContext;
+use crate::game_spec::{UIElementRegistry, UIElementTag};
+use crate::setup::ui::{
+    text_font, GoalsOpenButton, SLOT_BORDER_COLOR, SLOT_RADIUS, TEXT_BODY, TEXT_HEADING,
+    TEXT_SECTION, TEXT_SMALL,
+};
+use crate::statistics::{PlayerStats, StatKind};
+
+const LOCKED_COLOR: Color = Color::srgb(0.45, 0.45, 0.45);
+const UNLOCKED_COLOR: Color = Color::srgb(1.0, 0.8, 0.0);
+
+/// Marker for the stats panel root
+#[derive(Component)]
+pub struct StatsPanel;
+
+/// Value text for one stat counter
+#[derive(Component)]
+pub struct StatsValueText(pub StatKind);
+
+/// Name text for one achievement row
+#[derive(Component)]
+pub struct AchievementRowText(pub &'static str);
+
+/// Back button on stats panel
+#[derive(Component)]
+pub struct StatsBackButton;
+
+/// Setup the stats UI panel
+pub fn setup_stats_ui(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    ui_registry: &UIElementRegistry,
+) {
+    commands
+        .spawn((
+            StatsPanel,
+            ui_registry
+                .get_id("base:stats_menu")
+                .map(UIElementTag::new)
+                .unwrap_or_else(|| UIElementTag::new(Default::default())),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::NONE), // No background (use PauseUI background)
+            GlobalZIndex(101),            // Above pause menu
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(500.0),
+                    max_height: Val::Percent(85.0),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    overflow: Overflow::scroll_y(),
+                    border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.12, 0.12, 0.14, 0.98)),
+                BorderColor::all(SLOT_BORDER_COLOR),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("統計"),
+                    text_font(font, TEXT_HEADING),
+                    TextColor(Color::WHITE),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+
+                spawn_section_header(panel, font, "記録");
+                for kind in StatKind::ALL {
+                    spawn_stat_row(panel, font, kind);
+                }
+
+                spawn_section_header(panel, font, "実績");
+                for achievement in ACHIEVEMENTS.iter() {
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            row.spawn((
+                                Text::new(achievement.name),
+                                AchievementRowText(achievement.id),
+                                text_font(font, TEXT_BODY),
+                                TextColor(LOCKED_COLOR),
+                            ));
+                            row.spawn((
+                                Text::new(achievement.description),
+                                text_font(font, TEXT_SMALL),
+                                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                            ));
+                        });
+                }
+
+                // Production goals
+                panel
+                    .spawn((
+                        Button,
+                        GoalsOpenButton,
+                        Node {
+                            width: Val::Px(200.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(20.0)),
+                            align_self: AlignSelf::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            border_radius: BorderRadius::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+                        BorderColor::all(Color::srgb(0.8, 0.5, 0.0)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("生産目標"),
+                            text_font(font, TEXT_SECTION),
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+
+                // Back button
+                panel
+                    .spawn((
+                        Button,
+                        StatsBackButton,
+                        Node {
+                            width: Val::Px(150.0),
+                            height: Val::Px(40.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(8.0)),
+                            align_self: AlignSelf::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            border_radius: BorderRadius::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+                        BorderColor::all(Color::srgb(0.8, 0.5, 0.0)),
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("戻る"),
+                            text_font(font, TEXT_SECTION),
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            });
+        });
+}
+
+fn spawn_section_header(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, label: &str) {
+    parent.spawn((
+        Text::new(label),
+        text_font(font, TEXT_SECTION),
+        TextColor(Color::srgb(1.0, 0.8, 0.0)),
+        Node {
+            margin: UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(15.0), Val::Px(5.0)),
+            ..default()
+        },
+    ));
+}
+
+fn spawn_stat_row(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, kind: StatKind) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::SpaceBetween,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(kind.label()),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+            row.spawn((
+                Text::new("0"),
+                StatsValueText(kind),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Update stats panel visibility based on UIState
+pub fn update_stats_visibility(
+    ui_state: Res<crate::components::UIState>,
+    mut stats_query: Query<&mut Visibility, With<StatsPanel>>,
+) {
+    let Ok(mut visibility) = stats_query.single_mut() else {
+        return;
+    };
+
+    *visibility = if ui_state.is_active(&UIContext::Stats) {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+/// Refresh counters and achievement colors while the panel is open
+pub fn update_stats_ui(
+    ui_state: Res<crate::components::UIState>,
+    stats: Res<PlayerStats>,
+    achievements: Res<PlayerAchievements>,
+    mut value_texts: Query<(&mut Text, &StatsValueText)>,
+    mut achievement_texts: Query<(&mut TextColor, &AchievementRowText)>,
+) {
+    if !ui_state.is_active(&UIContext::Stats) {
+        return;
+    }
+    if !ui_state.is_changed() && !stats.is_changed() && !achievements.is_changed() {
+        return;
+    }
+
+    for (mut text, value) in value_texts.iter_mut() {
+        **text = stats.get(value.0).to_string();
+    }
+    for (mut color, row) in achievement_texts.iter_mut() {
+        *color = TextColor(if achievements.is_unlocked(row.0) {
+            UNLOCKED_COLOR
+        } else {
+            LOCKED_COLOR
+        });
+    }
+}
+
+/// Handle stats back button
+#[allow(clippy::type_complexity)]
+pub fn handle_stats_back(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<StatsBackButton>),
+    >,
+    mut action_writer: MessageWriter<crate::components::UIAction>,
+) {
+    for (interaction, mut bg) in interaction_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                action_writer.write(crate::components::UIAction::Pop);
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(Color::srgba(0.3, 0.3, 0.3, 0.95));
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9));
+            }
+        }
+    }
+}
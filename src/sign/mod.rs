@@ -0,0 +1,650 @@
+//! Signs: player-written labels for factory rows
+//!
+//! Signs are regular world blocks (placement, breaking and world saves
+//! already handle them; a broken sign drops a blank sign item). Every placed
+//! sign gets a `Sign` entity that is also a UI text node, kept over the
+//! sign's front face by projecting it through the player camera, like
+//! display panels.
+//!
+//! - The front face points back at the player who placed the sign, whether it
+//!   went against a wall or on top of a block
+//! - Right-click opens the text entry, prefilled with the current text. It is
+//!   the command input's text box (`CommandInputState` with `sign` set), so it
+//!   holds the focus the same way: movement and other keybinds are blocked
+//! - Text is at most SIGN_MAX_CHARS characters over SIGN_MAX_LINES lines
+//! - Text and face are saved; signs are rebuilt from the world blocks on load
+
+use bevy::prelude::*;
+
+use crate::components::{CommandInputState, CursorController, GameFont, PlayerCamera, UIContext};
+use crate::core::items;
+use crate::events::game_events::{BlockBroken, BlockPlaced};
+use crate::input::{GameAction, InputManager};
+use crate::main_menu::{AppState, InGameSet};
+use crate::setup::ui::{
+    text_font, QUEST_BORDER_COLOR, QUEST_RADIUS, TEXT_BODY, TEXT_MINI, TEXT_TITLE,
+};
+use crate::systems::command::keycode_to_char;
+use crate::utils::yaw_to_direction;
+use crate::{ContinuousActionTimer, Direction, TargetBlock, BLOCK_SIZE};
+
+/// Most characters a sign holds (line breaks not counted)
+pub const SIGN_MAX_CHARS: usize = 64;
+
+/// Most lines a sign holds
+pub const SIGN_MAX_LINES: usize = 3;
+
+/// Signs farther than this from the camera are hidden (blocks)
+pub const SIGN_VIEW_DISTANCE: f32 = 16.0;
+
+/// Fraction of the face width a line may fill
+const FACE_TEXT_WIDTH: f32 = 0.9;
+
+/// Glyph advance relative to the font size
+const GLYPH_ASPECT: f32 = 0.6;
+
+/// Font sizes are rounded to this step (every size gets its own glyph atlas)
+const FONT_SIZE_STEP: f32 = 2.0;
+
+const MIN_FONT_SIZE: f32 = 6.0;
+const MAX_FONT_SIZE: f32 = 48.0;
+
+/// A placed sign (the entity is also the sign's UI text node)
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Sign {
+    /// Block position of the sign
+    pub position: IVec3,
+    /// Face the text is shown on
+    pub face: Direction,
+    /// Text, clamped by `clamp_sign_text`
+    pub text: String,
+}
+
+impl Sign {
+    pub fn new(position: IVec3, face: Direction) -> Self {
+        Self {
+            position,
+            face,
+            text: String::new(),
+        }
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = clamp_sign_text(text);
+        self
+    }
+
+    /// World-space center of the front face
+    pub fn face_center(&self) -> Vec3 {
+        (self.position.as_vec3() + Vec3::splat(0.5)) * BLOCK_SIZE
+            + self.face.to_ivec3().as_vec3() * (BLOCK_SIZE * 0.5 + 0.01)
+    }
+}
+
+/// Limit text to SIGN_MAX_LINES lines and SIGN_MAX_CHARS characters
+///
+/// Control characters other than line breaks are dropped. An empty last line
+/// is kept, so a line break just typed in the editor survives.
+pub fn clamp_sign_text(text: &str) -> String {
+    let mut budget = SIGN_MAX_CHARS;
+    let lines: Vec<String> = text
+        .split('\n')
+        .take(SIGN_MAX_LINES)
+        .map(|line| {
+            let line: String = line
+                .chars()
+                .filter(|c| !c.is_control())
+                .take(budget)
+                .collect();
+            budget -= line.chars().count();
+            line
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Font size that fits `text` on a face `face_px` pixels wide
+///
+/// Sized by the longest line and the line count (SIGN_MAX_LINES always fit
+/// the face height), rounded to FONT_SIZE_STEP.
+pub fn fit_font_size(face_px: f32, text: &str) -> f32 {
+    let longest = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let by_width = face_px * FACE_TEXT_WIDTH / (longest.max(1) as f32 * GLYPH_ASPECT);
+    let by_height = face_px / (SIGN_MAX_LINES as f32 * 1.4);
+    let size = by_width.min(by_height);
+    ((size / FONT_SIZE_STEP).floor() * FONT_SIZE_STEP).clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+}
+
+/// Spawn a sign entity for each placed sign block (facing back at the player),
+/// despawn it when the block breaks
+fn track_signs(
+    mut commands: Commands,
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+    camera_query: Query<&PlayerCamera>,
+    signs: Query<(Entity, &Sign)>,
+) {
+    let sign_item = items::sign_block();
+
+    for event in broken.read() {
+        if event.block != sign_item {
+            continue;
+        }
+        for (entity, sign) in signs.iter() {
+            if sign.position == event.pos {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    for event in placed.read() {
+        if event.block != sign_item {
+            continue;
+        }
+        let face = camera_query
+            .single()
+            .map(|camera| yaw_to_direction(camera.yaw).opposite())
+            .unwrap_or(Direction::South);
+        info!(pos = ?event.pos, ?face, "Sign placed");
+        commands.spawn(Sign::new(event.pos, face));
+    }
+}
+
+/// Turn new sign entities into UI text nodes
+fn attach_sign_text(
+    mut commands: Commands,
+    game_font: Res<GameFont>,
+    added: Query<(Entity, &Sign), Added<Sign>>,
+) {
+    for (entity, sign) in added.iter() {
+        commands.entity(entity).insert((
+            Text::new(sign.text.clone()),
+            text_font(&game_font.0, MIN_FONT_SIZE),
+            TextColor(Color::srgb(0.15, 0.1, 0.05)),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Keep each sign's text over its front face, sized to fit the face
+#[allow(clippy::type_complexity)]
+fn position_signs(
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    mut signs: Query<(&Sign, &mut Text, &mut Node, &mut TextFont, &mut Visibility)>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let camera_pos = camera_transform.translation();
+    let right = camera_transform.right().as_vec3();
+
+    for (sign, mut text, mut node, mut font, mut visibility) in signs.iter_mut() {
+        if **text != sign.text {
+            **text = sign.text.clone();
+        }
+
+        let center = sign.face_center();
+        let normal = sign.face.to_ivec3().as_vec3();
+        let to_camera = camera_pos - center;
+        // Only the front face carries the text
+        let facing = to_camera.dot(normal) > 0.0;
+        let in_range = to_camera.length() < SIGN_VIEW_DISTANCE;
+
+        let half_width = right * BLOCK_SIZE * 0.5;
+        let projected = (
+            camera.world_to_viewport(camera_transform, center),
+            camera.world_to_viewport(camera_transform, center - half_width),
+            camera.world_to_viewport(camera_transform, center + half_width),
+        );
+        let (Ok(screen), Ok(left), Ok(right_edge)) = projected else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if !facing || !in_range || sign.text.is_empty() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let face_px = left.distance(right_edge);
+        let size = fit_font_size(face_px, &sign.text);
+        if font.font_size != size {
+            font.font_size = size;
+        }
+        let lines = sign.text.lines().count().max(1) as f32;
+        node.left = Val::Px(screen.x - face_px / 2.0);
+        node.width = Val::Px(face_px);
+        node.top = Val::Px(screen.y - size * 0.6 * lines);
+        *visibility = Visibility::Visible;
+    }
+}
+
+/// Right-click a sign to edit its text
+///
+/// Swallows the right-click it handles (see `BlockClickSet`).
+pub fn handle_sign_click(
+    input: Res<InputManager>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    mut cursor: ResMut<CursorController>,
+    mut command_state: ResMut<CommandInputState>,
+    target: Res<TargetBlock>,
+    signs: Query<(Entity, &Sign)>,
+) {
+    if !input.pressed(GameAction::SecondaryAction) || input.pressed(GameAction::ModifierShift) {
+        return;
+    }
+    if !cursor.is_locked() || command_state.open {
+        return;
+    }
+    let Some(pos) = target.break_target else {
+        return;
+    };
+    let Some((entity, sign)) = signs.iter().find(|(_, sign)| sign.position == pos) else {
+        return;
+    };
+
+    // Keep block_place from building on the sign (also while the button is held)
+    action_timer.place_timer.reset();
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+    mouse_button.clear_just_pressed(MouseButton::Right);
+
+    open_sign_editor(&mut command_state, entity, &sign.text);
+    cursor.request_ui(UIContext::CommandInput);
+}
+
+/// Focus the text entry on a sign, prefilled with its text
+pub fn open_sign_editor(command_state: &mut CommandInputState, sign: Entity, text: &str) {
+    command_state.open = true;
+    command_state.sign = Some(sign);
+    command_state.text = text.to_string();
+    command_state.skip_input_frame = true;
+    command_state.suggestion_index = 0;
+}
+
+fn close_sign_editor(command_state: &mut CommandInputState, cursor: &mut CursorController) {
+    command_state.open = false;
+    command_state.sign = None;
+    command_state.text.clear();
+    cursor.release_ui(&UIContext::CommandInput);
+}
+
+/// Root node of the sign editor
+#[derive(Component)]
+pub struct SignEditorUI;
+
+/// Text being typed in the sign editor
+#[derive(Component)]
+pub struct SignEditorText;
+
+/// Character count of the sign editor
+#[derive(Component)]
+pub struct SignEditorCount;
+
+fn setup_sign_editor(mut commands: Commands, game_font: Res<GameFont>) {
+    let font = &game_font.0;
+    commands
+        .spawn((
+            SignEditorUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-180.0)),
+                width: Val::Px(360.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                border: UiRect::all(Val::Px(2.0)),
+                border_radius: BorderRadius::all(Val::Px(QUEST_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.10, 0.10, 0.10, 0.95)),
+            BorderColor::all(QUEST_BORDER_COLOR),
+            Visibility::Hidden,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("看板"),
+                text_font(font, TEXT_TITLE),
+                TextColor(Color::srgb(1.0, 0.8, 0.0)),
+            ));
+            panel.spawn((
+                SignEditorText,
+                Text::new(""),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(Justify::Center),
+            ));
+            panel.spawn((
+                SignEditorCount,
+                Text::new(""),
+                text_font(font, TEXT_MINI),
+                TextColor(Color::srgb(0.67, 0.67, 0.67)),
+            ));
+            panel.spawn((
+                Text::new("Enter で決定 / Shift+Enter で改行 / ESC で取消"),
+                text_font(font, TEXT_MINI),
+                TextColor(Color::srgb(0.67, 0.67, 0.67)),
+            ));
+        });
+}
+
+/// Type into the sign being edited; Enter writes the text to the sign
+#[allow(clippy::type_complexity)]
+pub fn sign_text_input(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut command_state: ResMut<CommandInputState>,
+    mut cursor: ResMut<CursorController>,
+    mut signs: Query<&mut Sign>,
+    mut ui_query: Query<&mut Visibility, With<SignEditorUI>>,
+    mut text_query: Query<&mut Text, (With<SignEditorText>, Without<SignEditorCount>)>,
+    mut count_query: Query<&mut Text, With<SignEditorCount>>,
+) {
+    let editing = command_state.sign.filter(|_| command_state.open);
+    for mut visibility in ui_query.iter_mut() {
+        let target = if editing.is_some() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+    let Some(entity) = editing else {
+        // Closed from elsewhere (UI state change) without going through the editor
+        if command_state.sign.is_some() {
+            command_state.sign = None;
+        }
+        return;
+    };
+
+    // The sign was broken (or the world reloaded) while editing
+    if signs.get(entity).is_err() || key_input.just_pressed(KeyCode::Escape) {
+        close_sign_editor(&mut command_state, &mut cursor);
+        return;
+    }
+
+    let shift = key_input.pressed(KeyCode::ShiftLeft) || key_input.pressed(KeyCode::ShiftRight);
+    if key_input.just_pressed(KeyCode::Enter) {
+        if !shift {
+            if let Ok(mut sign) = signs.get_mut(entity) {
+                sign.text = clamp_sign_text(command_state.text.trim_end_matches('\n'));
+                info!(pos = ?sign.position, text = %sign.text, "Sign edited");
+            }
+            close_sign_editor(&mut command_state, &mut cursor);
+            return;
+        }
+        command_state.text.push('\n');
+    }
+
+    if key_input.just_pressed(KeyCode::Backspace) {
+        command_state.text.pop();
+    }
+
+    // Skip the frame the editor opened in
+    if command_state.skip_input_frame {
+        command_state.skip_input_frame = false;
+    } else {
+        for key in key_input.get_just_pressed() {
+            if let Some(c) = keycode_to_char(*key, shift) {
+                command_state.text.push(c);
+            }
+        }
+    }
+
+    let clamped = clamp_sign_text(&command_state.text);
+    if command_state.text != clamped {
+        command_state.text = clamped;
+    }
+
+    let display = format!("{}|", command_state.text);
+    for mut text in text_query.iter_mut() {
+        if **text != display {
+            **text = display.clone();
+        }
+    }
+    let count = format!(
+        "{}/{}",
+        command_state.text.chars().filter(|c| *c != '\n').count(),
+        SIGN_MAX_CHARS
+    );
+    for mut text in count_query.iter_mut() {
+        if **text != count {
+            **text = count.clone();
+        }
+    }
+}
+
+pub struct SignPlugin;
+
+impl Plugin for SignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup_sign_editor)
+            .add_systems(
+                Update,
+                (
+                    track_signs,
+                    attach_sign_text,
+                    position_signs,
+                    sign_text_input,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{InputState, InteractingMachine, InventoryOpen};
+
+    #[test]
+    fn test_text_is_clamped() {
+        let long = "a".repeat(100);
+        assert_eq!(clamp_sign_text(&long).chars().count(), SIGN_MAX_CHARS);
+
+        // Lines past the third are dropped
+        assert_eq!(clamp_sign_text("one\ntwo\nthree\nfour"), "one\ntwo\nthree");
+
+        // The character budget spans the lines, line breaks don't count
+        let text = format!("{}\n{}\n{}", "a".repeat(30), "b".repeat(30), "c".repeat(30));
+        let clamped = clamp_sign_text(&text);
+        assert_eq!(
+            clamped.chars().filter(|c| *c != '\n').count(),
+            SIGN_MAX_CHARS
+        );
+        assert!(clamped.ends_with("\ncccc"));
+
+        // Multi-byte text counts characters, not bytes
+        let kana = "あ".repeat(70);
+        assert_eq!(clamp_sign_text(&kana).chars().count(), SIGN_MAX_CHARS);
+
+        // A fresh line break is kept, other control characters are not
+        assert_eq!(clamp_sign_text("smelting\n"), "smelting\n");
+        assert_eq!(clamp_sign_text("a\tb\r"), "ab");
+        assert_eq!(
+            Sign::new(IVec3::ZERO, Direction::North)
+                .with_text(&long)
+                .text
+                .len(),
+            SIGN_MAX_CHARS
+        );
+    }
+
+    #[test]
+    fn test_font_fits_face() {
+        for face_px in [20.0, 64.0, 150.0, 400.0] {
+            for text in ["A", "copper line", &"x".repeat(SIGN_MAX_CHARS)] {
+                let size = fit_font_size(face_px, text);
+                let width = size * GLYPH_ASPECT * text.chars().count() as f32;
+                assert!(size == MIN_FONT_SIZE || width <= face_px * FACE_TEXT_WIDTH);
+                assert!(size <= MAX_FONT_SIZE);
+            }
+        }
+        // Longer lines never get a larger font
+        assert!(fit_font_size(150.0, "smelting row") <= fit_font_size(150.0, "ore"));
+    }
+
+    fn editor_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<CommandInputState>()
+            .init_resource::<CursorController>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_systems(Update, sign_text_input);
+        app
+    }
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.reset_all();
+        for key in keys {
+            input.press(*key);
+        }
+        app.update();
+    }
+
+    #[test]
+    fn test_editor_prefills_and_writes_text() {
+        let mut app = editor_app();
+        let sign = app
+            .world_mut()
+            .spawn(Sign::new(IVec3::new(3, 8, 3), Direction::North).with_text("ore"))
+            .id();
+        open_sign_editor(
+            &mut app.world_mut().resource_mut::<CommandInputState>(),
+            sign,
+            "ore",
+        );
+
+        // The opening frame types nothing
+        press(&mut app, &[KeyCode::KeyX]);
+        assert_eq!(app.world().resource::<CommandInputState>().text, "ore");
+
+        press(&mut app, &[KeyCode::KeyS]);
+        press(&mut app, &[KeyCode::ShiftLeft, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::KeyA]);
+        assert_eq!(app.world().resource::<CommandInputState>().text, "ores\na");
+        // Nothing is written before Enter
+        assert_eq!(app.world().get::<Sign>(sign).unwrap().text, "ore");
+
+        press(&mut app, &[KeyCode::Enter]);
+        assert_eq!(app.world().get::<Sign>(sign).unwrap().text, "ores\na");
+        let state = app.world().resource::<CommandInputState>();
+        assert!(!state.open && state.sign.is_none());
+
+        // ESC discards the edit
+        open_sign_editor(
+            &mut app.world_mut().resource_mut::<CommandInputState>(),
+            sign,
+            "ores\na",
+        );
+        press(&mut app, &[]);
+        press(&mut app, &[KeyCode::Backspace]);
+        press(&mut app, &[KeyCode::Escape]);
+        assert_eq!(app.world().get::<Sign>(sign).unwrap().text, "ores\na");
+        assert!(!app.world().resource::<CommandInputState>().open);
+    }
+
+    #[test]
+    fn test_typing_stops_at_limits() {
+        let mut app = editor_app();
+        let sign = app
+            .world_mut()
+            .spawn(Sign::new(IVec3::ZERO, Direction::South))
+            .id();
+        let full = format!("{}\n{}", "a".repeat(40), "b".repeat(SIGN_MAX_CHARS - 40));
+        open_sign_editor(
+            &mut app.world_mut().resource_mut::<CommandInputState>(),
+            sign,
+            &full,
+        );
+        press(&mut app, &[]);
+        press(&mut app, &[KeyCode::KeyC]);
+        assert_eq!(app.world().resource::<CommandInputState>().text, full);
+
+        // A fourth line can't be started
+        open_sign_editor(
+            &mut app.world_mut().resource_mut::<CommandInputState>(),
+            sign,
+            "a\nb\nc",
+        );
+        press(&mut app, &[]);
+        press(&mut app, &[KeyCode::ShiftLeft, KeyCode::Enter]);
+        assert_eq!(app.world().resource::<CommandInputState>().text, "a\nb\nc");
+    }
+
+    #[test]
+    fn test_editing_holds_input_focus() {
+        let mut command_state = CommandInputState::default();
+        let mut cursor = CursorController::default();
+        cursor.request_lock();
+        let sign = World::new().spawn_empty().id();
+        open_sign_editor(&mut command_state, sign, "");
+        cursor.request_ui(UIContext::CommandInput);
+
+        let state = InputState::current(
+            &InventoryOpen::default(),
+            &InteractingMachine::default(),
+            &command_state,
+            &cursor,
+        );
+        assert_eq!(state, InputState::Command);
+        assert!(!state.allows_movement());
+        assert!(!state.allows_camera());
+        assert!(!state.allows_block_actions());
+        assert!(!state.allows_hotbar());
+
+        close_sign_editor(&mut command_state, &mut cursor);
+        let state = InputState::current(
+            &InventoryOpen::default(),
+            &InteractingMachine::default(),
+            &command_state,
+            &cursor,
+        );
+        assert_eq!(state, InputState::Gameplay);
+    }
+
+    #[test]
+    fn test_signs_track_blocks() {
+        let mut app = App::new();
+        app.add_message::<BlockPlaced>()
+            .add_message::<BlockBroken>()
+            .add_systems(Update, track_signs);
+
+        let pos = IVec3::new(1, 8, 1);
+        app.world_mut().write_message(BlockPlaced {
+            pos,
+            block: items::sign_block(),
+            source: crate::events::game_events::EventSource::System,
+        });
+        app.update();
+        let signs: Vec<Sign> = app
+            .world_mut()
+            .query::<&Sign>()
+            .iter(app.world())
+            .cloned()
+            .collect();
+        assert_eq!(signs, vec![Sign::new(pos, Direction::South)]);
+
+        app.world_mut().write_message(BlockBroken {
+            pos,
+            block: items::sign_block(),
+            source: crate::events::game_events::EventSource::System,
+        });
+        app.update();
+        assert_eq!(
+            app.world_mut().query::<&Sign>().iter(app.world()).count(),
+            0
+        );
+    }
+}
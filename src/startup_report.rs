@@ -0,0 +1,395 @@
+//! Startup data loading report
+//!
+//! Every data file read at startup (base mod items/machines/recipes, worldgen
+//! config, settings and key bindings) produces a `LoadOutcome` that is
+//! collected into the `StartupReport` resource:
+//!
+//! - warnings: the bad entry was skipped or defaults were used; each one is
+//!   logged under the `DATA` category (F8 console) and a toast points at them
+//!   when a world starts
+//! - errors: core content could not be read at all; instead of running with
+//!   missing items, an error screen lists the file and line/column
+//!
+//! The report is also written to `logs/startup_report.json`.
+
+use crate::components::GameFont;
+use crate::main_menu::AppState;
+use crate::setup::ui::{text_font, TEXT_BODY, TEXT_BUTTON, TEXT_LARGE, TEXT_SMALL};
+use crate::systems::WrenchToast;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// Where the report is written (native only)
+pub const STARTUP_REPORT_PATH: &str = "logs/startup_report.json";
+
+/// One problem in a data file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataIssue {
+    pub file: String,
+    /// 1-based line, when the parser reports one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column, when the parser reports one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl DataIssue {
+    pub fn new(file: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attach a 1-based position
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn from_yaml_error(file: impl Into<String>, error: &serde_yaml::Error) -> Self {
+        let issue = Self::new(file, error.to_string());
+        match error.location() {
+            Some(location) => issue.at(location.line(), location.column()),
+            None => issue,
+        }
+    }
+
+    /// `content` is the parsed text; toml errors only carry a byte span
+    pub fn from_toml_error(
+        file: impl Into<String>,
+        content: &str,
+        error: &toml::de::Error,
+    ) -> Self {
+        let issue = Self::new(file, error.message());
+        match error.span() {
+            Some(span) => {
+                let (line, column) = line_column(content, span.start);
+                issue.at(line, column)
+            }
+            None => issue,
+        }
+    }
+
+    pub fn from_json_error(file: impl Into<String>, error: &serde_json::Error) -> Self {
+        let issue = Self::new(file, error.to_string());
+        if error.line() == 0 {
+            issue
+        } else {
+            issue.at(error.line(), error.column())
+        }
+    }
+
+    /// "file:line:col" (or just the file without a position)
+    pub fn location(&self) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", self.file, line, column),
+            (Some(line), None) => format!("{}:{}", self.file, line),
+            _ => self.file.clone(),
+        }
+    }
+}
+
+impl fmt::Display for DataIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location(), self.message)
+    }
+}
+
+/// 1-based line and column of a byte offset
+pub fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Result of one loader
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LoadOutcome {
+    /// What was loaded ("base mod", "worldgen config", ...)
+    pub source: String,
+    /// Entries loaded successfully
+    pub loaded: usize,
+    /// Recoverable: entry skipped or defaults used
+    pub warnings: Vec<DataIssue>,
+    /// Fatal: content missing with no fallback
+    pub errors: Vec<DataIssue>,
+}
+
+impl LoadOutcome {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            ..default()
+        }
+    }
+
+    pub fn warn(&mut self, issue: DataIssue) {
+        self.warnings.push(issue);
+    }
+
+    pub fn error(&mut self, issue: DataIssue) {
+        self.errors.push(issue);
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Log every issue under the `DATA` category
+    pub fn log(&self) {
+        for issue in &self.warnings {
+            tracing::warn!(
+                category = "DATA",
+                action = "load_warning",
+                source = %self.source,
+                "{}",
+                issue
+            );
+        }
+        for issue in &self.errors {
+            tracing::error!(
+                category = "DATA",
+                action = "load_error",
+                source = %self.source,
+                "{}",
+                issue
+            );
+        }
+    }
+}
+
+/// Outcomes of every startup loader
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StartupReport {
+    pub outcomes: Vec<LoadOutcome>,
+}
+
+impl StartupReport {
+    /// Add an outcome and log its issues
+    pub fn record(&mut self, outcome: LoadOutcome) {
+        outcome.log();
+        self.outcomes.push(outcome);
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &DataIssue> {
+        self.outcomes.iter().flat_map(|o| &o.warnings)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &DataIssue> {
+        self.outcomes.iter().flat_map(|o| &o.errors)
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        self.outcomes.iter().any(LoadOutcome::is_fatal)
+    }
+
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Record an outcome while plugins are being built
+pub fn record_startup_outcome(app: &mut App, outcome: LoadOutcome) {
+    app.init_resource::<StartupReport>();
+    app.world_mut()
+        .resource_mut::<StartupReport>()
+        .record(outcome);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_startup_report(report: Res<StartupReport>) {
+    match report.write_to(Path::new(STARTUP_REPORT_PATH)) {
+        Ok(()) => info!(
+            "Startup report written to {} ({} warnings, {} errors)",
+            STARTUP_REPORT_PATH,
+            report.warnings().count(),
+            report.errors().count()
+        ),
+        Err(e) => warn!("Failed to write {}: {}", STARTUP_REPORT_PATH, e),
+    }
+}
+
+/// Full-screen error list shown when core content failed to load
+#[derive(Component)]
+pub struct StartupErrorScreen;
+
+/// Quit button on the error screen
+#[derive(Component)]
+pub struct StartupErrorQuitButton;
+
+fn show_startup_error_screen(
+    mut commands: Commands,
+    report: Res<StartupReport>,
+    game_font: Res<GameFont>,
+) {
+    if !report.is_fatal() {
+        return;
+    }
+    let font = &game_font.0;
+    commands
+        .spawn((
+            StartupErrorScreen,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.12, 0.05, 0.05)),
+            FocusPolicy::Block,
+            GlobalZIndex(200),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new("ゲームデータを読み込めませんでした"),
+                text_font(font, TEXT_LARGE),
+                TextColor(Color::WHITE),
+            ));
+            root.spawn((
+                Text::new("以下のファイルを修正するか、ゲームを再インストールしてください"),
+                text_font(font, TEXT_BODY),
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+            for issue in report.errors() {
+                root.spawn((
+                    Text::new(issue.to_string()),
+                    text_font(font, TEXT_SMALL),
+                    TextColor(Color::srgb(1.0, 0.55, 0.5)),
+                ));
+            }
+            root.spawn((
+                Text::new(format!("詳細: {}", STARTUP_REPORT_PATH)),
+                text_font(font, TEXT_SMALL),
+                TextColor(Color::srgb(0.67, 0.67, 0.67)),
+            ));
+            root.spawn((
+                Button,
+                StartupErrorQuitButton,
+                Node {
+                    width: Val::Px(200.0),
+                    height: Val::Px(44.0),
+                    margin: UiRect::top(Val::Px(12.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border_radius: BorderRadius::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.15, 0.15)),
+            ))
+            .with_child((
+                Text::new("終了"),
+                text_font(font, TEXT_BUTTON),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn startup_error_quit(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<StartupErrorQuitButton>)>,
+    mut app_exit: MessageWriter<AppExit>,
+) {
+    if buttons.iter().any(|i| *i == Interaction::Pressed) {
+        app_exit.write(AppExit::error());
+    }
+}
+
+/// Point at the skipped entries when a world starts
+fn toast_startup_warnings(report: Res<StartupReport>, mut toast: ResMut<WrenchToast>) {
+    let count = report.warnings().count();
+    if count > 0 {
+        toast.show(format!(
+            "データファイルの問題 {}件をスキップしました (F8で確認)",
+            count
+        ));
+    }
+}
+
+/// Plugin for the startup report, its error screen and warning toast
+pub struct StartupReportPlugin;
+
+impl Plugin for StartupReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StartupReport>()
+            .add_systems(PostStartup, show_startup_error_screen)
+            .add_systems(Update, startup_error_quit)
+            .add_systems(OnEnter(AppState::InGame), toast_startup_warnings);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(PostStartup, write_startup_report);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_positions() {
+        let yaml = "terrain:\n  surface_min: [oops\n";
+        let err = serde_yaml::from_str::<serde_yaml::Value>(yaml).unwrap_err();
+        let issue = DataIssue::from_yaml_error("worldgen.yaml", &err);
+        assert!(issue.line.is_some());
+        assert!(issue.location().starts_with("worldgen.yaml:"));
+
+        let toml_str = "[[item]]\nid = \"a\"\nname = \n";
+        let err = toml::from_str::<toml::Table>(toml_str).unwrap_err();
+        let issue = DataIssue::from_toml_error("items.toml", toml_str, &err);
+        assert_eq!(issue.line, Some(3));
+
+        let err = serde_json::from_str::<serde_json::Value>("{\n  \"a\": }").unwrap_err();
+        let issue = DataIssue::from_json_error("settings.json", &err);
+        assert_eq!(issue.line, Some(2));
+
+        assert_eq!(line_column("ab\ncd", 4), (2, 2));
+        assert_eq!(DataIssue::new("a.toml", "x").location(), "a.toml");
+    }
+
+    #[test]
+    fn test_report_classification_and_json() {
+        let mut ok = LoadOutcome::new("settings");
+        ok.loaded = 1;
+        let mut partial = LoadOutcome::new("base mod");
+        partial.loaded = 10;
+        partial.warn(DataIssue::new("recipes.toml", "bad recipe").at(4, 1));
+        let mut report = StartupReport::default();
+        report.record(ok);
+        report.record(partial);
+        assert!(!report.is_fatal());
+        assert_eq!(report.warnings().count(), 1);
+
+        let mut broken = LoadOutcome::new("base mod");
+        broken.error(DataIssue::new("items.toml", "expected value").at(3, 8));
+        report.record(broken);
+        assert!(report.is_fatal());
+        assert_eq!(
+            report.errors().next().unwrap().to_string(),
+            "items.toml:3:8: expected value"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("startup_report.json");
+        report.write_to(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["outcomes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["outcomes"][2]["errors"][0]["line"], 3);
+    }
+}
@@ -0,0 +1,679 @@
+//! Display panels: wall-mounted counters for one item type
+//!
+//! Display panels are regular world blocks (placement, breaking and world
+//! saves already handle them). Every placed panel gets a `DisplayPanel`
+//! entity that is also a UI text node, kept over the panel's front face
+//! by projecting it through the player camera each frame.
+//!
+//! - The front face points back at the player who placed the panel
+//! - Right-click opens a config UI (item type and data source); it reuses
+//!   `InteractingMachine`, so input blocking and E/ESC closing work as for machines
+//! - Values refresh at most every DISPLAY_REFRESH_SECS
+//! - Panels are rebuilt from the world blocks on load, configs are saved
+
+use bevy::prelude::*;
+
+use super::{DeliveryStats, ProductionStats};
+use crate::components::{
+    CursorController, GameFont, InteractingMachine, InventoryOpen, PlayerCamera, UIContext,
+};
+use crate::core::{items, ItemId};
+use crate::events::game_events::{BlockBroken, BlockPlaced};
+use crate::input::{GameAction, InputManager};
+use crate::main_menu::{AppState, InGameSet};
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::setup::ui::{
+    text_font, QUEST_BORDER_COLOR, QUEST_RADIUS, SLOT_BG, SLOT_BORDER, SLOT_BORDER_COLOR,
+    SLOT_RADIUS, TEXT_BODY, TEXT_MINI, TEXT_TITLE,
+};
+use crate::utils::yaw_to_direction;
+use crate::{ContinuousActionTimer, Direction, TargetBlock, BLOCK_SIZE};
+
+/// Minimum time between value refreshes (seconds)
+pub const DISPLAY_REFRESH_SECS: f32 = 0.5;
+
+/// Largest value the panel can show (7 digits); larger values are clamped
+pub const DISPLAY_MAX_VALUE: u64 = 9_999_999;
+
+/// Panels farther than this from the camera are hidden (blocks)
+pub const DISPLAY_VIEW_DISTANCE: f32 = 24.0;
+
+/// Fraction of the face width the number may fill
+const FACE_TEXT_WIDTH: f32 = 0.8;
+
+/// Digit advance relative to the font size
+const GLYPH_ASPECT: f32 = 0.6;
+
+/// Font sizes are rounded to this step (every size gets its own glyph atlas)
+const FONT_SIZE_STEP: f32 = 2.0;
+
+const MIN_FONT_SIZE: f32 = 8.0;
+const MAX_FONT_SIZE: f32 = 96.0;
+
+/// Where a display panel reads its number from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DisplaySource {
+    /// Total delivered to the platforms (DeliveryStats)
+    #[default]
+    Delivered,
+    /// Count currently in the player's inventory
+    Inventory,
+    /// Total produced by machines (ProductionStats)
+    Produced,
+}
+
+impl DisplaySource {
+    /// Cycle order of the config UI
+    pub const ALL: [DisplaySource; 3] = [
+        DisplaySource::Delivered,
+        DisplaySource::Inventory,
+        DisplaySource::Produced,
+    ];
+
+    /// Label shown in the config UI
+    pub fn label(self) -> &'static str {
+        match self {
+            DisplaySource::Delivered => "納品累計",
+            DisplaySource::Inventory => "手持ち数",
+            DisplaySource::Produced => "生産累計",
+        }
+    }
+
+    /// Stable ID used in save files
+    pub fn id(self) -> &'static str {
+        match self {
+            DisplaySource::Delivered => "delivered",
+            DisplaySource::Inventory => "inventory",
+            DisplaySource::Produced => "produced",
+        }
+    }
+
+    /// Parse a save file ID
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|source| source.id() == id)
+    }
+
+    /// Next source in the cycle (wraps around)
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// A placed display panel (the entity is also the panel's UI text node)
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DisplayPanel {
+    /// Block position of the panel
+    pub position: IVec3,
+    /// Face the number is shown on
+    pub face: Direction,
+    /// Item type to count (None = not configured)
+    pub item: Option<ItemId>,
+    pub source: DisplaySource,
+    /// Time left until the next refresh (seconds)
+    pub refresh_in: f32,
+}
+
+impl DisplayPanel {
+    pub fn new(position: IVec3, face: Direction) -> Self {
+        Self {
+            position,
+            face,
+            item: None,
+            source: DisplaySource::default(),
+            refresh_in: 0.0,
+        }
+    }
+
+    pub fn with_config(mut self, item: Option<ItemId>, source: DisplaySource) -> Self {
+        self.item = item;
+        self.source = source;
+        self
+    }
+
+    /// World-space center of the front face
+    pub fn face_center(&self) -> Vec3 {
+        (self.position.as_vec3() + Vec3::splat(0.5)) * BLOCK_SIZE
+            + self.face.to_ivec3().as_vec3() * (BLOCK_SIZE * 0.5 + 0.01)
+    }
+}
+
+/// Resolve the number a panel shows for an item and data source
+pub fn resolve_count(
+    source: DisplaySource,
+    item: ItemId,
+    delivery: &DeliveryStats,
+    production: &ProductionStats,
+    inventory: Option<&PlayerInventory>,
+) -> u64 {
+    match source {
+        DisplaySource::Delivered => delivery.get_total_delivered_by_id(item),
+        DisplaySource::Inventory => {
+            inventory.map_or(0, |inv| inv.get_total_count_by_id(item) as u64)
+        }
+        DisplaySource::Produced => production.get_total_produced_by_id(item),
+    }
+}
+
+/// Panel text for a value (clamped to 7 digits, "-" when unconfigured)
+pub fn format_count(value: Option<u64>) -> String {
+    match value {
+        Some(value) => value.min(DISPLAY_MAX_VALUE).to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Font size that fits `chars` digits across a face `face_px` pixels wide
+///
+/// The size is also capped by the face height and rounded to FONT_SIZE_STEP,
+/// so walking toward a panel doesn't build a new glyph atlas every frame.
+pub fn fit_font_size(face_px: f32, chars: usize) -> f32 {
+    let by_width = face_px * FACE_TEXT_WIDTH / (chars.max(1) as f32 * GLYPH_ASPECT);
+    let size = by_width.min(face_px * 0.5);
+    ((size / FONT_SIZE_STEP).floor() * FONT_SIZE_STEP).clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+}
+
+/// Spawn a panel entity for each placed panel block (facing back at the player),
+/// despawn it when the block breaks
+fn track_display_panels(
+    mut commands: Commands,
+    mut placed: MessageReader<BlockPlaced>,
+    mut broken: MessageReader<BlockBroken>,
+    camera_query: Query<&PlayerCamera>,
+    panels: Query<(Entity, &DisplayPanel)>,
+) {
+    let panel_item = items::display_panel();
+
+    for event in broken.read() {
+        if event.block != panel_item {
+            continue;
+        }
+        for (entity, panel) in panels.iter() {
+            if panel.position == event.pos {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    for event in placed.read() {
+        if event.block != panel_item {
+            continue;
+        }
+        let face = camera_query
+            .single()
+            .map(|camera| yaw_to_direction(camera.yaw).opposite())
+            .unwrap_or(Direction::South);
+        info!(pos = ?event.pos, ?face, "Display panel placed");
+        commands.spawn(DisplayPanel::new(event.pos, face));
+    }
+}
+
+/// Turn new panel entities into UI text nodes
+fn attach_display_text(
+    mut commands: Commands,
+    game_font: Res<GameFont>,
+    added: Query<Entity, Added<DisplayPanel>>,
+) {
+    for entity in added.iter() {
+        commands.entity(entity).insert((
+            Text::new(format_count(None)),
+            text_font(&game_font.0, MIN_FONT_SIZE),
+            TextColor(Color::srgb(0.4, 1.0, 0.5)),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Recompute panel values (each panel at most every DISPLAY_REFRESH_SECS)
+fn refresh_display_panels(
+    time: Res<Time>,
+    delivery: Res<DeliveryStats>,
+    production: Res<ProductionStats>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    mut panels: Query<(&mut DisplayPanel, &mut Text)>,
+) {
+    let inventory = local_player.and_then(|lp| inventories.get(lp.0).ok());
+    for (mut panel, mut text) in panels.iter_mut() {
+        panel.refresh_in -= time.delta_secs();
+        if panel.refresh_in > 0.0 {
+            continue;
+        }
+        panel.refresh_in = DISPLAY_REFRESH_SECS;
+
+        let value = panel
+            .item
+            .map(|item| resolve_count(panel.source, item, &delivery, &production, inventory));
+        let content = format_count(value);
+        if **text != content {
+            **text = content;
+        }
+    }
+}
+
+/// Keep each panel's text over its front face, sized to fit the face
+#[allow(clippy::type_complexity)]
+fn position_display_panels(
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    mut panels: Query<(
+        &DisplayPanel,
+        &Text,
+        &mut Node,
+        &mut TextFont,
+        &mut Visibility,
+    )>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let camera_pos = camera_transform.translation();
+    let right = camera_transform.right().as_vec3();
+
+    for (panel, text, mut node, mut font, mut visibility) in panels.iter_mut() {
+        let center = panel.face_center();
+        let normal = panel.face.to_ivec3().as_vec3();
+        let to_camera = camera_pos - center;
+        // Only the front face carries the number
+        let facing = to_camera.dot(normal) > 0.0;
+        let in_range = to_camera.length() < DISPLAY_VIEW_DISTANCE;
+
+        let half_width = right * BLOCK_SIZE * 0.5;
+        let projected = (
+            camera.world_to_viewport(camera_transform, center),
+            camera.world_to_viewport(camera_transform, center - half_width),
+            camera.world_to_viewport(camera_transform, center + half_width),
+        );
+        let (Ok(screen), Ok(left), Ok(right_edge)) = projected else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if !facing || !in_range {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let face_px = left.distance(right_edge);
+        let size = fit_font_size(face_px, text.chars().count());
+        if font.font_size != size {
+            font.font_size = size;
+        }
+        node.left = Val::Px(screen.x - face_px / 2.0);
+        node.width = Val::Px(face_px);
+        node.top = Val::Px(screen.y - size * 0.6);
+        *visibility = Visibility::Visible;
+    }
+}
+
+/// Right-click a display panel to open its config UI
+///
+/// Swallows the right-click it handles (see `BlockClickSet`).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_display_panel_click(
+    input: Res<InputManager>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    mut cursor: ResMut<CursorController>,
+    inventory_open: Res<InventoryOpen>,
+    mut interacting: ResMut<InteractingMachine>,
+    target: Res<TargetBlock>,
+    panels: Query<(Entity, &DisplayPanel)>,
+) {
+    if !input.pressed(GameAction::SecondaryAction) || input.pressed(GameAction::ModifierShift) {
+        return;
+    }
+    if !cursor.is_locked() || inventory_open.0 || interacting.0.is_some() {
+        return;
+    }
+    let Some(pos) = target.break_target else {
+        return;
+    };
+    let Some((entity, _)) = panels.iter().find(|(_, panel)| panel.position == pos) else {
+        return;
+    };
+
+    // Keep block_place from building on the panel (also while the button is held)
+    action_timer.place_timer.reset();
+    if !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+    mouse_button.clear_just_pressed(MouseButton::Right);
+
+    interacting.0 = Some(entity);
+    cursor.request_ui(UIContext::Machine(entity));
+}
+
+/// Root node of the display panel config UI
+#[derive(Component)]
+pub struct DisplayPanelUI;
+
+/// Button that cycles the counted item
+#[derive(Component)]
+pub struct DisplayPanelItemButton;
+
+/// Button that sets the counted item to the held item
+#[derive(Component)]
+pub struct DisplayPanelHeldButton;
+
+/// Button that cycles the data source
+#[derive(Component)]
+pub struct DisplayPanelSourceButton;
+
+/// Label of the item button
+#[derive(Component)]
+pub struct DisplayPanelItemText;
+
+/// Label of the source button
+#[derive(Component)]
+pub struct DisplayPanelSourceText;
+
+fn setup_display_panel_ui(mut commands: Commands, game_font: Res<GameFont>) {
+    let font = &game_font.0;
+    commands
+        .spawn((
+            DisplayPanelUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-130.0)),
+                width: Val::Px(260.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                border: UiRect::all(Val::Px(2.0)),
+                border_radius: BorderRadius::all(Val::Px(QUEST_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.10, 0.10, 0.10, 0.95)),
+            BorderColor::all(QUEST_BORDER_COLOR),
+            Visibility::Hidden,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("表示パネル"),
+                text_font(font, TEXT_TITLE),
+                TextColor(Color::srgb(1.0, 0.8, 0.0)),
+            ));
+            spawn_config_button(panel, font, DisplayPanelItemButton, DisplayPanelItemText);
+            spawn_config_button(panel, font, DisplayPanelHeldButton, ());
+            spawn_config_button(
+                panel,
+                font,
+                DisplayPanelSourceButton,
+                DisplayPanelSourceText,
+            );
+            panel.spawn((
+                Text::new("E/ESC で閉じる"),
+                text_font(font, TEXT_MINI),
+                TextColor(Color::srgb(0.67, 0.67, 0.67)),
+            ));
+        });
+}
+
+/// Spawn a config button (labels are set by update_display_panel_ui)
+fn spawn_config_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button_marker: impl Component,
+    label_marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            button_marker,
+            Node {
+                width: Val::Percent(100.0),
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(SLOT_BORDER)),
+                justify_content: JustifyContent::Center,
+                border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(SLOT_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new("手持ちアイテムを表示"),
+                label_marker,
+                text_font(font, TEXT_BODY),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Show the config UI for the open panel and keep its labels current
+#[allow(clippy::type_complexity)]
+fn update_display_panel_ui(
+    interacting: Res<InteractingMachine>,
+    panels: Query<&DisplayPanel>,
+    mut ui_query: Query<&mut Visibility, With<DisplayPanelUI>>,
+    mut item_text: Query<&mut Text, (With<DisplayPanelItemText>, Without<DisplayPanelSourceText>)>,
+    mut source_text: Query<&mut Text, With<DisplayPanelSourceText>>,
+) {
+    let open = interacting.0.and_then(|entity| panels.get(entity).ok());
+    for mut visibility in ui_query.iter_mut() {
+        let target = if open.is_some() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+    let Some(panel) = open else {
+        return;
+    };
+
+    let item_label = format!(
+        "アイテム: {}",
+        panel.item.map_or("未設定", |item| item.display_name())
+    );
+    for mut text in item_text.iter_mut() {
+        if **text != item_label {
+            **text = item_label.clone();
+        }
+    }
+    let source_label = format!("表示: {}", panel.source.label());
+    for mut text in source_text.iter_mut() {
+        if **text != source_label {
+            **text = source_label.clone();
+        }
+    }
+}
+
+/// Handle the config UI buttons of the open panel
+#[allow(clippy::type_complexity)]
+fn display_panel_ui_input(
+    interacting: Res<InteractingMachine>,
+    mut panels: Query<&mut DisplayPanel>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    buttons: Query<
+        (
+            &Interaction,
+            Has<DisplayPanelItemButton>,
+            Has<DisplayPanelHeldButton>,
+        ),
+        (
+            Changed<Interaction>,
+            Or<(
+                With<DisplayPanelItemButton>,
+                With<DisplayPanelHeldButton>,
+                With<DisplayPanelSourceButton>,
+            )>,
+        ),
+    >,
+) {
+    let Some(mut panel) = interacting.0.and_then(|entity| panels.get_mut(entity).ok()) else {
+        return;
+    };
+    for (interaction, is_item, is_held) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if is_item {
+            let all = items::all();
+            let next = panel
+                .item
+                .and_then(|item| all.iter().position(|i| *i == item))
+                .map_or(0, |i| (i + 1) % all.len());
+            panel.item = all.get(next).copied();
+        } else if is_held {
+            let held = local_player
+                .as_ref()
+                .and_then(|lp| inventories.get(lp.0).ok())
+                .and_then(|inv| inv.selected_item_id());
+            if held.is_some() {
+                panel.item = held;
+            }
+        } else {
+            panel.source = panel.source.next();
+        }
+        // Show the new config right away
+        panel.refresh_in = 0.0;
+    }
+}
+
+pub struct DisplayPanelPlugin;
+
+impl Plugin for DisplayPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup_display_panel_ui)
+            .add_systems(
+                Update,
+                (
+                    track_display_panels,
+                    attach_display_text,
+                    refresh_display_panels,
+                    position_display_panels,
+                    update_display_panel_ui,
+                    display_panel_ui_input,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_count_sources() {
+        let iron = items::iron_ingot();
+        let mut delivery = DeliveryStats::default();
+        delivery.record_delivery_by_id(iron, 12);
+        delivery.record_delivery_by_id(items::copper_ingot(), 99);
+        let mut production = ProductionStats::new();
+        production.record_production_by_id(iron, 30, 0.0);
+        production.record_production_by_id(iron, 5, 1.0);
+        let mut inventory = PlayerInventory::default();
+        inventory.add_item_by_id(iron, 7);
+
+        let count = |source| resolve_count(source, iron, &delivery, &production, Some(&inventory));
+        assert_eq!(count(DisplaySource::Delivered), 12);
+        assert_eq!(count(DisplaySource::Inventory), 7);
+        assert_eq!(count(DisplaySource::Produced), 35);
+
+        // No local player means an empty hand
+        assert_eq!(
+            resolve_count(DisplaySource::Inventory, iron, &delivery, &production, None),
+            0
+        );
+        // Unrecorded items count as zero
+        assert_eq!(
+            resolve_count(
+                DisplaySource::Produced,
+                items::coal(),
+                &delivery,
+                &production,
+                Some(&inventory)
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_format_count_clamps_to_seven_digits() {
+        assert_eq!(format_count(None), "-");
+        assert_eq!(format_count(Some(0)), "0");
+        assert_eq!(format_count(Some(1_234_567)), "1234567");
+        assert_eq!(format_count(Some(123_456_789)), "9999999");
+    }
+
+    #[test]
+    fn test_font_fits_face() {
+        for face_px in [20.0, 64.0, 150.0, 400.0] {
+            for chars in 1..=7 {
+                let size = fit_font_size(face_px, chars);
+                let text_width = size * GLYPH_ASPECT * chars as f32;
+                // Tiny faces bottom out at the minimum size
+                assert!(size == MIN_FONT_SIZE || text_width <= face_px * FACE_TEXT_WIDTH);
+                assert!(size <= MAX_FONT_SIZE);
+            }
+        }
+        // Longer numbers never get a larger font
+        assert!(fit_font_size(150.0, 7) <= fit_font_size(150.0, 1));
+    }
+
+    #[test]
+    fn test_source_ids_round_trip() {
+        for source in DisplaySource::ALL {
+            assert_eq!(DisplaySource::from_id(source.id()), Some(source));
+        }
+        assert_eq!(DisplaySource::from_id("unknown"), None);
+        assert_eq!(DisplaySource::Produced.next(), DisplaySource::Delivered);
+    }
+
+    #[test]
+    fn test_panels_track_blocks_independently() {
+        let mut app = App::new();
+        app.add_message::<BlockPlaced>()
+            .add_message::<BlockBroken>()
+            .add_systems(Update, track_display_panels);
+
+        let first = IVec3::new(1, 8, 1);
+        let second = IVec3::new(4, 8, 1);
+        for pos in [first, second] {
+            app.world_mut().write_message(BlockPlaced {
+                pos,
+                block: items::display_panel(),
+                source: crate::events::game_events::EventSource::System,
+            });
+        }
+        app.update();
+
+        let mut positions: Vec<IVec3> = app
+            .world_mut()
+            .query::<&DisplayPanel>()
+            .iter(app.world())
+            .map(|p| p.position)
+            .collect();
+        positions.sort_by_key(|p| p.to_array());
+        assert_eq!(positions, vec![first, second]);
+
+        app.world_mut().write_message(BlockBroken {
+            pos: first,
+            block: items::display_panel(),
+            source: crate::events::game_events::EventSource::System,
+        });
+        app.update();
+
+        let remaining: Vec<IVec3> = app
+            .world_mut()
+            .query::<&DisplayPanel>()
+            .iter(app.world())
+            .map(|p| p.position)
+            .collect();
+        assert_eq!(remaining, vec![second]);
+    }
+}
@@ -0,0 +1,442 @@
+//! 生産目標とボトルネック分析
+//!
+//! 目標アイテムからレシピを逆にたどり、各段に必要な生産レートと機械台数を
+//! 基本速度から求める。設置済みの機械数（`MachineCensus`）と
+//! `ThroughputTracker` の実測生産レートと比べ、原料側から見て最初に
+//! 実測が必要量を下回る段をボトルネックとする。
+//!
+//! 速度を変えるモジュールはまだ無いので、倍率は常に 1 として計算する。
+//! 採掘機は狙った鉱石が主産出のバイオームに置かれている前提で数える。
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::components::Machine;
+use crate::core::{items, ItemId};
+use crate::game_spec::planning::producer;
+use crate::game_spec::{machine_spec_for_type, MachineSpec, MachineType, Recipe, MINER};
+
+/// 実測がこの割合以上なら目標を満たしているとみなす（計測の揺れ対策）
+pub const RATE_TOLERANCE: f32 = 0.95;
+
+/// レシピをたどる深さの上限
+const MAX_DEPTH: u32 = 16;
+
+/// 目標レートの初期値（個/分）
+const DEFAULT_GOAL_RATE: f32 = 30.0;
+
+/// 設置済み機械の種類ごとの台数
+///
+/// 機械の設置・破壊（ロードやブループリントを含む）で更新される
+#[derive(Resource, Debug, Default)]
+pub struct MachineCensus {
+    /// 機械エンティティ → 機械ID
+    machines: HashMap<Entity, &'static str>,
+    /// 機械ID → 台数
+    counts: HashMap<&'static str, u32>,
+}
+
+impl MachineCensus {
+    /// 機械の設置を記録
+    pub fn add(&mut self, entity: Entity, machine_id: &'static str) {
+        if let Some(previous) = self.machines.insert(entity, machine_id) {
+            self.decrement(previous);
+        }
+        *self.counts.entry(machine_id).or_insert(0) += 1;
+    }
+
+    /// 機械の撤去を記録
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(machine_id) = self.machines.remove(&entity) {
+            self.decrement(machine_id);
+        }
+    }
+
+    fn decrement(&mut self, machine_id: &'static str) {
+        if let Some(count) = self.counts.get_mut(machine_id) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// 設置済みの台数
+    pub fn count(&self, machine_id: &str) -> u32 {
+        self.counts.get(machine_id).copied().unwrap_or(0)
+    }
+}
+
+/// プレイヤーが設定した生産目標
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct ProductionGoal {
+    /// 目標アイテム
+    pub item: ItemId,
+    /// 目標レート（個/分）
+    pub rate_per_min: f32,
+}
+
+impl Default for ProductionGoal {
+    fn default() -> Self {
+        Self {
+            item: items::iron_ingot(),
+            rate_per_min: DEFAULT_GOAL_RATE,
+        }
+    }
+}
+
+/// 生産ラインの1段（1アイテム）
+#[derive(Debug, Clone)]
+pub struct GoalStage {
+    /// この段で作るアイテム
+    pub item: ItemId,
+    /// 作る機械
+    pub machine: &'static MachineSpec,
+    /// 目標アイテムからの距離（0 = 目標アイテム）
+    pub depth: u32,
+    /// 必要レート（個/分）
+    pub required_rate: f32,
+    /// 実測生産レート（個/分）
+    pub actual_rate: f32,
+    /// 必要な機械台数
+    pub required_machines: u32,
+}
+
+impl GoalStage {
+    /// 実測が必要量を満たしているか
+    pub fn is_satisfied(&self) -> bool {
+        self.actual_rate >= self.required_rate * RATE_TOLERANCE
+    }
+}
+
+/// 生産目標の分析結果
+#[derive(Debug, Clone, Default)]
+pub struct GoalAnalysis {
+    /// 原料側から順に並べた各段
+    pub stages: Vec<GoalStage>,
+    /// ボトルネックの段（`stages` の添字）
+    pub bottleneck: Option<usize>,
+    /// ボトルネックへの一行の対処案
+    pub suggestion: Option<String>,
+}
+
+/// 目標に選べるアイテム（機械で作れるもの、レシピ順）
+pub fn goal_items(recipes: &[Recipe]) -> Vec<ItemId> {
+    let mut result: Vec<ItemId> = Vec::new();
+    for recipe in recipes.iter().filter(|r| r.machine != MachineType::Hand) {
+        for output in recipe.guaranteed_outputs() {
+            if !result.contains(&output.item) {
+                result.push(output.item);
+            }
+        }
+    }
+    result
+}
+
+/// レシピの種類を処理する機械
+fn machine_spec_for(machine: MachineType) -> &'static MachineSpec {
+    machine_spec_for_type(machine).unwrap_or(&MINER)
+}
+
+/// 必要レートを原料側へ積み上げる
+fn accumulate_demand(
+    recipes: &[Recipe],
+    item: ItemId,
+    rate: f32,
+    depth: u32,
+    demand: &mut Vec<(ItemId, f32, u32)>,
+) {
+    match demand.iter_mut().find(|(id, _, _)| *id == item) {
+        Some(entry) => {
+            entry.1 += rate;
+            entry.2 = entry.2.max(depth);
+        }
+        None => demand.push((item, rate, depth)),
+    }
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let Some(recipe) = producer(recipes, item) else {
+        return;
+    };
+    let crafts_per_min = rate / recipe.output_count_of(item) as f32;
+    for input in &recipe.inputs {
+        // タグ入力はどのアイテムで満たすか決まらないので数えない
+        if let Some(input_item) = input.item() {
+            let input_rate = crafts_per_min * input.count as f32;
+            accumulate_demand(recipes, input_item, input_rate, depth + 1, demand);
+        }
+    }
+    if let Some(fuel) = &recipe.fuel {
+        let fuel_rate = crafts_per_min * fuel.amount as f32;
+        accumulate_demand(recipes, fuel.fuel_type, fuel_rate, depth + 1, demand);
+    }
+}
+
+/// 生産目標を分析する
+///
+/// `produced` はアイテムごとの実測生産レート（個/分）
+pub fn analyze_goal(
+    recipes: &[Recipe],
+    census: &MachineCensus,
+    produced: &HashMap<ItemId, f32>,
+    goal: ProductionGoal,
+) -> GoalAnalysis {
+    let mut demand = Vec::new();
+    accumulate_demand(recipes, goal.item, goal.rate_per_min, 0, &mut demand);
+
+    let mut stages: Vec<GoalStage> = demand
+        .into_iter()
+        .map(|(item, required_rate, depth)| {
+            // レシピの無いアイテムは採掘機で掘る
+            let (machine, per_machine) = match producer(recipes, item) {
+                Some(recipe) => (
+                    machine_spec_for(recipe.machine),
+                    recipe.output_count_of(item) as f32 * 60.0 / recipe.craft_time,
+                ),
+                None => (&MINER, 60.0 / MINER.process_time),
+            };
+            GoalStage {
+                item,
+                machine,
+                depth,
+                required_rate,
+                actual_rate: produced.get(&item).copied().unwrap_or(0.0),
+                required_machines: (required_rate / per_machine - 1e-4).ceil().max(0.0) as u32,
+            }
+        })
+        .collect();
+    // 原料側から（安定ソートなので同じ深さはたどった順）
+    stages.sort_by_key(|stage| std::cmp::Reverse(stage.depth));
+
+    let bottleneck = stages.iter().position(|stage| !stage.is_satisfied());
+    let suggestion = bottleneck.map(|index| {
+        let stage = &stages[index];
+        let machine_id = stage.machine.id;
+        // 同じ機械を使う段の必要台数を合計して設置済みと比べる
+        let required: u32 = stages
+            .iter()
+            .filter(|s| s.machine.id == machine_id)
+            .map(|s| s.required_machines)
+            .sum();
+        let shortfall = required.saturating_sub(census.count(machine_id));
+        if shortfall > 0 {
+            format!("{}をあと{}台追加", stage.machine.name, shortfall)
+        } else if machine_id == MINER.id {
+            format!(
+                "{}が止まっている: ドリルと搬出ベルトを確認",
+                stage.machine.name
+            )
+        } else {
+            format!("{}の材料不足: 前段の機械かベルトを追加", stage.machine.name)
+        }
+    });
+
+    GoalAnalysis {
+        stages,
+        bottleneck,
+        suggestion,
+    }
+}
+
+/// 機械の設置・撤去を数える
+pub fn update_machine_census(
+    added: Query<(Entity, &Machine), Added<Machine>>,
+    mut removed: RemovedComponents<Machine>,
+    mut census: ResMut<MachineCensus>,
+) {
+    for entity in removed.read() {
+        census.remove(entity);
+    }
+    for (entity, machine) in added.iter() {
+        census.add(entity, machine.spec.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_spec::{FuelRequirement, RecipeInput, RecipeOutput, UnlockCondition};
+
+    fn recipe(
+        id: &'static str,
+        machine: MachineType,
+        inputs: Vec<RecipeInput>,
+        output: (ItemId, u32),
+        craft_time: f32,
+        fuel: Option<FuelRequirement>,
+    ) -> Recipe {
+        Recipe {
+            id,
+            machine,
+            inputs,
+            outputs: vec![RecipeOutput::guaranteed(output.0, output.1)],
+            craft_time,
+            fuel,
+            fluid_input: None,
+            fluid_output: None,
+            unlock: UnlockCondition::Always,
+            priority: 0,
+        }
+    }
+
+    /// 鉄鉱石 + 石炭 → 鉄インゴット（2秒）
+    fn smelting_fixture() -> Vec<Recipe> {
+        vec![recipe(
+            "smelt_iron",
+            MachineType::Furnace,
+            vec![RecipeInput::new(items::iron_ore(), 1, 0)],
+            (items::iron_ingot(), 1),
+            2.0,
+            Some(FuelRequirement::new(items::coal(), 1)),
+        )]
+    }
+
+    fn census(miners: u32, furnaces: u32) -> MachineCensus {
+        let mut world = World::new();
+        let mut census = MachineCensus::default();
+        for _ in 0..miners {
+            census.add(world.spawn_empty().id(), "miner");
+        }
+        for _ in 0..furnaces {
+            census.add(world.spawn_empty().id(), "furnace");
+        }
+        census
+    }
+
+    fn goal(rate_per_min: f32) -> ProductionGoal {
+        ProductionGoal {
+            item: items::iron_ingot(),
+            rate_per_min,
+        }
+    }
+
+    #[test]
+    fn test_stages_walk_back_to_raw_materials() {
+        let analysis = analyze_goal(
+            &smelting_fixture(),
+            &MachineCensus::default(),
+            &HashMap::new(),
+            goal(60.0),
+        );
+
+        let stages: Vec<_> = analysis
+            .stages
+            .iter()
+            .map(|s| (s.item, s.machine.id, s.required_rate, s.required_machines))
+            .collect();
+        // 採掘機は 40 個/分、精錬炉は 30 個/分
+        assert_eq!(
+            stages,
+            vec![
+                (items::iron_ore(), "miner", 60.0, 2),
+                (items::coal(), "miner", 60.0, 2),
+                (items::iron_ingot(), "furnace", 60.0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bottleneck_is_first_short_stage_with_machine_shortfall() {
+        let produced = HashMap::from([
+            (items::iron_ore(), 60.0),
+            (items::coal(), 60.0),
+            (items::iron_ingot(), 30.0),
+        ]);
+        let analysis = analyze_goal(&smelting_fixture(), &census(4, 1), &produced, goal(60.0));
+        assert_eq!(analysis.bottleneck, Some(2));
+        assert_eq!(analysis.suggestion.as_deref(), Some("精錬炉をあと1台追加"));
+
+        // 鉱石が足りないと、その先の精錬炉より先に採掘機が挙がる
+        let produced = HashMap::from([
+            (items::iron_ore(), 30.0),
+            (items::coal(), 60.0),
+            (items::iron_ingot(), 30.0),
+        ]);
+        let analysis = analyze_goal(&smelting_fixture(), &census(2, 1), &produced, goal(60.0));
+        assert_eq!(analysis.bottleneck, Some(0));
+        assert_eq!(analysis.suggestion.as_deref(), Some("採掘機をあと2台追加"));
+    }
+
+    #[test]
+    fn test_enough_machines_but_low_rate_suggests_supply() {
+        let produced = HashMap::from([
+            (items::iron_ore(), 60.0),
+            (items::coal(), 60.0),
+            (items::iron_ingot(), 12.0),
+        ]);
+        let analysis = analyze_goal(&smelting_fixture(), &census(4, 2), &produced, goal(60.0));
+        assert_eq!(analysis.bottleneck, Some(2));
+        assert_eq!(
+            analysis.suggestion.as_deref(),
+            Some("精錬炉の材料不足: 前段の機械かベルトを追加")
+        );
+
+        let produced = HashMap::from([(items::iron_ore(), 10.0)]);
+        let analysis = analyze_goal(&smelting_fixture(), &census(4, 2), &produced, goal(60.0));
+        assert_eq!(analysis.bottleneck, Some(0));
+        assert_eq!(
+            analysis.suggestion.as_deref(),
+            Some("採掘機が止まっている: ドリルと搬出ベルトを確認")
+        );
+    }
+
+    #[test]
+    fn test_goal_met_has_no_bottleneck() {
+        let produced = HashMap::from([
+            (items::iron_ore(), 61.0),
+            (items::coal(), 59.0),
+            (items::iron_ingot(), 58.0),
+        ]);
+        let analysis = analyze_goal(&smelting_fixture(), &census(4, 2), &produced, goal(60.0));
+        assert_eq!(analysis.bottleneck, None);
+        assert_eq!(analysis.suggestion, None);
+    }
+
+    #[test]
+    fn test_rates_scale_through_each_level() {
+        // ベルト ← 鉄インゴット ← 鉄鉱石 + 石炭
+        let mut recipes = smelting_fixture();
+        recipes.push(recipe(
+            "craft_conveyor",
+            MachineType::Assembler,
+            vec![RecipeInput::new(items::iron_ingot(), 2, 0)],
+            (items::conveyor_block(), 5),
+            2.0,
+            None,
+        ));
+        let analysis = analyze_goal(
+            &recipes,
+            &MachineCensus::default(),
+            &HashMap::new(),
+            ProductionGoal {
+                item: items::conveyor_block(),
+                rate_per_min: 150.0,
+            },
+        );
+        let ingot = analysis
+            .stages
+            .iter()
+            .find(|s| s.item == items::iron_ingot())
+            .unwrap();
+        // 150 個/分 = 30 回/分 × インゴット2
+        assert_eq!(ingot.required_rate, 60.0);
+        assert_eq!(ingot.depth, 1);
+        assert_eq!(analysis.stages.last().unwrap().machine.id, "assembler");
+        assert_eq!(analysis.stages.last().unwrap().required_machines, 1);
+    }
+
+    #[test]
+    fn test_census_add_remove() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let mut census = MachineCensus::default();
+        census.add(a, "furnace");
+        census.add(b, "furnace");
+        census.add(b, "furnace"); // 同じエンティティは二重に数えない
+        assert_eq!(census.count("furnace"), 2);
+
+        census.remove(a);
+        census.remove(a);
+        assert_eq!(census.count("furnace"), 1);
+        assert_eq!(census.count("miner"), 0);
+    }
+}
@@ -2,11 +2,22 @@
 //!
 //! Internal storage uses `ItemId` for item identification.
 
+pub mod display_panel;
+pub mod goals;
+
+pub use display_panel::{DisplayPanel, DisplayPanelPlugin, DisplaySource};
+pub use goals::{analyze_goal, goal_items, GoalAnalysis, GoalStage, MachineCensus, ProductionGoal};
+
 use bevy::prelude::*;
 use std::collections::{HashMap, VecDeque};
 
-use crate::core::ItemId;
-use crate::events::game_events::{ItemDelivered, MachineCompleted, MachineStarted};
+use crate::components::{Machine, Player};
+use crate::core::{items, ItemId};
+use crate::events::game_events::{
+    BlockBroken, BlockPlaced, ItemDelivered, MachineCompleted, MachineSpawned, MachineStarted,
+    QuestRewardsClaimed,
+};
+use crate::main_menu::InGameSet;
 
 /// 時系列データ
 #[derive(Debug, Clone, Default)]
@@ -174,6 +185,95 @@ impl DeliveryStats {
     }
 }
 
+/// 直近の納品・生産を数える期間（シミュレーションティック、5分）
+pub const THROUGHPUT_WINDOW_TICKS: u64 = 5 * 60 * crate::constants::SIMULATION_HZ as u64;
+
+/// 直近の納品レートと生産レート
+///
+/// 時計ではなくシミュレーションティックで数えるので、`/time` や
+/// `/skip-night` の影響を受けない
+#[derive(Resource, Debug, Default)]
+pub struct ThroughputTracker {
+    /// 経過ティック数
+    pub tick: u64,
+    /// (納品ティック, アイテム, 個数)
+    deliveries: VecDeque<(u64, ItemId, u32)>,
+    /// (生産ティック, アイテム, 個数)
+    produced: VecDeque<(u64, ItemId, u32)>,
+}
+
+impl ThroughputTracker {
+    /// 現在のティックでの納品を記録
+    pub fn record(&mut self, item_id: ItemId, count: u32) {
+        self.deliveries.push_back((self.tick, item_id, count));
+    }
+
+    /// 現在のティックでの機械の生産を記録
+    pub fn record_production(&mut self, item_id: ItemId, count: u32) {
+        self.produced.push_back((self.tick, item_id, count));
+    }
+
+    /// 1ティック進め、期間外の記録を捨てる
+    pub fn advance(&mut self) {
+        self.tick += 1;
+        let tick = self.tick;
+        for log in [&mut self.deliveries, &mut self.produced] {
+            while let Some(&(recorded, _, _)) = log.front() {
+                if recorded + THROUGHPUT_WINDOW_TICKS > tick {
+                    break;
+                }
+                log.pop_front();
+            }
+        }
+    }
+
+    /// 記録の合計を期間で割ったレート（個/分）
+    fn window_rate(&self, log: &VecDeque<(u64, ItemId, u32)>, item_id: ItemId) -> f32 {
+        let window = self.tick.min(THROUGHPUT_WINDOW_TICKS);
+        if window == 0 {
+            return 0.0;
+        }
+        let total: u32 = log
+            .iter()
+            .filter(|(_, item, _)| *item == item_id)
+            .map(|(_, _, count)| count)
+            .sum();
+        let minutes = window as f32 / (crate::constants::SIMULATION_HZ as f32 * 60.0);
+        total as f32 / minutes
+    }
+
+    /// 直近の納品レート（個/分）
+    pub fn rate_per_min(&self, item_id: ItemId) -> f32 {
+        self.window_rate(&self.deliveries, item_id)
+    }
+
+    /// 直近の生産レート（個/分）
+    pub fn production_rate_per_min(&self, item_id: ItemId) -> f32 {
+        self.window_rate(&self.produced, item_id)
+    }
+
+    /// 直近に納品された全アイテムのレート（アイテム名順）
+    pub fn rates(&self) -> Vec<(ItemId, f32)> {
+        let mut ids: Vec<ItemId> = self.deliveries.iter().map(|(_, item, _)| *item).collect();
+        ids.sort_by_key(|id| id.name());
+        ids.dedup();
+        ids.into_iter()
+            .map(|id| (id, self.rate_per_min(id)))
+            .collect()
+    }
+
+    /// 直近に生産された全アイテムの生産レート
+    pub fn production_rates(&self) -> HashMap<ItemId, f32> {
+        let mut rates = HashMap::new();
+        for (_, item_id, _) in &self.produced {
+            rates
+                .entry(*item_id)
+                .or_insert_with(|| self.production_rate_per_min(*item_id));
+        }
+        rates
+    }
+}
+
 /// 機械完了イベントを購読して生産統計を記録
 fn handle_machine_completed(
     mut events: MessageReader<MachineCompleted>,
@@ -212,19 +312,219 @@ fn handle_item_delivered(
     }
 }
 
+/// 納品と機械の生産をスループット計測に記録
+fn track_throughput(
+    mut events: MessageReader<ItemDelivered>,
+    mut completed: MessageReader<MachineCompleted>,
+    mut tracker: ResMut<ThroughputTracker>,
+) {
+    for event in events.read() {
+        tracker.record(event.item, event.count);
+    }
+    for event in completed.read() {
+        for (item_id, count) in &event.outputs {
+            tracker.record_production(*item_id, *count);
+        }
+    }
+}
+
+/// スループット計測のティックを進める
+fn advance_throughput(mut tracker: ResMut<ThroughputTracker>) {
+    tracker.advance();
+}
+
+/// 1フレームでこれ以上移動した場合はテレポートとみなし移動距離に含めない
+const MAX_WALK_STEP: f32 = 4.0;
+
+/// プレイヤーの累計統計の種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatKind {
+    BlocksMined,
+    BlocksPlaced,
+    MachinesPlaced,
+    ConveyorsPlaced,
+    ItemsSmelted,
+    ItemsDelivered,
+    IngotsDelivered,
+    QuestsCompleted,
+    DistanceWalked,
+}
+
+impl StatKind {
+    /// 統計画面の表示順
+    pub const ALL: [StatKind; 9] = [
+        StatKind::BlocksMined,
+        StatKind::BlocksPlaced,
+        StatKind::MachinesPlaced,
+        StatKind::ConveyorsPlaced,
+        StatKind::ItemsSmelted,
+        StatKind::ItemsDelivered,
+        StatKind::IngotsDelivered,
+        StatKind::QuestsCompleted,
+        StatKind::DistanceWalked,
+    ];
+
+    /// 表示名
+    pub fn label(self) -> &'static str {
+        match self {
+            StatKind::BlocksMined => "採掘したブロック",
+            StatKind::BlocksPlaced => "設置したブロック",
+            StatKind::MachinesPlaced => "設置した機械",
+            StatKind::ConveyorsPlaced => "設置したコンベア",
+            StatKind::ItemsSmelted => "精錬したアイテム",
+            StatKind::ItemsDelivered => "納品したアイテム",
+            StatKind::IngotsDelivered => "納品したインゴット",
+            StatKind::QuestsCompleted => "完了したクエスト",
+            StatKind::DistanceWalked => "移動距離 (m)",
+        }
+    }
+}
+
+/// プレイヤーの累計統計（セーブに保存）
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct PlayerStats {
+    pub blocks_mined: u64,
+    pub blocks_placed: u64,
+    /// コンベア以外の機械
+    pub machines_placed: u64,
+    pub conveyors_placed: u64,
+    /// 精錬炉の出力数
+    pub items_smelted: u64,
+    pub items_delivered: u64,
+    pub ingots_delivered: u64,
+    pub quests_completed: u64,
+    /// 水平移動距離（ブロック単位）
+    pub distance_walked: f64,
+}
+
+impl PlayerStats {
+    /// 統計値を取得（移動距離は切り捨て）
+    pub fn get(&self, kind: StatKind) -> u64 {
+        match kind {
+            StatKind::BlocksMined => self.blocks_mined,
+            StatKind::BlocksPlaced => self.blocks_placed,
+            StatKind::MachinesPlaced => self.machines_placed,
+            StatKind::ConveyorsPlaced => self.conveyors_placed,
+            StatKind::ItemsSmelted => self.items_smelted,
+            StatKind::ItemsDelivered => self.items_delivered,
+            StatKind::IngotsDelivered => self.ingots_delivered,
+            StatKind::QuestsCompleted => self.quests_completed,
+            StatKind::DistanceWalked => self.distance_walked as u64,
+        }
+    }
+
+    /// 別の統計とマージ（各カウンターの大きい方を採用）
+    ///
+    /// 累計値は単調増加なので、ロード時に現在値を巻き戻さないために使う
+    pub fn merge_max(&mut self, other: &PlayerStats) {
+        self.blocks_mined = self.blocks_mined.max(other.blocks_mined);
+        self.blocks_placed = self.blocks_placed.max(other.blocks_placed);
+        self.machines_placed = self.machines_placed.max(other.machines_placed);
+        self.conveyors_placed = self.conveyors_placed.max(other.conveyors_placed);
+        self.items_smelted = self.items_smelted.max(other.items_smelted);
+        self.items_delivered = self.items_delivered.max(other.items_delivered);
+        self.ingots_delivered = self.ingots_delivered.max(other.ingots_delivered);
+        self.quests_completed = self.quests_completed.max(other.quests_completed);
+        self.distance_walked = self.distance_walked.max(other.distance_walked);
+    }
+}
+
+/// インゴット判定（IDの末尾で判定）
+fn is_ingot(item: ItemId) -> bool {
+    item.name().is_some_and(|name| name.ends_with("_ingot"))
+}
+
+/// ブロック・機械の設置/破壊イベントを購読してプレイヤー統計を更新
+fn track_block_stats(
+    mut broken: MessageReader<BlockBroken>,
+    mut placed: MessageReader<BlockPlaced>,
+    mut spawned: MessageReader<MachineSpawned>,
+    mut stats: ResMut<PlayerStats>,
+) {
+    for _event in broken.read() {
+        stats.blocks_mined += 1;
+    }
+    for _event in placed.read() {
+        stats.blocks_placed += 1;
+    }
+    for event in spawned.read() {
+        if items::is_conveyor(event.machine_type) {
+            stats.conveyors_placed += 1;
+        } else {
+            stats.machines_placed += 1;
+        }
+    }
+}
+
+/// 精錬・納品・クエストのイベントを購読してプレイヤー統計を更新
+fn track_production_stats(
+    mut completed: MessageReader<MachineCompleted>,
+    mut delivered: MessageReader<ItemDelivered>,
+    mut claimed: MessageReader<QuestRewardsClaimed>,
+    machine_query: Query<&Machine>,
+    mut stats: ResMut<PlayerStats>,
+) {
+    for event in completed.read() {
+        let is_furnace = machine_query
+            .get(event.entity)
+            .is_ok_and(|machine| machine.spec.id == "furnace");
+        if is_furnace {
+            stats.items_smelted += event.outputs.iter().map(|(_, c)| *c as u64).sum::<u64>();
+        }
+    }
+    for event in delivered.read() {
+        stats.items_delivered += event.count as u64;
+        if is_ingot(event.item) {
+            stats.ingots_delivered += event.count as u64;
+        }
+    }
+    for _event in claimed.read() {
+        stats.quests_completed += 1;
+    }
+}
+
+/// プレイヤーの水平移動距離を累積
+fn track_distance_walked(
+    player_query: Query<&Transform, With<Player>>,
+    mut stats: ResMut<PlayerStats>,
+    mut last_pos: Local<Option<Vec3>>,
+) {
+    let Ok(transform) = player_query.single() else {
+        return;
+    };
+    let pos = transform.translation;
+    if let Some(prev) = last_pos.replace(pos) {
+        let step = (pos - prev).xz().length();
+        if step > 0.0 && step < MAX_WALK_STEP {
+            stats.distance_walked += step as f64;
+        }
+    }
+}
+
 pub struct StatisticsPlugin;
 
 impl Plugin for StatisticsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ProductionStats>()
             .init_resource::<DeliveryStats>()
+            .init_resource::<PlayerStats>()
+            .init_resource::<ThroughputTracker>()
+            .init_resource::<MachineCensus>()
+            .init_resource::<ProductionGoal>()
+            .add_systems(FixedUpdate, advance_throughput.in_set(InGameSet))
             .add_systems(
                 Update,
                 (
                     handle_machine_completed,
                     handle_machine_started,
                     handle_item_delivered,
-                ),
+                    track_throughput,
+                    track_block_stats,
+                    track_production_stats,
+                    track_distance_walked,
+                    goals::update_machine_census,
+                )
+                    .in_set(InGameSet),
             );
     }
 }
@@ -322,4 +622,74 @@ mod tests {
         assert_eq!(iron_total, 10);
         assert_eq!(copper_total, 5);
     }
+
+    #[test]
+    fn test_player_stats_merge_max() {
+        let mut current = PlayerStats {
+            blocks_mined: 10,
+            conveyors_placed: 2,
+            distance_walked: 5.0,
+            ..default()
+        };
+        let saved = PlayerStats {
+            blocks_mined: 4,
+            conveyors_placed: 20,
+            distance_walked: 120.5,
+            ..default()
+        };
+        current.merge_max(&saved);
+
+        assert_eq!(current.blocks_mined, 10);
+        assert_eq!(current.conveyors_placed, 20);
+        assert_eq!(current.get(StatKind::DistanceWalked), 120);
+    }
+
+    #[test]
+    fn test_is_ingot() {
+        assert!(is_ingot(items::iron_ingot()));
+        assert!(is_ingot(items::copper_ingot()));
+        assert!(!is_ingot(items::iron_ore()));
+    }
+
+    #[test]
+    fn test_throughput_window() {
+        let mut tracker = ThroughputTracker::default();
+        assert_eq!(tracker.rate_per_min(items::iron_ingot()), 0.0);
+
+        // 10 ingots over the first minute
+        tracker.record(items::iron_ingot(), 10);
+        for _ in 0..1200 {
+            tracker.advance();
+        }
+        assert_eq!(tracker.rate_per_min(items::iron_ingot()), 10.0);
+        assert_eq!(tracker.rates(), vec![(items::iron_ingot(), 10.0)]);
+
+        // The delivery leaves the window after five minutes
+        for _ in 1200..THROUGHPUT_WINDOW_TICKS {
+            tracker.advance();
+        }
+        assert_eq!(tracker.rate_per_min(items::iron_ingot()), 0.0);
+        assert!(tracker.rates().is_empty());
+    }
+
+    #[test]
+    fn test_production_rate_separate_from_deliveries() {
+        let mut tracker = ThroughputTracker::default();
+        tracker.record_production(items::iron_ingot(), 30);
+        tracker.record(items::iron_ingot(), 10);
+        for _ in 0..1200 {
+            tracker.advance();
+        }
+        assert_eq!(tracker.production_rate_per_min(items::iron_ingot()), 30.0);
+        assert_eq!(tracker.rate_per_min(items::iron_ingot()), 10.0);
+        assert_eq!(
+            tracker.production_rates(),
+            HashMap::from([(items::iron_ingot(), 30.0)])
+        );
+
+        for _ in 1200..THROUGHPUT_WINDOW_TICKS {
+            tracker.advance();
+        }
+        assert!(tracker.production_rates().is_empty());
+    }
 }
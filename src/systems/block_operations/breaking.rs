@@ -1,7 +1,6 @@
 //! Block breaking system with time-based breaking
 
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
 use crate::core::{items, ItemId};
 use crate::events::game_events::{BlockBroken, EventSource};
@@ -11,11 +10,13 @@ use crate::systems::TutorialEvent;
 use crate::utils::ray_aabb_intersection;
 use crate::world::{DirtyChunks, WorldData};
 use crate::{
-    BreakingProgress, ConveyorItemVisual, CreativeMode, CursorLockState, InputStateResources,
+    BreakingProgress, ConveyorItemVisual, CreativeMode, CursorController, InputStateResources,
     TargetBlock, BLOCK_SIZE, PLATFORM_SIZE, REACH_DISTANCE,
 };
 
-use super::{BlockBreakEvents, LocalPlayerInventory, MachineBreakQueries};
+use super::{
+    BlockBreakEvents, BlockToast, LocalPlayerInventory, MachineBreakQueries, ProtectionZone,
+};
 
 /// What type of thing we're trying to break
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -33,9 +34,8 @@ pub fn block_break(
     camera_query: Query<(&GlobalTransform, &crate::PlayerCamera)>,
     machines: MachineBreakQueries,
     mut player_inventory: LocalPlayerInventory,
-    cursor_query: Query<&CursorOptions, With<PrimaryWindow>>,
     item_visual_query: Query<Entity, With<ConveyorItemVisual>>,
-    mut cursor_state: ResMut<CursorLockState>,
+    mut cursor: ResMut<CursorController>,
     input_resources: InputStateResources,
     target_block: Res<TargetBlock>,
     mut world_data: ResMut<WorldData>,
@@ -44,6 +44,7 @@ pub fn block_break(
     time: Res<Time>,
     creative_mode: Res<CreativeMode>,
     mut events: BlockBreakEvents,
+    mut toast: BlockToast,
 ) {
     // Get player entity before consuming inventory
     let player_entity = player_inventory.entity();
@@ -51,23 +52,20 @@ pub fn block_break(
         breaking_progress.reset();
         return;
     };
-    // Only break blocks when cursor is locked and not paused
-    let Ok(cursor_options) = cursor_query.single() else {
+    // Left-click with the wrench copies machine settings instead of breaking
+    if inventory.selected_item_id() == Some(items::wrench()) {
         breaking_progress.reset();
         return;
-    };
-    let cursor_locked = cursor_options.grab_mode != CursorGrabMode::None;
-
-    // Use InputState to check if block actions are allowed
-    let input_state = input_resources.get_state_with(&cursor_state);
-    if !input_state.allows_block_actions() || !cursor_locked {
+    }
+    // Only break blocks when the game owns the cursor
+    let input_state = input_resources.get_state_with(&cursor);
+    if !input_state.allows_block_actions() {
         breaking_progress.reset();
         return;
     }
 
-    // Skip block break if we just locked the cursor
-    if cursor_state.just_locked {
-        cursor_state.just_locked = false;
+    // Skip the click that only returned focus to the window
+    if cursor.take_swallowed_click() {
         breaking_progress.reset();
         return;
     }
@@ -75,7 +73,7 @@ pub fn block_break(
     // Check if left mouse button is pressed
     let is_pressing = mouse_button.pressed(MouseButton::Left);
     if !is_pressing {
-        breaking_progress.reset();
+        breaking_progress.release();
         return;
     }
 
@@ -106,53 +104,35 @@ pub fn block_break(
         return;
     };
 
-    // Get the block type for timing calculation
-    let block_type = match target {
-        BreakTarget::Machine(_, bt) => bt,
-        BreakTarget::WorldBlock(_, bt) => bt,
-    };
-
-    // Get selected tool (now uses ItemId directly)
-    let selected_tool = inventory.selected_item_id();
-    let tool_multiplier = breaking_spec::get_tool_multiplier(selected_tool);
-    let base_time = breaking_spec::get_base_break_time(ItemId::from(block_type));
-    let total_time = base_time * tool_multiplier;
+    // Never break the machine whose UI is open (it may have opened this frame)
+    if is_interacting_with(target, input_resources.interacting_machine.0) {
+        breaking_progress.reset();
+        return;
+    }
 
-    // Check if target changed
-    let target_changed = match target {
-        BreakTarget::Machine(entity, _) => breaking_progress.target_entity != Some(entity),
-        BreakTarget::WorldBlock(pos, _) => {
-            breaking_progress.target_pos != Some(pos) || breaking_progress.is_machine
+    // Platform pads and the ground under the platforms are protected
+    let zone = toast.protection_zone(
+        creative_mode.enabled,
+        machines.platforms.iter(),
+        world_data.gen_config.initial_furnace_position(),
+    );
+    if is_protected(target, &zone) {
+        if mouse_button.just_pressed(MouseButton::Left) {
+            toast.show_protected();
         }
-    };
-
-    if target_changed {
-        // Start new breaking session
         breaking_progress.reset();
-        breaking_progress.total_time = total_time;
-        match target {
-            BreakTarget::Machine(entity, _) => {
-                breaking_progress.target_entity = Some(entity);
-                breaking_progress.is_machine = true;
-            }
-            BreakTarget::WorldBlock(pos, _) => {
-                breaking_progress.target_pos = Some(pos);
-                breaking_progress.is_machine = false;
-            }
-        }
+        return;
     }
 
-    // Update progress (fast break in creative mode, but not instant)
-    if creative_mode.enabled {
-        // Fast but not instant - 0.1 seconds per block for smoother control
-        breaking_progress.progress += time.delta_secs() / 0.1;
-    } else {
-        let delta = time.delta_secs();
-        breaking_progress.progress += delta / total_time;
-    }
+    let total_time = break_time(target, inventory.selected_item_id(), creative_mode.enabled);
 
     // Check if breaking is complete
-    if breaking_progress.progress >= 1.0 {
+    if advance_break(
+        &mut breaking_progress,
+        target,
+        total_time,
+        time.delta_secs(),
+    ) {
         // Execute the break
         match target {
             BreakTarget::Machine(entity, machine_type) => {
@@ -183,10 +163,83 @@ pub fn block_break(
         }
         // Send tutorial event for block breaking
         events.tutorial.write(TutorialEvent::BlockBroken);
-        breaking_progress.reset();
+        finish_break(&mut breaking_progress, target);
     }
 }
 
+/// Whether `target` is the machine the local player has open
+fn is_interacting_with(target: BreakTarget, interacting: Option<Entity>) -> bool {
+    matches!(target, BreakTarget::Machine(entity, _) if interacting == Some(entity))
+}
+
+/// Whether spawn protection keeps `target` from being broken (machines never are)
+fn is_protected(target: BreakTarget, zone: &ProtectionZone) -> bool {
+    matches!(target, BreakTarget::WorldBlock(pos, _) if zone.blocks_break(pos))
+}
+
+/// Seconds needed to break a target
+///
+/// Machines take a fixed hold (instant in creative) so a misclick can't
+/// delete them; world blocks depend on hardness and tool.
+fn break_time(target: BreakTarget, tool: Option<ItemId>, creative: bool) -> f32 {
+    match target {
+        BreakTarget::Machine(..) if creative => 0.0,
+        BreakTarget::Machine(..) => breaking_spec::MACHINE_HOLD_SECS,
+        BreakTarget::WorldBlock(..) if creative => breaking_spec::CREATIVE_BLOCK_BREAK_SECS,
+        BreakTarget::WorldBlock(_, block) => {
+            breaking_spec::get_base_break_time(block) * breaking_spec::get_tool_multiplier(tool)
+        }
+    }
+}
+
+/// Advance progress on the held target, restarting when the target changes
+///
+/// Returns true when the break completes.
+fn advance_break(
+    progress: &mut BreakingProgress,
+    target: BreakTarget,
+    total_time: f32,
+    delta: f32,
+) -> bool {
+    if progress.wait_for_release {
+        return false;
+    }
+
+    let target_changed = match target {
+        BreakTarget::Machine(entity, _) => progress.target_entity != Some(entity),
+        BreakTarget::WorldBlock(pos, _) => progress.target_pos != Some(pos) || progress.is_machine,
+    };
+    if target_changed {
+        // Start new breaking session
+        progress.reset();
+        progress.total_time = total_time;
+        match target {
+            BreakTarget::Machine(entity, _) => {
+                progress.target_entity = Some(entity);
+                progress.is_machine = true;
+            }
+            BreakTarget::WorldBlock(pos, _) => {
+                progress.target_pos = Some(pos);
+                progress.is_machine = false;
+            }
+        }
+    }
+
+    if total_time <= 0.0 {
+        progress.progress = 1.0;
+    } else {
+        progress.progress += delta / total_time;
+    }
+    progress.is_complete()
+}
+
+/// Clear progress after a break
+fn finish_break(progress: &mut BreakingProgress, target: BreakTarget) {
+    progress.reset();
+    // Still holding after a machine vanishes must not start breaking the terrain behind it
+    progress.wait_for_release = matches!(target, BreakTarget::Machine(..));
+}
+
 /// Find the closest break target (machine or world block)
 fn find_break_target(
     ray_origin: Vec3,
@@ -214,7 +267,7 @@ fn find_break_target(
     });
 
     // Check conveyors
-    for (entity, _conveyor, conveyor_transform) in machines.conveyor.iter() {
+    for (entity, conveyor, conveyor_transform) in machines.conveyor.iter() {
         let pos = conveyor_transform.translation();
         if let Some(t) = ray_aabb_intersection(
             ray_origin,
@@ -223,7 +276,7 @@ fn find_break_target(
             pos + Vec3::new(half_size, 0.15, half_size),
         ) {
             if t > 0.0 && t < REACH_DISTANCE && closest.as_ref().is_none_or(|(_, d)| t < *d) {
-                closest = Some((BreakTarget::Machine(entity, items::conveyor_block()), t));
+                closest = Some((BreakTarget::Machine(entity, conveyor.tier_item()), t));
             }
         }
     }
@@ -283,17 +336,17 @@ fn execute_machine_break(
     item_visual_query: &Query<Entity, With<ConveyorItemVisual>>,
     inventory: &mut PlayerInventory,
 ) {
-    if machine_id == items::conveyor_block() {
+    if items::is_conveyor(machine_id) {
         if let Ok((_, conveyor, transform)) = machines.conveyor.get(entity) {
             let pos = transform.translation();
-            let count = conveyor.items.len();
+            let count = conveyor.total_item_count();
             for item in &conveyor.items {
                 if let Some(visual_entity) = item.visual_entity {
                     if item_visual_query.get(visual_entity).is_ok() {
                         commands.entity(visual_entity).despawn();
                     }
                 }
-                inventory.add_item_by_id(item.item_id, 1);
+                inventory.add_item_by_id(item.item_id, item.count);
             }
             info!(
                 category = "MACHINE",
@@ -305,10 +358,13 @@ fn execute_machine_break(
             );
         }
         commands.entity(entity).despawn();
-        inventory.add_item_by_id(items::conveyor_block(), 1);
+        // Return the belt's own tier
+        inventory.add_item_by_id(machine_id, 1);
     } else if machine_id == items::miner_block()
         || machine_id == items::crusher_block()
         || machine_id == items::furnace_block()
+        || machine_id == items::recycler_block()
+        || machine_id == items::hopper_block()
     {
         // Return contents from machine slots
         if let Ok((_, machine, _)) = machines.machine.get(entity) {
@@ -380,3 +436,119 @@ fn execute_block_break(
         source,
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine_target(world: &mut World) -> BreakTarget {
+        BreakTarget::Machine(world.spawn_empty().id(), items::furnace_block())
+    }
+
+    #[test]
+    fn test_machine_needs_full_hold() {
+        let mut world = World::new();
+        let furnace = machine_target(&mut world);
+        let other = machine_target(&mut world);
+        let mut progress = BreakingProgress::default();
+        let hold = break_time(furnace, None, false);
+        assert_eq!(hold, breaking_spec::MACHINE_HOLD_SECS);
+
+        assert!(!advance_break(&mut progress, furnace, hold, hold * 0.5));
+        assert!(progress.is_breaking());
+
+        // Looking at another machine starts over
+        assert!(!advance_break(&mut progress, other, hold, hold * 0.5));
+        assert!((progress.progress - 0.5).abs() < 1e-5);
+
+        // Releasing the button starts over too
+        progress.release();
+        assert!(!progress.is_breaking());
+        assert!(!advance_break(&mut progress, furnace, hold, hold * 0.5));
+        assert!((progress.progress - 0.5).abs() < 1e-5);
+
+        assert!(advance_break(&mut progress, furnace, hold, hold * 0.5));
+    }
+
+    #[test]
+    fn test_open_machine_is_not_a_break_target() {
+        let mut world = World::new();
+        let furnace = machine_target(&mut world);
+        let BreakTarget::Machine(entity, _) = furnace else {
+            unreachable!();
+        };
+
+        assert!(is_interacting_with(furnace, Some(entity)));
+        assert!(!is_interacting_with(furnace, None));
+        assert!(!is_interacting_with(
+            furnace,
+            Some(world.spawn_empty().id())
+        ));
+        let stone = BreakTarget::WorldBlock(IVec3::ZERO, items::stone());
+        assert!(!is_interacting_with(stone, Some(entity)));
+    }
+
+    #[test]
+    fn test_protected_ground_is_not_broken() {
+        use super::super::SpawnProtection;
+        use crate::DeliveryPlatform;
+
+        let mut world = World::new();
+        let furnace = machine_target(&mut world);
+        let platform = DeliveryPlatform::new(IVec3::new(20, 8, 10));
+        let under = BreakTarget::WorldBlock(IVec3::new(22, 7, 12), items::stone());
+        let outside = BreakTarget::WorldBlock(IVec3::new(10, 7, 12), items::stone());
+
+        let zone = ProtectionZone::new(&SpawnProtection::default(), false, [&platform], None);
+        assert!(is_protected(under, &zone));
+        assert!(!is_protected(outside, &zone));
+        // Machines on the platform can still be picked up
+        assert!(!is_protected(furnace, &zone));
+
+        let creative = ProtectionZone::new(&SpawnProtection::default(), true, [&platform], None);
+        assert!(!is_protected(under, &creative));
+    }
+
+    #[test]
+    fn test_creative_breaks_machines_instantly() {
+        let mut world = World::new();
+        let furnace = machine_target(&mut world);
+        let mut progress = BreakingProgress::default();
+
+        let total = break_time(furnace, None, true);
+        assert_eq!(total, 0.0);
+        assert!(advance_break(&mut progress, furnace, total, 0.0));
+
+        // Terrain keeps its own cadence in creative mode
+        let stone = BreakTarget::WorldBlock(IVec3::ZERO, items::stone());
+        assert_eq!(
+            break_time(stone, None, true),
+            breaking_spec::CREATIVE_BLOCK_BREAK_SECS
+        );
+    }
+
+    #[test]
+    fn test_holding_after_machine_break_waits_for_release() {
+        let mut world = World::new();
+        let furnace = machine_target(&mut world);
+        let behind = BreakTarget::WorldBlock(IVec3::new(0, 4, 0), items::stone());
+        let mut progress = BreakingProgress::default();
+
+        assert!(advance_break(&mut progress, furnace, 0.0, 0.0));
+        finish_break(&mut progress, furnace);
+
+        // The block behind the machine is not touched while the button stays down
+        for _ in 0..10 {
+            assert!(!advance_break(&mut progress, behind, 0.1, 0.1));
+        }
+        assert_eq!(progress.progress, 0.0);
+
+        progress.release();
+        assert!(!advance_break(&mut progress, behind, 0.1, 0.05));
+        assert!(advance_break(&mut progress, behind, 0.1, 0.05));
+
+        // World blocks break back to back while held
+        finish_break(&mut progress, behind);
+        assert!(!progress.wait_for_release);
+    }
+}
@@ -3,12 +3,23 @@
 //! This module contains the core block interaction systems:
 //! - block_break: Breaking world blocks and machines
 //! - block_place: Placing blocks and machines
+//! - validation: Placement checks shared with the placement preview
+//! - protection: Spawn protection zone around the delivery platforms
 
 mod breaking;
 mod placement;
+mod protection;
+mod validation;
 
 pub use breaking::block_break;
 pub use placement::block_place;
+pub use protection::{
+    handle_protection_command, ProtectionCommandEvent, ProtectionZone, SpawnProtection,
+    PROTECTED_MESSAGE,
+};
+pub use validation::{
+    requires_support, PlacementCheck, PlacementError, PlacementPreview, PlacementWarning,
+};
 
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
@@ -17,9 +28,17 @@ use crate::components::Machine;
 use crate::core::ItemId;
 use crate::events::game_events::InventoryChanged;
 use crate::events::GuardedMessageWriter;
+use crate::graphics::BlockTextures;
 use crate::player::{LocalPlayer, PlayerInventory};
 use crate::{Conveyor, DeliveryPlatform};
 
+/// Right-click handlers that act on a targeted block instead of placing one
+///
+/// Runs after the wrench and before block_place. A handler that uses the
+/// click swallows it, so placement (and the handlers after it) never see it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockClickSet;
+
 /// Bundled local player inventory access (reduces parameter count)
 #[derive(SystemParam)]
 pub struct LocalPlayerInventory<'w, 's> {
@@ -106,6 +125,7 @@ pub struct MachineBreakQueries<'w, 's> {
     pub conveyor: Query<'w, 's, (Entity, &'static Conveyor, &'static GlobalTransform)>,
     pub machine: Query<'w, 's, (Entity, &'static Machine, &'static GlobalTransform)>,
     pub platform: Query<'w, 's, &'static Transform, With<DeliveryPlatform>>,
+    pub platforms: Query<'w, 's, &'static DeliveryPlatform>,
 }
 
 /// Bundled machine queries for block_place system (reduces parameter count)
@@ -120,6 +140,21 @@ pub struct MachinePlaceQueries<'w, 's> {
 pub struct ChunkAssets<'w> {
     pub meshes: ResMut<'w, Assets<Mesh>>,
     pub materials: ResMut<'w, Assets<StandardMaterial>>,
+    pub textures: Option<Res<'w, BlockTextures>>,
+}
+
+impl ChunkAssets<'_> {
+    /// Fallback cube material for a block (its texture tile if loaded, else its color)
+    pub fn cube_material(&mut self, item_id: ItemId) -> Handle<StandardMaterial> {
+        let material = match &self.textures {
+            Some(textures) => textures.material(item_id),
+            None => StandardMaterial {
+                base_color: item_id.color(),
+                ..default()
+            },
+        };
+        self.materials.add(material)
+    }
 }
 
 /// Bundled block break events (reduces parameter count)
@@ -137,6 +172,41 @@ pub struct BlockPlaceEvents<'w> {
     pub machine_spawned: GuardedMessageWriter<'w, crate::events::game_events::MachineSpawned>,
 }
 
+/// Block action toast with the warning setting and spawn protection behind it (reduces parameter count)
+#[derive(SystemParam)]
+pub struct BlockToast<'w> {
+    settings: Res<'w, crate::settings::GameSettings>,
+    protection: Res<'w, SpawnProtection>,
+    toast: ResMut<'w, crate::systems::WrenchToast>,
+}
+
+impl BlockToast<'_> {
+    /// Whether placement warnings are on in the settings
+    pub fn warnings_enabled(&self) -> bool {
+        self.settings.placement_warnings
+    }
+
+    /// Show the warning as a toast (placement goes ahead either way)
+    pub fn show_warning(&mut self, warning: PlacementWarning) {
+        self.toast.show(format!("⚠ {}", warning.message()));
+    }
+
+    /// Protected area for this frame
+    pub fn protection_zone<'a>(
+        &self,
+        creative: bool,
+        platforms: impl IntoIterator<Item = &'a DeliveryPlatform>,
+        initial_furnace: Option<IVec3>,
+    ) -> ProtectionZone {
+        ProtectionZone::new(&self.protection, creative, platforms, initial_furnace)
+    }
+
+    /// Tell the player the spawn protection refused the action
+    pub fn show_protected(&mut self) {
+        self.toast.show(PROTECTED_MESSAGE);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
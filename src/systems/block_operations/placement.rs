@@ -1,16 +1,16 @@
 //! Block placement system
 
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
-use crate::components::MachineBundle;
+use crate::components::{conveyor_speed_multiplier, MachineBundle};
 use crate::core::items;
 use crate::events::game_events::{BlockPlaced, EventSource, MachineSpawned};
-use crate::game_spec::{CRUSHER, FURNACE, MINER};
+use crate::game_spec::{data_machine_spec, CRUSHER, FURNACE, HOPPER, MINER, RECYCLER};
+use crate::meshes::create_conveyor_mesh;
 use crate::systems::TutorialEvent;
 use crate::utils::{
-    auto_conveyor_direction, dda_raycast, ray_aabb_intersection, ray_aabb_intersection_with_normal,
-    yaw_to_direction,
+    auto_conveyor_direction, auto_faces_conveyors, auto_machine_facing, ray_aabb_intersection,
+    ray_aabb_intersection_with_normal, yaw_to_direction,
 };
 use crate::world::{DirtyChunks, WorldData};
 use crate::{
@@ -20,7 +20,10 @@ use crate::{
     REACH_DISTANCE,
 };
 
-use super::{BlockPlaceEvents, ChunkAssets, LocalPlayerInventory, MachinePlaceQueries};
+use super::{
+    BlockPlaceEvents, BlockToast, ChunkAssets, LocalPlayerInventory, MachinePlaceQueries,
+    PlacementCheck, PlacementError,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn block_place(
@@ -28,31 +31,27 @@ pub fn block_place(
     mouse_button: Res<ButtonInput<MouseButton>>,
     camera_query: Query<(&GlobalTransform, &PlayerCamera)>,
     machines: MachinePlaceQueries,
-    platform_query: Query<&Transform, With<DeliveryPlatform>>,
+    platform_query: Query<(&Transform, &DeliveryPlatform)>,
     mut world_data: ResMut<WorldData>,
     mut player_inventory: LocalPlayerInventory,
     mut dirty_chunks: ResMut<DirtyChunks>,
     mut chunk_assets: ChunkAssets,
-    cursor_query: Query<&CursorOptions, With<PrimaryWindow>>,
     creative_mode: Res<CreativeMode>,
     input_resources: InputStateResourcesWithCursor,
     mut action_timer: ResMut<ContinuousActionTimer>,
     mut rotation: ResMut<ConveyorRotationOffset>,
     machine_models: Res<MachineModels>,
     mut events: BlockPlaceEvents,
+    mut toast: BlockToast,
 ) {
     // Get player entity before consuming inventory
     let player_entity = player_inventory.entity();
     let Some(mut inventory) = player_inventory.get_mut() else {
         return;
     };
-    let cursor_locked = cursor_query
-        .single()
-        .map(|c| c.grab_mode != CursorGrabMode::None)
-        .unwrap_or(false);
 
     let input_state = input_resources.get_state();
-    if !input_state.allows_block_actions() || !cursor_locked {
+    if !input_state.allows_block_actions() {
         return;
     }
 
@@ -130,9 +129,7 @@ pub fn block_place(
     // Find closest block intersection with hit normal using DDA
     let mut closest_hit: Option<(IVec3, Vec3, f32)> = None;
 
-    if let Some(hit) = dda_raycast(ray_origin, ray_direction, REACH_DISTANCE, |pos| {
-        world_data.has_block(pos)
-    }) {
+    if let Some(hit) = world_data.raycast_dda(ray_origin, ray_direction, REACH_DISTANCE) {
         let normal = Vec3::new(
             hit.normal.x as f32,
             hit.normal.y as f32,
@@ -142,7 +139,7 @@ pub fn block_place(
     }
 
     // Also check DeliveryPlatform for raycast hit
-    if let Ok(platform_transform) = platform_query.single() {
+    if let Ok((platform_transform, _)) = platform_query.single() {
         let platform_center = platform_transform.translation;
         let platform_half_x = (PLATFORM_SIZE as f32 * BLOCK_SIZE) / 2.0;
         let platform_half_y = BLOCK_SIZE * 0.1;
@@ -184,19 +181,35 @@ pub fn block_place(
                 normal.z.round() as i32,
             );
 
-        // Don't place if already occupied
-        if world_data.has_block(place_pos) {
-            return;
-        }
-        for conveyor in machines.conveyor.iter() {
-            if conveyor.position == place_pos {
-                return;
-            }
-        }
-        for (machine, _) in machines.machine.iter() {
-            if machine.position == place_pos {
-                return;
+        // Same checks as the placement preview (occupancy, support, inventory)
+        let conveyor_at = |p: IVec3| machines.conveyor.iter().any(|c| c.position == p);
+        let machine_at = |p: IVec3| machines.machine.iter().any(|(m, _)| m.position == p);
+        let on_platform = |p: IVec3| {
+            platform_query
+                .iter()
+                .any(|(_, platform)| platform.contains(p))
+        };
+        let zone = toast.protection_zone(
+            creative_mode.enabled,
+            platform_query.iter().map(|(_, platform)| platform),
+            world_data.gen_config.initial_furnace_position(),
+        );
+        let protected = |p: IVec3, item| zone.blocks_placement(p, item);
+        let check = PlacementCheck {
+            world: &world_data,
+            conveyor_at: &conveyor_at,
+            machine_at: &machine_at,
+            extra_support: &on_platform,
+            protected: &protected,
+            inventory_count: inventory.get_total_count_by_id(selected_item_id),
+            creative: creative_mode.enabled,
+        };
+        if let Err(reason) = check.validate(place_pos, selected_item_id) {
+            debug!(?place_pos, ?reason, "Placement rejected");
+            if reason == PlacementError::Protected {
+                toast.show_protected();
             }
+            return;
         }
 
         // Consume from inventory (unless in creative mode)
@@ -208,7 +221,7 @@ pub fn block_place(
         let chunk_coord = WorldData::world_to_chunk(place_pos);
         let player_facing = yaw_to_direction(player_camera.yaw);
 
-        let facing_direction = if selected_item_id == items::conveyor_block() {
+        let facing_direction = if items::is_conveyor(selected_item_id) {
             let conveyors: Vec<(IVec3, Direction)> = machines
                 .conveyor
                 .iter()
@@ -227,8 +240,17 @@ pub fn block_place(
             }
             dir
         } else if selected_item_id.is_machine() {
-            // Apply rotation offset to all directional machines
-            let mut dir = player_facing;
+            // Line processing machines up with adjacent belts, then apply rotation offset
+            let mut dir = if auto_faces_conveyors(selected_item_id) {
+                let conveyors: Vec<(IVec3, Direction)> = machines
+                    .conveyor
+                    .iter()
+                    .map(|c| (c.position, c.direction))
+                    .collect();
+                auto_machine_facing(place_pos, player_facing, &conveyors)
+            } else {
+                player_facing
+            };
             for _ in 0..rotation.offset {
                 dir = dir.rotate_cw();
             }
@@ -237,6 +259,13 @@ pub fn block_place(
             player_facing
         };
 
+        // Same heuristics as the preview tint; only a heads-up, placement goes ahead
+        if toast.warnings_enabled() {
+            if let Some(warning) = check.warning(place_pos, selected_item_id, facing_direction) {
+                toast.show_warning(warning);
+            }
+        }
+
         if selected_item_id == items::miner_block() {
             info!(
                 category = "MACHINE",
@@ -258,10 +287,7 @@ pub fn block_place(
                 let cube_mesh = chunk_assets
                     .meshes
                     .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                let material = chunk_assets.materials.add(StandardMaterial {
-                    base_color: selected_item_id.color(),
-                    ..default()
-                });
+                let material = chunk_assets.cube_material(selected_item_id);
                 commands
                     .spawn((
                         Mesh3d(cube_mesh),
@@ -280,7 +306,7 @@ pub fn block_place(
             events
                 .tutorial
                 .write(TutorialEvent::MachinePlaced(items::miner_block()));
-        } else if selected_item_id == items::conveyor_block() {
+        } else if let Some(speed_multiplier) = conveyor_speed_multiplier(selected_item_id) {
             let front_pos = place_pos + facing_direction.to_ivec3();
             let mut final_shape = ConveyorShape::Straight;
             let final_direction = facing_direction;
@@ -336,18 +362,16 @@ pub fn block_place(
                             output_direction: final_direction, // Will be updated by update_conveyor_shapes
                             items: Vec::new(),
                             last_output_index: 0,
-                            last_input_source: 0,
+                            last_input_pos: None,
+                            enabled: true,
                             shape: final_shape,
+                            speed_multiplier,
                         },
                         ConveyorVisual,
                     ))
                     .id()
             } else {
-                let conveyor_mesh = chunk_assets.meshes.add(Cuboid::new(
-                    BLOCK_SIZE * CONVEYOR_BELT_WIDTH,
-                    BLOCK_SIZE * CONVEYOR_BELT_HEIGHT,
-                    BLOCK_SIZE,
-                ));
+                let conveyor_mesh = chunk_assets.meshes.add(create_conveyor_mesh(final_shape));
                 let material = chunk_assets.materials.add(StandardMaterial {
                     base_color: selected_item_id.color(),
                     ..default()
@@ -378,8 +402,10 @@ pub fn block_place(
                             output_direction: final_direction,
                             items: Vec::new(),
                             last_output_index: 0,
-                            last_input_source: 0,
+                            last_input_pos: None,
+                            enabled: true,
                             shape: final_shape,
+                            speed_multiplier,
                         },
                         ConveyorVisual,
                     ))
@@ -399,7 +425,7 @@ pub fn block_place(
             // Send MachineSpawned event
             let _ = events.machine_spawned.write(MachineSpawned {
                 entity,
-                machine_type: items::conveyor_block(),
+                machine_type: selected_item_id,
                 pos: place_pos,
             });
             rotation.offset = 0;
@@ -420,7 +446,7 @@ pub fn block_place(
                 commands
                     .spawn((
                         SceneRoot(model),
-                        MachineBundle::new(&CRUSHER, place_pos, player_facing),
+                        MachineBundle::new(&CRUSHER, place_pos, facing_direction),
                     ))
                     .id()
             } else {
@@ -428,15 +454,12 @@ pub fn block_place(
                 let cube_mesh = chunk_assets
                     .meshes
                     .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                let material = chunk_assets.materials.add(StandardMaterial {
-                    base_color: selected_item_id.color(),
-                    ..default()
-                });
+                let material = chunk_assets.cube_material(selected_item_id);
                 commands
                     .spawn((
                         Mesh3d(cube_mesh),
                         MeshMaterial3d(material),
-                        MachineBundle::new_centered(&CRUSHER, place_pos, player_facing),
+                        MachineBundle::new_centered(&CRUSHER, place_pos, facing_direction),
                     ))
                     .id()
             };
@@ -450,6 +473,58 @@ pub fn block_place(
             events
                 .tutorial
                 .write(TutorialEvent::MachinePlaced(items::crusher_block()));
+        } else if selected_item_id == items::recycler_block() {
+            info!(
+                category = "MACHINE",
+                action = "place",
+                machine = "recycler",
+                ?place_pos,
+                "Recycler placed"
+            );
+
+            // No model yet: fallback cube mesh has center origin, so use new_centered
+            let cube_mesh = chunk_assets
+                .meshes
+                .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let material = chunk_assets.cube_material(selected_item_id);
+            let entity = commands
+                .spawn((
+                    Mesh3d(cube_mesh),
+                    MeshMaterial3d(material),
+                    MachineBundle::new_centered(&RECYCLER, place_pos, facing_direction),
+                ))
+                .id();
+            let _ = events.machine_spawned.write(MachineSpawned {
+                entity,
+                machine_type: items::recycler_block(),
+                pos: place_pos,
+            });
+        } else if selected_item_id == items::hopper_block() {
+            info!(
+                category = "MACHINE",
+                action = "place",
+                machine = "hopper",
+                ?place_pos,
+                "Hopper placed"
+            );
+
+            // No model yet: fallback cube mesh has center origin, so use new_centered
+            let cube_mesh = chunk_assets
+                .meshes
+                .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let material = chunk_assets.cube_material(selected_item_id);
+            let entity = commands
+                .spawn((
+                    Mesh3d(cube_mesh),
+                    MeshMaterial3d(material),
+                    MachineBundle::new_centered(&HOPPER, place_pos, facing_direction),
+                ))
+                .id();
+            let _ = events.machine_spawned.write(MachineSpawned {
+                entity,
+                machine_type: items::hopper_block(),
+                pos: place_pos,
+            });
         } else if selected_item_id == items::furnace_block() {
             info!(
                 category = "MACHINE",
@@ -463,7 +538,7 @@ pub fn block_place(
                 commands
                     .spawn((
                         SceneRoot(model),
-                        MachineBundle::new(&FURNACE, place_pos, player_facing),
+                        MachineBundle::new(&FURNACE, place_pos, facing_direction),
                     ))
                     .id()
             } else {
@@ -471,15 +546,12 @@ pub fn block_place(
                 let cube_mesh = chunk_assets
                     .meshes
                     .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
-                let material = chunk_assets.materials.add(StandardMaterial {
-                    base_color: selected_item_id.color(),
-                    ..default()
-                });
+                let material = chunk_assets.cube_material(selected_item_id);
                 commands
                     .spawn((
                         Mesh3d(cube_mesh),
                         MeshMaterial3d(material),
-                        MachineBundle::new_centered(&FURNACE, place_pos, player_facing),
+                        MachineBundle::new_centered(&FURNACE, place_pos, facing_direction),
                     ))
                     .id()
             };
@@ -493,6 +565,32 @@ pub fn block_place(
             events
                 .tutorial
                 .write(TutorialEvent::MachinePlaced(items::furnace_block()));
+        } else if let Some(spec) = data_machine_spec(selected_item_id) {
+            info!(
+                category = "MACHINE",
+                action = "place",
+                machine = spec.id,
+                ?place_pos,
+                "Data machine placed"
+            );
+
+            // Data machines have no model: fallback cube mesh has center origin
+            let cube_mesh = chunk_assets
+                .meshes
+                .add(Cuboid::new(BLOCK_SIZE, BLOCK_SIZE, BLOCK_SIZE));
+            let material = chunk_assets.cube_material(selected_item_id);
+            let entity = commands
+                .spawn((
+                    Mesh3d(cube_mesh),
+                    MeshMaterial3d(material),
+                    MachineBundle::new_centered(spec, place_pos, facing_direction),
+                ))
+                .id();
+            let _ = events.machine_spawned.write(MachineSpawned {
+                entity,
+                machine_type: selected_item_id,
+                pos: place_pos,
+            });
         } else {
             // Regular block placement
             info!(category = "BLOCK", action = "place", ?place_pos, block = ?selected_item_id.name(), "Block placed");
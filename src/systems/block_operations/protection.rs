@@ -0,0 +1,246 @@
+//! Spawn protection around the delivery platforms
+//!
+//! Terrain blocks can't be placed within `PROTECTION_MARGIN` blocks of a
+//! platform, so its ports can't be walled in, and the pads and the ground
+//! under a platform can't be broken. Machines and conveyors are still
+//! allowed. The zone follows the platform entities (the starter platform and
+//! every pad platform), so it moves with the worldgen platform origin. The
+//! starting furnace from the worldgen config (`initial_furnace`) gets the
+//! same treatment as a one-block platform.
+//!
+//! Creative mode bypasses the protection; `/protection off` turns it off for
+//! the current save.
+
+use bevy::prelude::*;
+
+use crate::core::ItemId;
+use crate::DeliveryPlatform;
+
+/// Blocks around a platform's edge covered by the zone
+pub const PROTECTION_MARGIN: i32 = 2;
+
+/// Height of the zone above the platform surface (blocks)
+pub const PROTECTION_HEIGHT: i32 = 4;
+
+/// Toast shown when the protection refuses a placement or break
+pub const PROTECTED_MESSAGE: &str = "保護エリアです";
+
+/// Whether spawn protection is on in this save
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnProtection {
+    pub enabled: bool,
+}
+
+impl Default for SpawnProtection {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// `/protection` command
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectionCommandEvent {
+    /// Log whether the protection is on
+    Query,
+    /// Turn the protection on or off
+    Set(bool),
+}
+
+/// Protected area for one frame, built from the platform entities
+///
+/// Empty while the protection is off or in creative mode, so callers don't
+/// check either themselves.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectionZone {
+    /// Origin and side length of each protected footprint (platforms, starting furnace)
+    platforms: Vec<(IVec3, i32)>,
+}
+
+impl ProtectionZone {
+    pub fn new<'a>(
+        protection: &SpawnProtection,
+        creative: bool,
+        platforms: impl IntoIterator<Item = &'a DeliveryPlatform>,
+        initial_furnace: Option<IVec3>,
+    ) -> Self {
+        if !protection.enabled || creative {
+            return Self::default();
+        }
+        Self {
+            platforms: platforms
+                .into_iter()
+                .map(|platform| (platform.position, platform.size))
+                .chain(initial_furnace.map(|pos| (pos, 1)))
+                .collect(),
+        }
+    }
+
+    /// Whether `pos` is within PROTECTION_MARGIN of a footprint, from its surface up
+    pub fn covers(&self, pos: IVec3) -> bool {
+        self.platforms.iter().any(|&(origin, size)| {
+            (origin.x - PROTECTION_MARGIN..origin.x + size + PROTECTION_MARGIN).contains(&pos.x)
+                && (origin.z - PROTECTION_MARGIN..origin.z + size + PROTECTION_MARGIN)
+                    .contains(&pos.z)
+                && (origin.y..origin.y + PROTECTION_HEIGHT).contains(&pos.y)
+        })
+    }
+
+    /// Whether placing `item_id` at `pos` is refused (terrain blocks only)
+    pub fn blocks_placement(&self, pos: IVec3, item_id: ItemId) -> bool {
+        !item_id.is_machine() && self.covers(pos)
+    }
+
+    /// Whether breaking the world block at `pos` is refused
+    ///
+    /// Covers the pads of pad platforms and the ground under any platform
+    /// (which holds up the starter platform and its port markers) or under
+    /// the starting furnace.
+    pub fn blocks_break(&self, pos: IVec3) -> bool {
+        self.platforms.iter().any(|&(origin, size)| {
+            (origin.y - 1..=origin.y).contains(&pos.y)
+                && (origin.x..origin.x + size).contains(&pos.x)
+                && (origin.z..origin.z + size).contains(&pos.z)
+        })
+    }
+}
+
+/// Apply `/protection` commands
+pub fn handle_protection_command(
+    mut events: MessageReader<ProtectionCommandEvent>,
+    mut protection: ResMut<SpawnProtection>,
+) {
+    for event in events.read() {
+        if let ProtectionCommandEvent::Set(enabled) = *event {
+            protection.enabled = enabled;
+        }
+        info!(
+            "Spawn protection {}",
+            if protection.enabled { "on" } else { "off" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    fn starter() -> DeliveryPlatform {
+        DeliveryPlatform::new(IVec3::new(20, 8, 10))
+    }
+
+    #[test]
+    fn test_zone_follows_platform_with_margin() {
+        let platform = starter();
+        let zone = ProtectionZone::new(&SpawnProtection::default(), false, [&platform], None);
+        let size = platform.size;
+
+        assert!(zone.covers(IVec3::new(18, 8, 8)));
+        assert!(zone.covers(IVec3::new(20 + size + 1, 11, 10)));
+        assert!(!zone.covers(IVec3::new(17, 8, 10)));
+        assert!(!zone.covers(IVec3::new(20 + size + 2, 8, 10)));
+        // Below the surface and above the zone height are free
+        assert!(!zone.covers(IVec3::new(22, 7, 12)));
+        assert!(!zone.covers(IVec3::new(22, 8 + PROTECTION_HEIGHT, 12)));
+
+        // A moved platform moves the zone
+        let moved = DeliveryPlatform::new(IVec3::new(-40, 30, 0));
+        let zone = ProtectionZone::new(&SpawnProtection::default(), false, [&moved], None);
+        assert!(!zone.covers(IVec3::new(20, 8, 10)));
+        assert!(zone.covers(IVec3::new(-41, 30, 1)));
+    }
+
+    #[test]
+    fn test_terrain_refused_machines_allowed() {
+        let platform = starter();
+        let zone = ProtectionZone::new(&SpawnProtection::default(), false, [&platform], None);
+        let edge = IVec3::new(19, 8, 12);
+
+        assert!(zone.blocks_placement(edge, items::stone()));
+        assert!(!zone.blocks_placement(edge, items::conveyor_block()));
+        assert!(!zone.blocks_placement(edge, items::furnace_block()));
+        assert!(!zone.blocks_placement(IVec3::new(10, 8, 12), items::stone()));
+    }
+
+    #[test]
+    fn test_platform_ground_and_pads_unbreakable() {
+        let platform = starter();
+        let pads = crate::logistics::delivery_pad::pad_platform(IVec3::new(0, 8, 0)).0;
+        let zone =
+            ProtectionZone::new(&SpawnProtection::default(), false, [&platform, &pads], None);
+
+        // Ground under the starter platform (and its port markers)
+        assert!(zone.blocks_break(IVec3::new(21, 7, 10)));
+        // Pads of a pad platform
+        assert!(zone.blocks_break(IVec3::new(2, 8, 2)));
+        // Terrain in the margin and deeper down can still be mined
+        assert!(!zone.blocks_break(IVec3::new(19, 7, 10)));
+        assert!(!zone.blocks_break(IVec3::new(21, 6, 10)));
+    }
+
+    #[test]
+    fn test_initial_furnace_protected_with_margin() {
+        let furnace = IVec3::new(-10, 8, -10);
+        let zone = ProtectionZone::new(
+            &SpawnProtection::default(),
+            false,
+            [&starter()],
+            Some(furnace),
+        );
+
+        // Can't be walled in...
+        assert!(zone.blocks_placement(furnace + IVec3::X * PROTECTION_MARGIN, items::stone()));
+        assert!(zone.blocks_placement(furnace + IVec3::new(0, 1, -1), items::stone()));
+        assert!(
+            !zone.blocks_placement(furnace + IVec3::X * (PROTECTION_MARGIN + 1), items::stone())
+        );
+        // ...but belts can still be run up to it
+        assert!(!zone.blocks_placement(furnace + IVec3::X, items::conveyor_block()));
+
+        // The block it stands on stays put
+        assert!(zone.blocks_break(furnace - IVec3::Y));
+        assert!(!zone.blocks_break(furnace - IVec3::Y + IVec3::X));
+
+        // Creative mode ignores it like the platforms
+        let creative = ProtectionZone::new(
+            &SpawnProtection::default(),
+            true,
+            [&starter()],
+            Some(furnace),
+        );
+        assert!(!creative.covers(furnace + IVec3::X));
+    }
+
+    #[test]
+    fn test_creative_and_off_bypass() {
+        let platform = starter();
+        let on = SpawnProtection::default();
+        let off = SpawnProtection { enabled: false };
+        let inside = IVec3::new(21, 8, 11);
+
+        for zone in [
+            ProtectionZone::new(&on, true, [&platform], None),
+            ProtectionZone::new(&off, false, [&platform], None),
+        ] {
+            assert!(!zone.blocks_placement(inside, items::stone()));
+            assert!(!zone.blocks_break(inside - IVec3::Y));
+        }
+    }
+
+    #[test]
+    fn test_protection_command() {
+        let mut app = App::new();
+        app.init_resource::<SpawnProtection>()
+            .add_message::<ProtectionCommandEvent>()
+            .add_systems(Update, handle_protection_command);
+
+        app.world_mut()
+            .write_message(ProtectionCommandEvent::Set(false));
+        app.update();
+        assert!(!app.world().resource::<SpawnProtection>().enabled);
+
+        app.world_mut().write_message(ProtectionCommandEvent::Query);
+        app.update();
+        assert!(!app.world().resource::<SpawnProtection>().enabled);
+    }
+}
@@ -0,0 +1,399 @@
+//! Placement validation shared by block_place and the placement preview
+
+use bevy::prelude::*;
+
+use crate::core::{items, ItemId};
+use crate::world::WorldData;
+use crate::Direction;
+
+/// Depth of the column below a miner searched for ore (blocks)
+pub const MINER_ORE_DEPTH: i32 = 3;
+
+/// Reason a placement would be rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlacementError {
+    /// A world block already occupies the position
+    OccupiedByBlock,
+    /// A conveyor already occupies the position
+    OccupiedByConveyor,
+    /// A machine already occupies the position
+    OccupiedByMachine,
+    /// Machines can't go in the space beneath an elevated conveyor
+    UnderConveyor,
+    /// Machines need a solid block (or the platform) directly beneath
+    NoSupport,
+    /// The player has none of the selected item (survival only)
+    NotInInventory,
+    /// Terrain block inside the spawn protection zone
+    Protected,
+}
+
+/// Likely mistake in an otherwise valid placement (the block is still placed)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlacementWarning {
+    /// Miner with no ore in the MINER_ORE_DEPTH blocks beneath
+    NoOreBelow,
+    /// Furnace or crusher with every side and the top walled in
+    NoAccess,
+    /// Conveyor pointing straight into a world block
+    BeltBlocked,
+}
+
+impl PlacementWarning {
+    /// Toast text
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::NoOreBelow => "下に鉱石がありません",
+            Self::NoAccess => "コンベアをつなげる面がありません",
+            Self::BeltBlocked => "ベルトの先がふさがっています",
+        }
+    }
+}
+
+/// Snapshot of everything needed to decide whether an item can be placed
+pub struct PlacementCheck<'a> {
+    pub world: &'a WorldData,
+    /// Whether a conveyor occupies a position (queried per cell, never collected)
+    pub conveyor_at: &'a dyn Fn(IVec3) -> bool,
+    /// Whether a machine occupies a position
+    pub machine_at: &'a dyn Fn(IVec3) -> bool,
+    /// Positions that count as support besides world blocks (delivery platform surface)
+    pub extra_support: &'a dyn Fn(IVec3) -> bool,
+    /// Whether spawn protection refuses the item at a position (see `ProtectionZone`)
+    pub protected: &'a dyn Fn(IVec3, ItemId) -> bool,
+    /// How many of the item the player has
+    pub inventory_count: u32,
+    pub creative: bool,
+}
+
+impl PlacementCheck<'_> {
+    /// Validate placing `item_id` at `pos`
+    pub fn validate(&self, pos: IVec3, item_id: ItemId) -> Result<(), PlacementError> {
+        if self.world.has_block(pos) {
+            return Err(PlacementError::OccupiedByBlock);
+        }
+        if (self.conveyor_at)(pos) {
+            return Err(PlacementError::OccupiedByConveyor);
+        }
+        if (self.machine_at)(pos) {
+            return Err(PlacementError::OccupiedByMachine);
+        }
+        if (self.protected)(pos, item_id) {
+            return Err(PlacementError::Protected);
+        }
+        if requires_support(item_id) && (self.conveyor_at)(pos + IVec3::Y) {
+            return Err(PlacementError::UnderConveyor);
+        }
+        if requires_support(item_id) {
+            let below = pos - IVec3::Y;
+            if !self.world.has_block(below) && !(self.extra_support)(below) {
+                return Err(PlacementError::NoSupport);
+            }
+        }
+        if !self.creative && self.inventory_count == 0 {
+            return Err(PlacementError::NotInInventory);
+        }
+        Ok(())
+    }
+
+    /// Heuristic warning for placing `item_id` at `pos` facing `facing`
+    ///
+    /// Only meaningful for placements `validate` accepts.
+    pub fn warning(
+        &self,
+        pos: IVec3,
+        item_id: ItemId,
+        facing: Direction,
+    ) -> Option<PlacementWarning> {
+        if item_id == items::miner_block() {
+            let ore_below = (1..=MINER_ORE_DEPTH).any(|depth| {
+                self.world
+                    .get_block(pos - IVec3::Y * depth)
+                    .is_some_and(items::is_ore)
+            });
+            return (!ore_below).then_some(PlacementWarning::NoOreBelow);
+        }
+        if item_id == items::furnace_block() || item_id == items::crusher_block() {
+            // Belts and the player reach a machine from the sides or the top
+            let open = [
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West,
+            ]
+            .iter()
+            .map(|side| pos + side.to_ivec3())
+            .chain([pos + IVec3::Y])
+            .any(|p| !self.world.has_block(p) && !(self.machine_at)(p));
+            return (!open).then_some(PlacementWarning::NoAccess);
+        }
+        if items::is_conveyor(item_id) && self.world.has_block(pos + facing.to_ivec3()) {
+            return Some(PlacementWarning::BeltBlocked);
+        }
+        None
+    }
+}
+
+/// Machines (except conveyors and pipes) must stand on something solid
+pub fn requires_support(item_id: ItemId) -> bool {
+    item_id.is_machine() && !items::is_conveyor(item_id) && item_id != items::pipe_block()
+}
+
+/// Per-frame cached placement result for the current place target
+///
+/// Computed once in update_target_block and read by update_target_highlight.
+#[derive(Resource, Default)]
+pub struct PlacementPreview {
+    /// Position and item the result was computed for
+    pub target: Option<(IVec3, ItemId)>,
+    /// Why placement would fail (None = valid)
+    pub error: Option<PlacementError>,
+    /// Heuristic warning for a valid placement (None with warnings off in settings)
+    pub warning: Option<PlacementWarning>,
+}
+
+impl PlacementPreview {
+    /// Whether the cached placement would succeed
+    pub fn is_valid(&self) -> bool {
+        self.target.is_some() && self.error.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::ChunkData;
+
+    fn test_world() -> WorldData {
+        let mut world = WorldData::default();
+        world
+            .chunks
+            .insert(IVec2::ZERO, ChunkData::generate(IVec2::ZERO));
+        world
+    }
+
+    fn check<'a>(
+        world: &'a WorldData,
+        conveyors: &'a dyn Fn(IVec3) -> bool,
+        machines: &'a dyn Fn(IVec3) -> bool,
+        inventory_count: u32,
+        creative: bool,
+    ) -> PlacementCheck<'a> {
+        PlacementCheck {
+            world,
+            conveyor_at: conveyors,
+            machine_at: machines,
+            extra_support: &|_| false,
+            protected: &|_, _| false,
+            inventory_count,
+            creative,
+        }
+    }
+
+    // Surface is at y=7, so y=8 sits on the ground
+    const ON_GROUND: IVec3 = IVec3::new(3, 8, 3);
+
+    #[test]
+    fn test_valid_placement() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let c = check(&world, &empty, &empty, 1, false);
+        assert_eq!(c.validate(ON_GROUND, items::stone()), Ok(()));
+        assert_eq!(c.validate(ON_GROUND, items::furnace_block()), Ok(()));
+    }
+
+    #[test]
+    fn test_occupied_by_block() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let c = check(&world, &empty, &empty, 1, false);
+        assert_eq!(
+            c.validate(IVec3::new(3, 7, 3), items::stone()),
+            Err(PlacementError::OccupiedByBlock)
+        );
+    }
+
+    #[test]
+    fn test_occupied_by_conveyor_and_machine() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let occupied = |p: IVec3| p == ON_GROUND;
+
+        let c = check(&world, &occupied, &empty, 1, false);
+        assert_eq!(
+            c.validate(ON_GROUND, items::stone()),
+            Err(PlacementError::OccupiedByConveyor)
+        );
+
+        let c = check(&world, &empty, &occupied, 1, false);
+        assert_eq!(
+            c.validate(ON_GROUND, items::stone()),
+            Err(PlacementError::OccupiedByMachine)
+        );
+    }
+
+    #[test]
+    fn test_machine_requires_support() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let c = check(&world, &empty, &empty, 1, false);
+        let floating = IVec3::new(3, 12, 3);
+
+        assert_eq!(
+            c.validate(floating, items::miner_block()),
+            Err(PlacementError::NoSupport)
+        );
+        // Regular blocks and conveyors may float
+        assert_eq!(c.validate(floating, items::stone()), Ok(()));
+        assert_eq!(c.validate(floating, items::conveyor_block()), Ok(()));
+        assert_eq!(c.validate(floating, items::conveyor_block_mk3()), Ok(()));
+        assert_eq!(c.validate(floating, items::pipe_block()), Ok(()));
+    }
+
+    #[test]
+    fn test_machine_blocked_under_elevated_conveyor() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let elevated = |p: IVec3| p == ON_GROUND + IVec3::Y;
+        let c = check(&world, &elevated, &empty, 1, false);
+
+        assert_eq!(
+            c.validate(ON_GROUND, items::furnace_block()),
+            Err(PlacementError::UnderConveyor)
+        );
+        // Blocks and belts may still fill the space
+        assert_eq!(c.validate(ON_GROUND, items::stone()), Ok(()));
+        assert_eq!(c.validate(ON_GROUND, items::conveyor_block()), Ok(()));
+    }
+
+    #[test]
+    fn test_extra_support_counts() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let floating = IVec3::new(3, 12, 3);
+        let c = PlacementCheck {
+            world: &world,
+            conveyor_at: &empty,
+            machine_at: &empty,
+            extra_support: &|pos| pos == floating - IVec3::Y,
+            protected: &|_, _| false,
+            inventory_count: 1,
+            creative: false,
+        };
+        assert_eq!(c.validate(floating, items::crusher_block()), Ok(()));
+    }
+
+    #[test]
+    fn test_protected_zone_refuses_terrain() {
+        use super::super::protection::{ProtectionZone, SpawnProtection};
+        use crate::DeliveryPlatform;
+
+        let world = test_world();
+        let empty = |_: IVec3| false;
+        let platform = DeliveryPlatform::new(IVec3::new(5, 8, 0));
+        let protection = SpawnProtection::default();
+
+        for (creative, refused) in [(false, true), (true, false)] {
+            let zone = ProtectionZone::new(&protection, creative, [&platform], None);
+            let protected = |pos, item| zone.blocks_placement(pos, item);
+            let c = PlacementCheck {
+                protected: &protected,
+                ..check(&world, &empty, &empty, 1, creative)
+            };
+            // ON_GROUND is two blocks west of the platform edge
+            let expected = if refused {
+                Err(PlacementError::Protected)
+            } else {
+                Ok(())
+            };
+            assert_eq!(c.validate(ON_GROUND, items::stone()), expected);
+            assert_eq!(c.validate(ON_GROUND, items::conveyor_block()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_inventory_check_skipped_in_creative() {
+        let world = test_world();
+        let empty = |_: IVec3| false;
+
+        let survival = check(&world, &empty, &empty, 0, false);
+        assert_eq!(
+            survival.validate(ON_GROUND, items::stone()),
+            Err(PlacementError::NotInInventory)
+        );
+
+        let creative = check(&world, &empty, &empty, 0, true);
+        assert_eq!(creative.validate(ON_GROUND, items::stone()), Ok(()));
+    }
+
+    #[test]
+    fn test_miner_warns_without_ore_below() {
+        let mut world = test_world();
+        for depth in 1..=MINER_ORE_DEPTH {
+            world.set_block(ON_GROUND - IVec3::Y * depth, items::stone());
+        }
+        let empty = |_: IVec3| false;
+        let miner = items::miner_block();
+        assert_eq!(
+            check(&world, &empty, &empty, 1, false).warning(ON_GROUND, miner, Direction::North),
+            Some(PlacementWarning::NoOreBelow)
+        );
+
+        // Ore anywhere in the column is enough
+        world.set_block(ON_GROUND - IVec3::Y * MINER_ORE_DEPTH, items::iron_ore());
+        assert_eq!(
+            check(&world, &empty, &empty, 1, false).warning(ON_GROUND, miner, Direction::North),
+            None
+        );
+    }
+
+    #[test]
+    fn test_enclosed_furnace_warns() {
+        let mut world = test_world();
+        let empty = |_: IVec3| false;
+        for side in [IVec3::X, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z] {
+            world.set_block(ON_GROUND + side, items::stone());
+        }
+        let c = check(&world, &empty, &empty, 1, false);
+        // The top is still open
+        assert_eq!(
+            c.warning(ON_GROUND, items::furnace_block(), Direction::North),
+            None
+        );
+
+        // A machine on top closes the last face
+        let above = |p: IVec3| p == ON_GROUND + IVec3::Y;
+        let c = check(&world, &empty, &above, 1, false);
+        assert_eq!(
+            c.warning(ON_GROUND, items::crusher_block(), Direction::North),
+            Some(PlacementWarning::NoAccess)
+        );
+
+        // A conveyor beside it counts as access
+        world.remove_block(ON_GROUND + IVec3::X);
+        let beside = |p: IVec3| p == ON_GROUND + IVec3::X;
+        let c = check(&world, &beside, &above, 1, false);
+        assert_eq!(
+            c.warning(ON_GROUND, items::furnace_block(), Direction::North),
+            None
+        );
+    }
+
+    #[test]
+    fn test_conveyor_into_block_warns() {
+        let mut world = test_world();
+        let empty = |_: IVec3| false;
+        world.set_block(ON_GROUND + Direction::East.to_ivec3(), items::stone());
+        let c = check(&world, &empty, &empty, 1, false);
+        assert_eq!(
+            c.warning(ON_GROUND, items::conveyor_block(), Direction::East),
+            Some(PlacementWarning::BeltBlocked)
+        );
+        assert_eq!(
+            c.warning(ON_GROUND, items::conveyor_block_mk2(), Direction::West),
+            None
+        );
+        // Other blocks never warn
+        assert_eq!(c.warning(ON_GROUND, items::stone(), Direction::East), None);
+    }
+}
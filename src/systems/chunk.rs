@@ -1,10 +1,16 @@
 //! Chunk loading, unloading, and mesh generation systems
 
 use crate::components::Player;
+use crate::constants::CHUNK_SECTIONS;
+use crate::events::game_events::{BlockBroken, BlockPlaced};
 use crate::graphics::VoxelMaterial;
 use crate::settings::GameSettings;
 use crate::vox_loader::VoxelArrayTexture;
-use crate::world::{ChunkData, ChunkLod, ChunkMesh, ChunkMeshData, ChunkMeshTasks, WorldData};
+use crate::world::{
+    edit_patch_faces, edit_patch_mesh, BlockEdit, ChunkData, ChunkLod, ChunkMesh, ChunkMeshData,
+    ChunkMeshTasks, EditPatch, PatchFace, WorldData, WorldGenConfig,
+};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
 use futures_lite::future;
@@ -18,9 +24,82 @@ fn calculate_lod(chunk_coord: IVec2, player_chunk: IVec2) -> ChunkLod {
     ChunkLod::from_distance(distance)
 }
 
+/// Spawns chunk section meshes (bundled to keep system parameter counts down)
+#[derive(SystemParam)]
+pub struct ChunkMeshSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    voxel_materials: ResMut<'w, Assets<VoxelMaterial>>,
+    array_texture: Res<'w, VoxelArrayTexture>,
+}
+
+impl ChunkMeshSpawner<'_, '_> {
+    /// Rebuild one section mesh, replacing only that section's entity
+    ///
+    /// Returns false if the chunk isn't loaded.
+    pub fn respawn_section(
+        &mut self,
+        world_data: &mut WorldData,
+        coord: IVec2,
+        section: i32,
+        lod: ChunkLod,
+    ) -> bool {
+        let Some(new_mesh) = world_data.generate_section_mesh_with_lod(coord, section, lod) else {
+            return false;
+        };
+        let mesh_handle = self.meshes.add(new_mesh);
+        let material = self.voxel_materials.add(VoxelMaterial {
+            array_texture: self.array_texture.texture.clone(),
+        });
+
+        let sections = world_data.chunk_entities.entry(coord).or_default();
+        if let Some(old_entity) = sections.remove(&section) {
+            self.commands.entity(old_entity).try_despawn();
+        }
+        let entity = self
+            .commands
+            .spawn((
+                Mesh3d(mesh_handle),
+                MeshMaterial3d(material),
+                Transform::IDENTITY,
+                ChunkMesh {
+                    coord,
+                    section,
+                    lod,
+                },
+            ))
+            .id();
+        sections.insert(section, entity);
+        true
+    }
+
+    /// Spawn a stand-in mesh for a block edit, kept until `sections` are rebuilt
+    pub fn spawn_edit_patch(&mut self, faces: &[PatchFace], sections: Vec<(IVec2, i32)>) {
+        let mesh_handle = self.meshes.add(edit_patch_mesh(faces));
+        let material = self.voxel_materials.add(VoxelMaterial {
+            array_texture: self.array_texture.texture.clone(),
+        });
+        self.commands.spawn((
+            Mesh3d(mesh_handle),
+            MeshMaterial3d(material),
+            Transform::IDENTITY,
+            EditPatch { sections },
+        ));
+    }
+
+    /// Rebuild every section of a chunk
+    pub fn respawn_chunk(&mut self, world_data: &mut WorldData, coord: IVec2, lod: ChunkLod) {
+        for section in 0..CHUNK_SECTIONS {
+            if !self.respawn_section(world_data, coord, section, lod) {
+                return;
+            }
+        }
+    }
+}
+
 /// Generate chunk data synchronously
-fn generate_chunk_sync(chunk_coord: IVec2) -> ChunkMeshData {
-    let chunk_data = ChunkData::generate(chunk_coord);
+fn generate_chunk_sync(chunk_coord: IVec2, config: &WorldGenConfig) -> ChunkMeshData {
+    let chunk_data = ChunkData::generate_with(chunk_coord, config);
     let mesh = chunk_data.generate_mesh(chunk_coord);
 
     // Convert flat array to world positions HashMap for ChunkMeshData
@@ -68,8 +147,9 @@ pub fn spawn_chunk_tasks(
 
             let chunk_coord = IVec2::new(player_chunk.x + dx, player_chunk.y + dz);
 
-            // Skip if already loaded or being generated
-            if world_data.chunks.contains_key(&chunk_coord)
+            // Skip if outside the world border, already loaded or being generated
+            if !world_data.gen_config.border.contains_chunk(chunk_coord)
+                || world_data.chunks.contains_key(&chunk_coord)
                 || tasks.pending.contains_key(&chunk_coord)
             {
                 continue;
@@ -77,7 +157,8 @@ pub fn spawn_chunk_tasks(
 
             // Spawn async task
             let task_pool = AsyncComputeTaskPool::get();
-            let task = task_pool.spawn(async move { generate_chunk_sync(chunk_coord) });
+            let config = world_data.gen_config.clone();
+            let task = task_pool.spawn(async move { generate_chunk_sync(chunk_coord, &config) });
             tasks.pending.insert(chunk_coord, PendingChunk::Task(task));
 
             spawned += 1;
@@ -87,13 +168,10 @@ pub fn spawn_chunk_tasks(
 
 /// Receive completed chunk meshes and spawn them
 pub fn receive_chunk_meshes(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut voxel_materials: ResMut<Assets<VoxelMaterial>>,
+    mut spawner: ChunkMeshSpawner,
     mut world_data: ResMut<WorldData>,
     mut tasks: ResMut<ChunkMeshTasks>,
     player_query: Query<&Transform, With<Player>>,
-    array_texture: Res<VoxelArrayTexture>,
 ) {
     // Get player chunk for LOD calculation
     let player_chunk = player_query
@@ -179,32 +257,9 @@ pub fn receive_chunk_meshes(
         // Calculate LOD based on distance from player
         let lod = calculate_lod(coord, player_chunk);
 
-        // Regenerate this chunk's mesh with neighbor awareness and LOD
-        if let Some(new_mesh) = world_data.generate_chunk_mesh_with_lod(coord, lod) {
-            let mesh_handle = meshes.add(new_mesh);
-            let material = voxel_materials.add(VoxelMaterial {
-                array_texture: array_texture.texture.clone(),
-            });
-
-            // Find and despawn old mesh entity if exists
-            if let Some(entities) = world_data.chunk_entities.remove(&coord) {
-                for entity in entities {
-                    commands.entity(entity).try_despawn();
-                }
-            }
-
-            let entity = commands
-                .spawn((
-                    Mesh3d(mesh_handle),
-                    MeshMaterial3d(material),
-                    Transform::IDENTITY,
-                    ChunkMesh { coord, lod },
-                ))
-                .id();
-
-            world_data.chunk_entities.insert(coord, vec![entity]);
-            tracing::trace!("Chunk {:?} mesh spawned with LOD {:?}", coord, lod);
-        }
+        // Regenerate this chunk's section meshes with neighbor awareness and LOD
+        spawner.respawn_chunk(&mut world_data, coord, lod);
+        tracing::trace!("Chunk {:?} mesh spawned with LOD {:?}", coord, lod);
 
         // Also regenerate neighboring chunks' meshes
         let neighbors = [
@@ -223,36 +278,7 @@ pub fn receive_chunk_meshes(
             }
 
             let neighbor_lod = calculate_lod(neighbor_coord, player_chunk);
-            if let Some(new_mesh) =
-                world_data.generate_chunk_mesh_with_lod(neighbor_coord, neighbor_lod)
-            {
-                let mesh_handle = meshes.add(new_mesh);
-                let material = voxel_materials.add(VoxelMaterial {
-                    array_texture: array_texture.texture.clone(),
-                });
-
-                if let Some(entities) = world_data.chunk_entities.remove(&neighbor_coord) {
-                    for entity in entities {
-                        commands.entity(entity).try_despawn();
-                    }
-                }
-
-                let entity = commands
-                    .spawn((
-                        Mesh3d(mesh_handle),
-                        MeshMaterial3d(material),
-                        Transform::IDENTITY,
-                        ChunkMesh {
-                            coord: neighbor_coord,
-                            lod: neighbor_lod,
-                        },
-                    ))
-                    .id();
-
-                world_data
-                    .chunk_entities
-                    .insert(neighbor_coord, vec![entity]);
-            }
+            spawner.respawn_chunk(&mut world_data, neighbor_coord, neighbor_lod);
         }
     }
 }
@@ -263,7 +289,6 @@ pub fn unload_distant_chunks(
     mut world_data: ResMut<WorldData>,
     mut tasks: ResMut<ChunkMeshTasks>,
     player_query: Query<&Transform, With<Player>>,
-    chunk_mesh_query: Query<(Entity, &ChunkMesh)>,
     settings: Res<GameSettings>,
 ) {
     let Ok(player_transform) = player_query.single() else {
@@ -288,14 +313,13 @@ pub fn unload_distant_chunks(
 
     // Unload chunks
     for chunk_coord in chunks_to_unload {
-        for (entity, chunk_mesh) in chunk_mesh_query.iter() {
-            if chunk_mesh.coord == chunk_coord {
-                commands.entity(entity).despawn();
+        if let Some(sections) = world_data.chunk_entities.remove(&chunk_coord) {
+            for entity in sections.into_values() {
+                commands.entity(entity).try_despawn();
             }
         }
 
         world_data.chunks.remove(&chunk_coord);
-        world_data.chunk_entities.remove(&chunk_coord);
         tasks.pending.remove(&chunk_coord);
     }
 }
@@ -304,16 +328,13 @@ pub fn unload_distant_chunks(
 pub use crate::world::DirtyChunks;
 pub use crate::world::PendingChunk;
 
-/// Update LOD for chunks based on player distance
-/// Regenerates mesh if LOD level should change
+/// Update LOD for chunk sections based on player distance
+/// Regenerates a section mesh if its LOD level should change
 pub fn update_chunk_lod(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut voxel_materials: ResMut<Assets<VoxelMaterial>>,
+    mut spawner: ChunkMeshSpawner,
     mut world_data: ResMut<WorldData>,
     player_query: Query<&Transform, With<Player>>,
-    chunk_mesh_query: Query<(Entity, &ChunkMesh)>,
-    array_texture: Res<VoxelArrayTexture>,
+    chunk_mesh_query: Query<&ChunkMesh>,
 ) {
     let Ok(player_transform) = player_query.single() else {
         return;
@@ -322,11 +343,11 @@ pub fn update_chunk_lod(
     let player_grid = crate::world_to_grid(player_transform.translation);
     let player_chunk = WorldData::world_to_chunk(IVec3::new(player_grid.x, 0, player_grid.z));
 
-    // Limit LOD updates per frame to avoid frame spikes
-    const MAX_LOD_UPDATES_PER_FRAME: usize = 2;
+    // Limit LOD updates per frame to avoid frame spikes (counted in sections)
+    const MAX_LOD_UPDATES_PER_FRAME: usize = 2 * CHUNK_SECTIONS as usize;
     let mut updates = 0;
 
-    for (entity, chunk_mesh) in chunk_mesh_query.iter() {
+    for chunk_mesh in chunk_mesh_query.iter() {
         if updates >= MAX_LOD_UPDATES_PER_FRAME {
             break;
         }
@@ -338,42 +359,17 @@ pub fn update_chunk_lod(
             continue;
         }
 
-        // Check if chunk still exists
-        if !world_data.chunks.contains_key(&chunk_mesh.coord) {
-            continue;
-        }
-
-        // Regenerate mesh with new LOD
-        if let Some(new_mesh) = world_data.generate_chunk_mesh_with_lod(chunk_mesh.coord, new_lod) {
-            // Despawn old entity
-            commands.entity(entity).despawn();
-
-            // Remove from chunk_entities
-            world_data.chunk_entities.remove(&chunk_mesh.coord);
-
-            let mesh_handle = meshes.add(new_mesh);
-            let material = voxel_materials.add(VoxelMaterial {
-                array_texture: array_texture.texture.clone(),
-            });
-
-            let new_entity = commands
-                .spawn((
-                    Mesh3d(mesh_handle),
-                    MeshMaterial3d(material),
-                    Transform::IDENTITY,
-                    ChunkMesh {
-                        coord: chunk_mesh.coord,
-                        lod: new_lod,
-                    },
-                ))
-                .id();
-
-            world_data
-                .chunk_entities
-                .insert(chunk_mesh.coord, vec![new_entity]);
+        // Regenerate mesh with new LOD (skipped if the chunk is gone)
+        if spawner.respawn_section(
+            &mut world_data,
+            chunk_mesh.coord,
+            chunk_mesh.section,
+            new_lod,
+        ) {
             tracing::debug!(
-                "Chunk {:?} LOD updated: {:?} -> {:?}",
+                "Chunk {:?} section {} LOD updated: {:?} -> {:?}",
                 chunk_mesh.coord,
+                chunk_mesh.section,
                 chunk_mesh.lod,
                 new_lod
             );
@@ -382,16 +378,16 @@ pub fn update_chunk_lod(
     }
 }
 
-/// Process dirty chunks - regenerate meshes for chunks that had block changes
+/// Process dirty chunk sections - regenerate meshes for sections that had block changes
 /// Limits regeneration to MAX_DIRTY_PER_FRAME to avoid frame spikes
+///
+/// Only the sections touching the edited block are rebuilt; the other
+/// section entities of the chunk are left alone.
 pub fn process_dirty_chunks(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut voxel_materials: ResMut<Assets<VoxelMaterial>>,
+    mut spawner: ChunkMeshSpawner,
     mut world_data: ResMut<WorldData>,
     mut dirty_chunks: ResMut<DirtyChunks>,
     player_query: Query<&Transform, With<Player>>,
-    array_texture: Res<VoxelArrayTexture>,
 ) {
     if dirty_chunks.is_empty() {
         return;
@@ -406,61 +402,285 @@ pub fn process_dirty_chunks(
         })
         .unwrap_or(IVec2::ZERO);
 
-    // Limit chunks processed per frame to avoid frame spikes
-    const MAX_DIRTY_PER_FRAME: usize = 4;
+    // Limit sections processed per frame to avoid frame spikes
+    const MAX_DIRTY_PER_FRAME: usize = 8;
 
-    let all_dirty = dirty_chunks.take_all();
+    let mut all_dirty = dirty_chunks.take_all().into_iter();
     let mut processed_count = 0;
 
-    for coord in all_dirty.into_iter() {
-        // Skip if chunk doesn't exist (unloaded)
-        if !world_data.chunks.contains_key(&coord) {
-            continue;
-        }
-
+    for (coord, section) in all_dirty.by_ref() {
         // Calculate LOD for this chunk
         let lod = calculate_lod(coord, player_chunk);
 
-        // Regenerate mesh with LOD
-        if let Some(new_mesh) = world_data.generate_chunk_mesh_with_lod(coord, lod) {
-            // Remove old mesh entity
-            if let Some(old_entities) = world_data.chunk_entities.remove(&coord) {
-                for entity in old_entities {
-                    commands.entity(entity).try_despawn();
-                }
-            }
-
-            let mesh_handle = meshes.add(new_mesh);
-            let material = voxel_materials.add(VoxelMaterial {
-                array_texture: array_texture.texture.clone(),
-            });
-
-            let entity = commands
-                .spawn((
-                    Mesh3d(mesh_handle),
-                    MeshMaterial3d(material),
-                    Transform::IDENTITY,
-                    ChunkMesh { coord, lod },
-                ))
-                .id();
-
-            world_data.chunk_entities.insert(coord, vec![entity]);
-            tracing::trace!(
-                "Dirty chunk {:?} mesh regenerated with LOD {:?}",
-                coord,
-                lod
-            );
+        // Skip if chunk doesn't exist (unloaded)
+        if !spawner.respawn_section(&mut world_data, coord, section, lod) {
+            continue;
         }
+        tracing::trace!(
+            "Dirty chunk {:?} section {} mesh regenerated with LOD {:?}",
+            coord,
+            section,
+            lod
+        );
 
         processed_count += 1;
         if processed_count >= MAX_DIRTY_PER_FRAME {
-            // Re-add remaining dirty chunks for next frame
-            // (Not needed since we took all - remaining are processed next frame)
             break;
         }
     }
 
+    // Re-add remaining dirty sections for next frame
+    dirty_chunks.chunks.extend(all_dirty);
+
     if processed_count > 0 {
-        tracing::debug!("Processed {} dirty chunks this frame", processed_count);
+        tracing::debug!("Processed {} dirty sections this frame", processed_count);
+    }
+}
+
+/// Show this frame's block edits right away with patch meshes
+///
+/// The dirty sections may only be rebuilt a few frames later; each patch
+/// covers its edit until then (see `clear_edit_patches`).
+pub fn spawn_edit_patches(
+    mut spawner: ChunkMeshSpawner,
+    world_data: Res<WorldData>,
+    mut placed_events: MessageReader<BlockPlaced>,
+    mut broken_events: MessageReader<BlockBroken>,
+) {
+    let placed = placed_events
+        .read()
+        .map(|event| (event.pos, BlockEdit::Placed(event.block)));
+    let broken = broken_events
+        .read()
+        .map(|event| (event.pos, BlockEdit::Removed));
+
+    for (pos, edit) in placed.chain(broken) {
+        // Machines aren't part of the chunk mesh; a later edit may also have undone this one
+        let current = world_data.get_block(pos);
+        let applies = match edit {
+            BlockEdit::Placed(block) => current == Some(block),
+            BlockEdit::Removed => current.is_none(),
+        };
+        if !applies {
+            continue;
+        }
+
+        let faces = edit_patch_faces(pos, edit, |p| world_data.get_block(p));
+        if faces.is_empty() {
+            continue;
+        }
+
+        // Same sections the edit marked dirty
+        let mut sections = DirtyChunks::default();
+        sections.mark_dirty(
+            WorldData::world_to_chunk(pos),
+            WorldData::world_to_local(pos),
+        );
+        spawner.spawn_edit_patch(&faces, sections.take_all().into_iter().collect());
+    }
+}
+
+/// Despawn patches whose sections have all been rebuilt (or dropped with their chunk)
+pub fn clear_edit_patches(
+    mut commands: Commands,
+    dirty_chunks: Res<DirtyChunks>,
+    patch_query: Query<(Entity, &EditPatch)>,
+) {
+    for (entity, patch) in patch_query.iter() {
+        if !patch
+            .sections
+            .iter()
+            .any(|section| dirty_chunks.chunks.contains(section))
+        {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load a row of chunks out to x = 6 and return which ones survive unloading
+    fn loaded_after_unload(view_distance: i32) -> Vec<i32> {
+        let mut app = App::new();
+        app.init_resource::<WorldData>()
+            .init_resource::<ChunkMeshTasks>()
+            .insert_resource(GameSettings {
+                view_distance,
+                ..Default::default()
+            })
+            .add_systems(Update, unload_distant_chunks);
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(8.0, 8.0, 8.0)));
+
+        let mut world_data = app.world_mut().resource_mut::<WorldData>();
+        for x in 0..=6 {
+            world_data.ensure_chunk_loaded(IVec2::new(x, 0));
+        }
+
+        app.update();
+
+        let mut loaded: Vec<i32> = app
+            .world()
+            .resource::<WorldData>()
+            .chunks
+            .keys()
+            .map(|coord| coord.x)
+            .collect();
+        loaded.sort();
+        loaded
+    }
+
+    #[test]
+    fn test_view_distance_changes_unloaded_chunks() {
+        // Chunks stay loaded up to view_distance + 1 from the player's chunk
+        assert_eq!(loaded_after_unload(1), vec![0, 1, 2]);
+        assert_eq!(loaded_after_unload(4), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    /// App that rebuilds dirty sections, with the chunk at the origin loaded
+    fn remesh_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<WorldData>()
+            .init_resource::<DirtyChunks>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<VoxelMaterial>>()
+            .init_resource::<VoxelArrayTexture>()
+            .add_systems(Update, process_dirty_chunks);
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .ensure_chunk_loaded(IVec2::ZERO);
+        app
+    }
+
+    fn section_entities(app: &App) -> HashMap<i32, Entity> {
+        app.world().resource::<WorldData>().chunk_entities[&IVec2::ZERO].clone()
+    }
+
+    #[test]
+    fn test_block_edit_only_rebuilds_its_section() {
+        let mut app = remesh_app();
+        {
+            let mut dirty = app.world_mut().resource_mut::<DirtyChunks>();
+            for section in 0..CHUNK_SECTIONS {
+                dirty.chunks.insert((IVec2::ZERO, section));
+            }
+        }
+        app.update();
+        let before = section_entities(&app);
+        assert_eq!(before.len(), CHUNK_SECTIONS as usize);
+
+        // Dig out a block at y = 5 (section 0)
+        let pos = IVec3::new(3, 5, 3);
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .remove_block(pos);
+        app.world_mut()
+            .resource_mut::<DirtyChunks>()
+            .mark_dirty(IVec2::ZERO, WorldData::world_to_local(pos));
+        app.update();
+
+        let after = section_entities(&app);
+        assert_ne!(after[&0], before[&0]);
+        assert!(app.world().get_entity(before[&0]).is_err());
+        // The section above (y 16..32) keeps its entity
+        assert_eq!(after[&1], before[&1]);
+        assert!(app.world().get_entity(before[&1]).is_ok());
+    }
+
+    /// App that spawns and clears edit patches, optionally rebuilding dirty sections
+    fn patch_app(rebuild: bool) -> App {
+        let mut app = App::new();
+        app.init_resource::<WorldData>()
+            .init_resource::<DirtyChunks>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<VoxelMaterial>>()
+            .init_resource::<VoxelArrayTexture>()
+            .add_message::<BlockPlaced>()
+            .add_message::<BlockBroken>();
+        if rebuild {
+            app.add_systems(
+                Update,
+                (spawn_edit_patches, process_dirty_chunks, clear_edit_patches).chain(),
+            );
+        } else {
+            app.add_systems(Update, (spawn_edit_patches, clear_edit_patches).chain());
+        }
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .ensure_chunk_loaded(IVec2::ZERO);
+        app
+    }
+
+    fn dig(app: &mut App, pos: IVec3) {
+        let mut world_data = app.world_mut().resource_mut::<WorldData>();
+        let block = world_data.get_block(pos).expect("solid block");
+        world_data.remove_block(pos);
+        app.world_mut().resource_mut::<DirtyChunks>().mark_dirty(
+            WorldData::world_to_chunk(pos),
+            WorldData::world_to_local(pos),
+        );
+        app.world_mut().write_message(BlockBroken {
+            pos,
+            block,
+            source: crate::events::game_events::EventSource::System,
+        });
+    }
+
+    fn patch_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&EditPatch>()
+            .iter(app.world())
+            .count()
+    }
+
+    #[test]
+    fn test_edit_patches_wait_for_rebuild() {
+        let mut app = patch_app(false);
+
+        // Rapid edits in one chunk: one patch each, kept while the section is dirty
+        dig(&mut app, IVec3::new(3, 5, 3));
+        dig(&mut app, IVec3::new(4, 5, 3));
+        app.update();
+        assert_eq!(patch_count(&mut app), 2);
+        app.update();
+        assert_eq!(patch_count(&mut app), 2);
+
+        // The rebuild clears both, and nothing is left behind
+        app.world_mut().resource_mut::<DirtyChunks>().take_all();
+        app.update();
+        assert_eq!(patch_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_edit_patch_cleared_by_section_rebuild() {
+        let mut app = patch_app(true);
+        dig(&mut app, IVec3::new(3, 5, 3));
+        // The section is rebuilt in the same frame, so the patch goes with it
+        app.update();
+        assert_eq!(patch_count(&mut app), 0);
+        assert!(app.world().resource::<DirtyChunks>().is_empty());
+        assert!(section_entities(&app).contains_key(&0));
+    }
+
+    #[test]
+    fn test_section_boundary_marks_both_sections() {
+        let dirty_after = |y: i32| {
+            let mut dirty = DirtyChunks::default();
+            dirty.mark_dirty(IVec2::ZERO, IVec3::new(5, y, 5));
+            let mut sections: Vec<i32> = dirty.take_all().into_iter().map(|(_, s)| s).collect();
+            sections.sort();
+            sections
+        };
+
+        assert_eq!(dirty_after(5), vec![0]);
+        assert_eq!(dirty_after(20), vec![1]);
+        // Faces between sections belong to both meshes
+        assert_eq!(dirty_after(15), vec![0, 1]);
+        assert_eq!(dirty_after(16), vec![0, 1]);
+        // No section outside the chunk
+        assert_eq!(dirty_after(0), vec![0]);
+        assert_eq!(dirty_after(31), vec![1]);
     }
 }
@@ -2,34 +2,35 @@
 //!
 //! Parses and executes slash commands like /creative, /give, /tp, etc.
 
+use crate::blueprint::{LayoutCommandEvent, RegionShareCommandEvent};
+use crate::cinematic::CameraCommandEvent;
 use crate::components::{CreativeMode, LoadGameEvent, SaveGameEvent};
 use crate::core::{items, ItemId};
+use crate::daynight::{parse_time_value, TimeCommandEvent};
+use crate::devlink::DevLinkCommandEvent;
 use crate::events::SpawnMachineEvent;
+use crate::log_console::parse_log_command;
+use crate::logistics::TraceCommandEvent;
+use crate::machines::TickRateCommandEvent;
 use crate::player::PlayerInventory;
-use crate::utils::parse_item_name;
+use crate::respawn::RespawnCommandEvent;
+use crate::systems::ProtectionCommandEvent;
+use crate::utils::{find_item_by_name, parse_item_name};
+use crate::waypoint::{parse_waypoint_command, WaypointCommandEvent};
 use bevy::prelude::*;
 use tracing::info;
 
 use super::{
-    AssertMachineEvent, DebugEvent, DebugEventType, LookEvent, MachineAssertType, ScreenshotEvent,
-    SetBlockEvent, TeleportEvent,
+    AssertMachineEvent, CommandEvents, DebugEvent, DebugEventType, LookEvent, MachineAssertType,
+    ScreenshotEvent, SetBlockEvent, TeleportEvent,
 };
 
 /// Execute a command
-#[allow(clippy::too_many_arguments)]
 pub fn execute_command(
     command: &str,
     creative_mode: &mut ResMut<CreativeMode>,
     inventory: &mut Mut<PlayerInventory>,
-    save_events: &mut MessageWriter<SaveGameEvent>,
-    load_events: &mut MessageWriter<LoadGameEvent>,
-    tp_events: &mut MessageWriter<TeleportEvent>,
-    look_events: &mut MessageWriter<LookEvent>,
-    setblock_events: &mut MessageWriter<SetBlockEvent>,
-    spawn_machine_events: &mut MessageWriter<SpawnMachineEvent>,
-    debug_events: &mut MessageWriter<DebugEvent>,
-    assert_machine_events: &mut MessageWriter<AssertMachineEvent>,
-    screenshot_events: &mut MessageWriter<ScreenshotEvent>,
+    events: &mut CommandEvents,
 ) {
     info!("execute_command called with: '{}'", command);
     let parts: Vec<&str> = command.split_whitespace().collect();
@@ -75,7 +76,7 @@ pub fn execute_command(
                 tracing::error!("Invalid filename: path traversal not allowed");
                 return;
             }
-            save_events.write(SaveGameEvent { filename });
+            events.save.write(SaveGameEvent { filename });
         }
         "/load" | "load" => {
             // /load [filename]
@@ -85,14 +86,19 @@ pub fn execute_command(
                 tracing::error!("Invalid filename: path traversal not allowed");
                 return;
             }
-            load_events.write(LoadGameEvent { filename });
+            events.load.write(LoadGameEvent { filename });
         }
         "/help" | "help" => {
-            info!("Commands: /creative, /survival, /give <item> [count], /clear, /save [name], /load [name], /tp x y z, /look pitch yaw, /setblock x y z type");
+            info!("Commands: /creative, /survival, /give <item> [count], /clear, /save [name], /load [name], /tp x y z, /tp <waypoint>, /waypoint [add|remove] <name> | list, /log export, /look pitch yaw, /setblock x y z type, /time [set|add] <value>, /skip-night, /tickrate [speed], /camera [keyframe add|clear | play <secs> | save|load <name>], /spawn, /setspawn, /protection [on|off], /worldgen dump, /recipes conflicts, /cost <item> [count], /stalled, /trace, /export layout <name> x1 y1 z1 x2 y2 z2, /import layout <name>, /debug copy-region [x1 y1 z1 x2 y2 z2], /debug paste-region [string], /devlink [on|off]");
         }
         "/tp" | "tp" => {
             // /tp x y z - Teleport player
-            if parts.len() >= 4 {
+            // /tp <name> - Go to a waypoint (survival: point the compass at it)
+            if parts.len() == 2 {
+                events
+                    .waypoint
+                    .write(WaypointCommandEvent::Goto(parts[1].to_string()));
+            } else if parts.len() >= 4 {
                 let x: f32 = parts[1].parse().unwrap_or(0.0);
                 let y: f32 = parts[2].parse().unwrap_or(12.0);
                 let z: f32 = parts[3].parse().unwrap_or(0.0);
@@ -101,12 +107,12 @@ pub fn execute_command(
                     tracing::error!("Invalid coordinates: NaN/Infinity not allowed");
                     return;
                 }
-                tp_events.write(TeleportEvent {
+                events.teleport.write(TeleportEvent {
                     position: Vec3::new(x, y, z),
                 });
                 info!("Teleporting to ({}, {}, {})", x, y, z);
             } else {
-                info!("Usage: /tp x y z");
+                info!("Usage: /tp x y z | /tp <waypoint>");
             }
         }
         "/look" | "look" => {
@@ -121,7 +127,7 @@ pub fn execute_command(
                 }
                 let pitch = pitch_deg.to_radians();
                 let yaw = yaw_deg.to_radians();
-                look_events.write(LookEvent { pitch, yaw });
+                events.look.write(LookEvent { pitch, yaw });
                 info!("Looking at pitch={:.1}° yaw={:.1}°", pitch_deg, yaw_deg);
             } else {
                 info!("Usage: /look pitch_deg yaw_deg");
@@ -135,7 +141,7 @@ pub fn execute_command(
                 let z: i32 = parts[3].parse().unwrap_or(0);
                 let block_name = parts[4].to_lowercase();
                 if let Some(item_id) = parse_item_name(&block_name) {
-                    setblock_events.write(SetBlockEvent {
+                    events.setblock.write(SetBlockEvent {
                         position: IVec3::new(x, y, z),
                         block_type: item_id,
                     });
@@ -154,9 +160,12 @@ pub fn execute_command(
             }
         }
         "/spawn" | "spawn" => {
+            // /spawn - Teleport to the spawn point
             // /spawn x y z machine [direction] - Spawn a machine entity (E2E testing)
             // direction: 0=North, 1=East, 2=South, 3=West (for conveyors)
-            if parts.len() >= 5 {
+            if parts.len() == 1 {
+                events.respawn.write(RespawnCommandEvent::Spawn);
+            } else if parts.len() >= 5 {
                 let x: i32 = parts[1].parse().unwrap_or(0);
                 let y: i32 = parts[2].parse().unwrap_or(0);
                 let z: i32 = parts[3].parse().unwrap_or(0);
@@ -164,7 +173,7 @@ pub fn execute_command(
                 let direction: Option<u8> = parts.get(5).and_then(|s| s.parse().ok());
 
                 if let Some(machine_id) = parse_item_name(&machine_name) {
-                    spawn_machine_events.write(SpawnMachineEvent {
+                    events.spawn_machine.write(SpawnMachineEvent {
                         position: IVec3::new(x, y, z),
                         machine_id,
                         direction,
@@ -174,9 +183,13 @@ pub fn execute_command(
                     info!("Unknown machine type: {}", machine_name);
                 }
             } else {
-                info!("Usage: /spawn x y z machine [direction]");
+                info!("Usage: /spawn | /spawn x y z machine [direction]");
             }
         }
+        "/setspawn" | "setspawn" => {
+            // /setspawn - Move the spawn point to the current position
+            events.respawn.write(RespawnCommandEvent::SetSpawn);
+        }
         "/spawn_line" | "spawn_line" => {
             // /spawn_line start_x start_z direction count [machine]
             // Spawn a line of machines for E2E testing
@@ -202,7 +215,7 @@ pub fn execute_command(
                 for i in 0..count {
                     let x = start_x + dx * i as i32;
                     let z = start_z + dz * i as i32;
-                    spawn_machine_events.write(SpawnMachineEvent {
+                    events.spawn_machine.write(SpawnMachineEvent {
                         position: IVec3::new(x, y, z),
                         machine_id,
                         direction: Some(dir),
@@ -226,21 +239,21 @@ pub fn execute_command(
                     use crate::core::items;
                     // Production line: Miner -> Conveyor x3 -> Furnace
                     // Place miner on iron ore
-                    spawn_machine_events.write(SpawnMachineEvent {
+                    events.spawn_machine.write(SpawnMachineEvent {
                         position: IVec3::new(0, 8, 0),
                         machine_id: items::miner_block(),
                         direction: None,
                     });
                     // Conveyors from miner to furnace
                     for i in 1..4 {
-                        spawn_machine_events.write(SpawnMachineEvent {
+                        events.spawn_machine.write(SpawnMachineEvent {
                             position: IVec3::new(i, 8, 0),
                             machine_id: items::conveyor_block(),
                             direction: Some(1), // East
                         });
                     }
                     // Furnace at the end
-                    spawn_machine_events.write(SpawnMachineEvent {
+                    events.spawn_machine.write(SpawnMachineEvent {
                         position: IVec3::new(4, 8, 0),
                         machine_id: items::furnace_block(),
                         direction: None,
@@ -254,7 +267,7 @@ pub fn execute_command(
                     // Stress test: 10x10 conveyor grid
                     for x in 0..10 {
                         for z in 0..10 {
-                            spawn_machine_events.write(SpawnMachineEvent {
+                            events.spawn_machine.write(SpawnMachineEvent {
                                 position: IVec3::new(x, 8, z),
                                 machine_id: items::conveyor_block(),
                                 direction: Some(1), // East
@@ -350,12 +363,12 @@ pub fn execute_command(
                     // /assert machine <type> count <min> - Check machine count
                     match parts.get(2).map(|s| s.as_ref()) {
                         Some("miner") if parts.get(3).map(|s| s.as_ref()) == Some("working") => {
-                            assert_machine_events.write(AssertMachineEvent {
+                            events.assert_machine.write(AssertMachineEvent {
                                 assert_type: MachineAssertType::MinerWorking,
                             });
                         }
                         Some("conveyor") if parts.get(3).map(|s| s.as_ref()) == Some("items") => {
-                            assert_machine_events.write(AssertMachineEvent {
+                            events.assert_machine.write(AssertMachineEvent {
                                 assert_type: MachineAssertType::ConveyorHasItems,
                             });
                         }
@@ -363,7 +376,7 @@ pub fn execute_command(
                             if let Some(block_type) = parse_item_name(machine_name) {
                                 let min_count: u32 =
                                     parts.get(4).and_then(|s| s.parse().ok()).unwrap_or(1);
-                                assert_machine_events.write(AssertMachineEvent {
+                                events.assert_machine.write(AssertMachineEvent {
                                     assert_type: MachineAssertType::MachineCount {
                                         machine: block_type,
                                         min_count,
@@ -392,19 +405,129 @@ pub fn execute_command(
             }
         }
         "/debug_conveyor" | "debug_conveyor" => {
-            debug_events.write(DebugEvent {
+            events.debug.write(DebugEvent {
                 debug_type: DebugEventType::Conveyor,
             });
             info!("Dumping conveyor debug info...");
         }
         "/debug_machine" | "debug_machine" => {
-            debug_events.write(DebugEvent {
+            events.debug.write(DebugEvent {
                 debug_type: DebugEventType::Machine,
             });
             info!("Dumping machine debug info...");
         }
+        "/stalled" | "stalled" => {
+            // /stalled - List machines that aren't working and why
+            events.debug.write(DebugEvent {
+                debug_type: DebugEventType::Stalled,
+            });
+        }
+        "/waypoint" | "waypoint" => {
+            // /waypoint add <name>, /waypoint remove <name>, /waypoint list
+            match parse_waypoint_command(&parts[1..]) {
+                Some(command) => {
+                    events.waypoint.write(command);
+                }
+                None => {
+                    info!("Usage: /waypoint add <name> | /waypoint remove <name> | /waypoint list")
+                }
+            }
+        }
+        "/log" | "log" => {
+            // /log export - Write the in-game log buffer as JSON lines
+            match parse_log_command(&parts[1..]) {
+                Some(command) => {
+                    events.log.write(command);
+                }
+                None => info!("Usage: /log export"),
+            }
+        }
+        "/trace" | "trace" => {
+            // /trace - Show where items on the targeted belt end up
+            events.trace.write(TraceCommandEvent);
+        }
+        "/worldgen" | "worldgen" => {
+            // /worldgen dump - Write the active worldgen config to the log
+            if parts.get(1) == Some(&"dump") {
+                events.debug.write(DebugEvent {
+                    debug_type: DebugEventType::WorldGen,
+                });
+            } else {
+                info!("Usage: /worldgen dump");
+            }
+        }
+        "/recipes" | "recipes" => {
+            // /recipes conflicts - List inputs several recipes tie for
+            if parts.get(1) == Some(&"conflicts") {
+                let conflicts = crate::game_spec::recipe_conflicts(crate::game_spec::all_recipes());
+                info!("=== Recipe conflicts ({}) ===", conflicts.len());
+                for conflict in &conflicts {
+                    info!("{}", conflict);
+                }
+            } else {
+                info!("Usage: /recipes conflicts");
+            }
+        }
+        "/cost" | "cost" => {
+            // /cost <item> [count] - Raw materials and machine time for count items
+            let (name, count) = match parts.last().and_then(|s| s.parse::<u64>().ok()) {
+                Some(count) if parts.len() > 2 => (parts[1..parts.len() - 1].join(" "), count),
+                _ => (parts[1..].join(" "), 1),
+            };
+            let Some(item) = find_item_by_name(&name).filter(|_| !name.is_empty()) else {
+                info!("Usage: /cost <item> [count]");
+                return;
+            };
+            let cost = crate::game_spec::material_cost(
+                crate::game_spec::all_recipes(),
+                item,
+                count,
+                |item| crate::game_spec::has_tag(item, crate::game_spec::RAW_TAG),
+            );
+            match cost {
+                Ok(cost) => {
+                    info!("=== Cost of {} x{} ===", item.display_name(), count);
+                    info!("Materials: {}", cost.materials_line());
+                    if !cost.machine_seconds.is_empty() {
+                        info!("Machine time: {}", cost.machine_line());
+                    }
+                }
+                Err(e) => info!("{}", e),
+            }
+        }
+        "/debug" | "debug" => {
+            // /debug copy-region [x1 y1 z1 x2 y2 z2], /debug paste-region [string]
+            match parts.get(1).copied() {
+                Some("copy-region") => {
+                    let coords: Vec<i32> =
+                        parts[2..].iter().filter_map(|s| s.parse().ok()).collect();
+                    let bounds = match (parts.len(), coords.len()) {
+                        (2, _) => None,
+                        (8, 6) => Some((
+                            IVec3::new(coords[0], coords[1], coords[2]),
+                            IVec3::new(coords[3], coords[4], coords[5]),
+                        )),
+                        _ => {
+                            info!("Usage: /debug copy-region [x1 y1 z1 x2 y2 z2]");
+                            return;
+                        }
+                    };
+                    events
+                        .region
+                        .write(RegionShareCommandEvent::Copy { bounds });
+                }
+                Some("paste-region") => {
+                    events.region.write(RegionShareCommandEvent::Paste {
+                        encoded: parts.get(2).map(|s| s.to_string()),
+                    });
+                }
+                _ => {
+                    info!("Usage: /debug copy-region [x1 y1 z1 x2 y2 z2] | /debug paste-region [string]");
+                }
+            }
+        }
         "/debug_connection" | "debug_connection" => {
-            debug_events.write(DebugEvent {
+            events.debug.write(DebugEvent {
                 debug_type: DebugEventType::Connection,
             });
             info!("Dumping connection debug info...");
@@ -423,11 +546,150 @@ pub fn execute_command(
                 return;
             }
 
-            screenshot_events.write(ScreenshotEvent {
+            events.screenshot.write(ScreenshotEvent {
                 filename: filename.clone(),
             });
             info!("Taking screenshot: {}.png", filename);
         }
+        "/time" | "time" => {
+            // /time [set|add] <value> - Query or change the time of day
+            match (parts.get(1).copied(), parts.get(2)) {
+                (None, _) => {
+                    events.time.write(TimeCommandEvent::Query);
+                }
+                (Some("set"), Some(value)) => {
+                    if let Some(hours) = parse_time_value(value) {
+                        events.time.write(TimeCommandEvent::Set(hours));
+                    } else {
+                        info!(
+                            "Invalid time: {} (use 0-24 or day/noon/dusk/night/midnight)",
+                            value
+                        );
+                    }
+                }
+                (Some("add"), Some(value)) => match value.parse::<f32>() {
+                    Ok(hours) if hours.is_finite() => {
+                        events.time.write(TimeCommandEvent::Add(hours));
+                    }
+                    _ => info!("Invalid hours: {}", value),
+                },
+                _ => {
+                    info!("Usage: /time [set <0-24|day|noon|dusk|night|midnight> | add <hours>]");
+                }
+            }
+        }
+        "/skip-night" | "skip-night" => {
+            events.time.write(TimeCommandEvent::SkipNight);
+        }
+        "/tickrate" | "tickrate" => {
+            // /tickrate [speed] - Query or scale the simulation speed (1 = normal)
+            match parts.get(1) {
+                None => {
+                    events.tickrate.write(TickRateCommandEvent::Query);
+                }
+                Some(value) => match value.parse::<f32>() {
+                    Ok(speed) if speed.is_finite() && speed > 0.0 => {
+                        events.tickrate.write(TickRateCommandEvent::Set(speed));
+                    }
+                    _ => info!("Usage: /tickrate [speed] (e.g. /tickrate 4 for 4x)"),
+                },
+            }
+        }
+        "/protection" | "protection" => {
+            // /protection [on|off] - Query or toggle the spawn protection (saved per world)
+            match parts.get(1).copied() {
+                None => {
+                    events.protection.write(ProtectionCommandEvent::Query);
+                }
+                Some("on") => {
+                    events.protection.write(ProtectionCommandEvent::Set(true));
+                }
+                Some("off") => {
+                    events.protection.write(ProtectionCommandEvent::Set(false));
+                }
+                Some(_) => info!("Usage: /protection [on|off]"),
+            }
+        }
+        "/devlink" | "devlink" => {
+            // /devlink [on|off] - Editor live link on 127.0.0.1 (prints the session token)
+            match parts.get(1).copied() {
+                None => {
+                    events.devlink.write(DevLinkCommandEvent::Query);
+                }
+                Some("on") => {
+                    events.devlink.write(DevLinkCommandEvent::Set(true));
+                }
+                Some("off") => {
+                    events.devlink.write(DevLinkCommandEvent::Set(false));
+                }
+                Some(_) => info!("Usage: /devlink [on|off]"),
+            }
+        }
+        "/export" | "export" | "/import" | "import" => {
+            // /export layout <name> x1 y1 z1 x2 y2 z2, /import layout <name>
+            let export = parts[0].ends_with("export");
+            let name = match (parts.get(1).copied(), parts.get(2).copied()) {
+                (Some("layout"), Some(name)) => name,
+                _ => {
+                    info!("Usage: /export layout <name> x1 y1 z1 x2 y2 z2 | /import layout <name>");
+                    return;
+                }
+            };
+            // Security: prevent path traversal
+            if name.contains('/') || name.contains('\\') || name.contains("..") {
+                tracing::error!("Invalid layout name: path traversal not allowed");
+                return;
+            }
+            if !export {
+                events.layout.write(LayoutCommandEvent::Import {
+                    name: name.to_string(),
+                });
+                return;
+            }
+            let coords: Vec<i32> = parts[3..].iter().filter_map(|s| s.parse().ok()).collect();
+            if coords.len() != 6 || parts.len() != 9 {
+                info!("Usage: /export layout <name> x1 y1 z1 x2 y2 z2");
+                return;
+            }
+            events.layout.write(LayoutCommandEvent::Export {
+                name: name.to_string(),
+                min: IVec3::new(coords[0], coords[1], coords[2]),
+                max: IVec3::new(coords[3], coords[4], coords[5]),
+            });
+        }
+        "/camera" | "camera" => {
+            // /camera - Toggle the cinematic free camera (plus keyframe path subcommands)
+            match (parts.get(1).copied(), parts.get(2).copied()) {
+                (None, _) => {
+                    events.camera.write(CameraCommandEvent::Toggle);
+                }
+                (Some("keyframe"), Some("add")) => {
+                    events.camera.write(CameraCommandEvent::KeyframeAdd);
+                }
+                (Some("keyframe"), Some("clear")) => {
+                    events.camera.write(CameraCommandEvent::KeyframeClear);
+                }
+                (Some("play"), Some(value)) => match value.parse::<f32>() {
+                    Ok(secs) if secs.is_finite() && secs > 0.0 => {
+                        events.camera.write(CameraCommandEvent::Play(secs));
+                    }
+                    _ => info!("Invalid duration: {}", value),
+                },
+                (Some("save"), Some(name)) => {
+                    events
+                        .camera
+                        .write(CameraCommandEvent::Save(name.to_string()));
+                }
+                (Some("load"), Some(name)) => {
+                    events
+                        .camera
+                        .write(CameraCommandEvent::Load(name.to_string()));
+                }
+                _ => {
+                    info!("Usage: /camera [keyframe add|clear | play <seconds> | save <name> | load <name>]");
+                }
+            }
+        }
         _ => {
             info!("Unknown command: {}", command);
         }
@@ -9,8 +9,12 @@ use crate::components::{MachineBundle, *};
 use crate::core::items;
 use crate::events::SpawnMachineEvent;
 use crate::game_spec::{CRUSHER, FURNACE, MINER};
+use crate::meshes::create_conveyor_mesh;
 use crate::world::WorldData;
-use crate::{Conveyor, ConveyorShape, ConveyorVisual, Direction, MachineModels, BLOCK_SIZE};
+use crate::{
+    Conveyor, ConveyorShape, ConveyorVisual, Direction, MachineModels, BLOCK_SIZE,
+    CONVEYOR_BELT_HEIGHT,
+};
 use bevy::prelude::*;
 use tracing::info;
 
@@ -130,14 +134,16 @@ pub fn handle_spawn_machine_event(
                         output_direction: direction,
                         items: Vec::new(),
                         last_output_index: 0,
-                        last_input_source: 0,
+                        last_input_pos: None,
+                        enabled: true,
                         shape: ConveyorShape::Straight,
+                        speed_multiplier: 1.0,
                     },
                     ConveyorVisual,
                 ));
             } else {
-                // Fallback to procedural mesh
-                let mesh = meshes.add(Cuboid::new(BLOCK_SIZE * 0.9, BLOCK_SIZE * 0.15, BLOCK_SIZE));
+                // Fallback to procedural mesh (centered, so raise it onto the block)
+                let mesh = meshes.add(create_conveyor_mesh(ConveyorShape::Straight));
                 let material = materials.add(StandardMaterial {
                     base_color: items::conveyor_block().color(),
                     ..default()
@@ -145,16 +151,20 @@ pub fn handle_spawn_machine_event(
                 commands.spawn((
                     Mesh3d(mesh),
                     MeshMaterial3d(material),
-                    Transform::from_translation(conveyor_pos)
-                        .with_rotation(direction.to_rotation()),
+                    Transform::from_translation(
+                        conveyor_pos + Vec3::Y * CONVEYOR_BELT_HEIGHT / 2.0,
+                    )
+                    .with_rotation(direction.to_rotation()),
                     Conveyor {
                         position: pos,
                         direction,
                         output_direction: direction,
                         items: Vec::new(),
                         last_output_index: 0,
-                        last_input_source: 0,
+                        last_input_pos: None,
+                        enabled: true,
                         shape: ConveyorShape::Straight,
+                        speed_multiplier: 1.0,
                     },
                     ConveyorVisual,
                 ));
@@ -228,30 +238,47 @@ pub fn handle_debug_event(
     mut events: MessageReader<DebugEvent>,
     conveyor_query: Query<(Entity, &Conveyor, &GlobalTransform)>,
     machine_query: Query<(Entity, &Machine)>,
+    world_data: Res<WorldData>,
 ) {
     for event in events.read() {
         match event.debug_type {
+            DebugEventType::WorldGen => {
+                let config = &world_data.gen_config;
+                info!(
+                    "=== Worldgen Config (hash {:016x}) ===",
+                    config.config_hash()
+                );
+                match config.to_yaml() {
+                    Ok(yaml) => {
+                        for line in yaml.lines() {
+                            info!("{}", line);
+                        }
+                    }
+                    Err(e) => info!("{}", e),
+                }
+            }
             DebugEventType::Conveyor => {
                 info!("=== Conveyor Debug Dump ===");
                 let mut count = 0;
                 for (entity, conveyor, transform) in conveyor_query.iter() {
                     info!(
-                        "Conveyor {:?}: pos={:?}, dir={:?}, shape={:?}, items={}, last_input={}, world_pos={:.1},{:.1},{:.1}",
+                        "Conveyor {:?}: pos={:?}, dir={:?}, shape={:?}, items={}, last_input={:?}, world_pos={:.1},{:.1},{:.1}",
                         entity,
                         conveyor.position,
                         conveyor.direction,
                         conveyor.shape,
                         conveyor.items.len(),
-                        conveyor.last_input_source,
+                        conveyor.last_input_pos,
                         transform.translation().x,
                         transform.translation().y,
                         transform.translation().z,
                     );
                     for (i, item) in conveyor.items.iter().enumerate() {
                         info!(
-                            "  Item {}: {} @ progress={:.2}, lateral={:.2}",
+                            "  Item {}: {} x{} @ progress={:.2}, lateral={:.2}",
                             i,
                             item.get_item_id().display_name(),
+                            item.count,
                             item.progress,
                             item.lateral_offset
                         );
@@ -323,6 +350,21 @@ pub fn handle_debug_event(
                     miner_count, furnace_count, crusher_count
                 );
             }
+            DebugEventType::Stalled => {
+                let mut stalled: Vec<&Machine> = machine_query
+                    .iter()
+                    .map(|(_, machine)| machine)
+                    .filter(|machine| !machine.status.is_working())
+                    .collect();
+                stalled.sort_by_key(|machine| machine.position.to_array());
+                info!("=== Stalled machines ({}) ===", stalled.len());
+                for machine in stalled {
+                    info!(
+                        "{} at {:?}: {:?}",
+                        machine.spec.name, machine.position, machine.status
+                    );
+                }
+            }
             DebugEventType::Connection => {
                 info!("=== Connection Debug Dump ===");
 
@@ -411,7 +453,10 @@ pub fn handle_assert_machine_event(
                     .iter()
                     .filter(|c| !c.items.is_empty())
                     .collect();
-                let total_items: usize = conveyors_with_items.iter().map(|c| c.items.len()).sum();
+                let total_items: u32 = conveyors_with_items
+                    .iter()
+                    .map(|c| c.total_item_count())
+                    .sum();
                 if total_items > 0 {
                     info!(
                         "PASS: {} item(s) on {} conveyor(s)",
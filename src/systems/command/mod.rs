@@ -9,7 +9,20 @@ mod executor;
 mod handlers;
 mod ui;
 
+use crate::blueprint::{LayoutCommandEvent, RegionShareCommandEvent};
+use crate::cinematic::CameraCommandEvent;
+use crate::components::{LoadGameEvent, SaveGameEvent};
 use crate::core::ItemId;
+use crate::daynight::TimeCommandEvent;
+use crate::devlink::DevLinkCommandEvent;
+use crate::events::SpawnMachineEvent;
+use crate::log_console::LogCommandEvent;
+use crate::logistics::TraceCommandEvent;
+use crate::machines::TickRateCommandEvent;
+use crate::respawn::RespawnCommandEvent;
+use crate::systems::ProtectionCommandEvent;
+use crate::waypoint::WaypointCommandEvent;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 // Re-export public items
@@ -17,7 +30,9 @@ pub use handlers::{
     handle_assert_machine_event, handle_debug_event, handle_look_event, handle_screenshot_event,
     handle_setblock_event, handle_spawn_machine_event, handle_teleport_event,
 };
-pub use ui::{command_input_handler, command_input_toggle, update_command_suggestions};
+pub use ui::{
+    command_input_handler, command_input_toggle, keycode_to_char, update_command_suggestions,
+};
 
 /// E2E test command events
 #[derive(Message)]
@@ -46,6 +61,10 @@ pub enum DebugEventType {
     Machine,
     /// Show machine input/output port connections
     Connection,
+    /// Dump the active worldgen config
+    WorldGen,
+    /// List machines that aren't working, with the reason
+    Stalled,
 }
 
 /// Debug event (for /debug_* commands)
@@ -81,3 +100,28 @@ pub struct AssertMachineEvent {
 pub struct ScreenshotEvent {
     pub filename: String,
 }
+
+/// Bundled command message writers (reduces parameter count)
+#[derive(SystemParam)]
+pub struct CommandEvents<'w> {
+    pub save: MessageWriter<'w, SaveGameEvent>,
+    pub load: MessageWriter<'w, LoadGameEvent>,
+    pub teleport: MessageWriter<'w, TeleportEvent>,
+    pub look: MessageWriter<'w, LookEvent>,
+    pub setblock: MessageWriter<'w, SetBlockEvent>,
+    pub spawn_machine: MessageWriter<'w, SpawnMachineEvent>,
+    pub debug: MessageWriter<'w, DebugEvent>,
+    pub assert_machine: MessageWriter<'w, AssertMachineEvent>,
+    pub screenshot: MessageWriter<'w, ScreenshotEvent>,
+    pub time: MessageWriter<'w, TimeCommandEvent>,
+    pub tickrate: MessageWriter<'w, TickRateCommandEvent>,
+    pub camera: MessageWriter<'w, CameraCommandEvent>,
+    pub respawn: MessageWriter<'w, RespawnCommandEvent>,
+    pub layout: MessageWriter<'w, LayoutCommandEvent>,
+    pub region: MessageWriter<'w, RegionShareCommandEvent>,
+    pub trace: MessageWriter<'w, TraceCommandEvent>,
+    pub waypoint: MessageWriter<'w, WaypointCommandEvent>,
+    pub log: MessageWriter<'w, LogCommandEvent>,
+    pub protection: MessageWriter<'w, ProtectionCommandEvent>,
+    pub devlink: MessageWriter<'w, DevLinkCommandEvent>,
+}
@@ -7,17 +7,12 @@
 //! - Command suggestions/autocomplete with Tab
 
 use crate::components::*;
-use crate::events::SpawnMachineEvent;
 use crate::input::{GameAction, InputManager};
 use crate::player::{LocalPlayer, PlayerInventory};
-use crate::systems::cursor;
 use bevy::prelude::*;
-use bevy::window::{CursorOptions, PrimaryWindow};
 
 use super::executor::execute_command;
-use super::{
-    AssertMachineEvent, DebugEvent, LookEvent, ScreenshotEvent, SetBlockEvent, TeleportEvent,
-};
+use super::CommandEvents;
 
 /// Get matching command suggestions for the current input
 fn get_suggestions(input: &str) -> Vec<&'static str> {
@@ -41,7 +36,7 @@ pub fn command_input_toggle(
     mut command_state: ResMut<CommandInputState>,
     mut ui_query: Query<&mut Visibility, With<CommandInputUI>>,
     mut text_query: Query<&mut Text, With<CommandInputText>>,
-    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    mut cursor: ResMut<CursorController>,
     interacting_machine: Res<InteractingMachine>,
     inventory_open: Res<InventoryOpen>,
 ) {
@@ -72,9 +67,7 @@ pub fn command_input_toggle(
         }
 
         // Unlock cursor
-        if let Ok(mut cursor_options) = cursor_query.single_mut() {
-            cursor::unlock_cursor(&mut cursor_options);
-        }
+        cursor.request_ui(UIContext::CommandInput);
     }
 }
 
@@ -88,21 +81,14 @@ pub fn command_input_handler(
         (Option<&mut Visibility>, Option<&mut Text>),
         Or<(With<CommandInputUI>, With<CommandInputText>)>,
     >,
-    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    mut cursor: ResMut<CursorController>,
     mut creative_mode: ResMut<CreativeMode>,
     local_player: Option<Res<LocalPlayer>>,
     mut inventory_query: Query<&mut PlayerInventory>,
-    mut save_events: MessageWriter<SaveGameEvent>,
-    mut load_events: MessageWriter<LoadGameEvent>,
-    mut tp_events: MessageWriter<TeleportEvent>,
-    mut look_events: MessageWriter<LookEvent>,
-    mut setblock_events: MessageWriter<SetBlockEvent>,
-    mut spawn_machine_events: MessageWriter<SpawnMachineEvent>,
-    mut debug_events: MessageWriter<DebugEvent>,
-    mut assert_machine_events: MessageWriter<AssertMachineEvent>,
-    mut screenshot_events: MessageWriter<ScreenshotEvent>,
+    mut command_events: CommandEvents,
 ) {
-    if !command_state.open {
+    // A sign being edited owns the text box (see crate::sign)
+    if !command_state.open || command_state.sign.is_some() {
         return;
     }
 
@@ -119,9 +105,7 @@ pub fn command_input_handler(
         }
 
         // Lock cursor
-        if let Ok(mut cursor_options) = cursor_query.single_mut() {
-            cursor::lock_cursor(&mut cursor_options);
-        }
+        cursor.release_ui(&UIContext::CommandInput);
         return;
     }
 
@@ -150,9 +134,7 @@ pub fn command_input_handler(
         }
 
         // Lock cursor
-        if let Ok(mut cursor_options) = cursor_query.single_mut() {
-            cursor::lock_cursor(&mut cursor_options);
-        }
+        cursor.release_ui(&UIContext::CommandInput);
 
         // Execute command (requires player inventory)
         let Some(local_player) = local_player.as_ref() else {
@@ -165,15 +147,7 @@ pub fn command_input_handler(
             &command,
             &mut creative_mode,
             &mut inventory,
-            &mut save_events,
-            &mut load_events,
-            &mut tp_events,
-            &mut look_events,
-            &mut setblock_events,
-            &mut spawn_machine_events,
-            &mut debug_events,
-            &mut assert_machine_events,
-            &mut screenshot_events,
+            &mut command_events,
         );
         return;
     }
@@ -232,8 +206,8 @@ pub fn update_command_suggestions(
         ),
     >,
 ) {
-    if !command_state.open {
-        // Hide everything when closed
+    if !command_state.open || command_state.sign.is_some() {
+        // Hide everything when closed (or editing a sign)
         for mut vis in suggestions_ui_query.iter_mut() {
             *vis = Visibility::Hidden;
         }
@@ -275,7 +249,7 @@ pub fn update_command_suggestions(
 }
 
 /// Convert key code to character
-fn keycode_to_char(key_code: KeyCode, shift: bool) -> Option<char> {
+pub fn keycode_to_char(key_code: KeyCode, shift: bool) -> Option<char> {
     match key_code {
         KeyCode::KeyA => Some(if shift { 'A' } else { 'a' }),
         KeyCode::KeyB => Some(if shift { 'B' } else { 'b' }),
@@ -1,117 +1,149 @@
-//! Centralized cursor management utilities
+//! Centralized cursor management
 //!
-//! This module provides unified functions for cursor state management.
+//! Systems never touch the window's `CursorOptions` directly. UI open/close
+//! paths send requests to `CursorController` (see `components::cursor`), and
+//! `apply_cursor_controller` is the only system that writes the window.
 //!
-//! ## CAD-style Controls (New)
+//! ## CAD-style Controls
 //!
-//! In CAD-style mode, the cursor is always visible. Camera rotation is controlled
-//! by middle-mouse drag or Alt+left-drag.
+//! The cursor is always visible. In gameplay (`CursorState::Locked`) it is
+//! confined to the window and the camera turns with middle-drag or
+//! Alt+left-drag; every other state releases it.
 //!
-//! ## Usage Patterns (Bevy 0.18+)
+//! ## Usage Patterns
 //!
-//! ### Opening UI (unlock cursor)
+//! ### Opening UI
 //! ```ignore
-//! cursor::unlock_cursor(&mut cursor_options);
+//! cursor.request_ui(UIContext::Machine(entity));
 //! ```
 //!
 //! ### Closing UI
 //! ```ignore
-//! cursor::release_cursor(&mut cursor_options);
+//! cursor.release_ui(&UIContext::Machine(entity));
 //! ```
 
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, CursorOptions};
+use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow, WindowFocused};
 use tracing::debug;
 
+use crate::components::{CursorController, CursorState};
 use crate::systems::inventory_ui::set_ui_open_state;
 
-/// Lock cursor for camera rotation (middle-drag mode)
-///
-/// In CAD-style controls, cursor remains visible but confined to window
-/// during camera rotation operations.
-#[inline]
-pub fn lock_cursor(cursor_options: &mut CursorOptions) {
-    debug!("[Cursor] lock_cursor called (CAD mode - confined, visible)");
-    // Confine cursor to window during rotation, but keep it visible
-    cursor_options.grab_mode = CursorGrabMode::Confined;
-    // CAD-style: cursor always visible
-    cursor_options.visible = true;
-    set_ui_open_state(false);
-}
-
-/// Unlock cursor and show it (enter UI mode)
-///
-/// Use when:
-/// - Opening any UI (inventory, machine, storage, command)
-/// - Pausing the game with ESC
-#[inline]
-pub fn unlock_cursor(cursor_options: &mut CursorOptions) {
-    debug!("[Cursor] unlock_cursor called");
-    cursor_options.grab_mode = CursorGrabMode::None;
-    cursor_options.visible = true;
-    set_ui_open_state(true);
-}
-
-/// Release cursor (return to normal gameplay)
-///
-/// In CAD-style controls, cursor is always visible and not grabbed.
-#[inline]
-pub fn release_cursor(cursor_options: &mut CursorOptions) {
-    debug!("[Cursor] release_cursor called");
-    cursor_options.grab_mode = CursorGrabMode::None;
-    cursor_options.visible = true;
-    // Note: does NOT call set_ui_open_state
-}
-
-/// Check if cursor is currently grabbed (during camera rotation)
-#[inline]
-pub fn is_locked(cursor_options: &CursorOptions) -> bool {
-    cursor_options.grab_mode != CursorGrabMode::None
+/// Grab mode for a cursor state (CAD-style: confined in gameplay, free otherwise)
+pub fn grab_mode_for(state: &CursorState) -> CursorGrabMode {
+    match state {
+        CursorState::Locked => CursorGrabMode::Confined,
+        CursorState::UnlockedForUi { .. } | CursorState::Paused | CursorState::FocusLost => {
+            CursorGrabMode::None
+        }
+    }
 }
 
-/// Check if cursor is currently free (normal state)
-#[inline]
-pub fn is_unlocked(cursor_options: &CursorOptions) -> bool {
-    cursor_options.grab_mode == CursorGrabMode::None
+/// Move the controller to `FocusLost` and back on window focus changes
+pub fn handle_window_focus(
+    mut focus_events: MessageReader<WindowFocused>,
+    primary: Query<(), With<PrimaryWindow>>,
+    mut cursor: ResMut<CursorController>,
+) {
+    for event in focus_events.read() {
+        if primary.get(event.window).is_err() {
+            continue;
+        }
+        if event.focused {
+            cursor.focus_gained();
+        } else {
+            cursor.focus_lost();
+        }
+        debug!("[Cursor] focus {}: {:?}", event.focused, cursor.state());
+    }
 }
 
-// =============================================================================
-// Cursor Sync System (CAD-style: cursor always visible)
-// =============================================================================
-
-use crate::components::UIState;
-
-/// Synchronize cursor state based on UIState
-///
-/// In CAD-style controls, the cursor is always visible. This system only
-/// manages UI-related state changes (not cursor visibility).
+/// Apply the controller state to the primary window (the only cursor writer)
 ///
-/// CAD-style behavior:
-/// - Cursor always visible
-/// - Camera controlled by middle-drag or Alt+left-drag
-pub fn sync_cursor_to_ui_state(
-    ui_state: Res<UIState>,
-    mut cursor_query: Query<&mut CursorOptions, With<bevy::window::PrimaryWindow>>,
+/// Also re-applies when the OS or browser changed the grab behind our back.
+pub fn apply_cursor_controller(
+    cursor: Res<CursorController>,
+    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
 ) {
     let Ok(mut cursor_options) = cursor_query.single_mut() else {
         return;
     };
 
-    // CAD-style: ensure cursor is always visible
-    if !cursor_options.visible {
-        debug!("[Cursor] sync: ensuring cursor visible (CAD mode)");
+    let grab_mode = grab_mode_for(cursor.state());
+    if cursor_options.grab_mode != grab_mode || !cursor_options.visible {
+        debug!("[Cursor] apply {:?} -> {:?}", cursor.state(), grab_mode);
+        cursor_options.grab_mode = grab_mode;
+        // CAD-style: cursor always visible
         cursor_options.visible = true;
     }
-
-    // Release any grab when UI is open
-    if !ui_state.is_gameplay() && is_locked(&cursor_options) {
-        debug!("[Cursor] sync: releasing grab ({:?})", ui_state.current());
-        release_cursor(&mut cursor_options);
+    if cursor.is_changed() {
+        set_ui_open_state(cursor.ui_open());
     }
 }
 
 #[cfg(test)]
 mod tests {
-    // Note: Window cannot be easily unit tested without Bevy app context
-    // These tests would require integration testing
+    use super::*;
+    use crate::components::UIContext;
+
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CursorController>()
+            .add_message::<WindowFocused>()
+            .add_systems(
+                Update,
+                (handle_window_focus, apply_cursor_controller).chain(),
+            );
+        let window = app
+            .world_mut()
+            .spawn((PrimaryWindow, CursorOptions::default()))
+            .id();
+        (app, window)
+    }
+
+    fn grab(app: &App, window: Entity) -> CursorGrabMode {
+        app.world().get::<CursorOptions>(window).unwrap().grab_mode
+    }
+
+    #[test]
+    fn test_window_follows_controller_and_focus() {
+        let (mut app, window) = app();
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::None);
+
+        app.world_mut()
+            .resource_mut::<CursorController>()
+            .request_lock();
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::Confined);
+
+        // Alt-tab releases the cursor, coming back confines it again
+        app.world_mut().write_message(WindowFocused {
+            window,
+            focused: false,
+        });
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::None);
+        app.world_mut().write_message(WindowFocused {
+            window,
+            focused: true,
+        });
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::Confined);
+
+        // Something else dropping the grab gets corrected
+        app.world_mut()
+            .get_mut::<CursorOptions>(window)
+            .unwrap()
+            .grab_mode = CursorGrabMode::None;
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::Confined);
+
+        app.world_mut()
+            .resource_mut::<CursorController>()
+            .request_ui(UIContext::Inventory);
+        app.update();
+        assert_eq!(grab(&app, window), CursorGrabMode::None);
+    }
 }
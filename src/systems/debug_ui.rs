@@ -67,9 +67,12 @@ pub fn update_debug_hud(
     camera_query: Query<&PlayerCamera>,
     world_data: Res<WorldData>,
     creative_mode: Res<CreativeMode>,
-    cursor_state: Res<CursorLockState>,
+    cursor: Res<CursorController>,
     target_block: Res<TargetBlock>,
     conveyor_query: Query<&Conveyor>,
+    item_culling: Option<Res<ConveyorItemCulling>>,
+    content_version: Option<Res<crate::modding::ContentVersion>>,
+    machine_sleep: Option<Res<MachineSleepStats>>,
 ) {
     if !debug_state.visible {
         return;
@@ -140,7 +143,11 @@ pub fn update_debug_hud(
     } else {
         "Survival"
     };
-    let pause_str = if cursor_state.paused { " [PAUSED]" } else { "" };
+    let pause_str = if cursor.is_released() {
+        " [PAUSED]"
+    } else {
+        ""
+    };
 
     let conveyor_line = if conveyor_info.is_empty() {
         String::new()
@@ -155,8 +162,23 @@ pub fn update_debug_hud(
         "Biome: N/A".to_string()
     };
 
+    // Conveyor item visuals (active / culled by distance)
+    let items_str = item_culling
+        .map(|c| format!("{} active / {} culled", c.active, c.culled))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    // Generic machines processed this tick / skipped as idle
+    let machines_str = machine_sleep
+        .map(|s| format!("{} active / {} sleeping", s.active, s.sleeping))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    // Content bundle version (loose files when no bundle was loaded)
+    let content_str = content_version
+        .and_then(|v| v.0.clone())
+        .unwrap_or_else(|| "loose files".to_string());
+
     text.0 = format!(
-        "FPS: {:.0}\nPos: {}\nDir: {}\n{}\nTarget: {} ({})\nPlace: {}\nChunks: {}\nMode: {}{}{}",
+        "FPS: {:.0}\nPos: {}\nDir: {}\n{}\nTarget: {} ({})\nPlace: {}\nChunks: {}\nItems: {}\nMachines: {}\nContent: {}\nMode: {}{}{}",
         fps,
         pos_str,
         dir_str,
@@ -165,6 +187,9 @@ pub fn update_debug_hud(
         block_type_str,
         place_str,
         chunk_count,
+        items_str,
+        machines_str,
+        content_str,
         mode_str,
         pause_str,
         conveyor_line
@@ -291,7 +316,7 @@ pub fn export_e2e_state(
     camera_query: Query<&PlayerCamera>,
     world_data: Res<WorldData>,
     creative_mode: Res<CreativeMode>,
-    cursor_state: Res<CursorLockState>,
+    cursor: Res<CursorController>,
     target_block: Res<TargetBlock>,
     current_quest: Res<CurrentQuest>,
     platform_inventory: crate::player::LocalPlatformInventory,
@@ -443,7 +468,7 @@ pub fn export_e2e_state(
         target_place,
         target_block_type,
         creative_mode: creative_mode.enabled,
-        paused: cursor_state.paused,
+        paused: cursor.is_released(),
         fps,
         quest,
         conveyors,
@@ -0,0 +1,260 @@
+//! Machine hibernation for unloaded chunks
+//!
+//! Machines and conveyors whose chunk is not loaded are converted to their
+//! save format, stored in `WorldData::hibernated_machines` and despawned, so
+//! a large base far from the player costs no entities and no ticks. When
+//! the chunk loads again they are respawned from the stored data.
+//!
+//! There is no offline simulation: a hibernated machine is frozen, and since
+//! its conveyors are gone too, nothing is delivered from unloaded chunks.
+//! Saving writes hibernated machines alongside the live ones.
+
+use bevy::prelude::*;
+
+use crate::components::{ConveyorItemVisual, Machine};
+use crate::save::{conveyor_to_save, machine_to_save, spawn_saved_machine};
+use crate::world::WorldData;
+use crate::Conveyor;
+
+/// Store and despawn machines/conveyors whose chunk is not loaded
+pub fn hibernate_unloaded_machines(
+    mut commands: Commands,
+    mut world_data: ResMut<WorldData>,
+    machine_query: Query<(Entity, &Machine)>,
+    conveyor_query: Query<(Entity, &Conveyor)>,
+    item_visual_query: Query<Entity, With<ConveyorItemVisual>>,
+) {
+    let mut hibernated = 0;
+
+    for (entity, machine) in machine_query.iter() {
+        let chunk = WorldData::world_to_chunk(machine.position);
+        if world_data.chunks.contains_key(&chunk) {
+            continue;
+        }
+        let Some(data) = machine_to_save(machine) else {
+            continue;
+        };
+        world_data.hibernated_machines.insert(data);
+        commands.entity(entity).despawn();
+        hibernated += 1;
+    }
+
+    for (entity, conveyor) in conveyor_query.iter() {
+        let chunk = WorldData::world_to_chunk(conveyor.position);
+        if world_data.chunks.contains_key(&chunk) {
+            continue;
+        }
+        // Item visuals are separate entities
+        for visual in conveyor.items.iter().filter_map(|item| item.visual_entity) {
+            if item_visual_query.contains(visual) {
+                commands.entity(visual).despawn();
+            }
+        }
+        world_data
+            .hibernated_machines
+            .insert(conveyor_to_save(conveyor));
+        commands.entity(entity).despawn();
+        hibernated += 1;
+    }
+
+    if hibernated > 0 {
+        debug!(
+            count = hibernated,
+            total = world_data.hibernated_machines.len(),
+            "Machines hibernated"
+        );
+    }
+}
+
+/// Respawn hibernated machines/conveyors of chunks that are loaded again
+pub fn wake_hibernated_machines(
+    mut commands: Commands,
+    mut world_data: ResMut<WorldData>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if world_data.hibernated_machines.is_empty() {
+        return;
+    }
+
+    let loaded: Vec<IVec2> = world_data
+        .hibernated_machines
+        .chunk_coords()
+        .filter(|chunk| world_data.chunks.contains_key(chunk))
+        .collect();
+
+    for chunk in loaded {
+        let machines = world_data.hibernated_machines.take(chunk);
+        debug!(?chunk, count = machines.len(), "Machines woken");
+        for machine in &machines {
+            spawn_saved_machine(&mut commands, &mut meshes, &mut materials, machine);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ConveyorItem, MachineBundle, MachineSlot, SlotRoute};
+    use crate::core::{items, ItemId};
+    use crate::game_spec::{FURNACE, MINER};
+    use crate::{ConveyorShape, Direction};
+
+    fn hibernation_app() -> App {
+        let mut world_data = WorldData::default();
+        world_data.ensure_chunk_loaded(IVec2::ZERO);
+
+        let mut app = App::new();
+        app.insert_resource(world_data)
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .add_systems(
+                Update,
+                (hibernate_unloaded_machines, wake_hibernated_machines).chain(),
+            );
+        app
+    }
+
+    fn stacks(slots: &[MachineSlot]) -> Vec<(Option<ItemId>, u32)> {
+        slots.iter().map(|s| (s.item_id, s.count)).collect()
+    }
+
+    fn machines(app: &mut App) -> Vec<Machine> {
+        let mut machines: Vec<Machine> = app
+            .world_mut()
+            .query::<&Machine>()
+            .iter(app.world())
+            .cloned()
+            .collect();
+        machines.sort_by_key(|m| m.position.to_array());
+        machines
+    }
+
+    #[test]
+    fn test_machines_freeze_while_chunk_unloaded() {
+        let mut app = hibernation_app();
+
+        let mut furnace =
+            MachineBundle::new_centered(&FURNACE, IVec3::new(2, 20, 2), Direction::East);
+        furnace.machine.slots.fuel = 3;
+        furnace.machine.slots.inputs[0].add_id(items::iron_ore(), 5);
+        furnace.machine.slots.outputs[0].add_id(items::iron_ingot(), 2);
+        furnace.machine.progress = 0.4;
+        furnace.machine.enabled = false;
+        furnace.machine.pass_through_sides = vec![Direction::North];
+        furnace
+            .machine
+            .set_slot_route(Direction::South, SlotRoute::Fuel);
+        app.world_mut().spawn(furnace);
+
+        let mut miner = MachineBundle::new_centered(&MINER, IVec3::new(5, 20, 2), Direction::West);
+        miner.machine.slots.outputs[0].add_id(items::coal(), 7);
+        miner.machine.progress = 0.25;
+        app.world_mut().spawn(miner);
+
+        app.world_mut().spawn(Conveyor {
+            position: IVec3::new(3, 20, 2),
+            direction: Direction::East,
+            output_direction: Direction::East,
+            items: vec![ConveyorItem::new(items::iron_ingot(), 0.5)],
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        });
+
+        app.update();
+        let before = machines(&mut app);
+        assert_eq!(before.len(), 2);
+
+        // Walk away: the chunk unloads and everything in it hibernates
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .chunks
+            .remove(&IVec2::ZERO);
+        app.update();
+        assert!(machines(&mut app).is_empty());
+        let conveyors = app
+            .world_mut()
+            .query::<&Conveyor>()
+            .iter(app.world())
+            .count();
+        assert_eq!(conveyors, 0);
+        assert_eq!(
+            app.world()
+                .resource::<WorldData>()
+                .hibernated_machines
+                .len(),
+            3
+        );
+
+        // Frozen while away
+        for _ in 0..10 {
+            app.update();
+        }
+
+        // Walk back: the chunk loads and the machines come back unchanged
+        app.world_mut()
+            .resource_mut::<WorldData>()
+            .ensure_chunk_loaded(IVec2::ZERO);
+        app.update();
+
+        let after = machines(&mut app);
+        assert_eq!(after.len(), before.len());
+        for (a, b) in before.iter().zip(&after) {
+            assert_eq!(a.spec.item_id(), b.spec.item_id());
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.facing, b.facing);
+            assert_eq!(a.progress, b.progress);
+            assert_eq!(a.enabled, b.enabled);
+            assert_eq!(a.slots.fuel, b.slots.fuel);
+            assert_eq!(stacks(&a.slots.inputs), stacks(&b.slots.inputs));
+            assert_eq!(stacks(&a.slots.outputs), stacks(&b.slots.outputs));
+            assert_eq!(a.pass_through_sides, b.pass_through_sides);
+            assert_eq!(a.slot_routes, b.slot_routes);
+        }
+
+        let belt_items: Vec<Vec<(ItemId, f32)>> = app
+            .world_mut()
+            .query::<&Conveyor>()
+            .iter(app.world())
+            .map(|c| c.items.iter().map(|i| (i.item_id, i.progress)).collect())
+            .collect();
+        assert_eq!(belt_items, vec![vec![(items::iron_ingot(), 0.5)]]);
+        assert!(app
+            .world()
+            .resource::<WorldData>()
+            .hibernated_machines
+            .is_empty());
+    }
+
+    #[test]
+    fn test_machines_in_loaded_chunks_stay_live() {
+        let mut app = hibernation_app();
+        app.world_mut().spawn(MachineBundle::new_centered(
+            &FURNACE,
+            IVec3::new(1, 20, 1),
+            Direction::North,
+        ));
+        // Chunk (1, 0) was never loaded
+        app.world_mut().spawn(MachineBundle::new_centered(
+            &FURNACE,
+            IVec3::new(40, 20, 1),
+            Direction::North,
+        ));
+
+        app.update();
+
+        let live = machines(&mut app);
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].position, IVec3::new(1, 20, 1));
+        assert_eq!(
+            app.world()
+                .resource::<WorldData>()
+                .hibernated_machines
+                .len(),
+            1
+        );
+    }
+}
@@ -1,9 +1,12 @@
 //! Hotbar UI systems
 
 use crate::components::*;
+use crate::core::items;
+use crate::graphics::BlockPalette;
 use crate::input::{GameAction, InputManager};
 use crate::player::{LocalPlayer, PlayerInventory};
 use crate::systems::block_operations::LocalPlayerInventory;
+use crate::systems::wrench::MachineSettingsClipboard;
 use bevy::prelude::*;
 
 /// Update hotbar UI display
@@ -11,6 +14,7 @@ pub fn update_hotbar_ui(
     local_player: Option<Res<LocalPlayer>>,
     inventory_query: Query<&PlayerInventory>,
     item_sprites: Res<ItemSprites>,
+    palette: Res<BlockPalette>,
     mut slot_query: Query<(&HotbarSlot, &mut BackgroundColor, &mut BorderColor)>,
     mut count_query: Query<(&HotbarSlotCount, &mut Text)>,
     mut image_query: Query<(&HotbarSlotImage, &mut ImageNode, &mut Visibility)>,
@@ -26,15 +30,17 @@ pub fn update_hotbar_ui(
     // Update slot backgrounds - always run (for selection highlight)
     for (slot, mut bg, mut border) in slot_query.iter_mut() {
         let is_selected = inventory.selected_slot == slot.0;
-        let has_item = inventory.get_slot_item_id(slot.0).is_some();
+        let slot_item = inventory.get_slot_item_id(slot.0);
+        let has_item = slot_item.is_some();
 
         if is_selected {
             // Selected slot - same highlight for empty and filled
             *bg = BackgroundColor(Color::srgba(0.4, 0.4, 0.2, 0.9));
             *border = BorderColor::all(Color::srgba(1.0, 1.0, 0.5, 1.0));
         } else if has_item {
-            // Non-selected filled slot
-            *bg = BackgroundColor(Color::srgba(0.3, 0.3, 0.3, 0.8));
+            // Non-selected filled slot (tinted by item in colorblind mode)
+            let tint = slot_item.and_then(|item_id| palette.slot_tint(item_id));
+            *bg = BackgroundColor(tint.unwrap_or(Color::srgba(0.3, 0.3, 0.3, 0.8)));
             *border = BorderColor::all(Color::srgba(0.5, 0.5, 0.5, 1.0));
         } else {
             // Non-selected empty slot
@@ -77,6 +83,7 @@ pub fn update_hotbar_item_name(
     local_player: Option<Res<LocalPlayer>>,
     inventory_query: Query<&PlayerInventory>,
     inventory_open: Res<InventoryOpen>,
+    clipboard: Res<MachineSettingsClipboard>,
     mut text_query: Query<(&mut Text, &mut Node), With<HotbarItemNameText>>,
 ) {
     let Ok((mut text, mut node)) = text_query.single_mut() else {
@@ -102,7 +109,14 @@ pub fn update_hotbar_item_name(
     // Show selected item name
     if let Some(item_id) = inventory.selected_item_id() {
         // Get display name using ItemId's display_name() method
-        let name = item_id.display_name().to_string();
+        let mut name = item_id.display_name().to_string();
+        // The wrench shows what it would paste
+        if item_id == items::wrench() {
+            match clipboard.settings() {
+                Some(settings) => name = format!("{} [{}]", name, settings.summary()),
+                None => name.push_str(" [空]"),
+            }
+        }
         text.0 = name.clone();
         // Center the text by adjusting margin based on text length
         let char_width = 8.0; // Approximate character width
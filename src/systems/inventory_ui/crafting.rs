@@ -0,0 +1,139 @@
+//! Crafting tab systems (instant hand crafting from the inventory)
+
+use crate::components::*;
+use crate::craft::{available_for_input, hand_craft, input_label};
+use crate::game_spec::find_recipe_by_id;
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::setup::ui::{
+    CraftingPanel, CraftingRecipeList, CraftingTabButton, HandCraftButton, HandCraftRequirement,
+};
+use bevy::prelude::*;
+use tracing::info;
+
+/// Requirement text color when the inventory has enough
+const REQUIREMENT_MET: Color = Color::srgb(0.5, 0.9, 0.5);
+/// Requirement text color when an ingredient is short
+const REQUIREMENT_SHORT: Color = Color::srgb(1.0, 0.45, 0.4);
+
+const CRAFT_BUTTON_BG: Color = Color::srgba(0.2, 0.2, 0.25, 1.0);
+const CRAFT_BUTTON_HOVER_BG: Color = Color::srgba(0.3, 0.3, 0.35, 1.0);
+const CRAFT_BUTTON_DISABLED_BG: Color = Color::srgba(0.12, 0.12, 0.14, 1.0);
+
+/// Toggle the crafting recipe list
+pub fn crafting_tab_click(
+    mut tab_open: ResMut<CraftingTabOpen>,
+    query: Query<&Interaction, (With<CraftingTabButton>, Changed<Interaction>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            tab_open.0 = !tab_open.0;
+        }
+    }
+}
+
+/// Show/hide the crafting tab and refresh required vs available counts
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn update_crafting_panel(
+    inventory_open: Res<InventoryOpen>,
+    creative_mode: Res<CreativeMode>,
+    tab_open: Res<CraftingTabOpen>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventory_query: Query<&PlayerInventory>,
+    mut panel_query: Query<&mut Node, (With<CraftingPanel>, Without<CraftingRecipeList>)>,
+    mut list_query: Query<&mut Node, (With<CraftingRecipeList>, Without<CraftingPanel>)>,
+    mut requirement_query: Query<(&HandCraftRequirement, &mut TextSpan, &mut TextColor)>,
+    mut button_query: Query<(&HandCraftButton, &Interaction, &mut BackgroundColor)>,
+) {
+    if !inventory_open.0 {
+        return;
+    }
+
+    // Creative mode has everything already, so the tab is hidden
+    for mut node in panel_query.iter_mut() {
+        node.display = if creative_mode.enabled {
+            Display::None
+        } else {
+            Display::Flex
+        };
+    }
+    let show_list = tab_open.0 && !creative_mode.enabled;
+    for mut node in list_query.iter_mut() {
+        node.display = if show_list {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if !show_list {
+        return;
+    }
+
+    let Some(inventory) = local_player.and_then(|lp| inventory_query.get(lp.0).ok()) else {
+        return;
+    };
+
+    for (requirement, mut span, mut color) in requirement_query.iter_mut() {
+        let Some(input) = find_recipe_by_id(requirement.recipe_id)
+            .and_then(|recipe| recipe.inputs.get(requirement.input))
+        else {
+            continue;
+        };
+        let have = available_for_input(inventory, input);
+        let text = format!("{} {}/{}  ", input_label(input), have, input.count);
+        if span.0 != text {
+            span.0 = text;
+        }
+        color.0 = if have >= input.count {
+            REQUIREMENT_MET
+        } else {
+            REQUIREMENT_SHORT
+        };
+    }
+
+    for (button, interaction, mut bg_color) in button_query.iter_mut() {
+        let craftable = find_recipe_by_id(button.0).is_some_and(|recipe| {
+            recipe
+                .inputs
+                .iter()
+                .all(|input| available_for_input(inventory, input) >= input.count)
+        });
+        *bg_color = BackgroundColor(match (craftable, interaction) {
+            (false, _) => CRAFT_BUTTON_DISABLED_BG,
+            (true, Interaction::Hovered | Interaction::Pressed) => CRAFT_BUTTON_HOVER_BG,
+            (true, Interaction::None) => CRAFT_BUTTON_BG,
+        });
+    }
+}
+
+/// Craft a hand recipe instantly when its button is pressed
+pub fn hand_craft_click(
+    inventory_open: Res<InventoryOpen>,
+    creative_mode: Res<CreativeMode>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventory_query: Query<&mut PlayerInventory>,
+    button_query: Query<(&Interaction, &HandCraftButton), Changed<Interaction>>,
+) {
+    if !inventory_open.0 || creative_mode.enabled {
+        return;
+    }
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let Ok(mut inventory) = inventory_query.get_mut(local_player.0) else {
+        return;
+    };
+
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(recipe) = find_recipe_by_id(button.0) else {
+            continue;
+        };
+        match hand_craft(recipe, &mut inventory) {
+            Ok(()) => info!("[CRAFT] Hand crafted {}", recipe.id),
+            Err(err) => info!("[CRAFT] Cannot craft {}: {:?}", recipe.id, err),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Inventory bottom-row buttons: sort and quick stack to nearby machines
+
+use crate::components::*;
+use crate::core::items;
+use crate::player::{LocalPlayer, PlayerInventory, StackTarget};
+use crate::systems::WrenchToast;
+use bevy::prelude::*;
+
+/// Machines within this many blocks of the player receive quick-stacked items
+const QUICK_STACK_RADIUS: f32 = 8.0;
+
+/// Where a quick stack target lives in a machine
+#[derive(Clone, Copy)]
+enum TargetSlot {
+    Input(Entity, usize),
+    Fuel(Entity),
+}
+
+/// Sort the main inventory (the hotbar is left alone)
+pub fn inventory_sort_click(
+    inventory_open: Res<InventoryOpen>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventory_query: Query<&mut PlayerInventory>,
+    button_query: Query<&Interaction, (With<InventorySortButton>, Changed<Interaction>)>,
+) {
+    if !inventory_open.0 || !button_query.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let Some(local_player) = local_player else {
+        return;
+    };
+    if let Ok(mut inventory) = inventory_query.get_mut(local_player.0) {
+        inventory.sort_main();
+    }
+}
+
+/// Move main inventory items into nearby machines that already hold them
+///
+/// Only input slots that have an item (and the fuel slot of fueled machines
+/// that have coal) are filled, nearest machine first.
+pub fn inventory_quick_stack_click(
+    inventory_open: Res<InventoryOpen>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut player_query: Query<(&mut PlayerInventory, &Transform)>,
+    mut machine_query: Query<(Entity, &mut Machine)>,
+    mut toast: ResMut<WrenchToast>,
+    button_query: Query<&Interaction, (With<InventoryQuickStackButton>, Changed<Interaction>)>,
+) {
+    if !inventory_open.0 || !button_query.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let Ok((mut inventory, transform)) = player_query.get_mut(local_player.0) else {
+        return;
+    };
+    let player_pos = transform.translation;
+
+    let mut nearby: Vec<(f32, Entity)> = machine_query
+        .iter()
+        .filter_map(|(entity, machine)| {
+            let center = machine.position.as_vec3() + Vec3::splat(0.5);
+            let distance = center.distance(player_pos);
+            (distance <= QUICK_STACK_RADIUS).then_some((distance, entity))
+        })
+        .collect();
+    nearby.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut slots = Vec::new();
+    let mut targets = Vec::new();
+    for &(_, entity) in &nearby {
+        let Ok((_, machine)) = machine_query.get(entity) else {
+            continue;
+        };
+        let capacity = machine.spec.buffer_size;
+        for (index, slot) in machine.slots.inputs.iter().enumerate() {
+            if let (Some(item_id), true) = (slot.item_id, slot.count > 0) {
+                slots.push(TargetSlot::Input(entity, index));
+                targets.push(StackTarget {
+                    item_id,
+                    count: slot.count,
+                    capacity,
+                });
+            }
+        }
+        if machine.spec.requires_fuel && machine.slots.fuel > 0 {
+            slots.push(TargetSlot::Fuel(entity));
+            targets.push(StackTarget {
+                item_id: items::coal(),
+                count: machine.slots.fuel,
+                capacity,
+            });
+        }
+    }
+
+    let moved = inventory.quick_stack_main(&mut targets);
+    if moved == 0 {
+        toast.show("近くの機械に収納できるアイテムがありません");
+        return;
+    }
+
+    for (slot, target) in slots.iter().zip(&targets) {
+        match *slot {
+            TargetSlot::Input(entity, index) => {
+                if let Ok((_, mut machine)) = machine_query.get_mut(entity) {
+                    if let Some(input) = machine.slots.inputs.get_mut(index) {
+                        input.count = target.count;
+                    }
+                }
+            }
+            TargetSlot::Fuel(entity) => {
+                if let Ok((_, mut machine)) = machine_query.get_mut(entity) {
+                    machine.slots.fuel = target.count;
+                }
+            }
+        }
+    }
+    toast.show(format!("{}個を近くの機械に収納しました", moved));
+}
@@ -7,8 +7,12 @@
 //! - Tooltip display
 //! - Breaking progress bar
 //! - Upper panel (creative/platform inventory)
+//! - Crafting tab (hand recipes)
+//! - Sort and quick stack buttons
 
 mod breaking_bar;
+mod crafting;
+mod inventory_actions;
 mod slot_display;
 mod slot_interaction;
 mod tooltip;
@@ -17,10 +21,14 @@ mod visibility;
 
 // Re-export public systems
 pub use breaking_bar::{spawn_breaking_progress_ui, update_breaking_progress_ui};
-pub use slot_display::{inventory_update_slots, update_held_item_display};
+pub use crafting::{crafting_tab_click, hand_craft_click, update_crafting_panel};
+pub use inventory_actions::{inventory_quick_stack_click, inventory_sort_click};
+pub use slot_display::{
+    inventory_update_slots, update_held_item_display, update_trash_slot_display,
+};
 pub use slot_interaction::{
     creative_inventory_click, inventory_continuous_shift_click, inventory_slot_click,
-    trash_slot_click,
+    trash_slot_click, update_pending_trash,
 };
 pub use tooltip::update_inventory_tooltip;
 pub use upper_panel::{
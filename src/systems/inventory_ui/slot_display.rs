@@ -2,6 +2,7 @@
 
 use crate::components::*;
 use crate::core::ItemId;
+use crate::graphics::BlockPalette;
 use crate::player::{LocalPlayer, PlayerInventory};
 use crate::setup::ui::{SLOT_BG, SLOT_HOVER_BG};
 use bevy::prelude::*;
@@ -12,6 +13,7 @@ pub fn inventory_update_slots(
     local_player: Option<Res<LocalPlayer>>,
     inventory_query: Query<&PlayerInventory>,
     item_sprites: Res<ItemSprites>,
+    palette: Res<BlockPalette>,
     mut slot_query: Query<(
         &InventorySlotUI,
         &mut BackgroundColor,
@@ -54,9 +56,9 @@ pub fn inventory_update_slots(
     for (slot_ui, mut bg_color, children, interaction) in slot_query.iter_mut() {
         let slot_idx = slot_ui.0;
 
-        if let Some((_block_type, count)) = inventory.slots[slot_idx] {
-            // Use consistent dark background regardless of sprite availability
-            *bg_color = BackgroundColor(SLOT_BG);
+        if let Some((block_type, count)) = inventory.slots[slot_idx] {
+            // Dark background regardless of sprite availability (tinted in colorblind mode)
+            *bg_color = BackgroundColor(palette.slot_tint(block_type).unwrap_or(SLOT_BG));
 
             // Update text (count)
             for child in children.iter() {
@@ -161,3 +163,39 @@ pub fn update_held_item_display(
         }
     }
 }
+
+/// Show the stack waiting in the trash slot (dimmed sprite and count), or the "X" label
+pub fn update_trash_slot_display(
+    pending: Res<PendingTrash>,
+    item_sprites: Res<ItemSprites>,
+    mut image_query: Query<(&mut ImageNode, &mut Visibility), With<TrashPendingImage>>,
+    mut text_query: Query<&mut Text, With<TrashSlotText>>,
+) {
+    if !pending.is_changed() {
+        return;
+    }
+
+    let Ok((mut image, mut visibility)) = image_query.single_mut() else {
+        return;
+    };
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    match pending.stack {
+        Some((item_id, count)) => {
+            if let Some(sprite) = item_sprites.get_id(item_id) {
+                image.image = sprite;
+                image.color = Color::srgba(1.0, 1.0, 1.0, 0.45);
+                *visibility = Visibility::Inherited;
+            } else {
+                *visibility = Visibility::Hidden;
+            }
+            text.0 = count.to_string();
+        }
+        None => {
+            *visibility = Visibility::Hidden;
+            text.0 = "X".to_string();
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Inventory slot interaction systems
 
 use crate::components::*;
+use crate::graphics::BlockPalette;
 use crate::input::{GameAction, InputManager};
 use crate::player::{LocalPlayer, PlayerInventory};
 use crate::setup::ui::{
@@ -14,6 +15,7 @@ use bevy::prelude::*;
 pub fn creative_inventory_click(
     creative_inv_open: Res<InventoryOpen>,
     creative_mode: Res<CreativeMode>,
+    palette: Res<BlockPalette>,
     mut held_item: ResMut<HeldItem>,
     mut interaction_query: Query<
         (
@@ -45,7 +47,7 @@ pub fn creative_inventory_click(
                 // Highlight on hover
                 *border_color = BorderColor::all(Color::srgb(0.8, 0.8, 0.8));
                 // Slightly brighter background
-                let base = block_type.color();
+                let base = palette.item_color(block_type);
                 let Srgba {
                     red,
                     green,
@@ -62,7 +64,7 @@ pub fn creative_inventory_click(
             Interaction::None => {
                 // Reset to normal
                 *border_color = BorderColor::all(Color::srgba(0.3, 0.3, 0.3, 1.0));
-                *bg_color = BackgroundColor(block_type.color());
+                *bg_color = BackgroundColor(palette.item_color(block_type));
             }
         }
     }
@@ -290,14 +292,61 @@ pub fn inventory_continuous_shift_click(
     }
 }
 
-/// Handle trash slot clicks (delete held item)
+/// Return the stack waiting in the trash to the inventory
+///
+/// What doesn't fit goes back to the hand if it is free, otherwise it keeps waiting.
+pub(super) fn return_pending_trash(
+    inventory: &mut PlayerInventory,
+    held_item: &mut HeldItem,
+    pending: &mut PendingTrash,
+) {
+    let Some((item_id, count)) = pending.take() else {
+        return;
+    };
+    let remaining = inventory.add_item_by_id(item_id, count);
+    if remaining == 0 {
+        return;
+    }
+    if held_item.0.is_none() {
+        held_item.0 = Some((item_id, remaining));
+    } else {
+        pending.stage((item_id, remaining));
+    }
+}
+
+/// Settle the waiting trash stack when the inventory closes
+///
+/// A plain close back to gameplay confirms the deletion; closing because another
+/// screen (machine UI, pause menu) took over gives the stack back.
+pub(super) fn settle_pending_trash_on_close(
+    to_gameplay: bool,
+    inventory: &mut PlayerInventory,
+    held_item: &mut HeldItem,
+    pending: &mut PendingTrash,
+) {
+    if to_gameplay {
+        if let Some((item_id, count)) = pending.take() {
+            info!(item = ?item_id.name(), count, "Trashed stack on close");
+        }
+    } else {
+        return_pending_trash(inventory, held_item, pending);
+    }
+}
+
+/// Handle trash slot clicks
+///
+/// - Click with a held stack: the stack waits in the trash (dimmed) for confirmation
+/// - Click with an empty hand: confirm, deleting the waiting stack
+/// - Right-click: take the waiting stack back into the hand
 #[allow(clippy::type_complexity)]
 pub fn trash_slot_click(
     inventory_open: Res<InventoryOpen>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
     mut held_item: ResMut<HeldItem>,
+    mut pending: ResMut<PendingTrash>,
     mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &mut BorderColor),
-        (Changed<Interaction>, With<TrashSlot>),
+        (Ref<Interaction>, &mut BackgroundColor, &mut BorderColor),
+        With<TrashSlot>,
     >,
 ) {
     if !inventory_open.0 {
@@ -305,10 +354,25 @@ pub fn trash_slot_click(
     }
 
     for (interaction, mut bg_color, mut border_color) in interaction_query.iter_mut() {
+        if *interaction == Interaction::Hovered
+            && mouse_button.just_pressed(MouseButton::Right)
+            && held_item.0.is_none()
+        {
+            held_item.0 = pending.take();
+        }
+        if !interaction.is_changed() {
+            continue;
+        }
+
         match *interaction {
             Interaction::Pressed => {
-                // Delete held item
-                held_item.0 = None;
+                if let Some(stack) = held_item.0.take() {
+                    if let Some((item_id, count)) = pending.stage(stack) {
+                        info!(item = ?item_id.name(), count, "Trashed previous stack");
+                    }
+                } else if let Some((item_id, count)) = pending.take() {
+                    info!(item = ?item_id.name(), count, "Trashed stack");
+                }
                 *border_color = BorderColor::all(Color::srgb(1.0, 0.0, 0.0));
             }
             Interaction::Hovered => {
@@ -322,3 +386,122 @@ pub fn trash_slot_click(
         }
     }
 }
+
+/// Give the waiting trash stack back on Ctrl+Z or when the confirmation window runs out
+pub fn update_pending_trash(
+    time: Res<Time>,
+    input: Res<InputManager>,
+    inventory_open: Res<InventoryOpen>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventory_query: Query<&mut PlayerInventory>,
+    mut held_item: ResMut<HeldItem>,
+    mut pending: ResMut<PendingTrash>,
+) {
+    if !inventory_open.0 || pending.stack.is_none() {
+        return;
+    }
+    let undo = input.pressed(GameAction::ModifierCtrl) && input.just_pressed(GameAction::Undo);
+    let expired = pending.tick(time.delta_secs());
+    if !undo && !expired {
+        return;
+    }
+
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let Ok(mut inventory) = inventory_query.get_mut(local_player.0) else {
+        return;
+    };
+    return_pending_trash(&mut inventory, &mut held_item, &mut pending);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    fn iron(count: u32) -> (crate::core::ItemId, u32) {
+        (items::iron_ingot(), count)
+    }
+
+    #[test]
+    fn test_second_stack_confirms_first() {
+        let mut pending = PendingTrash::default();
+        assert_eq!(pending.stage(iron(10)), None);
+        // Only one stack waits: the first one is deleted
+        assert_eq!(pending.stage((items::coal(), 3)), Some(iron(10)));
+        assert_eq!(pending.stack, Some((items::coal(), 3)));
+
+        // Confirming deletes it for good
+        assert_eq!(pending.take(), Some((items::coal(), 3)));
+        assert_eq!(pending.stack, None);
+    }
+
+    #[test]
+    fn test_undo_returns_stack_to_inventory() {
+        let mut inventory = PlayerInventory::default();
+        let mut held = HeldItem::default();
+        let mut pending = PendingTrash::default();
+        pending.stage(iron(12));
+
+        return_pending_trash(&mut inventory, &mut held, &mut pending);
+
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 12);
+        assert!(held.0.is_none());
+        assert!(pending.stack.is_none());
+    }
+
+    #[test]
+    fn test_timeout_only_after_confirm_window() {
+        let mut pending = PendingTrash::default();
+        assert!(!pending.tick(10.0), "nothing waiting, nothing expires");
+
+        pending.stage(iron(5));
+        assert!(!pending.tick(TRASH_CONFIRM_SECS - 0.5));
+        assert!(pending.tick(1.0));
+
+        // Expiry gives the stack back rather than deleting it
+        let mut inventory = PlayerInventory::default();
+        let mut held = HeldItem::default();
+        return_pending_trash(&mut inventory, &mut held, &mut pending);
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 5);
+    }
+
+    #[test]
+    fn test_close_confirms_or_returns() {
+        let mut inventory = PlayerInventory::default();
+        let mut held = HeldItem::default();
+        let mut pending = PendingTrash::default();
+
+        // Closing back to gameplay confirms
+        pending.stage(iron(7));
+        settle_pending_trash_on_close(true, &mut inventory, &mut held, &mut pending);
+        assert!(pending.stack.is_none());
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 0);
+
+        // A machine UI or pause taking over gives it back
+        pending.stage(iron(7));
+        settle_pending_trash_on_close(false, &mut inventory, &mut held, &mut pending);
+        assert!(pending.stack.is_none());
+        assert_eq!(inventory.get_total_count_by_id(items::iron_ingot()), 7);
+    }
+
+    #[test]
+    fn test_return_overflow_goes_to_hand_or_keeps_waiting() {
+        let mut inventory = PlayerInventory::default();
+        for slot in inventory.slots.iter_mut() {
+            *slot = Some((items::stone(), MAX_STACK_SIZE));
+        }
+        let mut held = HeldItem::default();
+        let mut pending = PendingTrash::default();
+
+        pending.stage(iron(4));
+        return_pending_trash(&mut inventory, &mut held, &mut pending);
+        assert_eq!(held.0, Some(iron(4)));
+
+        // Hand busy too: the stack stays in the trash instead of vanishing
+        pending.stage((items::coal(), 2));
+        return_pending_trash(&mut inventory, &mut held, &mut pending);
+        assert_eq!(pending.stack, Some((items::coal(), 2)));
+    }
+}
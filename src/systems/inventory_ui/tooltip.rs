@@ -1,85 +1,183 @@
 //! Inventory tooltip system
 
 use crate::components::*;
-use crate::player::{LocalPlayer, PlayerInventory};
+use crate::core::{items, ItemId};
+use crate::game_spec::{self, ItemInfoCache};
+use crate::player::{LocalPlatform, LocalPlayer, PlatformInventory, PlayerInventory};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-/// Update inventory tooltip to show item name when hovering over slots
+/// Gap between the cursor and the tooltip's top-left corner
+const TOOLTIP_CURSOR_OFFSET: f32 = 15.0;
+
+/// Hoverable UI elements that show an item tooltip (reduces parameter count)
+#[derive(SystemParam)]
+pub struct TooltipHoverSources<'w, 's> {
+    slot_query: Query<'w, 's, (&'static Interaction, &'static InventorySlotUI)>,
+    creative_query: Query<'w, 's, (&'static Interaction, &'static CreativeItemButton)>,
+    machine_slot_query: Query<'w, 's, (&'static Interaction, &'static GenericMachineSlotButton)>,
+    interacting: Res<'w, InteractingMachine>,
+    machine_query: Query<'w, 's, &'static Machine>,
+}
+
+impl TooltipHoverSources<'_, '_> {
+    /// Hovered item and stack count (None for creative catalog entries)
+    fn hovered_item(&self, inventory: Option<&PlayerInventory>) -> Option<(ItemId, Option<u32>)> {
+        // Inventory slots
+        if let Some(inventory) = inventory {
+            for (interaction, slot_ui) in self.slot_query.iter() {
+                if *interaction == Interaction::Hovered {
+                    if let Some((item_id, count)) = inventory.slots[slot_ui.0] {
+                        return Some((item_id, Some(count)));
+                    }
+                }
+            }
+        }
+
+        // Machine slots of the open machine UI
+        if let Some(machine) = self
+            .interacting
+            .0
+            .and_then(|entity| self.machine_query.get(entity).ok())
+        {
+            for (interaction, slot_btn) in self.machine_slot_query.iter() {
+                if *interaction != Interaction::Hovered {
+                    continue;
+                }
+                if slot_btn.is_fuel {
+                    if machine.slots.fuel > 0 {
+                        return Some((items::coal(), Some(machine.slots.fuel)));
+                    }
+                    continue;
+                }
+                let slots = if slot_btn.is_input {
+                    &machine.slots.inputs
+                } else {
+                    &machine.slots.outputs
+                };
+                if let Some(slot) = slots.get(slot_btn.slot_id as usize) {
+                    if let Some(item_id) = slot.item_id.filter(|_| slot.count > 0) {
+                        return Some((item_id, Some(slot.count)));
+                    }
+                }
+            }
+        }
+
+        // Creative catalog
+        self.creative_query
+            .iter()
+            .find(|(interaction, _)| **interaction == Interaction::Hovered)
+            .map(|(_, button)| (button.0, None))
+    }
+}
+
+/// Place the tooltip at the cursor, flipping it inside the window near the edges
+pub fn clamp_tooltip_position(cursor: Vec2, size: Vec2, window: Vec2) -> Vec2 {
+    let mut pos = cursor + Vec2::splat(TOOLTIP_CURSOR_OFFSET);
+    if pos.x + size.x > window.x {
+        pos.x = cursor.x - TOOLTIP_CURSOR_OFFSET - size.x;
+    }
+    if pos.y + size.y > window.y {
+        pos.y = cursor.y - TOOLTIP_CURSOR_OFFSET - size.y;
+    }
+    pos.max(Vec2::ZERO)
+}
+
+/// Update the item tooltip when hovering inventory slots, machine slots or catalog entries
 #[allow(clippy::too_many_arguments)]
 pub fn update_inventory_tooltip(
     inventory_open: Res<InventoryOpen>,
     local_player: Option<Res<LocalPlayer>>,
     inventory_query: Query<&PlayerInventory>,
     windows: Query<&Window>,
-    slot_query: Query<(&Interaction, &InventorySlotUI)>,
-    creative_query: Query<(&Interaction, &CreativeItemButton)>,
-    mut tooltip_query: Query<(&mut Node, &mut Visibility, &Children), With<InventoryTooltip>>,
-    mut text_query: Query<&mut Text>,
+    hover: TooltipHoverSources,
+    mut tooltip_query: Query<
+        (&mut Node, &mut Visibility, &mut Text, &ComputedNode),
+        With<InventoryTooltip>,
+    >,
+    item_info: Res<ItemInfoCache>,
+    current_quest: Res<CurrentQuest>,
+    local_platform: Option<Res<LocalPlatform>>,
+    platform_query: Query<&PlatformInventory>,
 ) {
-    let Ok((mut node, mut visibility, children)) = tooltip_query.single_mut() else {
+    let Ok((mut node, mut visibility, mut text, computed)) = tooltip_query.single_mut() else {
         return;
     };
 
-    // Hide tooltip if inventory is closed
-    if !inventory_open.0 {
+    // Hide tooltip unless the inventory or a machine UI is open
+    if !inventory_open.0 && hover.interacting.0.is_none() {
         *visibility = Visibility::Hidden;
         return;
     }
 
-    // Get inventory if available
     let inventory = local_player
         .as_ref()
         .and_then(|lp| inventory_query.get(lp.0).ok());
 
-    // Find hovered slot (inventory slots)
-    let mut hovered_item: Option<(crate::core::ItemId, Option<u32>)> = None;
-    if let Some(inventory) = inventory {
-        for (interaction, slot_ui) in slot_query.iter() {
-            if *interaction == Interaction::Hovered {
-                let slot_idx = slot_ui.0;
-                if let Some((item_id, count)) = inventory.slots[slot_idx] {
-                    hovered_item = Some((item_id, Some(count)));
-                    break;
-                }
-            }
+    let Some((item_id, count_opt)) = hover.hovered_item(inventory) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    // Position near the cursor, kept inside the window
+    if let Ok(window) = windows.single() {
+        if let Some(cursor_pos) = window.cursor_position() {
+            let size = computed.size() * computed.inverse_scale_factor();
+            let pos = clamp_tooltip_position(
+                cursor_pos,
+                size,
+                Vec2::new(window.width(), window.height()),
+            );
+            node.left = Val::Px(pos.x);
+            node.top = Val::Px(pos.y);
         }
     }
 
-    // Check creative catalog items if no inventory slot is hovered
-    if hovered_item.is_none() {
-        for (interaction, creative_btn) in creative_query.iter() {
-            if *interaction == Interaction::Hovered {
-                hovered_item = Some((creative_btn.0, None));
-                break;
-            }
+    let mut tooltip = item_info.tooltip(item_id, count_opt);
+    if count_opt.is_none() {
+        // Creative catalog item - add the unlock condition if locked
+        let no_platform = PlatformInventory::new();
+        let platform = local_platform
+            .as_ref()
+            .and_then(|lp| platform_query.get(lp.0).ok())
+            .unwrap_or(&no_platform);
+        let condition = game_spec::locked_recipe_for_output(item_id, &current_quest, platform)
+            .and_then(|recipe| recipe.unlock.description());
+        if let Some(condition) = condition {
+            tooltip.push_str(&format!("\n[ロック] {}", condition));
         }
     }
+    if text.0 != tooltip {
+        text.0 = tooltip;
+    }
+}
 
-    if let Some((item_id, count_opt)) = hovered_item {
-        *visibility = Visibility::Inherited;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Position tooltip near the mouse cursor
-        if let Ok(window) = windows.single() {
-            if let Some(cursor_pos) = window.cursor_position() {
-                // Offset tooltip to bottom-right of cursor
-                node.left = Val::Px(cursor_pos.x + 15.0);
-                node.top = Val::Px(cursor_pos.y + 15.0);
-            }
-        }
+    #[test]
+    fn test_tooltip_stays_inside_window() {
+        let window = Vec2::new(800.0, 600.0);
+        let size = Vec2::new(200.0, 80.0);
 
-        // Update tooltip text
-        if let Some(&child) = children.first() {
-            if let Ok(mut text) = text_query.get_mut(child) {
-                let name = item_id.name().unwrap_or("unknown");
-                if let Some(count) = count_opt {
-                    text.0 = format!("{} ({})", name, count);
-                } else {
-                    // Creative catalog item - just show name
-                    text.0 = name.to_string();
-                }
-            }
-        }
-    } else {
-        *visibility = Visibility::Hidden;
+        // Room to the bottom-right: offset from the cursor
+        assert_eq!(
+            clamp_tooltip_position(Vec2::new(100.0, 100.0), size, window),
+            Vec2::new(115.0, 115.0)
+        );
+
+        // Near the right/bottom edges: flipped to the other side of the cursor
+        let pos = clamp_tooltip_position(Vec2::new(750.0, 580.0), size, window);
+        assert_eq!(pos, Vec2::new(535.0, 485.0));
+        assert!(pos.x + size.x <= window.x && pos.y + size.y <= window.y);
+
+        // Never pushed past the top-left corner
+        let big = Vec2::new(900.0, 700.0);
+        assert_eq!(
+            clamp_tooltip_position(Vec2::new(10.0, 10.0), big, window),
+            Vec2::ZERO
+        );
     }
 }
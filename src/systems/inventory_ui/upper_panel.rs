@@ -2,6 +2,7 @@
 
 use crate::components::*;
 use crate::core::{items, ItemId};
+use crate::game_spec;
 use crate::player::{LocalPlatform, PlatformInventory};
 use crate::setup::ui::{
     UpperPanelPageText, UpperPanelSlot, UpperPanelSlotCount, UpperPanelSlotImage, SLOT_BG,
@@ -12,24 +13,30 @@ use bevy::prelude::*;
 /// Get all available items for the upper panel based on creative mode and category
 pub(super) fn get_filtered_items(creative_mode: bool, category: &ItemCategory) -> Vec<ItemId> {
     if creative_mode {
-        // In creative mode, show all items
+        // In creative mode, show all items, then those added by mods
         let all_items = [
             items::grass(),
             items::stone(),
             items::iron_ore(),
             items::copper_ore(),
             items::coal(),
+            items::crystal(),
             items::iron_ingot(),
             items::copper_ingot(),
             items::miner_block(),
             items::conveyor_block(),
+            items::conveyor_block_mk2(),
+            items::conveyor_block_mk3(),
             items::furnace_block(),
             items::crusher_block(),
             items::assembler_block(),
+            items::recycler_block(),
+            items::hopper_block(),
         ];
 
         all_items
             .into_iter()
+            .chain(items::runtime_items())
             .filter(|item| category.matches(*item))
             .collect()
     } else {
@@ -337,20 +344,40 @@ pub fn upper_panel_category_click(
     }
 }
 
-/// Update creative catalog item sprites
+/// Tint for catalog items whose recipe is still locked
+const LOCKED_ITEM_TINT: Color = Color::srgba(0.35, 0.35, 0.35, 0.6);
+
+/// Update creative catalog item sprites (items from locked recipes are greyed out)
 pub fn update_creative_catalog_sprites(
     inventory_open: Res<InventoryOpen>,
     creative_mode: Res<CreativeMode>,
     item_sprites: Res<ItemSprites>,
+    current_quest: Res<CurrentQuest>,
+    local_platform: Option<Res<LocalPlatform>>,
+    platform_query: Query<&PlatformInventory>,
     mut query: Query<(&CreativeItemImage, &mut ImageNode, &mut Visibility)>,
 ) {
     // Only show sprites when inventory is open in creative mode
     let should_show = inventory_open.0 && creative_mode.enabled;
 
+    let no_platform = PlatformInventory::new();
+    let platform = local_platform
+        .and_then(|lp| platform_query.get(lp.0).ok())
+        .unwrap_or(&no_platform);
+
     for (item, mut image, mut visibility) in query.iter_mut() {
         if should_show {
-            if let Some(sprite) = item_sprites.get_id(ItemId::from(item.0)) {
+            let item_id = ItemId::from(item.0);
+            if let Some(sprite) = item_sprites.get_id(item_id) {
                 image.image = sprite;
+                image.color =
+                    if game_spec::locked_recipe_for_output(item_id, &current_quest, platform)
+                        .is_some()
+                    {
+                        LOCKED_ITEM_TINT
+                    } else {
+                        Color::WHITE
+                    };
                 *visibility = Visibility::Visible;
             } else {
                 *visibility = Visibility::Hidden;
@@ -6,9 +6,20 @@ use crate::setup::ui::{UpperPanel, UpperPanelTabs};
 use bevy::prelude::*;
 use tracing::{info, warn};
 
-/// Set the UI open state (no-op, kept for API compatibility)
-pub fn set_ui_open_state(_ui_open: bool) {
-    // No-op
+/// Mirror whether a screen owns the cursor to `data-ui-open` on the page body
+///
+/// Lets the hosting web page tell when a screen (or the pause menu) is open.
+/// No-op on native builds.
+pub fn set_ui_open_state(ui_open: bool) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(body) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.body())
+    {
+        let _ = body.set_attribute("data-ui-open", if ui_open { "true" } else { "false" });
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = ui_open;
 }
 
 /// Return held item to inventory when closing
@@ -39,6 +50,8 @@ pub fn update_inventory_visibility(
     local_platform: Option<Res<crate::player::LocalPlatform>>,
     mut inventory_query: Query<&mut PlayerInventory>,
     mut held_item: ResMut<HeldItem>,
+    mut pending_trash: ResMut<PendingTrash>,
+    ui_state: Res<UIState>,
     creative_mode: Res<CreativeMode>,
     mut ui_query: Query<&mut Visibility, With<InventoryUI>>,
     mut overlay_query: Query<
@@ -93,6 +106,12 @@ pub fn update_inventory_visibility(
         if let Some(ref local_player) = local_player {
             if let Ok(mut inventory) = inventory_query.get_mut(local_player.0) {
                 return_held_item_to_inventory(&mut inventory, &mut held_item);
+                super::slot_interaction::settle_pending_trash_on_close(
+                    ui_state.current() == UIContext::Gameplay,
+                    &mut inventory,
+                    &mut held_item,
+                    &mut pending_trash,
+                );
             }
         }
     }
@@ -0,0 +1,260 @@
+//! Machine hover card
+//!
+//! Resting the crosshair on a machine or conveyor for a moment shows a small
+//! card with its state (fuel, slots, progress), so a stalled furnace can be
+//! diagnosed without opening its UI or the debug HUD. Only the targeted
+//! entity is queried. Signs show their text the same way.
+
+use bevy::prelude::*;
+
+use crate::components::{DebugHudState, Machine, MachineHoverCard, MachineSlot, UIState};
+use crate::game_spec::drill_spec::DRILL_DURABILITY;
+use crate::game_spec::{ItemInfoCache, ProcessType};
+use crate::settings::GameSettings;
+use crate::sign::Sign;
+use crate::{Conveyor, TargetBlock};
+
+/// Seconds the crosshair must rest on a machine before the card shows
+pub const MACHINE_HOVER_DWELL_SECS: f32 = 0.5;
+
+/// Dwell timer for the hover card
+#[derive(Resource, Debug, Default)]
+pub struct MachineHoverState {
+    /// Entity the crosshair is resting on
+    pub target: Option<Entity>,
+    /// Seconds spent on `target`
+    pub dwell: f32,
+}
+
+impl MachineHoverState {
+    /// Advance the timer; returns the entity to show once the dwell time is reached
+    ///
+    /// A different target (or none) restarts the timer.
+    pub fn update(&mut self, target: Option<Entity>, delta: f32) -> Option<Entity> {
+        if target != self.target {
+            self.target = target;
+            self.dwell = 0.0;
+            return None;
+        }
+        let target = self.target?;
+        self.dwell += delta;
+        (self.dwell >= MACHINE_HOVER_DWELL_SECS).then_some(target)
+    }
+
+    /// Forget the current target
+    pub fn clear(&mut self) {
+        self.target = None;
+        self.dwell = 0.0;
+    }
+}
+
+fn slots_label(slots: &[MachineSlot], item_info: &ItemInfoCache) -> String {
+    let stacks: Vec<String> = slots
+        .iter()
+        .filter_map(|slot| {
+            let item_id = slot.item_id.filter(|_| slot.count > 0)?;
+            let name = item_info
+                .get(item_id)
+                .map(|info| info.name.clone())
+                .unwrap_or_else(|| item_id.display_name().to_string());
+            Some(format!("{} x{}", name, slot.count))
+        })
+        .collect();
+    if stacks.is_empty() {
+        "-".to_string()
+    } else {
+        stacks.join(", ")
+    }
+}
+
+fn enabled_label(enabled: bool) -> &'static str {
+    if enabled {
+        "ON"
+    } else {
+        "OFF"
+    }
+}
+
+/// Card text for a machine
+pub fn machine_card_text(machine: &Machine, item_info: &ItemInfoCache) -> String {
+    let name = item_info
+        .get(machine.spec.item_id())
+        .map(|info| info.name.as_str())
+        .unwrap_or(machine.spec.name);
+    let mut lines = vec![
+        format!("{} [{}]", name, enabled_label(machine.enabled)),
+        format!("Status: {}", machine.status.label()),
+        format!("Progress: {}%", (machine.progress * 100.0).round() as u32),
+    ];
+    if machine.spec.requires_fuel {
+        lines.push(format!("Fuel: {}", machine.slots.fuel));
+    }
+    if machine.spec.process_type == ProcessType::AutoGenerate {
+        let drill = match &machine.slots.drill {
+            Some(drill) => {
+                let name = item_info
+                    .get(drill.item_id)
+                    .map(|info| info.name.clone())
+                    .unwrap_or_else(|| drill.item_id.display_name().to_string());
+                format!("{} ({}/{})", name, drill.durability, DRILL_DURABILITY)
+            }
+            None => "-".to_string(),
+        };
+        lines.push(format!("Drill: {}", drill));
+    }
+    if !machine.slots.inputs.is_empty() {
+        lines.push(format!(
+            "Input: {}",
+            slots_label(&machine.slots.inputs, item_info)
+        ));
+    }
+    lines.push(format!(
+        "Output: {}",
+        slots_label(&machine.slots.outputs, item_info)
+    ));
+    lines.join("\n")
+}
+
+/// Card text for a conveyor
+pub fn conveyor_card_text(conveyor: &Conveyor, item_info: &ItemInfoCache) -> String {
+    let tier = conveyor.tier_item();
+    let name = item_info
+        .get(tier)
+        .map(|info| info.name.as_str())
+        .unwrap_or(tier.display_name());
+    [
+        format!("{} [{}]", name, enabled_label(conveyor.enabled)),
+        format!("Direction: {:?}", conveyor.output_direction),
+        format!("Items: {}", conveyor.total_item_count()),
+    ]
+    .join("\n")
+}
+
+/// Card text for a sign
+pub fn sign_card_text(sign: &Sign, item_info: &ItemInfoCache) -> String {
+    let item = crate::core::items::sign_block();
+    let name = item_info
+        .get(item)
+        .map(|info| info.name.as_str())
+        .unwrap_or(item.display_name());
+    if sign.text.is_empty() {
+        format!("{}\n(空白)", name)
+    } else {
+        format!("{}\n{}", name, sign.text)
+    }
+}
+
+/// Show the hover card for the targeted machine after the dwell time
+#[allow(clippy::too_many_arguments)]
+pub fn update_machine_hover_card(
+    time: Res<Time>,
+    target: Res<TargetBlock>,
+    settings: Res<GameSettings>,
+    ui_state: Res<UIState>,
+    debug_hud: Res<DebugHudState>,
+    item_info: Res<ItemInfoCache>,
+    mut state: ResMut<MachineHoverState>,
+    machines: Query<&Machine>,
+    conveyors: Query<&Conveyor>,
+    signs: Query<(Entity, &Sign)>,
+    mut card_query: Query<(&mut Text, &mut Visibility), With<MachineHoverCard>>,
+) {
+    let Ok((mut text, mut visibility)) = card_query.single_mut() else {
+        return;
+    };
+
+    if !settings.machine_hover_card || !ui_state.is_gameplay() || debug_hud.visible {
+        state.clear();
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    // Signs are world blocks, so they are found by the break target
+    let hovered = target.machine_target.or_else(|| {
+        let pos = target.break_target?;
+        signs
+            .iter()
+            .find(|(_, sign)| sign.position == pos)
+            .map(|(entity, _)| entity)
+    });
+    let Some(entity) = state.update(hovered, time.delta_secs()) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let card = if let Ok(machine) = machines.get(entity) {
+        machine_card_text(machine, &item_info)
+    } else if let Ok(conveyor) = conveyors.get(entity) {
+        conveyor_card_text(conveyor, &item_info)
+    } else if let Ok((_, sign)) = signs.get(entity) {
+        sign_card_text(sign, &item_info)
+    } else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    if text.0 != card {
+        text.0 = card;
+    }
+    *visibility = Visibility::Inherited;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities() -> (Entity, Entity) {
+        let mut world = World::new();
+        (world.spawn_empty().id(), world.spawn_empty().id())
+    }
+
+    #[test]
+    fn test_card_shows_after_dwell() {
+        let (furnace, _) = entities();
+        let mut state = MachineHoverState::default();
+
+        // First frame on the target only starts the timer
+        assert_eq!(state.update(Some(furnace), 0.2), None);
+        assert_eq!(state.update(Some(furnace), 0.3), None);
+        assert_eq!(state.update(Some(furnace), 0.3), Some(furnace));
+        assert_eq!(state.update(Some(furnace), 0.3), Some(furnace));
+    }
+
+    #[test]
+    fn test_target_change_resets_timer() {
+        let (furnace, belt) = entities();
+        let mut state = MachineHoverState::default();
+
+        state.update(Some(furnace), 0.3);
+        state.update(Some(furnace), 0.3);
+        // Moving to the belt hides the card and starts over
+        assert_eq!(state.update(Some(belt), 0.3), None);
+        assert_eq!(state.update(Some(belt), 0.3), None);
+        assert_eq!(state.update(Some(belt), 0.3), Some(belt));
+
+        // Looking away and back also starts over
+        assert_eq!(state.update(None, 0.3), None);
+        assert_eq!(state.update(Some(belt), 0.3), None);
+    }
+
+    #[test]
+    fn test_sign_card_shows_text() {
+        use crate::Direction;
+
+        let item_info = ItemInfoCache::default();
+        let sign = Sign::new(IVec3::ZERO, Direction::North).with_text("smelting row\ncopper");
+        let card = sign_card_text(&sign, &item_info);
+        assert!(card.ends_with("\nsmelting row\ncopper"), "{}", card);
+
+        let blank = Sign::new(IVec3::ZERO, Direction::North);
+        assert!(sign_card_text(&blank, &item_info).ends_with("(空白)"));
+    }
+
+    #[test]
+    fn test_no_target_never_shows() {
+        let mut state = MachineHoverState::default();
+        for _ in 0..10 {
+            assert_eq!(state.update(None, 1.0), None);
+        }
+        assert_eq!(state.dwell, 0.0);
+    }
+}
@@ -7,30 +7,42 @@ pub mod chunk;
 pub mod command;
 pub mod cursor;
 pub mod debug_ui;
+pub mod hibernation;
 pub mod hotbar;
 pub mod invariants;
 pub mod inventory_ui;
+pub mod machine_hover;
+pub mod ore_scanner;
 pub mod player;
 pub mod quest;
+pub mod quick_select;
 pub mod targeting;
 pub mod tutorial;
 pub mod ui_navigation;
 pub mod ui_visibility;
+pub mod world_border;
+pub mod wrench;
 
 pub use block_operations::*;
 pub use chunk::*;
 pub use command::*;
 pub use cursor::*;
 pub use debug_ui::*;
+pub use hibernation::*;
 pub use hotbar::*;
 pub use invariants::*;
 pub use inventory_ui::*;
+pub use machine_hover::*;
+pub use ore_scanner::*;
 pub use player::*;
 pub use quest::*;
+pub use quick_select::*;
 pub use targeting::*;
 pub use tutorial::*;
 pub use ui_navigation::*;
 pub use ui_visibility::*;
+pub use world_border::*;
+pub use wrench::*;
 
 // Re-export conveyor systems from logistics module
 pub use crate::logistics::conveyor::*;
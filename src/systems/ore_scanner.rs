@@ -0,0 +1,287 @@
+//! Ore scanner: right-click to highlight nearby ores through the terrain
+//!
+//! A scan looks at every block in a `SCAN_RADIUS` sphere around the player and
+//! marks the nearest `SCAN_MAX_RESULTS` ores with colored wireframe cubes for
+//! `SCAN_DISPLAY_SECS`. The markers render on the overlay camera's layer, so
+//! they show through the terrain. Scanning again replaces the old markers.
+//!
+//! The sphere is about 17k block lookups, well under a millisecond, so the
+//! scan runs inline instead of as an async task.
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+
+use crate::components::{CreativeMode, HotbarSlotCooldown};
+use crate::core::{items, ItemId};
+use crate::meshes::create_wireframe_cube_mesh;
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::world::WorldData;
+use crate::{ContinuousActionTimer, InputStateResourcesWithCursor, PlayerCamera};
+
+/// Scan radius in blocks
+pub const SCAN_RADIUS: i32 = 16;
+
+/// Most ores marked by one scan (nearest first)
+pub const SCAN_MAX_RESULTS: usize = 64;
+
+/// How long the markers stay (seconds)
+pub const SCAN_DISPLAY_SECS: f32 = 10.0;
+
+/// Time between scans outside creative mode (seconds)
+pub const SCAN_COOLDOWN_SECS: f32 = 30.0;
+
+/// Scanner cooldown and the markers of the last scan
+#[derive(Resource, Debug, Default)]
+pub struct OreScanner {
+    /// Seconds until the next scan is allowed
+    cooldown: f32,
+    /// Seconds the current markers stay
+    display: f32,
+    markers: Vec<Entity>,
+}
+
+impl OreScanner {
+    /// Whether a scan may start now (creative mode ignores the cooldown)
+    pub fn can_scan(&self, creative: bool) -> bool {
+        creative || self.cooldown <= 0.0
+    }
+
+    /// A scan was started
+    pub fn start(&mut self) {
+        self.cooldown = SCAN_COOLDOWN_SECS;
+        self.display = SCAN_DISPLAY_SECS;
+    }
+
+    /// Count down the cooldown and the display time
+    pub fn tick(&mut self, delta: f32) {
+        self.cooldown = (self.cooldown - delta).max(0.0);
+        self.display = (self.display - delta).max(0.0);
+    }
+
+    /// Remaining cooldown as a fraction (1.0 = just scanned, 0.0 = ready)
+    pub fn cooldown_fraction(&self) -> f32 {
+        (self.cooldown / SCAN_COOLDOWN_SECS).clamp(0.0, 1.0)
+    }
+}
+
+/// Every ore block within `radius` of `center`
+pub fn scan_ores(world: &WorldData, center: IVec3, radius: i32) -> Vec<(IVec3, ItemId)> {
+    let mut found = Vec::new();
+    for x in -radius..=radius {
+        for y in -radius..=radius {
+            for z in -radius..=radius {
+                let offset = IVec3::new(x, y, z);
+                if offset.length_squared() > radius * radius {
+                    continue;
+                }
+                let pos = center + offset;
+                if let Some(item_id) = world.get_block(pos) {
+                    if items::is_ore(item_id) {
+                        found.push((pos, item_id));
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// The `max` results closest to `center` (ties broken by position for a stable order)
+pub fn nearest_ores(
+    mut found: Vec<(IVec3, ItemId)>,
+    center: IVec3,
+    max: usize,
+) -> Vec<(IVec3, ItemId)> {
+    found.sort_by_key(|(pos, _)| ((*pos - center).length_squared(), pos.to_array()));
+    found.truncate(max);
+    found
+}
+
+/// Marker color per ore
+fn ore_color(item_id: ItemId) -> Color {
+    if item_id == items::iron_ore() {
+        Color::srgba(0.95, 0.6, 0.4, 0.9)
+    } else if item_id == items::copper_ore() {
+        Color::srgba(0.3, 0.9, 0.7, 0.9)
+    } else {
+        // Coal
+        Color::srgba(0.75, 0.75, 0.8, 0.9)
+    }
+}
+
+/// Right-click with the scanner selected starts a scan
+///
+/// Runs before the wrench and block placement and swallows the right-click.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_ore_scanner(
+    mut commands: Commands,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    input_resources: InputStateResourcesWithCursor,
+    world_data: Res<WorldData>,
+    creative_mode: Res<CreativeMode>,
+    mut scanner: ResMut<OreScanner>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let holding_scanner = local_player
+        .and_then(|lp| inventories.get(lp.0).ok())
+        .and_then(|inv| inv.selected_item_id())
+        == Some(items::ore_scanner());
+    if !holding_scanner || !mouse_button.just_pressed(MouseButton::Right) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    // The scanner is never placed
+    mouse_button.clear_just_pressed(MouseButton::Right);
+    action_timer.place_timer.reset();
+
+    if !scanner.can_scan(creative_mode.enabled) {
+        return;
+    }
+
+    for entity in scanner.markers.drain(..) {
+        commands.entity(entity).despawn();
+    }
+
+    let center = camera_transform.translation().floor().as_ivec3();
+    let ores = nearest_ores(
+        scan_ores(&world_data, center, SCAN_RADIUS),
+        center,
+        SCAN_MAX_RESULTS,
+    );
+
+    let mesh = meshes.add(create_wireframe_cube_mesh());
+    let mut ore_materials: Vec<(ItemId, Handle<StandardMaterial>)> = Vec::new();
+    for (pos, item_id) in ores {
+        let material = match ore_materials.iter().find(|(id, _)| *id == item_id) {
+            Some((_, material)) => material.clone(),
+            None => {
+                let material = materials.add(StandardMaterial {
+                    base_color: ore_color(item_id),
+                    unlit: true,
+                    alpha_mode: AlphaMode::Blend,
+                    ..default()
+                });
+                ore_materials.push((item_id, material.clone()));
+                material
+            }
+        };
+        let entity = commands
+            .spawn((
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material),
+                Transform::from_translation(pos.as_vec3() + Vec3::splat(0.5)),
+                NotShadowCaster,
+                // Overlay layer: drawn after the world, so visible through terrain
+                RenderLayers::layer(1),
+            ))
+            .id();
+        scanner.markers.push(entity);
+    }
+    scanner.start();
+}
+
+/// Count down the scanner, clear expired markers and show the cooldown on the hotbar
+pub fn update_ore_scanner(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut scanner: ResMut<OreScanner>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    mut overlay_query: Query<(&HotbarSlotCooldown, &mut Node, &mut Visibility)>,
+) {
+    scanner.tick(time.delta_secs());
+    if scanner.display <= 0.0 && !scanner.markers.is_empty() {
+        for entity in scanner.markers.drain(..) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let inventory = local_player.and_then(|lp| inventories.get(lp.0).ok());
+    let fraction = scanner.cooldown_fraction();
+    for (slot, mut node, mut vis) in overlay_query.iter_mut() {
+        let is_scanner =
+            inventory.and_then(|inv| inv.get_slot_item_id(slot.0)) == Some(items::ore_scanner());
+        if is_scanner && fraction > 0.0 {
+            node.height = Val::Percent(fraction * 100.0);
+            *vis = Visibility::Inherited;
+        } else {
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_ores_keeps_closest_64() {
+        let center = IVec3::new(10, 10, 10);
+        let iron = items::iron_ore();
+        let coal = items::coal();
+        // 100 ores on a line, farthest first
+        let found: Vec<_> = (0..100)
+            .rev()
+            .map(|i| {
+                let item = if i % 2 == 0 { iron } else { coal };
+                (center + IVec3::new(i, 0, 0), item)
+            })
+            .collect();
+
+        let nearest = nearest_ores(found, center, SCAN_MAX_RESULTS);
+        assert_eq!(nearest.len(), SCAN_MAX_RESULTS);
+        assert_eq!(nearest[0], (center, iron));
+        assert_eq!(nearest[63].0, center + IVec3::new(63, 0, 0));
+        assert!(nearest
+            .iter()
+            .all(|(pos, _)| (*pos - center).length_squared() < 64 * 64));
+    }
+
+    #[test]
+    fn test_nearest_ores_under_cap_keeps_all() {
+        let center = IVec3::ZERO;
+        let found = vec![
+            (IVec3::new(0, -5, 0), items::copper_ore()),
+            (IVec3::new(1, 0, 0), items::coal()),
+        ];
+        let nearest = nearest_ores(found, center, SCAN_MAX_RESULTS);
+        assert_eq!(
+            nearest,
+            vec![
+                (IVec3::new(1, 0, 0), items::coal()),
+                (IVec3::new(0, -5, 0), items::copper_ore()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_cooldown_gating() {
+        let mut scanner = OreScanner::default();
+        assert!(scanner.can_scan(false));
+
+        scanner.start();
+        assert!(!scanner.can_scan(false));
+        assert!(scanner.can_scan(true)); // Creative ignores the cooldown
+        assert_eq!(scanner.cooldown_fraction(), 1.0);
+
+        scanner.tick(SCAN_COOLDOWN_SECS - 1.0);
+        assert!(!scanner.can_scan(false));
+        assert!(scanner.display <= 0.0); // Markers expired long before
+
+        scanner.tick(1.0);
+        assert!(scanner.can_scan(false));
+        assert_eq!(scanner.cooldown_fraction(), 0.0);
+    }
+}
@@ -6,24 +6,21 @@
 //! - WASD + Space/Shift for fly movement (no collision)
 
 use crate::components::{
-    CommandInputState, ContinuousActionTimer, CursorLockState, InputStateResourcesWithCursor,
-    InteractingMachine, InventoryOpen, PauseUI, Player, PlayerCamera, TutorialShown, UIAction,
-    UIContext, UIState,
+    ContinuousActionTimer, CursorLockState, InputStateResourcesWithCursor, PauseUI, Player,
+    PlayerCamera, TutorialShown, UIAction, UIContext, UIState,
 };
 use crate::input::{GameAction, InputManager};
 use crate::settings::GameSettings;
-use crate::systems::cursor;
+use crate::systems::quick_select::QuickSelectMenu;
+use crate::world::WorldData;
 use crate::{KEY_ROTATION_SPEED, PLAYER_SPEED};
 use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
-use bevy::window::{CursorOptions, PrimaryWindow};
-use tracing::info;
 
 /// CAD-style controls: no cursor lock needed
 /// Left as no-op for compatibility with system registration
 pub fn toggle_cursor_lock(
     _input: Res<InputManager>,
-    _cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
     _ui_state: Res<UIState>,
     _cursor_state: ResMut<CursorLockState>,
 ) {
@@ -41,23 +38,24 @@ pub fn player_look(
     key_input: Res<ButtonInput<KeyCode>>,
     accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
     mut cursor_lock_state: ResMut<CursorLockState>,
-    inventory_open: Res<InventoryOpen>,
-    interacting_machine: Res<InteractingMachine>,
-    command_state: Res<CommandInputState>,
+    input_resources: InputStateResourcesWithCursor,
     tutorial_shown: Res<TutorialShown>,
     settings: Res<GameSettings>,
+    quick_select: Option<Res<QuickSelectMenu>>,
 ) {
     // Block look while tutorial is showing
     if !tutorial_shown.0 {
         return;
     }
 
-    // Don't look around while any UI is open or game is paused
-    if inventory_open.0
-        || interacting_machine.0.is_some()
-        || command_state.open
-        || cursor_lock_state.paused
-    {
+    // Mouse movement picks a ring segment while quick-select is open
+    if quick_select.is_some_and(|menu| menu.open) {
+        return;
+    }
+
+    // Don't look around while any UI is open, the game is paused, the window
+    // is unfocused or the free camera owns look input
+    if !input_resources.get_state().allows_camera() {
         return;
     }
 
@@ -135,6 +133,7 @@ pub fn player_look(
 }
 
 /// CAD-style fly movement (no collision, no gravity)
+/// The player stays inside the world border
 #[allow(clippy::too_many_arguments)]
 pub fn player_move(
     time: Res<Time>,
@@ -143,6 +142,7 @@ pub fn player_move(
     camera_query: Query<&PlayerCamera>,
     input_resources: InputStateResourcesWithCursor,
     tutorial_shown: Res<TutorialShown>,
+    world_data: Res<WorldData>,
 ) {
     // Block movement while tutorial is showing
     if !tutorial_shown.0 {
@@ -193,7 +193,8 @@ pub fn player_move(
 
     if direction.length_squared() > 0.0 {
         direction = direction.normalize();
-        player_transform.translation += direction * PLAYER_SPEED * dt;
+        let moved = player_transform.translation + direction * PLAYER_SPEED * dt;
+        player_transform.translation = world_data.gen_config.border.clamp(moved);
     }
 }
 
@@ -205,7 +206,7 @@ pub fn tick_action_timers(time: Res<Time>, mut action_timer: ResMut<ContinuousAc
 }
 
 /// Update pause UI visibility based on UIState
-/// Note: Cursor control moved to sync_cursor_to_ui_state (PostUpdate)
+/// Note: the cursor follows UIState through CursorController (apply_cursor_controller)
 pub fn update_pause_ui(
     ui_state: Res<UIState>,
     mut pause_query: Query<&mut Visibility, With<PauseUI>>,
@@ -228,33 +229,6 @@ pub fn update_pause_ui(
     };
 }
 
-/// Initialize cursor state at startup (CAD-style: always visible)
-///
-/// In CAD-style controls, cursor is always visible.
-/// Camera rotation is controlled by middle-drag or Alt+left-drag.
-pub fn initialize_cursor(
-    mut cursor_query: Query<&mut CursorOptions, With<PrimaryWindow>>,
-    mut frame_count: Local<u32>,
-) {
-    // Wait until window is ready (5 frames after startup)
-    *frame_count += 1;
-    if *frame_count < 5 {
-        return;
-    }
-    if *frame_count > 5 {
-        return; // Only run once on frame 5
-    }
-
-    if let Ok(mut cursor_options) = cursor_query.single_mut() {
-        // CAD-style: always visible, never grabbed
-        cursor::release_cursor(&mut cursor_options);
-        info!(
-            "[CURSOR] Initialized (frame {}): CAD-style (always visible)",
-            *frame_count
-        );
-    }
-}
-
 /// Handle pause menu button clicks
 pub fn handle_pause_menu_buttons(
     mut interaction_query: Query<
@@ -265,7 +239,6 @@ pub fn handle_pause_menu_buttons(
         ),
         Changed<Interaction>,
     >,
-    mut cursor_state: ResMut<CursorLockState>,
     mut app_exit: MessageWriter<bevy::app::AppExit>,
     mut action_writer: MessageWriter<UIAction>,
 ) {
@@ -274,16 +247,22 @@ pub fn handle_pause_menu_buttons(
             Interaction::Pressed => {
                 match button_type {
                     crate::setup::ui::PauseMenuButton::Resume => {
-                        // Resume game
-                        cursor_state.paused = false;
+                        // Resume game (the cursor follows UIState)
                         action_writer.write(UIAction::Pop);
                     }
                     crate::setup::ui::PauseMenuButton::Settings => {
                         // Open settings (will implement in D.3)
                         action_writer.write(UIAction::Push(UIContext::Settings));
                     }
+                    crate::setup::ui::PauseMenuButton::Stats => {
+                        action_writer.write(UIAction::Push(UIContext::Stats));
+                    }
+                    crate::setup::ui::PauseMenuButton::QuestLog => {
+                        action_writer.write(UIAction::Push(UIContext::QuestLog));
+                    }
                     crate::setup::ui::PauseMenuButton::Quit => {
-                        // Exit application (native only)
+                        // Exit application (native only); save_on_exit writes the
+                        // world synchronously in Last, before the runner sees AppExit
                         #[cfg(not(target_arch = "wasm32"))]
                         app_exit.write(bevy::app::AppExit::Success);
                     }
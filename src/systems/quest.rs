@@ -2,7 +2,12 @@
 
 use crate::components::*;
 use crate::core::ItemId;
+use crate::events::game_events::QuestRewardsClaimed;
 use crate::input::{GameAction, InputManager};
+use crate::logistics::delivery_filter::REJECT_BUFFER_CAPACITY;
+use crate::logistics::timed_contract::contract_status_text;
+use crate::logistics::{DeliveryContract, DeliveryFilter, DeliveryPolicy, TimedContracts};
+use crate::network::NetClient;
 use crate::player::{LocalPlatform, LocalPlatformInventory, PlatformInventory};
 use crate::{game_spec, BLOCK_SIZE, PLATFORM_SIZE};
 use bevy::prelude::*;
@@ -10,7 +15,6 @@ use bevy::prelude::*;
 /// Quest definition structure (runtime representation)
 /// Uses ItemId for all item references.
 pub struct QuestDef {
-    #[allow(dead_code)]
     pub id: &'static str,
     pub description: &'static str,
     pub required_items: Vec<(ItemId, u32)>,
@@ -72,6 +76,95 @@ pub fn quest_progress_check(mut current_quest: ResMut<CurrentQuest>, quest_cache
     }
 }
 
+/// Whether every main quest has been delivered and claimed
+///
+/// The last quest stays current after its rewards are claimed.
+pub fn main_quests_finished(current_quest: &CurrentQuest, quest_count: usize) -> bool {
+    current_quest.index >= quest_count
+        || (current_quest.index + 1 == quest_count && current_quest.rewards_claimed)
+}
+
+/// Where a quest stands, as shown in the quest log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestLogStatus {
+    /// Rewards claimed (Unix ms; None for saves made before claim times were kept)
+    Claimed(Option<u64>),
+    /// Current quest, items not delivered yet
+    Active,
+    /// Current quest delivered, rewards waiting
+    Claimable,
+    /// Not reached yet
+    Locked,
+}
+
+/// Status of every main quest, in quest order
+pub fn quest_log_entries(quests: &[QuestDef], current_quest: &CurrentQuest) -> Vec<QuestLogStatus> {
+    quests
+        .iter()
+        .enumerate()
+        .map(|(i, quest)| {
+            let claimed_at = current_quest.claimed_at.get(quest.id).copied();
+            // The last quest stays current after its rewards are claimed
+            if i < current_quest.index
+                || (i == current_quest.index && current_quest.rewards_claimed)
+            {
+                QuestLogStatus::Claimed(claimed_at)
+            } else if i > current_quest.index {
+                QuestLogStatus::Locked
+            } else if current_quest.completed {
+                QuestLogStatus::Claimable
+            } else {
+                QuestLogStatus::Active
+            }
+        })
+        .collect()
+}
+
+/// Current time as Unix milliseconds (0 if the clock is before the epoch)
+pub fn unix_millis_now() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Give out the current quest's rewards and move on to the next quest
+///
+/// Shared by the Q key and the quest log's claim button. Records the claim
+/// time in `CurrentQuest::claimed_at`. Returns the claimed quest's id, or
+/// None if the current quest has nothing to claim.
+pub fn claim_current_quest(
+    current_quest: &mut CurrentQuest,
+    quests: &[QuestDef],
+    platform_inventory: &mut PlatformInventory,
+    now_ms: u64,
+) -> Option<&'static str> {
+    if !current_quest.completed || current_quest.rewards_claimed {
+        return None;
+    }
+    let quest = quests.get(current_quest.index)?;
+
+    // Add rewards to PlatformInventory (machines and items)
+    for (item_id, amount) in &quest.rewards {
+        platform_inventory.add_item_by_id(*item_id, *amount);
+    }
+
+    current_quest.rewards_claimed = true;
+    current_quest
+        .claimed_at
+        .insert(quest.id.to_string(), now_ms);
+
+    // Move to next quest
+    if current_quest.index + 1 < quests.len() {
+        current_quest.index += 1;
+        current_quest.completed = false;
+        current_quest.rewards_claimed = false;
+    }
+
+    Some(quest.id)
+}
+
 /// Claim quest rewards with Q key
 pub fn quest_claim_rewards(
     input: Res<InputManager>,
@@ -79,6 +172,7 @@ pub fn quest_claim_rewards(
     mut platform_inventory: LocalPlatformInventory,
     command_state: Res<CommandInputState>,
     quest_cache: Res<QuestCache>,
+    mut claimed_events: MessageWriter<QuestRewardsClaimed>,
 ) {
     // Don't process while command input is open
     if command_state.open {
@@ -93,22 +187,16 @@ pub fn quest_claim_rewards(
         return;
     }
 
-    let Some(quest) = quest_cache.main_quests.get(current_quest.index) else {
+    let Some(mut platform) = platform_inventory.get_mut() else {
         return;
     };
-
-    // Add rewards to PlatformInventory (machines and items)
-    for (item_id, amount) in &quest.rewards {
-        platform_inventory.add_item(*item_id, *amount);
-    }
-
-    current_quest.rewards_claimed = true;
-
-    // Move to next quest
-    if current_quest.index + 1 < quest_cache.main_quests.len() {
-        current_quest.index += 1;
-        current_quest.completed = false;
-        current_quest.rewards_claimed = false;
+    if let Some(quest_id) = claim_current_quest(
+        &mut current_quest,
+        &quest_cache.main_quests,
+        &mut platform,
+        unix_millis_now(),
+    ) {
+        claimed_events.write(QuestRewardsClaimed { quest_id });
     }
 }
 
@@ -144,6 +232,7 @@ pub fn update_quest_ui(
     >,
     quest_cache: Res<QuestCache>,
     tutorial_progress: Res<TutorialProgress>,
+    timed_contracts: Option<Res<TimedContracts>>,
 ) {
     // Skip quest UI updates during tutorial
     if !tutorial_progress.completed {
@@ -153,8 +242,15 @@ pub fn update_quest_ui(
         return;
     };
 
-    if current_quest.index >= quest_cache.main_quests.len() {
-        **text = "🎉 全クエスト完了！".to_string();
+    if main_quests_finished(&current_quest, quest_cache.main_quests.len()) {
+        // Timed contracts take over the panel with their countdown
+        **text = match timed_contracts {
+            Some(contracts) => format!(
+                "🎉 全クエスト完了！\n\n{}",
+                contract_status_text(&contracts)
+            ),
+            None => "🎉 全クエスト完了！".to_string(),
+        };
         // Hide deliver button and progress bars
         for (mut vis, _) in button_query.iter_mut() {
             *vis = Visibility::Hidden;
@@ -267,6 +363,7 @@ pub fn quest_deliver_button(
         (Changed<Interaction>, With<QuestDeliverButton>),
     >,
     quest_cache: Res<QuestCache>,
+    mut net_client: Option<ResMut<NetClient>>,
 ) {
     if current_quest.completed {
         return;
@@ -284,6 +381,12 @@ pub fn quest_deliver_button(
                     continue;
                 }
 
+                // In co-op the server consumes the items and completes the quest
+                if let Some(client) = net_client.as_deref_mut() {
+                    client.request_delivery(&quest.required_items);
+                    continue;
+                }
+
                 // Consume items from PlatformInventory
                 for (item_id, required) in &quest.required_items {
                     platform_inventory.remove_item(*item_id, *required);
@@ -310,9 +413,10 @@ pub fn setup_delivery_platform(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    world_data: Res<crate::world::WorldData>,
 ) {
-    // Platform position: 8x8 area starting at (20, 8, 10)
-    let platform_origin = IVec3::new(20, 8, 10);
+    // Platform position: 8x8 area at the worldgen platform origin (default (20, 8, 10))
+    let platform_origin = world_data.gen_config.platform.origin();
 
     // Create platform mesh (flat plate)
     let platform_mesh = meshes.add(Cuboid::new(
@@ -337,6 +441,7 @@ pub fn setup_delivery_platform(
                 platform_origin.z as f32 * BLOCK_SIZE + (PLATFORM_SIZE as f32 * BLOCK_SIZE / 2.0),
             )),
             DeliveryPlatform::new(platform_origin),
+            DeliveryFilter::default(),
             PlatformInventory::with_items_by_id(&game_spec::initial_equipment()),
         ))
         .id();
@@ -393,21 +498,64 @@ pub fn setup_delivery_platform(
     }
 }
 
-/// Update delivery UI text (now just shows platform status)
+/// Update delivery UI text (platform count, pad platform contracts, policies and rejections)
 pub fn update_delivery_ui(
-    platform_query: Query<&DeliveryPlatform>,
+    platform_query: Query<
+        (Option<&DeliveryContract>, Option<&DeliveryFilter>),
+        With<DeliveryPlatform>,
+    >,
     mut text_query: Query<&mut Text, With<DeliveryUIText>>,
 ) {
-    let Ok(_platform) = platform_query.single() else {
-        if let Ok(mut text) = text_query.single_mut() {
-            **text = "プラットフォームを設置してください".to_string();
-        }
+    let Ok(mut text) = text_query.single_mut() else {
         return;
     };
+    let platforms = platform_query.iter().count();
+    if platforms == 0 {
+        **text = "プラットフォームを設置してください".to_string();
+        return;
+    }
 
-    // Platform is active - items go directly to GlobalInventory
-    if let Ok(mut text) = text_query.single_mut() {
-        **text = "✓ プラットフォーム稼働中".to_string();
+    // Platforms are active - items go directly to the platform inventory
+    let mut lines = vec![if platforms == 1 {
+        "✓ プラットフォーム稼働中".to_string()
+    } else {
+        format!("✓ プラットフォーム稼働中 ({})", platforms)
+    }];
+    for contract in platform_query.iter().filter_map(|(contract, _)| contract) {
+        let rate = contract
+            .last_rate
+            .map_or("-".to_string(), |rate| rate.to_string());
+        lines.push(format!(
+            "{} 契約: {} {}/{} 個/分",
+            if contract.is_met() { "✓" } else { "…" },
+            contract.item.display_name(),
+            rate,
+            contract.target_per_min
+        ));
+    }
+    // Rejected items are listed apart from deliveries (they never reach the inventory)
+    for filter in platform_query.iter().filter_map(|(_, filter)| filter) {
+        if filter.policy == DeliveryPolicy::AcceptAll && filter.rejected.is_empty() {
+            continue;
+        }
+        lines.push(format!("受入: {}", filter.policy.label()));
+        if !filter.rejected.is_empty() {
+            let rejected: Vec<String> = filter
+                .rejected
+                .iter()
+                .map(|(item, count)| format!("{} {}", item.display_name(), count))
+                .collect();
+            lines.push(format!(
+                "✕ 返品: {} (待機 {}/{})",
+                rejected.join(", "),
+                filter.buffered(),
+                REJECT_BUFFER_CAPACITY
+            ));
+        }
+    }
+    let content = lines.join("\n");
+    if **text != content {
+        **text = content;
     }
 }
 
@@ -510,3 +658,115 @@ pub fn load_machine_models(
         asset_server.load("textures/items/stone_pickaxe.png"),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest_at(index: usize, completed: bool, rewards_claimed: bool) -> CurrentQuest {
+        CurrentQuest {
+            index,
+            completed,
+            rewards_claimed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_quest_log_entries_by_progress() {
+        let quests = get_main_quests();
+        assert!(quests.len() >= 3);
+
+        let entries = quest_log_entries(&quests, &quest_at(1, false, false));
+        assert_eq!(entries[0], QuestLogStatus::Claimed(None));
+        assert_eq!(entries[1], QuestLogStatus::Active);
+        assert!(entries[2..].iter().all(|s| *s == QuestLogStatus::Locked));
+
+        let entries = quest_log_entries(&quests, &quest_at(1, true, false));
+        assert_eq!(entries[1], QuestLogStatus::Claimable);
+    }
+
+    #[test]
+    fn test_quest_log_entries_keep_claim_times() {
+        let quests = get_main_quests();
+        let mut current = quest_at(1, false, false);
+        current
+            .claimed_at
+            .insert(quests[0].id.to_string(), 1_700_000_000_000);
+
+        let entries = quest_log_entries(&quests, &current);
+        assert_eq!(entries[0], QuestLogStatus::Claimed(Some(1_700_000_000_000)));
+    }
+
+    #[test]
+    fn test_quest_log_last_quest_claimed() {
+        let quests = get_main_quests();
+        let last = quests.len() - 1;
+        let entries = quest_log_entries(&quests, &quest_at(last, true, true));
+        assert!(entries
+            .iter()
+            .all(|s| matches!(s, QuestLogStatus::Claimed(_))));
+    }
+
+    #[test]
+    fn test_main_quests_finished_after_last_claim() {
+        let count = get_main_quests().len();
+        assert!(!main_quests_finished(
+            &quest_at(count - 1, true, false),
+            count
+        ));
+        assert!(!main_quests_finished(&quest_at(0, true, true), count));
+        assert!(main_quests_finished(
+            &quest_at(count - 1, true, true),
+            count
+        ));
+    }
+
+    #[test]
+    fn test_claim_current_quest_advances_and_records_time() {
+        let quests = get_main_quests();
+        let mut platform = PlatformInventory::new();
+
+        // Nothing to claim before delivery
+        let mut current = quest_at(0, false, false);
+        assert_eq!(
+            claim_current_quest(&mut current, &quests, &mut platform, 5),
+            None
+        );
+        assert!(platform.is_empty());
+
+        current.completed = true;
+        assert_eq!(
+            claim_current_quest(&mut current, &quests, &mut platform, 5),
+            Some(quests[0].id)
+        );
+        for (item_id, amount) in &quests[0].rewards {
+            assert_eq!(platform.get_count_by_id(*item_id), *amount);
+        }
+        assert_eq!(current.index, 1);
+        assert!(!current.completed && !current.rewards_claimed);
+        assert_eq!(current.claimed_at.get(quests[0].id), Some(&5));
+
+        // Claiming twice does nothing
+        assert_eq!(
+            claim_current_quest(&mut current, &quests, &mut platform, 6),
+            None
+        );
+    }
+
+    #[test]
+    fn test_claim_last_quest_stays_on_it() {
+        let quests = get_main_quests();
+        let last = quests.len() - 1;
+        let mut platform = PlatformInventory::new();
+        let mut current = quest_at(last, true, false);
+
+        assert!(claim_current_quest(&mut current, &quests, &mut platform, 9).is_some());
+        assert_eq!(current.index, last);
+        assert!(current.rewards_claimed);
+        assert_eq!(
+            quest_log_entries(&quests, &current)[last],
+            QuestLogStatus::Claimed(Some(9))
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Radial quick-select menu
+//!
+//! Holding the quick-select key (Tab) shows a ring of the most recently
+//! placed item types around the crosshair. Mouse movement picks a segment
+//! instead of turning the camera; releasing the key puts the picked item
+//! into the current hotbar slot.
+
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::components::{
+    ItemSprites, QuickSelectRing, QuickSelectSegment, QuickSelectSegmentImage,
+    QuickSelectSegmentText,
+};
+use crate::core::ItemId;
+use crate::events::game_events::{BlockPlaced, EventSource, MachineSpawned};
+use crate::input::{GameAction, InputManager};
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::setup::ui::{SLOT_BG, SLOT_BORDER_COLOR, SLOT_HOVER_BG, SLOT_SELECTED_BORDER};
+use crate::{InputStateResourcesWithCursor, HOTBAR_SLOTS};
+
+/// Number of ring segments (and recently used items remembered)
+pub const QUICK_SELECT_SEGMENTS: usize = 8;
+
+/// Cursor distance from the center below which no segment is picked (px)
+pub const QUICK_SELECT_DEADZONE: f32 = 24.0;
+
+/// Distance from the crosshair to the segment centers (px)
+pub const QUICK_SELECT_RADIUS: f32 = 110.0;
+
+/// Most recently placed item types, newest first (saved with the inventory)
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct RecentPlaceables {
+    pub items: Vec<ItemId>,
+}
+
+impl RecentPlaceables {
+    /// Move `item_id` to the front, dropping the oldest entry past the ring size
+    pub fn record(&mut self, item_id: ItemId) {
+        if self.items.first() == Some(&item_id) {
+            return;
+        }
+        self.items.retain(|&id| id != item_id);
+        self.items.insert(0, item_id);
+        self.items.truncate(QUICK_SELECT_SEGMENTS);
+    }
+
+    /// Item shown in a ring segment
+    pub fn get(&self, segment: usize) -> Option<ItemId> {
+        self.items.get(segment).copied()
+    }
+}
+
+/// Open state of the ring
+#[derive(Resource, Debug, Default)]
+pub struct QuickSelectMenu {
+    pub open: bool,
+    /// Virtual cursor offset from the crosshair (screen space, +y down)
+    pub cursor: Vec2,
+}
+
+impl QuickSelectMenu {
+    /// Segment under the virtual cursor
+    pub fn segment(&self) -> Option<usize> {
+        segment_from_offset(self.cursor, QUICK_SELECT_SEGMENTS)
+    }
+}
+
+/// Segment for a screen-space offset from the ring center
+///
+/// Segment 0 is straight up, the rest follow clockwise. Offsets inside the
+/// dead zone pick nothing.
+pub fn segment_from_offset(offset: Vec2, segments: usize) -> Option<usize> {
+    if segments == 0 || offset.length() < QUICK_SELECT_DEADZONE {
+        return None;
+    }
+    // Clockwise angle from "up" (screen y grows downwards)
+    let angle = offset.x.atan2(-offset.y).rem_euclid(TAU);
+    let width = TAU / segments as f32;
+    Some(((angle + width / 2.0) / width) as usize % segments)
+}
+
+/// Screen-space unit vector from the ring center to a segment's center
+pub fn segment_direction(segment: usize, segments: usize) -> Vec2 {
+    let angle = segment as f32 * TAU / segments as f32;
+    Vec2::new(angle.sin(), -angle.cos())
+}
+
+/// Bring `item_id` into the selected hotbar slot
+///
+/// Already in the hotbar: just switch to that slot. Otherwise the stack is
+/// swapped in from the main inventory. Returns false when the player has none.
+pub fn select_into_hotbar(inventory: &mut PlayerInventory, item_id: ItemId) -> bool {
+    let holds = |slot: &Option<(ItemId, u32)>| slot.is_some_and(|(id, _)| id == item_id);
+
+    if let Some(slot) = inventory.slots[..HOTBAR_SLOTS].iter().position(holds) {
+        inventory.selected_slot = slot;
+        return true;
+    }
+    let Some(slot) = inventory.slots[HOTBAR_SLOTS..]
+        .iter()
+        .position(holds)
+        .map(|i| i + HOTBAR_SLOTS)
+    else {
+        return false;
+    };
+    let selected = inventory.selected_slot;
+    inventory.slots.swap(selected, slot);
+    true
+}
+
+/// Remember placed blocks, machines and conveyors
+pub fn track_recent_placeables(
+    mut block_placed: MessageReader<BlockPlaced>,
+    mut machine_spawned: MessageReader<MachineSpawned>,
+    mut recent: ResMut<RecentPlaceables>,
+) {
+    for event in block_placed.read() {
+        if matches!(event.source, EventSource::Player(_)) {
+            recent.record(event.block);
+        }
+    }
+    for event in machine_spawned.read() {
+        recent.record(event.machine_type);
+    }
+}
+
+/// Open the ring while the key is held, select on release
+pub fn update_quick_select(
+    input: Res<InputManager>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    input_resources: InputStateResourcesWithCursor,
+    recent: Res<RecentPlaceables>,
+    local_player: Option<Res<LocalPlayer>>,
+    mut inventories: Query<&mut PlayerInventory>,
+    mut menu: ResMut<QuickSelectMenu>,
+) {
+    if !menu.open {
+        if input.just_pressed(GameAction::QuickSelect)
+            && input_resources.get_state().allows_block_actions()
+        {
+            menu.open = true;
+            menu.cursor = Vec2::ZERO;
+        }
+        return;
+    }
+
+    // A UI opening on top (machine UI, pause) cancels the ring
+    if !input_resources.get_state().allows_block_actions() {
+        menu.open = false;
+        return;
+    }
+
+    if input.pressed(GameAction::QuickSelect) {
+        // Keep the virtual cursor on the ring so a small move back changes segment
+        menu.cursor = (menu.cursor + mouse_motion.delta).clamp_length_max(QUICK_SELECT_RADIUS);
+        return;
+    }
+
+    menu.open = false;
+    let Some(item_id) = menu.segment().and_then(|segment| recent.get(segment)) else {
+        return;
+    };
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let Ok(mut inventory) = inventories.get_mut(local_player.0) else {
+        return;
+    };
+    if select_into_hotbar(&mut inventory, item_id) {
+        info!(item = ?item_id.name(), "Quick-selected item");
+    }
+}
+
+/// Show the ring with icons, counts and the picked segment
+#[allow(clippy::type_complexity)]
+pub fn update_quick_select_ui(
+    menu: Res<QuickSelectMenu>,
+    recent: Res<RecentPlaceables>,
+    item_sprites: Res<ItemSprites>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    mut ring_query: Query<&mut Visibility, With<QuickSelectRing>>,
+    mut segment_query: Query<
+        (&QuickSelectSegment, &mut BackgroundColor, &mut BorderColor),
+        Without<QuickSelectRing>,
+    >,
+    mut image_query: Query<
+        (&QuickSelectSegmentImage, &mut ImageNode, &mut Visibility),
+        Without<QuickSelectRing>,
+    >,
+    mut text_query: Query<(&QuickSelectSegmentText, &mut Text)>,
+) {
+    let Ok(mut ring_visibility) = ring_query.single_mut() else {
+        return;
+    };
+    if !menu.open {
+        *ring_visibility = Visibility::Hidden;
+        return;
+    }
+    *ring_visibility = Visibility::Visible;
+
+    let inventory = local_player
+        .as_ref()
+        .and_then(|lp| inventories.get(lp.0).ok());
+    let picked = menu.segment();
+
+    for (segment, mut bg, mut border) in segment_query.iter_mut() {
+        let is_picked = picked == Some(segment.0) && recent.get(segment.0).is_some();
+        *bg = BackgroundColor(if is_picked { SLOT_HOVER_BG } else { SLOT_BG });
+        *border = BorderColor::all(if is_picked {
+            SLOT_SELECTED_BORDER
+        } else {
+            SLOT_BORDER_COLOR
+        });
+    }
+
+    for (segment, mut image, mut visibility) in image_query.iter_mut() {
+        match recent.get(segment.0).and_then(|id| item_sprites.get_id(id)) {
+            Some(sprite) => {
+                image.image = sprite;
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    for (segment, mut text) in text_query.iter_mut() {
+        let label = recent
+            .get(segment.0)
+            .map(|id| {
+                inventory
+                    .map(|inv| inv.get_total_count_by_id(id))
+                    .unwrap_or(0)
+                    .to_string()
+            })
+            .unwrap_or_default();
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    #[test]
+    fn test_record_moves_to_front_without_duplicates() {
+        let mut recent = RecentPlaceables::default();
+        recent.record(items::conveyor_block());
+        recent.record(items::miner_block());
+        recent.record(items::furnace_block());
+        recent.record(items::miner_block());
+
+        assert_eq!(
+            recent.items,
+            vec![
+                items::miner_block(),
+                items::furnace_block(),
+                items::conveyor_block()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_keeps_ring_size() {
+        let mut recent = RecentPlaceables::default();
+        let all = [
+            items::stone(),
+            items::grass(),
+            items::copper_ore(),
+            items::iron_ore(),
+            items::coal(),
+            items::conveyor_block(),
+            items::miner_block(),
+            items::furnace_block(),
+            items::crusher_block(),
+        ];
+        for item in all {
+            recent.record(item);
+        }
+
+        assert_eq!(recent.items.len(), QUICK_SELECT_SEGMENTS);
+        assert_eq!(recent.get(0), Some(items::crusher_block()));
+        // The oldest one fell off
+        assert!(!recent.items.contains(&items::stone()));
+    }
+
+    #[test]
+    fn test_segment_from_offset() {
+        let n = QUICK_SELECT_SEGMENTS;
+        let r = QUICK_SELECT_RADIUS;
+        assert_eq!(segment_from_offset(Vec2::new(0.0, -r), n), Some(0));
+        assert_eq!(segment_from_offset(Vec2::new(r, -r), n), Some(1));
+        assert_eq!(segment_from_offset(Vec2::new(r, 0.0), n), Some(2));
+        assert_eq!(segment_from_offset(Vec2::new(0.0, r), n), Some(4));
+        assert_eq!(segment_from_offset(Vec2::new(-r, 0.0), n), Some(6));
+        // Just left of straight up wraps to segment 0, not 8
+        assert_eq!(segment_from_offset(Vec2::new(-1.0, -r), n), Some(0));
+        // Dead zone picks nothing
+        assert_eq!(segment_from_offset(Vec2::new(3.0, -3.0), n), None);
+    }
+
+    #[test]
+    fn test_segment_direction_matches_picking() {
+        let n = QUICK_SELECT_SEGMENTS;
+        for segment in 0..n {
+            let offset = segment_direction(segment, n) * QUICK_SELECT_RADIUS;
+            assert_eq!(segment_from_offset(offset, n), Some(segment));
+        }
+    }
+
+    #[test]
+    fn test_select_into_hotbar() {
+        let mut inventory = PlayerInventory::default();
+        inventory.slots[3] = Some((items::furnace_block(), 2));
+        inventory.slots[HOTBAR_SLOTS + 4] = Some((items::miner_block(), 5));
+        inventory.slots[0] = Some((items::stone(), 10));
+        inventory.selected_slot = 0;
+
+        // Already in the hotbar: only the selection moves
+        assert!(select_into_hotbar(&mut inventory, items::furnace_block()));
+        assert_eq!(inventory.selected_slot, 3);
+
+        // In the main inventory: swapped into the selected slot
+        inventory.selected_slot = 0;
+        assert!(select_into_hotbar(&mut inventory, items::miner_block()));
+        assert_eq!(inventory.selected_slot, 0);
+        assert_eq!(inventory.slots[0], Some((items::miner_block(), 5)));
+        assert_eq!(
+            inventory.slots[HOTBAR_SLOTS + 4],
+            Some((items::stone(), 10))
+        );
+
+        // Not owned
+        assert!(!select_into_hotbar(&mut inventory, items::crusher_block()));
+        assert_eq!(inventory.slots[0], Some((items::miner_block(), 5)));
+    }
+}
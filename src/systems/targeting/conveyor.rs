@@ -1,52 +1,219 @@
-//! Conveyor rotation and shape update systems
+//! Conveyor shape, toggle and upgrade systems
 
 use bevy::prelude::*;
 use std::collections::HashSet;
 
-use crate::components::Machine;
-use crate::core::{items, ItemId};
+use crate::components::{conveyor_speed_multiplier, Machine};
+use crate::core::items;
 use crate::input::{GameAction, InputManager};
 use crate::meshes::create_conveyor_mesh;
 use crate::player::{LocalPlayer, PlayerInventory};
+use crate::utils::ray_aabb_intersection;
 use crate::{
-    Conveyor, ConveyorRotationOffset, ConveyorShape, ConveyorVisual, Direction,
-    InputStateResourcesWithCursor, MachineModels,
+    ContinuousActionTimer, Conveyor, ConveyorShape, ConveyorTierVisual, ConveyorVisual,
+    CreativeMode, Direction, InputStateResourcesWithCursor, MachineModels, PlayerCamera,
+    BLOCK_SIZE, CONVEYOR_BELT_HEIGHT, CONVEYOR_BELT_WIDTH, REACH_DISTANCE,
 };
 
-/// Handle R key to rotate conveyor/machine placement direction
-pub fn rotate_conveyor_placement(
+/// Distance along the camera ray to a conveyor's belt box, if hit within reach
+pub(super) fn conveyor_ray_hit(
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    position: IVec3,
+) -> Option<f32> {
+    let half = Vec3::new(
+        BLOCK_SIZE * CONVEYOR_BELT_WIDTH / 2.0,
+        CONVEYOR_BELT_HEIGHT / 2.0,
+        BLOCK_SIZE / 2.0,
+    );
+    let center = Vec3::new(
+        position.x as f32 * BLOCK_SIZE + 0.5,
+        position.y as f32 * BLOCK_SIZE + CONVEYOR_BELT_HEIGHT / 2.0,
+        position.z as f32 * BLOCK_SIZE + 0.5,
+    );
+    ray_aabb_intersection(ray_origin, ray_direction, center - half, center + half)
+        .filter(|&t| t > 0.0 && t < REACH_DISTANCE)
+}
+
+/// Shift+right-click on a conveyor toggles it on/off
+///
+/// Runs before block_place and swallows the click so nothing is placed on the belt.
+#[allow(clippy::too_many_arguments)]
+pub fn toggle_conveyor_enabled(
+    input: Res<InputManager>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    mut conveyors: Query<&mut Conveyor>,
+    input_resources: InputStateResourcesWithCursor,
+) {
+    if !input.pressed(GameAction::ModifierShift) || !input.pressed(GameAction::SecondaryAction) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    let ray_origin = camera_transform.translation();
+    let ray_direction = camera_transform.forward().as_vec3();
+
+    // Closest conveyor under the crosshair
+    let mut closest: Option<(f32, Mut<Conveyor>)> = None;
+    for conveyor in conveyors.iter_mut() {
+        if let Some(t) = conveyor_ray_hit(ray_origin, ray_direction, conveyor.position) {
+            if closest.as_ref().is_none_or(|(d, _)| t < *d) {
+                closest = Some((t, conveyor));
+            }
+        }
+    }
+    let Some((_, mut conveyor)) = closest else {
+        return;
+    };
+
+    // Keep block_place from placing on the belt (also while the button is held)
+    action_timer.place_timer.reset();
+    if mouse_button.just_pressed(MouseButton::Right) {
+        mouse_button.clear_just_pressed(MouseButton::Right);
+        conveyor.enabled = !conveyor.enabled;
+        info!(
+            "Conveyor at {:?} {}",
+            conveyor.position,
+            if conveyor.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+    }
+}
+
+/// Right-click on a conveyor while holding a faster conveyor upgrades it in place
+///
+/// The belt entity stays (no despawn); the held item is consumed and the old tier
+/// returned. Runs before block_place and swallows the click.
+#[allow(clippy::too_many_arguments)]
+pub fn upgrade_conveyor_in_place(
     input: Res<InputManager>,
-    mut rotation: ResMut<ConveyorRotationOffset>,
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     local_player: Option<Res<LocalPlayer>>,
-    inventories: Query<&PlayerInventory>,
+    mut inventories: Query<&mut PlayerInventory>,
+    creative_mode: Res<CreativeMode>,
+    mut conveyors: Query<&mut Conveyor>,
     input_resources: InputStateResourcesWithCursor,
 ) {
-    // Only active when placing conveyors or machines
+    if !input.pressed(GameAction::SecondaryAction) || input.pressed(GameAction::ModifierShift) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
     let Some(local_player) = local_player else {
         return;
     };
-    let Ok(inventory) = inventories.get(local_player.0) else {
+    let Ok(mut inventory) = inventories.get_mut(local_player.0) else {
         return;
     };
-    let selected_item_id: Option<ItemId> = inventory.get_selected_item_id();
-    // Check if the selected item is rotatable (conveyor or machine)
-    let is_rotatable =
-        selected_item_id.is_some_and(|id| id == items::conveyor_block() || id.is_machine());
-    if !is_rotatable {
-        // Reset rotation when not placing rotatable block
-        rotation.offset = 0;
+    let Some(held) = inventory.get_selected_item_id() else {
         return;
+    };
+    let Some(held_speed) = conveyor_speed_multiplier(held) else {
+        return;
+    };
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    let ray_origin = camera_transform.translation();
+    let ray_direction = camera_transform.forward().as_vec3();
+    let mut closest: Option<(f32, Mut<Conveyor>)> = None;
+    for conveyor in conveyors.iter_mut() {
+        if let Some(t) = conveyor_ray_hit(ray_origin, ray_direction, conveyor.position) {
+            if closest.as_ref().is_none_or(|(d, _)| t < *d) {
+                closest = Some((t, conveyor));
+            }
+        }
     }
+    // Same or slower tier: leave the click to normal placement
+    let Some((_, mut conveyor)) = closest.filter(|(_, c)| held_speed > c.speed_multiplier) else {
+        return;
+    };
 
-    // Check input state allows this action
-    let input_state = input_resources.get_state();
-    if !input_state.allows_block_actions() {
+    // Keep block_place from placing on the belt (also while the button is held)
+    action_timer.place_timer.reset();
+    if !mouse_button.just_pressed(MouseButton::Right) {
         return;
     }
+    mouse_button.clear_just_pressed(MouseButton::Right);
+
+    if !creative_mode.enabled && !inventory.consume_item_by_id(held, 1) {
+        return;
+    }
+    if let Some(old) = conveyor.upgrade_tier(held) {
+        if !creative_mode.enabled {
+            inventory.add_item_by_id(old, 1);
+        }
+        info!(
+            "Conveyor at {:?} upgraded to {}x speed",
+            conveyor.position, conveyor.speed_multiplier
+        );
+    }
+}
+
+/// Keep the tier rails on belts above the base tier in sync with their speed
+///
+/// Conveyors are respawned on glTF shape swaps, so the rails are (re)built here
+/// rather than at placement.
+pub fn update_conveyor_tier_visuals(
+    mut commands: Commands,
+    conveyors: Query<(Entity, &Conveyor, Option<&Children>, Has<SceneRoot>)>,
+    rails: Query<&ConveyorTierVisual>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, conveyor, children, uses_gltf) in conveyors.iter() {
+        let existing: Vec<(Entity, f32)> = children
+            .into_iter()
+            .flatten()
+            .filter_map(|child| rails.get(*child).ok().map(|rail| (*child, rail.0)))
+            .collect();
+        let shown = existing.first().map(|(_, speed)| *speed).unwrap_or(1.0);
+        if shown == conveyor.speed_multiplier {
+            continue;
+        }
+        for (rail, _) in existing {
+            commands.entity(rail).despawn();
+        }
+        if conveyor.speed_multiplier <= 1.0 {
+            continue;
+        }
 
-    // R key rotates 90 degrees clockwise
-    if input.just_pressed(GameAction::RotateBlock) {
-        rotation.offset = (rotation.offset + 1) % 4;
+        // glTF belts have their origin at the bottom, procedural belts at the center
+        let top = if uses_gltf {
+            CONVEYOR_BELT_HEIGHT
+        } else {
+            CONVEYOR_BELT_HEIGHT / 2.0
+        };
+        let mesh = meshes.add(Cuboid::new(0.06, 0.04, BLOCK_SIZE));
+        let material = materials.add(StandardMaterial {
+            base_color: conveyor.tier_item().color(),
+            ..default()
+        });
+        let edge = BLOCK_SIZE * CONVEYOR_BELT_WIDTH / 2.0;
+        commands.entity(entity).with_children(|parent| {
+            for x in [-edge, edge] {
+                parent.spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_translation(Vec3::new(x, top + 0.02, 0.0)),
+                    ConveyorTierVisual(conveyor.speed_multiplier),
+                ));
+            }
+        });
     }
 }
 
@@ -73,17 +240,28 @@ pub fn update_conveyor_shapes(
         .map(|(_, c, _, _, _)| (c.position, c.direction))
         .collect();
 
-    // Collect positions that can accept items (conveyors, furnaces, crushers)
+    // Collect positions that can accept items (conveyors, furnaces, crushers, recyclers, hoppers)
     let conveyor_positions: HashSet<IVec3> = conveyor_data.iter().map(|(p, _)| *p).collect();
     let mut furnace_positions: HashSet<IVec3> = HashSet::new();
     let mut crusher_positions: HashSet<IVec3> = HashSet::new();
+    let mut recycler_positions: HashSet<IVec3> = HashSet::new();
+    let mut hopper_positions: HashSet<IVec3> = HashSet::new();
+    // (machine position, neighbor position) pairs whose face is marked pass-through
+    let mut pass_through_faces: HashSet<(IVec3, IVec3)> = HashSet::new();
 
     for machine in machine_query.iter() {
+        for side in &machine.pass_through_sides {
+            pass_through_faces.insert((machine.position, machine.position + side.to_ivec3()));
+        }
         let machine_id = machine.spec.item_id();
         if machine_id == items::furnace_block() {
             furnace_positions.insert(machine.position);
         } else if machine_id == items::crusher_block() {
             crusher_positions.insert(machine.position);
+        } else if machine_id == items::recycler_block() {
+            recycler_positions.insert(machine.position);
+        } else if machine_id == items::hopper_block() {
+            hopper_positions.insert(machine.position);
         }
     }
 
@@ -125,6 +303,13 @@ pub fn update_conveyor_shapes(
         // Check "waiting": which neighbors can receive input from this conveyor
         // A neighbor is "waiting" if it can receive from our position (back, left, or right)
         // and is not already outputting to us
+        let accepting_machine = |machine_pos: IVec3, from_pos: IVec3| -> bool {
+            (furnace_positions.contains(&machine_pos)
+                || crusher_positions.contains(&machine_pos)
+                || recycler_positions.contains(&machine_pos)
+                || hopper_positions.contains(&machine_pos))
+                && !pass_through_faces.contains(&(machine_pos, from_pos))
+        };
         let can_receive_from = |neighbor_pos: IVec3, from_pos: IVec3| -> bool {
             for (pos, dir) in &conveyor_data {
                 if *pos == neighbor_pos {
@@ -135,8 +320,8 @@ pub fn update_conveyor_shapes(
                     return from_pos == nb_back || from_pos == nb_left || from_pos == nb_right;
                 }
             }
-            // Also check if furnace or crusher at this position (always accepts)
-            furnace_positions.contains(&neighbor_pos) || crusher_positions.contains(&neighbor_pos)
+            // Also check if furnace or crusher at this position (accepts unless pass-through)
+            accepting_machine(neighbor_pos, from_pos)
         };
 
         let left_waiting = !has_left_input
@@ -148,8 +333,7 @@ pub fn update_conveyor_shapes(
         let front_waiting = !has_front_input
             && (conveyor_positions.contains(&front_pos)
                 && can_receive_from(front_pos, conveyor.position)
-                || furnace_positions.contains(&front_pos)
-                || crusher_positions.contains(&front_pos));
+                || accepting_machine(front_pos, conveyor.position));
 
         let input_count = [
             has_back_input,
@@ -234,8 +418,10 @@ pub fn update_conveyor_shapes(
                         output_direction: new_output_dir,
                         items: std::mem::take(&mut conveyor.items),
                         last_output_index: conveyor.last_output_index,
-                        last_input_source: conveyor.last_input_source,
+                        last_input_pos: conveyor.last_input_pos,
+                        enabled: conveyor.enabled,
                         shape: new_shape,
+                        speed_multiplier: conveyor.speed_multiplier,
                     };
                     let conv_transform = *transform;
 
@@ -46,9 +46,10 @@ pub fn update_guide_markers(
     };
 
     // Only show guides for placeable machines (not Miner - too noisy)
-    if item_id != items::conveyor_block()
+    if !items::is_conveyor(item_id)
         && item_id != items::furnace_block()
         && item_id != items::crusher_block()
+        && item_id != items::recycler_block()
     {
         return;
     }
@@ -57,10 +58,13 @@ pub fn update_guide_markers(
     let pulse = (time.elapsed_secs() * 3.0).sin() * 0.2 + 0.5;
 
     // Generate guide positions based on selected item
-    let guide_positions = if item_id == items::conveyor_block() {
+    let guide_positions = if items::is_conveyor(item_id) {
         // Show positions extending from existing machines
         generate_conveyor_guide_positions(&machine_query, &conveyor_query)
-    } else if item_id == items::furnace_block() || item_id == items::crusher_block() {
+    } else if item_id == items::furnace_block()
+        || item_id == items::crusher_block()
+        || item_id == items::recycler_block()
+    {
         // Show positions along conveyor paths
         generate_processor_guide_positions(&conveyor_query)
     } else {
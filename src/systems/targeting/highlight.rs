@@ -5,11 +5,17 @@ use bevy::prelude::*;
 
 use crate::components::Machine;
 use crate::core::{items, ItemId};
+use crate::game_spec::get_machine_spec_by_id;
+use crate::logistics::{connection_hints, BeltConnection};
 use crate::meshes::{
-    create_conveyor_mesh, create_conveyor_wireframe_mesh, create_wireframe_cube_mesh,
+    create_conveyor_mesh, create_conveyor_wireframe_mesh, create_wireframe_cross_mesh,
+    create_wireframe_cube_mesh,
 };
 use crate::player::{LocalPlayer, PlayerInventory};
-use crate::utils::{auto_conveyor_direction, yaw_to_direction};
+use crate::systems::block_operations::PlacementPreview;
+use crate::utils::{
+    auto_conveyor_direction, auto_faces_conveyors, auto_machine_facing, yaw_to_direction,
+};
 use crate::{
     Conveyor, ConveyorRotationOffset, ConveyorShape, Direction, PlaceHighlight, PlayerCamera,
     TargetBlock, TargetHighlight, BLOCK_SIZE,
@@ -19,10 +25,20 @@ use crate::{
 #[derive(Component)]
 pub struct ConveyorPreviewArrow;
 
+/// Marker for a belt connection chevron on the machine preview
+#[derive(Component)]
+pub struct ConnectionHintMarker;
+
+/// Marker for the "X" shown on an invalid place target
+#[derive(Component)]
+pub struct InvalidPlacementMarker;
+
 /// Cached meshes for highlight wireframes (avoid recreation every frame)
 #[derive(Resource)]
 pub struct HighlightMeshCache {
     pub cube_mesh: Handle<Mesh>,
+    // "X" marker for invalid placement
+    pub cross_mesh: Handle<Mesh>,
     pub conveyor_north: Handle<Mesh>,
     pub conveyor_south: Handle<Mesh>,
     pub conveyor_east: Handle<Mesh>,
@@ -44,6 +60,16 @@ pub struct HighlightMeshCache {
     pub machine_preview_material: Handle<StandardMaterial>,
     // Bright yellow for arrow visibility
     pub arrow_material: Handle<StandardMaterial>,
+    // Semi-transparent red for invalid conveyor/machine preview
+    pub invalid_preview_material: Handle<StandardMaterial>,
+    // Semi-transparent orange for a valid placement with a warning (e.g. no ore below a miner)
+    pub warning_preview_material: Handle<StandardMaterial>,
+    // Small square for adjacent belts that won't connect
+    pub connection_idle_mesh: Handle<Mesh>,
+    // Connection chevrons: belt feeds the machine / machine outputs to belt / no interaction
+    pub connection_feed_material: Handle<StandardMaterial>,
+    pub connection_output_material: Handle<StandardMaterial>,
+    pub connection_idle_material: Handle<StandardMaterial>,
 }
 
 impl HighlightMeshCache {
@@ -74,6 +100,7 @@ pub fn setup_highlight_cache(
 ) {
     commands.insert_resource(HighlightMeshCache {
         cube_mesh: meshes.add(create_wireframe_cube_mesh()),
+        cross_mesh: meshes.add(create_wireframe_cross_mesh()),
         conveyor_north: meshes.add(create_conveyor_wireframe_mesh(Direction::North)),
         conveyor_south: meshes.add(create_conveyor_wireframe_mesh(Direction::South)),
         conveyor_east: meshes.add(create_conveyor_wireframe_mesh(Direction::East)),
@@ -121,6 +148,35 @@ pub fn setup_highlight_cache(
             unlit: true,
             ..default()
         }),
+        // Semi-transparent red for invalid conveyor/machine preview
+        invalid_preview_material: materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.2, 0.2, 0.5),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+        warning_preview_material: materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.55, 0.1, 0.5),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+        connection_idle_mesh: meshes.add(Cuboid::new(0.12, 0.04, 0.12)),
+        connection_feed_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 1.0, 0.3),
+            unlit: true,
+            ..default()
+        }),
+        connection_output_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.6, 1.0),
+            unlit: true,
+            ..default()
+        }),
+        connection_idle_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.55, 0.55, 0.55),
+            unlit: true,
+            ..default()
+        }),
     });
 }
 
@@ -231,6 +287,7 @@ pub fn update_target_highlight(
     machine_query: Query<&Machine>,
     camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     rotation: Res<ConveyorRotationOffset>,
+    preview: Res<PlacementPreview>,
 ) {
     let Some(local_player) = local_player else {
         return;
@@ -244,9 +301,13 @@ pub fn update_target_highlight(
 
     // Check what item is selected
     let selected_item_id: Option<ItemId> = inventory.get_selected_item_id();
-    let placing_conveyor = selected_item_id == Some(items::conveyor_block());
+    let placing_conveyor = selected_item_id.is_some_and(items::is_conveyor);
     let placing_machine = selected_item_id.is_some_and(|id| {
-        id == items::miner_block() || id == items::furnace_block() || id == items::crusher_block()
+        id == items::miner_block()
+            || id == items::furnace_block()
+            || id == items::crusher_block()
+            || id == items::recycler_block()
+            || id == items::hopper_block()
     });
 
     // Get player's facing direction as fallback
@@ -283,8 +344,16 @@ pub fn update_target_highlight(
                 }
                 Some(dir)
             } else {
-                // Machine: use player facing with rotation offset
-                let mut dir = fallback_dir;
+                // Machine: same belt-aware facing as block_place, with rotation offset
+                let mut dir = if selected_item_id.is_some_and(auto_faces_conveyors) {
+                    let conveyors: Vec<(IVec3, crate::Direction)> = conveyor_query
+                        .iter()
+                        .map(|c| (c.position, c.direction))
+                        .collect();
+                    auto_machine_facing(place_pos, fallback_dir, &conveyors)
+                } else {
+                    fallback_dir
+                };
                 for _ in 0..rotation.offset {
                     dir = dir.rotate_cw();
                 }
@@ -347,6 +416,12 @@ pub fn update_target_highlight(
             }
         }
 
+        // Placement validity was computed by update_target_block this frame
+        let is_valid = preview.is_valid()
+            && preview.target.map(|(p, _)| p) == Some(pos)
+            && preview.target.map(|(_, id)| id) == selected_item_id;
+        let warned = is_valid && preview.warning.is_some();
+
         // Calculate position based on item type
         let entity = if placing_conveyor {
             // Conveyor: position at ground level (Y = 0 for Y=0 block)
@@ -357,10 +432,17 @@ pub fn update_target_highlight(
             );
             let dir = place_direction.unwrap_or(Direction::North);
             let rotation = dir.to_rotation();
+            let material = if warned {
+                cache.warning_preview_material.clone()
+            } else if is_valid {
+                cache.conveyor_preview_material.clone()
+            } else {
+                cache.invalid_preview_material.clone()
+            };
             commands
                 .spawn((
                     Mesh3d(cache.conveyor_solid.clone()),
-                    MeshMaterial3d(cache.conveyor_preview_material.clone()),
+                    MeshMaterial3d(material),
                     Transform::from_translation(conveyor_center).with_rotation(rotation),
                     PlaceHighlight,
                     NotShadowCaster,
@@ -383,10 +465,17 @@ pub fn update_target_highlight(
                 Vec3::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5, pos.z as f32 + 0.5);
             let dir = place_direction.unwrap_or(Direction::North);
             let rotation = dir.to_rotation();
+            let material = if warned {
+                cache.warning_preview_material.clone()
+            } else if is_valid {
+                cache.machine_preview_material.clone()
+            } else {
+                cache.invalid_preview_material.clone()
+            };
             commands
                 .spawn((
                     Mesh3d(cache.machine_solid.clone()),
-                    MeshMaterial3d(cache.machine_preview_material.clone()),
+                    MeshMaterial3d(material),
                     Transform::from_translation(machine_center).with_rotation(rotation),
                     PlaceHighlight,
                     NotShadowCaster,
@@ -400,21 +489,77 @@ pub fn update_target_highlight(
                         ConveyorPreviewArrow,
                         NotShadowCaster,
                     ));
+
+                    // Chevrons on the top edges toward adjacent belts
+                    let Some(spec) = selected_item_id.and_then(get_machine_spec_by_id) else {
+                        return;
+                    };
+                    for hint in connection_hints(spec, pos, dir, conveyor_query.iter()) {
+                        let (mesh, material) = match hint.connection {
+                            BeltConnection::Feeds(_) => (
+                                cache.get_arrow_mesh(hint.side.opposite()),
+                                cache.connection_feed_material.clone(),
+                            ),
+                            BeltConnection::Receives => (
+                                cache.get_arrow_mesh(hint.side),
+                                cache.connection_output_material.clone(),
+                            ),
+                            BeltConnection::Idle => (
+                                cache.connection_idle_mesh.clone(),
+                                cache.connection_idle_material.clone(),
+                            ),
+                        };
+                        // Arrow meshes sit at y=0.55 already; the square needs lifting
+                        let lift = match hint.connection {
+                            BeltConnection::Idle => Vec3::Y * 0.5,
+                            _ => Vec3::ZERO,
+                        };
+                        let offset = hint.side.to_ivec3().as_vec3() * 0.38 + lift;
+                        parent.spawn((
+                            Mesh3d(mesh),
+                            MeshMaterial3d(material),
+                            Transform {
+                                translation: rotation.inverse() * offset,
+                                rotation: rotation.inverse(),
+                                scale: Vec3::new(0.5, 1.0, 0.5),
+                            },
+                            ConnectionHintMarker,
+                            NotShadowCaster,
+                        ));
+                    }
                 })
                 .id()
         } else {
-            // Other items: green wireframe at block center
+            // Other items: green (valid) or red (invalid) wireframe at block center
             let center = Vec3::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5, pos.z as f32 + 0.5);
+            let material = if is_valid {
+                cache.green_material.clone()
+            } else {
+                cache.red_material.clone()
+            };
             commands
                 .spawn((
                     Mesh3d(cache.cube_mesh.clone()),
-                    MeshMaterial3d(cache.green_material.clone()),
+                    MeshMaterial3d(material),
                     Transform::from_translation(center),
                     PlaceHighlight,
                     NotShadowCaster,
                 ))
                 .id()
         };
+
+        // Invalid placement: add a red "X" on top (child so it despawns with the highlight)
+        if !is_valid {
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn((
+                    Mesh3d(cache.cross_mesh.clone()),
+                    MeshMaterial3d(cache.red_material.clone()),
+                    Transform::default(),
+                    InvalidPlacementMarker,
+                    NotShadowCaster,
+                ));
+            });
+        }
         target.place_highlight_entity = Some(entity);
     } else if let Some(entity) = target.place_highlight_entity.take() {
         if place_query.get(entity).is_ok() {
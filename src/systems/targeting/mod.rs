@@ -5,15 +5,27 @@
 //! ## Modules
 //! - `raycast`: Update target block based on player view
 //! - `highlight`: Visual highlighting of target blocks
-//! - `conveyor`: Conveyor rotation and shape updates
+//! - `conveyor`: Conveyor shape updates, toggling and in-place upgrades
+//! - `rotation`: R / Shift+R rotation of the placement and of placed blocks
 //! - `guide`: Guide markers for placement
 
 mod conveyor;
 mod guide;
 mod highlight;
 mod raycast;
+mod rotation;
 
-pub use conveyor::{rotate_conveyor_placement, update_conveyor_shapes};
+pub use conveyor::{
+    toggle_conveyor_enabled, update_conveyor_shapes, update_conveyor_tier_visuals,
+    upgrade_conveyor_in_place,
+};
 pub use guide::update_guide_markers;
-pub use highlight::{setup_highlight_cache, update_target_highlight, HighlightMeshCache};
+pub use highlight::{
+    setup_highlight_cache, update_target_highlight, ConnectionHintMarker, HighlightMeshCache,
+    InvalidPlacementMarker,
+};
 pub use raycast::update_target_block;
+pub use rotation::{
+    machine_belt_links, rotate_conveyor, rotate_conveyor_placement, rotate_machine,
+    rotate_targeted_block, update_belt_warnings, BeltWarningFlash, BELT_WARNING_SECS,
+};
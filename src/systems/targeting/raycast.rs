@@ -1,35 +1,76 @@
 //! Target block raycast system
 
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
-use crate::utils::dda_raycast;
+use super::conveyor::conveyor_ray_hit;
+use crate::components::Machine;
+use crate::core::items;
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::settings::GameSettings;
+use crate::systems::block_operations::{
+    PlacementCheck, PlacementPreview, ProtectionZone, SpawnProtection,
+};
+use crate::utils::{
+    auto_conveyor_direction, grid_to_world_center, ray_aabb_intersection, yaw_to_direction,
+};
 use crate::world::WorldData;
-use crate::{CursorLockState, InteractingMachine, PlayerCamera, TargetBlock, REACH_DISTANCE};
+use crate::{
+    Conveyor, ConveyorRotationOffset, CreativeMode, CursorController, DeliveryPlatform,
+    InteractingMachine, PlayerCamera, TargetBlock, BLOCK_SIZE, REACH_DISTANCE,
+};
+
+/// Closest machine or conveyor hit by the ray within `max_distance`
+fn targeted_machine(
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    max_distance: f32,
+    machines: impl Iterator<Item = (Entity, IVec3)>,
+    conveyors: impl Iterator<Item = (Entity, IVec3)>,
+) -> Option<Entity> {
+    let half = Vec3::splat(BLOCK_SIZE / 2.0);
+    let machine_hits = machines.filter_map(|(entity, position)| {
+        let center = grid_to_world_center(position);
+        ray_aabb_intersection(ray_origin, ray_direction, center - half, center + half)
+            .map(|t| (t, entity))
+    });
+    let conveyor_hits = conveyors.filter_map(|(entity, position)| {
+        conveyor_ray_hit(ray_origin, ray_direction, position).map(|t| (t, entity))
+    });
+    machine_hits
+        .chain(conveyor_hits)
+        .filter(|&(t, _)| t > 0.0 && t < max_distance)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, entity)| entity)
+}
 
 /// Update target block based on player's view direction
+///
+/// Also validates the place target once per frame so the highlight
+/// doesn't have to repeat the raycast or occupancy checks.
+#[allow(clippy::too_many_arguments)]
 pub fn update_target_block(
     camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     world_data: Res<WorldData>,
-    cursor_query: Query<&CursorOptions, With<PrimaryWindow>>,
     mut target: ResMut<TargetBlock>,
+    mut preview: ResMut<PlacementPreview>,
     interacting_machine: Res<InteractingMachine>,
-    cursor_state: Res<CursorLockState>,
+    cursor: Res<CursorController>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    conveyor_query: Query<(Entity, &Conveyor)>,
+    machine_query: Query<(Entity, &Machine)>,
+    platform_query: Query<&DeliveryPlatform>,
+    creative_mode: Res<CreativeMode>,
+    settings: Res<GameSettings>,
+    rotation: Res<ConveyorRotationOffset>,
+    protection: Res<SpawnProtection>,
 ) {
-    // Don't update target while UI is open or paused
-    if interacting_machine.0.is_some() || cursor_state.paused {
-        target.break_target = None;
-        target.place_target = None;
-        return;
-    }
-
-    let cursor_locked = cursor_query
-        .single()
-        .map(|c| c.grab_mode != CursorGrabMode::None)
-        .unwrap_or(false);
-    if !cursor_locked {
+    // Don't update target while UI is open, paused or unfocused
+    if interacting_machine.0.is_some() || cursor.is_released() {
         target.break_target = None;
         target.place_target = None;
+        target.machine_target = None;
+        *preview = PlacementPreview::default();
         return;
     }
 
@@ -41,13 +82,84 @@ pub fn update_target_block(
     let ray_direction = camera_transform.forward().as_vec3();
 
     // Use DDA raycast to find the first block
-    if let Some(hit) = dda_raycast(ray_origin, ray_direction, REACH_DISTANCE, |pos| {
-        world_data.has_block(pos)
-    }) {
+    let hit = world_data.raycast_dda(ray_origin, ray_direction, REACH_DISTANCE);
+    if let Some(hit) = hit {
         target.break_target = Some(hit.position);
         target.place_target = Some(hit.position + hit.normal);
     } else {
         target.break_target = None;
         target.place_target = None;
     }
+
+    // Machine or conveyor in front of the terrain hit
+    let max_distance = hit.map_or(REACH_DISTANCE, |hit| hit.distance + 0.5);
+    let machine_target = targeted_machine(
+        ray_origin,
+        ray_direction,
+        max_distance,
+        machine_query.iter().map(|(e, m)| (e, m.position)),
+        conveyor_query.iter().map(|(e, c)| (e, c.position)),
+    );
+    if target.machine_target != machine_target {
+        target.machine_target = machine_target;
+    }
+
+    // Validate the place target with the selected item
+    let inventory = local_player
+        .as_ref()
+        .and_then(|lp| inventories.get(lp.0).ok());
+    let selected = inventory.and_then(|inv| inv.get_selected_item_id());
+    let (Some(pos), Some(item_id), Some(inventory)) = (target.place_target, selected, inventory)
+    else {
+        *preview = PlacementPreview::default();
+        return;
+    };
+
+    let conveyor_at = |p: IVec3| conveyor_query.iter().any(|(_, c)| c.position == p);
+    let machine_at = |p: IVec3| machine_query.iter().any(|(_, m)| m.position == p);
+    let on_platform = |p: IVec3| platform_query.iter().any(|platform| platform.contains(p));
+    let zone = ProtectionZone::new(
+        &protection,
+        creative_mode.enabled,
+        platform_query.iter(),
+        world_data.gen_config.initial_furnace_position(),
+    );
+    let protected = |p: IVec3, item| zone.blocks_placement(p, item);
+    let check = PlacementCheck {
+        world: &world_data,
+        conveyor_at: &conveyor_at,
+        machine_at: &machine_at,
+        extra_support: &on_platform,
+        protected: &protected,
+        inventory_count: inventory.get_total_count_by_id(item_id),
+        creative: creative_mode.enabled,
+    };
+    preview.target = Some((pos, item_id));
+    preview.error = check.validate(pos, item_id).err();
+
+    // Warnings only matter for placements that would go ahead
+    preview.warning = None;
+    if settings.placement_warnings && preview.error.is_none() {
+        // Only belts warn by direction; same auto-direction as block_place
+        let mut facing = yaw_to_direction(-ray_direction.x.atan2(-ray_direction.z));
+        if items::is_conveyor(item_id) {
+            // Auto-direction only looks at direct neighbours of the target
+            let adjacent = |p: IVec3| (p - pos).abs().element_sum() == 1;
+            let conveyors: Vec<_> = conveyor_query
+                .iter()
+                .map(|(_, c)| (c.position, c.direction))
+                .filter(|&(p, _)| adjacent(p))
+                .collect();
+            let machines: Vec<IVec3> = machine_query
+                .iter()
+                .map(|(_, m)| m.position)
+                .filter(|&p| adjacent(p))
+                .collect();
+            facing = auto_conveyor_direction(pos, facing, &conveyors, &machines);
+            for _ in 0..rotation.offset {
+                facing = facing.rotate_cw();
+            }
+        }
+        preview.warning = check.warning(pos, item_id, facing);
+    }
 }
@@ -0,0 +1,329 @@
+//! Rotation with R: placement direction and already placed blocks
+//!
+//! R turns clockwise, Shift+R counter-clockwise. While holding a conveyor or
+//! machine item it turns the placement direction; otherwise it turns the
+//! targeted conveyor or machine in place, so nothing has to be broken (and
+//! emptied) to change its direction.
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+
+use crate::components::Machine;
+use crate::core::{items, ItemId};
+use crate::input::{GameAction, InputManager};
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::{
+    Conveyor, ConveyorRotationOffset, Direction, InputStateResourcesWithCursor, TargetBlock,
+    BLOCK_SIZE, CONVEYOR_BELT_HEIGHT, CONVEYOR_BELT_WIDTH,
+};
+
+/// How long a belt that lost its machine connection flashes (seconds)
+pub const BELT_WARNING_SECS: f32 = 1.0;
+
+/// Yellow flash over a belt whose connection a rotation broke
+#[derive(Component)]
+pub struct BeltWarningFlash {
+    pub remaining: f32,
+}
+
+/// Rotation requested this frame: Some(true) clockwise, Some(false) counter-clockwise
+fn rotation_request(input: &InputManager) -> Option<bool> {
+    input
+        .just_pressed(GameAction::RotateBlock)
+        .then(|| !input.pressed(GameAction::ModifierShift))
+}
+
+fn is_rotatable_item(item_id: ItemId) -> bool {
+    item_id == items::conveyor_block() || item_id.is_machine()
+}
+
+fn turn(direction: Direction, clockwise: bool) -> Direction {
+    if clockwise {
+        direction.rotate_cw()
+    } else {
+        direction.left()
+    }
+}
+
+/// Handle R key to rotate conveyor/machine placement direction
+pub fn rotate_conveyor_placement(
+    input: Res<InputManager>,
+    mut rotation: ResMut<ConveyorRotationOffset>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    input_resources: InputStateResourcesWithCursor,
+) {
+    // Only active when placing conveyors or machines
+    let Some(local_player) = local_player else {
+        return;
+    };
+    let Ok(inventory) = inventories.get(local_player.0) else {
+        return;
+    };
+    let selected_item_id: Option<ItemId> = inventory.get_selected_item_id();
+    if !selected_item_id.is_some_and(is_rotatable_item) {
+        // Reset rotation when not placing rotatable block
+        rotation.offset = 0;
+        return;
+    }
+
+    // Check input state allows this action
+    let input_state = input_resources.get_state();
+    if !input_state.allows_block_actions() {
+        return;
+    }
+
+    // R rotates 90 degrees clockwise, Shift+R counter-clockwise
+    match rotation_request(&input) {
+        Some(true) => rotation.offset = (rotation.offset + 1) % 4,
+        Some(false) => rotation.offset = (rotation.offset + 3) % 4,
+        None => {}
+    }
+}
+
+/// Turn a placed conveyor in place
+///
+/// Items stay on the belt. The shape follows on the next
+/// `update_conveyor_shapes` run, which re-detects it from the new direction.
+pub fn rotate_conveyor(conveyor: &mut Conveyor, transform: &mut Transform, clockwise: bool) {
+    conveyor.direction = turn(conveyor.direction, clockwise);
+    conveyor.output_direction = conveyor.direction;
+    transform.rotation = conveyor.direction.to_rotation();
+}
+
+/// Turn a placed machine in place, keeping its contents
+pub fn rotate_machine(machine: &mut Machine, transform: &mut Transform, clockwise: bool) {
+    machine.facing = turn(machine.facing, clockwise);
+    transform.rotation = machine.facing.to_rotation();
+}
+
+/// Belts connected to a machine: the ones feeding an input port and the ones
+/// taking its output
+pub fn machine_belt_links(machine: &Machine, belts: &[(IVec3, Direction)]) -> Vec<IVec3> {
+    let outputs = machine.output_positions();
+    belts
+        .iter()
+        .filter(|(pos, dir)| {
+            let points_at_machine = *pos + dir.to_ivec3() == machine.position;
+            if points_at_machine {
+                machine.accepts_input_from(*pos)
+            } else {
+                outputs.contains(pos)
+            }
+        })
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+/// Handle R key to rotate the targeted conveyor or machine
+///
+/// Only while not holding a conveyor or machine (then R rotates the placement
+/// instead). Rotating a machine is always allowed; belts it no longer connects
+/// to flash yellow. The write through `&mut Machine` also wakes a sleeping
+/// machine and the next save picks up the new facing.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate_targeted_block(
+    mut commands: Commands,
+    input: Res<InputManager>,
+    target: Res<TargetBlock>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    mut conveyors: Query<(&mut Conveyor, &mut Transform), Without<Machine>>,
+    mut machines: Query<(&mut Machine, &mut Transform), Without<Conveyor>>,
+    input_resources: InputStateResourcesWithCursor,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(clockwise) = rotation_request(&input) else {
+        return;
+    };
+    let holding_rotatable = local_player
+        .and_then(|lp| inventories.get(lp.0).ok())
+        .and_then(|inv| inv.get_selected_item_id())
+        .is_some_and(is_rotatable_item);
+    if holding_rotatable || !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Some(entity) = target.machine_target else {
+        return;
+    };
+
+    if let Ok((mut conveyor, mut transform)) = conveyors.get_mut(entity) {
+        rotate_conveyor(&mut conveyor, &mut transform, clockwise);
+        return;
+    }
+    let Ok((mut machine, mut transform)) = machines.get_mut(entity) else {
+        return;
+    };
+    let belts: Vec<(IVec3, Direction)> = conveyors
+        .iter()
+        .map(|(c, _)| (c.position, c.direction))
+        .collect();
+    let before = machine_belt_links(&machine, &belts);
+    rotate_machine(&mut machine, &mut transform, clockwise);
+    let after = machine_belt_links(&machine, &belts);
+
+    let broken: Vec<IVec3> = before
+        .into_iter()
+        .filter(|pos| !after.contains(pos))
+        .collect();
+    if broken.is_empty() {
+        return;
+    }
+    let mesh = meshes.add(Cuboid::new(
+        BLOCK_SIZE * CONVEYOR_BELT_WIDTH * 1.1,
+        CONVEYOR_BELT_HEIGHT * 1.5,
+        BLOCK_SIZE * 1.02,
+    ));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 0.9, 0.1, 0.6),
+        unlit: true,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    for pos in broken {
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(Vec3::new(
+                pos.x as f32 * BLOCK_SIZE + 0.5,
+                pos.y as f32 * BLOCK_SIZE + CONVEYOR_BELT_HEIGHT / 2.0,
+                pos.z as f32 * BLOCK_SIZE + 0.5,
+            )),
+            NotShadowCaster,
+            BeltWarningFlash {
+                remaining: BELT_WARNING_SECS,
+            },
+        ));
+    }
+}
+
+/// Blink the belt warnings and remove them when they expire
+pub fn update_belt_warnings(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut BeltWarningFlash, &mut Visibility)>,
+) {
+    for (entity, mut flash, mut vis) in flashes.iter_mut() {
+        flash.remaining -= time.delta_secs();
+        if flash.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // Four blinks per second
+        *vis = if (flash.remaining * 8.0) as u32 % 2 == 0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_spec::FURNACE;
+    use crate::meshes::create_conveyor_mesh;
+    use crate::systems::update_conveyor_shapes;
+    use crate::{ConveyorShape, MachineModels};
+
+    fn belt(
+        position: IVec3,
+        direction: Direction,
+        meshes: &mut Assets<Mesh>,
+    ) -> (Conveyor, Mesh3d, Transform) {
+        (
+            Conveyor {
+                position,
+                direction,
+                output_direction: direction,
+                items: Vec::new(),
+                last_output_index: 0,
+                last_input_pos: None,
+                enabled: true,
+                shape: ConveyorShape::Straight,
+                speed_multiplier: 1.0,
+            },
+            Mesh3d(meshes.add(create_conveyor_mesh(ConveyorShape::Straight))),
+            Transform::from_rotation(direction.to_rotation()),
+        )
+    }
+
+    #[test]
+    fn test_rotating_placed_conveyor_updates_direction_shape_and_visuals() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<MachineModels>()
+            .add_systems(Update, update_conveyor_shapes);
+
+        // A feeds B from behind: B is straight
+        let mut meshes = app.world_mut().resource_mut::<Assets<Mesh>>();
+        let a = belt(IVec3::new(0, 8, 0), Direction::East, &mut meshes);
+        let b = belt(IVec3::new(1, 8, 0), Direction::East, &mut meshes);
+        app.world_mut().spawn(a);
+        let b = app.world_mut().spawn(b).id();
+        app.update();
+        let straight_mesh = app.world().get::<Mesh3d>(b).unwrap().0.clone();
+        assert_eq!(
+            app.world().get::<Conveyor>(b).unwrap().shape,
+            ConveyorShape::Straight
+        );
+
+        // Turn B to face south: A now feeds it from the right
+        {
+            let mut entity = app.world_mut().entity_mut(b);
+            let mut conveyor = entity.take::<Conveyor>().unwrap();
+            let mut transform = *entity.get::<Transform>().unwrap();
+            rotate_conveyor(&mut conveyor, &mut transform, true);
+            entity.insert((conveyor, transform));
+        }
+        app.update();
+
+        let conveyor = app.world().get::<Conveyor>(b).unwrap();
+        assert_eq!(conveyor.direction, Direction::South);
+        assert_eq!(conveyor.shape, ConveyorShape::CornerLeft);
+        assert_eq!(
+            app.world().get::<Transform>(b).unwrap().rotation,
+            Direction::South.to_rotation()
+        );
+        assert_ne!(app.world().get::<Mesh3d>(b).unwrap().0, straight_mesh);
+
+        // Shift+R turns it back
+        {
+            let mut entity = app.world_mut().entity_mut(b);
+            let mut conveyor = entity.take::<Conveyor>().unwrap();
+            let mut transform = *entity.get::<Transform>().unwrap();
+            rotate_conveyor(&mut conveyor, &mut transform, false);
+            entity.insert((conveyor, transform));
+        }
+        app.update();
+        let conveyor = app.world().get::<Conveyor>(b).unwrap();
+        assert_eq!(conveyor.direction, Direction::East);
+        assert_eq!(conveyor.shape, ConveyorShape::Straight);
+        assert_eq!(
+            app.world().get::<Transform>(b).unwrap().rotation,
+            Direction::East.to_rotation()
+        );
+    }
+
+    #[test]
+    fn test_rotating_machine_reports_broken_belt_links() {
+        let mut furnace = Machine::new(&FURNACE, IVec3::ZERO, Direction::East);
+        let mut transform = Transform::default();
+        // Belt into the back port, belt taking the output
+        let belts = [
+            (IVec3::new(-1, 0, 0), Direction::East),
+            (IVec3::new(1, 0, 0), Direction::East),
+        ];
+        let before = machine_belt_links(&furnace, &belts);
+        assert_eq!(before, vec![IVec3::new(-1, 0, 0), IVec3::new(1, 0, 0)]);
+
+        // Facing south: the feeding belt now hits the right-side port, the
+        // output belt is left without input
+        rotate_machine(&mut furnace, &mut transform, true);
+        assert_eq!(furnace.facing, Direction::South);
+        assert_eq!(transform.rotation, Direction::South.to_rotation());
+        let after = machine_belt_links(&furnace, &belts);
+        assert_eq!(after, vec![IVec3::new(-1, 0, 0)]);
+    }
+}
@@ -5,10 +5,11 @@
 use bevy::prelude::*;
 
 use crate::components::{
-    CommandInputState, CursorLockState, InteractingMachine, InventoryOpen, UIAction, UIContext,
+    CommandInputState, CursorController, InteractingMachine, InventoryOpen, UIAction, UIContext,
     UIState,
 };
 use crate::input::{GameAction, InputManager};
+use crate::setup::ui::KeyRebindState;
 
 /// Handle UIAction events and update UIState
 pub fn ui_action_handler(
@@ -52,7 +53,7 @@ pub fn sync_legacy_ui_state(
     mut inv_open: ResMut<InventoryOpen>,
     mut cmd_state: ResMut<CommandInputState>,
     mut machine_res: ResMut<InteractingMachine>,
-    mut cursor_lock: ResMut<CursorLockState>,
+    mut cursor: ResMut<CursorController>,
 ) {
     // Only sync if UIState changed
     if !ui_state.is_changed() {
@@ -66,29 +67,20 @@ pub fn sync_legacy_ui_state(
     cmd_state.open = false;
     machine_res.0 = None;
 
+    // The top of the stack owns the cursor
+    cursor.request_ui(current.clone());
+
     // Set legacy state based on current UIState
     match current {
-        UIContext::Gameplay => {
-            cursor_lock.paused = false;
-        }
-        UIContext::Inventory => {
-            inv_open.0 = true;
-            cursor_lock.paused = true;
-        }
-        UIContext::CommandInput => {
-            cmd_state.open = true;
-            cursor_lock.paused = true;
-        }
-        UIContext::PauseMenu => {
-            cursor_lock.paused = true;
-        }
-        UIContext::Settings => {
-            cursor_lock.paused = true;
-        }
-        UIContext::Machine(entity) => {
-            cursor_lock.paused = true;
-            machine_res.0 = Some(entity);
-        }
+        UIContext::Inventory => inv_open.0 = true,
+        UIContext::CommandInput => cmd_state.open = true,
+        UIContext::Machine(entity) => machine_res.0 = Some(entity),
+        UIContext::Gameplay
+        | UIContext::PauseMenu
+        | UIContext::Settings
+        | UIContext::Stats
+        | UIContext::QuestLog
+        | UIContext::Goals => {}
     }
 }
 
@@ -98,6 +90,7 @@ pub fn ui_escape_handler(
     input: Res<InputManager>,
     ui_state: Res<UIState>,
     command_state: Res<CommandInputState>,
+    rebind: Option<Res<KeyRebindState>>,
     mut action_writer: MessageWriter<UIAction>,
 ) {
     if !input.just_pressed(GameAction::Cancel) {
@@ -109,6 +102,11 @@ pub fn ui_escape_handler(
         return;
     }
 
+    // ESC cancels a pending key rebind instead of leaving the settings screen
+    if rebind.is_some_and(|r| r.is_capturing()) {
+        return;
+    }
+
     if ui_state.is_gameplay() {
         // In gameplay, ESC opens pause menu
         action_writer.write(UIAction::Push(UIContext::PauseMenu));
@@ -150,6 +148,34 @@ pub fn ui_inventory_handler(
     }
 }
 
+/// Handle J key for quest log toggle
+pub fn ui_quest_log_handler(
+    input: Res<InputManager>,
+    ui_state: Res<UIState>,
+    command_state: Res<CommandInputState>,
+    mut action_writer: MessageWriter<UIAction>,
+) {
+    if !input.just_pressed(GameAction::ToggleQuestLog) {
+        return;
+    }
+
+    // Don't handle J if command input is open (it handles its own text input)
+    if command_state.open {
+        return;
+    }
+
+    // Open from gameplay, close from the log itself
+    match ui_state.current() {
+        UIContext::Gameplay => {
+            action_writer.write(UIAction::Push(UIContext::QuestLog));
+        }
+        UIContext::QuestLog => {
+            action_writer.write(UIAction::Pop);
+        }
+        _ => {} // Ignore J in other contexts
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
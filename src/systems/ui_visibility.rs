@@ -16,6 +16,9 @@ fn ui_context_to_string(context: &UIContext) -> &'static str {
         UIContext::CommandInput => "CommandInput",
         UIContext::PauseMenu => "PauseMenu",
         UIContext::Settings => "Settings",
+        UIContext::Stats => "Stats",
+        UIContext::QuestLog => "QuestLog",
+        UIContext::Goals => "Goals",
         UIContext::Machine(_) => "MachineUI",
     }
 }
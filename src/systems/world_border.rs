@@ -0,0 +1,215 @@
+//! World border walls and the platform compass
+//!
+//! The border itself is `BorderConfig` in the worldgen config: chunks outside
+//! never generate and `player_move` clamps the player inside. Here the border
+//! is drawn as translucent walls that only show when the player is close, and
+//! a small compass near the hotbar points new players to the delivery
+//! platform until their first delivery, or at the waypoint picked with
+//! `/tp <name>` in survival.
+
+use bevy::prelude::*;
+use bevy::ui::UiTransform;
+use std::f32::consts::{PI, TAU};
+
+use crate::components::{PlatformCompass, Player, PlayerCamera, UIState};
+use crate::constants::CHUNK_HEIGHT;
+use crate::player::LocalPlatform;
+use crate::statistics::PlayerStats;
+use crate::waypoint::Waypoints;
+use crate::world::WorldData;
+use crate::{DeliveryPlatform, BLOCK_SIZE};
+
+/// Walls show when the player is within this many blocks of them
+pub const BORDER_WALL_SHOW_DISTANCE: f32 = 16.0;
+
+/// One side of the world border
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WorldBorderWall {
+    /// Point on the wall plane
+    pub center: Vec3,
+    /// Wall normal (pointing into the playable area)
+    pub normal: Vec3,
+}
+
+impl WorldBorderWall {
+    /// Distance from a position to the wall plane
+    pub fn distance(&self, pos: Vec3) -> f32 {
+        (pos - self.center).dot(self.normal).abs()
+    }
+}
+
+/// Angle (radians, counter-clockwise) from the view direction to `target`
+///
+/// 0 = straight ahead, positive = to the left, in [-PI, PI).
+/// Uses the camera convention: yaw 0 looks towards -Z.
+pub fn compass_angle(player_pos: Vec3, yaw: f32, target: Vec3) -> f32 {
+    let to_target = target - player_pos;
+    let target_yaw = (-to_target.x).atan2(-to_target.z);
+    (target_yaw - yaw + PI).rem_euclid(TAU) - PI
+}
+
+/// Spawn the four border walls (hidden until the player comes close)
+pub fn setup_world_border(
+    mut commands: Commands,
+    world_data: Res<WorldData>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let border = &world_data.gen_config.border;
+    if !border.is_enabled() {
+        return;
+    }
+
+    let (min, max) = border.bounds();
+    let mid = (min + max) / 2.0;
+    let size = max - min;
+    let height = CHUNK_HEIGHT as f32 * BLOCK_SIZE;
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.4, 0.7, 1.0, 0.25),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        cull_mode: None,
+        ..default()
+    });
+
+    // (plane center, inward normal, wall width)
+    let walls = [
+        (Vec2::new(min.x, mid.y), Vec3::X, size.y),
+        (Vec2::new(max.x, mid.y), Vec3::NEG_X, size.y),
+        (Vec2::new(mid.x, min.y), Vec3::Z, size.x),
+        (Vec2::new(mid.x, max.y), Vec3::NEG_Z, size.x),
+    ];
+    for (pos, normal, width) in walls {
+        let center = Vec3::new(pos.x, height / 2.0, pos.y);
+        commands.spawn((
+            Mesh3d(meshes.add(Rectangle::new(width, height))),
+            MeshMaterial3d(material.clone()),
+            // Rectangle faces +Z; turn it to face along the normal
+            Transform::from_translation(center)
+                .with_rotation(Quat::from_rotation_arc(Vec3::Z, normal)),
+            Visibility::Hidden,
+            WorldBorderWall { center, normal },
+        ));
+    }
+}
+
+/// Show border walls near the player
+pub fn update_world_border_walls(
+    player_query: Query<&Transform, (With<Player>, Without<WorldBorderWall>)>,
+    mut wall_query: Query<(&WorldBorderWall, &mut Visibility)>,
+) {
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+    for (wall, mut visibility) in wall_query.iter_mut() {
+        let show = wall.distance(player.translation) <= BORDER_WALL_SHOW_DISTANCE * BLOCK_SIZE;
+        visibility.set_if_neq(if show {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// Point the compass at the delivery platform until the first delivery, or at a followed waypoint
+#[allow(clippy::type_complexity)]
+pub fn update_platform_compass(
+    world_data: Res<WorldData>,
+    ui_state: Res<UIState>,
+    stats: Option<Res<PlayerStats>>,
+    waypoints: Option<Res<Waypoints>>,
+    local_platform: Option<Res<LocalPlatform>>,
+    platforms: Query<&DeliveryPlatform>,
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&PlayerCamera>,
+    mut compass_query: Query<(&mut UiTransform, &mut Visibility), With<PlatformCompass>>,
+) {
+    let Ok((mut transform, mut visibility)) = compass_query.single_mut() else {
+        return;
+    };
+    let delivered = stats.is_some_and(|s| s.items_delivered > 0);
+    let waypoint = waypoints.and_then(|w| w.compass_position());
+    let (Ok(player), Ok(camera)) = (player_query.single(), camera_query.single()) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    if (delivered && waypoint.is_none()) || !ui_state.is_gameplay() {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    // A followed waypoint, else the placed starter platform or its pad from the worldgen config
+    let target = waypoint.unwrap_or_else(|| {
+        let (origin, size) = match local_platform.and_then(|p| platforms.get(p.0).ok()) {
+            Some(platform) => (platform.position, platform.size),
+            None => {
+                let pad = &world_data.gen_config.platform;
+                (pad.origin(), pad.size)
+            }
+        };
+        (origin.as_vec3() + Vec3::new(size as f32, 0.0, size as f32) / 2.0) * BLOCK_SIZE
+    });
+
+    let angle = compass_angle(player.translation, camera.yaw, target);
+    // UI rotation is clockwise (screen y points down)
+    transform.rotation = Rot2::radians(-angle);
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_angle(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_compass_angle_quadrants() {
+        let player = Vec3::new(5.0, 9.0, 5.0);
+        let at = |dx: f32, dz: f32| player + Vec3::new(dx, 0.0, dz);
+
+        // Facing -Z (yaw 0)
+        assert_angle(compass_angle(player, 0.0, at(0.0, -10.0)), 0.0);
+        assert_angle(compass_angle(player, 0.0, at(-10.0, 0.0)), PI / 2.0);
+        assert_angle(compass_angle(player, 0.0, at(10.0, 0.0)), -PI / 2.0);
+        assert_angle(compass_angle(player, 0.0, at(0.0, 10.0)).abs(), PI);
+        assert_angle(compass_angle(player, 0.0, at(-10.0, -10.0)), PI / 4.0);
+        assert_angle(compass_angle(player, 0.0, at(10.0, 10.0)), -3.0 * PI / 4.0);
+
+        // Height difference does not matter
+        assert_angle(
+            compass_angle(player, 0.0, at(-10.0, 0.0) + Vec3::Y * 20.0),
+            PI / 2.0,
+        );
+    }
+
+    #[test]
+    fn test_compass_angle_follows_yaw() {
+        let player = Vec3::ZERO;
+        let target = Vec3::new(-10.0, 0.0, 0.0);
+
+        // Turned left to face -X: the target is straight ahead
+        assert_angle(compass_angle(player, PI / 2.0, target), 0.0);
+        // Facing +X: behind
+        assert_angle(compass_angle(player, -PI / 2.0, target).abs(), PI);
+        // Facing +Z: to the right
+        assert_angle(compass_angle(player, PI, target), -PI / 2.0);
+        // Full turns wrap around
+        assert_angle(compass_angle(player, PI / 2.0 + TAU, target), 0.0);
+        assert_angle(compass_angle(player, -3.0 * PI / 2.0, target), 0.0);
+    }
+
+    #[test]
+    fn test_wall_distance() {
+        let wall = WorldBorderWall {
+            center: Vec3::new(-248.0, 32.0, 20.0),
+            normal: Vec3::X,
+        };
+        assert_angle(wall.distance(Vec3::new(-240.0, 9.0, 100.0)), 8.0);
+        assert_angle(wall.distance(Vec3::new(-248.0, 9.0, -50.0)), 0.0);
+    }
+}
@@ -0,0 +1,603 @@
+//! Wrench tool: copy settings from one machine and paste them onto another
+//!
+//! Left-click copies, right-click pastes, Ctrl+Z reverts the last paste.
+//! Shift+right-click on a machine face toggles its pass-through marking.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::{GameFont, Machine, SlotRoute};
+use crate::core::items;
+use crate::input::{GameAction, InputManager};
+use crate::player::{LocalPlayer, PlayerInventory};
+use crate::setup::ui::{text_font, TEXT_BODY};
+use crate::utils::ray_aabb_intersection;
+use crate::{
+    ContinuousActionTimer, Conveyor, Direction, InputStateResourcesWithCursor, PlayerCamera,
+    BLOCK_SIZE, CONVEYOR_BELT_HEIGHT, CONVEYOR_BELT_WIDTH, REACH_DISTANCE,
+};
+
+/// How long a wrench toast stays on screen (seconds)
+pub const WRENCH_TOAST_SECS: f32 = 2.0;
+
+/// Settings the wrench can copy
+///
+/// Conveyor direction is layout rather than a setting, so belts (including
+/// splitters) only carry their enabled flag.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MachineSettings {
+    Machine {
+        spec_id: &'static str,
+        name: &'static str,
+        facing: Direction,
+        enabled: bool,
+        output_any_side: bool,
+        /// World-space faces routed to one slot (valid with the copied facing)
+        slot_routes: Vec<(Direction, SlotRoute)>,
+    },
+    Conveyor {
+        enabled: bool,
+    },
+}
+
+/// Reason a paste was rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasteError {
+    /// Nothing has been copied yet
+    EmptyClipboard,
+    /// The target is a different kind of machine
+    Incompatible,
+}
+
+impl MachineSettings {
+    pub fn from_machine(machine: &Machine) -> Self {
+        Self::Machine {
+            spec_id: machine.spec.id,
+            name: machine.spec.name,
+            facing: machine.facing,
+            enabled: machine.enabled,
+            output_any_side: machine.output_any_side,
+            slot_routes: machine.slot_routes.clone(),
+        }
+    }
+
+    pub fn from_conveyor(conveyor: &Conveyor) -> Self {
+        Self::Conveyor {
+            enabled: conveyor.enabled,
+        }
+    }
+
+    /// Apply to a machine of the same spec, returning its previous settings
+    pub fn paste_to_machine(&self, machine: &mut Machine) -> Result<Self, PasteError> {
+        let Self::Machine {
+            spec_id,
+            facing,
+            enabled,
+            output_any_side,
+            slot_routes,
+            ..
+        } = self
+        else {
+            return Err(PasteError::Incompatible);
+        };
+        if *spec_id != machine.spec.id {
+            return Err(PasteError::Incompatible);
+        }
+        let previous = Self::from_machine(machine);
+        machine.facing = *facing;
+        machine.enabled = *enabled;
+        machine.output_any_side = *output_any_side;
+        machine.slot_routes = slot_routes.clone();
+        Ok(previous)
+    }
+
+    /// Apply to a conveyor, returning its previous settings
+    pub fn paste_to_conveyor(&self, conveyor: &mut Conveyor) -> Result<Self, PasteError> {
+        let Self::Conveyor { enabled } = *self else {
+            return Err(PasteError::Incompatible);
+        };
+        let previous = Self::from_conveyor(conveyor);
+        conveyor.enabled = enabled;
+        Ok(previous)
+    }
+
+    /// One-line description for the hotbar tooltip and toasts
+    pub fn summary(&self) -> String {
+        let state = |enabled: bool| if enabled { "稼働" } else { "停止" };
+        match self {
+            Self::Machine {
+                name,
+                facing,
+                enabled,
+                output_any_side,
+                slot_routes,
+                ..
+            } => {
+                let sides = if *output_any_side {
+                    ", 全方向出力"
+                } else {
+                    ""
+                };
+                let routes = if slot_routes.is_empty() {
+                    ""
+                } else {
+                    ", 投入先指定"
+                };
+                format!(
+                    "{} ({}向き, {}{}{})",
+                    name,
+                    facing.label(),
+                    state(*enabled),
+                    sides,
+                    routes
+                )
+            }
+            Self::Conveyor { enabled } => format!("コンベア ({})", state(*enabled)),
+        }
+    }
+}
+
+/// Copied settings plus the undo entry for the last paste
+#[derive(Resource, Debug, Default)]
+pub struct MachineSettingsClipboard {
+    settings: Option<MachineSettings>,
+    /// Entity that was pasted onto and the settings it had before
+    undo: Option<(Entity, MachineSettings)>,
+}
+
+impl MachineSettingsClipboard {
+    pub fn copy(&mut self, settings: MachineSettings) {
+        self.settings = Some(settings);
+    }
+
+    pub fn settings(&self) -> Option<&MachineSettings> {
+        self.settings.as_ref()
+    }
+
+    /// Paste onto a machine and remember its previous settings for undo
+    pub fn paste_to_machine(
+        &mut self,
+        entity: Entity,
+        machine: &mut Machine,
+    ) -> Result<(), PasteError> {
+        let settings = self.settings.as_ref().ok_or(PasteError::EmptyClipboard)?;
+        let previous = settings.paste_to_machine(machine)?;
+        self.undo = Some((entity, previous));
+        Ok(())
+    }
+
+    /// Paste onto a conveyor and remember its previous settings for undo
+    pub fn paste_to_conveyor(
+        &mut self,
+        entity: Entity,
+        conveyor: &mut Conveyor,
+    ) -> Result<(), PasteError> {
+        let settings = self.settings.as_ref().ok_or(PasteError::EmptyClipboard)?;
+        let previous = settings.paste_to_conveyor(conveyor)?;
+        self.undo = Some((entity, previous));
+        Ok(())
+    }
+
+    /// Take the undo entry (only the most recent paste can be undone, once)
+    pub fn take_undo(&mut self) -> Option<(Entity, MachineSettings)> {
+        self.undo.take()
+    }
+}
+
+/// Short message shown after a wrench action (also used by the inventory buttons)
+#[derive(Resource, Debug, Default)]
+pub struct WrenchToast {
+    pub text: String,
+    pub remaining: f32,
+}
+
+impl WrenchToast {
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.remaining = WRENCH_TOAST_SECS;
+    }
+}
+
+/// Root node of the wrench toast
+#[derive(Component)]
+pub struct WrenchToastUI;
+
+/// Message text of the wrench toast
+#[derive(Component)]
+pub struct WrenchToastText;
+
+/// Machines and conveyors the wrench can target (reduces parameter count)
+#[derive(SystemParam)]
+pub struct WrenchTargets<'w, 's> {
+    pub machines: Query<'w, 's, (Entity, &'static mut Machine, &'static mut Transform)>,
+    pub conveyors: Query<'w, 's, (Entity, &'static mut Conveyor), Without<Machine>>,
+}
+
+/// What the crosshair is pointing at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WrenchTarget {
+    Machine(Entity),
+    Conveyor(Entity),
+}
+
+fn find_wrench_target(
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    targets: &WrenchTargets,
+) -> Option<WrenchTarget> {
+    let mut closest: Option<(WrenchTarget, f32)> = None;
+    let mut consider = |target: WrenchTarget, min: Vec3, max: Vec3| {
+        if let Some(t) = ray_aabb_intersection(ray_origin, ray_direction, min, max) {
+            if t > 0.0 && t < REACH_DISTANCE && closest.is_none_or(|(_, d)| t < d) {
+                closest = Some((target, t));
+            }
+        }
+    };
+
+    let belt_half = Vec3::new(
+        BLOCK_SIZE * CONVEYOR_BELT_WIDTH / 2.0,
+        CONVEYOR_BELT_HEIGHT / 2.0,
+        BLOCK_SIZE / 2.0,
+    );
+    for (entity, conveyor) in targets.conveyors.iter() {
+        let center = Vec3::new(
+            conveyor.position.x as f32 * BLOCK_SIZE + 0.5,
+            conveyor.position.y as f32 * BLOCK_SIZE + CONVEYOR_BELT_HEIGHT / 2.0,
+            conveyor.position.z as f32 * BLOCK_SIZE + 0.5,
+        );
+        consider(
+            WrenchTarget::Conveyor(entity),
+            center - belt_half,
+            center + belt_half,
+        );
+    }
+    for (entity, machine, _) in targets.machines.iter() {
+        let min = machine.position.as_vec3() * BLOCK_SIZE;
+        consider(
+            WrenchTarget::Machine(entity),
+            min,
+            min + Vec3::splat(BLOCK_SIZE),
+        );
+    }
+
+    closest.map(|(target, _)| target)
+}
+
+/// Horizontal face of a machine block that a ray hit point lies on
+///
+/// Top/bottom hits pick the nearest side edge.
+fn hit_face(machine_pos: IVec3, hit: Vec3) -> Direction {
+    let center = machine_pos.as_vec3() * BLOCK_SIZE + Vec3::splat(BLOCK_SIZE / 2.0);
+    let offset = hit - center;
+    if offset.x.abs() >= offset.z.abs() {
+        if offset.x >= 0.0 {
+            Direction::East
+        } else {
+            Direction::West
+        }
+    } else if offset.z >= 0.0 {
+        Direction::South
+    } else {
+        Direction::North
+    }
+}
+
+fn paste_error_message(err: PasteError) -> &'static str {
+    match err {
+        PasteError::EmptyClipboard => "レンチ: 設定がコピーされていません",
+        PasteError::Incompatible => "レンチ: この機械には貼り付けできません",
+    }
+}
+
+/// Left-click copies, right-click pastes while the wrench is selected
+///
+/// Runs before block_place/toggle_conveyor_enabled and swallows the right-click.
+/// With Shift held, right-clicking a machine toggles pass-through on the clicked face.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_wrench(
+    mut mouse_button: ResMut<ButtonInput<MouseButton>>,
+    input: Res<InputManager>,
+    mut action_timer: ResMut<ContinuousActionTimer>,
+    local_player: Option<Res<LocalPlayer>>,
+    inventories: Query<&PlayerInventory>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
+    input_resources: InputStateResourcesWithCursor,
+    mut targets: WrenchTargets,
+    mut clipboard: ResMut<MachineSettingsClipboard>,
+    mut toast: ResMut<WrenchToast>,
+) {
+    let holding_wrench = local_player
+        .and_then(|lp| inventories.get(lp.0).ok())
+        .and_then(|inv| inv.selected_item_id())
+        == Some(items::wrench());
+    if !holding_wrench {
+        return;
+    }
+    let copy = mouse_button.just_pressed(MouseButton::Left);
+    let paste = mouse_button.just_pressed(MouseButton::Right);
+    if !copy && !paste {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+
+    // Keep the click away from placement/conveyor toggling
+    if paste {
+        mouse_button.clear_just_pressed(MouseButton::Right);
+        action_timer.place_timer.reset();
+    }
+
+    let ray_origin = camera_transform.translation();
+    let ray_direction = camera_transform.forward().as_vec3();
+    let Some(target) = find_wrench_target(ray_origin, ray_direction, &targets) else {
+        return;
+    };
+
+    let toggle_face = paste && input.pressed(GameAction::ModifierShift);
+    if let (true, WrenchTarget::Machine(entity)) = (toggle_face, target) {
+        // Shift+right-click on a machine: toggle the clicked face's pass-through marking
+        let Ok((_, mut machine, _)) = targets.machines.get_mut(entity) else {
+            return;
+        };
+        let min = machine.position.as_vec3() * BLOCK_SIZE;
+        let Some(t) = ray_aabb_intersection(
+            ray_origin,
+            ray_direction,
+            min,
+            min + Vec3::splat(BLOCK_SIZE),
+        ) else {
+            return;
+        };
+        let side = hit_face(machine.position, ray_origin + ray_direction * t);
+        let message = if machine.toggle_pass_through(side) {
+            "通過"
+        } else {
+            "通常"
+        };
+        toast.show(format!("{}面: {}", side.label(), message));
+        return;
+    }
+
+    if copy {
+        let settings = match target {
+            WrenchTarget::Machine(entity) => targets
+                .machines
+                .get(entity)
+                .map(|(_, machine, _)| MachineSettings::from_machine(machine)),
+            WrenchTarget::Conveyor(entity) => targets
+                .conveyors
+                .get(entity)
+                .map(|(_, conveyor)| MachineSettings::from_conveyor(conveyor)),
+        };
+        if let Ok(settings) = settings {
+            toast.show(format!("コピー: {}", settings.summary()));
+            clipboard.copy(settings);
+        }
+        return;
+    }
+
+    let result = match target {
+        WrenchTarget::Machine(entity) => {
+            let Ok((_, mut machine, mut transform)) = targets.machines.get_mut(entity) else {
+                return;
+            };
+            let result = clipboard.paste_to_machine(entity, &mut machine);
+            if result.is_ok() {
+                transform.rotation = machine.facing.to_rotation();
+            }
+            result
+        }
+        WrenchTarget::Conveyor(entity) => {
+            let Ok((_, mut conveyor)) = targets.conveyors.get_mut(entity) else {
+                return;
+            };
+            clipboard.paste_to_conveyor(entity, &mut conveyor)
+        }
+    };
+    match result {
+        Ok(()) => toast.show("貼り付けました (Ctrl+Zで元に戻す)"),
+        Err(err) => toast.show(paste_error_message(err)),
+    }
+}
+
+/// Ctrl+Z restores the settings the last pasted machine had before the paste
+pub fn undo_wrench_paste(
+    input: Res<InputManager>,
+    input_resources: InputStateResourcesWithCursor,
+    mut targets: WrenchTargets,
+    mut clipboard: ResMut<MachineSettingsClipboard>,
+    mut toast: ResMut<WrenchToast>,
+) {
+    if !input.pressed(GameAction::ModifierCtrl) || !input.just_pressed(GameAction::Undo) {
+        return;
+    }
+    if !input_resources.get_state().allows_block_actions() {
+        return;
+    }
+    let Some((entity, previous)) = clipboard.take_undo() else {
+        return;
+    };
+
+    let restored = if let Ok((_, mut machine, mut transform)) = targets.machines.get_mut(entity) {
+        let restored = previous.paste_to_machine(&mut machine).is_ok();
+        transform.rotation = machine.facing.to_rotation();
+        restored
+    } else if let Ok((_, mut conveyor)) = targets.conveyors.get_mut(entity) {
+        previous.paste_to_conveyor(&mut conveyor).is_ok()
+    } else {
+        false
+    };
+    if restored {
+        toast.show("貼り付けを元に戻しました");
+    }
+}
+
+pub fn setup_wrench_toast(mut commands: Commands, game_font: Res<GameFont>) {
+    commands
+        .spawn((
+            WrenchToastUI,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(120.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            // Above the inventory panel, which the inventory buttons toast over
+            GlobalZIndex(60),
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                WrenchToastText,
+                Text::new(""),
+                text_font(&game_font.0, TEXT_BODY),
+                TextColor(Color::WHITE),
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    border_radius: BorderRadius::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+            ));
+        });
+}
+
+/// Show the toast text until it expires
+pub fn update_wrench_toast(
+    time: Res<Time>,
+    mut toast: ResMut<WrenchToast>,
+    mut ui_query: Query<&mut Visibility, With<WrenchToastUI>>,
+    mut text_query: Query<&mut Text, With<WrenchToastText>>,
+) {
+    if toast.remaining <= 0.0 && !toast.is_changed() {
+        return;
+    }
+    toast.remaining -= time.delta_secs();
+    let visible = toast.remaining > 0.0;
+    for mut vis in ui_query.iter_mut() {
+        *vis = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if visible {
+        for mut text in text_query.iter_mut() {
+            if **text != toast.text {
+                **text = toast.text.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_spec::{FURNACE, MINER};
+    use crate::ConveyorShape;
+
+    #[test]
+    fn test_hit_face_picks_nearest_side() {
+        let pos = IVec3::new(2, 8, -3);
+        let center = pos.as_vec3() + Vec3::splat(0.5);
+        assert_eq!(
+            hit_face(pos, center + Vec3::new(0.5, 0.1, 0.2)),
+            Direction::East
+        );
+        assert_eq!(
+            hit_face(pos, center + Vec3::new(-0.5, 0.0, 0.0)),
+            Direction::West
+        );
+        assert_eq!(
+            hit_face(pos, center + Vec3::new(0.1, 0.0, 0.5)),
+            Direction::South
+        );
+        // Top face hit near the north edge
+        assert_eq!(
+            hit_face(pos, center + Vec3::new(0.1, 0.5, -0.4)),
+            Direction::North
+        );
+    }
+
+    fn splitter(pos: IVec3, enabled: bool) -> Conveyor {
+        Conveyor {
+            position: pos,
+            direction: Direction::North,
+            output_direction: Direction::North,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled,
+            shape: ConveyorShape::Splitter,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_copy_splitter_paste_to_splitter() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+
+        let source = splitter(IVec3::ZERO, false);
+        let mut dest = splitter(IVec3::X, true);
+
+        let mut clipboard = MachineSettingsClipboard::default();
+        clipboard.copy(MachineSettings::from_conveyor(&source));
+        assert_eq!(clipboard.paste_to_conveyor(target, &mut dest), Ok(()));
+        assert!(!dest.enabled);
+        // Layout is untouched
+        assert_eq!(dest.position, IVec3::X);
+        assert_eq!(dest.shape, ConveyorShape::Splitter);
+
+        // One undo restores the previous settings, a second does nothing
+        let (entity, previous) = clipboard.take_undo().unwrap();
+        assert_eq!(entity, target);
+        previous.paste_to_conveyor(&mut dest).unwrap();
+        assert!(dest.enabled);
+        assert!(clipboard.take_undo().is_none());
+    }
+
+    #[test]
+    fn test_paste_to_incompatible_machine_is_rejected() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+
+        let mut miner = Machine::new(&MINER, IVec3::ZERO, Direction::East);
+        miner.enabled = false;
+        miner.output_any_side = true;
+        let mut furnace = Machine::new(&FURNACE, IVec3::X, Direction::North);
+        let mut belt = splitter(IVec3::Y, true);
+
+        let mut clipboard = MachineSettingsClipboard::default();
+        assert_eq!(
+            clipboard.paste_to_machine(target, &mut furnace),
+            Err(PasteError::EmptyClipboard)
+        );
+
+        clipboard.copy(MachineSettings::from_machine(&miner));
+        assert_eq!(
+            clipboard.paste_to_machine(target, &mut furnace),
+            Err(PasteError::Incompatible)
+        );
+        assert_eq!(
+            clipboard.paste_to_conveyor(target, &mut belt),
+            Err(PasteError::Incompatible)
+        );
+        assert!(furnace.enabled);
+        assert_eq!(furnace.facing, Direction::North);
+        assert!(belt.enabled);
+        assert!(clipboard.take_undo().is_none());
+
+        // Same spec accepts facing, enabled and the output side toggle
+        let mut other_miner = Machine::new(&MINER, IVec3::Z, Direction::South);
+        assert_eq!(clipboard.paste_to_machine(target, &mut other_miner), Ok(()));
+        assert_eq!(other_miner.facing, Direction::East);
+        assert!(!other_miner.enabled);
+        assert!(other_miner.output_any_side);
+    }
+}
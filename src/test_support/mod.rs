@@ -0,0 +1,189 @@
+//! Headless app harness for gameplay integration tests
+//!
+//! `build_headless_app` returns an [`App`] with `MinimalPlugins`, the
+//! resources and messages the simulation needs, and the simulation systems
+//! themselves. Nothing that needs a window, GPU or UI is registered.
+//!
+//! ## Determinism
+//!
+//! In the game the simulation runs in `FixedUpdate`, driven by real time.
+//! Here the same systems run in `Update`, in the same order
+//! (`generic_machine_tick` → `hopper_tick` → `conveyor_transfer`, with
+//! dropped items falling before the hoppers look for them), so every
+//! [`App::update`] is exactly one simulation tick of `SIMULATION_TICK_SECS`
+//! regardless of wall-clock time. Use [`run_ticks`] to advance.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let mut app = build_headless_app();
+//! let furnace = place_machine(&mut app, items::furnace_block(), IVec3::new(0, 20, 0), Direction::North);
+//! app.world_mut().get_mut::<Machine>(furnace).unwrap().slots.fuel = 1;
+//! run_ticks(&mut app, 100);
+//! ```
+//!
+//! Keep machines away from the platform spawned by [`spawn_platform`] and
+//! from terrain unless a test is about them: the chunk at the origin is
+//! generated so [`world_block`] has real blocks to look at.
+
+use bevy::prelude::*;
+
+use crate::components::{CurrentQuest, DeliveryPlatform, Machine};
+use crate::core::{fluids, items, ItemId};
+use crate::events::game_events::{
+    ConveyorTransfer, ItemDelivered, MachineCompleted, MachineStarted,
+};
+use crate::events::{EventDepth, EventSystemConfig};
+use crate::game_spec::recipes::register_recipes;
+use crate::game_spec::{
+    get_machine_spec_by_id, FluidAmount, MachineSpec, MachineType, ProcessType, Recipe,
+    RecipeInput, RecipeOutput, UnlockCondition, CRUSHER,
+};
+use crate::logistics::dropped_item::fall_dropped_items;
+use crate::logistics::DeliveryFilter;
+use crate::machines::generic::{generic_machine_tick, hopper_tick};
+use crate::player::{LocalPlatform, PlatformInventory};
+use crate::systems::conveyor_transfer;
+use crate::world::{BiomeMap, WorldData};
+use crate::{Conveyor, ConveyorShape, Direction};
+
+mod tests;
+
+/// Build an app that runs the gameplay simulation without rendering, window or UI
+pub fn build_headless_app() -> App {
+    let mut world_data = WorldData::default();
+    world_data.ensure_chunk_loaded(IVec2::ZERO);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(world_data)
+        .init_resource::<BiomeMap>()
+        .init_resource::<CurrentQuest>()
+        .init_resource::<EventDepth>()
+        .init_resource::<EventSystemConfig>()
+        .add_message::<MachineStarted>()
+        .add_message::<MachineCompleted>()
+        .add_message::<ConveyorTransfer>()
+        .add_message::<ItemDelivered>()
+        .add_systems(
+            Update,
+            (
+                generic_machine_tick,
+                fall_dropped_items,
+                hopper_tick,
+                conveyor_transfer,
+            )
+                .chain(),
+        );
+    app
+}
+
+/// Spawn a machine of the given item kind (furnace_block, crusher_block, ...)
+///
+/// Panics if the item is not a machine with a spec.
+pub fn place_machine(app: &mut App, kind: ItemId, pos: IVec3, facing: Direction) -> Entity {
+    let spec = get_machine_spec_by_id(kind)
+        .unwrap_or_else(|| panic!("{:?} has no machine spec", kind.name()));
+    app.world_mut().spawn(Machine::new(spec, pos, facing)).id()
+}
+
+/// Spawn a straight base-speed conveyor
+pub fn place_conveyor(app: &mut App, pos: IVec3, direction: Direction) -> Entity {
+    app.world_mut()
+        .spawn(Conveyor {
+            position: pos,
+            direction,
+            output_direction: direction,
+            items: Vec::new(),
+            last_output_index: 0,
+            last_input_pos: None,
+            enabled: true,
+            shape: ConveyorShape::Straight,
+            speed_multiplier: 1.0,
+        })
+        .id()
+}
+
+/// Spawn a delivery platform at `origin` and make it the local player's platform
+pub fn spawn_platform(app: &mut App, origin: IVec3) -> Entity {
+    let platform = app
+        .world_mut()
+        .spawn((
+            DeliveryPlatform::new(origin),
+            DeliveryFilter::default(),
+            PlatformInventory::new(),
+        ))
+        .id();
+    app.insert_resource(LocalPlatform(platform));
+    platform
+}
+
+/// Advance the simulation by `n` ticks
+pub fn run_ticks(app: &mut App, n: u32) {
+    for _ in 0..n {
+        app.update();
+    }
+}
+
+/// Work type of the fixture recipes from [`register_fluid_recipes`]
+pub const FLUID_TEST_WORK: MachineType = MachineType::Custom("test_fluids");
+
+/// Crusher-shaped machine that runs the fluid fixture recipes
+pub const FLUID_TEST_MACHINE: MachineSpec = MachineSpec {
+    id: "test_fluid_machine",
+    process_type: ProcessType::Recipe(FLUID_TEST_WORK),
+    ..CRUSHER
+};
+
+/// Register recipes that move fluids (no built-in recipe does yet)
+///
+/// `test_grass_to_water`: 2 grass → 100 water in 2s.
+/// `test_wash_stone`: 4 stone + 100 water → 1 iron dust in 3s.
+pub fn register_fluid_recipes() {
+    let recipe = |id, input, output: Option<RecipeOutput>, craft_time| Recipe {
+        id,
+        machine: FLUID_TEST_WORK,
+        inputs: vec![input],
+        outputs: output.into_iter().collect(),
+        craft_time,
+        fuel: None,
+        fluid_input: None,
+        fluid_output: None,
+        unlock: UnlockCondition::Always,
+        priority: 0,
+    };
+    let water = FluidAmount::new(fluids::water(), 100.0);
+    register_recipes([
+        Recipe {
+            fluid_output: Some(water),
+            ..recipe(
+                "test_grass_to_water",
+                RecipeInput::new(items::grass(), 2, 0),
+                None,
+                2.0,
+            )
+        },
+        Recipe {
+            fluid_input: Some(water),
+            ..recipe(
+                "test_wash_stone",
+                RecipeInput::new(items::stone(), 4, 0),
+                Some(RecipeOutput::guaranteed(items::iron_dust(), 1)),
+                3.0,
+            )
+        },
+    ]);
+}
+
+/// Block at a world position (None for air or unloaded chunks)
+pub fn world_block(app: &App, pos: IVec3) -> Option<ItemId> {
+    app.world().resource::<WorldData>().get_block(pos)
+}
+
+/// Count of an item in the local platform's inventory (0 without a platform)
+pub fn inventory_count(app: &App, item_id: ItemId) -> u32 {
+    app.world()
+        .get_resource::<LocalPlatform>()
+        .and_then(|platform| app.world().get::<PlatformInventory>(platform.0))
+        .map_or(0, |inventory| inventory.get_count_by_id(item_id))
+}
@@ -0,0 +1,306 @@
+//! Gameplay behaviors exercised through the headless harness
+
+use super::*;
+use crate::components::ConveyorItem;
+use crate::core::items;
+
+#[test]
+fn test_furnace_smelts_with_fuel() {
+    let mut app = build_headless_app();
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        IVec3::new(0, 20, 0),
+        Direction::North,
+    );
+    {
+        let world = app.world_mut();
+        let mut machine = world.get_mut::<Machine>(furnace).unwrap();
+        machine.slots.fuel = 5;
+        machine.slots.inputs[0].add_id(items::iron_ore(), 2);
+    }
+
+    run_ticks(&mut app, 200);
+
+    let machine = app.world().get::<Machine>(furnace).unwrap();
+    assert_eq!(machine.slots.outputs[0].item_id, Some(items::iron_ingot()));
+    assert_eq!(machine.slots.outputs[0].count, 2);
+    assert_eq!(machine.slots.fuel, 3);
+    assert!(machine.slots.inputs[0].is_empty());
+}
+
+#[test]
+fn test_furnace_without_fuel_does_nothing() {
+    let mut app = build_headless_app();
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        IVec3::new(0, 20, 0),
+        Direction::North,
+    );
+    app.world_mut()
+        .get_mut::<Machine>(furnace)
+        .unwrap()
+        .slots
+        .inputs[0]
+        .add_id(items::iron_ore(), 1);
+
+    run_ticks(&mut app, 200);
+
+    let machine = app.world().get::<Machine>(furnace).unwrap();
+    assert!(machine.slots.outputs[0].is_empty());
+    assert_eq!(machine.slots.inputs[0].count, 1);
+}
+
+#[test]
+fn test_crusher_doubles_ore() {
+    let mut app = build_headless_app();
+    let crusher = place_machine(
+        &mut app,
+        items::crusher_block(),
+        IVec3::new(0, 20, 0),
+        Direction::North,
+    );
+    app.world_mut()
+        .get_mut::<Machine>(crusher)
+        .unwrap()
+        .slots
+        .inputs[0]
+        .add_id(items::iron_ore(), 3);
+
+    run_ticks(&mut app, 200);
+
+    let machine = app.world().get::<Machine>(crusher).unwrap();
+    assert_eq!(machine.slots.outputs[0].item_id, Some(items::iron_dust()));
+    assert_eq!(machine.slots.outputs[0].count, 6);
+}
+
+#[test]
+fn test_conveyor_chain_delivers_to_platform() {
+    let mut app = build_headless_app();
+    // Platform covers x/z 0..PLATFORM_SIZE at y = 20; belts run east into it
+    spawn_platform(&mut app, IVec3::new(0, 20, 0));
+    let belts: Vec<Entity> = (-4..0)
+        .map(|x| place_conveyor(&mut app, IVec3::new(x, 20, 2), Direction::East))
+        .collect();
+    app.world_mut()
+        .get_mut::<Conveyor>(belts[0])
+        .unwrap()
+        .items
+        .push(ConveyorItem::new(items::iron_ingot(), 0.0));
+
+    assert_eq!(inventory_count(&app, items::iron_ingot()), 0);
+    run_ticks(&mut app, 400);
+
+    assert_eq!(inventory_count(&app, items::iron_ingot()), 1);
+    for belt in belts {
+        assert!(app.world().get::<Conveyor>(belt).unwrap().items.is_empty());
+    }
+}
+
+#[test]
+fn test_crusher_output_feeds_furnace_over_belt() {
+    let mut app = build_headless_app();
+    // Crusher faces east onto a belt that runs into the furnace's back port
+    let crusher = place_machine(
+        &mut app,
+        items::crusher_block(),
+        IVec3::new(0, 20, 0),
+        Direction::East,
+    );
+    place_conveyor(&mut app, IVec3::new(1, 20, 0), Direction::East);
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        IVec3::new(2, 20, 0),
+        Direction::East,
+    );
+    app.world_mut()
+        .get_mut::<Machine>(crusher)
+        .unwrap()
+        .slots
+        .inputs[0]
+        .add_id(items::iron_ore(), 1);
+    app.world_mut()
+        .get_mut::<Machine>(furnace)
+        .unwrap()
+        .slots
+        .fuel = 5;
+
+    run_ticks(&mut app, 400);
+
+    let furnace = app.world().get::<Machine>(furnace).unwrap();
+    assert_eq!(furnace.slots.outputs[0].item_id, Some(items::iron_ingot()));
+    assert_eq!(furnace.slots.outputs[0].count, 2);
+}
+
+#[test]
+fn test_world_block_reads_generated_chunk() {
+    let app = build_headless_app();
+    assert!(world_block(&app, IVec3::new(3, 0, 3)).is_some());
+    // Chunks other than the origin are not loaded
+    assert!(world_block(&app, IVec3::new(1000, 0, 1000)).is_none());
+}
+
+#[test]
+fn test_machines_sharing_a_belt_cell_keep_item_spacing() {
+    use crate::constants::CONVEYOR_ITEM_SPACING;
+
+    let mut app = build_headless_app();
+    // Miner from the west and furnace from the north both output onto (1, 20, 0)
+    let miner = place_machine(
+        &mut app,
+        items::miner_block(),
+        IVec3::new(0, 20, 0),
+        Direction::East,
+    );
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        IVec3::new(1, 20, -1),
+        Direction::South,
+    );
+    // Different items so the two outputs never merge into one stack
+    app.world_mut()
+        .get_mut::<Machine>(miner)
+        .unwrap()
+        .slots
+        .outputs[0]
+        .add_id(items::iron_ore(), 6);
+    {
+        let world = app.world_mut();
+        let mut furnace = world.get_mut::<Machine>(furnace).unwrap();
+        furnace.slots.fuel = 20;
+        furnace.slots.inputs[0].add_id(items::iron_ore(), 20);
+        furnace.slots.outputs[0].add_id(items::iron_ingot(), 6);
+    }
+    let belts: Vec<Entity> = (1..5)
+        .map(|x| place_conveyor(&mut app, IVec3::new(x, 20, 0), Direction::East))
+        .collect();
+
+    for tick in 0..300 {
+        run_ticks(&mut app, 1);
+        for &belt in &belts {
+            let conveyor = app.world().get::<Conveyor>(belt).unwrap();
+            for (i, a) in conveyor.items.iter().enumerate() {
+                for b in &conveyor.items[i + 1..] {
+                    let gap = (a.progress - b.progress).abs();
+                    assert!(
+                        gap >= CONVEYOR_ITEM_SPACING - 1e-4,
+                        "tick {tick}: items {gap} apart on {:?}",
+                        conveyor.position
+                    );
+                }
+            }
+        }
+    }
+
+    // Both machines got items onto the shared belt, not just the one processed first
+    let on_belts = |item_id| -> u32 {
+        belts
+            .iter()
+            .flat_map(|&belt| &app.world().get::<Conveyor>(belt).unwrap().items)
+            .filter(|item| item.item_id == item_id)
+            .map(|item| item.count)
+            .sum()
+    };
+    assert!(on_belts(items::iron_ore()) > 0);
+    assert!(on_belts(items::iron_ingot()) > 0);
+}
+
+/// Drop a stack at a world position (it falls from there)
+fn drop_stack(app: &mut App, pos: Vec3, item_id: ItemId, count: u32) -> Entity {
+    use crate::logistics::DroppedItem;
+
+    app.world_mut()
+        .spawn((
+            DroppedItem::new(item_id, count),
+            Transform::from_translation(pos),
+        ))
+        .id()
+}
+
+#[test]
+fn test_dropped_stack_through_hopper_is_smelted() {
+    use crate::logistics::DroppedItem;
+
+    let mut app = build_headless_app();
+    let furnace_pos = IVec3::new(0, 20, 0);
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        furnace_pos,
+        Direction::North,
+    );
+    let hopper = place_machine(
+        &mut app,
+        items::hopper_block(),
+        furnace_pos + IVec3::Y,
+        Direction::North,
+    );
+    drop_stack(&mut app, Vec3::new(0.5, 25.5, 0.5), items::iron_ore(), 3);
+    drop_stack(&mut app, Vec3::new(0.5, 26.5, 0.5), items::coal(), 2);
+
+    run_ticks(&mut app, 400);
+
+    // Both stacks fell into the hopper, the hopper fed ore and fuel below
+    let dropped = app
+        .world_mut()
+        .query::<&DroppedItem>()
+        .iter(app.world())
+        .count();
+    assert_eq!(dropped, 0);
+    let hopper = app.world().get::<Machine>(hopper).unwrap();
+    assert!(hopper.slots.inputs.iter().all(|slot| slot.is_empty()));
+
+    let machine = app.world().get::<Machine>(furnace).unwrap();
+    assert_eq!(machine.slots.outputs[0].item_id, Some(items::iron_ingot()));
+    assert_eq!(machine.slots.outputs[0].count, 3);
+    assert!(machine.slots.inputs[0].is_empty());
+}
+
+#[test]
+fn test_full_target_backs_up_hopper() {
+    use crate::components::MachineStatus;
+
+    let mut app = build_headless_app();
+    let furnace_pos = IVec3::new(0, 20, 0);
+    let furnace = place_machine(
+        &mut app,
+        items::furnace_block(),
+        furnace_pos,
+        Direction::North,
+    );
+    // Unfuelled furnace with a full input slot of another ore
+    app.world_mut()
+        .get_mut::<Machine>(furnace)
+        .unwrap()
+        .slots
+        .inputs[0]
+        .add_id(items::copper_ore(), 64);
+    let hopper = place_machine(
+        &mut app,
+        items::hopper_block(),
+        furnace_pos + IVec3::Y,
+        Direction::North,
+    );
+    drop_stack(&mut app, Vec3::new(0.5, 22.0, 0.5), items::iron_ore(), 5);
+
+    run_ticks(&mut app, 200);
+
+    // Nothing was voided: the ore waits in the hopper
+    let machine = app.world().get::<Machine>(hopper).unwrap();
+    assert_eq!(machine.slots.inputs[0].item_id, Some(items::iron_ore()));
+    assert_eq!(machine.slots.inputs[0].count, 5);
+    assert_eq!(machine.status, MachineStatus::OutputFull);
+    let furnace = app.world().get::<Machine>(furnace).unwrap();
+    assert_eq!(furnace.slots.inputs[0].item_id, Some(items::copper_ore()));
+    assert_eq!(furnace.slots.inputs[0].count, 64);
+
+    // Fuel goes through though: it has its own slot
+    drop_stack(&mut app, Vec3::new(0.5, 22.0, 0.5), items::coal(), 1);
+    run_ticks(&mut app, 30);
+    let machine = app.world().get::<Machine>(hopper).unwrap();
+    assert_eq!(machine.slots.inputs[0].count, 5);
+    assert!(machine.slots.inputs[1].is_empty());
+}
@@ -1,9 +1,14 @@
-//! Machine UI setup (Furnace, Crusher, Miner)
+//! Machine UI setup (Furnace, Crusher, Miner, Recycler, Hopper)
 //!
 //! Follows design rules from .specify/memory/ui-design-rules.md
+//!
+//! Recipe machines also get a recipe browser that replaces the slot view;
+//! its rows are built when it opens (see `machines::generic::recipe_browser`).
 
 use crate::components::*;
-use crate::game_spec::{MachineSpec, UIElementRegistry, UIElementTag, UiSlotDef, UiSlotType};
+use crate::game_spec::{
+    MachineSpec, PortSide, ProcessType, UIElementRegistry, UIElementTag, UiSlotDef, UiSlotType,
+};
 use crate::setup::ui::{
     text_font, QUEST_BORDER_COLOR, QUEST_PROGRESS_COLOR, QUEST_RADIUS, SLOT_BG, SLOT_BORDER,
     SLOT_BORDER_COLOR, SLOT_RADIUS, SLOT_SIZE, TEXT_BODY, TEXT_BUTTON, TEXT_MINI, TEXT_SMALL,
@@ -14,6 +19,9 @@ use bevy::prelude::*;
 // === Design Rule Constants ===
 const PANEL_PADDING: f32 = 20.0;
 const HEADER_HEIGHT: f32 = 30.0;
+const SLOT_GAP: f32 = 8.0;
+/// More output slots than this are laid out as a 2-column grid (recycler)
+const OUTPUT_ROW_MAX: usize = 2;
 
 // Factory theme colors (consistent with other UI)
 const PANEL_BG: Color = Color::srgba(0.10, 0.10, 0.10, 0.95);
@@ -23,6 +31,9 @@ const PROGRESS_FILL: Color = QUEST_PROGRESS_COLOR; // Orange theme
 const TEXT_PRIMARY: Color = Color::WHITE;
 const TEXT_SECONDARY: Color = Color::srgb(0.67, 0.67, 0.67);
 const HEADER_COLOR: Color = Color::srgb(1.0, 0.8, 0.0); // Yellow header
+const DRILL_FILL: Color = Color::srgb(0.3, 0.8, 0.4);
+/// Width of one utilization sparkline bar (120 samples = 180 px)
+const ACTIVITY_BAR_WIDTH: f32 = 1.5;
 
 // =============================================================================
 // Generic Machine UI Generator
@@ -79,8 +90,15 @@ fn spawn_generic_slot(
 /// - Input slots row
 /// - Progress bar
 /// - Fuel slot (if present)
+/// - Slot route buttons per input face (if the machine takes fuel)
+/// - Drill head slot and durability bar (if present)
 /// - Output slots row
+/// - Facing, status and utilization sparkline
+/// - Enable/disable toggle
+/// - Recipes button (recipe machines)
 /// - Instructions
+///
+/// Recipe machines get a hidden recipe browser next to the slot view.
 pub fn setup_generic_machine_ui(
     commands: &mut Commands,
     spec: &'static MachineSpec,
@@ -124,13 +142,16 @@ pub fn setup_generic_machine_ui(
             // === Content ===
             let font_content = font.clone();
             panel
-                .spawn((Node {
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    padding: UiRect::all(Val::Px(PANEL_PADDING)),
-                    row_gap: Val::Px(12.0),
-                    ..default()
-                },))
+                .spawn((
+                    GenericMachineSlotView,
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                        row_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                ))
                 .with_children(|content| {
                     // Input -> Output row
                     spawn_io_row(content, spec, &font_content);
@@ -141,6 +162,57 @@ pub fn setup_generic_machine_ui(
                     // Fuel slot (if any)
                     spawn_fuel_row(content, spec, &font_content);
 
+                    // Per-face slot routing (fuel machines)
+                    spawn_slot_route_row(content, spec, &font_content);
+
+                    // Drill head slot and durability (miner)
+                    spawn_drill_row(content, spec, &font_content);
+
+                    // Facing (output face)
+                    content.spawn((
+                        Text::new(""),
+                        text_font(&font_content, TEXT_SMALL),
+                        TextColor(TEXT_SECONDARY),
+                        GenericMachineFacingText,
+                    ));
+
+                    // Status
+                    content.spawn((
+                        Text::new(""),
+                        text_font(&font_content, TEXT_SMALL),
+                        TextColor(TEXT_SECONDARY),
+                        GenericMachineStatusText,
+                    ));
+
+                    // Utilization history
+                    spawn_activity_row(content, &font_content);
+
+                    // Enable/disable toggle
+                    spawn_toggle_button(
+                        content,
+                        &font_content,
+                        GenericMachineEnableButton,
+                        GenericMachineEnableText,
+                    );
+
+                    // Output side fallback toggle
+                    spawn_toggle_button(
+                        content,
+                        &font_content,
+                        GenericMachineOutputSideButton,
+                        GenericMachineOutputSideText,
+                    );
+
+                    // Recipe browser
+                    if matches!(spec.process_type, ProcessType::Recipe(_)) {
+                        spawn_label_button(
+                            content,
+                            &font_content,
+                            GenericMachineRecipesButton,
+                            "レシピ",
+                        );
+                    }
+
                     // Instructions
                     content.spawn((
                         Text::new("E/ESC で閉じる"),
@@ -148,6 +220,110 @@ pub fn setup_generic_machine_ui(
                         TextColor(TEXT_SECONDARY),
                     ));
                 });
+
+            if matches!(spec.process_type, ProcessType::Recipe(_)) {
+                spawn_recipe_view(panel, spec, &font);
+            }
+        });
+}
+
+/// Spawn the hidden recipe browser (rows are added when it opens)
+fn spawn_recipe_view(panel: &mut ChildSpawnerCommands, spec: &MachineSpec, font: &Handle<Font>) {
+    panel
+        .spawn((
+            GenericMachineRecipeView,
+            Node {
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                row_gap: Val::Px(SLOT_GAP),
+                ..default()
+            },
+        ))
+        .with_children(|view| {
+            view.spawn((
+                GenericMachineRecipeList {
+                    machine_id: spec.id,
+                },
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    max_height: Val::Vh(50.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+            ));
+            view.spawn(Node {
+                justify_content: JustifyContent::Center,
+                ..default()
+            })
+            .with_children(|row| {
+                spawn_label_button(row, font, GenericMachineRecipeBackButton, "戻る");
+            });
+        });
+}
+
+/// Spawn a button with a fixed label
+fn spawn_label_button(
+    content: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button_marker: impl Component,
+    label: &str,
+) {
+    content
+        .spawn((
+            Button,
+            button_marker,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(SLOT_BORDER)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(SLOT_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                text_font(font, TEXT_SMALL),
+                TextColor(TEXT_PRIMARY),
+            ));
+        });
+}
+
+/// Spawn a toggle button (label is set by update_generic_machine_ui)
+fn spawn_toggle_button(
+    content: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button_marker: impl Component,
+    label_marker: impl Component,
+) {
+    content
+        .spawn((
+            Button,
+            button_marker,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(SLOT_BORDER)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                ..default()
+            },
+            BackgroundColor(SLOT_BG),
+            BorderColor::all(SLOT_BORDER_COLOR),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                label_marker,
+                Text::new(""),
+                text_font(font, TEXT_SMALL),
+                TextColor(TEXT_PRIMARY),
+            ));
         });
 }
 
@@ -164,7 +340,8 @@ fn calculate_panel_width(spec: &MachineSpec) -> f32 {
         .filter(|s| matches!(s.slot_type, UiSlotType::Output))
         .count();
 
-    let slot_count = input_count.max(1) + output_count.max(1);
+    let output_columns = output_count.clamp(1, OUTPUT_ROW_MAX);
+    let slot_count = input_count.max(1) + output_columns;
     let base_width = (slot_count as f32 * (SLOT_SIZE + 12.0)) + 60.0;
     base_width.max(250.0)
 }
@@ -185,11 +362,14 @@ fn spawn_header(panel: &mut ChildSpawnerCommands, spec: &MachineSpec, font: &Han
             BorderColor::all(PANEL_BORDER),
         ))
         .with_children(|header| {
+            let color = spec
+                .ui_color
+                .map_or(HEADER_COLOR, |(r, g, b)| Color::srgb(r, g, b));
             header.spawn((
                 GenericMachineHeaderText,
                 Text::new(spec.name),
                 text_font(font, TEXT_BUTTON),
-                TextColor(HEADER_COLOR),
+                TextColor(color),
             ));
         });
 }
@@ -229,9 +409,25 @@ fn spawn_io_row(content: &mut ChildSpawnerCommands, spec: &MachineSpec, font: &H
                 ));
             }
 
-            // Output slots
-            for slot_def in output_slots {
-                spawn_generic_slot(row, slot_def, font);
+            // Output slots (2-column grid when there are many)
+            if output_slots.len() > OUTPUT_ROW_MAX {
+                row.spawn((Node {
+                    width: Val::Px(SLOT_SIZE * OUTPUT_ROW_MAX as f32 + SLOT_GAP),
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(SLOT_GAP),
+                    row_gap: Val::Px(SLOT_GAP),
+                    ..default()
+                },))
+                    .with_children(|grid| {
+                        for slot_def in output_slots {
+                            spawn_generic_slot(grid, slot_def, font);
+                        }
+                    });
+            } else {
+                for slot_def in output_slots {
+                    spawn_generic_slot(row, slot_def, font);
+                }
             }
         });
 }
@@ -260,6 +456,41 @@ fn spawn_progress_bar(content: &mut ChildSpawnerCommands) {
         });
 }
 
+/// Spawn the utilization sparkline (one thin bar per sample, newest on the right) and its label
+fn spawn_activity_row(content: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    content
+        .spawn((
+            Node {
+                width: Val::Px(ACTIVITY_BAR_WIDTH * ACTIVITY_SAMPLES as f32),
+                height: Val::Px(20.0),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            BackgroundColor(PROGRESS_BG),
+        ))
+        .with_children(|bars| {
+            for index in 0..ACTIVITY_SAMPLES {
+                bars.spawn((
+                    GenericMachineActivityBar(index),
+                    Node {
+                        width: Val::Px(ACTIVITY_BAR_WIDTH),
+                        height: Val::Percent(0.0),
+                        ..default()
+                    },
+                    BackgroundColor(DRILL_FILL),
+                ));
+            }
+        });
+
+    content.spawn((
+        Text::new(""),
+        text_font(font, TEXT_MINI),
+        TextColor(TEXT_SECONDARY),
+        GenericMachineActivityText,
+    ));
+}
+
 /// Spawn fuel row if machine requires fuel
 fn spawn_fuel_row(content: &mut ChildSpawnerCommands, spec: &MachineSpec, font: &Handle<Font>) {
     let fuel_slots: Vec<_> = spec
@@ -297,3 +528,113 @@ fn spawn_fuel_row(content: &mut ChildSpawnerCommands, spec: &MachineSpec, font:
             }
         });
 }
+
+/// Spawn one slot route button per horizontal input face if the machine takes fuel
+fn spawn_slot_route_row(
+    content: &mut ChildSpawnerCommands,
+    spec: &MachineSpec,
+    font: &Handle<Font>,
+) {
+    if !spec
+        .ui_slots
+        .iter()
+        .any(|s| matches!(s.slot_type, UiSlotType::Fuel))
+    {
+        return;
+    }
+    let mut sides: Vec<PortSide> = Vec::new();
+    for port in spec.ports.iter().filter(|p| p.is_input) {
+        if !matches!(port.side, PortSide::Top | PortSide::Bottom) && !sides.contains(&port.side) {
+            sides.push(port.side);
+        }
+    }
+
+    content
+        .spawn((Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        },))
+        .with_children(|row| {
+            for side in sides {
+                spawn_toggle_button(
+                    row,
+                    font,
+                    GenericMachineSlotRouteButton(side),
+                    GenericMachineSlotRouteText(side),
+                );
+            }
+        });
+}
+
+/// Spawn the drill head slot with its durability bar if the machine takes a drill
+fn spawn_drill_row(content: &mut ChildSpawnerCommands, spec: &MachineSpec, font: &Handle<Font>) {
+    let Some(slot_def) = spec
+        .ui_slots
+        .iter()
+        .find(|s| matches!(s.slot_type, UiSlotType::Drill))
+    else {
+        return;
+    };
+
+    content
+        .spawn((Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        },))
+        .with_children(|row| {
+            row.spawn((
+                Button,
+                GenericMachineDrillButton,
+                Node {
+                    width: Val::Px(SLOT_SIZE),
+                    height: Val::Px(SLOT_SIZE),
+                    border: UiRect::all(Val::Px(SLOT_BORDER)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border_radius: BorderRadius::all(Val::Px(SLOT_RADIUS)),
+                    ..default()
+                },
+                BackgroundColor(SLOT_BG),
+                BorderColor::all(SLOT_BORDER_COLOR),
+            ))
+            .with_children(|slot| {
+                slot.spawn((
+                    GenericMachineDrillText,
+                    Text::new(""),
+                    text_font(font, TEXT_BODY),
+                    TextColor(TEXT_PRIMARY),
+                ));
+            });
+            row.spawn((
+                Text::new(slot_def.label),
+                text_font(font, TEXT_SMALL),
+                TextColor(TEXT_SECONDARY),
+            ));
+        });
+
+    // Durability bar
+    content
+        .spawn((
+            Node {
+                width: Val::Px(SLOT_SIZE * 2.0 + 12.0),
+                height: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(PROGRESS_BG),
+        ))
+        .with_children(|bar| {
+            bar.spawn((
+                GenericMachineDrillBar,
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(DRILL_FILL),
+            ));
+        });
+}
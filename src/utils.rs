@@ -478,6 +478,50 @@ pub fn auto_conveyor_direction(
     fallback_direction
 }
 
+/// Machines whose facing follows adjacent conveyors when placed
+pub fn auto_faces_conveyors(item_id: crate::core::ItemId) -> bool {
+    use crate::core::items;
+    item_id == items::furnace_block()
+        || item_id == items::crusher_block()
+        || item_id == items::assembler_block()
+        || item_id == items::recycler_block()
+        || crate::game_spec::data_machine_spec(item_id).is_some()
+}
+
+/// Determine machine facing from adjacent conveyors
+///
+/// A belt pointing into `place_pos` should meet the machine's back (input) face,
+/// and a belt leading straight away from it should meet the front (output) face.
+/// If no belt suggests a facing, or belts suggest different ones, the fallback is used.
+pub fn auto_machine_facing(
+    place_pos: IVec3,
+    fallback_direction: Direction,
+    conveyors: &[(IVec3, Direction)], // (position, direction)
+) -> Direction {
+    let mut resolved: Option<Direction> = None;
+    for (conv_pos, conv_dir) in conveyors {
+        let offset = *conv_pos - place_pos;
+        if offset.y != 0 || offset.x.abs() + offset.z.abs() != 1 {
+            continue;
+        }
+        let candidate = if *conv_pos + conv_dir.to_ivec3() == place_pos {
+            // Input belt: it sits behind the machine, so face the way it flows
+            *conv_dir
+        } else if conv_dir.to_ivec3() == offset {
+            // Output belt: it carries items away from the front face
+            *conv_dir
+        } else {
+            continue;
+        };
+        match resolved {
+            None => resolved = Some(candidate),
+            Some(dir) if dir == candidate => {}
+            Some(_) => return fallback_direction,
+        }
+    }
+    resolved.unwrap_or(fallback_direction)
+}
+
 /// Convert keycode to character for text input
 pub fn keycode_to_char(key_code: KeyCode, shift: bool) -> Option<char> {
     match key_code {
@@ -531,11 +575,111 @@ pub fn keycode_to_char(key_code: KeyCode, shift: bool) -> Option<char> {
 /// Supports both short names (e.g., "stone") and full IDs (e.g., "base:stone")
 pub fn parse_item_name(name: &str) -> Option<crate::core::ItemId> {
     use crate::core::items;
-    // First try exact match with "base:" prefix
-    if name.contains(':') {
-        items::by_name(name)
-    } else {
-        // Try with base namespace prefix
-        items::by_name(&format!("base:{}", name))
+    // Full IDs ("base:stone", "mymod:gear") first, then base names ("stone")
+    items::by_string_id(name).or_else(|| items::by_name(name))
+}
+
+/// Find an item by ID or display name, ignoring case, spaces and underscores
+/// ("iron ore", "Iron_Ore", "base:iron_ore" and "IronOre" all match)
+pub fn find_item_by_name(name: &str) -> Option<crate::core::ItemId> {
+    fn normalize(s: &str) -> String {
+        s.chars()
+            .filter(|c| !c.is_whitespace() && *c != '_')
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    let wanted = normalize(name.trim());
+    let wanted = wanted.strip_prefix("base:").unwrap_or(&wanted);
+    crate::game_spec::item_descriptors()
+        .iter()
+        .find(|(id, desc)| {
+            normalize(desc.name) == wanted
+                || id
+                    .name()
+                    .is_some_and(|n| normalize(n.strip_prefix("base:").unwrap_or(n)) == wanted)
+        })
+        .map(|(id, _)| *id)
+        .or_else(|| parse_item_name(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_machine_facing_input_belt() {
+        // Belt west of the machine flowing east into it -> back faces west
+        let conveyors = [(IVec3::new(-1, 0, 0), Direction::East)];
+        let dir = auto_machine_facing(IVec3::ZERO, Direction::North, &conveyors);
+        assert_eq!(dir, Direction::East);
+    }
+
+    #[test]
+    fn test_auto_machine_facing_output_belt() {
+        // Belt south of the machine flowing south -> front faces south
+        let conveyors = [(IVec3::new(0, 0, 1), Direction::South)];
+        let dir = auto_machine_facing(IVec3::ZERO, Direction::North, &conveyors);
+        assert_eq!(dir, Direction::South);
+    }
+
+    #[test]
+    fn test_auto_machine_facing_input_and_output_belts() {
+        // In from the west, out to the east: both agree on East
+        let conveyors = [
+            (IVec3::new(-1, 0, 0), Direction::East),
+            (IVec3::new(1, 0, 0), Direction::East),
+        ];
+        let dir = auto_machine_facing(IVec3::ZERO, Direction::North, &conveyors);
+        assert_eq!(dir, Direction::East);
+
+        // In from the west, out to the south: ambiguous, use fallback
+        let conveyors = [
+            (IVec3::new(-1, 0, 0), Direction::East),
+            (IVec3::new(0, 0, 1), Direction::South),
+        ];
+        let dir = auto_machine_facing(IVec3::ZERO, Direction::North, &conveyors);
+        assert_eq!(dir, Direction::North);
+    }
+
+    #[test]
+    fn test_auto_machine_facing_no_belts() {
+        assert_eq!(
+            auto_machine_facing(IVec3::ZERO, Direction::West, &[]),
+            Direction::West
+        );
+
+        // Passing belts and belts on other levels don't count
+        let conveyors = [
+            (IVec3::new(0, 0, -1), Direction::East),
+            (IVec3::new(-1, 1, 0), Direction::East),
+        ];
+        assert_eq!(
+            auto_machine_facing(IVec3::ZERO, Direction::West, &conveyors),
+            Direction::West
+        );
+    }
+
+    #[test]
+    fn test_parse_item_name_short_and_full() {
+        use crate::core::items;
+        assert_eq!(parse_item_name("iron_ore"), Some(items::iron_ore()));
+        assert_eq!(parse_item_name("base:iron_ore"), Some(items::iron_ore()));
+        assert_eq!(parse_item_name("unknown_thing"), None);
+    }
+
+    #[test]
+    fn test_find_item_by_id_or_display_name() {
+        use crate::core::items;
+        for name in [
+            "iron_ore",
+            "base:iron_ore",
+            "Iron Ore",
+            "iron ore",
+            "IRON_ORE",
+        ] {
+            assert_eq!(find_item_by_name(name), Some(items::iron_ore()), "{}", name);
+        }
+        assert_eq!(find_item_by_name("unknown thing"), None);
     }
 }
@@ -72,6 +72,7 @@ impl Plugin for VoxLoaderPlugin {
         app.init_resource::<VoxMeshes>()
             .init_resource::<BlockTextureAtlas>()
             .init_resource::<VoxelArrayTexture>()
+            .init_resource::<crate::graphics::BlockTextures>()
             .add_message::<VoxFileChanged>()
             .add_message::<TextureAtlasChanged>()
             .add_systems(
@@ -80,6 +81,10 @@ impl Plugin for VoxLoaderPlugin {
                     setup_file_watcher,
                     load_initial_vox_models,
                     load_initial_texture_atlas,
+                    // Native builds stitch per-block PNGs, WASM loads the prebuilt array
+                    #[cfg(not(target_arch = "wasm32"))]
+                    crate::graphics::block_textures::build_block_texture_array,
+                    #[cfg(target_arch = "wasm32")]
                     load_voxel_array_texture,
                 ),
             )
@@ -321,10 +326,12 @@ fn handle_texture_atlas_reload(
     }
 }
 
-/// Number of texture layers in the block texture array
+/// Number of texture layers in the prebuilt block texture array
+#[cfg(target_arch = "wasm32")]
 const BLOCK_TEXTURE_LAYERS: u32 = 8;
 
-/// Load the voxel array texture at startup
+/// Load the prebuilt voxel array texture at startup
+#[cfg(target_arch = "wasm32")]
 fn load_voxel_array_texture(
     asset_server: Res<AssetServer>,
     mut array_tex: ResMut<VoxelArrayTexture>,
@@ -355,8 +362,8 @@ fn configure_array_texture(
     // Check if texture is loaded
     if let Some(image) = images.get_mut(&array_tex.texture) {
         // Convert stacked 2D image to 2D array texture
-        // The image is 16x128 (8 layers of 16x16 stacked vertically)
-        let _ = image.reinterpret_stacked_2d_as_array(BLOCK_TEXTURE_LAYERS);
+        // (layer_count tiles of 16x16 stacked vertically)
+        let _ = image.reinterpret_stacked_2d_as_array(array_tex.layer_count);
 
         // Set sampler to repeat for tiling
         image.sampler =
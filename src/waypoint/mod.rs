@@ -0,0 +1,519 @@
+//! Personal waypoints
+//!
+//! - `/waypoint add <name>` marks the player's position, `/waypoint remove <name>`
+//!   and `/waypoint list` manage the marks (at most `MAX_WAYPOINTS`, saved per slot)
+//! - `/tp <name>` teleports there in creative mode; in survival it points the
+//!   platform compass at the waypoint instead
+//! - Each waypoint shows a beacon column through the terrain within
+//!   `BEACON_VIEW_DISTANCE`, and a label that sticks to the screen edge while
+//!   the waypoint is off-screen
+
+use crate::components::{CreativeMode, GameFont, Player, PlayerCamera};
+use crate::logistics::conveyor::billboard_rotation;
+use crate::main_menu::InGameSet;
+use crate::setup::ui::{text_font, TEXT_CAPTION};
+use crate::systems::TeleportEvent;
+use bevy::camera::visibility::RenderLayers;
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Most waypoints a save can hold
+pub const MAX_WAYPOINTS: usize = 32;
+
+/// Beacons show within this many blocks of the camera
+pub const BEACON_VIEW_DISTANCE: f32 = 128.0;
+
+/// Beacon column size (blocks)
+const BEACON_HEIGHT: f32 = 24.0;
+const BEACON_WIDTH: f32 = 0.35;
+
+/// Off-screen labels keep this far from the screen edge (px)
+const LABEL_EDGE_MARGIN: f32 = 16.0;
+/// Approximate label character width for centering (px)
+const LABEL_CHAR_WIDTH: f32 = 7.0;
+
+/// Colors handed out to new waypoints in turn
+const WAYPOINT_COLORS: [Color; 6] = [
+    Color::srgb(0.3, 0.8, 1.0),
+    Color::srgb(1.0, 0.55, 0.2),
+    Color::srgb(0.5, 1.0, 0.4),
+    Color::srgb(1.0, 0.4, 0.75),
+    Color::srgb(1.0, 0.9, 0.3),
+    Color::srgb(0.7, 0.5, 1.0),
+];
+
+/// A named position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Waypoint {
+    pub position: Vec3,
+    pub color: Color,
+}
+
+/// The player's waypoints, by name
+#[derive(Resource, Debug, Default)]
+pub struct Waypoints {
+    entries: BTreeMap<String, Waypoint>,
+    /// Waypoint the compass points at (set by `/tp <name>` in survival)
+    pub compass_target: Option<String>,
+}
+
+/// Why a waypoint command failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaypointError {
+    /// Already `MAX_WAYPOINTS` waypoints
+    Full,
+    /// No waypoint with that name
+    Unknown(String),
+}
+
+impl std::fmt::Display for WaypointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "ウェイポイントは最大{}個までです", MAX_WAYPOINTS),
+            Self::Unknown(name) => write!(f, "ウェイポイント '{}' はありません", name),
+        }
+    }
+}
+
+impl Waypoints {
+    /// Add or move a waypoint, returning whether an existing one was overwritten
+    pub fn add(&mut self, name: &str, position: Vec3) -> Result<bool, WaypointError> {
+        if let Some(existing) = self.entries.get_mut(name) {
+            existing.position = position;
+            return Ok(true);
+        }
+        if self.entries.len() >= MAX_WAYPOINTS {
+            return Err(WaypointError::Full);
+        }
+        let color = self.next_color();
+        self.entries
+            .insert(name.to_string(), Waypoint { position, color });
+        Ok(false)
+    }
+
+    /// Insert a waypoint as is (loading)
+    pub fn insert(&mut self, name: String, waypoint: Waypoint) {
+        if self.entries.len() < MAX_WAYPOINTS || self.entries.contains_key(&name) {
+            self.entries.insert(name, waypoint);
+        }
+    }
+
+    /// Remove a waypoint (the compass stops pointing at it)
+    pub fn remove(&mut self, name: &str) -> Result<Waypoint, WaypointError> {
+        let waypoint = self
+            .entries
+            .remove(name)
+            .ok_or_else(|| WaypointError::Unknown(name.to_string()))?;
+        if self.compass_target.as_deref() == Some(name) {
+            self.compass_target = None;
+        }
+        Ok(waypoint)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Waypoint> {
+        self.entries.get(name)
+    }
+
+    /// Waypoints sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Waypoint)> {
+        self.entries.iter().map(|(name, wp)| (name.as_str(), wp))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Position the compass should point at, if it follows a waypoint
+    pub fn compass_position(&self) -> Option<Vec3> {
+        let name = self.compass_target.as_deref()?;
+        self.get(name).map(|wp| wp.position)
+    }
+
+    /// First palette color not in use (cycles once all are taken)
+    fn next_color(&self) -> Color {
+        WAYPOINT_COLORS
+            .iter()
+            .find(|color| !self.entries.values().any(|wp| wp.color == **color))
+            .copied()
+            .unwrap_or(WAYPOINT_COLORS[self.entries.len() % WAYPOINT_COLORS.len()])
+    }
+}
+
+/// Waypoint command from `/waypoint ...` and `/tp <name>`
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub enum WaypointCommandEvent {
+    /// Mark the player's position
+    Add(String),
+    Remove(String),
+    /// Log all waypoints
+    List,
+    /// Teleport (creative) or point the compass (survival)
+    Goto(String),
+}
+
+/// Parse the arguments after `/waypoint` (None = show usage)
+pub fn parse_waypoint_command(args: &[&str]) -> Option<WaypointCommandEvent> {
+    match args {
+        ["add", name] => Some(WaypointCommandEvent::Add(name.to_string())),
+        ["remove", name] => Some(WaypointCommandEvent::Remove(name.to_string())),
+        ["list"] => Some(WaypointCommandEvent::List),
+        _ => None,
+    }
+}
+
+/// Screen-space direction (y down) toward a point given in camera space
+///
+/// The camera looks down -Z. Points behind the camera keep their side, so the
+/// label stays on the edge the player has to turn toward.
+pub fn offscreen_direction(view: Vec3) -> Vec2 {
+    let dir = Vec2::new(view.x, -view.y);
+    if dir.length_squared() < 1e-6 {
+        // Straight behind: point down
+        Vec2::Y
+    } else {
+        dir.normalize()
+    }
+}
+
+/// Where a ray from the screen center along `dir` meets the screen rect
+/// shrunk by `margin`
+pub fn screen_edge_position(size: Vec2, dir: Vec2, margin: f32) -> Vec2 {
+    let center = size / 2.0;
+    let half = (center - Vec2::splat(margin)).max(Vec2::ZERO);
+    // Division by a zero component gives infinity, so the other axis wins
+    let scale = (half.x / dir.x.abs()).min(half.y / dir.y.abs());
+    center + dir * scale
+}
+
+/// Beacon column of a waypoint
+#[derive(Component)]
+pub struct WaypointBeacon(pub String);
+
+/// Screen label of a waypoint
+#[derive(Component)]
+pub struct WaypointLabel(pub String);
+
+fn handle_waypoint_command(
+    mut events: MessageReader<WaypointCommandEvent>,
+    mut waypoints: ResMut<Waypoints>,
+    creative_mode: Res<CreativeMode>,
+    player_query: Query<&Transform, With<Player>>,
+    mut teleport: MessageWriter<TeleportEvent>,
+) {
+    for event in events.read() {
+        match event {
+            WaypointCommandEvent::Add(name) => {
+                let Ok(player) = player_query.single() else {
+                    continue;
+                };
+                match waypoints.add(name, player.translation) {
+                    Ok(true) => info!(
+                        "ウェイポイント '{}' を上書きしました {:?}",
+                        name, player.translation
+                    ),
+                    Ok(false) => info!(
+                        "ウェイポイント '{}' を追加しました {:?}",
+                        name, player.translation
+                    ),
+                    Err(e) => info!("{}", e),
+                }
+            }
+            WaypointCommandEvent::Remove(name) => match waypoints.remove(name) {
+                Ok(_) => info!("ウェイポイント '{}' を削除しました", name),
+                Err(e) => info!("{}", e),
+            },
+            WaypointCommandEvent::List => {
+                info!("=== Waypoints ({}/{}) ===", waypoints.len(), MAX_WAYPOINTS);
+                for (name, wp) in waypoints.iter() {
+                    info!(
+                        "{}: ({:.0}, {:.0}, {:.0})",
+                        name, wp.position.x, wp.position.y, wp.position.z
+                    );
+                }
+            }
+            WaypointCommandEvent::Goto(name) => {
+                let Some(wp) = waypoints.get(name).copied() else {
+                    info!("{}", WaypointError::Unknown(name.clone()));
+                    continue;
+                };
+                if creative_mode.enabled {
+                    teleport.write(TeleportEvent {
+                        position: wp.position,
+                    });
+                    info!("Teleporting to waypoint '{}'", name);
+                } else {
+                    waypoints.compass_target = Some(name.clone());
+                    info!("コンパスがウェイポイント '{}' を指しています", name);
+                }
+            }
+        }
+    }
+}
+
+/// Respawn beacons and labels whenever the waypoints change
+fn sync_waypoint_markers(
+    mut commands: Commands,
+    waypoints: Res<Waypoints>,
+    game_font: Res<GameFont>,
+    beacons: Query<Entity, With<WaypointBeacon>>,
+    labels: Query<Entity, With<WaypointLabel>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !waypoints.is_changed() {
+        return;
+    }
+    for entity in beacons.iter().chain(labels.iter()) {
+        commands.entity(entity).despawn();
+    }
+
+    let mesh = meshes.add(Rectangle::new(BEACON_WIDTH, BEACON_HEIGHT));
+    for (name, wp) in waypoints.iter() {
+        let material = materials.add(StandardMaterial {
+            base_color: wp.color.with_alpha(0.45),
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            cull_mode: None,
+            ..default()
+        });
+        commands.spawn((
+            WaypointBeacon(name.to_string()),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(wp.position + Vec3::Y * BEACON_HEIGHT / 2.0),
+            NotShadowCaster,
+            // Overlay layer: drawn after the world, so visible through terrain
+            RenderLayers::layer(1),
+            Visibility::Hidden,
+        ));
+        commands.spawn((
+            WaypointLabel(name.to_string()),
+            Text::new(name),
+            text_font(&game_font.0, TEXT_CAPTION),
+            TextColor(wp.color),
+            Node {
+                position_type: PositionType::Absolute,
+                padding: UiRect::axes(Val::Px(4.0), Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Face the beacons to the camera and place the labels
+#[allow(clippy::type_complexity)]
+fn update_waypoint_markers(
+    waypoints: Res<Waypoints>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    mut beacons: Query<(&WaypointBeacon, &mut Transform, &mut Visibility), Without<WaypointLabel>>,
+    mut labels: Query<
+        (&WaypointLabel, &mut Text, &mut Node, &mut Visibility),
+        Without<WaypointBeacon>,
+    >,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let camera_pos = camera_transform.translation();
+
+    for (beacon, mut transform, mut visibility) in beacons.iter_mut() {
+        let Some(wp) = waypoints.get(&beacon.0) else {
+            continue;
+        };
+        let in_range = wp.position.distance(camera_pos) <= BEACON_VIEW_DISTANCE;
+        visibility.set_if_neq(if in_range {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+        if in_range {
+            transform.rotation = billboard_rotation(transform.translation, camera_pos);
+        }
+    }
+
+    let Some(size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let world_to_view = camera_transform.affine().inverse();
+    for (label, mut text, mut node, mut visibility) in labels.iter_mut() {
+        let Some(wp) = waypoints.get(&label.0) else {
+            continue;
+        };
+        let distance = wp.position.distance(camera_pos);
+        let label_text = format!("{} {:.0}m", label.0, distance);
+        if **text != label_text {
+            **text = label_text;
+        }
+
+        // Above the beacon base while on screen, otherwise on the screen edge
+        let view = world_to_view.transform_point3(wp.position);
+        let on_screen = (view.z < 0.0)
+            .then(|| camera.world_to_viewport(camera_transform, wp.position).ok())
+            .flatten()
+            .filter(|p| p.x >= 0.0 && p.y >= 0.0 && p.x <= size.x && p.y <= size.y);
+        let anchor = match on_screen {
+            Some(pos) if distance <= BEACON_VIEW_DISTANCE => pos,
+            Some(_) => {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+            None => screen_edge_position(size, offscreen_direction(view), LABEL_EDGE_MARGIN),
+        };
+        let width = text.chars().count() as f32 * LABEL_CHAR_WIDTH;
+        node.left = Val::Px((anchor.x - width / 2.0).clamp(0.0, (size.x - width).max(0.0)));
+        node.top = Val::Px((anchor.y - TEXT_CAPTION).clamp(0.0, size.y - TEXT_CAPTION * 2.0));
+        visibility.set_if_neq(Visibility::Visible);
+    }
+}
+
+pub struct WaypointPlugin;
+
+impl Plugin for WaypointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Waypoints>()
+            .add_message::<WaypointCommandEvent>()
+            .add_systems(
+                Update,
+                (
+                    handle_waypoint_command,
+                    sync_waypoint_markers,
+                    update_waypoint_markers,
+                )
+                    .chain()
+                    .in_set(InGameSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_parse_waypoint_command() {
+        assert_eq!(
+            parse_waypoint_command(&["add", "base"]),
+            Some(WaypointCommandEvent::Add("base".to_string()))
+        );
+        assert_eq!(
+            parse_waypoint_command(&["remove", "base"]),
+            Some(WaypointCommandEvent::Remove("base".to_string()))
+        );
+        assert_eq!(
+            parse_waypoint_command(&["list"]),
+            Some(WaypointCommandEvent::List)
+        );
+        assert_eq!(parse_waypoint_command(&[]), None);
+        assert_eq!(parse_waypoint_command(&["add"]), None);
+        assert_eq!(parse_waypoint_command(&["add", "a", "b"]), None);
+        assert_eq!(parse_waypoint_command(&["rename", "a"]), None);
+    }
+
+    #[test]
+    fn test_add_overwrites_and_caps() {
+        let mut waypoints = Waypoints::default();
+        assert_eq!(waypoints.add("mine", Vec3::ZERO), Ok(false));
+        let color = waypoints.get("mine").unwrap().color;
+        assert_eq!(waypoints.add("mine", Vec3::ONE), Ok(true));
+        let mine = waypoints.get("mine").unwrap();
+        assert_eq!(mine.position, Vec3::ONE);
+        assert_eq!(mine.color, color, "overwriting keeps the color");
+
+        for i in 1..MAX_WAYPOINTS {
+            assert_eq!(waypoints.add(&format!("wp{i}"), Vec3::ZERO), Ok(false));
+        }
+        assert_eq!(
+            waypoints.add("one_more", Vec3::ZERO),
+            Err(WaypointError::Full)
+        );
+        // Overwriting still works at the cap
+        assert_eq!(waypoints.add("wp1", Vec3::X), Ok(true));
+        assert_eq!(waypoints.len(), MAX_WAYPOINTS);
+    }
+
+    #[test]
+    fn test_remove_clears_compass_target() {
+        let mut waypoints = Waypoints::default();
+        waypoints.add("home", Vec3::new(4.0, 9.0, 4.0)).unwrap();
+        waypoints.compass_target = Some("home".to_string());
+        assert_eq!(waypoints.compass_position(), Some(Vec3::new(4.0, 9.0, 4.0)));
+
+        assert!(waypoints.remove("home").is_ok());
+        assert_eq!(waypoints.compass_target, None);
+        assert_eq!(
+            waypoints.remove("home"),
+            Err(WaypointError::Unknown("home".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_waypoints_survive_save_round_trip() {
+        use crate::save::format::WaypointSaveDataV2;
+        use crate::save::systems::{waypoints_from_save, waypoints_to_save};
+
+        let mut waypoints = Waypoints::default();
+        waypoints.add("base", Vec3::new(8.0, 12.0, 20.0)).unwrap();
+        waypoints
+            .add("copper", Vec3::new(-140.5, 6.0, 33.0))
+            .unwrap();
+
+        let json = serde_json::to_string(&waypoints_to_save(&waypoints)).unwrap();
+        let restored: Vec<WaypointSaveDataV2> = serde_json::from_str(&json).unwrap();
+        let loaded = waypoints_from_save(&restored);
+
+        let names: Vec<_> = loaded.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["base", "copper"]);
+        for (name, wp) in waypoints.iter() {
+            let got = loaded.get(name).unwrap();
+            assert_eq!(got.position, wp.position);
+            assert_eq!(got.color.to_srgba(), wp.color.to_srgba());
+        }
+    }
+
+    #[test]
+    fn test_screen_edge_position() {
+        let size = Vec2::new(800.0, 600.0);
+        // Straight right / left / down hit the middle of that edge
+        assert_near(
+            screen_edge_position(size, Vec2::X, 10.0),
+            Vec2::new(790.0, 300.0),
+        );
+        assert_near(
+            screen_edge_position(size, Vec2::NEG_X, 10.0),
+            Vec2::new(10.0, 300.0),
+        );
+        assert_near(
+            screen_edge_position(size, Vec2::Y, 10.0),
+            Vec2::new(400.0, 590.0),
+        );
+        // Diagonal: the shorter half-height is reached first
+        let diag = screen_edge_position(size, Vec2::new(1.0, -1.0).normalize(), 0.0);
+        assert_near(diag, Vec2::new(700.0, 0.0));
+    }
+
+    #[test]
+    fn test_offscreen_direction() {
+        // Right of the camera, and up (screen y points down)
+        assert_near(offscreen_direction(Vec3::new(5.0, 0.0, -1.0)), Vec2::X);
+        assert_near(offscreen_direction(Vec3::new(0.0, 3.0, -1.0)), Vec2::NEG_Y);
+        // Behind and to the left stays on the left
+        assert_near(offscreen_direction(Vec3::new(-2.0, 0.0, 5.0)), Vec2::NEG_X);
+        // Straight behind falls back to the bottom edge
+        assert_near(offscreen_direction(Vec3::new(0.0, 0.0, 5.0)), Vec2::Y);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Contains ChunkData, ChunkLod, ChunkMesh and related types for chunk management.
 
+use super::worldgen::{pick_ore, WorldGenConfig};
 use crate::constants::*;
 use crate::core::{items, ItemId};
 use bevy::mesh::PrimitiveTopology;
@@ -43,10 +44,12 @@ impl ChunkLod {
     }
 }
 
-/// Marker for chunk mesh entity (single mesh per chunk)
+/// Marker for chunk mesh entity (one mesh per chunk section)
 #[derive(Component)]
 pub struct ChunkMesh {
     pub coord: IVec2,
+    /// Section index (y / CHUNK_SECTION_HEIGHT)
+    pub section: i32,
     pub lod: ChunkLod,
 }
 
@@ -82,43 +85,56 @@ pub struct ChunkMeshTasks {
     pub pending: HashMap<IVec2, PendingChunk>,
 }
 
-/// Resource to track chunks that need mesh regeneration due to block changes
+/// Resource to track chunk sections that need mesh regeneration due to block changes
 /// This enables batched mesh updates instead of immediate per-block regeneration
 #[derive(Resource, Default)]
 pub struct DirtyChunks {
-    /// Set of chunk coordinates that need mesh regeneration
-    pub chunks: std::collections::HashSet<IVec2>,
+    /// Set of (chunk coordinate, section index) that need mesh regeneration
+    pub chunks: std::collections::HashSet<(IVec2, i32)>,
 }
 
 impl DirtyChunks {
-    /// Mark a chunk and its affected neighbors as needing mesh regeneration
+    /// Mark the section holding a block and its affected neighbors as needing mesh regeneration
     pub fn mark_dirty(&mut self, chunk_coord: IVec2, local_pos: IVec3) {
-        use crate::constants::CHUNK_SIZE;
+        use crate::constants::{CHUNK_SECTIONS, CHUNK_SECTION_HEIGHT, CHUNK_SIZE};
+
+        let section = ChunkData::section_of(local_pos.y);
+        let mut sections = vec![section];
+        // The face between two sections belongs to both meshes
+        let section_y = local_pos.y.rem_euclid(CHUNK_SECTION_HEIGHT);
+        if section_y == 0 && section > 0 {
+            sections.push(section - 1);
+        }
+        if section_y == CHUNK_SECTION_HEIGHT - 1 && section < CHUNK_SECTIONS - 1 {
+            sections.push(section + 1);
+        }
 
         // Always mark the changed chunk
-        self.chunks.insert(chunk_coord);
+        for &dirty in &sections {
+            self.chunks.insert((chunk_coord, dirty));
+        }
 
-        // Mark neighbors if block is at boundary
+        // Mark neighbors if block is at boundary (they only share side faces)
         if local_pos.x == 0 {
             self.chunks
-                .insert(IVec2::new(chunk_coord.x - 1, chunk_coord.y));
+                .insert((IVec2::new(chunk_coord.x - 1, chunk_coord.y), section));
         }
         if local_pos.x == CHUNK_SIZE - 1 {
             self.chunks
-                .insert(IVec2::new(chunk_coord.x + 1, chunk_coord.y));
+                .insert((IVec2::new(chunk_coord.x + 1, chunk_coord.y), section));
         }
         if local_pos.z == 0 {
             self.chunks
-                .insert(IVec2::new(chunk_coord.x, chunk_coord.y - 1));
+                .insert((IVec2::new(chunk_coord.x, chunk_coord.y - 1), section));
         }
         if local_pos.z == CHUNK_SIZE - 1 {
             self.chunks
-                .insert(IVec2::new(chunk_coord.x, chunk_coord.y + 1));
+                .insert((IVec2::new(chunk_coord.x, chunk_coord.y + 1), section));
         }
     }
 
-    /// Take all dirty chunks, clearing the set
-    pub fn take_all(&mut self) -> std::collections::HashSet<IVec2> {
+    /// Take all dirty sections, clearing the set
+    pub fn take_all(&mut self) -> std::collections::HashSet<(IVec2, i32)> {
         std::mem::take(&mut self.chunks)
     }
 
@@ -168,9 +184,20 @@ impl ChunkData {
         }
     }
 
+    /// Section index containing local y
+    #[inline(always)]
+    pub fn section_of(y: i32) -> i32 {
+        y.div_euclid(CHUNK_SECTION_HEIGHT)
+    }
+
+    /// Local y range covered by a section
+    pub fn section_y_range(section: i32) -> std::ops::Range<i32> {
+        let start = section * CHUNK_SECTION_HEIGHT;
+        start..start + CHUNK_SECTION_HEIGHT
+    }
+
     /// Convert array index to local position
     #[inline(always)]
-    #[allow(dead_code)]
     pub fn index_to_pos(idx: usize) -> IVec3 {
         let idx = idx as i32;
         let y = idx / (CHUNK_SIZE * CHUNK_SIZE);
@@ -180,27 +207,29 @@ impl ChunkData {
         IVec3::new(x, y, z)
     }
 
-    /// Check if world position is in the delivery platform area
+    /// Check if world position is in the delivery platform area (default worldgen config)
     #[inline(always)]
     pub fn is_platform_area(world_x: i32, world_z: i32) -> bool {
-        // Platform is at (20, 8, 10) with size 12x12
-        // Clear the top layer (y=7, which is CHUNK_HEIGHT-1) in the platform area
-        const PLATFORM_X_MIN: i32 = 20;
-        const PLATFORM_X_MAX: i32 = 31; // 20 + 12 - 1
-        const PLATFORM_Z_MIN: i32 = 10;
-        const PLATFORM_Z_MAX: i32 = 21; // 10 + 12 - 1
-
-        (PLATFORM_X_MIN..=PLATFORM_X_MAX).contains(&world_x)
-            && (PLATFORM_Z_MIN..=PLATFORM_Z_MAX).contains(&world_z)
+        WorldGenConfig::default_ref()
+            .platform
+            .contains(world_x, world_z)
     }
 
-    /// Generate a chunk at the given chunk coordinate
+    /// Generate a chunk at the given chunk coordinate with the default worldgen config
     pub fn generate(chunk_coord: IVec2) -> Self {
+        Self::generate_with(chunk_coord, WorldGenConfig::default_ref())
+    }
+
+    /// Generate a chunk at the given chunk coordinate
+    pub fn generate_with(chunk_coord: IVec2, config: &WorldGenConfig) -> Self {
         tracing::debug!("Generating chunk at {:?}", chunk_coord);
         let mut blocks = vec![None; Self::ARRAY_SIZE];
         let mut block_count = 0usize;
+        let ores = config.ores.resolve();
+        let seed = config.seed;
+        // Platform pad sits one below the platform origin
+        let platform_ground = (config.platform.origin[1] - 1).clamp(0, CHUNK_HEIGHT - 1);
 
-        // Generate a 16x16x8 chunk of blocks
         // Bottom layers are stone with ore veins, top layer is grass or ore
         for x in 0..CHUNK_SIZE {
             for z in 0..CHUNK_SIZE {
@@ -208,17 +237,20 @@ impl ChunkData {
                 let world_z = chunk_coord.y * CHUNK_SIZE + z;
 
                 // Get biome for this position
-                let biome = Self::get_biome(world_x, world_z);
-                let is_ore_patch = Self::is_surface_ore_patch(world_x, world_z);
-
-                // Only generate blocks up to GROUND_LEVEL (y <= 7)
-                for y in 0..=GROUND_LEVEL {
-                    // Platform area: generate stone at ground level (no skip)
-                    // This ensures no "hole" appears under the delivery platform
-
-                    let item_id = if y == GROUND_LEVEL {
+                let biome = Self::get_biome(seed, world_x, world_z);
+                let is_ore_patch = Self::is_surface_ore_patch(seed, world_x, world_z);
+                let is_platform = config.platform.contains(world_x, world_z);
+                let surface = if is_platform {
+                    platform_ground
+                } else {
+                    config.surface_height(world_x, world_z)
+                };
+
+                for y in 0..=surface {
+                    let item_id = if y == surface {
                         // Platform area: always stone at ground level
-                        if Self::is_platform_area(world_x, world_z) {
+                        // This ensures no "hole" appears under the delivery platform
+                        if is_platform {
                             items::stone()
                         } else if is_ore_patch {
                             // Surface layer: show ore in patches based on biome
@@ -233,54 +265,9 @@ impl ChunkData {
                         }
                     } else {
                         // Underground: biome-weighted ore distribution
-                        let hash = Self::simple_hash(world_x, y, world_z);
-
-                        match biome {
-                            1 => {
-                                // Iron biome: higher iron, some coal
-                                if y <= 5 && hash % 8 == 0 {
-                                    items::iron_ore() // ~12.5% iron
-                                } else if y <= 4 && hash % 20 == 1 {
-                                    items::coal() // ~5% coal
-                                } else {
-                                    items::stone()
-                                }
-                            }
-                            2 => {
-                                // Copper biome: higher copper, some iron
-                                if y <= 5 && hash % 8 == 0 {
-                                    items::copper_ore() // ~12.5% copper
-                                } else if y <= 4 && hash % 25 == 1 {
-                                    items::iron_ore() // ~4% iron
-                                } else {
-                                    items::stone()
-                                }
-                            }
-                            3 => {
-                                // Coal biome: high coal, some iron/copper
-                                if y <= 6 && hash % 6 == 0 {
-                                    items::coal() // ~16% coal
-                                } else if y <= 3 && hash % 30 == 1 {
-                                    items::iron_ore() // ~3% iron
-                                } else if y <= 3 && hash % 30 == 2 {
-                                    items::copper_ore() // ~3% copper
-                                } else {
-                                    items::stone()
-                                }
-                            }
-                            _ => {
-                                // Mixed biome: original distribution
-                                if y <= 4 && hash % 20 == 0 {
-                                    items::iron_ore() // 5% iron
-                                } else if y <= 3 && hash % 25 == 1 {
-                                    items::copper_ore() // 4% copper
-                                } else if y <= 5 && hash % 15 == 2 {
-                                    items::coal() // ~7% coal
-                                } else {
-                                    items::stone()
-                                }
-                            }
-                        }
+                        let hash = Self::seeded_hash(seed, world_x, y, world_z);
+                        let roll = hash as f32 / u32::MAX as f32;
+                        pick_ore(&ores[biome as usize], y, roll).unwrap_or_else(items::stone)
                     };
                     let idx = Self::pos_to_index(x, y, z);
                     blocks[idx] = Some(item_id);
@@ -288,6 +275,7 @@ impl ChunkData {
                 }
             }
         }
+        super::decoration::decorate(&mut blocks, chunk_coord, config);
         tracing::debug!(
             "Chunk {:?} generated with {} blocks",
             chunk_coord,
@@ -308,14 +296,30 @@ impl ChunkData {
         h
     }
 
+    /// `simple_hash` with the world seed folded in (seed 0 = `simple_hash`)
+    #[inline(always)]
+    pub fn seeded_hash(seed: u64, x: i32, y: i32, z: i32) -> u32 {
+        let h = Self::simple_hash(x, y, z);
+        if seed == 0 {
+            return h;
+        }
+        // Re-mix so neighbouring seeds don't give near-identical worlds
+        let mut h = h ^ seed as u32 ^ ((seed >> 32) as u32).rotate_left(16);
+        h = h.wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 13;
+        h = h.wrapping_mul(0xc2b2_ae35);
+        h ^= h >> 16;
+        h
+    }
+
     /// Determine biome type based on world coordinates
     /// Returns: 0=Mixed, 1=Iron, 2=Copper, 3=Coal
     #[inline(always)]
-    pub fn get_biome(world_x: i32, world_z: i32) -> u8 {
+    pub fn get_biome(seed: u64, world_x: i32, world_z: i32) -> u8 {
         // Use larger scale hash for biome regions (32-block regions)
         let region_x = world_x.div_euclid(32);
         let region_z = world_z.div_euclid(32);
-        let biome_hash = Self::simple_hash(region_x, 0, region_z);
+        let biome_hash = Self::seeded_hash(seed, region_x, 0, region_z);
 
         // Assign biomes based on hash
         match biome_hash % 10 {
@@ -328,9 +332,9 @@ impl ChunkData {
 
     /// Check if position should have surface ore (visible ore patch)
     #[inline(always)]
-    pub fn is_surface_ore_patch(world_x: i32, world_z: i32) -> bool {
+    pub fn is_surface_ore_patch(seed: u64, world_x: i32, world_z: i32) -> bool {
         // Create ore patches every 8-12 blocks based on hash
-        let patch_hash = Self::simple_hash(world_x.div_euclid(4), 100, world_z.div_euclid(4));
+        let patch_hash = Self::seeded_hash(seed, world_x.div_euclid(4), 100, world_z.div_euclid(4));
         patch_hash.is_multiple_of(8)
     }
 
@@ -0,0 +1,155 @@
+//! Surface decoration pass: boulders, ore outcrops and crystals
+//!
+//! Runs after a chunk's terrain is filled. Every structure is rolled from a
+//! seed derived from the chunk coordinate and kept inside the chunk (anchors
+//! leave a one-column margin), so a chunk decorates the same way whichever
+//! neighbors are loaded. Decorations are ordinary blocks in `ChunkData`, so
+//! they mesh, break and save like the terrain around them.
+
+use super::worldgen::WorldGenConfig;
+use super::ChunkData;
+use crate::constants::{CHUNK_HEIGHT, CHUNK_SIZE};
+use crate::core::{items, ItemId};
+use bevy::prelude::*;
+use std::ops::Range;
+
+/// Structure kinds, rolled in this order for every chunk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Decoration {
+    Boulder,
+    Outcrop,
+    Crystal,
+}
+
+/// Deterministic per-chunk generator (SplitMix64)
+struct DecorationRng(u64);
+
+impl DecorationRng {
+    fn for_chunk(seed: u64, chunk_coord: IVec2) -> Self {
+        let coord = ChunkData::simple_hash(chunk_coord.x, 300, chunk_coord.y) as u64;
+        Self(seed ^ ((coord << 32) | coord))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn range(&mut self, range: Range<i32>) -> i32 {
+        let len = (range.end - range.start).max(1) as u64;
+        range.start + (self.next_u64() % len) as i32
+    }
+
+    /// True with probability `p`
+    fn chance(&mut self, p: f32) -> bool {
+        ((self.next_u64() >> 40) as f32 / (1u64 << 24) as f32) < p
+    }
+
+    /// Whole part of `average`, plus one more with the fractional chance
+    fn count(&mut self, average: f32) -> u32 {
+        let average = average.max(0.0);
+        let whole = average.floor();
+        whole as u32 + u32::from(self.chance(average - whole))
+    }
+}
+
+/// Blocks of one structure as (dx, dz, block), relative to its anchor column
+///
+/// Each block lands on top of its column, so repeating a column stacks.
+fn structure_blocks(
+    kind: Decoration,
+    rng: &mut DecorationRng,
+    biome: u8,
+) -> Vec<(i32, i32, ItemId)> {
+    match kind {
+        Decoration::Boulder => {
+            let stone = items::stone();
+            let mut blocks = vec![(0, 0, stone), (1, 0, stone), (0, 1, stone)];
+            if rng.chance(0.5) {
+                blocks.push((1, 1, stone));
+            }
+            if rng.chance(0.5) {
+                blocks.push((0, 0, stone));
+            }
+            blocks
+        }
+        Decoration::Outcrop => {
+            let ore = match biome {
+                1 => items::iron_ore(),
+                2 => items::copper_ore(),
+                3 => items::coal(),
+                _ => [items::iron_ore(), items::copper_ore(), items::coal()]
+                    [rng.range(0..3) as usize],
+            };
+            let height = rng.range(1..3);
+            (0..height).map(|_| (0, 0, ore)).collect()
+        }
+        Decoration::Crystal => {
+            let height = rng.range(1..3);
+            (0..height).map(|_| (0, 0, items::crystal())).collect()
+        }
+    }
+}
+
+/// Highest solid block of a column
+fn column_top(blocks: &[Option<ItemId>], x: i32, z: i32) -> Option<i32> {
+    (0..CHUNK_HEIGHT)
+        .rev()
+        .find(|&y| blocks[ChunkData::pos_to_index(x, y, z)].is_some())
+}
+
+/// Add boulders, ore outcrops and crystals on top of freshly generated terrain
+pub(crate) fn decorate(blocks: &mut [Option<ItemId>], chunk_coord: IVec2, config: &WorldGenConfig) {
+    let decorations = &config.decorations;
+    let mut rng = DecorationRng::for_chunk(config.seed, chunk_coord);
+    let spawn = IVec2::from_array(config.border.center);
+
+    // Bare, unplatformed ground outside the spawn area that hasn't been decorated yet
+    let is_open_ground = |blocks: &[Option<ItemId>], x: i32, z: i32| {
+        let world = chunk_coord * CHUNK_SIZE + IVec2::new(x, z);
+        let from_spawn = (world - spawn).abs().max_element();
+        from_spawn > decorations.spawn_clear_radius
+            && !config.platform.contains(world.x, world.y)
+            && column_top(blocks, x, z) == Some(config.surface_height(world.x, world.y))
+    };
+
+    let kinds = [
+        (Decoration::Boulder, decorations.boulders),
+        (Decoration::Outcrop, decorations.outcrops),
+        (Decoration::Crystal, decorations.crystals),
+    ];
+    for (kind, average) in kinds {
+        for _ in 0..rng.count(average) {
+            // Footprints are at most 2x2, so the anchor keeps them off the chunk edges
+            let x = rng.range(1..CHUNK_SIZE - 2);
+            let z = rng.range(1..CHUNK_SIZE - 2);
+            let biome = ChunkData::get_biome(
+                config.seed,
+                chunk_coord.x * CHUNK_SIZE + x,
+                chunk_coord.y * CHUNK_SIZE + z,
+            );
+            let structure = structure_blocks(kind, &mut rng, biome);
+
+            let fits = structure.iter().all(|&(dx, dz, _)| {
+                let height = structure
+                    .iter()
+                    .filter(|&&(ox, oz, _)| (ox, oz) == (dx, dz))
+                    .count() as i32;
+                is_open_ground(blocks, x + dx, z + dz)
+                    && column_top(blocks, x + dx, z + dz)
+                        .is_some_and(|top| top + height < CHUNK_HEIGHT)
+            });
+            if !fits {
+                continue;
+            }
+            for (dx, dz, block) in structure {
+                if let Some(top) = column_top(blocks, x + dx, z + dz) {
+                    blocks[ChunkData::pos_to_index(x + dx, top + 1, z + dz)] = Some(block);
+                }
+            }
+        }
+    }
+}
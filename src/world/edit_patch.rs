@@ -0,0 +1,232 @@
+//! Stand-in geometry for block edits whose section mesh hasn't been rebuilt yet
+//!
+//! A block edit only marks its sections dirty; `process_dirty_chunks` rebuilds a
+//! limited number of sections per frame. Until then the edit is shown with a
+//! small patch mesh built from the blocks around it:
+//!
+//! - placed block: the faces of the new cube that aren't against a block
+//! - removed block: the faces of the six neighbors that now face the hole
+
+use crate::core::ItemId;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+
+/// Face directions in the same order as the chunk mesher
+const FACE_DIRS: [IVec3; 6] = [
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::Z,
+    IVec3::NEG_Z,
+];
+
+/// Patch mesh entity, despawned once none of its sections is dirty any more
+#[derive(Component, Debug)]
+pub struct EditPatch {
+    /// Sections whose rebuild covers the edit
+    pub sections: Vec<(IVec2, i32)>,
+}
+
+/// A block edit to predict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockEdit {
+    Placed(ItemId),
+    Removed,
+}
+
+/// One unit face of a patch: the face of `block_pos` pointing along `normal`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchFace {
+    pub block_pos: IVec3,
+    pub normal: IVec3,
+    pub block: ItemId,
+}
+
+/// Faces that become visible after an edit at `pos`
+///
+/// `block_at` returns the block at a world position after the edit.
+pub fn edit_patch_faces<F>(pos: IVec3, edit: BlockEdit, block_at: F) -> Vec<PatchFace>
+where
+    F: Fn(IVec3) -> Option<ItemId>,
+{
+    FACE_DIRS
+        .iter()
+        .filter_map(|&dir| match edit {
+            BlockEdit::Placed(block) => block_at(pos + dir).is_none().then_some(PatchFace {
+                block_pos: pos,
+                normal: dir,
+                block,
+            }),
+            BlockEdit::Removed => block_at(pos + dir).map(|block| PatchFace {
+                block_pos: pos + dir,
+                normal: -dir,
+                block,
+            }),
+        })
+        .collect()
+}
+
+/// Corners (CCW from outside) and UVs of a unit face, matching the chunk mesher
+fn face_quad(normal: IVec3) -> ([[f32; 3]; 4], [[f32; 2]; 4]) {
+    match (normal.x, normal.y, normal.z) {
+        (1, _, _) => (
+            [[1., 0., 0.], [1., 1., 0.], [1., 1., 1.], [1., 0., 1.]],
+            [[0., 1.], [0., 0.], [1., 0.], [1., 1.]],
+        ),
+        (-1, _, _) => (
+            [[0., 0., 0.], [0., 0., 1.], [0., 1., 1.], [0., 1., 0.]],
+            [[1., 1.], [0., 1.], [0., 0.], [1., 0.]],
+        ),
+        (_, 1, _) => (
+            [[0., 1., 0.], [0., 1., 1.], [1., 1., 1.], [1., 1., 0.]],
+            [[0., 0.], [0., 1.], [1., 1.], [1., 0.]],
+        ),
+        (_, -1, _) => (
+            [[0., 0., 0.], [1., 0., 0.], [1., 0., 1.], [0., 0., 1.]],
+            [[0., 1.], [1., 1.], [1., 0.], [0., 0.]],
+        ),
+        (_, _, 1) => (
+            [[0., 0., 1.], [1., 0., 1.], [1., 1., 1.], [0., 1., 1.]],
+            [[0., 1.], [1., 1.], [1., 0.], [0., 0.]],
+        ),
+        _ => (
+            [[0., 0., 0.], [0., 1., 0.], [1., 1., 0.], [1., 0., 0.]],
+            [[1., 1.], [1., 0.], [0., 0.], [0., 1.]],
+        ),
+    }
+}
+
+/// Build a mesh with the chunk mesh vertex layout (world-space positions)
+pub fn edit_patch_mesh(faces: &[PatchFace]) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(faces.len() * 4);
+    let mut normals: Vec<[f32; 3]> = Vec::with_capacity(faces.len() * 4);
+    let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(faces.len() * 4);
+    let mut uv_layers: Vec<[f32; 2]> = Vec::with_capacity(faces.len() * 4);
+    let mut colors: Vec<[f32; 4]> = Vec::with_capacity(faces.len() * 4);
+    let mut indices: Vec<u32> = Vec::with_capacity(faces.len() * 6);
+
+    for face in faces {
+        let (corners, face_uvs) = face_quad(face.normal);
+        let origin = face.block_pos.as_vec3();
+        let tex_layer = face.block.texture_index_for_face(face.normal == IVec3::Y) as f32;
+
+        let base_idx = positions.len() as u32;
+        for (corner, uv) in corners.iter().zip(face_uvs) {
+            positions.push((origin + Vec3::from_array(*corner)).to_array());
+            normals.push(face.normal.as_vec3().to_array());
+            uvs.push(uv);
+            uv_layers.push([tex_layer, 0.0]);
+            colors.push([1.0, 1.0, 1.0, 1.0]);
+        }
+        indices.extend_from_slice(&[
+            base_idx,
+            base_idx + 1,
+            base_idx + 2,
+            base_idx,
+            base_idx + 2,
+            base_idx + 3,
+        ]);
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uv_layers);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+    use bevy::mesh::VertexAttributeValues;
+    use std::collections::HashMap;
+
+    fn faces(edit: BlockEdit, blocks: &[(IVec3, ItemId)]) -> Vec<PatchFace> {
+        let blocks: HashMap<IVec3, ItemId> = blocks.iter().copied().collect();
+        edit_patch_faces(IVec3::ZERO, edit, |p| blocks.get(&p).copied())
+    }
+
+    #[test]
+    fn test_removal_exposes_neighbor_faces() {
+        let stone = items::stone();
+        let grass = items::grass();
+
+        // Nothing around: no patch
+        assert!(faces(BlockEdit::Removed, &[]).is_empty());
+
+        // Dug out of the ground: the block below shows its top, the wall its -X face
+        let patch = faces(
+            BlockEdit::Removed,
+            &[(IVec3::NEG_Y, grass), (IVec3::X, stone)],
+        );
+        assert_eq!(
+            patch,
+            vec![
+                PatchFace {
+                    block_pos: IVec3::NEG_Y,
+                    normal: IVec3::Y,
+                    block: grass,
+                },
+                PatchFace {
+                    block_pos: IVec3::X,
+                    normal: IVec3::NEG_X,
+                    block: stone,
+                },
+            ]
+        );
+
+        // Fully enclosed: all six neighbors face the hole
+        let enclosed: Vec<_> = FACE_DIRS.iter().map(|&d| (d, stone)).collect();
+        let patch = faces(BlockEdit::Removed, &enclosed);
+        assert_eq!(patch.len(), 6);
+        assert!(patch.iter().all(|f| f.block_pos + f.normal == IVec3::ZERO));
+    }
+
+    #[test]
+    fn test_placement_shows_uncovered_faces() {
+        let stone = items::stone();
+
+        // Floating: the whole cube
+        assert_eq!(faces(BlockEdit::Placed(stone), &[]).len(), 6);
+
+        // On the ground against a wall: no bottom or +X face
+        let patch = faces(
+            BlockEdit::Placed(stone),
+            &[(IVec3::NEG_Y, stone), (IVec3::X, stone)],
+        );
+        let normals: Vec<_> = patch.iter().map(|f| f.normal).collect();
+        assert_eq!(
+            normals,
+            vec![IVec3::Y, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z]
+        );
+        assert!(patch.iter().all(|f| f.block_pos == IVec3::ZERO));
+
+        // Fully enclosed: nothing to show
+        let enclosed: Vec<_> = FACE_DIRS.iter().map(|&d| (d, stone)).collect();
+        assert!(faces(BlockEdit::Placed(stone), &enclosed).is_empty());
+    }
+
+    #[test]
+    fn test_patch_mesh_quads_face_outward() {
+        let stone = items::stone();
+        let patch = faces(BlockEdit::Placed(stone), &[]);
+        let mesh = edit_patch_mesh(&patch);
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        assert_eq!(positions.len(), 24);
+
+        // CCW winding: the triangle normal points along the face normal
+        for (i, face) in patch.iter().enumerate() {
+            let [a, b, c] = [0, 1, 2].map(|k| Vec3::from_array(positions[i * 4 + k]));
+            let winding = (b - a).cross(c - a).normalize();
+            assert_eq!(winding, face.normal.as_vec3(), "{:?}", face.normal);
+        }
+    }
+}
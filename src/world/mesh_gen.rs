@@ -6,6 +6,7 @@ use crate::constants::*;
 use crate::core::ItemId;
 use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
+use std::ops::Range;
 
 use super::chunk::{ChunkData, ChunkLod};
 
@@ -22,7 +23,43 @@ impl ChunkData {
     where
         F: Fn(IVec3) -> bool,
     {
-        let min_y = lod.min_y();
+        self.generate_mesh_in_y_range(chunk_coord, 0..CHUNK_HEIGHT, neighbor_checker, lod)
+    }
+
+    /// Generate the mesh of one section (CHUNK_SECTION_HEIGHT layers) of the chunk
+    ///
+    /// Faces are still culled against blocks in the neighboring sections.
+    pub fn generate_section_mesh_with_neighbors<F>(
+        &self,
+        chunk_coord: IVec2,
+        section: i32,
+        neighbor_checker: F,
+        lod: ChunkLod,
+    ) -> Mesh
+    where
+        F: Fn(IVec3) -> bool,
+    {
+        self.generate_mesh_in_y_range(
+            chunk_coord,
+            Self::section_y_range(section),
+            neighbor_checker,
+            lod,
+        )
+    }
+
+    /// Greedy-mesh the blocks whose y lies in `y_range`
+    fn generate_mesh_in_y_range<F>(
+        &self,
+        chunk_coord: IVec2,
+        y_range: Range<i32>,
+        neighbor_checker: F,
+        lod: ChunkLod,
+    ) -> Mesh
+    where
+        F: Fn(IVec3) -> bool,
+    {
+        let min_y = lod.min_y().max(y_range.start);
+        let max_y = y_range.end;
         // Pre-allocate with estimated capacity (greedy meshing produces fewer quads)
         let estimated_faces = (CHUNK_SIZE * CHUNK_SIZE) as usize;
         let mut positions: Vec<[f32; 3]> = Vec::with_capacity(estimated_faces * 4);
@@ -85,6 +122,11 @@ impl ChunkData {
 
             // Iterate through slices perpendicular to axis
             for slice in 0..axis_sizes[axis] {
+                // Y slices outside the range have nothing to mesh
+                if axis == 1 && !(min_y..max_y).contains(&slice) {
+                    continue;
+                }
+
                 // Create mask for this slice
                 // mask[u][v] = Some(ItemId) if face is visible
                 let mut mask: Vec<Vec<Option<ItemId>>> =
@@ -99,8 +141,8 @@ impl ChunkData {
                             _ => unreachable!(),
                         };
 
-                        // LOD: Skip blocks below min_y threshold
-                        if y < min_y {
+                        // LOD: Skip blocks below min_y threshold (and outside the section)
+                        if y < min_y || y >= max_y {
                             continue;
                         }
 
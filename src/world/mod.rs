@@ -6,13 +6,22 @@
 
 pub mod biome;
 mod chunk;
+mod decoration;
+mod edit_patch;
 mod mesh_gen;
 #[cfg(test)]
 mod tests;
+pub mod worldgen;
 
 // Explicit re-exports from biome
 pub use biome::{mining_random, BiomeMap};
 
+// Explicit re-exports from worldgen
+pub use worldgen::{WorldGenConfig, WorldPreset, WORLDGEN_CONFIG_PATH};
+
+// Explicit re-exports from edit_patch
+pub use edit_patch::{edit_patch_faces, edit_patch_mesh, BlockEdit, EditPatch, PatchFace};
+
 // Explicit re-exports from chunk
 pub use chunk::{
     ChunkData, ChunkLod, ChunkMesh, ChunkMeshData, ChunkMeshTasks, DirtyChunks, PendingChunk,
@@ -20,22 +29,82 @@ pub use chunk::{
 
 use crate::constants::*;
 use crate::core::ItemId;
+use crate::save::MachineSaveDataV2;
+use crate::utils::{dda_raycast, DdaHit};
 use bevy::prelude::*;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// World data - manages multiple chunks
 #[derive(Resource, Default)]
 pub struct WorldData {
     /// Loaded chunks indexed by chunk coordinate
     pub chunks: HashMap<IVec2, ChunkData>,
-    /// Block entities for each chunk (for despawning)
-    pub chunk_entities: HashMap<IVec2, Vec<Entity>>,
+    /// Section mesh entities for each chunk, keyed by section index (for despawning)
+    pub chunk_entities: HashMap<IVec2, HashMap<i32, Entity>>,
     /// Player-modified blocks (persists across chunk unload/reload)
     /// Key: world position, Value: Some(item_id) for placed, None for removed (air)
     pub modified_blocks: HashMap<IVec3, Option<ItemId>>,
+    /// Settings used to generate new chunks (shared with async generation tasks)
+    pub gen_config: Arc<WorldGenConfig>,
+    /// Machines and conveyors of unloaded chunks, frozen until the chunk loads again
+    pub hibernated_machines: HibernatedChunkMachines,
+}
+
+/// Machines and conveyors of unloaded chunks, kept in save format per chunk
+///
+/// Hibernated machines don't tick: they come back exactly as they were.
+#[derive(Default, Debug, Clone)]
+pub struct HibernatedChunkMachines {
+    chunks: HashMap<IVec2, Vec<MachineSaveDataV2>>,
+}
+
+impl HibernatedChunkMachines {
+    /// Store a machine under the chunk that contains it
+    pub fn insert(&mut self, machine: MachineSaveDataV2) {
+        let chunk = WorldData::world_to_chunk(machine.position());
+        self.chunks.entry(chunk).or_default().push(machine);
+    }
+
+    /// Remove and return the machines of a chunk
+    pub fn take(&mut self, chunk: IVec2) -> Vec<MachineSaveDataV2> {
+        self.chunks.remove(&chunk).unwrap_or_default()
+    }
+
+    /// Chunks that have hibernated machines
+    pub fn chunk_coords(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.chunks.keys().copied()
+    }
+
+    /// All hibernated machines (for saving)
+    pub fn iter(&self) -> impl Iterator<Item = &MachineSaveDataV2> {
+        self.chunks.values().flatten()
+    }
+
+    /// Number of hibernated machines
+    pub fn len(&self) -> usize {
+        self.chunks.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
 }
 
 impl WorldData {
+    /// Empty world that generates chunks with the given settings
+    pub fn with_gen_config(config: WorldGenConfig) -> Self {
+        Self {
+            gen_config: Arc::new(config),
+            ..default()
+        }
+    }
+
     /// Log block operation to file (logs/block_ops.log)
     /// Does not print to console to avoid noise
     fn log_block_op(op: &str, status: &str, world_pos: IVec3, item: Option<ItemId>) {
@@ -144,11 +213,53 @@ impl WorldData {
         block
     }
 
+    /// Generate a chunk synchronously if it isn't loaded (headless server, no meshing)
+    ///
+    /// Player modifications recorded for the chunk are applied on top.
+    pub fn ensure_chunk_loaded(&mut self, chunk_coord: IVec2) {
+        if self.chunks.contains_key(&chunk_coord) {
+            return;
+        }
+        let mut chunk = ChunkData::generate_with(chunk_coord, &self.gen_config);
+        for (&world_pos, &block) in &self.modified_blocks {
+            let local_pos = Self::world_to_local(world_pos);
+            if Self::world_to_chunk(world_pos) != chunk_coord
+                || local_pos.y < 0
+                || local_pos.y >= CHUNK_HEIGHT
+            {
+                continue;
+            }
+            chunk.blocks[ChunkData::pos_to_index(local_pos.x, local_pos.y, local_pos.z)] = block;
+        }
+        self.chunks.insert(chunk_coord, chunk);
+    }
+
     /// Check if block exists at world position
     pub fn has_block(&self, world_pos: IVec3) -> bool {
         self.get_block(world_pos).is_some()
     }
 
+    /// DDA raycast against solid blocks
+    ///
+    /// Caches the current chunk across steps, so a ray crossing several voxels
+    /// of the same chunk does a single chunk map lookup.
+    pub fn raycast_dda(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<DdaHit> {
+        let cache: Cell<Option<(IVec2, Option<&ChunkData>)>> = Cell::new(None);
+        dda_raycast(origin, direction, max_distance, |pos| {
+            let chunk_coord = Self::world_to_chunk(pos);
+            let chunk = match cache.get() {
+                Some((coord, chunk)) if coord == chunk_coord => chunk,
+                _ => {
+                    let chunk = self.chunks.get(&chunk_coord);
+                    cache.set(Some((chunk_coord, chunk)));
+                    chunk
+                }
+            };
+            let local = Self::world_to_local(pos);
+            chunk.is_some_and(|c| c.get_block(local.x, local.y, local.z).is_some())
+        })
+    }
+
     // =========================================================================
     // ItemId API (now primary)
     // =========================================================================
@@ -185,4 +296,21 @@ impl WorldData {
         );
         Some(mesh)
     }
+
+    /// Generate mesh for one section of a chunk with specific LOD level
+    pub fn generate_section_mesh_with_lod(
+        &self,
+        chunk_coord: IVec2,
+        section: i32,
+        lod: ChunkLod,
+    ) -> Option<Mesh> {
+        let chunk_data = self.chunks.get(&chunk_coord)?;
+        let mesh = chunk_data.generate_section_mesh_with_neighbors(
+            chunk_coord,
+            section,
+            |world_pos| self.has_block(world_pos),
+            lod,
+        );
+        Some(mesh)
+    }
 }
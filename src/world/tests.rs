@@ -63,11 +63,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ore_probability_changes_generated_counts() {
+        use crate::world::worldgen::OreRule;
+        use crate::world::WorldGenConfig;
+
+        fn config_with_iron(probability: f32) -> WorldGenConfig {
+            let mut config = WorldGenConfig::default();
+            let rule = OreRule {
+                ore: "iron_ore".to_string(),
+                probability,
+                min_y: 0,
+                max_y: GROUND_LEVEL - 1,
+            };
+            config.ores.mixed = vec![rule.clone()];
+            config.ores.iron = vec![rule.clone()];
+            config.ores.copper = vec![rule.clone()];
+            config.ores.coal = vec![rule];
+            config
+        }
+
+        fn underground_iron(chunk: &ChunkData) -> usize {
+            let mut count = 0;
+            for x in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    for y in 0..GROUND_LEVEL {
+                        if chunk.get_block(x, y, z) == Some(items::iron_ore()) {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+            count
+        }
+
+        // 16x16x7 = 1792 underground blocks per chunk
+        let coord = IVec2::new(-3, 5);
+        let rare = underground_iron(&ChunkData::generate_with(coord, &config_with_iron(0.02)));
+        let common = underground_iron(&ChunkData::generate_with(coord, &config_with_iron(0.4)));
+        let expected_rare = 1792.0 * 0.02;
+        let expected_common = 1792.0 * 0.4;
+        assert!(
+            (rare as f32 - expected_rare).abs() < expected_rare * 0.5,
+            "rare iron count {} far from {}",
+            rare,
+            expected_rare
+        );
+        assert!(
+            (common as f32 - expected_common).abs() < expected_common * 0.2,
+            "common iron count {} far from {}",
+            common,
+            expected_common
+        );
+        assert!(common > rare * 5);
+
+        // No iron at all when the probability is zero
+        let none = underground_iron(&ChunkData::generate_with(coord, &config_with_iron(0.0)));
+        assert_eq!(none, 0);
+    }
+
+    /// Blocks above the terrain surface: (local position, block)
+    fn decorations(chunk: &ChunkData) -> Vec<(IVec3, crate::core::ItemId)> {
+        let mut found = Vec::new();
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for y in GROUND_LEVEL + 1..CHUNK_HEIGHT {
+                    if let Some(block) = chunk.get_block(x, y, z) {
+                        found.push((IVec3::new(x, y, z), block));
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_decorations_deterministic_per_seed_and_chunk() {
+        use crate::world::WorldGenConfig;
+
+        let mut config = WorldGenConfig::default();
+        config.decorations.boulders = 3.0;
+        config.decorations.outcrops = 2.0;
+        config.decorations.crystals = 1.0;
+
+        let coord = IVec2::new(9, -6);
+        let first = ChunkData::generate_with(coord, &config);
+        let second = ChunkData::generate_with(coord, &config);
+        assert_eq!(first.blocks, second.blocks);
+        assert!(!decorations(&first).is_empty());
+
+        // Another seed moves the decorations somewhere in a handful of chunks
+        let mut reseeded = config.clone();
+        reseeded.seed = 42;
+        let coords = [IVec2::new(9, -6), IVec2::new(-7, 4), IVec2::new(12, 12)];
+        assert!(coords.iter().any(|&c| {
+            decorations(&ChunkData::generate_with(c, &config))
+                != decorations(&ChunkData::generate_with(c, &reseeded))
+        }));
+    }
+
+    #[test]
+    fn test_seed_changes_ores_and_biomes() {
+        use crate::world::WorldGenConfig;
+
+        let config = WorldGenConfig::default();
+        let mut reseeded = config.clone();
+        reseeded.seed = 42;
+
+        // Seed 0 is the original world
+        assert_eq!(
+            ChunkData::seeded_hash(0, 5, 3, -9),
+            ChunkData::simple_hash(5, 3, -9)
+        );
+
+        let coord = IVec2::new(-4, 7);
+        let original = ChunkData::generate_with(coord, &config);
+        assert_eq!(
+            original.blocks,
+            ChunkData::generate_with(coord, &config).blocks
+        );
+        assert_ne!(
+            original.blocks,
+            ChunkData::generate_with(coord, &reseeded).blocks
+        );
+
+        let biomes = |seed| {
+            (0..16)
+                .map(|i| ChunkData::get_biome(seed, i * 32, i * -64))
+                .collect::<Vec<_>>()
+        };
+        assert_ne!(biomes(0), biomes(42));
+    }
+
+    #[test]
+    fn test_decorations_stay_inside_chunk_and_off_spawn() {
+        use crate::world::WorldGenConfig;
+
+        let mut config = WorldGenConfig::default();
+        config.decorations.boulders = 8.0;
+        config.decorations.outcrops = 8.0;
+        config.decorations.crystals = 8.0;
+
+        let mut crystals = 0;
+        for coord in [IVec2::new(6, 6), IVec2::new(-5, -9), IVec2::new(-10, 3)] {
+            for (pos, block) in decorations(&ChunkData::generate_with(coord, &config)) {
+                assert!(
+                    (1..CHUNK_SIZE - 1).contains(&pos.x) && (1..CHUNK_SIZE - 1).contains(&pos.z),
+                    "decoration on the chunk edge at {:?}",
+                    pos
+                );
+                if block == items::crystal() {
+                    crystals += 1;
+                }
+            }
+        }
+        assert!(crystals > 0, "no crystals generated");
+
+        // The spawn chunk stays flat
+        assert!(decorations(&ChunkData::generate_with(IVec2::ZERO, &config)).is_empty());
+    }
+
     #[test]
     fn test_chunk_data_biome_deterministic() {
         // Same coordinates should always produce same biome
-        let biome1 = ChunkData::get_biome(100, 200);
-        let biome2 = ChunkData::get_biome(100, 200);
+        let biome1 = ChunkData::get_biome(0, 100, 200);
+        let biome2 = ChunkData::get_biome(0, 100, 200);
         assert_eq!(biome1, biome2);
 
         // Biome should be 0-3
@@ -194,6 +354,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_chunk_mesh_texture_layer_per_face() {
+        use crate::graphics::BlockTextureLayout;
+        use bevy::mesh::VertexAttributeValues;
+
+        // Lone grass block and lone miner block, far apart
+        let mut chunk = ChunkData {
+            blocks: vec![None; ChunkData::ARRAY_SIZE],
+        };
+        chunk.blocks[ChunkData::pos_to_index(2, 20, 2)] = Some(items::grass());
+        chunk.blocks[ChunkData::pos_to_index(10, 20, 10)] = Some(items::miner_block());
+        let mesh = chunk.generate_mesh(IVec2::ZERO);
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh has no positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh has no normals");
+        };
+        let Some(VertexAttributeValues::Float32x2(layers)) = mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+        else {
+            panic!("mesh has no texture layers");
+        };
+        assert_eq!(positions.len(), 2 * 6 * 4, "two cubes, six quads each");
+
+        let layout = BlockTextureLayout::base();
+        let grass_top = layout.layer("grass_top").unwrap() as f32;
+        let grass_side = layout.layer("grass_side").unwrap() as f32;
+        let miner = layout.layer("miner_block").unwrap() as f32;
+        for ((pos, normal), layer) in positions.iter().zip(normals).zip(layers) {
+            let expected = if pos[0] > 5.0 {
+                miner
+            } else if normal[1] > 0.5 {
+                grass_top
+            } else {
+                grass_side
+            };
+            assert_eq!(layer[0], expected, "vertex {pos:?} normal {normal:?}");
+        }
+    }
+
     // =========================================================================
     // ItemId API tests
     // =========================================================================
@@ -225,4 +430,164 @@ mod tests {
         assert!(removed.is_some());
         assert_eq!(removed.unwrap().name(), Some("base:stone"));
     }
+
+    // =========================================================================
+    // Raycast / lookup parity
+    // =========================================================================
+
+    /// Small deterministic LCG so parity tests cover many positions reproducibly
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next_f32(&mut self) -> f32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32
+        }
+
+        fn range(&mut self, min: f32, max: f32) -> f32 {
+            min + self.next_f32() * (max - min)
+        }
+    }
+
+    fn world_3x3() -> WorldData {
+        let mut world = WorldData::default();
+        for cx in -1..=1 {
+            for cz in -1..=1 {
+                let coord = IVec2::new(cx, cz);
+                world.chunks.insert(coord, ChunkData::generate(coord));
+            }
+        }
+        // Some player-built structures above ground
+        for i in 0..8 {
+            world.set_block(
+                IVec3::new(i * 3 - 12, GROUND_LEVEL + 2, i * 2 - 8),
+                items::stone(),
+            );
+        }
+        world
+    }
+
+    /// Reference lookup straight from the chunk's flat array
+    fn lookup_reference(world: &WorldData, pos: IVec3) -> Option<crate::core::ItemId> {
+        let chunk = world.chunks.get(&WorldData::world_to_chunk(pos))?;
+        let local = WorldData::world_to_local(pos);
+        if !(0..CHUNK_HEIGHT).contains(&local.y) {
+            return None;
+        }
+        chunk.blocks[ChunkData::pos_to_index(local.x, local.y, local.z)]
+    }
+
+    #[test]
+    fn test_get_block_matches_flat_array_on_random_positions() {
+        let world = world_3x3();
+        let mut rng = Lcg(42);
+        let extent = (CHUNK_SIZE * 2) as f32;
+
+        for _ in 0..5000 {
+            let pos = IVec3::new(
+                rng.range(-extent, extent).floor() as i32,
+                rng.range(-4.0, CHUNK_HEIGHT as f32 + 4.0).floor() as i32,
+                rng.range(-extent, extent).floor() as i32,
+            );
+            assert_eq!(
+                world.get_block(pos),
+                lookup_reference(&world, pos),
+                "{:?}",
+                pos
+            );
+        }
+    }
+
+    #[test]
+    fn test_raycast_dda_matches_per_voxel_lookup() {
+        let world = world_3x3();
+        let mut rng = Lcg(7);
+        let extent = CHUNK_SIZE as f32;
+        let mut hits = 0;
+
+        for _ in 0..2000 {
+            let origin = Vec3::new(
+                rng.range(-extent, extent),
+                rng.range(GROUND_LEVEL as f32 + 1.5, GROUND_LEVEL as f32 + 12.0),
+                rng.range(-extent, extent),
+            );
+            let direction = Vec3::new(
+                rng.range(-1.0, 1.0),
+                rng.range(-1.0, 0.3),
+                rng.range(-1.0, 1.0),
+            )
+            .normalize_or(Vec3::NEG_Y);
+
+            let cached = world.raycast_dda(origin, direction, 24.0);
+            let reference =
+                crate::utils::dda_raycast(origin, direction, 24.0, |pos| world.has_block(pos));
+
+            assert_eq!(
+                cached.map(|h| (h.position, h.normal)),
+                reference.map(|h| (h.position, h.normal)),
+                "origin {:?} dir {:?}",
+                origin,
+                direction
+            );
+            hits += cached.is_some() as u32;
+        }
+        assert!(hits > 0, "rays should hit the ground");
+    }
+
+    #[test]
+    fn test_raycast_dda_crosses_chunk_boundary() {
+        let mut world = WorldData::default();
+        for cx in 0..=1 {
+            let coord = IVec2::new(cx, 0);
+            world.chunks.insert(coord, ChunkData::generate(coord));
+        }
+        let target = IVec3::new(CHUNK_SIZE + 2, GROUND_LEVEL + 3, 4);
+        world.set_block(target, items::stone());
+
+        let origin = Vec3::new(1.5, GROUND_LEVEL as f32 + 3.5, 4.5);
+        let hit = world
+            .raycast_dda(origin, Vec3::X, 32.0)
+            .expect("should hit block in neighbor chunk");
+        assert_eq!(hit.position, target);
+        assert_eq!(hit.normal, IVec3::NEG_X);
+
+        // Ray into an unloaded chunk hits nothing
+        assert!(world.raycast_dda(origin, Vec3::NEG_X, 8.0).is_none());
+    }
+
+    /// Long equivalence run over many rays (run with `cargo test -- --ignored`)
+    #[test]
+    #[ignore]
+    fn test_raycast_dda_matches_per_voxel_many_rays() {
+        let world = world_3x3();
+        let mut rng = Lcg(1);
+        let rays: Vec<(Vec3, Vec3)> = (0..20_000)
+            .map(|_| {
+                let origin = Vec3::new(
+                    rng.range(-8.0, 8.0),
+                    GROUND_LEVEL as f32 + 20.0,
+                    rng.range(-8.0, 8.0),
+                );
+                let direction = Vec3::new(rng.range(-1.0, 1.0), 0.05, rng.range(-1.0, 1.0))
+                    .normalize_or(Vec3::X);
+                (origin, direction)
+            })
+            .collect();
+
+        for (origin, direction) in rays {
+            let cached = world.raycast_dda(origin, direction, 24.0);
+            let reference =
+                crate::utils::dda_raycast(origin, direction, 24.0, |pos| world.has_block(pos));
+            assert_eq!(
+                cached.map(|h| (h.position, h.normal)),
+                reference.map(|h| (h.position, h.normal)),
+                "origin {:?} dir {:?}",
+                origin,
+                direction
+            );
+        }
+    }
 }
@@ -0,0 +1,638 @@
+//! Data-driven world generation settings
+//!
+//! Loaded from `assets/config/worldgen.yaml`; every field is optional and falls
+//! back to the compiled defaults, which match the original hard-coded ore rates.
+//! The config hash is stored in save files so loading a world generated with
+//! different settings can warn about mismatched terrain.
+
+use crate::constants::{BLOCK_SIZE, CHUNK_HEIGHT, CHUNK_SIZE, GROUND_LEVEL};
+use crate::core::ItemId;
+use crate::startup_report::{DataIssue, LoadOutcome};
+use crate::utils::parse_item_name;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Path of the world generation config file
+pub const WORLDGEN_CONFIG_PATH: &str = "assets/config/worldgen.yaml";
+
+static DEFAULT_CONFIG: LazyLock<WorldGenConfig> = LazyLock::new(WorldGenConfig::default);
+
+/// World generation settings (terrain, ores, platform, starting machines)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct WorldGenConfig {
+    /// World seed: terrain noise, biomes, ores and decorations (0 = the original world)
+    pub seed: u64,
+    pub terrain: TerrainConfig,
+    pub ores: BiomeOreConfig,
+    pub platform: PlatformConfig,
+    pub border: BorderConfig,
+    pub decorations: DecorationConfig,
+    /// Furnace placed at startup (None = furnace is a tutorial reward)
+    pub initial_furnace: Option<[i32; 3]>,
+}
+
+/// Surface shape
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct TerrainConfig {
+    /// Lowest surface Y
+    pub surface_min: i32,
+    /// Highest surface Y (equal to surface_min = flat world)
+    pub surface_max: i32,
+    /// Size of one height noise cell in blocks (larger = gentler hills)
+    pub noise_scale: f32,
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        Self {
+            surface_min: GROUND_LEVEL,
+            surface_max: GROUND_LEVEL,
+            noise_scale: 24.0,
+        }
+    }
+}
+
+/// One underground ore rule: chance per block within a Y band
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OreRule {
+    /// Item ID ("base:iron_ore" or "iron_ore")
+    pub ore: String,
+    /// Chance (0.0-1.0) that a block in the band is this ore
+    pub probability: f32,
+    #[serde(default)]
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+impl OreRule {
+    fn new(ore: &str, probability: f32, max_y: i32) -> Self {
+        Self {
+            ore: ore.to_string(),
+            probability,
+            min_y: 0,
+            max_y,
+        }
+    }
+}
+
+/// Underground ore rules per biome (checked in order, remaining chance is stone)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BiomeOreConfig {
+    pub mixed: Vec<OreRule>,
+    pub iron: Vec<OreRule>,
+    pub copper: Vec<OreRule>,
+    pub coal: Vec<OreRule>,
+}
+
+impl Default for BiomeOreConfig {
+    fn default() -> Self {
+        Self {
+            mixed: vec![
+                OreRule::new("iron_ore", 0.05, 4),
+                OreRule::new("copper_ore", 0.04, 3),
+                OreRule::new("coal", 0.067, 5),
+            ],
+            iron: vec![
+                OreRule::new("iron_ore", 0.125, 5),
+                OreRule::new("coal", 0.05, 4),
+            ],
+            copper: vec![
+                OreRule::new("copper_ore", 0.125, 5),
+                OreRule::new("iron_ore", 0.04, 4),
+            ],
+            coal: vec![
+                OreRule::new("coal", 0.167, 6),
+                OreRule::new("iron_ore", 0.033, 3),
+                OreRule::new("copper_ore", 0.033, 3),
+            ],
+        }
+    }
+}
+
+/// Ore rule with the item resolved (built once per chunk)
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedOre {
+    pub item: ItemId,
+    pub probability: f32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+impl BiomeOreConfig {
+    /// Resolve item names, indexed by biome (0=Mixed, 1=Iron, 2=Copper, 3=Coal)
+    ///
+    /// Unknown items are skipped with a warning.
+    pub fn resolve(&self) -> [Vec<ResolvedOre>; 4] {
+        let resolve = |rules: &[OreRule]| {
+            rules
+                .iter()
+                .filter_map(|rule| {
+                    let Some(item) = parse_item_name(&rule.ore) else {
+                        tracing::warn!("worldgen: unknown ore '{}'", rule.ore);
+                        return None;
+                    };
+                    Some(ResolvedOre {
+                        item,
+                        probability: rule.probability,
+                        min_y: rule.min_y,
+                        max_y: rule.max_y,
+                    })
+                })
+                .collect()
+        };
+        [
+            resolve(&self.mixed),
+            resolve(&self.iron),
+            resolve(&self.copper),
+            resolve(&self.coal),
+        ]
+    }
+}
+
+/// Pick the ore for a block from resolved rules given a uniform roll in [0, 1)
+pub fn pick_ore(rules: &[ResolvedOre], y: i32, roll: f32) -> Option<ItemId> {
+    let mut cumulative = 0.0;
+    for rule in rules {
+        if !(rule.min_y..=rule.max_y).contains(&y) {
+            continue;
+        }
+        cumulative += rule.probability;
+        if roll < cumulative {
+            return Some(rule.item);
+        }
+    }
+    None
+}
+
+/// Surface decorations: boulders, ore outcrops and crystals
+///
+/// Counts are averages per chunk; the fraction is the chance of one more.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DecorationConfig {
+    /// Boulders (3-5 stone blocks) per chunk
+    pub boulders: f32,
+    /// Ore outcrops (1-2 ore blocks of the column's biome) per chunk
+    pub outcrops: f32,
+    /// Crystals per chunk
+    pub crystals: f32,
+    /// Columns within this many blocks of the border center stay bare
+    pub spawn_clear_radius: i32,
+}
+
+impl Default for DecorationConfig {
+    fn default() -> Self {
+        Self {
+            boulders: 1.5,
+            outcrops: 1.0,
+            crystals: 0.25,
+            spawn_clear_radius: 24,
+        }
+    }
+}
+
+/// Delivery platform placement
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PlatformConfig {
+    /// Platform origin (min corner, Y = top of the ground)
+    pub origin: [i32; 3],
+    /// Side length of the flat stone pad generated under the platform
+    pub size: i32,
+}
+
+impl Default for PlatformConfig {
+    fn default() -> Self {
+        Self {
+            origin: [20, GROUND_LEVEL + 1, 10],
+            size: 12,
+        }
+    }
+}
+
+impl PlatformConfig {
+    pub fn origin(&self) -> IVec3 {
+        IVec3::from_array(self.origin)
+    }
+
+    /// Whether a column lies on the platform pad
+    pub fn contains(&self, world_x: i32, world_z: i32) -> bool {
+        let [x, _, z] = self.origin;
+        (x..x + self.size).contains(&world_x) && (z..z + self.size).contains(&world_z)
+    }
+}
+
+/// Square world border around the spawn column
+///
+/// Chunks entirely outside are never generated and the player is kept inside.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BorderConfig {
+    /// Border center column [x, z]
+    pub center: [i32; 2],
+    /// Blocks from the center to each edge (0 = no border)
+    pub radius: i32,
+}
+
+impl Default for BorderConfig {
+    fn default() -> Self {
+        Self {
+            center: [8, 20],
+            radius: 256,
+        }
+    }
+}
+
+impl BorderConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.radius > 0
+    }
+
+    /// World-space (min, max) corners of the playable area on the XZ plane
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let center = IVec2::from_array(self.center);
+        let min = (center - IVec2::splat(self.radius)).as_vec2() * BLOCK_SIZE;
+        let max = (center + IVec2::splat(self.radius)).as_vec2() * BLOCK_SIZE;
+        (min, max)
+    }
+
+    /// Keep a world position inside the border (height is untouched)
+    pub fn clamp(&self, pos: Vec3) -> Vec3 {
+        if !self.is_enabled() {
+            return pos;
+        }
+        let (min, max) = self.bounds();
+        Vec3::new(pos.x.clamp(min.x, max.x), pos.y, pos.z.clamp(min.y, max.y))
+    }
+
+    /// Whether any column of the chunk lies inside the border
+    pub fn contains_chunk(&self, chunk_coord: IVec2) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        let center = IVec2::from_array(self.center);
+        let (min, max) = (center - self.radius, center + self.radius);
+        let chunk_min = chunk_coord * CHUNK_SIZE;
+        let chunk_max = chunk_min + IVec2::splat(CHUNK_SIZE);
+        chunk_min.x < max.x && chunk_max.x > min.x && chunk_min.y < max.y && chunk_max.y > min.y
+    }
+}
+
+impl WorldGenConfig {
+    /// Shared compiled defaults (avoids rebuilding the ore tables per chunk)
+    pub fn default_ref() -> &'static Self {
+        &DEFAULT_CONFIG
+    }
+
+    /// Parse from YAML; missing fields use defaults
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse worldgen config: {}", e))
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self)
+            .map_err(|e| format!("Failed to serialize worldgen config: {}", e))
+    }
+
+    /// Load from `WORLDGEN_CONFIG_PATH`, falling back to defaults
+    pub fn load() -> Self {
+        let (config, outcome) = Self::load_checked(Path::new(WORLDGEN_CONFIG_PATH));
+        outcome.log();
+        config
+    }
+
+    /// Load from `path`; a broken file falls back to defaults with a warning
+    pub fn load_checked(path: &Path) -> (Self, LoadOutcome) {
+        let mut outcome = LoadOutcome::new("worldgen config");
+        let config = match std::fs::read_to_string(path) {
+            Ok(yaml) => match serde_yaml::from_str(&yaml) {
+                Ok(config) => {
+                    tracing::info!("Worldgen config loaded from {}", path.display());
+                    outcome.loaded = 1;
+                    config
+                }
+                Err(e) => {
+                    outcome.warn(DataIssue::from_yaml_error(path.display().to_string(), &e));
+                    Self::default()
+                }
+            },
+            Err(_) => {
+                tracing::info!("No worldgen config found, using defaults");
+                Self::default()
+            }
+        };
+        (config, outcome)
+    }
+
+    /// Block position of the startup furnace, if any
+    pub fn initial_furnace_position(&self) -> Option<IVec3> {
+        self.initial_furnace.map(IVec3::from_array)
+    }
+
+    /// Stable hash of the settings (FNV-1a over the JSON form), stored in saves
+    pub fn config_hash(&self) -> u64 {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        json.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    /// Surface height of a column (value noise between surface_min and surface_max)
+    pub fn surface_height(&self, world_x: i32, world_z: i32) -> i32 {
+        let terrain = &self.terrain;
+        let min = terrain.surface_min.clamp(0, CHUNK_HEIGHT - 1);
+        let max = terrain.surface_max.clamp(min, CHUNK_HEIGHT - 1);
+        if min == max {
+            return min;
+        }
+
+        let scale = terrain.noise_scale.max(1.0);
+        let fx = world_x as f32 / scale;
+        let fz = world_z as f32 / scale;
+        let (cx, cz) = (fx.floor(), fz.floor());
+        let (tx, tz) = (smoothstep(fx - cx), smoothstep(fz - cz));
+        let corner = |dx: i32, dz: i32| {
+            let hash =
+                super::ChunkData::seeded_hash(self.seed, cx as i32 + dx, 200, cz as i32 + dz);
+            hash as f32 / u32::MAX as f32
+        };
+        let top = corner(0, 0) + (corner(1, 0) - corner(0, 0)) * tx;
+        let bottom = corner(0, 1) + (corner(1, 1) - corner(0, 1)) * tx;
+        let noise = top + (bottom - top) * tz;
+
+        min + (noise * (max - min) as f32).round() as i32
+    }
+}
+
+/// World type picked on the new world screen, applied on top of the loaded config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorldPreset {
+    /// The config as loaded from worldgen.yaml
+    #[default]
+    Standard,
+    /// Level ground, no surface decorations
+    Flat,
+    /// Taller, rolling hills
+    Hills,
+}
+
+impl WorldPreset {
+    pub const ALL: [WorldPreset; 3] =
+        [WorldPreset::Standard, WorldPreset::Flat, WorldPreset::Hills];
+
+    /// ID stored in save files
+    pub fn id(self) -> &'static str {
+        match self {
+            WorldPreset::Standard => "standard",
+            WorldPreset::Flat => "flat",
+            WorldPreset::Hills => "hills",
+        }
+    }
+
+    /// Unknown or empty IDs (saves from before presets) are Standard
+    pub fn from_id(id: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.id() == id)
+            .unwrap_or_default()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldPreset::Standard => "標準",
+            WorldPreset::Flat => "平坦",
+            WorldPreset::Hills => "丘陵",
+        }
+    }
+
+    /// Next preset for the selector button
+    pub fn next(self) -> Self {
+        match self {
+            WorldPreset::Standard => WorldPreset::Flat,
+            WorldPreset::Flat => WorldPreset::Hills,
+            WorldPreset::Hills => WorldPreset::Standard,
+        }
+    }
+
+    /// `base` with this preset and `seed` (None keeps the config's seed)
+    pub fn apply(self, mut base: WorldGenConfig, seed: Option<u64>) -> WorldGenConfig {
+        match self {
+            WorldPreset::Standard => {}
+            WorldPreset::Flat => {
+                base.terrain.surface_max = base.terrain.surface_min;
+                base.decorations.boulders = 0.0;
+                base.decorations.outcrops = 0.0;
+                base.decorations.crystals = 0.0;
+            }
+            WorldPreset::Hills => {
+                base.terrain.surface_max = base.terrain.surface_min + 8;
+                base.terrain.noise_scale = 32.0;
+            }
+        }
+        if let Some(seed) = seed {
+            base.seed = seed;
+        }
+        base
+    }
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::items;
+
+    #[test]
+    fn test_missing_optional_fields_use_defaults() {
+        let config = WorldGenConfig::from_yaml(
+            "terrain:\n  surface_max: 10\nplatform:\n  origin: [0, 8, 0]\n",
+        )
+        .expect("partial config");
+        assert_eq!(config.terrain.surface_min, GROUND_LEVEL);
+        assert_eq!(config.terrain.surface_max, 10);
+        assert_eq!(config.platform.origin(), IVec3::new(0, 8, 0));
+        assert_eq!(config.platform.size, 12);
+        assert_eq!(config.ores, BiomeOreConfig::default());
+        assert_eq!(config.initial_furnace, None);
+        assert_eq!(config.border, BorderConfig::default());
+        assert_eq!(config.decorations, DecorationConfig::default());
+
+        // Empty file is the default config
+        let empty = WorldGenConfig::from_yaml("{}").expect("empty config");
+        assert_eq!(empty, WorldGenConfig::default());
+    }
+
+    #[test]
+    fn test_broken_yaml_falls_back_with_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worldgen.yaml");
+        std::fs::write(&path, "terrain:\n  surface_min: [8\n").unwrap();
+        let (config, outcome) = WorldGenConfig::load_checked(&path);
+        assert_eq!(config, WorldGenConfig::default());
+        assert!(!outcome.is_fatal());
+        assert_eq!(outcome.loaded, 0);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].line.is_some());
+
+        // No file is not a problem
+        let (_, outcome) = WorldGenConfig::load_checked(&dir.path().join("missing.yaml"));
+        assert!(outcome.warnings.is_empty() && outcome.errors.is_empty());
+    }
+
+    #[test]
+    fn test_bundled_config_matches_defaults() {
+        let yaml = include_str!("../../assets/config/worldgen.yaml");
+        let config = WorldGenConfig::from_yaml(yaml).expect("bundled worldgen.yaml");
+        assert_eq!(config, WorldGenConfig::default());
+    }
+
+    #[test]
+    fn test_ore_rule_min_y_optional() {
+        let config = WorldGenConfig::from_yaml(
+            "ores:\n  mixed:\n    - ore: coal\n      probability: 0.5\n      max_y: 3\n",
+        )
+        .expect("ore rules");
+        assert_eq!(config.ores.mixed, vec![OreRule::new("coal", 0.5, 3)]);
+        // Other biomes keep their defaults
+        assert_eq!(config.ores.iron, BiomeOreConfig::default().iron);
+    }
+
+    #[test]
+    fn test_pick_ore_respects_bands_and_order() {
+        let rules = [
+            ResolvedOre {
+                item: items::iron_ore(),
+                probability: 0.25,
+                min_y: 0,
+                max_y: 4,
+            },
+            ResolvedOre {
+                item: items::coal(),
+                probability: 0.25,
+                min_y: 2,
+                max_y: 6,
+            },
+        ];
+        assert_eq!(pick_ore(&rules, 3, 0.1), Some(items::iron_ore()));
+        assert_eq!(pick_ore(&rules, 3, 0.3), Some(items::coal()));
+        assert_eq!(pick_ore(&rules, 3, 0.6), None);
+        // Above iron's band, coal gets the first slice
+        assert_eq!(pick_ore(&rules, 5, 0.1), Some(items::coal()));
+        assert_eq!(pick_ore(&rules, 7, 0.1), None);
+    }
+
+    #[test]
+    fn test_config_hash_tracks_changes() {
+        let config = WorldGenConfig::default();
+        assert_eq!(
+            config.config_hash(),
+            WorldGenConfig::default().config_hash()
+        );
+
+        let mut changed = config.clone();
+        changed.ores.iron[0].probability = 0.5;
+        assert_ne!(config.config_hash(), changed.config_hash());
+    }
+
+    #[test]
+    fn test_world_presets() {
+        let base = WorldGenConfig::default();
+        // Standard without a seed is the loaded config, so old saves keep their hash
+        assert_eq!(WorldPreset::Standard.apply(base.clone(), None), base);
+
+        let flat = WorldPreset::Flat.apply(base.clone(), Some(7));
+        assert_eq!(flat.terrain.surface_min, flat.terrain.surface_max);
+        assert_eq!(flat.decorations.boulders, 0.0);
+        assert_eq!(flat.seed, 7);
+
+        let hills = WorldPreset::Hills.apply(base.clone(), None);
+        assert!(hills.terrain.surface_max > hills.terrain.surface_min);
+        assert_ne!(hills.config_hash(), base.config_hash());
+
+        for preset in WorldPreset::ALL {
+            assert_eq!(WorldPreset::from_id(preset.id()), preset);
+        }
+        assert_eq!(WorldPreset::from_id(""), WorldPreset::Standard);
+    }
+
+    #[test]
+    fn test_border_clamps_position() {
+        let border = BorderConfig {
+            center: [10, -20],
+            radius: 100,
+        };
+        let inside = Vec3::new(50.0, 9.0, -60.0);
+        assert_eq!(border.clamp(inside), inside);
+        // Each side is clamped separately, height is kept
+        assert_eq!(
+            border.clamp(Vec3::new(500.0, 30.0, -500.0)),
+            Vec3::new(110.0, 30.0, -120.0)
+        );
+        assert_eq!(
+            border.clamp(Vec3::new(-95.0, 9.0, 85.0)),
+            Vec3::new(-90.0, 9.0, 80.0)
+        );
+
+        let open = BorderConfig {
+            radius: 0,
+            ..border
+        };
+        assert_eq!(open.clamp(Vec3::splat(1.0e6)), Vec3::splat(1.0e6));
+    }
+
+    #[test]
+    fn test_border_chunk_overlap() {
+        let border = BorderConfig {
+            center: [8, 8],
+            radius: 24,
+        };
+        // Columns -16..32 on both axes: chunks -1..=1
+        assert!(border.contains_chunk(IVec2::new(0, 0)));
+        assert!(border.contains_chunk(IVec2::new(-1, 1)));
+        assert!(!border.contains_chunk(IVec2::new(2, 0)));
+        assert!(!border.contains_chunk(IVec2::new(0, -2)));
+
+        // Partially covered chunks still generate
+        let offset = BorderConfig {
+            center: [0, 0],
+            radius: 20,
+        };
+        assert!(offset.contains_chunk(IVec2::new(1, 1)));
+        assert!(offset.contains_chunk(IVec2::new(-2, -2)));
+        assert!(!offset.contains_chunk(IVec2::new(2, 0)));
+        assert!(!offset.contains_chunk(IVec2::new(-3, 0)));
+    }
+
+    #[test]
+    fn test_surface_height_within_range() {
+        let mut config = WorldGenConfig::default();
+        assert_eq!(config.surface_height(123, -45), GROUND_LEVEL);
+
+        config.terrain.surface_min = 4;
+        config.terrain.surface_max = 10;
+        let heights: Vec<i32> = (0..64)
+            .map(|x| config.surface_height(x * 7, x * 3))
+            .collect();
+        assert!(heights.iter().all(|h| (4..=10).contains(h)));
+        assert!(
+            heights.iter().any(|&h| h != heights[0]),
+            "terrain is not flat"
+        );
+
+        // Another seed reshapes the hills
+        let mut reseeded = config.clone();
+        reseeded.seed = 42;
+        let reseeded_heights: Vec<i32> = (0..64)
+            .map(|x| reseeded.surface_height(x * 7, x * 3))
+            .collect();
+        assert_ne!(heights, reseeded_heights);
+    }
+}
@@ -2082,12 +2082,12 @@ fn test_zipper_merge() {
 
     struct ZipperConveyor {
         id: usize,
-        last_input_source: usize,
+        last_input_source: Option<usize>,
     }
 
     let mut target = ZipperConveyor {
         id: 0,
-        last_input_source: 0,
+        last_input_source: None,
     };
 
     let sources = vec![1_usize, 2_usize]; // Two source conveyors
@@ -2098,12 +2098,15 @@ fn test_zipper_merge() {
         // Determine which source is allowed this tick
         let mut sorted_sources = sources.clone();
         sorted_sources.sort();
-        let allowed_idx = target.last_input_source % sorted_sources.len();
-        let allowed_source = sorted_sources[allowed_idx];
+        // The source after the last one that fed the target goes next
+        let allowed_source = target
+            .last_input_source
+            .and_then(|last| sorted_sources.iter().copied().find(|&s| s > last))
+            .unwrap_or(sorted_sources[0]);
 
         // Accept from allowed source
         accepted_from.push(allowed_source);
-        target.last_input_source += 1;
+        target.last_input_source = Some(allowed_source);
     }
 
     // Count how many from each source
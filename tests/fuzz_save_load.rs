@@ -128,7 +128,8 @@ mod save_types {
         pub shape: ConveyorShapeSave,
         pub items: Vec<ConveyorItemSave>,
         pub last_output_index: usize,
-        pub last_input_source: usize,
+        #[serde(default)]
+        pub last_input_pos: Option<IVec3Save>,
     }
 
     #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -256,17 +257,17 @@ fn arb_conveyor_save() -> impl Strategy<Value = MachineSaveData> {
             0..5,
         ),
         0usize..10,
-        0usize..10,
+        proptest::option::of(arb_ivec3save()),
     )
         .prop_map(
-            |(position, direction, shape, items, last_output_index, last_input_source)| {
+            |(position, direction, shape, items, last_output_index, last_input_pos)| {
                 MachineSaveData::Conveyor(ConveyorSaveData {
                     position,
                     direction,
                     shape,
                     items,
                     last_output_index,
-                    last_input_source,
+                    last_input_pos,
                 })
             },
         )